@@ -3,7 +3,7 @@
 
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
-use aptos_mempool::mocks::MockSharedMempool;
+use aptos_mempool::{mocks::MockSharedMempool, TransactionFilter};
 use aptos_types::chain_id::ChainId;
 use std::sync::Arc;
 use storage_interface::mock::MockDbReaderWriter;
@@ -16,5 +16,6 @@ pub fn get_fake_context() -> Context {
         Arc::new(MockDbReaderWriter),
         mempool.ac_client,
         NodeConfig::default(),
+        TransactionFilter::default(),
     )
 }
@@ -65,6 +65,8 @@ impl AccountsApi {
     ///
     /// The Aptos nodes prune account state history, via a configurable time window (link).
     /// If the requested data has been pruned, the server responds with a 404.
+    ///
+    /// Resources stored in resource groups are returned as individual resources.
     #[oai(
         path = "/accounts/:address/resources",
         method = "get",
@@ -174,7 +176,17 @@ impl Account {
 
     pub fn resources(self, accept_type: &AcceptType) -> BasicResultWith404<Vec<MoveResource>> {
         let account_state = self.account_state()?;
-        let resources = account_state.get_resources();
+        let group_members = account_state
+            .get_resource_group_members()
+            .context("Failed to decode resource groups from DB")
+            .map_err(BasicErrorWith404::internal)
+            .map_err(|e| e.error_code(AptosErrorCode::InvalidBcsInStorageError))?;
+        // Resources stored in resource groups are listed like the others
+        let resources = account_state.get_resources().chain(
+            group_members
+                .iter()
+                .map(|(tag, bytes)| (tag.clone(), bytes.as_slice())),
+        );
         let move_resolver = self.context.move_resolver_poem()?;
         let converted_resources = move_resolver
             .as_converter(self.context.db.clone())
@@ -300,14 +312,24 @@ impl Account {
         struct_tag: &StructTag,
     ) -> Result<Vec<(Identifier, MoveValue)>, BasicErrorWith404> {
         let account_state = self.account_state()?;
-        let (typ, data) = account_state
+        let data = match account_state
             .get_resources()
             .find(|(tag, _data)| tag == struct_tag)
-            .ok_or_else(|| self.resource_not_found(struct_tag))?;
+        {
+            Some((_tag, data)) => data.to_vec(),
+            None => account_state
+                .get_resource_group_members()
+                .context("Failed to decode resource groups from DB")
+                .map_err(BasicErrorWith404::internal)?
+                .into_iter()
+                .find(|(tag, _data)| tag == struct_tag)
+                .map(|(_tag, data)| data)
+                .ok_or_else(|| self.resource_not_found(struct_tag))?,
+        };
         let move_resolver = self.context.move_resolver_poem()?;
         move_resolver
             .as_converter(self.context.db.clone())
-            .move_struct_fields(&typ, data)
+            .move_struct_fields(struct_tag, &data)
             .context("Failed to convert move structs")
             .map_err(BasicErrorWith404::internal)
     }
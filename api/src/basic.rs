@@ -8,10 +8,16 @@ use std::{
 
 use crate::accept_type::AcceptType;
 use crate::context::Context;
-use crate::response::{BasicError, BasicResponse, BasicResponseStatus, BasicResult, InternalError};
+use crate::generate_error_response;
+use crate::response::{
+    BasicError, BasicResponse, BasicResponseStatus, BasicResult, InternalError,
+    ServiceUnavailableError,
+};
 use crate::ApiTags;
 use anyhow::Context as AnyhowContext;
-use poem_openapi::{param::Query, payload::Html, Object, OpenApi};
+use aptos_api_types::U64;
+use aptos_types::on_chain_config::{OnChainConfig, ValidatorSet};
+use poem_openapi::{param::Query, payload::Html, Enum, Object, OpenApi};
 use serde::{Deserialize, Serialize};
 use std::ops::Sub;
 
@@ -34,6 +40,52 @@ impl HealthCheckSuccess {
     }
 }
 
+/// The status of a component of the node
+#[derive(Clone, Copy, Debug, Deserialize, Enum, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Healthy,
+    /// The component works but not as expected, this doesn't make the node not ready
+    Degraded,
+    /// The component makes the node not ready
+    Unhealthy,
+}
+
+/// The health of a component of the node
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Object)]
+pub struct ComponentHealth {
+    /// One of `storage`, `state_sync`, `consensus`, `api` and `mempool`
+    pub component: String,
+    pub status: ComponentStatus,
+    /// What was checked, or why the component is not healthy
+    pub message: String,
+}
+
+impl ComponentHealth {
+    fn new(component: &str, status: ComponentStatus, message: String) -> Self {
+        Self {
+            component: component.to_string(),
+            status,
+            message,
+        }
+    }
+}
+
+/// The health of each component of a node which is ready
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Object)]
+pub struct NodeReadiness {
+    pub ledger_version: U64,
+    /// How far, in seconds, the timestamp of the latest ledger info is behind
+    /// the wall clock of the node
+    pub sync_lag_secs: U64,
+    pub components: Vec<ComponentHealth>,
+}
+
+generate_error_response!(HealthCheckError, (500, Internal), (503, ServiceUnavailable));
+
+type HealthCheckResult<T> = poem::Result<BasicResponse<T>, HealthCheckError>;
+
 #[OpenApi]
 impl BasicApi {
     /// Show OpenAPI explorer
@@ -94,4 +146,214 @@ impl BasicApi {
             &accept_type,
         ))
     }
+
+    /// Check node liveness
+    ///
+    /// Returns 200 as long as the node serves requests and can read the
+    /// latest ledger info from storage, whether or not it is in sync. Meant
+    /// for liveness probes: a node failing it needs to be restarted.
+    #[oai(
+        path = "/-/healthy/live",
+        method = "get",
+        operation_id = "healthy_live",
+        tag = "ApiTags::General"
+    )]
+    async fn healthy_live(&self, accept_type: AcceptType) -> BasicResult<HealthCheckSuccess> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        BasicResponse::try_from_rust_value((
+            HealthCheckSuccess::new(),
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+
+    /// Check node readiness
+    ///
+    /// Checks each component of the node and returns 200 with their health if
+    /// none of them is unhealthy, 503 with the unhealthy ones otherwise. Meant
+    /// for readiness probes: a node failing it should not be sent requests.
+    ///
+    /// The components are:
+    /// - `storage`: the latest ledger info and state checkpoint can be read.
+    /// - `state_sync`: the timestamp of the latest ledger info is at most
+    /// `api.health_check.max_sync_lag_secs` behind the wall clock.
+    /// - `consensus`: for validators, whether the node is in the validator set.
+    /// It is only unhealthy when not in it if
+    /// `api.health_check.require_validator_set_membership` is set.
+    /// - `api`: the storage reads of the API are not all taken.
+    /// - `mempool`: mempool answers within `api.health_check.mempool_timeout_ms`.
+    #[oai(
+        path = "/-/healthy/ready",
+        method = "get",
+        operation_id = "healthy_ready",
+        tag = "ApiTags::General"
+    )]
+    async fn healthy_ready(&self, accept_type: AcceptType) -> HealthCheckResult<NodeReadiness> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let config = self.context.health_check_config().clone();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("Failed to determine the current unix time")
+            .map_err(HealthCheckError::internal)?;
+        let sync_lag_secs = now
+            .saturating_sub(Duration::from_micros(ledger_info.timestamp()))
+            .as_secs();
+
+        let components = vec![
+            self.storage_health(),
+            Self::state_sync_health(sync_lag_secs, config.max_sync_lag_secs),
+            self.consensus_health(config.require_validator_set_membership),
+            self.api_health(),
+            self.mempool_health(config.mempool_timeout_ms).await,
+        ];
+        let unhealthy: Vec<_> = components
+            .iter()
+            .filter(|component| component.status == ComponentStatus::Unhealthy)
+            .map(|component| format!("{}: {}", component.component, component.message))
+            .collect();
+        if !unhealthy.is_empty() {
+            return Err(HealthCheckError::service_unavailable_str(&format!(
+                "The node is not ready. {}",
+                unhealthy.join("; ")
+            )));
+        }
+
+        BasicResponse::try_from_rust_value((
+            NodeReadiness {
+                ledger_version: ledger_info.version().into(),
+                sync_lag_secs: sync_lag_secs.into(),
+                components,
+            },
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+}
+
+impl BasicApi {
+    fn storage_health(&self) -> ComponentHealth {
+        match self.context.get_latest_state_checkpoint_version() {
+            Ok(version) => ComponentHealth::new(
+                "storage",
+                ComponentStatus::Healthy,
+                format!("Latest state checkpoint at version {}", version),
+            ),
+            Err(err) => ComponentHealth::new(
+                "storage",
+                ComponentStatus::Unhealthy,
+                format!("Failed to read the latest state checkpoint: {}", err),
+            ),
+        }
+    }
+
+    fn state_sync_health(sync_lag_secs: u64, max_sync_lag_secs: u64) -> ComponentHealth {
+        if sync_lag_secs > max_sync_lag_secs {
+            ComponentHealth::new(
+                "state_sync",
+                ComponentStatus::Unhealthy,
+                format!(
+                    "The latest ledger info is {} seconds old, more than {} seconds",
+                    sync_lag_secs, max_sync_lag_secs
+                ),
+            )
+        } else {
+            ComponentHealth::new(
+                "state_sync",
+                ComponentStatus::Healthy,
+                format!("The latest ledger info is {} seconds old", sync_lag_secs),
+            )
+        }
+    }
+
+    fn consensus_health(&self, require_validator_set_membership: bool) -> ComponentHealth {
+        let address = match self.context.validator_address() {
+            Some(address) => address,
+            None => {
+                return ComponentHealth::new(
+                    "consensus",
+                    ComponentStatus::Healthy,
+                    "The node is not a validator".to_string(),
+                )
+            }
+        };
+        let validator_set = self
+            .context
+            .move_resolver()
+            .ok()
+            .and_then(|resolver| ValidatorSet::fetch_config(&resolver));
+        match validator_set {
+            Some(validator_set) => {
+                if validator_set
+                    .payload()
+                    .any(|validator| *validator.account_address() == address)
+                {
+                    ComponentHealth::new(
+                        "consensus",
+                        ComponentStatus::Healthy,
+                        format!("Validator {} is in the validator set", address),
+                    )
+                } else {
+                    ComponentHealth::new(
+                        "consensus",
+                        if require_validator_set_membership {
+                            ComponentStatus::Unhealthy
+                        } else {
+                            ComponentStatus::Degraded
+                        },
+                        format!("Validator {} is not in the validator set", address),
+                    )
+                }
+            }
+            None => ComponentHealth::new(
+                "consensus",
+                ComponentStatus::Unhealthy,
+                "Failed to read the validator set".to_string(),
+            ),
+        }
+    }
+
+    fn api_health(&self) -> ComponentHealth {
+        let available_slots = self.context.available_storage_read_slots();
+        if available_slots == 0 {
+            ComponentHealth::new(
+                "api",
+                ComponentStatus::Unhealthy,
+                "All storage reads of the API are taken".to_string(),
+            )
+        } else {
+            ComponentHealth::new(
+                "api",
+                ComponentStatus::Healthy,
+                format!("{} storage reads available", available_slots),
+            )
+        }
+    }
+
+    async fn mempool_health(&self, timeout_ms: u64) -> ComponentHealth {
+        let response = tokio::time::timeout(
+            Duration::from_millis(timeout_ms),
+            self.context.get_mempool_fee_statistics(),
+        )
+        .await;
+        match response {
+            Ok(Ok(_)) => ComponentHealth::new(
+                "mempool",
+                ComponentStatus::Healthy,
+                "Mempool is responsive".to_string(),
+            ),
+            Ok(Err(err)) => ComponentHealth::new(
+                "mempool",
+                ComponentStatus::Unhealthy,
+                format!("Mempool failed to answer: {}", err),
+            ),
+            Err(_) => ComponentHealth::new(
+                "mempool",
+                ComponentStatus::Unhealthy,
+                format!("Mempool didn't answer within {} ms", timeout_ms),
+            ),
+        }
+    }
 }
@@ -0,0 +1,379 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::accept_type::AcceptType;
+use crate::context::Context;
+use crate::failpoint::fail_point_poem;
+use crate::response::{
+    build_not_found, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+    BasicResultWith404, InternalError,
+};
+use crate::ApiTags;
+use anyhow::{bail, format_err, Context as AnyhowContext};
+use aptos_api_types::{AsConverter, BcsSchema, MoveType};
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256k1_ecdsa, secp256r1_ecdsa, Genesis, HashValue, SigningKey,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    contract_event::ContractEvent,
+    event::EventKey,
+    state_store::state_key::StateKey,
+    transaction::{
+        authenticator::{AccountAuthenticator, TransactionAuthenticator},
+        multi_key::{AnyPublicKey, AnySignature},
+        RawTransaction, Script, Transaction, TransactionArgument, TransactionPayload,
+        WriteSetPayload,
+    },
+    validator_txn::ValidatorTransaction,
+    write_set::WriteOp,
+};
+use move_deps::move_core_types::{
+    language_storage::TypeTag,
+    value::{MoveStructLayout, MoveTypeLayout},
+};
+use once_cell::sync::Lazy;
+use poem_openapi::{param::Path, OpenApi};
+use serde_reflection::{
+    ContainerFormat, Format, FormatHolder, Named, Registry, Samples, Tracer, TracerConfig,
+};
+use std::convert::TryFrom;
+
+/// The name of addresses in the registries, in Move layouts as well as in the core types
+const ACCOUNT_ADDRESS_TYPE_NAME: &str = "AccountAddress";
+
+/// The registry of the core types, traced from their Rust definitions so that it can't go stale.
+/// It's kept serialized, as the formats of serde-reflection aren't `Sync`.
+static CORE_TYPES: Lazy<serde_json::Value> = Lazy::new(|| {
+    let registry = trace_core_types().expect("Tracing the core types cannot fail");
+    serde_json::to_value(&registry).expect("Serializing the core types cannot fail")
+});
+
+pub struct BcsSchemaApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl BcsSchemaApi {
+    /// Get the BCS schema of a core type
+    ///
+    /// Returns the BCS layout of a type of the Aptos blockchain, e.g.
+    /// `SignedTransaction`, `RawTransaction`, `TransactionPayload`,
+    /// `TransactionAuthenticator`, `ContractEvent` or `Transaction`, along with
+    /// the layouts of all the types it is made of. The layouts are in the format
+    /// of serde-reflection, from which serde-generate generates the code to
+    /// serialize and deserialize the types in other languages.
+    ///
+    /// The schema is only available as JSON.
+    #[oai(
+        path = "/bcs_schema/core_types/:type_name",
+        method = "get",
+        operation_id = "get_core_type_bcs_schema",
+        tag = "ApiTags::General"
+    )]
+    async fn get_core_type_bcs_schema(
+        &self,
+        accept_type: AcceptType,
+        type_name: Path<String>,
+    ) -> BasicResultWith404<BcsSchema> {
+        fail_point_poem("endpoint_get_core_type_bcs_schema")?;
+        check_json(&accept_type)?;
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+
+        let core_types = core_types();
+        let type_name = type_name.0;
+        if !core_types.contains_key(&type_name) {
+            return Err(build_not_found(
+                "Core type",
+                format!("name({})", type_name),
+                latest_ledger_info.version(),
+            ));
+        }
+        let mut types = Registry::new();
+        add_core_type(&core_types, &type_name, &mut types);
+
+        BasicResponse::try_from_rust_value((
+            to_bcs_schema(&Format::TypeName(type_name), &types)?,
+            &latest_ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+
+    /// Get the BCS schema of a Move type
+    ///
+    /// Returns the BCS layout of the values of an on-chain Move type, e.g.
+    /// `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>` or `vector<u64>`, as
+    /// published at the latest ledger version, in the same format as the core
+    /// types. Structs are named by their struct tag, and addresses by
+    /// `AccountAddress`.
+    ///
+    /// The schema is only available as JSON.
+    #[oai(
+        path = "/bcs_schema/move_types/:type_tag",
+        method = "get",
+        operation_id = "get_move_type_bcs_schema",
+        tag = "ApiTags::General"
+    )]
+    async fn get_move_type_bcs_schema(
+        &self,
+        accept_type: AcceptType,
+        type_tag: Path<MoveType>,
+    ) -> BasicResultWith404<BcsSchema> {
+        fail_point_poem("endpoint_get_move_type_bcs_schema")?;
+        check_json(&accept_type)?;
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+
+        let type_tag = TypeTag::try_from(type_tag.0)
+            .context("Given type is invalid")
+            .map_err(BasicErrorWith404::bad_request)?;
+        let resolver = self.context.move_resolver_poem()?;
+        let layout = resolver
+            .as_converter(self.context.db.clone())
+            .get_type_layout(&type_tag)
+            .context(format!("Failed to resolve the layout of {}", type_tag))
+            .map_err(BasicErrorWith404::bad_request)?;
+
+        let mut types = Registry::new();
+        let root = move_type_format(&layout, &core_types(), &mut types)
+            .context("Failed to build the BCS schema of the Move type")
+            .map_err(BasicErrorWith404::internal)?;
+
+        BasicResponse::try_from_rust_value((
+            to_bcs_schema(&root, &types)?,
+            &latest_ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+}
+
+fn check_json(accept_type: &AcceptType) -> Result<(), BasicErrorWith404> {
+    if accept_type == &AcceptType::Bcs {
+        return Err(BasicErrorWith404::bad_request_str(
+            "BCS schemas are only available as JSON",
+        ));
+    }
+    Ok(())
+}
+
+fn core_types() -> Registry {
+    serde_json::from_value(CORE_TYPES.clone()).expect("The core types were serialized as JSON")
+}
+
+fn to_bcs_schema(root: &Format, types: &Registry) -> Result<BcsSchema, BasicErrorWith404> {
+    let to_value = |value: serde_json::Result<serde_json::Value>| {
+        value
+            .context("Failed to serialize the BCS schema")
+            .map_err(BasicErrorWith404::internal)
+    };
+    Ok(BcsSchema {
+        root: to_value(serde_json::to_value(root))?,
+        types: types
+            .iter()
+            .map(|(name, format)| Ok((name.clone(), to_value(serde_json::to_value(format))?)))
+            .collect::<Result<_, BasicErrorWith404>>()?,
+    })
+}
+
+/// Adds the core type `name`, and the core types it's made of, to `types`
+fn add_core_type(core_types: &Registry, name: &str, types: &mut Registry) {
+    if types.contains_key(name) {
+        return;
+    }
+    let format = match core_types.get(name) {
+        Some(format) => format.clone(),
+        None => return,
+    };
+    let mut type_names = vec![];
+    format
+        .visit(&mut |format| {
+            if let Format::TypeName(type_name) = format {
+                type_names.push(type_name.clone());
+            }
+            Ok(())
+        })
+        .expect("Collecting type names cannot fail");
+    types.insert(name.to_string(), format);
+    for type_name in type_names {
+        add_core_type(core_types, &type_name, types);
+    }
+}
+
+/// Converts a Move layout to the format of serde-reflection, adding the structs it's made of to
+/// `types`
+fn move_type_format(
+    layout: &MoveTypeLayout,
+    core_types: &Registry,
+    types: &mut Registry,
+) -> anyhow::Result<Format> {
+    Ok(match layout {
+        MoveTypeLayout::Bool => Format::Bool,
+        MoveTypeLayout::U8 => Format::U8,
+        MoveTypeLayout::U64 => Format::U64,
+        MoveTypeLayout::U128 => Format::U128,
+        // Signers are serialized as the address of their account
+        MoveTypeLayout::Address | MoveTypeLayout::Signer => {
+            add_core_type(core_types, ACCOUNT_ADDRESS_TYPE_NAME, types);
+            Format::TypeName(ACCOUNT_ADDRESS_TYPE_NAME.to_string())
+        }
+        MoveTypeLayout::Vector(item_layout) => {
+            Format::Seq(Box::new(move_type_format(item_layout, core_types, types)?))
+        }
+        MoveTypeLayout::Struct(MoveStructLayout::WithTypes { type_, fields }) => {
+            let name = type_.to_string();
+            if !types.contains_key(&name) {
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        Ok(Named {
+                            name: field.name.to_string(),
+                            value: move_type_format(&field.layout, core_types, types)?,
+                        })
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+                types.insert(name.clone(), ContainerFormat::Struct(fields));
+            }
+            Format::TypeName(name)
+        }
+        MoveTypeLayout::Struct(struct_layout) => bail!(
+            "Expecting `MoveStructLayout::WithTypes`, getting {:?}",
+            struct_layout
+        ),
+    })
+}
+
+/// Traces the core types, as `generate-format` does to check their formats don't change
+fn trace_core_types() -> anyhow::Result<Registry> {
+    let mut tracer =
+        Tracer::new(TracerConfig::default().is_human_readable(bcs::is_human_readable()));
+    let mut samples = Samples::new();
+    let trace_error = |e: serde_reflection::Error| format_err!("{}", e);
+
+    // 1. Record samples for types with custom deserializers, which only accept valid values.
+    let message = RawTransaction::new_script(
+        AccountAddress::ZERO,
+        0,
+        Script::new(vec![], vec![], vec![]),
+        0,
+        0,
+        0,
+        ChainId::test(),
+    );
+    let ed25519_private_key = Ed25519PrivateKey::genesis();
+    let ed25519_public_key = Ed25519PublicKey::from(&ed25519_private_key);
+    let ed25519_signature = ed25519_private_key.sign(&message);
+    let secp256k1_private_key = secp256k1_ecdsa::PrivateKey::genesis();
+    let secp256r1_private_key = secp256r1_ecdsa::PrivateKey::genesis();
+
+    tracer
+        .trace_value(&mut samples, &HashValue::zero())
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(&mut samples, &EventKey::new(0, AccountAddress::ZERO))
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(&mut samples, &ed25519_public_key)
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(&mut samples, &ed25519_signature)
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(
+            &mut samples,
+            &MultiEd25519PublicKey::from(ed25519_public_key),
+        )
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(
+            &mut samples,
+            &MultiEd25519Signature::from(ed25519_signature),
+        )
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(
+            &mut samples,
+            &secp256k1_ecdsa::PublicKey::from(&secp256k1_private_key),
+        )
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(&mut samples, &secp256k1_private_key.sign(&message))
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(
+            &mut samples,
+            &secp256r1_ecdsa::PublicKey::from(&secp256r1_private_key),
+        )
+        .map_err(trace_error)?;
+    tracer
+        .trace_value(&mut samples, &secp256r1_private_key.sign(&message))
+        .map_err(trace_error)?;
+
+    // 2. Trace the main entry points, and every enum separately so that all their variants are
+    // traced.
+    tracer
+        .trace_type::<Transaction>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<ContractEvent>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<TypeTag>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<TransactionArgument>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<TransactionPayload>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<WriteSetPayload>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<StateKey>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<WriteOp>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<ValidatorTransaction>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<AccountAuthenticator>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<TransactionAuthenticator>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<AnyPublicKey>(&samples)
+        .map_err(trace_error)?;
+    tracer
+        .trace_type::<AnySignature>(&samples)
+        .map_err(trace_error)?;
+    tracer.registry().map_err(trace_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_types_are_traced() {
+        let core_types = trace_core_types().unwrap();
+        for type_name in [
+            "SignedTransaction",
+            "RawTransaction",
+            "TransactionPayload",
+            "TransactionAuthenticator",
+            "ContractEvent",
+            ACCOUNT_ADDRESS_TYPE_NAME,
+        ] {
+            assert!(core_types.contains_key(type_name), "{}", type_name);
+        }
+    }
+}
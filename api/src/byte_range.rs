@@ -0,0 +1,133 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing of the HTTP `Range` header, for endpoints that allow clients to
+//! download large BCS payloads in chunks. Only a single `bytes` range is
+//! supported, see https://httpwg.org/specs/rfc9110.html#field.range.
+
+use anyhow::{bail, format_err};
+use std::{ops::Range, str::FromStr};
+
+const BYTES_UNIT: &str = "bytes=";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ByteRange {
+    /// `bytes=start-end`, both inclusive.
+    FromTo(u64, u64),
+    /// `bytes=start-`, everything from `start` onwards.
+    From(u64),
+    /// `bytes=-length`, the last `length` bytes.
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Resolves the range against a payload of `len` bytes, returning the
+    /// (exclusive) range of bytes to return, or `None` if the range cannot be
+    /// satisfied.
+    pub fn resolve(&self, len: u64) -> Option<Range<u64>> {
+        match *self {
+            ByteRange::FromTo(start, end) if start < len => Some(start..end.min(len - 1) + 1),
+            ByteRange::From(start) if start < len => Some(start..len),
+            ByteRange::Suffix(length) if length > 0 && len > 0 => {
+                Some(len.saturating_sub(length)..len)
+            }
+            _ => None,
+        }
+    }
+
+    /// The value of the `Content-Range` header for a response containing
+    /// `range` out of a payload of `len` bytes.
+    pub fn content_range(range: &Range<u64>, len: u64) -> String {
+        format!("bytes {}-{}/{}", range.start, range.end - 1, len)
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let spec = s
+            .trim()
+            .strip_prefix(BYTES_UNIT)
+            .ok_or_else(|| format_err!("Only byte ranges are supported, got: {}", s))?;
+        if spec.contains(',') {
+            bail!("Only a single byte range is supported, got: {}", s);
+        }
+        let (start, end) = spec
+            .split_once('-')
+            .ok_or_else(|| format_err!("Invalid byte range: {}", s))?;
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format_err!("Invalid byte range: {}", s))
+        };
+        Ok(match (start.trim().is_empty(), end.trim().is_empty()) {
+            (false, false) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if end < start {
+                    bail!("Invalid byte range, end is before start: {}", s);
+                }
+                ByteRange::FromTo(start, end)
+            }
+            (false, true) => ByteRange::From(parse(start)?),
+            (true, false) => ByteRange::Suffix(parse(end)?),
+            (true, true) => bail!("Invalid byte range: {}", s),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteRange;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "bytes=0-99".parse::<ByteRange>().unwrap(),
+            ByteRange::FromTo(0, 99)
+        );
+        assert_eq!(
+            "bytes=100-".parse::<ByteRange>().unwrap(),
+            ByteRange::From(100)
+        );
+        assert_eq!(
+            "bytes=-50".parse::<ByteRange>().unwrap(),
+            ByteRange::Suffix(50)
+        );
+        for invalid in [
+            "0-99",
+            "items=0-99",
+            "bytes=",
+            "bytes=-",
+            "bytes=10-5",
+            "bytes=a-b",
+            "bytes=0-1,5-6",
+        ] {
+            assert!(invalid.parse::<ByteRange>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(ByteRange::FromTo(0, 99).resolve(1000), Some(0..100));
+        assert_eq!(ByteRange::FromTo(900, 2000).resolve(1000), Some(900..1000));
+        assert_eq!(ByteRange::From(10).resolve(1000), Some(10..1000));
+        assert_eq!(ByteRange::Suffix(10).resolve(1000), Some(990..1000));
+        assert_eq!(ByteRange::Suffix(2000).resolve(1000), Some(0..1000));
+
+        assert_eq!(ByteRange::FromTo(1000, 1001).resolve(1000), None);
+        assert_eq!(ByteRange::From(1000).resolve(1000), None);
+        assert_eq!(ByteRange::Suffix(0).resolve(1000), None);
+        assert_eq!(ByteRange::Suffix(10).resolve(0), None);
+    }
+
+    #[test]
+    fn test_content_range() {
+        assert_eq!(ByteRange::content_range(&(0..100), 1000), "bytes 0-99/1000");
+        assert_eq!(
+            ByteRange::content_range(&(999..1000), 1000),
+            "bytes 999-999/1000"
+        );
+    }
+}
@@ -4,12 +4,17 @@
 use crate::response::{AptosErrorResponse, BasicErrorWith404, InternalError, NotFoundError};
 use anyhow::{anyhow, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_api_types::{
-    AptosErrorCode, AsConverter, Block, BlockInfo, LedgerInfo, TransactionOnChainData,
+    AptosErrorCode, AsConverter, Block, BlockInfo, LedgerInfo, ResponseProof,
+    TransactionOnChainData,
 };
-use aptos_config::config::{NodeConfig, RoleType};
+use aptos_config::config::{ApiHealthCheckConfig, NodeConfig, RoleType};
 use aptos_crypto::HashValue;
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    MempoolClientRequest, MempoolClientSender, MempoolFeeStatistics, SubmissionStatus,
+    TransactionFilter, API_SOURCE,
+};
 use aptos_state_view::StateView;
+use aptos_transaction_tracing::TxnStage;
 use aptos_types::account_config::NewBlockEvent;
 use aptos_types::transaction::Transaction;
 use aptos_types::{
@@ -19,13 +24,23 @@ use aptos_types::{
     contract_event::EventWithVersion,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
-    transaction::{SignedTransaction, TransactionWithProof, Version},
+    state_store::{
+        state_key::StateKey,
+        state_key_prefix::StateKeyPrefix,
+        state_value::{StateValue, StateValueWithProof},
+    },
+    transaction::{AccountActivity, SignedTransaction, TransactionWithProof, Version},
 };
 use aptos_vm::data_cache::{IntoMoveResolver, RemoteStorageOwned};
 use futures::{channel::oneshot, SinkExt};
-use std::{collections::HashMap, sync::Arc};
+use move_deps::move_core_types::language_storage::TypeTag;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 use storage_interface::{
+    async_db_reader::AsyncDbReader,
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
     DbReader, Order,
 };
@@ -35,8 +50,10 @@ use storage_interface::{
 pub struct Context {
     chain_id: ChainId,
     pub db: Arc<dyn DbReader>,
+    async_db: Arc<AsyncDbReader>,
     mp_sender: MempoolClientSender,
     node_config: NodeConfig,
+    transaction_filter: TransactionFilter,
 }
 
 impl Context {
@@ -45,15 +62,36 @@ impl Context {
         db: Arc<dyn DbReader>,
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
+        transaction_filter: TransactionFilter,
     ) -> Self {
+        let async_db = Arc::new(AsyncDbReader::new(
+            db.clone(),
+            node_config.api.storage_read_threads,
+            node_config.api.max_pending_storage_reads,
+            Duration::from_millis(node_config.api.storage_read_timeout_ms),
+        ));
         Self {
             chain_id,
             db,
+            async_db,
             mp_sender,
             node_config,
+            transaction_filter,
         }
     }
 
+    /// Runs `read` on the storage read pool rather than on the caller's async worker, see
+    /// [`AsyncDbReader::read`]. `read` is given the context to use its helpers, it must not block
+    /// on anything but storage.
+    pub async fn read_async<T, F>(&self, name: &'static str, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Context) -> Result<T> + Send + 'static,
+    {
+        let context = self.clone();
+        self.async_db.read(name, move |_db| read(&context)).await
+    }
+
     pub fn move_resolver(&self) -> Result<RemoteStorageOwned<DbStateView>> {
         self.db
             .latest_state_checkpoint_view()
@@ -84,18 +122,60 @@ impl Context {
         self.node_config.api.content_length_limit()
     }
 
+    pub fn max_view_gas(&self) -> u64 {
+        self.node_config.api.max_view_gas
+    }
+
     pub fn failpoints_enabled(&self) -> bool {
         self.node_config.api.failpoints_enabled
     }
 
+    pub fn health_check_config(&self) -> &ApiHealthCheckConfig {
+        &self.node_config.api.health_check
+    }
+
+    pub fn transaction_filter(&self) -> &TransactionFilter {
+        &self.transaction_filter
+    }
+
+    /// The account address of the node if it is a validator.
+    pub fn validator_address(&self) -> Option<AccountAddress> {
+        match self.node_role() {
+            RoleType::Validator => self.node_config.peer_id(),
+            RoleType::FullNode => None,
+        }
+    }
+
+    /// The number of storage reads which can be queued before requests have to wait for a slot.
+    pub fn available_storage_read_slots(&self) -> usize {
+        self.async_db.available_read_slots()
+    }
+
+    /// Submits the transaction to mempool, unless it matches the transaction filter, in which
+    /// case it's rejected without reaching mempool.
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
+        if let Err(mempool_status) = self.transaction_filter.check(&txn, API_SOURCE) {
+            return Ok((mempool_status, None));
+        }
+        let start = SystemTime::now();
+        let txn_hash =
+            aptos_transaction_tracing::is_enabled().then(|| txn.clone().committed_hash());
         let (req_sender, callback) = oneshot::channel();
         self.mp_sender
             .clone()
             .send(MempoolClientRequest::SubmitTransaction(txn, req_sender))
             .await?;
 
-        callback.await?
+        let status = callback.await?;
+        if let (Some(txn_hash), Ok((mempool_status, _))) = (txn_hash, &status) {
+            aptos_transaction_tracing::record_with_attributes(
+                txn_hash,
+                TxnStage::API_SUBMIT,
+                start,
+                vec![("mempool_status", format!("{:?}", mempool_status.code))],
+            );
+        }
+        status
     }
 
     // For use from external crates where they don't want to handle
@@ -370,8 +450,17 @@ impl Context {
             .map(|(i, ((txn, txn_output), info))| {
                 let version = start_version + i as u64;
                 let (write_set, events, _, _) = txn_output.unpack();
-                self.get_accumulator_root_hash(version)
-                    .map(|h| (version, txn, info, events, h, write_set).into())
+                let mut data: TransactionOnChainData = (
+                    version,
+                    txn,
+                    info,
+                    events,
+                    self.get_accumulator_root_hash(version)?,
+                    write_set,
+                )
+                    .into();
+                data.auxiliary_data = self.db.get_transaction_auxiliary_data_by_version(version)?;
+                Ok(data)
             })
             .collect()
     }
@@ -396,6 +485,64 @@ impl Context {
             .collect::<Result<Vec<_>>>()
     }
 
+    /// The transactions related to an account according to the internal indexer, see
+    /// [`DbReader::get_account_related_versions`].
+    pub fn get_account_related_transactions(
+        &self,
+        address: AccountAddress,
+        start_version: u64,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<Vec<TransactionOnChainData>> {
+        self.db
+            .get_account_related_versions(address, start_version, limit as u64, ledger_version)?
+            .into_iter()
+            .map(|version| self.get_transaction_by_version(version, ledger_version))
+            .collect()
+    }
+
+    /// Passes the activities of the transactions sent by an account, from `start_version` to
+    /// `end_version` (exclusive), to `visit` until it returns false, reading at most
+    /// `max_scanned` of them. Returns the version to resume the scan at, if it stopped before
+    /// `end_version`.
+    pub fn scan_account_activities(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        end_version: Version,
+        max_scanned: u64,
+        ledger_version: Version,
+        mut visit: impl FnMut(&AccountActivity) -> bool,
+    ) -> Result<Option<Version>> {
+        const BATCH_SIZE: u64 = 1000;
+
+        let mut next_version = start_version;
+        let mut num_scanned = 0;
+        while next_version < end_version {
+            if num_scanned >= max_scanned {
+                return Ok(Some(next_version));
+            }
+            let limit = std::cmp::min(BATCH_SIZE, max_scanned - num_scanned);
+            let activities =
+                self.db
+                    .get_account_activities(address, next_version, limit, ledger_version)?;
+            for activity in &activities {
+                if activity.version >= end_version {
+                    return Ok(None);
+                }
+                num_scanned += 1;
+                next_version = activity.version + 1;
+                if !visit(activity) {
+                    return Ok(Some(next_version).filter(|version| *version < end_version));
+                }
+            }
+            if (activities.len() as u64) < limit {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
     pub fn get_transaction_by_hash(
         &self,
         hash: HashValue,
@@ -407,6 +554,18 @@ impl Context {
             .transpose()
     }
 
+    pub async fn get_mempool_fee_statistics(&self) -> Result<MempoolFeeStatistics> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetFeeStatistics(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub async fn get_pending_transaction_by_hash(
         &self,
         hash: HashValue,
@@ -434,6 +593,50 @@ impl Context {
         )?)
     }
 
+    pub fn get_latest_state_checkpoint_version(&self) -> Result<Version> {
+        self.db
+            .get_latest_state_checkpoint_version()?
+            .ok_or_else(|| format_err!("No state checkpoint found in DB"))
+    }
+
+    /// Proves the transaction at `version` against the latest ledger info.
+    pub fn get_transaction_proof(&self, version: u64) -> Result<ResponseProof> {
+        let ledger_info = self.get_latest_ledger_info_with_signatures()?;
+        let txn = self.db.get_transaction_by_version(
+            version,
+            ledger_info.ledger_info().version(),
+            false,
+        )?;
+        ResponseProof::new(version, &txn.proof, &ledger_info)
+    }
+
+    /// Proves the state value of `state_key` at `version` against the latest
+    /// ledger info. Returns `None` if `version` is not a state checkpoint, as
+    /// the state is only authenticated at checkpoints.
+    pub fn get_state_value_proof(
+        &self,
+        state_key: &StateKey,
+        version: u64,
+    ) -> Result<Option<ResponseProof>> {
+        let ledger_info = self.get_latest_ledger_info_with_signatures()?;
+        let txn_info_with_proof = self
+            .db
+            .get_transaction_by_version(version, ledger_info.ledger_info().version(), false)?
+            .proof;
+        if txn_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let (value, state_proof) = self
+            .db
+            .get_state_value_with_proof_by_version(state_key, version)?;
+        let proof = StateValueWithProof::new(version, value, state_proof, txn_info_with_proof);
+        ResponseProof::new(version, &proof, &ledger_info).map(Some)
+    }
+
     pub fn get_accumulator_root_hash(&self, version: u64) -> Result<HashValue> {
         self.db.get_accumulator_root_hash(version)
     }
@@ -447,8 +650,11 @@ impl Context {
             .db
             .get_transaction_outputs(txn.version, 1, txn.version)?
             .transactions_and_outputs[0];
-        self.get_accumulator_root_hash(txn.version)
-            .map(|h| (txn, h, txn_output).into())
+        let version = txn.version;
+        let mut data: TransactionOnChainData =
+            (txn, self.get_accumulator_root_hash(version)?, txn_output).into();
+        data.auxiliary_data = self.db.get_transaction_auxiliary_data_by_version(version)?;
+        Ok(data)
     }
 
     pub fn get_events(
@@ -481,6 +687,36 @@ impl Context {
                 })
         }
     }
+
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: u64,
+        end_version: u64,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<Vec<EventWithVersion>> {
+        self.db.get_events_by_type(
+            type_tag,
+            start_version,
+            end_version,
+            limit as u64,
+            ledger_version,
+        )
+    }
+
+    /// The version of the first transaction committed at or after `timestamp`, or the version
+    /// after `ledger_version` if no block was committed at or after it yet.
+    pub fn get_first_version_at_or_after_timestamp(
+        &self,
+        timestamp: u64,
+        ledger_version: u64,
+    ) -> Result<u64> {
+        Ok(self
+            .db
+            .get_first_version_at_or_after_timestamp(timestamp, ledger_version)?
+            .unwrap_or(ledger_version + 1))
+    }
 }
 
 pub fn get_block_hash_and_timestamp(txn: &Transaction, version: u64) -> Result<(HashValue, u64)> {
@@ -16,8 +16,10 @@ use crate::ApiTags;
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{Address, EventKey, IdentifierWrapper, MoveStructTag, U64};
 use aptos_api_types::{AsConverter, VersionedEvent};
+use move_deps::move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::param::Query;
 use poem_openapi::{param::Path, OpenApi};
+use std::convert::TryFrom;
 
 pub struct EventsApi {
     pub context: Arc<Context>,
@@ -46,7 +48,7 @@ impl EventsApi {
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         fail_point_poem("endpoint_get_events_by_event_key")?;
         let page = Page::new(start.0.map(|v| v.0), limit.0);
-        self.list(accept_type, page, event_key.0)
+        self.list(accept_type, page, event_key.0).await
     }
 
     /// Get events by event handle
@@ -76,12 +78,49 @@ impl EventsApi {
         let key = account
             .find_event_key(event_handle.0, field_name.0.into())?
             .into();
-        self.list(accept_type, page, key)
+        self.list(accept_type, page, key).await
+    }
+
+    /// Get events by type
+    ///
+    /// This endpoint returns the events of a type, whichever event handles they were
+    /// emitted to, in ascending order of the version of the transaction that emitted
+    /// them. Events can be restricted to the versions from `start_version` (inclusive)
+    /// to `end_version` (exclusive), and to the blocks committed from `start_timestamp`
+    /// (inclusive) to `end_timestamp` (exclusive), in microseconds.
+    #[oai(
+        path = "/events/by_type/:event_type",
+        method = "get",
+        operation_id = "get_events_by_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn get_events_by_type(
+        &self,
+        accept_type: AcceptType,
+        event_type: Path<MoveStructTag>,
+        start_version: Query<Option<U64>>,
+        end_version: Query<Option<U64>>,
+        start_timestamp: Query<Option<U64>>,
+        end_timestamp: Query<Option<U64>>,
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        fail_point_poem("endpoint_get_events_by_type")?;
+        let page = Page::new(None, limit.0);
+        self.list_by_type(
+            accept_type,
+            page,
+            event_type.0,
+            start_version.0.map(|v| v.0),
+            end_version.0.map(|v| v.0),
+            start_timestamp.0.map(|v| v.0),
+            end_timestamp.0.map(|v| v.0),
+        )
+        .await
     }
 }
 
 impl EventsApi {
-    fn list(
+    async fn list(
         &self,
         accept_type: AcceptType,
         page: Page,
@@ -89,14 +128,14 @@ impl EventsApi {
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
         let ledger_version = latest_ledger_info.version();
+        let start = page.start_option();
+        let limit = page.limit()?;
         let events = self
             .context
-            .get_events(
-                &event_key.into(),
-                page.start_option(),
-                page.limit()?,
-                ledger_version,
-            )
+            .read_async("get_events", move |context| {
+                context.get_events(&event_key.into(), start, limit, ledger_version)
+            })
+            .await
             // TODO: Previously this was a 500, but I'm making this a 400. I suspect
             // both could be true depending on the error. Make this more specific.
             .context(format!("Failed to find events by key {}", event_key))
@@ -116,4 +155,78 @@ impl EventsApi {
             &accept_type,
         ))
     }
+
+    async fn list_by_type(
+        &self,
+        accept_type: AcceptType,
+        page: Page,
+        event_type: MoveStructTag,
+        start_version: Option<u64>,
+        end_version: Option<u64>,
+        start_timestamp: Option<u64>,
+        end_timestamp: Option<u64>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_version = latest_ledger_info.version();
+        let type_tag = TypeTag::Struct(
+            StructTag::try_from(event_type)
+                .context("Given event type is invalid")
+                .map_err(BasicErrorWith404::bad_request)?,
+        );
+
+        // Resolve the time range to the versions of the blocks committed within it.
+        let mut start_version =
+            start_version.unwrap_or_else(|| latest_ledger_info.oldest_ledger_version.0);
+        let mut end_version = end_version.unwrap_or(u64::MAX);
+        if let Some(start_timestamp) = start_timestamp {
+            let version = self
+                .context
+                .get_first_version_at_or_after_timestamp(start_timestamp, ledger_version)
+                .context("Failed to find the version of the start timestamp")
+                .map_err(BasicErrorWith404::internal)?;
+            start_version = std::cmp::max(start_version, version);
+        }
+        if let Some(end_timestamp) = end_timestamp {
+            let version = self
+                .context
+                .get_first_version_at_or_after_timestamp(end_timestamp, ledger_version)
+                .context("Failed to find the version of the end timestamp")
+                .map_err(BasicErrorWith404::internal)?;
+            end_version = std::cmp::min(end_version, version);
+        }
+
+        let events = if start_version < end_version {
+            let limit = page.limit()?;
+            let read_type_tag = type_tag.clone();
+            self.context
+                .read_async("get_events_by_type", move |context| {
+                    context.get_events_by_type(
+                        &read_type_tag,
+                        start_version,
+                        end_version,
+                        limit,
+                        ledger_version,
+                    )
+                })
+                .await
+                .context(format!("Failed to find events by type {}", type_tag))
+                .map_err(BasicErrorWith404::bad_request)?
+        } else {
+            vec![]
+        };
+
+        let resolver = self.context.move_resolver_poem()?;
+        let events = resolver
+            .as_converter(self.context.db.clone())
+            .try_into_versioned_events(&events)
+            .context("Failed to convert events from storage into response")
+            .map_err(BasicErrorWith404::internal)?;
+
+        BasicResponse::try_from_rust_value((
+            events,
+            &latest_ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
 }
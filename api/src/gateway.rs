@@ -0,0 +1,512 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::GATEWAY_REJECTIONS;
+use anyhow::{bail, ensure};
+use aptos_api_types::AptosError;
+use aptos_config::config::{ApiGatewayConfig, ApiKeyPolicy};
+use aptos_infallible::{Mutex, RwLock};
+use aptos_rate_limiter::rate_limit::{Bucket, TokenBucketRateLimiter};
+use poem::{
+    http::{header, HeaderValue, Method, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+use poem_openapi::payload::Json;
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+const ANONYMOUS: &str = "anonymous";
+
+/// This middleware enforces the API key policies from the ApiGatewayConfig.
+/// Each request is matched to an API key (or the anonymous policy), which
+/// determines the HTTP methods and paths it may use and how many requests per
+/// second it may make. If the gateway is disabled, it does nothing.
+///
+/// The policies can be replaced while the API runs with `update`, and the whole API can be turned
+/// off with `set_enabled`, both of which are shared by all clones of the gateway.
+#[derive(Clone)]
+pub struct ApiGateway {
+    inner: Arc<RwLock<Option<Arc<GatewayState>>>>,
+    /// Whether the API serves requests at all, regardless of the gateway config
+    api_enabled: Arc<AtomicBool>,
+}
+
+struct GatewayState {
+    api_key_header: String,
+    public_paths: Vec<String>,
+    api_keys: HashMap<String, KeyState>,
+    anonymous: Option<AnonymousState>,
+}
+
+/// The policy and rate limit state for a single API key.
+struct KeyState {
+    name: String,
+    policy: CompiledPolicy,
+    bucket: Option<Mutex<Bucket>>,
+}
+
+/// The anonymous policy, rate limited per client IP.
+struct AnonymousState {
+    policy: CompiledPolicy,
+    limiter: Option<TokenBucketRateLimiter<IpAddr>>,
+}
+
+struct CompiledPolicy {
+    allowed_methods: HashSet<Method>,
+    allowed_paths: Vec<String>,
+}
+
+impl CompiledPolicy {
+    fn new(policy: &ApiKeyPolicy) -> anyhow::Result<Self> {
+        let allowed_methods = policy
+            .allowed_methods
+            .iter()
+            .map(|method| {
+                Method::from_str(&method.to_uppercase())
+                    .map_err(|_| anyhow::format_err!("Invalid HTTP method: {}", method))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        if let Some(requests_per_second) = policy.requests_per_second {
+            ensure!(
+                requests_per_second > 0,
+                "requests_per_second must be greater than zero"
+            );
+            ensure!(
+                policy.burst_size().unwrap_or_default() >= requests_per_second,
+                "burst_size must be greater than or equal to requests_per_second"
+            );
+        }
+        Ok(Self {
+            allowed_methods,
+            allowed_paths: policy.allowed_paths.clone(),
+        })
+    }
+
+    fn check(&self, method: &Method, path: &str) -> std::result::Result<(), Rejection> {
+        if !self.allowed_methods.is_empty() && !self.allowed_methods.contains(method) {
+            return Err(Rejection::MethodNotAllowed);
+        }
+        if !self.allowed_paths.is_empty()
+            && !self
+                .allowed_paths
+                .iter()
+                .any(|prefix| path.starts_with(prefix.as_str()))
+        {
+            return Err(Rejection::PathNotAllowed);
+        }
+        Ok(())
+    }
+}
+
+fn new_bucket(name: &str, policy: &ApiKeyPolicy) -> Option<Mutex<Bucket>> {
+    let rate = policy.requests_per_second?;
+    let size = policy.burst_size().unwrap_or(rate);
+    Some(Mutex::new(Bucket::new(
+        "api_gateway".to_string(),
+        String::new(),
+        name.to_string(),
+        size,
+        size,
+        rate,
+        None,
+    )))
+}
+
+/// Why a request was rejected by the gateway.
+#[derive(Debug)]
+enum Rejection {
+    ApiDisabled,
+    MissingApiKey,
+    InvalidApiKey,
+    MethodNotAllowed,
+    PathNotAllowed,
+    RateLimited(Option<Instant>),
+}
+
+impl Rejection {
+    fn label(&self) -> &'static str {
+        match self {
+            Rejection::ApiDisabled => "api_disabled",
+            Rejection::MissingApiKey => "missing_api_key",
+            Rejection::InvalidApiKey => "invalid_api_key",
+            Rejection::MethodNotAllowed => "method_not_allowed",
+            Rejection::PathNotAllowed => "path_not_allowed",
+            Rejection::RateLimited(_) => "rate_limited",
+        }
+    }
+
+    fn into_response(self) -> Response {
+        let (status, message) = match &self {
+            Rejection::ApiDisabled => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "The API has been disabled by the node operator",
+            ),
+            Rejection::MissingApiKey => (StatusCode::UNAUTHORIZED, "An API key is required"),
+            Rejection::InvalidApiKey => (StatusCode::UNAUTHORIZED, "The API key is invalid"),
+            Rejection::MethodNotAllowed => (
+                StatusCode::FORBIDDEN,
+                "The HTTP method is not allowed for this API key",
+            ),
+            Rejection::PathNotAllowed => (
+                StatusCode::FORBIDDEN,
+                "The path is not allowed for this API key",
+            ),
+            Rejection::RateLimited(_) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "The rate limit for this API key has been exceeded",
+            ),
+        };
+        let mut response = Json(AptosError::new(message.to_string())).into_response();
+        response.set_status(status);
+        if let Rejection::RateLimited(Some(ready_at)) = self {
+            // Round up so clients don't retry before the token is available.
+            let retry_after = ready_at
+                .saturating_duration_since(Instant::now())
+                .as_secs()
+                .saturating_add(1);
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        response
+    }
+}
+
+impl ApiGateway {
+    pub fn new(config: &ApiGatewayConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            inner: Arc::new(RwLock::new(GatewayState::new(config)?)),
+            api_enabled: Arc::new(AtomicBool::new(true)),
+        })
+    }
+
+    /// Turns the API on or off. While it's off, every request is rejected as unavailable.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.api_enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.api_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Replaces the policies with those of `config`. Rate limits start over from a full bucket.
+    /// If `config` is invalid, the current policies are kept.
+    pub fn update(&self, config: &ApiGatewayConfig) -> anyhow::Result<()> {
+        let state = GatewayState::new(config)?;
+        *self.inner.write() = state;
+        Ok(())
+    }
+
+    fn state(&self) -> Option<Arc<GatewayState>> {
+        self.inner.read().clone()
+    }
+}
+
+impl GatewayState {
+    fn new(config: &ApiGatewayConfig) -> anyhow::Result<Option<Arc<Self>>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let mut api_keys = HashMap::new();
+        for api_key in &config.api_keys {
+            ensure!(!api_key.key.is_empty(), "API key {} is empty", api_key.name);
+            let state = KeyState {
+                name: api_key.name.clone(),
+                policy: CompiledPolicy::new(&api_key.policy)?,
+                bucket: new_bucket(&api_key.name, &api_key.policy),
+            };
+            if api_keys.insert(api_key.key.clone(), state).is_some() {
+                bail!("API key {} is configured more than once", api_key.name);
+            }
+        }
+
+        let anonymous = match &config.anonymous_policy {
+            Some(policy) => Some(AnonymousState {
+                policy: CompiledPolicy::new(policy)?,
+                limiter: policy.requests_per_second.map(|rate| {
+                    TokenBucketRateLimiter::new(
+                        "api_gateway_anonymous",
+                        String::new(),
+                        100,
+                        policy.burst_size().unwrap_or(rate),
+                        rate,
+                        None,
+                    )
+                }),
+            }),
+            None => None,
+        };
+
+        Ok(Some(Arc::new(GatewayState {
+            api_key_header: config.api_key_header.to_lowercase(),
+            public_paths: config.public_paths.clone(),
+            api_keys,
+            anonymous,
+        })))
+    }
+
+    fn api_key<'a>(&self, request: &'a Request) -> Option<&'a str> {
+        let headers = request.headers();
+        if let Some(value) = headers.get(self.api_key_header.as_str()) {
+            return value.to_str().ok();
+        }
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+    }
+
+    /// Returns the name of the key the request was admitted under.
+    fn admit(&self, request: &Request) -> std::result::Result<&str, (&str, Rejection)> {
+        let method = request.method();
+        let path = request.uri().path();
+
+        match self.api_key(request) {
+            Some(api_key) => {
+                let key = self
+                    .api_keys
+                    .get(api_key)
+                    .ok_or((ANONYMOUS, Rejection::InvalidApiKey))?;
+                key.policy
+                    .check(method, path)
+                    .map_err(|rejection| (key.name.as_str(), rejection))?;
+                if let Some(bucket) = &key.bucket {
+                    bucket.lock().acquire_all_tokens(1).map_err(|ready_at| {
+                        (key.name.as_str(), Rejection::RateLimited(ready_at))
+                    })?;
+                }
+                Ok(key.name.as_str())
+            }
+            None => {
+                let anonymous = self
+                    .anonymous
+                    .as_ref()
+                    .ok_or((ANONYMOUS, Rejection::MissingApiKey))?;
+                anonymous
+                    .policy
+                    .check(method, path)
+                    .map_err(|rejection| (ANONYMOUS, rejection))?;
+                if let (Some(limiter), Some(remote_addr)) =
+                    (&anonymous.limiter, request.remote_addr().as_socket_addr())
+                {
+                    limiter
+                        .bucket(remote_addr.ip())
+                        .lock()
+                        .acquire_all_tokens(1)
+                        .map_err(|ready_at| (ANONYMOUS, Rejection::RateLimited(ready_at)))?;
+                }
+                Ok(ANONYMOUS)
+            }
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ApiGateway {
+    type Output = ApiGatewayEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ApiGatewayEndpoint {
+            inner: ep,
+            gateway: self.clone(),
+        }
+    }
+}
+
+/// Endpoint for ApiGateway middleware.
+pub struct ApiGatewayEndpoint<E> {
+    inner: E,
+    gateway: ApiGateway,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for ApiGatewayEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if !self.gateway.is_enabled() {
+            let rejection = Rejection::ApiDisabled;
+            GATEWAY_REJECTIONS
+                .with_label_values(&[ANONYMOUS, rejection.label()])
+                .inc();
+            return Ok(rejection.into_response());
+        }
+
+        let state = match self.gateway.state() {
+            Some(state) => state,
+            None => return self.inner.call(req).await.map(IntoResponse::into_response),
+        };
+
+        let path = req.uri().path();
+        if state.public_paths.iter().any(|public| public == path) {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        if let Err((key_name, rejection)) = state.admit(&req) {
+            GATEWAY_REJECTIONS
+                .with_label_values(&[key_name, rejection.label()])
+                .inc();
+            return Ok(rejection.into_response());
+        }
+
+        self.inner.call(req).await.map(IntoResponse::into_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiGateway, Rejection};
+    use aptos_config::config::{ApiGatewayConfig, ApiKeyConfig, ApiKeyPolicy};
+    use poem::{
+        endpoint::make_sync,
+        http::{Method, StatusCode},
+        Endpoint, EndpointExt, Request,
+    };
+
+    fn config() -> ApiGatewayConfig {
+        ApiGatewayConfig {
+            enabled: true,
+            api_keys: vec![
+                ApiKeyConfig {
+                    name: "reader".to_string(),
+                    key: "reader-key".to_string(),
+                    policy: ApiKeyPolicy {
+                        requests_per_second: Some(2),
+                        burst_size: None,
+                        allowed_methods: vec!["get".to_string()],
+                        allowed_paths: vec!["/v1/accounts".to_string()],
+                    },
+                },
+                ApiKeyConfig {
+                    name: "admin".to_string(),
+                    key: "admin-key".to_string(),
+                    policy: ApiKeyPolicy::default(),
+                },
+            ],
+            ..ApiGatewayConfig::default()
+        }
+    }
+
+    fn request(method: Method, path: &str, api_key: Option<&str>) -> Request {
+        let mut builder = Request::builder().method(method).uri_str(path);
+        if let Some(api_key) = api_key {
+            builder = builder.header("x-aptos-api-key", api_key);
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn test_api_key_policies() {
+        let gateway = ApiGateway::new(&config()).unwrap();
+        let state = gateway.state().unwrap();
+
+        let admit = |method, path, api_key| state.admit(&request(method, path, api_key));
+
+        assert!(matches!(
+            admit(Method::GET, "/v1/accounts/0x1", None),
+            Err((_, Rejection::MissingApiKey))
+        ));
+        assert!(matches!(
+            admit(Method::GET, "/v1/accounts/0x1", Some("nope")),
+            Err((_, Rejection::InvalidApiKey))
+        ));
+        assert!(matches!(
+            admit(Method::POST, "/v1/accounts/0x1", Some("reader-key")),
+            Err(("reader", Rejection::MethodNotAllowed))
+        ));
+        assert!(matches!(
+            admit(Method::GET, "/v1/transactions", Some("reader-key")),
+            Err(("reader", Rejection::PathNotAllowed))
+        ));
+        assert_eq!(
+            admit(Method::POST, "/v1/transactions", Some("admin-key")).unwrap(),
+            "admin"
+        );
+
+        // The reader key has a burst of 2 requests.
+        assert!(admit(Method::GET, "/v1/accounts/0x1", Some("reader-key")).is_ok());
+        assert!(admit(Method::GET, "/v1/accounts/0x1", Some("reader-key")).is_ok());
+        assert!(matches!(
+            admit(Method::GET, "/v1/accounts/0x1", Some("reader-key")),
+            Err(("reader", Rejection::RateLimited(Some(_))))
+        ));
+        // Other keys are unaffected.
+        assert!(admit(Method::GET, "/v1/accounts/0x1", Some("admin-key")).is_ok());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let gateway = ApiGateway::new(&config()).unwrap();
+        let state = gateway.state().unwrap();
+        let request = Request::builder()
+            .uri_str("/v1/transactions")
+            .header("authorization", "Bearer admin-key")
+            .finish();
+        assert_eq!(state.admit(&request).unwrap(), "admin");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut duplicate = config();
+        duplicate.api_keys[1].key = "reader-key".to_string();
+        assert!(ApiGateway::new(&duplicate).is_err());
+
+        let mut invalid_method = config();
+        invalid_method.api_keys[0].policy.allowed_methods = vec!["G ET".to_string()];
+        assert!(ApiGateway::new(&invalid_method).is_err());
+
+        let mut invalid_burst = config();
+        invalid_burst.api_keys[0].policy.burst_size = Some(1);
+        assert!(ApiGateway::new(&invalid_burst).is_err());
+
+        let mut disabled = duplicate;
+        disabled.enabled = false;
+        assert!(ApiGateway::new(&disabled).unwrap().state().is_none());
+    }
+
+    #[test]
+    fn test_update() {
+        let gateway = ApiGateway::new(&ApiGatewayConfig::default()).unwrap();
+        let endpoint_gateway = gateway.clone();
+        assert!(endpoint_gateway.state().is_none());
+
+        gateway.update(&config()).unwrap();
+        let admin_request = request(Method::GET, "/v1/transactions", Some("admin-key"));
+        assert_eq!(
+            endpoint_gateway
+                .state()
+                .unwrap()
+                .admit(&admin_request)
+                .unwrap(),
+            "admin"
+        );
+
+        // An invalid config leaves the policies unchanged
+        let mut invalid_burst = config();
+        invalid_burst.api_keys[0].policy.burst_size = Some(1);
+        assert!(gateway.update(&invalid_burst).is_err());
+        assert!(endpoint_gateway.state().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_disable_api() {
+        let gateway = ApiGateway::new(&ApiGatewayConfig::default()).unwrap();
+        let endpoint = make_sync(|_| "ok").with(gateway.clone());
+
+        gateway.set_enabled(false);
+        let response = endpoint.call(Request::default()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        gateway.set_enabled(true);
+        let response = endpoint.call(Request::default()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,511 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::accept_type::AcceptType;
+use crate::context::Context;
+use crate::failpoint::fail_point_poem;
+use crate::page::Page;
+use crate::response::{
+    build_not_found, AptosErrorResponse, BadRequestError, BasicErrorWith404, BasicResponse,
+    BasicResponseStatus, BasicResultWith404, InternalError, NotFoundError,
+};
+use crate::ApiTags;
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    Address, AptosErrorCode, GovernanceConfig, GovernanceProposal, GovernanceProposalState,
+    GovernanceVotingRecord, LedgerInfo, StakePoolSummary, TransactionId, ValidatorState, U64,
+};
+use aptos_state_view::StateView;
+use aptos_types::access_path::AccessPath;
+use aptos_types::account_address::AccountAddress;
+use aptos_types::account_config::CORE_CODE_ADDRESS;
+use aptos_types::governance::{
+    GovernanceConfigResource, GovernanceVotingForumResource, GovernanceVotingProposal,
+    VotingRecordKey, VotingRecordsResource,
+};
+use aptos_types::stake_pool::StakePool;
+use aptos_types::state_store::state_key::StateKey;
+use aptos_types::state_store::table::TableHandle;
+use aptos_vm::AptosVM;
+use move_deps::move_core_types::identifier::Identifier;
+use move_deps::move_core_types::language_storage::{ModuleId, ResourceKey, TypeTag};
+use move_deps::move_core_types::move_resource::{MoveResource, MoveStructType};
+use poem_openapi::param::{Path, Query};
+use poem_openapi::OpenApi;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use storage_interface::state_view::DbStateView;
+
+/// Decodes the governance resources of the framework, so that clients don't
+/// have to follow their layout across framework upgrades.
+pub struct GovernanceApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl GovernanceApi {
+    /// Get governance configuration
+    ///
+    /// Get the configuration of on-chain governance at a specified ledger
+    /// version (AKA transaction version): the stake required to create a
+    /// proposal, how long proposals can be voted on, and the minimum number of
+    /// votes for a proposal to pass. If the ledger version is not specified in
+    /// the request, the latest ledger version is used.
+    #[oai(
+        path = "/governance/config",
+        method = "get",
+        operation_id = "get_governance_config",
+        tag = "ApiTags::Governance"
+    )]
+    async fn get_governance_config(
+        &self,
+        accept_type: AcceptType,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<GovernanceConfig> {
+        fail_point_poem("endpoint_get_governance_config")?;
+        self.config(&accept_type, ledger_version.0)
+    }
+
+    /// Get governance proposals
+    ///
+    /// Get the governance proposals in order of proposal id, at a specified
+    /// ledger version (AKA transaction version). The state of each proposal is
+    /// computed by the framework as of that version. If the ledger version is
+    /// not specified in the request, the latest ledger version is used.
+    ///
+    /// To retrieve a large number of proposals, use the `start` proposal id and
+    /// the `limit` to page through them.
+    #[oai(
+        path = "/governance/proposals",
+        method = "get",
+        operation_id = "get_governance_proposals",
+        tag = "ApiTags::Governance"
+    )]
+    async fn get_governance_proposals(
+        &self,
+        accept_type: AcceptType,
+        start: Query<Option<U64>>,
+        limit: Query<Option<u16>>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<GovernanceProposal>> {
+        fail_point_poem("endpoint_get_governance_proposals")?;
+        let page = Page::new(start.0.map(|v| v.0), limit.0);
+        self.proposals(&accept_type, page, ledger_version.0)
+    }
+
+    /// Get governance proposal
+    ///
+    /// Get the governance proposal with a given id at a specified ledger
+    /// version (AKA transaction version). If the ledger version is not
+    /// specified in the request, the latest ledger version is used.
+    #[oai(
+        path = "/governance/proposals/:proposal_id",
+        method = "get",
+        operation_id = "get_governance_proposal",
+        tag = "ApiTags::Governance"
+    )]
+    async fn get_governance_proposal(
+        &self,
+        accept_type: AcceptType,
+        proposal_id: Path<U64>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<GovernanceProposal> {
+        fail_point_poem("endpoint_get_governance_proposal")?;
+        self.proposal(&accept_type, proposal_id.0 .0, ledger_version.0)
+    }
+
+    /// Get governance voting record
+    ///
+    /// Get whether a stake pool voted on the governance proposal with a given
+    /// id, at a specified ledger version (AKA transaction version). How it
+    /// voted, and with how much stake, is recorded in the
+    /// `0x1::aptos_governance::VoteEvent` events. If the ledger version is not
+    /// specified in the request, the latest ledger version is used.
+    #[oai(
+        path = "/governance/proposals/:proposal_id/votes/:stake_pool",
+        method = "get",
+        operation_id = "get_governance_voting_record",
+        tag = "ApiTags::Governance"
+    )]
+    async fn get_governance_voting_record(
+        &self,
+        accept_type: AcceptType,
+        proposal_id: Path<U64>,
+        stake_pool: Path<Address>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<GovernanceVotingRecord> {
+        fail_point_poem("endpoint_get_governance_voting_record")?;
+        self.voting_record(
+            &accept_type,
+            proposal_id.0 .0,
+            stake_pool.0,
+            ledger_version.0,
+        )
+    }
+
+    /// Get stake pool
+    ///
+    /// Get a summary of the stake pool at a given address, at a specified
+    /// ledger version (AKA transaction version): its stake, lockup, operator
+    /// and delegated voter, the state of its validator and its voting power in
+    /// the current epoch. If the ledger version is not specified in the
+    /// request, the latest ledger version is used.
+    #[oai(
+        path = "/governance/stake_pools/:address",
+        method = "get",
+        operation_id = "get_stake_pool",
+        tag = "ApiTags::Governance"
+    )]
+    async fn get_stake_pool(
+        &self,
+        accept_type: AcceptType,
+        address: Path<Address>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<StakePoolSummary> {
+        fail_point_poem("endpoint_get_stake_pool")?;
+        self.stake_pool(&accept_type, address.0, ledger_version.0)
+    }
+}
+
+impl GovernanceApi {
+    fn preprocess_request<E: NotFoundError + InternalError>(
+        &self,
+        requested_ledger_version: Option<U64>,
+    ) -> Result<(LedgerInfo, u64, DbStateView), E> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_version: u64 = requested_ledger_version
+            .map(|v| v.0)
+            .unwrap_or_else(|| latest_ledger_info.version());
+
+        if ledger_version > latest_ledger_info.version() {
+            return Err(build_not_found(
+                "ledger",
+                TransactionId::Version(U64::from(ledger_version)),
+                latest_ledger_info.version(),
+            ));
+        }
+
+        let state_view = self
+            .context
+            .state_view_at_version(ledger_version)
+            .context(format!(
+                "Failed to get state view at version {}",
+                ledger_version
+            ))
+            .map_err(E::internal)?;
+
+        Ok((latest_ledger_info, ledger_version, state_view))
+    }
+
+    fn config(
+        &self,
+        accept_type: &AcceptType,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<GovernanceConfig> {
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let config: GovernanceConfigResource =
+            read_resource(&state_view, CORE_CODE_ADDRESS, ledger_version)?;
+
+        let config = GovernanceConfig {
+            min_voting_threshold: config.min_voting_threshold.into(),
+            required_proposer_stake: config.required_proposer_stake.into(),
+            voting_duration_secs: config.voting_duration_secs.into(),
+        };
+        BasicResponse::try_from_rust_value((
+            config,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    fn proposals(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<Vec<GovernanceProposal>> {
+        let limit = page.limit()?;
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let forum: GovernanceVotingForumResource =
+            read_resource(&state_view, CORE_CODE_ADDRESS, ledger_version)?;
+
+        let start = page.start_option().unwrap_or(0);
+        let end = start
+            .saturating_add(limit as u64)
+            .min(forum.next_proposal_id);
+        let proposals = (start..end)
+            .map(|proposal_id| self.read_proposal(&state_view, &forum, proposal_id))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(BasicErrorWith404::internal)?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        BasicResponse::try_from_rust_value((
+            proposals,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    fn proposal(
+        &self,
+        accept_type: &AcceptType,
+        proposal_id: u64,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<GovernanceProposal> {
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let forum: GovernanceVotingForumResource =
+            read_resource(&state_view, CORE_CODE_ADDRESS, ledger_version)?;
+        let proposal = self
+            .read_proposal(&state_view, &forum, proposal_id)
+            .map_err(BasicErrorWith404::internal)?
+            .ok_or_else(|| {
+                build_not_found::<_, BasicErrorWith404>(
+                    "Governance proposal",
+                    format!("id {}", proposal_id),
+                    ledger_version,
+                )
+            })?;
+
+        BasicResponse::try_from_rust_value((
+            proposal,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    fn voting_record(
+        &self,
+        accept_type: &AcceptType,
+        proposal_id: u64,
+        stake_pool: Address,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<GovernanceVotingRecord> {
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let forum: GovernanceVotingForumResource =
+            read_resource(&state_view, CORE_CODE_ADDRESS, ledger_version)?;
+        if proposal_id >= forum.next_proposal_id {
+            return Err(build_not_found(
+                "Governance proposal",
+                format!("id {}", proposal_id),
+                ledger_version,
+            ));
+        }
+
+        let records: VotingRecordsResource =
+            read_resource(&state_view, CORE_CODE_ADDRESS, ledger_version)?;
+        let key = VotingRecordKey {
+            stake_pool: stake_pool.into(),
+            proposal_id,
+        };
+        let voted = read_table_item::<_, bool>(&state_view, records.votes, &key)
+            .map_err(BasicErrorWith404::internal)?
+            .unwrap_or(false);
+
+        let record = GovernanceVotingRecord {
+            proposal_id: proposal_id.into(),
+            stake_pool,
+            voted,
+        };
+        BasicResponse::try_from_rust_value((
+            record,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    fn stake_pool(
+        &self,
+        accept_type: &AcceptType,
+        address: Address,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<StakePoolSummary> {
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let stake_pool: StakePool = read_resource(&state_view, address.into(), ledger_version)?;
+
+        let pool_address = bcs::to_bytes(&AccountAddress::from(address))
+            .context("Failed to serialize the stake pool address")
+            .map_err(BasicErrorWith404::internal)?;
+        let validator_state = match self
+            .call_u64_function(
+                &state_view,
+                "stake",
+                "get_validator_state",
+                vec![],
+                vec![pool_address.clone()],
+            )
+            .map_err(BasicErrorWith404::internal)?
+        {
+            1 => ValidatorState::PendingActive,
+            2 => ValidatorState::Active,
+            3 => ValidatorState::PendingInactive,
+            4 => ValidatorState::Inactive,
+            state => {
+                return Err(BasicErrorWith404::internal_str(&format!(
+                    "Unknown validator state {}",
+                    state
+                )))
+            }
+        };
+        let voting_power = self
+            .call_u64_function(
+                &state_view,
+                "stake",
+                "get_current_epoch_voting_power",
+                vec![],
+                vec![pool_address],
+            )
+            .map_err(BasicErrorWith404::internal)?;
+
+        let summary = StakePoolSummary {
+            pool_address: address,
+            operator_address: stake_pool.operator_address.into(),
+            delegated_voter: stake_pool.delegated_voter.into(),
+            active: stake_pool.active.value.into(),
+            inactive: stake_pool.inactive.value.into(),
+            pending_active: stake_pool.pending_active.value.into(),
+            pending_inactive: stake_pool.pending_inactive.value.into(),
+            locked_until_secs: stake_pool.locked_until_secs.into(),
+            validator_state,
+            voting_power: voting_power.into(),
+        };
+        BasicResponse::try_from_rust_value((
+            summary,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    /// Reads a proposal from the governance voting forum, with its state as
+    /// computed by the framework. Returns `None` if there's no such proposal.
+    fn read_proposal(
+        &self,
+        state_view: &DbStateView,
+        forum: &GovernanceVotingForumResource,
+        proposal_id: u64,
+    ) -> anyhow::Result<Option<GovernanceProposal>> {
+        if proposal_id >= forum.next_proposal_id {
+            return Ok(None);
+        }
+        let proposal: GovernanceVotingProposal =
+            match read_table_item(state_view, forum.proposals, &proposal_id)? {
+                Some(proposal) => proposal,
+                None => return Ok(None),
+            };
+
+        let state = match self.call_u64_function(
+            state_view,
+            "voting",
+            "get_proposal_state",
+            vec![TypeTag::Struct(
+                aptos_types::governance::GovernanceProposal::struct_tag(),
+            )],
+            vec![
+                bcs::to_bytes(&CORE_CODE_ADDRESS)?,
+                bcs::to_bytes(&proposal_id)?,
+            ],
+        )? {
+            0 => GovernanceProposalState::Pending,
+            1 => GovernanceProposalState::Succeeded,
+            3 => GovernanceProposalState::Failed,
+            state => anyhow::bail!("Unknown state {} of proposal {}", state, proposal_id),
+        };
+        let metadata = |key| {
+            proposal
+                .metadata(key)
+                .map(|value| String::from_utf8_lossy(value).into_owned())
+        };
+
+        Ok(Some(GovernanceProposal {
+            proposal_id: proposal_id.into(),
+            proposer: proposal.proposer.into(),
+            state,
+            is_resolved: proposal.is_resolved,
+            creation_time_secs: proposal.creation_time_secs.into(),
+            expiration_secs: proposal.expiration_secs.into(),
+            execution_hash: proposal.execution_hash.clone().into(),
+            metadata_location: metadata("metadata_location"),
+            metadata_hash: metadata("metadata_hash"),
+            min_vote_threshold: proposal.min_vote_threshold.into(),
+            early_resolution_vote_threshold: proposal
+                .early_resolution_vote_threshold
+                .map(Into::into),
+            yes_votes: proposal.yes_votes.into(),
+            no_votes: proposal.no_votes.into(),
+        }))
+    }
+
+    /// Calls a framework function returning a single u64, e.g. a getter
+    /// deriving a state from several resources
+    fn call_u64_function(
+        &self,
+        state_view: &DbStateView,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+    ) -> anyhow::Result<u64> {
+        let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(module)?);
+        let function = Identifier::new(function)?;
+        let return_values = AptosVM::execute_view_function(
+            state_view,
+            &module_id,
+            &function,
+            type_args,
+            arguments,
+            self.context.max_view_gas(),
+        )
+        .context(format!("Failed to call {}::{}", module_id, function))?;
+        match return_values.as_slice() {
+            [value] => bcs::from_bytes(value)
+                .context(format!("Failed to deserialize the result of {}", function)),
+            _ => anyhow::bail!(
+                "{} returned {} values instead of 1",
+                function,
+                return_values.len()
+            ),
+        }
+    }
+}
+
+/// Reads and deserializes a resource, which is reported as not found if it doesn't exist
+fn read_resource<T: MoveResource>(
+    state_view: &DbStateView,
+    address: AccountAddress,
+    ledger_version: u64,
+) -> Result<T, BasicErrorWith404> {
+    let resource_key = ResourceKey::new(address, T::struct_tag());
+    let state_key = StateKey::AccessPath(AccessPath::resource_access_path(resource_key.clone()));
+    let bytes = state_view
+        .get_state_value(&state_key)
+        .context(format!("Failed to query DB to check for {:?}", state_key))
+        .map_err(BasicErrorWith404::internal)?
+        .ok_or_else(|| build_not_found("Resource", resource_key, ledger_version))?;
+    bcs::from_bytes(&bytes)
+        .context(format!("Failed to deserialize {}", T::struct_tag()))
+        .map_err(|err| {
+            BasicErrorWith404::internal(err).error_code(AptosErrorCode::InvalidBcsInStorageError)
+        })
+}
+
+/// Reads and deserializes a table item, given its key
+fn read_table_item<K: Serialize, V: DeserializeOwned>(
+    state_view: &DbStateView,
+    handle: TableHandle,
+    key: &K,
+) -> anyhow::Result<Option<V>> {
+    let state_key = StateKey::table_item(handle, bcs::to_bytes(key)?);
+    state_view
+        .get_state_value(&state_key)
+        .context(format!("Failed to query DB to check for {:?}", state_key))?
+        .map(|bytes| bcs::from_bytes(&bytes).context("Failed to deserialize table item"))
+        .transpose()
+}
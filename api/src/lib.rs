@@ -7,14 +7,20 @@ mod accept_type;
 mod accounts;
 mod basic;
 mod bcs_payload;
+mod bcs_schema;
 mod blocks;
+mod byte_range;
 mod check_size;
 pub mod context;
 mod error_converter;
 mod events;
 mod failpoint;
+mod gateway;
+mod governance;
 mod index;
+mod load_shedding;
 mod log;
+mod mempool;
 pub mod metrics;
 mod page;
 mod response;
@@ -35,9 +41,15 @@ pub enum ApiTags {
     /// Access to events
     Events,
 
+    /// Experimental APIs, which may change without notice
+    Experimental,
+
     /// General information
     General,
 
+    /// Access to on-chain governance and staking
+    Governance,
+
     /// Access to tables
     Tables,
 
@@ -48,5 +60,6 @@ pub enum ApiTags {
 // Note: Many of these exports are just for the test-context crate, which is
 // needed outside of the API, e.g. for fh-stream.
 pub use context::Context;
+pub use gateway::ApiGateway;
 pub use response::BasicError;
 pub use runtime::{attach_poem_to_runtime, bootstrap, get_api_service};
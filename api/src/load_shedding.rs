@@ -0,0 +1,297 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{IN_FLIGHT_REQUESTS, PRIORITY_REQUESTS};
+use anyhow::ensure;
+use aptos_api_types::AptosError;
+use aptos_config::config::ApiLoadSheddingConfig;
+use poem::{
+    http::{header, HeaderValue, Method, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+use poem_openapi::payload::Json;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// The priority class of a request, from the highest to the lowest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Priority {
+    HealthCheck,
+    Submission,
+    Read,
+    /// Reads with proofs, and simulations and view functions, which run the VM
+    Heavy,
+}
+
+impl Priority {
+    fn classify(request: &Request) -> Self {
+        let path = request.uri().path();
+        if path.starts_with("/v1/-/healthy") {
+            return Priority::HealthCheck;
+        }
+        if request.method() == Method::POST {
+            match path {
+                "/v1/transactions" => return Priority::Submission,
+                "/v1/transactions/simulate" | "/v1/view" => return Priority::Heavy,
+                _ => (),
+            }
+        }
+        let with_proof = request
+            .uri()
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .any(|param| param == "with_proof=true");
+        if with_proof {
+            Priority::Heavy
+        } else {
+            Priority::Read
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Priority::HealthCheck => "health_check",
+            Priority::Submission => "submission",
+            Priority::Read => "read",
+            Priority::Heavy => "heavy",
+        }
+    }
+}
+
+/// This middleware sheds requests based on the number of requests in flight, as configured by
+/// the ApiLoadSheddingConfig. Each request is classified by priority, and rejected with a 429 if
+/// the number of requests in flight has reached the threshold of its class, so that under load
+/// the node keeps serving health checks and transaction submissions. If load shedding is disabled,
+/// it only counts requests.
+#[derive(Clone)]
+pub struct LoadShedder {
+    config: ApiLoadSheddingConfig,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl LoadShedder {
+    pub fn new(config: &ApiLoadSheddingConfig) -> anyhow::Result<Self> {
+        ensure!(
+            config.max_in_flight_heavy <= config.max_in_flight_reads
+                && config.max_in_flight_reads <= config.max_in_flight_submissions,
+            "The load shedding thresholds of lower priority requests must not be above those of \
+            higher priority requests"
+        );
+        Ok(Self {
+            config: config.clone(),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    fn threshold(&self, priority: Priority) -> Option<usize> {
+        if !self.config.enabled {
+            return None;
+        }
+        match priority {
+            Priority::HealthCheck => None,
+            Priority::Submission => Some(self.config.max_in_flight_submissions),
+            Priority::Read => Some(self.config.max_in_flight_reads),
+            Priority::Heavy => Some(self.config.max_in_flight_heavy),
+        }
+    }
+
+    /// Counts the request as in flight until the returned guard is dropped, unless it's shed.
+    /// Concurrent requests may overshoot a threshold by a few, which is fine for shedding load.
+    fn admit(&self, priority: Priority) -> Option<InFlightGuard> {
+        if let Some(threshold) = self.threshold(priority) {
+            if self.in_flight.load(Ordering::Relaxed) >= threshold {
+                return None;
+            }
+        }
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        IN_FLIGHT_REQUESTS
+            .with_label_values(&[priority.label()])
+            .inc();
+        Some(InFlightGuard {
+            in_flight: self.in_flight.clone(),
+            priority,
+        })
+    }
+
+    fn shed_response(&self) -> Response {
+        let mut response = Json(AptosError::new(
+            "The node is overloaded, please retry later".to_string(),
+        ))
+        .into_response();
+        response.set_status(StatusCode::TOO_MANY_REQUESTS);
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(self.config.retry_after_secs),
+        );
+        response
+    }
+}
+
+/// Counts a request as in flight while it's alive, including when its future is dropped.
+struct InFlightGuard {
+    in_flight: Arc<AtomicUsize>,
+    priority: Priority,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        IN_FLIGHT_REQUESTS
+            .with_label_values(&[self.priority.label()])
+            .dec();
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for LoadShedder {
+    type Output = LoadShedderEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        LoadShedderEndpoint {
+            inner: ep,
+            shedder: self.clone(),
+        }
+    }
+}
+
+/// Endpoint for LoadShedder middleware.
+pub struct LoadShedderEndpoint<E> {
+    inner: E,
+    shedder: LoadShedder,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for LoadShedderEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let priority = Priority::classify(&req);
+        let _guard = match self.shedder.admit(priority) {
+            Some(guard) => guard,
+            None => {
+                PRIORITY_REQUESTS
+                    .with_label_values(&[priority.label(), "shed"])
+                    .inc();
+                return Ok(self.shedder.shed_response());
+            }
+        };
+        PRIORITY_REQUESTS
+            .with_label_values(&[priority.label(), "admitted"])
+            .inc();
+
+        self.inner.call(req).await.map(IntoResponse::into_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoadShedder, Priority};
+    use aptos_config::config::ApiLoadSheddingConfig;
+    use poem::{
+        endpoint::make_sync,
+        http::{header, Method, StatusCode},
+        Endpoint, EndpointExt, Request,
+    };
+
+    fn request(method: Method, uri: &str) -> Request {
+        Request::builder().method(method).uri_str(uri).finish()
+    }
+
+    fn config() -> ApiLoadSheddingConfig {
+        ApiLoadSheddingConfig {
+            enabled: true,
+            max_in_flight_submissions: 3,
+            max_in_flight_reads: 2,
+            max_in_flight_heavy: 1,
+            retry_after_secs: 5,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let classify = |method, uri| Priority::classify(&request(method, uri));
+
+        assert_eq!(
+            classify(Method::GET, "/v1/-/healthy/ready"),
+            Priority::HealthCheck
+        );
+        assert_eq!(
+            classify(Method::POST, "/v1/transactions"),
+            Priority::Submission
+        );
+        assert_eq!(classify(Method::GET, "/v1/transactions"), Priority::Read);
+        assert_eq!(
+            classify(Method::POST, "/v1/transactions/encode_submission"),
+            Priority::Read
+        );
+        assert_eq!(
+            classify(
+                Method::GET,
+                "/v1/transactions/by_version/1?with_proof=false"
+            ),
+            Priority::Read
+        );
+        assert_eq!(
+            classify(Method::GET, "/v1/transactions/by_version/1?with_proof=true"),
+            Priority::Heavy
+        );
+        assert_eq!(
+            classify(Method::POST, "/v1/transactions/simulate"),
+            Priority::Heavy
+        );
+        assert_eq!(classify(Method::POST, "/v1/view"), Priority::Heavy);
+    }
+
+    #[test]
+    fn test_shed_by_priority() {
+        let shedder = LoadShedder::new(&config()).unwrap();
+
+        let heavy = shedder.admit(Priority::Heavy).unwrap();
+        assert!(shedder.admit(Priority::Heavy).is_none());
+        let read = shedder.admit(Priority::Read).unwrap();
+        assert!(shedder.admit(Priority::Read).is_none());
+        let submission = shedder.admit(Priority::Submission).unwrap();
+        assert!(shedder.admit(Priority::Submission).is_none());
+        assert!(shedder.admit(Priority::HealthCheck).is_some());
+
+        // Requests which finish make room for others
+        drop((heavy, read, submission));
+        assert!(shedder.admit(Priority::Heavy).is_some());
+
+        let mut disabled = config();
+        disabled.enabled = false;
+        let shedder = LoadShedder::new(&disabled).unwrap();
+        let _guards: Vec<_> = (0..5)
+            .map(|_| shedder.admit(Priority::Heavy).unwrap())
+            .collect();
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut invalid = config();
+        invalid.max_in_flight_heavy = 3;
+        assert!(LoadShedder::new(&invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shed_response() {
+        let shedder = LoadShedder::new(&config()).unwrap();
+        let endpoint = make_sync(|_| "ok").with(shedder.clone());
+
+        let _heavy = shedder.admit(Priority::Heavy).unwrap();
+        let response = endpoint
+            .call(request(Method::POST, "/v1/view"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "5");
+
+        let response = endpoint
+            .call(request(Method::GET, "/v1/transactions"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::accept_type::AcceptType;
+use crate::context::Context;
+use crate::failpoint::fail_point_poem;
+use crate::response::{BasicError, BasicResponse, BasicResponseStatus, BasicResult, InternalError};
+use crate::ApiTags;
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{GasPriceBand, MempoolFeeStatistics};
+use poem_openapi::OpenApi;
+
+pub struct MempoolApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl MempoolApi {
+    /// Get mempool fee statistics
+    ///
+    /// Summarizes the transactions pending in the mempool of this node by gas
+    /// unit price: how many transactions each price band holds, and how long
+    /// the transactions of each band recently waited before being committed.
+    /// Also reports how many transactions were recently evicted or rejected
+    /// because mempool was full, a sign that a higher gas unit price is needed.
+    ///
+    /// These statistics are local to the node, other nodes may see a different
+    /// mempool.
+    #[oai(
+        path = "/mempool/fee_statistics",
+        method = "get",
+        operation_id = "get_mempool_fee_statistics",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_fee_statistics(
+        &self,
+        accept_type: AcceptType,
+    ) -> BasicResult<MempoolFeeStatistics> {
+        fail_point_poem("endpoint_get_mempool_fee_statistics")?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let statistics = self
+            .context
+            .get_mempool_fee_statistics()
+            .await
+            .context("Failed to get fee statistics from mempool")
+            .map_err(BasicError::internal)?;
+
+        let statistics = MempoolFeeStatistics {
+            bands: statistics
+                .bands
+                .into_iter()
+                .map(|band| GasPriceBand {
+                    min_gas_unit_price: band.min_gas_unit_price.into(),
+                    max_gas_unit_price: band.max_gas_unit_price.map(Into::into),
+                    num_txns: band.num_txns.into(),
+                    estimated_inclusion_delay_ms: band.estimated_inclusion_delay_ms.map(Into::into),
+                })
+                .collect(),
+            num_evicted_recently: statistics.num_evicted_recently.into(),
+            num_rejected_mempool_full_recently: statistics
+                .num_rejected_mempool_full_recently
+                .into(),
+            eviction_window_secs: statistics.eviction_window_secs.into(),
+        };
+        BasicResponse::try_from_rust_value((
+            statistics,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            &accept_type,
+        ))
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_metrics_core::{register_histogram_vec, HistogramVec};
+use aptos_metrics_core::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, HistogramVec,
+    IntCounterVec, IntGaugeVec,
+};
 use once_cell::sync::Lazy;
 
 pub static HISTOGRAM: Lazy<HistogramVec> = Lazy::new(|| {
@@ -21,3 +24,30 @@ pub static RESPONSE_STATUS: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static GATEWAY_REJECTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_gateway_rejections",
+        "Number of requests rejected by the API gateway, grouped by API key name and reason",
+        &["api_key", "reason"]
+    )
+    .unwrap()
+});
+
+pub static PRIORITY_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_priority_requests",
+        "Number of requests admitted or shed by load shedding, grouped by priority class and outcome",
+        &["priority", "outcome"]
+    )
+    .unwrap()
+});
+
+pub static IN_FLIGHT_REQUESTS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_api_in_flight_requests",
+        "Number of requests in flight, grouped by priority class",
+        &["priority"]
+    )
+    .unwrap()
+});
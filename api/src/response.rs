@@ -307,9 +307,12 @@ generate_success_response!(BasicResponse, (200, Ok));
 // The error response then impls these traits for each status type they mention.
 generate_error_traits!(
     BadRequest,
+    Forbidden,
     NotFound,
     PayloadTooLarge,
+    RangeNotSatisfiable,
     Internal,
+    ServiceUnavailable,
     InsufficientStorage
 );
 
@@ -7,14 +7,15 @@ use crate::blocks::BlocksApi;
 use crate::log::middleware_log;
 use crate::set_failpoints;
 use crate::{
-    accounts::AccountsApi, basic::BasicApi, check_size::PostSizeLimit, context::Context,
-    error_converter::convert_error, events::EventsApi, index::IndexApi, state::StateApi,
-    transactions::TransactionsApi,
+    accounts::AccountsApi, basic::BasicApi, bcs_schema::BcsSchemaApi, check_size::PostSizeLimit,
+    context::Context, error_converter::convert_error, events::EventsApi, gateway::ApiGateway,
+    governance::GovernanceApi, index::IndexApi, load_shedding::LoadShedder, mempool::MempoolApi,
+    state::StateApi, transactions::TransactionsApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::NodeConfig;
 use aptos_logger::info;
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{MempoolClientSender, TransactionFilter};
 use aptos_types::chain_id::ChainId;
 use poem::{
     http::{header, Method},
@@ -28,25 +29,30 @@ use tokio::runtime::{Builder, Handle, Runtime};
 
 const VERSION: &str = include_str!("../doc/.version");
 
-/// Create a runtime and attach the Poem webserver to it.
+/// Create a runtime and attach the Poem webserver to it. Returns the API gateway too, so that its
+/// policies can be updated while the API runs. Submitted transactions are checked against
+/// `transaction_filter` before they're sent to mempool.
 pub fn bootstrap(
     config: &NodeConfig,
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
-) -> anyhow::Result<Runtime> {
+    transaction_filter: TransactionFilter,
+) -> anyhow::Result<(Runtime, ApiGateway)> {
     let runtime = Builder::new_multi_thread()
         .thread_name("api")
         .enable_all()
         .build()
         .context("[api] failed to create runtime")?;
 
-    let context = Context::new(chain_id, db, mp_sender, config.clone());
+    let context = Context::new(chain_id, db, mp_sender, config.clone(), transaction_filter);
+    let gateway =
+        ApiGateway::new(&config.api.gateway).context("Failed to build the API gateway")?;
 
-    attach_poem_to_runtime(runtime.handle(), context, config, false)
+    attach_poem_to_runtime(runtime.handle(), context, config, gateway.clone(), false)
         .context("Failed to attach poem to runtime")?;
 
-    Ok(runtime)
+    Ok((runtime, gateway))
 }
 
 // TODOs regarding spec generation:
@@ -61,9 +67,12 @@ pub fn get_api_service(
     (
         AccountsApi,
         BasicApi,
+        BcsSchemaApi,
         BlocksApi,
         EventsApi,
+        GovernanceApi,
         IndexApi,
+        MempoolApi,
         StateApi,
         TransactionsApi,
     ),
@@ -77,15 +86,24 @@ pub fn get_api_service(
         BasicApi {
             context: context.clone(),
         },
+        BcsSchemaApi {
+            context: context.clone(),
+        },
         BlocksApi {
             context: context.clone(),
         },
         EventsApi {
             context: context.clone(),
         },
+        GovernanceApi {
+            context: context.clone(),
+        },
         IndexApi {
             context: context.clone(),
         },
+        MempoolApi {
+            context: context.clone(),
+        },
         StateApi {
             context: context.clone(),
         },
@@ -112,11 +130,14 @@ pub fn attach_poem_to_runtime(
     runtime_handle: &Handle,
     context: Context,
     config: &NodeConfig,
+    gateway: ApiGateway,
     random_port: bool,
 ) -> anyhow::Result<SocketAddr> {
     let context = Arc::new(context);
 
     let size_limit = context.content_length_limit();
+    let load_shedder = LoadShedder::new(&config.api.load_shedding)
+        .context("Failed to build the API load shedder")?;
 
     let api_service = get_api_service(context.clone());
 
@@ -179,6 +200,10 @@ pub fn attach_poem_to_runtime(
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
                     ),
             )
+            // NOTE: Keep this inside the CORS middleware so that preflight
+            // requests are answered without needing an API key.
+            .with(gateway)
+            .with(load_shedder)
             .with(cors)
             .with(PostSizeLimit::new(size_limit))
             // NOTE: Make sure to keep this after all the `with` middleware.
@@ -204,6 +229,7 @@ mod tests {
 
     use aptos_api_test_context::{new_test_context, TestContext};
     use aptos_config::config::NodeConfig;
+    use aptos_mempool::TransactionFilter;
     use aptos_types::chain_id::ChainId;
 
     use super::bootstrap;
@@ -229,6 +255,7 @@ mod tests {
             ChainId::test(),
             context.db.clone(),
             context.mempool.ac_client.clone(),
+            TransactionFilter::default(),
         );
         assert!(ret.is_ok());
 
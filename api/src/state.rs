@@ -2,32 +2,127 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::accept_type::AcceptType;
+use crate::bcs_payload::Bcs;
+use crate::byte_range::ByteRange;
 use crate::context::Context;
 use crate::failpoint::fail_point_poem;
+use crate::generate_error_response;
 use crate::response::{
-    build_not_found, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
-    BasicResultWith404, InternalError, NotFoundError,
+    build_not_found, AptosErrorResponse, BadRequestError, BasicErrorWith404, BasicResponse,
+    BasicResponseStatus, BasicResultWith404, InternalError, NotFoundError,
+    RangeNotSatisfiableError,
 };
 use crate::ApiTags;
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    Address, AsConverter, IdentifierWrapper, MoveModuleBytecode, MoveStructTag, MoveValue,
-    TableItemRequest, TransactionId, U128, U64,
+    Address, AptosErrorCode, AsConverter, IdentifierWrapper, JsonPathFilter, MoveModuleAbi,
+    MoveModuleBytecode, MoveStructTag, MoveValue, RawStateValueRequest, TableItemRequest,
+    TransactionId, ViewRequest, U128, U64,
 };
-use aptos_api_types::{LedgerInfo, MoveResource};
+use aptos_api_types::{HexEncodedBytes, LedgerInfo, MoveResource};
 use aptos_state_view::StateView;
 use aptos_types::access_path::AccessPath;
 use aptos_types::state_store::state_key::StateKey;
 use aptos_types::state_store::table::TableHandle;
 use aptos_vm::data_cache::AsMoveResolver;
-use move_deps::move_core_types::language_storage::{ModuleId, ResourceKey, StructTag};
-use poem_openapi::param::Query;
+use aptos_vm::AptosVM;
+use framework::natives::code::{PackageBundle, PackageRegistry};
+use move_deps::move_binary_format::CompiledModule;
+use move_deps::move_core_types::identifier::Identifier;
+use move_deps::move_core_types::language_storage::{ModuleId, ResourceKey, StructTag, TypeTag};
+use poem_openapi::param::{Header, Query};
 use poem_openapi::payload::Json;
-use poem_openapi::{param::Path, OpenApi};
-use std::convert::TryInto;
+use poem_openapi::{param::Path, ApiResponse, OpenApi};
+use std::convert::{TryFrom, TryInto};
 use std::sync::Arc;
 use storage_interface::state_view::DbStateView;
 
+generate_error_response!(
+    PackageBundleError,
+    (400, BadRequest),
+    (404, NotFound),
+    (416, RangeNotSatisfiable),
+    (500, Internal)
+);
+
+/// Response for the package bundle endpoint. It is always BCS, and when a
+/// `Range` is requested only that part of the bundle is returned.
+#[derive(ApiResponse)]
+pub enum PackageBundleResponse {
+    #[oai(status = 200)]
+    Ok(
+        Bcs,
+        #[oai(header = "Accept-Ranges")] String,
+        #[oai(header = "X-Aptos-Chain-Id")] u16,
+        #[oai(header = "X-Aptos-Ledger-Version")] u64,
+        #[oai(header = "X-Aptos-Ledger-Oldest-Version")] u64,
+        #[oai(header = "X-Aptos-Ledger-TimestampUsec")] u64,
+        #[oai(header = "X-Aptos-Epoch")] u64,
+        #[oai(header = "X-Aptos-Block-Height")] u64,
+        #[oai(header = "X-Aptos-Oldest-Block-Height")] u64,
+    ),
+    #[oai(status = 206)]
+    PartialContent(
+        Bcs,
+        #[oai(header = "Accept-Ranges")] String,
+        #[oai(header = "Content-Range")] String,
+        #[oai(header = "X-Aptos-Chain-Id")] u16,
+        #[oai(header = "X-Aptos-Ledger-Version")] u64,
+        #[oai(header = "X-Aptos-Ledger-Oldest-Version")] u64,
+        #[oai(header = "X-Aptos-Ledger-TimestampUsec")] u64,
+        #[oai(header = "X-Aptos-Epoch")] u64,
+        #[oai(header = "X-Aptos-Block-Height")] u64,
+        #[oai(header = "X-Aptos-Oldest-Block-Height")] u64,
+    ),
+}
+
+impl PackageBundleResponse {
+    fn new(
+        bytes: Vec<u8>,
+        range: Option<ByteRange>,
+        ledger_info: &LedgerInfo,
+    ) -> Result<Self, PackageBundleError> {
+        let accept_ranges = "bytes".to_string();
+        let range = match range {
+            Some(range) => range,
+            None => {
+                return Ok(PackageBundleResponse::Ok(
+                    Bcs(bytes),
+                    accept_ranges,
+                    ledger_info.chain_id as u16,
+                    ledger_info.ledger_version.into(),
+                    ledger_info.oldest_ledger_version.into(),
+                    ledger_info.ledger_timestamp.into(),
+                    ledger_info.epoch.into(),
+                    ledger_info.block_height.into(),
+                    ledger_info.oldest_block_height.into(),
+                ))
+            }
+        };
+
+        let len = bytes.len() as u64;
+        let range = range.resolve(len).ok_or_else(|| {
+            PackageBundleError::range_not_satisfiable_str(&format!(
+                "Requested range {:?} is not satisfiable, the bundle is {} bytes",
+                range, len
+            ))
+        })?;
+        let content_range = ByteRange::content_range(&range, len);
+        Ok(PackageBundleResponse::PartialContent(
+            Bcs(bytes[range.start as usize..range.end as usize].to_vec()),
+            accept_ranges,
+            content_range,
+            ledger_info.chain_id as u16,
+            ledger_info.ledger_version.into(),
+            ledger_info.oldest_ledger_version.into(),
+            ledger_info.ledger_timestamp.into(),
+            ledger_info.epoch.into(),
+            ledger_info.block_height.into(),
+            ledger_info.oldest_block_height.into(),
+        ))
+    }
+}
+
 pub struct StateApi {
     pub context: Arc<Context>,
 }
@@ -43,6 +138,21 @@ impl StateApi {
     ///
     /// The Aptos nodes prune account state history, via a configurable time window (link).
     /// If the requested data has been pruned, the server responds with a 404.
+    ///
+    /// To only return part of the resource, pass a comma separated list of JSON
+    /// paths rooted at the resource data as the `filter` query parameter, e.g.
+    /// `filter=$.coin.value`. Supported syntax is `$` for the root, `.field` or
+    /// `['field']` for an object field, `[3]` for an array element and `.*` or
+    /// `[*]` for every field or element. A malformed filter gets a 400.
+    /// Filtering is only supported for JSON responses.
+    ///
+    /// If `with_proof` is set, the response includes a proof of the resource
+    /// against the latest ledger info, which light clients can verify. Proofs
+    /// are only available at state checkpoint versions; if no ledger version
+    /// is specified the latest state checkpoint is used.
+    ///
+    /// Resources stored in resource groups are found by their own type. They
+    /// have no proof of their own, so they are not found if `with_proof` is set.
     #[oai(
         path = "/accounts/:address/resource/:resource_type",
         method = "get",
@@ -55,9 +165,18 @@ impl StateApi {
         address: Path<Address>,
         resource_type: Path<MoveStructTag>,
         ledger_version: Query<Option<U64>>,
+        filter: Query<Option<String>>,
+        with_proof: Query<Option<bool>>,
     ) -> BasicResultWith404<MoveResource> {
         fail_point_poem("endpoint_get_account_resource")?;
-        self.resource(&accept_type, address.0, resource_type.0, ledger_version.0)
+        self.resource(
+            &accept_type,
+            address.0,
+            resource_type.0,
+            ledger_version.0,
+            filter.0,
+            with_proof.0.unwrap_or_default(),
+        )
     }
 
     /// Get specific account module
@@ -86,6 +205,65 @@ impl StateApi {
         self.module(&accept_type, address.0, module_name.0, ledger_version.0)
     }
 
+    /// Get account module ABI
+    ///
+    /// This endpoint returns the ABI of the module with a specific name residing
+    /// at a given account at a specified ledger version (AKA transaction version):
+    /// its entry functions, view functions and structs, with the argument and
+    /// return types of each function as deployed on chain. This can be used to
+    /// generate typed clients for the module. If the ledger version is not
+    /// specified in the request, the latest ledger version is used.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window (link).
+    /// If the requested data has been pruned, the server responds with a 404.
+    #[oai(
+        path = "/accounts/:address/module/:module_name/abi",
+        method = "get",
+        operation_id = "get_account_module_abi",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn get_account_module_abi(
+        &self,
+        accept_type: AcceptType,
+        address: Path<Address>,
+        module_name: Path<IdentifierWrapper>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<MoveModuleAbi> {
+        fail_point_poem("endpoint_get_account_module_abi")?;
+        self.module_abi(&accept_type, address.0, module_name.0, ledger_version.0)
+    }
+
+    /// Get account package bundle
+    ///
+    /// This endpoint returns the package with a specific name published at a
+    /// given account, at a specified ledger version (AKA transaction version).
+    /// The response is a BCS encoded `PackageBundle`: the on-chain package
+    /// metadata together with the bytecode of every module in the package. If
+    /// the ledger version is not specified in the request, the latest ledger
+    /// version is used.
+    ///
+    /// Large packages can be downloaded in chunks by passing a single byte range
+    /// in the `Range` header, e.g. `Range: bytes=0-1048575`, in which case the
+    /// server responds with a 206 and a `Content-Range` header. To make sure all
+    /// chunks come from the same bundle, pin the `ledger_version` to the value of
+    /// the `X-Aptos-Ledger-Version` header of the first response.
+    #[oai(
+        path = "/accounts/:address/package/:package_name/bundle",
+        method = "get",
+        operation_id = "get_account_package_bundle",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn get_account_package_bundle(
+        &self,
+        address: Path<Address>,
+        package_name: Path<String>,
+        ledger_version: Query<Option<U64>>,
+        #[oai(name = "Range")] range: Header<Option<String>>,
+    ) -> poem::Result<PackageBundleResponse, PackageBundleError> {
+        fail_point_poem("endpoint_get_account_package_bundle")?;
+        self.package_bundle(address.0, package_name.0, ledger_version.0, range.0)
+    }
+
     /// Get table item
     ///
     /// Get a table item from the table identified by {table_handle} in the
@@ -116,6 +294,59 @@ impl StateApi {
             ledger_version.0,
         )
     }
+    /// Get raw state value
+    ///
+    /// Get the raw bytes of the state value stored under the BCS encoded
+    /// `StateKey` given in the request body, at a specified ledger version (AKA
+    /// transaction version). These are the bytes the VM reads when executing
+    /// transactions, so this can be used to replay transactions locally. If the
+    /// ledger version is not specified in the request, the latest ledger version
+    /// is used.
+    ///
+    /// With a BCS `Accept` header the bytes are returned as is, otherwise they
+    /// are returned hex encoded.
+    #[oai(
+        path = "/experimental/state_values/raw",
+        method = "post",
+        operation_id = "get_raw_state_value",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_raw_state_value(
+        &self,
+        accept_type: AcceptType,
+        request: Json<RawStateValueRequest>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<HexEncodedBytes> {
+        fail_point_poem("endpoint_get_raw_state_value")?;
+        self.raw_state_value(&accept_type, request.0, ledger_version.0)
+    }
+
+    /// Call a view function
+    ///
+    /// Call a public function which returns values, without submitting a
+    /// transaction, at a specified ledger version (AKA transaction version).
+    /// The arguments are given BCS encoded, and the return values are returned
+    /// BCS encoded, in the order the function returns them. Functions taking a
+    /// signer can't be called. If the ledger version is not specified in the
+    /// request, the latest ledger version is used.
+    ///
+    /// With a BCS `Accept` header the return values are returned as a BCS
+    /// encoded vector of byte vectors, otherwise they are returned hex encoded.
+    #[oai(
+        path = "/view",
+        method = "post",
+        operation_id = "view",
+        tag = "ApiTags::General"
+    )]
+    async fn view(
+        &self,
+        accept_type: AcceptType,
+        request: Json<ViewRequest>,
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<HexEncodedBytes>> {
+        fail_point_poem("endpoint_view")?;
+        self.view_function(&accept_type, request.0, ledger_version.0)
+    }
 }
 
 impl StateApi {
@@ -149,7 +380,37 @@ impl StateApi {
         address: Address,
         resource_type: MoveStructTag,
         ledger_version: Option<U64>,
+        filter: Option<String>,
+        with_proof: bool,
     ) -> BasicResultWith404<MoveResource> {
+        if filter.is_some() && accept_type == &AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_str(
+                "Filtering resources is only supported for JSON responses",
+            ));
+        }
+        let filter = filter
+            .map(|filter| {
+                filter
+                    .parse::<JsonPathFilter>()
+                    .context(format!("Failed to parse filter {:?}", filter))
+            })
+            .transpose()
+            .map_err(BasicErrorWith404::bad_request)?;
+        if with_proof && accept_type == &AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_str(
+                "Proofs are only supported for JSON responses",
+            ));
+        }
+        let ledger_version = match ledger_version {
+            None if with_proof => Some(
+                self.context
+                    .get_latest_state_checkpoint_version()
+                    .context("Failed to get latest state checkpoint version")
+                    .map_err(BasicErrorWith404::internal)?
+                    .into(),
+            ),
+            ledger_version => ledger_version,
+        };
         let resource_type: StructTag = resource_type
             .try_into()
             .context("Failed to parse given resource type")
@@ -158,11 +419,19 @@ impl StateApi {
         let access_path = AccessPath::resource_access_path(resource_key.clone());
         let state_key = StateKey::AccessPath(access_path);
         let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
-        let bytes = state_view
+        let bytes = match state_view
             .get_state_value(&state_key)
             .context(format!("Failed to query DB to check for {:?}", state_key))
             .map_err(BasicErrorWith404::internal)?
-            .ok_or_else(|| build_not_found("Resource", resource_key, ledger_version))?;
+        {
+            Some(bytes) => bytes,
+            // Members of resource groups have no state key of their own, so they can't be
+            // proven individually
+            None if !with_proof => self
+                .resource_group_member(address, &resource_type, ledger_version)?
+                .ok_or_else(|| build_not_found("Resource", resource_key, ledger_version))?,
+            None => return Err(build_not_found("Resource", resource_key, ledger_version)),
+        };
 
         let resource = state_view
             .as_move_resolver()
@@ -170,6 +439,27 @@ impl StateApi {
             .try_into_resource(&resource_type, &bytes)
             .context("Failed to deserialize resource data retrieved from DB")
             .map_err(BasicErrorWith404::internal)?;
+        let mut resource = match filter {
+            Some(filter) => resource
+                .filter(&filter)
+                .context("Failed to apply filter to resource")
+                .map_err(BasicErrorWith404::internal)?,
+            None => resource,
+        };
+        if with_proof {
+            let proof = self
+                .context
+                .get_state_value_proof(&state_key, ledger_version)
+                .context(format!("Failed to prove {:?}", state_key))
+                .map_err(BasicErrorWith404::internal)?
+                .ok_or_else(|| {
+                    BasicErrorWith404::bad_request_str(&format!(
+                        "Proofs are only available at state checkpoint versions, version {} is not a checkpoint",
+                        ledger_version
+                    ))
+                })?;
+            resource.proof = Some(proof);
+        }
 
         BasicResponse::try_from_rust_value((
             resource,
@@ -179,6 +469,31 @@ impl StateApi {
         ))
     }
 
+    /// Looks for `resource_type` in the resource groups of `address`
+    fn resource_group_member(
+        &self,
+        address: Address,
+        resource_type: &StructTag,
+        ledger_version: u64,
+    ) -> Result<Option<Vec<u8>>, BasicErrorWith404> {
+        let account_state = match self
+            .context
+            .get_account_state(address.into(), ledger_version)
+            .context("Failed to read account state from DB")
+            .map_err(BasicErrorWith404::internal)?
+        {
+            Some(account_state) => account_state,
+            None => return Ok(None),
+        };
+        Ok(account_state
+            .get_resource_group_members()
+            .context("Failed to decode resource groups from DB")
+            .map_err(BasicErrorWith404::internal)?
+            .into_iter()
+            .find(|(tag, _bytes)| tag == resource_type)
+            .map(|(_tag, bytes)| bytes))
+    }
+
     pub fn module(
         &self,
         accept_type: &AcceptType,
@@ -209,6 +524,93 @@ impl StateApi {
         ))
     }
 
+    pub fn module_abi(
+        &self,
+        accept_type: &AcceptType,
+        address: Address,
+        name: IdentifierWrapper,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<MoveModuleAbi> {
+        let module_id = ModuleId::new(address.into(), name.into());
+        let access_path = AccessPath::code_access_path(module_id.clone());
+        let state_key = StateKey::AccessPath(access_path);
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let bytes = state_view
+            .get_state_value(&state_key)
+            .context(format!("Failed to query DB to check for {:?}", state_key))
+            .map_err(BasicErrorWith404::internal)?
+            .ok_or_else(|| build_not_found("Module", module_id, ledger_version))?;
+
+        let abi: MoveModuleAbi = CompiledModule::deserialize(&bytes)
+            .context("Failed to deserialize move module retrieved from storage")
+            .map_err(BasicErrorWith404::internal)?
+            .into();
+
+        BasicResponse::try_from_rust_value((
+            abi,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    pub fn package_bundle(
+        &self,
+        address: Address,
+        package_name: String,
+        ledger_version: Option<U64>,
+        range: Option<String>,
+    ) -> poem::Result<PackageBundleResponse, PackageBundleError> {
+        let range = range
+            .map(|range| range.parse::<ByteRange>())
+            .transpose()
+            .context("Failed to parse Range header")
+            .map_err(PackageBundleError::bad_request)?;
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+
+        let registry_tag: StructTag = "0x1::code::PackageRegistry"
+            .parse()
+            .context("Failed to parse PackageRegistry type")
+            .map_err(PackageBundleError::internal)?;
+        let registry_key = ResourceKey::new(address.into(), registry_tag);
+        let state_key = StateKey::AccessPath(AccessPath::resource_access_path(registry_key));
+        let bytes = state_view
+            .get_state_value(&state_key)
+            .context(format!("Failed to query DB to check for {:?}", state_key))
+            .map_err(PackageBundleError::internal)?
+            .ok_or_else(|| build_not_found("Package", &package_name, ledger_version))?;
+        let registry: PackageRegistry = bcs::from_bytes(&bytes)
+            .context("Failed to deserialize package registry retrieved from DB")
+            .map_err(PackageBundleError::internal)?;
+        let metadata = registry
+            .packages
+            .into_iter()
+            .find(|package| package.name == package_name)
+            .ok_or_else(|| build_not_found("Package", &package_name, ledger_version))?;
+
+        let modules = metadata
+            .modules
+            .iter()
+            .map(|module| {
+                let name = Identifier::new(module.name.as_str())?;
+                let module_id = ModuleId::new(address.into(), name);
+                let state_key = StateKey::AccessPath(AccessPath::code_access_path(module_id));
+                state_view
+                    .get_state_value(&state_key)?
+                    .ok_or_else(|| anyhow::format_err!("Module {} not found", module.name))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .context(format!("Failed to get modules of package {}", package_name))
+            .map_err(PackageBundleError::internal)?;
+
+        let bundle = bcs::to_bytes(&PackageBundle { metadata, modules })
+            .context("Failed to serialize package bundle")
+            .map_err(|e| {
+                PackageBundleError::internal(e).error_code(AptosErrorCode::BcsSerializationError)
+            })?;
+        PackageBundleResponse::new(bundle, range, &ledger_info)
+    }
+
     pub fn table_item(
         &self,
         accept_type: &AcceptType,
@@ -263,4 +665,86 @@ impl StateApi {
             accept_type,
         ))
     }
+
+    pub fn raw_state_value(
+        &self,
+        accept_type: &AcceptType,
+        request: RawStateValueRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<HexEncodedBytes> {
+        let state_key: StateKey = bcs::from_bytes(request.key.inner())
+            .context("Failed to deserialize given state key")
+            .map_err(BasicErrorWith404::bad_request)?;
+        let (ledger_info, ledger_version, state_view) = self.preprocess_request(ledger_version)?;
+        let bytes = state_view
+            .get_state_value(&state_key)
+            .context(format!("Failed to query DB to check for {:?}", state_key))
+            .map_err(BasicErrorWith404::internal)?
+            .ok_or_else(|| build_not_found("State value", request.key, ledger_version))?;
+
+        match accept_type {
+            AcceptType::Bcs => Ok(BasicResponse::from((
+                Bcs(bytes),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            ))),
+            AcceptType::Json => Ok(BasicResponse::from((
+                Json(HexEncodedBytes::from(bytes)),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            ))),
+        }
+    }
+
+    pub fn view_function(
+        &self,
+        accept_type: &AcceptType,
+        request: ViewRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<Vec<HexEncodedBytes>> {
+        let type_args = request
+            .type_arguments
+            .into_iter()
+            .map(TypeTag::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .context("Failed to parse given type arguments")
+            .map_err(BasicErrorWith404::bad_request)?;
+        let arguments = request
+            .arguments
+            .into_iter()
+            .map(|argument| argument.into())
+            .collect();
+        let (ledger_info, _, state_view) = self.preprocess_request(ledger_version)?;
+        let module_id: ModuleId = request.function.module.clone().into();
+        let return_values = AptosVM::execute_view_function(
+            &state_view,
+            &module_id,
+            request.function.name.0.as_ident_str(),
+            type_args,
+            arguments,
+            self.context.max_view_gas(),
+        )
+        .context(format!("Failed to call {}", request.function))
+        .map_err(BasicErrorWith404::bad_request)?;
+
+        match accept_type {
+            AcceptType::Bcs => Ok(BasicResponse::from((
+                Bcs(bcs::to_bytes(&return_values)
+                    .context("Failed to serialize return values")
+                    .map_err(BasicErrorWith404::internal)?),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            ))),
+            AcceptType::Json => Ok(BasicResponse::from((
+                Json(
+                    return_values
+                        .into_iter()
+                        .map(HexEncodedBytes::from)
+                        .collect(),
+                ),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            ))),
+        }
+    }
 }
@@ -0,0 +1,90 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::current_function_name;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_core_type_bcs_schema() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .get("/bcs_schema/core_types/SignedTransaction")
+        .await;
+    assert_eq!(resp["root"], json!({"TYPENAME": "SignedTransaction"}));
+
+    let types = resp["types"].as_object().unwrap();
+    for type_name in [
+        "SignedTransaction",
+        "RawTransaction",
+        "TransactionPayload",
+        "TransactionAuthenticator",
+        "AccountAddress",
+    ] {
+        assert!(types.contains_key(type_name), "{}", type_name);
+    }
+    // Types which the signed transaction isn't made of are left out
+    assert!(!types.contains_key("ContractEvent"));
+
+    let payload_variants = types["TransactionPayload"]["ENUM"].as_object().unwrap();
+    assert_eq!(payload_variants.len(), 4);
+    assert!(payload_variants["3"]
+        .as_object()
+        .unwrap()
+        .contains_key("OrderlessEntryFunction"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_core_type_bcs_schema_not_found() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(404)
+        .get("/bcs_schema/core_types/NotAType")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_move_type_bcs_schema() {
+    let context = new_test_context(current_function_name!());
+    let coin_store = "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>";
+    let resp = context
+        .get(&format!(
+            "/bcs_schema/move_types/{}",
+            utf8_percent_encode(coin_store, NON_ALPHANUMERIC)
+        ))
+        .await;
+    assert_eq!(resp["root"], json!({ "TYPENAME": coin_store }));
+
+    let types = resp["types"].as_object().unwrap();
+    assert_eq!(
+        types["0x1::coin::Coin<0x1::aptos_coin::AptosCoin>"],
+        json!({"STRUCT": [{"value": "U64"}]})
+    );
+    assert_eq!(
+        types["AccountAddress"],
+        json!({"NEWTYPESTRUCT": {"TUPLEARRAY": {"CONTENT": "U8", "SIZE": 32}}})
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_move_type_bcs_schema_of_vector() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .get(&format!(
+            "/bcs_schema/move_types/{}",
+            utf8_percent_encode("vector<u64>", NON_ALPHANUMERIC)
+        ))
+        .await;
+    assert_eq!(resp["root"], json!({"SEQ": "U64"}));
+    assert_eq!(resp["types"], json!({}));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_move_type_bcs_schema_of_unknown_struct() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(400)
+        .get("/bcs_schema/move_types/0x1::coin::NotAStruct")
+        .await;
+}
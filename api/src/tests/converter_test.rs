@@ -3,10 +3,17 @@
 
 use super::new_test_context;
 use aptos_api_test_context::current_function_name;
-use aptos_api_types::{new_vm_utf8_string, AsConverter, MoveConverter, MoveType};
+use aptos_api_types::{new_vm_utf8_string, AsConverter, MoveConverter, MoveType, WriteSetChange};
+use aptos_types::{
+    access_path::AccessPath,
+    resource_group::{encode_resource_group, object_group_tag, ResourceGroup},
+    state_store::state_key::StateKey,
+    write_set::WriteOp,
+};
 use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::MoveResolverExt};
 use move_deps::move_core_types::{
     account_address::AccountAddress,
+    parser::parse_struct_tag,
     value::{MoveStruct, MoveValue as VmMoveValue},
 };
 use serde::Serialize;
@@ -74,3 +81,53 @@ fn assert_value_conversion<'r, R: MoveResolverExt, V: Serialize>(
     let json_value_back = serde_json::to_value(move_value_back).unwrap();
     assert_eq!(json_value_back, json!(json_value));
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_resource_group_write_set_changes() {
+    let context = new_test_context(current_function_name!());
+    let address = AccountAddress::from_hex_literal("0xA550C18").unwrap();
+
+    let state_view = context.latest_state_view();
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter(context.db);
+
+    let id_tag = parse_struct_tag("0x1::guid::ID").unwrap();
+    let guid_tag = parse_struct_tag("0x1::guid::GUID").unwrap();
+    let id = bcs::to_bytes(&(1u64, address)).unwrap();
+    let mut group = ResourceGroup::new();
+    group.insert(id_tag, id.clone());
+    group.insert(guid_tag, id);
+    let state_key = StateKey::AccessPath(AccessPath::resource_group_access_path(
+        address,
+        object_group_tag(),
+    ));
+
+    let changes = converter
+        .try_into_write_set_changes(
+            state_key.clone(),
+            WriteOp::Creation(encode_resource_group(&group).unwrap()),
+        )
+        .unwrap();
+    let resources: Vec<_> = changes
+        .into_iter()
+        .map(|change| match change {
+            WriteSetChange::WriteResource(write) => serde_json::to_value(write.data).unwrap(),
+            change => panic!("Unexpected change {:?}", change),
+        })
+        .collect();
+    assert_eq!(
+        resources,
+        vec![
+            json!({"type": "0x1::guid::GUID", "data": {"id": {"addr": "0xa550c18", "creation_num": "1"}}}),
+            json!({"type": "0x1::guid::ID", "data": {"addr": "0xa550c18", "creation_num": "1"}}),
+        ]
+    );
+
+    let changes = converter
+        .try_into_write_set_changes(state_key, WriteOp::Deletion)
+        .unwrap();
+    assert!(matches!(
+        changes.as_slice(),
+        [WriteSetChange::DeleteResource(delete)] if delete.resource.to_string() == "0x1::object::ObjectGroup"
+    ));
+}
@@ -131,3 +131,71 @@ async fn test_get_events_by_struct_type_has_generic_type_parameter() {
     let resp = context.expect_status_code(404).get(path.as_str()).await;
     context.check_golden_output(resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    // The block committed above starts at version 1.
+    let resp = context
+        .get("/events/by_type/0x1::block::NewBlockEvent?start_version=1")
+        .await;
+    let events = resp.as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["version"], "1");
+    assert_eq!(events[0]["type"], "0x1::block::NewBlockEvent");
+
+    let resp = context
+        .get("/events/by_type/0x1::block::NewBlockEvent?start_version=1&end_version=1")
+        .await;
+    assert_eq!(resp.as_array().unwrap().len(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type_filter_by_timestamp() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let ledger_timestamp = context.get("/").await["ledger_timestamp"]
+        .as_str()
+        .unwrap()
+        .parse::<u64>()
+        .unwrap();
+    let resp = context
+        .get(
+            format!(
+                "/events/by_type/0x1::block::NewBlockEvent?start_timestamp={}",
+                ledger_timestamp
+            )
+            .as_str(),
+        )
+        .await;
+    let events = resp.as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["version"], "1");
+
+    let resp = context
+        .get(
+            format!(
+                "/events/by_type/0x1::block::NewBlockEvent?start_timestamp={}",
+                ledger_timestamp + 1
+            )
+            .as_str(),
+        )
+        .await;
+    assert_eq!(resp.as_array().unwrap().len(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_invalid_type() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(400)
+        .get("/events/by_type/0x1::block")
+        .await;
+}
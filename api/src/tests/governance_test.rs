@@ -0,0 +1,66 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::current_function_name;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_governance_config() {
+    let context = new_test_context(current_function_name!());
+    let resp = context.get("/governance/config").await;
+    assert!(resp["min_voting_threshold"].is_string());
+    assert!(resp["required_proposer_stake"].is_string());
+    assert!(resp["voting_duration_secs"].is_string());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_governance_proposals_without_proposals() {
+    let context = new_test_context(current_function_name!());
+    let resp = context.get("/governance/proposals").await;
+    assert_eq!(resp, json!([]));
+
+    context
+        .expect_status_code(404)
+        .get("/governance/proposals/0")
+        .await;
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "/governance/proposals/0/votes/{}",
+            context.validator_owner.to_hex_literal()
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_governance_proposals_with_zero_limit() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(400)
+        .get("/governance/proposals?limit=0")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_stake_pool() {
+    let mut context = new_test_context(current_function_name!());
+    let pool_address = context.validator_owner.to_hex_literal();
+    let resp = context
+        .get(&format!("/governance/stake_pools/{}", pool_address))
+        .await;
+    assert_eq!(resp["pool_address"], pool_address);
+    assert_eq!(resp["validator_state"], "active");
+    assert_ne!(resp["active"], "0");
+    assert_eq!(resp["voting_power"], resp["active"]);
+
+    // An account without a stake pool
+    let account = context.gen_account();
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "/governance/stake_pools/{}",
+            account.address().to_hex_literal()
+        ))
+        .await;
+}
@@ -4,6 +4,7 @@
 use super::new_test_context;
 use aptos_api_test_context::current_function_name;
 use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_index() {
@@ -42,6 +43,49 @@ async fn test_health_check() {
     assert_eq!(resp.status(), 200)
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_health_check_live() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/-/healthy/live"),
+        )
+        .await;
+    assert_eq!(resp.status(), 200)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_health_check_ready() {
+    let mut context = new_test_context(current_function_name!());
+
+    // The ledger of the test context starts at timestamp 0, far behind the wall clock.
+    let resp = context
+        .expect_status_code(503)
+        .get("/-/healthy/ready")
+        .await;
+    assert!(resp["message"].as_str().unwrap().contains("state_sync"));
+
+    let now_usecs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_micros() as u64;
+    context.set_fake_time_usecs(now_usecs);
+    context.commit_block(&[]).await;
+
+    let resp = context.get("/-/healthy/ready").await;
+    let components = resp["components"].as_array().unwrap();
+    assert_eq!(components.len(), 5);
+    for component in ["storage", "state_sync", "api", "mempool"] {
+        let health = components
+            .iter()
+            .find(|health| health["component"] == component)
+            .unwrap();
+        assert_eq!(health["status"], "healthy", "{}", health);
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_openapi_spec() {
     let context = new_test_context(current_function_name!());
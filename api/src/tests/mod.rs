@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod accounts_test;
+mod bcs_schema_test;
 mod blocks_test;
 mod converter_test;
 mod events_test;
+mod governance_test;
 mod index_test;
 mod invalid_post_request_test;
 mod state_test;
@@ -17,3 +19,7 @@ use aptos_api_test_context::{new_test_context as super_new_test_context, TestCon
 fn new_test_context(test_name: String) -> TestContext {
     super_new_test_context(test_name, false)
 }
+
+fn new_test_context_with_indexer(test_name: String) -> TestContext {
+    super_new_test_context(test_name, true)
+}
@@ -4,7 +4,21 @@
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
 use aptos_sdk::types::LocalAccount;
-use move_deps::{move_core_types::account_address::AccountAddress, move_package::BuildConfig};
+use aptos_types::{
+    access_path::AccessPath,
+    ledger_info::LedgerInfoWithSignatures,
+    state_store::{state_key::StateKey, state_value::StateValueWithProof},
+};
+use framework::natives::code::PackageBundle;
+use move_deps::{
+    move_core_types::{
+        account_address::AccountAddress,
+        identifier::Identifier,
+        language_storage::{ModuleId, ResourceKey},
+    },
+    move_package::BuildConfig,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde::Serialize;
 use serde_json::{json, Value};
 use std::{convert::TryInto, path::PathBuf};
@@ -93,6 +107,82 @@ async fn test_get_account_resource_with_version_too_large() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_with_filter() {
+    let context = new_test_context(current_function_name!());
+    let resource = context
+        .get(&get_account_resource("0xA550C18", "0x1::account::Account"))
+        .await;
+    let resp = context
+        .get(&get_account_resource_with_filter(
+            "0xA550C18",
+            "0x1::account::Account",
+            "$.sequence_number,$.coin_register_events.counter",
+        ))
+        .await;
+    assert_eq!(resp["type"], resource["type"]);
+    assert_eq!(
+        resp["data"],
+        json!({
+            "sequence_number": resource["data"]["sequence_number"],
+            "coin_register_events": {
+                "counter": resource["data"]["coin_register_events"]["counter"],
+            },
+        })
+    );
+
+    let resp = context
+        .get(&get_account_resource_with_filter(
+            "0xA550C18",
+            "0x1::account::Account",
+            "$.does_not_exist",
+        ))
+        .await;
+    assert_eq!(resp["data"], json!({}));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_with_invalid_filter() {
+    let context = new_test_context(current_function_name!());
+    for invalid_filter in ["coin", "$.", "$[x]"] {
+        let resp = context
+            .expect_status_code(400)
+            .get(&get_account_resource_with_filter(
+                "0xA550C18",
+                "0x1::account::Account",
+                invalid_filter,
+            ))
+            .await;
+        let message = resp["message"].as_str().unwrap();
+        assert!(message.contains("Failed to parse filter"), "{}", message);
+        assert!(message.contains("JSON path"), "{}", message);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_with_proof() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .get(&format!(
+            "{}?with_proof=true",
+            get_account_resource("0xA550C18", "0x1::account::Account")
+        ))
+        .await;
+
+    let version: u64 = resp["proof"]["version"].as_str().unwrap().parse().unwrap();
+    let proof: StateValueWithProof = bcs::from_bytes(&decode_hex(&resp["proof"]["proof"])).unwrap();
+    let ledger_info: LedgerInfoWithSignatures =
+        bcs::from_bytes(&decode_hex(&resp["proof"]["ledger_info"])).unwrap();
+    let state_key = StateKey::AccessPath(AccessPath::resource_access_path(ResourceKey::new(
+        AccountAddress::from_hex_literal("0xA550C18").unwrap(),
+        "0x1::account::Account".parse().unwrap(),
+    )));
+    proof
+        .verify(ledger_info.ledger_info(), version, &state_key)
+        .unwrap();
+    assert!(proof.value.is_some());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_module() {
     let mut context = new_test_context(current_function_name!());
@@ -100,6 +190,123 @@ async fn test_get_account_module() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_module_abi() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .get(&format!("{}/abi", get_account_module("0x1", "coin")))
+        .await;
+    assert_eq!(resp["address"], "0x1");
+    assert_eq!(resp["name"], "coin");
+
+    let names = |functions: &Value| -> Vec<String> {
+        functions
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap().to_owned())
+            .collect()
+    };
+    let entry_functions = names(&resp["entry_functions"]);
+    assert!(entry_functions.contains(&"transfer".to_owned()));
+    assert!(!entry_functions.contains(&"balance".to_owned()));
+    let view_functions = names(&resp["view_functions"]);
+    assert!(view_functions.contains(&"balance".to_owned()));
+    assert!(!view_functions.contains(&"transfer".to_owned()));
+
+    let transfer = resp["entry_functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["name"] == "transfer")
+        .unwrap();
+    assert_eq!(transfer["is_entry"], true);
+    assert_eq!(transfer["params"], json!(["&signer", "address", "u64"]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_module_abi_not_found() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "{}/abi",
+            get_account_module("0x1", "no_such_module")
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_raw_state_value() {
+    let context = new_test_context(current_function_name!());
+    let raw_state_value = |module: &str| {
+        let state_key = StateKey::AccessPath(AccessPath::code_access_path(ModuleId::new(
+            AccountAddress::ONE,
+            Identifier::new(module).unwrap(),
+        )));
+        json!({ "key": format!("0x{}", hex::encode(bcs::to_bytes(&state_key).unwrap())) })
+    };
+
+    let resp = context
+        .post("/experimental/state_values/raw", raw_state_value("guid"))
+        .await;
+    let module = context.get(&get_account_module("0x1", "guid")).await;
+    assert_eq!(decode_hex(&resp), decode_hex(&module["bytecode"]));
+
+    context
+        .expect_status_code(404)
+        .post(
+            "/experimental/state_values/raw",
+            raw_state_value("no_such_module"),
+        )
+        .await;
+    context
+        .expect_status_code(400)
+        .post("/experimental/state_values/raw", json!({ "key": "0x00" }))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view() {
+    let context = new_test_context(current_function_name!());
+    let view = |function: &str, arguments: Vec<String>| {
+        json!({
+            "function": function,
+            "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+            "arguments": arguments,
+        })
+    };
+
+    let resp = context
+        .post("/view", view("0x1::coin::decimals", vec![]))
+        .await;
+    let return_values = resp.as_array().unwrap();
+    assert_eq!(return_values.len(), 1);
+    assert_eq!(decode_hex(&return_values[0]), bcs::to_bytes(&8u8).unwrap());
+
+    let address = format!(
+        "0x{}",
+        hex::encode(bcs::to_bytes(&AccountAddress::ONE).unwrap())
+    );
+    let resp = context
+        .post(
+            "/view",
+            view("0x1::coin::is_account_registered", vec![address]),
+        )
+        .await;
+    assert_eq!(decode_hex(&resp[0]), bcs::to_bytes(&false).unwrap());
+
+    // Entry functions, and functions taking a signer, can't be called
+    context
+        .expect_status_code(400)
+        .post("/view", view("0x1::coin::transfer", vec![]))
+        .await;
+    context
+        .expect_status_code(400)
+        .post("/view", view("0x1::coin::withdraw", vec![]))
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_module_by_invalid_address() {
     let mut context = new_test_context(current_function_name!());
@@ -120,6 +327,69 @@ async fn test_get_account_module_not_found() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_package_bundle() {
+    let context = new_test_context(current_function_name!());
+    let path = context.prepend_path(&get_account_package_bundle("0x1", "AptosFramework"));
+    let resp = context
+        .reply(warp::test::request().method("GET").path(&path))
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["accept-ranges"], "bytes");
+    let bundle: PackageBundle = bcs::from_bytes(resp.body()).unwrap();
+    assert_eq!(bundle.metadata.name, "AptosFramework");
+    assert_eq!(bundle.modules.len(), bundle.metadata.modules.len());
+
+    // Download the same bundle again in chunks, pinned to the same version.
+    let full = resp.body().to_vec();
+    let path = format!(
+        "{}?ledger_version={}",
+        path,
+        resp.headers()["x-aptos-ledger-version"].to_str().unwrap()
+    );
+    let chunk_size = full.len() / 3 + 1;
+    let mut chunks = vec![];
+    for start in (0..full.len()).step_by(chunk_size) {
+        let end = (start + chunk_size).min(full.len()) - 1;
+        let resp = context
+            .reply(warp::test::request().method("GET").path(&path).header(
+                "Range",
+                format!("bytes={}-{}", start, start + chunk_size - 1),
+            ))
+            .await;
+        assert_eq!(resp.status(), 206);
+        assert_eq!(
+            resp.headers()["content-range"],
+            format!("bytes {}-{}/{}", start, end, full.len()).as_str()
+        );
+        chunks.extend_from_slice(resp.body());
+    }
+    assert_eq!(chunks, full);
+
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path(&path)
+                .header("Range", format!("bytes={}-", full.len())),
+        )
+        .await;
+    assert_eq!(resp.status(), 416);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_package_bundle_not_found() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(404)
+        .get(&get_account_package_bundle("0x1", "DoesNotExist"))
+        .await;
+    context
+        .expect_status_code(404)
+        .get(&get_account_package_bundle("0xA550C18", "AptosFramework"))
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_table_item() {
     let mut context = new_test_context(current_function_name!());
@@ -213,10 +483,27 @@ fn get_account_resource_with_version(address: &str, struct_tag: &str, version: u
     )
 }
 
+fn get_account_resource_with_filter(address: &str, struct_tag: &str, filter: &str) -> String {
+    format!(
+        "/accounts/{}/resource/{}?filter={}",
+        address,
+        struct_tag,
+        utf8_percent_encode(filter, NON_ALPHANUMERIC)
+    )
+}
+
+fn decode_hex(value: &Value) -> Vec<u8> {
+    hex::decode(value.as_str().unwrap().trim_start_matches("0x")).unwrap()
+}
+
 fn get_account_module(address: &str, name: &str) -> String {
     format!("/accounts/{}/module/{}", address, name)
 }
 
+fn get_account_package_bundle(address: &str, name: &str) -> String {
+    format!("/accounts/{}/package/{}/bundle", address, name)
+}
+
 fn get_table_item(handle: u128) -> String {
     format!("/tables/{}/item", handle)
 }
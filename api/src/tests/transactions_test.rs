@@ -1,8 +1,9 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_indexer};
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_config::config::{TransactionFilterConfig, TransactionFilterRule};
 
 use aptos_crypto::{
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
@@ -11,6 +12,8 @@ use aptos_crypto::{
 use aptos_sdk::types::LocalAccount;
 use aptos_types::{
     account_address::AccountAddress,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::TransactionInfoWithProof,
     transaction::{
         authenticator::{AuthenticationKey, TransactionAuthenticator},
         EntryFunction, Script, SignedTransaction,
@@ -232,6 +235,70 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_transaction_rejected_by_filter() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    let body = bcs::to_bytes(&txn).unwrap();
+
+    context
+        .context
+        .transaction_filter()
+        .update(&TransactionFilterConfig {
+            rules: vec![TransactionFilterRule::Sender(txn.sender())],
+        })
+        .unwrap();
+    let resp = context
+        .expect_status_code(403)
+        .post_bcs_txn("/transactions", &body)
+        .await;
+    assert!(resp["message"]
+        .as_str()
+        .unwrap()
+        .contains("matches the filter rule Sender"));
+
+    // Once the rule is removed, the transaction is accepted
+    context
+        .context
+        .transaction_filter()
+        .update(&TransactionFilterConfig::default())
+        .unwrap();
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", &body)
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_fee_statistics() {
+    let mut context = new_test_context(current_function_name!());
+    let resp = context.get("/mempool/fee_statistics").await;
+    let bands = resp["bands"].as_array().unwrap();
+    assert_eq!(bands.len(), 8);
+    assert!(bands.iter().all(|band| band["num_txns"] == "0"));
+    assert_eq!(bands[0]["min_gas_unit_price"], "0");
+    assert!(bands[7]["max_gas_unit_price"].is_null());
+    assert_eq!(resp["eviction_window_secs"], "600");
+
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", &bcs::to_bytes(&txn).unwrap())
+        .await;
+
+    let resp = context.get("/mempool/fee_statistics").await;
+    let num_txns: u64 = resp["bands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|band| band["num_txns"].as_str().unwrap().parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(num_txns, 1);
+    assert_eq!(resp["num_evicted_recently"], "0");
+}
+
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_agent_signed_transaction() {
@@ -425,6 +492,56 @@ async fn test_get_transaction_by_version() {
     assert_json(resp, txns[0].clone())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transaction_by_version_with_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let resp = context
+        .get("/transactions/by_version/2?with_proof=true")
+        .await;
+    let decode_hex =
+        |value: &serde_json::Value| hex::decode(&value.as_str().unwrap()[2..]).unwrap();
+    let proof: TransactionInfoWithProof =
+        bcs::from_bytes(&decode_hex(&resp["proof"]["proof"])).unwrap();
+    let ledger_info: LedgerInfoWithSignatures =
+        bcs::from_bytes(&decode_hex(&resp["proof"]["ledger_info"])).unwrap();
+    proof.verify(ledger_info.ledger_info(), 2).unwrap();
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        proof.transaction_info().transaction_hash().to_hex_literal()
+    );
+
+    // The proof is only included when requested.
+    let resp = context.get("/transactions/by_version/2").await;
+    assert!(resp.get("proof").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_raw_transaction_by_version() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let resp = context
+        .get("/experimental/transactions/by_version/2/raw")
+        .await;
+    let bytes = hex::decode(&resp.as_str().unwrap()[2..]).unwrap();
+    let raw_txn: aptos_types::transaction::Transaction = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        raw_txn,
+        aptos_types::transaction::Transaction::UserTransaction(txn)
+    );
+
+    context
+        .expect_status_code(404)
+        .get("/experimental/transactions/by_version/10000/raw")
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_pending_transaction_by_hash() {
     let mut context = new_test_context(current_function_name!());
@@ -580,6 +697,138 @@ async fn test_get_account_transactions() {
     assert_json(txns, expected_txns);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_related_transactions() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    // The new account didn't send the transaction creating it, but is related to it.
+    let txns = context
+        .get(format!("/accounts/{}/related_transactions", account.address()).as_str())
+        .await;
+    assert_eq!(1, txns.as_array().unwrap().len());
+    let expected_txns = context.get("/transactions?start=2&limit=1").await;
+    assert_json(txns, expected_txns);
+
+    let txns = context
+        .get(
+            format!(
+                "/accounts/{}/related_transactions?start=3",
+                account.address()
+            )
+            .as_str(),
+        )
+        .await;
+    assert_json(txns, json!([]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_related_transactions_without_indexer() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    context
+        .expect_status_code(400)
+        .get(format!("/accounts/{}/related_transactions", account.address()).as_str())
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_transactions_filtered() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+    let root = context.root_account().address();
+    let expected_txns = context.get("/transactions?start=2&limit=1").await;
+
+    let txns = context
+        .get(
+            format!(
+                "/accounts/{}/transactions?success=true&entry_function=0x1::account::create_account",
+                root
+            )
+            .as_str(),
+        )
+        .await;
+    assert_json(txns, expected_txns);
+
+    let txns = context
+        .get(format!("/accounts/{}/transactions?success=false", root).as_str())
+        .await;
+    assert_json(txns, json!([]));
+    let txns = context
+        .get(
+            format!(
+                "/accounts/{}/transactions?entry_function=0x1::coin::transfer",
+                root
+            )
+            .as_str(),
+        )
+        .await;
+    assert_json(txns, json!([]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_transaction_facets() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let facets = context
+        .get(
+            format!(
+                "/accounts/{}/transactions/facets",
+                context.root_account().address()
+            )
+            .as_str(),
+        )
+        .await;
+    assert_json(
+        facets,
+        json!({
+            "successful": "1",
+            "failed": "0",
+            "entry_functions": [{
+                "entry_function": "0x1::account::create_account",
+                "count": "1",
+            }],
+        }),
+    );
+
+    let facets = context
+        .get(format!("/accounts/{}/transactions/facets", account.address()).as_str())
+        .await;
+    assert_json(
+        facets,
+        json!({"successful": "0", "failed": "0", "entry_functions": []}),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_transactions_filtered_without_indexer() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    context
+        .expect_status_code(400)
+        .get(
+            format!(
+                "/accounts/{}/transactions?success=true",
+                context.root_account().address()
+            )
+            .as_str(),
+        )
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_transactions_filter_transactions_by_start_sequence_number() {
     let mut context = new_test_context(current_function_name!());
@@ -4,6 +4,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::accept_type::AcceptType;
@@ -12,32 +13,38 @@ use crate::context::Context;
 use crate::failpoint::fail_point_poem;
 use crate::page::Page;
 use crate::response::{
-    AptosErrorResponse, BadRequestError, BasicError, BasicErrorWith404, BasicResponse,
-    BasicResponseStatus, BasicResult, BasicResultWith404, InsufficientStorageError, InternalError,
-    NotFoundError,
+    build_not_found, AptosErrorResponse, AptosResponseContent, BadRequestError, BasicError,
+    BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
+    ForbiddenError, InsufficientStorageError, InternalError, NotFoundError,
 };
 use crate::ApiTags;
 use crate::{generate_error_response, generate_success_response};
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    Address, AptosErrorCode, AsConverter, EncodeSubmissionRequest, HashValue, HexEncodedBytes,
-    LedgerInfo, PendingTransaction, SubmitTransactionRequest, Transaction, TransactionData,
+    AccountTransactionFacets, Address, AptosErrorCode, AsConverter, EncodeSubmissionRequest,
+    EntryFunctionCount, EntryFunctionId, HashValue, HexEncodedBytes, LedgerInfo,
+    PendingTransaction, SubmitTransactionRequest, Transaction, TransactionData, TransactionId,
     TransactionOnChainData, UserTransaction, U64,
 };
 use aptos_crypto::signing_message;
 use aptos_types::mempool_status::MempoolStatusCode;
 use aptos_types::transaction::{
-    ExecutionStatus, RawTransaction, RawTransactionWithData, SignedTransaction, TransactionStatus,
+    AccountActivity, ExecutionStatus, RawTransaction, RawTransactionWithData, SignedTransaction,
+    TransactionStatus,
 };
 use aptos_vm::AptosVM;
+use move_deps::move_core_types::identifier::Identifier;
+use move_deps::move_core_types::language_storage::ModuleId;
 use poem_openapi::param::{Path, Query};
 use poem_openapi::payload::Json;
-use poem_openapi::{ApiRequest, OpenApi};
+use poem_openapi::types::ToJSON;
+use poem_openapi::{ApiRequest, ApiResponse, OpenApi};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 generate_error_response!(
     SubmitTransactionError,
     (400, BadRequest),
+    (403, Forbidden),
     (413, PayloadTooLarge),
     (500, Internal),
     (507, InsufficientStorage)
@@ -46,6 +53,95 @@ generate_error_response!(
 type SubmitTransactionResult<T> =
     poem::Result<SubmitTransactionResponse<T>, SubmitTransactionError>;
 
+/// Response for the endpoints reading the account activity index. `X-Aptos-Cursor` is set
+/// when the scan of the index stopped before the end of the requested range: pass it as the
+/// `cursor` query parameter to resume it.
+#[derive(ApiResponse)]
+pub enum AccountTransactionsResponse<T: ToJSON + Send + Sync> {
+    #[oai(status = 200)]
+    Ok(
+        AptosResponseContent<T>,
+        #[oai(header = "X-Aptos-Cursor")] Option<String>,
+        #[oai(header = "X-Aptos-Chain-Id")] u16,
+        #[oai(header = "X-Aptos-Ledger-Version")] u64,
+        #[oai(header = "X-Aptos-Ledger-Oldest-Version")] u64,
+        #[oai(header = "X-Aptos-Ledger-TimestampUsec")] u64,
+        #[oai(header = "X-Aptos-Epoch")] u64,
+        #[oai(header = "X-Aptos-Block-Height")] u64,
+        #[oai(header = "X-Aptos-Oldest-Block-Height")] u64,
+    ),
+}
+
+impl<T: ToJSON + Send + Sync> AccountTransactionsResponse<T> {
+    fn new(response: BasicResponse<T>, cursor: Option<u64>) -> Self {
+        match response {
+            BasicResponse::Ok(
+                content,
+                chain_id,
+                ledger_version,
+                oldest_ledger_version,
+                ledger_timestamp,
+                epoch,
+                block_height,
+                oldest_block_height,
+            ) => AccountTransactionsResponse::Ok(
+                content,
+                cursor.map(|version| version.to_string()),
+                chain_id,
+                ledger_version,
+                oldest_ledger_version,
+                ledger_timestamp,
+                epoch,
+                block_height,
+                oldest_block_height,
+            ),
+        }
+    }
+}
+
+type AccountTransactionsResult<T> = poem::Result<AccountTransactionsResponse<T>, BasicErrorWith404>;
+
+/// The max number of entries of the account activity index a request listing the transactions
+/// of an account reads
+const MAX_ACTIVITIES_SCANNED_PER_PAGE: u64 = 10_000;
+/// The max number of entries of the account activity index a request counting the transactions
+/// of an account reads
+const MAX_ACTIVITIES_SCANNED_PER_COUNT: u64 = 100_000;
+
+/// Which of the transactions sent by an account to return
+struct AccountTransactionFilter {
+    start_sequence_number: Option<u64>,
+    success: Option<bool>,
+    entry_function: Option<(ModuleId, Identifier)>,
+    start_timestamp: Option<u64>,
+    end_timestamp: Option<u64>,
+    /// The version to resume the scan of the account activity index at
+    cursor: Option<u64>,
+}
+
+impl AccountTransactionFilter {
+    /// Whether nothing but the start sequence number is given, in which case the transactions
+    /// are read by sequence number, not from the account activity index.
+    fn is_empty(&self) -> bool {
+        self.success.is_none()
+            && self.entry_function.is_none()
+            && self.start_timestamp.is_none()
+            && self.end_timestamp.is_none()
+            && self.cursor.is_none()
+    }
+
+    fn matches(&self, activity: &AccountActivity) -> bool {
+        self.start_sequence_number
+            .map_or(true, |start| activity.sequence_number >= start)
+            && self
+                .success
+                .map_or(true, |success| activity.success == success)
+            && self.entry_function.as_ref().map_or(true, |entry_function| {
+                activity.entry_function.as_ref() == Some(entry_function)
+            })
+    }
+}
+
 type SimulateTransactionResult<T> = poem::Result<BasicResponse<T>, SubmitTransactionError>;
 
 // TODO: Consider making both content types accept either
@@ -90,7 +186,7 @@ impl TransactionsApi {
     ) -> BasicResultWith404<Vec<Transaction>> {
         fail_point_poem("endppoint_get_transactions")?;
         let page = Page::new(start.0.map(|v| v.0), limit.0);
-        self.list(&accept_type, page)
+        self.list(&accept_type, page).await
     }
 
     /// Get transaction by hash
@@ -106,6 +202,10 @@ impl TransactionsApi {
     ///   1. Hash message bytes: "RawTransaction" bytes + BCS bytes of [Transaction](https://aptos-labs.github.io/aptos-core/aptos_types/transaction/enum.Transaction.html).
     ///   2. Apply hash algorithm `SHA3-256` to the hash message bytes.
     ///   3. Hex-encode the hash bytes with `0x` prefix.
+    ///
+    /// If `with_proof` is set, committed transactions include a proof of their
+    /// transaction info against the latest ledger info. Pending transactions
+    /// cannot be proven and are returned without one.
     // TODO: Include a link to an example of how to do this ^
     #[oai(
         path = "/transactions/by_hash/:txn_hash",
@@ -117,16 +217,22 @@ impl TransactionsApi {
         &self,
         accept_type: AcceptType,
         txn_hash: Path<HashValue>,
+        with_proof: Query<Option<bool>>,
         // TODO: Use a new request type that can't return 507.
     ) -> BasicResultWith404<Transaction> {
         fail_point_poem("endpoint_transaction_by_hash")?;
-        self.get_transaction_by_hash_inner(&accept_type, txn_hash.0)
-            .await
+        self.get_transaction_by_hash_inner(
+            &accept_type,
+            txn_hash.0,
+            with_proof.0.unwrap_or_default(),
+        )
+        .await
     }
 
     /// Get transaction by version
     ///
-    /// todo
+    /// If `with_proof` is set, the transaction includes a proof of its
+    /// transaction info against the latest ledger info.
     #[oai(
         path = "/transactions/by_version/:txn_version",
         method = "get",
@@ -137,15 +243,51 @@ impl TransactionsApi {
         &self,
         accept_type: AcceptType,
         txn_version: Path<U64>,
+        with_proof: Query<Option<bool>>,
     ) -> BasicResultWith404<Transaction> {
         fail_point_poem("endpoint_transaction_by_version")?;
-        self.get_transaction_by_version_inner(&accept_type, txn_version.0)
+        self.get_transaction_by_version_inner(
+            &accept_type,
+            txn_version.0,
+            with_proof.0.unwrap_or_default(),
+        )
+        .await
+    }
+
+    /// Get raw transaction by version
+    ///
+    /// Get the BCS encoded `Transaction` committed at the given version, as
+    /// executed by the VM, e.g. to replay it locally. With a BCS `Accept`
+    /// header the bytes are returned as is, otherwise they are returned hex
+    /// encoded.
+    #[oai(
+        path = "/experimental/transactions/by_version/:txn_version/raw",
+        method = "get",
+        operation_id = "get_raw_transaction_by_version",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_raw_transaction_by_version(
+        &self,
+        accept_type: AcceptType,
+        txn_version: Path<U64>,
+    ) -> BasicResultWith404<HexEncodedBytes> {
+        fail_point_poem("endpoint_raw_transaction_by_version")?;
+        self.get_raw_transaction_by_version_inner(&accept_type, txn_version.0)
             .await
     }
 
     /// Get account transactions
     ///
-    /// todo
+    /// Retrieves the transactions sent by an account, in ascending order of sequence
+    /// number, starting at the `start` sequence number.
+    ///
+    /// The transactions can be filtered by outcome with `success`, by the entry function
+    /// they call with `entry_function`, e.g. `0x1::coin::transfer`, and by the blocks they
+    /// were committed in from `start_timestamp` (inclusive) to `end_timestamp` (exclusive),
+    /// in microseconds. Filtering requires the internal indexer to be enabled on the node,
+    /// and only covers the transactions indexed since the node was upgraded. A filtered
+    /// request reads a bounded number of transactions: when it stops before the end, the
+    /// `X-Aptos-Cursor` header is set, to pass as `cursor` to get the next page.
     #[oai(
         path = "/accounts/:address/transactions",
         method = "get",
@@ -159,10 +301,97 @@ impl TransactionsApi {
         address: Path<Address>,
         start: Query<Option<U64>>,
         limit: Query<Option<u16>>,
-    ) -> BasicResultWith404<Vec<Transaction>> {
+        success: Query<Option<bool>>,
+        entry_function: Query<Option<EntryFunctionId>>,
+        start_timestamp: Query<Option<U64>>,
+        end_timestamp: Query<Option<U64>>,
+        cursor: Query<Option<U64>>,
+    ) -> AccountTransactionsResult<Vec<Transaction>> {
         fail_point_poem("endpoint_get_accounts_transactions")?;
         let page = Page::new(start.0.map(|v| v.0), limit.0);
-        self.list_by_account(&accept_type, page, address.0)
+        let filter = AccountTransactionFilter {
+            start_sequence_number: start.0.map(|v| v.0),
+            success: success.0,
+            entry_function: entry_function
+                .0
+                .map(|id| (id.module.into(), id.name.into())),
+            start_timestamp: start_timestamp.0.map(|v| v.0),
+            end_timestamp: end_timestamp.0.map(|v| v.0),
+            cursor: cursor.0.map(|v| v.0),
+        };
+        if filter.is_empty() {
+            let response = self.list_by_account(&accept_type, page, address.0).await?;
+            return Ok(AccountTransactionsResponse::new(response, None));
+        }
+        self.list_by_account_filtered(&accept_type, page, address.0, filter)
+            .await
+    }
+
+    /// Get account transaction facets
+    ///
+    /// Counts the transactions sent by an account, by outcome and by the entry function they
+    /// call. The query parameters filter the transactions counted, as for
+    /// `/accounts/{address}/transactions`. The counts are read from the internal indexer,
+    /// which must be enabled on the node, and only cover the transactions indexed since the
+    /// node was upgraded. A request reads a bounded number of transactions: when it stops
+    /// before the end, the `X-Aptos-Cursor` header is set, to pass as `cursor` to count the
+    /// rest.
+    #[oai(
+        path = "/accounts/:address/transactions/facets",
+        method = "get",
+        operation_id = "get_account_transaction_facets",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_account_transaction_facets(
+        &self,
+        accept_type: AcceptType,
+        address: Path<Address>,
+        start: Query<Option<U64>>,
+        success: Query<Option<bool>>,
+        entry_function: Query<Option<EntryFunctionId>>,
+        start_timestamp: Query<Option<U64>>,
+        end_timestamp: Query<Option<U64>>,
+        cursor: Query<Option<U64>>,
+    ) -> AccountTransactionsResult<AccountTransactionFacets> {
+        fail_point_poem("endpoint_get_account_transaction_facets")?;
+        let filter = AccountTransactionFilter {
+            start_sequence_number: start.0.map(|v| v.0),
+            success: success.0,
+            entry_function: entry_function
+                .0
+                .map(|id| (id.module.into(), id.name.into())),
+            start_timestamp: start_timestamp.0.map(|v| v.0),
+            end_timestamp: end_timestamp.0.map(|v| v.0),
+            cursor: cursor.0.map(|v| v.0),
+        };
+        self.count_by_account(&accept_type, address.0, filter).await
+    }
+
+    /// Get account related transactions
+    ///
+    /// Retrieves the transactions related to an account: not only the transactions it sent, but
+    /// every transaction that wrote to its resources or modules, or emitted an event to one of its
+    /// event handles. Transactions are returned in ascending order of version, starting at the
+    /// `start` version.
+    ///
+    /// This requires the internal indexer to be enabled on the node.
+    #[oai(
+        path = "/accounts/:address/related_transactions",
+        method = "get",
+        operation_id = "get_account_related_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_account_related_transactions(
+        &self,
+        accept_type: AcceptType,
+        address: Path<Address>,
+        start: Query<Option<U64>>,
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        fail_point_poem("endpoint_get_account_related_transactions")?;
+        let page = Page::new(start.0.map(|v| v.0), limit.0);
+        self.list_related_to_account(&accept_type, page, address.0)
+            .await
     }
 
     /// Submit transaction
@@ -261,7 +490,11 @@ impl TransactionsApi {
 }
 
 impl TransactionsApi {
-    fn list(&self, accept_type: &AcceptType, page: Page) -> BasicResultWith404<Vec<Transaction>> {
+    async fn list(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+    ) -> BasicResultWith404<Vec<Transaction>> {
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
         let ledger_version = latest_ledger_info.version();
 
@@ -270,7 +503,10 @@ impl TransactionsApi {
         let start_version = page.compute_start(limit, ledger_version)?;
         let data = self
             .context
-            .get_transactions(start_version, limit, ledger_version)
+            .read_async("get_transactions", move |context| {
+                context.get_transactions(start_version, limit, ledger_version)
+            })
+            .await
             .context("Failed to read raw transactions from storage")
             .map_err(BasicErrorWith404::internal)
             .map_err(|e| e.error_code(AptosErrorCode::InvalidBcsInStorageError))?;
@@ -312,6 +548,7 @@ impl TransactionsApi {
         &self,
         accept_type: &AcceptType,
         hash: HashValue,
+        with_proof: bool,
     ) -> BasicResultWith404<Transaction> {
         let ledger_info = self.context.get_latest_ledger_info()?;
         let txn_data = self
@@ -322,7 +559,7 @@ impl TransactionsApi {
             .context(format!("Failed to find transaction with hash: {}", hash))
             .map_err(BasicErrorWith404::not_found)?;
 
-        self.get_transaction_inner(accept_type, txn_data, &ledger_info)
+        self.get_transaction_inner(accept_type, txn_data, &ledger_info, with_proof)
             .await
     }
 
@@ -330,10 +567,12 @@ impl TransactionsApi {
         &self,
         accept_type: &AcceptType,
         version: U64,
+        with_proof: bool,
     ) -> BasicResultWith404<Transaction> {
         let ledger_info = self.context.get_latest_ledger_info()?;
         let txn_data = self
             .get_by_version(version.0, &ledger_info)
+            .await
             .context(format!("Failed to get transaction by version {}", version))
             .map_err(BasicErrorWith404::not_found)?
             .context(format!(
@@ -342,7 +581,7 @@ impl TransactionsApi {
             ))
             .map_err(BasicErrorWith404::not_found)?;
 
-        self.get_transaction_inner(accept_type, txn_data, &ledger_info)
+        self.get_transaction_inner(accept_type, txn_data, &ledger_info, with_proof)
             .await
     }
 
@@ -351,20 +590,42 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         transaction_data: TransactionData,
         ledger_info: &LedgerInfo,
+        with_proof: bool,
     ) -> BasicResultWith404<Transaction> {
+        if with_proof && accept_type == &AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_str(
+                "Proofs are only supported for JSON responses",
+            ));
+        }
         let resolver = self.context.move_resolver_poem()?;
         let transaction = match transaction_data {
             TransactionData::OnChain(txn) => {
+                let version = txn.version;
                 let timestamp = self
                     .context
-                    .get_block_timestamp(txn.version)
+                    .get_block_timestamp(version)
                     .context("Failed to get block timestamp from DB")
                     .map_err(BasicErrorWith404::internal)?;
-                resolver
+                let mut transaction = resolver
                     .as_converter(self.context.db.clone())
                     .try_into_onchain_transaction(timestamp, txn)
                     .context("Failed to convert on chain transaction to Transaction")
-                    .map_err(BasicErrorWith404::internal)?
+                    .map_err(BasicErrorWith404::internal)?;
+                if with_proof {
+                    let proof = self
+                        .context
+                        .get_transaction_proof(version)
+                        .context(format!(
+                            "Failed to prove transaction at version {}",
+                            version
+                        ))
+                        .map_err(BasicErrorWith404::internal)?;
+                    transaction
+                        .transaction_info_mut()
+                        .map_err(BasicErrorWith404::internal)?
+                        .proof = Some(proof);
+                }
+                transaction
             }
             TransactionData::Pending(txn) => resolver
                 .as_converter(self.context.db.clone())
@@ -381,33 +642,80 @@ impl TransactionsApi {
         ))
     }
 
-    fn get_by_version(
+    async fn get_raw_transaction_by_version_inner(
+        &self,
+        accept_type: &AcceptType,
+        version: U64,
+    ) -> BasicResultWith404<HexEncodedBytes> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let transaction = match self
+            .get_by_version(version.0, &ledger_info)
+            .await
+            .context(format!("Failed to get transaction by version {}", version))
+            .map_err(BasicErrorWith404::not_found)?
+        {
+            Some(TransactionData::OnChain(txn)) => txn.transaction,
+            _ => {
+                return Err(build_not_found(
+                    "Transaction",
+                    TransactionId::Version(version),
+                    ledger_info.version(),
+                ))
+            }
+        };
+        let bytes = bcs::to_bytes(&transaction)
+            .context("Failed to serialize transaction")
+            .map_err(|e| {
+                BasicErrorWith404::internal(e).error_code(AptosErrorCode::BcsSerializationError)
+            })?;
+
+        match accept_type {
+            AcceptType::Bcs => Ok(BasicResponse::from((
+                Bcs(bytes),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            ))),
+            AcceptType::Json => Ok(BasicResponse::from((
+                Json(HexEncodedBytes::from(bytes)),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            ))),
+        }
+    }
+
+    async fn get_by_version(
         &self,
         version: u64,
         ledger_info: &LedgerInfo,
     ) -> anyhow::Result<Option<TransactionData>> {
-        if version > ledger_info.version() {
+        let ledger_version = ledger_info.version();
+        if version > ledger_version {
             return Ok(None);
         }
         Ok(Some(
             self.context
-                .get_transaction_by_version(version, ledger_info.version())?
+                .read_async("get_transaction_by_version", move |context| {
+                    context.get_transaction_by_version(version, ledger_version)
+                })
+                .await?
                 .into(),
         ))
     }
 
     // This function looks for the transaction by hash in database and then mempool,
     // because the period a transaction stay in the mempool is likely short.
-    // Although the mempool get transation is async, but looking up txn in database is a sync call,
-    // thus we keep it simple and call them in sequence.
     async fn get_by_hash(
         &self,
         hash: aptos_crypto::HashValue,
         ledger_info: &LedgerInfo,
     ) -> anyhow::Result<Option<TransactionData>> {
+        let ledger_version = ledger_info.version();
         let from_db = self
             .context
-            .get_transaction_by_hash(hash, ledger_info.version())?;
+            .read_async("get_transaction_by_hash", move |context| {
+                context.get_transaction_by_hash(hash, ledger_version)
+            })
+            .await?;
         Ok(match from_db {
             None => self
                 .context
@@ -418,7 +726,7 @@ impl TransactionsApi {
         })
     }
 
-    fn list_by_account(
+    async fn list_by_account(
         &self,
         accept_type: &AcceptType,
         page: Page,
@@ -426,14 +734,15 @@ impl TransactionsApi {
     ) -> BasicResultWith404<Vec<Transaction>> {
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
         // TODO: Return more specific errors from within this function.
+        let start = page.start(0, u64::MAX)?;
+        let limit = page.limit()?;
+        let ledger_version = latest_ledger_info.version();
         let data = self
             .context
-            .get_account_transactions(
-                address.into(),
-                page.start(0, u64::MAX)?,
-                page.limit()?,
-                latest_ledger_info.version(),
-            )
+            .read_async("get_account_transactions", move |context| {
+                context.get_account_transactions(address.into(), start, limit, ledger_version)
+            })
+            .await
             .context("Failed to get account transactions for the given account")
             .map_err(BasicErrorWith404::internal)?;
 
@@ -445,6 +754,192 @@ impl TransactionsApi {
         ))
     }
 
+    async fn list_related_to_account(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+        address: Address,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        if !self.context.db.indexer_enabled() {
+            return Err(BasicErrorWith404::bad_request(anyhow::anyhow!(
+                "The internal indexer is not enabled on this node"
+            )));
+        }
+        let ledger_version = latest_ledger_info.version();
+        let start = page.start(0, ledger_version)?;
+        let limit = page.limit()?;
+        let data = self
+            .context
+            .read_async("get_account_related_transactions", move |context| {
+                context.get_account_related_transactions(
+                    address.into(),
+                    start,
+                    limit,
+                    ledger_version,
+                )
+            })
+            .await
+            .context("Failed to get related transactions for the given account")
+            .map_err(BasicErrorWith404::internal)?;
+
+        BasicResponse::try_from_rust_value((
+            self.render_transactions(data)?,
+            &latest_ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    async fn list_by_account_filtered(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+        address: Address,
+        filter: AccountTransactionFilter,
+    ) -> AccountTransactionsResult<Vec<Transaction>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_version = latest_ledger_info.version();
+        let (start_version, end_version) =
+            self.account_activity_range(&latest_ledger_info, &filter)?;
+        let limit = page.limit()? as usize;
+        let (data, cursor) = self
+            .context
+            .read_async("get_account_transactions", move |context| {
+                let mut versions = vec![];
+                let cursor = context.scan_account_activities(
+                    address.into(),
+                    start_version,
+                    end_version,
+                    MAX_ACTIVITIES_SCANNED_PER_PAGE,
+                    ledger_version,
+                    |activity| {
+                        if filter.matches(activity) {
+                            versions.push(activity.version);
+                        }
+                        versions.len() < limit
+                    },
+                )?;
+                let data = versions
+                    .into_iter()
+                    .map(|version| context.get_transaction_by_version(version, ledger_version))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok((data, cursor))
+            })
+            .await
+            .context("Failed to get account transactions for the given account")
+            .map_err(BasicErrorWith404::internal)?;
+
+        let response = BasicResponse::try_from_rust_value::<BasicErrorWith404>((
+            self.render_transactions(data)?,
+            &latest_ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))?;
+        Ok(AccountTransactionsResponse::new(response, cursor))
+    }
+
+    async fn count_by_account(
+        &self,
+        accept_type: &AcceptType,
+        address: Address,
+        filter: AccountTransactionFilter,
+    ) -> AccountTransactionsResult<AccountTransactionFacets> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_version = latest_ledger_info.version();
+        let (start_version, end_version) =
+            self.account_activity_range(&latest_ledger_info, &filter)?;
+        let (facets, cursor) = self
+            .context
+            .read_async("get_account_transaction_facets", move |context| {
+                let (mut successful, mut failed) = (0, 0);
+                let mut entry_functions: BTreeMap<(ModuleId, Identifier), u64> = BTreeMap::new();
+                let cursor = context.scan_account_activities(
+                    address.into(),
+                    start_version,
+                    end_version,
+                    MAX_ACTIVITIES_SCANNED_PER_COUNT,
+                    ledger_version,
+                    |activity| {
+                        if filter.matches(activity) {
+                            if activity.success {
+                                successful += 1;
+                            } else {
+                                failed += 1;
+                            }
+                            if let Some(entry_function) = &activity.entry_function {
+                                *entry_functions.entry(entry_function.clone()).or_default() += 1;
+                            }
+                        }
+                        true
+                    },
+                )?;
+
+                let mut entry_functions: Vec<_> = entry_functions.into_iter().collect();
+                entry_functions.sort_by(|(_, a), (_, b)| b.cmp(a));
+                let facets = AccountTransactionFacets {
+                    successful: successful.into(),
+                    failed: failed.into(),
+                    entry_functions: entry_functions
+                        .into_iter()
+                        .map(|((module, name), count)| EntryFunctionCount {
+                            entry_function: EntryFunctionId {
+                                module: module.into(),
+                                name: name.into(),
+                            },
+                            count: count.into(),
+                        })
+                        .collect(),
+                };
+                Ok((facets, cursor))
+            })
+            .await
+            .context("Failed to count the transactions of the given account")
+            .map_err(BasicErrorWith404::internal)?;
+
+        let response = BasicResponse::try_from_rust_value::<BasicErrorWith404>((
+            facets,
+            &latest_ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))?;
+        Ok(AccountTransactionsResponse::new(response, cursor))
+    }
+
+    /// The versions, from the start (inclusive) to the end (exclusive), of the account activity
+    /// index to scan for the given filter.
+    fn account_activity_range(
+        &self,
+        latest_ledger_info: &LedgerInfo,
+        filter: &AccountTransactionFilter,
+    ) -> Result<(u64, u64), BasicErrorWith404> {
+        if !self.context.db.indexer_enabled() {
+            return Err(BasicErrorWith404::bad_request(anyhow::anyhow!(
+                "The internal indexer is not enabled on this node"
+            )));
+        }
+        let ledger_version = latest_ledger_info.version();
+        let mut start_version = filter.cursor.unwrap_or(0);
+        let mut end_version = ledger_version + 1;
+        if let Some(start_timestamp) = filter.start_timestamp {
+            let version = self
+                .context
+                .get_first_version_at_or_after_timestamp(start_timestamp, ledger_version)
+                .context("Failed to find the version of the start timestamp")
+                .map_err(BasicErrorWith404::internal)?;
+            start_version = std::cmp::max(start_version, version);
+        }
+        if let Some(end_timestamp) = filter.end_timestamp {
+            let version = self
+                .context
+                .get_first_version_at_or_after_timestamp(end_timestamp, ledger_version)
+                .context("Failed to find the version of the end timestamp")
+                .map_err(BasicErrorWith404::internal)?;
+            end_version = std::cmp::min(end_version, version);
+        }
+        Ok((start_version, end_version))
+    }
+
     fn get_signed_transaction(
         &self,
         data: SubmitTransactionPost,
@@ -496,6 +991,9 @@ impl TransactionsApi {
             MempoolStatusCode::MempoolIsFull => Err(
                 SubmitTransactionError::insufficient_storage_str(&mempool_status.message),
             ),
+            MempoolStatusCode::RejectedByFilter => Err(SubmitTransactionError::forbidden_str(
+                &format!("transaction is rejected: {}", mempool_status.message),
+            )),
             MempoolStatusCode::VmError => Err(SubmitTransactionError::bad_request_str(&format!(
                 "invalid transaction: {}",
                 vm_status_opt
@@ -526,7 +1024,7 @@ impl TransactionsApi {
         }
         let ledger_info = self.context.get_latest_ledger_info()?;
         let move_resolver = self.context.move_resolver_poem()?;
-        let (status, output_ext) = AptosVM::simulate_signed_transaction(&txn, &move_resolver);
+        let (_vm_status, output_ext) = AptosVM::simulate_signed_transaction(&txn, &move_resolver);
         let version = ledger_info.version();
 
         // Apply deltas.
@@ -540,8 +1038,8 @@ impl TransactionsApi {
         );
         let output = output.unwrap();
 
-        let exe_status = match status.into() {
-            TransactionStatus::Keep(exec_status) => exec_status,
+        let exe_status = match output.status() {
+            TransactionStatus::Keep(exec_status) => exec_status.clone(),
             _ => ExecutionStatus::MiscellaneousError(None),
         };
 
@@ -561,6 +1059,7 @@ impl TransactionsApi {
             events: output.events().to_vec(),
             accumulator_root_hash: aptos_crypto::HashValue::default(),
             changes: output.write_set().clone(),
+            auxiliary_data: None,
         };
 
         let transactions = self.render_transactions(vec![simulated_txn])?;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{golden_output::GoldenOutputs, pretty};
-use aptos_api::{attach_poem_to_runtime, BasicError, Context};
+use aptos_api::{attach_poem_to_runtime, ApiGateway, BasicError, Context};
 use aptos_api_types::{
     mime_types, HexEncodedBytes, TransactionOnChainData, X_APTOS_CHAIN_ID,
     X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
@@ -12,7 +12,7 @@ use aptos_config::config::{
     NO_OP_STORAGE_PRUNER_CONFIG, TARGET_SNAPSHOT_SIZE,
 };
 use aptos_crypto::{hash::HashValue, SigningKey};
-use aptos_mempool::mocks::MockSharedMempool;
+use aptos_mempool::{mocks::MockSharedMempool, TransactionFilter};
 use aptos_sdk::{
     transaction_builder::TransactionFactory,
     types::{
@@ -132,12 +132,20 @@ pub fn new_test_context(test_name: String, use_db_with_indexer: bool) -> TestCon
         db.clone(),
         mempool.ac_client.clone(),
         node_config.clone(),
+        TransactionFilter::default(),
     );
 
     // Configure the testing depending on which API version we're testing.
     let runtime_handle = tokio::runtime::Handle::current();
-    let poem_address = attach_poem_to_runtime(&runtime_handle, context.clone(), &node_config, true)
-        .expect("Failed to attach poem to runtime");
+    let gateway = ApiGateway::new(&node_config.api.gateway).unwrap();
+    let poem_address = attach_poem_to_runtime(
+        &runtime_handle,
+        context.clone(),
+        &node_config,
+        gateway,
+        true,
+    )
+    .expect("Failed to attach poem to runtime");
     let api_specific_config = ApiSpecificConfig::V1(poem_address);
 
     TestContext::new(
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{HexEncodedBytes, U64};
+use crate::{EntryFunctionId, HexEncodedBytes, U64};
 
 use aptos_types::account_config::AccountResource;
 use poem_openapi::Object;
@@ -22,3 +22,21 @@ impl From<AccountResource> for AccountData {
         }
     }
 }
+
+/// Counts of the transactions sent by an account, by outcome and by entry function called.
+///
+/// `entry_functions` are in decreasing order of count. Transactions running a script or
+/// publishing modules are only counted in `successful` / `failed`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Object)]
+pub struct AccountTransactionFacets {
+    pub successful: U64,
+    pub failed: U64,
+    pub entry_functions: Vec<EntryFunctionCount>,
+}
+
+/// The number of transactions calling an entry function
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct EntryFunctionCount {
+    pub entry_function: EntryFunctionId,
+    pub count: U64,
+}
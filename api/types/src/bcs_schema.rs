@@ -0,0 +1,20 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use poem_openapi::Object as PoemObject;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The BCS layout of a type, in the format of
+/// [serde-reflection](https://github.com/zefchain/serde-reflection), which serde-generate
+/// takes to generate the code serializing and deserializing the type in other languages.
+///
+/// `root` is the `Format` of the type, e.g. `"U64"`, `{"SEQ": "U8"}` or
+/// `{"TYPENAME": "SignedTransaction"}`. `types` holds the `ContainerFormat` of every named
+/// type the type is made of, by name. Move structs are named by their full struct tag, e.g.
+/// `0x1::coin::Coin<0x1::aptos_coin::AptosCoin>`, and addresses by `AccountAddress`.
+#[derive(Clone, Debug, Deserialize, PartialEq, PoemObject, Serialize)]
+pub struct BcsSchema {
+    pub root: serde_json::Value,
+    pub types: BTreeMap<String, serde_json::Value>,
+}
@@ -11,19 +11,21 @@ use crate::{
     MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveValue,
     PendingTransaction, ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction,
     TransactionInfo, TransactionOnChainData, TransactionPayload, UserTransactionRequest,
-    VersionedEvent, WriteSet, WriteSetChange, WriteSetPayload,
+    VersionedEvent, WriteSet, WriteSetChange, WriteSetPayload, U64,
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_transaction_builder::error_explain;
-use aptos_types::state_store::table::TableHandle;
+use aptos_types::state_store::table::{TableHandle, TableInfo};
 use aptos_types::{
     access_path::{AccessPath, Path},
     chain_id::ChainId,
     contract_event::{ContractEvent, EventWithVersion},
+    resource_group::decode_resource_group,
     state_store::state_key::StateKey,
     transaction::{
-        EntryFunction, ExecutionStatus, ModuleBundle, RawTransaction, Script, SignedTransaction,
+        EntryFunction, ExecutionStatus, ModuleBundle, OrderlessEntryFunction, RawTransaction,
+        Script, SignedTransaction,
     },
     vm_status::AbortLocation,
     write_set::WriteOp,
@@ -100,12 +102,13 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         data: TransactionOnChainData,
     ) -> Result<Transaction> {
         use aptos_types::transaction::Transaction::*;
-        let info = self.into_transaction_info(
+        let mut info = self.into_transaction_info(
             data.version,
             &data.info,
             data.accumulator_root_hash,
             data.changes,
         );
+        info.auxiliary_data = data.auxiliary_data.as_ref().map(Into::into);
         let events = self.try_into_events(&data.events)?;
         Ok(match data.transaction {
             UserTransaction(txn) => {
@@ -117,6 +120,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                 (info, payload, events).into()
             }
             BlockMetadata(txn) => (&txn, info, events).into(),
+            ValidatorTransaction(txn) => (&txn, info, events, timestamp).into(),
             StateCheckpoint(_) => {
                 Transaction::StateCheckpointTransaction(StateCheckpointTransaction {
                     info,
@@ -145,8 +149,11 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
             // TODO: the resource value is interpreted by the type definition at the version of the converter, not the version of the tx: must be fixed before we allow module updates
             changes: write_set
                 .into_iter()
-                .filter_map(|(sk, wo)| self.try_into_write_set_change(sk, wo).ok())
+                .filter_map(|(sk, wo)| self.try_into_write_set_changes(sk, wo).ok())
+                .flatten()
                 .collect(),
+            proof: None,
+            auxiliary_data: None,
         }
     }
 
@@ -163,35 +170,48 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     .map(|module| MoveModuleBytecode::from(module).try_parse_abi())
                     .collect::<Result<Vec<_>>>()?,
             }),
-            EntryFunction(fun) => {
-                let (module, function, ty_args, args) = fun.into_inner();
-                let func_args = self
-                    .inner
-                    .view_function_arguments(&module, &function, &args);
-                let json_args = match func_args {
-                    Ok(values) => values
-                        .into_iter()
-                        .map(|v| MoveValue::try_from(v)?.json())
-                        .collect::<Result<_>>()?,
-                    Err(_e) => args
-                        .into_iter()
-                        .map(|arg| HexEncodedBytes::from(arg).json())
-                        .collect::<Result<_>>()?,
-                };
-
-                TransactionPayload::EntryFunctionPayload(EntryFunctionPayload {
-                    arguments: json_args,
-                    function: EntryFunctionId {
-                        module: module.into(),
-                        name: function.into(),
-                    },
-                    type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
-                })
+            EntryFunction(fun) => self.try_into_entry_function_payload(fun, None)?,
+            OrderlessEntryFunction(orderless) => {
+                let (nonce, fun) = orderless.into_inner();
+                self.try_into_entry_function_payload(fun, Some(nonce))?
             }
         };
         Ok(ret)
     }
 
+    fn try_into_entry_function_payload(
+        &self,
+        fun: EntryFunction,
+        replay_nonce: Option<u64>,
+    ) -> Result<TransactionPayload> {
+        let (module, function, ty_args, args) = fun.into_inner();
+        let func_args = self
+            .inner
+            .view_function_arguments(&module, &function, &args);
+        let json_args = match func_args {
+            Ok(values) => values
+                .into_iter()
+                .map(|v| MoveValue::try_from(v)?.json())
+                .collect::<Result<_>>()?,
+            Err(_e) => args
+                .into_iter()
+                .map(|arg| HexEncodedBytes::from(arg).json())
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(TransactionPayload::EntryFunctionPayload(
+            EntryFunctionPayload {
+                arguments: json_args,
+                function: EntryFunctionId {
+                    module: module.into(),
+                    name: function.into(),
+                },
+                type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
+                replay_nonce: replay_nonce.map(U64::from),
+            },
+        ))
+    }
+
     pub fn try_into_write_set_payload(
         &self,
         payload: aptos_types::transaction::WriteSetPayload,
@@ -211,8 +231,11 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                         // TODO: the resource value is interpreted by the type definition at the version of the converter, not the version of the tx: must be fixed before we allow module updates
                         changes: write_set
                             .into_iter()
-                            .map(|(state_key, op)| self.try_into_write_set_change(state_key, op))
-                            .collect::<Result<_>>()?,
+                            .map(|(state_key, op)| self.try_into_write_set_changes(state_key, op))
+                            .collect::<Result<Vec<_>>>()?
+                            .into_iter()
+                            .flatten()
+                            .collect(),
                         events: self.try_into_events(&events)?,
                     }),
                 }
@@ -221,6 +244,47 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    /// Converts a write to the changes it makes, one per member for a resource group, whose
+    /// members are shown as individual resources.
+    pub fn try_into_write_set_changes(
+        &self,
+        state_key: StateKey,
+        op: WriteOp,
+    ) -> Result<Vec<WriteSetChange>> {
+        let access_path = match &state_key {
+            StateKey::AccessPath(access_path) => access_path,
+            _ => return Ok(vec![self.try_into_write_set_change(state_key, op)?]),
+        };
+        let group_tag = match access_path.get_path() {
+            Path::ResourceGroup(group_tag) => group_tag,
+            Path::Code(_) | Path::Resource(_) => {
+                return Ok(vec![self.try_into_write_set_change(state_key, op)?])
+            }
+        };
+        let state_key_hash = state_key.hash().to_hex_literal();
+        let address = access_path.address.into();
+        match op {
+            // The members of a deleted group are not known, so the group is shown as deleted
+            WriteOp::Deletion => Ok(vec![WriteSetChange::DeleteResource(DeleteResource {
+                address,
+                state_key_hash,
+                resource: group_tag.into(),
+            })]),
+            WriteOp::Modification(val) | WriteOp::Creation(val) => {
+                decode_resource_group(&group_tag, &val)?
+                    .into_iter()
+                    .map(|(typ, bytes)| {
+                        Ok(WriteSetChange::WriteResource(WriteResource {
+                            address,
+                            state_key_hash: state_key_hash.clone(),
+                            data: self.try_into_resource(&typ, &bytes)?,
+                        }))
+                    })
+                    .collect()
+            }
+        }
+    }
+
     pub fn try_into_write_set_change(
         &self,
         state_key: StateKey,
@@ -255,11 +319,13 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     state_key_hash,
                     module: module_id.into(),
                 }),
-                Path::Resource(typ) => WriteSetChange::DeleteResource(DeleteResource {
-                    address: access_path.address.into(),
-                    state_key_hash,
-                    resource: typ.into(),
-                }),
+                Path::Resource(typ) | Path::ResourceGroup(typ) => {
+                    WriteSetChange::DeleteResource(DeleteResource {
+                        address: access_path.address.into(),
+                        state_key_hash,
+                        resource: typ.into(),
+                    })
+                }
             },
             WriteOp::Modification(val) | WriteOp::Creation(val) => match access_path.get_path() {
                 Path::Code(_) => WriteSetChange::WriteModule(WriteModule {
@@ -272,6 +338,10 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     state_key_hash,
                     data: self.try_into_resource(&typ, &val)?,
                 }),
+                Path::ResourceGroup(group_tag) => bail!(
+                    "Writes to resource group {} change several resources, convert them with try_into_write_set_changes",
+                    group_tag
+                ),
             },
         };
         Ok(ret)
@@ -319,10 +389,10 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         key: &[u8],
         value: &[u8],
     ) -> Result<Option<DecodedTableData>> {
-        if !self.db.indexer_enabled() {
-            return Ok(None);
-        }
-        let table_info = self.db.get_table_info(handle).unwrap();
+        let table_info = match self.get_table_info(handle) {
+            Some(table_info) => table_info,
+            None => return Ok(None),
+        };
         let key = self.try_into_move_value(&table_info.key_type, key)?;
         let value = self.try_into_move_value(&table_info.value_type, value)?;
 
@@ -339,10 +409,10 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         handle: TableHandle,
         key: &[u8],
     ) -> Result<Option<DeletedTableData>> {
-        if !self.db.indexer_enabled() {
-            return Ok(None);
-        }
-        let table_info = self.db.get_table_info(handle).unwrap();
+        let table_info = match self.get_table_info(handle) {
+            Some(table_info) => table_info,
+            None => return Ok(None),
+        };
         let key = self.try_into_move_value(&table_info.key_type, key)?;
 
         Ok(Some(DeletedTableData {
@@ -351,6 +421,15 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         }))
     }
 
+    /// The table info service parses the table infos asynchronously, so the info of a table
+    /// created recently may be missing, in which case the table data isn't decoded.
+    fn get_table_info(&self, handle: TableHandle) -> Option<TableInfo> {
+        if !self.db.table_info_enabled() {
+            return None;
+        }
+        self.db.get_table_info(handle).ok()
+    }
+
     pub fn try_into_events(&self, events: &[ContractEvent]) -> Result<Vec<Event>> {
         let mut ret = vec![];
         for event in events {
@@ -467,6 +546,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     function,
                     type_arguments,
                     arguments,
+                    replay_nonce,
                 } = entry_func_payload;
 
                 let module = function.module.clone();
@@ -487,7 +567,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     .map(bcs::to_bytes)
                     .collect::<Result<_, bcs::Error>>()?;
 
-                Target::EntryFunction(EntryFunction::new(
+                let entry_function = EntryFunction::new(
                     module.into(),
                     function.name.into(),
                     type_arguments
@@ -495,7 +575,14 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                         .map(|v| v.try_into())
                         .collect::<Result<_>>()?,
                     args,
-                ))
+                );
+                match replay_nonce {
+                    Some(nonce) => Target::OrderlessEntryFunction(OrderlessEntryFunction::new(
+                        nonce.0,
+                        entry_function,
+                    )),
+                    None => Target::EntryFunction(entry_function),
+                }
             }
             TransactionPayload::ModuleBundlePayload(payload) => {
                 Target::ModuleBundle(ModuleBundle::new(
@@ -575,6 +662,11 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
             .collect::<Result<_>>()
     }
 
+    /// The layout of the values of `type_tag`, with the struct tags and field names of structs
+    pub fn get_type_layout(&self, type_tag: &TypeTag) -> Result<MoveTypeLayout> {
+        self.inner.get_type_layout_with_types(type_tag)
+    }
+
     // Converts JSON object to `MoveValue`, which can be bcs serialized into the same
     // representation in the DB.
     // Notice that structs are of the `MoveStruct::Runtime` flavor, matching the representation in
@@ -724,6 +816,13 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     )
                 )
             }
+            ExecutionStatus::ChangeSetLimitExceeded { limit, state_key } => match state_key {
+                Some(state_key) => format!(
+                    "Transaction output exceeds the limit on the {}, writing {:?}",
+                    limit, state_key
+                ),
+                None => format!("Transaction output exceeds the limit on the {}", limit),
+            },
         }
     }
 
@@ -279,11 +279,13 @@ impl_poem_type!(
 
 impl_poem_parameter!(
     Address,
+    EntryFunctionId,
     EventKey,
     HashValue,
     IdentifierWrapper,
     HexEncodedBytes,
     MoveStructTag,
+    MoveType,
     U64,
     U128
 );
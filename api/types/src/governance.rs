@@ -0,0 +1,90 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Address, HexEncodedBytes, U128, U64};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+
+/// The configuration of on-chain governance
+#[derive(Clone, Debug, Deserialize, PartialEq, Object, Serialize)]
+pub struct GovernanceConfig {
+    pub min_voting_threshold: U128,
+    pub required_proposer_stake: U64,
+    pub voting_duration_secs: U64,
+}
+
+/// A governance proposal
+///
+/// `metadata_location` and `metadata_hash` are the location and hash of the
+/// description of the proposal given by the proposer. The vote counts are in
+/// units of stake.
+#[derive(Clone, Debug, Deserialize, PartialEq, Object, Serialize)]
+pub struct GovernanceProposal {
+    pub proposal_id: U64,
+    pub proposer: Address,
+    pub state: GovernanceProposalState,
+    pub is_resolved: bool,
+    pub creation_time_secs: U64,
+    pub expiration_secs: U64,
+    pub execution_hash: HexEncodedBytes,
+    pub metadata_location: Option<String>,
+    pub metadata_hash: Option<String>,
+    pub min_vote_threshold: U128,
+    pub early_resolution_vote_threshold: Option<U128>,
+    pub yes_votes: U128,
+    pub no_votes: U128,
+}
+
+/// The state of a governance proposal
+///
+/// A proposal is pending until it expires or enough stake voted for the same
+/// outcome to resolve it early. It then succeeded if there were more yes than
+/// no votes and the minimum vote threshold was reached, and failed otherwise.
+#[derive(Clone, Copy, Debug, Deserialize, Enum, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum GovernanceProposalState {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+/// Whether a stake pool voted on a governance proposal
+///
+/// Only one vote is allowed per stake pool and proposal. How it voted is
+/// recorded in the `0x1::aptos_governance::VoteEvent` events.
+#[derive(Clone, Debug, Deserialize, PartialEq, Object, Serialize)]
+pub struct GovernanceVotingRecord {
+    pub proposal_id: U64,
+    pub stake_pool: Address,
+    pub voted: bool,
+}
+
+/// A summary of a stake pool
+///
+/// `voting_power` is the stake the pool can vote with in the current epoch,
+/// which is zero unless the pool's validator is in the validator set.
+#[derive(Clone, Debug, Deserialize, PartialEq, Object, Serialize)]
+pub struct StakePoolSummary {
+    pub pool_address: Address,
+    pub operator_address: Address,
+    pub delegated_voter: Address,
+    pub active: U64,
+    pub inactive: U64,
+    pub pending_active: U64,
+    pub pending_inactive: U64,
+    pub locked_until_secs: U64,
+    pub validator_state: ValidatorState,
+    pub voting_power: U64,
+}
+
+/// The state of the validator of a stake pool in the validator set
+#[derive(Clone, Copy, Debug, Deserialize, Enum, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum ValidatorState {
+    PendingActive,
+    Active,
+    PendingInactive,
+    Inactive,
+}
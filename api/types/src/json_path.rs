@@ -0,0 +1,415 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A small subset of JSONPath used to project the JSON representation of Move
+//! values down to only the fields a client asked for. This lets clients fetch
+//! a couple of fields out of a large resource (e.g. one with big vectors or
+//! option-wrapped blobs) without downloading the entire thing.
+//!
+//! The supported syntax is:
+//!
+//!   - `$` - The root of the value. Every path must start with this.
+//!   - `.field` or `['field']` - A field of an object.
+//!   - `[3]` - An element of an array.
+//!   - `.*` or `[*]` - Every field of an object or every element of an array.
+//!
+//! Multiple paths can be given separated by commas, e.g.
+//! `$.coin.value,$.frozen`. Unlike regular JSONPath, which returns a flat list
+//! of matches, the result of a projection keeps the shape of the input, so the
+//! output for `$.coin.value` on a `CoinStore` is `{"coin": {"value": "100"}}`.
+//! Array elements keep their relative order, but not their original indices.
+
+use anyhow::{bail, format_err};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, fmt, iter::Peekable, str::Chars, str::FromStr};
+
+/// A single step in a [`JsonPath`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JsonPathSegment {
+    Field(String),
+    Index(usize),
+    Wildcard,
+}
+
+impl fmt::Display for JsonPathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonPathSegment::Field(field) if is_plain_field(field) => write!(f, ".{}", field),
+            JsonPathSegment::Field(field) => write!(f, "['{}']", field),
+            JsonPathSegment::Index(index) => write!(f, "[{}]", index),
+            JsonPathSegment::Wildcard => write!(f, "[*]"),
+        }
+    }
+}
+
+fn is_plain_field(field: &str) -> bool {
+    !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A path from the root of a JSON value to some set of values within it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct JsonPath(Vec<JsonPathSegment>);
+
+impl JsonPath {
+    pub fn segments(&self) -> &[JsonPathSegment] {
+        &self.0
+    }
+
+    fn parse(chars: &mut Peekable<Chars>) -> anyhow::Result<Self> {
+        if chars.next() != Some('$') {
+            bail!("JSON path must start with '$'");
+        }
+        let mut segments = vec![];
+        while let Some(c) = chars.peek().copied() {
+            match c {
+                ',' => break,
+                '.' => {
+                    chars.next();
+                    if chars.peek() == Some(&'*') {
+                        chars.next();
+                        segments.push(JsonPathSegment::Wildcard);
+                        continue;
+                    }
+                    let mut field = String::new();
+                    while let Some(c) = chars.peek().copied() {
+                        if c == '.' || c == '[' || c == ',' {
+                            break;
+                        }
+                        field.push(c);
+                        chars.next();
+                    }
+                    if !is_plain_field(&field) {
+                        bail!("Invalid field name {:?} in JSON path", field);
+                    }
+                    segments.push(JsonPathSegment::Field(field));
+                }
+                '[' => {
+                    chars.next();
+                    segments.push(Self::parse_bracket(chars)?);
+                }
+                c => bail!("Unexpected character {:?} in JSON path", c),
+            }
+        }
+        Ok(Self(segments))
+    }
+
+    /// Parses the inside of a `[...]` segment, consuming the closing bracket.
+    fn parse_bracket(chars: &mut Peekable<Chars>) -> anyhow::Result<JsonPathSegment> {
+        let segment = match chars.peek().copied() {
+            Some('*') => {
+                chars.next();
+                JsonPathSegment::Wildcard
+            }
+            Some(quote @ ('\'' | '"')) => {
+                chars.next();
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some(c) if c == quote => break,
+                        Some(c) => field.push(c),
+                        None => bail!("Unterminated quoted field in JSON path"),
+                    }
+                }
+                JsonPathSegment::Field(field)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let mut index = String::new();
+                while let Some(c) = chars.peek().copied() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    index.push(c);
+                    chars.next();
+                }
+                JsonPathSegment::Index(
+                    index
+                        .parse()
+                        .map_err(|_| format_err!("Invalid array index {:?} in JSON path", index))?,
+                )
+            }
+            _ => bail!("Expected an index, a quoted field or '*' after '[' in JSON path"),
+        };
+        if chars.next() != Some(']') {
+            bail!("Expected ']' in JSON path");
+        }
+        Ok(segment)
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut chars = s.trim().chars().peekable();
+        let path = Self::parse(&mut chars)?;
+        if chars.next().is_some() {
+            bail!("Unexpected trailing input in JSON path {:?}", s);
+        }
+        Ok(path)
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "$")?;
+        for segment in &self.0 {
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// One or more comma separated [`JsonPath`]s. Applying the filter to a value
+/// keeps everything matched by at least one of the paths.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JsonPathFilter(Vec<JsonPath>);
+
+impl JsonPathFilter {
+    /// The maximum number of paths in a single filter, to bound the work a
+    /// single request can cause.
+    pub const MAX_PATHS: usize = 32;
+
+    pub fn paths(&self) -> &[JsonPath] {
+        &self.0
+    }
+
+    /// Project `value` down to the parts matched by this filter. Returns
+    /// `None` if nothing matched.
+    pub fn apply(&self, value: &Value) -> Option<Value> {
+        let mut selection = Selection::default();
+        for path in &self.0 {
+            selection.insert(path.segments());
+        }
+        selection.project(value)
+    }
+}
+
+impl FromStr for JsonPathFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut chars = s.trim().chars().peekable();
+        let mut paths = vec![JsonPath::parse(&mut chars)?];
+        while let Some(c) = chars.next() {
+            if c != ',' {
+                bail!("Unexpected character {:?} in JSON path filter", c);
+            }
+            while chars.peek().map_or(false, |c| c.is_whitespace()) {
+                chars.next();
+            }
+            paths.push(JsonPath::parse(&mut chars)?);
+        }
+        if paths.len() > Self::MAX_PATHS {
+            bail!(
+                "JSON path filter has {} paths, at most {} are allowed",
+                paths.len(),
+                Self::MAX_PATHS
+            );
+        }
+        Ok(Self(paths))
+    }
+}
+
+impl fmt::Display for JsonPathFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let paths: Vec<String> = self.0.iter().map(|path| path.to_string()).collect();
+        write!(f, "{}", paths.join(","))
+    }
+}
+
+impl Serialize for JsonPathFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonPathFilter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let filter = <String>::deserialize(deserializer)?;
+        filter.parse().map_err(D::Error::custom)
+    }
+}
+
+/// The union of a set of paths, represented as a tree so that every value in
+/// the input only needs to be visited once.
+#[derive(Debug, Default)]
+struct Selection {
+    /// A path ended here, so this whole subtree is selected.
+    whole: bool,
+    fields: BTreeMap<String, Selection>,
+    indices: BTreeMap<usize, Selection>,
+    wildcard: Option<Box<Selection>>,
+}
+
+impl Selection {
+    fn insert(&mut self, segments: &[JsonPathSegment]) {
+        let (first, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                self.whole = true;
+                return;
+            }
+        };
+        let child = match first {
+            JsonPathSegment::Field(field) => self.fields.entry(field.clone()).or_default(),
+            JsonPathSegment::Index(index) => self.indices.entry(*index).or_default(),
+            JsonPathSegment::Wildcard => self.wildcard.get_or_insert_with(Default::default),
+        };
+        child.insert(rest);
+    }
+
+    fn project(&self, value: &Value) -> Option<Value> {
+        Self::project_all(&[self], value)
+    }
+
+    /// Project `value` using every selection in `selections`. More than one
+    /// selection applies when a wildcard and a specific field / index both
+    /// match the same value.
+    fn project_all(selections: &[&Selection], value: &Value) -> Option<Value> {
+        if selections.iter().any(|selection| selection.whole) {
+            return Some(value.clone());
+        }
+        match value {
+            Value::Object(map) => {
+                let mut out = Map::new();
+                for (key, value) in map {
+                    let children: Vec<&Selection> = selections
+                        .iter()
+                        .flat_map(|s| s.fields.get(key).into_iter().chain(s.wildcard.as_deref()))
+                        .collect();
+                    if children.is_empty() {
+                        continue;
+                    }
+                    if let Some(projected) = Self::project_all(&children, value) {
+                        out.insert(key.clone(), projected);
+                    }
+                }
+                (!out.is_empty()).then(|| Value::Object(out))
+            }
+            Value::Array(values) => {
+                let mut out = vec![];
+                for (index, value) in values.iter().enumerate() {
+                    let children: Vec<&Selection> = selections
+                        .iter()
+                        .flat_map(|s| {
+                            s.indices
+                                .get(&index)
+                                .into_iter()
+                                .chain(s.wildcard.as_deref())
+                        })
+                        .collect();
+                    if children.is_empty() {
+                        continue;
+                    }
+                    if let Some(projected) = Self::project_all(&children, value) {
+                        out.push(projected);
+                    }
+                }
+                (!out.is_empty()).then(|| Value::Array(out))
+            }
+            // The path goes deeper than the value does.
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonPath, JsonPathFilter, JsonPathSegment};
+    use serde_json::json;
+
+    fn filter(s: &str) -> JsonPathFilter {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_json_path() {
+        let path: JsonPath = "$.coin['value'][2][*].*".parse().unwrap();
+        assert_eq!(
+            path.segments(),
+            &[
+                JsonPathSegment::Field("coin".to_string()),
+                JsonPathSegment::Field("value".to_string()),
+                JsonPathSegment::Index(2),
+                JsonPathSegment::Wildcard,
+                JsonPathSegment::Wildcard,
+            ]
+        );
+        assert_eq!(path.to_string(), "$.coin.value[2][*][*]");
+        assert_eq!("$".parse::<JsonPath>().unwrap().segments(), &[]);
+    }
+
+    #[test]
+    fn test_parse_invalid_json_path() {
+        for invalid in [
+            "",
+            "coin",
+            "$.",
+            "$.coin.",
+            "$[",
+            "$[x]",
+            "$['coin'",
+            "$[1",
+            "$.co-in",
+            "$.coin $.frozen",
+        ] {
+            assert!(invalid.parse::<JsonPathFilter>().is_err(), "{}", invalid);
+        }
+        let too_many = vec!["$.a"; JsonPathFilter::MAX_PATHS + 1].join(",");
+        assert!(too_many.parse::<JsonPathFilter>().is_err());
+    }
+
+    #[test]
+    fn test_filter_roundtrip() {
+        let f = filter("$.coin.value, $.frozen");
+        assert_eq!(f.paths().len(), 2);
+        assert_eq!(f.to_string(), "$.coin.value,$.frozen");
+        assert_eq!(filter(&f.to_string()), f);
+    }
+
+    #[test]
+    fn test_apply_filter() {
+        let value = json!({
+            "coin": {"value": "100"},
+            "frozen": false,
+            "deposit_events": {
+                "counter": "2",
+                "guid": {"id": {"addr": "0x1", "creation_num": "3"}}
+            },
+            "items": [{"a": 1, "b": 2}, {"a": 3, "b": 4}, {"a": 5, "b": 6}],
+        });
+
+        assert_eq!(
+            filter("$.coin.value").apply(&value),
+            Some(json!({"coin": {"value": "100"}}))
+        );
+        assert_eq!(
+            filter("$.coin.value,$.frozen").apply(&value),
+            Some(json!({"coin": {"value": "100"}, "frozen": false}))
+        );
+        assert_eq!(
+            filter("$.deposit_events.*.id.addr").apply(&value),
+            Some(json!({"deposit_events": {"guid": {"id": {"addr": "0x1"}}}}))
+        );
+        assert_eq!(
+            filter("$.items[*].a").apply(&value),
+            Some(json!({"items": [{"a": 1}, {"a": 3}, {"a": 5}]}))
+        );
+        assert_eq!(
+            filter("$.items[2],$.items[0].b").apply(&value),
+            Some(json!({"items": [{"b": 2}, {"a": 5, "b": 6}]}))
+        );
+        assert_eq!(
+            filter("$.items[*].a,$.items[1]").apply(&value),
+            Some(json!({"items": [{"a": 1}, {"a": 3, "b": 4}, {"a": 5}]}))
+        );
+        assert_eq!(filter("$").apply(&value), Some(value.clone()));
+        assert_eq!(filter("$.missing").apply(&value), None);
+        assert_eq!(filter("$.coin.value.deeper").apply(&value), None);
+        assert_eq!(filter("$.items[7]").apply(&value), None);
+    }
+}
@@ -3,48 +3,68 @@
 
 mod account;
 mod address;
+mod bcs_schema;
 mod block;
 mod bytecode;
 mod convert;
 mod derives;
 mod error;
 mod event_key;
+mod governance;
 mod hash;
 mod headers;
 mod index;
+mod json_path;
 mod ledger_info;
+mod mempool;
 pub mod mime_types;
 mod move_types;
+mod proof;
+mod state_value;
 mod table;
 mod transaction;
+mod view;
 mod wrappers;
 
-pub use account::AccountData;
+pub use account::{AccountData, AccountTransactionFacets, EntryFunctionCount};
 pub use address::Address;
+pub use bcs_schema::BcsSchema;
 pub use block::Block;
 pub use block::BlockInfo;
 pub use bytecode::Bytecode;
 pub use convert::{new_vm_utf8_string, AsConverter, MoveConverter};
 pub use error::{AptosError, AptosErrorCode};
 pub use event_key::EventKey;
+pub use governance::{
+    GovernanceConfig, GovernanceProposal, GovernanceProposalState, GovernanceVotingRecord,
+    StakePoolSummary, ValidatorState,
+};
 pub use hash::HashValue;
 pub use headers::*;
 pub use index::IndexResponse;
+pub use json_path::{JsonPath, JsonPathFilter, JsonPathSegment};
 pub use ledger_info::LedgerInfo;
+pub use mempool::{GasPriceBand, MempoolFeeStatistics};
 pub use move_types::{
     EntryFunctionId, HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
-    MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveResource,
-    MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType, MoveValue, U128, U64,
+    MoveFunctionVisibility, MoveModule, MoveModuleAbi, MoveModuleBytecode, MoveModuleId,
+    MoveResource, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
+    MoveValue, U128, U64,
 };
+pub use proof::ResponseProof;
+pub use state_value::RawStateValueRequest;
 pub use table::TableItemRequest;
 pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
     DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
-    GenesisPayload, GenesisTransaction, ModuleBundlePayload, MultiEd25519Signature,
-    PendingTransaction, ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction,
-    TransactionData, TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
+    GenesisPayload, GenesisTransaction, KeylessSignature, ModuleBundlePayload,
+    MultiEd25519Signature, MultiKeySignature, PendingTransaction, ScriptPayload, ScriptWriteSet,
+    SubmitTransactionRequest, Transaction, TransactionAuxiliaryData, TransactionData,
+    TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
     TransactionSignature, TransactionSigningMessage, UserCreateSigningMessageRequest,
-    UserTransaction, UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet,
-    WriteSetChange, WriteSetPayload, WriteTableItem,
+    UserTransaction, UserTransactionRequest, ValidatorTransaction, VersionedEvent,
+    WebAuthnSignature, WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload,
+    WriteTableItem,
 };
+pub use view::ViewRequest;
 pub use wrappers::IdentifierWrapper;
@@ -0,0 +1,33 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::U64;
+use poem_openapi::Object as PoemObject;
+use serde::{Deserialize, Serialize};
+
+/// Summary of the transactions pending in the mempool of the node, by gas unit price.
+///
+/// `bands` are in increasing order of gas unit price. `num_evicted_recently` and
+/// `num_rejected_mempool_full_recently` count the transactions evicted to make room
+/// for others, and rejected because mempool was full, over the last
+/// `eviction_window_secs` seconds.
+#[derive(Clone, Debug, Deserialize, PartialEq, PoemObject, Serialize)]
+pub struct MempoolFeeStatistics {
+    pub bands: Vec<GasPriceBand>,
+    pub num_evicted_recently: U64,
+    pub num_rejected_mempool_full_recently: U64,
+    pub eviction_window_secs: U64,
+}
+
+/// The transactions in mempool with a gas unit price from `min_gas_unit_price`
+/// to `max_gas_unit_price` (exclusive, absent for the last band).
+///
+/// `estimated_inclusion_delay_ms` is a moving average of the time the committed
+/// transactions of the band spent in mempool, absent until one of them commits.
+#[derive(Clone, Debug, Deserialize, PartialEq, PoemObject, Serialize)]
+pub struct GasPriceBand {
+    pub min_gas_unit_price: U64,
+    pub max_gas_unit_price: Option<U64>,
+    pub num_txns: U64,
+    pub estimated_inclusion_delay_ms: Option<U64>,
+}
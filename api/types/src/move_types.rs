@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{Address, Bytecode, IdentifierWrapper};
+use crate::{Address, Bytecode, IdentifierWrapper, JsonPathFilter, ResponseProof};
 use anyhow::{bail, format_err};
 use aptos_types::{account_config::CORE_CODE_ADDRESS, event::EventKey, transaction::Module};
 use move_deps::{
@@ -38,6 +38,26 @@ pub struct MoveResource {
     #[oai(rename = "type")]
     pub typ: MoveStructTag,
     pub data: MoveStructValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub proof: Option<ResponseProof>,
+}
+
+impl MoveResource {
+    /// Project the resource data down to the fields matched by `filter`. The
+    /// paths in the filter are rooted at the resource data, so `$.coin.value`
+    /// selects `data.coin.value`.
+    pub fn filter(self, filter: &JsonPathFilter) -> anyhow::Result<Self> {
+        let data = match filter.apply(&serde_json::to_value(&self.data)?) {
+            Some(value) => serde_json::from_value(value)?,
+            None => MoveStructValue(BTreeMap::new()),
+        };
+        Ok(Self {
+            typ: self.typ,
+            data,
+            proof: self.proof,
+        })
+    }
 }
 
 impl TryFrom<AnnotatedMoveStruct> for MoveResource {
@@ -47,6 +67,7 @@ impl TryFrom<AnnotatedMoveStruct> for MoveResource {
         Ok(Self {
             typ: s.type_.clone().into(),
             data: s.try_into()?,
+            proof: None,
         })
     }
 }
@@ -656,6 +677,51 @@ impl From<CompiledModule> for MoveModule {
     }
 }
 
+/// The ABI of a module, limited to what clients need to interact with it:
+/// the functions that can be called from outside the chain and the structs
+/// their arguments and return values are built from.
+///
+/// `entry_functions` contains every entry function, including private ones,
+/// as these can all be called by transactions. `view_functions` contains the
+/// public non-entry functions that return values, which clients can use to
+/// read state, e.g. through simulation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct MoveModuleAbi {
+    pub address: Address,
+    pub name: IdentifierWrapper,
+    pub entry_functions: Vec<MoveFunction>,
+    pub view_functions: Vec<MoveFunction>,
+    pub structs: Vec<MoveStruct>,
+}
+
+impl From<CompiledModule> for MoveModuleAbi {
+    fn from(m: CompiledModule) -> Self {
+        let (address, name) = <(AccountAddress, Identifier)>::from(m.self_id());
+        Self {
+            address: address.into(),
+            name: name.into(),
+            entry_functions: m
+                .function_defs
+                .iter()
+                .filter(|def| def.is_entry)
+                .map(|def| m.new_move_function(def))
+                .collect(),
+            view_functions: m
+                .function_defs
+                .iter()
+                .filter(|def| def.visibility == Visibility::Public && !def.is_entry)
+                .map(|def| m.new_move_function(def))
+                .filter(|function| !function.return_.is_empty())
+                .collect(),
+            structs: m
+                .struct_defs
+                .iter()
+                .map(|def| m.new_move_struct(def))
+                .collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct MoveModuleId {
     pub address: Address,
@@ -0,0 +1,35 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{HexEncodedBytes, U64};
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use poem_openapi::Object as PoemObject;
+use serde::{Deserialize, Serialize};
+
+/// Proof that the data in a response is part of the ledger, so that light
+/// clients can verify responses without trusting the node serving them.
+///
+/// `proof` proves the data at `version`, and is a BCS encoded `StateValueWithProof`
+/// for account state or a BCS encoded `TransactionInfoWithProof` for transactions.
+/// Either is verified against `ledger_info`, a BCS encoded `LedgerInfoWithSignatures`
+/// whose signatures should be checked against the validator set of its epoch.
+#[derive(Clone, Debug, Deserialize, PartialEq, PoemObject, Serialize)]
+pub struct ResponseProof {
+    pub version: U64,
+    pub proof: HexEncodedBytes,
+    pub ledger_info: HexEncodedBytes,
+}
+
+impl ResponseProof {
+    pub fn new<P: Serialize>(
+        version: u64,
+        proof: &P,
+        ledger_info: &LedgerInfoWithSignatures,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            version: version.into(),
+            proof: bcs::to_bytes(proof)?.into(),
+            ledger_info: bcs::to_bytes(ledger_info)?.into(),
+        })
+    }
+}
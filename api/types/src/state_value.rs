@@ -0,0 +1,12 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::HexEncodedBytes;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// Request for the raw state value stored under `key`, a BCS encoded `StateKey`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct RawStateValueRequest {
+    pub key: HexEncodedBytes,
+}
@@ -3,22 +3,28 @@
 
 use crate::{
     Address, EntryFunctionId, EventKey, HashValue, HexEncodedBytes, MoveModuleBytecode,
-    MoveModuleId, MoveResource, MoveScriptBytecode, MoveStructTag, MoveType, MoveValue, U64,
+    MoveModuleId, MoveResource, MoveScriptBytecode, MoveStructTag, MoveType, MoveValue,
+    ResponseProof, U64,
 };
 
 use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey},
     multi_ed25519::{self, MultiEd25519PublicKey},
+    secp256r1_ecdsa,
 };
 use aptos_types::{
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
     contract_event::{ContractEvent, EventWithVersion},
+    keyless::{self, KeylessPublicKey},
     transaction::{
         authenticator::{AccountAuthenticator, TransactionAuthenticator},
+        multi_key,
+        webauthn::WebAuthnAssertion,
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
+    validator_txn::ValidatorTransaction as ValidatorTransactionType,
 };
 
 use poem_openapi::{Object, Union};
@@ -60,6 +66,9 @@ pub struct TransactionOnChainData {
     pub events: Vec<ContractEvent>,
     pub accumulator_root_hash: aptos_crypto::HashValue,
     pub changes: aptos_types::write_set::WriteSet,
+    /// Only known once the transaction is committed, and only if the node recorded it
+    #[serde(default)]
+    pub auxiliary_data: Option<aptos_types::transaction::TransactionAuxiliaryData>,
 }
 
 impl From<(TransactionWithProof, aptos_crypto::HashValue)> for TransactionOnChainData {
@@ -71,6 +80,7 @@ impl From<(TransactionWithProof, aptos_crypto::HashValue)> for TransactionOnChai
             events: txn.events.unwrap_or_default(),
             accumulator_root_hash,
             changes: Default::default(),
+            auxiliary_data: None,
         }
     }
 }
@@ -96,6 +106,7 @@ impl
             events: txn.events.unwrap_or_default(),
             accumulator_root_hash,
             changes: txn_output.write_set().clone(),
+            auxiliary_data: None,
         }
     }
 }
@@ -127,6 +138,7 @@ impl
             events,
             accumulator_root_hash,
             changes: write_set,
+            auxiliary_data: None,
         }
     }
 }
@@ -140,6 +152,7 @@ pub enum Transaction {
     GenesisTransaction(GenesisTransaction),
     BlockMetadataTransaction(BlockMetadataTransaction),
     StateCheckpointTransaction(StateCheckpointTransaction),
+    ValidatorTransaction(ValidatorTransaction),
 }
 
 impl Transaction {
@@ -150,6 +163,7 @@ impl Transaction {
             Transaction::PendingTransaction(_) => 0,
             Transaction::GenesisTransaction(_) => 0,
             Transaction::StateCheckpointTransaction(txn) => txn.timestamp.0,
+            Transaction::ValidatorTransaction(txn) => txn.timestamp.0,
         }
     }
 
@@ -160,6 +174,7 @@ impl Transaction {
             Transaction::PendingTransaction(_) => None,
             Transaction::GenesisTransaction(txn) => Some(txn.info.version.into()),
             Transaction::StateCheckpointTransaction(txn) => Some(txn.info.version.into()),
+            Transaction::ValidatorTransaction(txn) => Some(txn.info.version.into()),
         }
     }
 
@@ -170,6 +185,7 @@ impl Transaction {
             Transaction::PendingTransaction(_txn) => false,
             Transaction::GenesisTransaction(txn) => txn.info.success,
             Transaction::StateCheckpointTransaction(txn) => txn.info.success,
+            Transaction::ValidatorTransaction(txn) => txn.info.success,
         }
     }
 
@@ -184,6 +200,7 @@ impl Transaction {
             Transaction::PendingTransaction(_txn) => "pending".to_owned(),
             Transaction::GenesisTransaction(txn) => txn.info.vm_status.clone(),
             Transaction::StateCheckpointTransaction(txn) => txn.info.vm_status.clone(),
+            Transaction::ValidatorTransaction(txn) => txn.info.vm_status.clone(),
         }
     }
 
@@ -194,6 +211,7 @@ impl Transaction {
             Transaction::GenesisTransaction(_) => "genesis_transaction",
             Transaction::BlockMetadataTransaction(_) => "block_metadata_transaction",
             Transaction::StateCheckpointTransaction(_) => "state_checkpoint_transaction",
+            Transaction::ValidatorTransaction(_) => "validator_transaction",
         }
     }
 
@@ -206,6 +224,20 @@ impl Transaction {
             }
             Transaction::GenesisTransaction(txn) => &txn.info,
             Transaction::StateCheckpointTransaction(txn) => &txn.info,
+            Transaction::ValidatorTransaction(txn) => &txn.info,
+        })
+    }
+
+    pub fn transaction_info_mut(&mut self) -> anyhow::Result<&mut TransactionInfo> {
+        Ok(match self {
+            Transaction::UserTransaction(txn) => &mut txn.info,
+            Transaction::BlockMetadataTransaction(txn) => &mut txn.info,
+            Transaction::PendingTransaction(_txn) => {
+                bail!("pending transaction does not have TransactionInfo")
+            }
+            Transaction::GenesisTransaction(txn) => &mut txn.info,
+            Transaction::StateCheckpointTransaction(txn) => &mut txn.info,
+            Transaction::ValidatorTransaction(txn) => &mut txn.info,
         })
     }
 }
@@ -273,6 +305,25 @@ impl From<(&BlockMetadata, TransactionInfo, Vec<Event>)> for Transaction {
     }
 }
 
+impl From<(&ValidatorTransactionType, TransactionInfo, Vec<Event>, u64)> for Transaction {
+    fn from(
+        (txn, info, events, timestamp): (
+            &ValidatorTransactionType,
+            TransactionInfo,
+            Vec<Event>,
+            u64,
+        ),
+    ) -> Self {
+        Transaction::ValidatorTransaction(ValidatorTransaction {
+            info,
+            kind: txn.kind_name().to_string(),
+            payload: txn.payload().to_vec().into(),
+            events,
+            timestamp: timestamp.into(),
+        })
+    }
+}
+
 impl From<(&SignedTransaction, TransactionPayload)> for UserTransactionRequest {
     fn from((txn, payload): (&SignedTransaction, TransactionPayload)) -> Self {
         Self {
@@ -298,6 +349,34 @@ pub struct TransactionInfo {
     pub vm_status: String,
     pub accumulator_root_hash: HashValue,
     pub changes: Vec<WriteSetChange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub proof: Option<ResponseProof>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub auxiliary_data: Option<TransactionAuxiliaryData>,
+}
+
+/// The block a transaction was executed in, and the gas unit price it paid
+///
+/// It's only available for transactions committed by nodes recording it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionAuxiliaryData {
+    pub block_hash: HashValue,
+    pub block_height: U64,
+    pub proposer: Address,
+    pub gas_unit_price: U64,
+}
+
+impl From<&aptos_types::transaction::TransactionAuxiliaryData> for TransactionAuxiliaryData {
+    fn from(data: &aptos_types::transaction::TransactionAuxiliaryData) -> Self {
+        Self {
+            block_hash: data.block_hash().into(),
+            block_height: data.block_height().into(),
+            proposer: data.proposer().into(),
+            gas_unit_price: data.gas_unit_price().into(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
@@ -337,6 +416,20 @@ pub struct StateCheckpointTransaction {
     pub timestamp: U64,
 }
 
+/// A transaction through which validators record data they agreed on, e.g. the transcript of a
+/// distributed key generation. It has no sender, signature or gas.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct ValidatorTransaction {
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub info: TransactionInfo,
+    /// The kind of the validator transaction, e.g. `dkg_transcript`
+    pub kind: String,
+    pub payload: HexEncodedBytes,
+    pub events: Vec<Event>,
+    pub timestamp: U64,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
 pub struct SubmitTransactionRequest {
     #[serde(flatten)]
@@ -489,6 +582,10 @@ pub struct EntryFunctionPayload {
     pub type_arguments: Vec<MoveType>,
     // TODO: Use the real data here, not a JSON representation.
     pub arguments: Vec<serde_json::Value>,
+    // Set for orderless transactions, which are protected from replays by this nonce instead of
+    // the sequence number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_nonce: Option<U64>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
@@ -637,6 +734,9 @@ pub enum TransactionSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
     MultiAgentSignature(MultiAgentSignature),
+    WebAuthnSignature(WebAuthnSignature),
+    KeylessSignature(KeylessSignature),
+    MultiKeySignature(MultiKeySignature),
 }
 
 impl TryFrom<TransactionSignature> for TransactionAuthenticator {
@@ -646,6 +746,9 @@ impl TryFrom<TransactionSignature> for TransactionAuthenticator {
             TransactionSignature::Ed25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiEd25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiAgentSignature(sig) => sig.try_into()?,
+            TransactionSignature::WebAuthnSignature(sig) => sig.try_into()?,
+            TransactionSignature::KeylessSignature(sig) => sig.try_into()?,
+            TransactionSignature::MultiKeySignature(sig) => sig.try_into()?,
         })
     }
 }
@@ -766,12 +869,179 @@ impl TryFrom<MultiEd25519Signature> for AccountAuthenticator {
     }
 }
 
+/// A WebAuthn assertion from a secp256r1 authenticator, e.g. a passkey.
+///
+/// `public_key` is in uncompressed SEC1 form, and `signature` is the
+/// concatenation of `r` and `s`, where `s` must be in the lower half of
+/// the scalar field.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct WebAuthnSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+    pub authenticator_data: HexEncodedBytes,
+    pub client_data_json: HexEncodedBytes,
+}
+
+impl WebAuthnSignature {
+    fn into_parts(self) -> anyhow::Result<(secp256r1_ecdsa::PublicKey, WebAuthnAssertion)> {
+        let WebAuthnSignature {
+            public_key,
+            signature,
+            authenticator_data,
+            client_data_json,
+        } = self;
+        Ok((
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a secp256r1 PublicKey")?,
+            WebAuthnAssertion::new(
+                signature
+                    .inner()
+                    .try_into()
+                    .context("Failed to parse given signature as a secp256r1 Signature")?,
+                authenticator_data.0,
+                client_data_json.0,
+            ),
+        ))
+    }
+}
+
+impl TryFrom<WebAuthnSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: WebAuthnSignature) -> Result<Self, Self::Error> {
+        let (public_key, assertion) = value.into_parts()?;
+        Ok(TransactionAuthenticator::web_authn(public_key, assertion))
+    }
+}
+
+impl TryFrom<WebAuthnSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: WebAuthnSignature) -> Result<Self, Self::Error> {
+        let (public_key, assertion) = value.into_parts()?;
+        Ok(AccountAuthenticator::web_authn(public_key, assertion))
+    }
+}
+
+/// A signature of a keyless account, with a proof of an OpenID Connect sign-in.
+///
+/// `public_key` and `signature` are the BCS-serialized `KeylessPublicKey`
+/// and `KeylessSignature`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct KeylessSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+}
+
+impl KeylessSignature {
+    fn into_parts(self) -> anyhow::Result<(KeylessPublicKey, keyless::KeylessSignature)> {
+        Ok((
+            bcs::from_bytes(self.public_key.inner())
+                .context("Failed to parse given public_key bytes as a KeylessPublicKey")?,
+            bcs::from_bytes(self.signature.inner())
+                .context("Failed to parse given signature bytes as a KeylessSignature")?,
+        ))
+    }
+}
+
+impl TryFrom<KeylessSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: KeylessSignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.into_parts()?;
+        Ok(TransactionAuthenticator::keyless(public_key, signature))
+    }
+}
+
+impl TryFrom<KeylessSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: KeylessSignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.into_parts()?;
+        Ok(AccountAuthenticator::keyless(public_key, signature))
+    }
+}
+
+/// A K-of-N signature of a multi-key account, whose public keys may be of
+/// mixed schemes.
+///
+/// `public_keys` and `signatures` are the BCS-serialized `AnyPublicKey`s and
+/// `AnySignature`s, and `signature_indices` are the indices of the public keys
+/// that the signatures are from, in increasing order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct MultiKeySignature {
+    pub public_keys: Vec<HexEncodedBytes>,
+    pub signatures: Vec<HexEncodedBytes>,
+    pub signatures_required: u8,
+    pub signature_indices: Vec<u8>,
+}
+
+impl MultiKeySignature {
+    fn into_parts(self) -> anyhow::Result<(multi_key::MultiKey, multi_key::MultiKeySignature)> {
+        let MultiKeySignature {
+            public_keys,
+            signatures,
+            signatures_required,
+            signature_indices,
+        } = self;
+        if signatures.len() != signature_indices.len() {
+            bail!("The number of signatures and of signature indices must be equal");
+        }
+
+        let public_keys = public_keys
+            .iter()
+            .map(|key| {
+                bcs::from_bytes(key.inner())
+                    .context("Failed to parse given public_keys bytes as an AnyPublicKey")
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let signatures = signature_indices
+            .into_iter()
+            .zip(signatures.iter())
+            .map(|(index, signature)| {
+                Ok((
+                    index,
+                    bcs::from_bytes(signature.inner())
+                        .context("Failed to parse given signatures bytes as an AnySignature")?,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok((
+            multi_key::MultiKey::new(public_keys, signatures_required)?,
+            multi_key::MultiKeySignature::new(signatures)?,
+        ))
+    }
+}
+
+impl TryFrom<MultiKeySignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MultiKeySignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.into_parts()?;
+        Ok(TransactionAuthenticator::multi_key(public_key, signature))
+    }
+}
+
+impl TryFrom<MultiKeySignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: MultiKeySignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.into_parts()?;
+        Ok(AccountAuthenticator::multi_key(public_key, signature))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
 pub enum AccountSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
+    WebAuthnSignature(WebAuthnSignature),
+    KeylessSignature(KeylessSignature),
+    MultiKeySignature(MultiKeySignature),
 }
 
 impl TryFrom<AccountSignature> for AccountAuthenticator {
@@ -781,6 +1051,9 @@ impl TryFrom<AccountSignature> for AccountAuthenticator {
         Ok(match sig {
             AccountSignature::Ed25519Signature(s) => s.try_into()?,
             AccountSignature::MultiEd25519Signature(s) => s.try_into()?,
+            AccountSignature::WebAuthnSignature(s) => s.try_into()?,
+            AccountSignature::KeylessSignature(s) => s.try_into()?,
+            AccountSignature::MultiKeySignature(s) => s.try_into()?,
         })
     }
 }
@@ -853,6 +1126,53 @@ impl
     }
 }
 
+impl From<(&secp256r1_ecdsa::PublicKey, &WebAuthnAssertion)> for WebAuthnSignature {
+    fn from((pk, assertion): (&secp256r1_ecdsa::PublicKey, &WebAuthnAssertion)) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: assertion.signature().to_bytes().to_vec().into(),
+            authenticator_data: assertion.authenticator_data().to_vec().into(),
+            client_data_json: assertion.client_data_json().to_vec().into(),
+        }
+    }
+}
+
+impl From<(&KeylessPublicKey, &keyless::KeylessSignature)> for KeylessSignature {
+    fn from((pk, sig): (&KeylessPublicKey, &keyless::KeylessSignature)) -> Self {
+        Self {
+            public_key: pk.to_bytes().into(),
+            signature: sig.to_bytes().into(),
+        }
+    }
+}
+
+impl From<(&multi_key::MultiKey, &multi_key::MultiKeySignature)> for MultiKeySignature {
+    fn from((pk, sig): (&multi_key::MultiKey, &multi_key::MultiKeySignature)) -> Self {
+        Self {
+            public_keys: pk
+                .public_keys()
+                .iter()
+                .map(|k| {
+                    bcs::to_bytes(k)
+                        .expect("AnyPublicKey serialization cannot fail")
+                        .into()
+                })
+                .collect(),
+            signatures: sig
+                .signatures()
+                .iter()
+                .map(|(_, s)| {
+                    bcs::to_bytes(s)
+                        .expect("AnySignature serialization cannot fail")
+                        .into()
+                })
+                .collect(),
+            signatures_required: pk.signatures_required(),
+            signature_indices: sig.signatures().iter().map(|(index, _)| *index).collect(),
+        }
+    }
+}
+
 impl From<&AccountAuthenticator> for AccountSignature {
     fn from(auth: &AccountAuthenticator) -> Self {
         use AccountAuthenticator::*;
@@ -865,6 +1185,18 @@ impl From<&AccountAuthenticator> for AccountSignature {
                 public_key,
                 signature,
             } => Self::MultiEd25519Signature((public_key, signature).into()),
+            WebAuthn {
+                public_key,
+                signature,
+            } => Self::WebAuthnSignature((public_key, signature).into()),
+            Keyless {
+                public_key,
+                signature,
+            } => Self::KeylessSignature((public_key, signature).into()),
+            MultiKey {
+                public_key,
+                signature,
+            } => Self::MultiKeySignature((public_key, signature).into()),
         }
     }
 }
@@ -910,6 +1242,18 @@ impl From<TransactionAuthenticator> for TransactionSignature {
             } => Self::MultiAgentSignature(
                 (sender, secondary_signer_addresses, secondary_signers).into(),
             ),
+            WebAuthn {
+                public_key,
+                signature,
+            } => Self::WebAuthnSignature((public_key, signature).into()),
+            Keyless {
+                public_key,
+                signature,
+            } => Self::KeylessSignature((public_key, signature).into()),
+            MultiKey {
+                public_key,
+                signature,
+            } => Self::MultiKeySignature((public_key, signature).into()),
         }
     }
 }
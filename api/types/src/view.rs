@@ -0,0 +1,15 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{EntryFunctionId, HexEncodedBytes, MoveType};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// Request to call a view function, i.e. a public function which returns values.
+/// The `arguments` are BCS encoded, as the function takes them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Object)]
+pub struct ViewRequest {
+    pub function: EntryFunctionId,
+    pub type_arguments: Vec<MoveType>,
+    pub arguments: Vec<HexEncodedBytes>,
+}
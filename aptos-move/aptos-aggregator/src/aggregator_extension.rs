@@ -1,7 +1,9 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::delta_change_set::{addition, deserialize, serialize, subtraction, DeltaOp};
+use crate::delta_change_set::{
+    addition, deserialize, serialize, subtraction, DeltaOp, SnapshotFormat,
+};
 use aptos_crypto::hash::DefaultHasher;
 use aptos_types::vm_status::StatusCode;
 use better_any::{Tid, TidAble};
@@ -38,7 +40,7 @@ pub enum AggregatorState {
 }
 
 /// Uniquely identifies each aggregator instance in storage.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AggregatorID {
     // A handle that is shared accross all aggregator instances created by the
     // same `AggregatorFactory` and which is used for fine-grained storage
@@ -140,6 +142,40 @@ impl Aggregator {
     }
 }
 
+/// Internal snapshot data structure: the value of an aggregator when the
+/// snapshot was taken in the current context.
+struct Snapshot {
+    // The aggregator of which the snapshot was taken.
+    source: AggregatorID,
+    // Describes the value of the aggregator when the snapshot was taken: if
+    // the state is `Data`, the value itself, otherwise the delta to apply to
+    // the value of the aggregator in storage.
+    value: u128,
+    state: AggregatorState,
+    limit: u128,
+    // Describes how the value of the snapshot is stored.
+    format: SnapshotFormat,
+}
+
+impl Snapshot {
+    /// Returns the value of the snapshot, going to storage if the snapshot
+    /// holds a delta.
+    fn materialize(&self, context: &NativeAggregatorContext) -> PartialVMResult<u128> {
+        match self.state {
+            AggregatorState::Data => Ok(self.value),
+            AggregatorState::PositiveDelta => {
+                let mut aggregator = Aggregator {
+                    value: self.value,
+                    state: self.state,
+                    limit: self.limit,
+                };
+                aggregator.materialize(context, &self.source)?;
+                Ok(aggregator.value)
+            }
+        }
+    }
+}
+
 /// Stores all information about aggregators (how many have been created or
 /// removed), what are their states, etc. per context (i.e. single
 /// transaction).
@@ -153,6 +189,14 @@ struct AggregatorData {
     destroyed_aggregators: BTreeSet<AggregatorID>,
     // All aggregator instances that exist in the current context.
     aggregators: BTreeMap<AggregatorID, Aggregator>,
+    // All snapshots taken in the current context which still exist.
+    snapshots: BTreeMap<AggregatorID, Snapshot>,
+    // All snapshots that were taken somewhere else and destroyed in the
+    // current context.
+    destroyed_snapshots: BTreeSet<AggregatorID>,
+    // The number of snapshots taken in the current context, used to generate
+    // their keys.
+    num_snapshots: u128,
 }
 
 impl AggregatorData {
@@ -204,6 +248,35 @@ impl AggregatorData {
             self.destroyed_aggregators.insert(id);
         }
     }
+
+    /// Takes a snapshot with `id` of the aggregator with `source` id, in a
+    /// given `format`.
+    fn create_snapshot(
+        &mut self,
+        id: AggregatorID,
+        source: AggregatorID,
+        limit: u128,
+        format: SnapshotFormat,
+    ) {
+        let aggregator = self.get_aggregator(source, limit);
+        let snapshot = Snapshot {
+            source,
+            value: aggregator.value,
+            state: aggregator.state,
+            limit,
+            format,
+        };
+        self.snapshots.insert(id, snapshot);
+        self.num_snapshots += 1;
+    }
+
+    /// If snapshot has been taken in this context, it is removed. Otherwise,
+    /// it is marked for deletion.
+    fn remove_snapshot(&mut self, id: AggregatorID) {
+        if self.snapshots.remove(&id).is_none() {
+            self.destroyed_snapshots.insert(id);
+        }
+    }
 }
 
 /// Represents a single aggregator change.
@@ -217,11 +290,28 @@ pub enum AggregatorChange {
     Delete,
 }
 
+/// Represents a single snapshot change.
+#[derive(Clone, Debug)]
+pub enum SnapshotChange {
+    // A value should be written to storage.
+    Write(Vec<u8>),
+    // The value should be resolved by applying a delta to the value of the
+    // `source` aggregator in storage, and then written to storage.
+    Resolve {
+        source: AggregatorID,
+        delta: DeltaOp,
+        format: SnapshotFormat,
+    },
+    // A value should be deleted from the storage.
+    Delete,
+}
+
 /// Represents changes made by all aggregators during this context. This change
 /// set can be converted into appropriate `WriteSet` and `DeltaChangeSet` by the
 /// user, e.g. VM session.
 pub struct AggregatorChangeSet {
     pub changes: BTreeMap<AggregatorID, AggregatorChange>,
+    pub snapshots: BTreeMap<AggregatorID, SnapshotChange>,
 }
 
 /// Native context that can be attached to VM `NativeContextExtensions`.
@@ -254,6 +344,8 @@ impl<'a> NativeAggregatorContext<'a> {
         let AggregatorData {
             destroyed_aggregators,
             aggregators,
+            snapshots: taken_snapshots,
+            destroyed_snapshots,
             ..
         } = aggregator_data.into_inner();
 
@@ -282,7 +374,33 @@ impl<'a> NativeAggregatorContext<'a> {
             changes.insert(id, AggregatorChange::Delete);
         }
 
-        AggregatorChangeSet { changes }
+        // Snapshots with known values are written, others are resolved when
+        // deltas are applied.
+        let mut snapshots = BTreeMap::new();
+        for (id, snapshot) in taken_snapshots {
+            let Snapshot {
+                source,
+                value,
+                state,
+                limit,
+                format,
+            } = snapshot;
+
+            let change = match state {
+                AggregatorState::Data => SnapshotChange::Write(format.serialize(value)),
+                AggregatorState::PositiveDelta => SnapshotChange::Resolve {
+                    source,
+                    delta: DeltaOp::Addition { value, limit },
+                    format,
+                },
+            };
+            snapshots.insert(id, change);
+        }
+        for id in destroyed_snapshots {
+            snapshots.insert(id, SnapshotChange::Delete);
+        }
+
+        AggregatorChangeSet { changes, snapshots }
     }
 }
 
@@ -303,6 +421,41 @@ pub fn aggregator_natives(aggregator_addr: AccountAddress) -> NativeFunctionTabl
                 "new_aggregator",
                 Arc::new(native_new_aggregator),
             ),
+            (
+                "aggregator_factory",
+                "new_aggregator_v2",
+                Arc::new(native_new_aggregator_v2),
+            ),
+            ("aggregator_v2", "add", Arc::new(native_add_v2)),
+            ("aggregator_v2", "sub", Arc::new(native_sub_v2)),
+            ("aggregator_v2", "read", Arc::new(native_read_v2)),
+            ("aggregator_v2", "destroy", Arc::new(native_destroy_v2)),
+            ("aggregator_v2", "snapshot", Arc::new(native_snapshot)),
+            (
+                "aggregator_v2",
+                "read_snapshot",
+                Arc::new(native_read_snapshot),
+            ),
+            (
+                "aggregator_v2",
+                "derive_string_concat",
+                Arc::new(native_derive_string_concat),
+            ),
+            (
+                "aggregator_v2",
+                "read_derived_string",
+                Arc::new(native_read_derived_string),
+            ),
+            (
+                "aggregator_v2",
+                "destroy_snapshot",
+                Arc::new(native_destroy_snapshot),
+            ),
+            (
+                "aggregator_v2",
+                "destroy_derived_string",
+                Arc::new(native_destroy_snapshot),
+            ),
         ],
     )
 }
@@ -331,22 +484,12 @@ fn native_new_aggregator(
     let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
     let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
 
-    // Every aggregator instance uses a unique key in its id. Here we can reuse
-    // the strategy from `table` implementation: taking hash of transaction and
-    // number of aggregator instances created so far and truncating them to
-    // 128 bits.
-    let txn_hash_buffer = u128::to_be_bytes(aggregator_context.txn_hash);
-    let num_aggregators_buffer = u128::to_be_bytes(aggregator_data.num_aggregators());
-
-    let mut hasher = DefaultHasher::new(&[0_u8; 0]);
-    hasher.update(&txn_hash_buffer);
-    hasher.update(&num_aggregators_buffer);
-    let hash = hasher.finish();
-
-    // TODO: Using u128 is not enough, and it should be u256 instead. For now,
-    // just take first 16 bytes of the hash.
-    let bytes = &hash.to_vec()[..16];
-    let key = u128::from_be_bytes(bytes.try_into().expect("not enough bytes"));
+    // Every aggregator instance uses a unique key in its id.
+    let key = new_key(
+        aggregator_context.txn_hash,
+        &[0_u8; 0],
+        aggregator_data.num_aggregators(),
+    );
 
     let id = AggregatorID::new(handle, key);
     aggregator_data.create_new_aggregator(id, limit);
@@ -485,6 +628,393 @@ fn native_destroy(
     Ok(NativeResult::ok(0.into(), smallvec![]))
 }
 
+// =============================== Natives (V2) ===============================
+
+/// Move signature:
+/// fun new_aggregator_v2<IntElement>(
+///   aggregator_factory: &mut AggregatorFactory,
+///   max_value: IntElement
+/// ): Aggregator<IntElement>;
+fn native_new_aggregator_v2(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 2);
+    let element = IntElement::from_ty_args(&ty_args)?;
+
+    // Extract fields: `max_value` of the new aggregator and a `phantom_handle`
+    // of the parent factory.
+    let max_value = element.value_as_u128(args.pop_back().unwrap())?;
+    let handle = get_handle(&pop_arg!(args, StructRef))?;
+
+    // Get the current aggregator data.
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    // Aggregators of both versions share the same keys.
+    let key = new_key(
+        aggregator_context.txn_hash,
+        &[0_u8; 0],
+        aggregator_data.num_aggregators(),
+    );
+
+    let id = AggregatorID::new(handle, key);
+    aggregator_data.create_new_aggregator(id, max_value);
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![Value::struct_(Struct::pack(vec![
+            Value::u128(handle),
+            Value::u128(key),
+            element.value_from_u128(max_value),
+        ]))],
+    ))
+}
+
+/// Move signature:
+/// fun add<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement);
+fn native_add_v2(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 2);
+    let element = IntElement::from_ty_args(&ty_args)?;
+
+    // Get aggregator fields and a value to add.
+    let value = element.value_as_u128(args.pop_back().unwrap())?;
+    let aggregator_ref = pop_arg!(args, StructRef);
+    let (handle, key, max_value) = get_aggregator_v2_fields(&aggregator_ref, element)?;
+    let id = AggregatorID::new(handle, key);
+
+    // Get aggregator.
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let aggregator = aggregator_data.get_aggregator(id, max_value);
+
+    aggregator.add(value)?;
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(0.into(), smallvec![]))
+}
+
+/// Move signature:
+/// fun sub<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement);
+fn native_sub_v2(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 2);
+    let element = IntElement::from_ty_args(&ty_args)?;
+
+    // Get aggregator fields and a value to subtract.
+    let value = element.value_as_u128(args.pop_back().unwrap())?;
+    let aggregator_ref = pop_arg!(args, StructRef);
+    let (handle, key, max_value) = get_aggregator_v2_fields(&aggregator_ref, element)?;
+    let id = AggregatorID::new(handle, key);
+
+    // Get aggregator.
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let aggregator = aggregator_data.get_aggregator(id, max_value);
+
+    // As in V1, subtraction materializes the value first.
+    aggregator.materialize(aggregator_context, &id)?;
+    aggregator.sub(value)?;
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(0.into(), smallvec![]))
+}
+
+/// Move signature:
+/// fun read<IntElement>(aggregator: &Aggregator<IntElement>): IntElement;
+fn native_read_v2(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 1);
+    let element = IntElement::from_ty_args(&ty_args)?;
+
+    // Extract fields from aggregator struct reference.
+    let aggregator_ref = pop_arg!(args, StructRef);
+    let (handle, key, max_value) = get_aggregator_v2_fields(&aggregator_ref, element)?;
+    let id = AggregatorID::new(handle, key);
+
+    // Get aggregator.
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let aggregator = aggregator_data.get_aggregator(id, max_value);
+
+    // Materialize the value.
+    aggregator.materialize(aggregator_context, &id)?;
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![element.value_from_u128(aggregator.value)],
+    ))
+}
+
+/// Move signature:
+/// fun destroy<IntElement>(aggregator: Aggregator<IntElement>);
+fn native_destroy_v2(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 1);
+
+    // First, unpack the struct. The last field is `max_value`, which we do
+    // not need.
+    let (handle, key) = unpack_id_fields(pop_arg!(args, Struct), 3)?;
+
+    // Get aggregator data.
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    // Actually remove the aggregator.
+    let id = AggregatorID::new(handle, key);
+    aggregator_data.remove_aggregator(id);
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(0.into(), smallvec![]))
+}
+
+/// Move signature:
+/// fun snapshot<IntElement>(
+///   aggregator: &Aggregator<IntElement>
+/// ): AggregatorSnapshot<IntElement>;
+fn native_snapshot(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 1);
+    let element = IntElement::from_ty_args(&ty_args)?;
+
+    let aggregator_ref = pop_arg!(args, StructRef);
+    let (handle, key, max_value) = get_aggregator_v2_fields(&aggregator_ref, element)?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    // The snapshot is stored next to its aggregator, under a key generated
+    // with a different salt so that it cannot collide with aggregator keys.
+    let snapshot_key = new_key(
+        aggregator_context.txn_hash,
+        SNAPSHOT_KEY_SALT,
+        aggregator_data.num_snapshots,
+    );
+    aggregator_data.create_snapshot(
+        AggregatorID::new(handle, snapshot_key),
+        AggregatorID::new(handle, key),
+        max_value,
+        SnapshotFormat::Integer,
+    );
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![Value::struct_(Struct::pack(vec![
+            Value::u128(handle),
+            Value::u128(snapshot_key),
+        ]))],
+    ))
+}
+
+/// Move signature:
+/// fun read_snapshot<IntElement>(snapshot: &AggregatorSnapshot<IntElement>): IntElement;
+fn native_read_snapshot(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 1);
+    let element = IntElement::from_ty_args(&ty_args)?;
+
+    let (handle, key) = get_snapshot_fields(&pop_arg!(args, StructRef))?;
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let value = read_snapshot_value(aggregator_context, AggregatorID::new(handle, key))?;
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![element.value_from_u128(value)],
+    ))
+}
+
+/// Move signature:
+/// fun derive_string_concat<IntElement>(
+///   prefix: String,
+///   snapshot: &AggregatorSnapshot<IntElement>,
+///   suffix: String
+/// ): DerivedStringSnapshot;
+fn native_derive_string_concat(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 3);
+    IntElement::from_ty_args(&ty_args)?;
+
+    let suffix = unpack_string(pop_arg!(args, Struct))?;
+    let (handle, key) = get_snapshot_fields(&pop_arg!(args, StructRef))?;
+    let prefix = unpack_string(pop_arg!(args, Struct))?;
+    let id = AggregatorID::new(handle, key);
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    let format = SnapshotFormat::String { prefix, suffix };
+
+    // The derived string shares the value of the snapshot: a delta if the
+    // snapshot was taken in this context of an aggregator holding a delta,
+    // otherwise a known value.
+    let derived = match aggregator_data.snapshots.get(&id) {
+        Some(snapshot) => Snapshot {
+            source: snapshot.source,
+            value: snapshot.value,
+            state: snapshot.state,
+            limit: snapshot.limit,
+            format,
+        },
+        None => Snapshot {
+            source: id,
+            value: deserialize(&read_snapshot_bytes(aggregator_context, id)?),
+            state: AggregatorState::Data,
+            limit: u128::MAX,
+            format,
+        },
+    };
+
+    let derived_key = new_key(
+        aggregator_context.txn_hash,
+        SNAPSHOT_KEY_SALT,
+        aggregator_data.num_snapshots,
+    );
+    aggregator_data
+        .snapshots
+        .insert(AggregatorID::new(handle, derived_key), derived);
+    aggregator_data.num_snapshots += 1;
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![Value::struct_(Struct::pack(vec![
+            Value::u128(handle),
+            Value::u128(derived_key),
+        ]))],
+    ))
+}
+
+/// Move signature:
+/// fun read_derived_string(snapshot: &DerivedStringSnapshot): String;
+fn native_read_derived_string(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 1);
+
+    let (handle, key) = get_snapshot_fields(&pop_arg!(args, StructRef))?;
+    let id = AggregatorID::new(handle, key);
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let aggregator_data = aggregator_context.aggregator_data.borrow();
+
+    // In both cases, we get the string as it is stored.
+    let bytes = match aggregator_data.snapshots.get(&id) {
+        Some(snapshot) => snapshot
+            .format
+            .serialize(snapshot.materialize(aggregator_context)?),
+        None => read_snapshot_bytes(aggregator_context, id)?,
+    };
+    let string = bcs::from_bytes::<Vec<u8>>(&bytes)
+        .map_err(|_| extension_error("could not deserialize the derived string"))?;
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![Value::struct_(Struct::pack(vec![Value::vector_u8(string)]))],
+    ))
+}
+
+/// Move signatures:
+/// fun destroy_snapshot<IntElement>(snapshot: AggregatorSnapshot<IntElement>);
+/// fun destroy_derived_string(snapshot: DerivedStringSnapshot);
+fn native_destroy_snapshot(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    if !cfg!(any(test, feature = "aggregator-extension")) {
+        return Err(not_supported_error());
+    }
+    assert!(args.len() == 1);
+
+    let (handle, key) = unpack_id_fields(pop_arg!(args, Struct), 2)?;
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+    aggregator_data.remove_snapshot(AggregatorID::new(handle, key));
+
+    // TODO(Gas): charge gas properly.
+    Ok(NativeResult::ok(0.into(), smallvec![]))
+}
+
+/// Returns the value of the integer snapshot with `id`.
+fn read_snapshot_value(
+    context: &NativeAggregatorContext,
+    id: AggregatorID,
+) -> PartialVMResult<u128> {
+    if let Some(snapshot) = context.aggregator_data.borrow().snapshots.get(&id) {
+        return snapshot.materialize(context);
+    }
+    Ok(deserialize(&read_snapshot_bytes(context, id)?))
+}
+
+/// Returns the bytes of a snapshot with `id` taken somewhere else.
+fn read_snapshot_bytes(
+    context: &NativeAggregatorContext,
+    id: AggregatorID,
+) -> PartialVMResult<Vec<u8>> {
+    context
+        .resolver
+        .resolve_table_entry(&TableHandle(id.handle), &serialize(&id.key))
+        .map_err(|_| extension_error("could not find the value of the snapshot"))?
+        .ok_or_else(|| extension_error("could not find the value of the snapshot"))
+}
+
 // ================================ Utilities ================================
 
 /// The index of the `phantom_table` field in the `AggregatorFactory` Move
@@ -543,6 +1073,110 @@ fn unpack_aggregator_struct(aggregator_struct: Struct) -> PartialVMResult<(u128,
     Ok((handle, key, limit))
 }
 
+/// The salt of the hash generating the keys of snapshots.
+const SNAPSHOT_KEY_SALT: &[u8] = b"AggregatorSnapshot";
+
+/// Generates a unique key: the strategy is reused from `table`
+/// implementation, taking hash of transaction and a counter of the instances
+/// created so far and truncating them to 128 bits.
+fn new_key(txn_hash: u128, salt: &[u8], counter: u128) -> u128 {
+    let txn_hash_buffer = u128::to_be_bytes(txn_hash);
+    let counter_buffer = u128::to_be_bytes(counter);
+
+    let mut hasher = DefaultHasher::new(salt);
+    hasher.update(&txn_hash_buffer);
+    hasher.update(&counter_buffer);
+    let hash = hasher.finish();
+
+    // TODO: Using u128 is not enough, and it should be u256 instead. For now,
+    // just take first 16 bytes of the hash.
+    let bytes = &hash.to_vec()[..16];
+    u128::from_be_bytes(bytes.try_into().expect("not enough bytes"))
+}
+
+/// Integer types of the elements of `aggregator_v2` aggregators and snapshots.
+#[derive(Clone, Copy)]
+enum IntElement {
+    U64,
+    U128,
+}
+
+impl IntElement {
+    /// Returns the element type given as the only type argument of a native.
+    fn from_ty_args(ty_args: &[Type]) -> PartialVMResult<Self> {
+        match ty_args {
+            [Type::U64] => Ok(IntElement::U64),
+            [Type::U128] => Ok(IntElement::U128),
+            _ => Err(unsupported_type_error()),
+        }
+    }
+
+    /// Converts a Move value of this type into `u128`, in which values are
+    /// stored.
+    fn value_as_u128(self, value: Value) -> PartialVMResult<u128> {
+        match self {
+            IntElement::U64 => value.value_as::<u64>().map(u128::from),
+            IntElement::U128 => value.value_as::<u128>(),
+        }
+    }
+
+    /// Converts `value` into a Move value of this type. Values never exceed
+    /// the `max_value` of their aggregator, so the conversion is lossless.
+    fn value_from_u128(self, value: u128) -> Value {
+        match self {
+            IntElement::U64 => Value::u64(value as u64),
+            IntElement::U128 => Value::u128(value),
+        }
+    }
+}
+
+/// Given a reference to `aggregator_v2::Aggregator` Move struct, returns a
+/// tuple of its fields: (`handle`, `key`, `max_value`).
+fn get_aggregator_v2_fields(
+    aggregator: &StructRef,
+    element: IntElement,
+) -> PartialVMResult<(u128, u128, u128)> {
+    let handle = get_aggregator_field(aggregator, HANDLE_FIELD_INDEX)?.value_as::<u128>()?;
+    let key = get_aggregator_field(aggregator, KEY_FIELD_INDEX)?.value_as::<u128>()?;
+    let max_value = element.value_as_u128(get_aggregator_field(aggregator, LIMIT_FIELD_INDEX)?)?;
+    Ok((handle, key, max_value))
+}
+
+/// Given a reference to a snapshot Move struct, returns a tuple of its
+/// fields: (`handle`, `key`).
+fn get_snapshot_fields(snapshot: &StructRef) -> PartialVMResult<(u128, u128)> {
+    let handle = get_aggregator_field(snapshot, HANDLE_FIELD_INDEX)?.value_as::<u128>()?;
+    let key = get_aggregator_field(snapshot, KEY_FIELD_INDEX)?.value_as::<u128>()?;
+    Ok((handle, key))
+}
+
+/// Given a Move struct of `num_fields` fields starting with `handle` and
+/// `key`, unpacks them.
+fn unpack_id_fields(value: Struct, num_fields: usize) -> PartialVMResult<(u128, u128)> {
+    let mut fields: Vec<Value> = value.unpack()?.collect();
+    assert!(fields.len() == num_fields);
+    fields.truncate(2);
+
+    let pop_with_err = |vec: &mut Vec<Value>, msg: &str| {
+        vec.pop()
+            .map_or(Err(extension_error(msg)), |v| v.value_as::<u128>())
+    };
+
+    let key = pop_with_err(&mut fields, "unable to pop 'key' field")?;
+    let handle = pop_with_err(&mut fields, "unable to pop 'handle' field")?;
+    Ok((handle, key))
+}
+
+/// Given a Move `String` struct, returns its bytes.
+fn unpack_string(string: Struct) -> PartialVMResult<Vec<u8>> {
+    string
+        .unpack()?
+        .next()
+        .map_or(Err(extension_error("unable to pop 'bytes' field")), |v| {
+            v.value_as::<Vec<u8>>()
+        })
+}
+
 /// Returns partial VM error on extension failure.
 fn extension_error(message: impl ToString) -> PartialVMError {
     PartialVMError::new(StatusCode::VM_EXTENSION_ERROR).with_message(message.to_string())
@@ -558,6 +1192,17 @@ fn not_supported_error() -> PartialVMError {
         .with_sub_status(ENOT_SUPPORTED)
 }
 
+/// When the element type of an aggregator or snapshot is not `u64` or `u128`.
+const EUNSUPPORTED_AGGREGATOR_TYPE: u64 = 0x01_0004;
+
+/// Returns partial VM error when an aggregator or snapshot has an
+/// unsupported element type.
+fn unsupported_type_error() -> PartialVMError {
+    PartialVMError::new(StatusCode::ABORTED)
+        .with_message("aggregators only support u64 and u128 elements".to_string())
+        .with_sub_status(EUNSUPPORTED_AGGREGATOR_TYPE)
+}
+
 // ================================= Tests =================================
 
 #[cfg(test)]
@@ -566,7 +1211,7 @@ mod test {
     use aptos_state_view::state_storage_usage::StateStorageUsage;
     use aptos_state_view::StateView;
     use aptos_types::state_store::{state_key::StateKey, table::TableHandle as AptosTableHandle};
-    use claim::{assert_err, assert_matches, assert_ok};
+    use claim::{assert_err, assert_matches, assert_ok, assert_ok_eq};
     use move_deps::{
         move_core_types::gas_algebra::InternalGas, move_table_extension::TableOperation,
     };
@@ -659,7 +1304,7 @@ mod test {
         let context = NativeAggregatorContext::new(0, &*TEST_RESOLVER);
         test_set_up(&context);
 
-        let AggregatorChangeSet { changes } = context.into_change_set();
+        let AggregatorChangeSet { changes, .. } = context.into_change_set();
 
         assert!(!changes.contains_key(&test_id(0)));
 
@@ -745,4 +1390,58 @@ mod test {
         assert_matches!(aggregator.state, AggregatorState::Data);
         assert_eq!(aggregator.value, 0);
     }
+
+    #[test]
+    fn test_snapshots() {
+        let context = NativeAggregatorContext::new(0, &*TEST_RESOLVER);
+        test_set_up(&context);
+
+        {
+            let mut aggregator_data = context.aggregator_data.borrow_mut();
+            assert_ok!(aggregator_data.get_aggregator(test_id(1), 1000).add(10));
+            assert_ok!(aggregator_data.get_aggregator(test_id(4), 1000).add(20));
+
+            // A snapshot of an aggregator with data, and snapshots of an
+            // aggregator with a delta.
+            aggregator_data.create_snapshot(test_id(10), test_id(1), 1000, SnapshotFormat::Integer);
+            aggregator_data.create_snapshot(test_id(11), test_id(4), 1000, SnapshotFormat::Integer);
+            aggregator_data.create_snapshot(
+                test_id(12),
+                test_id(4),
+                1000,
+                SnapshotFormat::String {
+                    prefix: b"#".to_vec(),
+                    suffix: vec![],
+                },
+            );
+            // Snapshots do not change with their aggregator.
+            assert_ok!(aggregator_data.get_aggregator(test_id(4), 1000).add(30));
+
+            aggregator_data.remove_snapshot(test_id(12));
+            aggregator_data.remove_snapshot(test_id(13));
+            assert_eq!(aggregator_data.num_snapshots, 3);
+        }
+
+        assert_ok_eq!(read_snapshot_value(&context, test_id(10)), 10);
+        assert_ok_eq!(read_snapshot_value(&context, test_id(11)), 920);
+
+        let AggregatorChangeSet { snapshots, .. } = context.into_change_set();
+        assert_matches!(
+            snapshots.get(&test_id(10)).unwrap(),
+            SnapshotChange::Write(bytes) if *bytes == serialize(&10)
+        );
+        assert_matches!(
+            snapshots.get(&test_id(11)).unwrap(),
+            SnapshotChange::Resolve {
+                source,
+                delta: DeltaOp::Addition {
+                    value: 20,
+                    limit: 1000
+                },
+                format: SnapshotFormat::Integer,
+            } if *source == test_id(4)
+        );
+        assert!(!snapshots.contains_key(&test_id(12)));
+        assert_matches!(snapshots.get(&test_id(13)).unwrap(), SnapshotChange::Delete);
+    }
 }
@@ -88,6 +88,17 @@ impl DeltaOp {
         state_view: &impl StateView,
         state_key: &StateKey,
     ) -> anyhow::Result<WriteOp, VMStatus> {
+        self.try_apply_to_state(state_view, state_key)
+            .map(|result| WriteOp::Modification(serialize(&result)))
+    }
+
+    /// Tries to apply the delta to the value stored at a given state key, and
+    /// returns the result.
+    pub fn try_apply_to_state(
+        &self,
+        state_view: &impl StateView,
+        state_key: &StateKey,
+    ) -> anyhow::Result<u128, VMStatus> {
         state_view
             .get_state_value(state_key)
             .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR))
@@ -95,15 +106,13 @@ impl DeltaOp {
                 match maybe_bytes {
                     Some(bytes) => {
                         let base = deserialize(&bytes);
-                        self.apply_to(base)
-                            .map_err(|partial_error| {
-                                // If delta application fails, transform partial VM
-                                // error into an appropriate VM status.
-                                partial_error
-                                    .finish(Location::Module(AGGREGATOR_MODULE.clone()))
-                                    .into_vm_status()
-                            })
-                            .map(|result| WriteOp::Modification(serialize(&result)))
+                        self.apply_to(base).map_err(|partial_error| {
+                            // If delta application fails, transform partial VM
+                            // error into an appropriate VM status.
+                            partial_error
+                                .finish(Location::Module(AGGREGATOR_MODULE.clone()))
+                                .into_vm_status()
+                        })
                     }
                     // Something is wrong, the value to which we apply delta should
                     // always exist. Guard anyway.
@@ -168,21 +177,76 @@ pub fn deserialize(value_bytes: &[u8]) -> u128 {
     bcs::from_bytes(value_bytes).expect("unexpected deserialization error in aggregator")
 }
 
-/// `DeltaChangeSet` contains all access paths that one transaction wants to update with deltas.
+/// Specifies how the value of an aggregator snapshot is stored.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// The value itself, stored as the value of an aggregator.
+    Integer,
+    /// The decimal string of the value between `prefix` and `suffix`, stored
+    /// as a Move string.
+    String { prefix: Vec<u8>, suffix: Vec<u8> },
+}
+
+impl SnapshotFormat {
+    /// Serializes `value` in this format.
+    pub fn serialize(&self, value: u128) -> Vec<u8> {
+        match self {
+            SnapshotFormat::Integer => serialize(&value),
+            SnapshotFormat::String { prefix, suffix } => {
+                let mut bytes = prefix.clone();
+                bytes.extend(value.to_string().into_bytes());
+                bytes.extend(suffix);
+                bcs::to_bytes(&bytes).expect("unexpected serialization error in aggregator")
+            }
+        }
+    }
+}
+
+/// A snapshot of an aggregator whose value is not known by the transaction
+/// which takes it: the value of the snapshot is the result of applying `delta`
+/// to the value of the aggregator stored at `source`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SnapshotOp {
+    pub source: StateKey,
+    pub delta: DeltaOp,
+    pub format: SnapshotFormat,
+}
+
+impl SnapshotOp {
+    /// Consumes a single snapshot and tries to resolve its value. If resolution
+    /// succeeds, a write op creating the snapshot is produced. Otherwise, an
+    /// error VM status is returned.
+    pub fn try_into_write_op(
+        self,
+        state_view: &impl StateView,
+    ) -> anyhow::Result<WriteOp, VMStatus> {
+        self.delta
+            .try_apply_to_state(state_view, &self.source)
+            .map(|value| WriteOp::Creation(self.format.serialize(value)))
+    }
+}
+
+/// `DeltaChangeSet` contains all access paths that one transaction wants to update with deltas,
+/// and the snapshots it takes of aggregators holding deltas.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct DeltaChangeSet {
     delta_change_set: Vec<(StateKey, DeltaOp)>,
+    snapshots: Vec<(StateKey, SnapshotOp)>,
 }
 
 impl DeltaChangeSet {
     pub fn empty() -> Self {
         DeltaChangeSet {
             delta_change_set: vec![],
+            snapshots: vec![],
         }
     }
 
     pub fn new(delta_change_set: Vec<(StateKey, DeltaOp)>) -> Self {
-        DeltaChangeSet { delta_change_set }
+        DeltaChangeSet {
+            delta_change_set,
+            snapshots: vec![],
+        }
     }
 
     pub fn push(&mut self, delta: (StateKey, DeltaOp)) {
@@ -193,9 +257,13 @@ impl DeltaChangeSet {
         self.delta_change_set.pop();
     }
 
+    pub fn push_snapshot(&mut self, snapshot: (StateKey, SnapshotOp)) {
+        self.snapshots.push(snapshot);
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.delta_change_set.is_empty()
+        self.delta_change_set.is_empty() && self.snapshots.is_empty()
     }
 
     /// Consumes the delta change set and tries to materialize it. Returns a
@@ -211,6 +279,13 @@ impl DeltaChangeSet {
             materialized_write_set.push((state_key, write_op));
         }
 
+        // Snapshots are resolved against the same state as deltas, i.e. the
+        // values of aggregators before the transaction.
+        for (state_key, snapshot_op) in self.snapshots {
+            let write_op = snapshot_op.try_into_write_op(state_view)?;
+            materialized_write_set.push((state_key, write_op));
+        }
+
         // All deltas are applied successfully.
         Ok(WriteSetMut::new(materialized_write_set))
     }
@@ -353,4 +428,54 @@ mod tests {
             Err(VMStatus::MoveAbort(_, ESUB_UNDERFLOW))
         );
     }
+
+    #[test]
+    fn test_snapshot_resolution() {
+        let mut state_view = FakeView::default();
+        state_view.data.insert(KEY.clone(), serialize(&100));
+        let snapshot_key = StateKey::Raw(String::from("snapshot-key").into_bytes());
+
+        let integer = SnapshotOp {
+            source: KEY.clone(),
+            delta: addition(5, 200),
+            format: SnapshotFormat::Integer,
+        };
+        let string = SnapshotOp {
+            source: KEY.clone(),
+            delta: addition(5, 200),
+            format: SnapshotFormat::String {
+                prefix: b"Token #".to_vec(),
+                suffix: vec![],
+            },
+        };
+        let overflow = SnapshotOp {
+            source: KEY.clone(),
+            delta: addition(150, 200),
+            format: SnapshotFormat::Integer,
+        };
+
+        assert_ok_eq!(
+            integer.try_into_write_op(&state_view),
+            WriteOp::Creation(serialize(&105))
+        );
+        assert_ok_eq!(
+            string.clone().try_into_write_op(&state_view),
+            WriteOp::Creation(bcs::to_bytes("Token #105").unwrap())
+        );
+        assert_matches!(
+            overflow.try_into_write_op(&state_view),
+            Err(VMStatus::MoveAbort(_, EADD_OVERFLOW))
+        );
+
+        let mut delta_change_set = DeltaChangeSet::empty();
+        delta_change_set.push_snapshot((snapshot_key.clone(), string));
+        assert!(!delta_change_set.is_empty());
+        assert_ok_eq!(
+            delta_change_set.try_into_write_set_mut(&state_view),
+            WriteSetMut::new(vec![(
+                snapshot_key,
+                WriteOp::Creation(bcs::to_bytes("Token #105").unwrap())
+            )])
+        );
+    }
 }
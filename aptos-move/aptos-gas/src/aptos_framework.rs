@@ -36,6 +36,61 @@ crate::natives::define_gas_parameters_for_natives!(GasParameters, "aptos_framewo
     [.secp256k1.base, "secp256k1.base", 1],
     [.secp256k1.ecdsa_recover, "secp256k1.ecdsa_recover", 1],
 
+    [.secp256r1.base, "secp256r1.base", 1],
+    [.secp256r1.per_pubkey_deserialize, "secp256r1.per_pubkey_deserialize", 1],
+    [.secp256r1.per_sig_deserialize, "secp256r1.per_sig_deserialize", 1],
+    [.secp256r1.per_sig_strict_verify, "secp256r1.per_sig_strict_verify", 1],
+    [.secp256r1.per_msg_hashing_base, "secp256r1.per_msg_hashing_base", 1],
+    [.secp256r1.per_msg_byte_hashing, "secp256r1.per_msg_byte_hashing", 1],
+
+    // Estimates of the relative costs of the operations, yet to be calibrated against the other
+    // natives. Multiplying the basepoint uses a precomputed table, so it is much cheaper than
+    // `point_mul`.
+    [.ristretto255.basepoint_mul, "ristretto255.basepoint_mul", 130],
+    [.ristretto255.basepoint_double_mul, "ristretto255.basepoint_double_mul", 400],
+    [.ristretto255.point_add, "ristretto255.point_add", 4],
+    [.ristretto255.point_compress, "ristretto255.point_compress", 30],
+    [.ristretto255.point_decompress, "ristretto255.point_decompress", 40],
+    [.ristretto255.point_equals, "ristretto255.point_equals", 3],
+    [.ristretto255.point_identity, "ristretto255.point_identity", 1],
+    [.ristretto255.point_is_identity, "ristretto255.point_is_identity", 2],
+    [.ristretto255.point_mul, "ristretto255.point_mul", 450],
+    [.ristretto255.point_neg, "ristretto255.point_neg", 1],
+    [.ristretto255.scalar_is_canonical, "ristretto255.scalar_is_canonical", 1],
+    [.ristretto255.scalar_from_sha512_base, "ristretto255.scalar_from_sha512_base", 10],
+    [.ristretto255.scalar_from_sha512_per_byte, "ristretto255.scalar_from_sha512_per_byte", 1],
+    [.ristretto255.scalar_load, "ristretto255.scalar_load", 1],
+    [.ristretto255.scalar_store, "ristretto255.scalar_store", 1],
+    [.ristretto255.scalar_add_assign, "ristretto255.scalar_add_assign", 1],
+    [.ristretto255.scalar_mul_assign, "ristretto255.scalar_mul_assign", 2],
+
+    [.streaming_hash.init, "streaming_hash.init", 1],
+    [.streaming_hash.update_base, "streaming_hash.update_base", 1],
+    [.streaming_hash.update_per_byte, "streaming_hash.update_per_byte", 1],
+    [.streaming_hash.finalize, "streaming_hash.finalize", 1],
+
+    [.merkle.sparse_merkle_proof.base, "merkle.sparse_merkle_proof.base", 1],
+    [.merkle.sparse_merkle_proof.per_sibling, "merkle.sparse_merkle_proof.per_sibling", 1],
+    [.merkle.accumulator_proof.base, "merkle.accumulator_proof.base", 1],
+    [.merkle.accumulator_proof.per_sibling, "merkle.accumulator_proof.per_sibling", 1],
+
+    [.light_client.eth_sync_committee_verify.base, "light_client.eth_sync_committee_verify.base", 1],
+    [.light_client.eth_sync_committee_verify.per_pubkey_deserialize, "light_client.eth_sync_committee_verify.per_pubkey_deserialize", 1],
+    [.light_client.eth_sync_committee_verify.per_pubkey_aggregate, "light_client.eth_sync_committee_verify.per_pubkey_aggregate", 1],
+    [.light_client.eth_sync_committee_verify.per_sig_verify, "light_client.eth_sync_committee_verify.per_sig_verify", 1],
+    [.light_client.eth_sync_committee_verify.per_byte_hashing, "light_client.eth_sync_committee_verify.per_byte_hashing", 1],
+    [.light_client.eth_beacon_header_root.base, "light_client.eth_beacon_header_root.base", 1],
+    [.light_client.tendermint_verify_commit.base, "light_client.tendermint_verify_commit.base", 1],
+    [.light_client.tendermint_verify_commit.per_sig_verify, "light_client.tendermint_verify_commit.per_sig_verify", 1],
+    [.light_client.tendermint_verify_commit.per_msg_byte_hashing, "light_client.tendermint_verify_commit.per_msg_byte_hashing", 1],
+    [.light_client.tendermint_merkle_root.base, "light_client.tendermint_merkle_root.base", 1],
+    [.light_client.tendermint_merkle_root.per_leaf, "light_client.tendermint_merkle_root.per_leaf", 1],
+    [.light_client.tendermint_merkle_root.per_byte, "light_client.tendermint_merkle_root.per_byte", 1],
+
+    [.poseidon.base, "poseidon.base", 1],
+    [.poseidon.per_permutation, "poseidon.per_permutation", 1],
+    [.poseidon.per_input, "poseidon.per_input", 1],
+
     [.hash.sip_hash.base, "hash.sip_hash.base", 1],
     [.hash.sip_hash.per_byte, "hash.sip_hash.per_byte", 1],
 
@@ -5,9 +5,13 @@
 //! parameters and traits to help manipulate them.
 
 use crate::{
-    algebra::Gas, instr::InstructionGasParameters, misc::MiscGasParameters,
+    algebra::{AbstractValueSize, Gas},
+    instr::InstructionGasParameters,
+    misc::MiscGasParameters,
     transaction::TransactionGasParameters,
+    version::{gas_feature_version, GAS_FEATURE_VERSION_KEY, LATEST_GAS_FEATURE_VERSION},
 };
+use aptos_types::on_chain_config::ExecutionLimits;
 use move_binary_format::errors::{Location, PartialVMError, PartialVMResult, VMResult};
 use move_core_types::{
     gas_algebra::{InternalGas, NumArgs, NumBytes},
@@ -88,6 +92,8 @@ impl InitialGasSchedule for NativeGasParameters {
 /// instructions, transactions and native functions from various packages.
 #[derive(Debug, Clone)]
 pub struct AptosGasParameters {
+    /// The gas feature version of the gas schedule, see `LATEST_GAS_FEATURE_VERSION`
+    pub feature_version: u64,
     pub misc: MiscGasParameters,
     pub instr: InstructionGasParameters,
     pub txn: TransactionGasParameters,
@@ -97,6 +103,7 @@ pub struct AptosGasParameters {
 impl FromOnChainGasSchedule for AptosGasParameters {
     fn from_on_chain_gas_schedule(gas_schedule: &BTreeMap<String, u64>) -> Option<Self> {
         Some(Self {
+            feature_version: gas_feature_version(gas_schedule),
             misc: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule)?,
             instr: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule)?,
             txn: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule)?,
@@ -111,6 +118,7 @@ impl ToOnChainGasSchedule for AptosGasParameters {
         entries.extend(self.txn.to_on_chain_gas_schedule());
         entries.extend(self.natives.to_on_chain_gas_schedule());
         entries.extend(self.misc.to_on_chain_gas_schedule());
+        entries.push((GAS_FEATURE_VERSION_KEY.to_string(), self.feature_version));
         entries
     }
 }
@@ -118,6 +126,7 @@ impl ToOnChainGasSchedule for AptosGasParameters {
 impl AptosGasParameters {
     pub fn zeros() -> Self {
         Self {
+            feature_version: LATEST_GAS_FEATURE_VERSION,
             misc: MiscGasParameters::zeros(),
             instr: InstructionGasParameters::zeros(),
             txn: TransactionGasParameters::zeros(),
@@ -129,6 +138,7 @@ impl AptosGasParameters {
 impl InitialGasSchedule for AptosGasParameters {
     fn initial() -> Self {
         Self {
+            feature_version: LATEST_GAS_FEATURE_VERSION,
             misc: InitialGasSchedule::initial(),
             instr: InitialGasSchedule::initial(),
             txn: InitialGasSchedule::initial(),
@@ -140,9 +150,34 @@ impl InitialGasSchedule for AptosGasParameters {
 /// The official gas meter used inside the Aptos VM.
 /// It maintains an internal gas counter, measured in internal gas units, and carries an environment
 /// consisting all the gas parameters, which it can lookup when performing gas calcuations.
+///
+/// Since it sees every call and branch, it also enforces the `ExecutionLimits` of the transaction.
+/// It does so per metering tick: the execution of a function the VM calls directly, i.e. the
+/// script or entry function of the transaction, the creation of the account of an implicit
+/// sender, or an `init_module`. The VM starts each tick with [`AptosGasMeter::start_tick`].
+///
+/// It also charges for the memory the transaction allocates, measured in abstract value size.
+/// Memory is allocated by copying values, loading constants, packing structs and vectors, and
+/// pushing to vectors. There is no quota on the memory in use, since the gas meter doesn't see
+/// values being dropped, so it can't tell how much of the memory allocated is still in use.
 pub struct AptosGasMeter {
     gas_params: AptosGasParameters,
     balance: InternalGas,
+    limits: ExecutionLimits,
+    /// The calls in progress in the current tick, innermost last. The first one is the root call
+    /// of the tick, which stays once it returned, until the next tick. Empty before the first
+    /// tick.
+    call_stack: Vec<CallFrame>,
+    /// The loop iterations of the current tick, counted as the branches it executed, since each
+    /// iteration executes at least one.
+    loop_iterations: u64,
+}
+
+/// A call in progress, as seen by the gas meter
+struct CallFrame {
+    /// The module of the function, only tracked if cross-module reentrancy isn't allowed. None
+    /// for a script.
+    module_id: Option<ModuleId>,
 }
 
 impl AptosGasMeter {
@@ -152,14 +187,36 @@ impl AptosGasMeter {
         Self {
             gas_params,
             balance,
+            limits: ExecutionLimits::default(),
+            call_stack: vec![],
+            loop_iterations: 0,
         }
     }
 
+    /// Enforces `limits` instead of the default ones.
+    pub fn with_execution_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn balance(&self) -> Gas {
         self.balance
             .to_unit_round_down_with_params(&self.gas_params.txn)
     }
 
+    /// Starts a metering tick, whose root call is a function of `module_id`, or a script if
+    /// `None`. The Move VM doesn't charge the root call as a call, so it must be called before
+    /// the VM executes it.
+    pub fn start_tick(&mut self, module_id: Option<&ModuleId>) {
+        let module_id = if self.limits.allow_cross_module_reentrancy {
+            None
+        } else {
+            module_id.cloned()
+        };
+        self.call_stack = vec![CallFrame { module_id }];
+        self.loop_iterations = 0;
+    }
+
     #[inline]
     fn charge(&mut self, amount: InternalGas) -> PartialVMResult<()> {
         match self.balance.checked_sub(amount) {
@@ -173,17 +230,97 @@ impl AptosGasMeter {
             }
         }
     }
+
+    /// Charges for `size` more memory allocated.
+    #[inline]
+    fn use_memory(&mut self, size: AbstractValueSize) -> PartialVMResult<()> {
+        self.charge(self.gas_params.misc.memory.per_abs_val_unit * size)
+    }
+
+    /// Enters a call to a function of `module_id`, checking the call depth and, if not allowed,
+    /// cross-module reentrancy.
+    fn enter_call(&mut self, module_id: &ModuleId) -> PartialVMResult<()> {
+        if self.call_stack.is_empty() {
+            return Err(no_tick_started());
+        }
+        if self.call_stack.len() as u64 >= self.limits.max_call_depth {
+            return Err(limit_exceeded(ExecutionLimits::ECALL_DEPTH_EXCEEDED));
+        }
+        let module_id = if self.limits.allow_cross_module_reentrancy {
+            None
+        } else {
+            let caller = self
+                .call_stack
+                .last()
+                .and_then(|frame| frame.module_id.as_ref());
+            if caller != Some(module_id)
+                && self
+                    .call_stack
+                    .iter()
+                    .any(|frame| frame.module_id.as_ref() == Some(module_id))
+            {
+                return Err(limit_exceeded(ExecutionLimits::ECROSS_MODULE_REENTRANCY));
+            }
+            Some(module_id.clone())
+        };
+        self.call_stack.push(CallFrame { module_id });
+        Ok(())
+    }
+
+    /// Exits the innermost call. The frame of the root call stays, so that the tick can't run
+    /// anything the limits don't see.
+    fn exit_call(&mut self) -> PartialVMResult<()> {
+        match self.call_stack.len() {
+            0 => Err(no_tick_started()),
+            1 => Ok(()),
+            _ => {
+                self.call_stack.pop();
+                Ok(())
+            }
+        }
+    }
+
+    /// Counts a branch of the current tick, each iteration of a loop executing at least one.
+    fn count_branch(&mut self) -> PartialVMResult<()> {
+        if self.call_stack.is_empty() {
+            return Err(no_tick_started());
+        }
+        self.loop_iterations += 1;
+        if self.loop_iterations > self.limits.max_loop_iterations_per_tick {
+            return Err(limit_exceeded(ExecutionLimits::ELOOP_ITERATIONS_EXCEEDED));
+        }
+        Ok(())
+    }
+}
+
+/// The error of code running outside of a tick, which the VM must start before executing any.
+fn no_tick_started() -> PartialVMError {
+    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+        .with_message("the gas meter has no call in progress".to_string())
+}
+
+fn limit_exceeded(abort_code: u64) -> PartialVMError {
+    PartialVMError::new(StatusCode::ABORTED).with_sub_status(abort_code)
 }
 
 impl GasMeter for AptosGasMeter {
     #[inline]
     fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        match instr {
+            SimpleInstruction::Ret => self.exit_call()?,
+            SimpleInstruction::Branch | SimpleInstruction::BrTrue | SimpleInstruction::BrFalse => {
+                self.count_branch()?
+            }
+            _ => (),
+        }
         let cost = self.gas_params.instr.simple_instr_cost(instr)?;
         self.charge(cost)
     }
 
     #[inline]
     fn charge_native_function(&mut self, amount: InternalGas) -> PartialVMResult<()> {
+        // Native functions are charged as calls too, but don't return through `Ret`.
+        self.exit_call()?;
         self.charge(amount)
     }
 
@@ -203,10 +340,11 @@ impl GasMeter for AptosGasMeter {
     #[inline]
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
+        module_id: &ModuleId,
         _func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        self.enter_call(module_id)?;
         let params = &self.gas_params.instr;
         self.charge(params.call_base + params.call_per_arg * NumArgs::new(args.len() as u64))
     }
@@ -214,11 +352,12 @@ impl GasMeter for AptosGasMeter {
     #[inline]
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
+        module_id: &ModuleId,
         _func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
+        self.enter_call(module_id)?;
         let params = &self.gas_params.instr;
         self.charge(
             params.call_generic_base
@@ -230,18 +369,20 @@ impl GasMeter for AptosGasMeter {
     #[inline]
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
         let params = &self.gas_params.instr;
-        self.charge(params.ld_const_base + params.ld_const_per_byte * size)
+        self.charge(params.ld_const_base + params.ld_const_per_byte * size)?;
+        // The serialized size is close enough to the abstract size of the constant.
+        self.use_memory(u64::from(size).into())
     }
 
     #[inline]
     fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
         // Note(Gas): this makes a deep copy so we need to charge for the full value size
         let instr_params = &self.gas_params.instr;
-        let cost = instr_params.copy_loc_base
-            + instr_params.copy_loc_per_abs_val_unit
-                * self.gas_params.misc.abs_val.abstract_value_size(val);
+        let size = self.gas_params.misc.abs_val.abstract_value_size(val);
+        let cost = instr_params.copy_loc_base + instr_params.copy_loc_per_abs_val_unit * size;
 
-        self.charge(cost)
+        self.charge(cost)?;
+        self.use_memory(size)
     }
 
     #[inline]
@@ -268,7 +409,9 @@ impl GasMeter for AptosGasMeter {
                     + params.pack_generic_per_field * NumArgs::new(args.len() as u64)
             }
         };
-        self.charge(cost)
+        self.charge(cost)?;
+        // The fields are moved into the struct, only the struct itself is new.
+        self.use_memory(self.gas_params.misc.abs_val.struct_)
     }
 
     #[inline]
@@ -292,10 +435,10 @@ impl GasMeter for AptosGasMeter {
     fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
         // Note(Gas): this makes a deep copy so we need to charge for the full value size
         let instr_params = &self.gas_params.instr;
-        let cost = instr_params.read_ref_base
-            + instr_params.read_ref_per_abs_val_unit
-                * self.gas_params.misc.abs_val.abstract_value_size(val);
-        self.charge(cost)
+        let size = self.gas_params.misc.abs_val.abstract_value_size(val);
+        let cost = instr_params.read_ref_base + instr_params.read_ref_per_abs_val_unit * size;
+        self.charge(cost)?;
+        self.use_memory(size)
     }
 
     #[inline]
@@ -404,7 +547,9 @@ impl GasMeter for AptosGasMeter {
         let params = &self.gas_params.instr;
         let cost =
             params.vec_pack_base + params.vec_pack_per_elem * NumArgs::new(args.len() as u64);
-        self.charge(cost)
+        self.charge(cost)?;
+        // The elements are moved into the vector, only the vector itself is new.
+        self.use_memory(self.gas_params.misc.abs_val.vector)
     }
 
     #[inline]
@@ -443,9 +588,12 @@ impl GasMeter for AptosGasMeter {
     fn charge_vec_push_back(
         &mut self,
         _ty: impl TypeView,
-        _val: impl ValueView,
+        val: impl ValueView,
     ) -> PartialVMResult<()> {
-        self.charge(self.gas_params.instr.vec_push_back_base)
+        self.charge(self.gas_params.instr.vec_push_back_base)?;
+        // The vector grows by the size of the element, which may be stored more compactly in it.
+        let size = self.gas_params.misc.abs_val.abstract_value_size(val);
+        self.use_memory(size)
     }
 
     #[inline]
@@ -469,3 +617,124 @@ impl AptosGasMeter {
         self.charge(cost).map_err(|e| e.finish(Location::Undefined))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+    use move_vm_types::values::Value;
+
+    fn module_id(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    fn gas_meter(limits: ExecutionLimits) -> AptosGasMeter {
+        AptosGasMeter::new(AptosGasParameters::zeros(), 0).with_execution_limits(limits)
+    }
+
+    fn call(gas_meter: &mut AptosGasMeter, module: &str) -> PartialVMResult<()> {
+        gas_meter.charge_call(&module_id(module), "f", std::iter::empty::<Value>())
+    }
+
+    fn sub_status(result: PartialVMResult<()>) -> Option<u64> {
+        result.unwrap_err().finish(Location::Undefined).sub_status()
+    }
+
+    #[test]
+    fn cross_module_reentrancy() {
+        let mut gas_meter = gas_meter(ExecutionLimits {
+            allow_cross_module_reentrancy: false,
+            ..ExecutionLimits::default()
+        });
+        gas_meter.start_tick(Some(&module_id("m")));
+
+        // M::entry -> M::f -> N::f -> N::f is fine, but not M::entry -> N::f -> M::f
+        call(&mut gas_meter, "m").unwrap();
+        call(&mut gas_meter, "n").unwrap();
+        call(&mut gas_meter, "n").unwrap();
+        assert_eq!(
+            sub_status(call(&mut gas_meter, "m")),
+            Some(ExecutionLimits::ECROSS_MODULE_REENTRANCY)
+        );
+
+        // Once N returned, M::entry -> M::f -> M::f is fine again
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        call(&mut gas_meter, "m").unwrap();
+    }
+
+    #[test]
+    fn root_call_stays_on_the_stack() {
+        let mut gas_meter = gas_meter(ExecutionLimits {
+            allow_cross_module_reentrancy: false,
+            ..ExecutionLimits::default()
+        });
+        gas_meter.start_tick(Some(&module_id("m")));
+
+        // Returning more often than calling doesn't hide M from the reentrancy check
+        call(&mut gas_meter, "n").unwrap();
+        for _ in 0..3 {
+            gas_meter
+                .charge_simple_instr(SimpleInstruction::Ret)
+                .unwrap();
+        }
+        call(&mut gas_meter, "n").unwrap();
+        assert_eq!(
+            sub_status(call(&mut gas_meter, "m")),
+            Some(ExecutionLimits::ECROSS_MODULE_REENTRANCY)
+        );
+    }
+
+    #[test]
+    fn calls_outside_of_a_tick() {
+        let mut gas_meter = gas_meter(ExecutionLimits::default());
+        for result in [
+            call(&mut gas_meter, "m"),
+            gas_meter.charge_simple_instr(SimpleInstruction::Ret),
+            gas_meter.charge_simple_instr(SimpleInstruction::Branch),
+        ] {
+            assert_eq!(
+                result.unwrap_err().major_status(),
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR
+            );
+        }
+    }
+
+    #[test]
+    fn loop_iterations_per_tick() {
+        let mut gas_meter = gas_meter(ExecutionLimits {
+            max_loop_iterations_per_tick: 3,
+            ..ExecutionLimits::default()
+        });
+        gas_meter.start_tick(Some(&module_id("m")));
+
+        // The iterations of all the calls of a tick count towards its limit
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Branch)
+            .unwrap();
+        call(&mut gas_meter, "n").unwrap();
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::BrTrue)
+            .unwrap();
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::BrFalse)
+            .unwrap();
+        assert_eq!(
+            sub_status(gas_meter.charge_simple_instr(SimpleInstruction::Branch)),
+            Some(ExecutionLimits::ELOOP_ITERATIONS_EXCEEDED)
+        );
+
+        // The next tick starts from zero
+        gas_meter.start_tick(None);
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Branch)
+            .unwrap();
+    }
+}
@@ -28,9 +28,11 @@ mod algebra;
 mod aptos_framework;
 mod gas_meter;
 mod instr;
+mod memory;
 mod misc;
 mod move_stdlib;
 mod transaction;
+mod version;
 
 pub use algebra::*;
 pub use gas_meter::{
@@ -38,9 +40,14 @@ pub use gas_meter::{
     NativeGasParameters, ToOnChainGasSchedule,
 };
 pub use instr::InstructionGasParameters;
+pub use memory::MemoryGasParameters;
 pub use misc::{AbstractMemorySizeGasParameters, MiscGasParameters};
 pub use move_core_types::gas_algebra::{
     Arg, Byte, GasQuantity, InternalGas, InternalGasPerArg, InternalGasPerByte, InternalGasUnit,
     NumArgs, NumBytes, UnitDiv,
 };
 pub use transaction::TransactionGasParameters;
+pub use version::{
+    gas_feature_version, GAS_FEATURE_VERSION_CHANGE_SET_LIMITS, GAS_FEATURE_VERSION_KEY,
+    GAS_FEATURE_VERSION_MEMORY, LATEST_GAS_FEATURE_VERSION,
+};
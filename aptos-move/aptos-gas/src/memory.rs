@@ -0,0 +1,22 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the gas parameters for the memory Move values take up during the execution
+//! of a transaction, measured in abstract value size. They are part of the gas schedule from
+//! `GAS_FEATURE_VERSION_MEMORY` on.
+
+use crate::algebra::InternalGasPerAbstractValueUnit;
+
+crate::params::define_gas_parameters!(
+    MemoryGasParameters,
+    "misc.memory",
+    [
+        // Charged for every abstract value unit of memory a transaction allocates, on top of
+        // the cost of the instruction allocating it.
+        [
+            per_abs_val_unit: InternalGasPerAbstractValueUnit,
+            "per_abs_val_unit",
+            1
+        ],
+    ]
+);
@@ -5,6 +5,8 @@ use std::collections::BTreeMap;
 
 use crate::algebra::{AbstractValueSize, AbstractValueSizePerArg};
 use crate::gas_meter::{FromOnChainGasSchedule, InitialGasSchedule, ToOnChainGasSchedule};
+use crate::memory::MemoryGasParameters;
+use crate::version::{gas_feature_version, GAS_FEATURE_VERSION_MEMORY};
 use move_core_types::{account_address::AccountAddress, gas_algebra::NumArgs};
 use move_vm_types::views::{ValueView, ValueVisitor};
 
@@ -130,19 +132,29 @@ impl AbstractMemorySizeGasParameters {
 #[derive(Debug, Clone)]
 pub struct MiscGasParameters {
     pub abs_val: AbstractMemorySizeGasParameters,
+    pub memory: MemoryGasParameters,
 }
 
 impl FromOnChainGasSchedule for MiscGasParameters {
     fn from_on_chain_gas_schedule(gas_schedule: &BTreeMap<String, u64>) -> Option<Self> {
+        // Older gas schedules don't charge for memory, and don't have its parameters.
+        let memory = if gas_feature_version(gas_schedule) >= GAS_FEATURE_VERSION_MEMORY {
+            FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule)?
+        } else {
+            MemoryGasParameters::zeros()
+        };
         Some(Self {
             abs_val: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule)?,
+            memory,
         })
     }
 }
 
 impl ToOnChainGasSchedule for MiscGasParameters {
     fn to_on_chain_gas_schedule(&self) -> Vec<(String, u64)> {
-        self.abs_val.to_on_chain_gas_schedule()
+        let mut entries = self.abs_val.to_on_chain_gas_schedule();
+        entries.extend(self.memory.to_on_chain_gas_schedule());
+        entries
     }
 }
 
@@ -150,6 +162,7 @@ impl MiscGasParameters {
     pub fn zeros() -> Self {
         Self {
             abs_val: AbstractMemorySizeGasParameters::zeros(),
+            memory: MemoryGasParameters::zeros(),
         }
     }
 }
@@ -158,6 +171,43 @@ impl InitialGasSchedule for MiscGasParameters {
     fn initial() -> Self {
         Self {
             abs_val: InitialGasSchedule::initial(),
+            memory: InitialGasSchedule::initial(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::GAS_FEATURE_VERSION_KEY;
+
+    fn gas_schedule(feature_version: Option<u64>) -> BTreeMap<String, u64> {
+        let mut gas_schedule: BTreeMap<_, _> = MiscGasParameters::initial()
+            .abs_val
+            .to_on_chain_gas_schedule()
+            .into_iter()
+            .collect();
+        if let Some(feature_version) = feature_version {
+            gas_schedule.insert(GAS_FEATURE_VERSION_KEY.to_string(), feature_version);
         }
+        gas_schedule
+    }
+
+    #[test]
+    fn memory_parameters_gated_by_feature_version() {
+        // Schedules from before memory was charged don't have its parameters, and charge nothing
+        for feature_version in [None, Some(GAS_FEATURE_VERSION_MEMORY - 1)] {
+            let params =
+                MiscGasParameters::from_on_chain_gas_schedule(&gas_schedule(feature_version))
+                    .unwrap();
+            assert_eq!(u64::from(params.memory.per_abs_val_unit), 0);
+        }
+
+        // Newer ones must have them
+        let mut gas_schedule = gas_schedule(Some(GAS_FEATURE_VERSION_MEMORY));
+        assert!(MiscGasParameters::from_on_chain_gas_schedule(&gas_schedule).is_none());
+        gas_schedule.extend(MemoryGasParameters::initial().to_on_chain_gas_schedule());
+        let params = MiscGasParameters::from_on_chain_gas_schedule(&gas_schedule).unwrap();
+        assert_eq!(u64::from(params.memory.per_abs_val_unit), 1);
     }
 }
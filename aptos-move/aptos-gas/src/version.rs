@@ -0,0 +1,28 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the gas feature version of a gas schedule, which gates the gas parameters
+//! and limits added after the launch. It's an entry of the on-chain gas schedule, so a schedule
+//! published without it is of version 0 and keeps working the way it did when it was published.
+
+use std::collections::BTreeMap;
+
+/// The key of the gas feature version in the on-chain gas schedule.
+pub const GAS_FEATURE_VERSION_KEY: &str = "gas_feature_version";
+
+/// Charges for the memory allocated by Move values, with the `misc.memory` parameters.
+pub const GAS_FEATURE_VERSION_MEMORY: u64 = 1;
+
+/// Limits the size of the write set and the number of events of a transaction.
+pub const GAS_FEATURE_VERSION_CHANGE_SET_LIMITS: u64 = 2;
+
+/// The gas feature version of the gas schedules of this release.
+pub const LATEST_GAS_FEATURE_VERSION: u64 = GAS_FEATURE_VERSION_CHANGE_SET_LIMITS;
+
+/// Returns the gas feature version of an on-chain gas schedule, 0 if it has none.
+pub fn gas_feature_version(gas_schedule: &BTreeMap<String, u64>) -> u64 {
+    gas_schedule
+        .get(GAS_FEATURE_VERSION_KEY)
+        .copied()
+        .unwrap_or(0)
+}
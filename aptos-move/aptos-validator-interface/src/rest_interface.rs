@@ -0,0 +1,102 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosValidatorInterface;
+use anyhow::{anyhow, bail, Result};
+use aptos_rest_client::Client;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    contract_event::EventWithVersion,
+    event::EventKey,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, Version},
+};
+use tokio::runtime::Handle;
+
+/// Reads the state and transactions to replay from a node's REST API, instead of a local DB.
+///
+/// The REST client is async, so requests are run on the given runtime. The interface must be
+/// used from outside of the runtime's worker threads, e.g. from `tokio::task::spawn_blocking`.
+pub struct RestDebuggerInterface {
+    client: Client,
+    runtime: Handle,
+}
+
+impl RestDebuggerInterface {
+    pub fn new(client: Client, runtime: Handle) -> Self {
+        Self { client, runtime }
+    }
+}
+
+impl AptosValidatorInterface for RestDebuggerInterface {
+    fn get_account_state_by_version(
+        &self,
+        _account: AccountAddress,
+        _version: Version,
+    ) -> Result<Option<AccountState>> {
+        bail!("Reading a whole account state is not supported over the REST API")
+    }
+
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        Ok(self
+            .runtime
+            .block_on(self.client.get_raw_state_value(state_key, version))?
+            .map(StateValue::from))
+    }
+
+    fn get_events(
+        &self,
+        _key: &EventKey,
+        _start_seq: u64,
+        _limit: u64,
+        _ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        bail!("Reading events is not supported over the REST API")
+    }
+
+    fn get_committed_transactions(&self, start: Version, limit: u64) -> Result<Vec<Transaction>> {
+        (start..start + limit)
+            .map(|version| {
+                Ok(self
+                    .runtime
+                    .block_on(self.client.get_raw_transaction_by_version(version))?
+                    .into_inner())
+            })
+            .collect()
+    }
+
+    fn get_latest_version(&self) -> Result<Version> {
+        Ok(self
+            .runtime
+            .block_on(self.client.get_ledger_information())?
+            .into_inner()
+            .version)
+    }
+
+    fn get_version_by_account_sequence(
+        &self,
+        account: AccountAddress,
+        seq: u64,
+    ) -> Result<Option<Version>> {
+        let transactions = self
+            .runtime
+            .block_on(
+                self.client
+                    .get_account_transactions(account, Some(seq), Some(1)),
+            )?
+            .into_inner();
+        match transactions.first() {
+            Some(transaction) => {
+                Ok(Some(transaction.version().ok_or_else(|| {
+                    anyhow!("Transaction {} is not committed", seq)
+                })?))
+            }
+            None => Ok(None),
+        }
+    }
+}
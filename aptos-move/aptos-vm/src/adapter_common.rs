@@ -19,6 +19,7 @@ use aptos_logger::prelude::*;
 use aptos_types::{
     block_metadata::BlockMetadata,
     transaction::{Transaction, TransactionOutput, TransactionStatus, WriteSetPayload},
+    validator_txn::ValidatorTransaction,
     write_set::WriteSet,
 };
 use rayon::prelude::*;
@@ -39,9 +40,26 @@ pub trait VMAdapter {
     /// `Ok(SignatureCheckedTransaction)` if the signature is valid.
     fn check_signature(txn: SignedTransaction) -> Result<SignatureCheckedTransaction>;
 
+    /// Checks the signatures of the given signed transactions for validation. Unlike
+    /// `check_signature`, this is not used during execution, so it may verify signatures in
+    /// batches even if a batch doesn't always agree with individual verification.
+    fn check_signatures_for_validation(
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Result<SignatureCheckedTransaction>> {
+        txns.into_iter().map(Self::check_signature).collect()
+    }
+
     /// Check if the transaction format is supported.
     fn check_transaction_format(&self, txn: &SignedTransaction) -> Result<(), VMStatus>;
 
+    /// Checks the parts of the authenticators of the given transaction that depend on on-chain
+    /// state, and so are not checked by `check_signature`.
+    fn check_authenticators<S: MoveResolverExt>(
+        &self,
+        resolver: &S,
+        txn: &SignatureCheckedTransaction,
+    ) -> Result<(), VMStatus>;
+
     /// Runs the prologue for the given transaction.
     fn run_prologue<S: MoveResolverExt>(
         &self,
@@ -74,8 +92,33 @@ pub fn validate_signed_transaction<A: VMAdapter>(
     state_view: &impl StateView,
 ) -> VMValidatorResult {
     let _timer = TXN_VALIDATION_SECONDS.start_timer();
+    validate_signature_checked_result(adapter, A::check_signature(transaction), state_view)
+}
+
+/// Validates signed transactions as `validate_signed_transaction` does, except that the
+/// signatures of all of them are checked at once by `check_signatures_for_validation`.
+pub fn validate_signed_transactions<A: VMAdapter>(
+    adapter: &A,
+    transactions: Vec<SignedTransaction>,
+    state_view: &impl StateView,
+) -> Vec<VMValidatorResult> {
+    A::check_signatures_for_validation(transactions)
+        .into_iter()
+        .map(|txn| {
+            let _timer = TXN_VALIDATION_SECONDS.start_timer();
+            validate_signature_checked_result(adapter, txn, state_view)
+        })
+        .collect()
+}
+
+/// Validates a transaction whose signature check returned `txn`.
+fn validate_signature_checked_result<A: VMAdapter>(
+    adapter: &A,
+    txn: Result<SignatureCheckedTransaction>,
+    state_view: &impl StateView,
+) -> VMValidatorResult {
     let log_context = AdapterLogSchema::new(state_view.id(), 0);
-    let txn = match A::check_signature(transaction) {
+    let txn = match txn {
         Ok(t) => t,
         _ => {
             return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
@@ -86,8 +129,14 @@ pub fn validate_signed_transaction<A: VMAdapter>(
     let resolver = remote_cache.as_move_resolver();
     let mut session = adapter.new_session(&resolver, SessionId::txn(&txn));
 
-    let validation_result =
-        validate_signature_checked_transaction(adapter, &mut session, &txn, true, &log_context);
+    let validation_result = validate_signature_checked_transaction(
+        adapter,
+        &mut session,
+        &resolver,
+        &txn,
+        true,
+        &log_context,
+    );
 
     // Increment the counter for transactions verified.
     let (counter_label, result) = match validation_result {
@@ -110,11 +159,13 @@ pub fn validate_signed_transaction<A: VMAdapter>(
 pub(crate) fn validate_signature_checked_transaction<S: MoveResolverExt, A: VMAdapter>(
     adapter: &A,
     session: &mut SessionExt<S>,
+    resolver: &S,
     transaction: &SignatureCheckedTransaction,
     allow_too_new: bool,
     log_context: &AdapterLogSchema,
 ) -> Result<(), VMStatus> {
     adapter.check_transaction_format(transaction)?;
+    adapter.check_authenticators(resolver, transaction)?;
 
     let prologue_status = adapter.run_prologue(session, transaction, log_context);
     match prologue_status {
@@ -199,13 +250,14 @@ pub(crate) fn execute_block_impl<A: VMAdapter, S: StateView>(
 }
 
 /// Transactions after signature checking:
-/// Waypoints and BlockPrologues are not signed and are unaffected by signature checking,
+/// Waypoints, BlockPrologues and validator transactions are not signed and are unaffected by signature checking,
 /// but a user transaction or writeset transaction is transformed to a SignatureCheckedTransaction.
 #[derive(Debug)]
 pub enum PreprocessedTransaction {
     UserTransaction(Box<SignatureCheckedTransaction>),
     WaypointWriteSet(WriteSetPayload),
     BlockMetadata(BlockMetadata),
+    ValidatorTransaction(ValidatorTransaction),
     InvalidSignature,
     StateCheckpoint,
 }
@@ -219,6 +271,9 @@ pub(crate) fn preprocess_transaction<A: VMAdapter>(txn: Transaction) -> Preproce
         Transaction::BlockMetadata(b) => PreprocessedTransaction::BlockMetadata(b),
         Transaction::GenesisTransaction(ws) => PreprocessedTransaction::WaypointWriteSet(ws),
         Transaction::UserTransaction(txn) => {
+            // Signatures are verified one by one here: every node must reach the same result, and
+            // batch verification of Ed25519 signatures may accept some that strict verification
+            // rejects.
             let checked_txn = match A::check_signature(txn) {
                 Ok(checked_txn) => checked_txn,
                 _ => {
@@ -228,6 +283,9 @@ pub(crate) fn preprocess_transaction<A: VMAdapter>(txn: Transaction) -> Preproce
             PreprocessedTransaction::UserTransaction(Box::new(checked_txn))
         }
         Transaction::StateCheckpoint(_) => PreprocessedTransaction::StateCheckpoint,
+        Transaction::ValidatorTransaction(txn) => {
+            PreprocessedTransaction::ValidatorTransaction(txn)
+        }
     }
 }
 
@@ -5,26 +5,34 @@ use crate::{
     adapter_common,
     adapter_common::{
         discard_error_output, discard_error_vm_status, validate_signature_checked_transaction,
-        validate_signed_transaction, PreprocessedTransaction, VMAdapter,
+        validate_signed_transaction, validate_signed_transactions, PreprocessedTransaction,
+        VMAdapter,
     },
-    aptos_vm_impl::{get_transaction_output, AptosVMImpl, AptosVMInternals},
+    aptos_vm_impl::{
+        get_transaction_output, AptosVMImpl, AptosVMInternals, IMPLICIT_ACCOUNT_CREATION_MAX_GAS,
+    },
+    block_output_limit::{cut_output, is_cuttable, BlockOutputTracker},
     counters::*,
     data_cache::{AsMoveResolver, StateViewCache},
+    entry_function_metrics,
     errors::expect_only_successful_execution,
+    keyless_validation,
     logging::AdapterLogSchema,
-    move_vm_ext::{MoveResolverExt, SessionExt, SessionId},
+    move_vm_ext::{
+        warm_vm_cache::WarmVmStateView, MoveResolverExt, SessionExt, SessionId, WarmVmCache,
+    },
     system_module_names::*,
     transaction_arg_validation,
     transaction_metadata::TransactionMetadata,
-    VMExecutor, VMValidator,
+    validator_txn_validation, VMExecutor, VMValidator,
 };
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use aptos_aggregator::{
     delta_change_set::DeltaChangeSet,
     transaction::{ChangeSetExt, TransactionOutputExt},
 };
 use aptos_crypto::HashValue;
-use aptos_gas::AptosGasMeter;
+use aptos_gas::{AptosGasMeter, Gas};
 use aptos_logger::prelude::*;
 use aptos_module_verifier::module_init::verify_module_init_function;
 use aptos_state_view::StateView;
@@ -32,27 +40,31 @@ use aptos_types::account_config::new_block_event_key;
 use aptos_types::{
     account_config,
     block_metadata::BlockMetadata,
-    on_chain_config::{new_epoch_event_key, GasSchedule, Version},
+    keyless::QuorumCertifiedUpdate,
+    on_chain_config::{new_epoch_event_key, BlockOutputLimit, FeatureFlag, GasSchedule, Version},
     transaction::{
-        ChangeSet, ExecutionStatus, ModuleBundle, SignatureCheckedTransaction, SignedTransaction,
-        Transaction, TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
-        WriteSetPayload,
+        authenticator::AccountAuthenticator, ChangeSet, ExecutionStatus, ModuleBundle,
+        SignatureCheckedTransaction, SignedTransaction, Transaction, TransactionOutput,
+        TransactionPayload, TransactionStatus, VMValidatorResult, WriteSetPayload,
     },
+    validator_txn::ValidatorTransaction,
     vm_status::{StatusCode, VMStatus},
     write_set::WriteSet,
 };
 use fail::fail_point;
-use framework::natives::code::PublishRequest;
+use framework::{check_upgrade_compatibility, natives::code::PublishRequest};
 use move_deps::{
     move_binary_format::{
         access::ModuleAccess,
-        errors::{verification_error, Location, PartialVMError, VMResult},
+        errors::{verification_error, Location, PartialVMError, VMError, VMResult},
+        file_format::{SignatureToken, Visibility},
         CompiledModule, IndexKind,
     },
     move_core_types::{
         account_address::AccountAddress,
         ident_str,
-        language_storage::ModuleId,
+        identifier::IdentStr,
+        language_storage::{ModuleId, TypeTag},
         transaction_argument::convert_txn_args,
         value::{serialize_values, MoveValue},
     },
@@ -65,11 +77,16 @@ use std::{
     cmp::min,
     convert::{AsMut, AsRef},
     sync::Arc,
+    time::Instant,
 };
 
 static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
 
+/// The number of transactions per execution thread executed at a time by blocks with an output
+/// limit, which bounds how many transactions past the cut get executed for nothing.
+const OUTPUT_LIMIT_BATCH_SIZE_PER_THREAD: usize = 8;
+
 #[derive(Clone)]
 pub struct AptosVM(pub(crate) AptosVMImpl);
 
@@ -77,7 +94,15 @@ struct AptosSimulationVM(AptosVM);
 
 impl AptosVM {
     pub fn new<S: StateView>(state: &S) -> Self {
-        Self(AptosVMImpl::new(state))
+        Self(AptosVMImpl::new(state, None))
+    }
+
+    /// Creates a VM sharing the Move VM kept in `warm_vm_cache`, if any.
+    pub(crate) fn new_with_warm_vm<S: StateView>(
+        state: &S,
+        warm_vm_cache: Option<&WarmVmCache>,
+    ) -> Self {
+        Self(AptosVMImpl::new(state, warm_vm_cache))
     }
 
     pub fn new_for_validation<S: StateView>(state: &S) -> Self {
@@ -109,6 +134,12 @@ impl AptosVM {
         }
     }
 
+    /// Enables per entry function execution metrics, for at most `max_functions` functions, when
+    /// invoked the first time.
+    pub fn set_entry_function_metrics_once(max_functions: usize) {
+        entry_function_metrics::enable_once(max_functions);
+    }
+
     /// Sets the # of async proof reading threads.
     pub fn set_num_proof_reading_threads_once(mut num_threads: usize) {
         // TODO(grao): Do more analysis to tune this magic number.
@@ -167,28 +198,15 @@ impl AptosVM {
         storage: &S,
         log_context: &AdapterLogSchema,
     ) -> (VMStatus, TransactionOutputExt) {
-        let mut session = self.0.new_session(storage, SessionId::txn_meta(txn_data));
         match TransactionStatus::from(error_code.clone()) {
-            TransactionStatus::Keep(status) => {
-                // The transaction should be charged for gas, so run the epilogue to do that.
-                // This is running in a new session that drops any side effects from the
-                // attempted transaction (e.g., spending funds that were needed to pay for gas),
-                // so even if the previous failure occurred while running the epilogue, it
-                // should not fail now. If it somehow fails here, there is no choice but to
-                // discard the transaction.
-                if let Err(e) = self.0.run_failure_epilogue(
-                    &mut session,
-                    gas_meter.balance(),
-                    txn_data,
-                    log_context,
-                ) {
-                    return discard_error_vm_status(e);
-                }
-                let txn_output =
-                    get_transaction_output(&mut (), session, gas_meter.balance(), txn_data, status)
-                        .unwrap_or_else(|e| discard_error_vm_status(e).1);
-                (error_code, txn_output)
-            }
+            TransactionStatus::Keep(status) => self.keep_failed_transaction(
+                error_code,
+                status,
+                gas_meter,
+                txn_data,
+                storage,
+                log_context,
+            ),
             TransactionStatus::Discard(status) => {
                 (VMStatus::Error(status), discard_error_output(status))
             }
@@ -196,8 +214,85 @@ impl AptosVM {
         }
     }
 
+    /// Generates the output of a failed transaction which is kept with `status`, and only charges
+    /// the sender for gas.
+    fn keep_failed_transaction<S: MoveResolverExt>(
+        &self,
+        error_code: VMStatus,
+        status: ExecutionStatus,
+        gas_meter: &mut AptosGasMeter,
+        txn_data: &TransactionMetadata,
+        storage: &S,
+        log_context: &AdapterLogSchema,
+    ) -> (VMStatus, TransactionOutputExt) {
+        let mut session = self.0.new_session(storage, SessionId::txn_meta(txn_data));
+        // The account of an implicit sender was dropped with the side effects of the
+        // transaction, so it's created again before the epilogue. `gas_meter` may be exhausted
+        // by now, so the creation gets a fresh meter of its own, and the gas it uses is charged
+        // on top, capped by the max gas amount that the prologue checked the sender can pay.
+        let gas_remaining = match self.create_implicit_account_after_failure(
+            &mut session,
+            storage,
+            gas_meter.balance(),
+            txn_data,
+            log_context,
+        ) {
+            Ok(gas_remaining) => gas_remaining,
+            Err(e) => return discard_error_vm_status(e),
+        };
+        // The transaction should be charged for gas, so run the epilogue to do that.
+        // This is running in a new session that drops any side effects from the
+        // attempted transaction (e.g., spending funds that were needed to pay for gas),
+        // so even if the previous failure occurred while running the epilogue, it
+        // should not fail now. If it somehow fails here, there is no choice but to
+        // discard the transaction.
+        if let Err(e) =
+            self.0
+                .run_failure_epilogue(&mut session, gas_remaining, txn_data, log_context)
+        {
+            return discard_error_vm_status(e);
+        }
+        let txn_output = get_transaction_output(&mut (), session, gas_remaining, txn_data, status)
+            .unwrap_or_else(|e| discard_error_vm_status(e).1);
+        (error_code, txn_output)
+    }
+
+    /// Creates the account of an implicit sender for the output of its failed transaction, with
+    /// a meter bounded by `IMPLICIT_ACCOUNT_CREATION_MAX_GAS`, and returns the gas left to the
+    /// transaction once the creation is paid for. The creation only runs framework code within
+    /// that bound, so a failure here is an invariant violation.
+    fn create_implicit_account_after_failure<S: MoveResolverExt>(
+        &self,
+        session: &mut SessionExt<S>,
+        storage: &S,
+        gas_remaining: Gas,
+        txn_data: &TransactionMetadata,
+        log_context: &AdapterLogSchema,
+    ) -> Result<Gas, VMStatus> {
+        let gas_params = self.0.get_gas_parameters(log_context)?;
+        let budget: Gas = IMPLICIT_ACCOUNT_CREATION_MAX_GAS.into();
+        let mut creation_meter = AptosGasMeter::new(gas_params.clone(), budget);
+        self.0
+            .run_implicit_account_creation(session, storage, &mut creation_meter, txn_data)
+            .map_err(|e| {
+                error!(
+                    *log_context,
+                    "[aptos_vm] Implicit account creation failed after a failed transaction: {:?}",
+                    e
+                );
+                VMStatus::Error(StatusCode::UNEXPECTED_ERROR_FROM_KNOWN_MOVE_FUNCTION)
+            })?;
+        let creation_gas = budget
+            .checked_sub(creation_meter.balance())
+            .expect("Balance should always be less than or equal to the budget");
+        Ok(gas_remaining
+            .checked_sub(creation_gas)
+            .unwrap_or_else(|| 0.into()))
+    }
+
     fn success_transaction_cleanup<S: MoveResolverExt>(
         &self,
+        storage: &S,
         mut session: SessionExt<S>,
         gas_meter: &mut AptosGasMeter,
         txn_data: &TransactionMetadata,
@@ -206,20 +301,35 @@ impl AptosVM {
         self.0
             .run_success_epilogue(&mut session, gas_meter.balance(), txn_data, log_context)?;
 
-        Ok((
-            VMStatus::Executed,
-            get_transaction_output(
-                &mut (),
-                session,
-                gas_meter.balance(),
+        let txn_output = get_transaction_output(
+            &mut (),
+            session,
+            gas_meter.balance(),
+            txn_data,
+            ExecutionStatus::Success,
+        )?;
+        // An output exceeding a limit fails the transaction, which keeps a status naming the
+        // limit instead of failing in storage.
+        if let Err(err) = self.0.change_set_configs().check_change_set(
+            txn_output.txn_output().write_set(),
+            txn_output.txn_output().events(),
+        ) {
+            let status = err.execution_status();
+            return Ok(self.keep_failed_transaction(
+                err.into_vm_status(),
+                status,
+                gas_meter,
                 txn_data,
-                ExecutionStatus::Success,
-            )?,
-        ))
+                storage,
+                log_context,
+            ));
+        }
+        Ok((VMStatus::Executed, txn_output))
     }
 
     fn execute_script_or_entry_function<S: MoveResolverExt>(
         &self,
+        storage: &S,
         mut session: SessionExt<S>,
         gas_meter: &mut AptosGasMeter,
         txn_data: &TransactionMetadata,
@@ -250,6 +360,7 @@ impl AptosVM {
                         convert_txn_args(script.args()),
                         &loaded_func,
                     )?;
+                    gas_meter.start_tick(None);
                     session.execute_script(
                         script.code(),
                         script.ty_args().to_vec(),
@@ -257,7 +368,15 @@ impl AptosVM {
                         gas_meter,
                     )
                 }
-                TransactionPayload::EntryFunction(script_fn) => {
+                TransactionPayload::EntryFunction(_)
+                | TransactionPayload::OrderlessEntryFunction(_) => {
+                    let script_fn = match payload {
+                        TransactionPayload::OrderlessEntryFunction(orderless) => {
+                            orderless.entry_function()
+                        }
+                        TransactionPayload::EntryFunction(script_fn) => script_fn,
+                        _ => unreachable!("not an entry function payload"),
+                    };
                     let mut senders = vec![txn_data.sender()];
 
                     senders.extend(txn_data.secondary_signers());
@@ -273,6 +392,7 @@ impl AptosVM {
                         script_fn.args().to_vec(),
                         &function,
                     )?;
+                    gas_meter.start_tick(Some(script_fn.module()));
                     session.execute_entry_function(
                         script_fn.module(),
                         script_fn.function(),
@@ -289,7 +409,7 @@ impl AptosVM {
 
             self.resolve_pending_code_publish(&mut session, gas_meter)?;
 
-            self.success_transaction_cleanup(session, gas_meter, txn_data, log_context)
+            self.success_transaction_cleanup(storage, session, gas_meter, txn_data, log_context)
         }
     }
 
@@ -339,6 +459,7 @@ impl AptosVM {
                         .iter()
                         .map(|s| MoveValue::Signer(*s).simple_serialize().unwrap())
                         .collect();
+                    gas_meter.start_tick(Some(&module.self_id()));
                     session.execute_function_bypass_visibility(
                         &module.self_id(),
                         init_func_name,
@@ -377,6 +498,7 @@ impl AptosVM {
     /// NativeCodeContext
     fn execute_modules<S: MoveResolverExt>(
         &self,
+        storage: &S,
         mut session: SessionExt<S>,
         gas_meter: &mut AptosGasMeter,
         txn_data: &TransactionMetadata,
@@ -406,7 +528,7 @@ impl AptosVM {
             &[txn_data.sender()],
         )?;
 
-        self.success_transaction_cleanup(session, gas_meter, txn_data, log_context)
+        self.success_transaction_cleanup(storage, session, gas_meter, txn_data, log_context)
     }
 
     /// Resolve a pending code publish request registered via the NativeCodeContext.
@@ -433,7 +555,9 @@ impl AptosVM {
 
             // Publish the bundle
             if check_compat {
-                session.publish_module_bundle(bundle.into_inner(), destination, gas_meter)?
+                session
+                    .publish_module_bundle(bundle.into_inner(), destination, gas_meter)
+                    .map_err(|err| Self::explain_incompatible_upgrade(session, &modules, err))?
             } else {
                 session.publish_module_bundle_relax_compatibility(
                     bundle.into_inner(),
@@ -449,6 +573,36 @@ impl AptosVM {
         }
     }
 
+    /// Lists the incompatible changes of an upgrade in the error message, if it was rejected
+    /// for breaking compatibility.
+    fn explain_incompatible_upgrade<S: MoveResolverExt>(
+        session: &mut SessionExt<S>,
+        modules: &[CompiledModule],
+        err: VMError,
+    ) -> VMError {
+        if err.major_status() != StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE {
+            return err;
+        }
+        let mut changes = vec![];
+        for module in modules {
+            let old_module = session
+                .get_data_store()
+                .load_module(&module.self_id())
+                .ok()
+                .and_then(|code| CompiledModule::deserialize(&code).ok());
+            if let Some(old_module) = old_module {
+                changes.extend(check_upgrade_compatibility(&old_module, module));
+            }
+        }
+        if changes.is_empty() {
+            return err;
+        }
+        let changes: Vec<_> = changes.iter().map(ToString::to_string).collect();
+        PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
+            .with_message(format!("incompatible changes: {}", changes.join("; ")))
+            .finish(err.location().clone())
+    }
+
     /// Validate a publish request.
     fn validate_publish_request(
         &self,
@@ -488,6 +642,7 @@ impl AptosVM {
         if let Err(err) = validate_signature_checked_transaction::<S, Self>(
             self,
             &mut session,
+            storage,
             txn,
             false,
             log_context,
@@ -497,12 +652,30 @@ impl AptosVM {
 
         let gas_params = unwrap_or_discard!(self.0.get_gas_parameters(log_context));
         let txn_data = TransactionMetadata::new(txn);
-        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
+        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), txn_data.max_gas_amount())
+            .with_execution_limits(self.0.execution_limits());
+
+        // The prologue accepts implicit senders without creating their account, which happens
+        // here instead, charged like the rest of the execution.
+        if let Err(err) =
+            self.0
+                .run_implicit_account_creation(&mut session, storage, &mut gas_meter, &txn_data)
+        {
+            return self.failed_transaction_cleanup_and_keep_vm_status(
+                err,
+                &mut gas_meter,
+                &txn_data,
+                storage,
+                log_context,
+            );
+        }
 
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
-            | payload @ TransactionPayload::EntryFunction(_) => self
+            | payload @ TransactionPayload::EntryFunction(_)
+            | payload @ TransactionPayload::OrderlessEntryFunction(_) => self
                 .execute_script_or_entry_function(
+                    storage,
                     session,
                     &mut gas_meter,
                     &txn_data,
@@ -510,7 +683,7 @@ impl AptosVM {
                     log_context,
                 ),
             TransactionPayload::ModuleBundle(m) => {
-                self.execute_modules(session, &mut gas_meter, &txn_data, m, log_context)
+                self.execute_modules(storage, session, &mut gas_meter, &txn_data, m, log_context)
             }
         };
 
@@ -706,15 +879,95 @@ impl AptosVM {
         Ok((VMStatus::Executed, output))
     }
 
+    /// Executes a validator transaction on behalf of the VM. Its payload is only trusted if it is
+    /// signed by a quorum of the current validator set, so the transaction is discarded
+    /// otherwise. Unlike the block prologue, a failing validator transaction is discarded rather
+    /// than failing the whole block, so that a malformed payload, or a chain where
+    /// `validator_txn` has not been published yet, can't halt the chain.
+    pub(crate) fn process_validator_transaction<S: MoveResolverExt>(
+        &self,
+        storage: &S,
+        txn: &ValidatorTransaction,
+        log_context: &AdapterLogSchema,
+    ) -> (VMStatus, TransactionOutputExt) {
+        let txn_data = TransactionMetadata {
+            sender: account_config::reserved_vm_address(),
+            max_gas_amount: 0.into(),
+            ..Default::default()
+        };
+        let mut gas_meter = UnmeteredGasMeter;
+        let mut session = self.0.new_session(storage, SessionId::validator_txn(txn));
+
+        let result = validator_txn_validation::verify_validator_transaction(storage, txn)
+            .and_then(|_| {
+                let args = serialize_values(&txn.get_move_args(txn_data.sender));
+                session
+                    .execute_function_bypass_visibility(
+                        &VALIDATOR_TXN_MODULE,
+                        VALIDATOR_TXN_PROCESS,
+                        vec![],
+                        args,
+                        &mut gas_meter,
+                    )
+                    .map_err(|err| err.into_vm_status())
+            })
+            .and_then(|_| match txn {
+                ValidatorTransaction::ObservedJWKUpdate(payload) => {
+                    let update = QuorumCertifiedUpdate::from_bytes(payload)
+                        .map_err(|_| VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT))?
+                        .update;
+                    let args = serialize_values(&update.get_move_args(txn_data.sender));
+                    session
+                        .execute_function_bypass_visibility(
+                            &KEYLESS_ACCOUNT_MODULE,
+                            UPSERT_OBSERVED_JWKS,
+                            vec![],
+                            args,
+                            &mut gas_meter,
+                        )
+                        .map(|_| ())
+                        .map_err(|err| err.into_vm_status())
+                }
+                _ => Ok(()),
+            });
+        if let Err(vm_status) = result {
+            warn!(
+                *log_context,
+                "[aptos_vm] Discarding validator transaction {}: {:?}", txn, vm_status
+            );
+            return discard_error_vm_status(vm_status);
+        }
+        SYSTEM_TRANSACTIONS_EXECUTED.inc();
+
+        match get_transaction_output(
+            &mut (),
+            session,
+            0.into(),
+            &txn_data,
+            ExecutionStatus::Success,
+        ) {
+            Ok(output) => (VMStatus::Executed, output),
+            Err(vm_status) => discard_error_vm_status(vm_status),
+        }
+    }
+
     /// Alternate form of 'execute_block' that keeps the vm_status before it goes into the
     /// `TransactionOutput`
     pub fn execute_block_and_keep_vm_status(
         transactions: Vec<Transaction>,
         state_view: &impl StateView,
+    ) -> Result<Vec<(VMStatus, TransactionOutput)>, VMStatus> {
+        Self::execute_block_and_keep_vm_status_with_warm_vm(transactions, state_view, None)
+    }
+
+    pub(crate) fn execute_block_and_keep_vm_status_with_warm_vm(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        warm_vm_cache: Option<&WarmVmCache>,
     ) -> Result<Vec<(VMStatus, TransactionOutput)>, VMStatus> {
         let mut state_view_cache = StateViewCache::new(state_view);
         let count = transactions.len();
-        let vm = AptosVM::new(&state_view_cache);
+        let vm = AptosVM::new_with_warm_vm(&state_view_cache, warm_vm_cache);
         let res = adapter_common::execute_block_impl(&vm, transactions, &mut state_view_cache)?;
         // Record the histogram count for transactions per block.
         BLOCK_TRANSACTION_COUNT.observe(count as f64);
@@ -731,6 +984,69 @@ impl AptosVM {
         simulation_vm.simulate_signed_transaction(&state_view.as_move_resolver(), txn, &log_context)
     }
 
+    /// Calls a view function, i.e. a public function which isn't an entry function and returns
+    /// values, and returns the BCS encoded values. The arguments are BCS encoded too. Functions
+    /// taking a signer can't be called, as they would be given whichever address was passed in.
+    pub fn execute_view_function(
+        state_view: &impl StateView,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> Result<Vec<Vec<u8>>> {
+        let vm = AptosVM::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let resolver = state_view.as_move_resolver();
+
+        let module =
+            vm.0.load_module(module_id, &resolver)
+                .map_err(|err| anyhow!("Failed to load module {}: {:?}", module_id, err))?;
+        let function = module
+            .function_defs
+            .iter()
+            .find(|def| {
+                module.identifier_at(module.function_handle_at(def.function).name) == function_name
+            })
+            .ok_or_else(|| anyhow!("Function {}::{} doesn't exist", module_id, function_name))?;
+        let handle = module.function_handle_at(function.function);
+        if function.visibility != Visibility::Public
+            || function.is_entry
+            || module.signature_at(handle.return_).is_empty()
+        {
+            bail!("{}::{} is not a view function", module_id, function_name);
+        }
+        let takes_signer = module.signature_at(handle.parameters).0.iter().any(|token| {
+            matches!(token, SignatureToken::Signer)
+                || matches!(token, SignatureToken::Reference(inner) if **inner == SignatureToken::Signer)
+        });
+        if takes_signer {
+            bail!("{}::{} takes a signer", module_id, function_name);
+        }
+
+        let gas_params =
+            vm.0.get_gas_parameters(&log_context)
+                .map_err(|status| anyhow!("Failed to load gas parameters: {:?}", status))?;
+        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), gas_budget)
+            .with_execution_limits(vm.0.execution_limits());
+        gas_meter.start_tick(Some(module_id));
+        let mut session = vm.0.new_session(&resolver, SessionId::Void);
+        let return_values = session
+            .execute_function_bypass_visibility(
+                module_id,
+                function_name,
+                type_args,
+                arguments,
+                &mut gas_meter,
+            )
+            .map_err(|err| anyhow!("{:?}", err.into_vm_status()))?
+            .return_values;
+        Ok(return_values
+            .into_iter()
+            .map(|(bytes, _layout)| bytes)
+            .collect())
+    }
+
     fn run_prologue_with_payload<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
@@ -748,6 +1064,18 @@ impl AptosVM {
                 self.0.check_gas(txn_data, log_context)?;
                 self.0.run_script_prologue(session, txn_data, log_context)
             }
+            TransactionPayload::OrderlessEntryFunction(_) => {
+                if !self
+                    .0
+                    .features()
+                    .is_enabled(FeatureFlag::OrderlessTransactions)
+                {
+                    return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+                }
+                self.0.check_gas(txn_data, log_context)?;
+                self.0
+                    .run_orderless_prologue(session, txn_data, log_context)
+            }
             TransactionPayload::ModuleBundle(_module) => {
                 self.0.check_gas(txn_data, log_context)?;
                 self.0.run_module_prologue(session, txn_data, log_context)
@@ -766,6 +1094,80 @@ impl VMExecutor for AptosVM {
     fn execute_block(
         transactions: Vec<Transaction>,
         state_view: &impl StateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Self::execute_block_impl(transactions, state_view, None)
+    }
+
+    fn execute_block_with_warm_vm(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        warm_vm_cache: &WarmVmCache,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Self::execute_block_impl(transactions, state_view, Some(warm_vm_cache))
+    }
+
+    /// Executes the block a batch of transactions at a time, each batch against the state left by
+    /// the previous ones, and stops at the first user transaction after the limit is crossed, so
+    /// that the transactions after the cut are not executed, in parallel or not.
+    fn execute_block_with_output_limit(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        warm_vm_cache: &WarmVmCache,
+        output_limit: &BlockOutputLimit,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let num_txns = transactions.len();
+        let batch_size = Self::get_concurrency_level() * OUTPUT_LIMIT_BATCH_SIZE_PER_THREAD;
+        let mut state_view_cache = StateViewCache::new(state_view);
+        let mut tracker = BlockOutputTracker::new(*output_limit);
+        let mut outputs = Vec::with_capacity(num_txns);
+        let mut limit_crossed = false;
+        let mut cut = false;
+        while !cut && outputs.len() < num_txns {
+            let batch_end = min(outputs.len() + batch_size, num_txns);
+            let batch = transactions[outputs.len()..batch_end].to_vec();
+            let mut batch_outputs =
+                Self::execute_block_impl(batch, &state_view_cache, Some(warm_vm_cache))?
+                    .into_iter();
+            for output in &mut batch_outputs {
+                limit_crossed |= tracker.record(&output);
+                state_view_cache.push_write_set(output.write_set());
+                outputs.push(output);
+                cut = limit_crossed && transactions.get(outputs.len()).map_or(false, is_cuttable);
+                if cut || tracker.is_reconfigured() {
+                    break;
+                }
+            }
+            // The warm VM executed the outputs after the cut, which are discarded.
+            warm_vm_cache.record_discarded_outputs(batch_outputs.as_slice());
+            // A reconfiguration ends the block, the transactions after it are retried.
+            cut |= tracker.is_reconfigured();
+        }
+
+        if outputs.len() < num_txns {
+            if tracker.is_reconfigured() {
+                outputs.resize_with(num_txns, || {
+                    TransactionOutput::new(WriteSet::default(), vec![], 0, TransactionStatus::Retry)
+                });
+            } else {
+                let num_executed = outputs.len();
+                outputs.extend(
+                    transactions
+                        .iter()
+                        .enumerate()
+                        .skip(num_executed)
+                        .map(|(idx, txn)| cut_output(txn, idx + 1 == num_txns)),
+                );
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+impl AptosVM {
+    fn execute_block_impl(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        warm_vm_cache: Option<&WarmVmCache>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         fail_point!("move_adapter::execute_block", |_| {
             Err(VMStatus::Error(
@@ -773,17 +1175,39 @@ impl VMExecutor for AptosVM {
             ))
         });
 
+        match warm_vm_cache {
+            // The warm VM learns which modules it may load through the state view.
+            Some(warm_vm_cache) => Self::execute_block_with_state_view(
+                transactions,
+                &WarmVmStateView::new(state_view, warm_vm_cache),
+                Some(warm_vm_cache),
+            ),
+            None => Self::execute_block_with_state_view(transactions, state_view, None),
+        }
+    }
+
+    fn execute_block_with_state_view(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        warm_vm_cache: Option<&WarmVmCache>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let concurrency_level = Self::get_concurrency_level();
         if concurrency_level > 1 {
-            let (result, err) = crate::parallel_executor::ParallelAptosVM::execute_block(
-                transactions,
-                state_view,
-                concurrency_level,
-            )?;
+            let (result, err) =
+                crate::parallel_executor::ParallelAptosVM::execute_block_with_warm_vm(
+                    transactions,
+                    state_view,
+                    concurrency_level,
+                    warm_vm_cache,
+                )?;
             debug!("Parallel execution error {:?}", err);
             Ok(result)
         } else {
-            let output = Self::execute_block_and_keep_vm_status(transactions, state_view)?;
+            let output = Self::execute_block_and_keep_vm_status_with_warm_vm(
+                transactions,
+                state_view,
+                warm_vm_cache,
+            )?;
             Ok(output
                 .into_iter()
                 .map(|(_vm_status, txn_output)| txn_output)
@@ -812,6 +1236,14 @@ impl VMValidator for AptosVM {
     ) -> VMValidatorResult {
         validate_signed_transaction(self, transaction, state_view)
     }
+
+    fn validate_transactions(
+        &self,
+        transactions: Vec<SignedTransaction>,
+        state_view: &impl StateView,
+    ) -> Vec<VMValidatorResult> {
+        validate_signed_transactions(self, transactions, state_view)
+    }
 }
 
 impl VMAdapter for AptosVM {
@@ -827,6 +1259,12 @@ impl VMAdapter for AptosVM {
         txn.check_signature()
     }
 
+    fn check_signatures_for_validation(
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Result<SignatureCheckedTransaction>> {
+        SignedTransaction::check_signatures_batched(txns)
+    }
+
     fn check_transaction_format(&self, txn: &SignedTransaction) -> Result<(), VMStatus> {
         if txn.contains_duplicate_signers() {
             return Err(VMStatus::Error(StatusCode::SIGNERS_CONTAIN_DUPLICATES));
@@ -835,6 +1273,30 @@ impl VMAdapter for AptosVM {
         Ok(())
     }
 
+    fn check_authenticators<S: MoveResolverExt>(
+        &self,
+        resolver: &S,
+        txn: &SignatureCheckedTransaction,
+    ) -> Result<(), VMStatus> {
+        let authenticator = txn.authenticator();
+        let mut authenticators = authenticator.secondary_signers();
+        authenticators.push(authenticator.sender());
+        for authenticator in &authenticators {
+            let feature = match authenticator {
+                AccountAuthenticator::Ed25519 { .. }
+                | AccountAuthenticator::MultiEd25519 { .. } => continue,
+                AccountAuthenticator::WebAuthn { .. } => FeatureFlag::WebAuthnAuthenticator,
+                AccountAuthenticator::Keyless { .. } => FeatureFlag::KeylessAuthenticator,
+                AccountAuthenticator::MultiKey { .. } => FeatureFlag::MultiKeyAuthenticator,
+            };
+            if !self.0.features().is_enabled(feature) {
+                debug!("Authenticator scheme is not enabled: {:?}", feature);
+                return Err(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
+            }
+        }
+        keyless_validation::validate_authenticators(resolver, &authenticator)
+    }
+
     fn run_prologue<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
@@ -866,6 +1328,11 @@ impl VMAdapter for AptosVM {
                     self.process_block_prologue(data_cache, block_metadata.clone(), log_context)?;
                 (vm_status, output, Some("block_prologue".to_string()))
             }
+            PreprocessedTransaction::ValidatorTransaction(txn) => {
+                let (vm_status, output) =
+                    self.process_validator_transaction(data_cache, txn, log_context);
+                (vm_status, output, Some("validator_transaction".to_string()))
+            }
             PreprocessedTransaction::WaypointWriteSet(write_set_payload) => {
                 let (vm_status, output) = self.process_waypoint_change_set(
                     data_cache,
@@ -877,8 +1344,26 @@ impl VMAdapter for AptosVM {
             PreprocessedTransaction::UserTransaction(txn) => {
                 let sender = txn.sender().to_string();
                 let _timer = TXN_TOTAL_SECONDS.start_timer();
+                let start = Instant::now();
                 let (vm_status, output) =
                     self.execute_user_transaction(data_cache, txn, log_context);
+                let entry_function = match txn.payload() {
+                    TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+                    TransactionPayload::OrderlessEntryFunction(orderless) => {
+                        Some(orderless.entry_function())
+                    }
+                    _ => None,
+                };
+                if let Some(entry_function) = entry_function {
+                    if entry_function_metrics::is_enabled() {
+                        entry_function_metrics::record(
+                            entry_function,
+                            start.elapsed(),
+                            output.txn_output().gas_used(),
+                            output.txn_output().status(),
+                        );
+                    }
+                }
 
                 // Increment the counter for user transactions executed.
                 let counter_label = match output.txn_output().status() {
@@ -966,12 +1451,30 @@ impl AptosSimulationVM {
             Err(err) => return discard_error_vm_status(err),
             Ok(s) => s,
         };
-        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
+        let mut gas_meter = AptosGasMeter::new(gas_params.clone(), txn_data.max_gas_amount())
+            .with_execution_limits(self.0 .0.execution_limits());
+
+        if let Err(err) = self.0 .0.run_implicit_account_creation(
+            &mut session,
+            storage,
+            &mut gas_meter,
+            &txn_data,
+        ) {
+            return self.0.failed_transaction_cleanup_and_keep_vm_status(
+                err,
+                &mut gas_meter,
+                &txn_data,
+                storage,
+                log_context,
+            );
+        }
 
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
-            | payload @ TransactionPayload::EntryFunction(_) => {
+            | payload @ TransactionPayload::EntryFunction(_)
+            | payload @ TransactionPayload::OrderlessEntryFunction(_) => {
                 self.0.execute_script_or_entry_function(
+                    storage,
                     session,
                     &mut gas_meter,
                     &txn_data,
@@ -981,7 +1484,7 @@ impl AptosSimulationVM {
             }
             TransactionPayload::ModuleBundle(m) => {
                 self.0
-                    .execute_modules(session, &mut gas_meter, &txn_data, m, log_context)
+                    .execute_modules(storage, session, &mut gas_meter, &txn_data, m, log_context)
             }
         };
 
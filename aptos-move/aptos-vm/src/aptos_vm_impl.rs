@@ -3,20 +3,30 @@
 
 use crate::{
     access_path_cache::AccessPathCache,
+    change_set_configs::ChangeSetConfigs,
     counters::*,
     data_cache::RemoteStorage,
     errors::{convert_epilogue_error, convert_prologue_error, expect_only_successful_execution},
     logging::AdapterLogSchema,
-    move_vm_ext::{MoveResolverExt, MoveVmExt, SessionExt, SessionId},
+    move_vm_ext::{
+        warm_vm_cache::WarmVmId, MoveResolverExt, MoveVmExt, SessionExt, SessionId, WarmVmCache,
+    },
     transaction_metadata::TransactionMetadata,
 };
 use aptos_aggregator::transaction::TransactionOutputExt;
-use aptos_gas::{AptosGasParameters, FromOnChainGasSchedule, Gas, NativeGasParameters};
+use aptos_gas::{
+    AptosGasMeter, AptosGasParameters, FromOnChainGasSchedule, Gas, NativeGasParameters,
+};
 use aptos_logger::prelude::*;
 use aptos_state_view::StateView;
 use aptos_types::{
-    account_config::{ChainSpecificAccountInfo, APTOS_CHAIN_INFO, CORE_CODE_ADDRESS},
-    on_chain_config::{GasSchedule, OnChainConfig, Version, APTOS_VERSION_3},
+    account_config::{
+        AccountResource, ChainSpecificAccountInfo, APTOS_CHAIN_INFO, CORE_CODE_ADDRESS,
+    },
+    on_chain_config::{
+        ConfigStorage, ConfigurationResource, EnabledFeatures, ExecutionLimits, FeatureFlag,
+        Features, GasSchedule, OnChainConfig, OnChainExecutionConfig, Version, APTOS_VERSION_3,
+    },
     transaction::{ExecutionStatus, TransactionOutput, TransactionStatus},
     vm_status::{StatusCode, VMStatus},
 };
@@ -24,6 +34,8 @@ use fail::fail_point;
 use move_deps::{
     move_binary_format::{errors::VMResult, CompiledModule},
     move_core_types::{
+        ident_str,
+        identifier::IdentStr,
         language_storage::ModuleId,
         move_resource::MoveStructType,
         resolver::ResourceResolver,
@@ -34,6 +46,16 @@ use move_deps::{
 };
 use std::sync::Arc;
 
+/// The prologue and epilogue of orderless transactions in the account module. Unlike the others,
+/// they aren't part of `ChainSpecificAccountInfo`, whose on-chain layout is fixed.
+const ORDERLESS_PROLOGUE_NAME: &IdentStr = ident_str!("orderless_prologue");
+const ORDERLESS_EPILOGUE_NAME: &IdentStr = ident_str!("orderless_epilogue");
+/// Creates the account of an implicit sender, which doesn't exist before its first transaction.
+const IMPLICIT_ACCOUNT_CREATION_NAME: &IdentStr = ident_str!("create_implicit_sender_account");
+/// The gas available to create the account of an implicit sender whose transaction failed. It's
+/// charged on top of the gas used by the transaction, up to its max gas amount.
+pub(crate) const IMPLICIT_ACCOUNT_CREATION_MAX_GAS: u64 = 2_000;
+
 #[derive(Clone)]
 /// A wrapper to make VMRuntime standalone and thread safe.
 pub struct AptosVMImpl {
@@ -41,18 +63,21 @@ pub struct AptosVMImpl {
     gas_params: Option<AptosGasParameters>,
     version: Option<Version>,
     chain_account_info: Option<ChainSpecificAccountInfo>,
+    change_set_configs: ChangeSetConfigs,
+    execution_limits: ExecutionLimits,
 }
 
 impl AptosVMImpl {
     #[allow(clippy::new_without_default)]
-    pub fn new<S: StateView>(state: &S) -> Self {
+    pub fn new<S: StateView>(state: &S, warm_vm_cache: Option<&WarmVmCache>) -> Self {
         let storage = RemoteStorage::new(state);
 
         // TODO(Gas): this should not panic
-        let gas_params = GasSchedule::fetch_config(&storage).and_then(|gas_schedule| {
-            let gas_schedule = gas_schedule.to_btree_map();
-            AptosGasParameters::from_on_chain_gas_schedule(&gas_schedule)
-        });
+        let gas_schedule =
+            GasSchedule::fetch_config(&storage).map(|schedule| schedule.to_btree_map());
+        let gas_params = gas_schedule
+            .as_ref()
+            .and_then(AptosGasParameters::from_on_chain_gas_schedule);
 
         // TODO(Gas): this doesn't look right.
         let native_gas_params = match &gas_params {
@@ -60,14 +85,36 @@ impl AptosVMImpl {
             None => NativeGasParameters::zeros(),
         };
 
-        let inner = MoveVmExt::new(native_gas_params)
-            .expect("should be able to create Move VM; check if there are duplicated natives");
-
+        let features = Self::get_enabled_features(&storage);
+        let create_vm = || MoveVmExt::new_with_features(native_gas_params, features.clone());
+        // Blocks share a VM, so that they don't load the same modules over and over.
+        let move_vm = match warm_vm_cache {
+            Some(warm_vm_cache) => {
+                let id = WarmVmId {
+                    gas_schedule,
+                    features: features.clone(),
+                };
+                warm_vm_cache.get_warm_vm(id, create_vm)
+            }
+            None => create_vm().map(Arc::new),
+        }
+        .expect("should be able to create Move VM; check if there are duplicated natives");
+
+        // Without a gas schedule, no transaction gets to produce an output anyway.
+        let change_set_configs = ChangeSetConfigs::new_from_gas_feature_version(
+            gas_params
+                .as_ref()
+                .map_or(0, |gas_params| gas_params.feature_version),
+        );
         let mut vm = Self {
-            move_vm: Arc::new(inner),
+            move_vm,
             gas_params,
             version: None,
             chain_account_info: None,
+            change_set_configs,
+            execution_limits: OnChainExecutionConfig::fetch_config(&storage)
+                .unwrap_or_default()
+                .execution_limits(),
         };
         vm.version = Version::fetch_config(&storage);
         vm.chain_account_info = Self::get_chain_specific_account_info(&RemoteStorage::new(state));
@@ -83,11 +130,15 @@ impl AptosVMImpl {
         let inner = MoveVmExt::new(gas_params.natives.clone())
             .expect("should be able to create Move VM; check if there are duplicated natives");
 
+        let change_set_configs =
+            ChangeSetConfigs::new_from_gas_feature_version(gas_params.feature_version);
         Self {
             move_vm: Arc::new(inner),
             gas_params: Some(gas_params),
             version: Some(version),
             chain_account_info: None,
+            change_set_configs,
+            execution_limits: ExecutionLimits::default(),
         }
     }
 
@@ -102,6 +153,39 @@ impl AptosVMImpl {
             .unwrap_or(&APTOS_CHAIN_INFO)
     }
 
+    /// The limits on the outputs of user transactions
+    pub(crate) fn change_set_configs(&self) -> &ChangeSetConfigs {
+        &self.change_set_configs
+    }
+
+    /// The limits on the execution of transactions, from the on-chain execution config
+    pub(crate) fn execution_limits(&self) -> ExecutionLimits {
+        self.execution_limits
+    }
+
+    /// The features enabled in the current epoch. Without a `Features` config, e.g. before it's
+    /// published, no feature is enabled.
+    fn get_enabled_features<S: ConfigStorage + ResourceResolver>(
+        remote_cache: &S,
+    ) -> EnabledFeatures {
+        let features = match Features::fetch_config(remote_cache) {
+            Some(features) => features,
+            None => return EnabledFeatures::default(),
+        };
+        let epoch = remote_cache
+            .get_resource(&CORE_CODE_ADDRESS, &ConfigurationResource::struct_tag())
+            .ok()
+            .flatten()
+            .and_then(|blob| bcs::from_bytes::<ConfigurationResource>(&blob).ok())
+            .map_or(0, |config| config.epoch());
+        features.enabled_at(epoch)
+    }
+
+    /// The features enabled in the current epoch
+    pub fn features(&self) -> &EnabledFeatures {
+        self.move_vm.features()
+    }
+
     // TODO: Move this to an on-chain config once those are a part of the core framework
     fn get_chain_specific_account_info<S: ResourceResolver>(
         remote_cache: &S,
@@ -322,6 +406,112 @@ impl AptosVMImpl {
             .or_else(|err| convert_prologue_error(chain_specific_info, err, log_context))
     }
 
+    /// Run the prologue of an orderless transaction by calling into `ORDERLESS_PROLOGUE_NAME`
+    /// function stored in the `ACCOUNT_MODULE` on chain. It checks the nonce of the transaction
+    /// instead of its sequence number.
+    pub(crate) fn run_orderless_prologue<S: MoveResolverExt>(
+        &self,
+        session: &mut SessionExt<S>,
+        txn_data: &TransactionMetadata,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(), VMStatus> {
+        let chain_specific_info = self.chain_info();
+        let txn_nonce = txn_data
+            .replay_nonce()
+            .ok_or(VMStatus::Error(StatusCode::UNREACHABLE))?;
+        let txn_authentication_key = txn_data.authentication_key();
+        let txn_gas_price = txn_data.gas_unit_price();
+        let txn_max_gas_units = txn_data.max_gas_amount();
+        let txn_expiration_timestamp_secs = txn_data.expiration_timestamp_secs();
+        let chain_id = txn_data.chain_id();
+        let mut gas_meter = UnmeteredGasMeter;
+        session
+            .execute_function_bypass_visibility(
+                &chain_specific_info.module_id(),
+                ORDERLESS_PROLOGUE_NAME,
+                vec![],
+                serialize_values(&vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_nonce),
+                    MoveValue::vector_u8(txn_authentication_key.to_vec()),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(txn_expiration_timestamp_secs),
+                    MoveValue::U8(chain_id.id()),
+                ]),
+                &mut gas_meter,
+            )
+            .map(|_return_vals| ())
+            .map_err(expect_no_verification_errors)
+            .or_else(|err| convert_prologue_error(chain_specific_info, err, log_context))
+    }
+
+    /// Create the account of the sender if it's an implicit sender, whose account only gets
+    /// created by its first transaction, by calling into `IMPLICIT_ACCOUNT_CREATION_NAME`. Unlike
+    /// the prologue, this is charged to `gas_meter`, so that the sender pays for its account.
+    pub(crate) fn run_implicit_account_creation<S: MoveResolverExt>(
+        &self,
+        session: &mut SessionExt<S>,
+        storage: &S,
+        gas_meter: &mut AptosGasMeter,
+        txn_data: &TransactionMetadata,
+    ) -> Result<(), VMStatus> {
+        if !self.features().is_enabled(FeatureFlag::ImplicitAccounts) {
+            return Ok(());
+        }
+        let account = storage
+            .get_resource(&txn_data.sender(), &AccountResource::struct_tag())
+            .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR))?;
+        if account.is_some() {
+            return Ok(());
+        }
+        gas_meter.start_tick(Some(&self.chain_info().module_id()));
+        session
+            .execute_function_bypass_visibility(
+                &self.chain_info().module_id(),
+                IMPLICIT_ACCOUNT_CREATION_NAME,
+                vec![],
+                serialize_values(&vec![MoveValue::Signer(txn_data.sender)]),
+                gas_meter,
+            )
+            .map(|_return_vals| ())
+            .map_err(|e| e.into_vm_status())
+    }
+
+    /// The name and arguments of the epilogue of a transaction: orderless transactions record
+    /// their nonce, others increment the sequence number of the sender.
+    fn epilogue_function_and_args<'a>(
+        chain_specific_info: &'a ChainSpecificAccountInfo,
+        gas_remaining: Gas,
+        txn_data: &TransactionMetadata,
+    ) -> (&'a IdentStr, Vec<MoveValue>) {
+        let txn_gas_price = txn_data.gas_unit_price();
+        let txn_max_gas_units = txn_data.max_gas_amount();
+        match txn_data.replay_nonce() {
+            Some(txn_nonce) => (
+                ORDERLESS_EPILOGUE_NAME,
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_nonce),
+                    MoveValue::U64(txn_data.expiration_timestamp_secs()),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+            None => (
+                chain_specific_info.user_epilogue_name.as_ident_str(),
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_data.sequence_number()),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+        }
+    }
+
     /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
     /// in the `ACCOUNT_MODULE` on chain.
     pub(crate) fn run_success_epilogue<S: MoveResolverExt>(
@@ -338,22 +528,15 @@ impl AptosVMImpl {
         });
 
         let chain_specific_info = self.chain_info();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
+        let (epilogue_name, args) =
+            Self::epilogue_function_and_args(chain_specific_info, gas_remaining, txn_data);
         session
             .execute_function_bypass_visibility(
                 &chain_specific_info.module_id(),
-                &chain_specific_info.user_epilogue_name,
+                epilogue_name,
                 // TODO: Deprecate this once we remove gas currency on the Move side.
                 vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
@@ -371,33 +554,20 @@ impl AptosVMImpl {
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
         let chain_specific_info = self.chain_info();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
+        let (epilogue_name, args) =
+            Self::epilogue_function_and_args(chain_specific_info, gas_remaining, txn_data);
         session
             .execute_function_bypass_visibility(
                 &chain_specific_info.module_id(),
-                &chain_specific_info.user_epilogue_name,
+                epilogue_name,
                 // TODO: Deprecate this once we remove gas currency on the Move side.
                 vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
             .map_err(expect_no_verification_errors)
-            .or_else(|e| {
-                expect_only_successful_execution(
-                    e,
-                    chain_specific_info.user_epilogue_name.as_str(),
-                    log_context,
-                )
-            })
+            .or_else(|e| expect_only_successful_execution(e, epilogue_name.as_str(), log_context))
     }
 
     pub fn new_session<'r, R: MoveResolverExt>(
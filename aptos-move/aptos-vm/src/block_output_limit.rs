@@ -0,0 +1,129 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cutting blocks short once the cumulative output of their transactions crosses a
+//! `BlockOutputLimit`.
+//!
+//! The user transactions after the one crossing the limit get a `Retry` output, so that they stay
+//! in mempool and go in a later block. The block metadata and validator transactions consensus puts
+//! ahead of them are never cut, as they aren't in mempool to be retried: a block is only cut from
+//! its first user transaction after the limit is crossed. A trailing state checkpoint is kept so
+//! that the block still ends with one. Nothing is cut once the block reconfigures, which ends it
+//! anyway.
+
+use aptos_types::{
+    on_chain_config::{new_epoch_event_key, BlockOutputLimit},
+    transaction::{ExecutionStatus, Transaction, TransactionOutput, TransactionStatus},
+    write_set::{WriteOp, WriteSet},
+};
+
+/// The cumulative output of the transactions of a block, in order.
+pub struct BlockOutputTracker {
+    limit: BlockOutputLimit,
+    write_set_bytes: u64,
+    num_events: u64,
+    reconfigured: bool,
+}
+
+impl BlockOutputTracker {
+    pub fn new(limit: BlockOutputLimit) -> Self {
+        Self {
+            limit,
+            write_set_bytes: 0,
+            num_events: 0,
+            reconfigured: false,
+        }
+    }
+
+    /// Adds the output of the next transaction, returns whether the transactions after it must be
+    /// cut.
+    pub fn record(&mut self, output: &TransactionOutput) -> bool {
+        if self.reconfigured {
+            return false;
+        }
+        let new_epoch_event_key = new_epoch_event_key();
+        if output
+            .events()
+            .iter()
+            .any(|event| *event.key() == new_epoch_event_key)
+        {
+            self.reconfigured = true;
+            return false;
+        }
+        self.write_set_bytes = self
+            .write_set_bytes
+            .saturating_add(write_set_size(output.write_set()));
+        self.num_events = self.num_events.saturating_add(output.events().len() as u64);
+        self.write_set_bytes > self.limit.max_write_set_bytes
+            || self.num_events > self.limit.max_events
+    }
+
+    /// Whether one of the outputs recorded so far reconfigures, which ends the block.
+    pub fn is_reconfigured(&self) -> bool {
+        self.reconfigured
+    }
+}
+
+/// Whether `txn` can be cut from its block, see the module documentation.
+pub fn is_cuttable(txn: &Transaction) -> bool {
+    matches!(txn, Transaction::UserTransaction(_))
+}
+
+/// The output of a transaction cut from its block.
+pub fn cut_output(txn: &Transaction, is_last: bool) -> TransactionOutput {
+    let status = match txn {
+        // The output the VM gives to the checkpoint ending a block.
+        Transaction::StateCheckpoint(_) if is_last => {
+            TransactionStatus::Keep(ExecutionStatus::Success)
+        }
+        _ => TransactionStatus::Retry,
+    };
+    TransactionOutput::new(WriteSet::default(), vec![], 0, status)
+}
+
+/// Cuts a block whose transactions were all executed, replacing the outputs of the transactions
+/// from the first user transaction after the one crossing `limit` with `cut_output`. Returns the
+/// number of transactions cut.
+pub fn limit_outputs(
+    transactions: &[Transaction],
+    outputs: &mut [TransactionOutput],
+    limit: &BlockOutputLimit,
+) -> usize {
+    let mut tracker = BlockOutputTracker::new(*limit);
+    let crossing = match outputs.iter().position(|output| tracker.record(output)) {
+        Some(idx) => idx,
+        None => return 0,
+    };
+    let first_cut = match transactions.iter().skip(crossing + 1).position(is_cuttable) {
+        Some(offset) => crossing + 1 + offset,
+        None => return 0,
+    };
+    let mut num_cut = 0;
+    for (idx, (txn, output)) in transactions
+        .iter()
+        .zip(outputs.iter_mut())
+        .enumerate()
+        .skip(first_cut)
+    {
+        let cut = cut_output(txn, idx + 1 == transactions.len());
+        if matches!(cut.status(), TransactionStatus::Retry) {
+            num_cut += 1;
+        }
+        *output = cut;
+    }
+    num_cut
+}
+
+/// The size of a write set, counting the state keys as well as the values.
+pub fn write_set_size(write_set: &WriteSet) -> u64 {
+    write_set
+        .iter()
+        .map(|(state_key, write_op)| {
+            let value_bytes = match write_op {
+                WriteOp::Creation(blob) | WriteOp::Modification(blob) => blob.len(),
+                WriteOp::Deletion => 0,
+            };
+            (state_key.size() + value_bytes) as u64
+        })
+        .sum()
+}
@@ -0,0 +1,274 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Limits on the output of a transaction, enforced once its change set is built so that an
+//! oversized output fails the transaction instead of failing deep in storage. The status of such a
+//! transaction tells which limit it exceeded.
+
+use aptos_gas::GAS_FEATURE_VERSION_CHANGE_SET_LIMITS;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
+    transaction::{ChangeSetLimit, ExecutionStatus},
+    write_set::{WriteOp, WriteSet},
+};
+use move_deps::move_core_types::vm_status::{StatusCode, VMStatus};
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeSetConfigs {
+    max_bytes_per_write_op: u64,
+    max_bytes_all_write_ops_per_transaction: u64,
+    max_write_ops_per_transaction: u64,
+    max_events_per_transaction: u64,
+}
+
+/// A limit of `ChangeSetConfigs` exceeded by a change set
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChangeSetLimitError {
+    WriteOpTooLarge {
+        state_key: StateKey,
+        bytes: u64,
+        limit: u64,
+    },
+    WriteSetTooLarge {
+        bytes: u64,
+        limit: u64,
+    },
+    TooManyWriteOps {
+        count: u64,
+        limit: u64,
+    },
+    TooManyEvents {
+        count: u64,
+        limit: u64,
+    },
+}
+
+impl ChangeSetConfigs {
+    pub fn new(
+        max_bytes_per_write_op: u64,
+        max_bytes_all_write_ops_per_transaction: u64,
+        max_write_ops_per_transaction: u64,
+        max_events_per_transaction: u64,
+    ) -> Self {
+        Self {
+            max_bytes_per_write_op,
+            max_bytes_all_write_ops_per_transaction,
+            max_write_ops_per_transaction,
+            max_events_per_transaction,
+        }
+    }
+
+    /// No limit, for the outputs of genesis and system transactions.
+    pub fn unlimited() -> Self {
+        Self::new(u64::MAX, u64::MAX, u64::MAX, u64::MAX)
+    }
+
+    /// The limits of the gas schedules of `gas_feature_version`. Older gas schedules have none,
+    /// so that the transactions they executed keep the outputs they had.
+    pub fn new_from_gas_feature_version(gas_feature_version: u64) -> Self {
+        if gas_feature_version < GAS_FEATURE_VERSION_CHANGE_SET_LIMITS {
+            return Self::unlimited();
+        }
+        Self::new(
+            // 1 MB per write op
+            1 << 20,
+            // 10 MB for all write ops
+            10 << 20,
+            8192,
+            10_000,
+        )
+    }
+
+    pub fn max_bytes_per_write_op(&self) -> u64 {
+        self.max_bytes_per_write_op
+    }
+
+    pub fn max_bytes_all_write_ops_per_transaction(&self) -> u64 {
+        self.max_bytes_all_write_ops_per_transaction
+    }
+
+    pub fn max_write_ops_per_transaction(&self) -> u64 {
+        self.max_write_ops_per_transaction
+    }
+
+    pub fn max_events_per_transaction(&self) -> u64 {
+        self.max_events_per_transaction
+    }
+
+    /// Checks the write set and events of a transaction against the limits. The size of a write
+    /// op accounts for its state key as well as its value.
+    pub fn check_change_set(
+        &self,
+        write_set: &WriteSet,
+        events: &[ContractEvent],
+    ) -> Result<(), ChangeSetLimitError> {
+        let num_events = events.len() as u64;
+        if num_events > self.max_events_per_transaction {
+            return Err(ChangeSetLimitError::TooManyEvents {
+                count: num_events,
+                limit: self.max_events_per_transaction,
+            });
+        }
+
+        let mut num_write_ops = 0u64;
+        let mut write_set_bytes = 0u64;
+        for (state_key, write_op) in write_set {
+            num_write_ops += 1;
+            let value_bytes = match write_op {
+                WriteOp::Creation(blob) | WriteOp::Modification(blob) => blob.len(),
+                WriteOp::Deletion => 0,
+            };
+            let bytes = (state_key.size() + value_bytes) as u64;
+            if bytes > self.max_bytes_per_write_op {
+                return Err(ChangeSetLimitError::WriteOpTooLarge {
+                    state_key: state_key.clone(),
+                    bytes,
+                    limit: self.max_bytes_per_write_op,
+                });
+            }
+            write_set_bytes = write_set_bytes.saturating_add(bytes);
+        }
+        if num_write_ops > self.max_write_ops_per_transaction {
+            return Err(ChangeSetLimitError::TooManyWriteOps {
+                count: num_write_ops,
+                limit: self.max_write_ops_per_transaction,
+            });
+        }
+        if write_set_bytes > self.max_bytes_all_write_ops_per_transaction {
+            return Err(ChangeSetLimitError::WriteSetTooLarge {
+                bytes: write_set_bytes,
+                limit: self.max_bytes_all_write_ops_per_transaction,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl ChangeSetLimitError {
+    pub fn limit(&self) -> ChangeSetLimit {
+        match self {
+            ChangeSetLimitError::WriteOpTooLarge { .. } => ChangeSetLimit::WriteOpBytes,
+            ChangeSetLimitError::WriteSetTooLarge { .. } => ChangeSetLimit::WriteSetBytes,
+            ChangeSetLimitError::TooManyWriteOps { .. } => ChangeSetLimit::WriteOps,
+            ChangeSetLimitError::TooManyEvents { .. } => ChangeSetLimit::Events,
+        }
+    }
+
+    /// The status kept for a transaction whose output exceeds the limit, naming the limit and the
+    /// offending state key.
+    pub fn execution_status(&self) -> ExecutionStatus {
+        let state_key = match self {
+            ChangeSetLimitError::WriteOpTooLarge { state_key, .. } => Some(state_key.clone()),
+            _ => None,
+        };
+        ExecutionStatus::ChangeSetLimitExceeded {
+            limit: self.limit(),
+            state_key,
+        }
+    }
+
+    /// The VM status of a transaction whose output exceeds the limit. Move status codes are shared
+    /// by every limit, the limit itself is reported by `execution_status`.
+    pub fn into_vm_status(self) -> VMStatus {
+        debug!("[aptos_vm] Transaction output exceeds a limit: {}", self);
+        VMStatus::Error(StatusCode::STORAGE_WRITE_LIMIT_REACHED)
+    }
+}
+
+impl fmt::Display for ChangeSetLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeSetLimitError::WriteOpTooLarge {
+                state_key,
+                bytes,
+                limit,
+            } => write!(
+                f,
+                "write of {:?} is {} bytes, exceeding the limit of {} bytes",
+                state_key, bytes, limit
+            ),
+            ChangeSetLimitError::WriteSetTooLarge { bytes, limit } => write!(
+                f,
+                "write set is {} bytes, exceeding the limit of {} bytes",
+                bytes, limit
+            ),
+            ChangeSetLimitError::TooManyWriteOps { count, limit } => write!(
+                f,
+                "write set has {} write ops, exceeding the limit of {}",
+                count, limit
+            ),
+            ChangeSetLimitError::TooManyEvents { count, limit } => write!(
+                f,
+                "{} events were emitted, exceeding the limit of {}",
+                count, limit
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::write_set::WriteSetMut;
+
+    fn write_set(values: Vec<(&[u8], usize)>) -> WriteSet {
+        WriteSetMut::new(
+            values
+                .into_iter()
+                .map(|(key, len)| (StateKey::Raw(key.to_vec()), WriteOp::Creation(vec![0; len])))
+                .collect(),
+        )
+        .freeze()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_limits_gated_by_gas_feature_version() {
+        for gas_feature_version in [0, GAS_FEATURE_VERSION_CHANGE_SET_LIMITS - 1] {
+            assert_eq!(
+                ChangeSetConfigs::new_from_gas_feature_version(gas_feature_version),
+                ChangeSetConfigs::unlimited()
+            );
+        }
+        let configs =
+            ChangeSetConfigs::new_from_gas_feature_version(GAS_FEATURE_VERSION_CHANGE_SET_LIMITS);
+        assert_eq!(configs.max_write_ops_per_transaction(), 8192);
+        assert_eq!(configs.max_events_per_transaction(), 10_000);
+    }
+
+    #[test]
+    fn test_status_names_exceeded_limit() {
+        let configs = ChangeSetConfigs::new(100, 150, 2, 0);
+        assert!(configs.check_change_set(&write_set(vec![]), &[]).is_ok());
+
+        let err = configs
+            .check_change_set(&write_set(vec![(b"a", 80), (b"b", 120)]), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.execution_status(),
+            ExecutionStatus::ChangeSetLimitExceeded {
+                limit: ChangeSetLimit::WriteOpBytes,
+                state_key: Some(StateKey::Raw(b"b".to_vec())),
+            }
+        );
+
+        let err = configs
+            .check_change_set(&write_set(vec![(b"a", 80), (b"b", 80)]), &[])
+            .unwrap_err();
+        assert_eq!(err.limit(), ChangeSetLimit::WriteSetBytes);
+
+        let err = configs
+            .check_change_set(&write_set(vec![(b"a", 1), (b"b", 1), (b"c", 1)]), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.execution_status(),
+            ExecutionStatus::ChangeSetLimitExceeded {
+                limit: ChangeSetLimit::WriteOps,
+                state_key: None,
+            }
+        );
+    }
+}
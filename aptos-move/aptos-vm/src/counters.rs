@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    register_histogram, register_int_counter, register_int_counter_vec, Histogram, IntCounter,
-    IntCounterVec,
+    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, Histogram, HistogramVec, IntCounter, IntCounterVec,
 };
 use once_cell::sync::Lazy;
 
@@ -66,6 +66,41 @@ pub static TXN_GAS_USAGE: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!("aptos_vm_txn_gas_usage", "Gas used per transaction").unwrap()
 });
 
+/// Execution time of user transactions calling an entry function, by the function called. Only
+/// recorded if entry function metrics are enabled.
+pub static ENTRY_FUNCTION_EXECUTION_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_vm_entry_function_execution_seconds",
+        "Execution time per user transaction calling an entry function",
+        &["address", "module", "function"],
+        exponential_buckets(/*start=*/ 1e-5, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Gas used by user transactions calling an entry function, by the function called. Only
+/// recorded if entry function metrics are enabled.
+pub static ENTRY_FUNCTION_GAS_USED: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_vm_entry_function_gas_used",
+        "Gas used per user transaction calling an entry function",
+        &["address", "module", "function"],
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 24).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Count the user transactions calling an entry function which failed, by the function called
+/// and the kind of failure. Only recorded if entry function metrics are enabled.
+pub static ENTRY_FUNCTION_FAILURES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_vm_entry_function_failures",
+        "Number of failed user transactions calling an entry function",
+        &["address", "module", "function", "status"]
+    )
+    .unwrap()
+});
+
 /// Count the number of critical errors. This is not intended for display
 /// on a dashboard but rather for triggering alerts.
 pub static CRITICAL_ERRORS: Lazy<IntCounter> = Lazy::new(|| {
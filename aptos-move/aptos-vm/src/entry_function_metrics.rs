@@ -0,0 +1,140 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in execution metrics of user transactions, labelled by the entry function they call, so
+//! that operators can spot expensive or failing contracts. As anyone can publish modules, the
+//! number of distinct label values is capped: once reached, transactions calling any other
+//! function, or failing with any other status, are recorded under the `other` label.
+
+use crate::counters::{
+    ENTRY_FUNCTION_EXECUTION_SECONDS, ENTRY_FUNCTION_FAILURES, ENTRY_FUNCTION_GAS_USED,
+};
+use aptos_infallible::RwLock;
+use aptos_types::transaction::{EntryFunction, ExecutionStatus, TransactionStatus};
+use once_cell::sync::OnceCell;
+use std::{collections::HashSet, time::Duration};
+
+/// The label value of functions and statuses past the cardinality limit
+const OTHER_LABEL: &str = "other";
+
+static ENTRY_FUNCTION_METRICS: OnceCell<EntryFunctionMetrics> = OnceCell::new();
+
+/// The label values already in use, of which there can be at most `max_values`
+struct LabelValues {
+    max_values: usize,
+    values: RwLock<HashSet<Vec<String>>>,
+}
+
+impl LabelValues {
+    fn new(max_values: usize) -> Self {
+        Self {
+            max_values,
+            values: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Returns `values` if they're already in use or can still be added, otherwise `None`
+    fn admit(&self, values: Vec<String>) -> Option<Vec<String>> {
+        if self.values.read().contains(&values) {
+            return Some(values);
+        }
+        let mut in_use = self.values.write();
+        if in_use.contains(&values) || in_use.len() < self.max_values {
+            in_use.insert(values.clone());
+            Some(values)
+        } else {
+            None
+        }
+    }
+}
+
+struct EntryFunctionMetrics {
+    functions: LabelValues,
+    failures: LabelValues,
+}
+
+/// Enables entry function metrics when invoked the first time. At most `max_functions` distinct
+/// functions, and as many distinct failures, are labelled.
+pub(crate) fn enable_once(max_functions: usize) {
+    // Only the first call succeeds, due to OnceCell semantics.
+    ENTRY_FUNCTION_METRICS
+        .set(EntryFunctionMetrics {
+            functions: LabelValues::new(max_functions),
+            failures: LabelValues::new(max_functions),
+        })
+        .ok();
+}
+
+pub(crate) fn is_enabled() -> bool {
+    ENTRY_FUNCTION_METRICS.get().is_some()
+}
+
+fn failure_label(status: &TransactionStatus) -> Option<String> {
+    Some(match status {
+        TransactionStatus::Keep(ExecutionStatus::Success) | TransactionStatus::Retry => {
+            return None
+        }
+        TransactionStatus::Keep(ExecutionStatus::OutOfGas) => "out_of_gas".to_string(),
+        TransactionStatus::Keep(ExecutionStatus::MoveAbort { code, .. }) => {
+            format!("move_abort_{}", code)
+        }
+        TransactionStatus::Keep(ExecutionStatus::ExecutionFailure { .. }) => {
+            "execution_failure".to_string()
+        }
+        TransactionStatus::Keep(ExecutionStatus::MiscellaneousError(code)) => match code {
+            Some(code) => format!("{:?}", code),
+            None => "miscellaneous_error".to_string(),
+        },
+        TransactionStatus::Keep(ExecutionStatus::ChangeSetLimitExceeded { limit, .. }) => {
+            format!("change_set_limit_{:?}", limit)
+        }
+        TransactionStatus::Discard(code) => format!("discarded_{:?}", code),
+    })
+}
+
+/// Records the execution of a user transaction calling `entry_function`, if enabled
+pub(crate) fn record(
+    entry_function: &EntryFunction,
+    execution_time: Duration,
+    gas_used: u64,
+    status: &TransactionStatus,
+) {
+    let metrics = match ENTRY_FUNCTION_METRICS.get() {
+        Some(metrics) => metrics,
+        None => return,
+    };
+    let module = entry_function.module();
+    let function = metrics
+        .functions
+        .admit(vec![
+            module.address().to_hex_literal(),
+            module.name().to_string(),
+            entry_function.function().to_string(),
+        ])
+        .unwrap_or_else(|| vec![OTHER_LABEL.to_string(); 3]);
+    let labels: Vec<&str> = function.iter().map(String::as_str).collect();
+
+    ENTRY_FUNCTION_EXECUTION_SECONDS
+        .with_label_values(&labels)
+        .observe(execution_time.as_secs_f64());
+    ENTRY_FUNCTION_GAS_USED
+        .with_label_values(&labels)
+        .observe(gas_used as f64);
+    if let Some(failure) = failure_label(status) {
+        let mut failure_labels = function.clone();
+        failure_labels.push(failure);
+        let failure_labels = metrics.failures.admit(failure_labels).unwrap_or_else(|| {
+            let mut labels = function;
+            labels.push(OTHER_LABEL.to_string());
+            labels
+        });
+        ENTRY_FUNCTION_FAILURES
+            .with_label_values(
+                &failure_labels
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>(),
+            )
+            .inc();
+    }
+}
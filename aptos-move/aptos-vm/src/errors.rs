@@ -34,6 +34,10 @@ pub const EINVALID_WRITESET_SENDER: u64 = 1008;
 pub const ESEQUENCE_NUMBER_TOO_BIG: u64 = 1009;
 // Counts of secondary keys and addresses don't match.
 pub const ESECONDARY_KEYS_ADDRESSES_COUNT_MISMATCH: u64 = 1010;
+// Nonce of an orderless transaction was already used by the sender.
+pub const ENONCE_ALREADY_USED: u64 = 1011;
+// Expiration time of an orderless transaction is too far in the future.
+pub const EEXPIRATION_TOO_FAR: u64 = 1012;
 
 const INVALID_ARGUMENT: u8 = 1;
 const LIMIT_EXCEEDED: u8 = 2;
@@ -91,6 +95,10 @@ pub fn convert_prologue_error(
                 (INVALID_ARGUMENT, ESECONDARY_KEYS_ADDRESSES_COUNT_MISMATCH) => {
                     StatusCode::SECONDARY_KEYS_ADDRESSES_COUNT_MISMATCH
                 }
+                // A reused nonce is a replay, just like a stale sequence number.
+                (INVALID_ARGUMENT, ENONCE_ALREADY_USED) => StatusCode::SEQUENCE_NUMBER_TOO_OLD,
+                // There is no dedicated status code for an expiration that is too far away.
+                (INVALID_ARGUMENT, EEXPIRATION_TOO_FAR) => StatusCode::TRANSACTION_EXPIRED,
                 (category, reason) => {
                     log_context.alert();
                     error!(
@@ -0,0 +1,81 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks the parts of keyless signatures which depend on on-chain state, and so cannot be
+//! checked with the other signatures: the expiry of the ephemeral key, and the proof, against
+//! the issuers, JWKs and verification key published in `0x1::keyless_account`.
+
+use crate::move_vm_ext::MoveResolverExt;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
+    keyless::{Configuration, Groth16VerificationKey, PreparedGroth16VerificationKey},
+    timestamp::TimestampResource,
+    transaction::authenticator::{AccountAuthenticator, TransactionAuthenticator},
+    vm_status::{StatusCode, VMStatus},
+};
+use move_deps::move_core_types::move_resource::MoveResource;
+use serde::de::DeserializeOwned;
+
+fn invalid_signature(msg: impl std::fmt::Display) -> VMStatus {
+    debug!("Invalid keyless signature: {}", msg);
+    VMStatus::Error(StatusCode::INVALID_SIGNATURE)
+}
+
+fn get_resource<S: MoveResolverExt, T: MoveResource + DeserializeOwned>(
+    resolver: &S,
+) -> Result<Option<T>, VMStatus> {
+    match resolver
+        .get_resource(&CORE_CODE_ADDRESS, &T::struct_tag())
+        .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR))?
+    {
+        Some(bytes) => bcs::from_bytes(&bytes)
+            .map(Some)
+            .map_err(|_| VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE)),
+        None => Ok(None),
+    }
+}
+
+/// Returns Ok if every keyless signature among the account authenticators of `authenticator` is
+/// unexpired and proven for the current on-chain configuration, Err otherwise.
+pub(crate) fn validate_authenticators<S: MoveResolverExt>(
+    resolver: &S,
+    authenticator: &TransactionAuthenticator,
+) -> Result<(), VMStatus> {
+    let mut authenticators = authenticator.secondary_signers();
+    authenticators.push(authenticator.sender());
+    let keyless: Vec<_> = authenticators
+        .iter()
+        .filter_map(|auth| match auth {
+            AccountAuthenticator::Keyless {
+                public_key,
+                signature,
+            } => Some((public_key, signature)),
+            _ => None,
+        })
+        .collect();
+    if keyless.is_empty() {
+        return Ok(());
+    }
+
+    let config = get_resource::<S, Configuration>(resolver)?
+        .ok_or_else(|| invalid_signature("keyless accounts are not configured"))?;
+    let vk = get_resource::<S, Groth16VerificationKey>(resolver)?
+        .ok_or_else(|| invalid_signature("no Groth16 verification key is published"))?;
+    let pvk = PreparedGroth16VerificationKey::try_from(&vk).map_err(invalid_signature)?;
+    let now_secs = get_resource::<S, TimestampResource>(resolver)?
+        .ok_or(VMStatus::Error(StatusCode::STORAGE_ERROR))?
+        .timestamp
+        .microseconds
+        / 1_000_000;
+
+    for (public_key, signature) in keyless {
+        signature
+            .verify_expiry(now_secs)
+            .map_err(invalid_signature)?;
+        signature
+            .verify_proof(public_key, &config, &pvk)
+            .map_err(invalid_signature)?;
+    }
+    Ok(())
+}
@@ -106,6 +106,7 @@ mod access_path_cache;
 #[macro_use]
 mod counters;
 pub mod data_cache;
+mod entry_function_metrics;
 
 #[cfg(feature = "mirai-contracts")]
 pub mod foreign_contracts;
@@ -113,7 +114,10 @@ pub mod foreign_contracts;
 mod adapter_common;
 pub mod aptos_vm;
 mod aptos_vm_impl;
+pub mod block_output_limit;
+pub mod change_set_configs;
 mod errors;
+mod keyless_validation;
 pub mod logging;
 pub mod move_vm_ext;
 pub mod natives;
@@ -122,12 +126,15 @@ pub mod read_write_set_analysis;
 pub mod system_module_names;
 mod transaction_arg_validation;
 pub mod transaction_metadata;
+mod validator_txn_validation;
 
 pub use crate::aptos_vm::AptosVM;
 
+use crate::move_vm_ext::WarmVmCache;
 use aptos_state_view::StateView;
 use aptos_types::{
     access_path::AccessPath,
+    on_chain_config::BlockOutputLimit,
     transaction::{SignedTransaction, Transaction, TransactionOutput, VMValidatorResult},
     vm_status::VMStatus,
 };
@@ -144,20 +151,55 @@ pub trait VMValidator {
         transaction: SignedTransaction,
         state_view: &impl StateView,
     ) -> VMValidatorResult;
+
+    /// Validates several transactions at once, which lets their signatures be verified in batches.
+    fn validate_transactions(
+        &self,
+        transactions: Vec<SignedTransaction>,
+        state_view: &impl StateView,
+    ) -> Vec<VMValidatorResult> {
+        transactions
+            .into_iter()
+            .map(|transaction| self.validate_transaction(transaction, state_view))
+            .collect()
+    }
 }
 
 /// This trait describes the VM's execution interface.
 pub trait VMExecutor: Send + Sync {
-    // NOTE: At the moment there are no persistent caches that live past the end of a block (that's
-    // why execute_block doesn't take &self.)
-    // There are some cache invalidation issues around transactions publishing code that need to be
-    // sorted out before that's possible.
-
     /// Executes a block of transactions and returns output for each one of them.
     fn execute_block(
         transactions: Vec<Transaction>,
         state_view: &impl StateView,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
+
+    /// Executes a block of transactions like `execute_block`, sharing the Move VM kept in
+    /// `warm_vm_cache` with the previous blocks of the caller, which keeps the cache valid for
+    /// `state_view`.
+    fn execute_block_with_warm_vm(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        _warm_vm_cache: &WarmVmCache,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        Self::execute_block(transactions, state_view)
+    }
+
+    /// Executes a block of transactions like `execute_block_with_warm_vm`, cutting it short once
+    /// the cumulative output of its transactions crosses `output_limit`. The transactions from the
+    /// first user transaction after the one crossing it are given the output of
+    /// `block_output_limit::cut_output`. The outputs the warm VM executed but which are cut are
+    /// passed to `WarmVmCache::record_discarded_outputs`.
+    fn execute_block_with_output_limit(
+        transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+        warm_vm_cache: &WarmVmCache,
+        output_limit: &BlockOutputLimit,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let mut outputs =
+            Self::execute_block_with_warm_vm(transactions.clone(), state_view, warm_vm_cache)?;
+        block_output_limit::limit_outputs(&transactions, &mut outputs, output_limit);
+        Ok(outputs)
+    }
 }
 
 /// Get the AccessPath to a resource stored under `address` with type name `tag`
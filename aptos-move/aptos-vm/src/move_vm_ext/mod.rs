@@ -6,9 +6,11 @@
 mod resolver;
 mod session;
 mod vm;
+pub(crate) mod warm_vm_cache;
 
 pub use crate::move_vm_ext::{
     resolver::MoveResolverExt,
     session::{SessionExt, SessionId, SessionOutput},
     vm::MoveVmExt,
+    warm_vm_cache::WarmVmCache,
 };
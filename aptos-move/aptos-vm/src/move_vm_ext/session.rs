@@ -8,8 +8,9 @@ use crate::{
 use aptos_aggregator::{
     aggregator_extension::{
         AggregatorChange, AggregatorChangeSet, AggregatorID, NativeAggregatorContext,
+        SnapshotChange,
     },
-    delta_change_set::{serialize, DeltaChangeSet},
+    delta_change_set::{serialize, DeltaChangeSet, SnapshotOp},
     transaction::ChangeSetExt,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -19,6 +20,7 @@ use aptos_types::{
     contract_event::ContractEvent,
     state_store::{state_key::StateKey, table::TableHandle},
     transaction::{ChangeSet, SignatureCheckedTransaction},
+    validator_txn::ValidatorTransaction,
     write_set::{WriteOp, WriteSetMut},
 };
 use framework::natives::code::{NativeCodeContext, PublishRequest};
@@ -57,6 +59,10 @@ pub enum SessionId {
     },
     // For those runs that are not a transaction and the output of which won't be committed.
     Void,
+    ValidatorTxn {
+        // hash of the validator transaction
+        hash: HashValue,
+    },
 }
 
 impl SessionId {
@@ -82,6 +88,10 @@ impl SessionId {
         }
     }
 
+    pub fn validator_txn(txn: &ValidatorTransaction) -> Self {
+        Self::ValidatorTxn { hash: txn.hash() }
+    }
+
     pub fn void() -> Self {
         Self::Void
     }
@@ -220,6 +230,29 @@ impl SessionOutput {
             }
         }
 
+        for (id, change) in aggregator_change_set.snapshots {
+            let state_key = aggregator_state_key(id);
+
+            match change {
+                SnapshotChange::Write(bytes) => {
+                    write_set_mut.push((state_key, WriteOp::Creation(bytes)));
+                }
+                SnapshotChange::Resolve {
+                    source,
+                    delta,
+                    format,
+                } => {
+                    let snapshot_op = SnapshotOp {
+                        source: aggregator_state_key(source),
+                        delta,
+                        format,
+                    };
+                    delta_change_set.push_snapshot((state_key, snapshot_op));
+                }
+                SnapshotChange::Delete => write_set_mut.push((state_key, WriteOp::Deletion)),
+            }
+        }
+
         let write_set = write_set_mut
             .freeze()
             .map_err(|_| VMStatus::Error(StatusCode::DATA_FORMAT_ERROR))?;
@@ -303,6 +336,32 @@ impl SessionOutput {
             }
         }
 
+        // Squash snapshot changes. Snapshots are immutable, so a snapshot can
+        // only be taken in one session and destroyed in a later one.
+        for (other_id, other_change) in other.aggregator_change_set.snapshots {
+            match (
+                self.aggregator_change_set.snapshots.remove(&other_id),
+                other_change,
+            ) {
+                // Taken and destroyed: no side-effects.
+                (
+                    Some(SnapshotChange::Write(_) | SnapshotChange::Resolve { .. }),
+                    SnapshotChange::Delete,
+                ) => {}
+                (_, change) => {
+                    self.aggregator_change_set
+                        .snapshots
+                        .insert(other_id, change);
+                }
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Returns the state key of the value of an aggregator or snapshot.
+fn aggregator_state_key(id: AggregatorID) -> StateKey {
+    let AggregatorID { handle, key } = id;
+    StateKey::table_item(TableHandle(handle), serialize(&key))
+}
@@ -7,8 +7,13 @@ use crate::{
 };
 use aptos_aggregator::aggregator_extension::NativeAggregatorContext;
 use aptos_gas::NativeGasParameters;
-use framework::natives::state_storage::NativeStateStorageContext;
+use aptos_types::on_chain_config::EnabledFeatures;
 use framework::natives::{code::NativeCodeContext, transaction_context::NativeTransactionContext};
+use framework::natives::{
+    cryptography::{ristretto255::NativeRistrettoContext, streaming_hash::NativeHashContext},
+    features::NativeFeaturesContext,
+    state_storage::NativeStateStorageContext,
+};
 use move_deps::{
     move_binary_format::errors::VMResult,
     move_bytecode_verifier::VerifierConfig,
@@ -19,10 +24,20 @@ use std::ops::Deref;
 
 pub struct MoveVmExt {
     inner: MoveVM,
+    features: EnabledFeatures,
 }
 
 impl MoveVmExt {
+    /// Creates a VM with every known feature enabled, as used by genesis and tools which don't
+    /// run against on-chain configs.
     pub fn new(native_gas_params: NativeGasParameters) -> VMResult<Self> {
+        Self::new_with_features(native_gas_params, EnabledFeatures::all())
+    }
+
+    pub fn new_with_features(
+        native_gas_params: NativeGasParameters,
+        features: EnabledFeatures,
+    ) -> VMResult<Self> {
         Ok(Self {
             inner: MoveVM::new_with_verifier_config(
                 aptos_natives(native_gas_params),
@@ -30,9 +45,15 @@ impl MoveVmExt {
                     max_loop_depth: Some(5),
                 },
             )?,
+            features,
         })
     }
 
+    /// The features enabled for the sessions of this VM
+    pub fn features(&self) -> &EnabledFeatures {
+        &self.features
+    }
+
     pub fn new_session<'r, S: MoveResolverExt>(
         &self,
         remote: &'r S,
@@ -53,6 +74,9 @@ impl MoveVmExt {
         extensions.add(NativeTransactionContext::new(script_hash));
         extensions.add(NativeCodeContext::default());
         extensions.add(NativeStateStorageContext::new(remote));
+        extensions.add(NativeFeaturesContext::new(self.features.clone()));
+        extensions.add(NativeRistrettoContext::default());
+        extensions.add(NativeHashContext::default());
 
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
@@ -0,0 +1,388 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A Move VM shared by the consecutive blocks an executor executes, so that the modules its loader
+//! has deserialized and verified, most of all the ones of the framework, don't get loaded again by
+//! every block.
+//!
+//! The loader caches modules by id, without knowing about the state they were read from. The
+//! blocks executed with the warm VM therefore read the state through a `WarmVmStateView`, which
+//! records the modules the loader may have cached, and each executor owns its cache and reports
+//! the outputs it executes or applies, with their versions. The VM is dropped as soon as a module
+//! it may have loaded is published again, at a version it didn't load: publishing other modules
+//! leaves it warm. Modules published by the VM itself already make its loader flush its cache, but
+//! the outputs publishing them may still be discarded, in which case the VM is dropped too. An
+//! executor executing against several branches of states also tells the cache which state each
+//! block runs against, so that the VM is only kept for the states derived from the one it was
+//! created on.
+
+use crate::move_vm_ext::MoveVmExt;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView, StateViewId};
+use aptos_types::{
+    access_path::Path,
+    on_chain_config::EnabledFeatures,
+    state_store::state_key::StateKey,
+    transaction::{TransactionOutput, TransactionStatus, Version},
+};
+use move_deps::{
+    move_binary_format::errors::VMResult, move_core_types::language_storage::ModuleId,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// The configuration a VM is created with. A VM is only shared by blocks with the same one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct WarmVmId {
+    /// The on-chain gas schedule, which the gas parameters of the natives come from
+    pub gas_schedule: Option<BTreeMap<String, u64>>,
+    pub features: EnabledFeatures,
+}
+
+struct WarmVm {
+    id: WarmVmId,
+    vm: Arc<MoveVmExt>,
+    /// The modules the VM may have loaded, with the version of their last publish recorded when
+    /// they were read or published, None if they weren't published since the VM was created.
+    loaded_modules: BTreeMap<ModuleId, Option<Version>>,
+}
+
+/// The Move VM shared by the blocks of one executor.
+///
+/// States are identified by ids chosen by the executor, e.g. the ids of the blocks resulting in
+/// them.
+#[derive(Default)]
+pub struct WarmVmCache {
+    warm_vm: Mutex<Option<WarmVm>>,
+    /// The states holding the modules the warm VM may have loaded: the last state executed against
+    /// and the one resulting from it.
+    valid_states: Mutex<Vec<HashValue>>,
+    /// Whether the block being executed publishes modules, which the warm VM may then have loaded
+    /// although the state it executed against doesn't hold them.
+    modules_published: AtomicBool,
+}
+
+impl WarmVmCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepares the cache for executing against the state `base`, dropping the warm VM unless it
+    /// is valid for it. Until `finish_execution` is called, the VM isn't valid for any state, in
+    /// case the execution fails.
+    pub fn start_execution(&self, base: HashValue) {
+        let mut valid_states = self.valid_states.lock();
+        if !valid_states.contains(&base) {
+            self.invalidate();
+        }
+        valid_states.clear();
+        self.modules_published.store(false, Ordering::SeqCst);
+    }
+
+    /// Records that the block executed against `base` resulted in the state `result`, with its
+    /// outputs passed to `record_outputs`. The warm VM, if it wasn't dropped, stays valid for
+    /// `result`, and for `base` unless the block published modules.
+    pub fn finish_execution(&self, base: HashValue, result: HashValue) {
+        *self.valid_states.lock() = if self.modules_published.load(Ordering::SeqCst) {
+            vec![result]
+        } else {
+            vec![base, result]
+        };
+    }
+
+    /// Records the outputs executed or applied by the executor, the first one committed at
+    /// `first_version`, dropping the warm VM if they publish a module it may have loaded at
+    /// another version. Recording the same outputs again has no effect.
+    pub fn record_outputs<'a>(
+        &self,
+        first_version: Version,
+        outputs: impl IntoIterator<Item = &'a TransactionOutput>,
+    ) {
+        let mut warm_vm = self.warm_vm.lock();
+        let committed = outputs
+            .into_iter()
+            .filter(|output| matches!(output.status(), TransactionStatus::Keep(_)));
+        for (version, output) in (first_version..).zip(committed) {
+            for module_id in published_modules(output) {
+                self.modules_published.store(true, Ordering::SeqCst);
+                let stale = match warm_vm.as_mut() {
+                    Some(vm) => matches!(
+                        vm.loaded_modules.insert(module_id.clone(), Some(version)),
+                        Some(loaded) if loaded != Some(version)
+                    ),
+                    None => false,
+                };
+                if stale {
+                    debug!(
+                        "Module {} published at version {}, dropping the warm VM",
+                        module_id, version
+                    );
+                    *warm_vm = None;
+                }
+            }
+        }
+    }
+
+    /// Records the outputs the warm VM executed but which are discarded, e.g. cut from their
+    /// block, dropping the VM if they publish modules.
+    pub fn record_discarded_outputs<'a>(
+        &self,
+        outputs: impl IntoIterator<Item = &'a TransactionOutput>,
+    ) {
+        if outputs
+            .into_iter()
+            .any(|output| published_modules(output).next().is_some())
+        {
+            debug!("Discarded outputs publish modules, dropping the warm VM");
+            self.invalidate();
+        }
+    }
+
+    /// Drops the warm VM, so that the next block creates a new one.
+    pub fn invalidate(&self) {
+        *self.warm_vm.lock() = None;
+    }
+
+    /// Returns the warm VM if it was created with `id`, otherwise replaces it with one created by
+    /// `create_vm`.
+    pub(crate) fn get_warm_vm(
+        &self,
+        id: WarmVmId,
+        create_vm: impl FnOnce() -> VMResult<MoveVmExt>,
+    ) -> VMResult<Arc<MoveVmExt>> {
+        let mut warm_vm = self.warm_vm.lock();
+        match warm_vm.as_ref() {
+            Some(vm) if vm.id == id => Ok(vm.vm.clone()),
+            _ => {
+                let vm = Arc::new(create_vm()?);
+                *warm_vm = Some(WarmVm {
+                    id,
+                    vm: vm.clone(),
+                    loaded_modules: BTreeMap::new(),
+                });
+                Ok(vm)
+            }
+        }
+    }
+
+    /// Records that the warm VM may have loaded `module_id`, as it was read from the state.
+    fn record_module_read(&self, module_id: ModuleId) {
+        if let Some(vm) = self.warm_vm.lock().as_mut() {
+            vm.loaded_modules.entry(module_id).or_insert(None);
+        }
+    }
+}
+
+/// The state view the blocks executed with the warm VM of `warm_vm_cache` read the state through,
+/// recording the modules read as the ones the VM may have loaded.
+pub(crate) struct WarmVmStateView<'a, S> {
+    state_view: &'a S,
+    warm_vm_cache: &'a WarmVmCache,
+}
+
+impl<'a, S: StateView> WarmVmStateView<'a, S> {
+    pub fn new(state_view: &'a S, warm_vm_cache: &'a WarmVmCache) -> Self {
+        Self {
+            state_view,
+            warm_vm_cache,
+        }
+    }
+}
+
+impl<'a, S: StateView> StateView for WarmVmStateView<'a, S> {
+    fn id(&self) -> StateViewId {
+        self.state_view.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> anyhow::Result<Option<Vec<u8>>> {
+        let value = self.state_view.get_state_value(state_key)?;
+        // Missing modules fail to load, they aren't cached.
+        if value.is_some() {
+            if let Some(module_id) = module_id(state_key) {
+                self.warm_vm_cache.record_module_read(module_id);
+            }
+        }
+        Ok(value)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.state_view.is_genesis()
+    }
+
+    fn get_usage(&self) -> anyhow::Result<StateStorageUsage> {
+        self.state_view.get_usage()
+    }
+}
+
+/// The id of the module stored under `state_key`, if any.
+fn module_id(state_key: &StateKey) -> Option<ModuleId> {
+    match state_key {
+        StateKey::AccessPath(path) => match path.get_path() {
+            Path::Code(module_id) => Some(module_id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The modules `output` writes.
+fn published_modules(output: &TransactionOutput) -> impl Iterator<Item = ModuleId> + '_ {
+    output
+        .write_set()
+        .iter()
+        .filter_map(|(state_key, _)| module_id(state_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_gas::NativeGasParameters;
+    use aptos_types::{
+        access_path::AccessPath,
+        account_address::AccountAddress,
+        on_chain_config::FeatureFlag,
+        transaction::ExecutionStatus,
+        write_set::{WriteOp, WriteSetMut},
+    };
+    use move_deps::move_core_types::identifier::Identifier;
+
+    fn module(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    fn id(features: EnabledFeatures) -> WarmVmId {
+        WarmVmId {
+            gas_schedule: None,
+            features,
+        }
+    }
+
+    fn get_vm(cache: &WarmVmCache, id: WarmVmId) -> Arc<MoveVmExt> {
+        let features = id.features.clone();
+        cache
+            .get_warm_vm(id, || {
+                MoveVmExt::new_with_features(NativeGasParameters::zeros(), features)
+            })
+            .unwrap()
+    }
+
+    /// The output of a transaction publishing `modules`.
+    fn publish(modules: &[ModuleId]) -> TransactionOutput {
+        let write_set = WriteSetMut::new(
+            modules
+                .iter()
+                .map(|module_id| {
+                    (
+                        StateKey::AccessPath(AccessPath::code_access_path(module_id.clone())),
+                        WriteOp::Modification(vec![0]),
+                    )
+                })
+                .collect(),
+        )
+        .freeze()
+        .unwrap();
+        TransactionOutput::new(
+            write_set,
+            vec![],
+            0,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+        )
+    }
+
+    /// Executes a block against `base`, resulting in `result`, the modules in `read` being read
+    /// and the ones in `published` being published.
+    fn execute(
+        cache: &WarmVmCache,
+        base: HashValue,
+        result: HashValue,
+        first_version: Version,
+        read: &[ModuleId],
+        published: &[ModuleId],
+    ) -> Arc<MoveVmExt> {
+        cache.start_execution(base);
+        let vm = get_vm(cache, id(EnabledFeatures::all()));
+        for module_id in read {
+            cache.record_module_read(module_id.clone());
+        }
+        cache.record_outputs(first_version, &[publish(published)]);
+        cache.finish_execution(base, result);
+        vm
+    }
+
+    #[test]
+    fn test_vm_reused_across_blocks() {
+        let cache = WarmVmCache::new();
+        let states: Vec<_> = (0..4u8).map(|i| HashValue::new([i; 32])).collect();
+        let vm = execute(&cache, states[0], states[1], 0, &[module("coin")], &[]);
+        let next = execute(&cache, states[1], states[2], 1, &[module("coin")], &[]);
+        assert!(Arc::ptr_eq(&vm, &next));
+        // A sibling of the last block executes against the same state
+        let sibling = execute(&cache, states[1], states[3], 1, &[], &[]);
+        assert!(Arc::ptr_eq(&vm, &sibling));
+
+        // An unrelated state gets a new VM
+        let other = execute(&cache, states[0], states[1], 0, &[], &[]);
+        assert!(!Arc::ptr_eq(&vm, &other));
+    }
+
+    #[test]
+    fn test_vm_dropped_on_republish() {
+        let cache = WarmVmCache::new();
+        let states: Vec<_> = (0..5u8).map(|i| HashValue::new([i; 32])).collect();
+        let vm = execute(&cache, states[0], states[1], 0, &[module("coin")], &[]);
+
+        // Publishing a module the VM didn't load keeps it
+        let next = execute(&cache, states[1], states[2], 1, &[], &[module("new")]);
+        assert!(Arc::ptr_eq(&vm, &next));
+        // Recording the same outputs again too, although the VM now may have loaded the module
+        cache.record_module_read(module("new"));
+        cache.record_outputs(1, &[publish(&[module("new")])]);
+        // Only the resulting state holds the published module
+        cache.start_execution(states[1]);
+        let next = get_vm(&cache, id(EnabledFeatures::all()));
+        assert!(!Arc::ptr_eq(&vm, &next));
+
+        let vm = execute(&cache, states[2], states[3], 2, &[module("coin")], &[]);
+        let next = execute(&cache, states[3], states[4], 3, &[], &[module("coin")]);
+        assert!(Arc::ptr_eq(&vm, &next));
+        cache.start_execution(states[4]);
+        let next = get_vm(&cache, id(EnabledFeatures::all()));
+        assert!(!Arc::ptr_eq(&vm, &next));
+
+        // Nor is the VM kept when a published module is discarded
+        let vm = get_vm(&cache, id(EnabledFeatures::all()));
+        cache.record_discarded_outputs(&[publish(&[module("new")])]);
+        assert!(!Arc::ptr_eq(
+            &vm,
+            &get_vm(&cache, id(EnabledFeatures::all()))
+        ));
+    }
+
+    #[test]
+    fn test_vm_dropped_on_config_change() {
+        let cache = WarmVmCache::new();
+        let vm = get_vm(&cache, id(EnabledFeatures::all()));
+        assert!(Arc::ptr_eq(
+            &vm,
+            &get_vm(&cache, id(EnabledFeatures::all()))
+        ));
+
+        let features = id(EnabledFeatures::default());
+        let next = get_vm(&cache, features.clone());
+        assert!(!Arc::ptr_eq(&vm, &next));
+        assert!(!next.features().is_enabled(FeatureFlag::Secp256r1Natives));
+
+        let gas_schedule = WarmVmId {
+            gas_schedule: Some(BTreeMap::from([("instr.nop".to_string(), 1)])),
+            ..features
+        };
+        assert!(!Arc::ptr_eq(&next, &get_vm(&cache, gas_schedule)));
+    }
+}
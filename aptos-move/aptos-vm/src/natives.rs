@@ -3,8 +3,14 @@
 
 use aptos_aggregator::aggregator_extension::{aggregator_natives, NativeAggregatorContext};
 use aptos_gas::NativeGasParameters;
-use aptos_types::account_config::CORE_CODE_ADDRESS;
-use framework::natives::{code::NativeCodeContext, transaction_context::NativeTransactionContext};
+use aptos_types::{account_config::CORE_CODE_ADDRESS, on_chain_config::EnabledFeatures};
+use framework::natives::{
+    code::NativeCodeContext,
+    cryptography::{ristretto255::NativeRistrettoContext, streaming_hash::NativeHashContext},
+    features::NativeFeaturesContext,
+    test_env::NativeTestEnvContext,
+    transaction_context::NativeTransactionContext,
+};
 use move_deps::move_unit_test;
 use move_deps::move_vm_runtime::native_extensions::NativeContextExtensions;
 use move_deps::move_vm_test_utils::BlankStorage;
@@ -38,6 +44,15 @@ pub fn aptos_natives(gas_params: NativeGasParameters) -> NativeFunctionTable {
         .collect()
 }
 
+/// The natives of `aptos_natives`, plus the ones of the test-only modules of the framework, for
+/// running Move unit tests.
+pub fn aptos_natives_for_unit_test(gas_params: NativeGasParameters) -> NativeFunctionTable {
+    aptos_natives(gas_params)
+        .into_iter()
+        .chain(framework::natives::all_test_natives(CORE_CODE_ADDRESS))
+        .collect()
+}
+
 pub fn configure_for_unit_test() {
     move_unit_test::extensions::set_extension_hook(Box::new(unit_test_extensions_hook))
 }
@@ -46,4 +61,8 @@ fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
     exts.add(NativeCodeContext::default());
     exts.add(NativeTransactionContext::new(vec![1]));
     exts.add(NativeAggregatorContext::new(0, &*DUMMY_RESOLVER));
+    exts.add(NativeFeaturesContext::new(EnabledFeatures::all()));
+    exts.add(NativeRistrettoContext::default());
+    exts.add(NativeHashContext::default());
+    exts.add(NativeTestEnvContext::default());
 }
@@ -7,6 +7,7 @@ mod vm_wrapper;
 use crate::{
     adapter_common::{preprocess_transaction, PreprocessedTransaction},
     aptos_vm::AptosVM,
+    move_vm_ext::WarmVmCache,
     parallel_executor::vm_wrapper::AptosVMWrapper,
 };
 use aptos_parallel_executor::{
@@ -65,6 +66,15 @@ impl ParallelAptosVM {
         transactions: Vec<Transaction>,
         state_view: &S,
         concurrency_level: usize,
+    ) -> Result<(Vec<TransactionOutput>, Option<Error<VMStatus>>), VMStatus> {
+        Self::execute_block_with_warm_vm(transactions, state_view, concurrency_level, None)
+    }
+
+    pub(crate) fn execute_block_with_warm_vm<S: StateView>(
+        transactions: Vec<Transaction>,
+        state_view: &S,
+        concurrency_level: usize,
+        warm_vm_cache: Option<&WarmVmCache>,
     ) -> Result<(Vec<TransactionOutput>, Option<Error<VMStatus>>), VMStatus> {
         // Verify the signatures of all the transactions in parallel.
         // This is time consuming so don't wait and do the checking
@@ -77,7 +87,7 @@ impl ParallelAptosVM {
         match ParallelTransactionExecutor::<PreprocessedTransaction, AptosVMWrapper<S>>::new(
             concurrency_level,
         )
-        .execute_transactions_parallel(state_view, signature_verified_block)
+        .execute_transactions_parallel((state_view, warm_vm_cache), signature_verified_block)
         {
             Ok(results) => Ok((
                 results
@@ -87,7 +97,11 @@ impl ParallelAptosVM {
                 None,
             )),
             Err(err @ Error::ModulePathReadWrite) => {
-                let output = AptosVM::execute_block_and_keep_vm_status(transactions, state_view)?;
+                let output = AptosVM::execute_block_and_keep_vm_status_with_warm_vm(
+                    transactions,
+                    state_view,
+                    warm_vm_cache,
+                )?;
                 Ok((
                     output
                         .into_iter()
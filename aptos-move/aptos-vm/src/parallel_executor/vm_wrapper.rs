@@ -6,6 +6,7 @@ use crate::{
     aptos_vm::AptosVM,
     data_cache::RemoteStorage,
     logging::AdapterLogSchema,
+    move_vm_ext::WarmVmCache,
     parallel_executor::{storage_wrapper::VersionedView, AptosTransactionOutput},
 };
 use aptos_logger::prelude::*;
@@ -30,10 +31,10 @@ impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
     type T = PreprocessedTransaction;
     type Output = AptosTransactionOutput;
     type Error = VMStatus;
-    type Argument = &'a S;
+    type Argument = (&'a S, Option<&'a WarmVmCache>);
 
-    fn init(argument: &'a S) -> Self {
-        let vm = AptosVM::new(argument);
+    fn init((base_view, warm_vm_cache): Self::Argument) -> Self {
+        let vm = AptosVM::new_with_warm_vm(base_view, warm_vm_cache);
 
         // Loading `0x1::account` and its transitive dependency into the code cache.
         //
@@ -45,13 +46,10 @@ impl<'a, S: 'a + StateView> ExecutorTask for AptosVMWrapper<'a, S> {
 
         let _ = vm.load_module(
             &ModuleId::new(CORE_CODE_ADDRESS, ident_str!("account").to_owned()),
-            &RemoteStorage::new(argument),
+            &RemoteStorage::new(base_view),
         );
 
-        Self {
-            vm,
-            base_view: argument,
-        }
+        Self { vm, base_view }
     }
 
     fn execute_transaction(
@@ -4,12 +4,18 @@
 use crate::{
     adapter_common::PreprocessedTransaction,
     move_vm_ext::MoveResolverExt,
-    system_module_names::{BLOCK_MODULE, BLOCK_PROLOGUE, SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_NAME},
+    system_module_names::{
+        BLOCK_MODULE, BLOCK_PROLOGUE, KEYLESS_ACCOUNT_MODULE, SCRIPT_PROLOGUE_NAME,
+        UPSERT_OBSERVED_JWKS, USER_EPILOGUE_NAME, VALIDATOR_TXN_MODULE, VALIDATOR_TXN_PROCESS,
+    },
 };
 use anyhow::{anyhow, bail, Result};
 use aptos_types::{
-    account_config,
+    account_config::{self, CORE_CODE_ADDRESS},
+    keyless::QuorumCertifiedUpdate,
+    on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
     transaction::{SignedTransaction, TransactionPayload},
+    validator_txn::ValidatorTransaction,
 };
 use move_deps::{
     move_bytecode_utils::module_cache::SyncModuleCache,
@@ -17,6 +23,7 @@ use move_deps::{
         ident_str,
         identifier::{IdentStr, Identifier},
         language_storage::{ModuleId, ResourceKey, StructTag, TypeTag},
+        move_resource::MoveStructType,
         resolver::ModuleResolver,
         value::{serialize_values, MoveValue},
     },
@@ -36,6 +43,14 @@ const TRANSACTION_FEES_NAME: &IdentStr = ident_str!("TransactionFee");
 pub fn add_on_functions_list() -> Vec<(ModuleId, Identifier)> {
     vec![
         (BLOCK_MODULE.clone(), BLOCK_PROLOGUE.to_owned()),
+        (
+            VALIDATOR_TXN_MODULE.clone(),
+            VALIDATOR_TXN_PROCESS.to_owned(),
+        ),
+        (
+            KEYLESS_ACCOUNT_MODULE.clone(),
+            UPSERT_OBSERVED_JWKS.to_owned(),
+        ),
         (
             account_config::constants::APTOS_ACCOUNT_MODULE.clone(),
             SCRIPT_PROLOGUE_NAME.to_owned(),
@@ -180,6 +195,51 @@ impl<'a, R: MoveResolverExt> ReadWriteSetAnalysis<'a, R> {
                 )?;
                 self.concretize_secondary_indexes(metadata_access, concretize)
             }
+            PreprocessedTransaction::ValidatorTransaction(txn) => {
+                let args =
+                    serialize_values(&txn.get_move_args(account_config::reserved_vm_address()));
+                let validator_txn_access = self.get_partially_concretized_summary(
+                    &VALIDATOR_TXN_MODULE,
+                    VALIDATOR_TXN_PROCESS,
+                    &[],
+                    &args,
+                    &[],
+                    &self.module_cache,
+                )?;
+                let (mut reads, mut writes) =
+                    self.concretize_secondary_indexes(validator_txn_access, concretize)?;
+                // The VM verifies the payload against the validator set of the current epoch
+                let validator_set_tag = StructTag {
+                    address: CORE_CODE_ADDRESS,
+                    module: Identifier::new(ValidatorSet::MODULE_IDENTIFIER)?,
+                    name: Identifier::new(ValidatorSet::TYPE_IDENTIFIER)?,
+                    type_params: vec![],
+                };
+                reads.push(ResourceKey::new(CORE_CODE_ADDRESS, validator_set_tag));
+                reads.push(ResourceKey::new(
+                    CORE_CODE_ADDRESS,
+                    ConfigurationResource::struct_tag(),
+                ));
+                if let ValidatorTransaction::ObservedJWKUpdate(payload) = txn {
+                    let update = QuorumCertifiedUpdate::from_bytes(payload)?.update;
+                    let args = serialize_values(
+                        &update.get_move_args(account_config::reserved_vm_address()),
+                    );
+                    let jwks_access = self.get_partially_concretized_summary(
+                        &KEYLESS_ACCOUNT_MODULE,
+                        UPSERT_OBSERVED_JWKS,
+                        &[],
+                        &args,
+                        &[],
+                        &self.module_cache,
+                    )?;
+                    let (jwks_reads, jwks_writes) =
+                        self.concretize_secondary_indexes(jwks_access, concretize)?;
+                    reads.extend(jwks_reads);
+                    writes.extend(jwks_writes);
+                }
+                Ok((reads, writes))
+            }
             PreprocessedTransaction::InvalidSignature => Ok((vec![], vec![])),
             PreprocessedTransaction::StateCheckpoint => Ok((vec![], vec![])),
             PreprocessedTransaction::WaypointWriteSet(_) => {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 //! Names of modules, functions, and types used by Aptos System.
 
-use aptos_types::account_config;
+use aptos_types::{account_config, keyless::KEYLESS_ACCOUNT_MODULE_NAME};
 use move_deps::move_core_types::{ident_str, identifier::IdentStr, language_storage::ModuleId};
 use once_cell::sync::Lazy;
 
@@ -24,3 +24,21 @@ pub const WRITESET_PROLOGUE_NAME: &IdentStr = ident_str!("writeset_prologue");
 pub const WRITESET_EPILOGUE_NAME: &IdentStr = ident_str!("writeset_epilogue");
 pub const USER_EPILOGUE_NAME: &IdentStr = ident_str!("epilogue");
 pub const BLOCK_PROLOGUE: &IdentStr = ident_str!("block_prologue");
+
+/// The ModuleId for the module recording validator transactions
+pub static VALIDATOR_TXN_MODULE: Lazy<ModuleId> = Lazy::new(|| {
+    ModuleId::new(
+        account_config::CORE_CODE_ADDRESS,
+        ident_str!("validator_txn").to_owned(),
+    )
+});
+pub const VALIDATOR_TXN_PROCESS: &IdentStr = ident_str!("process");
+
+/// The ModuleId for the keyless account module, where observed JWK updates are applied
+pub static KEYLESS_ACCOUNT_MODULE: Lazy<ModuleId> = Lazy::new(|| {
+    ModuleId::new(
+        account_config::CORE_CODE_ADDRESS,
+        ident_str!(KEYLESS_ACCOUNT_MODULE_NAME).to_owned(),
+    )
+});
+pub const UPSERT_OBSERVED_JWKS: &IdentStr = ident_str!("upsert_observed_jwks");
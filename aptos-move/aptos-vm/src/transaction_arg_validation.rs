@@ -3,23 +3,36 @@
 
 //! Module defines validation of transaction arguments.
 //!
-//! TODO: we should not only validate the types but also the actual values, e.g.
-//! for strings whether they consist of correct characters.
+//! Besides primitives and vectors, arguments can be structs from an allowlist: strings and
+//! options. A struct argument is constructed from the bytes of the transaction rather than by its
+//! module, so only structs whose invariants the VM can check itself are allowed; any other struct
+//! could be forged, bypassing the constructors of its module. Arguments containing structs have
+//! their values validated against the layout of their type, e.g. strings must be UTF-8 and
+//! options have at most one element.
 
 use crate::{
     move_vm_ext::{MoveResolverExt, SessionExt},
     VMStatus,
 };
 use move_deps::{
-    move_core_types::{account_address::AccountAddress, value::MoveValue, vm_status::StatusCode},
+    move_core_types::{
+        account_address::AccountAddress,
+        value::{MoveStruct, MoveValue},
+        vm_status::StatusCode,
+    },
     move_vm_runtime::session::LoadedFunctionInstantiation,
-    move_vm_types::loaded_data::runtime_types::Type,
+    move_vm_types::loaded_data::runtime_types::{StructType, Type},
 };
 use once_cell::sync::Lazy;
 use std::collections::BTreeSet;
 
+const STRING_STRUCT: &str = "0x1::string::String";
+const OPTION_STRUCT: &str = "0x1::option::Option";
+
+/// Structs which are valid arguments, each of which has its invariants checked by
+/// `is_valid_arg_value`
 static ALLOWED_STRUCTS: Lazy<BTreeSet<String>> = Lazy::new(|| {
-    ["0x1::string::String"]
+    [STRING_STRUCT, OPTION_STRUCT]
         .iter()
         .map(|s| s.to_string())
         .collect()
@@ -30,6 +43,7 @@ static ALLOWED_STRUCTS: Lazy<BTreeSet<String>> = Lazy::new(|| {
 /// 1. return signature is empty
 /// 2. number of signers is same as the number of senders
 /// 3. check arg types are allowed after signers
+/// 4. check the values of args which contain structs
 ///
 /// after validation, add senders and non-signer arguments to generate the final args
 pub(crate) fn validate_combine_signer_and_txn_args<S: MoveResolverExt>(
@@ -65,6 +79,11 @@ pub(crate) fn validate_combine_signer_and_txn_args<S: MoveResolverExt>(
     if (signer_param_cnt + args.len()) != func.parameters.len() {
         return Err(VMStatus::Error(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH));
     }
+    for (ty, arg) in func.parameters[signer_param_cnt..].iter().zip(args.iter()) {
+        if contains_struct(ty) {
+            validate_arg_value(session, ty, arg)?;
+        }
+    }
     // if function doesn't require signer, we reuse txn args
     // if the function require signer, we check senders number same as signers
     // and then combine senders with txn args.
@@ -91,14 +110,96 @@ fn is_valid_txn_arg<S: MoveResolverExt>(session: &SessionExt<S>, typ: &Type) ->
     match typ {
         Bool | U8 | U64 | U128 | Address => true,
         Vector(inner) => is_valid_txn_arg(session, &(*inner)),
-        Struct(idx) | StructInstantiation(idx, _) => {
-            if let Some(st) = session.get_struct_type(*idx) {
-                let full_name = format!("{}::{}", st.module.short_str_lossless(), st.name);
-                ALLOWED_STRUCTS.contains(&full_name)
-            } else {
-                false
-            }
-        }
+        Struct(idx) => match session.get_struct_type(*idx) {
+            Some(st) => is_valid_struct_txn_arg(session, &st, &[]),
+            None => false,
+        },
+        StructInstantiation(idx, ty_args) => match session.get_struct_type(*idx) {
+            Some(st) => is_valid_struct_txn_arg(session, &st, ty_args),
+            None => false,
+        },
         Signer | Reference(_) | MutableReference(_) | TyParam(_) => false,
     }
 }
+
+fn is_valid_struct_txn_arg<S: MoveResolverExt>(
+    session: &SessionExt<S>,
+    st: &StructType,
+    ty_args: &[Type],
+) -> bool {
+    let full_name = format!("{}::{}", st.module.short_str_lossless(), st.name);
+    ALLOWED_STRUCTS.contains(&full_name) && ty_args.iter().all(|ty| is_valid_txn_arg(session, ty))
+}
+
+fn contains_struct(typ: &Type) -> bool {
+    match typ {
+        Type::Vector(inner) => contains_struct(inner),
+        Type::Struct(_) | Type::StructInstantiation(_, _) => true,
+        _ => false,
+    }
+}
+
+/// Deserializes an argument according to the layout of its type, and checks the invariants of
+/// the structs in it which the layout doesn't capture.
+fn validate_arg_value<S: MoveResolverExt>(
+    session: &SessionExt<S>,
+    typ: &Type,
+    arg: &[u8],
+) -> Result<(), VMStatus> {
+    let failed_to_deserialize = || VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT);
+    let layout = session
+        .get_type_tag(typ)
+        .and_then(|tag| session.get_type_layout(&tag))
+        .map_err(|_| failed_to_deserialize())?;
+    let value = MoveValue::simple_deserialize(arg, &layout).map_err(|_| failed_to_deserialize())?;
+    if is_valid_arg_value(session, typ, &value) {
+        Ok(())
+    } else {
+        Err(failed_to_deserialize())
+    }
+}
+
+fn is_valid_arg_value<S: MoveResolverExt>(
+    session: &SessionExt<S>,
+    typ: &Type,
+    value: &MoveValue,
+) -> bool {
+    let (idx, ty_args) = match typ {
+        Type::Vector(inner) => {
+            return match value {
+                MoveValue::Vector(elems) => elems
+                    .iter()
+                    .all(|elem| is_valid_arg_value(session, inner, elem)),
+                _ => false,
+            };
+        }
+        Type::Struct(idx) => (*idx, &[][..]),
+        Type::StructInstantiation(idx, ty_args) => (*idx, ty_args.as_slice()),
+        _ => return true,
+    };
+    let (st, fields) = match (session.get_struct_type(idx), value) {
+        (Some(st), MoveValue::Struct(MoveStruct::Runtime(fields))) => (st, fields),
+        _ => return false,
+    };
+    let full_name = format!("{}::{}", st.module.short_str_lossless(), st.name);
+    match (full_name.as_str(), fields.as_slice()) {
+        (STRING_STRUCT, [MoveValue::Vector(bytes)]) => {
+            let bytes = bytes
+                .iter()
+                .map(|byte| match byte {
+                    MoveValue::U8(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            matches!(bytes, Some(bytes) if std::str::from_utf8(&bytes).is_ok())
+        }
+        // An option is a vector of at most one element.
+        (OPTION_STRUCT, [MoveValue::Vector(elems)]) => match (elems.as_slice(), ty_args) {
+            ([], _) => true,
+            ([elem], [elem_ty]) => is_valid_arg_value(session, elem_ty, elem),
+            _ => false,
+        },
+        // Other structs are rejected by `is_valid_struct_txn_arg`
+        _ => false,
+    }
+}
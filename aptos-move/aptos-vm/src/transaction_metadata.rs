@@ -17,6 +17,7 @@ pub struct TransactionMetadata {
     pub secondary_signers: Vec<AccountAddress>,
     pub secondary_authentication_keys: Vec<Vec<u8>>,
     pub sequence_number: u64,
+    pub replay_nonce: Option<u64>,
     pub max_gas_amount: Gas,
     pub gas_unit_price: FeePerGasUnit,
     pub transaction_size: NumBytes,
@@ -38,6 +39,7 @@ impl TransactionMetadata {
                 .map(|account_auth| account_auth.authentication_key().to_vec())
                 .collect(),
             sequence_number: txn.sequence_number(),
+            replay_nonce: txn.payload().replay_nonce(),
             max_gas_amount: txn.max_gas_amount().into(),
             gas_unit_price: txn.gas_unit_price().into(),
             transaction_size: (txn.raw_txn_bytes_len() as u64).into(),
@@ -45,7 +47,8 @@ impl TransactionMetadata {
             chain_id: txn.chain_id(),
             script_hash: match txn.payload() {
                 TransactionPayload::Script(s) => HashValue::sha3_256_of(s.code()).to_vec(),
-                TransactionPayload::EntryFunction(_) => vec![],
+                TransactionPayload::EntryFunction(_)
+                | TransactionPayload::OrderlessEntryFunction(_) => vec![],
                 TransactionPayload::ModuleBundle(_) => vec![],
             },
         }
@@ -75,6 +78,12 @@ impl TransactionMetadata {
         self.sequence_number
     }
 
+    /// The nonce of an orderless transaction, which is protected from replays by it instead of
+    /// the sequence number
+    pub fn replay_nonce(&self) -> Option<u64> {
+        self.replay_nonce
+    }
+
     pub fn transaction_size(&self) -> NumBytes {
         self.transaction_size
     }
@@ -103,6 +112,7 @@ impl Default for TransactionMetadata {
             secondary_signers: vec![],
             secondary_authentication_keys: vec![],
             sequence_number: 0,
+            replay_nonce: None,
             max_gas_amount: 100_000_000.into(),
             gas_unit_price: 0.into(),
             transaction_size: 0.into(),
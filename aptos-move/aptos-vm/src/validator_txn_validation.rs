@@ -0,0 +1,60 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that the payload of a validator transaction is signed by a quorum of the current
+//! validator set before it is executed. Consensus checks the same before voting for a block, but
+//! the VM doesn't rely on it: a block is certified by the quorum voting for it, which doesn't
+//! make its proposer's choice of validator transactions trusted data.
+
+use crate::move_vm_ext::MoveResolverExt;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
+    on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
+    validator_txn::ValidatorTransaction,
+    validator_verifier::ValidatorVerifier,
+    vm_status::{StatusCode, VMStatus},
+};
+use move_deps::move_core_types::{
+    identifier::Identifier, language_storage::StructTag, move_resource::MoveStructType,
+};
+use serde::de::DeserializeOwned;
+
+fn get_resource<S: MoveResolverExt, T: DeserializeOwned>(
+    resolver: &S,
+    struct_tag: &StructTag,
+) -> Result<T, VMStatus> {
+    match resolver
+        .get_resource(&CORE_CODE_ADDRESS, struct_tag)
+        .map_err(|_| VMStatus::Error(StatusCode::STORAGE_ERROR))?
+    {
+        Some(bytes) => bcs::from_bytes(&bytes)
+            .map_err(|_| VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE)),
+        None => Err(VMStatus::Error(StatusCode::STORAGE_ERROR)),
+    }
+}
+
+/// Returns Ok if the payload of `txn` is signed by a quorum of the current validator set, in the
+/// current epoch unless it is a JWK update, Err otherwise.
+pub(crate) fn verify_validator_transaction<S: MoveResolverExt>(
+    resolver: &S,
+    txn: &ValidatorTransaction,
+) -> Result<(), VMStatus> {
+    let validator_set_tag = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(ValidatorSet::MODULE_IDENTIFIER).expect("fail to make identifier"),
+        name: Identifier::new(ValidatorSet::TYPE_IDENTIFIER).expect("fail to make identifier"),
+        type_params: vec![],
+    };
+    let validator_set: ValidatorSet = get_resource(resolver, &validator_set_tag)?;
+    let configuration: ConfigurationResource =
+        get_resource(resolver, &ConfigurationResource::struct_tag())?;
+    txn.verify(
+        &ValidatorVerifier::from(&validator_set),
+        configuration.epoch(),
+    )
+    .map_err(|e| {
+        debug!("Invalid validator transaction {}: {}", txn, e);
+        VMStatus::Error(StatusCode::INVALID_SIGNATURE)
+    })
+}
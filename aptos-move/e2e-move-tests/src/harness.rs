@@ -6,8 +6,12 @@ use aptos::move_tool::MemberId;
 use aptos_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
+    on_chain_config::{
+        access_path_for_config, ConfigID, GasSchedule, OnChainConfig, OnChainExecutionConfig,
+    },
     state_store::state_key::StateKey,
     transaction::{EntryFunction, SignedTransaction, TransactionPayload, TransactionStatus},
+    write_set::{WriteOp, WriteSetMut},
 };
 use cached_packages::aptos_stdlib;
 use framework::{BuildOptions, BuiltPackage};
@@ -206,6 +210,29 @@ impl MoveHarness {
         self.executor.new_block()
     }
 
+    /// Replaces the on-chain execution config, as governance would.
+    pub fn set_execution_config(&mut self, config: OnChainExecutionConfig) {
+        // The config resource holds the BCS-encoded config as bytes
+        let bytes = bcs::to_bytes(&bcs::to_bytes(&config).unwrap()).unwrap();
+        self.set_config(OnChainExecutionConfig::CONFIG_ID, bytes);
+    }
+
+    /// Replaces the on-chain gas schedule, as governance would.
+    pub fn set_gas_schedule(&mut self, gas_schedule: GasSchedule) {
+        let bytes = bcs::to_bytes(&gas_schedule).unwrap();
+        self.set_config(GasSchedule::CONFIG_ID, bytes);
+    }
+
+    fn set_config(&mut self, config_id: ConfigID, bytes: Vec<u8>) {
+        let write_set = WriteSetMut::new(vec![(
+            StateKey::AccessPath(access_path_for_config(config_id)),
+            WriteOp::Modification(bytes),
+        )])
+        .freeze()
+        .unwrap();
+        self.executor.apply_write_set(&write_set);
+    }
+
     pub fn new_block_with_metadata(
         &mut self,
         proposer: AccountAddress,
@@ -4,6 +4,8 @@
 use aptos_types::account_address::AccountAddress;
 use e2e_move_tests::{assert_abort, assert_success, assert_vm_status, MoveHarness};
 use framework::natives::code::PackageRegistry;
+use framework::{check_upgrade_compatibility, BuildOptions, BuiltPackage, IncompatibleChangeKind};
+use move_deps::move_binary_format::CompiledModule;
 use move_deps::move_core_types::parser::parse_struct_tag;
 use move_deps::move_core_types::vm_status::StatusCode;
 use serde::{Deserialize, Serialize};
@@ -216,3 +218,33 @@ fn code_publishing_framework_upgrade_fail() {
     );
     assert_vm_status!(result, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
 }
+
+#[test]
+fn code_publishing_upgrade_incompat_changes() {
+    let module = |path: &str| {
+        let package = BuiltPackage::build(common::package_path(path), BuildOptions::default())
+            .expect("building package must succeed");
+        CompiledModule::deserialize(&package.extract_code()[0]).unwrap()
+    };
+    let initial = module("code_publishing.data/pack_initial");
+
+    assert!(check_upgrade_compatibility(
+        &initial,
+        &module("code_publishing.data/pack_upgrade_compat")
+    )
+    .is_empty());
+
+    let changes = check_upgrade_compatibility(
+        &initial,
+        &module("code_publishing.data/pack_upgrade_incompat"),
+    );
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].member.as_str(), "hello");
+    assert_eq!(
+        changes[0].kind,
+        IncompatibleChangeKind::FunctionSignatureChanged {
+            old: "<>(&signer, u64): ()".to_string(),
+            new: "<>(&signer, u64, u64): ()".to_string(),
+        }
+    );
+}
@@ -0,0 +1,8 @@
+module 0xcafe::test_init {
+    fun init_module(_s: &signer) {
+        let i = 0;
+        while (i < 100000) {
+            i = i + 1;
+        }
+    }
+}
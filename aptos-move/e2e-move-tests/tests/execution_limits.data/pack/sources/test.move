@@ -0,0 +1,8 @@
+module 0xcafe::test {
+    public entry fun loop_n(_s: &signer, n: u64) {
+        let i = 0;
+        while (i < n) {
+            i = i + 1;
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::AccountResource,
+    on_chain_config::{
+        ExecutionConfigV2, ExecutionLimits, OnChainExecutionConfig, TransactionShufflerType,
+    },
+    transaction::{EntryFunction, ExecutionStatus, TransactionStatus},
+};
+use e2e_move_tests::{assert_success, MoveHarness};
+use language_e2e_tests::account::Account;
+use move_deps::move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, move_resource::MoveStructType,
+    vm_status::AbortLocation,
+};
+
+mod common;
+
+/// High enough for the framework code the tests run, which is limited too.
+const MAX_LOOP_ITERATIONS: u64 = 10_000;
+
+fn module_id(name: &str) -> ModuleId {
+    ModuleId::new(
+        AccountAddress::from_hex_literal("0xcafe").unwrap(),
+        Identifier::new(name).unwrap(),
+    )
+}
+
+/// Publishes the test package, before loops are limited.
+fn setup() -> (MoveHarness, Account) {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(h.publish_package(&acc, &common::package_path("execution_limits.data/pack")));
+    (h, acc)
+}
+
+fn limit_loops(h: &mut MoveHarness) {
+    h.set_execution_config(OnChainExecutionConfig::V2(ExecutionConfigV2 {
+        transaction_shuffler_type: TransactionShufflerType::NoShuffling,
+        execution_limits: ExecutionLimits {
+            max_loop_iterations_per_tick: MAX_LOOP_ITERATIONS,
+            ..ExecutionLimits::default()
+        },
+    }));
+}
+
+fn loop_n(
+    h: &mut MoveHarness,
+    sender: &Account,
+    sequence_number: u64,
+    n: u64,
+) -> TransactionStatus {
+    let txn = sender
+        .transaction()
+        .sequence_number(sequence_number)
+        .gas_unit_price(1)
+        .max_gas_amount(100_000)
+        .entry_function(EntryFunction::new(
+            module_id("test"),
+            Identifier::new("loop_n").unwrap(),
+            vec![],
+            vec![bcs::to_bytes(&n).unwrap()],
+        ))
+        .sign();
+    h.run(txn)
+}
+
+fn assert_loop_limit_exceeded(status: TransactionStatus, module: &str) {
+    assert_eq!(
+        status,
+        TransactionStatus::Keep(ExecutionStatus::MoveAbort {
+            location: AbortLocation::Module(module_id(module)),
+            code: ExecutionLimits::ELOOP_ITERATIONS_EXCEEDED,
+        })
+    );
+}
+
+#[test]
+fn loop_limit_after_implicit_account_creation() {
+    let (mut h, acc) = setup();
+    let sender = Account::new();
+    assert_success!(h.run_entry_function(
+        &acc,
+        str::parse("0x1::account::transfer").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(sender.address()).unwrap(),
+            bcs::to_bytes(&1_000_000u64).unwrap(),
+        ],
+    ));
+    limit_loops(&mut h);
+
+    // The account is created first, in a tick of its own, which must not lift the limit of the
+    // entry function
+    assert_loop_limit_exceeded(loop_n(&mut h, &sender, 0, 10 * MAX_LOOP_ITERATIONS), "test");
+    assert!(h
+        .read_resource::<AccountResource>(sender.address(), AccountResource::struct_tag())
+        .is_some());
+    assert_success!(loop_n(&mut h, &sender, 1, 10));
+}
+
+#[test]
+fn loop_limit_in_init_module() {
+    let (mut h, acc) = setup();
+    limit_loops(&mut h);
+
+    // `init_module` runs in a tick of its own once the package is published, limited too
+    assert_loop_limit_exceeded(
+        h.publish_package(
+            &acc,
+            &common::package_path("execution_limits.data/pack-init"),
+        ),
+        "test_init",
+    );
+}
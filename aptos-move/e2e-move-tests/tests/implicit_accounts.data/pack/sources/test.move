@@ -0,0 +1,24 @@
+module 0xcafe::test {
+    use std::signer;
+
+    struct Counter has key {
+        value: u64,
+    }
+
+    public entry fun increment(s: &signer) acquires Counter {
+        let addr = signer::address_of(s);
+        if (!exists<Counter>(addr)) {
+            move_to(s, Counter { value: 0 })
+        };
+        let counter = borrow_global_mut<Counter>(addr);
+        counter.value = counter.value + 1;
+    }
+
+    public entry fun abort_now(_s: &signer) {
+        abort 7
+    }
+
+    public entry fun loop_forever(_s: &signer) {
+        loop {}
+    }
+}
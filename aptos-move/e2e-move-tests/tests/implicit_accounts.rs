@@ -0,0 +1,116 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{AccountResource, CoinStoreResource},
+    transaction::{EntryFunction, ExecutionStatus, TransactionStatus},
+};
+use e2e_move_tests::{assert_abort, assert_success, MoveHarness};
+use language_e2e_tests::account::Account;
+use move_deps::move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, move_resource::MoveStructType,
+    parser::parse_struct_tag,
+};
+
+mod common;
+
+const FUNDS: u64 = 1_000_000;
+
+/// Publishes the test package, and returns an implicit sender holding `FUNDS`: its coin store
+/// exists, but its account doesn't yet.
+fn setup() -> (MoveHarness, Account) {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(h.publish_package(&acc, &common::package_path("implicit_accounts.data/pack")));
+
+    let sender = Account::new();
+    assert_success!(h.run_entry_function(
+        &acc,
+        str::parse("0x1::account::transfer").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(sender.address()).unwrap(),
+            bcs::to_bytes(&FUNDS).unwrap(),
+        ],
+    ));
+    assert!(account(&h, &sender).is_none());
+    assert_eq!(balance(&h, &sender), FUNDS);
+    (h, sender)
+}
+
+/// Runs `0xcafe::test::<function>` as the first transaction of `sender`.
+fn run_first(
+    h: &mut MoveHarness,
+    sender: &Account,
+    function: &str,
+    max_gas_amount: u64,
+) -> TransactionStatus {
+    let txn = sender
+        .transaction()
+        .sequence_number(0)
+        .gas_unit_price(1)
+        .max_gas_amount(max_gas_amount)
+        .entry_function(EntryFunction::new(
+            ModuleId::new(
+                AccountAddress::from_hex_literal("0xcafe").unwrap(),
+                Identifier::new("test").unwrap(),
+            ),
+            Identifier::new(function).unwrap(),
+            vec![],
+            vec![],
+        ))
+        .sign();
+    h.run(txn)
+}
+
+fn account(h: &MoveHarness, sender: &Account) -> Option<AccountResource> {
+    h.read_resource(sender.address(), AccountResource::struct_tag())
+}
+
+fn balance(h: &MoveHarness, sender: &Account) -> u64 {
+    h.read_resource::<CoinStoreResource>(sender.address(), CoinStoreResource::struct_tag())
+        .unwrap()
+        .coin()
+}
+
+#[test]
+fn implicit_account_created_by_first_transaction() {
+    let (mut h, sender) = setup();
+
+    assert_success!(run_first(&mut h, &sender, "increment", 100_000));
+    let account = account(&h, &sender).unwrap();
+    assert_eq!(account.sequence_number(), 1);
+    assert_eq!(account.authentication_key(), sender.auth_key().as_slice());
+    assert!(balance(&h, &sender) < FUNDS);
+    assert!(h.exists_resource(
+        sender.address(),
+        parse_struct_tag("0xCAFE::test::Counter").unwrap()
+    ));
+}
+
+#[test]
+fn implicit_account_created_by_failed_transaction() {
+    let (mut h, sender) = setup();
+
+    // The account is kept, and paid for, even though the rest of the transaction is dropped
+    assert_abort!(run_first(&mut h, &sender, "abort_now", 100_000), 7);
+    assert_eq!(account(&h, &sender).unwrap().sequence_number(), 1);
+    assert!(balance(&h, &sender) < FUNDS);
+}
+
+#[test]
+fn implicit_account_created_by_out_of_gas_transaction() {
+    // Runs out of gas in the payload, and when creating the account itself
+    for max_gas_amount in [1_000, 1] {
+        let (mut h, sender) = setup();
+
+        // The sender pays its whole max gas amount instead of executing for free
+        assert_eq!(
+            run_first(&mut h, &sender, "loop_forever", max_gas_amount),
+            TransactionStatus::Keep(ExecutionStatus::OutOfGas)
+        );
+        assert_eq!(account(&h, &sender).unwrap().sequence_number(), 1);
+        assert_eq!(balance(&h, &sender), FUNDS - max_gas_amount);
+    }
+}
@@ -0,0 +1,20 @@
+module 0xcafe::test {
+    use std::vector;
+
+    /// Copies a vector of 1000 u64s `copies` times.
+    public entry fun copy_vector(copies: u64) {
+        let v = vector::empty<u64>();
+        let i = 0;
+        while (i < 1000) {
+            vector::push_back(&mut v, i);
+            i = i + 1;
+        };
+
+        let i = 0;
+        while (i < copies) {
+            let w = copy v;
+            vector::push_back(&mut w, i);
+            i = i + 1;
+        };
+    }
+}
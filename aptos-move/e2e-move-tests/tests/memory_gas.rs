@@ -0,0 +1,43 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_gas::{
+    AptosGasParameters, InitialGasSchedule, ToOnChainGasSchedule, GAS_FEATURE_VERSION_KEY,
+};
+use aptos_types::{account_address::AccountAddress, on_chain_config::GasSchedule};
+use e2e_move_tests::{assert_success, MoveHarness};
+
+mod common;
+
+#[test]
+fn memory_gas() {
+    let mut h = MoveHarness::new();
+
+    // Load the code
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(h.publish_package(&acc, &common::package_path("memory_gas.data/pack")));
+
+    // Each copy allocates about 8000 units of abstract value size
+    let txn = h.create_entry_function(
+        &acc,
+        str::parse("0xcafe::test::copy_vector").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&100u64).unwrap()],
+    );
+    let charged = h.executor.execute_transaction(txn.clone());
+    assert_success!(charged.status().to_owned());
+
+    // The same gas schedule, as published before memory was charged, still works but doesn't
+    // charge for it
+    let entries = AptosGasParameters::initial()
+        .to_on_chain_gas_schedule()
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("misc.memory.") && key != GAS_FEATURE_VERSION_KEY)
+        .collect();
+    h.set_gas_schedule(GasSchedule { entries });
+    let uncharged = h.executor.execute_transaction(txn);
+    assert_success!(uncharged.status().to_owned());
+    // At 1 internal gas unit per unit of abstract value size, and 1000 internal gas units per
+    // gas unit, the copies cost about 800 gas units more
+    assert!(charged.gas_used() > uncharged.gas_used() + 700);
+}
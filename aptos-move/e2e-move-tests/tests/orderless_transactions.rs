@@ -0,0 +1,119 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{AccountResource, CoinStoreResource},
+    chain_id::ChainId,
+    transaction::{EntryFunction, RawTransaction, SignedTransaction, TransactionStatus},
+    vm_status::StatusCode,
+};
+use e2e_move_tests::{assert_success, MoveHarness};
+use language_e2e_tests::account::Account;
+use move_deps::move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, move_resource::MoveStructType,
+};
+
+const AMOUNT: u64 = 1_000;
+
+/// Block time of the tests, in seconds.
+const NOW_SECS: u64 = 1_000_000;
+
+fn setup() -> (MoveHarness, Account, Account) {
+    let mut h = MoveHarness::new();
+    h.executor.new_block_with_timestamp(NOW_SECS * 1_000_000);
+    let sender = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let receiver = h.new_account_at(AccountAddress::from_hex_literal("0xbeef").unwrap());
+    (h, sender, receiver)
+}
+
+/// An orderless transfer of `AMOUNT` from `sender` to `receiver`, expiring `ttl_secs` from now.
+fn orderless_transfer(
+    sender: &Account,
+    receiver: &Account,
+    nonce: u64,
+    ttl_secs: u64,
+) -> SignedTransaction {
+    RawTransaction::new_orderless_entry_function(
+        *sender.address(),
+        nonce,
+        EntryFunction::new(
+            ModuleId::new(AccountAddress::ONE, Identifier::new("account").unwrap()),
+            Identifier::new("transfer").unwrap(),
+            vec![],
+            vec![
+                bcs::to_bytes(receiver.address()).unwrap(),
+                bcs::to_bytes(&AMOUNT).unwrap(),
+            ],
+        ),
+        100_000,
+        1,
+        NOW_SECS + ttl_secs,
+        ChainId::test(),
+    )
+    .sign(&sender.privkey, sender.pubkey.clone())
+    .unwrap()
+    .into_inner()
+}
+
+fn sequence_number(h: &MoveHarness, account: &Account) -> u64 {
+    h.read_resource::<AccountResource>(account.address(), AccountResource::struct_tag())
+        .unwrap()
+        .sequence_number()
+}
+
+fn balance(h: &MoveHarness, account: &Account) -> u64 {
+    h.read_resource::<CoinStoreResource>(account.address(), CoinStoreResource::struct_tag())
+        .unwrap()
+        .coin()
+}
+
+#[test]
+fn orderless_transaction_executes() {
+    let (mut h, sender, receiver) = setup();
+    let sequence_number_before = sequence_number(&h, &sender);
+    let balance_before = balance(&h, &receiver);
+
+    assert_success!(h.run(orderless_transfer(&sender, &receiver, 7, 30)));
+    assert_eq!(balance(&h, &receiver), balance_before + AMOUNT);
+    // The sequence number of the sender is left to its sequenced transactions
+    assert_eq!(sequence_number(&h, &sender), sequence_number_before);
+
+    // Nonces don't have to be used in order
+    assert_success!(h.run(orderless_transfer(&sender, &receiver, 3, 30)));
+    assert_eq!(balance(&h, &receiver), balance_before + 2 * AMOUNT);
+}
+
+#[test]
+fn orderless_transaction_cannot_be_replayed() {
+    let (mut h, sender, receiver) = setup();
+    let txn = orderless_transfer(&sender, &receiver, 7, 30);
+    assert_success!(h.run(txn.clone()));
+    let balance_after = balance(&h, &receiver);
+
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::SEQUENCE_NUMBER_TOO_OLD)
+    );
+    // Nor with a different payload or expiration, under the same nonce
+    assert_eq!(
+        h.run(orderless_transfer(&sender, &receiver, 7, 40)),
+        TransactionStatus::Discard(StatusCode::SEQUENCE_NUMBER_TOO_OLD)
+    );
+    assert_eq!(balance(&h, &receiver), balance_after);
+
+    // The nonce is only used up for its sender
+    assert_success!(h.run(orderless_transfer(&receiver, &sender, 7, 30)));
+}
+
+#[test]
+fn orderless_transaction_expiration_is_bounded() {
+    let (mut h, sender, receiver) = setup();
+    // Nonces are only remembered until the transactions using them expire, so an expiration
+    // further ahead than that window would allow a replay
+    assert_eq!(
+        h.run(orderless_transfer(&sender, &receiver, 7, 3600)),
+        TransactionStatus::Discard(StatusCode::TRANSACTION_EXPIRED)
+    );
+    assert_success!(h.run(orderless_transfer(&sender, &receiver, 7, 30)));
+}
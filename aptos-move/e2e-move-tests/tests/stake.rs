@@ -28,7 +28,7 @@ fn test_staking_end_to_end() {
         owner_address
     ));
     let stake_pool = get_stake_pool(&harness, &owner_address);
-    assert_eq!(stake_pool.active, stake_amount);
+    assert_eq!(stake_pool.active.value, stake_amount);
     assert_eq!(stake_pool.operator_address, operator_address);
     assert_eq!(stake_pool.delegated_voter, owner_address);
 
@@ -49,23 +49,23 @@ fn test_staking_end_to_end() {
     let remaining_stake = stake_amount - amount_to_withdraw;
     assert_success!(unlock_stake(&mut harness, &owner, amount_to_withdraw));
     let stake_pool = get_stake_pool(&harness, &owner_address);
-    assert_eq!(stake_pool.active, remaining_stake);
-    assert_eq!(stake_pool.pending_inactive, amount_to_withdraw);
+    assert_eq!(stake_pool.active.value, remaining_stake);
+    assert_eq!(stake_pool.pending_inactive.value, amount_to_withdraw);
 
     // Wait until stake is unlocked.
     harness.fast_forward(7200);
     harness.new_epoch();
     let stake_pool = get_stake_pool(&harness, &owner_address);
-    assert_eq!(stake_pool.pending_inactive, 0);
-    assert_eq!(stake_pool.inactive, amount_to_withdraw);
+    assert_eq!(stake_pool.pending_inactive.value, 0);
+    assert_eq!(stake_pool.inactive.value, amount_to_withdraw);
 
     // Withdraw and verify that coins are returned.
     assert_success!(withdraw_stake(&mut harness, &owner, stake_amount / 2));
     let stake_pool = get_stake_pool(&harness, &owner_address);
-    assert_eq!(stake_pool.inactive, 0);
+    assert_eq!(stake_pool.inactive.value, 0);
 
     // Verify that lockup has been renewed for remaining stake.
-    assert_eq!(stake_pool.active, remaining_stake);
+    assert_eq!(stake_pool.active.value, remaining_stake);
     assert_eq!(
         stake_pool.locked_until_secs,
         harness.executor.get_block_time_seconds() + 7200
@@ -77,8 +77,8 @@ fn test_staking_end_to_end() {
     harness.new_epoch();
     assert_success!(withdraw_stake(&mut harness, &owner, remaining_stake));
     let stake_pool = get_stake_pool(&harness, &owner_address);
-    assert_eq!(stake_pool.active, 0);
-    assert_eq!(stake_pool.inactive, 0);
+    assert_eq!(stake_pool.active.value, 0);
+    assert_eq!(stake_pool.inactive.value, 0);
 }
 
 #[test]
@@ -133,11 +133,11 @@ fn test_staking_rewards() {
     stake_amount_1 += rewards_per_epoch;
     stake_amount_2 += rewards_per_epoch;
     assert_eq!(
-        get_stake_pool(&harness, &validator_1_address).active,
+        get_stake_pool(&harness, &validator_1_address).active.value,
         stake_amount_1
     );
     assert_eq!(
-        get_stake_pool(&harness, &validator_2_address).active,
+        get_stake_pool(&harness, &validator_2_address).active.value,
         stake_amount_2
     );
 
@@ -148,22 +148,22 @@ fn test_staking_rewards() {
     harness.new_epoch();
     stake_amount_1 += rewards_per_epoch;
     assert_eq!(
-        get_stake_pool(&harness, &validator_1_address).active,
+        get_stake_pool(&harness, &validator_1_address).active.value,
         stake_amount_1
     );
     assert_eq!(
-        get_stake_pool(&harness, &validator_2_address).active,
+        get_stake_pool(&harness, &validator_2_address).active.value,
         stake_amount_2
     );
     harness.new_block_with_metadata(validator_2_address, vec![]);
     harness.new_epoch();
     assert_eq!(
-        get_stake_pool(&harness, &validator_1_address).active,
+        get_stake_pool(&harness, &validator_1_address).active.value,
         stake_amount_1
     );
     stake_amount_2 += rewards_per_epoch;
     assert_eq!(
-        get_stake_pool(&harness, &validator_2_address).active,
+        get_stake_pool(&harness, &validator_2_address).active.value,
         stake_amount_2
     );
 
@@ -172,12 +172,12 @@ fn test_staking_rewards() {
     harness.new_block_with_metadata(validator_2_address, vec![index_1]);
     harness.new_epoch();
     assert_eq!(
-        get_stake_pool(&harness, &validator_1_address).active,
+        get_stake_pool(&harness, &validator_1_address).active.value,
         stake_amount_1
     );
     stake_amount_2 += rewards_per_epoch;
     assert_eq!(
-        get_stake_pool(&harness, &validator_2_address).active,
+        get_stake_pool(&harness, &validator_2_address).active.value,
         stake_amount_2
     );
 
@@ -186,7 +186,7 @@ fn test_staking_rewards() {
     harness.new_epoch();
     stake_amount_1 += rewards_per_epoch / 2;
     assert_eq!(
-        get_stake_pool(&harness, &validator_1_address).active,
+        get_stake_pool(&harness, &validator_1_address).active.value,
         stake_amount_1
     );
 }
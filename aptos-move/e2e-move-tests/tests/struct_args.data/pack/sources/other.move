@@ -0,0 +1,18 @@
+/// A module whose struct can only be created through its constructor, which checks its invariant.
+module 0xCAFE::other {
+    /// The value of a secret must be non-zero
+    const EZERO_SECRET: u64 = 1;
+
+    struct Secret has copy, drop, store {
+        value: u64,
+    }
+
+    public fun new(value: u64): Secret {
+        assert!(value != 0, EZERO_SECRET);
+        Secret { value }
+    }
+
+    public fun value(secret: &Secret): u64 {
+        secret.value
+    }
+}
@@ -0,0 +1,46 @@
+module 0xCAFE::test {
+    use std::option::{Self, Option};
+    use std::signer;
+    use std::string::String;
+    use std::vector;
+    use aptos_std::guid::{Self, ID};
+
+    use 0xCAFE::other::{Self, Secret};
+
+    struct ModuleData has key {
+        value: u64,
+    }
+
+    fun set(sender: &signer, value: u64) acquires ModuleData {
+        let addr = signer::address_of(sender);
+        if (!exists<ModuleData>(addr)) {
+            move_to(sender, ModuleData { value })
+        } else {
+            borrow_global_mut<ModuleData>(addr).value = value;
+        }
+    }
+
+    public entry fun set_option(sender: &signer, value: Option<u64>) acquires ModuleData {
+        set(sender, option::get_with_default(&value, 0))
+    }
+
+    public entry fun set_name_length(sender: &signer, names: vector<String>) acquires ModuleData {
+        set(sender, vector::length(&names))
+    }
+
+    public entry fun set_secret(sender: &signer, secret: Secret) acquires ModuleData {
+        set(sender, other::value(&secret))
+    }
+
+    public entry fun set_secrets(sender: &signer, secrets: vector<Secret>) acquires ModuleData {
+        set(sender, other::value(vector::borrow(&secrets, 0)))
+    }
+
+    public entry fun set_optional_secret(sender: &signer, secret: Option<Secret>) acquires ModuleData {
+        set(sender, other::value(option::borrow(&secret)))
+    }
+
+    public entry fun set_id(sender: &signer, id: ID) acquires ModuleData {
+        set(sender, guid::id_creation_num(&id))
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    account_address::AccountAddress, transaction::TransactionStatus, vm_status::StatusCode,
+};
+use e2e_move_tests::{assert_success, assert_vm_status, MoveHarness};
+use language_e2e_tests::account::Account;
+use move_deps::move_core_types::parser::parse_struct_tag;
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+/// Mimics `0xcafe::test::ModuleData`
+#[derive(Serialize, Deserialize)]
+struct ModuleData {
+    value: u64,
+}
+
+fn setup() -> (MoveHarness, Account) {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(h.publish_package(&acc, &common::package_path("struct_args.data/pack")));
+    (h, acc)
+}
+
+fn run(h: &mut MoveHarness, acc: &Account, function: &str, arg: Vec<u8>) -> TransactionStatus {
+    h.run_entry_function(
+        acc,
+        str::parse(&format!("0xcafe::test::{}", function)).unwrap(),
+        vec![],
+        vec![arg],
+    )
+}
+
+fn value(h: &MoveHarness, acc: &Account) -> u64 {
+    h.read_resource::<ModuleData>(
+        acc.address(),
+        parse_struct_tag("0xCAFE::test::ModuleData").unwrap(),
+    )
+    .unwrap()
+    .value
+}
+
+#[test]
+fn allowed_struct_args() {
+    let (mut h, acc) = setup();
+
+    assert_success!(run(
+        &mut h,
+        &acc,
+        "set_option",
+        bcs::to_bytes(&vec![7u64]).unwrap()
+    ));
+    assert_eq!(value(&h, &acc), 7);
+    assert_success!(run(
+        &mut h,
+        &acc,
+        "set_option",
+        bcs::to_bytes(&Vec::<u64>::new()).unwrap()
+    ));
+    assert_eq!(value(&h, &acc), 0);
+
+    let names = vec!["a".to_string(), "b".to_string()];
+    assert_success!(run(
+        &mut h,
+        &acc,
+        "set_name_length",
+        bcs::to_bytes(&names).unwrap()
+    ));
+    assert_eq!(value(&h, &acc), 2);
+}
+
+#[test]
+fn invalid_allowed_struct_values() {
+    let (mut h, acc) = setup();
+
+    // An option holds at most one element
+    assert_vm_status!(
+        run(
+            &mut h,
+            &acc,
+            "set_option",
+            bcs::to_bytes(&vec![1u64, 2u64]).unwrap()
+        ),
+        StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT
+    );
+    // Strings are UTF-8
+    assert_vm_status!(
+        run(
+            &mut h,
+            &acc,
+            "set_name_length",
+            bcs::to_bytes(&vec![vec![0xffu8]]).unwrap()
+        ),
+        StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT
+    );
+}
+
+#[test]
+fn third_party_struct_args_rejected() {
+    let (mut h, acc) = setup();
+
+    // `Secret` can only be created through `other::new`, which rejects zero, so it must not be
+    // forged from the bytes of a transaction, even though it has `copy` and `drop`
+    let zero_secret = bcs::to_bytes(&0u64).unwrap();
+    assert_vm_status!(
+        run(&mut h, &acc, "set_secret", zero_secret),
+        StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE
+    );
+    assert_vm_status!(
+        run(
+            &mut h,
+            &acc,
+            "set_secrets",
+            bcs::to_bytes(&vec![0u64]).unwrap()
+        ),
+        StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE
+    );
+    assert_vm_status!(
+        run(
+            &mut h,
+            &acc,
+            "set_optional_secret",
+            bcs::to_bytes(&vec![0u64]).unwrap()
+        ),
+        StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE
+    );
+    // Framework structs outside of the allowlist are rejected too
+    assert_vm_status!(
+        run(
+            &mut h,
+            &acc,
+            "set_id",
+            bcs::to_bytes(&(1u64, *acc.address())).unwrap()
+        ),
+        StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE
+    );
+    assert!(!h.exists_resource(
+        acc.address(),
+        parse_struct_tag("0xCAFE::test::ModuleData").unwrap()
+    ));
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_types::{
+    on_chain_config::BlockOutputLimit,
+    transaction::{ExecutionStatus, Transaction, TransactionStatus},
+};
+use aptos_vm::{move_vm_ext::WarmVmCache, AptosVM, VMExecutor};
+use language_e2e_tests::{common_transactions::peer_to_peer_txn, executor::FakeExecutor};
+
+const NUM_TRANSFERS: usize = 6;
+
+fn transfers(executor: &mut FakeExecutor) -> Vec<Transaction> {
+    let receiver = executor.create_raw_account_data(100_000, 0);
+    executor.add_account_data(&receiver);
+    (0..NUM_TRANSFERS)
+        .map(|_| {
+            let sender = executor.create_raw_account_data(1_000_000, 0);
+            executor.add_account_data(&sender);
+            Transaction::UserTransaction(peer_to_peer_txn(
+                sender.account(),
+                receiver.account(),
+                0,
+                1_000,
+            ))
+        })
+        .chain(std::iter::once(Transaction::StateCheckpoint(
+            HashValue::zero(),
+        )))
+        .collect()
+}
+
+#[test]
+fn block_cut_in_the_middle_of_a_batch() {
+    let mut executor = FakeExecutor::from_head_genesis();
+    let txns = transfers(&mut executor);
+    let full_outputs = AptosVM::execute_block(txns.clone(), executor.get_state_view()).unwrap();
+    let events_per_transfer = full_outputs[0].events().len() as u64;
+
+    // The third transfer crosses the limit, the whole block fits in the first batch.
+    let limit = BlockOutputLimit {
+        max_write_set_bytes: u64::MAX,
+        max_events: 2 * events_per_transfer,
+    };
+    let outputs = AptosVM::execute_block_with_output_limit(
+        txns.clone(),
+        executor.get_state_view(),
+        &WarmVmCache::new(),
+        &limit,
+    )
+    .unwrap();
+    assert_eq!(outputs.len(), txns.len());
+    assert_eq!(outputs[..3], full_outputs[..3]);
+    for output in &outputs[3..NUM_TRANSFERS] {
+        assert_eq!(output.status(), &TransactionStatus::Retry);
+        assert!(output.write_set().is_empty());
+    }
+    // The block still ends with its state checkpoint
+    assert_eq!(
+        outputs[NUM_TRANSFERS].status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+}
+
+#[test]
+fn block_not_cut_under_the_limit() {
+    let mut executor = FakeExecutor::from_head_genesis();
+    let txns = transfers(&mut executor);
+    let full_outputs = AptosVM::execute_block(txns.clone(), executor.get_state_view()).unwrap();
+
+    let outputs = AptosVM::execute_block_with_output_limit(
+        txns,
+        executor.get_state_view(),
+        &WarmVmCache::new(),
+        &BlockOutputLimit::default(),
+    )
+    .unwrap();
+    assert_eq!(outputs, full_outputs);
+}
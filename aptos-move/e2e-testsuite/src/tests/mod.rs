@@ -12,6 +12,7 @@
 //! Set env REGENERATE_GOLDENFILES to update the golden files when running tests..
 
 mod account_universe;
+mod block_output_limit;
 mod create_account;
 mod data_store;
 mod execution_strategies;
@@ -10,6 +10,8 @@ module aptos_framework::account {
     use aptos_framework::aptos_coin::AptosCoin;
     use aptos_framework::chain_id;
     use aptos_framework::coin;
+    use aptos_framework::features;
+    use aptos_framework::nonce_validation;
     use aptos_framework::system_addresses;
     use aptos_framework::timestamp;
     use aptos_framework::transaction_fee;
@@ -101,6 +103,8 @@ module aptos_framework::account {
     const PROLOGUE_EINVALID_WRITESET_SENDER: u64 = 1008;
     const PROLOGUE_ESEQUENCE_NUMBER_TOO_BIG: u64 = 1009;
     const PROLOGUE_ESECONDARY_KEYS_ADDRESSES_COUNT_MISMATCH: u64 = 1010;
+    const PROLOGUE_ENONCE_ALREADY_USED: u64 = 1011;
+    const PROLOGUE_EEXPIRATION_TOO_FAR: u64 = 1012;
 
     #[test_only]
     public fun create_address_for_test(bytes: vector<u8>): address {
@@ -272,12 +276,13 @@ module aptos_framework::account {
         account_resource.authentication_key = new_auth_key;
     }
 
-    fun prologue_common(
-        sender: signer,
-        txn_sequence_number: u64,
+    /// Checks of the prologue which don't depend on the replay protection of the transaction.
+    ///
+    /// An implicit sender doesn't have an account yet. It's created by `create_implicit_sender_account`
+    /// once the prologue passed, as part of the metered execution of the transaction.
+    fun check_sender_and_expiration(
+        transaction_sender: address,
         txn_authentication_key: vector<u8>,
-        txn_gas_price: u64,
-        txn_max_gas_units: u64,
         txn_expiration_time: u64,
         chain_id: u8,
     ) acquires Account {
@@ -285,37 +290,67 @@ module aptos_framework::account {
             timestamp::now_seconds() < txn_expiration_time,
             error::invalid_argument(PROLOGUE_ETRANSACTION_EXPIRED),
         );
-        let transaction_sender = signer::address_of(&sender);
         assert!(chain_id::get() == chain_id, error::invalid_argument(PROLOGUE_EBAD_CHAIN_ID));
+        if (!exists<Account>(transaction_sender) && features::is_enabled(features::implicit_accounts())) {
+            // The authentication key of an implicit account is its address, until it gets rotated.
+            assert!(
+                txn_authentication_key == bcs::to_bytes(&transaction_sender),
+                error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
+            );
+            return
+        };
         assert!(exists<Account>(transaction_sender), error::invalid_argument(PROLOGUE_EACCOUNT_DOES_NOT_EXIST));
         let sender_account = borrow_global<Account>(transaction_sender);
         assert!(
             txn_authentication_key == *&sender_account.authentication_key,
             error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
         );
+    }
+
+    fun check_gas_deposit(transaction_sender: address, txn_gas_price: u64, txn_max_gas_units: u64) {
+        let max_transaction_fee = txn_gas_price * txn_max_gas_units;
+        assert!(
+            coin::is_account_registered<AptosCoin>(transaction_sender),
+            error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT),
+        );
+        let balance = coin::balance<AptosCoin>(transaction_sender);
+        assert!(balance >= max_transaction_fee, error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT));
+    }
+
+    fun prologue_common(
+        sender: signer,
+        txn_sequence_number: u64,
+        txn_authentication_key: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+    ) acquires Account {
+        let transaction_sender = signer::address_of(&sender);
+        check_sender_and_expiration(transaction_sender, txn_authentication_key, txn_expiration_time, chain_id);
+        // The sequence number of an implicit sender is 0 until its account gets created.
+        let account_sequence_number = if (exists<Account>(transaction_sender)) {
+            borrow_global<Account>(transaction_sender).sequence_number
+        } else {
+            0
+        };
         assert!(
             (txn_sequence_number as u128) < MAX_U64,
             error::out_of_range(PROLOGUE_ESEQUENCE_NUMBER_TOO_BIG)
         );
 
         assert!(
-            txn_sequence_number >= sender_account.sequence_number,
+            txn_sequence_number >= account_sequence_number,
             error::invalid_argument(PROLOGUE_ESEQUENCE_NUMBER_TOO_OLD)
         );
 
         // [PCA12]: Check that the transaction's sequence number matches the
         // current sequence number. Otherwise sequence number is too new by [PCA11].
         assert!(
-            txn_sequence_number == sender_account.sequence_number,
+            txn_sequence_number == account_sequence_number,
             error::invalid_argument(PROLOGUE_ESEQUENCE_NUMBER_TOO_NEW)
         );
-        let max_transaction_fee = txn_gas_price * txn_max_gas_units;
-        assert!(
-            coin::is_account_registered<AptosCoin>(transaction_sender),
-            error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT),
-        );
-        let balance = coin::balance<AptosCoin>(transaction_sender);
-        assert!(balance >= max_transaction_fee, error::invalid_argument(PROLOGUE_ECANT_PAY_GAS_DEPOSIT));
+        check_gas_deposit(transaction_sender, txn_gas_price, txn_max_gas_units);
     }
 
     ///////////////////////////////////////////////////////////////////////////
@@ -346,6 +381,31 @@ module aptos_framework::account {
         prologue_common(sender, txn_sequence_number, txn_public_key, txn_gas_price, txn_max_gas_units, txn_expiration_time, chain_id)
     }
 
+    /// Prologue of orderless transactions, which are protected from replays by a nonce instead of
+    /// the sequence number of the sender.
+    fun orderless_prologue(
+        sender: signer,
+        txn_nonce: u64,
+        txn_public_key: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+    ) acquires Account {
+        let transaction_sender = signer::address_of(&sender);
+        check_sender_and_expiration(transaction_sender, txn_public_key, txn_expiration_time, chain_id);
+        // Bounding the expiration bounds how long the nonce must be remembered.
+        assert!(
+            txn_expiration_time <= timestamp::now_seconds() + nonce_validation::max_expiration_window_secs(),
+            error::invalid_argument(PROLOGUE_EEXPIRATION_TOO_FAR),
+        );
+        assert!(
+            nonce_validation::is_nonce_available(transaction_sender, txn_nonce),
+            error::invalid_argument(PROLOGUE_ENONCE_ALREADY_USED),
+        );
+        check_gas_deposit(transaction_sender, txn_gas_price, txn_max_gas_units);
+    }
+
     fun writeset_prologue(
         _sender: signer,
         _txn_sequence_number: u64,
@@ -437,6 +497,43 @@ module aptos_framework::account {
         account_resource.sequence_number = old_sequence_number + 1;
     }
 
+    /// Creates the account of an implicit sender before its transaction executes. Called by the VM with
+    /// the gas meter of the transaction, and again before the epilogue if the transaction failed, so
+    /// that the sender pays for its account.
+    fun create_implicit_sender_account(sender: signer) {
+        create_account_unchecked(signer::address_of(&sender));
+    }
+
+    /// Epilogue of orderless transactions. It charges the fee like `epilogue` but records the nonce
+    /// of the transaction instead of incrementing the sequence number of the sender.
+    fun orderless_epilogue(
+        account: signer,
+        txn_nonce: u64,
+        txn_expiration_time: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64
+    ) {
+        assert!(txn_max_gas_units >= gas_units_remaining, error::invalid_argument(EOUT_OF_GAS));
+        let gas_used = txn_max_gas_units - gas_units_remaining;
+
+        assert!(
+            (txn_gas_price as u128) * (gas_used as u128) <= MAX_U64,
+            error::out_of_range(EOUT_OF_GAS)
+        );
+        let transaction_fee_amount = txn_gas_price * gas_used;
+        let addr = signer::address_of(&account);
+        // it's important to maintain the error code consistent with vm
+        // to do failed transaction cleanup.
+        assert!(
+            coin::balance<AptosCoin>(addr) >= transaction_fee_amount,
+            error::out_of_range(PROLOGUE_ECANT_PAY_GAS_DEPOSIT),
+        );
+        transaction_fee::burn_fee(addr, transaction_fee_amount);
+
+        nonce_validation::record_nonce(addr, txn_nonce, txn_expiration_time);
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     /// Basic account creation methods.
     ///////////////////////////////////////////////////////////////////////////
@@ -470,11 +567,27 @@ module aptos_framework::account {
 
     public entry fun transfer(source: &signer, to: address, amount: u64) acquires Account {
         if(!exists<Account>(to)) {
-            create_account(to)
+            if (features::is_enabled(features::implicit_accounts())) {
+                create_implicit_account(to)
+            } else {
+                create_account(to)
+            }
         };
         coin::transfer<AptosCoin>(source, to, amount)
     }
 
+    /// Only publishes an AptosCoin store at `addr`, so that it can receive coins. The account itself is
+    /// created by the first transaction `addr` sends, which pays for it.
+    fun create_implicit_account(addr: address) {
+        assert!(
+            addr != @vm_reserved && addr != @aptos_framework,
+            error::invalid_argument(ECANNOT_RESERVED_ADDRESS)
+        );
+        if (!coin::is_account_registered<AptosCoin>(addr)) {
+            coin::register<AptosCoin>(&create_signer(addr));
+        }
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     /// Coin management methods.
     ///////////////////////////////////////////////////////////////////////////
@@ -588,6 +701,28 @@ module aptos_framework::account {
         let _bob = bob;
     }
 
+    #[test(core = @0x1)]
+    public fun test_implicit_account(core: signer) acquires Account {
+        let bob = create_address(x"0000000000000000000000000000000000000000000000000000000000000b0b");
+
+        let (burn_cap, mint_cap) = aptos_framework::aptos_coin::initialize_for_test(&core);
+        create_implicit_account(bob);
+        assert!(!exists_at(bob), 0);
+        coin::deposit(bob, coin::mint(500, &mint_cap));
+        assert!(coin::balance<AptosCoin>(bob) == 500, 1);
+        // Coins can keep coming in before the account exists.
+        create_implicit_account(bob);
+
+        // As done by the VM for the first transaction of bob.
+        create_implicit_sender_account(create_signer(bob));
+        assert!(get_sequence_number(bob) == 0, 2);
+        assert!(get_authentication_key(bob) == bcs::to_bytes(&bob), 3);
+        assert!(coin::balance<AptosCoin>(bob) == 500, 4);
+
+        coin::destroy_burn_cap(burn_cap);
+        coin::destroy_mint_cap(mint_cap);
+    }
+
     #[test(alice = @0xa11ce)]
     #[expected_failure(abort_code = 65537)]
     public entry fun test_empty_public_key(alice: signer) acquires Account, OriginatingAddress {
@@ -33,8 +33,12 @@ module aptos_framework::aggregator_factory {
     use aptos_std::aggregator::Aggregator;
     use aptos_std::table::{Self, Table};
 
+    use aptos_framework::aggregator_v2;
+
     #[test_only]
     friend aptos_framework::aggregator_tests;
+    #[test_only]
+    friend aptos_framework::aggregator_v2_tests;
 
     // TODO: only certain modules are allowed to create a aggregator.
     friend aptos_framework::optional_aggregator;
@@ -77,5 +81,19 @@ module aptos_framework::aggregator_factory {
         new_aggregator(aggregator_factory, limit)
     }
 
+    /// Creates a new `u64` or `u128` aggregator instance which overflows on
+    /// exceeding `max_value`.
+    public(friend) fun create_aggregator_v2<IntElement>(
+        max_value: IntElement
+    ): aggregator_v2::Aggregator<IntElement> acquires AggregatorFactory {
+        let aggregator_factory = borrow_global_mut<AggregatorFactory>(@aptos_framework);
+        new_aggregator_v2(aggregator_factory, max_value)
+    }
+
     native fun new_aggregator(aggregator_factory: &mut AggregatorFactory, limit: u128): Aggregator;
+
+    native fun new_aggregator_v2<IntElement>(
+        aggregator_factory: &mut AggregatorFactory,
+        max_value: IntElement
+    ): aggregator_v2::Aggregator<IntElement>;
 }
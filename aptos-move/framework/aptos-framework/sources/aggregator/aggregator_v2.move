@@ -0,0 +1,105 @@
+/// This module provides the second version of aggregators: aggregatable integers
+/// of type `u64` or `u128`, and snapshots of their values.
+///
+/// Design rationale (V2)
+/// =====================
+/// Like in V1, additions are speculative and cheap, and are resolved as deltas
+/// when the transaction output is committed. V2 generalizes aggregators over
+/// their element type, and adds snapshots, which let a transaction use the value
+/// of an aggregator without reading (materializing) it.
+///
+/// snapshot(): AggregatorSnapshot
+///   Takes a snapshot of the current value of an aggregator. If the value is not
+///   known, the snapshot holds the delta of the aggregator, and its value is
+///   resolved together with the deltas, so that taking a snapshot does not
+///   serialize transactions.
+///
+/// derive_string_concat(prefix, snapshot, suffix): DerivedStringSnapshot
+///   Derives a string from the value of a snapshot, e.g. the name of a token
+///   with its index in a collection: "Token #" ++ index ++ "". The string is
+///   materialized along with the snapshot.
+///
+/// Reading a snapshot (`read_snapshot` or `read_derived_string`) taken in the
+/// same transaction materializes its value, and is as expensive as reading the
+/// aggregator.
+///
+/// Aggregators are created by `AggregatorFactory`, see `aggregator_factory.move`.
+/// Snapshots are stored next to their aggregator, and should be destroyed by the
+/// user once they are no longer in use.
+module aptos_framework::aggregator_v2 {
+    use std::string::String;
+
+    /// When the value of aggregator (actual or accumulated) overflows (raised
+    /// by native code).
+    const EAGGREGATOR_OVERFLOW: u64 = 1;
+
+    /// When the value of aggregator (actual or accumulated) underflows, i.e goes
+    /// below zero (raised by native code).
+    const EAGGREGATOR_UNDERFLOW: u64 = 2;
+
+    /// When aggregator feature is not supported (raised by native code).
+    const ENOT_SUPPORTED: u64 = 3;
+
+    /// When the element type of an aggregator or snapshot is neither `u64` nor
+    /// `u128` (raised by native code).
+    const EUNSUPPORTED_AGGREGATOR_TYPE: u64 = 4;
+
+    /// An aggregator of `u64` or `u128` values, which overflows on exceeding
+    /// `max_value`.
+    struct Aggregator<IntElement> has store {
+        handle: u128,
+        key: u128,
+        max_value: IntElement,
+    }
+
+    /// The value of an aggregator at the time the snapshot was taken.
+    struct AggregatorSnapshot<phantom Element> has store {
+        handle: u128,
+        key: u128,
+    }
+
+    /// A string derived from the value of a snapshot.
+    struct DerivedStringSnapshot has store {
+        handle: u128,
+        key: u128,
+    }
+
+    /// Returns `max_value` exceeding which aggregator overflows.
+    public fun max_value<IntElement: copy + drop>(aggregator: &Aggregator<IntElement>): IntElement {
+        aggregator.max_value
+    }
+
+    /// Adds `value` to aggregator. Aborts on overflowing `max_value`.
+    public native fun add<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement);
+
+    /// Subtracts `value` from aggregator. Aborts on going below zero.
+    public native fun sub<IntElement>(aggregator: &mut Aggregator<IntElement>, value: IntElement);
+
+    /// Returns a value stored in this aggregator.
+    public native fun read<IntElement>(aggregator: &Aggregator<IntElement>): IntElement;
+
+    /// Destroys an aggregator and removes it from its `AggregatorFactory`.
+    public native fun destroy<IntElement>(aggregator: Aggregator<IntElement>);
+
+    /// Takes a snapshot of the current value of aggregator.
+    public native fun snapshot<IntElement>(aggregator: &Aggregator<IntElement>): AggregatorSnapshot<IntElement>;
+
+    /// Returns the value of a snapshot.
+    public native fun read_snapshot<IntElement>(snapshot: &AggregatorSnapshot<IntElement>): IntElement;
+
+    /// Derives the string `prefix` ++ the decimal value of `snapshot` ++ `suffix`.
+    public native fun derive_string_concat<IntElement>(
+        prefix: String,
+        snapshot: &AggregatorSnapshot<IntElement>,
+        suffix: String
+    ): DerivedStringSnapshot;
+
+    /// Returns the value of a derived string.
+    public native fun read_derived_string(snapshot: &DerivedStringSnapshot): String;
+
+    /// Destroys a snapshot and removes it from storage.
+    public native fun destroy_snapshot<IntElement>(snapshot: AggregatorSnapshot<IntElement>);
+
+    /// Destroys a derived string and removes it from storage.
+    public native fun destroy_derived_string(snapshot: DerivedStringSnapshot);
+}
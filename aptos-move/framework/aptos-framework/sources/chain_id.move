@@ -6,6 +6,8 @@ module aptos_framework::chain_id {
     use aptos_framework::timestamp;
 
     friend aptos_framework::genesis;
+    #[test_only]
+    friend aptos_framework::test_env;
 
     struct ChainId has key {
         id: u8
@@ -23,4 +25,15 @@ module aptos_framework::chain_id {
         timestamp::assert_operating();
         borrow_global<ChainId>(@aptos_framework).id
     }
+
+    #[test_only]
+    /// Sets the chain ID, publishing it if needed.
+    public(friend) fun set_for_testing(aptos_framework: &signer, id: u8) acquires ChainId {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        if (exists<ChainId>(@aptos_framework)) {
+            borrow_global_mut<ChainId>(@aptos_framework).id = id;
+        } else {
+            move_to(aptos_framework, ChainId { id });
+        }
+    }
 }
@@ -0,0 +1,203 @@
+/// Maintains the feature flags of the VM and natives, so that new behaviors can be turned on (and off) by governance
+/// instead of with a release.
+///
+/// The features are a bitset, where feature `i` is bit `i % 8` of byte `i / 8`. A change of a feature takes effect from
+/// an epoch on, so that every validator switches at the same point: until the epoch of its activation, a changed
+/// feature keeps its previous state.
+module aptos_framework::features {
+    use std::error;
+    use std::vector;
+
+    use aptos_framework::reconfiguration;
+    use aptos_framework::system_addresses;
+
+    friend aptos_framework::genesis;
+
+    /// Whether accounts can be authenticated with WebAuthn assertions, e.g. passkeys.
+    const WEBAUTHN_AUTHENTICATOR: u64 = 1;
+    public fun webauthn_authenticator(): u64 { WEBAUTHN_AUTHENTICATOR }
+
+    /// Whether accounts can be authenticated with OpenID Connect sign-ins.
+    const KEYLESS_AUTHENTICATOR: u64 = 2;
+    public fun keyless_authenticator(): u64 { KEYLESS_AUTHENTICATOR }
+
+    /// Whether accounts can be authenticated with K-of-N signatures of mixed schemes.
+    const MULTI_KEY_AUTHENTICATOR: u64 = 3;
+    public fun multi_key_authenticator(): u64 { MULTI_KEY_AUTHENTICATOR }
+
+    /// Whether the natives of `aptos_std::secp256r1` can be called.
+    const SECP256R1_NATIVES: u64 = 4;
+    public fun secp256r1_natives(): u64 { SECP256R1_NATIVES }
+
+    /// Whether the natives of `aptos_std::ristretto255` can be called.
+    const RISTRETTO255_NATIVES: u64 = 5;
+    public fun ristretto255_natives(): u64 { RISTRETTO255_NATIVES }
+
+    /// Whether the natives of `aptos_std::streaming_hash` can be called.
+    const STREAMING_HASH_NATIVES: u64 = 6;
+    public fun streaming_hash_natives(): u64 { STREAMING_HASH_NATIVES }
+
+    /// Whether the natives of `aptos_std::merkle` can be called.
+    const MERKLE_PROOF_NATIVES: u64 = 7;
+    public fun merkle_proof_natives(): u64 { MERKLE_PROOF_NATIVES }
+
+    /// Whether the natives of `aptos_std::light_client` can be called.
+    const LIGHT_CLIENT_NATIVES: u64 = 8;
+    public fun light_client_natives(): u64 { LIGHT_CLIENT_NATIVES }
+
+    /// Whether the natives of `aptos_std::poseidon` can be called.
+    const POSEIDON_NATIVES: u64 = 9;
+    public fun poseidon_natives(): u64 { POSEIDON_NATIVES }
+
+    /// Whether transactions may use nonces instead of sequence numbers for replay protection,
+    /// which lets a sender submit them in any order.
+    /// Lifetime: transient
+    const ORDERLESS_TRANSACTIONS: u64 = 10;
+    public fun orderless_transactions(): u64 { ORDERLESS_TRANSACTIONS }
+
+    /// Whether a transfer of AptosCoin to an address without account only publishes a coin store there,
+    /// the account being created by the first transaction sent from the address.
+    /// Lifetime: transient
+    const IMPLICIT_ACCOUNTS: u64 = 11;
+    public fun implicit_accounts(): u64 { IMPLICIT_ACCOUNTS }
+
+    /// The activation epoch of a change must be after the current epoch.
+    const EINVALID_ACTIVATION_EPOCH: u64 = 1;
+
+    /// A change of a feature, which takes effect from `epoch` on.
+    struct FeatureActivation has copy, drop, store {
+        feature: u64,
+        epoch: u64,
+    }
+
+    /// The bit of a pending feature is already changed, but the feature keeps its previous state until the epoch of
+    /// its activation.
+    struct Features has key {
+        features: vector<u8>,
+        activations: vector<FeatureActivation>,
+    }
+
+    /// Only called during genesis.
+    /// Publishes the Features config, with the given features enabled from the start.
+    public(friend) fun initialize(aptos_framework: &signer, enabled: vector<u64>) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+
+        let features = Features { features: vector::empty(), activations: vector::empty() };
+        let i = 0;
+        while (i < vector::length(&enabled)) {
+            set_bit(&mut features.features, *vector::borrow(&enabled, i), true);
+            i = i + 1;
+        };
+        move_to(aptos_framework, features);
+    }
+
+    /// Enables and disables features from `activation_epoch` on. This can only be called by on-chain governance.
+    ///
+    /// A change replaces any pending change of the same feature.
+    public fun change_feature_flags(
+        aptos_framework: &signer,
+        enable: vector<u64>,
+        disable: vector<u64>,
+        activation_epoch: u64,
+    ) acquires Features {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        let current_epoch = reconfiguration::current_epoch();
+        assert!(activation_epoch > current_epoch, error::invalid_argument(EINVALID_ACTIVATION_EPOCH));
+
+        let features = borrow_global_mut<Features>(@aptos_framework);
+        apply_changes(features, &enable, true, current_epoch, activation_epoch);
+        apply_changes(features, &disable, false, current_epoch, activation_epoch);
+    }
+
+    /// Whether `feature` is enabled in the current epoch.
+    public fun is_enabled(feature: u64): bool acquires Features {
+        exists<Features>(@aptos_framework) &&
+            is_enabled_at(borrow_global<Features>(@aptos_framework), feature, reconfiguration::current_epoch())
+    }
+
+    fun is_enabled_at(features: &Features, feature: u64, epoch: u64): bool {
+        let bit = get_bit(&features.features, feature);
+        let (pending, i) = find_activation(features, feature);
+        if (pending && epoch < vector::borrow(&features.activations, i).epoch) {
+            !bit
+        } else {
+            bit
+        }
+    }
+
+    fun apply_changes(
+        features: &mut Features,
+        changed: &vector<u64>,
+        enable: bool,
+        current_epoch: u64,
+        activation_epoch: u64,
+    ) {
+        let i = 0;
+        while (i < vector::length(changed)) {
+            let feature = *vector::borrow(changed, i);
+            let current = is_enabled_at(features, feature, current_epoch);
+            let (pending, j) = find_activation(features, feature);
+            if (pending) {
+                vector::swap_remove(&mut features.activations, j);
+            };
+            set_bit(&mut features.features, feature, enable);
+            if (enable != current) {
+                vector::push_back(&mut features.activations, FeatureActivation { feature, epoch: activation_epoch });
+            };
+            i = i + 1;
+        };
+    }
+
+    fun find_activation(features: &Features, feature: u64): (bool, u64) {
+        let i = 0;
+        while (i < vector::length(&features.activations)) {
+            if (vector::borrow(&features.activations, i).feature == feature) {
+                return (true, i)
+            };
+            i = i + 1;
+        };
+        (false, 0)
+    }
+
+    fun get_bit(features: &vector<u8>, feature: u64): bool {
+        let byte_index = feature / 8;
+        byte_index < vector::length(features) &&
+            (*vector::borrow(features, byte_index) & (1u8 << ((feature % 8) as u8))) != 0
+    }
+
+    fun set_bit(features: &mut vector<u8>, feature: u64, enable: bool) {
+        let byte_index = feature / 8;
+        while (vector::length(features) <= byte_index) {
+            vector::push_back(features, 0);
+        };
+        let byte = vector::borrow_mut(features, byte_index);
+        let bit_mask = 1u8 << ((feature % 8) as u8);
+        if (enable) {
+            *byte = *byte | bit_mask;
+        } else {
+            *byte = *byte & (0xff ^ bit_mask);
+        }
+    }
+
+    #[test]
+    fun test_change_feature_flags() {
+        let features = Features { features: vector::empty(), activations: vector::empty() };
+        set_bit(&mut features.features, WEBAUTHN_AUTHENTICATOR, true);
+
+        // In epoch 1, keyless is enabled and WebAuthn disabled from epoch 3 on
+        apply_changes(&mut features, &vector[KEYLESS_AUTHENTICATOR], true, 1, 3);
+        apply_changes(&mut features, &vector[WEBAUTHN_AUTHENTICATOR], false, 1, 3);
+        assert!(!is_enabled_at(&features, KEYLESS_AUTHENTICATOR, 2), 0);
+        assert!(is_enabled_at(&features, WEBAUTHN_AUTHENTICATOR, 2), 1);
+        assert!(is_enabled_at(&features, KEYLESS_AUTHENTICATOR, 3), 2);
+        assert!(!is_enabled_at(&features, WEBAUTHN_AUTHENTICATOR, 3), 3);
+        assert!(!is_enabled_at(&features, SECP256R1_NATIVES, 3), 4);
+
+        // In epoch 2, the pending change of WebAuthn is reverted, which leaves nothing pending for it
+        apply_changes(&mut features, &vector[WEBAUTHN_AUTHENTICATOR], true, 2, 4);
+        assert!(is_enabled_at(&features, WEBAUTHN_AUTHENTICATOR, 3), 5);
+        assert!(vector::length(&features.activations) == 1, 6);
+
+        let Features { features: _, activations: _ } = features;
+    }
+}
@@ -6,18 +6,36 @@ module aptos_framework::genesis {
     use aptos_framework::aptos_governance;
     use aptos_framework::block;
     use aptos_framework::chain_id;
-    use aptos_framework::coin::MintCapability;
+    use aptos_framework::coin::{Self, MintCapability};
     use aptos_framework::coins;
     use aptos_framework::consensus_config;
+    use aptos_framework::features;
     use aptos_framework::gas_schedule;
+    use aptos_framework::nonce_validation;
     use aptos_framework::reconfiguration;
     use aptos_framework::stake;
     use aptos_framework::timestamp;
     use aptos_framework::transaction_fee;
+    use aptos_framework::validator_txn;
     use aptos_framework::staking_config;
     use aptos_framework::version;
+    use aptos_framework::vesting;
     use aptos_framework::state_storage;
 
+    struct AccountMap has drop {
+        account_address: address,
+        balance: u64,
+    }
+
+    struct VestingAccountMap has drop {
+        account_address: address,
+        total: u64,
+        schedule_numerators: vector<u64>,
+        schedule_denominator: u64,
+        start_timestamp_secs: u64,
+        period_duration_secs: u64,
+    }
+
     struct ValidatorConfiguration has copy, drop {
         owner_address: address,
         operator_address: address,
@@ -88,9 +106,16 @@ module aptos_framework::genesis {
         reconfiguration::initialize(&aptos_framework_account);
         block::initialize(&aptos_framework_account, epoch_interval_microsecs);
         state_storage::initialize(&aptos_framework_account);
+        nonce_validation::initialize(&aptos_framework_account);
+        validator_txn::initialize(&aptos_framework_account);
         timestamp::set_time_has_started(&aptos_framework_account);
     }
 
+    /// Genesis step 1.5: Enable the initial features.
+    fun initialize_features(aptos_framework: &signer, feature_flags: vector<u64>) {
+        features::initialize(aptos_framework, feature_flags);
+    }
+
     /// Genesis step 2: Initialize Aptos coin.
     fun initialize_aptos_coin(aptos_framework: &signer): MintCapability<AptosCoin> {
         let (burn_cap, mint_cap) = aptos_coin::initialize(aptos_framework);
@@ -114,6 +139,47 @@ module aptos_framework::genesis {
         aptos_coin::configure_accounts_for_test(aptos_framework, &core_resources, mint_cap);
     }
 
+    /// Creates accounts with an initial balance. Must be called before the validators are created, which destroys the
+    /// aptos framework account's ability to mint coins.
+    fun create_accounts(aptos_framework: &signer, accounts: vector<AccountMap>) {
+        let i = 0;
+        let num_accounts = vector::length(&accounts);
+        while (i < num_accounts) {
+            let account_map = vector::borrow(&accounts, i);
+            let account = &account::create_account_internal(account_map.account_address);
+            coins::register<AptosCoin>(account);
+            aptos_coin::mint(aptos_framework, account_map.account_address, account_map.balance);
+
+            i = i + 1;
+        };
+    }
+
+    /// Creates accounts whose coins are held by a vesting contract, and released over time according to their
+    /// schedule. Must be called before the validators are created, which destroys the aptos framework account's
+    /// ability to mint coins.
+    fun create_vesting_accounts(aptos_framework: &signer, accounts: vector<VestingAccountMap>) {
+        let i = 0;
+        let num_accounts = vector::length(&accounts);
+        while (i < num_accounts) {
+            let account_map = vector::borrow(&accounts, i);
+            let account = &account::create_account_internal(account_map.account_address);
+            coins::register<AptosCoin>(account);
+            // The coins can only be minted to a balance, from which they are moved to the vesting contract
+            aptos_coin::mint(aptos_framework, account_map.account_address, account_map.total);
+            let coins = coin::withdraw<AptosCoin>(account, account_map.total);
+            vesting::create_vesting_contract(
+                account,
+                coins,
+                account_map.schedule_numerators,
+                account_map.schedule_denominator,
+                account_map.start_timestamp_secs,
+                account_map.period_duration_secs,
+            );
+
+            i = i + 1;
+        };
+    }
+
     /// Sets up the initial validator set for the network.
     /// The validator "owner" accounts, and their authentication
     /// Addresses (and keys) are encoded in the `owners`
@@ -201,4 +267,24 @@ module aptos_framework::genesis {
         setup();
         assert!(account::exists_at(@aptos_framework), 0);
     }
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_create_accounts(aptos_framework: signer) {
+        setup();
+        let mint_cap = initialize_aptos_coin(&aptos_framework);
+        coin::destroy_mint_cap(mint_cap);
+
+        create_accounts(&aptos_framework, vector[AccountMap { account_address: @0x123, balance: 100 }]);
+        create_vesting_accounts(&aptos_framework, vector[VestingAccountMap {
+            account_address: @0x456,
+            total: 500,
+            schedule_numerators: vector[1],
+            schedule_denominator: 5,
+            start_timestamp_secs: 0,
+            period_duration_secs: 3600,
+        }]);
+        assert!(coin::balance<AptosCoin>(@0x123) == 100, 0);
+        assert!(coin::balance<AptosCoin>(@0x456) == 0, 1);
+        assert!(vesting::remaining(@0x456) == 500, 2);
+    }
 }
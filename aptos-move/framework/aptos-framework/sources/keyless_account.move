@@ -0,0 +1,306 @@
+/// Configures keyless accounts, whose transactions are authorized by signing in with an OpenID
+/// Connect provider, such as Google or Apple, instead of with a long-lived private key.
+///
+/// A keyless signature carries a Groth16 proof, over BN254, that the user holds a JWT issued by
+/// one of the issuers below and signed with one of its JSON Web Keys (JWKs). The VM verifies the
+/// proof against the verification key published here, so keyless transactions are rejected until
+/// both the `Configuration` and the `Groth16VerificationKey` are published by governance.
+///
+/// Governance chooses the issuers, but their JWKs are kept up to date by the validators: they
+/// fetch the JWKs of each issuer, and once a quorum agrees on a change, the update is published
+/// through a validator transaction and applied by `upsert_observed_jwks`.
+module aptos_framework::keyless_account {
+    use std::error;
+    use std::string::{Self, String};
+    use std::vector;
+    use aptos_std::simple_map::{Self, SimpleMap};
+
+    use aptos_framework::system_addresses;
+
+    /// A point of the verification key does not have the length of a compressed point
+    const EINVALID_POINT_LENGTH: u64 = 1;
+    /// The verification key is not for a circuit with exactly one public input
+    const EINVALID_NUM_PUBLIC_INPUTS: u64 = 2;
+    /// No issuer with this `iss` is configured
+    const EISSUER_NOT_FOUND: u64 = 3;
+    /// The observed JWK update is not for the next version of the issuer's JWKs
+    const EUNEXPECTED_JWK_VERSION: u64 = 4;
+    /// The lists of JWK fields of the observed JWK update have different lengths
+    const EJWK_FIELDS_MISMATCH: u64 = 5;
+
+    /// The length of a compressed point of G1 of BN254
+    const G1_COMPRESSED_LENGTH: u64 = 32;
+    /// The length of a compressed point of G2 of BN254
+    const G2_COMPRESSED_LENGTH: u64 = 64;
+
+    /// An RSA JSON Web Key, with which an issuer signs JWTs. `e` and `n` are base64url-encoded.
+    struct RSA_JWK has copy, drop, store {
+        kid: String,
+        alg: String,
+        e: String,
+        n: String,
+    }
+
+    /// An OpenID Connect provider, and the keys it currently signs JWTs with
+    struct Issuer has copy, drop, store {
+        iss: String,
+        jwks: vector<RSA_JWK>,
+    }
+
+    struct Configuration has key, copy, drop {
+        issuers: vector<Issuer>,
+        /// How long after a JWT is issued the ephemeral key it commits to may expire
+        max_exp_horizon_secs: u64,
+        max_jwt_header_bytes: u64,
+    }
+
+    /// The version of the JWKs of each issuer updated by the validators. An update must be for the
+    /// next version, so that it only applies once.
+    struct ObservedJWKVersions has key {
+        versions: SimpleMap<String, u64>,
+    }
+
+    /// The verification key of the keyless circuit, with compressed points
+    struct Groth16VerificationKey has key, copy, drop {
+        alpha_g1: vector<u8>,
+        beta_g2: vector<u8>,
+        gamma_g2: vector<u8>,
+        delta_g2: vector<u8>,
+        gamma_abc_g1: vector<vector<u8>>,
+    }
+
+    public fun new_rsa_jwk(kid: String, alg: String, e: String, n: String): RSA_JWK {
+        RSA_JWK { kid, alg, e, n }
+    }
+
+    public fun new_issuer(iss: String, jwks: vector<RSA_JWK>): Issuer {
+        Issuer { iss, jwks }
+    }
+
+    public fun new_groth16_verification_key(
+        alpha_g1: vector<u8>,
+        beta_g2: vector<u8>,
+        gamma_g2: vector<u8>,
+        delta_g2: vector<u8>,
+        gamma_abc_g1: vector<vector<u8>>,
+    ): Groth16VerificationKey {
+        assert!(vector::length(&alpha_g1) == G1_COMPRESSED_LENGTH, error::invalid_argument(EINVALID_POINT_LENGTH));
+        assert!(vector::length(&beta_g2) == G2_COMPRESSED_LENGTH, error::invalid_argument(EINVALID_POINT_LENGTH));
+        assert!(vector::length(&gamma_g2) == G2_COMPRESSED_LENGTH, error::invalid_argument(EINVALID_POINT_LENGTH));
+        assert!(vector::length(&delta_g2) == G2_COMPRESSED_LENGTH, error::invalid_argument(EINVALID_POINT_LENGTH));
+        assert!(vector::length(&gamma_abc_g1) == 2, error::invalid_argument(EINVALID_NUM_PUBLIC_INPUTS));
+        let i = 0;
+        while (i < 2) {
+            assert!(
+                vector::length(vector::borrow(&gamma_abc_g1, i)) == G1_COMPRESSED_LENGTH,
+                error::invalid_argument(EINVALID_POINT_LENGTH)
+            );
+            i = i + 1;
+        };
+        Groth16VerificationKey { alpha_g1, beta_g2, gamma_g2, delta_g2, gamma_abc_g1 }
+    }
+
+    /// This can be called by on-chain governance to set the verification key of the circuit.
+    public fun set_groth16_verification_key(
+        aptos_framework: &signer,
+        vk: Groth16VerificationKey,
+    ) acquires Groth16VerificationKey {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        if (exists<Groth16VerificationKey>(@aptos_framework)) {
+            *borrow_global_mut<Groth16VerificationKey>(@aptos_framework) = vk;
+        } else {
+            move_to(aptos_framework, vk);
+        }
+    }
+
+    /// This can be called by on-chain governance to set the limits on keyless signatures.
+    public fun set_limits(
+        aptos_framework: &signer,
+        max_exp_horizon_secs: u64,
+        max_jwt_header_bytes: u64,
+    ) acquires Configuration {
+        let config = borrow_or_create_configuration(aptos_framework);
+        config.max_exp_horizon_secs = max_exp_horizon_secs;
+        config.max_jwt_header_bytes = max_jwt_header_bytes;
+    }
+
+    /// This can be called by on-chain governance to add an issuer, or to replace the JWKs of an
+    /// issuer with the same `iss`.
+    public fun upsert_issuer(aptos_framework: &signer, issuer: Issuer) acquires Configuration {
+        let config = borrow_or_create_configuration(aptos_framework);
+        let (found, i) = index_of_issuer(&config.issuers, &issuer.iss);
+        if (found) {
+            *vector::borrow_mut(&mut config.issuers, i) = issuer;
+        } else {
+            vector::push_back(&mut config.issuers, issuer);
+        }
+    }
+
+    /// This can be called by on-chain governance to stop accepting JWTs from the issuer `iss`.
+    public fun remove_issuer(aptos_framework: &signer, iss: String) acquires Configuration {
+        let config = borrow_or_create_configuration(aptos_framework);
+        let (found, i) = index_of_issuer(&config.issuers, &iss);
+        assert!(found, error::not_found(EISSUER_NOT_FOUND));
+        vector::remove(&mut config.issuers, i);
+    }
+
+    /// The version of the JWKs of the issuer `iss` updated by the validators, 0 if never updated.
+    public fun observed_jwk_version(iss: String): u64 acquires ObservedJWKVersions {
+        if (!exists<ObservedJWKVersions>(@aptos_framework)) {
+            return 0
+        };
+        let versions = &borrow_global<ObservedJWKVersions>(@aptos_framework).versions;
+        if (simple_map::contains_key(versions, &iss)) {
+            *simple_map::borrow(versions, &iss)
+        } else {
+            0
+        }
+    }
+
+    /// Replaces the JWKs of the issuer `iss` with the ones a quorum of validators observed.
+    /// The runtime runs this for each observed JWK update, after verifying its quorum signature.
+    fun upsert_observed_jwks(
+        vm: signer,
+        iss: vector<u8>,
+        version: u64,
+        kids: vector<vector<u8>>,
+        algs: vector<vector<u8>>,
+        es: vector<vector<u8>>,
+        ns: vector<vector<u8>>,
+    ) acquires Configuration, ObservedJWKVersions {
+        // Operational constraint: can only be invoked by the VM.
+        system_addresses::assert_vm(&vm);
+        let num_jwks = vector::length(&kids);
+        assert!(
+            vector::length(&algs) == num_jwks && vector::length(&es) == num_jwks && vector::length(&ns) == num_jwks,
+            error::invalid_argument(EJWK_FIELDS_MISMATCH),
+        );
+
+        let iss = string::utf8(iss);
+        let config = borrow_global_mut<Configuration>(@aptos_framework);
+        let (found, i) = index_of_issuer(&config.issuers, &iss);
+        assert!(found, error::not_found(EISSUER_NOT_FOUND));
+        let versions = &mut borrow_global_mut<ObservedJWKVersions>(@aptos_framework).versions;
+        if (simple_map::contains_key(versions, &iss)) {
+            let current = simple_map::borrow_mut(versions, &iss);
+            assert!(version == *current + 1, error::invalid_argument(EUNEXPECTED_JWK_VERSION));
+            *current = version;
+        } else {
+            assert!(version == 1, error::invalid_argument(EUNEXPECTED_JWK_VERSION));
+            simple_map::add(versions, iss, version);
+        };
+
+        let jwks = vector::empty();
+        let j = 0;
+        while (j < num_jwks) {
+            vector::push_back(&mut jwks, RSA_JWK {
+                kid: string::utf8(*vector::borrow(&kids, j)),
+                alg: string::utf8(*vector::borrow(&algs, j)),
+                e: string::utf8(*vector::borrow(&es, j)),
+                n: string::utf8(*vector::borrow(&ns, j)),
+            });
+            j = j + 1;
+        };
+        vector::borrow_mut(&mut config.issuers, i).jwks = jwks;
+    }
+
+    fun borrow_or_create_configuration(aptos_framework: &signer): &mut Configuration acquires Configuration {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        if (!exists<Configuration>(@aptos_framework)) {
+            move_to(aptos_framework, Configuration {
+                issuers: vector::empty(),
+                max_exp_horizon_secs: 0,
+                max_jwt_header_bytes: 0,
+            });
+        };
+        // The validators can only update the JWKs of an issuer configured by governance.
+        if (!exists<ObservedJWKVersions>(@aptos_framework)) {
+            move_to(aptos_framework, ObservedJWKVersions { versions: simple_map::create() });
+        };
+        borrow_global_mut<Configuration>(@aptos_framework)
+    }
+
+    fun index_of_issuer(issuers: &vector<Issuer>, iss: &String): (bool, u64) {
+        let i = 0;
+        let len = vector::length(issuers);
+        while (i < len) {
+            if (&vector::borrow(issuers, i).iss == iss) {
+                return (true, i)
+            };
+            i = i + 1;
+        };
+        (false, 0)
+    }
+
+    #[test_only]
+    use std::string::utf8;
+
+    #[test(aptos_framework = @aptos_framework)]
+    public entry fun test_upsert_and_remove_issuers(aptos_framework: signer) acquires Configuration {
+        let jwk = new_rsa_jwk(utf8(b"kid"), utf8(b"RS256"), utf8(b"AQAB"), utf8(b"n"));
+        upsert_issuer(&aptos_framework, new_issuer(utf8(b"https://accounts.google.com"), vector[jwk]));
+        upsert_issuer(&aptos_framework, new_issuer(utf8(b"https://appleid.apple.com"), vector[jwk]));
+        upsert_issuer(&aptos_framework, new_issuer(utf8(b"https://accounts.google.com"), vector[]));
+
+        let config = borrow_global<Configuration>(@aptos_framework);
+        assert!(vector::length(&config.issuers) == 2, 0);
+        assert!(vector::is_empty(&vector::borrow(&config.issuers, 0).jwks), 1);
+
+        remove_issuer(&aptos_framework, utf8(b"https://accounts.google.com"));
+        let config = borrow_global<Configuration>(@aptos_framework);
+        assert!(vector::length(&config.issuers) == 1, 2);
+        assert!(vector::borrow(&config.issuers, 0).iss == utf8(b"https://appleid.apple.com"), 3);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x60003)]
+    public entry fun test_remove_unknown_issuer_should_fail(aptos_framework: signer) acquires Configuration {
+        remove_issuer(&aptos_framework, utf8(b"https://accounts.google.com"));
+    }
+
+    #[test(aptos_framework = @aptos_framework, vm = @vm_reserved)]
+    public entry fun test_upsert_observed_jwks(
+        aptos_framework: signer,
+        vm: signer,
+    ) acquires Configuration, ObservedJWKVersions {
+        let iss = utf8(b"https://accounts.google.com");
+        upsert_issuer(&aptos_framework, new_issuer(iss, vector[]));
+        assert!(observed_jwk_version(iss) == 0, 0);
+
+        upsert_observed_jwks(vm, b"https://accounts.google.com", 1, vector[b"kid"], vector[b"RS256"], vector[b"AQAB"], vector[b"n"]);
+        assert!(observed_jwk_version(iss) == 1, 1);
+        let config = borrow_global<Configuration>(@aptos_framework);
+        let jwks = &vector::borrow(&config.issuers, 0).jwks;
+        assert!(*jwks == vector[new_rsa_jwk(utf8(b"kid"), utf8(b"RS256"), utf8(b"AQAB"), utf8(b"n"))], 2);
+    }
+
+    #[test(aptos_framework = @aptos_framework, vm = @vm_reserved)]
+    #[expected_failure(abort_code = 0x10004)]
+    public entry fun test_upsert_observed_jwks_skipping_a_version_should_fail(
+        aptos_framework: signer,
+        vm: signer,
+    ) acquires Configuration, ObservedJWKVersions {
+        upsert_issuer(&aptos_framework, new_issuer(utf8(b"https://accounts.google.com"), vector[]));
+        upsert_observed_jwks(vm, b"https://accounts.google.com", 2, vector[], vector[], vector[], vector[]);
+    }
+
+    #[test(account = @0x123)]
+    #[expected_failure(abort_code = 0x50003)]
+    public entry fun test_set_limits_unauthorized_should_fail(account: signer) acquires Configuration {
+        set_limits(&account, 1, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10002)]
+    public entry fun test_verification_key_with_too_many_inputs_should_fail() {
+        let g1 = vector::empty<u8>();
+        let g2 = vector::empty<u8>();
+        let i = 0;
+        while (i < G2_COMPRESSED_LENGTH) {
+            if (i < G1_COMPRESSED_LENGTH) vector::push_back(&mut g1, 0);
+            vector::push_back(&mut g2, 0);
+            i = i + 1;
+        };
+        new_groth16_verification_key(g1, g2, g2, g2, vector[g1, g1, g1]);
+    }
+}
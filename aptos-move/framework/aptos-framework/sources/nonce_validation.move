@@ -0,0 +1,222 @@
+/// Replay protection of orderless transactions.
+///
+/// Instead of a sequence number, an orderless transaction carries a nonce chosen by its sender. A
+/// (sender, nonce) pair can only be used once until the transaction that used it expires. Since the
+/// expiration of an orderless transaction can be at most `MAX_EXPIRATION_WINDOW_SECS` away, the
+/// history of used nonces stays bounded: expired entries are pruned as new nonces get recorded.
+///
+/// The history is sharded into buckets by the hash of the (sender, nonce) pair, each pruned on its
+/// own, so that orderless transactions only write the state of one bucket. Transactions recording
+/// nonces in different buckets then don't conflict and can be executed in parallel.
+module aptos_framework::nonce_validation {
+    use std::error;
+    use std::vector;
+    use aptos_std::aptos_hash;
+    use aptos_std::table::{Self, Table};
+    use aptos_framework::system_addresses;
+    use aptos_framework::timestamp;
+
+    friend aptos_framework::account;
+
+    /// The nonce history was already initialized.
+    const ENONCE_HISTORY_ALREADY_EXISTS: u64 = 1;
+
+    /// How far in the future the expiration of an orderless transaction may be.
+    const MAX_EXPIRATION_WINDOW_SECS: u64 = 60;
+
+    /// How many seconds of expirations are pruned at most when recording a nonce. This is twice
+    /// the expiration window so that pruning catches up with time after a pause.
+    const MAX_PRUNED_SECS_PER_RECORD: u64 = 120;
+
+    /// The number of buckets the nonces are sharded into.
+    const NUM_BUCKETS: u64 = 4096;
+
+    struct NonceKey has copy, drop, store {
+        sender: address,
+        nonce: u64,
+    }
+
+    /// The nonces used by transactions which may not have expired yet. The resource itself is never
+    /// written once initialized, only the buckets, which are created as nonces fall into them.
+    struct NonceHistory has key {
+        buckets: Table<u64, Bucket>,
+    }
+
+    /// The nonces whose key hashes into a bucket.
+    struct Bucket has store {
+        /// The expiration of the transaction which used each nonce.
+        nonce_to_expiration: Table<NonceKey, u64>,
+        /// The nonces recorded for each expiration, to find what can be pruned.
+        expiration_to_nonces: Table<u64, vector<NonceKey>>,
+        /// The number of entries of `expiration_to_nonces`.
+        num_expirations: u64,
+        /// Every expiration before this one has been pruned.
+        next_expiration_to_prune: u64,
+    }
+
+    /// Publishes the nonce history. Called at genesis, or by governance on an existing chain before
+    /// orderless transactions get enabled.
+    public fun initialize(aptos_framework: &signer) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(
+            !exists<NonceHistory>(@aptos_framework),
+            error::already_exists(ENONCE_HISTORY_ALREADY_EXISTS)
+        );
+        move_to(aptos_framework, NonceHistory {
+            buckets: table::new(),
+        });
+    }
+
+    public fun is_initialized(): bool {
+        exists<NonceHistory>(@aptos_framework)
+    }
+
+    public fun max_expiration_window_secs(): u64 {
+        MAX_EXPIRATION_WINDOW_SECS
+    }
+
+    /// Whether `sender` can use `nonce`: it was never used, or the transaction which used it expired.
+    public(friend) fun is_nonce_available(sender: address, nonce: u64): bool acquires NonceHistory {
+        let history = borrow_global<NonceHistory>(@aptos_framework);
+        let key = NonceKey { sender, nonce };
+        let bucket_index = bucket_index(&key);
+        if (!table::contains(&history.buckets, bucket_index)) {
+            return true
+        };
+        let bucket = table::borrow(&history.buckets, bucket_index);
+        !table::contains(&bucket.nonce_to_expiration, key)
+            || *table::borrow(&bucket.nonce_to_expiration, key) <= timestamp::now_seconds()
+    }
+
+    /// Records that `sender` used `nonce` in a transaction expiring at `expiration`.
+    public(friend) fun record_nonce(sender: address, nonce: u64, expiration: u64) acquires NonceHistory {
+        let history = borrow_global_mut<NonceHistory>(@aptos_framework);
+        let key = NonceKey { sender, nonce };
+        let bucket_index = bucket_index(&key);
+        if (!table::contains(&history.buckets, bucket_index)) {
+            table::add(&mut history.buckets, bucket_index, Bucket {
+                nonce_to_expiration: table::new(),
+                expiration_to_nonces: table::new(),
+                num_expirations: 0,
+                next_expiration_to_prune: timestamp::now_seconds(),
+            });
+        };
+        let bucket = table::borrow_mut(&mut history.buckets, bucket_index);
+        prune_expired(bucket);
+
+        table::upsert(&mut bucket.nonce_to_expiration, key, expiration);
+        if (!table::contains(&bucket.expiration_to_nonces, expiration)) {
+            table::add(&mut bucket.expiration_to_nonces, expiration, vector::empty());
+            bucket.num_expirations = bucket.num_expirations + 1;
+        };
+        vector::push_back(table::borrow_mut(&mut bucket.expiration_to_nonces, expiration), key);
+    }
+
+    fun bucket_index(key: &NonceKey): u64 {
+        aptos_hash::sip_hash_from_value(key) % NUM_BUCKETS
+    }
+
+    /// Removes the nonces of `bucket` whose transactions have expired, a bounded number of seconds
+    /// at a time.
+    fun prune_expired(bucket: &mut Bucket) {
+        let now = timestamp::now_seconds();
+        if (bucket.num_expirations == 0) {
+            // Nothing to prune, skip the seconds in between.
+            bucket.next_expiration_to_prune = now;
+            return
+        };
+
+        let pruned_secs = 0;
+        while (bucket.next_expiration_to_prune <= now && pruned_secs < MAX_PRUNED_SECS_PER_RECORD) {
+            let expiration = bucket.next_expiration_to_prune;
+            if (table::contains(&bucket.expiration_to_nonces, expiration)) {
+                let keys = table::remove(&mut bucket.expiration_to_nonces, expiration);
+                bucket.num_expirations = bucket.num_expirations - 1;
+                let i = 0;
+                let len = vector::length(&keys);
+                while (i < len) {
+                    let key = *vector::borrow(&keys, i);
+                    // The nonce may have been used again since, by a transaction expiring later.
+                    if (table::contains(&bucket.nonce_to_expiration, key)
+                        && *table::borrow(&bucket.nonce_to_expiration, key) == expiration) {
+                        table::remove(&mut bucket.nonce_to_expiration, key);
+                    };
+                    i = i + 1;
+                };
+            };
+            bucket.next_expiration_to_prune = expiration + 1;
+            pruned_secs = pruned_secs + 1;
+        };
+    }
+
+    #[test_only]
+    fun setup(aptos_framework: &signer) {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        initialize(aptos_framework);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_nonce_reuse_after_expiration(aptos_framework: signer) acquires NonceHistory {
+        setup(&aptos_framework);
+        let now = timestamp::now_seconds();
+        assert!(is_nonce_available(@0xcafe, 1), 0);
+
+        record_nonce(@0xcafe, 1, now + 10);
+        assert!(!is_nonce_available(@0xcafe, 1), 1);
+        // Nonces are per sender.
+        assert!(is_nonce_available(@0xbeef, 1), 2);
+        assert!(is_nonce_available(@0xcafe, 2), 3);
+
+        timestamp::fast_forward_seconds(10);
+        assert!(is_nonce_available(@0xcafe, 1), 4);
+    }
+
+    #[test_only]
+    /// Another nonce of `sender` falling into the same bucket as `nonce`, since pruning is per bucket.
+    fun nonce_in_same_bucket(sender: address, nonce: u64): u64 {
+        let bucket = bucket_index(&NonceKey { sender, nonce });
+        let other = nonce + 1;
+        while (bucket_index(&NonceKey { sender, nonce: other }) != bucket) {
+            other = other + 1;
+        };
+        other
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_pruning(aptos_framework: signer) acquires NonceHistory {
+        setup(&aptos_framework);
+        let now = timestamp::now_seconds();
+        let other = nonce_in_same_bucket(@0xcafe, 1);
+        record_nonce(@0xcafe, 1, now + 5);
+        record_nonce(@0xcafe, other, now + 30);
+
+        timestamp::fast_forward_seconds(10);
+        let third = nonce_in_same_bucket(@0xcafe, other);
+        record_nonce(@0xcafe, third, now + 40);
+        let history = borrow_global<NonceHistory>(@aptos_framework);
+        let bucket = table::borrow(&history.buckets, bucket_index(&NonceKey { sender: @0xcafe, nonce: 1 }));
+        assert!(!table::contains(&bucket.nonce_to_expiration, NonceKey { sender: @0xcafe, nonce: 1 }), 0);
+        assert!(table::contains(&bucket.nonce_to_expiration, NonceKey { sender: @0xcafe, nonce: other }), 1);
+        assert!(bucket.num_expirations == 2, 2);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    fun test_pruning_keeps_reused_nonce(aptos_framework: signer) acquires NonceHistory {
+        setup(&aptos_framework);
+        let now = timestamp::now_seconds();
+        record_nonce(@0xcafe, 1, now + 5);
+        timestamp::fast_forward_seconds(5);
+        // The nonce expired and is used again, then its first expiration gets pruned.
+        record_nonce(@0xcafe, 1, now + 50);
+        timestamp::fast_forward_seconds(1);
+        record_nonce(@0xcafe, nonce_in_same_bucket(@0xcafe, 1), now + 50);
+        assert!(!is_nonce_available(@0xcafe, 1), 0);
+    }
+
+    #[test(aptos_framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x80001)]
+    fun test_initialize_twice(aptos_framework: signer) {
+        setup(&aptos_framework);
+        initialize(&aptos_framework);
+    }
+}
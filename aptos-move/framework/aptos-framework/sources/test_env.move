@@ -0,0 +1,61 @@
+#[test_only]
+/// Control over the environment of Move unit tests: the wall clock, the chain id and randomness.
+///
+/// The values are set through natives which are only registered when running unit tests, so tests
+/// of the framework and of dapps can set them deterministically, without any function in the
+/// released modules for doing so.
+module aptos_framework::test_env {
+    use aptos_framework::chain_id;
+    use aptos_framework::timestamp;
+
+    /// Sets the wall clock to `microseconds`, starting it if needed. Time can be moved backwards.
+    public fun set_time_microseconds(microseconds: u64) {
+        timestamp::set_time_microseconds(&create_framework_signer(), microseconds);
+    }
+
+    /// Sets the chain id returned by `chain_id::get`.
+    public fun set_chain_id(id: u8) {
+        chain_id::set_for_testing(&create_framework_signer(), id);
+    }
+
+    /// Seeds the numbers returned by `random_u64`. The same seed gives the same sequence of numbers.
+    public native fun set_randomness_seed(seed: vector<u8>);
+
+    /// Returns the next pseudo-random number of the sequence of the current seed.
+    public native fun random_u64(): u64;
+
+    native fun create_framework_signer(): signer;
+
+    #[test]
+    fun test_set_time() {
+        set_time_microseconds(5000000);
+        assert!(timestamp::now_seconds() == 5, 0);
+        set_time_microseconds(1000000);
+        assert!(timestamp::now_seconds() == 1, 1);
+    }
+
+    #[test]
+    fun test_set_chain_id() {
+        // Reading the chain id requires time to have started.
+        set_time_microseconds(0);
+        set_chain_id(4);
+        assert!(chain_id::get() == 4, 0);
+        set_chain_id(27);
+        assert!(chain_id::get() == 27, 1);
+    }
+
+    #[test]
+    fun test_randomness_is_deterministic() {
+        set_randomness_seed(b"seed");
+        let first = random_u64();
+        let second = random_u64();
+        assert!(first != second, 0);
+
+        set_randomness_seed(b"seed");
+        assert!(random_u64() == first, 1);
+        assert!(random_u64() == second, 2);
+
+        set_randomness_seed(b"other seed");
+        assert!(random_u64() != first, 3);
+    }
+}
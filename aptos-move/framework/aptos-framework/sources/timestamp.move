@@ -14,6 +14,8 @@ module aptos_framework::timestamp {
     use std::error;
 
     friend aptos_framework::genesis;
+    #[test_only]
+    friend aptos_framework::test_env;
 
     /// A singleton resource holding the current Unix time in microseconds
     struct CurrentTimeMicroseconds has key {
@@ -98,6 +100,20 @@ module aptos_framework::timestamp {
         global_timer.microseconds = timestamp_microsecs;
     }
 
+    #[test_only]
+    /// Sets the wall clock, starting it if needed. Unlike consensus, this can move time backwards.
+    public(friend) fun set_time_microseconds(
+        aptos_framework: &signer,
+        microseconds: u64
+    ) acquires CurrentTimeMicroseconds {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        if (is_genesis()) {
+            move_to(aptos_framework, CurrentTimeMicroseconds { microseconds });
+        } else {
+            borrow_global_mut<CurrentTimeMicroseconds>(@aptos_framework).microseconds = microseconds;
+        }
+    }
+
     #[test_only]
     public fun fast_forward_seconds(timestamp_seconds: u64) acquires CurrentTimeMicroseconds {
         update_global_time_for_test(now_microseconds() + timestamp_seconds * 1000000);
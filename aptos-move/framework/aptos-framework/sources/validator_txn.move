@@ -0,0 +1,119 @@
+/// Records the data validators agree on through validator transactions, e.g. the transcript of a
+/// distributed key generation, or JWKs and prices observed off chain.
+///
+/// Validator transactions are proposed in blocks and have no sender, signature or gas. Instead,
+/// each payload carries the signatures of a quorum of validators over its data, which the VM
+/// checks against the current validator set before executing the transaction by calling
+/// `process`, right after the block prologue. Only the latest payload of each kind is kept, so
+/// processing the same transaction again only refreshes its record.
+module aptos_framework::validator_txn {
+    use std::error;
+    use std::vector;
+    use aptos_std::event::{Self, EventHandle};
+    use aptos_std::table::{Self, Table};
+    use aptos_framework::reconfiguration;
+    use aptos_framework::system_addresses;
+    use aptos_framework::timestamp;
+
+    /// Should be in-sync with `ValidatorTransaction::kind` in validator_txn.rs
+    const KIND_DKG_TRANSCRIPT: u64 = 0;
+    const KIND_OBSERVED_JWK_UPDATE: u64 = 1;
+    const KIND_OBSERVED_PRICES: u64 = 2;
+
+    /// The kind of the validator transaction is unknown.
+    const EUNKNOWN_KIND: u64 = 1;
+    /// The validator transaction records were already initialized.
+    const ERECORDS_ALREADY_EXIST: u64 = 2;
+    /// No validator transaction of the given kind has been recorded.
+    const ENO_RECORD: u64 = 3;
+
+    /// The latest validator transaction of a kind.
+    struct Record has store, drop {
+        payload: vector<u8>,
+        /// The epoch in which the payload was recorded.
+        epoch: u64,
+        /// On-chain time when the payload was recorded.
+        timestamp_microseconds: u64,
+    }
+
+    struct ValidatorTxnRecords has key {
+        /// The latest record of each kind.
+        records: Table<u64, Record>,
+        /// Handle where an event is emitted for each processed validator transaction.
+        events: EventHandle<ValidatorTxnEvent>,
+    }
+
+    struct ValidatorTxnEvent has drop, store {
+        kind: u64,
+        epoch: u64,
+        payload_size: u64,
+    }
+
+    /// Publishes the records. Called at genesis, or by governance on an existing chain before
+    /// validator transactions get enabled in the consensus config.
+    public fun initialize(aptos_framework: &signer) {
+        system_addresses::assert_aptos_framework(aptos_framework);
+        assert!(
+            !exists<ValidatorTxnRecords>(@aptos_framework),
+            error::already_exists(ERECORDS_ALREADY_EXIST)
+        );
+        move_to(aptos_framework, ValidatorTxnRecords {
+            records: table::new(),
+            events: event::new_event_handle<ValidatorTxnEvent>(aptos_framework),
+        });
+    }
+
+    public fun is_initialized(): bool {
+        exists<ValidatorTxnRecords>(@aptos_framework)
+    }
+
+    /// Whether a validator transaction of `kind` has been recorded.
+    public fun has_record(kind: u64): bool acquires ValidatorTxnRecords {
+        is_initialized() && table::contains(&borrow_global<ValidatorTxnRecords>(@aptos_framework).records, kind)
+    }
+
+    /// Returns the payload of the latest validator transaction of `kind` and the epoch it was
+    /// recorded in.
+    public fun latest_payload(kind: u64): (vector<u8>, u64) acquires ValidatorTxnRecords {
+        assert!(has_record(kind), error::not_found(ENO_RECORD));
+        let record = table::borrow(&borrow_global<ValidatorTxnRecords>(@aptos_framework).records, kind);
+        (record.payload, record.epoch)
+    }
+
+    /// Records a validator transaction.
+    /// The runtime runs this for each validator transaction in a block, after the block prologue,
+    /// once it has verified that `payload` is certified by a quorum of the current validators.
+    fun process(vm: signer, kind: u64, payload: vector<u8>) acquires ValidatorTxnRecords {
+        // Operational constraint: can only be invoked by the VM.
+        system_addresses::assert_vm(&vm);
+        assert!(
+            kind == KIND_DKG_TRANSCRIPT || kind == KIND_OBSERVED_JWK_UPDATE || kind == KIND_OBSERVED_PRICES,
+            error::invalid_argument(EUNKNOWN_KIND),
+        );
+
+        let epoch = reconfiguration::current_epoch();
+        let payload_size = vector::length(&payload);
+        let validator_txn_records = borrow_global_mut<ValidatorTxnRecords>(@aptos_framework);
+        table::upsert(&mut validator_txn_records.records, kind, Record {
+            payload,
+            epoch,
+            timestamp_microseconds: timestamp::now_microseconds(),
+        });
+        event::emit_event(&mut validator_txn_records.events, ValidatorTxnEvent {
+            kind,
+            epoch,
+            payload_size,
+        });
+    }
+
+    #[test(vm = @vm_reserved)]
+    #[expected_failure(abort_code = 0x10001)]
+    fun test_unknown_kind(vm: signer) acquires ValidatorTxnRecords {
+        process(vm, 3, b"");
+    }
+
+    #[test]
+    fun test_no_record_before_initialize() acquires ValidatorTxnRecords {
+        assert!(!has_record(KIND_DKG_TRANSCRIPT), 0);
+    }
+}
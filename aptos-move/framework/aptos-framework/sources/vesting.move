@@ -0,0 +1,168 @@
+/// Vesting of coins allocated at genesis, e.g. to employees or investors.
+///
+/// The coins of a vesting contract are held by the contract, under the beneficiary's account, and are released to the
+/// beneficiary's balance over time: the i-th period after the start vests `schedule_numerators[i]` /
+/// `schedule_denominator` of the total, and once the schedule is exhausted its last fraction keeps vesting every
+/// period until all coins are vested. Vested coins are released by calling `vest`.
+module aptos_framework::vesting {
+    use std::error;
+    use std::signer;
+    use std::vector;
+
+    use aptos_framework::aptos_coin::AptosCoin;
+    use aptos_framework::coin::{Self, Coin};
+    use aptos_framework::timestamp;
+
+    friend aptos_framework::genesis;
+
+    /// The account already has a vesting contract
+    const EVESTING_CONTRACT_EXISTS: u64 = 1;
+    /// The vesting schedule is empty
+    const EEMPTY_VESTING_SCHEDULE: u64 = 2;
+    /// The last fraction of the vesting schedule is zero, so the coins would never be fully vested
+    const EZERO_LAST_VESTING_FRACTION: u64 = 3;
+    /// The denominator of the vesting schedule is zero
+    const EZERO_VESTING_SCHEDULE_DENOMINATOR: u64 = 4;
+    /// The vesting period is zero
+    const EZERO_VESTING_PERIOD: u64 = 5;
+    /// The account has no vesting contract
+    const EVESTING_CONTRACT_NOT_FOUND: u64 = 6;
+
+    struct VestingContract has key {
+        /// The coins which have not been released yet
+        balance: Coin<AptosCoin>,
+        /// The amount of coins the contract was created with
+        total: u64,
+        schedule_numerators: vector<u64>,
+        schedule_denominator: u64,
+        start_timestamp_secs: u64,
+        period_duration_secs: u64,
+    }
+
+    /// Creates a vesting contract for the coins in `coins`, under `beneficiary`'s account. Only called at genesis.
+    public(friend) fun create_vesting_contract(
+        beneficiary: &signer,
+        coins: Coin<AptosCoin>,
+        schedule_numerators: vector<u64>,
+        schedule_denominator: u64,
+        start_timestamp_secs: u64,
+        period_duration_secs: u64,
+    ) {
+        assert!(
+            !exists<VestingContract>(signer::address_of(beneficiary)),
+            error::already_exists(EVESTING_CONTRACT_EXISTS),
+        );
+        let num_periods = vector::length(&schedule_numerators);
+        assert!(num_periods > 0, error::invalid_argument(EEMPTY_VESTING_SCHEDULE));
+        assert!(
+            *vector::borrow(&schedule_numerators, num_periods - 1) > 0,
+            error::invalid_argument(EZERO_LAST_VESTING_FRACTION),
+        );
+        assert!(schedule_denominator > 0, error::invalid_argument(EZERO_VESTING_SCHEDULE_DENOMINATOR));
+        assert!(period_duration_secs > 0, error::invalid_argument(EZERO_VESTING_PERIOD));
+
+        move_to(beneficiary, VestingContract {
+            total: coin::value(&coins),
+            balance: coins,
+            schedule_numerators,
+            schedule_denominator,
+            start_timestamp_secs,
+            period_duration_secs,
+        });
+    }
+
+    /// Releases the coins which have vested since the last release to the beneficiary's balance.
+    public entry fun vest(beneficiary: &signer) acquires VestingContract {
+        let beneficiary_address = signer::address_of(beneficiary);
+        assert!(
+            exists<VestingContract>(beneficiary_address),
+            error::not_found(EVESTING_CONTRACT_NOT_FOUND),
+        );
+        let contract = borrow_global_mut<VestingContract>(beneficiary_address);
+        let released = contract.total - coin::value(&contract.balance);
+        let vested = vested_amount(contract, timestamp::now_seconds());
+        if (vested > released) {
+            let coins = coin::extract(&mut contract.balance, vested - released);
+            coin::deposit(beneficiary_address, coins);
+        };
+    }
+
+    /// Returns the amount of coins which have not been released yet from the vesting contract of `beneficiary`.
+    public fun remaining(beneficiary: address): u64 acquires VestingContract {
+        assert!(exists<VestingContract>(beneficiary), error::not_found(EVESTING_CONTRACT_NOT_FOUND));
+        coin::value(&borrow_global<VestingContract>(beneficiary).balance)
+    }
+
+    /// The amount of coins of `contract` which have vested by `now_secs`, released or not.
+    fun vested_amount(contract: &VestingContract, now_secs: u64): u64 {
+        if (now_secs < contract.start_timestamp_secs) {
+            return 0
+        };
+        let completed_periods = (now_secs - contract.start_timestamp_secs) / contract.period_duration_secs;
+        let num_periods = vector::length(&contract.schedule_numerators);
+        let vested_numerator: u128 = 0;
+        let i = 0;
+        while (i < completed_periods && i < num_periods) {
+            vested_numerator = vested_numerator + (*vector::borrow(&contract.schedule_numerators, i) as u128);
+            i = i + 1;
+        };
+        if (completed_periods > num_periods) {
+            let last_numerator = *vector::borrow(&contract.schedule_numerators, num_periods - 1);
+            vested_numerator = vested_numerator + ((completed_periods - num_periods) as u128) * (last_numerator as u128);
+        };
+
+        let denominator = (contract.schedule_denominator as u128);
+        if (vested_numerator >= denominator) {
+            contract.total
+        } else {
+            (((contract.total as u128) * vested_numerator / denominator) as u64)
+        }
+    }
+
+    #[test_only]
+    use aptos_framework::aptos_coin;
+
+    #[test_only]
+    fun setup(aptos_framework: &signer, beneficiary: &signer, total: u64) {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        let (burn_cap, mint_cap) = aptos_coin::initialize_for_test(aptos_framework);
+        coin::register_for_test<AptosCoin>(beneficiary);
+        let coins = coin::mint(total, &mint_cap);
+        // A quarter vests after a cliff of two periods, then an eighth every period
+        create_vesting_contract(beneficiary, coins, vector[0, 2, 1], 8, 100, 10);
+        coin::destroy_burn_cap(burn_cap);
+        coin::destroy_mint_cap(mint_cap);
+    }
+
+    #[test(aptos_framework = @aptos_framework, beneficiary = @0x123)]
+    public entry fun test_vesting(aptos_framework: signer, beneficiary: signer) acquires VestingContract {
+        let beneficiary_address = signer::address_of(&beneficiary);
+        setup(&aptos_framework, &beneficiary, 800);
+
+        // Nothing vests before the cliff
+        timestamp::update_global_time_for_test(119 * 1000000);
+        vest(&beneficiary);
+        assert!(coin::balance<AptosCoin>(beneficiary_address) == 0, 0);
+
+        timestamp::update_global_time_for_test(120 * 1000000);
+        vest(&beneficiary);
+        assert!(coin::balance<AptosCoin>(beneficiary_address) == 200, 1);
+        assert!(remaining(beneficiary_address) == 600, 2);
+
+        // The last fraction repeats until all coins are vested
+        timestamp::update_global_time_for_test(150 * 1000000);
+        vest(&beneficiary);
+        assert!(coin::balance<AptosCoin>(beneficiary_address) == 500, 3);
+
+        timestamp::update_global_time_for_test(1000 * 1000000);
+        vest(&beneficiary);
+        assert!(coin::balance<AptosCoin>(beneficiary_address) == 800, 4);
+        assert!(remaining(beneficiary_address) == 0, 5);
+    }
+
+    #[test(beneficiary = @0x123)]
+    #[expected_failure(abort_code = 0x60006)]
+    public entry fun test_vest_without_contract(beneficiary: signer) acquires VestingContract {
+        vest(&beneficiary);
+    }
+}
@@ -0,0 +1,73 @@
+#[test_only]
+module aptos_framework::aggregator_v2_tests {
+
+    use std::string;
+
+    use aptos_framework::aggregator_factory;
+    use aptos_framework::aggregator_v2;
+
+    #[test(account = @aptos_framework)]
+    fun test_u64_and_u128_aggregators(account: signer) {
+        aggregator_factory::initialize_aggregator_factory(&account);
+        let aggregator = aggregator_factory::create_aggregator_v2<u64>(/*max_value=*/1000);
+        let wide_aggregator = aggregator_factory::create_aggregator_v2<u128>(/*max_value=*/1000);
+        assert!(aggregator_v2::max_value(&aggregator) == 1000, 0);
+
+        aggregator_v2::add(&mut aggregator, 12);
+        aggregator_v2::add(&mut wide_aggregator, 15);
+        assert!(aggregator_v2::read(&aggregator) == 12, 0);
+        assert!(aggregator_v2::read(&wide_aggregator) == 15, 0);
+
+        aggregator_v2::sub(&mut aggregator, 12);
+        assert!(aggregator_v2::read(&aggregator) == 0, 0);
+
+        aggregator_v2::destroy(aggregator);
+        aggregator_v2::destroy(wide_aggregator);
+    }
+
+    #[test(account = @aptos_framework)]
+    fun test_snapshots(account: signer) {
+        aggregator_factory::initialize_aggregator_factory(&account);
+        let aggregator = aggregator_factory::create_aggregator_v2<u64>(/*max_value=*/1000);
+
+        aggregator_v2::add(&mut aggregator, 41);
+        let snapshot = aggregator_v2::snapshot(&aggregator);
+        aggregator_v2::add(&mut aggregator, 1);
+
+        // The snapshot keeps the value of the aggregator when it was taken.
+        assert!(aggregator_v2::read_snapshot(&snapshot) == 41, 0);
+        assert!(aggregator_v2::read(&aggregator) == 42, 0);
+
+        let name = aggregator_v2::derive_string_concat(
+            string::utf8(b"Token #"),
+            &snapshot,
+            string::utf8(b" of 1000"),
+        );
+        assert!(aggregator_v2::read_derived_string(&name) == string::utf8(b"Token #41 of 1000"), 0);
+
+        aggregator_v2::destroy_derived_string(name);
+        aggregator_v2::destroy_snapshot(snapshot);
+        aggregator_v2::destroy(aggregator);
+    }
+
+    #[test(account = @aptos_framework)]
+    #[expected_failure(abort_code = 0x020001)]
+    fun test_overflow(account: signer) {
+        aggregator_factory::initialize_aggregator_factory(&account);
+        let aggregator = aggregator_factory::create_aggregator_v2<u64>(/*max_value=*/10);
+
+        // Overflow!
+        aggregator_v2::add(&mut aggregator, 12);
+
+        aggregator_v2::destroy(aggregator);
+    }
+
+    #[test(account = @aptos_framework)]
+    #[expected_failure(abort_code = 0x010004)]
+    fun test_unsupported_type(account: signer) {
+        aggregator_factory::initialize_aggregator_factory(&account);
+        let aggregator = aggregator_factory::create_aggregator_v2<u8>(/*max_value=*/10);
+
+        aggregator_v2::destroy(aggregator);
+    }
+}
@@ -0,0 +1,271 @@
+/// This module provides the building blocks of on-chain light clients of other chains, e.g. for bridges: the
+/// verification of the signatures of the Ethereum beacon chain sync committee and of Tendermint commits, and the hashes
+/// of their block headers. The signature verification and hashing run natively, so that following a chain costs
+/// acceptable gas; the bookkeeping of a light client, e.g. which committee or validator set it trusts, is left to the
+/// contracts.
+module aptos_std::light_client {
+    use std::hash::sha2_256;
+    use std::vector;
+
+    /// A hash is not 32 bytes long.
+    const E_WRONG_HASH_LENGTH: u64 = 1;
+
+    /// The domain type of the signatures of the sync committee.
+    const DOMAIN_SYNC_COMMITTEE: vector<u8> = x"07000000";
+
+    //
+    // Ethereum beacon chain
+    //
+
+    /// Returns the SSZ hash tree root of the `BeaconBlockHeader` of the given fields, which is the object signed by
+    /// the sync committee.
+    public fun beacon_header_root(
+        slot: u64,
+        proposer_index: u64,
+        parent_root: vector<u8>,
+        state_root: vector<u8>,
+        body_root: vector<u8>,
+    ): vector<u8> {
+        eth_beacon_header_root_internal(slot, proposer_index, parent_root, state_root, body_root)
+    }
+
+    /// Returns the domain of the sync committee signatures for the fork of version `fork_version` (4 bytes) of the chain
+    /// of genesis validators root `genesis_validators_root`.
+    public fun sync_committee_domain(fork_version: vector<u8>, genesis_validators_root: vector<u8>): vector<u8> {
+        // The hash tree root of the `ForkData` of the fork
+        let fork_data = fork_version;
+        while (vector::length(&fork_data) < 32) {
+            vector::push_back(&mut fork_data, 0);
+        };
+        vector::append(&mut fork_data, genesis_validators_root);
+        let fork_data_root = sha2_256(fork_data);
+
+        let domain = DOMAIN_SYNC_COMMITTEE;
+        let i = 0;
+        while (i < 28) {
+            vector::push_back(&mut domain, *vector::borrow(&fork_data_root, i));
+            i = i + 1;
+        };
+        domain
+    }
+
+    /// Returns the message signed for the object of root `object_root` in `domain`.
+    public fun signing_root(object_root: vector<u8>, domain: vector<u8>): vector<u8> {
+        vector::append(&mut object_root, domain);
+        sha2_256(object_root)
+    }
+
+    /// Returns whether `branch` proves that `leaf` is at `index` among the `2^depth` leaves of the SSZ Merkle tree of
+    /// root `root`, e.g. that the next sync committee is in the state of a header. `branch` goes from the bottom of the
+    /// tree to the root.
+    public fun is_valid_merkle_branch(
+        leaf: vector<u8>,
+        branch: &vector<vector<u8>>,
+        depth: u64,
+        index: u64,
+        root: vector<u8>,
+    ): bool {
+        if (vector::length(branch) != depth) {
+            return false
+        };
+        let value = leaf;
+        let i = 0;
+        while (i < depth) {
+            let sibling = *vector::borrow(branch, i);
+            if (((index >> (i as u8)) & 1) == 1) {
+                vector::append(&mut sibling, value);
+                value = sha2_256(sibling);
+            } else {
+                vector::append(&mut value, sibling);
+                value = sha2_256(value);
+            };
+            i = i + 1;
+        };
+        value == root
+    }
+
+    /// Returns whether `signature` is a valid signature of `signing_root` by the members of the sync committee of keys
+    /// `committee` whose bits are set in `participation_bits`, and at least `min_participants` members participated.
+    public fun verify_sync_committee_signature(
+        committee: vector<vector<u8>>,
+        participation_bits: vector<u8>,
+        signing_root: vector<u8>,
+        signature: vector<u8>,
+        min_participants: u64,
+    ): bool {
+        let (num_participants, valid) =
+            eth_sync_committee_verify_internal(committee, participation_bits, signing_root, signature);
+        valid && num_participants >= min_participants
+    }
+
+    //
+    // Tendermint
+    //
+
+    /// Returns the root of the Merkle tree of `leaves` which Tendermint uses for the hashes of headers, from their
+    /// encoded fields, and of validator sets.
+    public fun tendermint_merkle_root(leaves: vector<vector<u8>>): vector<u8> {
+        tendermint_merkle_root_internal(leaves)
+    }
+
+    /// Returns whether the validators of keys `pubkeys` and voting powers `voting_powers` signed the vote of
+    /// `sign_bytes` with `signatures`, and have more than two thirds of `total_voting_power`.
+    public fun verify_tendermint_commit(
+        pubkeys: vector<vector<u8>>,
+        voting_powers: vector<u64>,
+        sign_bytes: vector<vector<u8>>,
+        signatures: vector<vector<u8>>,
+        total_voting_power: u64,
+    ): bool {
+        let signed_voting_power = 0u128;
+        let i = 0;
+        while (i < vector::length(&voting_powers)) {
+            signed_voting_power = signed_voting_power + (*vector::borrow(&voting_powers, i) as u128);
+            i = i + 1;
+        };
+        vector::length(&voting_powers) == vector::length(&pubkeys) &&
+            signed_voting_power * 3 > (total_voting_power as u128) * 2 &&
+            tendermint_verify_commit_internal(pubkeys, sign_bytes, signatures)
+    }
+
+    //
+    // Native functions
+    //
+
+    /// Returns the number of participants, and whether their aggregate signature is valid.
+    native fun eth_sync_committee_verify_internal(
+        committee: vector<vector<u8>>,
+        participation_bits: vector<u8>,
+        signing_root: vector<u8>,
+        signature: vector<u8>,
+    ): (u64, bool);
+
+    native fun eth_beacon_header_root_internal(
+        slot: u64,
+        proposer_index: u64,
+        parent_root: vector<u8>,
+        state_root: vector<u8>,
+        body_root: vector<u8>,
+    ): vector<u8>;
+
+    /// Returns whether every Ed25519 signature is valid.
+    native fun tendermint_verify_commit_internal(
+        pubkeys: vector<vector<u8>>,
+        messages: vector<vector<u8>>,
+        signatures: vector<vector<u8>>,
+    ): bool;
+
+    native fun tendermint_merkle_root_internal(leaves: vector<vector<u8>>): vector<u8>;
+
+    //
+    // Tests
+    //
+
+    #[test_only]
+    /// BLS12-381 keys and multisignatures from `aptos_std::bls12381`
+    fun test_committee(): vector<vector<u8>> {
+        vector[
+            x"92e201a806af246f805f460fbdc6fc90dd16a18d6accc236e85d3578671d6f6690dde22134d19596c58ce9d63252410a",
+            x"ab9df801c6f96ade1c0490c938c87d5bcc2e52ccb8768e1b5d14197c5e8bfa562783b96711b702dda411a1a9f08ebbfa",
+            x"b698c932cf7097d99c17bd6e9c9dc4eeba84278c621700a8f80ec726b1daa11e3ab55fc045b4dbadefbeef05c4182494",
+            x"934706a8b876d47a996d427e1526ce52c952d5ec0858d49cd262efb785b62b1972d06270b0a7adda1addc98433ad1843",
+            x"a4cd352daad3a0651c1998dfbaa7a748e08d248a54347544bfedd51a197e016bb6008e9b8e45a744e1a030cc3b27d2da",
+        ]
+    }
+
+    #[test]
+    fun test_sync_committee_signature() {
+        // Signatures on "Hello, Aptoverse!" by the first three members, and by all five members
+        let sig_3 = x"b523a31813e771e55aa0fc99a48db716ecc1085f9899ccadb64e759ecb481a2fb1cdcc0b266f036695f941361de773081729311f6a1bca9d47393f5359c8c87dc34a91f5dae335590aacbff974076ad1f910dd81750553a72ccbcad3c8cc0f07";
+        let sig_5 = x"b627b2cfd8ae59dcf5e58cc6c230ae369985fd096e1bc3be38da5deafcbed7d939f07cccc75383539940c56c6b6453db193f563f5b6e4fe54915afd9e1baea40a297fa7eda74abbdcd4cc5c667d6db3b9bd265782f7693798894400f2beb4637";
+        let message = b"Hello, Aptoverse!";
+
+        assert!(verify_sync_committee_signature(test_committee(), x"07", message, sig_3, 3), 1);
+        assert!(verify_sync_committee_signature(test_committee(), x"1f", message, sig_5, 4), 2);
+        // Too few participants
+        assert!(!verify_sync_committee_signature(test_committee(), x"07", message, sig_3, 4), 3);
+        // Other participants
+        assert!(!verify_sync_committee_signature(test_committee(), x"0b", message, sig_3, 3), 4);
+        assert!(!verify_sync_committee_signature(test_committee(), x"00", message, sig_3, 0), 5);
+        // Another message
+        assert!(!verify_sync_committee_signature(test_committee(), x"07", b"Hello, Aptos!", sig_3, 3), 6);
+    }
+
+    #[test]
+    fun test_beacon_header_root() {
+        let root = beacon_header_root(
+            7,
+            3,
+            x"1111111111111111111111111111111111111111111111111111111111111111",
+            x"2222222222222222222222222222222222222222222222222222222222222222",
+            x"3333333333333333333333333333333333333333333333333333333333333333",
+        );
+        assert!(root == x"5f22c0f7c225d40b0bf62bc4a52a65e36049a55466cfe426751e790bde3f409c", 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_beacon_header_root_wrong_hash_length() {
+        beacon_header_root(7, 3, x"11", x"22", x"33");
+    }
+
+    #[test]
+    fun test_sync_committee_domain() {
+        // The Altair fork of mainnet
+        let domain = sync_committee_domain(
+            x"01000000",
+            x"4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95",
+        );
+        assert!(domain == x"07000000afcaaba0efab1ca832a15152469bb09bb84641c405171dfa2d3fb45f", 1);
+    }
+
+    #[test]
+    fun test_merkle_branch() {
+        let leaf = sha2_256(b"leaf");
+        let siblings = vector[sha2_256(b"sibling 0"), sha2_256(b"sibling 1")];
+
+        // The leaf is the second of four leaves
+        let level_1 = *vector::borrow(&siblings, 0);
+        vector::append(&mut level_1, leaf);
+        let root = sha2_256(level_1);
+        vector::append(&mut root, *vector::borrow(&siblings, 1));
+        let root = sha2_256(root);
+
+        assert!(is_valid_merkle_branch(leaf, &siblings, 2, 1, root), 1);
+        assert!(!is_valid_merkle_branch(leaf, &siblings, 2, 0, root), 2);
+        assert!(!is_valid_merkle_branch(leaf, &siblings, 1, 1, root), 3);
+    }
+
+    #[test]
+    fun test_tendermint_merkle_root() {
+        assert!(
+            tendermint_merkle_root(vector[]) == x"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            1
+        );
+        assert!(
+            tendermint_merkle_root(vector[b"a", b"b", b"c"]) ==
+                x"36642e73c2540ab121e3a6bf9545b0a24982cd830eb13d3cd19de3ce6c021ec1",
+            2
+        );
+    }
+
+    #[test]
+    fun test_tendermint_commit() {
+        // Test vectors 1 and 2 of RFC 8032
+        let pubkeys = vector[
+            x"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            x"3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        ];
+        let sign_bytes = vector[b"", x"72"];
+        let signatures = vector[
+            x"e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            x"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ];
+
+        assert!(verify_tendermint_commit(pubkeys, vector[1, 2], sign_bytes, signatures, 4), 1);
+        // Not more than two thirds of the voting power
+        assert!(!verify_tendermint_commit(pubkeys, vector[1, 2], sign_bytes, signatures, 5), 2);
+        // The votes are swapped
+        assert!(!verify_tendermint_commit(pubkeys, vector[1, 2], vector[x"72", b""], signatures, 4), 3);
+    }
+}
@@ -0,0 +1,172 @@
+/// This module verifies the Merkle proofs of the Aptos blockchain: proofs of the state in the sparse Merkle tree, and
+/// proofs of transactions and events in their accumulators. The proofs are checked with the hashes of the node, so that
+/// contracts, e.g. bridges and light clients, can authenticate Aptos data on-chain against a root hash they trust.
+///
+/// The hashes are the 32-byte SHA3-256 hashes of `aptos_crypto::HashValue`.
+module aptos_std::merkle {
+    use std::option::{Self, Option};
+
+    /// A hash is not 32 bytes long.
+    const E_WRONG_HASH_LENGTH: u64 = 1;
+
+    /// The accumulator is unknown.
+    const E_UNKNOWN_ACCUMULATOR: u64 = 2;
+
+    const TRANSACTION_ACCUMULATOR: u8 = 1;
+    const EVENT_ACCUMULATOR: u8 = 2;
+
+    /// The leaf of a sparse Merkle tree: the hash of the key of a state value, and the hash of the value.
+    struct SparseMerkleLeaf has copy, drop, store {
+        key: vector<u8>,
+        value_hash: vector<u8>,
+    }
+
+    /// A proof that a key has a value in a sparse Merkle tree, or that it doesn't exist in the tree, as in
+    /// `aptos_types::proof::SparseMerkleProof`. The siblings go from the bottom of the tree to the root.
+    struct SparseMerkleProof has copy, drop, store {
+        leaf: Option<SparseMerkleLeaf>,
+        siblings: vector<vector<u8>>,
+    }
+
+    public fun new_sparse_merkle_leaf(key: vector<u8>, value_hash: vector<u8>): SparseMerkleLeaf {
+        SparseMerkleLeaf { key, value_hash }
+    }
+
+    public fun new_sparse_merkle_proof(
+        leaf: Option<SparseMerkleLeaf>,
+        siblings: vector<vector<u8>>
+    ): SparseMerkleProof {
+        SparseMerkleProof { leaf, siblings }
+    }
+
+    /// If `value_hash` is present, returns whether `proof` shows that `key` has a value of hash `value_hash` in the
+    /// tree of root `root_hash`. Otherwise returns whether `proof` shows that `key` doesn't exist in the tree.
+    public fun verify_sparse_merkle_proof(
+        proof: &SparseMerkleProof,
+        root_hash: vector<u8>,
+        key: vector<u8>,
+        value_hash: Option<vector<u8>>,
+    ): bool {
+        let (leaf_key, leaf_value_hash) = if (option::is_some(&proof.leaf)) {
+            let leaf = option::borrow(&proof.leaf);
+            (leaf.key, leaf.value_hash)
+        } else {
+            (vector[], vector[])
+        };
+        verify_sparse_merkle_proof_internal(
+            root_hash,
+            key,
+            option::destroy_with_default(value_hash, vector[]),
+            leaf_key,
+            leaf_value_hash,
+            proof.siblings,
+        )
+    }
+
+    /// Returns whether `siblings` prove that the transaction info of hash `element_hash` is at version
+    /// `element_index` of the transaction accumulator of root `root_hash`. The siblings go from the bottom of the
+    /// accumulator to the root.
+    public fun verify_transaction_accumulator_proof(
+        root_hash: vector<u8>,
+        element_hash: vector<u8>,
+        element_index: u64,
+        siblings: vector<vector<u8>>,
+    ): bool {
+        verify_accumulator_proof_internal(TRANSACTION_ACCUMULATOR, root_hash, element_hash, element_index, siblings)
+    }
+
+    /// Returns whether `siblings` prove that the event of hash `element_hash` is at index `element_index` of the event
+    /// accumulator of root `root_hash`, i.e. of a transaction. The siblings go from the bottom of the accumulator to
+    /// the root.
+    public fun verify_event_accumulator_proof(
+        root_hash: vector<u8>,
+        element_hash: vector<u8>,
+        element_index: u64,
+        siblings: vector<vector<u8>>,
+    ): bool {
+        verify_accumulator_proof_internal(EVENT_ACCUMULATOR, root_hash, element_hash, element_index, siblings)
+    }
+
+    //
+    // Native functions
+    //
+
+    /// An empty `value_hash` stands for a missing value, and empty `leaf_key` and `leaf_value_hash` for a missing leaf.
+    native fun verify_sparse_merkle_proof_internal(
+        root_hash: vector<u8>,
+        key: vector<u8>,
+        value_hash: vector<u8>,
+        leaf_key: vector<u8>,
+        leaf_value_hash: vector<u8>,
+        siblings: vector<vector<u8>>,
+    ): bool;
+
+    native fun verify_accumulator_proof_internal(
+        accumulator: u8,
+        root_hash: vector<u8>,
+        element_hash: vector<u8>,
+        element_index: u64,
+        siblings: vector<vector<u8>>,
+    ): bool;
+
+    //
+    // Tests
+    //
+
+    #[test_only]
+    use std::hash::sha3_256;
+
+    #[test_only]
+    /// The hash of an empty subtree of a sparse Merkle tree
+    fun sparse_merkle_placeholder_hash(): vector<u8> {
+        x"5350415253455f4d45524b4c455f504c414345484f4c4445525f484153480000"
+    }
+
+    #[test]
+    fun test_sparse_merkle_non_inclusion() {
+        // In an empty tree, a key doesn't exist
+        let empty_proof = new_sparse_merkle_proof(option::none(), vector[]);
+        let key = sha3_256(b"key");
+        assert!(verify_sparse_merkle_proof(&empty_proof, sparse_merkle_placeholder_hash(), key, option::none()), 1);
+        assert!(!verify_sparse_merkle_proof(&empty_proof, sparse_merkle_placeholder_hash(), key, option::some(key)), 2);
+
+        // A key which starts with bit 1 is in the empty right subtree of this root
+        let proof = new_sparse_merkle_proof(option::none(), vector[sha3_256(b"sibling")]);
+        let root_hash = x"8eb3a5bc254f838ae52a8926146e06eff1e08c12f255db112db74a201a8a6f65";
+        let key = x"8000000000000000000000000000000000000000000000000000000000000000";
+        assert!(verify_sparse_merkle_proof(&proof, root_hash, key, option::none()), 3);
+        let key = x"0000000000000000000000000000000000000000000000000000000000000000";
+        assert!(!verify_sparse_merkle_proof(&proof, root_hash, key, option::none()), 4);
+    }
+
+    #[test]
+    fun test_sparse_merkle_inclusion_mismatch() {
+        let key = sha3_256(b"key");
+        let value_hash = sha3_256(b"value");
+        let proof = new_sparse_merkle_proof(option::some(new_sparse_merkle_leaf(key, value_hash)), vector[]);
+        // The leaf holds another value
+        assert!(!verify_sparse_merkle_proof(&proof, sha3_256(b"root"), key, option::some(sha3_256(b"other"))), 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_sparse_merkle_wrong_hash_length() {
+        let proof = new_sparse_merkle_proof(option::none(), vector[x"01"]);
+        verify_sparse_merkle_proof(&proof, sparse_merkle_placeholder_hash(), sha3_256(b"key"), option::none());
+    }
+
+    #[test]
+    fun test_accumulator_proofs() {
+        // The element is the right child of the left subtree of a 4-element accumulator
+        let element_hash = sha3_256(b"element");
+        let siblings = vector[sha3_256(b"s0"), sha3_256(b"s1")];
+        let transaction_root = x"0a0b78bcfcdf5d89195132bceb05fe483e85d0d58e3e401ab477f2c4db8eba92";
+        let event_root = x"d89c9047ece45b6481c1556b3c1028db31601df4f11e12ef77206502590ba161";
+
+        assert!(verify_transaction_accumulator_proof(transaction_root, element_hash, 1, siblings), 1);
+        assert!(!verify_transaction_accumulator_proof(transaction_root, element_hash, 0, siblings), 2);
+        assert!(verify_event_accumulator_proof(event_root, element_hash, 1, siblings), 3);
+        // The accumulators have different domain separation
+        assert!(!verify_event_accumulator_proof(transaction_root, element_hash, 1, siblings), 4);
+    }
+}
@@ -0,0 +1,59 @@
+/// This module implements the Poseidon hash over the scalar field of BN254, with the parameters of circomlib, so that
+/// contracts compute the same hashes as zero-knowledge circuits, e.g. those of keyless accounts.
+///
+/// Scalars are 32-byte little-endian encodings of integers smaller than the order of the field. A hash takes from 1 to
+/// `MAX_ARITY` scalars, and is itself a scalar.
+module aptos_std::poseidon {
+    /// The number of inputs is not between 1 and `MAX_ARITY`.
+    const E_INVALID_ARITY: u64 = 1;
+
+    /// An input is not the 32-byte encoding of a scalar.
+    const E_INVALID_INPUT: u64 = 2;
+
+    /// The most inputs of a hash.
+    const MAX_ARITY: u64 = 12;
+
+    /// Returns the Poseidon hash of the scalars `inputs`.
+    public fun hash_bn254(inputs: vector<vector<u8>>): vector<u8> {
+        poseidon_bn254_internal(inputs)
+    }
+
+    /// Returns the most inputs of a hash.
+    public fun max_arity(): u64 {
+        MAX_ARITY
+    }
+
+    //
+    // Native functions
+    //
+
+    native fun poseidon_bn254_internal(inputs: vector<vector<u8>>): vector<u8>;
+
+    //
+    // Tests
+    //
+
+    #[test]
+    fun test_hash_bn254() {
+        // A test vector of light-poseidon, which matches circomlibjs
+        let inputs = vector[
+            x"0101010101010101010101010101010101010101010101010101010101010101",
+            x"0202020202020202020202020202020202020202020202020202020202020202",
+        ];
+        let expected = x"90198229c835e7261bcea29cfe847b201963f255035eeb7d1c8c8a8f93e1540d";
+        assert!(hash_bn254(inputs) == expected, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_hash_bn254_no_input() {
+        hash_bn254(vector[]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun test_hash_bn254_non_canonical_input() {
+        // The order of the field
+        hash_bn254(vector[x"010000f093f5e1439170b97948e833285d588181b64550b829a031e1724e6430"]);
+    }
+}
@@ -0,0 +1,306 @@
+/// This module implements the Ristretto255 prime-order group, over Curve25519, for protocols such as Schnorr
+/// signatures, Pedersen commitments and sigma protocols.
+///
+/// A `RistrettoPoint` is a handle to a point kept decompressed by the VM for the rest of the transaction, so that
+/// operations don't decompress their inputs again. Points can't be stored: `CompressedRistretto` is their storable
+/// form. Likewise, a `ScalarHandle` is a scalar kept by the VM, which is updated in place by arithmetic, so that long
+/// computations don't copy every intermediate scalar in and out of Move.
+module aptos_std::ristretto255 {
+    use std::bcs;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    /// The transaction created more points than the VM keeps for it.
+    const E_TOO_MANY_POINTS_CREATED: u64 = 1;
+
+    /// A compressed point doesn't decompress to a point. Unreachable, as compressed points are compressed from points.
+    const E_INVALID_COMPRESSED_POINT: u64 = 2;
+
+    /// The transaction created more scalar handles than the VM keeps for it.
+    const E_TOO_MANY_SCALARS_CREATED: u64 = 3;
+
+    /// The size of a compressed point or a scalar, in bytes.
+    const NUM_BYTES: u64 = 32;
+
+    /// A point of the group, decompressed in the current transaction.
+    struct RistrettoPoint has drop {
+        handle: u64
+    }
+
+    /// A point of the group, in its canonical 32-byte encoding.
+    struct CompressedRistretto has copy, drop, store {
+        data: vector<u8>
+    }
+
+    /// A scalar modulo the order of the group, in its canonical 32-byte little-endian encoding.
+    struct Scalar has copy, drop, store {
+        data: vector<u8>
+    }
+
+    /// A scalar kept by the VM for the rest of the transaction.
+    struct ScalarHandle has drop {
+        handle: u64
+    }
+
+    //
+    // Points
+    //
+
+    /// Decompresses the 32-byte encoding of a point, or returns `None` if `bytes` aren't the canonical encoding of a
+    /// point.
+    public fun new_point_from_bytes(bytes: vector<u8>): Option<RistrettoPoint> {
+        let (handle, valid) = point_decompress_internal(bytes);
+        if (valid) {
+            option::some(RistrettoPoint { handle })
+        } else {
+            option::none()
+        }
+    }
+
+    /// Compresses `point` to its storable form.
+    public fun point_compress(point: &RistrettoPoint): CompressedRistretto {
+        CompressedRistretto { data: point_compress_internal(point.handle) }
+    }
+
+    /// Decompresses `point`, which is valid as it was compressed from a point.
+    public fun point_decompress(point: &CompressedRistretto): RistrettoPoint {
+        let (handle, valid) = point_decompress_internal(point.data);
+        assert!(valid, std::error::internal(E_INVALID_COMPRESSED_POINT));
+        RistrettoPoint { handle }
+    }
+
+    /// Serializes `point` to its 32-byte encoding.
+    public fun compressed_point_to_bytes(point: CompressedRistretto): vector<u8> {
+        point.data
+    }
+
+    /// Returns the identity point.
+    public fun point_identity(): RistrettoPoint {
+        RistrettoPoint { handle: point_identity_internal() }
+    }
+
+    /// Returns `a * G`, where `G` is the basepoint.
+    public fun basepoint_mul(a: &Scalar): RistrettoPoint {
+        RistrettoPoint { handle: basepoint_mul_internal(a.data) }
+    }
+
+    /// Returns `a * point + b * G`, where `G` is the basepoint, in variable time. `a` and `b` must not be secret.
+    public fun basepoint_double_mul(a: &Scalar, point: &RistrettoPoint, b: &Scalar): RistrettoPoint {
+        RistrettoPoint { handle: basepoint_double_mul_internal(a.data, point.handle, b.data) }
+    }
+
+    /// Returns `a * point`.
+    public fun point_mul(point: &RistrettoPoint, a: &Scalar): RistrettoPoint {
+        RistrettoPoint { handle: point_mul_internal(point.handle, a.data) }
+    }
+
+    /// Returns `a + b`.
+    public fun point_add(a: &RistrettoPoint, b: &RistrettoPoint): RistrettoPoint {
+        RistrettoPoint { handle: point_add_internal(a.handle, b.handle) }
+    }
+
+    /// Returns `a - b`.
+    public fun point_sub(a: &RistrettoPoint, b: &RistrettoPoint): RistrettoPoint {
+        RistrettoPoint { handle: point_sub_internal(a.handle, b.handle) }
+    }
+
+    /// Returns `-a`.
+    public fun point_neg(a: &RistrettoPoint): RistrettoPoint {
+        RistrettoPoint { handle: point_neg_internal(a.handle) }
+    }
+
+    /// Returns `true` if `a` and `b` are the same point. Cheaper than comparing their encodings, which
+    /// requires compressing both points.
+    public fun point_equals(a: &RistrettoPoint, b: &RistrettoPoint): bool {
+        point_equals_internal(a.handle, b.handle)
+    }
+
+    /// Returns `true` if `point` is the identity point.
+    public fun point_is_identity(point: &RistrettoPoint): bool {
+        point_is_identity_internal(point.handle)
+    }
+
+    //
+    // Scalars
+    //
+
+    /// Returns the scalar encoded by `bytes`, or `None` if `bytes` aren't the canonical encoding of a scalar.
+    public fun new_scalar_from_bytes(bytes: vector<u8>): Option<Scalar> {
+        if (vector::length(&bytes) == NUM_BYTES && scalar_is_canonical_internal(bytes)) {
+            option::some(Scalar { data: bytes })
+        } else {
+            option::none()
+        }
+    }
+
+    /// Returns the scalar `value`.
+    public fun new_scalar_from_u64(value: u64): Scalar {
+        let data = bcs::to_bytes(&value);
+        while (vector::length(&data) < NUM_BYTES) {
+            vector::push_back(&mut data, 0);
+        };
+        Scalar { data }
+    }
+
+    /// Hashes `bytes` to a scalar with SHA2-512, e.g. for the challenge of a Schnorr signature.
+    public fun new_scalar_from_sha512(bytes: vector<u8>): Scalar {
+        Scalar { data: scalar_from_sha512_internal(bytes) }
+    }
+
+    /// Serializes `scalar` to its 32-byte encoding.
+    public fun scalar_to_bytes(scalar: &Scalar): vector<u8> {
+        scalar.data
+    }
+
+    /// Copies `scalar` to a new handle, to compute with it in place.
+    public fun scalar_load(scalar: &Scalar): ScalarHandle {
+        ScalarHandle { handle: scalar_load_internal(scalar.data) }
+    }
+
+    /// Copies the current value of `scalar` out of its handle.
+    public fun scalar_store(scalar: &ScalarHandle): Scalar {
+        Scalar { data: scalar_store_internal(scalar.handle) }
+    }
+
+    /// Sets `a` to `a + b`.
+    public fun scalar_add_assign(a: &mut ScalarHandle, b: &ScalarHandle) {
+        scalar_add_assign_internal(a.handle, b.handle)
+    }
+
+    /// Sets `a` to `a * b`.
+    public fun scalar_mul_assign(a: &mut ScalarHandle, b: &ScalarHandle) {
+        scalar_mul_assign_internal(a.handle, b.handle)
+    }
+
+    //
+    // Native functions
+    //
+
+    /// Decompresses `bytes` into the point table, and returns the handle of the point and `true`, or `false` if
+    /// `bytes` aren't the canonical encoding of a point.
+    native fun point_decompress_internal(bytes: vector<u8>): (u64, bool);
+
+    native fun point_compress_internal(handle: u64): vector<u8>;
+
+    native fun point_identity_internal(): u64;
+
+    native fun basepoint_mul_internal(a: vector<u8>): u64;
+
+    native fun basepoint_double_mul_internal(a: vector<u8>, handle: u64, b: vector<u8>): u64;
+
+    native fun point_mul_internal(handle: u64, a: vector<u8>): u64;
+
+    native fun point_add_internal(a: u64, b: u64): u64;
+
+    native fun point_sub_internal(a: u64, b: u64): u64;
+
+    native fun point_neg_internal(a: u64): u64;
+
+    native fun point_equals_internal(a: u64, b: u64): bool;
+
+    native fun point_is_identity_internal(handle: u64): bool;
+
+    native fun scalar_is_canonical_internal(bytes: vector<u8>): bool;
+
+    native fun scalar_from_sha512_internal(bytes: vector<u8>): vector<u8>;
+
+    native fun scalar_load_internal(bytes: vector<u8>): u64;
+
+    native fun scalar_store_internal(handle: u64): vector<u8>;
+
+    /// Sets the scalar of handle `a` to `a + b`.
+    native fun scalar_add_assign_internal(a: u64, b: u64);
+
+    /// Sets the scalar of handle `a` to `a * b`.
+    native fun scalar_mul_assign_internal(a: u64, b: u64);
+
+    //
+    // Tests
+    //
+
+    #[test_only]
+    /// The encoding of the basepoint
+    fun basepoint_compressed(): vector<u8> {
+        x"e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76"
+    }
+
+    #[test]
+    fun test_point_compression() {
+        let basepoint = basepoint_mul(&new_scalar_from_u64(1));
+        let compressed = point_compress(&basepoint);
+        assert!(compressed.data == basepoint_compressed(), 1);
+
+        let decompressed = option::destroy_some(new_point_from_bytes(basepoint_compressed()));
+        assert!(point_equals(&decompressed, &basepoint), 2);
+        assert!(point_equals(&point_decompress(&compressed), &basepoint), 3);
+
+        // Not the encoding of a point
+        let not_a_point = basepoint_compressed();
+        *vector::borrow_mut(&mut not_a_point, 0) = 0xff;
+        assert!(option::is_none(&new_point_from_bytes(not_a_point)), 4);
+        assert!(option::is_none(&new_point_from_bytes(x"01")), 5);
+    }
+
+    #[test]
+    fun test_point_arithmetic() {
+        let g = basepoint_mul(&new_scalar_from_u64(1));
+        let two_g = basepoint_mul(&new_scalar_from_u64(2));
+        assert!(point_equals(&point_add(&g, &g), &two_g), 1);
+        assert!(point_equals(&point_mul(&g, &new_scalar_from_u64(2)), &two_g), 2);
+        assert!(point_equals(&point_sub(&two_g, &g), &g), 3);
+        assert!(point_equals(&point_add(&g, &point_neg(&g)), &point_identity()), 4);
+        assert!(point_is_identity(&point_sub(&g, &g)), 5);
+        assert!(point_is_identity(&basepoint_mul(&new_scalar_from_u64(0))), 6);
+        assert!(!point_is_identity(&g), 7);
+
+        // 3 * (2 * G) + 5 * G = 11 * G
+        let eleven_g = basepoint_double_mul(&new_scalar_from_u64(3), &two_g, &new_scalar_from_u64(5));
+        assert!(point_equals(&eleven_g, &basepoint_mul(&new_scalar_from_u64(11))), 8);
+    }
+
+    #[test]
+    fun test_scalars() {
+        let one = new_scalar_from_u64(1);
+        assert!(scalar_to_bytes(&one) == x"0100000000000000000000000000000000000000000000000000000000000000", 1);
+        assert!(option::destroy_some(new_scalar_from_bytes(scalar_to_bytes(&one))) == one, 2);
+
+        // The order of the group isn't a canonical scalar
+        let order = x"edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+        assert!(option::is_none(&new_scalar_from_bytes(order)), 3);
+        assert!(option::is_none(&new_scalar_from_bytes(x"01")), 4);
+
+        let hashed = new_scalar_from_sha512(b"test aptos ristretto255");
+        assert!(option::is_some(&new_scalar_from_bytes(scalar_to_bytes(&hashed))), 5);
+    }
+
+    #[test]
+    fun test_scalar_handles() {
+        // (2 + 3) * 7 = 35
+        let a = scalar_load(&new_scalar_from_u64(2));
+        scalar_add_assign(&mut a, &scalar_load(&new_scalar_from_u64(3)));
+        scalar_mul_assign(&mut a, &scalar_load(&new_scalar_from_u64(7)));
+        assert!(scalar_store(&a) == new_scalar_from_u64(35), 1);
+    }
+
+    #[test]
+    fun test_schnorr_signature() {
+        let secret_key = new_scalar_from_sha512(b"secret key");
+        let public_key = basepoint_mul(&secret_key);
+        let nonce = new_scalar_from_sha512(b"nonce");
+        let commitment = basepoint_mul(&nonce);
+
+        // The challenge is e = H(R || P || message), and the signature is (R, s = k + e * x)
+        let challenge_input = compressed_point_to_bytes(point_compress(&commitment));
+        vector::append(&mut challenge_input, compressed_point_to_bytes(point_compress(&public_key)));
+        vector::append(&mut challenge_input, b"message");
+        let challenge = new_scalar_from_sha512(challenge_input);
+        let s = scalar_load(&challenge);
+        scalar_mul_assign(&mut s, &scalar_load(&secret_key));
+        scalar_add_assign(&mut s, &scalar_load(&nonce));
+        let s = scalar_store(&s);
+
+        // s * G = R + e * P
+        let expected = point_add(&commitment, &point_mul(&public_key, &challenge));
+        assert!(point_equals(&basepoint_mul(&s), &expected), 1);
+    }
+}
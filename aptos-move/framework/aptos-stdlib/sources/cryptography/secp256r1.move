@@ -0,0 +1,141 @@
+/// This module implements ECDSA signatures over the NIST P-256 (a.k.a. secp256r1) elliptic curve, with SHA2-256 as the
+/// message digest. These are the signatures produced by WebAuthn authenticators, such as passkeys.
+///
+/// Verification is strict: signatures whose `s` component is in the upper half of the scalar field are rejected, so
+/// that signatures are not malleable.
+module aptos_std::secp256r1 {
+    use std::hash;
+    use std::vector;
+
+    /// Wrong number of bytes were given as input when deserializing a secp256r1 public key.
+    const E_WRONG_PUBKEY_SIZE: u64 = 1;
+
+    /// Wrong number of bytes were given as input when deserializing a secp256r1 signature.
+    const E_WRONG_SIGNATURE_SIZE: u64 = 2;
+
+    /// The size of a secp256r1 public key in uncompressed SEC1 form (`0x04 | x | y`), in bytes.
+    const PUBLIC_KEY_NUM_BYTES: u64 = 65;
+
+    /// The size of a secp256r1 ECDSA signature (`r | s`), in bytes.
+    const SIGNATURE_NUM_BYTES: u64 = 64;
+
+    /// The identifier of the WebAuthn scheme, whose authentication keys are derived from secp256r1 public keys.
+    const WEBAUTHN_SCHEME: u8 = 2;
+
+    /// A 65-byte secp256r1 public key, in uncompressed SEC1 form. Not necessarily a point on the curve.
+    struct ECDSAPublicKey has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// A 64-byte secp256r1 ECDSA signature.
+    struct ECDSASignature has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// Constructs an ECDSAPublicKey struct, given its 65-byte uncompressed SEC1 form.
+    public fun ecdsa_public_key_from_bytes(bytes: vector<u8>): ECDSAPublicKey {
+        assert!(vector::length(&bytes) == PUBLIC_KEY_NUM_BYTES, std::error::invalid_argument(E_WRONG_PUBKEY_SIZE));
+        ECDSAPublicKey { bytes }
+    }
+
+    /// Constructs an ECDSASignature struct from the given 64 bytes.
+    public fun ecdsa_signature_from_bytes(bytes: vector<u8>): ECDSASignature {
+        assert!(vector::length(&bytes) == SIGNATURE_NUM_BYTES, std::error::invalid_argument(E_WRONG_SIGNATURE_SIZE));
+        ECDSASignature { bytes }
+    }
+
+    /// Serializes an ECDSAPublicKey struct to 65 bytes.
+    public fun ecdsa_public_key_to_bytes(pk: &ECDSAPublicKey): vector<u8> {
+        pk.bytes
+    }
+
+    /// Serializes an ECDSASignature struct to 64 bytes.
+    public fun ecdsa_signature_to_bytes(sig: &ECDSASignature): vector<u8> {
+        sig.bytes
+    }
+
+    /// Returns `true` if `pk` is a point on the curve, other than the identity.
+    public fun ecdsa_public_key_validate(pk: &ECDSAPublicKey): bool {
+        public_key_validate_internal(pk.bytes)
+    }
+
+    /// Verifies a secp256r1 ECDSA `signature` under `public_key` on the SHA2-256 digest of `message`.
+    public fun ecdsa_signature_verify_strict(
+        signature: &ECDSASignature,
+        public_key: &ECDSAPublicKey,
+        message: vector<u8>
+    ): bool {
+        signature_verify_strict_internal(signature.bytes, public_key.bytes, message)
+    }
+
+    /// Derives the authentication key of an account controlled by a WebAuthn authenticator (e.g., a passkey) with
+    /// the secp256r1 `public_key`: the SHA3-256 hash of the public key followed by the WebAuthn scheme identifier.
+    public fun webauthn_authentication_key(public_key: &ECDSAPublicKey): vector<u8> {
+        let bytes = public_key.bytes;
+        vector::push_back(&mut bytes, WEBAUTHN_SCHEME);
+        hash::sha3_256(bytes)
+    }
+
+    //
+    // Native functions
+    //
+
+    /// Returns `true` if `public_key` is a point on the curve, in uncompressed SEC1 form, other than the identity.
+    native fun public_key_validate_internal(public_key: vector<u8>): bool;
+
+    /// Returns `true` if the secp256r1 ECDSA `signature` on the SHA2-256 digest of `message` verifies against
+    /// `public_key`. Returns `false` if either:
+    /// - `signature` or `public_key` are of wrong sizes,
+    /// - `public_key` is not a point on the curve,
+    /// - `signature` has a high `s` component,
+    /// - the signature on `message` does not verify.
+    native fun signature_verify_strict_internal(
+        signature: vector<u8>,
+        public_key: vector<u8>,
+        message: vector<u8>
+    ): bool;
+
+    //
+    // Tests
+    //
+
+    #[test_only]
+    /// The public key of sk = x"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+    fun test_public_key_bytes(): vector<u8> {
+        x"04d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97fa5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f48"
+    }
+
+    #[test]
+    fun test_ecdsa_signature_verify_strict() {
+        let pk = ecdsa_public_key_from_bytes(test_public_key_bytes());
+        assert!(ecdsa_public_key_validate(&pk), 1);
+
+        let sig = ecdsa_signature_from_bytes(x"8264a90cf511539d94a02854e9fb70111f9aa618291ddd44dca50d168f3ed8bb5869738cebfe0f3324ae4027585f032d9308b41f38339075541781eb44758b43");
+        assert!(ecdsa_signature_verify_strict(&sig, &pk, b"test aptos secp256r1"), 2);
+        assert!(!ecdsa_signature_verify_strict(&sig, &pk, b"test aptos secp256r2"), 3);
+
+        // The same signature with `s` replaced by `n - s`, which is valid but malleable
+        let high_s = ecdsa_signature_from_bytes(x"8264a90cf511539d94a02854e9fb70111f9aa618291ddd44dca50d168f3ed8bba7968c721401f0cddb51bfd8a7a0fcd229de468e6ee40e0f9fa248d7b7ed9a0e");
+        assert!(!ecdsa_signature_verify_strict(&high_s, &pk, b"test aptos secp256r1"), 4);
+    }
+
+    #[test]
+    fun test_ecdsa_public_key_validate() {
+        let not_on_curve = test_public_key_bytes();
+        let last = vector::pop_back(&mut not_on_curve);
+        vector::push_back(&mut not_on_curve, last ^ 1);
+        assert!(!ecdsa_public_key_validate(&ecdsa_public_key_from_bytes(not_on_curve)), 1);
+    }
+
+    #[test]
+    fun test_webauthn_authentication_key() {
+        let pk = ecdsa_public_key_from_bytes(test_public_key_bytes());
+        assert!(webauthn_authentication_key(&pk) == x"9b7b35a2e64c1ec645871ba43283e7520358120027e322a574a4066174e38a8e", 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun test_ecdsa_public_key_wrong_size() {
+        ecdsa_public_key_from_bytes(x"02d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97f");
+    }
+}
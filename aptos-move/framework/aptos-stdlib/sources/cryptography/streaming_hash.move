@@ -0,0 +1,95 @@
+/// This module implements incremental SHA2-512 and SHA3-512 hashing: a message is fed to a hasher in chunks, so that
+/// large messages don't have to be concatenated in Move memory first, and hashers can be reused across the levels of
+/// a Merkle tree.
+///
+/// A `Hasher` is a handle to the state of a hash kept by the VM for the rest of the transaction, so hashers can't be
+/// stored.
+module aptos_std::streaming_hash {
+    /// The transaction created more hashers than the VM keeps for it.
+    const E_TOO_MANY_HASHERS_CREATED: u64 = 1;
+
+    /// The hash algorithm is unknown.
+    const E_UNKNOWN_ALGORITHM: u64 = 2;
+
+    const SHA2_512: u8 = 1;
+    const SHA3_512: u8 = 2;
+
+    /// The state of a hash, in the current transaction.
+    struct Hasher has drop {
+        handle: u64
+    }
+
+    /// Starts a SHA2-512 hash.
+    public fun sha2_512_init(): Hasher {
+        Hasher { handle: hash_init_internal(SHA2_512) }
+    }
+
+    /// Starts a SHA3-512 hash.
+    public fun sha3_512_init(): Hasher {
+        Hasher { handle: hash_init_internal(SHA3_512) }
+    }
+
+    /// Appends `bytes` to the message hashed by `hasher`.
+    public fun hash_update(hasher: &mut Hasher, bytes: vector<u8>) {
+        hash_update_internal(hasher.handle, bytes)
+    }
+
+    /// Returns the 64-byte hash of the message fed to `hasher`.
+    public fun hash_finalize(hasher: Hasher): vector<u8> {
+        let Hasher { handle } = hasher;
+        hash_finalize_internal(handle)
+    }
+
+    /// Returns the SHA2-512 hash of `bytes`.
+    public fun sha2_512(bytes: vector<u8>): vector<u8> {
+        let hasher = sha2_512_init();
+        hash_update(&mut hasher, bytes);
+        hash_finalize(hasher)
+    }
+
+    /// Returns the SHA3-512 hash of `bytes`.
+    public fun sha3_512(bytes: vector<u8>): vector<u8> {
+        let hasher = sha3_512_init();
+        hash_update(&mut hasher, bytes);
+        hash_finalize(hasher)
+    }
+
+    //
+    // Native functions
+    //
+
+    native fun hash_init_internal(algorithm: u8): u64;
+
+    native fun hash_update_internal(handle: u64, bytes: vector<u8>);
+
+    /// Returns the hash, and releases the hasher of `handle`.
+    native fun hash_finalize_internal(handle: u64): vector<u8>;
+
+    //
+    // Tests
+    //
+
+    #[test]
+    fun test_sha2_512() {
+        let hasher = sha2_512_init();
+        hash_update(&mut hasher, b"a");
+        hash_update(&mut hasher, b"bc");
+        let expected = x"ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+        assert!(hash_finalize(hasher) == expected, 1);
+        assert!(sha2_512(b"abc") == expected, 2);
+    }
+
+    #[test]
+    fun test_sha3_512() {
+        let hasher = sha3_512_init();
+        hash_update(&mut hasher, b"ab");
+        hash_update(&mut hasher, b"");
+        hash_update(&mut hasher, b"c");
+        let expected = x"b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0";
+        assert!(hash_finalize(hasher) == expected, 1);
+        assert!(sha3_512(b"abc") == expected, 2);
+
+        let empty = x"a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26";
+        assert!(hash_finalize(sha3_512_init()) == empty, 3);
+    }
+}
@@ -242,6 +242,9 @@ pub enum EntryFunctionCall {
     VersionSetVersion {
         major: u64,
     },
+
+    /// Releases the coins which have vested since the last release to the beneficiary's balance.
+    VestingVest {},
 }
 
 impl EntryFunctionCall {
@@ -359,6 +362,7 @@ impl EntryFunctionCall {
             ),
             StakeWithdraw { withdraw_amount } => stake_withdraw(withdraw_amount),
             VersionSetVersion { major } => version_set_version(major),
+            VestingVest {} => vesting_vest(),
         }
     }
 
@@ -1008,6 +1012,22 @@ pub fn version_set_version(major: u64) -> TransactionPayload {
         vec![bcs::to_bytes(&major).unwrap()],
     ))
 }
+
+/// Releases the coins which have vested since the last release to the beneficiary's balance.
+pub fn vesting_vest() -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("vesting").to_owned(),
+        ),
+        ident_str!("vest").to_owned(),
+        vec![],
+        vec![],
+    ))
+}
 mod decoder {
     use super::*;
     pub fn account_create_account(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
@@ -1371,6 +1391,14 @@ mod decoder {
             None
         }
     }
+
+    pub fn vesting_vest(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(_script) = payload {
+            Some(EntryFunctionCall::VestingVest {})
+        } else {
+            None
+        }
+    }
 }
 
 type EntryFunctionDecoderMap = std::collections::HashMap<
@@ -1510,5 +1538,6 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "version_set_version".to_string(),
             Box::new(decoder::version_set_version),
         );
+        map.insert("vesting_vest".to_string(), Box::new(decoder::vesting_vest));
         map
     });
@@ -74,6 +74,12 @@ impl ReleaseTarget {
         result
     }
 
+    /// Returns the path (relative to `framework`) of the file where the Rust structs of the
+    /// framework resources are generated, in `aptos-types`.
+    pub fn struct_bindings(self) -> &'static str {
+        "../../types/src/framework_bindings.rs"
+    }
+
     /// Returns the file name under which this particular target's release buundle is stored.
     /// For example, for `Head` the file name will be `head.mrb`.
     pub fn file_name(self) -> String {
@@ -114,6 +120,7 @@ impl ReleaseTarget {
                     }
                 })
                 .collect(),
+            struct_bindings: Some(crate_dir.join(self.struct_bindings())),
             output: if let Some(path) = out {
                 path
             } else {
@@ -53,6 +53,7 @@ pub struct BuiltPackage {
     package_path: PathBuf,
     package: CompiledPackage,
     error_map: Option<ErrorMapping>,
+    options: BuildOptions,
 }
 
 impl BuiltPackage {
@@ -82,6 +83,7 @@ impl BuiltPackage {
             package_path,
             package,
             error_map,
+            options,
         })
     }
 
@@ -114,9 +116,18 @@ impl BuiltPackage {
         let mut modules = vec![];
         for u in &self.package.root_compiled_units {
             let name = u.unit.name().to_string();
-            let source = std::fs::read_to_string(&u.source_path)?;
-            let source = zip_metadata(source.as_bytes())?;
-            let source_map = zip_metadata(&u.unit.serialize_source_map())?;
+            // Modules published without their source can still be depended on, through
+            // interface stubs generated from their bytecode
+            let source = if self.options.with_srcs {
+                zip_metadata(std::fs::read_to_string(&u.source_path)?.as_bytes())?
+            } else {
+                String::new()
+            };
+            let source_map = if self.options.with_source_maps {
+                zip_metadata(&u.unit.serialize_source_map())?
+            } else {
+                String::new()
+            };
             modules.push(ModuleMetadata {
                 name,
                 source,
@@ -0,0 +1,315 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lists the changes of a module upgrade which break the `compatible` upgrade policy. The Move
+//! VM only tells whether an upgrade is compatible, this module tells why it isn't.
+//!
+//! An upgrade is compatible if the structs of the old module keep their abilities, type
+//! parameters and fields, and its public and entry functions keep their visibility and
+//! signature.
+
+use move_deps::{
+    move_binary_format::{
+        access::ModuleAccess,
+        file_format::{
+            Ability, AbilitySet, FunctionDefinition, SignatureToken, StructDefinition,
+            StructFieldInformation, StructHandleIndex, StructTypeParameter, Visibility,
+        },
+        CompiledModule,
+    },
+    move_core_types::{identifier::Identifier, language_storage::ModuleId},
+};
+use std::{collections::BTreeMap, fmt};
+
+/// A change of a module upgrade which breaks compatibility
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncompatibleChange {
+    pub module: ModuleId,
+    /// The struct or function which changed
+    pub member: Identifier,
+    pub kind: IncompatibleChangeKind,
+}
+
+/// What changed. The old and new declarations are rendered as text, as types can't be compared
+/// across modules otherwise.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IncompatibleChangeKind {
+    StructRemoved,
+    StructAbilitiesChanged { old: String, new: String },
+    StructTypeParametersChanged { old: String, new: String },
+    StructLayoutChanged { old: String, new: String },
+    FunctionRemoved,
+    FunctionNoLongerPublic,
+    FunctionNoLongerEntry,
+    FunctionSignatureChanged { old: String, new: String },
+}
+
+impl fmt::Display for IncompatibleChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}: ", self.module.short_str_lossless(), self.member)?;
+        match &self.kind {
+            IncompatibleChangeKind::StructRemoved => write!(f, "struct was removed"),
+            IncompatibleChangeKind::StructAbilitiesChanged { old, new } => {
+                write!(f, "abilities changed from `{}` to `{}`", old, new)
+            }
+            IncompatibleChangeKind::StructTypeParametersChanged { old, new } => {
+                write!(f, "type parameters changed from `{}` to `{}`", old, new)
+            }
+            IncompatibleChangeKind::StructLayoutChanged { old, new } => {
+                write!(f, "fields changed from `{}` to `{}`", old, new)
+            }
+            IncompatibleChangeKind::FunctionRemoved => write!(f, "function was removed"),
+            IncompatibleChangeKind::FunctionNoLongerPublic => {
+                write!(f, "function is no longer public")
+            }
+            IncompatibleChangeKind::FunctionNoLongerEntry => {
+                write!(f, "function is no longer an entry function")
+            }
+            IncompatibleChangeKind::FunctionSignatureChanged { old, new } => {
+                write!(f, "signature changed from `{}` to `{}`", old, new)
+            }
+        }
+    }
+}
+
+/// Returns the changes from `old` to `new` which break compatibility, or nothing if `new` is a
+/// compatible upgrade of `old`
+pub fn check_upgrade_compatibility(
+    old: &CompiledModule,
+    new: &CompiledModule,
+) -> Vec<IncompatibleChange> {
+    let module = old.self_id();
+    let mut changes = vec![];
+    let mut change = |member: &Identifier, kind| {
+        changes.push(IncompatibleChange {
+            module: module.clone(),
+            member: member.clone(),
+            kind,
+        })
+    };
+
+    let new_structs = structs_by_name(new);
+    for (name, old_struct) in structs_by_name(old) {
+        let new_struct = match new_structs.get(&name) {
+            Some(new_struct) => new_struct,
+            None => {
+                change(&name, IncompatibleChangeKind::StructRemoved);
+                continue;
+            }
+        };
+        let (old_handle, new_handle) = (
+            old.struct_handle_at(old_struct.struct_handle),
+            new.struct_handle_at(new_struct.struct_handle),
+        );
+        if old_handle.abilities != new_handle.abilities {
+            change(
+                &name,
+                IncompatibleChangeKind::StructAbilitiesChanged {
+                    old: render_abilities(old_handle.abilities),
+                    new: render_abilities(new_handle.abilities),
+                },
+            );
+        }
+        if old_handle.type_parameters != new_handle.type_parameters {
+            change(
+                &name,
+                IncompatibleChangeKind::StructTypeParametersChanged {
+                    old: render_struct_type_parameters(&old_handle.type_parameters),
+                    new: render_struct_type_parameters(&new_handle.type_parameters),
+                },
+            );
+        }
+        let (old_fields, new_fields) = (
+            render_fields(old, old_struct),
+            render_fields(new, new_struct),
+        );
+        if old_fields != new_fields {
+            change(
+                &name,
+                IncompatibleChangeKind::StructLayoutChanged {
+                    old: old_fields,
+                    new: new_fields,
+                },
+            );
+        }
+    }
+
+    let new_functions = functions_by_name(new);
+    for (name, old_function) in functions_by_name(old) {
+        // Friend functions can only be called from the same address, so they may change freely
+        let is_public = old_function.visibility == Visibility::Public;
+        if !is_public && (!old_function.is_entry || old_function.visibility == Visibility::Friend) {
+            continue;
+        }
+        let new_function = match new_functions.get(&name) {
+            Some(new_function) => new_function,
+            None => {
+                change(&name, IncompatibleChangeKind::FunctionRemoved);
+                continue;
+            }
+        };
+        if is_public && new_function.visibility != Visibility::Public {
+            change(&name, IncompatibleChangeKind::FunctionNoLongerPublic);
+        }
+        if old_function.is_entry && !new_function.is_entry {
+            change(&name, IncompatibleChangeKind::FunctionNoLongerEntry);
+        }
+        let (old_signature, new_signature) = (
+            render_signature(old, old_function),
+            render_signature(new, new_function),
+        );
+        if old_signature != new_signature {
+            change(
+                &name,
+                IncompatibleChangeKind::FunctionSignatureChanged {
+                    old: old_signature,
+                    new: new_signature,
+                },
+            );
+        }
+    }
+    changes
+}
+
+fn structs_by_name(module: &CompiledModule) -> BTreeMap<Identifier, &StructDefinition> {
+    module
+        .struct_defs()
+        .iter()
+        .map(|def| {
+            let handle = module.struct_handle_at(def.struct_handle);
+            (module.identifier_at(handle.name).to_owned(), def)
+        })
+        .collect()
+}
+
+fn functions_by_name(module: &CompiledModule) -> BTreeMap<Identifier, &FunctionDefinition> {
+    module
+        .function_defs()
+        .iter()
+        .map(|def| {
+            let handle = module.function_handle_at(def.function);
+            (module.identifier_at(handle.name).to_owned(), def)
+        })
+        .collect()
+}
+
+fn render_abilities(abilities: AbilitySet) -> String {
+    abilities
+        .into_iter()
+        .map(|ability| match ability {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_struct_type_parameters(type_parameters: &[StructTypeParameter]) -> String {
+    let type_parameters: Vec<_> = type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, type_parameter)| {
+            let phantom = if type_parameter.is_phantom {
+                "phantom "
+            } else {
+                ""
+            };
+            render_type_parameter(phantom, i, type_parameter.constraints)
+        })
+        .collect();
+    format!("<{}>", type_parameters.join(", "))
+}
+
+fn render_type_parameter(prefix: &str, index: usize, constraints: AbilitySet) -> String {
+    if constraints == AbilitySet::EMPTY {
+        format!("{}T{}", prefix, index)
+    } else {
+        let constraints = render_abilities(constraints).replace(", ", " + ");
+        format!("{}T{}: {}", prefix, index, constraints)
+    }
+}
+
+fn render_fields(module: &CompiledModule, def: &StructDefinition) -> String {
+    match &def.field_information {
+        StructFieldInformation::Native => "native".to_string(),
+        StructFieldInformation::Declared(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|field| {
+                    format!(
+                        "{}: {}",
+                        module.identifier_at(field.name),
+                        render_type(module, &field.signature.0)
+                    )
+                })
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+    }
+}
+
+fn render_signature(module: &CompiledModule, def: &FunctionDefinition) -> String {
+    let handle = module.function_handle_at(def.function);
+    let render_types = |tokens: &[SignatureToken]| {
+        tokens
+            .iter()
+            .map(|token| render_type(module, token))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let type_parameters: Vec<_> = handle
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(i, constraints)| render_type_parameter("", i, *constraints))
+        .collect();
+    format!(
+        "<{}>({}): ({})",
+        type_parameters.join(", "),
+        render_types(&module.signature_at(handle.parameters).0),
+        render_types(&module.signature_at(handle.return_).0)
+    )
+}
+
+fn render_type(module: &CompiledModule, token: &SignatureToken) -> String {
+    match token {
+        SignatureToken::Bool => "bool".to_string(),
+        SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U64 => "u64".to_string(),
+        SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::Address => "address".to_string(),
+        SignatureToken::Signer => "signer".to_string(),
+        SignatureToken::Vector(element) => format!("vector<{}>", render_type(module, element)),
+        SignatureToken::Struct(index) => render_struct(module, *index),
+        SignatureToken::StructInstantiation(index, type_args) => {
+            let type_args: Vec<_> = type_args
+                .iter()
+                .map(|type_arg| render_type(module, type_arg))
+                .collect();
+            format!(
+                "{}<{}>",
+                render_struct(module, *index),
+                type_args.join(", ")
+            )
+        }
+        SignatureToken::Reference(inner) => format!("&{}", render_type(module, inner)),
+        SignatureToken::MutableReference(inner) => format!("&mut {}", render_type(module, inner)),
+        SignatureToken::TypeParameter(index) => format!("T{}", index),
+    }
+}
+
+/// The fully qualified name of a struct, which is the same in every module referring to it
+fn render_struct(module: &CompiledModule, index: StructHandleIndex) -> String {
+    let handle = module.struct_handle_at(index);
+    let module_handle = module.module_handle_at(handle.module);
+    format!(
+        "{}::{}::{}",
+        module
+            .address_identifier_at(module_handle.address)
+            .short_str_lossless(),
+        module.identifier_at(module_handle.name),
+        module.identifier_at(handle.name)
+    )
+}
@@ -11,12 +11,17 @@ use std::io::{Read, Write};
 mod built_package;
 pub use built_package::*;
 
+mod compatibility;
+pub use compatibility::*;
+
 mod error_map;
 pub mod natives;
 mod release_builder;
 pub use release_builder::*;
 mod release_bundle;
 pub use release_bundle::*;
+mod struct_bindings;
+pub use struct_bindings::*;
 
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
@@ -63,6 +63,15 @@ pub struct ModuleMetadata {
     pub source_map: String,
 }
 
+/// A package's metadata together with the bytecode of its modules, which allows
+/// tooling to download a whole package at once.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackageBundle {
+    pub metadata: PackageMetadata,
+    /// The bytecode of each module, in the same order as `metadata.modules`.
+    pub modules: Vec<Vec<u8>>,
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct UpgradePolicy {
     pub policy: u8,
@@ -0,0 +1,367 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of `aptos_std::light_client`, which run the expensive steps of light clients of other
+//! chains: the sync committee signatures and block header hashes of the Ethereum beacon chain, and
+//! the commit signatures and header hashes of Tendermint chains.
+
+use crate::{
+    natives::{
+        features::NativeFeaturesContext, status::NFE_FEATURE_NOT_ENABLED,
+        util::make_native_from_func,
+    },
+    pop_vec_arg,
+};
+use aptos_crypto::{bls12381, ed25519, traits::Signature};
+use aptos_types::on_chain_config::FeatureFlag;
+use move_deps::{
+    move_binary_format::errors::PartialVMResult,
+    move_core_types::gas_algebra::{
+        InternalGas, InternalGasPerArg, InternalGasPerByte, NumArgs, NumBytes,
+    },
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use sha2::{Digest, Sha256};
+use smallvec::smallvec;
+use std::{collections::VecDeque, convert::TryFrom};
+
+pub mod abort_codes {
+    /// A hash is not 32 bytes long
+    pub const E_WRONG_HASH_LENGTH: u64 = 1;
+}
+
+fn is_enabled(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<NativeFeaturesContext>()
+        .is_enabled(FeatureFlag::LightClientNatives)
+}
+
+/***************************************************************************************************
+ * native fun eth_sync_committee_verify_internal
+ *
+ *   gas cost: base + (per_pubkey_deserialize + per_pubkey_aggregate) * num_participants
+ *                  + per_sig_verify + per_byte_hashing * |signing_root|
+ *
+ * The sync committee signs with the same ciphersuite as Aptos validators, i.e., BLS12-381 with
+ * signatures in G2 and proofs-of-possession. The keys of the committee are not subgroup-checked
+ * here: the beacon chain verified their proofs-of-possession when the validators deposited.
+ *
+ **************************************************************************************************/
+fn native_eth_sync_committee_verify(
+    gas_params: &SyncCommitteeGasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 4);
+
+    let signature = pop_arg!(arguments, Vec<u8>);
+    let signing_root = pop_arg!(arguments, Vec<u8>);
+    let participation_bits = pop_arg!(arguments, Vec<u8>);
+    let pubkeys = pop_vec_arg!(arguments, Vec<u8>);
+
+    let mut cost = gas_params.base;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let invalid = |cost: InternalGas| -> PartialVMResult<NativeResult> {
+        Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::u64(0), Value::bool(false)],
+        ))
+    };
+
+    // The participation of the members of the committee is an SSZ bitvector: member `i` is bit
+    // `i % 8` of byte `i / 8`
+    if participation_bits.len() * 8 < pubkeys.len() {
+        return invalid(cost);
+    }
+    let participants: Vec<_> = pubkeys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| participation_bits[i / 8] & (1 << (i % 8)) != 0)
+        .map(|(_, pubkey)| pubkey)
+        .collect();
+    if participants.is_empty() {
+        return invalid(cost);
+    }
+    let num_participants = participants.len() as u64;
+
+    cost += gas_params.per_pubkey_deserialize * NumArgs::new(num_participants);
+    let pubkeys = match participants
+        .into_iter()
+        .map(|pubkey| bls12381::PublicKey::try_from(pubkey.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(pubkeys) => pubkeys,
+        Err(_) => return invalid(cost),
+    };
+
+    cost += gas_params.per_pubkey_aggregate * NumArgs::new(num_participants);
+    let aggregate_pubkey = match bls12381::PublicKey::aggregate(pubkeys.iter().collect()) {
+        Ok(aggregate_pubkey) => aggregate_pubkey,
+        Err(_) => return invalid(cost),
+    };
+
+    let signature = match bls12381::Signature::try_from(signature.as_slice()) {
+        Ok(signature) => signature,
+        Err(_) => return invalid(cost),
+    };
+
+    // NOTE(Gas): hashing the signing root to G2, and two pairings
+    cost += gas_params.per_sig_verify * NumArgs::one()
+        + gas_params.per_byte_hashing * NumBytes::new(signing_root.len() as u64);
+    let valid = signature
+        .verify_arbitrary_msg(&signing_root, &aggregate_pubkey)
+        .is_ok();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u64(num_participants), Value::bool(valid)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun eth_beacon_header_root_internal
+ *
+ *   gas cost: base
+ *
+ **************************************************************************************************/
+fn native_eth_beacon_header_root(
+    gas_params: &BeaconHeaderRootGasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 5);
+
+    let body_root = pop_arg!(arguments, Vec<u8>);
+    let state_root = pop_arg!(arguments, Vec<u8>);
+    let parent_root = pop_arg!(arguments, Vec<u8>);
+    let proposer_index = pop_arg!(arguments, u64);
+    let slot = pop_arg!(arguments, u64);
+
+    let cost = gas_params.base;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+    if [&parent_root, &state_root, &body_root]
+        .iter()
+        .any(|root| root.len() != 32)
+    {
+        return Ok(NativeResult::err(cost, abort_codes::E_WRONG_HASH_LENGTH));
+    }
+
+    // The SSZ hash tree root of a `BeaconBlockHeader`: its five fields are the leaves of a Merkle
+    // tree of depth 3, where integers are little-endian and padded to 32 bytes
+    let mut level = vec![[0u8; 32]; 8];
+    level[0][..8].copy_from_slice(&slot.to_le_bytes());
+    level[1][..8].copy_from_slice(&proposer_index.to_le_bytes());
+    level[2].copy_from_slice(&parent_root);
+    level[3].copy_from_slice(&state_root);
+    level[4].copy_from_slice(&body_root);
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                Sha256::new()
+                    .chain(pair[0])
+                    .chain(pair[1])
+                    .finalize()
+                    .into()
+            })
+            .collect();
+    }
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(level[0].to_vec())],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun tendermint_verify_commit_internal
+ *
+ *   gas cost: base + per_sig_verify * num_signatures + per_msg_byte_hashing * sum(|messages|)
+ *
+ * Each signature is verified on its own: a randomized Ed25519 batch may disagree with
+ * `verify_strict` on some invalid signatures, and every validator must reach the same verdict.
+ *
+ **************************************************************************************************/
+fn native_tendermint_verify_commit(
+    gas_params: &TendermintCommitGasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let signatures = pop_vec_arg!(arguments, Vec<u8>);
+    let messages = pop_vec_arg!(arguments, Vec<u8>);
+    let pubkeys = pop_vec_arg!(arguments, Vec<u8>);
+
+    let mut cost = gas_params.base;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+    if pubkeys.len() != messages.len() || pubkeys.len() != signatures.len() {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    }
+
+    for ((pubkey, message), signature) in pubkeys.iter().zip(&messages).zip(&signatures) {
+        cost += gas_params.per_sig_verify * NumArgs::one()
+            + gas_params.per_msg_byte_hashing * NumBytes::new(message.len() as u64);
+
+        let valid = match (
+            ed25519::Ed25519PublicKey::try_from(pubkey.as_slice()),
+            ed25519::Ed25519Signature::try_from(signature.as_slice()),
+        ) {
+            (Ok(pubkey), Ok(signature)) => signature.verify_arbitrary_msg(message, &pubkey).is_ok(),
+            _ => false,
+        };
+        if !valid {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    }
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(true)]))
+}
+
+/***************************************************************************************************
+ * native fun tendermint_merkle_root_internal
+ *
+ *   gas cost: base + per_leaf * num_leaves + per_byte * sum(|leaves|)
+ *
+ **************************************************************************************************/
+fn native_tendermint_merkle_root(
+    gas_params: &TendermintMerkleRootGasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let leaves = pop_vec_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base
+        + gas_params.per_leaf * NumArgs::new(leaves.len() as u64)
+        + gas_params.per_byte
+            * leaves.iter().fold(NumBytes::new(0), |sum, leaf| {
+                sum + NumBytes::new(leaf.len() as u64)
+            });
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(tendermint_merkle_root(&leaves).to_vec())],
+    ))
+}
+
+/// The root of the RFC 6962 Merkle tree of `leaves`, which Tendermint uses for the hashes of
+/// headers and validator sets: the left subtree holds the largest power of two of leaves smaller
+/// than the number of leaves, and leaves and inner nodes are hashed with distinct prefixes
+fn tendermint_merkle_root(leaves: &[Vec<u8>]) -> [u8; 32] {
+    match leaves.len() {
+        0 => Sha256::digest(b"").into(),
+        1 => Sha256::new()
+            .chain(&[0u8])
+            .chain(&leaves[0])
+            .finalize()
+            .into(),
+        n => {
+            let split = n.next_power_of_two() / 2;
+            Sha256::new()
+                .chain(&[1u8])
+                .chain(tendermint_merkle_root(&leaves[..split]))
+                .chain(tendermint_merkle_root(&leaves[split..]))
+                .finalize()
+                .into()
+        }
+    }
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct SyncCommitteeGasParameters {
+    pub base: InternalGas,
+    pub per_pubkey_deserialize: InternalGasPerArg,
+    pub per_pubkey_aggregate: InternalGasPerArg,
+    pub per_sig_verify: InternalGasPerArg,
+    pub per_byte_hashing: InternalGasPerByte,
+}
+
+#[derive(Debug, Clone)]
+pub struct BeaconHeaderRootGasParameters {
+    pub base: InternalGas,
+}
+
+#[derive(Debug, Clone)]
+pub struct TendermintCommitGasParameters {
+    pub base: InternalGas,
+    pub per_sig_verify: InternalGasPerArg,
+    pub per_msg_byte_hashing: InternalGasPerByte,
+}
+
+#[derive(Debug, Clone)]
+pub struct TendermintMerkleRootGasParameters {
+    pub base: InternalGas,
+    pub per_leaf: InternalGasPerArg,
+    pub per_byte: InternalGasPerByte,
+}
+
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub eth_sync_committee_verify: SyncCommitteeGasParameters,
+    pub eth_beacon_header_root: BeaconHeaderRootGasParameters,
+    pub tendermint_verify_commit: TendermintCommitGasParameters,
+    pub tendermint_merkle_root: TendermintMerkleRootGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "eth_sync_committee_verify_internal",
+            make_native_from_func(
+                gas_params.eth_sync_committee_verify,
+                native_eth_sync_committee_verify,
+            ),
+        ),
+        (
+            "eth_beacon_header_root_internal",
+            make_native_from_func(
+                gas_params.eth_beacon_header_root,
+                native_eth_beacon_header_root,
+            ),
+        ),
+        (
+            "tendermint_verify_commit_internal",
+            make_native_from_func(
+                gas_params.tendermint_verify_commit,
+                native_tendermint_verify_commit,
+            ),
+        ),
+        (
+            "tendermint_merkle_root_internal",
+            make_native_from_func(
+                gas_params.tendermint_merkle_root,
+                native_tendermint_merkle_root,
+            ),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
@@ -0,0 +1,221 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of `aptos_std::merkle`, which verify the proofs of `aptos_types::proof` with the same
+//! hash domain separation as the node, so that contracts can check Aptos state and events
+//! on-chain.
+
+use crate::{
+    natives::{
+        features::NativeFeaturesContext, status::NFE_FEATURE_NOT_ENABLED,
+        util::make_native_from_func,
+    },
+    pop_vec_arg,
+};
+use aptos_crypto::HashValue;
+use aptos_types::{
+    on_chain_config::FeatureFlag,
+    proof::{
+        definition::EventAccumulatorProof, SparseMerkleLeafNode, SparseMerkleProof,
+        TransactionAccumulatorProof,
+    },
+};
+use move_deps::{
+    move_binary_format::errors::PartialVMResult,
+    move_core_types::gas_algebra::{InternalGas, InternalGasPerArg, NumArgs},
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub mod abort_codes {
+    /// A hash is not 32 bytes long
+    pub const E_WRONG_HASH_LENGTH: u64 = 1;
+    /// The accumulator is unknown
+    pub const E_UNKNOWN_ACCUMULATOR: u64 = 2;
+}
+
+/// The accumulators, as numbered by `aptos_std::merkle`
+const TRANSACTION_ACCUMULATOR: u8 = 1;
+const EVENT_ACCUMULATOR: u8 = 2;
+
+fn is_enabled(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<NativeFeaturesContext>()
+        .is_enabled(FeatureFlag::MerkleProofNatives)
+}
+
+fn hashes_from_bytes(bytes: &[Vec<u8>]) -> Option<Vec<HashValue>> {
+    bytes
+        .iter()
+        .map(|hash| HashValue::from_slice(hash).ok())
+        .collect()
+}
+
+/// An empty vector stands for a missing hash
+fn optional_hash_from_bytes(bytes: &[u8]) -> Option<Option<HashValue>> {
+    if bytes.is_empty() {
+        Some(None)
+    } else {
+        HashValue::from_slice(bytes).ok().map(Some)
+    }
+}
+
+/***************************************************************************************************
+ * native fun verify_sparse_merkle_proof_internal
+ *
+ *   gas cost: base + per_sibling * num_siblings
+ *
+ **************************************************************************************************/
+fn native_verify_sparse_merkle_proof(
+    gas_params: &SparseMerkleProofGasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 6);
+
+    let siblings = pop_vec_arg!(arguments, Vec<u8>);
+    let leaf_value_hash = pop_arg!(arguments, Vec<u8>);
+    let leaf_key = pop_arg!(arguments, Vec<u8>);
+    let value_hash = pop_arg!(arguments, Vec<u8>);
+    let key = pop_arg!(arguments, Vec<u8>);
+    let root_hash = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_sibling * NumArgs::new(siblings.len() as u64);
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let parsed = (|| {
+        let leaf = match (
+            optional_hash_from_bytes(&leaf_key)?,
+            optional_hash_from_bytes(&leaf_value_hash)?,
+        ) {
+            (Some(key), Some(value_hash)) => Some(SparseMerkleLeafNode::new(key, value_hash)),
+            (None, None) => None,
+            _ => return None,
+        };
+        Some((
+            HashValue::from_slice(&root_hash).ok()?,
+            HashValue::from_slice(&key).ok()?,
+            optional_hash_from_bytes(&value_hash)?,
+            leaf,
+            hashes_from_bytes(&siblings)?,
+        ))
+    })();
+    let (root_hash, key, value_hash, leaf, siblings) = match parsed {
+        Some(parsed) => parsed,
+        None => return Ok(NativeResult::err(cost, abort_codes::E_WRONG_HASH_LENGTH)),
+    };
+
+    let valid = SparseMerkleProof::new(leaf, siblings)
+        .verify_by_hash(root_hash, key, value_hash)
+        .is_ok();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * native fun verify_accumulator_proof_internal
+ *
+ *   gas cost: base + per_sibling * num_siblings
+ *
+ **************************************************************************************************/
+fn native_verify_accumulator_proof(
+    gas_params: &AccumulatorProofGasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 5);
+
+    let siblings = pop_vec_arg!(arguments, Vec<u8>);
+    let element_index = pop_arg!(arguments, u64);
+    let element_hash = pop_arg!(arguments, Vec<u8>);
+    let root_hash = pop_arg!(arguments, Vec<u8>);
+    let accumulator = pop_arg!(arguments, u8);
+
+    let cost = gas_params.base + gas_params.per_sibling * NumArgs::new(siblings.len() as u64);
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let parsed = (|| {
+        Some((
+            HashValue::from_slice(&root_hash).ok()?,
+            HashValue::from_slice(&element_hash).ok()?,
+            hashes_from_bytes(&siblings)?,
+        ))
+    })();
+    let (root_hash, element_hash, siblings) = match parsed {
+        Some(parsed) => parsed,
+        None => return Ok(NativeResult::err(cost, abort_codes::E_WRONG_HASH_LENGTH)),
+    };
+
+    let result = match accumulator {
+        TRANSACTION_ACCUMULATOR => TransactionAccumulatorProof::new(siblings).verify(
+            root_hash,
+            element_hash,
+            element_index,
+        ),
+        EVENT_ACCUMULATOR => {
+            EventAccumulatorProof::new(siblings).verify(root_hash, element_hash, element_index)
+        }
+        _ => return Ok(NativeResult::err(cost, abort_codes::E_UNKNOWN_ACCUMULATOR)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(result.is_ok())],
+    ))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct SparseMerkleProofGasParameters {
+    pub base: InternalGas,
+    pub per_sibling: InternalGasPerArg,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccumulatorProofGasParameters {
+    pub base: InternalGas,
+    pub per_sibling: InternalGasPerArg,
+}
+
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub sparse_merkle_proof: SparseMerkleProofGasParameters,
+    pub accumulator_proof: AccumulatorProofGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "verify_sparse_merkle_proof_internal",
+            make_native_from_func(
+                gas_params.sparse_merkle_proof,
+                native_verify_sparse_merkle_proof,
+            ),
+        ),
+        (
+            "verify_accumulator_proof_internal",
+            make_native_from_func(
+                gas_params.accumulator_proof,
+                native_verify_accumulator_proof,
+            ),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
@@ -3,4 +3,10 @@
 
 pub mod bls12381;
 pub mod ed25519;
+pub mod light_client;
+pub mod merkle;
+pub mod poseidon;
+pub mod ristretto255;
 pub mod secp256k1;
+pub mod secp256r1;
+pub mod streaming_hash;
@@ -0,0 +1,116 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of `aptos_std::poseidon`: the Poseidon hash over the scalar field of BN254, with the
+//! parameters of circomlib, so that hashes computed on-chain match those of circom circuits.
+//!
+//! The permutation comes from `light-poseidon`, a small pure Rust implementation over arkworks
+//! fields, whose circom parameters are tested against circomlib's. Being a native, it must behave
+//! the same on every validator, so it's not behind a feature. `ark-bn254` is the same version
+//! `aptos-types` already uses for keyless proofs.
+
+use crate::{
+    natives::{
+        features::NativeFeaturesContext, status::NFE_FEATURE_NOT_ENABLED,
+        util::make_native_from_func,
+    },
+    pop_vec_arg,
+};
+use aptos_types::on_chain_config::FeatureFlag;
+use ark_bn254::Fr;
+use light_poseidon::{Poseidon, PoseidonBytesHasher};
+use move_deps::{
+    move_binary_format::errors::PartialVMResult,
+    move_core_types::gas_algebra::{InternalGas, InternalGasPerArg, NumArgs},
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, values::Value,
+    },
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub mod abort_codes {
+    /// The number of inputs is not supported
+    pub const E_INVALID_ARITY: u64 = 1;
+    /// An input is not the 32-byte encoding of a scalar
+    pub const E_INVALID_INPUT: u64 = 2;
+}
+
+/// The most inputs of a hash: circomlib has parameters for states of up to 13 scalars, one of
+/// which is the capacity
+pub const MAX_ARITY: usize = 12;
+
+/***************************************************************************************************
+ * native fun poseidon_bn254_internal
+ *
+ *   gas cost: base + per_permutation + per_input * num_inputs
+ *
+ * A hash is a single permutation, of a state of `num_inputs + 1` scalars.
+ *
+ **************************************************************************************************/
+fn native_poseidon_bn254(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let inputs = pop_vec_arg!(arguments, Vec<u8>);
+
+    let mut cost = gas_params.base;
+    let enabled = context
+        .extensions()
+        .get::<NativeFeaturesContext>()
+        .is_enabled(FeatureFlag::PoseidonNatives);
+    if !enabled {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+    if inputs.is_empty() || inputs.len() > MAX_ARITY {
+        return Ok(NativeResult::err(cost, abort_codes::E_INVALID_ARITY));
+    }
+    if inputs.iter().any(|input| input.len() != 32) {
+        return Ok(NativeResult::err(cost, abort_codes::E_INVALID_INPUT));
+    }
+
+    cost += gas_params.per_permutation * NumArgs::one()
+        + gas_params.per_input * NumArgs::new(inputs.len() as u64);
+
+    let mut hasher = match Poseidon::<Fr>::new_circom(inputs.len()) {
+        Ok(hasher) => hasher,
+        Err(_) => return Ok(NativeResult::err(cost, abort_codes::E_INVALID_ARITY)),
+    };
+    let inputs: Vec<&[u8]> = inputs.iter().map(Vec::as_slice).collect();
+    // This fails only on inputs which are not canonical scalars
+    let hash = match hasher.hash_bytes_le(&inputs) {
+        Ok(hash) => hash,
+        Err(_) => return Ok(NativeResult::err(cost, abort_codes::E_INVALID_INPUT)),
+    };
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash.to_vec())],
+    ))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub base: InternalGas,
+    pub per_permutation: InternalGasPerArg,
+    pub per_input: InternalGasPerArg,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [(
+        "poseidon_bn254_internal",
+        make_native_from_func(gas_params, native_poseidon_bn254),
+    )];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
@@ -0,0 +1,685 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of `aptos_std::ristretto255`. Decompressed points live in a per-session table of the
+//! `NativeRistrettoContext`, and Move refers to them with opaque handles, so that a point is only
+//! decompressed once however many operations it takes part in. Scalars can be kept in the table
+//! too, and updated in place, for long computations.
+
+use crate::natives::{
+    features::NativeFeaturesContext, status::NFE_FEATURE_NOT_ENABLED, util::make_native_from_func,
+};
+use aptos_types::on_chain_config::FeatureFlag;
+use better_any::{Tid, TidAble};
+use curve25519_dalek::{
+    constants::RISTRETTO_BASEPOINT_TABLE,
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::{Identity, IsIdentity},
+};
+use move_deps::{
+    move_binary_format::errors::{PartialVMError, PartialVMResult},
+    move_core_types::{
+        gas_algebra::{InternalGas, InternalGasPerByte, NumBytes},
+        vm_status::StatusCode,
+    },
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use sha2::Sha512;
+use smallvec::smallvec;
+use std::{collections::VecDeque, convert::TryFrom};
+
+pub mod abort_codes {
+    /// Too many points were created in the session
+    pub const E_TOO_MANY_POINTS_CREATED: u64 = 1;
+    /// Too many scalars were created in the session
+    pub const E_TOO_MANY_SCALARS_CREATED: u64 = 3;
+}
+
+/// The most points a session can create, which bounds the memory of the point table
+pub const MAX_POINTS_PER_SESSION: usize = 10_000;
+
+/// The most scalars a session can create
+pub const MAX_SCALARS_PER_SESSION: usize = 10_000;
+
+/// The decompressed points and the scalars of the session, indexed by their handles. This needs to
+/// be attached to the NativeContextExtensions value which is passed into session functions.
+#[derive(Default, Tid)]
+pub struct NativeRistrettoContext {
+    points: Vec<RistrettoPoint>,
+    scalars: Vec<Scalar>,
+}
+
+impl NativeRistrettoContext {
+    /// Adds `point` to the table and returns its handle, or `None` if the table is full
+    fn add_point(&mut self, point: RistrettoPoint) -> Option<u64> {
+        if self.points.len() >= MAX_POINTS_PER_SESSION {
+            return None;
+        }
+        self.points.push(point);
+        Some((self.points.len() - 1) as u64)
+    }
+
+    fn point(&self, handle: u64) -> PartialVMResult<&RistrettoPoint> {
+        // Handles can only be created by these natives, in the same session
+        self.points.get(handle as usize).ok_or_else(|| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message(format!("Unknown Ristretto point handle {}", handle))
+        })
+    }
+
+    /// Adds `scalar` to the table and returns its handle, or `None` if the table is full
+    fn add_scalar(&mut self, scalar: Scalar) -> Option<u64> {
+        if self.scalars.len() >= MAX_SCALARS_PER_SESSION {
+            return None;
+        }
+        self.scalars.push(scalar);
+        Some((self.scalars.len() - 1) as u64)
+    }
+
+    fn scalar(&self, handle: u64) -> PartialVMResult<&Scalar> {
+        self.scalars
+            .get(handle as usize)
+            .ok_or_else(|| unknown_scalar(handle))
+    }
+
+    fn scalar_mut(&mut self, handle: u64) -> PartialVMResult<&mut Scalar> {
+        self.scalars
+            .get_mut(handle as usize)
+            .ok_or_else(|| unknown_scalar(handle))
+    }
+}
+
+fn unknown_scalar(handle: u64) -> PartialVMError {
+    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+        .with_message(format!("Unknown Ristretto scalar handle {}", handle))
+}
+
+fn is_enabled(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<NativeFeaturesContext>()
+        .is_enabled(FeatureFlag::Ristretto255Natives)
+}
+
+/// Scalars are checked to be canonical when created in Move
+fn scalar(bytes: Vec<u8>) -> PartialVMResult<Scalar> {
+    <[u8; 32]>::try_from(bytes)
+        .ok()
+        .and_then(Scalar::from_canonical_bytes)
+        .ok_or_else(|| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message("Non-canonical Ristretto scalar".to_string())
+        })
+}
+
+/// Adds `point` to the table of the session, and returns the result of a native returning its
+/// handle
+fn return_point(
+    context: &mut NativeContext,
+    cost: InternalGas,
+    point: RistrettoPoint,
+) -> PartialVMResult<NativeResult> {
+    let ristretto_context = context.extensions_mut().get_mut::<NativeRistrettoContext>();
+    Ok(match ristretto_context.add_point(point) {
+        Some(handle) => NativeResult::ok(cost, smallvec![Value::u64(handle)]),
+        None => NativeResult::err(cost, abort_codes::E_TOO_MANY_POINTS_CREATED),
+    })
+}
+
+/***************************************************************************************************
+ * native fun point_decompress_internal
+ *
+ *   gas cost: point_decompress
+ *
+ **************************************************************************************************/
+fn native_point_decompress(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let cost = gas_params.point_decompress;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let point = <[u8; 32]>::try_from(bytes)
+        .ok()
+        .and_then(|bytes| CompressedRistretto(bytes).decompress());
+    let point = match point {
+        Some(point) => point,
+        None => {
+            return Ok(NativeResult::ok(
+                cost,
+                smallvec![Value::u64(0), Value::bool(false)],
+            ))
+        }
+    };
+
+    let ristretto_context = context.extensions_mut().get_mut::<NativeRistrettoContext>();
+    Ok(match ristretto_context.add_point(point) {
+        Some(handle) => NativeResult::ok(cost, smallvec![Value::u64(handle), Value::bool(true)]),
+        None => NativeResult::err(cost, abort_codes::E_TOO_MANY_POINTS_CREATED),
+    })
+}
+
+/***************************************************************************************************
+ * native fun point_compress_internal
+ *
+ *   gas cost: point_compress
+ *
+ **************************************************************************************************/
+fn native_point_compress(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let handle = pop_arg!(arguments, u64);
+    let ristretto_context = context.extensions().get::<NativeRistrettoContext>();
+    let bytes = ristretto_context
+        .point(handle)?
+        .compress()
+        .to_bytes()
+        .to_vec();
+
+    Ok(NativeResult::ok(
+        gas_params.point_compress,
+        smallvec![Value::vector_u8(bytes)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun point_identity_internal
+ *
+ *   gas cost: point_identity
+ *
+ **************************************************************************************************/
+fn native_point_identity(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(_arguments.is_empty());
+
+    let cost = gas_params.point_identity;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+    return_point(context, cost, RistrettoPoint::identity())
+}
+
+/***************************************************************************************************
+ * native fun basepoint_mul_internal
+ *
+ *   gas cost: basepoint_mul
+ *
+ **************************************************************************************************/
+fn native_basepoint_mul(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let cost = gas_params.basepoint_mul;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    // NOTE(Gas): the multiples of the basepoint are precomputed in a table, which makes this
+    // several times cheaper than `point_mul` of the basepoint.
+    let scalar = scalar(pop_arg!(arguments, Vec<u8>))?;
+    return_point(context, cost, &scalar * &RISTRETTO_BASEPOINT_TABLE)
+}
+
+/***************************************************************************************************
+ * native fun basepoint_double_mul_internal
+ *
+ *   gas cost: basepoint_double_mul
+ *
+ **************************************************************************************************/
+fn native_basepoint_double_mul(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let basepoint_scalar = scalar(pop_arg!(arguments, Vec<u8>))?;
+    let handle = pop_arg!(arguments, u64);
+    let point_scalar = scalar(pop_arg!(arguments, Vec<u8>))?;
+
+    // NOTE(Gas): a single variable-time double-scalar multiplication, cheaper than two
+    // multiplications and an addition. Variable time is fine, as the scalars are public.
+    let point = RistrettoPoint::vartime_double_scalar_mul_basepoint(
+        &point_scalar,
+        context
+            .extensions()
+            .get::<NativeRistrettoContext>()
+            .point(handle)?,
+        &basepoint_scalar,
+    );
+    return_point(context, gas_params.basepoint_double_mul, point)
+}
+
+/***************************************************************************************************
+ * native fun point_mul_internal
+ *
+ *   gas cost: point_mul
+ *
+ **************************************************************************************************/
+fn native_point_mul(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let scalar = scalar(pop_arg!(arguments, Vec<u8>))?;
+    let handle = pop_arg!(arguments, u64);
+
+    let point = context
+        .extensions()
+        .get::<NativeRistrettoContext>()
+        .point(handle)?
+        * &scalar;
+    return_point(context, gas_params.point_mul, point)
+}
+
+/***************************************************************************************************
+ * native fun point_add_internal, point_sub_internal
+ *
+ *   gas cost: point_add
+ *
+ **************************************************************************************************/
+fn native_point_add(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u64);
+    let a = pop_arg!(arguments, u64);
+
+    let ristretto_context = context.extensions().get::<NativeRistrettoContext>();
+    let point = ristretto_context.point(a)? + ristretto_context.point(b)?;
+    return_point(context, gas_params.point_add, point)
+}
+
+fn native_point_sub(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u64);
+    let a = pop_arg!(arguments, u64);
+
+    let ristretto_context = context.extensions().get::<NativeRistrettoContext>();
+    let point = ristretto_context.point(a)? - ristretto_context.point(b)?;
+    return_point(context, gas_params.point_add, point)
+}
+
+/***************************************************************************************************
+ * native fun point_neg_internal
+ *
+ *   gas cost: point_neg
+ *
+ **************************************************************************************************/
+fn native_point_neg(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let handle = pop_arg!(arguments, u64);
+
+    let point = -context
+        .extensions()
+        .get::<NativeRistrettoContext>()
+        .point(handle)?;
+    return_point(context, gas_params.point_neg, point)
+}
+
+/***************************************************************************************************
+ * native fun point_equals_internal
+ *
+ *   gas cost: point_equals
+ *
+ **************************************************************************************************/
+fn native_point_equals(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u64);
+    let a = pop_arg!(arguments, u64);
+
+    let ristretto_context = context.extensions().get::<NativeRistrettoContext>();
+    let equal = ristretto_context.point(a)? == ristretto_context.point(b)?;
+
+    Ok(NativeResult::ok(
+        gas_params.point_equals,
+        smallvec![Value::bool(equal)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun point_is_identity_internal
+ *
+ *   gas cost: point_is_identity
+ *
+ **************************************************************************************************/
+fn native_point_is_identity(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let handle = pop_arg!(arguments, u64);
+    let is_identity = context
+        .extensions()
+        .get::<NativeRistrettoContext>()
+        .point(handle)?
+        .is_identity();
+
+    Ok(NativeResult::ok(
+        gas_params.point_is_identity,
+        smallvec![Value::bool(is_identity)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun scalar_is_canonical_internal
+ *
+ *   gas cost: scalar_is_canonical
+ *
+ **************************************************************************************************/
+fn native_scalar_is_canonical(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let cost = gas_params.scalar_is_canonical;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let canonical = <[u8; 32]>::try_from(bytes)
+        .ok()
+        .and_then(Scalar::from_canonical_bytes)
+        .is_some();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(canonical)]))
+}
+
+/***************************************************************************************************
+ * native fun scalar_from_sha512_internal
+ *
+ *   gas cost: scalar_from_sha512_base + scalar_from_sha512_per_byte * |bytes|
+ *
+ **************************************************************************************************/
+fn native_scalar_from_sha512(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let cost = gas_params.scalar_from_sha512_base
+        + gas_params.scalar_from_sha512_per_byte * NumBytes::new(bytes.len() as u64);
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let scalar = Scalar::hash_from_bytes::<Sha512>(&bytes);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(scalar.to_bytes().to_vec())],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun scalar_load_internal
+ *
+ *   gas cost: scalar_load
+ *
+ **************************************************************************************************/
+fn native_scalar_load(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let cost = gas_params.scalar_load;
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let scalar = scalar(pop_arg!(arguments, Vec<u8>))?;
+
+    let ristretto_context = context.extensions_mut().get_mut::<NativeRistrettoContext>();
+    Ok(match ristretto_context.add_scalar(scalar) {
+        Some(handle) => NativeResult::ok(cost, smallvec![Value::u64(handle)]),
+        None => NativeResult::err(cost, abort_codes::E_TOO_MANY_SCALARS_CREATED),
+    })
+}
+
+/***************************************************************************************************
+ * native fun scalar_store_internal
+ *
+ *   gas cost: scalar_store
+ *
+ **************************************************************************************************/
+fn native_scalar_store(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let handle = pop_arg!(arguments, u64);
+    let ristretto_context = context.extensions().get::<NativeRistrettoContext>();
+    let bytes = ristretto_context.scalar(handle)?.to_bytes().to_vec();
+
+    Ok(NativeResult::ok(
+        gas_params.scalar_store,
+        smallvec![Value::vector_u8(bytes)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun scalar_add_assign_internal, scalar_mul_assign_internal
+ *
+ *   gas cost: scalar_add_assign | scalar_mul_assign
+ *
+ * NOTE(Gas): the result is written over the scalar of the first handle, which spares allocating,
+ * copying and checking a 32-byte encoding for every intermediate value
+ **************************************************************************************************/
+fn native_scalar_add_assign(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u64);
+    let a = pop_arg!(arguments, u64);
+
+    let ristretto_context = context.extensions_mut().get_mut::<NativeRistrettoContext>();
+    let b = *ristretto_context.scalar(b)?;
+    *ristretto_context.scalar_mut(a)? += b;
+
+    Ok(NativeResult::ok(gas_params.scalar_add_assign, smallvec![]))
+}
+
+fn native_scalar_mul_assign(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let b = pop_arg!(arguments, u64);
+    let a = pop_arg!(arguments, u64);
+
+    let ristretto_context = context.extensions_mut().get_mut::<NativeRistrettoContext>();
+    let b = *ristretto_context.scalar(b)?;
+    *ristretto_context.scalar_mut(a)? *= b;
+
+    Ok(NativeResult::ok(gas_params.scalar_mul_assign, smallvec![]))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub basepoint_mul: InternalGas,
+    pub basepoint_double_mul: InternalGas,
+    pub point_add: InternalGas,
+    pub point_compress: InternalGas,
+    pub point_decompress: InternalGas,
+    pub point_equals: InternalGas,
+    pub point_identity: InternalGas,
+    pub point_is_identity: InternalGas,
+    pub point_mul: InternalGas,
+    pub point_neg: InternalGas,
+    pub scalar_is_canonical: InternalGas,
+    pub scalar_from_sha512_base: InternalGas,
+    pub scalar_from_sha512_per_byte: InternalGasPerByte,
+    pub scalar_load: InternalGas,
+    pub scalar_store: InternalGas,
+    pub scalar_add_assign: InternalGas,
+    pub scalar_mul_assign: InternalGas,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "point_decompress_internal",
+            make_native_from_func(gas_params.clone(), native_point_decompress),
+        ),
+        (
+            "point_compress_internal",
+            make_native_from_func(gas_params.clone(), native_point_compress),
+        ),
+        (
+            "point_identity_internal",
+            make_native_from_func(gas_params.clone(), native_point_identity),
+        ),
+        (
+            "basepoint_mul_internal",
+            make_native_from_func(gas_params.clone(), native_basepoint_mul),
+        ),
+        (
+            "basepoint_double_mul_internal",
+            make_native_from_func(gas_params.clone(), native_basepoint_double_mul),
+        ),
+        (
+            "point_mul_internal",
+            make_native_from_func(gas_params.clone(), native_point_mul),
+        ),
+        (
+            "point_add_internal",
+            make_native_from_func(gas_params.clone(), native_point_add),
+        ),
+        (
+            "point_sub_internal",
+            make_native_from_func(gas_params.clone(), native_point_sub),
+        ),
+        (
+            "point_neg_internal",
+            make_native_from_func(gas_params.clone(), native_point_neg),
+        ),
+        (
+            "point_equals_internal",
+            make_native_from_func(gas_params.clone(), native_point_equals),
+        ),
+        (
+            "point_is_identity_internal",
+            make_native_from_func(gas_params.clone(), native_point_is_identity),
+        ),
+        (
+            "scalar_is_canonical_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_is_canonical),
+        ),
+        (
+            "scalar_from_sha512_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_from_sha512),
+        ),
+        (
+            "scalar_load_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_load),
+        ),
+        (
+            "scalar_store_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_store),
+        ),
+        (
+            "scalar_add_assign_internal",
+            make_native_from_func(gas_params.clone(), native_scalar_add_assign),
+        ),
+        (
+            "scalar_mul_assign_internal",
+            make_native_from_func(gas_params, native_scalar_mul_assign),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
@@ -0,0 +1,144 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::{
+    features::NativeFeaturesContext, status::NFE_FEATURE_NOT_ENABLED, util::make_native_from_func,
+};
+use aptos_crypto::{secp256r1_ecdsa, traits::*};
+use aptos_types::on_chain_config::FeatureFlag;
+use move_deps::{
+    move_binary_format::errors::PartialVMResult,
+    move_core_types::gas_algebra::{
+        InternalGas, InternalGasPerArg, InternalGasPerByte, NumArgs, NumBytes,
+    },
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, convert::TryFrom};
+
+fn is_enabled(context: &NativeContext) -> bool {
+    context
+        .extensions()
+        .get::<NativeFeaturesContext>()
+        .is_enabled(FeatureFlag::Secp256r1Natives)
+}
+
+/***************************************************************************************************
+ * native fun public_key_validate_internal
+ *
+ *   gas cost: base_cost + per_pubkey_deserialize_cost
+ *
+ **************************************************************************************************/
+fn native_public_key_validate(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(gas_params.base, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let key_bytes = pop_arg!(arguments, Vec<u8>);
+
+    // NOTE(Gas): O(1) cost: decompression is not needed, but the point-on-curve check is a few
+    // field multiplications
+    let cost = gas_params.base + gas_params.per_pubkey_deserialize * NumArgs::one();
+    let valid = secp256r1_ecdsa::PublicKey::try_from(key_bytes.as_slice()).is_ok();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(valid)]))
+}
+
+/***************************************************************************************************
+ * native fun signature_verify_strict_internal
+ *
+ *   gas cost: base_cost + per_pubkey_deserialize_cost
+ *                       +? ( per_sig_deserialize_cost
+ *                            +? ( per_sig_strict_verify_cost + per_msg_hashing_base_cost
+ *                                 + per_msg_byte_hashing_cost * |msg| ) )
+ *
+ * where +? indicates that the expression stops evaluating there if the previous gas-charging step
+ * failed
+ **************************************************************************************************/
+fn native_signature_verify_strict(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    if !is_enabled(context) {
+        return Ok(NativeResult::err(gas_params.base, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let msg = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let mut cost = gas_params.base;
+
+    cost += gas_params.per_pubkey_deserialize * NumArgs::one();
+    let pk = match secp256r1_ecdsa::PublicKey::try_from(pubkey.as_slice()) {
+        Ok(pk) => pk,
+        Err(_) => {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    };
+
+    // Deserialization rejects signatures with a high `s`
+    cost += gas_params.per_sig_deserialize * NumArgs::one();
+    let sig = match secp256r1_ecdsa::Signature::try_from(signature.as_slice()) {
+        Ok(sig) => sig,
+        Err(_) => {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    };
+
+    // NOTE(Gas): hashing the message with SHA2-256 and a size-2 multi-scalar multiplication
+    cost += gas_params.per_sig_strict_verify * NumArgs::one()
+        + gas_params.per_msg_hashing_base * NumArgs::one()
+        + gas_params.per_msg_byte_hashing * NumBytes::new(msg.len() as u64);
+
+    let verify_result = sig.verify_arbitrary_msg(msg.as_slice(), &pk).is_ok();
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub base: InternalGas,
+    pub per_pubkey_deserialize: InternalGasPerArg,
+    pub per_sig_deserialize: InternalGasPerArg,
+    pub per_sig_strict_verify: InternalGasPerArg,
+    pub per_msg_hashing_base: InternalGasPerArg,
+    pub per_msg_byte_hashing: InternalGasPerByte,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "public_key_validate_internal",
+            make_native_from_func(gas_params.clone(), native_public_key_validate),
+        ),
+        (
+            "signature_verify_strict_internal",
+            make_native_from_func(gas_params, native_signature_verify_strict),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
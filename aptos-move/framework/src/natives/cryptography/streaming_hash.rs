@@ -0,0 +1,210 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of `aptos_std::streaming_hash`. The hashers live in a per-session table of the
+//! `NativeHashContext`, and Move refers to them with opaque handles, so that a message can be
+//! hashed in chunks instead of being concatenated in Move memory first.
+
+use crate::natives::{
+    features::NativeFeaturesContext, status::NFE_FEATURE_NOT_ENABLED, util::make_native_from_func,
+};
+use aptos_types::on_chain_config::FeatureFlag;
+use better_any::{Tid, TidAble};
+use move_deps::{
+    move_binary_format::errors::{PartialVMError, PartialVMResult},
+    move_core_types::{
+        gas_algebra::{InternalGas, InternalGasPerByte, NumBytes},
+        vm_status::StatusCode,
+    },
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use sha2::{Digest, Sha512};
+use sha3::Sha3_512;
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+pub mod abort_codes {
+    /// Too many hashers were created in the session
+    pub const E_TOO_MANY_HASHERS_CREATED: u64 = 1;
+    /// The hash algorithm is unknown
+    pub const E_UNKNOWN_ALGORITHM: u64 = 2;
+}
+
+/// The algorithms, as numbered by `aptos_std::streaming_hash`
+const SHA2_512: u8 = 1;
+const SHA3_512: u8 = 2;
+
+/// The most hashers a session can create, which bounds the memory of the hasher table
+pub const MAX_HASHERS_PER_SESSION: usize = 1_000;
+
+enum StreamingHasher {
+    Sha2_512(Sha512),
+    Sha3_512(Sha3_512),
+}
+
+/// The hashers of the session, indexed by their handles. A finalized hasher leaves an empty slot,
+/// so that handles stay stable. This needs to be attached to the NativeContextExtensions value
+/// which is passed into session functions.
+#[derive(Default, Tid)]
+pub struct NativeHashContext {
+    hashers: Vec<Option<StreamingHasher>>,
+}
+
+impl NativeHashContext {
+    fn hasher_mut(&mut self, handle: u64) -> PartialVMResult<&mut StreamingHasher> {
+        self.hashers
+            .get_mut(handle as usize)
+            .and_then(Option::as_mut)
+            .ok_or_else(|| unknown_hasher(handle))
+    }
+
+    fn take_hasher(&mut self, handle: u64) -> PartialVMResult<StreamingHasher> {
+        self.hashers
+            .get_mut(handle as usize)
+            .and_then(Option::take)
+            .ok_or_else(|| unknown_hasher(handle))
+    }
+}
+
+/// Handles can only be created by these natives, in the same session, and Move consumes them on
+/// finalization
+fn unknown_hasher(handle: u64) -> PartialVMError {
+    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+        .with_message(format!("Unknown hasher handle {}", handle))
+}
+
+/***************************************************************************************************
+ * native fun hash_init_internal
+ *
+ *   gas cost: init
+ *
+ **************************************************************************************************/
+fn native_hash_init(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let cost = gas_params.init;
+    let enabled = context
+        .extensions()
+        .get::<NativeFeaturesContext>()
+        .is_enabled(FeatureFlag::StreamingHashNatives);
+    if !enabled {
+        return Ok(NativeResult::err(cost, NFE_FEATURE_NOT_ENABLED));
+    }
+
+    let hasher = match pop_arg!(arguments, u8) {
+        SHA2_512 => StreamingHasher::Sha2_512(Sha512::new()),
+        SHA3_512 => StreamingHasher::Sha3_512(Sha3_512::new()),
+        _ => return Ok(NativeResult::err(cost, abort_codes::E_UNKNOWN_ALGORITHM)),
+    };
+
+    let hash_context = context.extensions_mut().get_mut::<NativeHashContext>();
+    if hash_context.hashers.len() >= MAX_HASHERS_PER_SESSION {
+        return Ok(NativeResult::err(
+            cost,
+            abort_codes::E_TOO_MANY_HASHERS_CREATED,
+        ));
+    }
+    hash_context.hashers.push(Some(hasher));
+    let handle = (hash_context.hashers.len() - 1) as u64;
+
+    Ok(NativeResult::ok(cost, smallvec![Value::u64(handle)]))
+}
+
+/***************************************************************************************************
+ * native fun hash_update_internal
+ *
+ *   gas cost: update_base + update_per_byte * |bytes|
+ *
+ **************************************************************************************************/
+fn native_hash_update(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let handle = pop_arg!(arguments, u64);
+
+    let cost =
+        gas_params.update_base + gas_params.update_per_byte * NumBytes::new(bytes.len() as u64);
+
+    let hash_context = context.extensions_mut().get_mut::<NativeHashContext>();
+    match hash_context.hasher_mut(handle)? {
+        StreamingHasher::Sha2_512(hasher) => hasher.update(&bytes),
+        StreamingHasher::Sha3_512(hasher) => hasher.update(&bytes),
+    }
+
+    Ok(NativeResult::ok(cost, smallvec![]))
+}
+
+/***************************************************************************************************
+ * native fun hash_finalize_internal
+ *
+ *   gas cost: finalize
+ *
+ **************************************************************************************************/
+fn native_hash_finalize(
+    gas_params: &GasParameters,
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let handle = pop_arg!(arguments, u64);
+
+    let hash_context = context.extensions_mut().get_mut::<NativeHashContext>();
+    let digest = match hash_context.take_hasher(handle)? {
+        StreamingHasher::Sha2_512(hasher) => hasher.finalize().to_vec(),
+        StreamingHasher::Sha3_512(hasher) => hasher.finalize().to_vec(),
+    };
+
+    Ok(NativeResult::ok(
+        gas_params.finalize,
+        smallvec![Value::vector_u8(digest)],
+    ))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub init: InternalGas,
+    pub update_base: InternalGas,
+    pub update_per_byte: InternalGasPerByte,
+    pub finalize: InternalGas,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "hash_init_internal",
+            make_native_from_func(gas_params.clone(), native_hash_init),
+        ),
+        (
+            "hash_update_internal",
+            make_native_from_func(gas_params.clone(), native_hash_update),
+        ),
+        (
+            "hash_finalize_internal",
+            make_native_from_func(gas_params, native_hash_finalize),
+        ),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
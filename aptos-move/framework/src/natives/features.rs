@@ -0,0 +1,23 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::on_chain_config::{EnabledFeatures, FeatureFlag};
+use better_any::{Tid, TidAble};
+
+/// The features enabled in the current epoch, so that natives behind a feature flag can check
+/// it. This needs to be attached to the NativeContextExtensions value which is passed into
+/// session functions.
+#[derive(Tid)]
+pub struct NativeFeaturesContext {
+    features: EnabledFeatures,
+}
+
+impl NativeFeaturesContext {
+    pub fn new(features: EnabledFeatures) -> Self {
+        Self { features }
+    }
+
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        self.features.is_enabled(flag)
+    }
+}
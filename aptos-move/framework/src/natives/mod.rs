@@ -5,9 +5,11 @@ pub mod account;
 pub mod code;
 pub mod cryptography;
 pub mod event;
+pub mod features;
 pub mod hash;
 mod helpers;
 pub mod state_storage;
+pub mod test_env;
 pub mod transaction_context;
 pub mod type_info;
 pub mod util;
@@ -23,6 +25,8 @@ pub mod status {
     pub const NFE_EXPECTED_STRUCT_TYPE_TAG: u64 = 0x1;
     // Failure in address parsing (likely no correct length)
     pub const NFE_UNABLE_TO_PARSE_ADDRESS: u64 = 0x2;
+    // Call to a native which is behind a feature flag that isn't enabled
+    pub const NFE_FEATURE_NOT_ENABLED: u64 = 0x3;
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +35,12 @@ pub struct GasParameters {
     pub ed25519: ed25519::GasParameters,
     pub bls12381: cryptography::bls12381::GasParameters,
     pub secp256k1: cryptography::secp256k1::GasParameters,
+    pub secp256r1: cryptography::secp256r1::GasParameters,
+    pub ristretto255: cryptography::ristretto255::GasParameters,
+    pub streaming_hash: cryptography::streaming_hash::GasParameters,
+    pub merkle: cryptography::merkle::GasParameters,
+    pub light_client: cryptography::light_client::GasParameters,
+    pub poseidon: cryptography::poseidon::GasParameters,
     pub hash: hash::GasParameters,
     pub type_info: type_info::GasParameters,
     pub util: util::GasParameters,
@@ -74,6 +84,78 @@ impl GasParameters {
                 base: 0.into(),
                 ecdsa_recover: 0.into(),
             },
+            secp256r1: cryptography::secp256r1::GasParameters {
+                base: 0.into(),
+                per_pubkey_deserialize: 0.into(),
+                per_sig_deserialize: 0.into(),
+                per_sig_strict_verify: 0.into(),
+                per_msg_hashing_base: 0.into(),
+                per_msg_byte_hashing: 0.into(),
+            },
+            ristretto255: cryptography::ristretto255::GasParameters {
+                basepoint_mul: 0.into(),
+                basepoint_double_mul: 0.into(),
+                point_add: 0.into(),
+                point_compress: 0.into(),
+                point_decompress: 0.into(),
+                point_equals: 0.into(),
+                point_identity: 0.into(),
+                point_is_identity: 0.into(),
+                point_mul: 0.into(),
+                point_neg: 0.into(),
+                scalar_is_canonical: 0.into(),
+                scalar_from_sha512_base: 0.into(),
+                scalar_from_sha512_per_byte: 0.into(),
+                scalar_load: 0.into(),
+                scalar_store: 0.into(),
+                scalar_add_assign: 0.into(),
+                scalar_mul_assign: 0.into(),
+            },
+            streaming_hash: cryptography::streaming_hash::GasParameters {
+                init: 0.into(),
+                update_base: 0.into(),
+                update_per_byte: 0.into(),
+                finalize: 0.into(),
+            },
+            merkle: cryptography::merkle::GasParameters {
+                sparse_merkle_proof: cryptography::merkle::SparseMerkleProofGasParameters {
+                    base: 0.into(),
+                    per_sibling: 0.into(),
+                },
+                accumulator_proof: cryptography::merkle::AccumulatorProofGasParameters {
+                    base: 0.into(),
+                    per_sibling: 0.into(),
+                },
+            },
+            light_client: cryptography::light_client::GasParameters {
+                eth_sync_committee_verify: cryptography::light_client::SyncCommitteeGasParameters {
+                    base: 0.into(),
+                    per_pubkey_deserialize: 0.into(),
+                    per_pubkey_aggregate: 0.into(),
+                    per_sig_verify: 0.into(),
+                    per_byte_hashing: 0.into(),
+                },
+                eth_beacon_header_root: cryptography::light_client::BeaconHeaderRootGasParameters {
+                    base: 0.into(),
+                },
+                tendermint_verify_commit:
+                    cryptography::light_client::TendermintCommitGasParameters {
+                        base: 0.into(),
+                        per_sig_verify: 0.into(),
+                        per_msg_byte_hashing: 0.into(),
+                    },
+                tendermint_merkle_root:
+                    cryptography::light_client::TendermintMerkleRootGasParameters {
+                        base: 0.into(),
+                        per_leaf: 0.into(),
+                        per_byte: 0.into(),
+                    },
+            },
+            poseidon: cryptography::poseidon::GasParameters {
+                base: 0.into(),
+                per_permutation: 0.into(),
+                per_input: 0.into(),
+            },
             hash: hash::GasParameters {
                 sip_hash: hash::SipHashGasParameters {
                     base: 0.into(),
@@ -144,6 +226,27 @@ pub fn all_natives(
         "secp256k1",
         cryptography::secp256k1::make_all(gas_params.secp256k1)
     );
+    add_natives_from_module!(
+        "secp256r1",
+        cryptography::secp256r1::make_all(gas_params.secp256r1)
+    );
+    add_natives_from_module!(
+        "ristretto255",
+        cryptography::ristretto255::make_all(gas_params.ristretto255)
+    );
+    add_natives_from_module!(
+        "streaming_hash",
+        cryptography::streaming_hash::make_all(gas_params.streaming_hash)
+    );
+    add_natives_from_module!("merkle", cryptography::merkle::make_all(gas_params.merkle));
+    add_natives_from_module!(
+        "light_client",
+        cryptography::light_client::make_all(gas_params.light_client)
+    );
+    add_natives_from_module!(
+        "poseidon",
+        cryptography::poseidon::make_all(gas_params.poseidon)
+    );
     add_natives_from_module!("aptos_hash", hash::make_all(gas_params.hash));
     add_natives_from_module!("type_info", type_info::make_all(gas_params.type_info));
     add_natives_from_module!("util", util::make_all(gas_params.util));
@@ -161,6 +264,16 @@ pub fn all_natives(
     make_table_from_iter(framework_addr, natives)
 }
 
+/// The natives of the test-only modules of the framework, which are only registered when running
+/// Move unit tests.
+pub fn all_test_natives(framework_addr: AccountAddress) -> NativeFunctionTable {
+    make_table_from_iter(
+        framework_addr,
+        test_env::make_all(framework_addr)
+            .map(|(func_name, func)| ("test_env".to_string(), func_name, func)),
+    )
+}
+
 /// A temporary hack to patch Table -> table module name as long as it is not upgraded
 /// in the Move repo.
 pub fn patch_table_module(table: NativeFunctionTable) -> NativeFunctionTable {
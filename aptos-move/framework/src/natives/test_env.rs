@@ -0,0 +1,112 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Natives of the test-only `test_env` module, which lets Move unit tests control the wall clock,
+//! the chain id and randomness. They are only registered when running unit tests.
+
+use better_any::{Tid, TidAble};
+use move_deps::{
+    move_binary_format::errors::PartialVMResult,
+    move_core_types::account_address::AccountAddress,
+    move_vm_runtime::native_functions::{NativeContext, NativeFunction},
+    move_vm_types::{
+        loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+    },
+};
+use sha3::{Digest, Sha3_256};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// The randomness of a unit test. The random numbers are derived from the seed and the number of
+/// numbers drawn so far, so a test draws the same numbers every time it runs.
+#[derive(Default, Tid)]
+pub struct NativeTestEnvContext {
+    seed: Vec<u8>,
+    num_draws: u64,
+}
+
+/***************************************************************************************************
+ * native fun create_framework_signer
+ *
+ **************************************************************************************************/
+fn native_create_framework_signer(
+    framework_addr: AccountAddress,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.is_empty());
+
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![Value::signer(framework_addr)],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun set_randomness_seed
+ *
+ **************************************************************************************************/
+fn native_set_randomness_seed(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let seed = pop_arg!(arguments, Vec<u8>);
+    let test_env_context = context.extensions_mut().get_mut::<NativeTestEnvContext>();
+    test_env_context.seed = seed;
+    test_env_context.num_draws = 0;
+
+    Ok(NativeResult::ok(0.into(), smallvec![]))
+}
+
+/***************************************************************************************************
+ * native fun random_u64
+ *
+ **************************************************************************************************/
+fn native_random_u64(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.is_empty());
+
+    let test_env_context = context.extensions_mut().get_mut::<NativeTestEnvContext>();
+    let mut hasher = Sha3_256::new();
+    hasher.update(&test_env_context.seed);
+    hasher.update(test_env_context.num_draws.to_le_bytes());
+    test_env_context.num_draws += 1;
+    let hash = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+
+    Ok(NativeResult::ok(
+        0.into(),
+        smallvec![Value::u64(u64::from_le_bytes(bytes))],
+    ))
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(framework_addr: AccountAddress) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives: [(&str, NativeFunction); 3] = [
+        (
+            "create_framework_signer",
+            Arc::new(move |context, ty_args, args| {
+                native_create_framework_signer(framework_addr, context, ty_args, args)
+            }),
+        ),
+        ("set_randomness_seed", Arc::new(native_set_randomness_seed)),
+        ("random_u64", Arc::new(native_random_u64)),
+    ];
+
+    crate::natives::helpers::make_module_natives(natives)
+}
@@ -4,6 +4,7 @@
 use crate::built_package::{BuildOptions, BuiltPackage};
 use crate::path_relative_to_crate;
 use crate::release_bundle::{ReleaseBundle, ReleasePackage};
+use crate::struct_bindings::generate_struct_bindings;
 use aptos_sdk_builder::rust;
 use clap::Parser;
 use std::path::{Path, PathBuf};
@@ -24,6 +25,10 @@ pub struct ReleaseOptions {
     /// generated.
     #[clap(long)]
     pub rust_bindings: Vec<String>,
+    /// The path where to place the generated Rust structs of the framework resources listed in
+    /// `FRAMEWORK_STRUCT_BINDINGS`. If not given, no structs are generated.
+    #[clap(long, parse(from_os_str))]
+    pub struct_bindings: Option<PathBuf>,
     /// The path to the file where to place the release bundle.
     #[clap(long, default_value = "head.mrb", parse(from_os_str))]
     pub output: PathBuf,
@@ -36,6 +41,7 @@ impl ReleaseOptions {
             build_options,
             packages,
             rust_bindings,
+            struct_bindings,
             output,
         } = self;
         let mut released_packages = vec![];
@@ -64,6 +70,9 @@ impl ReleaseOptions {
             let relative_path = path_relative_to_crate(package_path.join("sources"));
             source_paths.push(relative_path.display().to_string());
         }
+        if let Some(path) = struct_bindings {
+            Self::generate_struct_bindings(&released_packages, &path)?;
+        }
         let bundle = ReleaseBundle::new(released_packages, source_paths);
         std::fs::create_dir_all(&output.parent().unwrap())?;
         std::fs::write(&output, bcs::to_bytes(&bundle)?)?;
@@ -82,4 +91,10 @@ impl ReleaseOptions {
             .status()?;
         Ok(())
     }
+
+    fn generate_struct_bindings(packages: &[ReleasePackage], path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, generate_struct_bindings(packages)?)?;
+        std::process::Command::new("rustfmt").arg(path).status()?;
+        Ok(())
+    }
 }
@@ -0,0 +1,325 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates Rust structs with the layout of framework structs, so that Rust code decoding
+//! resources from storage doesn't rely on hand-written copies which drift from the Move code.
+
+use crate::release_bundle::ReleasePackage;
+use anyhow::{bail, format_err};
+use move_deps::move_binary_format::access::ModuleAccess;
+use move_deps::move_binary_format::file_format::{
+    Ability, SignatureToken, StructFieldInformation, StructHandleIndex,
+};
+use move_deps::move_binary_format::CompiledModule;
+use move_deps::move_core_types::account_address::AccountAddress;
+use move_deps::move_core_types::language_storage::ModuleId;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+/// The structs of the framework for which Rust structs are generated, as (module, struct). The
+/// structs they contain are generated as well.
+pub const FRAMEWORK_STRUCT_BINDINGS: &[(&str, &str)] = &[
+    ("account", "Account"),
+    ("aptos_governance", "GovernanceConfig"),
+    ("aptos_governance", "RecordKey"),
+    ("aptos_governance", "VotingRecords"),
+    ("coin", "CoinStore"),
+    ("governance_proposal", "GovernanceProposal"),
+    ("stake", "StakePool"),
+    ("voting", "Proposal"),
+    ("voting", "VotingForum"),
+];
+
+/// Structs which are represented by an existing Rust type, as (module, struct, Rust type). The
+/// type arguments of `Option` are kept, the ones of the others are phantom.
+const EXISTING_TYPES: &[(&str, &str, &str)] = &[
+    ("event", "EventHandle", "EventHandle"),
+    ("option", "Option", "Option"),
+    ("string", "String", "String"),
+    ("table", "Table", "TableHandle"),
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+const HEADER: &str = "// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// This file was generated. Do not modify!
+//
+// To update this code, run: `cargo run --release -p framework`.
+
+//! Rust structs with the layout of framework structs, for decoding the resources read from
+//! storage. They are generated from the compiled framework, one module per Move module.
+//!
+//! Type parameters which are phantom, or only used as phantom type arguments, are left out.
+//! Generic structs therefore don't implement `MoveStructType`: their type arguments are
+//! chosen where they are used.
+
+#![allow(unused_imports)]
+";
+
+const MODULE_IMPORTS: &str = "use crate::{
+    account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+};
+use move_deps::move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+};
+#[cfg(any(test, feature = \"fuzzing\"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+";
+
+/// Generates the Rust structs for `FRAMEWORK_STRUCT_BINDINGS` from the modules of `packages`.
+pub fn generate_struct_bindings(packages: &[ReleasePackage]) -> anyhow::Result<String> {
+    let modules = packages
+        .iter()
+        .flat_map(|package| package.sorted_code_and_modules())
+        .map(|(_, module)| (module.self_id(), module))
+        .collect::<BTreeMap<_, _>>();
+    let mut generator = StructBindingsGenerator {
+        modules: &modules,
+        structs: BTreeMap::new(),
+    };
+    for (module, name) in FRAMEWORK_STRUCT_BINDINGS {
+        generator.generate_struct(module, name)?;
+    }
+    Ok(generator.output())
+}
+
+/// A generated struct
+struct RustStruct {
+    /// The type parameters of the Move struct
+    num_type_params: usize,
+    /// The type parameters which are kept in the Rust struct
+    kept_type_params: Vec<u16>,
+    is_resource: bool,
+    /// The names and Rust types of the fields
+    fields: Vec<(String, String)>,
+}
+
+struct StructBindingsGenerator<'a> {
+    modules: &'a BTreeMap<ModuleId, CompiledModule>,
+    /// The generated structs by module and name
+    structs: BTreeMap<String, BTreeMap<String, RustStruct>>,
+}
+
+impl<'a> StructBindingsGenerator<'a> {
+    /// Generates the struct `name` of the framework module `module_name`, and the structs of its
+    /// fields, returning the type parameters it keeps.
+    fn generate_struct(&mut self, module_name: &str, name: &str) -> anyhow::Result<Vec<u16>> {
+        if let Some(generated) = self
+            .structs
+            .get(module_name)
+            .and_then(|structs| structs.get(name))
+        {
+            return Ok(generated.kept_type_params.clone());
+        }
+        let module_id = ModuleId::new(AccountAddress::ONE, module_name.parse()?);
+        let modules = self.modules;
+        let module = modules
+            .get(&module_id)
+            .ok_or_else(|| format_err!("Module {} not found", module_id))?;
+        let def = module
+            .struct_defs()
+            .iter()
+            .find(|def| {
+                module
+                    .identifier_at(module.struct_handle_at(def.struct_handle).name)
+                    .as_str()
+                    == name
+            })
+            .ok_or_else(|| format_err!("Struct {}::{} not found", module_id, name))?;
+        let handle = module.struct_handle_at(def.struct_handle);
+        let fields = match &def.field_information {
+            StructFieldInformation::Declared(fields) => fields,
+            StructFieldInformation::Native => bail!("Native struct {}::{}", module_id, name),
+        };
+
+        let mut used_type_params = BTreeSet::new();
+        let mut rust_fields = vec![];
+        for field in fields {
+            let rust_type = self.rust_type(
+                module,
+                module_name,
+                &field.signature.0,
+                &mut used_type_params,
+            )?;
+            rust_fields.push((module.identifier_at(field.name).to_string(), rust_type));
+        }
+        let kept_type_params = used_type_params.into_iter().collect::<Vec<_>>();
+        self.structs
+            .entry(module_name.to_string())
+            .or_default()
+            .insert(
+                name.to_string(),
+                RustStruct {
+                    num_type_params: handle.type_parameters.len(),
+                    kept_type_params: kept_type_params.clone(),
+                    is_resource: handle.abilities.has_ability(Ability::Key),
+                    fields: rust_fields,
+                },
+            );
+        Ok(kept_type_params)
+    }
+
+    /// Returns the Rust type of a field of a struct of `module`, adding the type parameters it
+    /// uses to `used_type_params`.
+    fn rust_type(
+        &mut self,
+        module: &CompiledModule,
+        module_name: &str,
+        token: &SignatureToken,
+        used_type_params: &mut BTreeSet<u16>,
+    ) -> anyhow::Result<String> {
+        Ok(match token {
+            SignatureToken::Bool => "bool".to_string(),
+            SignatureToken::U8 => "u8".to_string(),
+            SignatureToken::U64 => "u64".to_string(),
+            SignatureToken::U128 => "u128".to_string(),
+            SignatureToken::Address => "AccountAddress".to_string(),
+            SignatureToken::Vector(element) => format!(
+                "Vec<{}>",
+                self.rust_type(module, module_name, element, used_type_params)?
+            ),
+            SignatureToken::TypeParameter(idx) => {
+                used_type_params.insert(*idx);
+                format!("T{}", idx)
+            }
+            SignatureToken::Struct(handle) => {
+                self.rust_struct_type(module, module_name, *handle, &[], used_type_params)?
+            }
+            SignatureToken::StructInstantiation(handle, type_args) => {
+                self.rust_struct_type(module, module_name, *handle, type_args, used_type_params)?
+            }
+            SignatureToken::Signer
+            | SignatureToken::Reference(_)
+            | SignatureToken::MutableReference(_) => {
+                bail!("Unexpected field type {:?}", token)
+            }
+        })
+    }
+
+    fn rust_struct_type(
+        &mut self,
+        module: &CompiledModule,
+        module_name: &str,
+        handle: StructHandleIndex,
+        type_args: &[SignatureToken],
+        used_type_params: &mut BTreeSet<u16>,
+    ) -> anyhow::Result<String> {
+        let handle = module.struct_handle_at(handle);
+        let defining_module = module.module_handle_at(handle.module);
+        let address = *module.address_identifier_at(defining_module.address);
+        let defining_module_name = module.identifier_at(defining_module.name).as_str();
+        let name = module.identifier_at(handle.name).as_str();
+        if address != AccountAddress::ONE {
+            bail!(
+                "Struct {}::{}::{} is not in the framework",
+                address,
+                defining_module_name,
+                name
+            );
+        }
+
+        if let Some((_, _, rust_type)) = EXISTING_TYPES
+            .iter()
+            .find(|(module, name_, _)| *module == defining_module_name && *name_ == name)
+        {
+            return Ok(if *rust_type == "Option" {
+                format!(
+                    "Option<{}>",
+                    self.rust_type(module, module_name, &type_args[0], used_type_params)?
+                )
+            } else {
+                rust_type.to_string()
+            });
+        }
+
+        let kept_type_params = self.generate_struct(defining_module_name, name)?;
+        let path = if defining_module_name == module_name {
+            name.to_string()
+        } else {
+            format!("super::{}::{}", defining_module_name, name)
+        };
+        if kept_type_params.is_empty() {
+            return Ok(path);
+        }
+        let rust_type_args = kept_type_params
+            .iter()
+            .map(|idx| {
+                self.rust_type(
+                    module,
+                    module_name,
+                    &type_args[*idx as usize],
+                    used_type_params,
+                )
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(format!("{}<{}>", path, rust_type_args.join(", ")))
+    }
+
+    fn output(&self) -> String {
+        let mut out = HEADER.to_string();
+        for (module_name, structs) in &self.structs {
+            writeln!(out, "\npub mod {} {{", module_name).unwrap();
+            out.push_str(MODULE_IMPORTS);
+            for (name, rust_struct) in structs {
+                let type_params = if rust_struct.kept_type_params.is_empty() {
+                    String::new()
+                } else {
+                    let params = rust_struct
+                        .kept_type_params
+                        .iter()
+                        .map(|idx| format!("T{}", idx))
+                        .collect::<Vec<_>>();
+                    format!("<{}>", params.join(", "))
+                };
+                writeln!(out, "\n/// `0x1::{}::{}`", module_name, name).unwrap();
+                writeln!(
+                    out,
+                    "#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]"
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "#[cfg_attr(any(test, feature = \"fuzzing\"), derive(Arbitrary))]"
+                )
+                .unwrap();
+                writeln!(out, "pub struct {}{} {{", name, type_params).unwrap();
+                for (field, rust_type) in &rust_struct.fields {
+                    let field = if RUST_KEYWORDS.contains(&field.as_str()) {
+                        format!("r#{}", field)
+                    } else {
+                        field.clone()
+                    };
+                    writeln!(out, "pub {}: {},", field, rust_type).unwrap();
+                }
+                writeln!(out, "}}").unwrap();
+
+                if rust_struct.num_type_params == 0 {
+                    writeln!(
+                        out,
+                        "\nimpl MoveStructType for {} {{
+const MODULE_NAME: &'static IdentStr = ident_str!(\"{}\");
+const STRUCT_NAME: &'static IdentStr = ident_str!(\"{}\");
+}}",
+                        name, module_name, name
+                    )
+                    .unwrap();
+                    if rust_struct.is_resource {
+                        writeln!(out, "\nimpl MoveResource for {} {{}}", name).unwrap();
+                    }
+                }
+            }
+            writeln!(out, "}}").unwrap();
+        }
+        out
+    }
+}
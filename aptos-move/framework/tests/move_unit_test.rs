@@ -32,7 +32,7 @@ pub fn aptos_test_natives() -> NativeFunctionTable {
     // By side effect, configure for unit tests
     natives::configure_for_unit_test();
     // move_stdlib has the testing feature enabled to include debug native functions
-    natives::aptos_natives(NativeGasParameters::zeros())
+    natives::aptos_natives_for_unit_test(NativeGasParameters::zeros())
 }
 
 #[test]
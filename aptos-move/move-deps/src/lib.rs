@@ -9,6 +9,7 @@ pub use move_cli;
 pub use move_command_line_common;
 pub use move_compiler;
 pub use move_core_types;
+pub use move_coverage;
 pub use move_docgen;
 pub use move_errmapgen;
 pub use move_ir_compiler;
@@ -17,6 +18,7 @@ pub use move_package;
 pub use move_prover;
 pub use move_prover_test_utils;
 pub use move_resource_viewer;
+pub use move_stackless_bytecode;
 pub use move_stackless_bytecode_interpreter;
 pub use move_stdlib;
 pub use move_symbol_pool;
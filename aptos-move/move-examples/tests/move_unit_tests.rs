@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_types::account_address::AccountAddress;
-use aptos_vm::natives::aptos_natives;
+use aptos_vm::natives::{aptos_natives_for_unit_test, configure_for_unit_test};
 use move_deps::move_cli::base::test::run_move_unit_tests;
 use move_deps::move_unit_test::UnitTestingConfig;
 use std::{collections::BTreeMap, path::PathBuf};
@@ -22,6 +22,7 @@ pub fn run_tests_for_pkg(
     named_addr: BTreeMap<String, AccountAddress>,
 ) {
     let pkg_path = path_in_crate(path_to_pkg);
+    configure_for_unit_test();
     run_move_unit_tests(
         &pkg_path,
         move_deps::move_package::BuildConfig {
@@ -32,7 +33,7 @@ pub fn run_tests_for_pkg(
         },
         UnitTestingConfig::default_with_bound(Some(100_000)),
         // TODO(Gas): we may want to switch to non-zero costs in the future
-        aptos_natives(aptos_gas::NativeGasParameters::zeros()),
+        aptos_natives_for_unit_test(aptos_gas::NativeGasParameters::zeros()),
         /* compute_coverage */ false,
         &mut std::io::stdout(),
     )
@@ -18,7 +18,9 @@ use aptos_types::{
     account_config::{self, events::NewEpochEvent, CORE_CODE_ADDRESS},
     chain_id::ChainId,
     contract_event::ContractEvent,
-    on_chain_config::{ConsensusConfigV1, OnChainConsensusConfig, APTOS_MAX_KNOWN_VERSION},
+    on_chain_config::{
+        ConsensusConfigV1, FeatureFlag, OnChainConsensusConfig, APTOS_MAX_KNOWN_VERSION,
+    },
     transaction::{authenticator::AuthenticationKey, ChangeSet, Transaction, WriteSetPayload},
 };
 use aptos_vm::{
@@ -52,6 +54,8 @@ const MICRO_SECONDS_PER_SECOND: u64 = 1_000_000;
 const APTOS_COINS_BASE_WITH_DECIMALS: u64 = u64::pow(10, 8);
 
 pub struct GenesisConfiguration {
+    /// Accounts created with an initial balance, in addition to the validators.
+    pub accounts: Vec<AccountBalance>,
     pub allow_new_validators: bool,
     pub epoch_duration_secs: u64,
     // If true, genesis will create a special core resources account that can mint coins.
@@ -62,6 +66,8 @@ pub struct GenesisConfiguration {
     pub recurring_lockup_duration_secs: u64,
     pub required_proposer_stake: u64,
     pub rewards_apy_percentage: u64,
+    /// Accounts created with coins released to them over time by a vesting contract.
+    pub vesting_accounts: Vec<VestingAccount>,
     pub voting_duration_secs: u64,
     pub voting_power_increase_limit: u64,
 }
@@ -114,12 +120,16 @@ pub fn encode_genesis_change_set(
 
     // On-chain genesis process.
     initialize(&mut session, consensus_config, chain_id, genesis_config);
+    initialize_features(&mut session);
     if genesis_config.is_test {
         initialize_core_resources_and_aptos_coin(&mut session, core_resources_key);
     } else {
         initialize_aptos_coin(&mut session);
     }
     initialize_on_chain_governance(&mut session, genesis_config);
+    // Accounts must be created before the validators, after which coins can't be minted anymore.
+    create_accounts(&mut session, &genesis_config.accounts);
+    create_vesting_accounts(&mut session, &genesis_config.vesting_accounts);
     create_and_initialize_validators(&mut session, validators);
 
     // Reconfiguration should happen after all on-chain invocations.
@@ -275,6 +285,24 @@ fn initialize(
     );
 }
 
+/// Enables every feature known to this release
+fn initialize_features(session: &mut SessionExt<impl MoveResolver>) {
+    let features = FeatureFlag::ALL
+        .iter()
+        .map(|feature| MoveValue::U64(*feature as u64))
+        .collect();
+    exec_function(
+        session,
+        GENESIS_MODULE_NAME,
+        "initialize_features",
+        vec![],
+        serialize_values(&vec![
+            MoveValue::Signer(CORE_CODE_ADDRESS),
+            MoveValue::Vector(features),
+        ]),
+    );
+}
+
 fn initialize_aptos_coin(session: &mut SessionExt<impl MoveResolver>) {
     exec_function(
         session,
@@ -321,6 +349,37 @@ fn initialize_on_chain_governance(
     );
 }
 
+/// Creates each account and mints its initial balance.
+fn create_accounts(session: &mut SessionExt<impl MoveResolver>, accounts: &[AccountBalance]) {
+    let accounts_bytes = bcs::to_bytes(accounts).expect("Accounts can be serialized");
+    let mut serialized_values = serialize_values(&vec![MoveValue::Signer(CORE_CODE_ADDRESS)]);
+    serialized_values.push(accounts_bytes);
+    exec_function(
+        session,
+        GENESIS_MODULE_NAME,
+        "create_accounts",
+        vec![],
+        serialized_values,
+    );
+}
+
+/// Creates each vesting account, with a vesting contract holding its coins.
+fn create_vesting_accounts(
+    session: &mut SessionExt<impl MoveResolver>,
+    accounts: &[VestingAccount],
+) {
+    let accounts_bytes = bcs::to_bytes(accounts).expect("Vesting accounts can be serialized");
+    let mut serialized_values = serialize_values(&vec![MoveValue::Signer(CORE_CODE_ADDRESS)]);
+    serialized_values.push(accounts_bytes);
+    exec_function(
+        session,
+        GENESIS_MODULE_NAME,
+        "create_vesting_accounts",
+        vec![],
+        serialized_values,
+    );
+}
+
 /// Creates and initializes each validator owner and validator operator. This method creates all
 /// the required accounts, sets the validator operators for each validator owner, and sets the
 /// validator config on-chain.
@@ -454,6 +513,27 @@ pub fn test_genesis_change_set_and_validators(
     generate_test_genesis(cached_packages::head_release_bundle(), count)
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccountBalance {
+    pub account_address: AccountAddress,
+    /// Amount of coins minted to the account.
+    pub balance: u64,
+}
+
+/// An account whose coins are held by a vesting contract. The i-th period after the start vests
+/// `schedule_numerators[i] / schedule_denominator` of the total, and the last fraction keeps
+/// vesting every period once the schedule is exhausted.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VestingAccount {
+    pub account_address: AccountAddress,
+    /// Amount of coins minted to the vesting contract.
+    pub total: u64,
+    pub schedule_numerators: Vec<u64>,
+    pub schedule_denominator: u64,
+    pub start_timestamp_secs: u64,
+    pub period_duration_secs: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Validator {
     /// The Aptos account address of the validator.
@@ -539,6 +619,7 @@ pub fn generate_test_genesis(
         OnChainConsensusConfig::default(),
         ChainId::test(),
         &GenesisConfiguration {
+            accounts: vec![],
             allow_new_validators: true,
             epoch_duration_secs: 3600,
             is_test: true,
@@ -549,6 +630,7 @@ pub fn generate_test_genesis(
             recurring_lockup_duration_secs: 7200,
             required_proposer_stake: 0,
             rewards_apy_percentage: 10,
+            vesting_accounts: vec![],
             voting_duration_secs: 3600,
             voting_power_increase_limit: 50,
         },
@@ -573,6 +655,7 @@ pub fn generate_mainnet_genesis(
         ChainId::test(),
         // TODO: Update once mainnet numbers are decided. These numbers are just placeholders.
         &GenesisConfiguration {
+            accounts: vec![],
             allow_new_validators: true,
             epoch_duration_secs: 2 * 3600, // 2 hours
             is_test: false,
@@ -583,6 +666,7 @@ pub fn generate_mainnet_genesis(
             recurring_lockup_duration_secs: 30 * 24 * 3600,         // 1 month
             required_proposer_stake: 1_000_000 * APTOS_COINS_BASE_WITH_DECIMALS, // 1M APT
             rewards_apy_percentage: 10,
+            vesting_accounts: vec![],
             voting_duration_secs: 7 * 24 * 3600, // 7 days
             voting_power_increase_limit: 30,
         },
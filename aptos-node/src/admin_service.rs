@@ -0,0 +1,327 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure};
+use aptos_api::ApiGateway;
+use aptos_config::{
+    config::{Identity, NetworkConfig, NodeConfig},
+    network_id::NetworkId,
+};
+use aptos_crypto::{x25519, PrivateKey, ValidCryptoMaterial};
+use aptos_logger::prelude::*;
+use aptos_protos::admin::v1::{
+    node_admin_server::{NodeAdmin, NodeAdminServer},
+    DumpConsensusStateRequest, DumpConsensusStateResponse, RotateNetworkKeyRequest,
+    RotateNetworkKeyResponse, RunNetworkBenchmarkRequest, RunNetworkBenchmarkResponse,
+    SetApiEnabledRequest, SetApiEnabledResponse, SetFailpointRequest, SetFailpointResponse,
+    TakeStateSnapshotRequest, TakeStateSnapshotResponse, TriggerPruningRequest,
+    TriggerPruningResponse,
+};
+use aptos_secure_storage::{CryptoStorage, Storage};
+use aptos_types::PeerId;
+use aptosdb::AptosDB;
+use consensus::consensus_provider::ConsensusStateHandle;
+use network_benchmark::NetworkBenchmarkClient;
+use std::{
+    path::PathBuf,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use storage_interface::DbReader;
+use tokio::runtime::{Builder, Runtime};
+use tonic::{transport::Server, Request, Response, Status};
+
+/// How long to wait for consensus to answer a state dump request
+const CONSENSUS_DUMP_TIMEOUT_SECS: u64 = 5;
+
+/// The components of a running node the admin service operates on
+pub struct AdminTargets {
+    pub aptos_db: Arc<AptosDB>,
+    pub api_gateway: ApiGateway,
+    /// Only set on validators, which run consensus
+    pub consensus_state: Option<ConsensusStateHandle>,
+    /// Only set if the network benchmark is enabled
+    pub network_benchmark: Option<NetworkBenchmarkClient>,
+}
+
+/// Serves the `NodeAdmin` gRPC service, see `aptos/admin/v1/admin.proto`
+struct AdminService {
+    config: NodeConfig,
+    snapshot_dir: PathBuf,
+    targets: AdminTargets,
+}
+
+impl AdminService {
+    fn network_config(&self, network_id: NetworkId) -> Option<&NetworkConfig> {
+        self.config
+            .validator_network
+            .iter()
+            .chain(self.config.full_node_networks.iter())
+            .find(|network| network.network_id == network_id)
+    }
+}
+
+fn internal_error(error: impl std::fmt::Display) -> Status {
+    Status::internal(error.to_string())
+}
+
+#[tonic::async_trait]
+impl NodeAdmin for AdminService {
+    async fn trigger_pruning(
+        &self,
+        _request: Request<TriggerPruningRequest>,
+    ) -> Result<Response<TriggerPruningResponse>, Status> {
+        info!("[admin-service] triggering pruning");
+        let (min_readable_state_version, min_readable_ledger_version) = self
+            .targets
+            .aptos_db
+            .trigger_pruning()
+            .map_err(|error| Status::failed_precondition(error.to_string()))?;
+        Ok(Response::new(TriggerPruningResponse {
+            min_readable_state_version,
+            min_readable_ledger_version,
+        }))
+    }
+
+    async fn dump_consensus_state(
+        &self,
+        _request: Request<DumpConsensusStateRequest>,
+    ) -> Result<Response<DumpConsensusStateResponse>, Status> {
+        let consensus_state = self
+            .targets
+            .consensus_state
+            .as_ref()
+            .ok_or_else(|| Status::failed_precondition("Only validators run consensus"))?;
+        let state = tokio::time::timeout(
+            Duration::from_secs(CONSENSUS_DUMP_TIMEOUT_SECS),
+            consensus_state.dump(),
+        )
+        .await
+        .map_err(|_| Status::deadline_exceeded("Consensus didn't respond in time"))?
+        .map_err(|error| Status::unavailable(error.to_string()))?
+        .ok_or_else(|| Status::unavailable("Consensus hasn't started an epoch yet"))?;
+        Ok(Response::new(DumpConsensusStateResponse {
+            state: serde_json::to_string_pretty(&state).map_err(internal_error)?,
+        }))
+    }
+
+    async fn rotate_network_key(
+        &self,
+        request: Request<RotateNetworkKeyRequest>,
+    ) -> Result<Response<RotateNetworkKeyResponse>, Status> {
+        let network_id =
+            NetworkId::from_str(&request.get_ref().network_id).map_err(Status::invalid_argument)?;
+        let network = self
+            .network_config(network_id)
+            .ok_or_else(|| Status::not_found(format!("The node has no {} network", network_id)))?;
+        let identity = match &network.identity {
+            Identity::FromStorage(identity) => identity,
+            _ => {
+                return Err(Status::failed_precondition(
+                    "Only identity keys loaded from secure storage can be rotated",
+                ))
+            }
+        };
+
+        info!(
+            "[admin-service] rotating the identity key of the {} network",
+            network_id
+        );
+        let mut storage: Storage = (&identity.backend).into();
+        storage
+            .rotate_key(&identity.key_name)
+            .map_err(internal_error)?;
+        // As in `NetworkConfig::identity_key`, the key is stored as an Ed25519 key
+        let key = storage
+            .export_private_key(&identity.key_name)
+            .map_err(internal_error)?;
+        let key = x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
+            .map_err(internal_error)?;
+        Ok(Response::new(RotateNetworkKeyResponse {
+            public_key: key.public_key().as_slice().to_vec(),
+        }))
+    }
+
+    async fn take_state_snapshot(
+        &self,
+        _request: Request<TakeStateSnapshotRequest>,
+    ) -> Result<Response<TakeStateSnapshotResponse>, Status> {
+        let aptos_db = self.targets.aptos_db.clone();
+        let version = aptos_db.get_latest_version().map_err(internal_error)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(internal_error)?
+            .as_secs();
+        let path = self
+            .snapshot_dir
+            .join(format!("snapshot_{}_{}", version, timestamp));
+
+        info!("[admin-service] taking a state snapshot in {:?}", path);
+        let checkpoint_path = path.clone();
+        tokio::task::spawn_blocking(move || aptos_db.create_checkpoint(checkpoint_path))
+            .await
+            .map_err(internal_error)?
+            .map_err(internal_error)?;
+        Ok(Response::new(TakeStateSnapshotResponse {
+            path: path.display().to_string(),
+            version,
+        }))
+    }
+
+    async fn set_api_enabled(
+        &self,
+        request: Request<SetApiEnabledRequest>,
+    ) -> Result<Response<SetApiEnabledResponse>, Status> {
+        let enabled = request.get_ref().enabled;
+        let previously_enabled = self.targets.api_gateway.is_enabled();
+        info!(
+            "[admin-service] {} the API",
+            if enabled { "enabling" } else { "disabling" }
+        );
+        self.targets.api_gateway.set_enabled(enabled);
+        Ok(Response::new(SetApiEnabledResponse { previously_enabled }))
+    }
+
+    async fn set_failpoint(
+        &self,
+        request: Request<SetFailpointRequest>,
+    ) -> Result<Response<SetFailpointResponse>, Status> {
+        if !fail::has_failpoints() {
+            return Err(Status::failed_precondition(
+                "The node was built without the `failpoints` feature",
+            ));
+        }
+        let SetFailpointRequest { name, actions } = request.into_inner();
+        if name.is_empty() {
+            return Err(Status::invalid_argument("A failpoint name is required"));
+        }
+        let previous_actions = fail::list()
+            .into_iter()
+            .find(|(failpoint, _)| *failpoint == name)
+            .map(|(_, actions)| actions)
+            .unwrap_or_default();
+
+        info!(
+            "[admin-service] setting failpoint {} to {:?}",
+            name, actions
+        );
+        if actions.is_empty() {
+            fail::remove(&name);
+        } else {
+            fail::cfg(&name, &actions).map_err(Status::invalid_argument)?;
+        }
+        Ok(Response::new(SetFailpointResponse { previous_actions }))
+    }
+
+    async fn run_network_benchmark(
+        &self,
+        request: Request<RunNetworkBenchmarkRequest>,
+    ) -> Result<Response<RunNetworkBenchmarkResponse>, Status> {
+        let network_benchmark =
+            self.targets.network_benchmark.as_ref().ok_or_else(|| {
+                Status::failed_precondition("The network benchmark isn't enabled")
+            })?;
+        let RunNetworkBenchmarkRequest {
+            network_id,
+            peer_id,
+            duration_ms,
+            bytes_per_sec,
+            message_size,
+        } = request.into_inner();
+        let network_id = NetworkId::from_str(&network_id).map_err(Status::invalid_argument)?;
+        let peer_id = PeerId::from_hex_literal(&peer_id)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        info!(
+            "[admin-service] running a network benchmark against {} on the {} network",
+            peer_id, network_id
+        );
+        let result = network_benchmark
+            .run(
+                network_id,
+                peer_id,
+                duration_ms,
+                bytes_per_sec,
+                message_size,
+            )
+            .await
+            .map_err(|error| match error {
+                network_benchmark::Error::InvalidParameters(_) => {
+                    Status::invalid_argument(error.to_string())
+                }
+                network_benchmark::Error::AlreadyRunning => {
+                    Status::resource_exhausted(error.to_string())
+                }
+                network_benchmark::Error::NetworkNotEnabled(_) => {
+                    Status::failed_precondition(error.to_string())
+                }
+            })?;
+        Ok(Response::new(RunNetworkBenchmarkResponse {
+            messages_acknowledged: result.messages_acknowledged,
+            messages_failed: result.messages_failed,
+            bytes_acknowledged: result.bytes_acknowledged,
+            duration_ms: result.duration.as_millis() as u64,
+            throughput_bytes_per_sec: result.throughput_bytes_per_sec,
+            min_latency_us: result.min_latency.as_micros() as u64,
+            avg_latency_us: result.avg_latency.as_micros() as u64,
+            p50_latency_us: result.p50_latency.as_micros() as u64,
+            p99_latency_us: result.p99_latency.as_micros() as u64,
+            max_latency_us: result.max_latency.as_micros() as u64,
+            jitter_us: result.jitter.as_micros() as u64,
+        }))
+    }
+}
+
+/// Starts the admin service, if it's enabled. It refuses to start on an address other than a
+/// loopback one, or without an auth token.
+pub fn start_admin_service(
+    config: &NodeConfig,
+    targets: AdminTargets,
+) -> anyhow::Result<Option<Runtime>> {
+    let admin_config = &config.admin_service;
+    if !admin_config.enabled {
+        return Ok(None);
+    }
+    ensure!(
+        admin_config.address.ip().is_loopback(),
+        "The admin service must listen on a loopback address, not {}",
+        admin_config.address
+    );
+    ensure!(
+        !admin_config.auth_token.is_empty(),
+        "The admin service requires an auth token"
+    );
+
+    let runtime = Builder::new_multi_thread()
+        .thread_name("admin-service")
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to create admin service runtime {}", err))?;
+    let service = AdminService {
+        config: config.clone(),
+        snapshot_dir: admin_config
+            .snapshot_dir
+            .clone()
+            .unwrap_or_else(|| config.base.data_dir.join("snapshots")),
+        targets,
+    };
+    let authorization = format!("Bearer {}", admin_config.auth_token);
+    let check_auth = move |request: Request<()>| match request.metadata().get("authorization") {
+        Some(value) if value == authorization.as_str() => Ok(request),
+        _ => Err(Status::unauthenticated("Invalid or missing auth token")),
+    };
+
+    let address = admin_config.address;
+    runtime.spawn(async move {
+        info!("[admin-service] serving on {}", address);
+        if let Err(err) = Server::builder()
+            .add_service(NodeAdminServer::with_interceptor(service, check_auth))
+            .serve(address)
+            .await
+        {
+            error!("[admin-service] server failed: {}", err);
+        }
+    });
+    Ok(Some(runtime))
+}
@@ -0,0 +1,190 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use aptos_api::ApiGateway;
+use aptos_config::config::{NodeConfig, ReloadableConfig};
+use aptos_logger::{prelude::*, Filter, Logger};
+use aptos_mempool::{MempoolCapacityHandle, TransactionFilter};
+use data_streaming_service::streaming_service::StreamConcurrencyHandle;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    runtime::{Builder, Runtime},
+    signal::unix::{signal, SignalKind},
+    time::interval,
+};
+
+/// How often the config file is checked for changes, in addition to checking on SIGHUP
+const CONFIG_POLL_INTERVAL_SECS: u64 = 10;
+
+/// The running components whose config can be changed without a restart
+pub struct ReloadTargets {
+    pub logger: Arc<Logger>,
+    pub api_gateway: ApiGateway,
+    pub mempool: MempoolCapacityHandle,
+    pub stream_concurrency: StreamConcurrencyHandle,
+    pub transaction_filter: TransactionFilter,
+}
+
+impl ReloadTargets {
+    /// Applies the fields of `config` which differ from `current`. The transaction filter and
+    /// API gateway are updated first, as they're the only updates which can fail, so that a
+    /// rejected config changes nothing.
+    fn apply(&self, current: &ReloadableConfig, config: &ReloadableConfig) -> anyhow::Result<()> {
+        if current.transaction_filter != config.transaction_filter {
+            // Checked on its own first, so that it's left unchanged if the API gateway is invalid
+            TransactionFilter::new(&config.transaction_filter)
+                .map_err(|error| anyhow!("Invalid transaction filter config: {}", error))?;
+        }
+        if current.api_gateway != config.api_gateway {
+            self.api_gateway
+                .update(&config.api_gateway)
+                .map_err(|error| anyhow!("Invalid API gateway config: {}", error))?;
+        }
+        if current.transaction_filter != config.transaction_filter {
+            self.transaction_filter
+                .update(&config.transaction_filter)
+                .map_err(|error| anyhow!("Invalid transaction filter config: {}", error))?;
+        }
+        if current.log_level != config.log_level {
+            // As on startup, the environment takes precedence over the config
+            if std::env::var("RUST_LOG").is_ok() {
+                warn!("RUST_LOG is set, so the log level in the config is ignored");
+            } else {
+                self.logger.set_filter(
+                    Filter::builder()
+                        .filter_level(config.log_level.into())
+                        .build(),
+                );
+            }
+        }
+        if current.mempool_capacity != config.mempool_capacity
+            || current.mempool_capacity_per_user != config.mempool_capacity_per_user
+        {
+            self.mempool
+                .update_capacity(config.mempool_capacity, config.mempool_capacity_per_user);
+        }
+        if current.max_concurrent_requests != config.max_concurrent_requests
+            || current.max_concurrent_state_requests != config.max_concurrent_state_requests
+        {
+            self.stream_concurrency.update_concurrency(
+                config.max_concurrent_requests,
+                config.max_concurrent_state_requests,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Reloads the reloadable part of the node config from its file on SIGHUP, or when the file is
+/// modified. Each applied change is logged. If the file can't be loaded, or changes any field
+/// which requires a restart, the whole file is rejected and the running config is unchanged.
+struct ConfigWatcher {
+    config_path: PathBuf,
+    /// The config the node was started with, which the non-reloadable fields must match
+    startup_config: NodeConfig,
+    current: ReloadableConfig,
+    targets: ReloadTargets,
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.config_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn reload(&mut self, trigger: &str) {
+        let config = NodeConfig::load(&self.config_path)
+            .map_err(|error| anyhow!("Failed to load the config: {}", error))
+            .and_then(|new_config| {
+                ReloadableConfig::from_reload(&self.startup_config, &new_config)
+                    .map_err(anyhow::Error::from)
+            });
+        let config = match config {
+            Ok(config) => config,
+            Err(error) => {
+                error!(trigger = trigger, "Rejected node config reload: {}", error);
+                return;
+            }
+        };
+
+        let changes = self.current.changes(&config);
+        if changes.is_empty() {
+            return;
+        }
+        if let Err(error) = self.targets.apply(&self.current, &config) {
+            error!(trigger = trigger, "Rejected node config reload: {}", error);
+            return;
+        }
+        for change in changes {
+            info!(
+                trigger = trigger,
+                change = change,
+                "Applied node config change"
+            );
+        }
+        self.current = config;
+    }
+
+    async fn run(mut self) {
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => Some(hangup),
+            Err(error) => {
+                warn!(
+                    "Failed to listen for SIGHUP, only watching the config file: {}",
+                    error
+                );
+                None
+            }
+        };
+        let mut poll_interval = interval(Duration::from_secs(CONFIG_POLL_INTERVAL_SECS));
+        loop {
+            let on_hangup = async {
+                match hangup.as_mut() {
+                    Some(hangup) => hangup.recv().await,
+                    None => futures::future::pending::<Option<()>>().await,
+                }
+            };
+            tokio::select! {
+                _ = on_hangup => self.reload("sighup"),
+                _ = poll_interval.tick() => {
+                    let modified = self.modified();
+                    if modified != self.last_modified {
+                        self.last_modified = modified;
+                        self.reload("file_change");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Starts watching the config file at `config_path`, which `config` was loaded from
+pub fn start_config_watcher(
+    config_path: PathBuf,
+    config: &NodeConfig,
+    targets: ReloadTargets,
+) -> anyhow::Result<Runtime> {
+    let runtime = Builder::new_multi_thread()
+        .thread_name("config-watcher")
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to create config watcher runtime {}", err))?;
+    let mut watcher = ConfigWatcher {
+        config_path,
+        startup_config: config.clone(),
+        current: ReloadableConfig::new(config),
+        targets,
+        last_modified: None,
+    };
+    watcher.last_modified = watcher.modified();
+    runtime.spawn(watcher.run());
+    Ok(runtime)
+}
@@ -3,8 +3,13 @@
 
 #![forbid(unsafe_code)]
 
+mod admin_service;
+mod config_watcher;
+mod table_info_service;
+
+use admin_service::{start_admin_service, AdminTargets};
 use anyhow::anyhow;
-use aptos_api::bootstrap as bootstrap_api;
+use aptos_api::{bootstrap as bootstrap_api, ApiGateway};
 use aptos_config::{
     config::{
         AptosDataClientConfig, BaseConfig, DataStreamingServiceConfig, NetworkConfig, NodeConfig,
@@ -16,8 +21,10 @@ use aptos_config::{
 use aptos_data_client::aptosnet::AptosNetDataClient;
 use aptos_fh_stream::runtime::bootstrap as bootstrap_fh_stream;
 use aptos_infallible::RwLock;
-use aptos_logger::{prelude::*, Level};
+use aptos_logger::{prelude::*, Level, Writer};
+use aptos_mempool::{MempoolCapacityHandle, TransactionFilter};
 use aptos_state_view::account_with_state_view::AsAccountWithStateView;
+use aptos_telemetry::crash_report::{crash_reports_enabled, RecentLogsWriter};
 use aptos_time_service::TimeService;
 use aptos_types::{
     account_config::CORE_CODE_ADDRESS, account_view::AccountView, chain_id::ChainId,
@@ -27,11 +34,12 @@ use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
 use backup_service::start_backup_service;
 use clap::Parser;
-use consensus::consensus_provider::start_consensus;
+use config_watcher::{start_config_watcher, ReloadTargets};
+use consensus::{consensus_provider::start_consensus, validator_txn_pool::ValidatorTxnPool};
 use consensus_notifications::ConsensusNotificationListener;
 use data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
-    streaming_service::DataStreamingService,
+    streaming_service::{DataStreamingService, StreamConcurrencyHandle},
 };
 use event_notifications::EventSubscriptionService;
 use executor::{chunk_executor::ChunkExecutor, db_bootstrapper::maybe_bootstrap};
@@ -40,6 +48,7 @@ use futures::channel::mpsc::channel;
 use hex::FromHex;
 use mempool_notifications::MempoolNotificationSender;
 use network::application::storage::PeerMetadataStorage;
+use network_benchmark::{NetworkBenchmarkClient, NetworkBenchmarkServer};
 use network_builder::builder::NetworkBuilder;
 use rand::{rngs::StdRng, SeedableRng};
 use state_sync_driver::{
@@ -140,14 +149,15 @@ impl AptosNodeArgs {
             });
             println!("Using node config {:?}", &config);
 
-            // Start the node
-            start(config, None).expect("Node should start correctly");
+            // Start the node, reloading the config when the file changes
+            start(config, Some(config_path), None).expect("Node should start correctly");
         };
     }
 }
 
 /// Runtime handle to ensure that all inner runtimes stay in scope
 pub struct AptosHandle {
+    _admin_service: Option<Runtime>,
     _api: Runtime,
     _backup: Runtime,
     _consensus_runtime: Option<Runtime>,
@@ -155,11 +165,23 @@ pub struct AptosHandle {
     _network_runtimes: Vec<Runtime>,
     _fh_stream: Option<Runtime>,
     _state_sync_runtimes: StateSyncRuntimes,
+    _table_info_service: Option<Runtime>,
     _telemetry_runtime: Option<Runtime>,
+    _transaction_log_runtime: Option<Runtime>,
+    _transaction_tracing_runtime: Option<Runtime>,
+    api_gateway: ApiGateway,
+    mempool_capacity: MempoolCapacityHandle,
+    stream_concurrency: StreamConcurrencyHandle,
+    transaction_filter: TransactionFilter,
 }
 
-/// Start an aptos node
-pub fn start(config: NodeConfig, log_file: Option<PathBuf>) -> anyhow::Result<()> {
+/// Start an aptos node. If `config_path` is given, the reloadable part of the config is reloaded
+/// from it on SIGHUP or when the file changes.
+pub fn start(
+    config: NodeConfig,
+    config_path: Option<PathBuf>,
+    log_file: Option<PathBuf>,
+) -> anyhow::Result<()> {
     crash_handler::setup_panic_handler();
 
     let mut logger = aptos_logger::Logger::new();
@@ -172,10 +194,17 @@ pub fn start(config: NodeConfig, log_file: Option<PathBuf>) -> anyhow::Result<()
     if config.logger.enable_backtrace {
         logger.enable_backtrace();
     }
-    if let Some(log_file) = log_file {
-        logger.printer(Box::new(FileWriter::new(log_file)));
+    let printer: Box<dyn Writer + Send + Sync> = match log_file {
+        Some(log_file) => Box::new(FileWriter::new(log_file)),
+        None => Box::new(StdoutWriter::new()),
+    };
+    if crash_reports_enabled() {
+        // Keep the most recent logs for crash reports
+        logger.printer(Box::new(RecentLogsWriter::new(printer)));
+    } else {
+        logger.printer(printer);
     }
-    let _logger = Some(logger.build());
+    let logger = logger.build();
 
     // Let's now log some important information, since the logger is set up
     info!(config = config, "Loaded AptosNode config");
@@ -191,7 +220,21 @@ pub fn start(config: NodeConfig, log_file: Option<PathBuf>) -> anyhow::Result<()
         warn!("failpoints is set in config, but the binary doesn't compile with this feature");
     }
 
-    let _node_handle = setup_environment(config)?;
+    let node_handle = setup_environment(config.clone())?;
+    let _config_watcher = match config_path {
+        Some(config_path) => Some(start_config_watcher(
+            config_path,
+            &config,
+            ReloadTargets {
+                logger,
+                api_gateway: node_handle.api_gateway.clone(),
+                mempool: node_handle.mempool_capacity.clone(),
+                stream_concurrency: node_handle.stream_concurrency.clone(),
+                transaction_filter: node_handle.transaction_filter.clone(),
+            },
+        )?),
+        None => None,
+    };
     let term = Arc::new(AtomicBool::new(false));
 
     while !term.load(Ordering::Acquire) {
@@ -301,7 +344,7 @@ where
 
     println!("\nAptos is running, press ctrl-c to exit\n");
 
-    start(config, Some(log_file))
+    start(config, None, Some(log_file))
 }
 
 // Fetch chain ID from on-chain resource
@@ -331,7 +374,7 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
-) -> anyhow::Result<StateSyncRuntimes> {
+) -> anyhow::Result<(StateSyncRuntimes, StreamConcurrencyHandle)> {
     // Start the state sync storage service
     let storage_service_runtime = setup_state_sync_storage_service(
         node_config.state_sync.storage_service,
@@ -349,10 +392,11 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
     )?;
 
     // Start the data streaming service
-    let (streaming_service_client, streaming_service_runtime) = setup_data_streaming_service(
-        node_config.state_sync.data_streaming_service,
-        aptos_data_client.clone(),
-    )?;
+    let (streaming_service_client, stream_concurrency, streaming_service_runtime) =
+        setup_data_streaming_service(
+            node_config.state_sync.data_streaming_service,
+            aptos_data_client.clone(),
+        )?;
 
     // Create the chunk executor and persistent storage
     let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new(db_rw.clone()));
@@ -374,23 +418,25 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
     );
 
     // Create and return the new state sync handle
-    Ok(StateSyncRuntimes::new(
+    let state_sync_runtimes = StateSyncRuntimes::new(
         aptos_data_client_runtime,
         state_sync,
         storage_service_runtime,
         streaming_service_runtime,
-    ))
+    );
+    Ok((state_sync_runtimes, stream_concurrency))
 }
 
 fn setup_data_streaming_service(
     config: DataStreamingServiceConfig,
     aptos_data_client: AptosNetDataClient,
-) -> anyhow::Result<(StreamingServiceClient, Runtime)> {
+) -> anyhow::Result<(StreamingServiceClient, StreamConcurrencyHandle, Runtime)> {
     // Create the data streaming service
     let (streaming_service_client, streaming_service_listener) =
         new_streaming_service_client_listener_pair();
     let data_streaming_service =
         DataStreamingService::new(config, aptos_data_client, streaming_service_listener);
+    let stream_concurrency = data_streaming_service.concurrency_handle();
 
     // Start the data streaming service
     let streaming_service_runtime = Builder::new_multi_thread()
@@ -400,7 +446,11 @@ fn setup_data_streaming_service(
         .map_err(|err| anyhow!("Failed to create data streaming service {}", err))?;
     streaming_service_runtime.spawn(data_streaming_service.start_service());
 
-    Ok((streaming_service_client, streaming_service_runtime))
+    Ok((
+        streaming_service_client,
+        stream_concurrency,
+        streaming_service_runtime,
+    ))
 }
 
 fn setup_aptos_data_client(
@@ -472,20 +522,28 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
         inspection_service::inspection_service::start_inspection_service(node_config_clone)
     });
 
+    // Start exporting transaction traces, before any component can record them
+    let transaction_tracing_runtime =
+        aptos_transaction_tracing::init(&node_config.transaction_tracing)?;
+
     // Open the database
     let mut instant = Instant::now();
-    let (aptos_db, db_rw) = DbReaderWriter::wrap(
-        AptosDB::open(
-            &node_config.storage.dir(),
-            false, /* readonly */
-            node_config.storage.storage_pruner_config,
-            node_config.storage.rocksdb_configs,
-            node_config.storage.enable_indexer,
-            node_config.storage.target_snapshot_size,
-            node_config.storage.max_num_nodes_per_lru_cache_shard,
-        )
-        .map_err(|err| anyhow!("DB failed to open {}", err))?,
-    );
+    let aptos_db = AptosDB::open(
+        &node_config.storage.dir(),
+        false, /* readonly */
+        node_config.storage.storage_pruner_config,
+        node_config.storage.rocksdb_configs,
+        node_config.storage.enable_indexer,
+        node_config.storage.target_snapshot_size,
+        node_config.storage.max_num_nodes_per_lru_cache_shard,
+    )
+    .map_err(|err| anyhow!("DB failed to open {}", err))?;
+    aptos_db
+        .enable_hot_state_cache(&node_config.storage.hot_state_cache)
+        .map_err(|err| anyhow!("Failed to enable the hot state cache {}", err))?;
+    let (aptos_db, db_rw) = DbReaderWriter::wrap(aptos_db);
+    let table_info_service_runtime =
+        table_info_service::bootstrap(&node_config, Arc::clone(&aptos_db))?;
     let backup_service = start_backup_service(
         node_config.storage.backup_service_address,
         Arc::clone(&aptos_db),
@@ -503,6 +561,11 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
     AptosVM::set_num_proof_reading_threads_once(
         node_config.execution.num_proof_reading_threads as usize,
     );
+    if node_config.execution.entry_function_metrics {
+        AptosVM::set_entry_function_metrics_once(
+            node_config.execution.entry_function_metrics_max_functions as usize,
+        );
+    }
 
     debug!(
         "Storage service started in {} ms",
@@ -515,6 +578,7 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
     let mut consensus_network_handles = None;
     let mut storage_service_server_network_handles = vec![];
     let mut storage_service_client_network_handles = HashMap::new();
+    let mut network_benchmark_senders = HashMap::new();
 
     // Create an event subscription service so that components can be notified of events and reconfigs
     let mut event_subscription_service = EventSubscriptionService::new(
@@ -605,6 +669,22 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
         );
         mempool_network_handles.push((network_id, mempool_sender, mempool_events));
 
+        // Register the network benchmark, if the operator opted in
+        if node_config.network_benchmark.enabled {
+            let (network_benchmark_sender, network_benchmark_events) = network_builder
+                .add_p2p_service(&network_benchmark::network_endpoint_config(
+                    &node_config.network_benchmark,
+                ));
+            network_benchmark_senders.insert(network_id, network_benchmark_sender);
+            runtime.spawn(
+                NetworkBenchmarkServer::new(
+                    node_config.network_benchmark.clone(),
+                    network_benchmark_events,
+                )
+                .start(),
+            );
+        }
+
         // Perform steps relevant specifically to Validator networks.
         if network_id.is_validator_network() {
             // A valid config is allowed to have at most one ValidatorNetwork
@@ -641,7 +721,7 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
         );
 
     // Create the state sync runtimes
-    let state_sync_runtimes = create_state_sync_runtimes(
+    let (state_sync_runtimes, stream_concurrency) = create_state_sync_runtimes(
         &node_config,
         storage_service_server_network_handles,
         storage_service_client_network_handles,
@@ -655,23 +735,30 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
 
     let (mp_client_sender, mp_client_events) = channel(AC_SMP_CHANNEL_BUFFER_SIZE);
 
-    let api_runtime = bootstrap_api(
+    // The API and mempool share the transaction filter, so reloading it updates both
+    let transaction_filter = TransactionFilter::new(&node_config.transaction_filter)
+        .map_err(|error| anyhow!("Invalid transaction filter config: {}", error))?;
+    let (api_runtime, api_gateway) = bootstrap_api(
         &node_config,
         chain_id,
         aptos_db.clone(),
         mp_client_sender.clone(),
+        transaction_filter.clone(),
     )?;
-    let sf_runtime = match bootstrap_fh_stream(&node_config, chain_id, aptos_db, mp_client_sender) {
-        None => None,
-        Some(res) => Some(res?),
-    };
+    let sf_runtime =
+        match bootstrap_fh_stream(&node_config, chain_id, aptos_db.clone(), mp_client_sender) {
+            None => None,
+            Some(res) => Some(res?),
+        };
+    let transaction_log_runtime = aptos_transaction_log::bootstrap(&node_config, aptos_db.clone())?;
 
     let mut consensus_runtime = None;
+    let mut consensus_state = None;
     let (consensus_to_mempool_sender, consensus_to_mempool_receiver) =
         channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
 
     instant = Instant::now();
-    let mempool = aptos_mempool::bootstrap(
+    let (mempool, mempool_capacity) = aptos_mempool::bootstrap(
         &node_config,
         Arc::clone(&db_rw.reader),
         mempool_network_handles,
@@ -680,6 +767,7 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
         mempool_listener,
         mempool_reconfig_subscription,
         peer_metadata_storage.clone(),
+        transaction_filter.clone(),
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
@@ -705,9 +793,11 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
         state_sync_runtimes.block_until_initialized();
         debug!("State sync initialization complete.");
 
-        // Initialize and start consensus.
+        // Initialize and start consensus. Components producing validator transactions put them
+        // in the pool for consensus to propose.
         instant = Instant::now();
-        consensus_runtime = Some(start_consensus(
+        let validator_txn_pool = ValidatorTxnPool::new();
+        let (runtime, state_handle) = start_consensus(
             &node_config,
             consensus_network_sender,
             consensus_network_events,
@@ -717,7 +807,10 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
             consensus_reconfig_subscription
                 .expect("Consensus requires a reconfiguration subscription!"),
             peer_metadata_storage,
-        ));
+            validator_txn_pool,
+        );
+        consensus_runtime = Some(runtime);
+        consensus_state = Some(state_handle);
         debug!("Consensus started in {} ms", instant.elapsed().as_millis());
     }
 
@@ -725,7 +818,25 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
     let telemetry_runtime =
         aptos_telemetry::service::start_telemetry_service(node_config.clone(), chain_id);
 
+    let admin_service_runtime = start_admin_service(
+        &node_config,
+        AdminTargets {
+            aptos_db,
+            api_gateway: api_gateway.clone(),
+            consensus_state,
+            network_benchmark: if node_config.network_benchmark.enabled {
+                Some(NetworkBenchmarkClient::new(
+                    node_config.network_benchmark.clone(),
+                    network_benchmark_senders,
+                ))
+            } else {
+                None
+            },
+        },
+    )?;
+
     Ok(AptosHandle {
+        _admin_service: admin_service_runtime,
         _api: api_runtime,
         _backup: backup_service,
         _consensus_runtime: consensus_runtime,
@@ -733,6 +844,13 @@ pub fn setup_environment(node_config: NodeConfig) -> anyhow::Result<AptosHandle>
         _network_runtimes: network_runtimes,
         _fh_stream: sf_runtime,
         _state_sync_runtimes: state_sync_runtimes,
+        _table_info_service: table_info_service_runtime,
         _telemetry_runtime: telemetry_runtime,
+        _transaction_log_runtime: transaction_log_runtime,
+        _transaction_tracing_runtime: transaction_tracing_runtime,
+        api_gateway,
+        mempool_capacity,
+        stream_concurrency,
+        transaction_filter,
     })
 }
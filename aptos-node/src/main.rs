@@ -10,5 +10,10 @@ use clap::Parser;
 static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 
 fn main() {
+    // The inspection service re-executes the node to capture its thread backtraces
+    if inspection_service::diagnostics::is_thread_dump_child() {
+        inspection_service::diagnostics::run_thread_dump_child();
+        return;
+    }
     AptosNodeArgs::parse().run()
 }
@@ -0,0 +1,119 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use aptos_config::config::NodeConfig;
+use aptos_logger::prelude::*;
+use aptos_protos::indexer::v1::{
+    table_info_service_server::{TableInfoService, TableInfoServiceServer},
+    GetTableInfoRequest, GetTableInfoResponse,
+};
+use aptos_types::state_store::table::TableHandle;
+use aptosdb::AptosDB;
+use aptosdb_indexer::table_info::TableInfoDb;
+use std::{str::FromStr, sync::Arc, time::Duration};
+use storage_interface::DbReader;
+use tokio::runtime::{Builder, Runtime};
+use tonic::{transport::Server, Request, Response, Status};
+
+/// Serves the `TableInfoService` gRPC service, see `aptos/indexer/v1/table_info.proto`
+struct TableInfoServer {
+    table_info_db: Arc<TableInfoDb>,
+}
+
+#[tonic::async_trait]
+impl TableInfoService for TableInfoServer {
+    async fn get_table_info(
+        &self,
+        request: Request<GetTableInfoRequest>,
+    ) -> Result<Response<GetTableInfoResponse>, Status> {
+        let handle =
+            TableHandle::from_str(&request.get_ref().handle).map_err(Status::invalid_argument)?;
+        // Read the next version first, so that it's never ahead of the infos read.
+        let next_version = self.table_info_db.next_version();
+        let table_info = self
+            .table_info_db
+            .get_table_info(handle)
+            .map_err(|error| Status::internal(error.to_string()))?
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "Unknown table, transactions parsed up to version {}",
+                    next_version
+                ))
+            })?;
+        Ok(Response::new(GetTableInfoResponse {
+            key_type: table_info.key_type.to_string(),
+            value_type: table_info.value_type.to_string(),
+            next_version,
+        }))
+    }
+}
+
+/// Starts parsing the table infos out of the committed write sets in the background, and serves
+/// them from the AptosDB if the internal indexer isn't enabled, and over gRPC if configured.
+pub fn bootstrap(config: &NodeConfig, aptos_db: Arc<AptosDB>) -> anyhow::Result<Option<Runtime>> {
+    let service_config = &config.storage.table_info_service;
+    if !service_config.enabled {
+        return Ok(None);
+    }
+
+    let table_info_db = Arc::new(
+        TableInfoDb::open(
+            config.storage.dir(),
+            config.storage.rocksdb_configs.index_db_config,
+        )
+        .map_err(|err| anyhow!("Failed to open the table info db {}", err))?,
+    );
+    aptos_db.set_table_info_db(table_info_db.clone());
+    info!(
+        "[table-info-service] parsing from version {}",
+        table_info_db.next_version()
+    );
+
+    let runtime = Builder::new_multi_thread()
+        .thread_name("table-info")
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to create table info service runtime {}", err))?;
+
+    let parser_db = table_info_db.clone();
+    let batch_size = service_config.parser_batch_size.max(1);
+    let poll_interval = Duration::from_millis(service_config.poll_interval_ms);
+    runtime.spawn(async move {
+        loop {
+            let table_info_db = parser_db.clone();
+            let db_reader: Arc<dyn DbReader> = aptos_db.clone();
+            let parsed = tokio::task::spawn_blocking(move || {
+                table_info_db.parse_next_batch(db_reader, batch_size)
+            })
+            .await;
+            match parsed {
+                Ok(Ok(0)) => tokio::time::sleep(poll_interval).await,
+                Ok(Ok(_)) => (),
+                Ok(Err(err)) => {
+                    error!("[table-info-service] failed to parse write sets: {}", err);
+                    tokio::time::sleep(poll_interval).await;
+                }
+                Err(err) => {
+                    error!("[table-info-service] parser panicked: {}", err);
+                    break;
+                }
+            }
+        }
+    });
+
+    if let Some(address) = service_config.grpc_address {
+        let service = TableInfoServer { table_info_db };
+        runtime.spawn(async move {
+            info!("[table-info-service] serving on {}", address);
+            if let Err(err) = Server::builder()
+                .add_service(TableInfoServiceServer::new(service))
+                .serve(address)
+                .await
+            {
+                error!("[table-info-service] server failed: {}", err);
+            }
+        });
+    }
+    Ok(Some(runtime))
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils;
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+/// The gRPC service operators use to run commands against a running node, e.g. to trigger pruning
+/// or take a state snapshot. It only listens on a loopback address, and every request must carry
+/// `auth_token` as a bearer token.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdminServiceConfig {
+    pub enabled: bool,
+    pub address: SocketAddr,
+    pub auth_token: String,
+    // Where state snapshots are written. Defaults to the `snapshots` directory in the data dir.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_dir: Option<PathBuf>,
+}
+
+impl Default for AdminServiceConfig {
+    fn default() -> AdminServiceConfig {
+        AdminServiceConfig {
+            enabled: false,
+            address: SocketAddr::from((Ipv4Addr::LOCALHOST, 9102)),
+            auth_token: String::new(),
+            snapshot_dir: None,
+        }
+    }
+}
+
+impl AdminServiceConfig {
+    pub fn randomize_ports(&mut self) {
+        self.address.set_port(utils::get_available_port());
+    }
+}
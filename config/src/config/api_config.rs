@@ -20,6 +20,24 @@ pub struct ApiConfig {
     pub content_length_limit: Option<u64>,
     #[serde(default = "default_disabled")]
     pub failpoints_enabled: bool,
+    /// Gas units a single view function call may use
+    #[serde(default = "default_max_view_gas")]
+    pub max_view_gas: u64,
+    /// Threads reading from storage on behalf of requests
+    #[serde(default = "default_storage_read_threads")]
+    pub storage_read_threads: usize,
+    /// Storage reads queued or running at once, further reads wait for a slot
+    #[serde(default = "default_max_pending_storage_reads")]
+    pub max_pending_storage_reads: usize,
+    /// Time a storage read may take, including waiting for a slot, before the request fails
+    #[serde(default = "default_storage_read_timeout_ms")]
+    pub storage_read_timeout_ms: u64,
+    #[serde(default)]
+    pub gateway: ApiGatewayConfig,
+    #[serde(default)]
+    pub health_check: ApiHealthCheckConfig,
+    #[serde(default)]
+    pub load_shedding: ApiLoadSheddingConfig,
 }
 
 pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -34,6 +52,22 @@ fn default_disabled() -> bool {
     false
 }
 
+fn default_max_view_gas() -> u64 {
+    100_000
+}
+
+fn default_storage_read_threads() -> usize {
+    16
+}
+
+fn default_max_pending_storage_reads() -> usize {
+    1024
+}
+
+fn default_storage_read_timeout_ms() -> u64 {
+    10_000
+}
+
 impl Default for ApiConfig {
     fn default() -> ApiConfig {
         ApiConfig {
@@ -45,6 +79,13 @@ impl Default for ApiConfig {
             tls_key_path: None,
             content_length_limit: None,
             failpoints_enabled: default_disabled(),
+            max_view_gas: default_max_view_gas(),
+            storage_read_threads: default_storage_read_threads(),
+            max_pending_storage_reads: default_max_pending_storage_reads(),
+            storage_read_timeout_ms: default_storage_read_timeout_ms(),
+            gateway: ApiGatewayConfig::default(),
+            health_check: ApiHealthCheckConfig::default(),
+            load_shedding: ApiLoadSheddingConfig::default(),
         }
     }
 }
@@ -61,3 +102,133 @@ impl ApiConfig {
         }
     }
 }
+
+/// Thresholds of the readiness probe, `/-/healthy/ready`. The node is reported as not ready when a
+/// component of the node exceeds them, so that load balancers and orchestrators stop routing
+/// requests to it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiHealthCheckConfig {
+    /// How far, in seconds, the timestamp of the latest ledger info may be behind the wall clock
+    pub max_sync_lag_secs: u64,
+    /// Time mempool has to answer before it is considered unresponsive
+    pub mempool_timeout_ms: u64,
+    /// Whether a validator which is not in the validator set is not ready
+    pub require_validator_set_membership: bool,
+}
+
+impl Default for ApiHealthCheckConfig {
+    fn default() -> ApiHealthCheckConfig {
+        ApiHealthCheckConfig {
+            max_sync_lag_secs: 30,
+            mempool_timeout_ms: 1_000,
+            require_validator_set_membership: false,
+        }
+    }
+}
+
+/// Load shedding of the API. Requests are classified by priority: health checks first, then
+/// transaction submissions, then reads, then heavy requests, i.e. reads with proofs, simulations
+/// and view functions. A request is shed, with a 429, once the number of requests in flight
+/// reaches the threshold of its class. Lower priority classes have lower thresholds, so they're
+/// shed first and leave room for the others. Health checks are never shed.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiLoadSheddingConfig {
+    pub enabled: bool,
+    /// Requests in flight from which transaction submissions are shed
+    pub max_in_flight_submissions: usize,
+    /// Requests in flight from which reads are shed
+    pub max_in_flight_reads: usize,
+    /// Requests in flight from which heavy requests are shed
+    pub max_in_flight_heavy: usize,
+    /// The Retry-After, in seconds, of shed requests
+    pub retry_after_secs: u64,
+}
+
+impl Default for ApiLoadSheddingConfig {
+    fn default() -> ApiLoadSheddingConfig {
+        ApiLoadSheddingConfig {
+            enabled: default_enabled(),
+            max_in_flight_submissions: 1024,
+            max_in_flight_reads: 768,
+            max_in_flight_heavy: 256,
+            retry_after_secs: 1,
+        }
+    }
+}
+
+/// Optional API key based access control for the API. When enabled, every
+/// request must either present a configured API key or fall under the
+/// anonymous policy, and is then subject to that key's rate limit and
+/// allowlists. This lets public fullnode operators get basic abuse protection
+/// without putting a separate proxy in front of the node.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiGatewayConfig {
+    pub enabled: bool,
+    /// The header clients use to present their API key. Keys may also be
+    /// given as a bearer token in the Authorization header.
+    pub api_key_header: String,
+    /// Paths that are accessible without an API key and without any limits,
+    /// e.g. for load balancer health checks.
+    pub public_paths: Vec<String>,
+    /// The policy for requests without an API key, rate limited per client
+    /// IP. If unset, requests without an API key are rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anonymous_policy: Option<ApiKeyPolicy>,
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+pub const DEFAULT_API_KEY_HEADER: &str = "x-aptos-api-key";
+
+impl Default for ApiGatewayConfig {
+    fn default() -> ApiGatewayConfig {
+        ApiGatewayConfig {
+            enabled: default_disabled(),
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            public_paths: vec![
+                "/v1/-/healthy".to_string(),
+                "/v1/-/healthy/live".to_string(),
+                "/v1/-/healthy/ready".to_string(),
+            ],
+            anonymous_policy: None,
+            api_keys: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// A human readable name for the key, used in logs and metrics.
+    pub name: String,
+    pub key: String,
+    #[serde(default)]
+    pub policy: ApiKeyPolicy,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiKeyPolicy {
+    /// The sustained number of requests per second allowed. If unset, there is
+    /// no rate limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_per_second: Option<usize>,
+    /// The maximum number of requests allowed in a burst. Defaults to
+    /// `requests_per_second`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burst_size: Option<usize>,
+    /// The HTTP methods allowed, e.g. `["GET"]` for a read only key. If empty,
+    /// all methods are allowed.
+    pub allowed_methods: Vec<String>,
+    /// The path prefixes allowed, e.g. `["/v1/accounts"]`. If empty, all
+    /// paths are allowed.
+    pub allowed_paths: Vec<String>,
+}
+
+impl ApiKeyPolicy {
+    pub fn burst_size(&self) -> Option<usize> {
+        self.burst_size.or(self.requests_per_second)
+    }
+}
@@ -27,6 +27,7 @@ pub struct ConsensusConfig {
     // the period = (poll_count - 1) * 30ms
     pub quorum_store_poll_count: u64,
     pub intra_consensus_channel_buffer_size: usize,
+    pub jwk_consensus: JWKConsensusConfig,
 }
 
 impl Default for ConsensusConfig {
@@ -45,6 +46,32 @@ impl Default for ConsensusConfig {
             quorum_store_pull_timeout_ms: 1000,
             quorum_store_poll_count: 20,
             intra_consensus_channel_buffer_size: 10,
+            jwk_consensus: JWKConsensusConfig::default(),
+        }
+    }
+}
+
+/// How validators observe the JWKs of the OIDC providers used by keyless accounts, and agree on
+/// updates. Only used when validator transactions are enabled on chain.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JWKConsensusConfig {
+    pub enabled: bool,
+    // How often the JWKs of each provider are fetched (in milliseconds)
+    pub observation_interval_ms: u64,
+    // Timeout for fetching the JWKs of a provider (in milliseconds)
+    pub fetch_timeout_ms: u64,
+    // Timeout for a peer to respond with the JWKs it observed (in milliseconds)
+    pub observation_request_timeout_ms: u64,
+}
+
+impl Default for JWKConsensusConfig {
+    fn default() -> JWKConsensusConfig {
+        JWKConsensusConfig {
+            enabled: true,
+            observation_interval_ms: 10_000,
+            fetch_timeout_ms: 5_000,
+            observation_request_timeout_ms: 2_000,
         }
     }
 }
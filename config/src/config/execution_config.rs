@@ -1,12 +1,17 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::{Error, RootPath};
+use crate::{
+    config::{Error, RootPath},
+    keys::ConfigKey,
+};
+use aptos_crypto::x25519;
 use aptos_types::transaction::Transaction;
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
     io::{Read, Write},
+    net::SocketAddr,
     path::PathBuf,
 };
 
@@ -21,6 +26,33 @@ pub struct ExecutionConfig {
     pub network_timeout_ms: u64,
     pub concurrency_level: u16,
     pub num_proof_reading_threads: u16,
+    /// Records the execution time, gas used and failures of user transactions by the entry
+    /// function they call
+    pub entry_function_metrics: bool,
+    /// The most entry functions labelled in those metrics, beyond which they're all recorded
+    /// as `other`
+    pub entry_function_metrics_max_functions: u16,
+    /// The executor services the user transactions of a block are sharded across. Blocks are
+    /// executed locally when not set.
+    pub remote_executor: Option<RemoteExecutorConfig>,
+}
+
+/// The executor services a validator shards its blocks across. The connection to each of them is
+/// authenticated and encrypted through a Noise IK handshake.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteExecutorConfig {
+    /// The key consensus authenticates with to the executor services
+    pub identity_key: ConfigKey<x25519::PrivateKey>,
+    pub shards: Vec<RemoteExecutorShardConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteExecutorShardConfig {
+    pub address: SocketAddr,
+    /// The key of the executor service, consensus only connects to a server holding it.
+    pub server_key: x25519::PublicKey,
 }
 
 impl std::fmt::Debug for ExecutionConfig {
@@ -49,6 +81,9 @@ impl Default for ExecutionConfig {
             // Sequential execution by default.
             concurrency_level: 1,
             num_proof_reading_threads: 32,
+            entry_function_metrics: false,
+            entry_function_metrics_max_functions: 1000,
+            remote_executor: None,
         }
     }
 }
@@ -86,6 +121,7 @@ impl ExecutionConfig {
 #[cfg(test)]
 mod test {
     use super::*;
+    use aptos_crypto::{PrivateKey, Uniform};
     use aptos_temppath::TempPath;
     use aptos_types::{
         transaction::{ChangeSet, Transaction, WriteSetPayload},
@@ -120,6 +156,22 @@ mod test {
         assert_eq!(config.genesis, Some(fake_genesis));
     }
 
+    #[test]
+    fn test_remote_executor_requires_keys() {
+        let server_key = x25519::PrivateKey::generate_for_testing().public_key();
+        let shard = format!(
+            "address: \"127.0.0.1:6191\"\nserver_key: \"{}\"",
+            server_key
+        );
+        assert!(serde_yaml::from_str::<RemoteExecutorShardConfig>(&shard).is_ok());
+        assert!(
+            serde_yaml::from_str::<RemoteExecutorShardConfig>("address: \"127.0.0.1:6191\"")
+                .is_err()
+        );
+        // Consensus can't connect to the executor services without an identity key
+        assert!(serde_yaml::from_str::<ExecutionConfig>("remote_executor:\n  shards: []").is_err());
+    }
+
     fn generate_config() -> (ExecutionConfig, TempPath) {
         let temp_dir = TempPath::new();
         temp_dir.create_as_dir().expect("error creating tempdir");
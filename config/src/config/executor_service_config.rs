@@ -0,0 +1,48 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{config::LoggerConfig, keys::ConfigKey};
+use aptos_crypto::x25519;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// The config of an executor service, which executes the sub-blocks a validator shards its blocks
+/// into.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutorServiceConfig {
+    #[serde(default = "default_listen_address")]
+    pub listen_address: SocketAddr,
+    #[serde(default)]
+    pub logger: LoggerConfig,
+    // Read/Write/Connect networking operation timeout in milliseconds.
+    #[serde(default = "default_network_timeout_ms")]
+    pub network_timeout_ms: u64,
+    #[serde(default = "default_concurrency_level")]
+    pub concurrency_level: u16,
+    /// The keys to authenticate the connection with. The executor service only serves validators
+    /// it trusts, as it is sent the state they execute against and returns the outputs they
+    /// commit.
+    pub authentication: ExecutorServiceAuthentication,
+}
+
+fn default_listen_address() -> SocketAddr {
+    "0.0.0.0:6191".parse().unwrap()
+}
+
+fn default_network_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_concurrency_level() -> u16 {
+    1
+}
+
+/// The keys used for the Noise IK handshake between a validator and its executor service.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutorServiceAuthentication {
+    pub identity_key: ConfigKey<x25519::PrivateKey>,
+    /// The keys of the validators the executor service accepts connections from.
+    pub trusted_clients: Vec<x25519::PublicKey>,
+}
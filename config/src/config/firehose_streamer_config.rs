@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -11,4 +12,7 @@ pub struct FirehoseStreamerConfig {
     // Alternatively can set the `STARTING_VERSION` env var
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub starting_version: Option<u64>,
+    // If set, serves the gRPC transaction stream on this address, independently of `enabled`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_address: Option<SocketAddr>,
 }
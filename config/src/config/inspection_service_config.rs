@@ -11,6 +11,10 @@ pub struct InspectionServiceConfig {
     pub port: u16,
     pub expose_configuration: bool,
     pub expose_system_information: bool,
+    // Exposes the `/debug/` endpoints (thread backtraces and lock contention), which also
+    // require the admin service auth token. Thread backtraces require building the node with the
+    // `thread-dump` feature.
+    pub expose_debug_endpoints: bool,
 }
 
 impl Default for InspectionServiceConfig {
@@ -20,6 +24,7 @@ impl Default for InspectionServiceConfig {
             port: 9101,
             expose_configuration: false,
             expose_system_information: true,
+            expose_debug_endpoints: false,
         }
     }
 }
@@ -16,12 +16,16 @@ use std::{
 };
 use thiserror::Error;
 
+mod admin_service_config;
+pub use admin_service_config::*;
 mod consensus_config;
 pub use consensus_config::*;
 mod error;
 pub use error::*;
 mod execution_config;
 pub use execution_config::*;
+mod executor_service_config;
+pub use executor_service_config::*;
 mod inspection_service_config;
 pub use inspection_service_config::*;
 mod logger_config;
@@ -32,6 +36,8 @@ mod network_config;
 pub use network_config::*;
 mod secure_backend_config;
 pub use secure_backend_config::*;
+mod signer_config;
+pub use signer_config::*;
 mod state_sync_config;
 pub use state_sync_config::*;
 mod firehose_streamer_config;
@@ -42,11 +48,19 @@ mod safety_rules_config;
 pub use safety_rules_config::*;
 mod test_config;
 pub use test_config::*;
+mod transaction_filter_config;
+pub use transaction_filter_config::*;
+mod transaction_log_config;
+pub use transaction_log_config::*;
+mod transaction_tracing_config;
+pub use transaction_tracing_config::*;
 mod api_config;
 pub use api_config::*;
+mod reloadable_config;
 use aptos_crypto::{bls12381, ed25519::Ed25519PrivateKey, x25519};
 use aptos_types::account_address::AccountAddress;
 use poem_openapi::Enum as PoemEnum;
+pub use reloadable_config::*;
 
 /// Represents a deprecated config that provides no field verification.
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -59,6 +73,8 @@ pub struct DeprecatedConfig {}
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct NodeConfig {
+    #[serde(default)]
+    pub admin_service: AdminServiceConfig,
     #[serde(default)]
     pub base: BaseConfig,
     #[serde(default)]
@@ -76,6 +92,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub metrics: DeprecatedConfig,
     #[serde(default)]
+    pub network_benchmark: NetworkBenchmarkConfig,
+    #[serde(default)]
     pub peer_monitoring_service: PeerMonitoringServiceConfig,
     #[serde(default)]
     pub api: ApiConfig,
@@ -88,6 +106,12 @@ pub struct NodeConfig {
     #[serde(default)]
     pub test: Option<TestConfig>,
     #[serde(default)]
+    pub transaction_filter: TransactionFilterConfig,
+    #[serde(default)]
+    pub transaction_log: TransactionLogConfig,
+    #[serde(default)]
+    pub transaction_tracing: TransactionTracingConfig,
+    #[serde(default)]
     pub validator_network: Option<NetworkConfig>,
     #[serde(default)]
     pub failpoints: Option<HashMap<String, String>>,
@@ -264,6 +288,9 @@ impl NodeConfig {
         config.execution.load(&input_dir)?;
 
         let mut config = config.validate_network_configs()?;
+        if config.base.role.is_validator() {
+            config.consensus.safety_rules.sanitize()?;
+        }
         config.set_data_dir(config.data_dir().to_path_buf());
         Ok(config)
     }
@@ -336,6 +363,7 @@ impl NodeConfig {
     }
 
     pub fn randomize_ports(&mut self) {
+        self.admin_service.randomize_ports();
         self.api.randomize_ports();
         self.inspection_service.randomize_ports();
         self.storage.randomize_ports();
@@ -317,6 +317,8 @@ impl NetworkConfig {
 pub struct PeerMonitoringServiceConfig {
     pub max_concurrent_requests: u64, // Max num of concurrent server tasks
     pub max_network_channel_size: u64, // Max num of pending network messages
+    pub peer_monitor_interval_ms: u64, // Interval between the rounds of pings and depth requests to peers
+    pub peer_monitor_request_timeout_ms: u64, // Timeout of the requests sent to peers by the monitor
 }
 
 impl Default for PeerMonitoringServiceConfig {
@@ -324,6 +326,39 @@ impl Default for PeerMonitoringServiceConfig {
         Self {
             max_concurrent_requests: 1000,
             max_network_channel_size: 1000,
+            peer_monitor_interval_ms: 30_000,
+            peer_monitor_request_timeout_ms: 10_000,
+        }
+    }
+}
+
+/// The opt-in network benchmark, which lets operators measure the link to a connected peer
+/// through the admin service. A node only answers benchmark messages when it's enabled, and
+/// benchmarks it runs are capped by the limits below.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkBenchmarkConfig {
+    pub enabled: bool,
+    pub max_duration_ms: u64,      // Longest a single benchmark can run for
+    pub max_bytes_per_sec: u64,    // Highest rate a benchmark can send at
+    pub default_message_size: u64, // Size of the messages sent, unless the request asks otherwise
+    pub max_message_size: u64,     // Largest message a benchmark can send, or the server answers
+    pub max_in_flight_messages: u64, // Max num of messages awaiting a response during a benchmark
+    pub max_network_channel_size: u64, // Max num of pending network messages
+    pub request_timeout_ms: u64,   // Timeout of each benchmark message
+}
+
+impl Default for NetworkBenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_duration_ms: 30_000,
+            max_bytes_per_sec: 10 * 1024 * 1024, // 10 MiB
+            default_message_size: 64 * 1024,     // 64 KiB
+            max_message_size: 1024 * 1024,       // 1 MiB
+            max_in_flight_messages: 8,
+            max_network_channel_size: 100,
+            request_timeout_ms: 5_000,
         }
     }
 }
@@ -0,0 +1,262 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{ApiGatewayConfig, Error, NodeConfig, TransactionFilterConfig};
+use aptos_logger::Level;
+
+/// The part of the node config which can be changed while the node runs, by editing the config
+/// file. Any other change to the file is rejected, as it only takes effect on restart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReloadableConfig {
+    /// `logger.level`
+    pub log_level: Level,
+    /// `api.gateway`, i.e. the API keys and their rate limits
+    pub api_gateway: ApiGatewayConfig,
+    /// `mempool.capacity`
+    pub mempool_capacity: usize,
+    /// `mempool.capacity_per_user`
+    pub mempool_capacity_per_user: usize,
+    /// `state_sync.data_streaming_service.max_concurrent_requests`
+    pub max_concurrent_requests: u64,
+    /// `state_sync.data_streaming_service.max_concurrent_state_requests`
+    pub max_concurrent_state_requests: u64,
+    /// `transaction_filter`
+    pub transaction_filter: TransactionFilterConfig,
+}
+
+impl ReloadableConfig {
+    pub fn new(config: &NodeConfig) -> Self {
+        let data_streaming_service = &config.state_sync.data_streaming_service;
+        Self {
+            log_level: config.logger.level,
+            api_gateway: config.api.gateway.clone(),
+            mempool_capacity: config.mempool.capacity,
+            mempool_capacity_per_user: config.mempool.capacity_per_user,
+            max_concurrent_requests: data_streaming_service.max_concurrent_requests,
+            max_concurrent_state_requests: data_streaming_service.max_concurrent_state_requests,
+            transaction_filter: config.transaction_filter.clone(),
+        }
+    }
+
+    /// Returns the reloadable part of `new_config`, after checking that it only differs from
+    /// `current_config` in reloadable fields.
+    pub fn from_reload(
+        current_config: &NodeConfig,
+        new_config: &NodeConfig,
+    ) -> Result<Self, Error> {
+        let reloadable_config = Self::new(new_config);
+
+        // Any remaining difference is in a field which can't be reloaded
+        let mut new_config = new_config.clone();
+        Self::new(current_config).apply(&mut new_config);
+        let changed_sections: Vec<&str> = [
+            (
+                "admin_service",
+                current_config.admin_service == new_config.admin_service,
+            ),
+            ("base", current_config.base == new_config.base),
+            (
+                "consensus",
+                current_config.consensus == new_config.consensus,
+            ),
+            (
+                "execution",
+                current_config.execution == new_config.execution,
+            ),
+            (
+                "full_node_networks",
+                current_config.full_node_networks == new_config.full_node_networks,
+            ),
+            (
+                "inspection_service",
+                current_config.inspection_service == new_config.inspection_service,
+            ),
+            ("logger", current_config.logger == new_config.logger),
+            ("mempool", current_config.mempool == new_config.mempool),
+            (
+                "network_benchmark",
+                current_config.network_benchmark == new_config.network_benchmark,
+            ),
+            (
+                "peer_monitoring_service",
+                current_config.peer_monitoring_service == new_config.peer_monitoring_service,
+            ),
+            ("api", current_config.api == new_config.api),
+            (
+                "state_sync",
+                current_config.state_sync == new_config.state_sync,
+            ),
+            (
+                "firehose_stream",
+                current_config.firehose_stream == new_config.firehose_stream,
+            ),
+            ("storage", current_config.storage == new_config.storage),
+            ("test", current_config.test == new_config.test),
+            (
+                "transaction_log",
+                current_config.transaction_log == new_config.transaction_log,
+            ),
+            (
+                "transaction_tracing",
+                current_config.transaction_tracing == new_config.transaction_tracing,
+            ),
+            (
+                "validator_network",
+                current_config.validator_network == new_config.validator_network,
+            ),
+            (
+                "failpoints",
+                current_config.failpoints == new_config.failpoints,
+            ),
+        ]
+        .iter()
+        .filter(|(_, unchanged)| !unchanged)
+        .map(|(section, _)| *section)
+        .collect();
+        if !changed_sections.is_empty() {
+            return Err(Error::InvariantViolation(format!(
+                "Fields which require a restart were changed in: {}",
+                changed_sections.join(", ")
+            )));
+        }
+        Ok(reloadable_config)
+    }
+
+    /// Overwrites the reloadable fields of `config` with this config.
+    pub fn apply(&self, config: &mut NodeConfig) {
+        config.logger.level = self.log_level;
+        config.api.gateway = self.api_gateway.clone();
+        config.mempool.capacity = self.mempool_capacity;
+        config.mempool.capacity_per_user = self.mempool_capacity_per_user;
+        let data_streaming_service = &mut config.state_sync.data_streaming_service;
+        data_streaming_service.max_concurrent_requests = self.max_concurrent_requests;
+        data_streaming_service.max_concurrent_state_requests = self.max_concurrent_state_requests;
+        config.transaction_filter = self.transaction_filter.clone();
+    }
+
+    /// Describes each field which differs in `new_config`, as `field: old -> new`. The API
+    /// gateway holds API keys, so only whether it changed is described. Transaction filter rules
+    /// are described one by one, as added or removed.
+    pub fn changes(&self, new_config: &Self) -> Vec<String> {
+        let mut changes = vec![];
+        if self.log_level != new_config.log_level {
+            changes.push(format!(
+                "logger.level: {} -> {}",
+                self.log_level, new_config.log_level
+            ));
+        }
+        if self.api_gateway != new_config.api_gateway {
+            changes.push("api.gateway: changed".to_string());
+        }
+        if self.mempool_capacity != new_config.mempool_capacity {
+            changes.push(format!(
+                "mempool.capacity: {} -> {}",
+                self.mempool_capacity, new_config.mempool_capacity
+            ));
+        }
+        if self.mempool_capacity_per_user != new_config.mempool_capacity_per_user {
+            changes.push(format!(
+                "mempool.capacity_per_user: {} -> {}",
+                self.mempool_capacity_per_user, new_config.mempool_capacity_per_user
+            ));
+        }
+        if self.max_concurrent_requests != new_config.max_concurrent_requests {
+            changes.push(format!(
+                "state_sync.data_streaming_service.max_concurrent_requests: {} -> {}",
+                self.max_concurrent_requests, new_config.max_concurrent_requests
+            ));
+        }
+        if self.max_concurrent_state_requests != new_config.max_concurrent_state_requests {
+            changes.push(format!(
+                "state_sync.data_streaming_service.max_concurrent_state_requests: {} -> {}",
+                self.max_concurrent_state_requests, new_config.max_concurrent_state_requests
+            ));
+        }
+        let current_rules = &self.transaction_filter.rules;
+        let new_rules = &new_config.transaction_filter.rules;
+        for rule in current_rules
+            .iter()
+            .filter(|rule| !new_rules.contains(rule))
+        {
+            changes.push(format!("transaction_filter.rules: removed {:?}", rule));
+        }
+        for rule in new_rules
+            .iter()
+            .filter(|rule| !current_rules.contains(rule))
+        {
+            changes.push(format!("transaction_filter.rules: added {:?}", rule));
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::{TransactionFilterRule, TransactionPayloadType};
+    use aptos_types::account_address::AccountAddress;
+
+    #[test]
+    fn test_reload_reloadable_fields() {
+        let current_config = NodeConfig::default_for_validator();
+        let mut new_config = current_config.clone();
+        new_config.logger.level = Level::Debug;
+        new_config.mempool.capacity_per_user = 200;
+
+        let current = ReloadableConfig::new(&current_config);
+        let new = ReloadableConfig::from_reload(&current_config, &new_config).unwrap();
+        assert_eq!(new.log_level, Level::Debug);
+        assert_eq!(new.mempool_capacity_per_user, 200);
+        assert_eq!(
+            current.changes(&new),
+            vec![
+                format!("logger.level: {} -> DEBUG", current.log_level),
+                format!(
+                    "mempool.capacity_per_user: {} -> 200",
+                    current.mempool_capacity_per_user
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reload_rejects_immutable_fields() {
+        let current_config = NodeConfig::default_for_validator();
+        let mut new_config = current_config.clone();
+        new_config.mempool.capacity = 1;
+        new_config.mempool.shared_mempool_batch_size += 1;
+        new_config.storage.enable_indexer = !new_config.storage.enable_indexer;
+
+        let error = ReloadableConfig::from_reload(&current_config, &new_config).unwrap_err();
+        assert!(error.to_string().contains("mempool, storage"));
+    }
+
+    #[test]
+    fn test_reload_transaction_filter() {
+        let mut current_config = NodeConfig::default_for_validator();
+        current_config.transaction_filter.rules = vec![
+            TransactionFilterRule::Sender(AccountAddress::ONE),
+            TransactionFilterRule::PayloadType(TransactionPayloadType::Script),
+        ];
+        let mut new_config = current_config.clone();
+        new_config.transaction_filter.rules = vec![
+            TransactionFilterRule::PayloadType(TransactionPayloadType::Script),
+            TransactionFilterRule::EntryFunction("0x1::coin::transfer".to_string()),
+        ];
+
+        let current = ReloadableConfig::new(&current_config);
+        let new = ReloadableConfig::from_reload(&current_config, &new_config).unwrap();
+        assert_eq!(new.transaction_filter, new_config.transaction_filter);
+        assert_eq!(
+            current.changes(&new),
+            vec![
+                format!(
+                    "transaction_filter.rules: removed Sender({:?})",
+                    AccountAddress::ONE
+                ),
+                "transaction_filter.rules: added EntryFunction(\"0x1::coin::transfer\")"
+                    .to_string(),
+            ]
+        );
+    }
+}
@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::{IdentityBlob, LoggerConfig, SecureBackend, WaypointConfig},
+    config::{
+        invariant, Error, IdentityBlob, LoggerConfig, SecureBackend, SignerConfig, WaypointConfig,
+    },
     keys::ConfigKey,
 };
-use aptos_crypto::{bls12381, Uniform};
+use aptos_crypto::{bls12381, x25519, Uniform};
 use aptos_types::{network_address::NetworkAddress, waypoint::Waypoint, PeerId};
 use rand::rngs::StdRng;
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,9 @@ pub struct SafetyRulesConfig {
     pub network_timeout_ms: u64,
     pub enable_cached_safety_data: bool,
     pub initial_safety_rules_config: InitialSafetyRulesConfig,
+    /// Signs with the consensus key held by this backend, rather than with the consensus key in
+    /// `backend`, which is then not needed.
+    pub consensus_signer: Option<SignerConfig>,
 }
 
 impl Default for SafetyRulesConfig {
@@ -38,6 +43,7 @@ impl Default for SafetyRulesConfig {
             network_timeout_ms: 30_000,
             enable_cached_safety_data: true,
             initial_safety_rules_config: InitialSafetyRulesConfig::None,
+            consensus_signer: None,
         }
     }
 }
@@ -50,6 +56,18 @@ impl SafetyRulesConfig {
             backend.set_data_dir(data_dir);
         }
     }
+
+    /// Checks the config of a validator, or of its safety rules process: the connection between
+    /// them must be authenticated, as the process signs with the consensus key of the validator.
+    pub fn sanitize(&self) -> Result<(), Error> {
+        if let SafetyRulesService::Process(service) = &self.service {
+            invariant(
+                service.authentication.is_some(),
+                "The safety rules process service requires authentication".into(),
+            )?;
+        }
+        Ok(())
+    }
 }
 
 // TODO: Find a cleaner way so WaypointConfig isn't duplicated
@@ -107,6 +125,11 @@ pub enum SafetyRulesService {
 #[serde(deny_unknown_fields)]
 pub struct RemoteService {
     pub server_address: NetworkAddress,
+    /// Mutually authenticates consensus and the safety rules process, and encrypts their
+    /// traffic. The connection is neither authenticated nor encrypted if not set, which
+    /// `SafetyRulesConfig::sanitize` rejects.
+    #[serde(default)]
+    pub authentication: Option<RemoteServiceAuthentication>,
 }
 
 impl RemoteService {
@@ -119,6 +142,18 @@ impl RemoteService {
     }
 }
 
+/// The keys used for the Noise IK handshake between consensus and the safety rules process.
+/// Each side sets its own `identity_key`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteServiceAuthentication {
+    pub identity_key: ConfigKey<x25519::PrivateKey>,
+    /// The key of the safety rules process, consensus only connects to a server holding it.
+    pub server_key: x25519::PublicKey,
+    /// The keys of the consensus processes the safety rules process accepts connections from.
+    pub trusted_clients: Vec<x25519::PublicKey>,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SafetyRulesTestConfig {
     pub author: PeerId,
@@ -144,3 +179,34 @@ impl SafetyRulesTestConfig {
         self.consensus_key = Some(ConfigKey::<bls12381::PrivateKey>::new(privkey));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_process_service() {
+        let server_address: NetworkAddress = "/ip4/127.0.0.1/tcp/5555".parse().unwrap();
+        let identity_key = x25519::PrivateKey::generate_for_testing();
+        let server_key = aptos_crypto::PrivateKey::public_key(&identity_key);
+
+        let mut config = SafetyRulesConfig::default();
+        assert!(config.sanitize().is_ok());
+
+        config.service = SafetyRulesService::Process(RemoteService {
+            server_address: server_address.clone(),
+            authentication: None,
+        });
+        assert!(config.sanitize().is_err());
+
+        config.service = SafetyRulesService::Process(RemoteService {
+            server_address,
+            authentication: Some(RemoteServiceAuthentication {
+                identity_key: ConfigKey::new(identity_key),
+                server_key,
+                trusted_clients: vec![],
+            }),
+        });
+        assert!(config.sanitize().is_ok());
+    }
+}
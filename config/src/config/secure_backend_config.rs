@@ -3,11 +3,12 @@
 
 use crate::config::Error;
 use aptos_secure_storage::{
-    GitHubStorage, InMemoryStorage, Namespaced, OnDiskStorage, RocksDbStorage, Storage,
-    VaultStorage, SECURE_STORAGE_DB_NAME,
+    AwsSecretsManagerStorage, GcpSecretManagerStorage, GitHubStorage, InMemoryStorage, KeyPolicy,
+    Namespaced, OnDiskStorage, RocksDbStorage, Storage, VaultStorage, SECURE_STORAGE_DB_NAME,
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::Read,
     path::{Path, PathBuf},
@@ -16,6 +17,8 @@ use std::{
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum SecureBackend {
+    AwsSecretsManager(AwsSecretsManagerConfig),
+    GcpSecretManager(GcpSecretManagerConfig),
     GitHub(GitHubConfig),
     InMemoryStorage,
     Vault(VaultConfig),
@@ -26,7 +29,9 @@ pub enum SecureBackend {
 impl SecureBackend {
     pub fn namespace(&self) -> Option<&str> {
         match self {
-            SecureBackend::GitHub(GitHubConfig { namespace, .. })
+            SecureBackend::AwsSecretsManager(AwsSecretsManagerConfig { namespace, .. })
+            | SecureBackend::GcpSecretManager(GcpSecretManagerConfig { namespace, .. })
+            | SecureBackend::GitHub(GitHubConfig { namespace, .. })
             | SecureBackend::Vault(VaultConfig { namespace, .. })
             | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
             | SecureBackend::RocksDbStorage(RocksDbStorageConfig { namespace, .. }) => {
@@ -38,7 +43,9 @@ impl SecureBackend {
 
     pub fn clear_namespace(&mut self) {
         match self {
-            SecureBackend::GitHub(GitHubConfig { namespace, .. })
+            SecureBackend::AwsSecretsManager(AwsSecretsManagerConfig { namespace, .. })
+            | SecureBackend::GcpSecretManager(GcpSecretManagerConfig { namespace, .. })
+            | SecureBackend::GitHub(GitHubConfig { namespace, .. })
             | SecureBackend::Vault(VaultConfig { namespace, .. })
             | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
             | SecureBackend::RocksDbStorage(RocksDbStorageConfig { namespace, .. }) => {
@@ -49,6 +56,52 @@ impl SecureBackend {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsSecretsManagerConfig {
+    /// The region of the secrets, e.g. us-east-1
+    pub region: String,
+    /// An optional endpoint to use instead of the public one of the region, e.g. a VPC endpoint
+    pub endpoint: Option<String>,
+    /// The access key id of the credentials
+    pub access_key_id: Token,
+    /// The secret access key of the credentials
+    pub secret_access_key: Token,
+    /// The session token of temporary credentials
+    pub session_token: Option<Token>,
+    /// A namespace is an optional portion of the name of a secret. For example, a key, S, without
+    /// a namespace would be stored in the secret S, with a namespace, N, it would be in N/S.
+    pub namespace: Option<String>,
+    /// The caching and rotation policies of keys, by their name including the namespace
+    #[serde(default)]
+    pub key_policies: BTreeMap<String, KeyPolicy>,
+    /// Timeout for new socket connections, in milliseconds.
+    pub connection_timeout_ms: Option<u64>,
+    /// Timeout for responses, in milliseconds.
+    pub response_timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GcpSecretManagerConfig {
+    /// The project holding the secrets
+    pub project: String,
+    /// The OAuth 2.0 access token for accessing secrets. If this is not specified, the tokens of
+    /// the service account of the instance are used, and refreshed before they expire.
+    pub token: Option<Token>,
+    /// A namespace is an optional portion of the id of a secret. For example, a key, S, without
+    /// a namespace would be stored in the secret S, with a namespace, N, it would be in N__S.
+    pub namespace: Option<String>,
+    /// The caching and rotation policies of keys, by their name including the namespace, e.g.
+    /// N/S
+    #[serde(default)]
+    pub key_policies: BTreeMap<String, KeyPolicy>,
+    /// Timeout for new socket connections, in milliseconds.
+    pub connection_timeout_ms: Option<u64>,
+    /// Timeout for responses, in milliseconds.
+    pub response_timeout_ms: Option<u64>,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GitHubConfig {
@@ -75,9 +128,12 @@ pub struct VaultConfig {
     /// a secret, S, without a namespace would be available in secret/data/S, with a namespace, N, it
     /// would be in secret/data/N/S.
     pub namespace: Option<String>,
+    /// The Vault Enterprise namespace the secrets and keys are in, not to be confused with the
+    /// namespace above. If this is not specified, the root namespace is used.
+    pub vault_namespace: Option<String>,
     /// Vault leverages leases on many tokens, specify this to automatically have your lease
-    /// renewed up to that many seconds more. If this is not specified, the lease will not
-    /// automatically be renewed.
+    /// renewed up to that many seconds more, halfway through the lease. If this is not
+    /// specified, the lease will not automatically be renewed.
     pub renew_ttl_secs: Option<u32>,
     /// Vault's URL, note: only HTTP is currently supported.
     pub server: String,
@@ -217,6 +273,49 @@ fn read_file(path: &Path) -> Result<String, Error> {
 impl From<&SecureBackend> for Storage {
     fn from(backend: &SecureBackend) -> Self {
         match backend {
+            SecureBackend::AwsSecretsManager(config) => {
+                let storage = Storage::from(AwsSecretsManagerStorage::new(
+                    config.region.clone(),
+                    config.endpoint.clone(),
+                    config
+                        .access_key_id
+                        .read_token()
+                        .expect("Unable to read access key id"),
+                    config
+                        .secret_access_key
+                        .read_token()
+                        .expect("Unable to read secret access key"),
+                    config
+                        .session_token
+                        .as_ref()
+                        .map(|token| token.read_token().expect("Unable to read session token")),
+                    config.key_policies.clone(),
+                    config.connection_timeout_ms,
+                    config.response_timeout_ms,
+                ));
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            }
+            SecureBackend::GcpSecretManager(config) => {
+                let storage = Storage::from(GcpSecretManagerStorage::new(
+                    config.project.clone(),
+                    config
+                        .token
+                        .as_ref()
+                        .map(|token| token.read_token().expect("Unable to read token")),
+                    config.key_policies.clone(),
+                    config.connection_timeout_ms,
+                    config.response_timeout_ms,
+                ));
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            }
             SecureBackend::GitHub(config) => {
                 let storage = Storage::from(GitHubStorage::new(
                     config.repository_owner.clone(),
@@ -255,6 +354,7 @@ impl From<&SecureBackend> for Storage {
                 let storage = Storage::from(VaultStorage::new(
                     config.server.clone(),
                     config.token.read_token().expect("Unable to read token"),
+                    config.vault_namespace.clone(),
                     config
                         .ca_certificate
                         .as_ref()
@@ -288,6 +388,7 @@ mod tests {
         let from_config = Config {
             vault: VaultConfig {
                 namespace: None,
+                vault_namespace: None,
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromConfig("test".to_string()),
@@ -316,6 +417,7 @@ vault:
         let from_config = Config {
             vault: VaultConfig {
                 namespace: None,
+                vault_namespace: None,
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromConfig("test".to_string()),
@@ -346,6 +448,7 @@ vault:
         let from_disk = Config {
             vault: VaultConfig {
                 namespace: None,
+                vault_namespace: None,
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromDisk(PathBuf::from("/token")),
@@ -369,6 +472,49 @@ vault:
         serde_yaml::to_string(&from_disk).unwrap();
     }
 
+    #[test]
+    fn test_secrets_manager_parsing() {
+        let text = r#"
+type: "aws_secrets_manager"
+region: "us-east-1"
+access_key_id:
+    from_config: "id"
+secret_access_key:
+    from_disk: "/secret"
+namespace: "validator"
+key_policies:
+    validator/consensus:
+        cache_ttl_secs: 60
+        rotation_interval_secs: 86400
+        "#;
+        let backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        let config = match &backend {
+            SecureBackend::AwsSecretsManager(config) => config,
+            _ => panic!("Expected an AWS Secrets Manager backend"),
+        };
+        assert_eq!(backend.namespace(), Some("validator"));
+        assert_eq!(config.secret_access_key, Token::FromDisk("/secret".into()));
+        assert_eq!(
+            config.key_policies["validator/consensus"],
+            KeyPolicy {
+                cache_ttl_secs: Some(60),
+                rotation_interval_secs: Some(86400),
+            }
+        );
+
+        let text = r#"
+type: "gcp_secret_manager"
+project: "aptos"
+        "#;
+        let backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        let config = match &backend {
+            SecureBackend::GcpSecretManager(config) => config,
+            _ => panic!("Expected a GCP Secret Manager backend"),
+        };
+        assert_eq!(config.token, None);
+        assert!(config.key_policies.is_empty());
+    }
+
     #[test]
     fn test_token_reading() {
         let temppath = aptos_temppath::TempPath::new();
@@ -0,0 +1,68 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::Token;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A key held outside of the node, in a hardware security module (HSM) or a key management service
+/// (KMS), which signs on the node's behalf so that the key never touches its disk.
+///
+/// PKCS#11 devices and the AWS and GCP KMSs hold Ed25519 keys only. BLS12-381 keys, such as the
+/// consensus key, must be held by a signing service.
+///
+/// Note, the validator network key is an x25519 key used for Diffie-Hellman in the Noise
+/// handshake, rather than for signing, so it cannot be held by a signing backend.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum SignerConfig {
+    Pkcs11(Pkcs11Config),
+    AwsKms(AwsKmsConfig),
+    GcpKms(GcpKmsConfig),
+    SigningService(SigningServiceConfig),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pkcs11Config {
+    /// Path to the PKCS#11 library of the device, e.g. `/usr/lib/softhsm/libsofthsm2.so`
+    pub library: PathBuf,
+    /// The slot holding the key. If not specified, the first slot with a token is used
+    pub slot: Option<u64>,
+    /// The PIN of the user of the token
+    pub pin: Token,
+    /// The label of the private key, and of its public key
+    pub key_label: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKmsConfig {
+    /// The region of the key, e.g. `us-east-1`
+    pub region: String,
+    /// The ID or ARN of the key
+    pub key_id: String,
+    /// Overrides the KMS endpoint of the region, e.g. for a VPC endpoint
+    pub endpoint: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GcpKmsConfig {
+    /// The resource name of the key version, i.e.
+    /// `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`
+    pub key_version: String,
+    /// An OAuth access token. If not specified, one is requested from the metadata server
+    pub token: Option<Token>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SigningServiceConfig {
+    /// The URL of the signing service
+    pub server: String,
+    /// The ID of the key within the signing service
+    pub key_id: String,
+    /// The bearer token for accessing the signing service, if it requires one
+    pub token: Option<Token>,
+}
@@ -87,6 +87,75 @@ pub struct StorageConfig {
     /// since genesis. To recover operation after data loss, or to bootstrap a node in fast sync
     /// mode, the indexer db needs to be copied in from another node.
     pub enable_indexer: bool,
+    /// The cache of the latest state values in front of the state KV reads
+    pub hot_state_cache: HotStateCacheConfig,
+    /// The service parsing the table infos out of the committed write sets, in the background
+    pub table_info_service: TableInfoServiceConfig,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotStateEvictionPolicy {
+    /// Evicts the least recently used state value
+    Lru,
+    /// Evicts the least frequently used state value, the least recently used one among those
+    /// used as often
+    Lfu,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HotStateCacheConfig {
+    pub enabled: bool,
+    /// The max total size of the cached state keys and values, in bytes
+    pub max_bytes: usize,
+    pub eviction_policy: HotStateEvictionPolicy,
+    /// Loads the resources and modules of the framework account, read by nearly every
+    /// transaction, into the cache on startup.
+    pub prefetch_framework: bool,
+    /// The directory, ideally on a local NVMe drive, the cache is periodically persisted to, so
+    /// that it's warm again after a restart. The cache is in memory only if unset.
+    pub persist_dir: Option<PathBuf>,
+    pub persist_interval_secs: u64,
+}
+
+impl Default for HotStateCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_bytes: 256 << 20,
+            eviction_policy: HotStateEvictionPolicy::Lru,
+            prefetch_framework: true,
+            persist_dir: None,
+            persist_interval_secs: 300,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableInfoServiceConfig {
+    /// Parses the table infos into their own DB, asynchronously to the commit, unlike the
+    /// internal indexer. The parser starts from genesis, so to enable the service on a node
+    /// bootstrapped in fast sync mode, the table info db needs to be copied in from another node.
+    pub enabled: bool,
+    /// The max # of write sets parsed at once
+    pub parser_batch_size: u64,
+    /// How long the parser waits for new transactions once caught up, in milliseconds
+    pub poll_interval_ms: u64,
+    /// The address the table infos are served at over gRPC, not served if unset
+    pub grpc_address: Option<SocketAddr>,
+}
+
+impl Default for TableInfoServiceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            parser_batch_size: 1000,
+            poll_interval_ms: 100,
+            grpc_address: None,
+        }
+    }
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -187,6 +256,8 @@ impl Default for StorageConfig {
             data_dir: PathBuf::from("/opt/aptos/data"),
             rocksdb_configs: RocksdbConfigs::default(),
             enable_indexer: false,
+            hot_state_cache: HotStateCacheConfig::default(),
+            table_info_service: TableInfoServiceConfig::default(),
             target_snapshot_size: TARGET_SNAPSHOT_SIZE,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         }
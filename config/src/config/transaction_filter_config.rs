@@ -0,0 +1,36 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+
+/// Transactions matching any of the rules are rejected on API submission and on mempool
+/// ingestion, whether they come from a client or a peer. Each rejection is logged with the rule
+/// it matched. The rules can be changed while the node runs, by editing the config file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionFilterConfig {
+    pub rules: Vec<TransactionFilterRule>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionFilterRule {
+    /// Transactions sent by the account
+    Sender(AccountAddress),
+    /// Transactions calling the entry function, given as `address::module::function`
+    EntryFunction(String),
+    /// Transactions calling an entry function of a module published at the address
+    ModuleAddress(AccountAddress),
+    /// Transactions with the type of payload
+    PayloadType(TransactionPayloadType),
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionPayloadType {
+    Script,
+    ModuleBundle,
+    EntryFunction,
+    OrderlessEntryFunction,
+}
@@ -0,0 +1,47 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Writes a JSON summary of each committed transaction (hash, sender, status, gas used, number of
+/// events) to a sink, separately from the node's own logs, for analytics pipelines.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionLogConfig {
+    pub enabled: bool,
+    // The fraction of transactions which are logged, between 0 and 1. As the decision only
+    // depends on the transaction hash, every node logs the same transactions.
+    pub sample_rate: f64,
+    // The version to start logging from. By default, only transactions committed after the node
+    // starts are logged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starting_version: Option<u64>,
+    pub sink: TransactionLogSink,
+}
+
+impl Default for TransactionLogConfig {
+    fn default() -> TransactionLogConfig {
+        TransactionLogConfig {
+            enabled: false,
+            sample_rate: 1.0,
+            starting_version: None,
+            sink: TransactionLogSink::File {
+                path: PathBuf::from("transactions.log"),
+            },
+        }
+    }
+}
+
+/// Where transaction summaries are written, one JSON object each
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum TransactionLogSink {
+    /// Appended to a file, one per line. A relative path is relative to the data directory.
+    File { path: PathBuf },
+    /// Sent as UDP datagrams to `address`, e.g. `127.0.0.1:5170`
+    Udp { address: String },
+    /// Produced to a Kafka topic, keyed by transaction hash. Requires the node to be built with
+    /// the `kafka` feature.
+    Kafka { brokers: String, topic: String },
+}
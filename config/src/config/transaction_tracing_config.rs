@@ -0,0 +1,32 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Exports an OpenTelemetry span for each stage of a transaction's lifecycle on the node, from
+/// API submission to commit, over OTLP. The spans of a transaction share a trace id derived from
+/// its hash, so its traces from different nodes can be viewed together. Requires the node to be
+/// built with the `otlp` feature.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionTracingConfig {
+    pub enabled: bool,
+    // The gRPC endpoint of the OTLP collector
+    pub otlp_endpoint: String,
+    // Reported as the `service.name` of the spans
+    pub service_name: String,
+    // The fraction of transactions which are traced, between 0 and 1. A transaction is either
+    // traced on every node or on none, as the decision only depends on its hash.
+    pub sample_rate: f64,
+}
+
+impl Default for TransactionTracingConfig {
+    fn default() -> TransactionTracingConfig {
+        TransactionTracingConfig {
+            enabled: false,
+            otlp_endpoint: "http://localhost:4317".to_string(),
+            service_name: "aptos-node".to_string(),
+            sample_rate: 1.0,
+        }
+    }
+}
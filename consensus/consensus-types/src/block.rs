@@ -15,8 +15,9 @@ use aptos_types::{
     block_metadata::BlockMetadata,
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
-    transaction::{Transaction, Version},
+    transaction::{SignedTransaction, Transaction, Version},
     validator_signer::ValidatorSigner,
+    validator_txn::ValidatorTransaction,
     validator_verifier::ValidatorVerifier,
 };
 use mirai_annotations::debug_checked_verify_eq;
@@ -101,6 +102,10 @@ impl Block {
         self.block_data.payload()
     }
 
+    pub fn validator_txns(&self) -> Option<&Vec<ValidatorTransaction>> {
+        self.block_data.validator_txns()
+    }
+
     pub fn quorum_cert(&self) -> &QuorumCert {
         self.block_data.quorum_cert()
     }
@@ -212,6 +217,28 @@ impl Block {
         Self::new_proposal_from_block_data(block_data, validator_signer)
     }
 
+    pub fn new_proposal_ext(
+        validator_txns: Vec<ValidatorTransaction>,
+        payload: Payload,
+        round: Round,
+        timestamp_usecs: u64,
+        quorum_cert: QuorumCert,
+        validator_signer: &ValidatorSigner,
+        failed_authors: Vec<(Round, Author)>,
+    ) -> Self {
+        let block_data = BlockData::new_proposal_ext(
+            validator_txns,
+            payload,
+            validator_signer.author(),
+            failed_authors,
+            round,
+            timestamp_usecs,
+            quorum_cert,
+        );
+
+        Self::new_proposal_from_block_data(block_data, validator_signer)
+    }
+
     pub fn new_proposal_from_block_data(
         block_data: BlockData,
         validator_signer: &ValidatorSigner,
@@ -237,7 +264,7 @@ impl Block {
         match self.block_data.block_type() {
             BlockType::Genesis => bail!("We should not accept genesis from others"),
             BlockType::NilBlock { .. } => self.quorum_cert().verify(validator),
-            BlockType::Proposal { author, .. } => {
+            BlockType::Proposal { author, .. } | BlockType::ProposalExt { author, .. } => {
                 let signature = self
                     .signature
                     .as_ref()
@@ -269,6 +296,10 @@ impl Block {
                 self.payload().map_or(true, |p| p.is_empty()),
                 "Reconfiguration suffix should not carry payload"
             );
+            ensure!(
+                self.validator_txns().map_or(true, |txns| txns.is_empty()),
+                "Reconfiguration suffix should not carry validator transactions"
+            );
         }
         if let Some(failed_authors) = self.block_data().failed_authors() {
             // when validating for being well formed,
@@ -329,17 +360,33 @@ impl Block {
         Ok(())
     }
 
-    pub fn transactions_to_execute(&self, validators: &[AccountAddress]) -> Vec<Transaction> {
+    /// The user transactions of the payload, in the order they are proposed.
+    pub fn payload_transactions(&self) -> Vec<SignedTransaction> {
+        self.payload()
+            .unwrap_or(&Payload::empty())
+            .clone()
+            .into_iter()
+            .collect()
+    }
+
+    /// `txns` are the user transactions of the payload, in the order they get executed.
+    /// The validator transactions of the block are executed right after the block metadata.
+    pub fn transactions_to_execute(
+        &self,
+        validators: &[AccountAddress],
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Transaction> {
         once(Transaction::BlockMetadata(
             self.new_block_metadata(validators),
         ))
         .chain(
-            self.payload()
-                .unwrap_or(&Payload::empty())
-                .clone()
+            self.validator_txns()
+                .cloned()
+                .unwrap_or_default()
                 .into_iter()
-                .map(Transaction::UserTransaction),
+                .map(Transaction::ValidatorTransaction),
         )
+        .chain(txns.into_iter().map(Transaction::UserTransaction))
         .chain(once(Transaction::StateCheckpoint(self.id)))
         .collect()
     }
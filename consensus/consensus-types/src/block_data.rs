@@ -12,6 +12,7 @@ use aptos_types::aggregate_signature::AggregateSignature;
 use aptos_types::{
     block_info::BlockInfo,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_txn::ValidatorTransaction,
 };
 use mirai_annotations::*;
 use serde::{Deserialize, Serialize};
@@ -41,6 +42,15 @@ pub enum BlockType {
     /// from the previous epoch.  The genesis block is used as the the first root block of the
     /// BlockTree for all epochs.
     Genesis,
+    /// A proposal that also carries validator transactions, which are executed after the block
+    /// metadata and before the transactions of the payload. Only proposed when validator
+    /// transactions are enabled in the on-chain consensus config.
+    ProposalExt {
+        validator_txns: Vec<ValidatorTransaction>,
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+    },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
@@ -78,10 +88,11 @@ pub struct BlockData {
 
 impl BlockData {
     pub fn author(&self) -> Option<Author> {
-        if let BlockType::Proposal { author, .. } = self.block_type {
-            Some(author)
-        } else {
-            None
+        match self.block_type {
+            BlockType::Proposal { author, .. } | BlockType::ProposalExt { author, .. } => {
+                Some(author)
+            }
+            _ => None,
         }
     }
 
@@ -98,8 +109,17 @@ impl BlockData {
     }
 
     pub fn payload(&self) -> Option<&Payload> {
-        if let BlockType::Proposal { payload, .. } = &self.block_type {
-            Some(payload)
+        match &self.block_type {
+            BlockType::Proposal { payload, .. } | BlockType::ProposalExt { payload, .. } => {
+                Some(payload)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn validator_txns(&self) -> Option<&Vec<ValidatorTransaction>> {
+        if let BlockType::ProposalExt { validator_txns, .. } = &self.block_type {
+            Some(validator_txns)
         } else {
             None
         }
@@ -131,6 +151,9 @@ impl BlockData {
         match self.block_type {
             BlockType::Proposal {
                 ref failed_authors, ..
+            }
+            | BlockType::ProposalExt {
+                ref failed_authors, ..
             } => Some(failed_authors),
             BlockType::NilBlock { ref failed_authors } => Some(failed_authors),
             BlockType::Genesis => None,
@@ -231,6 +254,29 @@ impl BlockData {
         }
     }
 
+    pub fn new_proposal_ext(
+        validator_txns: Vec<ValidatorTransaction>,
+        payload: Payload,
+        author: Author,
+        failed_authors: Vec<(Round, Author)>,
+        round: Round,
+        timestamp_usecs: u64,
+        quorum_cert: QuorumCert,
+    ) -> Self {
+        Self {
+            epoch: quorum_cert.certified_block().epoch(),
+            round,
+            timestamp_usecs,
+            quorum_cert,
+            block_type: BlockType::ProposalExt {
+                validator_txns,
+                payload,
+                author,
+                failed_authors,
+            },
+        }
+    }
+
     /// It's a reconfiguration suffix block if the parent block's executed state indicates next epoch.
     pub fn is_reconfiguration_suffix(&self) -> bool {
         self.quorum_cert.certified_block().has_reconfiguration()
@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ReplayProtector, SignedTransaction},
+};
 use serde::{Deserialize, Serialize};
 use std::{fmt, fmt::Write};
 
@@ -15,12 +18,12 @@ pub type Author = AccountAddress;
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct TransactionSummary {
     pub sender: AccountAddress,
-    pub sequence_number: u64,
+    pub replay_protector: ReplayProtector,
 }
 
 impl fmt::Display for TransactionSummary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.sender, self.sequence_number,)
+        write!(f, "{}:{}", self.sender, self.replay_protector,)
     }
 }
 
@@ -95,7 +98,7 @@ impl From<&Vec<&Payload>> for PayloadFilter {
                         for txn in txns {
                             exclude_txns.push(TransactionSummary {
                                 sender: txn.sender(),
-                                sequence_number: txn.sequence_number(),
+                                replay_protector: txn.replay_protector(),
                             });
                         }
                     }
@@ -0,0 +1,97 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::Author;
+use anyhow::ensure;
+use aptos_crypto::bls12381;
+use aptos_types::{keyless::ProviderJWKs, validator_verifier::ValidatorVerifier};
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use std::fmt;
+
+/// RPC to get the JWKs of an issuer observed by a peer, to certify an update of the issuer's
+/// on-chain JWKs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct JWKObservationRequest {
+    epoch: u64,
+    issuer: String,
+}
+
+impl JWKObservationRequest {
+    pub fn new(epoch: u64, issuer: String) -> Self {
+        Self { epoch, issuer }
+    }
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+}
+
+impl fmt::Display for JWKObservationRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[JWKObservationRequest for {} in epoch {}]",
+            self.issuer, self.epoch
+        )
+    }
+}
+
+/// The update of an issuer's JWKs a validator observed, signed with its consensus key.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ObservedUpdate {
+    author: Author,
+    update: ProviderJWKs,
+    signature: bls12381::Signature,
+}
+
+impl ObservedUpdate {
+    pub fn new(author: Author, update: ProviderJWKs, signature: bls12381::Signature) -> Self {
+        Self {
+            author,
+            update,
+            signature,
+        }
+    }
+
+    pub fn author(&self) -> Author {
+        self.author
+    }
+
+    pub fn update(&self) -> &ProviderJWKs {
+        &self.update
+    }
+
+    pub fn signature(&self) -> &bls12381::Signature {
+        &self.signature
+    }
+
+    /// Verifies that the update is for the requested issuer and signed by its author
+    pub fn verify(
+        &self,
+        request: &JWKObservationRequest,
+        sig_verifier: &ValidatorVerifier,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.update.issuer == request.issuer(),
+            "observed the JWKs of {}, rather than {}",
+            self.update.issuer,
+            request.issuer()
+        );
+        sig_verifier.verify(self.author, &self.update, &self.signature)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for ObservedUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[ObservedUpdate by {}: {}]",
+            self.author.short_str(),
+            self.update
+        )
+    }
+}
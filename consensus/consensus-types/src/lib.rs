@@ -10,11 +10,13 @@ pub mod common;
 pub mod epoch_retrieval;
 pub mod executed_block;
 pub mod experimental;
+pub mod jwk_observation;
 pub mod proposal_msg;
 pub mod quorum_cert;
 pub mod request_response;
 pub mod safety_data;
 pub mod sync_info;
+pub mod sync_point;
 pub mod timeout_2chain;
 pub mod vote;
 pub mod vote_data;
@@ -0,0 +1,125 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{block::Block, sync_info::SyncInfo};
+use anyhow::ensure;
+use aptos_crypto::hash::HashValue;
+use aptos_types::validator_verifier::ValidatorVerifier;
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use std::fmt;
+
+// A sync point replaces state sync only for short outages, beyond that state sync is cheaper
+// than replaying the blocks, and the response size has to stay bounded.
+pub const MAX_BLOCKS_PER_SYNC_POINT: u64 = 50;
+
+/// RPC to get the latest certified sync point of a peer together with the blocks connecting it
+/// to a block the requester already has.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SyncPointRequest {
+    known_block_id: HashValue,
+    max_blocks: u64,
+}
+
+impl SyncPointRequest {
+    pub fn new(known_block_id: HashValue, max_blocks: u64) -> Self {
+        Self {
+            known_block_id,
+            max_blocks,
+        }
+    }
+    pub fn known_block_id(&self) -> HashValue {
+        self.known_block_id
+    }
+    pub fn max_blocks(&self) -> u64 {
+        self.max_blocks
+    }
+}
+
+impl fmt::Display for SyncPointRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[SyncPointRequest from known id {} with at most {} blocks]",
+            self.known_block_id, self.max_blocks
+        )
+    }
+}
+
+/// Carries the peer's SyncInfo and the blocks from the block certified by its highest quorum
+/// cert back to (excluding) the requested known block, newest first.
+/// The blocks are empty if the known block can not be reached within the requested limit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SyncPointResponse {
+    sync_info: SyncInfo,
+    blocks: Vec<Block>,
+}
+
+impl SyncPointResponse {
+    pub fn new(sync_info: SyncInfo, blocks: Vec<Block>) -> Self {
+        Self { sync_info, blocks }
+    }
+
+    pub fn sync_info(&self) -> &SyncInfo {
+        &self.sync_info
+    }
+
+    pub fn blocks(&self) -> &Vec<Block> {
+        &self.blocks
+    }
+
+    pub fn verify(
+        &self,
+        request: SyncPointRequest,
+        sig_verifier: &ValidatorVerifier,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.blocks.len() as u64 <= request.max_blocks(),
+            "too many blocks returned, expect at most {}, get {}",
+            request.max_blocks(),
+            self.blocks.len(),
+        );
+        self.sync_info.verify(sig_verifier)?;
+        if self.blocks.is_empty() {
+            return Ok(());
+        }
+        let last_parent_id = self.blocks.iter().try_fold(
+            self.sync_info.highest_quorum_cert().certified_block().id(),
+            |expected_id, block| {
+                block.validate_signature(sig_verifier)?;
+                block.verify_well_formed()?;
+                ensure!(
+                    block.id() == expected_id,
+                    "blocks doesn't form a chain: expect {}, get {}",
+                    expected_id,
+                    block.id()
+                );
+                Ok(block.parent_id())
+            },
+        )?;
+        ensure!(
+            last_parent_id == request.known_block_id(),
+            "blocks don't reach the known block: expect {}, get {}",
+            request.known_block_id(),
+            last_parent_id
+        );
+        Ok(())
+    }
+}
+
+impl fmt::Display for SyncPointResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[SyncPointResponse: sync_info: {}, num_blocks: {}, block_ids: ",
+            self.sync_info,
+            self.blocks.len(),
+        )?;
+
+        f.debug_list()
+            .entries(self.blocks.iter().map(|b| b.id().short_str()))
+            .finish()?;
+
+        write!(f, "]")
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Error;
+use aptos_crypto::{bls12381, hash::CryptoHash};
+use aptos_secure_signer::RemoteSigner;
+use aptos_types::validator_signer::ValidatorSigner;
+use consensus_types::common::Author;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Signs on behalf of the validator, either with the consensus key exported from storage, or with
+/// a consensus key held by a remote signing backend.
+pub enum ConsensusSigner {
+    Local(ValidatorSigner),
+    Remote {
+        author: Author,
+        signer: Arc<RemoteSigner<bls12381::Signature>>,
+    },
+}
+
+impl ConsensusSigner {
+    pub fn author(&self) -> Author {
+        match self {
+            ConsensusSigner::Local(signer) => signer.author(),
+            ConsensusSigner::Remote { author, .. } => *author,
+        }
+    }
+
+    pub fn public_key(&self) -> bls12381::PublicKey {
+        match self {
+            ConsensusSigner::Local(signer) => signer.public_key(),
+            ConsensusSigner::Remote { signer, .. } => signer.public_key().clone(),
+        }
+    }
+
+    pub fn sign<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+    ) -> Result<bls12381::Signature, Error> {
+        match self {
+            ConsensusSigner::Local(signer) => Ok(signer.sign(message)),
+            ConsensusSigner::Remote { signer, .. } => Ok(signer.sign(message)?),
+        }
+    }
+}
@@ -55,6 +55,8 @@ pub enum Error {
     WaypointOutOfDate(u64, u64, u64, u64),
     #[error("Invalid Timeout: {0}")]
     InvalidTimeout(String),
+    #[error("Remote signer error: {0}")]
+    RemoteSignerError(String),
 }
 
 impl From<serde_json::Error> for Error {
@@ -69,6 +71,12 @@ impl From<aptos_secure_net::Error> for Error {
     }
 }
 
+impl From<aptos_secure_signer::Error> for Error {
+    fn from(error: aptos_secure_signer::Error) -> Self {
+        Self::RemoteSignerError(error.to_string())
+    }
+}
+
 impl From<aptos_secure_storage::Error> for Error {
     fn from(error: aptos_secure_storage::Error) -> Self {
         match error {
@@ -9,9 +9,11 @@ use crate::{
 use aptos_crypto::{bls12381, PrivateKey};
 use aptos_global_constants::{CONSENSUS_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT};
 use aptos_logger::prelude::*;
+use aptos_secure_signer::RemoteSigner;
 use aptos_secure_storage::{KVStorage, Storage};
 use aptos_types::waypoint::Waypoint;
 use consensus_types::{common::Author, safety_data::SafetyData};
+use std::sync::Arc;
 
 /// SafetyRules needs an abstract storage interface to act as a common utility for storing
 /// persistent data to local disk, cloud, secrets managers, or even memory (for tests)
@@ -21,10 +23,14 @@ use consensus_types::{common::Author, safety_data::SafetyData};
 /// only ever be used by safety rules, we maintain an in-memory copy to avoid issuing reads
 /// to the internal storage if the SafetyData hasn't changed. On writes, we update the
 /// cache and internal storage.
+///
+/// If the consensus key is held by a remote signer, e.g. an HSM, the storage holds no consensus
+/// key and SafetyRules signs with `remote_signer` instead.
 pub struct PersistentSafetyStorage {
     enable_cached_safety_data: bool,
     cached_safety_data: Option<SafetyData>,
     internal_store: Storage,
+    remote_signer: Option<Arc<RemoteSigner<bls12381::Signature>>>,
 }
 
 impl PersistentSafetyStorage {
@@ -41,12 +47,41 @@ impl PersistentSafetyStorage {
         Self::initialize_keys_and_accounts(&mut internal_store, author, consensus_private_key)
             .expect("Unable to initialize keys and accounts in storage");
 
+        Self::initialize_safety_data(internal_store, waypoint, enable_cached_safety_data)
+    }
+
+    /// Like [`PersistentSafetyStorage::initialize`], for a consensus key held by a remote signer,
+    /// which is not stored.
+    pub fn initialize_with_remote_signer(
+        mut internal_store: Storage,
+        author: Author,
+        remote_signer: Arc<RemoteSigner<bls12381::Signature>>,
+        waypoint: Waypoint,
+        enable_cached_safety_data: bool,
+    ) -> Self {
+        let result = internal_store.set(OWNER_ACCOUNT, author);
+        if let Err(aptos_secure_storage::Error::KeyAlreadyExists(_)) = result {
+            warn!("Attempted to re-initialize existing storage");
+        } else {
+            result.expect("Unable to initialize accounts in storage");
+        }
+
+        Self::initialize_safety_data(internal_store, waypoint, enable_cached_safety_data)
+            .with_remote_signer(remote_signer)
+    }
+
+    fn initialize_safety_data(
+        internal_store: Storage,
+        waypoint: Waypoint,
+        enable_cached_safety_data: bool,
+    ) -> Self {
         // Create the new persistent safety storage
         let safety_data = SafetyData::new(1, 0, 0, 0, None);
         let mut persisent_safety_storage = Self {
             enable_cached_safety_data,
             cached_safety_data: Some(safety_data.clone()),
             internal_store,
+            remote_signer: None,
         };
 
         // Initialize the safety data and waypoint
@@ -87,9 +122,23 @@ impl PersistentSafetyStorage {
             enable_cached_safety_data,
             cached_safety_data: None,
             internal_store,
+            remote_signer: None,
         }
     }
 
+    /// Signs with the consensus key held by `remote_signer`, rather than with one in storage.
+    pub fn with_remote_signer(
+        mut self,
+        remote_signer: Arc<RemoteSigner<bls12381::Signature>>,
+    ) -> Self {
+        self.remote_signer = Some(remote_signer);
+        self
+    }
+
+    pub fn remote_signer(&self) -> Option<&Arc<RemoteSigner<bls12381::Signature>>> {
+        self.remote_signer.as_ref()
+    }
+
     pub fn author(&self) -> Result<Author, Error> {
         let _timer = counters::start_timer("get", OWNER_ACCOUNT);
         Ok(self.internal_store.get(OWNER_ACCOUNT).map(|v| v.value)?)
@@ -6,7 +6,7 @@ use crate::{
     remote_service::{self, RemoteService},
     safety_rules_manager,
 };
-use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{RemoteServiceAuthentication, SafetyRulesConfig, SafetyRulesService};
 
 use std::net::SocketAddr;
 
@@ -23,19 +23,32 @@ impl Process {
             _ => panic!("Unexpected SafetyRules service: {:?}", config.service),
         };
         let server_addr = service.server_address();
+        if let Some(authentication) = &service.authentication {
+            assert_eq!(
+                authentication.identity_key.public_key(),
+                authentication.server_key,
+                "The identity key of the SafetyRules process does not match the server key"
+            );
+        }
 
         Self {
             data: Some(ProcessData {
                 server_addr,
                 storage,
                 network_timeout: config.network_timeout_ms,
+                authentication: service.authentication.clone(),
             }),
         }
     }
 
     pub fn start(&mut self) {
         let data = self.data.take().expect("Unable to retrieve ProcessData");
-        remote_service::execute(data.storage, data.server_addr, data.network_timeout);
+        remote_service::execute(
+            data.storage,
+            data.server_addr,
+            data.network_timeout,
+            data.authentication,
+        );
     }
 }
 
@@ -44,18 +57,25 @@ struct ProcessData {
     storage: PersistentSafetyStorage,
     // Timeout in Seconds for network operations
     network_timeout: u64,
+    authentication: Option<RemoteServiceAuthentication>,
 }
 
 pub struct ProcessService {
     server_addr: SocketAddr,
     network_timeout_ms: u64,
+    authentication: Option<RemoteServiceAuthentication>,
 }
 
 impl ProcessService {
-    pub fn new(server_addr: SocketAddr, network_timeout: u64) -> Self {
+    pub fn new(
+        server_addr: SocketAddr,
+        network_timeout: u64,
+        authentication: Option<RemoteServiceAuthentication>,
+    ) -> Self {
         Self {
             server_addr,
             network_timeout_ms: network_timeout,
+            authentication,
         }
     }
 }
@@ -68,4 +88,8 @@ impl RemoteService for ProcessService {
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout_ms
     }
+
+    fn authentication(&self) -> Option<&RemoteServiceAuthentication> {
+        self.authentication.as_ref()
+    }
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::ConsensusSigner,
     consensus_state::ConsensusState,
     counters,
     error::Error,
@@ -38,7 +39,7 @@ pub(crate) fn next_round(round: Round) -> Result<Round, Error> {
 /// @TODO consider a cache of verified QCs to cut down on verification costs
 pub struct SafetyRules {
     pub(crate) persistent_storage: PersistentSafetyStorage,
-    pub(crate) validator_signer: Option<ValidatorSigner>,
+    pub(crate) validator_signer: Option<ConsensusSigner>,
     pub(crate) epoch_state: Option<EpochState>,
 }
 
@@ -80,11 +81,10 @@ impl SafetyRules {
         &self,
         message: &T,
     ) -> Result<bls12381::Signature, Error> {
-        let signer = self.signer()?;
-        Ok(signer.sign(message))
+        self.signer()?.sign(message)
     }
 
-    pub(crate) fn signer(&self) -> Result<&ValidatorSigner, Error> {
+    pub(crate) fn signer(&self) -> Result<&ConsensusSigner, Error> {
         self.validator_signer
             .as_ref()
             .ok_or_else(|| Error::NotInitialized("validator_signer".into()))
@@ -268,6 +268,22 @@ impl SafetyRules {
                         "in set",
                     );
                     Ok(())
+                } else if let Some(signer) = self.persistent_storage.remote_signer() {
+                    // The consensus key is held by a remote signer, which cannot be rotated
+                    // from here, so it must already hold the expected key.
+                    if signer.public_key() == &expected_key {
+                        self.validator_signer = Some(ConsensusSigner::Remote {
+                            author,
+                            signer: signer.clone(),
+                        });
+                        Ok(())
+                    } else {
+                        Err(Error::ValidatorKeyNotFound(format!(
+                            "Remote signer holds {:?}, rather than {:?}",
+                            signer.public_key(),
+                            expected_key
+                        )))
+                    }
                 } else {
                     // Try to export the consensus key directly from storage.
                     match self
@@ -275,8 +291,9 @@ impl SafetyRules {
                         .consensus_key_for_version(expected_key)
                     {
                         Ok(consensus_key) => {
-                            self.validator_signer =
-                                Some(ValidatorSigner::new(author, consensus_key));
+                            self.validator_signer = Some(ConsensusSigner::Local(
+                                ValidatorSigner::new(author, consensus_key),
+                            ));
                             Ok(())
                         }
                         Err(Error::SecureStorageMissingDataError(error)) => {
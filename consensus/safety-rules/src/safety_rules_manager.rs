@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::ConsensusSigner,
+    error::Error,
     local_client::LocalClient,
     persistent_safety_storage::PersistentSafetyStorage,
     process::ProcessService,
@@ -10,11 +12,24 @@ use crate::{
     thread::ThreadService,
     SafetyRules, TSafetyRules,
 };
-use aptos_config::config::{InitialSafetyRulesConfig, SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{
+    InitialSafetyRulesConfig, RemoteServiceAuthentication, SafetyRulesConfig, SafetyRulesService,
+};
+use aptos_crypto::bls12381;
 use aptos_infallible::RwLock;
+use aptos_secure_signer::RemoteSigner;
 use aptos_secure_storage::{KVStorage, Storage};
+use aptos_types::{epoch_state::EpochState, validator_signer::ValidatorSigner};
 use std::{convert::TryInto, net::SocketAddr, sync::Arc};
 
+fn remote_signer(config: &SafetyRulesConfig) -> Option<Arc<RemoteSigner<bls12381::Signature>>> {
+    config.consensus_signer.as_ref().map(|signer_config| {
+        let signer: RemoteSigner<bls12381::Signature> = RemoteSigner::from_config(signer_config)
+            .unwrap_or_else(|error| panic!("Unable to initialize consensus signer: {}", error));
+        Arc::new(signer)
+    })
+}
+
 pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
     let backend = &config.backend;
     let internal_storage: Storage = backend.try_into().expect("Unable to initialize storage");
@@ -22,7 +37,9 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
         panic!("Storage is not available: {:?}", error);
     }
 
-    if let Some(test_config) = &config.test {
+    let remote_signer = remote_signer(config);
+
+    let storage = if let Some(test_config) = &config.test {
         let author = test_config.author;
         let consensus_private_key = test_config
             .consensus_key
@@ -54,22 +71,76 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
             let backend = &config.backend;
             let internal_storage: Storage =
                 backend.try_into().expect("Unable to initialize storage");
-            PersistentSafetyStorage::initialize(
-                internal_storage,
-                identity_blob
-                    .account_address
-                    .expect("AccountAddress needed for safety rules"),
-                identity_blob
-                    .consensus_private_key
-                    .expect("Consensus key needed for safety rules"),
-                waypoint,
-                config.enable_cached_safety_data,
-            )
+            let author = identity_blob
+                .account_address
+                .expect("AccountAddress needed for safety rules");
+            match &remote_signer {
+                // The consensus key is held by the signer, so none is needed in the identity blob
+                Some(remote_signer) => PersistentSafetyStorage::initialize_with_remote_signer(
+                    internal_storage,
+                    author,
+                    remote_signer.clone(),
+                    waypoint,
+                    config.enable_cached_safety_data,
+                ),
+                None => PersistentSafetyStorage::initialize(
+                    internal_storage,
+                    author,
+                    identity_blob
+                        .consensus_private_key
+                        .expect("Consensus key needed for safety rules"),
+                    waypoint,
+                    config.enable_cached_safety_data,
+                ),
+            }
         } else {
             panic!(
                 "Safety rules storage is not initialized, provide an initial safety rules config"
             )
         }
+    };
+
+    match remote_signer {
+        Some(remote_signer) => storage.with_remote_signer(remote_signer),
+        None => storage,
+    }
+}
+
+/// Returns a signer with the consensus key of this validator in `epoch_state`, for the components
+/// signing on behalf of the validator outside of SafetyRules, e.g. JWK consensus. The storage
+/// must already be initialized by SafetyRules, and the key isn't available when SafetyRules run
+/// in a separate process.
+pub fn consensus_signer(
+    config: &SafetyRulesConfig,
+    epoch_state: &EpochState,
+) -> Result<ConsensusSigner, Error> {
+    if let SafetyRulesService::Process(_) = &config.service {
+        return Err(Error::NotInitialized(
+            "consensus signer of a SafetyRules process".into(),
+        ));
+    }
+    let storage = PersistentSafetyStorage::new(
+        Storage::from(&config.backend),
+        config.enable_cached_safety_data,
+    );
+    let author = storage.author()?;
+    let expected_key = epoch_state
+        .verifier
+        .get_public_key(&author)
+        .ok_or_else(|| Error::ValidatorNotInSet(author.to_string()))?;
+    match remote_signer(config) {
+        Some(signer) if signer.public_key() == &expected_key => {
+            Ok(ConsensusSigner::Remote { author, signer })
+        }
+        Some(signer) => Err(Error::ValidatorKeyNotFound(format!(
+            "Remote signer holds {:?}, rather than {:?}",
+            signer.public_key(),
+            expected_key
+        ))),
+        None => Ok(ConsensusSigner::Local(ValidatorSigner::new(
+            author,
+            storage.consensus_key_for_version(expected_key)?,
+        ))),
     }
 }
 
@@ -87,7 +158,11 @@ pub struct SafetyRulesManager {
 impl SafetyRulesManager {
     pub fn new(config: &SafetyRulesConfig) -> Self {
         if let SafetyRulesService::Process(conf) = &config.service {
-            return Self::new_process(conf.server_address(), config.network_timeout_ms);
+            return Self::new_process(
+                conf.server_address(),
+                config.network_timeout_ms,
+                conf.authentication.clone(),
+            );
         }
 
         let storage = storage(config);
@@ -106,8 +181,12 @@ impl SafetyRulesManager {
         }
     }
 
-    pub fn new_process(server_addr: SocketAddr, timeout_ms: u64) -> Self {
-        let process_service = ProcessService::new(server_addr, timeout_ms);
+    pub fn new_process(
+        server_addr: SocketAddr,
+        timeout_ms: u64,
+        authentication: Option<RemoteServiceAuthentication>,
+    ) -> Self {
+        let process_service = ProcessService::new(server_addr, timeout_ms, authentication);
         Self {
             internal_safety_rules: SafetyRulesWrapper::Process(process_service),
         }
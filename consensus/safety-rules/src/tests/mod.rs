@@ -3,6 +3,7 @@
 
 mod local;
 mod networking;
+mod remote_signer;
 mod safety_rules;
 mod serializer;
 mod suite;
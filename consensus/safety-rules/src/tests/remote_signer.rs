@@ -0,0 +1,30 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{test_utils, tests::suite, PersistentSafetyStorage, SafetyRules};
+use aptos_secure_signer::{in_memory::InMemoryBackend, RemoteSigner};
+use aptos_secure_storage::{InMemoryStorage, Storage};
+use aptos_types::validator_signer::ValidatorSigner;
+use std::sync::Arc;
+
+#[test]
+fn test() {
+    suite::run_test_suite(&safety_rules());
+}
+
+fn safety_rules() -> suite::Callback {
+    Box::new(move || {
+        let signer = ValidatorSigner::from_int(0);
+        let backend = InMemoryBackend::Bls12381(signer.private_key().clone());
+        let remote_signer = RemoteSigner::new(Arc::new(backend)).unwrap();
+        let storage = PersistentSafetyStorage::initialize_with_remote_signer(
+            Storage::from(InMemoryStorage::new()),
+            signer.author(),
+            Arc::new(remote_signer),
+            test_utils::validator_signers_to_waypoint(&[&signer]),
+            true,
+        );
+        let safety_rules = Box::new(SafetyRules::new(storage));
+        (safety_rules, signer)
+    })
+}
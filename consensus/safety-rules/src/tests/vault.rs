@@ -26,6 +26,7 @@ fn safety_rules() -> suite::Callback {
             ROOT_TOKEN.to_string(),
             None,
             None,
+            None,
             true,
             None,
             None,
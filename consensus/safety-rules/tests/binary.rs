@@ -2,31 +2,90 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_config::{
-    config::{NodeConfig, PersistableConfig, RemoteService, SafetyRulesService},
+    config::{
+        NodeConfig, PersistableConfig, RemoteService, RemoteServiceAuthentication,
+        SafetyRulesConfig, SafetyRulesService,
+    },
+    keys::ConfigKey,
     utils,
 };
-use aptos_types::validator_signer::ValidatorSigner;
+use aptos_crypto::{x25519, PrivateKey, Uniform};
+use aptos_types::{network_address::NetworkAddress, validator_signer::ValidatorSigner};
+use rand::{rngs::StdRng, SeedableRng};
 use safety_rules::{test_utils, SafetyRulesManager};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 const BINARY: &str = env!("CARGO_BIN_EXE_safety-rules");
 
 #[test]
-fn test_consensus_state() {
+fn test_unauthenticated_process_rejected() {
+    let mut config = test_config();
+    let server_port = utils::get_available_port();
+    let server_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port).into();
+    config.service = SafetyRulesService::Process(RemoteService {
+        server_address,
+        authentication: None,
+    });
+
+    let config_path = aptos_temppath::TempPath::new();
+    config_path.create_as_file().unwrap();
+    config.save_config(config_path.path()).unwrap();
+    let status = std::process::Command::new(BINARY)
+        .arg(config_path.path())
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}
+
+#[test]
+fn test_consensus_state_authenticated() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let server_key = x25519::PrivateKey::generate(&mut rng);
+    let server_public_key = server_key.public_key();
+    let client_key = x25519::PrivateKey::generate(&mut rng);
+    let client_public_key = client_key.public_key();
+    let server_port = utils::get_available_port();
+    let server_address: NetworkAddress =
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port).into();
+
+    let mut process_config = test_config();
+    let mut consensus_config = process_config.clone();
+    process_config.service = SafetyRulesService::Process(RemoteService {
+        server_address: server_address.clone(),
+        authentication: Some(RemoteServiceAuthentication {
+            identity_key: ConfigKey::new(server_key),
+            server_key: server_public_key,
+            trusted_clients: vec![client_public_key],
+        }),
+    });
+    consensus_config.service = SafetyRulesService::Process(RemoteService {
+        server_address,
+        authentication: Some(RemoteServiceAuthentication {
+            identity_key: ConfigKey::new(client_key),
+            server_key: server_public_key,
+            trusted_clients: vec![],
+        }),
+    });
+
+    check_consensus_state(&process_config, &consensus_config);
+}
+
+fn test_config() -> SafetyRulesConfig {
     let mut config = NodeConfig::random().consensus.safety_rules;
     let test_config = config.test.as_mut().unwrap();
     let private_key = test_config.consensus_key.as_ref().unwrap().private_key();
     let signer = ValidatorSigner::new(test_config.author, private_key);
     let waypoint = test_utils::validator_signers_to_waypoint(&[&signer]);
     test_config.waypoint = Some(waypoint);
+    config
+}
 
-    let server_port = utils::get_available_port();
-    let server_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port).into();
-    config.service = SafetyRulesService::Process(RemoteService { server_address });
-
+/// Starts the safety-rules binary with `process_config` and queries it as configured by
+/// `consensus_config`.
+fn check_consensus_state(process_config: &SafetyRulesConfig, consensus_config: &SafetyRulesConfig) {
     let config_path = aptos_temppath::TempPath::new();
     config_path.create_as_file().unwrap();
-    config.save_config(config_path.path()).unwrap();
+    process_config.save_config(config_path.path()).unwrap();
 
     let mut command = std::process::Command::new(BINARY);
     command
@@ -36,7 +95,7 @@ fn test_consensus_state() {
         .stderr(std::process::Stdio::inherit());
     let mut child = command.spawn().unwrap();
 
-    let safety_rules_manager = SafetyRulesManager::new(&config);
+    let safety_rules_manager = SafetyRulesManager::new(consensus_config);
     let mut safety_rules = safety_rules_manager.client();
     let consensus_state = safety_rules.consensus_state();
 
@@ -3,13 +3,14 @@
 
 use crate::{
     block_storage::{BlockReader, BlockStore},
+    counters,
     logging::{LogEvent, LogSchema},
-    network::{IncomingBlockRetrievalRequest, NetworkSender},
+    network::{IncomingBlockRetrievalRequest, IncomingSyncPointRequest, NetworkSender},
     network_interface::ConsensusMsg,
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
     state_replication::StateComputer,
 };
-use anyhow::{bail, Context};
+use anyhow::{bail, ensure, Context};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_types::{
@@ -24,6 +25,7 @@ use consensus_types::{
     common::Author,
     quorum_cert::QuorumCert,
     sync_info::SyncInfo,
+    sync_point::{SyncPointRequest, SyncPointResponse, MAX_BLOCKS_PER_SYNC_POINT},
 };
 use fail::fail_point;
 use rand::{prelude::*, Rng};
@@ -155,7 +157,9 @@ impl BlockStore {
 
     /// Check the highest ordered cert sent by peer to see if we're behind and start a fast
     /// forward sync if the committed block doesn't exist in our tree.
-    /// It works as follows:
+    /// If the gap is small enough, the missing blocks are first requested as a sync point from
+    /// the peers, and state sync is only used if that fails.
+    /// The state sync works as follows:
     /// 1. request the gap blocks from the peer (from highest_ledger_info to highest_ordered_cert)
     /// 2. We persist the gap blocks to storage before start sync to ensure we could restart if we
     /// crash in the middle of the sync.
@@ -169,6 +173,31 @@ impl BlockStore {
         if !self.need_sync_for_ledger_info(highest_commit_cert.ledger_info()) {
             return Ok(());
         }
+        if highest_commit_cert.commit_info().round()
+            <= self.commit_root().round() + MAX_BLOCKS_PER_SYNC_POINT
+        {
+            match self
+                .sync_to_sync_point(&highest_commit_cert, retriever)
+                .await
+            {
+                Ok(()) => {
+                    counters::SYNC_POINT_COUNT
+                        .with_label_values(&["success"])
+                        .inc();
+                    return Ok(());
+                }
+                Err(e) => {
+                    counters::SYNC_POINT_COUNT
+                        .with_label_values(&["fallback"])
+                        .inc();
+                    warn!(
+                        LogSchema::new(LogEvent::SyncPoint).remote_peer(retriever.preferred_peer),
+                        error = ?e,
+                        "Failed to sync through a sync point, falling back to state sync",
+                    );
+                }
+            }
+        }
         let (root, root_metadata, blocks, quorum_certs) = Self::fast_forward_sync(
             &highest_ordered_cert,
             &highest_commit_cert,
@@ -198,6 +227,43 @@ impl BlockStore {
         Ok(())
     }
 
+    /// Catch up by replaying the blocks between our ordered root and the highest quorum cert of
+    /// a peer, which avoids a state sync after a brief outage.
+    /// The blocks are inserted oldest first through the regular execution path, the remaining
+    /// certificates are inserted by the caller afterwards.
+    async fn sync_to_sync_point(
+        &self,
+        highest_commit_cert: &QuorumCert,
+        retriever: &mut BlockRetriever,
+    ) -> anyhow::Result<()> {
+        let ordered_root = self.ordered_root();
+        let response = retriever
+            .retrieve_sync_point(highest_commit_cert, ordered_root.id())
+            .await?;
+        let sync_info = response.sync_info();
+        ensure!(
+            sync_info.epoch() == ordered_root.epoch(),
+            "Sync point from epoch {}, local epoch {}",
+            sync_info.epoch(),
+            ordered_root.epoch()
+        );
+        ensure!(
+            sync_info.highest_certified_round() >= highest_commit_cert.commit_info().round(),
+            "Sync point at round {} is behind the commit cert at round {}",
+            sync_info.highest_certified_round(),
+            highest_commit_cert.commit_info().round()
+        );
+        info!(
+            LogSchema::new(LogEvent::SyncPoint).round(ordered_root.round()),
+            "Sync to sync point: {}", response,
+        );
+        for block in response.blocks().iter().rev() {
+            self.insert_single_quorum_cert(block.quorum_cert().clone())?;
+            self.execute_and_insert_block(block.clone()).await?;
+        }
+        self.insert_single_quorum_cert(sync_info.highest_quorum_cert().clone())
+    }
+
     pub async fn fast_forward_sync<'a>(
         highest_ordered_cert: &'a QuorumCert,
         highest_commit_cert: &'a QuorumCert,
@@ -381,6 +447,47 @@ impl BlockStore {
             .send(Ok(response_bytes.into()))
             .map_err(|e| anyhow::anyhow!("{:?}", e))
     }
+
+    /// Responds with the local SyncInfo and the chain of blocks from the block certified by the
+    /// highest quorum cert back to the known block of the requester. The blocks are left empty
+    /// if the known block can not be reached within the requested number of blocks.
+    ///
+    /// The current version of the function is not really async, but keeping it this way for
+    /// future possible changes.
+    pub async fn process_sync_point_request(
+        &self,
+        request: IncomingSyncPointRequest,
+    ) -> anyhow::Result<()> {
+        fail_point!("consensus::process_sync_point_request", |_| {
+            Err(anyhow::anyhow!(
+                "Injected error in process_sync_point_request"
+            ))
+        });
+        let sync_info = self.sync_info();
+        let mut blocks = vec![];
+        let mut id = sync_info.highest_quorum_cert().certified_block().id();
+        while id != request.req.known_block_id() {
+            match self.get_block(id) {
+                Some(executed_block) if (blocks.len() as u64) < request.req.max_blocks() => {
+                    blocks.push(executed_block.block().clone());
+                    id = executed_block.parent_id();
+                }
+                _ => {
+                    blocks.clear();
+                    break;
+                }
+            }
+        }
+
+        let response = Box::new(SyncPointResponse::new(sync_info, blocks));
+        let response_bytes = request
+            .protocol
+            .to_bytes(&ConsensusMsg::SyncPointResponse(response))?;
+        request
+            .response_sender
+            .send(Ok(response_bytes.into()))
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
 }
 
 /// BlockRetriever is used internally to retrieve blocks
@@ -518,6 +625,53 @@ impl BlockRetriever {
         .await
     }
 
+    /// Retrieve the sync point connecting to known_block_id from the voters of the given QC.
+    ///
+    /// The first attempt always goes to preferred_peer, at most MAX_SYNC_POINT_ATTEMPTS peers are
+    /// tried as state sync is still available as a fallback.
+    async fn retrieve_sync_point(
+        &mut self,
+        qc: &QuorumCert,
+        known_block_id: HashValue,
+    ) -> anyhow::Result<SyncPointResponse> {
+        let mut peers = qc.ledger_info().get_voters(&self.validator_addresses);
+        let mut attempt = 0_u32;
+        while attempt < MAX_SYNC_POINT_ATTEMPTS && !peers.is_empty() {
+            let peer = self.pick_peer(attempt, &mut peers);
+            attempt += 1;
+            debug!(
+                LogSchema::new(LogEvent::SyncPoint).remote_peer(peer),
+                block_id = known_block_id,
+                "Fetching sync point, attempt {}",
+                attempt
+            );
+            let response = self
+                .network
+                .request_sync_point(
+                    SyncPointRequest::new(known_block_id, MAX_BLOCKS_PER_SYNC_POINT),
+                    peer,
+                    retrieval_timeout(attempt),
+                )
+                .await;
+            match response {
+                Ok(result) if !result.blocks().is_empty() => return Ok(result),
+                e => {
+                    warn!(
+                        remote_peer = peer,
+                        block_id = known_block_id,
+                        "{:?}, Failed to fetch sync point, trying another peer",
+                        e,
+                    );
+                }
+            }
+        }
+        bail!(
+            "Failed to fetch sync point from {} in {} attempts",
+            known_block_id,
+            attempt
+        )
+    }
+
     fn pick_peer(&self, attempt: u32, peers: &mut Vec<AccountAddress>) -> AccountAddress {
         assert!(!peers.is_empty(), "pick_peer on empty peer list");
 
@@ -541,6 +695,7 @@ impl BlockRetriever {
 // Max timeout is 16s=RETRIEVAL_INITIAL_TIMEOUT*(2^RETRIEVAL_MAX_EXP)
 const RETRIEVAL_INITIAL_TIMEOUT: Duration = Duration::from_millis(500);
 const RETRIEVAL_MAX_EXP: u32 = 2;
+const MAX_SYNC_POINT_ATTEMPTS: u32 = 3;
 
 /// Returns exponentially increasing timeout with
 /// limit of RETRIEVAL_INITIAL_TIMEOUT*(2^RETRIEVAL_MAX_EXP)
@@ -11,21 +11,81 @@ use crate::{
     state_computer::ExecutionProxy,
     txn_notifier::MempoolNotifier,
     util::time_service::ClockTimeService,
+    validator_txn_pool::ValidatorTxnPool,
 };
+use anyhow::anyhow;
 use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_vm::AptosVM;
 use consensus_notifications::ConsensusNotificationSender;
+use consensus_types::common::Round;
 use event_notifications::ReconfigNotificationListener;
 use executor::block_executor::BlockExecutor;
-use futures::channel::mpsc;
+use executor_service::RemoteExecutorClient;
+use executor_types::BlockExecutorTrait;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
 use network::application::storage::PeerMetadataStorage;
+use serde::Serialize;
 use std::sync::Arc;
 use storage_interface::DbReaderWriter;
 use tokio::runtime::{self, Runtime};
 
-/// Helper function to start consensus based on configuration and return the runtime
+/// How many state dump requests can be pending before new ones wait
+const STATE_DUMP_CHANNEL_SIZE: usize = 16;
+
+/// A summary of the consensus state of a running validator, for operators
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsensusStateDump {
+    /// The current epoch
+    pub epoch: u64,
+    /// The number of validators in the current epoch
+    pub num_validators: usize,
+    /// The id of the highest ordered block
+    pub ordered_root_id: HashValue,
+    /// The round of the highest ordered block
+    pub ordered_root_round: Round,
+    /// The round of the highest committed block
+    pub commit_root_round: Round,
+    /// The round of the block certified by the highest quorum cert
+    pub highest_quorum_cert_round: Round,
+    /// The round of the block certified by the highest ordered cert
+    pub highest_ordered_cert_round: Round,
+    /// The round of the block certified by the highest commit cert
+    pub highest_commit_cert_round: Round,
+}
+
+/// A request for the consensus state, which is answered with `None` before the first epoch starts
+pub(crate) type StateDumpRequest = oneshot::Sender<Option<ConsensusStateDump>>;
+
+/// Dumps the state of the consensus started with `start_consensus`
+#[derive(Clone)]
+pub struct ConsensusStateHandle {
+    sender: mpsc::Sender<StateDumpRequest>,
+}
+
+impl ConsensusStateHandle {
+    /// Returns the current consensus state, or `None` if no epoch has started yet
+    pub async fn dump(&self) -> anyhow::Result<Option<ConsensusStateDump>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.sender
+            .clone()
+            .send(response_sender)
+            .await
+            .map_err(|_| anyhow!("Consensus has stopped"))?;
+        response_receiver
+            .await
+            .map_err(|_| anyhow!("Consensus has stopped"))
+    }
+}
+
+/// Helper function to start consensus based on configuration and return the runtime, along with
+/// a handle to dump its state. The validator transactions put in `validator_txn_pool` are
+/// proposed when enabled by the on-chain consensus config.
 pub fn start_consensus(
     node_config: &NodeConfig,
     mut network_sender: ConsensusNetworkSender,
@@ -35,7 +95,8 @@ pub fn start_consensus(
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
-) -> Runtime {
+    validator_txn_pool: ValidatorTxnPool,
+) -> (Runtime, ConsensusStateHandle) {
     let runtime = runtime::Builder::new_multi_thread()
         .thread_name("consensus")
         .enable_all()
@@ -50,8 +111,20 @@ pub fn start_consensus(
         node_config.consensus.quorum_store_pull_timeout_ms,
     ));
 
+    let block_executor: Arc<dyn BlockExecutorTrait> = match &node_config.execution.remote_executor {
+        Some(remote_executor) => Arc::new(
+            BlockExecutor::<AptosVM>::new_with_transaction_block_executor(
+                aptos_db,
+                Arc::new(RemoteExecutorClient::new(
+                    remote_executor,
+                    node_config.execution.network_timeout_ms,
+                )),
+            ),
+        ),
+        None => Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+    };
     let state_computer = Arc::new(ExecutionProxy::new(
-        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        block_executor,
         txn_notifier,
         state_sync_notifier,
         commit_notifier.clone(),
@@ -75,13 +148,20 @@ pub fn start_consensus(
         storage,
         reconfig_events,
         commit_notifier,
+        validator_txn_pool,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
+    let (state_dump_sender, state_dump_receiver) = mpsc::channel(STATE_DUMP_CHANNEL_SIZE);
 
     runtime.spawn(network_task.start());
-    runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
+    runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, state_dump_receiver));
 
     debug!("Consensus started.");
-    runtime
+    (
+        runtime,
+        ConsensusStateHandle {
+            sender: state_dump_sender,
+        },
+    )
 }
@@ -334,6 +334,56 @@ pub static BLOCK_RETRIEVAL_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counters(queued,dequeued,dropped) related to sync point channel
+pub static SYNC_POINT_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_sync_point_channel_msgs_count",
+        "Counters(queued,dequeued,dropped) related to sync point channel",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Count of the rejoins through a sync point, labeled by result (success, fallback)
+pub static SYNC_POINT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_sync_point_count",
+        "Count of the rejoins through a sync point, labeled by result",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Counters(queued,dequeued,dropped) related to JWK observation request channel
+pub static JWK_OBSERVATION_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_jwk_observation_channel_msgs_count",
+        "Counters(queued,dequeued,dropped) related to JWK observation request channel",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Count of the JWK fetches from the OIDC providers, labeled by issuer and result (success, error)
+pub static JWK_FETCH_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_jwk_fetch_count",
+        "Count of the JWK fetches from the OIDC providers, labeled by issuer and result",
+        &["issuer", "result"]
+    )
+    .unwrap()
+});
+
+/// Count of the JWK updates certified by a quorum and put in the validator transaction pool
+pub static JWK_UPDATES_CERTIFIED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_jwk_updates_certified_count",
+        "Count of the JWK updates certified by a quorum, labeled by issuer",
+        &["issuer"]
+    )
+    .unwrap()
+});
+
 /// Count of the buffer manager retry requests since last restart.
 pub static BUFFER_MANAGER_RETRY_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
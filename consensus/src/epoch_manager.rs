@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    block_storage::BlockStore,
+    block_storage::{BlockReader, BlockStore},
     commit_notifier::CommitNotifier,
+    consensus_provider::{ConsensusStateDump, StateDumpRequest},
     counters,
     error::{error_kind, DbError},
     experimental::{
@@ -11,6 +12,7 @@ use crate::{
         decoupled_execution_utils::prepare_phases_and_buffer_manager,
         ordering_state_computer::OrderingStateComputer,
     },
+    jwk_consensus::jwk_manager::{JWKManager, JWKObservations},
     liveness::{
         cached_proposer_election::CachedProposerElection,
         leader_reputation::{
@@ -26,14 +28,19 @@ use crate::{
     logging::{LogEvent, LogSchema},
     metrics_safety_rules::MetricsSafetyRules,
     monitor,
-    network::{IncomingBlockRetrievalRequest, NetworkReceivers, NetworkSender},
+    network::{
+        IncomingBlockRetrievalRequest, IncomingJWKObservationRequest, IncomingSyncPointRequest,
+        NetworkReceivers, NetworkSender,
+    },
     network_interface::{ConsensusMsg, ConsensusNetworkSender},
     payload_manager::QuorumStoreClient,
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
     quorum_store::direct_mempool_quorum_store::DirectMempoolQuorumStore,
     round_manager::{RoundManager, UnverifiedEvent, VerifiedEvent},
     state_replication::StateComputer,
+    transaction_shuffler::create_transaction_shuffler,
     util::time_service::TimeService,
+    validator_txn_pool::ValidatorTxnPool,
 };
 use anyhow::{bail, ensure, Context};
 use aptos_config::config::{ConsensusConfig, NodeConfig};
@@ -45,8 +52,8 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     on_chain_config::{
-        LeaderReputationType, OnChainConfigPayload, OnChainConsensusConfig, ProposerElectionType,
-        ValidatorSet,
+        LeaderReputationType, OnChainConfigPayload, OnChainConsensusConfig, OnChainExecutionConfig,
+        ProposerElectionType, ValidatorSet,
     },
     validator_verifier::ValidatorVerifier,
 };
@@ -67,7 +74,7 @@ use futures::{
 };
 use itertools::Itertools;
 use network::protocols::network::{ApplicationNetworkSender, Event};
-use safety_rules::SafetyRulesManager;
+use safety_rules::{consensus_signer, SafetyRulesManager};
 use std::{
     cmp::Ordering,
     collections::HashMap,
@@ -113,6 +120,7 @@ pub struct EpochManager {
     safety_rules_manager: SafetyRulesManager,
     reconfig_events: ReconfigNotificationListener,
     commit_notifier: Arc<dyn CommitNotifier>,
+    validator_txn_pool: ValidatorTxnPool,
     // channels to buffer manager
     buffer_manager_msg_tx: Option<aptos_channel::Sender<AccountAddress, VerifiedEvent>>,
     buffer_manager_reset_tx: Option<UnboundedSender<ResetRequest>>,
@@ -120,6 +128,9 @@ pub struct EpochManager {
     round_manager_tx: Option<
         aptos_channel::Sender<(Author, Discriminant<VerifiedEvent>), (Author, VerifiedEvent)>,
     >,
+    // the JWK manager of the epoch, if validator transactions are enabled
+    jwk_observations: Option<JWKObservations>,
+    jwk_manager_close_tx: Option<oneshot::Sender<()>>,
     epoch_state: Option<EpochState>,
     block_store: Option<Arc<BlockStore>>,
 }
@@ -136,6 +147,7 @@ impl EpochManager {
         storage: Arc<dyn PersistentLivenessStorage>,
         reconfig_events: ReconfigNotificationListener,
         commit_notifier: Arc<dyn CommitNotifier>,
+        validator_txn_pool: ValidatorTxnPool,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -154,9 +166,12 @@ impl EpochManager {
             safety_rules_manager,
             reconfig_events,
             commit_notifier,
+            validator_txn_pool,
             buffer_manager_msg_tx: None,
             buffer_manager_reset_tx: None,
             round_manager_tx: None,
+            jwk_observations: None,
+            jwk_manager_close_tx: None,
             epoch_state: None,
             block_store: None,
         }
@@ -491,6 +506,12 @@ impl EpochManager {
                 .await
                 .expect("[EpochManager] Fail to drop buffer manager");
         }
+
+        // Stop the JWK manager, which removes its certified updates from the pool
+        self.jwk_observations = None;
+        if let Some(close_tx) = self.jwk_manager_close_tx.take() {
+            let _ = close_tx.send(());
+        }
     }
 
     async fn start_round_manager(
@@ -498,6 +519,7 @@ impl EpochManager {
         recovery_data: RecoveryData,
         epoch_state: EpochState,
         onchain_config: OnChainConsensusConfig,
+        execution_config: OnChainExecutionConfig,
     ) {
         let epoch = epoch_state.epoch;
         counters::EPOCH.set(epoch_state.epoch as i64);
@@ -548,7 +570,10 @@ impl EpochManager {
         self.commit_notifier
             .new_epoch(consensus_to_quorum_store_sender);
 
-        self.commit_state_computer.new_epoch(&epoch_state);
+        self.commit_state_computer.new_epoch(
+            &epoch_state,
+            create_transaction_shuffler(execution_config.transaction_shuffler_type()),
+        );
         let state_computer = if onchain_config.decoupled_execution() {
             Arc::new(self.spawn_decoupled_execution(
                 safety_rules_container.clone(),
@@ -578,6 +603,8 @@ impl EpochManager {
             self.time_service.clone(),
             self.config.max_block_size,
             onchain_config.max_failed_authors_to_store(),
+            self.validator_txn_pool.clone(),
+            onchain_config.validator_txn_config(),
         );
 
         let mut round_manager = RoundManager::new(
@@ -604,6 +631,54 @@ impl EpochManager {
         tokio::spawn(round_manager.start(round_manager_rx));
     }
 
+    fn start_jwk_manager(
+        &mut self,
+        epoch_state: &EpochState,
+        onchain_config: &OnChainConsensusConfig,
+    ) {
+        if !self.config.jwk_consensus.enabled || !onchain_config.validator_txn_config().enabled() {
+            return;
+        }
+        let epoch = epoch_state.epoch;
+        let signer = match consensus_signer(&self.config.safety_rules, epoch_state) {
+            Ok(signer) => signer,
+            Err(error) => {
+                warn!(
+                    epoch = epoch,
+                    error = ?error,
+                    "JWK consensus disabled, the consensus key is not available"
+                );
+                return;
+            }
+        };
+        let network_sender = NetworkSender::new(
+            self.author,
+            self.network_sender.clone(),
+            self.self_sender.clone(),
+            epoch_state.verifier.clone(),
+        );
+        let observations = JWKObservations::default();
+        let jwk_manager = match JWKManager::new(
+            epoch_state.clone(),
+            signer,
+            network_sender,
+            self.validator_txn_pool.clone(),
+            self.storage.aptos_db(),
+            self.config.jwk_consensus.clone(),
+            observations.clone(),
+        ) {
+            Ok(jwk_manager) => jwk_manager,
+            Err(error) => {
+                error!(epoch = epoch, error = ?error, "Unable to create the JWK manager");
+                return;
+            }
+        };
+        let (close_tx, close_rx) = oneshot::channel();
+        self.jwk_observations = Some(observations);
+        self.jwk_manager_close_tx = Some(close_tx);
+        tokio::spawn(jwk_manager.start(close_rx));
+    }
+
     async fn start_new_epoch(&mut self, payload: OnChainConfigPayload) {
         let validator_set: ValidatorSet = payload
             .get()
@@ -617,9 +692,16 @@ impl EpochManager {
         if let Err(error) = &onchain_config {
             error!("Failed to read on-chain consensus config {}", error);
         }
+        let execution_config: anyhow::Result<OnChainExecutionConfig> = payload.get();
+        if let Err(error) = &execution_config {
+            warn!("Failed to read on-chain execution config {}", error);
+        }
 
         self.epoch_state = Some(epoch_state.clone());
 
+        let onchain_config = onchain_config.unwrap_or_default();
+        self.start_jwk_manager(&epoch_state, &onchain_config);
+
         let initial_data = self
             .storage
             .start()
@@ -627,7 +709,8 @@ impl EpochManager {
         self.start_round_manager(
             initial_data,
             epoch_state,
-            onchain_config.unwrap_or_default(),
+            onchain_config,
+            execution_config.unwrap_or_default(),
         )
         .await;
     }
@@ -760,10 +843,70 @@ impl EpochManager {
         }
     }
 
+    async fn process_sync_point_request(
+        &self,
+        request: IncomingSyncPointRequest,
+    ) -> anyhow::Result<()> {
+        if let Some(block_store) = &self.block_store {
+            block_store.process_sync_point_request(request).await
+        } else {
+            Err(anyhow::anyhow!("Round manager not started"))
+        }
+    }
+
+    fn process_jwk_observation_request(
+        &self,
+        request: IncomingJWKObservationRequest,
+    ) -> anyhow::Result<()> {
+        let observations = self
+            .jwk_observations
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("JWK manager not started"))?;
+        ensure!(
+            request.req.epoch() == self.epoch(),
+            "[EpochManager] JWK observation requested for epoch {}, local epoch {}",
+            request.req.epoch(),
+            self.epoch()
+        );
+        let observed = observations
+            .lock()
+            .get(request.req.issuer())
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!("No JWK update observed for {}", request.req.issuer())
+            })?;
+        let response_bytes = request
+            .protocol
+            .to_bytes(&ConsensusMsg::JWKObservationResponse(Box::new(observed)))?;
+        request
+            .response_sender
+            .send(Ok(response_bytes.into()))
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
+
     fn process_local_timeout(&mut self, round: u64) {
         self.forward_to_round_manager(self.author, VerifiedEvent::LocalTimeout(round));
     }
 
+    fn dump_state(&self) -> Option<ConsensusStateDump> {
+        let epoch_state = self.epoch_state.as_ref()?;
+        let block_store = self.block_store.as_ref()?;
+        let ordered_root = block_store.ordered_root();
+        Some(ConsensusStateDump {
+            epoch: epoch_state.epoch,
+            num_validators: epoch_state.verifier.len(),
+            ordered_root_id: ordered_root.id(),
+            ordered_root_round: ordered_root.round(),
+            commit_root_round: block_store.commit_root().round(),
+            highest_quorum_cert_round: block_store.highest_quorum_cert().certified_block().round(),
+            highest_ordered_cert_round: block_store
+                .highest_ordered_cert()
+                .certified_block()
+                .round(),
+            highest_commit_cert_round: block_store.highest_commit_cert().certified_block().round(),
+        })
+    }
+
     async fn await_reconfig_notification(&mut self) {
         let reconfig_notification = self
             .reconfig_events
@@ -778,6 +921,7 @@ impl EpochManager {
         mut self,
         mut round_timeout_sender_rx: channel::Receiver<Round>,
         mut network_receivers: NetworkReceivers,
+        mut state_dump_requests: Receiver<StateDumpRequest>,
     ) {
         // initial start of the processor
         self.await_reconfig_notification().await;
@@ -793,9 +937,23 @@ impl EpochManager {
                         error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
                     }
                 }
+                Some(request) = network_receivers.sync_point.next() => {
+                    if let Err(e) = self.process_sync_point_request(request).await {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                }
+                Some(request) = network_receivers.jwk_observation.next() => {
+                    if let Err(e) = self.process_jwk_observation_request(request) {
+                        debug!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                }
                 Some(round) = round_timeout_sender_rx.next() => {
                     self.process_local_timeout(round);
                 }
+                Some(response_sender) = state_dump_requests.next() => {
+                    // The requester may have given up waiting
+                    let _ = response_sender.send(self.dump_state());
+                }
             }
             // Continually capture the time of consensus process to ensure that clock skew between
             // validators is reasonable and to find any unusual (possibly byzantine) clock behavior.
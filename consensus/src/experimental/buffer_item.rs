@@ -4,7 +4,7 @@
 use anyhow::anyhow;
 use itertools::zip_eq;
 
-use aptos_crypto::bls12381;
+use aptos_crypto::{batch_verifier::BatchVerifier, bls12381};
 use aptos_logger::prelude::*;
 use aptos_types::{
     block_info::BlockInfo,
@@ -36,18 +36,20 @@ fn verify_signatures(
     validator: &ValidatorVerifier,
     commit_ledger_info: &LedgerInfo,
 ) -> PartialSignatures {
-    // Returns a valid partial signature from a set of unverified signatures.
-    // TODO: Validating individual signatures in expensive. Replace this with optimistic signature
-    // verification for BLS. Here, we can implement a tree-based batch verification technique that
-    // filters out invalid signature shares much faster when there are only a few of them
-    // (e.g., [LM07]: Finding Invalid Signatures in Pairing-Based Batches,
-    // by Law, Laurie and Matt, Brian J., in Cryptography and Coding, 2007).
+    // Returns a valid partial signature from a set of unverified signatures. Signatures are
+    // verified in batches, and only the signatures of a batch which fails are verified one by one.
+    let mut authors = vec![];
+    let mut verifier = BatchVerifier::<bls12381::Signature>::new();
+    for (author, sig) in unverified_signatures.signatures() {
+        // Signatures from authors outside the validator set are dropped
+        if let Some(public_key) = validator.get_public_key(author) {
+            verifier.add(commit_ledger_info, public_key, sig.clone());
+            authors.push((*author, sig.clone()));
+        }
+    }
     PartialSignatures::new(
-        unverified_signatures
-            .signatures()
-            .iter()
-            .filter(|(author, sig)| validator.verify(**author, commit_ledger_info, sig).is_ok())
-            .map(|(author, sig)| (*author, sig.clone()))
+        zip_eq(authors, verifier.verify())
+            .filter_map(|(author_and_sig, result)| result.ok().map(|_| author_and_sig))
             .collect(),
     )
 }
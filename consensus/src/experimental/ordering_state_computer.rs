@@ -8,6 +8,7 @@ use crate::{
         errors::Error,
     },
     state_replication::{StateComputer, StateComputerCommitCallBackType},
+    transaction_shuffler::TransactionShuffler,
 };
 use anyhow::Result;
 use aptos_crypto::HashValue;
@@ -117,5 +118,5 @@ impl StateComputer for OrderingStateComputer {
         Ok(())
     }
 
-    fn new_epoch(&self, _: &EpochState) {}
+    fn new_epoch(&self, _: &EpochState, _: Arc<dyn TransactionShuffler>) {}
 }
@@ -0,0 +1,221 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    jwk_consensus::jwk_observer::fetch_jwks,
+    logging::{LogEvent, LogSchema},
+    network::NetworkSender,
+    validator_txn_pool::{TxnGuard, ValidatorTxnPool},
+};
+use anyhow::Result;
+use aptos_config::config::JWKConsensusConfig;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_state_view::account_with_state_view::AsAccountWithStateView;
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
+    account_view::AccountView,
+    aggregate_signature::PartialSignatures,
+    epoch_state::EpochState,
+    keyless::{Configuration, ObservedJWKVersions, ProviderJWKs, QuorumCertifiedUpdate},
+};
+use consensus_types::jwk_observation::{JWKObservationRequest, ObservedUpdate};
+use futures::{channel::oneshot, future::join_all};
+use safety_rules::ConsensusSigner;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use storage_interface::{state_view::LatestDbStateCheckpointView, DbReader};
+
+/// The latest update this validator observed for each issuer, which peers request
+pub type JWKObservations = Arc<Mutex<HashMap<String, ObservedUpdate>>>;
+
+/// Observes the JWKs of the issuers and certifies the updates during an epoch.
+pub struct JWKManager {
+    epoch_state: EpochState,
+    signer: ConsensusSigner,
+    network: NetworkSender,
+    validator_txn_pool: ValidatorTxnPool,
+    aptos_db: Arc<dyn DbReader>,
+    config: JWKConsensusConfig,
+    client: reqwest::Client,
+    observations: JWKObservations,
+    // The certified update of each issuer, which stays in the pool until the guard is dropped
+    certified: HashMap<String, (ProviderJWKs, TxnGuard)>,
+}
+
+impl JWKManager {
+    pub fn new(
+        epoch_state: EpochState,
+        signer: ConsensusSigner,
+        network: NetworkSender,
+        validator_txn_pool: ValidatorTxnPool,
+        aptos_db: Arc<dyn DbReader>,
+        config: JWKConsensusConfig,
+        observations: JWKObservations,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.fetch_timeout_ms))
+            .build()?;
+        Ok(Self {
+            epoch_state,
+            signer,
+            network,
+            validator_txn_pool,
+            aptos_db,
+            config,
+            client,
+            observations,
+            certified: HashMap::new(),
+        })
+    }
+
+    /// The on-chain issuers and the versions of their JWKs, None if keyless accounts are not
+    /// configured
+    fn onchain_state(&self) -> Result<Option<(Configuration, ObservedJWKVersions)>> {
+        let state_view = self.aptos_db.latest_state_checkpoint_view()?;
+        let account = state_view.as_account_with_state_view(&CORE_CODE_ADDRESS);
+        let config = match account.get_move_resource::<Configuration>()? {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        let versions = account
+            .get_move_resource::<ObservedJWKVersions>()?
+            .unwrap_or_default();
+        Ok(Some((config, versions)))
+    }
+
+    /// Fetches the JWKs of each issuer, and signs the updates of the ones differing from the
+    /// on-chain JWKs
+    async fn observe(&mut self) -> HashMap<String, ObservedUpdate> {
+        let (config, versions) = match self.onchain_state() {
+            Ok(Some(state)) => state,
+            Ok(None) => return HashMap::new(),
+            Err(e) => {
+                warn!(error = ?e, "[JWKManager] Failed to read the on-chain JWKs");
+                return HashMap::new();
+            }
+        };
+        // An update is done once committed, or if its issuer is no longer configured.
+        self.certified.retain(|issuer, (update, _)| {
+            update.version > versions.version(issuer)
+                && config.issuers.iter().any(|i| &i.iss == issuer)
+        });
+
+        let mut observations = HashMap::new();
+        for issuer in config.issuers {
+            let jwks = match fetch_jwks(&self.client, &issuer.iss).await {
+                Ok(jwks) => {
+                    counters::JWK_FETCH_COUNT
+                        .with_label_values(&[&issuer.iss, "success"])
+                        .inc();
+                    jwks
+                }
+                Err(e) => {
+                    counters::JWK_FETCH_COUNT
+                        .with_label_values(&[&issuer.iss, "error"])
+                        .inc();
+                    warn!(error = ?e, "[JWKManager] Failed to fetch the JWKs of {}", issuer.iss);
+                    continue;
+                }
+            };
+            let version = versions.version(&issuer.iss) + 1;
+            let onchain = ProviderJWKs::new(issuer.iss.clone(), version, issuer.jwks);
+            let update = ProviderJWKs::new(issuer.iss.clone(), version, jwks);
+            if update == onchain {
+                continue;
+            }
+            match self.signer.sign(&update) {
+                Ok(signature) => {
+                    let observed = ObservedUpdate::new(self.signer.author(), update, signature);
+                    observations.insert(issuer.iss, observed);
+                }
+                Err(e) => warn!(error = ?e, "[JWKManager] Failed to sign {}", update),
+            }
+        }
+        observations
+    }
+
+    /// Requests the updates the peers observed for the issuer, and aggregates the signatures of
+    /// the ones matching `observed`. Returns None if they are not signed by a quorum.
+    async fn certify(&self, observed: &ObservedUpdate) -> Option<QuorumCertifiedUpdate> {
+        let request =
+            JWKObservationRequest::new(self.epoch_state.epoch, observed.update().issuer.clone());
+        let timeout = Duration::from_millis(self.config.observation_request_timeout_ms);
+        let responses = join_all(
+            self.epoch_state
+                .verifier
+                .get_ordered_account_addresses_iter()
+                .filter(|peer| *peer != self.signer.author())
+                .map(|peer| {
+                    self.network
+                        .request_jwk_observation(request.clone(), peer, timeout)
+                }),
+        )
+        .await;
+
+        let mut signatures = PartialSignatures::empty();
+        signatures.add_signature(observed.author(), observed.signature().clone());
+        for response in responses {
+            match response {
+                Ok(peer_observed) if peer_observed.update() == observed.update() => {
+                    signatures
+                        .add_signature(peer_observed.author(), peer_observed.signature().clone());
+                }
+                Ok(peer_observed) => debug!(
+                    "[JWKManager] Peer observed {}, rather than {}",
+                    peer_observed,
+                    observed.update()
+                ),
+                Err(e) => debug!(error = ?e, "[JWKManager] Failed to request JWK observation"),
+            }
+        }
+        let verifier = &self.epoch_state.verifier;
+        verifier
+            .check_voting_power(signatures.signatures().keys())
+            .ok()?;
+        let multi_sig = verifier.aggregate_signatures(&signatures).ok()?;
+        Some(QuorumCertifiedUpdate {
+            update: observed.update().clone(),
+            multi_sig,
+        })
+    }
+
+    async fn observe_and_certify(&mut self) {
+        let observations = self.observe().await;
+        *self.observations.lock() = observations.clone();
+        for (issuer, observed) in observations {
+            if let Some((update, _)) = self.certified.get(&issuer) {
+                if update == observed.update() {
+                    continue;
+                }
+            }
+            if let Some(certified) = self.certify(&observed).await {
+                info!(
+                    LogSchema::new(LogEvent::JWKUpdateCertified).epoch(self.epoch_state.epoch),
+                    "{}", certified.update
+                );
+                counters::JWK_UPDATES_CERTIFIED
+                    .with_label_values(&[&issuer])
+                    .inc();
+                // Replaces the guard of a previous update of the issuer, if any.
+                let guard = self.validator_txn_pool.put(certified.to_validator_txn());
+                self.certified.insert(issuer, (certified.update, guard));
+            }
+        }
+    }
+
+    /// Observes the JWKs periodically until `close_rx` is signaled or dropped, at the end of the
+    /// epoch. The certified updates are then removed from the pool.
+    pub async fn start(mut self, mut close_rx: oneshot::Receiver<()>) {
+        info!(epoch = self.epoch_state.epoch, "JWKManager starts.");
+        let mut interval =
+            tokio::time::interval(Duration::from_millis(self.config.observation_interval_ms));
+        loop {
+            tokio::select! {
+                _ = &mut close_rx => break,
+                _ = interval.tick() => self.observe_and_certify().await,
+            }
+        }
+        info!(epoch = self.epoch_state.epoch, "JWKManager stops.");
+    }
+}
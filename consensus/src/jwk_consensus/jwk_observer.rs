@@ -0,0 +1,108 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use aptos_types::keyless::{RsaJwk, JWT_ALGORITHM_RS256};
+use serde::Deserialize;
+
+/// The fields of the OpenID configuration of a provider we need
+#[derive(Deserialize)]
+struct OpenIdConfiguration {
+    jwks_uri: String,
+}
+
+/// A JSON Web Key, with the fields of an RSA key
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    alg: Option<String>,
+    e: Option<String>,
+    n: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+/// Fetches the JWKs the issuer currently signs JWTs with, from the `jwks_uri` of its OpenID
+/// configuration.
+pub async fn fetch_jwks(client: &reqwest::Client, issuer: &str) -> Result<Vec<RsaJwk>> {
+    let config_url = format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    );
+    let config: OpenIdConfiguration = client
+        .get(&config_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .with_context(|| format!("Malformed OpenID configuration at {}", config_url))?;
+    let jwk_set = client
+        .get(&config.jwks_uri)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    parse_jwks(&jwk_set).with_context(|| format!("Malformed JWK set at {}", config.jwks_uri))
+}
+
+/// Parses a JWK set, keeping the RSA keys which can sign JWTs with RS256, the only algorithm
+/// supported by keyless accounts. A key without `alg` can be used with any algorithm.
+pub fn parse_jwks(jwk_set: &str) -> Result<Vec<RsaJwk>> {
+    let jwk_set: JwkSet = serde_json::from_str(jwk_set)?;
+    Ok(jwk_set
+        .keys
+        .into_iter()
+        .filter_map(|jwk| match jwk {
+            Jwk {
+                kty,
+                kid: Some(kid),
+                alg,
+                e: Some(e),
+                n: Some(n),
+            } if kty == "RSA" && alg.map_or(true, |alg| alg == JWT_ALGORITHM_RS256) => {
+                Some(RsaJwk {
+                    kid,
+                    alg: JWT_ALGORITHM_RS256.to_owned(),
+                    e,
+                    n,
+                })
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jwks() {
+        let jwk_set = r#"{
+            "keys": [
+                {"kty": "RSA", "kid": "1", "alg": "RS256", "use": "sig", "e": "AQAB", "n": "n1"},
+                {"kty": "RSA", "kid": "2", "e": "AQAB", "n": "n2"},
+                {"kty": "RSA", "kid": "3", "alg": "RS512", "e": "AQAB", "n": "n3"},
+                {"kty": "EC", "kid": "4", "alg": "ES256", "crv": "P-256", "x": "x", "y": "y"},
+                {"kty": "RSA", "alg": "RS256", "e": "AQAB", "n": "n5"}
+            ]
+        }"#;
+        let kids: Vec<_> = parse_jwks(jwk_set)
+            .unwrap()
+            .into_iter()
+            .map(|jwk| {
+                assert_eq!(jwk.alg, JWT_ALGORITHM_RS256);
+                jwk.kid
+            })
+            .collect();
+        assert_eq!(kids, vec!["1", "2"]);
+
+        assert!(parse_jwks(r#"{"jwks": []}"#).is_err());
+    }
+}
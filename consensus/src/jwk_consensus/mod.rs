@@ -0,0 +1,13 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! JWK consensus keeps the JWKs of the OIDC providers used by keyless accounts up to date.
+//!
+//! Each validator periodically fetches the JWKs of the issuers configured on chain. When they
+//! differ from the on-chain ones, it signs the update it observed and requests the updates its
+//! peers observed for the same issuer. Once a quorum signed the same update, the quorum certified
+//! update is put in the validator transaction pool, from which it is proposed, until it is
+//! committed.
+
+pub(crate) mod jwk_manager;
+pub(crate) mod jwk_observer;
@@ -18,6 +18,7 @@ mod consensusdb;
 mod epoch_manager;
 mod error;
 mod experimental;
+mod jwk_consensus;
 mod liveness;
 mod logging;
 mod metrics_safety_rules;
@@ -33,6 +34,7 @@ mod state_computer;
 mod state_replication;
 #[cfg(any(test, feature = "fuzzing"))]
 mod test_utils;
+mod transaction_shuffler;
 #[cfg(test)]
 mod twins;
 mod txn_notifier;
@@ -44,6 +46,8 @@ pub mod consensus_provider;
 pub mod counters;
 /// AptosNet interface.
 pub mod network_interface;
+/// Validator transactions waiting to be proposed
+pub mod validator_txn_pool;
 
 /// Required by the smoke tests
 pub use consensusdb::CONSENSUS_DB_NAME;
@@ -3,8 +3,11 @@
 
 use crate::{
     block_storage::BlockReader, state_replication::PayloadManager, util::time_service::TimeService,
+    validator_txn_pool::ValidatorTxnPool,
 };
 use anyhow::{bail, ensure, format_err, Context};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::on_chain_config::ValidatorTxnConfig;
 use consensus_types::{
     block::Block,
     block_data::BlockData,
@@ -15,7 +18,7 @@ use consensus_types::{
 use aptos_infallible::Mutex;
 use consensus_types::common::{Payload, PayloadFilter};
 use futures::future::BoxFuture;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use super::{
     proposer_election::ProposerElection, unequivocal_proposer_election::UnequivocalProposerElection,
@@ -49,6 +52,9 @@ pub struct ProposalGenerator {
     max_block_size: u64,
     // Max number of failed authors to be added to a proposed block.
     max_failed_authors_to_store: usize,
+    // Validator transactions to propose, if enabled by the on-chain consensus config.
+    validator_txn_pool: ValidatorTxnPool,
+    validator_txn_config: ValidatorTxnConfig,
     // Last round that a proposal was generated
     last_round_generated: Mutex<Round>,
 }
@@ -61,6 +67,8 @@ impl ProposalGenerator {
         time_service: Arc<dyn TimeService>,
         max_block_size: u64,
         max_failed_authors_to_store: usize,
+        validator_txn_pool: ValidatorTxnPool,
+        validator_txn_config: ValidatorTxnConfig,
     ) -> Self {
         Self {
             author,
@@ -69,6 +77,8 @@ impl ProposalGenerator {
             time_service,
             max_block_size,
             max_failed_authors_to_store,
+            validator_txn_pool,
+            validator_txn_config,
             last_round_generated: Mutex::new(0),
         }
     }
@@ -121,10 +131,14 @@ impl ProposalGenerator {
 
         let hqc = self.ensure_highest_quorum_cert(round)?;
 
-        let (payload, timestamp) = if hqc.certified_block().has_reconfiguration() {
+        let (validator_txns, payload, timestamp) = if hqc.certified_block().has_reconfiguration() {
             // Reconfiguration rule - we propose empty blocks with parents' timestamp
             // after reconfiguration until it's committed
-            (Payload::empty(), hqc.certified_block().timestamp_usecs())
+            (
+                vec![],
+                Payload::empty(),
+                hqc.certified_block().timestamp_usecs(),
+            )
         } else {
             // One needs to hold the blocks with the references to the payloads while get_block is
             // being executed: pending blocks vector keeps all the pending ancestors of the extended branch.
@@ -144,6 +158,22 @@ impl ProposalGenerator {
                 .collect();
             let payload_filter = PayloadFilter::from(&exclude_payload);
 
+            let validator_txns = if self.validator_txn_config.enabled() {
+                let exclude_validator_txns: HashSet<_> = pending_blocks
+                    .iter()
+                    .flat_map(|block| block.block().validator_txns())
+                    .flatten()
+                    .map(CryptoHash::hash)
+                    .collect();
+                self.validator_txn_pool.pull(
+                    self.validator_txn_config.per_block_limit_txn_count(),
+                    self.validator_txn_config.per_block_limit_total_bytes(),
+                    &exclude_validator_txns,
+                )
+            } else {
+                vec![]
+            };
+
             let pending_ordering = self
                 .block_store
                 .path_from_ordered_root(hqc.certified_block().id())
@@ -167,7 +197,7 @@ impl ProposalGenerator {
                 .await
                 .context("Fail to retrieve payload")?;
 
-            (payload, timestamp.as_micros() as u64)
+            (validator_txns, payload, timestamp.as_micros() as u64)
         };
 
         let quorum_cert = hqc.as_ref().clone();
@@ -177,15 +207,27 @@ impl ProposalGenerator {
             false,
             proposer_election,
         );
-        // create block proposal
-        Ok(BlockData::new_proposal(
-            payload,
-            self.author,
-            failed_authors,
-            round,
-            timestamp,
-            quorum_cert,
-        ))
+        // create block proposal, only blocks carrying validator txns need the extended type
+        Ok(if validator_txns.is_empty() {
+            BlockData::new_proposal(
+                payload,
+                self.author,
+                failed_authors,
+                round,
+                timestamp,
+                quorum_cert,
+            )
+        } else {
+            BlockData::new_proposal_ext(
+                validator_txns,
+                payload,
+                self.author,
+                failed_authors,
+                round,
+                timestamp,
+                quorum_cert,
+            )
+        })
     }
 
     fn ensure_highest_quorum_cert(&self, round: Round) -> anyhow::Result<Arc<QuorumCert>> {
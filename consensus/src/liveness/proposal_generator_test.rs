@@ -9,8 +9,12 @@ use crate::{
     },
     test_utils::{build_empty_tree, MockPayloadManager, TreeInserter},
     util::mock_time_service::SimulatedTimeService,
+    validator_txn_pool::ValidatorTxnPool,
+};
+use aptos_types::{
+    on_chain_config::ValidatorTxnConfig, validator_signer::ValidatorSigner,
+    validator_txn::ValidatorTransaction,
 };
-use aptos_types::validator_signer::ValidatorSigner;
 use consensus_types::{
     block::{block_test_utils::certificate_for_genesis, Block},
     common::Author,
@@ -33,6 +37,8 @@ async fn test_proposal_generation_empty_tree() {
         Arc::new(SimulatedTimeService::new()),
         1,
         10,
+        ValidatorTxnPool::new(),
+        ValidatorTxnConfig::Disabled,
     );
     let mut proposer_election =
         UnequivocalProposerElection::new(Box::new(RotatingProposer::new(vec![signer.author()], 1)));
@@ -68,6 +74,8 @@ async fn test_proposal_generation_parent() {
         Arc::new(SimulatedTimeService::new()),
         1,
         10,
+        ValidatorTxnPool::new(),
+        ValidatorTxnConfig::Disabled,
     );
     let mut proposer_election = UnequivocalProposerElection::new(Box::new(RotatingProposer::new(
         vec![inserter.signer().author()],
@@ -135,6 +143,8 @@ async fn test_old_proposal_generation() {
         Arc::new(SimulatedTimeService::new()),
         1,
         10,
+        ValidatorTxnPool::new(),
+        ValidatorTxnConfig::Disabled,
     );
     let mut proposer_election = UnequivocalProposerElection::new(Box::new(RotatingProposer::new(
         vec![inserter.signer().author()],
@@ -167,6 +177,8 @@ async fn test_correct_failed_authors() {
         Arc::new(SimulatedTimeService::new()),
         1,
         10,
+        ValidatorTxnPool::new(),
+        ValidatorTxnConfig::Disabled,
     );
     let mut proposer_election = UnequivocalProposerElection::new(Box::new(RotatingProposer::new(
         vec![author, peer1, peer2],
@@ -189,3 +201,39 @@ async fn test_correct_failed_authors() {
     assert_eq!(result.failed_authors().unwrap()[3], (4, peer1));
     assert_eq!(result.failed_authors().unwrap()[4], (5, peer2));
 }
+
+#[tokio::test]
+async fn test_proposal_with_validator_txns() {
+    let signer = ValidatorSigner::random(None);
+    let block_store = build_empty_tree();
+    let validator_txn_pool = ValidatorTxnPool::new();
+    let _dkg_guard = validator_txn_pool.put(ValidatorTransaction::DKGTranscript(vec![0; 10]));
+    let _jwk_guard = validator_txn_pool.put(ValidatorTransaction::ObservedJWKUpdate(vec![1; 10]));
+    let mut proposal_generator = ProposalGenerator::new(
+        signer.author(),
+        block_store.clone(),
+        Arc::new(MockPayloadManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        1,
+        10,
+        validator_txn_pool.clone(),
+        ValidatorTxnConfig::Enabled {
+            per_block_limit_txn_count: 1,
+            per_block_limit_total_bytes: 1024,
+        },
+    );
+    let mut proposer_election =
+        UnequivocalProposerElection::new(Box::new(RotatingProposer::new(vec![signer.author()], 1)));
+
+    let proposal_data = proposal_generator
+        .generate_proposal(1, &mut proposer_election, empty_callback())
+        .await
+        .unwrap();
+    // Only the oldest validator txn fits in the per block limit.
+    assert_eq!(
+        proposal_data.validator_txns(),
+        Some(&vec![ValidatorTransaction::DKGTranscript(vec![0; 10])])
+    );
+    let proposal = Block::new_proposal_from_block_data(proposal_data, &signer);
+    assert!(proposal.verify_well_formed().is_ok());
+}
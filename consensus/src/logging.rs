@@ -18,19 +18,23 @@ pub struct LogSchema {
 pub enum LogEvent {
     CommitViaBlock,
     CommitViaSync,
+    JWKUpdateCertified,
     NewEpoch,
     NewRound,
     Propose,
     ReceiveBlockRetrieval,
     ReceiveEpochChangeProof,
     ReceiveEpochRetrieval,
+    ReceiveJWKObservation,
     ReceiveMessageFromDifferentEpoch,
     ReceiveNewCertificate,
     ReceiveProposal,
     ReceiveSyncInfo,
+    ReceiveSyncPoint,
     ReceiveVote,
     RetrieveBlock,
     StateSync,
+    SyncPoint,
     Timeout,
     Vote,
     VoteNIL,
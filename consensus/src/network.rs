@@ -19,8 +19,10 @@ use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse, MAX_BLOCKS_PER_REQUEST},
     common::Author,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    jwk_observation::{JWKObservationRequest, ObservedUpdate},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
+    sync_point::{SyncPointRequest, SyncPointResponse, MAX_BLOCKS_PER_SYNC_POINT},
     vote_msg::VoteMsg,
 };
 use fail::fail_point;
@@ -46,6 +48,24 @@ pub struct IncomingBlockRetrievalRequest {
     pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
+/// The sync point request is used internally for implementing RPC: the callback is executed
+/// for carrying the response
+#[derive(Debug)]
+pub struct IncomingSyncPointRequest {
+    pub req: SyncPointRequest,
+    pub protocol: ProtocolId,
+    pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
+}
+
+/// The JWK observation request is used internally for implementing RPC: the callback is executed
+/// for carrying the response
+#[derive(Debug)]
+pub struct IncomingJWKObservationRequest {
+    pub req: JWKObservationRequest,
+    pub protocol: ProtocolId,
+    pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
+}
+
 /// Just a convenience struct to keep all the network proxy receiving queues in one place.
 /// Will be returned by the NetworkTask upon startup.
 pub struct NetworkReceivers {
@@ -55,6 +75,8 @@ pub struct NetworkReceivers {
         (AccountAddress, ConsensusMsg),
     >,
     pub block_retrieval: aptos_channel::Receiver<AccountAddress, IncomingBlockRetrievalRequest>,
+    pub sync_point: aptos_channel::Receiver<AccountAddress, IncomingSyncPointRequest>,
+    pub jwk_observation: aptos_channel::Receiver<AccountAddress, IncomingJWKObservationRequest>,
 }
 
 /// Implements the actual networking support for all consensus messaging.
@@ -120,6 +142,71 @@ impl NetworkSender {
         Ok(response)
     }
 
+    /// Tries to retrieve the latest certified sync point from the given peer together with the
+    /// blocks connecting it to the known block: the function returns a future that is fulfilled
+    /// with SyncPointResponse.
+    pub async fn request_sync_point(
+        &mut self,
+        sync_point_request: SyncPointRequest,
+        from: Author,
+        timeout: Duration,
+    ) -> anyhow::Result<SyncPointResponse> {
+        fail_point!("consensus::send::sync_point", |_| {
+            Err(anyhow::anyhow!("Injected error in request_sync_point"))
+        });
+
+        ensure!(from != self.author, "Retrieve sync point from self");
+        let msg = ConsensusMsg::SyncPointRequest(Box::new(sync_point_request.clone()));
+        let response_msg = monitor!(
+            "sync_point",
+            self.network_sender.send_rpc(from, msg, timeout).await
+        )?;
+        let response = match response_msg {
+            ConsensusMsg::SyncPointResponse(resp) => *resp,
+            _ => return Err(anyhow!("Invalid response to request")),
+        };
+        response
+            .verify(sync_point_request, &self.validators)
+            .map_err(|e| {
+                error!(
+                    SecurityEvent::InvalidRetrievedBlock,
+                    sync_point_response = response,
+                    error = ?e,
+                );
+                e
+            })?;
+
+        Ok(response)
+    }
+
+    /// Tries to retrieve the JWK update the given peer observed for an issuer: the function
+    /// returns a future that is fulfilled with the peer's signed ObservedUpdate.
+    pub async fn request_jwk_observation(
+        &self,
+        request: JWKObservationRequest,
+        from: Author,
+        timeout: Duration,
+    ) -> anyhow::Result<ObservedUpdate> {
+        ensure!(from != self.author, "Retrieve JWK observation from self");
+        let msg = ConsensusMsg::JWKObservationRequest(Box::new(request.clone()));
+        let response_msg = monitor!(
+            "jwk_observation",
+            self.network_sender.send_rpc(from, msg, timeout).await
+        )?;
+        let response = match response_msg {
+            ConsensusMsg::JWKObservationResponse(resp) => *resp,
+            _ => return Err(anyhow!("Invalid response to request")),
+        };
+        ensure!(
+            response.author() == from,
+            "JWK observation of {} returned by {}",
+            response.author(),
+            from
+        );
+        response.verify(&request, &self.validators)?;
+        Ok(response)
+    }
+
     /// Tries to send the given msg to all the participants.
     ///
     /// The future is fulfilled as soon as the message put into the mpsc channel to network
@@ -241,6 +328,8 @@ pub struct NetworkTask {
         (AccountAddress, ConsensusMsg),
     >,
     block_retrieval_tx: aptos_channel::Sender<AccountAddress, IncomingBlockRetrievalRequest>,
+    sync_point_tx: aptos_channel::Sender<AccountAddress, IncomingSyncPointRequest>,
+    jwk_observation_tx: aptos_channel::Sender<AccountAddress, IncomingJWKObservationRequest>,
     all_events: Box<dyn Stream<Item = Event<ConsensusMsg>> + Send + Unpin>,
 }
 
@@ -257,16 +346,30 @@ impl NetworkTask {
             1,
             Some(&counters::BLOCK_RETRIEVAL_CHANNEL_MSGS),
         );
+        let (sync_point_tx, sync_point) = aptos_channel::new(
+            QueueStyle::LIFO,
+            1,
+            Some(&counters::SYNC_POINT_CHANNEL_MSGS),
+        );
+        let (jwk_observation_tx, jwk_observation) = aptos_channel::new(
+            QueueStyle::LIFO,
+            1,
+            Some(&counters::JWK_OBSERVATION_CHANNEL_MSGS),
+        );
         let all_events = Box::new(select(network_events, self_receiver));
         (
             NetworkTask {
                 consensus_messages_tx,
                 block_retrieval_tx,
+                sync_point_tx,
+                jwk_observation_tx,
                 all_events,
             },
             NetworkReceivers {
                 consensus_messages,
                 block_retrieval,
+                sync_point,
+                jwk_observation,
             },
         )
     }
@@ -310,6 +413,46 @@ impl NetworkTask {
                             warn!(error = ?e, "aptos channel closed");
                         }
                     }
+                    ConsensusMsg::SyncPointRequest(request) => {
+                        debug!(
+                            remote_peer = peer_id,
+                            event = LogEvent::ReceiveSyncPoint,
+                            "{}",
+                            request
+                        );
+                        if request.max_blocks() > MAX_BLOCKS_PER_SYNC_POINT {
+                            warn!(
+                                remote_peer = peer_id,
+                                "Ignore sync point request with too many blocks: {}",
+                                request.max_blocks()
+                            );
+                            continue;
+                        }
+                        let req_with_callback = IncomingSyncPointRequest {
+                            req: *request,
+                            protocol,
+                            response_sender: callback,
+                        };
+                        if let Err(e) = self.sync_point_tx.push(peer_id, req_with_callback) {
+                            warn!(error = ?e, "aptos channel closed");
+                        }
+                    }
+                    ConsensusMsg::JWKObservationRequest(request) => {
+                        debug!(
+                            remote_peer = peer_id,
+                            event = LogEvent::ReceiveJWKObservation,
+                            "{}",
+                            request
+                        );
+                        let req_with_callback = IncomingJWKObservationRequest {
+                            req: *request,
+                            protocol,
+                            response_sender: callback,
+                        };
+                        if let Err(e) = self.jwk_observation_tx.push(peer_id, req_with_callback) {
+                            warn!(error = ?e, "aptos channel closed");
+                        }
+                    }
                     _ => {
                         warn!(remote_peer = peer_id, "Unexpected msg: {:?}", msg);
                         continue;
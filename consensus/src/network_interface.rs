@@ -14,8 +14,10 @@ use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse},
     epoch_retrieval::EpochRetrievalRequest,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    jwk_observation::{JWKObservationRequest, ObservedUpdate},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
+    sync_point::{SyncPointRequest, SyncPointResponse},
     vote_msg::VoteMsg,
 };
 use network::{
@@ -62,6 +64,15 @@ pub enum ConsensusMsg {
     /// than 2f + 1 signatures on the commit proposal. This part is not on the critical path, but
     /// it can save slow machines to quickly confirm the execution result.
     CommitDecisionMsg(Box<CommitDecision>),
+    /// RPC to get the latest certified sync point of a peer together with the blocks connecting
+    /// it to a block the requester already has.
+    SyncPointRequest(Box<SyncPointRequest>),
+    /// Carries the peer's SyncInfo and the blocks leading to its highest quorum cert.
+    SyncPointResponse(Box<SyncPointResponse>),
+    /// RPC to get the JWKs of an issuer observed by a peer.
+    JWKObservationRequest(Box<JWKObservationRequest>),
+    /// Carries the JWK update observed by the peer, signed by it.
+    JWKObservationResponse(Box<ObservedUpdate>),
 }
 
 /// The interface from Network to Consensus layer.
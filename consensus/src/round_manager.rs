@@ -522,6 +522,41 @@ impl RoundManager {
             expected_failed_authors,
         );
 
+        if let Some(validator_txns) = proposal.validator_txns() {
+            let validator_txn_config = self.onchain_config.validator_txn_config();
+            ensure!(
+                validator_txn_config.enabled(),
+                "[RoundManager] Proposal for block {} carries validator txns, which are disabled",
+                proposal.round(),
+            );
+            let total_bytes: u64 = validator_txns
+                .iter()
+                .map(|txn| txn.size_in_bytes() as u64)
+                .sum();
+            ensure!(
+                validator_txns.len() as u64 <= validator_txn_config.per_block_limit_txn_count()
+                    && total_bytes <= validator_txn_config.per_block_limit_total_bytes(),
+                "[RoundManager] Proposal for block {} has {} validator txns of {} bytes, exceeding the limits {} / {}",
+                proposal.round(),
+                validator_txns.len(),
+                total_bytes,
+                validator_txn_config.per_block_limit_txn_count(),
+                validator_txn_config.per_block_limit_total_bytes(),
+            );
+            // Voting certifies the block, not the data its proposer put in it, so every payload
+            // must be certified by a quorum of this epoch's validators on its own
+            for txn in validator_txns {
+                txn.verify(&self.epoch_state.verifier, self.epoch_state.epoch)
+                    .with_context(|| {
+                        format!(
+                            "[RoundManager] Proposal for block {} has an uncertified {}",
+                            proposal.round(),
+                            txn
+                        )
+                    })?;
+            }
+        }
+
         let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
 
         ensure!(
@@ -15,6 +15,7 @@ use crate::{
     round_manager::RoundManager,
     test_utils::{EmptyStateComputer, MockPayloadManager, MockStorage},
     util::{mock_time_service::SimulatedTimeService, time_service::TimeService},
+    validator_txn_pool::ValidatorTxnPool,
 };
 use aptos_infallible::Mutex;
 use aptos_types::aggregate_signature::AggregateSignature;
@@ -22,7 +23,7 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    on_chain_config::{OnChainConsensusConfig, ValidatorSet},
+    on_chain_config::{OnChainConsensusConfig, ValidatorSet, ValidatorTxnConfig},
     validator_info::ValidatorInfo,
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
@@ -147,6 +148,8 @@ fn create_node_for_fuzzing() -> RoundManager {
         time_service,
         1,
         10,
+        ValidatorTxnPool::new(),
+        ValidatorTxnConfig::Disabled,
     );
 
     //
@@ -10,7 +10,7 @@ use crate::{
         round_state::{ExponentialTimeInterval, RoundState},
     },
     metrics_safety_rules::MetricsSafetyRules,
-    network::{IncomingBlockRetrievalRequest, NetworkSender},
+    network::{IncomingBlockRetrievalRequest, IncomingSyncPointRequest, NetworkSender},
     network_interface::{ConsensusMsg, ConsensusNetworkEvents, ConsensusNetworkSender},
     network_tests::{NetworkPlayground, TwinId},
     persistent_liveness_storage::RecoveryData,
@@ -20,6 +20,7 @@ use crate::{
         TreeInserter,
     },
     util::time_service::{ClockTimeService, TimeService},
+    validator_txn_pool::ValidatorTxnPool,
 };
 use aptos_config::network_id::NetworkId;
 use aptos_crypto::HashValue;
@@ -29,7 +30,7 @@ use aptos_types::validator_verifier::generate_validator_verifier;
 use aptos_types::{
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    on_chain_config::OnChainConsensusConfig,
+    on_chain_config::{OnChainConsensusConfig, ValidatorTxnConfig},
     transaction::SignedTransaction,
     validator_signer::ValidatorSigner,
     validator_verifier::random_validator_verifier,
@@ -46,6 +47,7 @@ use consensus_types::{
     common::{Author, Payload, Round},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
+    sync_point::SyncPointRequest,
     timeout_2chain::TwoChainTimeout,
     vote_msg::VoteMsg,
 };
@@ -206,6 +208,8 @@ impl NodeSetup {
             time_service.clone(),
             1,
             10,
+            ValidatorTxnPool::new(),
+            ValidatorTxnConfig::Disabled,
         );
 
         let round_state = Self::create_round_state(time_service);
@@ -790,6 +794,88 @@ fn response_on_block_retrieval() {
     });
 }
 
+#[test]
+fn response_on_sync_point_request() {
+    let mut runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut node = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1)
+        .pop()
+        .unwrap();
+
+    timed_block_on(&mut runtime, async {
+        let proposal_msg = node.next_proposal().await;
+        let block_id = proposal_msg.proposal().id();
+        node.round_manager
+            .process_proposal_msg(proposal_msg)
+            .await
+            .unwrap();
+        let vote_msg = node.next_vote().await;
+        let vote_data = vote_msg.vote().vote_data();
+        let block_qc = gen_test_certificate(
+            &[node.signer.clone()],
+            vote_data.proposed().clone(),
+            vote_data.parent().clone(),
+            None,
+        );
+        node.block_store
+            .insert_single_quorum_cert(block_qc.clone())
+            .unwrap();
+        let root_id = node.block_store.ordered_root().id();
+
+        // the blocks connect the highest quorum cert to the known block
+        let (tx1, rx1) = oneshot::channel();
+        let known_root_request = IncomingSyncPointRequest {
+            req: SyncPointRequest::new(root_id, 10),
+            protocol: ProtocolId::ConsensusRpcBcs,
+            response_sender: tx1,
+        };
+        node.block_store
+            .process_sync_point_request(known_root_request)
+            .await
+            .unwrap();
+        match rx1.await {
+            Ok(Ok(bytes)) => {
+                let response = match bcs::from_bytes(&bytes) {
+                    Ok(ConsensusMsg::SyncPointResponse(resp)) => *resp,
+                    _ => panic!("sync point failure"),
+                };
+                assert_eq!(response.sync_info().highest_quorum_cert(), &block_qc);
+                assert_eq!(response.blocks().len(), 1);
+                assert_eq!(response.blocks()[0].id(), block_id);
+                assert_eq!(response.blocks()[0].parent_id(), root_id);
+            }
+            _ => panic!("sync point failure"),
+        }
+
+        // unknown blocks and too small limits return no blocks
+        for req in vec![
+            SyncPointRequest::new(HashValue::random(), 10),
+            SyncPointRequest::new(root_id, 0),
+        ] {
+            let (tx, rx) = oneshot::channel();
+            let request = IncomingSyncPointRequest {
+                req,
+                protocol: ProtocolId::ConsensusRpcBcs,
+                response_sender: tx,
+            };
+            node.block_store
+                .process_sync_point_request(request)
+                .await
+                .unwrap();
+            match rx.await {
+                Ok(Ok(bytes)) => {
+                    let response = match bcs::from_bytes(&bytes) {
+                        Ok(ConsensusMsg::SyncPointResponse(resp)) => *resp,
+                        _ => panic!("sync point failure"),
+                    };
+                    assert!(response.blocks().is_empty());
+                }
+                _ => panic!("sync point failure"),
+            }
+        }
+    });
+}
+
 #[test]
 /// rebuild a node from previous storage without violating safety guarantees.
 fn recover_on_restart() {
@@ -8,22 +8,31 @@ use crate::{
     counters,
     error::StateSyncError,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
+    transaction_shuffler::{NoOpShuffler, TransactionShuffler},
     txn_notifier::TxnNotifier,
 };
 use anyhow::Result;
-use aptos_crypto::HashValue;
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
+use aptos_transaction_tracing::TxnStage;
 use aptos_types::{
-    account_address::AccountAddress, contract_event::ContractEvent, epoch_state::EpochState,
-    ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    epoch_state::EpochState,
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{SignedTransaction, Transaction},
 };
 use consensus_notifications::ConsensusNotificationSender;
 use consensus_types::{block::Block, common::Round, executed_block::ExecutedBlock};
 use executor_types::{BlockExecutorTrait, Error as ExecutionError, StateComputeResult};
 use fail::fail_point;
 use futures::{SinkExt, StreamExt};
-use std::{boxed::Box, sync::Arc};
+use std::{
+    boxed::Box,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::Mutex as AsyncMutex;
 
 type NotificationType = (
@@ -43,6 +52,7 @@ pub struct ExecutionProxy {
     async_state_sync_notifier: channel::Sender<NotificationType>,
     async_commit_notifier: channel::Sender<CommitType>,
     validators: Mutex<Vec<AccountAddress>>,
+    transaction_shuffler: Mutex<Arc<dyn TransactionShuffler>>,
     write_mutex: AsyncMutex<()>,
 }
 
@@ -88,9 +98,27 @@ impl ExecutionProxy {
             async_state_sync_notifier: tx,
             async_commit_notifier: commit_tx,
             validators: Mutex::new(vec![]),
+            transaction_shuffler: Mutex::new(Arc::new(NoOpShuffler)),
             write_mutex: AsyncMutex::new(()),
         }
     }
+
+    /// The user transactions of `block`, in the order they get executed.
+    fn user_transactions_to_execute(&self, block: &Block) -> Vec<SignedTransaction> {
+        let transaction_shuffler = self.transaction_shuffler.lock().clone();
+        transaction_shuffler.shuffle(block.payload_transactions(), block.id())
+    }
+}
+
+/// The hashes of the user transactions in `txns`, if transaction tracing is enabled
+fn traced_txn_hashes(txns: &[Transaction]) -> Vec<HashValue> {
+    if !aptos_transaction_tracing::is_enabled() {
+        return vec![];
+    }
+    txns.iter()
+        .filter(|txn| matches!(txn, Transaction::UserTransaction(_)))
+        .map(CryptoHash::hash)
+        .collect()
 }
 
 #[async_trait::async_trait]
@@ -116,7 +144,11 @@ impl StateComputer for ExecutionProxy {
 
         // TODO: figure out error handling for the prologue txn
         let executor = self.executor.clone();
-        let transactions_to_execute = block.transactions_to_execute(&self.validators.lock());
+        let user_txns = self.user_transactions_to_execute(block);
+        let transactions_to_execute =
+            block.transactions_to_execute(&self.validators.lock(), user_txns.clone());
+        let traced_txns = traced_txn_hashes(&transactions_to_execute);
+        let execution_start = SystemTime::now();
         let compute_result = monitor!(
             "execute_block",
             tokio::task::spawn_blocking(move || {
@@ -126,11 +158,21 @@ impl StateComputer for ExecutionProxy {
         )
         .expect("spawn_blocking failed")?;
         observe_block(block.timestamp_usecs(), BlockStage::EXECUTED);
+        let proposal_time = UNIX_EPOCH + Duration::from_micros(block.timestamp_usecs());
+        for txn_hash in traced_txns {
+            aptos_transaction_tracing::record(txn_hash, TxnStage::CONSENSUS, proposal_time);
+            aptos_transaction_tracing::record_with_attributes(
+                txn_hash,
+                TxnStage::EXECUTION,
+                execution_start,
+                vec![("block_id", block_id.to_hex())],
+            );
+        }
 
         // notify mempool about failed transaction
         if let Err(e) = self
             .txn_notifier
-            .notify_failed_txn(block, &compute_result)
+            .notify_failed_txn(&user_txns, &compute_result)
             .await
         {
             error!(
@@ -158,7 +200,8 @@ impl StateComputer for ExecutionProxy {
 
         for block in blocks {
             block_ids.push(block.id());
-            txns.extend(block.transactions_to_commit(&self.validators.lock()));
+            let user_txns = self.user_transactions_to_execute(block.block());
+            txns.extend(block.transactions_to_commit(&self.validators.lock(), user_txns));
             reconfig_events.extend(block.reconfig_event());
 
             if block.epoch() > latest_epoch {
@@ -169,6 +212,8 @@ impl StateComputer for ExecutionProxy {
             }
         }
 
+        let traced_txns = traced_txn_hashes(&txns);
+        let commit_start = SystemTime::now();
         let executor = self.executor.clone();
         let proof = finality_proof.clone();
         monitor!(
@@ -181,6 +226,9 @@ impl StateComputer for ExecutionProxy {
             .await
         )
         .expect("spawn_blocking failed");
+        for txn_hash in traced_txns {
+            aptos_transaction_tracing::record(txn_hash, TxnStage::COMMIT, commit_start);
+        }
 
         let blocks = blocks.to_vec();
         let wrapped_callback = move || {
@@ -236,10 +284,15 @@ impl StateComputer for ExecutionProxy {
         })
     }
 
-    fn new_epoch(&self, epoch_state: &EpochState) {
+    fn new_epoch(
+        &self,
+        epoch_state: &EpochState,
+        transaction_shuffler: Arc<dyn TransactionShuffler>,
+    ) {
         *self.validators.lock() = epoch_state
             .verifier
             .get_ordered_account_addresses_iter()
             .collect();
+        *self.transaction_shuffler.lock() = transaction_shuffler;
     }
 }
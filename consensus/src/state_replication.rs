@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::error::{QuorumStoreError, StateSyncError};
+use crate::{
+    error::{QuorumStoreError, StateSyncError},
+    transaction_shuffler::TransactionShuffler,
+};
 use anyhow::Result;
 use aptos_crypto::HashValue;
 use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures};
@@ -61,5 +64,9 @@ pub trait StateComputer: Send + Sync {
     async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError>;
 
     // Reconfigure to execute transactions for a new epoch.
-    fn new_epoch(&self, epoch_state: &EpochState);
+    fn new_epoch(
+        &self,
+        epoch_state: &EpochState,
+        transaction_shuffler: Arc<dyn TransactionShuffler>,
+    );
 }
@@ -5,6 +5,7 @@ use crate::{
     error::StateSyncError,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
     test_utils::mock_storage::MockStorage,
+    transaction_shuffler::TransactionShuffler,
 };
 use anyhow::{format_err, Result};
 use aptos_crypto::HashValue;
@@ -102,7 +103,7 @@ impl StateComputer for MockStateComputer {
         Ok(())
     }
 
-    fn new_epoch(&self, _: &EpochState) {}
+    fn new_epoch(&self, _: &EpochState, _: Arc<dyn TransactionShuffler>) {}
 }
 
 pub struct EmptyStateComputer;
@@ -130,7 +131,7 @@ impl StateComputer for EmptyStateComputer {
         Ok(())
     }
 
-    fn new_epoch(&self, _: &EpochState) {}
+    fn new_epoch(&self, _: &EpochState, _: Arc<dyn TransactionShuffler>) {}
 }
 
 /// Random Compute Result State Computer
@@ -181,5 +182,5 @@ impl StateComputer for RandomComputeResultStateComputer {
         Ok(())
     }
 
-    fn new_epoch(&self, _: &EpochState) {}
+    fn new_epoch(&self, _: &EpochState, _: Arc<dyn TransactionShuffler>) {}
 }
@@ -0,0 +1,146 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_types::{
+    account_address::AccountAddress, on_chain_config::TransactionShufflerType,
+    transaction::SignedTransaction,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+/// Reorders the transactions of a block payload before execution. The order must only depend on
+/// the transactions and the seed, as every validator has to execute the same order.
+pub trait TransactionShuffler: Send + Sync {
+    fn shuffle(&self, txns: Vec<SignedTransaction>, seed: HashValue) -> Vec<SignedTransaction>;
+}
+
+pub fn create_transaction_shuffler(
+    shuffler_type: TransactionShufflerType,
+) -> Arc<dyn TransactionShuffler> {
+    match shuffler_type {
+        TransactionShufflerType::NoShuffling => Arc::new(NoOpShuffler),
+        TransactionShufflerType::SenderAwareV1 => Arc::new(SenderAwareShuffler),
+    }
+}
+
+/// Keeps the order of the payload.
+pub struct NoOpShuffler;
+
+impl TransactionShuffler for NoOpShuffler {
+    fn shuffle(&self, txns: Vec<SignedTransaction>, _seed: HashValue) -> Vec<SignedTransaction> {
+        txns
+    }
+}
+
+/// Takes one transaction of each sender in turn, so that a burst of transactions from one sender
+/// is spread over the block instead of serializing parallel execution. The transactions of a
+/// sender keep their relative order, the order of the senders is derived from the seed.
+pub struct SenderAwareShuffler;
+
+impl TransactionShuffler for SenderAwareShuffler {
+    fn shuffle(&self, txns: Vec<SignedTransaction>, seed: HashValue) -> Vec<SignedTransaction> {
+        let num_txns = txns.len();
+        let mut txns_by_sender: HashMap<AccountAddress, VecDeque<SignedTransaction>> =
+            HashMap::new();
+        for txn in txns {
+            txns_by_sender
+                .entry(txn.sender())
+                .or_insert_with(VecDeque::new)
+                .push_back(txn);
+        }
+
+        let mut senders: Vec<_> = txns_by_sender
+            .keys()
+            .map(|sender| (sender_order_key(seed, sender), *sender))
+            .collect();
+        senders.sort();
+
+        let mut shuffled = Vec::with_capacity(num_txns);
+        while !senders.is_empty() {
+            senders.retain(|(_, sender)| {
+                let sender_txns = txns_by_sender
+                    .get_mut(sender)
+                    .expect("Every sender has transactions.");
+                if let Some(txn) = sender_txns.pop_front() {
+                    shuffled.push(txn);
+                }
+                !sender_txns.is_empty()
+            });
+        }
+        shuffled
+    }
+}
+
+fn sender_order_key(seed: HashValue, sender: &AccountAddress) -> HashValue {
+    HashValue::sha3_256_of(&[seed.as_ref(), sender.as_ref()].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::test_helpers::transaction_test_helpers::get_test_signed_txn;
+
+    fn txns_of(sender: AccountAddress, count: u64) -> Vec<SignedTransaction> {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        (0..count)
+            .map(|i| get_test_signed_txn(sender, i, &private_key, public_key.clone(), None))
+            .collect()
+    }
+
+    fn senders(txns: &[SignedTransaction]) -> Vec<AccountAddress> {
+        txns.iter().map(SignedTransaction::sender).collect()
+    }
+
+    #[test]
+    fn test_sender_aware_shuffler() {
+        let (a, b, c) = (
+            AccountAddress::random(),
+            AccountAddress::random(),
+            AccountAddress::random(),
+        );
+        let txns: Vec<_> = txns_of(a, 4)
+            .into_iter()
+            .chain(txns_of(b, 2))
+            .chain(txns_of(c, 1))
+            .collect();
+        let seed = HashValue::random();
+
+        let shuffler = create_transaction_shuffler(TransactionShufflerType::SenderAwareV1);
+        let shuffled = shuffler.shuffle(txns.clone(), seed);
+        assert_eq!(shuffled, shuffler.shuffle(txns.clone(), seed));
+        assert_eq!(shuffled.len(), txns.len());
+
+        // One transaction of each sender in turn, until only the transactions of a remain.
+        let shuffled_senders = senders(&shuffled);
+        let mut first_round = shuffled_senders[..3].to_vec();
+        first_round.sort();
+        let mut all_senders = vec![a, b, c];
+        all_senders.sort();
+        assert_eq!(first_round, all_senders);
+        assert_eq!(shuffled_senders[5..], [a, a]);
+
+        // The transactions of each sender keep their order.
+        for sender in [a, b, c] {
+            let sequence_numbers: Vec<_> = shuffled
+                .iter()
+                .filter(|txn| txn.sender() == sender)
+                .map(SignedTransaction::sequence_number)
+                .collect();
+            let mut sorted = sequence_numbers.clone();
+            sorted.sort_unstable();
+            assert_eq!(sequence_numbers, sorted);
+        }
+    }
+
+    #[test]
+    fn test_no_op_shuffler() {
+        let txns = txns_of(AccountAddress::random(), 3);
+        let shuffler = create_transaction_shuffler(TransactionShufflerType::NoShuffling);
+        assert_eq!(shuffler.shuffle(txns.clone(), HashValue::random()), txns);
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
     network_tests::{NetworkPlayground, TwinId},
     test_utils::{MockStateComputer, MockStorage},
     util::time_service::ClockTimeService,
+    validator_txn_pool::ValidatorTxnPool,
 };
 use aptos_config::{
     config::{NodeConfig, WaypointConfig},
@@ -143,11 +144,13 @@ impl SMRNode {
             storage.clone(),
             reconfig_listener,
             commit_notifier,
+            ValidatorTxnPool::new(),
         );
         let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
+        let (_, state_dump_receiver) = mpsc::channel(1);
 
         runtime.spawn(network_task.start());
-        runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
+        runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, state_dump_receiver));
         Self {
             id: twin_id,
             _runtime: runtime,
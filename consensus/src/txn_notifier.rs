@@ -5,8 +5,8 @@ use crate::error::MempoolError;
 use crate::monitor;
 use anyhow::{format_err, Result};
 use aptos_mempool::QuorumStoreRequest;
-use aptos_types::transaction::TransactionStatus;
-use consensus_types::{block::Block, common::TransactionSummary};
+use aptos_types::transaction::{SignedTransaction, TransactionStatus};
+use consensus_types::common::TransactionSummary;
 use executor_types::StateComputeResult;
 use futures::channel::{mpsc, oneshot};
 use itertools::Itertools;
@@ -17,10 +17,10 @@ use tokio::time::timeout;
 #[async_trait::async_trait]
 pub trait TxnNotifier: Send + Sync {
     /// Notification of txns which failed execution. (Committed txns is notified by
-    /// state sync.)
+    /// state sync.) `txns` are the user txns of the block, in the order they were executed.
     async fn notify_failed_txn(
         &self,
-        block: &Block,
+        txns: &[SignedTransaction],
         compute_results: &StateComputeResult,
     ) -> Result<(), MempoolError>;
 }
@@ -49,35 +49,30 @@ impl MempoolNotifier {
 impl TxnNotifier for MempoolNotifier {
     async fn notify_failed_txn(
         &self,
-        block: &Block,
+        txns: &[SignedTransaction],
         compute_results: &StateComputeResult,
     ) -> Result<(), MempoolError> {
         let mut rejected_txns = vec![];
-        let txns: Vec<_> = match block.payload() {
-            Some(payload) => payload,
-            None => return Ok(()),
-        }
-        .clone()
-        .into_iter()
-        .collect();
-
         if txns.is_empty() {
             return Ok(());
         }
         let compute_status = compute_results.compute_status();
-        if txns.len() + 2 != compute_status.len() {
+        if txns.len() + 2 > compute_status.len() {
             return Err(format_err!(
                 "Block meta and state checkpoint txns are expected. txns len: {}, compute status len: {}",
                 txns.len(),
                 compute_status.len(),
             ).into());
         }
-        let user_txn_status = &compute_status[1..txns.len() + 1];
+        // The user txns come last, right before the state checkpoint, after the block meta and
+        // the validator txns.
+        let user_txn_status =
+            &compute_status[compute_status.len() - txns.len() - 1..compute_status.len() - 1];
         for (txn, status) in txns.iter().zip_eq(user_txn_status) {
             if let TransactionStatus::Discard(_) = status {
                 rejected_txns.push(TransactionSummary {
                     sender: txn.sender(),
-                    sequence_number: txn.sequence_number(),
+                    replay_protector: txn.replay_protector(),
                 });
             }
         }
@@ -0,0 +1,136 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::Mutex;
+use aptos_types::validator_txn::ValidatorTransaction;
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
+
+/// The validator transactions waiting to be proposed, in the order they were put.
+///
+/// A producer (e.g. DKG) puts a transaction and holds on to the returned `TxnGuard` for as long
+/// as the transaction should be proposed; dropping the guard removes the transaction. Proposers
+/// of different rounds may pull the same transaction until its producer sees it committed, so
+/// producers have to make sure that processing a transaction twice is harmless. Producers put
+/// payloads certified by a quorum of the epoch's validators (see `ValidatorTransaction::verify`),
+/// as validators don't vote for proposals carrying anything else.
+#[derive(Clone, Default)]
+pub struct ValidatorTxnPool {
+    inner: Arc<Mutex<PoolState>>,
+}
+
+#[derive(Default)]
+struct PoolState {
+    next_seq_num: u64,
+    txns: BTreeMap<u64, ValidatorTransaction>,
+}
+
+impl ValidatorTxnPool {
+    /// Creates an empty pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a transaction to propose, which stays in the pool until the returned guard is dropped
+    pub fn put(&self, txn: ValidatorTransaction) -> TxnGuard {
+        let mut state = self.inner.lock();
+        let seq_num = state.next_seq_num;
+        state.next_seq_num += 1;
+        state.txns.insert(seq_num, txn);
+        TxnGuard {
+            pool: self.inner.clone(),
+            seq_num,
+        }
+    }
+
+    /// Returns the oldest transactions not in `exclude` (by hash), at most `max_count` of them and
+    /// at most `max_bytes` of payload in total. A transaction that doesn't fit in the remaining
+    /// bytes is skipped, so that a big one doesn't hold back the smaller ones behind it.
+    pub(crate) fn pull(
+        &self,
+        max_count: u64,
+        max_bytes: u64,
+        exclude: &HashSet<HashValue>,
+    ) -> Vec<ValidatorTransaction> {
+        let state = self.inner.lock();
+        let mut remaining_bytes = max_bytes;
+        let mut txns = vec![];
+        for txn in state.txns.values() {
+            if txns.len() as u64 >= max_count {
+                break;
+            }
+            let size = txn.size_in_bytes() as u64;
+            if size > remaining_bytes || exclude.contains(&txn.hash()) {
+                continue;
+            }
+            remaining_bytes -= size;
+            txns.push(txn.clone());
+        }
+        txns
+    }
+
+    /// The number of transactions in the pool
+    pub fn len(&self) -> usize {
+        self.inner.lock().txns.len()
+    }
+
+    /// Whether the pool has no transaction
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Removes its transaction from the `ValidatorTxnPool` when dropped
+pub struct TxnGuard {
+    pool: Arc<Mutex<PoolState>>,
+    seq_num: u64,
+}
+
+impl Drop for TxnGuard {
+    fn drop(&mut self) {
+        self.pool.lock().txns.remove(&self.seq_num);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_respects_limits_and_exclusion() {
+        let pool = ValidatorTxnPool::new();
+        let _guard_0 = pool.put(ValidatorTransaction::DKGTranscript(vec![0; 100]));
+        let _guard_1 = pool.put(ValidatorTransaction::ObservedJWKUpdate(vec![1; 10]));
+        let _guard_2 = pool.put(ValidatorTransaction::ObservedPrices(vec![2; 10]));
+
+        assert_eq!(pool.pull(2, 1000, &HashSet::new()).len(), 2);
+        // The transcript doesn't fit, the smaller ones behind it are still pulled.
+        let txns = pool.pull(10, 50, &HashSet::new());
+        assert_eq!(txns.len(), 2);
+        assert!(txns.iter().all(|txn| txn.size_in_bytes() == 10));
+
+        let exclude: HashSet<_> = txns.iter().map(CryptoHash::hash).collect();
+        let txns = pool.pull(10, 1000, &exclude);
+        assert_eq!(
+            txns,
+            vec![ValidatorTransaction::DKGTranscript(vec![0; 100])]
+        );
+    }
+
+    #[test]
+    fn test_dropping_guard_removes_txn() {
+        let pool = ValidatorTxnPool::new();
+        let guard = pool.put(ValidatorTransaction::ObservedPrices(vec![1, 2, 3]));
+        let _other_guard = pool.put(ValidatorTransaction::ObservedPrices(vec![4, 5, 6]));
+        assert_eq!(pool.len(), 2);
+
+        drop(guard);
+        assert_eq!(
+            pool.pull(10, 1000, &HashSet::new()),
+            vec![ValidatorTransaction::ObservedPrices(vec![4, 5, 6])]
+        );
+    }
+}
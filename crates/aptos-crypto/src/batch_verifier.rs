@@ -0,0 +1,155 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Batch verification of signatures which were produced independently, i.e., possibly on
+//! different messages and under different keys.
+//!
+//! Verifying a batch of signatures at once costs much less than verifying each of them on its own:
+//! about half for Ed25519, and for BLS, one pairing per signature rather than two. A batch only
+//! tells whether all of its signatures are valid though, so when one fails, its signatures are
+//! verified one by one to tell the invalid ones apart. Batches are verified in parallel, on
+//! rayon's global thread pool.
+//!
+//! Each signature gets the same verdict as from `Signature::verify_arbitrary_msg`: Ed25519
+//! signatures whose `R` component or public key has a small-order component, on which the batch
+//! equation could disagree with the strict one, fail the batch and thus get verified one by one,
+//! and BLS signatures are subgroup-checked.
+//!
+//! # Example
+//!
+//! ```
+//! use aptos_crypto::{
+//!     batch_verifier::BatchVerifier,
+//!     ed25519::{Ed25519PrivateKey, Ed25519Signature},
+//!     test_utils::TestAptosCrypto,
+//!     PrivateKey, SigningKey, Uniform,
+//! };
+//!
+//! let mut verifier = BatchVerifier::<Ed25519Signature>::new();
+//! for i in 0..10 {
+//!     let private_key = Ed25519PrivateKey::generate_for_testing();
+//!     let message = TestAptosCrypto(format!("message {}", i));
+//!     let signature = private_key.sign(&message);
+//!     verifier.add(&message, private_key.public_key(), signature);
+//! }
+//! assert!(verifier.verify().iter().all(|result| result.is_ok()));
+//! ```
+
+use crate::{hash::CryptoHash, signing_message, traits::Signature};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// The default number of signatures per batch. Larger batches amortize more of the cost, but
+/// cost more to sort out when they contain an invalid signature, and parallelize less.
+pub const DEFAULT_BATCH_SIZE: usize = 64;
+
+/// A signature scheme whose signatures can be verified as a batch.
+pub trait BatchVerifiable: Signature + Send + Sync
+where
+    Self::VerifyingKeyMaterial: Send + Sync,
+{
+    /// Verifies that each signature is valid for its message under its public key. Succeeds only
+    /// if all of them are valid, without telling which are invalid otherwise.
+    fn batch_verify_arbitrary_msgs(
+        items: &[(&[u8], &Self::VerifyingKeyMaterial, &Self)],
+    ) -> Result<()>;
+}
+
+/// Queues signatures, to verify them as batches.
+pub struct BatchVerifier<S: BatchVerifiable>
+where
+    S::VerifyingKeyMaterial: Send + Sync,
+{
+    batch_size: usize,
+    items: Vec<(Vec<u8>, S::VerifyingKeyMaterial, S)>,
+}
+
+impl<S: BatchVerifiable> BatchVerifier<S>
+where
+    S::VerifyingKeyMaterial: Send + Sync,
+{
+    /// A verifier with batches of [`DEFAULT_BATCH_SIZE`] signatures.
+    pub fn new() -> Self {
+        Self::with_batch_size(DEFAULT_BATCH_SIZE)
+    }
+
+    /// A verifier with batches of up to `batch_size` signatures.
+    pub fn with_batch_size(batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batches must hold at least one signature");
+        Self {
+            batch_size,
+            items: vec![],
+        }
+    }
+
+    /// Queues the `signature` of `message` under `public_key`, and returns its index in the
+    /// results of [`BatchVerifier::verify`].
+    pub fn add<T: CryptoHash + Serialize>(
+        &mut self,
+        message: &T,
+        public_key: S::VerifyingKeyMaterial,
+        signature: S,
+    ) -> usize {
+        self.add_arbitrary_msg(signing_message(message), public_key, signature)
+    }
+
+    /// Like [`BatchVerifier::add`], for a `message` which is already serialized.
+    pub fn add_arbitrary_msg(
+        &mut self,
+        message: Vec<u8>,
+        public_key: S::VerifyingKeyMaterial,
+        signature: S,
+    ) -> usize {
+        self.items.push((message, public_key, signature));
+        self.items.len() - 1
+    }
+
+    /// The number of queued signatures.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no signature is queued.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Verifies the queued signatures, returning the result of each, in the order they were added.
+    pub fn verify(self) -> Vec<Result<()>> {
+        self.items
+            .par_chunks(self.batch_size)
+            .flat_map_iter(verify_batch::<S>)
+            .collect()
+    }
+}
+
+impl<S: BatchVerifiable> Default for BatchVerifier<S>
+where
+    S::VerifyingKeyMaterial: Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn verify_batch<S: BatchVerifiable>(
+    batch: &[(Vec<u8>, S::VerifyingKeyMaterial, S)],
+) -> Vec<Result<()>>
+where
+    S::VerifyingKeyMaterial: Send + Sync,
+{
+    let items: Vec<_> = batch
+        .iter()
+        .map(|(message, public_key, signature)| (message.as_slice(), public_key, signature))
+        .collect();
+    if batch.len() > 1 && S::batch_verify_arbitrary_msgs(&items).is_ok() {
+        return batch.iter().map(|_| Ok(())).collect();
+    }
+
+    // Some signature is invalid, find out which
+    items
+        .into_iter()
+        .map(|(message, public_key, signature)| signature.verify_arbitrary_msg(message, public_key))
+        .collect()
+}
@@ -17,6 +17,7 @@
 //! makes the caller's job easier and, more importantly, makes the library safer to use.
 
 use crate::{
+    batch_verifier::BatchVerifiable,
     bls12381::{
         bls12381_keys::{PrivateKey, PublicKey},
         DST_BLS_SIG_IN_G2_WITH_POP,
@@ -27,7 +28,8 @@ use crate::{
 };
 use anyhow::{anyhow, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
-use blst::BLST_ERROR;
+use blst::{blst_scalar, BLST_ERROR};
+use rand::Rng;
 use serde::Serialize;
 use std::convert::TryFrom;
 use std::fmt;
@@ -167,6 +169,50 @@ impl traits::Signature for Signature {
     }
 }
 
+impl BatchVerifiable for Signature {
+    /// Checks all signatures at once, with random 64-bit coefficients, so that invalid signatures
+    /// can't cancel each other out. Like `verify_arbitrary_msg`, signatures are subgroup-checked,
+    /// and public keys are assumed to be.
+    fn batch_verify_arbitrary_msgs(items: &[(&[u8], &PublicKey, &Self)]) -> Result<()> {
+        let messages: Vec<_> = items.iter().map(|(message, _, _)| *message).collect();
+        let public_keys: Vec<_> = items
+            .iter()
+            .map(|(_, public_key, _)| &public_key.pubkey)
+            .collect();
+        let signatures: Vec<_> = items
+            .iter()
+            .map(|(_, _, signature)| &signature.sig)
+            .collect();
+        let mut rng = rand::thread_rng();
+        let coefficients: Vec<_> = items
+            .iter()
+            .map(|_| {
+                // A zero coefficient would leave its signature unchecked
+                let coefficient = rng.gen::<u64>() | 1;
+                let mut scalar = blst_scalar::default();
+                scalar.b[..8].copy_from_slice(&coefficient.to_le_bytes());
+                scalar
+            })
+            .collect();
+
+        let result = blst::min_pk::Signature::verify_multiple_aggregate_signatures(
+            &messages,
+            DST_BLS_SIG_IN_G2_WITH_POP,
+            &public_keys,
+            false,
+            &signatures,
+            true,
+            &coefficients,
+            64,
+        );
+        if result == BLST_ERROR::BLST_SUCCESS {
+            Ok(())
+        } else {
+            Err(anyhow!("{:?}", result))
+        }
+    }
+}
+
 impl ValidCryptoMaterial for Signature {
     fn to_bytes(&self) -> Vec<u8> {
         self.to_bytes().to_vec()
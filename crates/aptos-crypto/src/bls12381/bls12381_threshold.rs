@@ -0,0 +1,388 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides APIs for threshold Boneh-Lynn-Shacham (BLS) signatures [^Bold03], where
+//! the private key of a group of `n` players is secret-shared among them, such that any `t` of
+//! them can collaborate to produce a signature under the group's public key, but fewer cannot.
+//!
+//! The private key is shared with a polynomial `f` of degree `t - 1` over the scalar field, with
+//! `f(0)` the private key. Player `i` holds the _private key share_ `f(i + 1)`, and the public
+//! coefficients of `f`, i.e., `g_1^{a_j}` for each coefficient `a_j`, are published as a
+//! `Transcript`. Anyone with the transcript can derive the group's public key, `g_1^{f(0)}`, as
+//! well as the _public key share_ `g_1^{f(i + 1)}` of any player `i`.
+//!
+//! A transcript is produced by a dealer via `deal`, which also returns the private key shares to
+//! send to the players. In a distributed key generation (DKG), every player deals, and each player
+//! sums the transcripts via `Transcript::aggregate`, and the private key shares it received via
+//! `PrivateKeyShare::aggregate`, so that no single party ever knows the group's private key.
+//! Players MUST check the private key shares they receive against the dealer's transcript, via
+//! `Transcript::verify_share`.
+//!
+//! Each player signs a message with its private key share, producing a `SignatureShare`, which can
+//! be checked against the player's public key share. Any `t` signature shares from distinct players
+//! are then aggregated via `aggregate_signature_shares`, interpolating `f` in the exponent at 0,
+//! into a normal `bls12381::Signature` which verifies under the group's public key.
+//!
+//! Public keys and signatures serialize just like their non-threshold counterparts, i.e., as the
+//! compressed 48-byte and 96-byte group elements accepted by the `aptos_std::bls12381` Move module,
+//! and shares serialize as the index of their player, followed by the key or signature.
+//!
+//! The arithmetic on scalars and group elements is done with the `bls12_381` crate, since blst
+//! only exposes it through its FFI. Nothing on chain or in the node uses threshold signatures yet,
+//! so this module, and the dependency, are only built with the `threshold` feature.
+//!
+//! [^Bold03]: Threshold Signatures, Multisignatures and Blind Signatures Based on the Gap-Diffie-Hellman-Group Signature Scheme; by Boldyreva, Alexandra; in PKC 2003; 2002
+
+use crate::{
+    bls12381::{PrivateKey, PublicKey, Signature},
+    hash::CryptoHash,
+    traits::{Signature as _, SigningKey},
+    Uniform,
+};
+use anyhow::{anyhow, ensure, Result};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use std::{collections::BTreeSet, convert::TryFrom};
+
+/// The public coefficients of the polynomial sharing the private key of a group, from which the
+/// group's public key and the public key share of each player are derived.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Transcript {
+    /// The number of players the key is shared among, whose indexes are below it
+    num_players: u64,
+    /// `g_1^{a_j}` for each coefficient `a_j` of the polynomial, starting with the constant one.
+    /// Never empty.
+    commitments: Vec<PublicKey>,
+}
+
+/// Rejects transcripts without commitments or with more commitments than players.
+impl<'de> Deserialize<'de> for Transcript {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Transcript")]
+        struct RawTranscript {
+            num_players: u64,
+            commitments: Vec<PublicKey>,
+        }
+
+        let RawTranscript {
+            num_players,
+            commitments,
+        } = RawTranscript::deserialize(deserializer)?;
+        if commitments.is_empty() || commitments.len() as u64 > num_players {
+            return Err(D::Error::custom(format!(
+                "Transcript has {} commitments for {} players",
+                commitments.len(),
+                num_players
+            )));
+        }
+        Ok(Transcript {
+            num_players,
+            commitments,
+        })
+    }
+}
+
+/// The share of the group's private key held by the player with index `index`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrivateKeyShare {
+    pub index: u64,
+    pub private_key: PrivateKey,
+}
+
+/// The public key corresponding to the private key share of the player with index `index`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeyShare {
+    pub index: u64,
+    pub public_key: PublicKey,
+}
+
+/// A signature by the player with index `index`, with its private key share.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignatureShare {
+    pub index: u64,
+    pub signature: Signature,
+}
+
+/// Deals the shares of a new random private key among `num_players` players, any `threshold` of
+/// whom can sign. Returns the transcript to publish, and the private key share of each player, in
+/// order of index, to send to that player alone.
+pub fn deal<R>(
+    rng: &mut R,
+    threshold: usize,
+    num_players: usize,
+) -> Result<(Transcript, Vec<PrivateKeyShare>)>
+where
+    R: ::rand::RngCore + ::rand::CryptoRng,
+{
+    ensure!(
+        threshold > 0 && threshold <= num_players,
+        "Threshold {} must be between 1 and the number of players, {}",
+        threshold,
+        num_players
+    );
+
+    let coefficients: Vec<PrivateKey> = (0..threshold).map(|_| PrivateKey::generate(rng)).collect();
+    let transcript = Transcript {
+        num_players: num_players as u64,
+        commitments: coefficients.iter().map(PublicKey::from).collect(),
+    };
+
+    let coefficients: Vec<Scalar> = coefficients
+        .iter()
+        .map(scalar_from_private_key)
+        .collect::<Result<_>>()?;
+    let shares = (0..transcript.num_players)
+        .map(|index| {
+            // Evaluates the polynomial at `index + 1` via Horner's rule
+            let x = evaluation_point(index, transcript.num_players)?;
+            let mut y = Scalar::from(0u64);
+            for coefficient in coefficients.iter().rev() {
+                y = y * x + coefficient;
+            }
+            Ok(PrivateKeyShare {
+                index,
+                private_key: private_key_from_scalar(&y)?,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((transcript, shares))
+}
+
+impl Transcript {
+    /// The minimum number of signature shares needed to produce a signature.
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// The number of players the key is shared among.
+    pub fn num_players(&self) -> u64 {
+        self.num_players
+    }
+
+    /// The public key of the group, which signatures aggregated from the signature shares verify
+    /// under.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.commitments[0]
+    }
+
+    /// Subgroup-checks the commitments of a transcript received from a dealer.
+    ///
+    /// WARNING: Transcripts obtained over the network MUST be checked before deriving any public
+    /// key from them.
+    pub fn validate(&self) -> Result<()> {
+        for commitment in &self.commitments {
+            commitment.subgroup_check()?;
+        }
+        Ok(())
+    }
+
+    /// Derives the public key share of the player with index `index`, which must be below the
+    /// number of players.
+    pub fn public_key_share(&self, index: u64) -> Result<PublicKeyShare> {
+        // Evaluates the polynomial at `index + 1` in the exponent, via Horner's rule
+        let x = evaluation_point(index, self.num_players)?;
+        let mut y = G1Projective::identity();
+        for commitment in self.commitments.iter().rev() {
+            y = y * x + g1_from_public_key(commitment)?;
+        }
+        Ok(PublicKeyShare {
+            index,
+            public_key: g1_to_public_key(&y)?,
+        })
+    }
+
+    /// Checks that `share` is the private key share of its player under this transcript.
+    pub fn verify_share(&self, share: &PrivateKeyShare) -> Result<()> {
+        let expected = self.public_key_share(share.index)?;
+        ensure!(
+            PublicKey::from(&share.private_key) == expected.public_key,
+            "Private key share of player {} does not match the transcript",
+            share.index
+        );
+        Ok(())
+    }
+
+    /// Sums the transcripts of every dealer of a DKG into the transcript of the group's key.
+    pub fn aggregate(transcripts: &[Transcript]) -> Result<Transcript> {
+        let first = transcripts
+            .first()
+            .ok_or_else(|| anyhow!("No transcripts to aggregate"))?;
+        let (threshold, num_players) = (first.threshold(), first.num_players);
+        ensure!(
+            transcripts
+                .iter()
+                .all(|t| t.threshold() == threshold && t.num_players == num_players),
+            "Transcripts have different thresholds or numbers of players"
+        );
+
+        let commitments = (0..threshold)
+            .map(|j| {
+                let mut sum = G1Projective::identity();
+                for transcript in transcripts {
+                    sum += g1_from_public_key(&transcript.commitments[j])?;
+                }
+                g1_to_public_key(&sum)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Transcript {
+            num_players,
+            commitments,
+        })
+    }
+}
+
+impl PrivateKeyShare {
+    /// Signs `message` with this private key share.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> SignatureShare {
+        SignatureShare {
+            index: self.index,
+            signature: self.private_key.sign(message),
+        }
+    }
+
+    /// Sums the private key shares a player received from every dealer of a DKG into its share of
+    /// the group's private key.
+    pub fn aggregate(shares: &[PrivateKeyShare]) -> Result<PrivateKeyShare> {
+        let index = shares
+            .first()
+            .ok_or_else(|| anyhow!("No private key shares to aggregate"))?
+            .index;
+        ensure!(
+            shares.iter().all(|share| share.index == index),
+            "Private key shares are for different players"
+        );
+
+        let mut sum = Scalar::from(0u64);
+        for share in shares {
+            sum += scalar_from_private_key(&share.private_key)?;
+        }
+        Ok(PrivateKeyShare {
+            index,
+            private_key: private_key_from_scalar(&sum)?,
+        })
+    }
+}
+
+impl SignatureShare {
+    /// Verifies this signature share on `message` under the public key share of its player.
+    pub fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key_share: &PublicKeyShare,
+    ) -> Result<()> {
+        ensure!(
+            self.index == public_key_share.index,
+            "Signature share of player {} checked against public key share of player {}",
+            self.index,
+            public_key_share.index
+        );
+        self.signature.verify(message, &public_key_share.public_key)
+    }
+}
+
+/// Aggregates the signature shares of `threshold` distinct players, among `num_players`, into a
+/// signature under the group's public key. Only the first `threshold` shares are used.
+///
+/// NOTE: Like `Signature::aggregate`, this does not check the signature shares, so an invalid one
+/// results in a signature which does not verify. The aggregator should then verify each share
+/// under its player's public key share to exclude the invalid ones.
+pub fn aggregate_signature_shares(
+    threshold: usize,
+    num_players: u64,
+    shares: &[SignatureShare],
+) -> Result<Signature> {
+    ensure!(
+        shares.len() >= threshold,
+        "Got {} signature shares, but {} are needed",
+        shares.len(),
+        threshold
+    );
+    let shares = &shares[..threshold];
+    let indexes: BTreeSet<u64> = shares.iter().map(|share| share.index).collect();
+    ensure!(
+        indexes.len() == shares.len(),
+        "Signature shares must be from distinct players"
+    );
+
+    let points: Vec<Scalar> = shares
+        .iter()
+        .map(|share| evaluation_point(share.index, num_players))
+        .collect::<Result<_>>()?;
+    let mut sum = G2Projective::identity();
+    for (i, share) in shares.iter().enumerate() {
+        let coefficient = lagrange_coefficient_at_zero(&points, i)?;
+        sum += g2_from_signature(&share.signature)? * coefficient;
+    }
+    g2_to_signature(&sum)
+}
+
+/// The Lagrange coefficient of `points[i]`, for interpolating at 0:
+/// `prod_{j != i} x_j / (x_j - x_i)`. The points must be distinct.
+fn lagrange_coefficient_at_zero(points: &[Scalar], i: usize) -> Result<Scalar> {
+    let mut numerator = Scalar::from(1u64);
+    let mut denominator = Scalar::from(1u64);
+    for (j, x_j) in points.iter().enumerate() {
+        if j != i {
+            numerator *= x_j;
+            denominator *= x_j - points[i];
+        }
+    }
+    let inverse = Option::<Scalar>::from(denominator.invert())
+        .ok_or_else(|| anyhow!("Evaluation points must be distinct"))?;
+    Ok(numerator * inverse)
+}
+
+/// Player `index` gets the evaluation at `index + 1`, since the evaluation at 0 is the secret.
+fn evaluation_point(index: u64, num_players: u64) -> Result<Scalar> {
+    ensure!(
+        index < num_players,
+        "Player index {} is out of range for {} players",
+        index,
+        num_players
+    );
+    Ok(Scalar::from(index + 1))
+}
+
+///////////////////////////////////////////////////////////////////////
+// Conversions between the blst-backed types and the `bls12_381` ones //
+///////////////////////////////////////////////////////////////////////
+
+fn scalar_from_private_key(private_key: &PrivateKey) -> Result<Scalar> {
+    // Private keys are big-endian, scalars little-endian
+    let mut bytes = private_key.to_bytes();
+    bytes.reverse();
+    Option::<Scalar>::from(Scalar::from_bytes(&bytes))
+        .ok_or_else(|| anyhow!("Non-canonical private key"))
+}
+
+fn private_key_from_scalar(value: &Scalar) -> Result<PrivateKey> {
+    let mut bytes = value.to_bytes();
+    bytes.reverse();
+    // Fails for a zero scalar, which only happens with negligible probability
+    PrivateKey::try_from(&bytes[..]).map_err(|e| anyhow!("{:?}", e))
+}
+
+fn g1_from_public_key(public_key: &PublicKey) -> Result<G1Projective> {
+    Option::<G1Affine>::from(G1Affine::from_compressed(&public_key.to_bytes()))
+        .map(G1Projective::from)
+        .ok_or_else(|| anyhow!("Invalid public key"))
+}
+
+fn g1_to_public_key(point: &G1Projective) -> Result<PublicKey> {
+    PublicKey::try_from(&G1Affine::from(point).to_compressed()[..]).map_err(|e| anyhow!("{:?}", e))
+}
+
+fn g2_from_signature(signature: &Signature) -> Result<G2Projective> {
+    Option::<G2Affine>::from(G2Affine::from_compressed(&signature.to_bytes()))
+        .map(G2Projective::from)
+        .ok_or_else(|| anyhow!("Invalid signature"))
+}
+
+fn g2_to_signature(point: &G2Projective) -> Result<Signature> {
+    Signature::try_from(&G2Affine::from(point).to_compressed()[..]).map_err(|e| anyhow!("{:?}", e))
+}
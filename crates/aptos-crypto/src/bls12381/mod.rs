@@ -79,6 +79,14 @@
 //!
 //! Aggregation proceeds the same as in a multisignature scheme (see notes in previous section).
 //!
+//! # Overview of threshold Boneh-Lynn-Shacham (BLS) signatures
+//!
+//! In a _threshold signature scheme_, a single private key is secret-shared among `n` signers, such
+//! that any `t` of them can produce a signature under the corresponding public key, while fewer
+//! than `t` cannot. Unlike a multisignature, the resulting signature does not depend on which `t`
+//! signers produced it, so it can serve as a source of randomness. See `bls12381_threshold`, which
+//! is only built with the `threshold` feature.
+//!
 //! # A note on subgroup checks
 //!
 //! This library was written so that users who know nothing about _small subgroup attacks_  [^LL97], [^BCM+15e]
@@ -422,6 +430,8 @@ const DST_BLS_SIG_IN_G2_WITH_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_
 pub mod bls12381_keys;
 pub mod bls12381_pop;
 pub mod bls12381_sigs;
+#[cfg(any(test, feature = "threshold"))]
+pub mod bls12381_threshold;
 pub mod bls12381_validatable;
 
 pub use bls12381_keys::{PrivateKey, PublicKey};
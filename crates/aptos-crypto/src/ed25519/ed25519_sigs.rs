@@ -4,13 +4,15 @@
 //! This file implements traits for Ed25519 signatures.
 
 use crate::{
+    batch_verifier::BatchVerifiable,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, ED25519_SIGNATURE_LENGTH, L},
     hash::CryptoHash,
     traits::*,
 };
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
 use core::convert::TryFrom;
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use serde::Serialize;
 use std::{cmp::Ordering, fmt};
 
@@ -135,6 +137,48 @@ impl Signature for Ed25519Signature {
     }
 }
 
+impl BatchVerifiable for Ed25519Signature {
+    /// Performs the same checks as `verify_arbitrary_msg` on each signature, except for the
+    /// verification equation, which is checked for all signatures at once with random
+    /// coefficients.
+    ///
+    /// The batch equation only agrees with the cofactorless equation of `verify_arbitrary_msg` for
+    /// points without a small-order component, which the random coefficients could cancel out.
+    /// The batch is therefore rejected if any R component or public key isn't torsion-free, or if
+    /// an R component isn't canonically encoded, so that its signatures get verified one by one.
+    fn batch_verify_arbitrary_msgs(items: &[(&[u8], &Ed25519PublicKey, &Self)]) -> Result<()> {
+        for (_, public_key, signature) in items {
+            let bytes = signature.to_bytes();
+            Ed25519Signature::check_s_malleability(&bytes)?;
+            ensure!(
+                is_batchable_point(&bytes[..32]) && is_batchable_point(public_key.0.as_bytes()),
+                "R component or public key with a small-order component or a non-canonical encoding"
+            );
+        }
+
+        let messages: Vec<_> = items.iter().map(|(message, _, _)| *message).collect();
+        let signatures: Vec<_> = items.iter().map(|(_, _, signature)| signature.0).collect();
+        let public_keys: Vec<_> = items
+            .iter()
+            .map(|(_, public_key, _)| public_key.0)
+            .collect();
+        ed25519_dalek::verify_batch(&messages, &signatures, &public_keys)
+            .map_err(|e| anyhow!("{}", e))
+    }
+}
+
+/// Whether `bytes` canonically encode a point which is torsion-free and not the identity, i.e. of
+/// order L.
+fn is_batchable_point(bytes: &[u8]) -> bool {
+    CompressedEdwardsY::from_slice(bytes)
+        .decompress()
+        .map_or(false, |point| {
+            !point.is_small_order()
+                && point.is_torsion_free()
+                && point.compress().as_bytes() == bytes
+        })
+}
+
 impl Length for Ed25519Signature {
     fn length(&self) -> usize {
         ED25519_SIGNATURE_LENGTH
@@ -5,14 +5,19 @@
 #![deny(missing_docs)]
 
 //! A library supplying various cryptographic primitives
+pub mod batch_verifier;
 pub mod bls12381;
 pub mod compat;
 pub mod ed25519;
 pub mod error;
 pub mod hash;
 pub mod hkdf;
+pub mod mnemonic;
 pub mod multi_ed25519;
 pub mod noise;
+pub mod secp256k1_ecdsa;
+pub mod secp256r1_ecdsa;
+pub mod slip10;
 pub mod test_utils;
 pub mod traits;
 pub mod validatable;
@@ -0,0 +1,85 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mnemonic phrases, as specified by [BIP-39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki),
+//! which encode the seed of [hierarchical key derivation](crate::slip10) in English words so that
+//! it can be written down, and restored in any compatible wallet.
+//!
+//! # Example
+//!
+//! ```
+//! use aptos_crypto::{mnemonic::Mnemonic, slip10::DerivationPath};
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! let mut rng = StdRng::from_seed([0u8; 32]);
+//! let mnemonic = Mnemonic::generate(&mut rng, 24).unwrap();
+//! let restored = Mnemonic::from_phrase(mnemonic.phrase()).unwrap();
+//!
+//! let path = DerivationPath::default();
+//! assert_eq!(
+//!     mnemonic.derive_ed25519_private_key("", &path),
+//!     restored.derive_ed25519_private_key("", &path),
+//! );
+//! ```
+
+use crate::{
+    ed25519::Ed25519PrivateKey,
+    slip10::{derive_ed25519_private_key, DerivationError, DerivationPath},
+};
+use bip39::{Language, Seed};
+use rand::{CryptoRng, RngCore};
+
+/// The numbers of words a mnemonic may have.
+pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// A BIP-39 mnemonic phrase, in English.
+#[derive(Clone)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// Generates a random mnemonic of `word_count` words, one of [`WORD_COUNTS`]. Every 3 words
+    /// encode 32 bits of entropy, less a checksum bit.
+    pub fn generate<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        word_count: usize,
+    ) -> Result<Self, DerivationError> {
+        if !WORD_COUNTS.contains(&word_count) {
+            return Err(DerivationError::InvalidMnemonic(format!(
+                "{} words, expected one of {:?}",
+                word_count, WORD_COUNTS
+            )));
+        }
+        let mut entropy = vec![0u8; word_count / 3 * 4];
+        rng.fill_bytes(&mut entropy);
+        bip39::Mnemonic::from_entropy(&entropy, Language::English)
+            .map(Self)
+            .map_err(|error| DerivationError::InvalidMnemonic(error.to_string()))
+    }
+
+    /// Parses `phrase`, checking its words and checksum.
+    pub fn from_phrase(phrase: &str) -> Result<Self, DerivationError> {
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+        bip39::Mnemonic::from_phrase(&phrase, Language::English)
+            .map(Self)
+            .map_err(|error| DerivationError::InvalidMnemonic(error.to_string()))
+    }
+
+    /// The words of the mnemonic, separated by spaces.
+    pub fn phrase(&self) -> &str {
+        self.0.phrase()
+    }
+
+    /// The 64 byte seed of the mnemonic, with an optional `passphrase`, which is empty if unused.
+    pub fn to_seed(&self, passphrase: &str) -> Vec<u8> {
+        Seed::new(&self.0, passphrase).as_bytes().to_vec()
+    }
+
+    /// Derives the Ed25519 private key at `path` from the seed of the mnemonic.
+    pub fn derive_ed25519_private_key(
+        &self,
+        passphrase: &str,
+        path: &DerivationPath,
+    ) -> Result<Ed25519PrivateKey, DerivationError> {
+        derive_ed25519_private_key(&self.to_seed(passphrase), path)
+    }
+}
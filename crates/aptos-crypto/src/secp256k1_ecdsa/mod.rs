@@ -0,0 +1,53 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for ECDSA signatures over the secp256k1 curve, with SHA3-256 as the
+//! message digest, so that keys held in HSMs and wallets for other blockchains can sign.
+//!
+//! Signature verification rejects signatures whose `s` component is in the upper half of the
+//! scalar field, so that signatures are not malleable. Our `sign` implementation always produces
+//! a low `s`.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256k1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<PrivateKey, PublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message);
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of a secp256k1 private key
+pub const PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of a secp256k1 public key, in uncompressed SEC1 form
+pub const PUBLIC_KEY_LENGTH: usize = 65;
+/// The length of a secp256k1 ECDSA signature, as the concatenation of `r` and `s`
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub mod secp256k1_ecdsa_keys;
+pub mod secp256k1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256k1_ecdsa_keys::keypair_strategy;
+
+pub use secp256k1_ecdsa_keys::{PrivateKey, PublicKey};
+pub use secp256k1_ecdsa_sigs::Signature;
+
+/// The digest of `message` that is signed, as a secp256k1 message
+fn message_digest(message: &[u8]) -> libsecp256k1::Message {
+    libsecp256k1::Message::parse(crate::HashValue::sha3_256_of(message).as_ref())
+}
@@ -0,0 +1,247 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256k1 ECDSA private keys and public keys.
+
+use crate::{
+    hash::CryptoHash,
+    secp256k1_ecdsa::{Signature, PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH},
+    signing_message, traits, CryptoMaterialError, Genesis, Length, SigningKey, Uniform,
+    ValidCryptoMaterial, ValidCryptoMaterialStringExt, VerifyingKey,
+};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use core::convert::TryFrom;
+use serde::Serialize;
+use std::fmt;
+
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::test_utils::{self, KeyPair};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest::prelude::*;
+
+/// A secp256k1 ECDSA private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct PrivateKey(pub(crate) libsecp256k1::SecretKey);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(PrivateKey: Clone);
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for PrivateKey {
+    fn clone(&self) -> Self {
+        PrivateKey(self.0.clone())
+    }
+}
+
+/// A secp256k1 ECDSA public key
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct PublicKey(pub(crate) libsecp256k1::PublicKey);
+
+impl PrivateKey {
+    /// The length of the PrivateKey
+    pub const LENGTH: usize = PRIVATE_KEY_LENGTH;
+
+    /// Serialize a PrivateKey.
+    pub fn to_bytes(&self) -> [u8; PRIVATE_KEY_LENGTH] {
+        self.0.serialize()
+    }
+
+    /// Private function aimed at minimizing code duplication between sign
+    /// methods of the SigningKey implementation. This should remain private.
+    fn sign_arbitrary_message(&self, message: &[u8]) -> Signature {
+        // The signature always has a low `s`
+        let (signature, _recovery_id) =
+            libsecp256k1::sign(&super::message_digest(message), &self.0);
+        Signature(signature)
+    }
+}
+
+impl PublicKey {
+    /// The length of the PublicKey
+    pub const LENGTH: usize = PUBLIC_KEY_LENGTH;
+
+    /// Serialize a PublicKey, in uncompressed SEC1 form.
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_LENGTH] {
+        self.0.serialize()
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl traits::PrivateKey for PrivateKey {
+    type PublicKeyMaterial = PublicKey;
+}
+
+impl SigningKey for PrivateKey {
+    type VerifyingKeyMaterial = PublicKey;
+    type SignatureMaterial = Signature;
+
+    fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Signature {
+        PrivateKey::sign_arbitrary_message(self, signing_message(message).as_ref())
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    fn sign_arbitrary_message(&self, message: &[u8]) -> Signature {
+        PrivateKey::sign_arbitrary_message(self, message)
+    }
+}
+
+impl Uniform for PrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng + ::rand_core::CryptoRng + ::rand_core::RngCore,
+    {
+        // Rejection sampling: the bytes must encode a non-zero scalar below the group order, which
+        // fails with negligible probability
+        loop {
+            let mut bytes = [0u8; PRIVATE_KEY_LENGTH];
+            rng.fill_bytes(&mut bytes);
+            if let Ok(private_key) = PrivateKey::try_from(&bytes[..]) {
+                return private_key;
+            }
+        }
+    }
+}
+
+impl PartialEq<Self> for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for PrivateKey {}
+
+impl TryFrom<&[u8]> for PrivateKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a PrivateKey. This method will check for private key validity: i.e., that it is
+    /// a non-zero scalar below the group order.
+    fn try_from(bytes: &[u8]) -> std::result::Result<PrivateKey, CryptoMaterialError> {
+        if bytes.len() != PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        libsecp256k1::SecretKey::parse_slice(bytes)
+            .map(PrivateKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for PrivateKey {
+    fn length(&self) -> usize {
+        Self::LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Genesis for PrivateKey {
+    fn genesis() -> Self {
+        let mut buf = [0u8; PRIVATE_KEY_LENGTH];
+        buf[PRIVATE_KEY_LENGTH - 1] = 1;
+        Self::try_from(buf.as_ref()).unwrap()
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+impl From<&PrivateKey> for PublicKey {
+    fn from(private_key: &PrivateKey) -> Self {
+        PublicKey(libsecp256k1::PublicKey::from_secret_key(&private_key.0))
+    }
+}
+
+impl traits::PublicKey for PublicKey {
+    type PrivateKeyMaterial = PrivateKey;
+}
+
+impl std::hash::Hash for PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_pubkey = self.to_bytes();
+        state.write(&encoded_pubkey);
+    }
+}
+
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &PublicKey) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for PublicKey {}
+
+impl VerifyingKey for PublicKey {
+    type SigningKeyMaterial = PrivateKey;
+    type SignatureMaterial = Signature;
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "secp256k1_ecdsa::PublicKey({})", self)
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a PublicKey from its uncompressed SEC1 form. This checks that the key is a
+    /// point on the curve other than the identity; as secp256k1 has a prime order, it is then in
+    /// the right group.
+    fn try_from(bytes: &[u8]) -> std::result::Result<PublicKey, CryptoMaterialError> {
+        // Compressed keys are rejected, so that every key has a single representation
+        if bytes.len() != PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        libsecp256k1::PublicKey::parse_slice(bytes, Some(libsecp256k1::PublicKeyFormat::Full))
+            .map(PublicKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for PublicKey {
+    fn length(&self) -> usize {
+        PUBLIC_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+/////////////
+// Fuzzing //
+/////////////
+
+/// Produces a uniformly random secp256k1 ECDSA keypair from a seed
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn keypair_strategy() -> impl Strategy<Value = KeyPair<PrivateKey, PublicKey>> {
+    test_utils::uniform_keypair_strategy::<PrivateKey, PublicKey>()
+}
+
+/// Produces a uniformly random secp256k1 ECDSA public key
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for PublicKey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        crate::test_utils::uniform_keypair_strategy::<PrivateKey, PublicKey>()
+            .prop_map(|v| v.public_key)
+            .boxed()
+    }
+}
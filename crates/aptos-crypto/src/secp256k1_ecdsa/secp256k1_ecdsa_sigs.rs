@@ -0,0 +1,133 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256k1 ECDSA signatures.
+
+use crate::{
+    hash::CryptoHash,
+    secp256k1_ecdsa::{PrivateKey, PublicKey, SIGNATURE_LENGTH},
+    signing_message, traits, CryptoMaterialError, Length, ValidCryptoMaterial,
+    ValidCryptoMaterialStringExt,
+};
+use anyhow::{anyhow, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
+use core::convert::TryFrom;
+use serde::Serialize;
+use std::fmt;
+
+/// A secp256k1 ECDSA signature
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Signature(pub(crate) libsecp256k1::Signature);
+
+impl Signature {
+    /// The length of the Signature
+    pub const LENGTH: usize = SIGNATURE_LENGTH;
+
+    /// Serialize a Signature, as the concatenation of `r` and `s`.
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        self.0.serialize()
+    }
+
+    /// Deserialize a Signature without checking that `s` is low, which verification does anyway.
+    pub(crate) fn from_bytes_unchecked(
+        bytes: &[u8],
+    ) -> std::result::Result<Signature, CryptoMaterialError> {
+        if bytes.len() != SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        // Rejects `r` and `s` which are not below the group order
+        libsecp256k1::Signature::parse_standard_slice(bytes)
+            .map(Signature)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+
+    /// Check for malleable signatures: of the two valid `s` for a signature, `s` and `n - s`, only
+    /// the one in the lower half of the scalar field is accepted.
+    pub fn check_s_malleability(&self) -> std::result::Result<(), CryptoMaterialError> {
+        if self.0.s.is_high() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(())
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl traits::Signature for Signature {
+    type VerifyingKeyMaterial = PublicKey;
+    type SigningKeyMaterial = PrivateKey;
+
+    /// Verifies that the provided signature is valid for the provided message, going beyond the
+    /// [SEC 1](https://www.secg.org/sec1-v2.pdf) specification, to prevent malleability by
+    /// rejecting signatures with a high `s`.
+    fn verify<T: CryptoHash + Serialize>(&self, message: &T, public_key: &PublicKey) -> Result<()> {
+        Self::verify_arbitrary_msg(self, &signing_message(message), public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`.
+    /// Outside of this crate, this particular function should only be used for native signature
+    /// verification in Move.
+    fn verify_arbitrary_msg(&self, message: &[u8], public_key: &PublicKey) -> Result<()> {
+        self.check_s_malleability()?;
+
+        if !libsecp256k1::verify(&super::message_digest(message), &self.0, &public_key.0) {
+            return Err(anyhow!("Invalid secp256k1 ECDSA signature"));
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Length for Signature {
+    fn length(&self) -> usize {
+        SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for Signature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Signature, CryptoMaterialError> {
+        let signature = Signature::from_bytes_unchecked(bytes)?;
+        signature.check_s_malleability()?;
+        Ok(signature)
+    }
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Signature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Signature {}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "secp256k1_ecdsa::Signature({})", self)
+    }
+}
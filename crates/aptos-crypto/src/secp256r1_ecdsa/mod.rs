@@ -0,0 +1,48 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for ECDSA signatures over the NIST P-256 (a.k.a. secp256r1) curve,
+//! with SHA2-256 as the message digest, as produced by WebAuthn authenticators (passkeys).
+//!
+//! Signature verification rejects signatures whose `s` component is in the upper half of the
+//! scalar field, so that signatures are not malleable. Signers must normalize `s` to `n - s`
+//! when it is high, which our `sign` implementation always does.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256r1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<PrivateKey, PublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message);
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of a secp256r1 private key
+pub const PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of a secp256r1 public key, in uncompressed SEC1 form
+pub const PUBLIC_KEY_LENGTH: usize = 65;
+/// The length of a secp256r1 ECDSA signature, as the concatenation of `r` and `s`
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub mod secp256r1_ecdsa_keys;
+pub mod secp256r1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256r1_ecdsa_keys::keypair_strategy;
+
+pub use secp256r1_ecdsa_keys::{PrivateKey, PublicKey};
+pub use secp256r1_ecdsa_sigs::Signature;
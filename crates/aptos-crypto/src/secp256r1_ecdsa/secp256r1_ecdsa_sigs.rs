@@ -0,0 +1,136 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256r1 ECDSA signatures.
+
+use crate::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{PrivateKey, PublicKey, SIGNATURE_LENGTH},
+    signing_message, traits, CryptoMaterialError, Length, ValidCryptoMaterial,
+    ValidCryptoMaterialStringExt,
+};
+use anyhow::{anyhow, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
+use core::convert::TryFrom;
+use p256::ecdsa::signature::Verifier;
+use serde::Serialize;
+use std::fmt;
+
+/// A secp256r1 ECDSA signature
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Signature(pub(crate) p256::ecdsa::Signature);
+
+impl Signature {
+    /// The length of the Signature
+    pub const LENGTH: usize = SIGNATURE_LENGTH;
+
+    /// Serialize a Signature, as the concatenation of `r` and `s`.
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes.copy_from_slice(self.0.as_ref());
+        bytes
+    }
+
+    /// Deserialize a Signature without checking that `s` is low, which verification does anyway.
+    pub(crate) fn from_bytes_unchecked(
+        bytes: &[u8],
+    ) -> std::result::Result<Signature, CryptoMaterialError> {
+        if bytes.len() != SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        p256::ecdsa::Signature::try_from(bytes)
+            .map(Signature)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+
+    /// Check for malleable signatures: of the two valid `s` for a signature, `s` and `n - s`, only
+    /// the one in the lower half of the scalar field is accepted.
+    pub fn check_s_malleability(&self) -> std::result::Result<(), CryptoMaterialError> {
+        match self.0.normalize_s() {
+            // `s` was high, and had to be normalized
+            Some(_) => Err(CryptoMaterialError::CanonicalRepresentationError),
+            None => Ok(()),
+        }
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl traits::Signature for Signature {
+    type VerifyingKeyMaterial = PublicKey;
+    type SigningKeyMaterial = PrivateKey;
+
+    /// Verifies that the provided signature is valid for the provided message, going beyond the
+    /// [NIST SP 800-186](https://csrc.nist.gov/publications/detail/sp/800-186/final) specification,
+    /// to prevent malleability by rejecting signatures with a high `s`.
+    fn verify<T: CryptoHash + Serialize>(&self, message: &T, public_key: &PublicKey) -> Result<()> {
+        Self::verify_arbitrary_msg(self, &signing_message(message), public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`.
+    /// Outside of this crate, this particular function should only be used for native signature
+    /// verification in Move, and for WebAuthn assertions, which sign data other than the message.
+    fn verify_arbitrary_msg(&self, message: &[u8], public_key: &PublicKey) -> Result<()> {
+        self.check_s_malleability()?;
+
+        public_key
+            .0
+            .verify(message, &self.0)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Length for Signature {
+    fn length(&self) -> usize {
+        SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for Signature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Signature, CryptoMaterialError> {
+        let signature = Signature::from_bytes_unchecked(bytes)?;
+        signature.check_s_malleability()?;
+        Ok(signature)
+    }
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Signature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Signature {}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "secp256r1_ecdsa::Signature({})", self)
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hierarchical deterministic derivation of Ed25519 keys, as specified by
+//! [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md), and the
+//! [BIP-44](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki) paths used by wallets.
+//!
+//! A master key is derived from a seed, typically that of a [`Mnemonic`](crate::mnemonic::Mnemonic),
+//! and child keys are derived from it along a path of indices. SLIP-0010 only defines hardened
+//! derivation for Ed25519, so public keys cannot be derived from public keys alone, and every index
+//! of a path must be hardened.
+//!
+//! Aptos accounts use the BIP-44 path `m/44'/637'/{account}'/0'/{address_index}'`, where 637 is
+//! the coin type registered for Aptos in
+//! [SLIP-0044](https://github.com/satoshilabs/slips/blob/master/slip-0044.md).
+//!
+//! # Example
+//!
+//! ```
+//! use aptos_crypto::slip10::{DerivationPath, ExtendedPrivateKey};
+//! use std::str::FromStr;
+//!
+//! let seed = [7u8; 64];
+//! let path = DerivationPath::from_str("m/44'/637'/0'/0'/0'").unwrap();
+//! assert_eq!(path, DerivationPath::bip44(0, 0, 0).unwrap());
+//!
+//! let private_key = ExtendedPrivateKey::from_seed(&seed)
+//!     .unwrap()
+//!     .derive(&path)
+//!     .private_key();
+//! ```
+
+use crate::ed25519::{Ed25519PrivateKey, ED25519_PRIVATE_KEY_LENGTH};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+use std::{convert::TryFrom, fmt, str::FromStr};
+use thiserror::Error;
+
+/// Indices from this one up are hardened.
+pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// The BIP-44 purpose.
+pub const BIP44_PURPOSE: u32 = 44;
+
+/// The coin type of Aptos in SLIP-0044.
+pub const APTOS_COIN_TYPE: u32 = 637;
+
+/// The HMAC key for deriving the master key of the Ed25519 curve.
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+
+/// Seeds must be between 128 and 512 bits long, as in BIP-32.
+const MIN_SEED_LENGTH: usize = 16;
+const MAX_SEED_LENGTH: usize = 64;
+
+/// An error type for key derivation issues.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum DerivationError {
+    /// The derivation path could not be parsed.
+    #[error("Invalid derivation path {0}, expected e.g. m/44'/637'/0'/0'/0'")]
+    InvalidPath(String),
+    /// Ed25519 keys can only be derived along hardened indices.
+    #[error("Index {0} is not hardened, as required for Ed25519 keys")]
+    NonHardenedIndex(u32),
+    /// The seed is too short or too long.
+    #[error("Seed of {0} bytes, expected between 16 and 64")]
+    InvalidSeedLength(usize),
+    /// The mnemonic phrase is not valid BIP-39.
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+}
+
+/// A path of hardened indices from a master key to one of its descendants, e.g. `m/44'/637'/0'/0'/0'`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// A path of `indices`, which must all be hardened, i.e. no less than [`HARDENED_OFFSET`].
+    pub fn new(indices: Vec<u32>) -> Result<Self, DerivationError> {
+        if let Some(index) = indices.iter().find(|index| **index < HARDENED_OFFSET) {
+            return Err(DerivationError::NonHardenedIndex(*index));
+        }
+        Ok(Self(indices))
+    }
+
+    /// The BIP-44 path of an Aptos account, `m/44'/637'/{account}'/{change}'/{address_index}'`.
+    /// Wallets use a `change` of 0, and either derive accounts or address indices.
+    pub fn bip44(account: u32, change: u32, address_index: u32) -> Result<Self, DerivationError> {
+        [
+            BIP44_PURPOSE,
+            APTOS_COIN_TYPE,
+            account,
+            change,
+            address_index,
+        ]
+        .iter()
+        .map(|index| harden(*index))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Self)
+    }
+
+    /// The hardened indices of the path, from the master key down.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+/// The path of the first Aptos account, `m/44'/637'/0'/0'/0'`.
+impl Default for DerivationPath {
+    fn default() -> Self {
+        Self::bip44(0, 0, 0).expect("Default path indices are valid")
+    }
+}
+
+fn harden(index: u32) -> Result<u32, DerivationError> {
+    if index >= HARDENED_OFFSET {
+        return Err(DerivationError::InvalidPath(format!(
+            "index {} exceeds {}",
+            index,
+            HARDENED_OFFSET - 1
+        )));
+    }
+    Ok(index | HARDENED_OFFSET)
+}
+
+impl FromStr for DerivationPath {
+    type Err = DerivationError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid = || DerivationError::InvalidPath(path.to_string());
+        let mut segments = path.trim().split('/');
+        if segments.next() != Some("m") {
+            return Err(invalid());
+        }
+
+        let indices = segments
+            .map(|segment| {
+                let (index, hardened) = match segment.strip_suffix(&['\'', 'h', 'H'][..]) {
+                    Some(index) => (index, true),
+                    None => (segment, false),
+                };
+                let index: u32 = index.parse().map_err(|_| invalid())?;
+                if hardened {
+                    harden(index)
+                } else {
+                    Err(DerivationError::NonHardenedIndex(index))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(indices))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index - HARDENED_OFFSET)?;
+        }
+        Ok(())
+    }
+}
+
+/// An Ed25519 private key along with the chain code from which its children are derived.
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    key: [u8; ED25519_PRIVATE_KEY_LENGTH],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedPrivateKey {
+    /// Derives the master key from `seed`, which must be between 16 and 64 bytes long.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, DerivationError> {
+        if seed.len() < MIN_SEED_LENGTH || seed.len() > MAX_SEED_LENGTH {
+            return Err(DerivationError::InvalidSeedLength(seed.len()));
+        }
+        Ok(Self::from_hmac(ED25519_CURVE_SEED, &[seed]))
+    }
+
+    /// Derives the child at the hardened `index`.
+    pub fn derive_child(&self, index: u32) -> Result<Self, DerivationError> {
+        if index < HARDENED_OFFSET {
+            return Err(DerivationError::NonHardenedIndex(index));
+        }
+        Ok(Self::from_hmac(
+            &self.chain_code,
+            &[&[0u8], &self.key, &index.to_be_bytes()],
+        ))
+    }
+
+    /// Derives the descendant at the end of `path`.
+    pub fn derive(&self, path: &DerivationPath) -> Self {
+        path.indices().iter().fold(self.clone(), |key, index| {
+            key.derive_child(*index)
+                .expect("Indices of derivation paths are hardened")
+        })
+    }
+
+    /// The chain code, from which children are derived along with the key.
+    pub fn chain_code(&self) -> [u8; 32] {
+        self.chain_code
+    }
+
+    /// The Ed25519 private key.
+    pub fn private_key(&self) -> Ed25519PrivateKey {
+        Ed25519PrivateKey::try_from(&self.key[..]).expect("Any 32 bytes are an Ed25519 private key")
+    }
+
+    /// Splits HMAC-SHA512(`key`, `data`) into the private key and the chain code.
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut mac = Hmac::<Sha512>::new_varkey(key).expect("HMAC accepts keys of any length");
+        for data in data {
+            mac.update(data);
+        }
+        let output = mac.finalize().into_bytes();
+
+        let mut extended_key = Self {
+            key: [0u8; ED25519_PRIVATE_KEY_LENGTH],
+            chain_code: [0u8; 32],
+        };
+        extended_key.key.copy_from_slice(&output[..32]);
+        extended_key.chain_code.copy_from_slice(&output[32..]);
+        extended_key
+    }
+}
+
+/// Derives the Ed25519 private key at `path` from `seed`.
+pub fn derive_ed25519_private_key(
+    seed: &[u8],
+    path: &DerivationPath,
+) -> Result<Ed25519PrivateKey, DerivationError> {
+    Ok(ExtendedPrivateKey::from_seed(seed)?
+        .derive(path)
+        .private_key())
+}
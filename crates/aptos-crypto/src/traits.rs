@@ -315,4 +315,12 @@ pub(crate) mod private {
     impl Sealed for crate::bls12381::PublicKey {}
     impl Sealed for crate::bls12381::Signature {}
     impl Sealed for crate::bls12381::ProofOfPossession {}
+
+    impl Sealed for crate::secp256k1_ecdsa::PrivateKey {}
+    impl Sealed for crate::secp256k1_ecdsa::PublicKey {}
+    impl Sealed for crate::secp256k1_ecdsa::Signature {}
+
+    impl Sealed for crate::secp256r1_ecdsa::PrivateKey {}
+    impl Sealed for crate::secp256r1_ecdsa::PublicKey {}
+    impl Sealed for crate::secp256r1_ecdsa::Signature {}
 }
@@ -0,0 +1,140 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    batch_verifier::{BatchVerifiable, BatchVerifier},
+    bls12381,
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    signing_message,
+    test_utils::{KeyPair, TestAptosCrypto},
+    PrivateKey, Signature, SigningKey, Uniform,
+};
+use core::convert::TryFrom;
+use curve25519_dalek::{constants::EIGHT_TORSION, edwards::CompressedEdwardsY};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Signs `num_signatures` distinct messages under distinct keys with `sign`, except those at
+/// `invalid`, which are checked under the key of another signature, and checks that exactly those
+/// fail batch verification.
+fn test_batch_verifier<S, F>(num_signatures: usize, batch_size: usize, invalid: &[usize], sign: F)
+where
+    S: BatchVerifiable,
+    S::VerifyingKeyMaterial: Send + Sync,
+    F: Fn(&mut StdRng, &TestAptosCrypto) -> (S::VerifyingKeyMaterial, S),
+{
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let mut verifier = BatchVerifier::<S>::with_batch_size(batch_size);
+    for i in 0..num_signatures {
+        let message = TestAptosCrypto(format!("message {}", i));
+        let (mut public_key, signature) = sign(&mut rng, &message);
+        if invalid.contains(&i) {
+            public_key = sign(&mut rng, &message).0;
+        }
+        assert_eq!(verifier.add(&message, public_key, signature), i);
+    }
+    assert_eq!(verifier.len(), num_signatures);
+
+    let results = verifier.verify();
+    assert_eq!(results.len(), num_signatures);
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.is_err(), invalid.contains(&i), "signature {}", i);
+    }
+}
+
+fn sign_ed25519(
+    rng: &mut StdRng,
+    message: &TestAptosCrypto,
+) -> (Ed25519PublicKey, Ed25519Signature) {
+    let private_key = Ed25519PrivateKey::generate(rng);
+    (private_key.public_key(), private_key.sign(message))
+}
+
+fn sign_bls12381(
+    rng: &mut StdRng,
+    message: &TestAptosCrypto,
+) -> (bls12381::PublicKey, bls12381::Signature) {
+    let private_key = bls12381::PrivateKey::generate(rng);
+    (private_key.public_key(), private_key.sign(message))
+}
+
+#[test]
+fn test_ed25519_batch_verifier() {
+    test_batch_verifier(0, 8, &[], sign_ed25519);
+    test_batch_verifier(50, 8, &[], sign_ed25519);
+    test_batch_verifier(50, 8, &[0, 17, 18, 49], sign_ed25519);
+    test_batch_verifier(5, 1, &[3], sign_ed25519);
+}
+
+#[test]
+fn test_bls12381_batch_verifier() {
+    test_batch_verifier(20, 8, &[], sign_bls12381);
+    test_batch_verifier(20, 8, &[1, 2, 19], sign_bls12381);
+}
+
+#[test]
+fn test_batch_verify_same_message() {
+    let mut rng = StdRng::from_seed([1u8; 32]);
+    let message = TestAptosCrypto("same message".to_string());
+    let key_pairs: Vec<_> = (0..4)
+        .map(|_| KeyPair::<Ed25519PrivateKey, Ed25519PublicKey>::generate(&mut rng))
+        .collect();
+    let signatures: Vec<_> = key_pairs
+        .iter()
+        .map(|key_pair| key_pair.private_key.sign(&message))
+        .collect();
+
+    let mut verifier = BatchVerifier::<Ed25519Signature>::new();
+    for (key_pair, signature) in key_pairs.iter().zip(signatures.iter()) {
+        verifier.add(&message, key_pair.public_key.clone(), signature.clone());
+    }
+    // Swapping the signatures of two keys is caught
+    verifier.add(
+        &message,
+        key_pairs[0].public_key.clone(),
+        signatures[1].clone(),
+    );
+    verifier.add(
+        &message,
+        key_pairs[1].public_key.clone(),
+        signatures[0].clone(),
+    );
+    let results = verifier.verify();
+    assert!(results[..4].iter().all(|result| result.is_ok()));
+    assert!(results[4..].iter().all(|result| result.is_err()));
+}
+
+#[test]
+fn test_ed25519_batch_excludes_mixed_order_points() {
+    let mut rng = StdRng::from_seed([2u8; 32]);
+    let message = TestAptosCrypto("mixed order".to_string());
+    let (public_key, signature) = sign_ed25519(&mut rng, &message);
+    let point = CompressedEdwardsY(public_key.to_bytes())
+        .decompress()
+        .unwrap();
+    let mixed_public_key =
+        Ed25519PublicKey::try_from(&(point + EIGHT_TORSION[1]).compress().to_bytes()[..]).unwrap();
+
+    // The random coefficients of a batch could cancel out the torsion component, which the strict
+    // verification doesn't, so such keys are never batched
+    let bytes = signing_message(&message);
+    let bytes = bytes.as_slice();
+    assert!(
+        Ed25519Signature::batch_verify_arbitrary_msgs(&[(bytes, &public_key, &signature)]).is_ok()
+    );
+    assert!(Ed25519Signature::batch_verify_arbitrary_msgs(&[(
+        bytes,
+        &mixed_public_key,
+        &signature
+    )])
+    .is_err());
+
+    let mut verifier = BatchVerifier::<Ed25519Signature>::new();
+    verifier.add(&message, public_key, signature.clone());
+    verifier.add(&message, mixed_public_key.clone(), signature.clone());
+    let results = verifier.verify();
+    assert!(results[0].is_ok());
+    assert_eq!(
+        results[1].is_ok(),
+        signature.verify(&message, &mixed_public_key).is_ok()
+    );
+}
@@ -0,0 +1,151 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381,
+    bls12381::bls12381_threshold::{
+        aggregate_signature_shares, deal, PrivateKeyShare, SignatureShare, Transcript,
+    },
+    test_utils::{random_subset, TestAptosCrypto},
+    Signature,
+};
+use rand_core::OsRng;
+
+/// Tests that any `t` signature shares aggregate into the same signature, which verifies under the
+/// group's public key, and that each signature share verifies under its public key share.
+#[test]
+fn bls12381_threshold_sign_and_aggregate() {
+    let mut rng = OsRng;
+    let (threshold, num_players) = (5, 9);
+    let (transcript, shares) = deal(&mut rng, threshold, num_players).unwrap();
+    assert_eq!(transcript.threshold(), threshold);
+
+    let message = TestAptosCrypto("test".to_owned());
+    let sig_shares: Vec<SignatureShare> = shares.iter().map(|share| share.sign(&message)).collect();
+    for sig_share in &sig_shares {
+        let public_key_share = transcript.public_key_share(sig_share.index).unwrap();
+        assert!(sig_share.verify(&message, &public_key_share).is_ok());
+        let other_index = (sig_share.index + 1) % num_players as u64;
+        let other_public_key_share = transcript.public_key_share(other_index).unwrap();
+        assert!(sig_share.verify(&message, &other_public_key_share).is_err());
+    }
+
+    let mut signatures = vec![];
+    for _ in 0..3 {
+        let subset: Vec<SignatureShare> = random_subset(&mut rng, num_players, threshold)
+            .into_iter()
+            .map(|i| sig_shares[i].clone())
+            .collect();
+        let signature = aggregate_signature_shares(threshold, num_players as u64, &subset).unwrap();
+        assert!(signature.verify(&message, transcript.public_key()).is_ok());
+        signatures.push(signature);
+    }
+    assert!(signatures.windows(2).all(|pair| pair[0] == pair[1]));
+
+    let num_players = num_players as u64;
+    // Not enough shares
+    assert!(
+        aggregate_signature_shares(threshold, num_players, &sig_shares[..threshold - 1]).is_err()
+    );
+    // Shares from the same player
+    let duplicates = vec![sig_shares[0].clone(); threshold];
+    assert!(aggregate_signature_shares(threshold, num_players, &duplicates).is_err());
+    // An aggregate of fewer shares than the threshold does not verify
+    let signature = aggregate_signature_shares(threshold - 1, num_players, &sig_shares).unwrap();
+    assert!(signature.verify(&message, transcript.public_key()).is_err());
+}
+
+/// Tests that player indexes are bounded by the number of players, and that transcripts without
+/// commitments don't deserialize.
+#[test]
+fn bls12381_threshold_out_of_range() {
+    let mut rng = OsRng;
+    let (transcript, shares) = deal(&mut rng, 2, 3).unwrap();
+    assert_eq!(transcript.num_players(), 3);
+    assert!(transcript.public_key_share(3).is_err());
+    assert!(transcript.public_key_share(u64::MAX).is_err());
+
+    let message = TestAptosCrypto("range".to_owned());
+    let mut sig_shares: Vec<SignatureShare> =
+        shares.iter().map(|share| share.sign(&message)).collect();
+    sig_shares[0].index = u64::MAX;
+    assert!(aggregate_signature_shares(2, 3, &sig_shares).is_err());
+
+    // The number of players, then no commitments
+    let bytes = bcs::to_bytes(&(3u64, Vec::<bls12381::PublicKey>::new())).unwrap();
+    assert!(bcs::from_bytes::<Transcript>(&bytes).is_err());
+}
+
+/// Tests that the key shared by several dealers, as in a DKG, can sign, and that private key shares
+/// are checked against transcripts.
+#[test]
+fn bls12381_threshold_dkg() {
+    let mut rng = OsRng;
+    let (threshold, num_players) = (3, 4);
+    let dealings: Vec<(Transcript, Vec<PrivateKeyShare>)> = (0..num_players)
+        .map(|_| deal(&mut rng, threshold, num_players).unwrap())
+        .collect();
+
+    for (transcript, shares) in &dealings {
+        assert!(transcript.validate().is_ok());
+        for share in shares {
+            assert!(transcript.verify_share(share).is_ok());
+        }
+    }
+    // A share dealt with another transcript is detected
+    assert!(dealings[0].0.verify_share(&dealings[1].1[0]).is_err());
+
+    let transcripts: Vec<Transcript> = dealings.iter().map(|(t, _)| t.clone()).collect();
+    let transcript = Transcript::aggregate(&transcripts).unwrap();
+    let mut dealt_shares: Vec<Vec<PrivateKeyShare>> =
+        dealings.into_iter().map(|(_, s)| s).collect();
+    let shares: Vec<PrivateKeyShare> = (0..num_players)
+        .map(|player| {
+            let received: Vec<PrivateKeyShare> = dealt_shares
+                .iter_mut()
+                .map(|shares| shares.remove(0))
+                .collect();
+            assert!(received.iter().all(|share| share.index == player as u64));
+            PrivateKeyShare::aggregate(&received).unwrap()
+        })
+        .collect();
+    for share in &shares {
+        assert!(transcript.verify_share(share).is_ok());
+    }
+
+    let message = TestAptosCrypto("dkg".to_owned());
+    let sig_shares: Vec<SignatureShare> = shares[1..]
+        .iter()
+        .map(|share| share.sign(&message))
+        .collect();
+    let signature = aggregate_signature_shares(threshold, num_players as u64, &sig_shares).unwrap();
+    assert!(signature.verify(&message, transcript.public_key()).is_ok());
+}
+
+/// Tests that aggregated signatures serialize like any other BLS signature, and shares round-trip.
+#[test]
+fn bls12381_threshold_serialization() {
+    let mut rng = OsRng;
+    let (transcript, shares) = deal(&mut rng, 2, 3).unwrap();
+    let message = TestAptosCrypto("bcs".to_owned());
+    let sig_shares: Vec<SignatureShare> = shares.iter().map(|share| share.sign(&message)).collect();
+
+    let bytes = bcs::to_bytes(&sig_shares[1]).unwrap();
+    assert_eq!(bytes[..8], 1u64.to_le_bytes());
+    assert_eq!(
+        bcs::from_bytes::<SignatureShare>(&bytes).unwrap(),
+        sig_shares[1]
+    );
+
+    let transcript_bytes = bcs::to_bytes(&transcript).unwrap();
+    assert_eq!(
+        bcs::from_bytes::<Transcript>(&transcript_bytes).unwrap(),
+        transcript
+    );
+
+    let signature = aggregate_signature_shares(2, 3, &sig_shares).unwrap();
+    let expected = bcs::to_bytes(&signature.to_bytes().to_vec()).unwrap();
+    assert_eq!(bcs::to_bytes(&signature).unwrap(), expected);
+    let signature: bls12381::Signature = bcs::from_bytes(&expected).unwrap();
+    assert!(signature.verify(&message, transcript.public_key()).is_ok());
+}
@@ -1,8 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod batch_verifier_test;
 mod bcs_test;
 mod bls12381_test;
+mod bls12381_threshold_test;
 mod compat_test;
 mod cross_test;
 mod cryptohasher;
@@ -11,3 +13,6 @@ mod hash_test;
 mod hkdf_test;
 mod multi_ed25519_test;
 mod noise_test;
+mod secp256k1_ecdsa_test;
+mod secp256r1_ecdsa_test;
+mod slip10_test;
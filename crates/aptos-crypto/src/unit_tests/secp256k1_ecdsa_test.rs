@@ -0,0 +1,96 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate as aptos_crypto;
+use crate::{
+    secp256k1_ecdsa::{
+        PrivateKey, PublicKey, Signature, PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
+    },
+    test_utils::uniform_keypair_strategy,
+    traits::{Signature as _, *},
+};
+
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(CryptoHasher, BCSCryptoHash, Serialize, Deserialize)]
+struct CryptoHashable(pub usize);
+
+/// The order of the secp256k1 group, big-endian
+const N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Returns the other valid signature for the same message and key, with `s` replaced by `n - s`
+fn negate_s(signature: &Signature) -> [u8; SIGNATURE_LENGTH] {
+    let mut bytes = signature.to_bytes();
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = N[i] as i16 - bytes[32 + i] as i16 - borrow;
+        bytes[32 + i] = diff.rem_euclid(256) as u8;
+        borrow = if diff < 0 { 1 } else { 0 };
+    }
+    bytes
+}
+
+proptest! {
+    #[test]
+    fn test_sign_and_verify(
+        keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        other_keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        message in any::<usize>(),
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(message));
+        prop_assert!(signature.verify(&CryptoHashable(message), &keypair.public_key).is_ok());
+        prop_assert!(signature
+            .verify(&CryptoHashable(message.wrapping_add(1)), &keypair.public_key)
+            .is_err());
+        prop_assert!(signature.verify(&CryptoHashable(message), &other_keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_high_s_is_rejected(
+        keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        message in any::<usize>(),
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(message));
+        let high_s = negate_s(&signature);
+        prop_assert_eq!(
+            Signature::try_from(&high_s[..]),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+
+        // The high `s` signature is valid ECDSA, so it is only rejected by the malleability check
+        let high_s = Signature::from_bytes_unchecked(&high_s).unwrap();
+        prop_assert!(high_s.verify(&CryptoHashable(message), &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_serialization(
+        keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        message in any::<usize>(),
+    ) {
+        let private_key_bytes = keypair.private_key.to_bytes();
+        prop_assert_eq!(private_key_bytes.len(), PRIVATE_KEY_LENGTH);
+        prop_assert_eq!(PrivateKey::try_from(&private_key_bytes[..]).unwrap(), keypair.private_key);
+
+        let public_key_bytes = keypair.public_key.to_bytes();
+        prop_assert_eq!(public_key_bytes.len(), PUBLIC_KEY_LENGTH);
+        prop_assert_eq!(public_key_bytes[0], 0x04);
+        prop_assert_eq!(PublicKey::try_from(&public_key_bytes[..]).unwrap(), keypair.public_key);
+
+        let signature = keypair.private_key.sign(&CryptoHashable(message));
+        let bytes = signature.to_bytes();
+        prop_assert_eq!(Signature::try_from(&bytes[..]).unwrap(), signature);
+        prop_assert!(Signature::try_from(&bytes[..SIGNATURE_LENGTH - 1]).is_err());
+    }
+}
+
+#[test]
+fn test_invalid_private_key() {
+    assert!(PrivateKey::try_from(&[0u8; PRIVATE_KEY_LENGTH][..]).is_err());
+    assert!(PrivateKey::try_from(&N[..]).is_err());
+}
@@ -0,0 +1,115 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate as aptos_crypto;
+use crate::{
+    secp256r1_ecdsa::{
+        PrivateKey, PublicKey, Signature, PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
+    },
+    test_utils::uniform_keypair_strategy,
+    traits::{Signature as _, *},
+};
+
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(CryptoHasher, BCSCryptoHash, Serialize, Deserialize)]
+struct CryptoHashable(pub usize);
+
+/// The order of the P-256 group, big-endian
+const N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+/// Returns the other valid signature for the same message and key, with `s` replaced by `n - s`
+fn negate_s(signature: &Signature) -> [u8; SIGNATURE_LENGTH] {
+    let mut bytes = signature.to_bytes();
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = N[i] as i16 - bytes[32 + i] as i16 - borrow;
+        bytes[32 + i] = diff.rem_euclid(256) as u8;
+        borrow = if diff < 0 { 1 } else { 0 };
+    }
+    bytes
+}
+
+proptest! {
+    #[test]
+    fn test_sign_and_verify(
+        keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        other_keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        message in any::<usize>(),
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(message));
+        prop_assert!(signature.verify(&CryptoHashable(message), &keypair.public_key).is_ok());
+        prop_assert!(signature
+            .verify(&CryptoHashable(message.wrapping_add(1)), &keypair.public_key)
+            .is_err());
+        prop_assert!(signature.verify(&CryptoHashable(message), &other_keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_high_s_is_rejected(
+        keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        message in any::<usize>(),
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(message));
+        let high_s = negate_s(&signature);
+        prop_assert_eq!(
+            Signature::try_from(&high_s[..]),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+
+        // The high `s` signature is valid ECDSA, so it is only rejected by the malleability check
+        let high_s = Signature::from_bytes_unchecked(&high_s).unwrap();
+        prop_assert!(high_s.verify(&CryptoHashable(message), &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_keys_serialization(keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>()) {
+        let private_key_bytes = keypair.private_key.to_bytes();
+        prop_assert_eq!(private_key_bytes.len(), PRIVATE_KEY_LENGTH);
+        prop_assert_eq!(PrivateKey::try_from(&private_key_bytes[..]).unwrap(), keypair.private_key);
+
+        let public_key_bytes = keypair.public_key.to_bytes();
+        prop_assert_eq!(public_key_bytes.len(), PUBLIC_KEY_LENGTH);
+        prop_assert_eq!(public_key_bytes[0], 0x04);
+        prop_assert_eq!(PublicKey::try_from(&public_key_bytes[..]).unwrap(), keypair.public_key);
+
+        // Compressed keys are rejected
+        let mut compressed = public_key_bytes[..33].to_vec();
+        compressed[0] = 0x02 + (public_key_bytes[64] & 1);
+        prop_assert!(PublicKey::try_from(&compressed[..]).is_err());
+    }
+
+    #[test]
+    fn test_signature_serialization(
+        keypair in uniform_keypair_strategy::<PrivateKey, PublicKey>(),
+        message in any::<usize>(),
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(message));
+        let bytes = signature.to_bytes();
+        prop_assert_eq!(Signature::try_from(&bytes[..]).unwrap(), signature);
+        prop_assert!(Signature::try_from(&bytes[..SIGNATURE_LENGTH - 1]).is_err());
+    }
+}
+
+#[test]
+fn test_invalid_public_key() {
+    // Not on the curve
+    let mut bytes = [1u8; PUBLIC_KEY_LENGTH];
+    bytes[0] = 0x04;
+    assert_eq!(
+        PublicKey::try_from(&bytes[..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+}
+
+#[test]
+fn test_invalid_private_key() {
+    assert!(PrivateKey::try_from(&[0u8; PRIVATE_KEY_LENGTH][..]).is_err());
+    assert!(PrivateKey::try_from(&N[..]).is_err());
+}
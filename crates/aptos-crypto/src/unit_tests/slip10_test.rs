@@ -0,0 +1,136 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    mnemonic::{self, Mnemonic},
+    slip10::*,
+    traits::PrivateKey,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::str::FromStr;
+
+// Test vector 1 for ed25519 of SLIP-0010, as (path, chain code, private key)
+const TEST_VECTOR_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+const TEST_VECTORS: [(&str, &str, &str); 6] = [
+    (
+        "m",
+        "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+    ),
+    (
+        "m/0'",
+        "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+    ),
+    (
+        "m/0'/1'",
+        "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+        "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+    ),
+    (
+        "m/0'/1'/2'",
+        "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+        "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+    ),
+    (
+        "m/0'/1'/2'/2'",
+        "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+        "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+    ),
+    (
+        "m/0'/1'/2'/2'/1000000000'",
+        "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+        "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+    ),
+];
+
+#[test]
+fn test_slip10_test_vectors() {
+    let seed = hex::decode(TEST_VECTOR_SEED).unwrap();
+    let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+    for (path, chain_code, private_key) in TEST_VECTORS.iter() {
+        let key = master.derive(&DerivationPath::from_str(path).unwrap());
+        assert_eq!(hex::encode(key.chain_code()), *chain_code);
+        assert_eq!(hex::encode(key.private_key().to_bytes()), *private_key);
+    }
+
+    let public_key = master.private_key().public_key();
+    assert_eq!(
+        hex::encode(public_key.to_bytes()),
+        "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"
+    );
+}
+
+#[test]
+fn test_derivation_path() {
+    let path = DerivationPath::from_str("m/44'/637'/0'/0'/0'").unwrap();
+    assert_eq!(path, DerivationPath::default());
+    assert_eq!(path, DerivationPath::bip44(0, 0, 0).unwrap());
+    assert_eq!(path.to_string(), "m/44'/637'/0'/0'/0'");
+    assert_eq!(
+        DerivationPath::from_str("m/44h/637H/1'/0'/2'").unwrap(),
+        DerivationPath::bip44(1, 0, 2).unwrap()
+    );
+    assert_eq!(
+        DerivationPath::from_str("m").unwrap().indices(),
+        &[] as &[u32]
+    );
+
+    // Ed25519 only supports hardened derivation
+    assert_eq!(
+        DerivationPath::from_str("m/44'/637'/0'/0/0").unwrap_err(),
+        DerivationError::NonHardenedIndex(0)
+    );
+    assert_eq!(
+        DerivationPath::new(vec![HARDENED_OFFSET, 1]).unwrap_err(),
+        DerivationError::NonHardenedIndex(1)
+    );
+    for path in ["44'/637'", "m/", "m/x'", "m/2147483648'", "n/0'"].iter() {
+        assert!(matches!(
+            DerivationPath::from_str(path).unwrap_err(),
+            DerivationError::InvalidPath(_)
+        ));
+    }
+    assert!(DerivationPath::bip44(HARDENED_OFFSET, 0, 0).is_err());
+
+    let master = ExtendedPrivateKey::from_seed(&[0u8; 32]).unwrap();
+    assert!(master.derive_child(1).is_err());
+    assert!(ExtendedPrivateKey::from_seed(&[0u8; 15]).is_err());
+    assert!(ExtendedPrivateKey::from_seed(&[0u8; 65]).is_err());
+}
+
+#[test]
+fn test_mnemonic() {
+    // Test vector of BIP-39, with the passphrase "TREZOR"
+    let mnemonic = Mnemonic::from_phrase(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(mnemonic.to_seed("TREZOR")),
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+
+    // The checksum is verified
+    assert!(Mnemonic::from_phrase(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+    )
+    .is_err());
+
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for word_count in mnemonic::WORD_COUNTS.iter() {
+        let mnemonic = Mnemonic::generate(&mut rng, *word_count).unwrap();
+        assert_eq!(mnemonic.phrase().split(' ').count(), *word_count);
+        // Whitespace is normalized
+        let restored = Mnemonic::from_phrase(&format!(" {}\n", mnemonic.phrase())).unwrap();
+        assert_eq!(
+            restored
+                .derive_ed25519_private_key("", &DerivationPath::default())
+                .unwrap(),
+            mnemonic
+                .derive_ed25519_private_key("", &DerivationPath::default())
+                .unwrap()
+        );
+    }
+    assert!(Mnemonic::generate(&mut rng, 13).is_err());
+}
@@ -18,6 +18,9 @@
 //! ```bash
 //! cargo run -p aptos-faucet -- -h
 //! ```
+//!
+//! Public faucets should limit how much can be requested, see [`policy`].  Busy faucets should
+//! send from a pool of accounts, see [`pool`].
 
 use anyhow::Result;
 use aptos_config::keys::ConfigKey;
@@ -33,12 +36,18 @@ use aptos_sdk::{
 };
 use clap::Parser;
 use futures::lock::Mutex;
+use once_cell::sync::OnceCell;
 use reqwest::StatusCode;
 use std::{convert::Infallible, fmt, path::PathBuf, sync::Arc};
 use url::Url;
 use warp::{http, Filter, Rejection, Reply};
 
 pub mod mint;
+pub mod policy;
+pub mod pool;
+
+use policy::{FundingPolicy, PolicyArgs};
+use pool::{PoolArgs, SenderPool};
 
 /// Aptos Testnet utility service for creating test accounts and minting test coins
 #[derive(Clone, Debug, Parser)]
@@ -83,6 +92,10 @@ pub struct FaucetArgs {
     pub maximum_amount: Option<u64>,
     #[clap(long)]
     pub do_not_delegate: bool,
+    #[clap(flatten)]
+    pub policy_args: PolicyArgs,
+    #[clap(flatten)]
+    pub pool_args: PoolArgs,
 }
 
 impl FaucetArgs {
@@ -121,12 +134,17 @@ impl FaucetArgs {
             None
         };
 
-        let service = Arc::new(Service::new(
-            self.server_url.clone(),
-            self.chain_id,
-            faucet_account,
-            maximum_amount,
-        ));
+        let policy =
+            FundingPolicy::from_args(&self.policy_args).expect("Failed to set up funding policy");
+        let service = Arc::new(
+            Service::new(
+                self.server_url.clone(),
+                self.chain_id,
+                faucet_account,
+                maximum_amount,
+            )
+            .with_policy(Arc::new(policy)),
+        );
 
         let actual_service = if self.do_not_delegate {
             service
@@ -135,12 +153,24 @@ impl FaucetArgs {
                 .await
         };
 
+        if self.pool_args.num_senders > 0 {
+            let senders = SenderPool::create(&actual_service, &self.pool_args)
+                .await
+                .expect("Failed to create sender accounts");
+            actual_service.set_senders(senders);
+            tokio::spawn(pool::top_up_senders(
+                actual_service.clone(),
+                self.pool_args.clone(),
+            ));
+        }
+
         println!("Faucet is running.  Faucet endpoint: {}", address);
 
         info!(
-            "[faucet]: running on: {}. Minting from {}",
+            "[faucet]: running on: {}. Minting from {}, with {} senders",
             address,
-            actual_service.faucet_account.lock().await.address()
+            actual_service.faucet_account.lock().await.address(),
+            actual_service.senders().map_or(0, SenderPool::len),
         );
         warp::serve(routes(actual_service)).run(address).await;
     }
@@ -152,6 +182,9 @@ pub struct Service {
     client: Client,
     endpoint: Url,
     maximum_amount: Option<u64>,
+    policy: Arc<FundingPolicy>,
+    /// Accounts requests are sent from instead of the faucet account, if any
+    senders: OnceCell<SenderPool>,
 }
 
 impl Service {
@@ -170,6 +203,33 @@ impl Service {
             client,
             endpoint,
             maximum_amount,
+            policy: Arc::new(FundingPolicy::default()),
+            senders: OnceCell::new(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: Arc<FundingPolicy>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sends requests from `senders` from now on, transferring funds rather than minting them.
+    /// Panics if the service already has senders
+    pub fn set_senders(&self, senders: SenderPool) {
+        if self.senders.set(senders).is_err() {
+            panic!("Senders are already set");
+        }
+    }
+
+    pub fn senders(&self) -> Option<&SenderPool> {
+        self.senders.get()
+    }
+
+    /// The account to send the next request from
+    fn sender(&self) -> &Mutex<LocalAccount> {
+        match self.senders.get() {
+            Some(senders) => senders.next(),
+            None => &self.faucet_account,
         }
     }
 
@@ -190,10 +250,12 @@ pub fn routes(
     service: Arc<Service>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     let mint = mint::mint_routes(service.clone());
+    let admin = policy::admin_routes(service.clone());
     let health = health_route(service);
 
     health
         .or(mint)
+        .or(admin)
         .with(warp::log::custom(|info| {
             info!(
                 "{} \"{} {} {:?}\" {} \"{}\" \"{}\" {:?}",
@@ -210,7 +272,11 @@ pub fn routes(
         .with(
             warp::cors()
                 .allow_any_origin()
-                .allow_headers(vec![http::header::CONTENT_TYPE])
+                .allow_headers(vec![
+                    http::header::CONTENT_TYPE,
+                    http::header::AUTHORIZATION,
+                    http::header::HeaderName::from_static(policy::CAPTCHA_HEADER),
+                ])
                 .allow_methods(vec!["POST"]),
         )
 }
@@ -325,10 +391,8 @@ pub async fn delegate_mint_account(
         .await
         .unwrap();
 
-    Arc::new(Service::new(
-        server_url,
-        chain_id,
-        delegated_account,
-        maximum_amount,
-    ))
+    Arc::new(
+        Service::new(server_url, chain_id, delegated_account, maximum_amount)
+            .with_policy(service.policy.clone()),
+    )
 }
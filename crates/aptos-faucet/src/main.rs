@@ -14,7 +14,11 @@ async fn main() {
 #[cfg(test)]
 mod tests {
     use aptos_crypto::{ed25519::Ed25519PublicKey, hash::HashValue};
-    use aptos_faucet::{routes, Service};
+    use aptos_faucet::{
+        policy::{FundingLimits, FundingPolicy, Limit, MemoryUsageStore},
+        pool::SenderPool,
+        routes, Service,
+    };
     use aptos_infallible::RwLock;
     use aptos_keygen::KeyGen;
     use aptos_rest_client::{
@@ -72,6 +76,13 @@ mod tests {
     }
 
     fn setup(maximum_amount: Option<u64>) -> (AccountStates, Arc<Service>) {
+        setup_with_policy(maximum_amount, FundingPolicy::default())
+    }
+
+    fn setup_with_policy(
+        maximum_amount: Option<u64>,
+        policy: FundingPolicy,
+    ) -> (AccountStates, Arc<Service>) {
         let mut keygen = KeyGen::from_seed([0; 32]);
         let (private_key, public_key) = keygen.generate_ed25519_keypair();
         let account_address = AuthenticationKey::ed25519(&public_key).derived_address();
@@ -118,6 +129,7 @@ mod tests {
             faucet_account,
             maximum_amount,
         )
+        .with_policy(Arc::new(policy))
         .configure_for_testing();
         (accounts, Arc::new(service))
     }
@@ -162,6 +174,8 @@ mod tests {
                     vm_status: "Executed".to_string(),
                     accumulator_root_hash: HashValue::zero().into(),
                     changes: vec![],
+                    proof: None,
+                    auxiliary_data: None,
                 };
                 let serializable_txn: aptos_rest_client::aptos_api_types::Transaction = (
                     txn.as_signed_user_txn().unwrap(),
@@ -210,6 +224,20 @@ mod tests {
                         .expect("account should be created");
                     account.balance += amount;
                 }
+                EntryFunctionCall::AccountTransfer { to, amount } => {
+                    if accounts.write().get_mut(&to).is_none() {
+                        yield_now().await;
+                    }
+                    let mut writer = accounts.write();
+                    writer
+                        .get_mut(&txn.sender())
+                        .expect("sender should exist")
+                        .balance -= amount;
+                    writer
+                        .get_mut(&to)
+                        .expect("account should be created")
+                        .balance += amount;
+                }
                 script => panic!("unexpected type of entry function: {:?}", script),
             }
         }
@@ -473,6 +501,159 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_mint_from_senders() {
+        let (accounts, service) = setup(None);
+        let senders: Vec<_> = (0..2)
+            .map(|_| LocalAccount::generate(&mut rand::rngs::OsRng))
+            .collect();
+        for sender in &senders {
+            let mut state = AccountState::new(1000);
+            state.authentication_key = sender.authentication_key();
+            accounts.write().insert(sender.address(), state);
+        }
+        let sender_addresses: Vec<_> = senders.iter().map(LocalAccount::address).collect();
+        service.set_senders(SenderPool::new(senders));
+        let filter = routes(service);
+
+        let receivers = [
+            "459c77a38803bd53f3adee52703810e3a74fd7c46952c497e75afb0a7932586d",
+            "9ff98e82355eb13098f3b1157ac018a725c62c0e0820f422000814cdba407835",
+        ];
+        for receiver in receivers {
+            let resp = warp::test::request()
+                .method("POST")
+                .path(format!("/mint?address={}&amount=100", receiver).as_str())
+                .reply(&filter)
+                .await;
+            assert_eq!(resp.status(), 200);
+        }
+
+        // Each request is sent from the next sender, and transferred from its funds
+        let reader = accounts.read();
+        for receiver in receivers {
+            let addr = AccountAddress::from_hex(receiver).unwrap();
+            assert_eq!(reader.get(&addr).unwrap().balance, 100);
+        }
+        for sender in sender_addresses {
+            assert_eq!(reader.get(&sender).unwrap().balance, 900);
+        }
+    }
+
+    fn limited_policy(per_account: Option<Limit>) -> FundingPolicy {
+        FundingPolicy::new(
+            FundingLimits {
+                period_secs: 3600,
+                per_ip: None,
+                per_account,
+            },
+            Box::new(MemoryUsageStore::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_mint_account_limit() {
+        let policy = limited_policy(Some(Limit {
+            max_requests: 2,
+            max_amount: 1000,
+        }));
+        let (_accounts, service) = setup_with_policy(None, policy);
+        let filter = routes(service);
+
+        let mint = |amount: u64| {
+            warp::test::request().method("POST").path(
+                format!(
+                    "/mint?address=459c77a38803bd53f3adee52703810e3a74fd7c46952c497e75afb0a7932586d&amount={}",
+                    amount
+                )
+                .as_str(),
+            )
+        };
+        assert_eq!(mint(600).reply(&filter).await.status(), 200);
+        // Over the amount limit
+        assert_eq!(mint(600).reply(&filter).await.status(), 429);
+        assert_eq!(mint(400).reply(&filter).await.status(), 200);
+        // Over the request limit
+        assert_eq!(mint(0).reply(&filter).await.status(), 429);
+    }
+
+    #[tokio::test]
+    async fn test_mint_bearer_token() {
+        let policy = FundingPolicy::default()
+            .with_bearer_tokens(vec!["token".to_string()].into_iter().collect());
+        let (_accounts, service) = setup_with_policy(None, policy);
+        let filter = routes(service);
+
+        let path = "/mint?address=459c77a38803bd53f3adee52703810e3a74fd7c46952c497e75afb0a7932586d&amount=10";
+        let resp = warp::test::request()
+            .method("POST")
+            .path(path)
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 401);
+        let resp = warp::test::request()
+            .method("POST")
+            .path(path)
+            .header("Authorization", "Bearer other")
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 401);
+        let resp = warp::test::request()
+            .method("POST")
+            .path(path)
+            .header("Authorization", "Bearer token")
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_admin_limits() {
+        let policy = limited_policy(None).with_admin_token("admin".to_string());
+        let (_accounts, service) = setup_with_policy(None, policy);
+        let filter = routes(service);
+
+        let resp = warp::test::request()
+            .method("GET")
+            .path("/admin/limits")
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 401);
+
+        let limits = FundingLimits {
+            period_secs: 60,
+            per_ip: None,
+            per_account: Some(Limit {
+                max_requests: 1,
+                max_amount: u64::MAX,
+            }),
+        };
+        let resp = warp::test::request()
+            .method("PUT")
+            .path("/admin/limits")
+            .header("Authorization", "Bearer admin")
+            .json(&limits)
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resp = warp::test::request()
+            .method("GET")
+            .path("/admin/limits")
+            .header("Authorization", "Bearer admin")
+            .reply(&filter)
+            .await;
+        let current: FundingLimits = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(current, limits);
+
+        let mint = || {
+            warp::test::request()
+                .method("POST")
+                .path("/mint?address=459c77a38803bd53f3adee52703810e3a74fd7c46952c497e75afb0a7932586d&amount=10")
+        };
+        assert_eq!(mint().reply(&filter).await.status(), 200);
+        assert_eq!(mint().reply(&filter).await.status(), 429);
+    }
+
     #[tokio::test]
     async fn create_account_with_client() {
         let (faucet_client, _service) = get_client().await;
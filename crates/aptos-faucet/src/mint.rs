@@ -10,12 +10,14 @@ use aptos_sdk::{
     types::{
         account_address::AccountAddress,
         transaction::{authenticator::AuthenticationKey, SignedTransaction},
+        LocalAccount,
     },
 };
+use futures::lock::Mutex;
 use reqwest::StatusCode;
 use serde::Deserialize;
-use std::{convert::Infallible, fmt, sync::Arc};
-use warp::{Filter, Rejection, Reply};
+use std::{convert::Infallible, fmt, net::SocketAddr, sync::Arc};
+use warp::{http::HeaderMap, Filter, Rejection, Reply};
 
 pub fn mint_routes(
     service: Arc<Service>,
@@ -27,19 +29,49 @@ pub fn mint_routes(
         .and(warp::post())
         .and(warp::any().map(move || service.clone()))
         .and(warp::query().map(move |params: MintParams| params))
-        .and_then(|_, service, params| handle(service, params))
+        .and(warp::header::headers_cloned())
+        .and(warp::addr::remote())
+        .and_then(|_, service, params, headers, remote| handle(service, params, headers, remote))
 }
 
 async fn handle(
     service: Arc<Service>,
     params: MintParams,
+    headers: HeaderMap,
+    remote: Option<SocketAddr>,
 ) -> Result<Box<dyn warp::Reply>, Infallible> {
+    let admission = match params.receiver() {
+        Some(receiver) => {
+            let amount = params.amount(&service);
+            match service
+                .policy
+                .admit(&headers, remote, receiver, amount)
+                .await
+            {
+                Ok(admission) => Some(admission),
+                Err(err) => {
+                    return Ok(Box::new(warp::reply::with_status(
+                        err.to_string(),
+                        err.status_code(),
+                    )))
+                }
+            }
+        }
+        // Requests without a receiver are rejected by `process`
+        None => None,
+    };
+
     match process(&service, params).await {
         Ok(body) => Ok(Box::new(body.to_string())),
-        Err(err) => Ok(Box::new(warp::reply::with_status(
-            err.to_string(),
-            StatusCode::INTERNAL_SERVER_ERROR,
-        ))),
+        Err(err) => {
+            if let Some(admission) = admission {
+                service.policy.release(admission).await;
+            }
+            Ok(Box::new(warp::reply::with_status(
+                err.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )))
+        }
     }
 }
 
@@ -78,6 +110,11 @@ impl std::fmt::Display for MintParams {
 }
 
 impl MintParams {
+    /// The amount to mint, capped to the maximum of the service
+    fn amount(&self, service: &Service) -> u64 {
+        std::cmp::min(self.amount, service.maximum_amount.unwrap_or(self.amount))
+    }
+
     fn receiver(&self) -> Option<AccountAddress> {
         if let Some(auth_key) = self.auth_key.as_ref() {
             return match AccountAddress::from_hex_literal(auth_key) {
@@ -105,16 +142,16 @@ impl MintParams {
 }
 
 pub async fn process(service: &Service, params: MintParams) -> Result<Response> {
-    let maybe_maximum_amount = service.maximum_amount.unwrap_or(params.amount);
-    let amount = std::cmp::min(params.amount, maybe_maximum_amount);
+    let amount = params.amount(service);
 
     let receiver_address = params.receiver().ok_or_else(|| {
         anyhow::format_err!("You must provide 'address' (preferred), 'pub_key', or 'auth_key'")
     })?;
 
-    let (mut faucet_seq, mut receiver_seq) = sequences(service, receiver_address).await?;
+    let sender = service.sender();
+    let (mut faucet_seq, mut receiver_seq) = sequences(service, sender, receiver_address).await?;
     let our_faucet_seq = {
-        let mut faucet_account = service.faucet_account.lock().await;
+        let mut faucet_account = sender.lock().await;
 
         // If the onchain sequence_number is greater than what we have, update our
         // sequence_numbers
@@ -135,7 +172,7 @@ pub async fn process(service: &Service, params: MintParams) -> Result<Response>
         );
 
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        let (lhs, rhs) = sequences(service, receiver_address).await?;
+        let (lhs, rhs) = sequences(service, sender, receiver_address).await?;
         faucet_seq = lhs;
         receiver_seq = rhs;
     }
@@ -143,7 +180,7 @@ pub async fn process(service: &Service, params: MintParams) -> Result<Response>
     // After 30 seconds, we still have not caught up, we are likely unhealthy
     if our_faucet_seq >= faucet_seq + 50 {
         error!("We are unhealthy, transactions have likely expired.");
-        let mut faucet_account = service.faucet_account.lock().await;
+        let mut faucet_account = sender.lock().await;
         if faucet_account.sequence_number() >= faucet_seq + 50 {
             info!("Resetting the sequence number counter.");
             *faucet_account.sequence_number_mut() = faucet_seq;
@@ -155,7 +192,7 @@ pub async fn process(service: &Service, params: MintParams) -> Result<Response>
     let mut txns = vec![];
 
     {
-        let mut faucet_account = service.faucet_account.lock().await;
+        let mut faucet_account = sender.lock().await;
 
         if receiver_seq.is_none() {
             let builder = service
@@ -167,12 +204,15 @@ pub async fn process(service: &Service, params: MintParams) -> Result<Response>
         }
 
         if amount != 0 {
+            // Senders of a pool hold funds to transfer, only the faucet account can mint
+            let payload = if service.senders().is_some() {
+                aptos_stdlib::account_transfer(receiver_address, amount)
+            } else {
+                aptos_stdlib::aptos_coin_mint(receiver_address, amount)
+            };
             txns.push(
-                faucet_account.sign_with_transaction_builder(
-                    service
-                        .transaction_factory
-                        .payload(aptos_stdlib::aptos_coin_mint(receiver_address, amount)),
-                ),
+                faucet_account
+                    .sign_with_transaction_builder(service.transaction_factory.payload(payload)),
             );
         }
     }
@@ -183,7 +223,7 @@ pub async fn process(service: &Service, params: MintParams) -> Result<Response>
     // If there was an issue submitting a transaction we should just reset our sequence_numbers
     // to what was on chain
     if responses.iter().any(Result::is_err) {
-        *service.faucet_account.lock().await.sequence_number_mut() = faucet_seq;
+        *sender.lock().await.sequence_number_mut() = faucet_seq;
     }
 
     while !responses.is_empty() {
@@ -202,8 +242,12 @@ pub async fn process(service: &Service, params: MintParams) -> Result<Response>
     }
 }
 
-async fn sequences(service: &Service, receiver: AccountAddress) -> Result<(u64, Option<u64>)> {
-    let faucet_address = service.faucet_account.lock().await.address();
+async fn sequences(
+    service: &Service,
+    sender: &Mutex<LocalAccount>,
+    receiver: AccountAddress,
+) -> Result<(u64, Option<u64>)> {
+    let faucet_address = sender.lock().await.address();
     let f_request = service.client.get_account(faucet_address);
     let r_request = service.client.get_account(receiver);
     let mut responses = futures::future::join_all([f_request, r_request]).await;
@@ -0,0 +1,567 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Funding policies, to keep bots from draining public faucets.
+//!
+//! Requests may be required to carry a bearer token, or to solve a captcha, and are limited in
+//! number and amount per period, both per client IP and per receiving account.  Usage is counted
+//! in memory, or in Redis so that it's shared by every replica of the faucet, which requires the
+//! `redis-store` feature.  The limits can be read and changed at runtime through the admin
+//! endpoint, `/admin/limits`.
+
+use crate::Service;
+use anyhow::{anyhow, Result};
+use aptos_logger::{info, warn};
+use aptos_sdk::types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use warp::{
+    http::{header::AUTHORIZATION, HeaderMap},
+    Filter, Rejection, Reply,
+};
+
+/// Header carrying the response to the captcha
+pub const CAPTCHA_HEADER: &str = "x-captcha-token";
+
+#[derive(Clone, Debug, Parser)]
+pub struct PolicyArgs {
+    /// Length in seconds of the period the limits apply to
+    #[clap(long, default_value_t = 86_400)]
+    pub limit_period_secs: u64,
+    /// Maximum number of requests from an IP per period
+    #[clap(long)]
+    pub ip_max_requests: Option<u64>,
+    /// Maximum amount requested from an IP per period
+    #[clap(long)]
+    pub ip_max_amount: Option<u64>,
+    /// Maximum number of requests to fund an account per period
+    #[clap(long)]
+    pub account_max_requests: Option<u64>,
+    /// Maximum amount funded to an account per period
+    #[clap(long)]
+    pub account_max_amount: Option<u64>,
+    /// Header holding the client IP, e.g. `X-Forwarded-For` behind a load balancer.
+    /// If not set, the address of the connection is used
+    #[clap(long)]
+    pub ip_header: Option<String>,
+    /// Redis URL to keep usage in, e.g. `redis://127.0.0.1/`, so it's shared between faucets.
+    /// If not set, usage is kept in memory.  Requires the `redis-store` feature
+    #[clap(long)]
+    pub redis_url: Option<String>,
+    /// File with one bearer token per line.  If set, requests must present one of them in the
+    /// `Authorization` header, or solve the captcha if there's one
+    #[clap(long, parse(from_os_str))]
+    pub bearer_tokens_file: Option<PathBuf>,
+    /// Secret to verify captcha responses with.  If set, requests must solve the captcha,
+    /// unless they present a bearer token
+    #[clap(long)]
+    pub captcha_secret: Option<String>,
+    /// Endpoint to verify captcha responses at, hCaptcha's by default.
+    /// reCAPTCHA's is `https://www.google.com/recaptcha/api/siteverify`
+    #[clap(long, default_value = "https://hcaptcha.com/siteverify")]
+    pub captcha_verify_url: String,
+    /// Token for the admin endpoint.  If not set, the admin endpoint is disabled
+    #[clap(long)]
+    pub admin_token: Option<String>,
+}
+
+/// How much may be requested within a period
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Limit {
+    pub max_requests: u64,
+    pub max_amount: u64,
+}
+
+impl Limit {
+    fn from_args(max_requests: Option<u64>, max_amount: Option<u64>) -> Option<Self> {
+        if max_requests.is_none() && max_amount.is_none() {
+            return None;
+        }
+        Some(Limit {
+            max_requests: max_requests.unwrap_or(u64::MAX),
+            max_amount: max_amount.unwrap_or(u64::MAX),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct FundingLimits {
+    pub period_secs: u64,
+    pub per_ip: Option<Limit>,
+    pub per_account: Option<Limit>,
+}
+
+impl Default for FundingLimits {
+    fn default() -> Self {
+        Self {
+            period_secs: 86_400,
+            per_ip: None,
+            per_account: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PolicyError {
+    /// No valid bearer token or captcha response was presented
+    Unauthorized(String),
+    /// A limit would be exceeded
+    RateLimited(String),
+    Internal(anyhow::Error),
+}
+
+impl PolicyError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            PolicyError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            PolicyError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            PolicyError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyError::Unauthorized(message) | PolicyError::RateLimited(message) => {
+                f.write_str(message)
+            }
+            PolicyError::Internal(err) => write!(f, "{:#}", err),
+        }
+    }
+}
+
+impl From<anyhow::Error> for PolicyError {
+    fn from(err: anyhow::Error) -> Self {
+        PolicyError::Internal(err)
+    }
+}
+
+/// Counts the requests and amounts within each period
+#[async_trait]
+pub trait UsageStore: Send + Sync {
+    /// Adds a request for `amount` to the usage of `key` in `period`, unless that would exceed
+    /// `limit`.  Returns whether it was added.
+    async fn try_consume(&self, key: &str, period: u64, amount: u64, limit: &Limit)
+        -> Result<bool>;
+
+    /// Removes a request for `amount` added by `try_consume`
+    async fn release(&self, key: &str, period: u64, amount: u64) -> Result<()>;
+}
+
+#[derive(Default)]
+struct Usage {
+    requests: u64,
+    amount: u64,
+}
+
+/// Keeps usage in memory, only for the current period
+#[derive(Default)]
+pub struct MemoryUsageStore {
+    usage: Mutex<HashMap<String, (u64, Usage)>>,
+}
+
+#[async_trait]
+impl UsageStore for MemoryUsageStore {
+    async fn try_consume(
+        &self,
+        key: &str,
+        period: u64,
+        amount: u64,
+        limit: &Limit,
+    ) -> Result<bool> {
+        let mut usage = self.usage.lock().unwrap();
+        // Usage from earlier periods is of no use anymore
+        usage.retain(|_, (usage_period, _)| *usage_period >= period);
+        let (_, usage) = usage
+            .entry(key.to_string())
+            .or_insert_with(|| (period, Usage::default()));
+        if usage.requests >= limit.max_requests
+            || usage.amount.saturating_add(amount) > limit.max_amount
+        {
+            return Ok(false);
+        }
+        usage.requests += 1;
+        usage.amount += amount;
+        Ok(true)
+    }
+
+    async fn release(&self, key: &str, period: u64, amount: u64) -> Result<()> {
+        if let Some((usage_period, usage)) = self.usage.lock().unwrap().get_mut(key) {
+            if *usage_period == period {
+                usage.requests = usage.requests.saturating_sub(1);
+                usage.amount = usage.amount.saturating_sub(amount);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adds to the usage and checks it against the limit at once, so that concurrent requests to
+/// different faucets can't both get through.  Redis integers are signed 64 bit, which the limits
+/// are capped to.
+#[cfg(feature = "redis-store")]
+const CONSUME_SCRIPT: &str = r#"
+local requests = redis.call('HINCRBY', KEYS[1], 'requests', 1)
+local amount = redis.call('HINCRBY', KEYS[1], 'amount', ARGV[1])
+redis.call('EXPIRE', KEYS[1], ARGV[4])
+if requests > tonumber(ARGV[2]) or amount > tonumber(ARGV[3]) then
+    redis.call('HINCRBY', KEYS[1], 'requests', -1)
+    redis.call('HINCRBY', KEYS[1], 'amount', -tonumber(ARGV[1]))
+    return 0
+end
+return 1
+"#;
+
+/// Keeps usage in Redis, in a hash per key and period, which expires after the period
+#[cfg(feature = "redis-store")]
+pub struct RedisUsageStore {
+    client: redis::Client,
+    period_secs: u64,
+}
+
+#[cfg(feature = "redis-store")]
+impl RedisUsageStore {
+    pub fn new(url: &str, period_secs: u64) -> Result<Self> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            period_secs,
+        })
+    }
+
+    async fn connection(&self) -> Result<redis::aio::Connection> {
+        Ok(self.client.get_async_connection().await?)
+    }
+}
+
+#[cfg(feature = "redis-store")]
+#[async_trait]
+impl UsageStore for RedisUsageStore {
+    async fn try_consume(
+        &self,
+        key: &str,
+        period: u64,
+        amount: u64,
+        limit: &Limit,
+    ) -> Result<bool> {
+        let cap = |value: u64| value.min(i64::MAX as u64);
+        let consumed: i64 = redis::Script::new(CONSUME_SCRIPT)
+            .key(format!("faucet:{}:{}", key, period))
+            .arg(cap(amount))
+            .arg(cap(limit.max_requests))
+            .arg(cap(limit.max_amount))
+            .arg(self.period_secs)
+            .invoke_async(&mut self.connection().await?)
+            .await?;
+        Ok(consumed == 1)
+    }
+
+    async fn release(&self, key: &str, period: u64, amount: u64) -> Result<()> {
+        let key = format!("faucet:{}:{}", key, period);
+        redis::pipe()
+            .hincr(&key, "requests", -1)
+            .hincr(&key, "amount", -(amount.min(i64::MAX as u64) as i64))
+            .query_async::<_, ()>(&mut self.connection().await?)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct CaptchaVerification {
+    success: bool,
+}
+
+/// Usage added for a request, to give back if the request fails
+pub struct Admission {
+    period: u64,
+    consumed: Vec<(String, u64)>,
+}
+
+pub struct FundingPolicy {
+    limits: RwLock<FundingLimits>,
+    store: Box<dyn UsageStore>,
+    ip_header: Option<String>,
+    bearer_tokens: HashSet<String>,
+    captcha_secret: Option<String>,
+    captcha_verify_url: String,
+    admin_token: Option<String>,
+    http: reqwest::Client,
+}
+
+impl Default for FundingPolicy {
+    /// No limits, and no tokens or captcha required
+    fn default() -> Self {
+        Self::new(
+            FundingLimits::default(),
+            Box::new(MemoryUsageStore::default()),
+        )
+    }
+}
+
+impl FundingPolicy {
+    pub fn new(limits: FundingLimits, store: Box<dyn UsageStore>) -> Self {
+        Self {
+            limits: RwLock::new(limits),
+            store,
+            ip_header: None,
+            bearer_tokens: HashSet::new(),
+            captcha_secret: None,
+            captcha_verify_url: String::new(),
+            admin_token: None,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn from_args(args: &PolicyArgs) -> Result<Self> {
+        let limits = FundingLimits {
+            period_secs: args.limit_period_secs,
+            per_ip: Limit::from_args(args.ip_max_requests, args.ip_max_amount),
+            per_account: Limit::from_args(args.account_max_requests, args.account_max_amount),
+        };
+        let store: Box<dyn UsageStore> = match &args.redis_url {
+            #[cfg(feature = "redis-store")]
+            Some(url) => Box::new(RedisUsageStore::new(url, args.limit_period_secs)?),
+            #[cfg(not(feature = "redis-store"))]
+            Some(_) => {
+                return Err(anyhow!(
+                    "--redis-url requires the faucet to be built with the `redis-store` feature"
+                ))
+            }
+            None => Box::new(MemoryUsageStore::default()),
+        };
+        let mut policy = Self::new(limits, store);
+        policy.ip_header = args.ip_header.clone();
+        if let Some(path) = &args.bearer_tokens_file {
+            let tokens = std::fs::read_to_string(path)
+                .map_err(|err| anyhow!("Failed to read {}: {}", path.display(), err))?;
+            policy.bearer_tokens = tokens
+                .lines()
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        policy.captcha_secret = args.captcha_secret.clone();
+        policy.captcha_verify_url = args.captcha_verify_url.clone();
+        policy.admin_token = args.admin_token.clone();
+        Ok(policy)
+    }
+
+    pub fn with_bearer_tokens(mut self, tokens: HashSet<String>) -> Self {
+        self.bearer_tokens = tokens;
+        self
+    }
+
+    pub fn with_admin_token(mut self, token: String) -> Self {
+        self.admin_token = Some(token);
+        self
+    }
+
+    pub fn limits(&self) -> FundingLimits {
+        self.limits.read().unwrap().clone()
+    }
+
+    pub fn set_limits(&self, limits: FundingLimits) {
+        *self.limits.write().unwrap() = limits;
+    }
+
+    /// Whether `headers` carry the admin token.  Always false if there's no admin token.
+    pub fn is_admin(&self, headers: &HeaderMap) -> bool {
+        match (&self.admin_token, bearer_token(headers)) {
+            (Some(admin_token), Some(token)) => admin_token == token,
+            _ => false,
+        }
+    }
+
+    /// Checks that a request to fund `receiver` with `amount` is authorized, and within the
+    /// limits, and adds it to the usage
+    pub async fn admit(
+        &self,
+        headers: &HeaderMap,
+        remote: Option<SocketAddr>,
+        receiver: AccountAddress,
+        amount: u64,
+    ) -> Result<Admission, PolicyError> {
+        let ip = self.client_ip(headers, remote);
+        self.authorize(headers, ip.as_deref()).await?;
+
+        let limits = self.limits();
+        let period = now_secs() / limits.period_secs.max(1);
+        let mut checks = vec![];
+        if let Some(limit) = limits.per_account {
+            checks.push((format!("account:{}", receiver), limit));
+        }
+        if let (Some(limit), Some(ip)) = (limits.per_ip, &ip) {
+            checks.push((format!("ip:{}", ip), limit));
+        }
+
+        let mut admission = Admission {
+            period,
+            consumed: vec![],
+        };
+        for (key, limit) in checks {
+            match self.store.try_consume(&key, period, amount, &limit).await {
+                Ok(true) => admission.consumed.push((key, amount)),
+                result => {
+                    self.release(admission).await;
+                    return Err(match result {
+                        Err(err) => PolicyError::Internal(err),
+                        _ => PolicyError::RateLimited(format!(
+                            "Limit of {} requests and {} coins per {} seconds exceeded for {}",
+                            limit.max_requests, limit.max_amount, limits.period_secs, key
+                        )),
+                    });
+                }
+            }
+        }
+        Ok(admission)
+    }
+
+    /// Gives back the usage added for a request which failed
+    pub async fn release(&self, admission: Admission) {
+        for (key, amount) in admission.consumed {
+            if let Err(err) = self.store.release(&key, admission.period, amount).await {
+                warn!("Failed to release usage of {}: {:#}", key, err);
+            }
+        }
+    }
+
+    async fn authorize(&self, headers: &HeaderMap, ip: Option<&str>) -> Result<(), PolicyError> {
+        if self.bearer_tokens.is_empty() && self.captcha_secret.is_none() {
+            return Ok(());
+        }
+        if let Some(token) = bearer_token(headers) {
+            if self.bearer_tokens.contains(token) {
+                return Ok(());
+            }
+        }
+        let secret = match &self.captcha_secret {
+            Some(secret) => secret,
+            None => {
+                return Err(PolicyError::Unauthorized(
+                    "A valid bearer token is required".to_string(),
+                ))
+            }
+        };
+        let response = headers
+            .get(CAPTCHA_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| {
+                PolicyError::Unauthorized(format!(
+                    "A captcha response is required in the {} header",
+                    CAPTCHA_HEADER
+                ))
+            })?;
+
+        let mut form = vec![("secret", secret.as_str()), ("response", response)];
+        if let Some(ip) = ip {
+            form.push(("remoteip", ip));
+        }
+        let body = self
+            .http
+            .post(&self.captcha_verify_url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|err| anyhow!("Failed to verify captcha: {}", err))?
+            .text()
+            .await
+            .map_err(|err| anyhow!("Failed to verify captcha: {}", err))?;
+        let verification: CaptchaVerification = serde_json::from_str(&body)
+            .map_err(|err| anyhow!("Invalid captcha verification: {}", err))?;
+        if verification.success {
+            Ok(())
+        } else {
+            Err(PolicyError::Unauthorized(
+                "Invalid captcha response".to_string(),
+            ))
+        }
+    }
+
+    fn client_ip(&self, headers: &HeaderMap, remote: Option<SocketAddr>) -> Option<String> {
+        match &self.ip_header {
+            // Proxies append to the header, so the first address is the client's
+            Some(header) => headers
+                .get(header.as_str())
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(',').next())
+                .map(|ip| ip.trim().to_string()),
+            None => remote.map(|remote| remote.ip().to_string()),
+        }
+    }
+}
+
+pub fn admin_routes(
+    service: Arc<Service>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    // GET /admin/limits
+    // PUT /admin/limits, with the new limits as JSON
+    let get_service = service.clone();
+    let get = warp::path!("admin" / "limits")
+        .and(warp::get())
+        .and(warp::header::headers_cloned())
+        .and(warp::any().map(move || get_service.clone()))
+        .and_then(handle_get_limits);
+    let put = warp::path!("admin" / "limits")
+        .and(warp::put())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::json())
+        .and(warp::any().map(move || service.clone()))
+        .and_then(handle_set_limits);
+    get.or(put)
+}
+
+async fn handle_get_limits(
+    headers: HeaderMap,
+    service: Arc<Service>,
+) -> Result<Box<dyn warp::Reply>, Infallible> {
+    if !service.policy.is_admin(&headers) {
+        return Ok(unauthorized());
+    }
+    Ok(Box::new(warp::reply::json(&service.policy.limits())))
+}
+
+async fn handle_set_limits(
+    headers: HeaderMap,
+    limits: FundingLimits,
+    service: Arc<Service>,
+) -> Result<Box<dyn warp::Reply>, Infallible> {
+    if !service.policy.is_admin(&headers) {
+        return Ok(unauthorized());
+    }
+    info!("[faucet]: funding limits changed to {:?}", limits);
+    service.policy.set_limits(limits);
+    Ok(Box::new(warp::reply::json(&service.policy.limits())))
+}
+
+fn unauthorized() -> Box<dyn warp::Reply> {
+    Box::new(warp::reply::with_status(
+        "A valid admin token is required".to_string(),
+        StatusCode::UNAUTHORIZED,
+    ))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
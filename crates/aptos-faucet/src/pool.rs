@@ -0,0 +1,163 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sending funds from a pool of accounts, rather than from the faucet account alone.
+//!
+//! Transactions from one account are committed in order of sequence number, so a faucet sending
+//! from a single account is limited to a few transactions per second.  With a pool, requests are
+//! spread over several pre-funded sender accounts in turn, which transfer funds rather than mint
+//! them.  The faucet account becomes the treasury: it creates and funds the senders at startup,
+//! and tops up those which run low.
+
+use crate::Service;
+use anyhow::{Context, Result};
+use aptos_logger::{error, info};
+use aptos_sdk::{
+    transaction_builder::aptos_stdlib,
+    types::{account_address::AccountAddress, transaction::TransactionPayload, LocalAccount},
+};
+use clap::Parser;
+use futures::lock::Mutex;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[derive(Clone, Debug, Parser)]
+pub struct PoolArgs {
+    /// Number of sender accounts to rotate requests between.  If 0, requests are minted by the
+    /// faucet account directly
+    #[clap(long, default_value_t = 0)]
+    pub num_senders: usize,
+    /// Amount the treasury funds each sender with, initially and on each top-up
+    #[clap(long, default_value_t = 100_000_000_000)]
+    pub sender_funding_amount: u64,
+    /// Balance under which a sender is topped up
+    #[clap(long, default_value_t = 10_000_000_000)]
+    pub sender_min_balance: u64,
+    /// Interval in seconds between checks of the senders' balances
+    #[clap(long, default_value_t = 10)]
+    pub top_up_interval_secs: u64,
+}
+
+pub struct SenderPool {
+    senders: Vec<Mutex<LocalAccount>>,
+    /// Index of the sender to use next, modulo the number of senders
+    next: AtomicUsize,
+}
+
+impl SenderPool {
+    pub fn new(senders: Vec<LocalAccount>) -> Self {
+        assert!(!senders.is_empty(), "At least one sender is required");
+        Self {
+            senders: senders.into_iter().map(Mutex::new).collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Generates `args.num_senders` accounts, and creates and funds them from the treasury
+    pub async fn create(treasury: &Service, args: &PoolArgs) -> Result<Self> {
+        let senders: Vec<_> = (0..args.num_senders)
+            .map(|_| LocalAccount::generate(&mut rand::rngs::OsRng))
+            .collect();
+        for sender in &senders {
+            fund(
+                treasury,
+                aptos_stdlib::account_create_account(sender.address()),
+            )
+            .await?;
+            fund(
+                treasury,
+                aptos_stdlib::aptos_coin_mint(sender.address(), args.sender_funding_amount),
+            )
+            .await?;
+            info!("[faucet]: created sender {}", sender.address());
+        }
+        Ok(Self::new(senders))
+    }
+
+    /// The sender to send the next request from
+    pub fn next(&self) -> &Mutex<LocalAccount> {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.senders.len();
+        &self.senders[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.senders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    pub async fn addresses(&self) -> Vec<AccountAddress> {
+        let mut addresses = Vec::with_capacity(self.senders.len());
+        for sender in &self.senders {
+            addresses.push(sender.lock().await.address());
+        }
+        addresses
+    }
+}
+
+/// Tops up the senders of `service` from its treasury, whenever they're under the minimum balance
+pub async fn top_up_senders(service: Arc<Service>, args: PoolArgs) {
+    let senders = match service.senders() {
+        Some(senders) => senders.addresses().await,
+        None => return,
+    };
+    let mut interval = tokio::time::interval(Duration::from_secs(args.top_up_interval_secs));
+    loop {
+        interval.tick().await;
+        for &sender in &senders {
+            if let Err(err) = top_up(&service, sender, &args).await {
+                error!("[faucet]: failed to top up sender {}: {:#}", sender, err);
+            }
+        }
+    }
+}
+
+async fn top_up(service: &Service, sender: AccountAddress, args: &PoolArgs) -> Result<()> {
+    let balance = service
+        .client
+        .get_account_balance(sender)
+        .await?
+        .into_inner()
+        .get();
+    if balance >= args.sender_min_balance {
+        return Ok(());
+    }
+    info!(
+        "[faucet]: topping up sender {}, balance {}",
+        sender, balance
+    );
+    fund(
+        service,
+        aptos_stdlib::aptos_coin_mint(sender, args.sender_funding_amount),
+    )
+    .await
+}
+
+/// Sends `payload` from the treasury, the faucet account of `service`, and waits for it
+async fn fund(service: &Service, payload: TransactionPayload) -> Result<()> {
+    let mut treasury = service.faucet_account.lock().await;
+    let sequence_number = service
+        .client
+        .get_account(treasury.address())
+        .await
+        .with_context(|| format!("treasury account {} not found", treasury.address()))?
+        .into_inner()
+        .sequence_number;
+    if sequence_number > treasury.sequence_number() {
+        *treasury.sequence_number_mut() = sequence_number;
+    }
+    let txn = treasury.sign_with_transaction_builder(service.transaction_factory.payload(payload));
+    if let Err(err) = service.client.submit_and_wait(&txn).await {
+        // Nothing after this transaction can be committed until it is
+        *treasury.sequence_number_mut() = sequence_number;
+        return Err(err.into());
+    }
+    Ok(())
+}
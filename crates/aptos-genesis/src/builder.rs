@@ -35,6 +35,7 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
+use vm_genesis::{AccountBalance, VestingAccount};
 
 const VALIDATOR_IDENTITY: &str = "validator-identity.yaml";
 const VFN_IDENTITY: &str = "vfn-identity.yaml";
@@ -375,6 +376,8 @@ const ONE_DAY: u64 = 86400;
 
 #[derive(Clone)]
 pub struct GenesisConfiguration {
+    /// Accounts created with an initial balance, in addition to the validators
+    pub accounts: Vec<AccountBalance>,
     pub allow_new_validators: bool,
     pub epoch_duration_secs: u64,
     pub is_test: bool,
@@ -384,6 +387,11 @@ pub struct GenesisConfiguration {
     pub recurring_lockup_duration_secs: u64,
     pub required_proposer_stake: u64,
     pub rewards_apy_percentage: u64,
+    /// If set, the coins allocated at genesis (stakes, balances and vesting totals) must add up
+    /// to exactly this amount
+    pub total_supply: Option<u64>,
+    /// Accounts created with coins released to them over time by a vesting contract
+    pub vesting_accounts: Vec<VestingAccount>,
     pub voting_duration_secs: u64,
     pub voting_power_increase_limit: u64,
 }
@@ -400,6 +408,9 @@ pub struct Builder {
     randomize_first_validator_ports: bool,
     init_config: Option<InitConfigFn>,
     init_genesis_config: Option<InitGenesisConfigFn>,
+    accounts: Vec<AccountBalance>,
+    vesting_accounts: Vec<VestingAccount>,
+    total_supply: Option<u64>,
 }
 
 impl Builder {
@@ -414,6 +425,9 @@ impl Builder {
             randomize_first_validator_ports: true,
             init_config: None,
             init_genesis_config: None,
+            accounts: vec![],
+            vesting_accounts: vec![],
+            total_supply: None,
         })
     }
 
@@ -440,6 +454,25 @@ impl Builder {
         self
     }
 
+    /// Accounts to create at genesis with an initial balance
+    pub fn with_accounts(mut self, accounts: Vec<AccountBalance>) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    /// Accounts to create at genesis with coins released to them by a vesting contract
+    pub fn with_vesting_accounts(mut self, vesting_accounts: Vec<VestingAccount>) -> Self {
+        self.vesting_accounts = vesting_accounts;
+        self
+    }
+
+    /// Require the coins allocated at genesis, including the validators' stakes, to add up to
+    /// exactly `total_supply`
+    pub fn with_total_supply(mut self, total_supply: u64) -> Self {
+        self.total_supply = Some(total_supply);
+        self
+    }
+
     /// Build all of the validators and save their configs
     pub fn build<R>(
         mut self,
@@ -566,6 +599,7 @@ impl Builder {
         }
 
         let mut genesis_config = GenesisConfiguration {
+            accounts: self.accounts.clone(),
             allow_new_validators: false,
             epoch_duration_secs: ONE_DAY,
             is_test: true,
@@ -575,6 +609,8 @@ impl Builder {
             recurring_lockup_duration_secs: ONE_DAY,
             required_proposer_stake: 0,
             rewards_apy_percentage: 10,
+            total_supply: self.total_supply,
+            vesting_accounts: self.vesting_accounts.clone(),
             voting_duration_secs: ONE_DAY / 24,
             voting_power_increase_limit: 50,
         };
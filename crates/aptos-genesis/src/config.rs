@@ -3,6 +3,7 @@
 
 use aptos_config::config::HANDSHAKE_VERSION;
 use aptos_crypto::{bls12381, ed25519::Ed25519PublicKey, x25519};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
@@ -286,3 +287,22 @@ pub struct StringOperatorConfiguration {
     pub full_node_network_public_key: Option<String>,
     pub full_node_host: Option<HostAndPort>,
 }
+
+/// What the owner of a validator signs with their account key to vouch for their owner
+/// configuration. The chain id and the username tie the signature to one ceremony and one
+/// validator, so it can't be replayed elsewhere.
+#[derive(Debug, Serialize, Deserialize, Clone, CryptoHasher, BCSCryptoHash)]
+pub struct OwnerContribution {
+    pub chain_id: ChainId,
+    pub username: String,
+    pub config: StringOwnerConfiguration,
+}
+
+/// What the operator of a validator signs with their account key to vouch for their operator
+/// configuration, see [`OwnerContribution`].
+#[derive(Debug, Serialize, Deserialize, Clone, CryptoHasher, BCSCryptoHash)]
+pub struct OperatorContribution {
+    pub chain_id: ChainId,
+    pub username: String,
+    pub config: StringOperatorConfiguration,
+}
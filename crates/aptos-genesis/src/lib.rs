@@ -11,19 +11,23 @@ pub mod keys;
 pub mod test_utils;
 
 use crate::{builder::GenesisConfiguration, config::ValidatorConfiguration};
+use anyhow::{anyhow, ensure};
 use aptos_config::config::{
     RocksdbConfigs, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
     TARGET_SNAPSHOT_SIZE,
 };
 use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_temppath::TempPath;
-use aptos_types::{chain_id::ChainId, transaction::Transaction, waypoint::Waypoint};
+use aptos_types::{
+    account_address::AccountAddress, chain_id::ChainId, transaction::Transaction,
+    waypoint::Waypoint,
+};
 use aptos_vm::AptosVM;
 use aptosdb::AptosDB;
 use framework::ReleaseBundle;
-use std::convert::TryInto;
+use std::{collections::HashSet, convert::TryInto};
 use storage_interface::DbReaderWriter;
-use vm_genesis::Validator;
+use vm_genesis::{AccountBalance, Validator, VestingAccount};
 
 /// Holder object for all pieces needed to generate a genesis transaction
 #[derive(Clone)]
@@ -36,6 +40,10 @@ pub struct GenesisInfo {
     validators: Vec<Validator>,
     /// Released framework packages
     framework: ReleaseBundle,
+    /// Accounts created with an initial balance
+    accounts: Vec<AccountBalance>,
+    /// Accounts created with coins held by a vesting contract
+    vesting_accounts: Vec<VestingAccount>,
     /// The genesis transaction, once it's been generated
     genesis: Option<Transaction>,
 
@@ -75,12 +83,15 @@ impl GenesisInfo {
         for config in configs {
             validators.push(config.try_into()?)
         }
+        validate_allocations(&validators, genesis_config)?;
 
         Ok(GenesisInfo {
             chain_id,
             root_key,
             validators,
             framework,
+            accounts: genesis_config.accounts.clone(),
+            vesting_accounts: genesis_config.vesting_accounts.clone(),
             genesis: None,
             allow_new_validators: genesis_config.allow_new_validators,
             epoch_duration_secs: genesis_config.epoch_duration_secs,
@@ -112,6 +123,7 @@ impl GenesisInfo {
             &self.framework,
             self.chain_id,
             vm_genesis::GenesisConfiguration {
+                accounts: self.accounts.clone(),
                 allow_new_validators: self.allow_new_validators,
                 epoch_duration_secs: self.epoch_duration_secs,
                 is_test: true,
@@ -121,6 +133,7 @@ impl GenesisInfo {
                 recurring_lockup_duration_secs: self.recurring_lockup_duration_secs,
                 required_proposer_stake: self.required_proposer_stake,
                 rewards_apy_percentage: self.rewards_apy_percentage,
+                vesting_accounts: self.vesting_accounts.clone(),
                 voting_duration_secs: self.voting_duration_secs,
                 voting_power_increase_limit: self.voting_power_increase_limit,
             },
@@ -143,3 +156,84 @@ impl GenesisInfo {
         executor::db_bootstrapper::generate_waypoint::<AptosVM>(&db_rw, genesis)
     }
 }
+
+/// Checks that every account is created once, that vesting schedules release all of their coins
+/// eventually, and that the coins allocated add up to the total supply, if one is set
+fn validate_allocations(
+    validators: &[Validator],
+    genesis_config: &GenesisConfiguration,
+) -> anyhow::Result<()> {
+    let mut addresses = HashSet::new();
+    let mut add_address = |address: AccountAddress| -> anyhow::Result<()> {
+        ensure!(
+            addresses.insert(address),
+            "Account {} is allocated more than once at genesis",
+            address
+        );
+        Ok(())
+    };
+    for validator in validators {
+        add_address(validator.owner_address)?;
+        // The operator and voter may be the owner, in which case they share its account
+        if validator.operator_address != validator.owner_address {
+            add_address(validator.operator_address)?;
+        }
+        if validator.voter_address != validator.owner_address
+            && validator.voter_address != validator.operator_address
+        {
+            add_address(validator.voter_address)?;
+        }
+    }
+    for account in &genesis_config.accounts {
+        add_address(account.account_address)?;
+    }
+    for account in &genesis_config.vesting_accounts {
+        add_address(account.account_address)?;
+        ensure!(
+            account.schedule_numerators.last().map_or(false, |n| *n > 0),
+            "Vesting schedule of {} must end with a non-zero fraction",
+            account.account_address
+        );
+        ensure!(
+            account.schedule_denominator > 0,
+            "Vesting schedule denominator of {} must be > 0",
+            account.account_address
+        );
+        ensure!(
+            account.period_duration_secs > 0,
+            "Vesting period of {} must be > 0",
+            account.account_address
+        );
+    }
+
+    if let Some(total_supply) = genesis_config.total_supply {
+        let amounts = validators
+            .iter()
+            .map(|validator| validator.stake_amount)
+            .chain(
+                genesis_config
+                    .accounts
+                    .iter()
+                    .map(|account| account.balance),
+            )
+            .chain(
+                genesis_config
+                    .vesting_accounts
+                    .iter()
+                    .map(|account| account.total),
+            );
+        let mut total = 0u64;
+        for amount in amounts {
+            total = total
+                .checked_add(amount)
+                .ok_or_else(|| anyhow!("Coins allocated at genesis overflow u64"))?;
+        }
+        ensure!(
+            total == total_supply,
+            "Coins allocated at genesis add up to {}, but the total supply is {}",
+            total,
+            total_supply
+        );
+    }
+    Ok(())
+}
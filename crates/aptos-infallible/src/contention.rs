@@ -0,0 +1,47 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use once_cell::sync::Lazy;
+use std::{collections::HashMap, panic::Location, sync::Mutex as StdMutex, time::Duration};
+
+/// How often, and for how long, threads had to wait to acquire the locks taken at `location`.
+/// Only waits are recorded: acquiring a free lock costs nothing.
+#[derive(Clone, Copy, Debug)]
+pub struct LockContention {
+    pub location: &'static Location<'static>,
+    pub contended: u64,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+}
+
+static CONTENTION: Lazy<StdMutex<HashMap<&'static Location<'static>, LockContention>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+pub(crate) fn record(location: &'static Location<'static>, wait: Duration) {
+    // The stats are only updated when a lock was contended, which is already the slow path
+    let mut contention = CONTENTION
+        .lock()
+        .expect("Cannot currently handle a poisoned lock");
+    let entry = contention.entry(location).or_insert(LockContention {
+        location,
+        contended: 0,
+        total_wait: Duration::ZERO,
+        max_wait: Duration::ZERO,
+    });
+    entry.contended += 1;
+    entry.total_wait += wait;
+    entry.max_wait = entry.max_wait.max(wait);
+}
+
+/// The contention of every lock which was waited for since the process started, by decreasing
+/// total wait
+pub fn lock_contention() -> Vec<LockContention> {
+    let mut contention: Vec<_> = CONTENTION
+        .lock()
+        .expect("Cannot currently handle a poisoned lock")
+        .values()
+        .copied()
+        .collect();
+    contention.sort_by(|a, b| b.total_wait.cmp(&a.total_wait));
+    contention
+}
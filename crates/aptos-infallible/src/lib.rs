@@ -1,12 +1,14 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod contention;
 mod math;
 mod mutex;
 mod nonzero;
 mod rwlock;
 mod time;
 
+pub use contention::{lock_contention, LockContention};
 pub use math::ArithmeticError;
 pub use mutex::{Mutex, MutexGuard};
 pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
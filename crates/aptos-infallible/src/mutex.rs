@@ -1,7 +1,12 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Mutex as StdMutex;
+use crate::contention;
+use std::{
+    panic::Location,
+    sync::{Mutex as StdMutex, TryLockError},
+    time::Instant,
+};
 
 pub use std::sync::MutexGuard;
 
@@ -16,11 +21,21 @@ impl<T> Mutex<T> {
         Self(StdMutex::new(t))
     }
 
-    /// lock the mutex
+    /// lock the mutex, recording how long it waited if the mutex was held
+    #[track_caller]
     pub fn lock(&self) -> MutexGuard<'_, T> {
-        self.0
+        match self.0.try_lock() {
+            Ok(guard) => return guard,
+            Err(TryLockError::Poisoned(_)) => panic!("Cannot currently handle a poisoned lock"),
+            Err(TryLockError::WouldBlock) => (),
+        }
+        let start = Instant::now();
+        let guard = self
+            .0
             .lock()
-            .expect("Cannot currently handle a poisoned lock")
+            .expect("Cannot currently handle a poisoned lock");
+        contention::record(Location::caller(), start.elapsed());
+        guard
     }
 }
 
@@ -51,4 +66,30 @@ mod tests {
 
         let _locked = mutex.lock();
     }
+
+    #[test]
+    fn test_mutex_contention() {
+        let mutex = Arc::new(Mutex::new(0u8));
+        let (locked_sender, locked_receiver) = std::sync::mpsc::channel();
+        let mutex2 = mutex.clone();
+        let thread = thread::spawn(move || {
+            let _locked = mutex2.lock();
+            locked_sender.send(()).unwrap();
+            thread::sleep(std::time::Duration::from_millis(50));
+        });
+
+        locked_receiver.recv().unwrap();
+        let line = line!() + 1;
+        let _locked = mutex.lock();
+        thread.join().unwrap();
+
+        let contention = crate::lock_contention()
+            .into_iter()
+            .find(|contention| {
+                contention.location.file() == file!() && contention.location.line() == line
+            })
+            .expect("The contended lock should be recorded");
+        assert_eq!(contention.contended, 1);
+        assert!(contention.max_wait > std::time::Duration::ZERO);
+    }
 }
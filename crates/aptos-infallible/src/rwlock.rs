@@ -1,7 +1,12 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::RwLock as StdRwLock;
+use crate::contention;
+use std::{
+    panic::Location,
+    sync::{RwLock as StdRwLock, TryLockError},
+    time::Instant,
+};
 
 pub use std::sync::{RwLockReadGuard, RwLockWriteGuard};
 
@@ -16,18 +21,38 @@ impl<T> RwLock<T> {
         Self(StdRwLock::new(t))
     }
 
-    /// lock the rwlock in read mode
+    /// lock the rwlock in read mode, recording how long it waited if a writer held the lock
+    #[track_caller]
     pub fn read(&self) -> RwLockReadGuard<'_, T> {
-        self.0
+        match self.0.try_read() {
+            Ok(guard) => return guard,
+            Err(TryLockError::Poisoned(_)) => panic!("Cannot currently handle a poisoned lock"),
+            Err(TryLockError::WouldBlock) => (),
+        }
+        let start = Instant::now();
+        let guard = self
+            .0
             .read()
-            .expect("Cannot currently handle a poisoned lock")
+            .expect("Cannot currently handle a poisoned lock");
+        contention::record(Location::caller(), start.elapsed());
+        guard
     }
 
-    /// lock the rwlock in write mode
+    /// lock the rwlock in write mode, recording how long it waited if the lock was held
+    #[track_caller]
     pub fn write(&self) -> RwLockWriteGuard<'_, T> {
-        self.0
+        match self.0.try_write() {
+            Ok(guard) => return guard,
+            Err(TryLockError::Poisoned(_)) => panic!("Cannot currently handle a poisoned lock"),
+            Err(TryLockError::WouldBlock) => (),
+        }
+        let start = Instant::now();
+        let guard = self
+            .0
             .write()
-            .expect("Cannot currently handle a poisoned lock")
+            .expect("Cannot currently handle a poisoned lock");
+        contention::record(Location::caller(), start.elapsed());
+        guard
     }
 
     /// return the owned type consuming the lock
@@ -4,6 +4,8 @@
 use aptos_crypto::{
     bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    mnemonic::Mnemonic,
+    slip10::DerivationError,
     x25519, CryptoMaterialError, PrivateKey, Uniform,
 };
 use aptos_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
@@ -63,6 +65,11 @@ impl KeyGen {
         Ok((private_key, public_key))
     }
 
+    /// Generate a BIP-39 mnemonic of `word_count` words, from which Ed25519 keys can be derived.
+    pub fn generate_mnemonic(&mut self, word_count: usize) -> Result<Mnemonic, DerivationError> {
+        Mnemonic::generate(&mut self.0, word_count)
+    }
+
     /// Same as `generate_keypair`, but returns a tuple of (private_key, auth_key, account_addr) instead.
     pub fn generate_credentials_for_account_creation(
         &mut self,
@@ -0,0 +1,281 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with the Aptos app on a Ledger hardware wallet, over USB HID.
+//!
+//! The private key never leaves the device: the host asks the app for the public key of a BIP32
+//! derivation path, and sends it the signing message of a transaction, which the app shows on
+//! the device's screen for the user to approve.
+//!
+//! Talking to the device needs the USB HID transport, which builds and links hidapi, so it's only
+//! compiled with the `hid` feature. Without it, derivation paths still work, but connecting to a
+//! device fails.
+
+#![forbid(unsafe_code)]
+
+use aptos_crypto::ed25519::{
+    Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH,
+};
+#[cfg(feature = "hid")]
+use ledger_apdu::APDUCommand;
+#[cfg(feature = "hid")]
+use ledger_transport_hid::{hidapi::HidApi, LedgerHIDError, TransportNativeHID};
+use std::{convert::TryFrom, str::FromStr};
+use thiserror::Error;
+
+/// The derivation path of the first Aptos account, as used by the Aptos wallets
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/637'/0'/0'/0'";
+
+/// The coin type registered for Aptos in SLIP-0044
+const APTOS_COIN_TYPE: u32 = 637;
+const HARDENED: u32 = 0x8000_0000;
+
+#[cfg(feature = "hid")]
+const CLA_APTOS: u8 = 0x5B;
+const INS_GET_VERSION: u8 = 0x03;
+const INS_GET_PUBLIC_KEY: u8 = 0x05;
+const INS_SIGN_TXN: u8 = 0x06;
+
+/// Whether the app shows the address on screen, for the user to check, before returning it
+const P1_NO_DISPLAY: u8 = 0x00;
+const P1_DISPLAY: u8 = 0x01;
+/// Whether more chunks of the message follow this one
+const P2_MORE: u8 = 0x80;
+const P2_LAST: u8 = 0x00;
+
+/// The most data a single APDU can carry
+const MAX_APDU_DATA_LENGTH: usize = 255;
+
+#[cfg(feature = "hid")]
+const APDU_CODE_SUCCESS: u16 = 0x9000;
+#[cfg(feature = "hid")]
+const APDU_CODE_USER_REJECTED: u16 = 0x6985;
+#[cfg(feature = "hid")]
+const APDU_CODE_APP_NOT_OPEN: u16 = 0x6E00;
+
+#[derive(Debug, Error)]
+pub enum AptosLedgerError {
+    #[cfg(feature = "hid")]
+    #[error("Failed to connect to the Ledger device: {0}")]
+    Transport(#[from] LedgerHIDError),
+    #[error("Ledger support isn't built in, build the CLI with the `ledger` feature")]
+    Unsupported,
+    #[error("Failed to access USB HID devices: {0}")]
+    Hid(String),
+    #[error("The Aptos app isn't open on the Ledger device")]
+    AppNotOpen,
+    #[error("The request was rejected on the Ledger device")]
+    UserRejected,
+    #[error("The Ledger device returned error code {0:#06x}")]
+    Device(u16),
+    #[error("Unexpected response from the Ledger device: {0}")]
+    UnexpectedResponse(String),
+    #[error("Invalid derivation path '{0}': {1}")]
+    InvalidDerivationPath(String, &'static str),
+}
+
+pub type Result<T> = std::result::Result<T, AptosLedgerError>;
+
+/// A BIP32 derivation path under the Aptos coin type, e.g. `m/44'/637'/0'/0'/0'`.  The Aptos
+/// app only derives Ed25519 keys along hardened paths, so every index must be hardened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The path of the account at `index`, following the layout of the Aptos wallets
+    pub fn for_account(index: u32) -> Self {
+        Self(vec![
+            44 | HARDENED,
+            APTOS_COIN_TYPE | HARDENED,
+            index | HARDENED,
+            HARDENED,
+            HARDENED,
+        ])
+    }
+
+    /// The path as sent to the app: the number of indices, then each index big endian
+    fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + 4 * self.0.len());
+        bytes.push(self.0.len() as u8);
+        for index in &self.0 {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+        bytes
+    }
+}
+
+impl Default for DerivationPath {
+    fn default() -> Self {
+        Self::for_account(0)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = AptosLedgerError;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let invalid = |reason| AptosLedgerError::InvalidDerivationPath(path.to_string(), reason);
+        let mut parts = path.split('/');
+        if parts.next() != Some("m") {
+            return Err(invalid("it must start with 'm/'"));
+        }
+        let indices = parts
+            .map(|part| {
+                let index = part
+                    .strip_suffix('\'')
+                    .ok_or_else(|| invalid("every index must be hardened, e.g. 0'"))?;
+                let index = u32::from_str(index).map_err(|_| invalid("indices must be numbers"))?;
+                if index >= HARDENED {
+                    return Err(invalid("indices must be less than 2^31"));
+                }
+                Ok(index | HARDENED)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if indices.len() < 3 || indices.len() > 10 {
+            return Err(invalid("it must have between 3 and 10 indices"));
+        }
+        if indices[0] != 44 | HARDENED || indices[1] != APTOS_COIN_TYPE | HARDENED {
+            return Err(invalid("it must start with m/44'/637'"));
+        }
+        Ok(Self(indices))
+    }
+}
+
+impl std::fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index & !HARDENED)?;
+        }
+        Ok(())
+    }
+}
+
+/// A connection to the Aptos app on the first Ledger device found
+pub struct AptosLedger {
+    #[cfg(feature = "hid")]
+    transport: TransportNativeHID,
+}
+
+impl AptosLedger {
+    #[cfg(feature = "hid")]
+    pub fn connect() -> Result<Self> {
+        let api = HidApi::new().map_err(|err| AptosLedgerError::Hid(err.to_string()))?;
+        Ok(Self {
+            transport: TransportNativeHID::new(&api)?,
+        })
+    }
+
+    #[cfg(not(feature = "hid"))]
+    pub fn connect() -> Result<Self> {
+        Err(AptosLedgerError::Unsupported)
+    }
+
+    /// The version of the Aptos app, as `major.minor.patch`
+    pub fn app_version(&self) -> Result<String> {
+        let response = self.exchange(INS_GET_VERSION, 0, 0, vec![])?;
+        match response.as_slice() {
+            [major, minor, patch, ..] => Ok(format!("{}.{}.{}", major, minor, patch)),
+            _ => Err(AptosLedgerError::UnexpectedResponse(
+                "version is too short".to_string(),
+            )),
+        }
+    }
+
+    /// The public key at `path`.  With `display`, the app shows the account's address and
+    /// waits for the user to confirm that it matches the one on the host.
+    pub fn public_key(&self, path: &DerivationPath, display: bool) -> Result<Ed25519PublicKey> {
+        let p1 = if display { P1_DISPLAY } else { P1_NO_DISPLAY };
+        let response = self.exchange(INS_GET_PUBLIC_KEY, p1, 0, path.serialize())?;
+        // The key is prefixed by its length, which counts a leading 0x04 tag byte
+        let key = match response.as_slice() {
+            [length, _tag, rest @ ..] if *length as usize == ED25519_PUBLIC_KEY_LENGTH + 1 => {
+                rest.get(..ED25519_PUBLIC_KEY_LENGTH)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| AptosLedgerError::UnexpectedResponse("malformed public key".to_string()))?;
+        Ed25519PublicKey::try_from(key)
+            .map_err(|err| AptosLedgerError::UnexpectedResponse(err.to_string()))
+    }
+
+    /// Signs `message` with the key at `path`, once the user approves it on the device.  The
+    /// message is the full signing message: the domain separating prefix followed by the BCS
+    /// bytes of the transaction, which the app decodes for display.
+    pub fn sign(&self, path: &DerivationPath, message: &[u8]) -> Result<Ed25519Signature> {
+        // The path goes first, on its own, and the message follows in chunks
+        let mut chunks = vec![path.serialize()];
+        chunks.extend(message.chunks(MAX_APDU_DATA_LENGTH).map(<[u8]>::to_vec));
+        let last = chunks.len() - 1;
+        let mut response = vec![];
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let p2 = if i == last { P2_LAST } else { P2_MORE };
+            response = self.exchange(INS_SIGN_TXN, i as u8, p2, chunk)?;
+        }
+
+        let signature = match response.as_slice() {
+            [length, rest @ ..] if *length as usize == ED25519_SIGNATURE_LENGTH => {
+                rest.get(..ED25519_SIGNATURE_LENGTH)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| AptosLedgerError::UnexpectedResponse("malformed signature".to_string()))?;
+        Ed25519Signature::try_from(signature)
+            .map_err(|err| AptosLedgerError::UnexpectedResponse(err.to_string()))
+    }
+
+    #[cfg(feature = "hid")]
+    fn exchange(&self, ins: u8, p1: u8, p2: u8, data: Vec<u8>) -> Result<Vec<u8>> {
+        let response = self.transport.exchange(&APDUCommand {
+            cla: CLA_APTOS,
+            ins,
+            p1,
+            p2,
+            data,
+        })?;
+        match response.retcode() {
+            APDU_CODE_SUCCESS => Ok(response.data().to_vec()),
+            APDU_CODE_USER_REJECTED => Err(AptosLedgerError::UserRejected),
+            APDU_CODE_APP_NOT_OPEN => Err(AptosLedgerError::AppNotOpen),
+            code => Err(AptosLedgerError::Device(code)),
+        }
+    }
+
+    #[cfg(not(feature = "hid"))]
+    fn exchange(&self, _ins: u8, _p1: u8, _p2: u8, _data: Vec<u8>) -> Result<Vec<u8>> {
+        Err(AptosLedgerError::Unsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derivation_path() {
+        let path = DerivationPath::from_str(DEFAULT_DERIVATION_PATH).unwrap();
+        assert_eq!(path, DerivationPath::default());
+        assert_eq!(path.to_string(), DEFAULT_DERIVATION_PATH);
+        assert_eq!(
+            path.serialize(),
+            vec![
+                5, 0x80, 0, 0, 44, 0x80, 0, 0x02, 0x7D, 0x80, 0, 0, 0, 0x80, 0, 0, 0, 0x80, 0, 0, 0
+            ]
+        );
+        assert_eq!(
+            DerivationPath::for_account(3).to_string(),
+            "m/44'/637'/3'/0'/0'"
+        );
+
+        for invalid in [
+            "44'/637'/0'/0'/0'",
+            "m/44'/637'/0'/0/0",
+            "m/44'/637'/x'",
+            "m/44'/637'",
+            "m/44'/1'/0'/0'/0'",
+            "m/44'/637'/2147483648'",
+        ] {
+            assert!(DerivationPath::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -566,11 +566,12 @@ pub trait Writer: Send + Sync {
 }
 
 /// A struct for writing logs to stdout
-struct StdoutWriter {
+pub struct StdoutWriter {
     buffer: std::io::BufWriter<Stdout>,
 }
 
 impl StdoutWriter {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         let buffer = std::io::BufWriter::new(std::io::stdout());
         Self { buffer }
@@ -135,7 +135,7 @@
 
 pub mod prelude {
     pub use crate::{
-        aptos_logger::FileWriter,
+        aptos_logger::{FileWriter, StdoutWriter},
         debug, error, info, sample,
         sample::{SampleRate, Sampling},
         security::SecurityEvent,
@@ -0,0 +1,1737 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// @generated
+impl serde::Serialize for DumpConsensusStateRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.DumpConsensusStateRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for DumpConsensusStateRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {}
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = DumpConsensusStateRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.DumpConsensusStateRequest")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<DumpConsensusStateRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {
+                    let _ = map.next_value::<serde::de::IgnoredAny>()?;
+                }
+                Ok(DumpConsensusStateRequest {})
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.DumpConsensusStateRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for DumpConsensusStateResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.state.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.DumpConsensusStateResponse", len)?;
+        if !self.state.is_empty() {
+            struct_ser.serialize_field("state", &self.state)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for DumpConsensusStateResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["state"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            State,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "state" => Ok(GeneratedField::State),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = DumpConsensusStateResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.DumpConsensusStateResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<DumpConsensusStateResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut state__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::State => {
+                            if state__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("state"));
+                            }
+                            state__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(DumpConsensusStateResponse {
+                    state: state__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.DumpConsensusStateResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for RotateNetworkKeyRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.network_id.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.RotateNetworkKeyRequest", len)?;
+        if !self.network_id.is_empty() {
+            struct_ser.serialize_field("networkId", &self.network_id)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RotateNetworkKeyRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["networkId"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            NetworkId,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "networkId" => Ok(GeneratedField::NetworkId),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RotateNetworkKeyRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.RotateNetworkKeyRequest")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<RotateNetworkKeyRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut network_id__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::NetworkId => {
+                            if network_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("networkId"));
+                            }
+                            network_id__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(RotateNetworkKeyRequest {
+                    network_id: network_id__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.RotateNetworkKeyRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for RotateNetworkKeyResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.RotateNetworkKeyResponse", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field(
+                "publicKey",
+                pbjson::private::base64::encode(&self.public_key).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RotateNetworkKeyResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["publicKey"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" => Ok(GeneratedField::PublicKey),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RotateNetworkKeyResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.RotateNetworkKeyResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<RotateNetworkKeyResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(RotateNetworkKeyResponse {
+                    public_key: public_key__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.RotateNetworkKeyResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for RunNetworkBenchmarkRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.network_id.is_empty() {
+            len += 1;
+        }
+        if !self.peer_id.is_empty() {
+            len += 1;
+        }
+        if self.duration_ms != 0 {
+            len += 1;
+        }
+        if self.bytes_per_sec != 0 {
+            len += 1;
+        }
+        if self.message_size != 0 {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.RunNetworkBenchmarkRequest", len)?;
+        if !self.network_id.is_empty() {
+            struct_ser.serialize_field("networkId", &self.network_id)?;
+        }
+        if !self.peer_id.is_empty() {
+            struct_ser.serialize_field("peerId", &self.peer_id)?;
+        }
+        if self.duration_ms != 0 {
+            struct_ser.serialize_field(
+                "durationMs",
+                ToString::to_string(&self.duration_ms).as_str(),
+            )?;
+        }
+        if self.bytes_per_sec != 0 {
+            struct_ser.serialize_field(
+                "bytesPerSec",
+                ToString::to_string(&self.bytes_per_sec).as_str(),
+            )?;
+        }
+        if self.message_size != 0 {
+            struct_ser.serialize_field(
+                "messageSize",
+                ToString::to_string(&self.message_size).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RunNetworkBenchmarkRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "networkId",
+            "peerId",
+            "durationMs",
+            "bytesPerSec",
+            "messageSize",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            NetworkId,
+            PeerId,
+            DurationMs,
+            BytesPerSec,
+            MessageSize,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "networkId" => Ok(GeneratedField::NetworkId),
+                            "peerId" => Ok(GeneratedField::PeerId),
+                            "durationMs" => Ok(GeneratedField::DurationMs),
+                            "bytesPerSec" => Ok(GeneratedField::BytesPerSec),
+                            "messageSize" => Ok(GeneratedField::MessageSize),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RunNetworkBenchmarkRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.RunNetworkBenchmarkRequest")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<RunNetworkBenchmarkRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut network_id__ = None;
+                let mut peer_id__ = None;
+                let mut duration_ms__ = None;
+                let mut bytes_per_sec__ = None;
+                let mut message_size__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::NetworkId => {
+                            if network_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("networkId"));
+                            }
+                            network_id__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::PeerId => {
+                            if peer_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("peerId"));
+                            }
+                            peer_id__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::DurationMs => {
+                            if duration_ms__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("durationMs"));
+                            }
+                            duration_ms__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::BytesPerSec => {
+                            if bytes_per_sec__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bytesPerSec"));
+                            }
+                            bytes_per_sec__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::MessageSize => {
+                            if message_size__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("messageSize"));
+                            }
+                            message_size__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(RunNetworkBenchmarkRequest {
+                    network_id: network_id__.unwrap_or_default(),
+                    peer_id: peer_id__.unwrap_or_default(),
+                    duration_ms: duration_ms__.unwrap_or_default(),
+                    bytes_per_sec: bytes_per_sec__.unwrap_or_default(),
+                    message_size: message_size__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.RunNetworkBenchmarkRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for RunNetworkBenchmarkResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.messages_acknowledged != 0 {
+            len += 1;
+        }
+        if self.messages_failed != 0 {
+            len += 1;
+        }
+        if self.bytes_acknowledged != 0 {
+            len += 1;
+        }
+        if self.duration_ms != 0 {
+            len += 1;
+        }
+        if self.throughput_bytes_per_sec != 0 {
+            len += 1;
+        }
+        if self.min_latency_us != 0 {
+            len += 1;
+        }
+        if self.avg_latency_us != 0 {
+            len += 1;
+        }
+        if self.p50_latency_us != 0 {
+            len += 1;
+        }
+        if self.p99_latency_us != 0 {
+            len += 1;
+        }
+        if self.max_latency_us != 0 {
+            len += 1;
+        }
+        if self.jitter_us != 0 {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.RunNetworkBenchmarkResponse", len)?;
+        if self.messages_acknowledged != 0 {
+            struct_ser.serialize_field(
+                "messagesAcknowledged",
+                ToString::to_string(&self.messages_acknowledged).as_str(),
+            )?;
+        }
+        if self.messages_failed != 0 {
+            struct_ser.serialize_field(
+                "messagesFailed",
+                ToString::to_string(&self.messages_failed).as_str(),
+            )?;
+        }
+        if self.bytes_acknowledged != 0 {
+            struct_ser.serialize_field(
+                "bytesAcknowledged",
+                ToString::to_string(&self.bytes_acknowledged).as_str(),
+            )?;
+        }
+        if self.duration_ms != 0 {
+            struct_ser.serialize_field(
+                "durationMs",
+                ToString::to_string(&self.duration_ms).as_str(),
+            )?;
+        }
+        if self.throughput_bytes_per_sec != 0 {
+            struct_ser.serialize_field(
+                "throughputBytesPerSec",
+                ToString::to_string(&self.throughput_bytes_per_sec).as_str(),
+            )?;
+        }
+        if self.min_latency_us != 0 {
+            struct_ser.serialize_field(
+                "minLatencyUs",
+                ToString::to_string(&self.min_latency_us).as_str(),
+            )?;
+        }
+        if self.avg_latency_us != 0 {
+            struct_ser.serialize_field(
+                "avgLatencyUs",
+                ToString::to_string(&self.avg_latency_us).as_str(),
+            )?;
+        }
+        if self.p50_latency_us != 0 {
+            struct_ser.serialize_field(
+                "p50LatencyUs",
+                ToString::to_string(&self.p50_latency_us).as_str(),
+            )?;
+        }
+        if self.p99_latency_us != 0 {
+            struct_ser.serialize_field(
+                "p99LatencyUs",
+                ToString::to_string(&self.p99_latency_us).as_str(),
+            )?;
+        }
+        if self.max_latency_us != 0 {
+            struct_ser.serialize_field(
+                "maxLatencyUs",
+                ToString::to_string(&self.max_latency_us).as_str(),
+            )?;
+        }
+        if self.jitter_us != 0 {
+            struct_ser
+                .serialize_field("jitterUs", ToString::to_string(&self.jitter_us).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for RunNetworkBenchmarkResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "messagesAcknowledged",
+            "messagesFailed",
+            "bytesAcknowledged",
+            "durationMs",
+            "throughputBytesPerSec",
+            "minLatencyUs",
+            "avgLatencyUs",
+            "p50LatencyUs",
+            "p99LatencyUs",
+            "maxLatencyUs",
+            "jitterUs",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            MessagesAcknowledged,
+            MessagesFailed,
+            BytesAcknowledged,
+            DurationMs,
+            ThroughputBytesPerSec,
+            MinLatencyUs,
+            AvgLatencyUs,
+            P50LatencyUs,
+            P99LatencyUs,
+            MaxLatencyUs,
+            JitterUs,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "messagesAcknowledged" => Ok(GeneratedField::MessagesAcknowledged),
+                            "messagesFailed" => Ok(GeneratedField::MessagesFailed),
+                            "bytesAcknowledged" => Ok(GeneratedField::BytesAcknowledged),
+                            "durationMs" => Ok(GeneratedField::DurationMs),
+                            "throughputBytesPerSec" => Ok(GeneratedField::ThroughputBytesPerSec),
+                            "minLatencyUs" => Ok(GeneratedField::MinLatencyUs),
+                            "avgLatencyUs" => Ok(GeneratedField::AvgLatencyUs),
+                            "p50LatencyUs" => Ok(GeneratedField::P50LatencyUs),
+                            "p99LatencyUs" => Ok(GeneratedField::P99LatencyUs),
+                            "maxLatencyUs" => Ok(GeneratedField::MaxLatencyUs),
+                            "jitterUs" => Ok(GeneratedField::JitterUs),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = RunNetworkBenchmarkResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.RunNetworkBenchmarkResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<RunNetworkBenchmarkResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut messages_acknowledged__ = None;
+                let mut messages_failed__ = None;
+                let mut bytes_acknowledged__ = None;
+                let mut duration_ms__ = None;
+                let mut throughput_bytes_per_sec__ = None;
+                let mut min_latency_us__ = None;
+                let mut avg_latency_us__ = None;
+                let mut p50_latency_us__ = None;
+                let mut p99_latency_us__ = None;
+                let mut max_latency_us__ = None;
+                let mut jitter_us__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::MessagesAcknowledged => {
+                            if messages_acknowledged__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "messagesAcknowledged",
+                                ));
+                            }
+                            messages_acknowledged__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::MessagesFailed => {
+                            if messages_failed__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("messagesFailed"));
+                            }
+                            messages_failed__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::BytesAcknowledged => {
+                            if bytes_acknowledged__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bytesAcknowledged"));
+                            }
+                            bytes_acknowledged__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::DurationMs => {
+                            if duration_ms__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("durationMs"));
+                            }
+                            duration_ms__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::ThroughputBytesPerSec => {
+                            if throughput_bytes_per_sec__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "throughputBytesPerSec",
+                                ));
+                            }
+                            throughput_bytes_per_sec__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::MinLatencyUs => {
+                            if min_latency_us__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("minLatencyUs"));
+                            }
+                            min_latency_us__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::AvgLatencyUs => {
+                            if avg_latency_us__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("avgLatencyUs"));
+                            }
+                            avg_latency_us__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::P50LatencyUs => {
+                            if p50_latency_us__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("p50LatencyUs"));
+                            }
+                            p50_latency_us__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::P99LatencyUs => {
+                            if p99_latency_us__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("p99LatencyUs"));
+                            }
+                            p99_latency_us__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::MaxLatencyUs => {
+                            if max_latency_us__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxLatencyUs"));
+                            }
+                            max_latency_us__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::JitterUs => {
+                            if jitter_us__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("jitterUs"));
+                            }
+                            jitter_us__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(RunNetworkBenchmarkResponse {
+                    messages_acknowledged: messages_acknowledged__.unwrap_or_default(),
+                    messages_failed: messages_failed__.unwrap_or_default(),
+                    bytes_acknowledged: bytes_acknowledged__.unwrap_or_default(),
+                    duration_ms: duration_ms__.unwrap_or_default(),
+                    throughput_bytes_per_sec: throughput_bytes_per_sec__.unwrap_or_default(),
+                    min_latency_us: min_latency_us__.unwrap_or_default(),
+                    avg_latency_us: avg_latency_us__.unwrap_or_default(),
+                    p50_latency_us: p50_latency_us__.unwrap_or_default(),
+                    p99_latency_us: p99_latency_us__.unwrap_or_default(),
+                    max_latency_us: max_latency_us__.unwrap_or_default(),
+                    jitter_us: jitter_us__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.RunNetworkBenchmarkResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for SetApiEnabledRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.enabled {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.SetApiEnabledRequest", len)?;
+        if self.enabled {
+            struct_ser.serialize_field("enabled", &self.enabled)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SetApiEnabledRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["enabled"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Enabled,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "enabled" => Ok(GeneratedField::Enabled),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SetApiEnabledRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.SetApiEnabledRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SetApiEnabledRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut enabled__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Enabled => {
+                            if enabled__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("enabled"));
+                            }
+                            enabled__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(SetApiEnabledRequest {
+                    enabled: enabled__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.SetApiEnabledRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for SetApiEnabledResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.previously_enabled {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.SetApiEnabledResponse", len)?;
+        if self.previously_enabled {
+            struct_ser.serialize_field("previouslyEnabled", &self.previously_enabled)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SetApiEnabledResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["previouslyEnabled"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PreviouslyEnabled,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "previouslyEnabled" => Ok(GeneratedField::PreviouslyEnabled),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SetApiEnabledResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.SetApiEnabledResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<SetApiEnabledResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut previously_enabled__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PreviouslyEnabled => {
+                            if previously_enabled__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("previouslyEnabled"));
+                            }
+                            previously_enabled__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(SetApiEnabledResponse {
+                    previously_enabled: previously_enabled__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.SetApiEnabledResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for SetFailpointRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.name.is_empty() {
+            len += 1;
+        }
+        if !self.actions.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.SetFailpointRequest", len)?;
+        if !self.name.is_empty() {
+            struct_ser.serialize_field("name", &self.name)?;
+        }
+        if !self.actions.is_empty() {
+            struct_ser.serialize_field("actions", &self.actions)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SetFailpointRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["name", "actions"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Name,
+            Actions,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "name" => Ok(GeneratedField::Name),
+                            "actions" => Ok(GeneratedField::Actions),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SetFailpointRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.SetFailpointRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SetFailpointRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut name__ = None;
+                let mut actions__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Name => {
+                            if name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("name"));
+                            }
+                            name__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Actions => {
+                            if actions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("actions"));
+                            }
+                            actions__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(SetFailpointRequest {
+                    name: name__.unwrap_or_default(),
+                    actions: actions__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.SetFailpointRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for SetFailpointResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.previous_actions.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.SetFailpointResponse", len)?;
+        if !self.previous_actions.is_empty() {
+            struct_ser.serialize_field("previousActions", &self.previous_actions)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SetFailpointResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["previousActions"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PreviousActions,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "previousActions" => Ok(GeneratedField::PreviousActions),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SetFailpointResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.SetFailpointResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<SetFailpointResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut previous_actions__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PreviousActions => {
+                            if previous_actions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("previousActions"));
+                            }
+                            previous_actions__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(SetFailpointResponse {
+                    previous_actions: previous_actions__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.SetFailpointResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for TakeStateSnapshotRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.TakeStateSnapshotRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TakeStateSnapshotRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {}
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TakeStateSnapshotRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.TakeStateSnapshotRequest")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<TakeStateSnapshotRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {
+                    let _ = map.next_value::<serde::de::IgnoredAny>()?;
+                }
+                Ok(TakeStateSnapshotRequest {})
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.TakeStateSnapshotRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for TakeStateSnapshotResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.path.is_empty() {
+            len += 1;
+        }
+        if self.version != 0 {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.TakeStateSnapshotResponse", len)?;
+        if !self.path.is_empty() {
+            struct_ser.serialize_field("path", &self.path)?;
+        }
+        if self.version != 0 {
+            struct_ser.serialize_field("version", ToString::to_string(&self.version).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TakeStateSnapshotResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["path", "version"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Path,
+            Version,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "path" => Ok(GeneratedField::Path),
+                            "version" => Ok(GeneratedField::Version),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TakeStateSnapshotResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.TakeStateSnapshotResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<TakeStateSnapshotResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut path__ = None;
+                let mut version__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Path => {
+                            if path__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("path"));
+                            }
+                            path__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Version => {
+                            if version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(TakeStateSnapshotResponse {
+                    path: path__.unwrap_or_default(),
+                    version: version__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.TakeStateSnapshotResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for TriggerPruningRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let len = 0;
+        let struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.TriggerPruningRequest", len)?;
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TriggerPruningRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {}
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        Err(serde::de::Error::unknown_field(value, FIELDS))
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TriggerPruningRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.TriggerPruningRequest")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<TriggerPruningRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                while map.next_key::<GeneratedField>()?.is_some() {
+                    let _ = map.next_value::<serde::de::IgnoredAny>()?;
+                }
+                Ok(TriggerPruningRequest {})
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.TriggerPruningRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for TriggerPruningResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.min_readable_state_version != 0 {
+            len += 1;
+        }
+        if self.min_readable_ledger_version != 0 {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.admin.v1.TriggerPruningResponse", len)?;
+        if self.min_readable_state_version != 0 {
+            struct_ser.serialize_field(
+                "minReadableStateVersion",
+                ToString::to_string(&self.min_readable_state_version).as_str(),
+            )?;
+        }
+        if self.min_readable_ledger_version != 0 {
+            struct_ser.serialize_field(
+                "minReadableLedgerVersion",
+                ToString::to_string(&self.min_readable_ledger_version).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TriggerPruningResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["minReadableStateVersion", "minReadableLedgerVersion"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            MinReadableStateVersion,
+            MinReadableLedgerVersion,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "minReadableStateVersion" => {
+                                Ok(GeneratedField::MinReadableStateVersion)
+                            }
+                            "minReadableLedgerVersion" => {
+                                Ok(GeneratedField::MinReadableLedgerVersion)
+                            }
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TriggerPruningResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.admin.v1.TriggerPruningResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<TriggerPruningResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut min_readable_state_version__ = None;
+                let mut min_readable_ledger_version__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::MinReadableStateVersion => {
+                            if min_readable_state_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "minReadableStateVersion",
+                                ));
+                            }
+                            min_readable_state_version__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::MinReadableLedgerVersion => {
+                            if min_readable_ledger_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "minReadableLedgerVersion",
+                                ));
+                            }
+                            min_readable_ledger_version__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(TriggerPruningResponse {
+                    min_readable_state_version: min_readable_state_version__.unwrap_or_default(),
+                    min_readable_ledger_version: min_readable_ledger_version__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.admin.v1.TriggerPruningResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
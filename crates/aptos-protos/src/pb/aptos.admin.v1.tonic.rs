@@ -0,0 +1,566 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// @generated
+/// Generated client implementations.
+pub mod node_admin_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// NodeAdmin runs operational commands against a running node. It only listens on a loopback address, and
+    /// every request must carry the configured token in an `authorization: Bearer <token>` header.
+    #[derive(Debug, Clone)]
+    pub struct NodeAdminClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl NodeAdminClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> NodeAdminClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> NodeAdminClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
+                Into<StdError> + Send + Sync,
+        {
+            NodeAdminClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with `gzip`.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_gzip(mut self) -> Self {
+            self.inner = self.inner.send_gzip();
+            self
+        }
+        /// Enable decompressing responses with `gzip`.
+        #[must_use]
+        pub fn accept_gzip(mut self) -> Self {
+            self.inner = self.inner.accept_gzip();
+            self
+        }
+        /// Prunes the state and ledger data outside of the configured pruning windows now, instead of waiting for enough
+        /// versions to be committed. Pruning runs in the background, so it may not have finished when this returns.
+        pub async fn trigger_pruning(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TriggerPruningRequest>,
+        ) -> Result<tonic::Response<super::TriggerPruningResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/aptos.admin.v1.NodeAdmin/TriggerPruning");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Returns a summary of the consensus state, e.g. the current epoch and the rounds of the highest blocks. Only
+        /// validators run consensus.
+        pub async fn dump_consensus_state(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DumpConsensusStateRequest>,
+        ) -> Result<tonic::Response<super::DumpConsensusStateResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/aptos.admin.v1.NodeAdmin/DumpConsensusState",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Replaces the identity key of a network with a new one, in the secure storage the key is loaded from. The new key
+        /// is used once the node restarts, so the addresses advertised for the node must be updated with the returned public
+        /// key before then.
+        pub async fn rotate_network_key(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RotateNetworkKeyRequest>,
+        ) -> Result<tonic::Response<super::RotateNetworkKeyResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/aptos.admin.v1.NodeAdmin/RotateNetworkKey");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Writes a checkpoint of the database to a new directory in the snapshot directory, which a node can be started
+        /// from.
+        pub async fn take_state_snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TakeStateSnapshotRequest>,
+        ) -> Result<tonic::Response<super::TakeStateSnapshotResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/aptos.admin.v1.NodeAdmin/TakeStateSnapshot");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Turns the REST API on or off. While it's off, every request is rejected with a 503, so load balancers stop
+        /// sending traffic to the node.
+        pub async fn set_api_enabled(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetApiEnabledRequest>,
+        ) -> Result<tonic::Response<super::SetApiEnabledResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/aptos.admin.v1.NodeAdmin/SetApiEnabled");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Configures a failpoint, to inject faults into a running node, e.g. `network::partition` or
+        /// `aptosdb::save_transactions::write`. The node must be built with the `failpoints` feature.
+        pub async fn set_failpoint(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetFailpointRequest>,
+        ) -> Result<tonic::Response<super::SetFailpointResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/aptos.admin.v1.NodeAdmin/SetFailpoint");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// Measures the throughput, latency and jitter of the link to a connected peer, by sending it messages over the
+        /// authenticated network transport for a bounded duration at a bounded rate. Both nodes must enable the network
+        /// benchmark in their config, and only one benchmark runs at a time.
+        pub async fn run_network_benchmark(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RunNetworkBenchmarkRequest>,
+        ) -> Result<tonic::Response<super::RunNetworkBenchmarkResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/aptos.admin.v1.NodeAdmin/RunNetworkBenchmark",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod node_admin_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with NodeAdminServer.
+    #[async_trait]
+    pub trait NodeAdmin: Send + Sync + 'static {
+        /// Prunes the state and ledger data outside of the configured pruning windows now, instead of waiting for enough
+        /// versions to be committed. Pruning runs in the background, so it may not have finished when this returns.
+        async fn trigger_pruning(
+            &self,
+            request: tonic::Request<super::TriggerPruningRequest>,
+        ) -> Result<tonic::Response<super::TriggerPruningResponse>, tonic::Status>;
+        /// Returns a summary of the consensus state, e.g. the current epoch and the rounds of the highest blocks. Only
+        /// validators run consensus.
+        async fn dump_consensus_state(
+            &self,
+            request: tonic::Request<super::DumpConsensusStateRequest>,
+        ) -> Result<tonic::Response<super::DumpConsensusStateResponse>, tonic::Status>;
+        /// Replaces the identity key of a network with a new one, in the secure storage the key is loaded from. The new key
+        /// is used once the node restarts, so the addresses advertised for the node must be updated with the returned public
+        /// key before then.
+        async fn rotate_network_key(
+            &self,
+            request: tonic::Request<super::RotateNetworkKeyRequest>,
+        ) -> Result<tonic::Response<super::RotateNetworkKeyResponse>, tonic::Status>;
+        /// Writes a checkpoint of the database to a new directory in the snapshot directory, which a node can be started
+        /// from.
+        async fn take_state_snapshot(
+            &self,
+            request: tonic::Request<super::TakeStateSnapshotRequest>,
+        ) -> Result<tonic::Response<super::TakeStateSnapshotResponse>, tonic::Status>;
+        /// Turns the REST API on or off. While it's off, every request is rejected with a 503, so load balancers stop
+        /// sending traffic to the node.
+        async fn set_api_enabled(
+            &self,
+            request: tonic::Request<super::SetApiEnabledRequest>,
+        ) -> Result<tonic::Response<super::SetApiEnabledResponse>, tonic::Status>;
+        /// Configures a failpoint, to inject faults into a running node, e.g. `network::partition` or
+        /// `aptosdb::save_transactions::write`. The node must be built with the `failpoints` feature.
+        async fn set_failpoint(
+            &self,
+            request: tonic::Request<super::SetFailpointRequest>,
+        ) -> Result<tonic::Response<super::SetFailpointResponse>, tonic::Status>;
+        /// Measures the throughput, latency and jitter of the link to a connected peer, by sending it messages over the
+        /// authenticated network transport for a bounded duration at a bounded rate. Both nodes must enable the network
+        /// benchmark in their config, and only one benchmark runs at a time.
+        async fn run_network_benchmark(
+            &self,
+            request: tonic::Request<super::RunNetworkBenchmarkRequest>,
+        ) -> Result<tonic::Response<super::RunNetworkBenchmarkResponse>, tonic::Status>;
+    }
+    /// NodeAdmin runs operational commands against a running node. It only listens on a loopback address, and
+    /// every request must carry the configured token in an `authorization: Bearer <token>` header.
+    #[derive(Debug)]
+    pub struct NodeAdminServer<T: NodeAdmin> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: NodeAdmin> NodeAdminServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with `gzip`.
+        #[must_use]
+        pub fn accept_gzip(mut self) -> Self {
+            self.accept_compression_encodings.enable_gzip();
+            self
+        }
+        /// Compress responses with `gzip`, if the client supports it.
+        #[must_use]
+        pub fn send_gzip(mut self) -> Self {
+            self.send_compression_encodings.enable_gzip();
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for NodeAdminServer<T>
+    where
+        T: NodeAdmin,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/aptos.admin.v1.NodeAdmin/TriggerPruning" => {
+                    #[allow(non_camel_case_types)]
+                    struct TriggerPruningSvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin> tonic::server::UnaryService<super::TriggerPruningRequest>
+                        for TriggerPruningSvc<T>
+                    {
+                        type Response = super::TriggerPruningResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TriggerPruningRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).trigger_pruning(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TriggerPruningSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.admin.v1.NodeAdmin/DumpConsensusState" => {
+                    #[allow(non_camel_case_types)]
+                    struct DumpConsensusStateSvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin> tonic::server::UnaryService<super::DumpConsensusStateRequest>
+                        for DumpConsensusStateSvc<T>
+                    {
+                        type Response = super::DumpConsensusStateResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DumpConsensusStateRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).dump_consensus_state(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DumpConsensusStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.admin.v1.NodeAdmin/RotateNetworkKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RotateNetworkKeySvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin> tonic::server::UnaryService<super::RotateNetworkKeyRequest>
+                        for RotateNetworkKeySvc<T>
+                    {
+                        type Response = super::RotateNetworkKeyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RotateNetworkKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).rotate_network_key(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RotateNetworkKeySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.admin.v1.NodeAdmin/TakeStateSnapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct TakeStateSnapshotSvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin> tonic::server::UnaryService<super::TakeStateSnapshotRequest>
+                        for TakeStateSnapshotSvc<T>
+                    {
+                        type Response = super::TakeStateSnapshotResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TakeStateSnapshotRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).take_state_snapshot(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TakeStateSnapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.admin.v1.NodeAdmin/SetApiEnabled" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetApiEnabledSvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin> tonic::server::UnaryService<super::SetApiEnabledRequest>
+                        for SetApiEnabledSvc<T>
+                    {
+                        type Response = super::SetApiEnabledResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetApiEnabledRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).set_api_enabled(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetApiEnabledSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.admin.v1.NodeAdmin/SetFailpoint" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetFailpointSvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin> tonic::server::UnaryService<super::SetFailpointRequest> for SetFailpointSvc<T> {
+                        type Response = super::SetFailpointResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetFailpointRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).set_failpoint(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetFailpointSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/aptos.admin.v1.NodeAdmin/RunNetworkBenchmark" => {
+                    #[allow(non_camel_case_types)]
+                    struct RunNetworkBenchmarkSvc<T: NodeAdmin>(pub Arc<T>);
+                    impl<T: NodeAdmin>
+                        tonic::server::UnaryService<super::RunNetworkBenchmarkRequest>
+                        for RunNetworkBenchmarkSvc<T>
+                    {
+                        type Response = super::RunNetworkBenchmarkResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RunNetworkBenchmarkRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).run_network_benchmark(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RunNetworkBenchmarkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: NodeAdmin> Clone for NodeAdminServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: NodeAdmin> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: NodeAdmin> tonic::transport::NamedService for NodeAdminServer<T> {
+        const NAME: &'static str = "aptos.admin.v1.NodeAdmin";
+    }
+}
@@ -28,11 +28,12 @@ pub struct Block {
     #[prost(uint32, tag = "4")]
     pub chain_id: u32,
 }
-/// Transaction as it happened on the chain, there are 4 types of transactions:
+/// Transaction as it happened on the chain, there are 5 types of transactions:
 /// - User Transaction: a user initiated transaction to interact with the chain
 /// - Block Metadata Transaction: transactions generated by the chain to group together transactions forming a "block"
 /// - State Checkpoint Transaction: transactions generated by the chain so when validator agreed on a particular global state
 /// - Genesis Transaction: the first transaction of the chain, with all core contract and validator information baked in
+/// - Validator Transaction: transactions through which validators record data they agreed on, e.g. a DKG transcript
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Transaction {
     #[prost(message, optional, tag = "1")]
@@ -47,7 +48,7 @@ pub struct Transaction {
     pub block_height: u64,
     #[prost(enumeration = "transaction::TransactionType", tag = "6")]
     pub r#type: i32,
-    #[prost(oneof = "transaction::TxnData", tags = "7, 8, 9, 10")]
+    #[prost(oneof = "transaction::TxnData", tags = "7, 8, 9, 10, 11")]
     pub txn_data: ::core::option::Option<transaction::TxnData>,
 }
 /// Nested message and enum types in `Transaction`.
@@ -59,6 +60,7 @@ pub mod transaction {
         BlockMetadata = 1,
         StateCheckpoint = 2,
         User = 3,
+        Validator = 4,
     }
     impl TransactionType {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -71,6 +73,7 @@ pub mod transaction {
                 TransactionType::BlockMetadata => "BLOCK_METADATA",
                 TransactionType::StateCheckpoint => "STATE_CHECKPOINT",
                 TransactionType::User => "USER",
+                TransactionType::Validator => "VALIDATOR",
             }
         }
     }
@@ -84,6 +87,8 @@ pub mod transaction {
         StateCheckpoint(super::StateCheckpointTransaction),
         #[prost(message, tag = "10")]
         User(super::UserTransaction),
+        #[prost(message, tag = "11")]
+        Validator(super::ValidatorTransaction),
     }
 }
 /// TransactionTrimmed is a real Transaction with most of the fields removed so that
@@ -127,6 +132,15 @@ pub struct UserTransaction {
     pub events: ::prost::alloc::vec::Vec<Event>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidatorTransaction {
+    #[prost(string, tag = "1")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag = "3")]
+    pub events: ::prost::alloc::vec::Vec<Event>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Event {
     #[prost(message, optional, tag = "1")]
     pub key: ::core::option::Option<EventKey>,
@@ -588,7 +602,7 @@ pub struct MoveStructTag {
 pub struct Signature {
     #[prost(enumeration = "signature::Type", tag = "1")]
     pub r#type: i32,
-    #[prost(oneof = "signature::Signature", tags = "2, 3, 4")]
+    #[prost(oneof = "signature::Signature", tags = "2, 3, 4, 5, 6, 7")]
     pub signature: ::core::option::Option<signature::Signature>,
 }
 /// Nested message and enum types in `Signature`.
@@ -599,6 +613,9 @@ pub mod signature {
         Ed25519 = 0,
         MultiEd25519 = 1,
         MultiAgent = 2,
+        WebAuthn = 3,
+        Keyless = 4,
+        MultiKey = 5,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -610,6 +627,9 @@ pub mod signature {
                 Type::Ed25519 => "ED25519",
                 Type::MultiEd25519 => "MULTI_ED25519",
                 Type::MultiAgent => "MULTI_AGENT",
+                Type::WebAuthn => "WEB_AUTHN",
+                Type::Keyless => "KEYLESS",
+                Type::MultiKey => "MULTI_KEY",
             }
         }
     }
@@ -621,6 +641,12 @@ pub mod signature {
         MultiEd25519(super::MultiEd25519Signature),
         #[prost(message, tag = "4")]
         MultiAgent(super::MultiAgentSignature),
+        #[prost(message, tag = "5")]
+        WebAuthn(super::WebAuthnSignature),
+        #[prost(message, tag = "6")]
+        Keyless(super::KeylessSignature),
+        #[prost(message, tag = "7")]
+        MultiKey(super::MultiKeySignature),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -642,6 +668,37 @@ pub struct MultiEd25519Signature {
     pub public_key_indices: ::prost::alloc::vec::Vec<u32>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebAuthnSignature {
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub authenticator_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub client_data_json: ::prost::alloc::vec::Vec<u8>,
+}
+/// The BCS-serialized keyless public key and signature
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeylessSignature {
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// The BCS-serialized public keys and signatures, of mixed schemes, of a multi-key account
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultiKeySignature {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub public_keys: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub signatures: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(uint32, tag = "3")]
+    pub signatures_required: u32,
+    #[prost(uint32, repeated, tag = "4")]
+    pub public_key_indices: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MultiAgentSignature {
     #[prost(message, optional, tag = "1")]
     pub sender: ::core::option::Option<AccountSignature>,
@@ -654,7 +711,7 @@ pub struct MultiAgentSignature {
 pub struct AccountSignature {
     #[prost(enumeration = "account_signature::Type", tag = "1")]
     pub r#type: i32,
-    #[prost(oneof = "account_signature::Signature", tags = "2, 3")]
+    #[prost(oneof = "account_signature::Signature", tags = "2, 3, 4, 5, 6")]
     pub signature: ::core::option::Option<account_signature::Signature>,
 }
 /// Nested message and enum types in `AccountSignature`.
@@ -664,6 +721,9 @@ pub mod account_signature {
     pub enum Type {
         Ed25519 = 0,
         MultiEd25519 = 1,
+        WebAuthn = 2,
+        Keyless = 3,
+        MultiKey = 4,
     }
     impl Type {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -674,6 +734,9 @@ pub mod account_signature {
             match self {
                 Type::Ed25519 => "ED25519",
                 Type::MultiEd25519 => "MULTI_ED25519",
+                Type::WebAuthn => "WEB_AUTHN",
+                Type::Keyless => "KEYLESS",
+                Type::MultiKey => "MULTI_KEY",
             }
         }
     }
@@ -683,6 +746,12 @@ pub mod account_signature {
         Ed25519(super::Ed25519Signature),
         #[prost(message, tag = "3")]
         MultiEd25519(super::MultiEd25519Signature),
+        #[prost(message, tag = "4")]
+        WebAuthn(super::WebAuthnSignature),
+        #[prost(message, tag = "5")]
+        Keyless(super::KeylessSignature),
+        #[prost(message, tag = "6")]
+        MultiKey(super::MultiKeySignature),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
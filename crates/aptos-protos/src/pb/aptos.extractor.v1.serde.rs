@@ -32,6 +32,15 @@ impl serde::Serialize for AccountSignature {
                 account_signature::Signature::MultiEd25519(v) => {
                     struct_ser.serialize_field("multiEd25519", v)?;
                 }
+                account_signature::Signature::WebAuthn(v) => {
+                    struct_ser.serialize_field("webAuthn", v)?;
+                }
+                account_signature::Signature::Keyless(v) => {
+                    struct_ser.serialize_field("keyless", v)?;
+                }
+                account_signature::Signature::MultiKey(v) => {
+                    struct_ser.serialize_field("multiKey", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -43,13 +52,23 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["type", "ed25519", "multiEd25519"];
+        const FIELDS: &[&str] = &[
+            "type",
+            "ed25519",
+            "multiEd25519",
+            "webAuthn",
+            "keyless",
+            "multiKey",
+        ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Type,
             Ed25519,
             MultiEd25519,
+            WebAuthn,
+            Keyless,
+            MultiKey,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -77,6 +96,9 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                             "type" => Ok(GeneratedField::Type),
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" => Ok(GeneratedField::MultiEd25519),
+                            "webAuthn" => Ok(GeneratedField::WebAuthn),
+                            "keyless" => Ok(GeneratedField::Keyless),
+                            "multiKey" => Ok(GeneratedField::MultiKey),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -121,6 +143,27 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                                 map.next_value()?,
                             ));
                         }
+                        GeneratedField::WebAuthn => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("webAuthn"));
+                            }
+                            signature__ =
+                                Some(account_signature::Signature::WebAuthn(map.next_value()?));
+                        }
+                        GeneratedField::Keyless => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("keyless"));
+                            }
+                            signature__ =
+                                Some(account_signature::Signature::Keyless(map.next_value()?));
+                        }
+                        GeneratedField::MultiKey => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("multiKey"));
+                            }
+                            signature__ =
+                                Some(account_signature::Signature::MultiKey(map.next_value()?));
+                        }
                     }
                 }
                 Ok(AccountSignature {
@@ -145,6 +188,9 @@ impl serde::Serialize for account_signature::Type {
         let variant = match self {
             Self::Ed25519 => "ED25519",
             Self::MultiEd25519 => "MULTI_ED25519",
+            Self::WebAuthn => "WEB_AUTHN",
+            Self::Keyless => "KEYLESS",
+            Self::MultiKey => "MULTI_KEY",
         };
         serializer.serialize_str(variant)
     }
@@ -155,7 +201,13 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519"];
+        const FIELDS: &[&str] = &[
+            "ED25519",
+            "MULTI_ED25519",
+            "WEB_AUTHN",
+            "KEYLESS",
+            "MULTI_KEY",
+        ];
 
         struct GeneratedVisitor;
 
@@ -199,6 +251,9 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
                 match value {
                     "ED25519" => Ok(account_signature::Type::Ed25519),
                     "MULTI_ED25519" => Ok(account_signature::Type::MultiEd25519),
+                    "WEB_AUTHN" => Ok(account_signature::Type::WebAuthn),
+                    "KEYLESS" => Ok(account_signature::Type::Keyless),
+                    "MULTI_KEY" => Ok(account_signature::Type::MultiKey),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -1941,6 +1996,131 @@ impl<'de> serde::Deserialize<'de> for GenesisTransaction {
         )
     }
 }
+impl serde::Serialize for KeylessSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.KeylessSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field(
+                "publicKey",
+                pbjson::private::base64::encode(&self.public_key).as_str(),
+            )?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field(
+                "signature",
+                pbjson::private::base64::encode(&self.signature).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for KeylessSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["publicKey", "signature"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = KeylessSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.KeylessSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<KeylessSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(KeylessSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.KeylessSignature",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
 impl serde::Serialize for ModuleBundlePayload {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4356,7 +4536,7 @@ impl<'de> serde::Deserialize<'de> for MultiEd25519Signature {
         )
     }
 }
-impl serde::Serialize for ScriptPayload {
+impl serde::Serialize for MultiKeySignature {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -4364,42 +4544,68 @@ impl serde::Serialize for ScriptPayload {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if self.code.is_some() {
+        if !self.public_keys.is_empty() {
             len += 1;
         }
-        if !self.type_arguments.is_empty() {
+        if !self.signatures.is_empty() {
             len += 1;
         }
-        if !self.arguments.is_empty() {
+        if self.signatures_required != 0 {
+            len += 1;
+        }
+        if !self.public_key_indices.is_empty() {
             len += 1;
         }
         let mut struct_ser =
-            serializer.serialize_struct("aptos.extractor.v1.ScriptPayload", len)?;
-        if let Some(v) = self.code.as_ref() {
-            struct_ser.serialize_field("code", v)?;
+            serializer.serialize_struct("aptos.extractor.v1.MultiKeySignature", len)?;
+        if !self.public_keys.is_empty() {
+            struct_ser.serialize_field(
+                "publicKeys",
+                &self
+                    .public_keys
+                    .iter()
+                    .map(pbjson::private::base64::encode)
+                    .collect::<Vec<_>>(),
+            )?;
         }
-        if !self.type_arguments.is_empty() {
-            struct_ser.serialize_field("typeArguments", &self.type_arguments)?;
+        if !self.signatures.is_empty() {
+            struct_ser.serialize_field(
+                "signatures",
+                &self
+                    .signatures
+                    .iter()
+                    .map(pbjson::private::base64::encode)
+                    .collect::<Vec<_>>(),
+            )?;
         }
-        if !self.arguments.is_empty() {
-            struct_ser.serialize_field("arguments", &self.arguments)?;
+        if self.signatures_required != 0 {
+            struct_ser.serialize_field("signaturesRequired", &self.signatures_required)?;
+        }
+        if !self.public_key_indices.is_empty() {
+            struct_ser.serialize_field("publicKeyIndices", &self.public_key_indices)?;
         }
         struct_ser.end()
     }
 }
-impl<'de> serde::Deserialize<'de> for ScriptPayload {
+impl<'de> serde::Deserialize<'de> for MultiKeySignature {
     #[allow(deprecated)]
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["code", "typeArguments", "arguments"];
+        const FIELDS: &[&str] = &[
+            "publicKeys",
+            "signatures",
+            "signaturesRequired",
+            "publicKeyIndices",
+        ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
-            Code,
-            TypeArguments,
-            Arguments,
+            PublicKeys,
+            Signatures,
+            SignaturesRequired,
+            PublicKeyIndices,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4424,9 +4630,10 @@ impl<'de> serde::Deserialize<'de> for ScriptPayload {
                         E: serde::de::Error,
                     {
                         match value {
-                            "code" => Ok(GeneratedField::Code),
-                            "typeArguments" => Ok(GeneratedField::TypeArguments),
-                            "arguments" => Ok(GeneratedField::Arguments),
+                            "publicKeys" => Ok(GeneratedField::PublicKeys),
+                            "signatures" => Ok(GeneratedField::Signatures),
+                            "signaturesRequired" => Ok(GeneratedField::SignaturesRequired),
+                            "publicKeyIndices" => Ok(GeneratedField::PublicKeyIndices),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4436,56 +4643,84 @@ impl<'de> serde::Deserialize<'de> for ScriptPayload {
         }
         struct GeneratedVisitor;
         impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
-            type Value = ScriptPayload;
+            type Value = MultiKeySignature;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("struct aptos.extractor.v1.ScriptPayload")
+                formatter.write_str("struct aptos.extractor.v1.MultiKeySignature")
             }
 
-            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScriptPayload, V::Error>
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<MultiKeySignature, V::Error>
             where
                 V: serde::de::MapAccess<'de>,
             {
-                let mut code__ = None;
-                let mut type_arguments__ = None;
-                let mut arguments__ = None;
+                let mut public_keys__ = None;
+                let mut signatures__ = None;
+                let mut signatures_required__ = None;
+                let mut public_key_indices__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
-                        GeneratedField::Code => {
-                            if code__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("code"));
+                        GeneratedField::PublicKeys => {
+                            if public_keys__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKeys"));
                             }
-                            code__ = Some(map.next_value()?);
+                            public_keys__ = Some(
+                                map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter()
+                                    .map(|x| x.0)
+                                    .collect(),
+                            );
                         }
-                        GeneratedField::TypeArguments => {
-                            if type_arguments__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("typeArguments"));
+                        GeneratedField::Signatures => {
+                            if signatures__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signatures"));
                             }
-                            type_arguments__ = Some(map.next_value()?);
+                            signatures__ = Some(
+                                map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter()
+                                    .map(|x| x.0)
+                                    .collect(),
+                            );
                         }
-                        GeneratedField::Arguments => {
-                            if arguments__.is_some() {
-                                return Err(serde::de::Error::duplicate_field("arguments"));
+                        GeneratedField::SignaturesRequired => {
+                            if signatures_required__.is_some() {
+                                return Err(serde::de::Error::duplicate_field(
+                                    "signaturesRequired",
+                                ));
                             }
-                            arguments__ = Some(map.next_value()?);
+                            signatures_required__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::PublicKeyIndices => {
+                            if public_key_indices__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKeyIndices"));
+                            }
+                            public_key_indices__ = Some(
+                                map.next_value::<Vec<::pbjson::private::NumberDeserialize<_>>>()?
+                                    .into_iter()
+                                    .map(|x| x.0)
+                                    .collect(),
+                            );
                         }
                     }
                 }
-                Ok(ScriptPayload {
-                    code: code__,
-                    type_arguments: type_arguments__.unwrap_or_default(),
-                    arguments: arguments__.unwrap_or_default(),
+                Ok(MultiKeySignature {
+                    public_keys: public_keys__.unwrap_or_default(),
+                    signatures: signatures__.unwrap_or_default(),
+                    signatures_required: signatures_required__.unwrap_or_default(),
+                    public_key_indices: public_key_indices__.unwrap_or_default(),
                 })
             }
         }
         deserializer.deserialize_struct(
-            "aptos.extractor.v1.ScriptPayload",
+            "aptos.extractor.v1.MultiKeySignature",
             FIELDS,
             GeneratedVisitor,
         )
     }
 }
-impl serde::Serialize for ScriptWriteSet {
+impl serde::Serialize for ScriptPayload {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
@@ -4493,7 +4728,136 @@ impl serde::Serialize for ScriptWriteSet {
     {
         use serde::ser::SerializeStruct;
         let mut len = 0;
-        if !self.execute_as.is_empty() {
+        if self.code.is_some() {
+            len += 1;
+        }
+        if !self.type_arguments.is_empty() {
+            len += 1;
+        }
+        if !self.arguments.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.ScriptPayload", len)?;
+        if let Some(v) = self.code.as_ref() {
+            struct_ser.serialize_field("code", v)?;
+        }
+        if !self.type_arguments.is_empty() {
+            struct_ser.serialize_field("typeArguments", &self.type_arguments)?;
+        }
+        if !self.arguments.is_empty() {
+            struct_ser.serialize_field("arguments", &self.arguments)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ScriptPayload {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["code", "typeArguments", "arguments"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Code,
+            TypeArguments,
+            Arguments,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "code" => Ok(GeneratedField::Code),
+                            "typeArguments" => Ok(GeneratedField::TypeArguments),
+                            "arguments" => Ok(GeneratedField::Arguments),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ScriptPayload;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.ScriptPayload")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScriptPayload, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut code__ = None;
+                let mut type_arguments__ = None;
+                let mut arguments__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Code => {
+                            if code__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("code"));
+                            }
+                            code__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::TypeArguments => {
+                            if type_arguments__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("typeArguments"));
+                            }
+                            type_arguments__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Arguments => {
+                            if arguments__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("arguments"));
+                            }
+                            arguments__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ScriptPayload {
+                    code: code__,
+                    type_arguments: type_arguments__.unwrap_or_default(),
+                    arguments: arguments__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.ScriptPayload",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for ScriptWriteSet {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.execute_as.is_empty() {
             len += 1;
         }
         if self.script.is_some() {
@@ -4630,6 +4994,15 @@ impl serde::Serialize for Signature {
                 signature::Signature::MultiAgent(v) => {
                     struct_ser.serialize_field("multiAgent", v)?;
                 }
+                signature::Signature::WebAuthn(v) => {
+                    struct_ser.serialize_field("webAuthn", v)?;
+                }
+                signature::Signature::Keyless(v) => {
+                    struct_ser.serialize_field("keyless", v)?;
+                }
+                signature::Signature::MultiKey(v) => {
+                    struct_ser.serialize_field("multiKey", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -4641,7 +5014,15 @@ impl<'de> serde::Deserialize<'de> for Signature {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["type", "ed25519", "multiEd25519", "multiAgent"];
+        const FIELDS: &[&str] = &[
+            "type",
+            "ed25519",
+            "multiEd25519",
+            "multiAgent",
+            "webAuthn",
+            "keyless",
+            "multiKey",
+        ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
@@ -4649,6 +5030,9 @@ impl<'de> serde::Deserialize<'de> for Signature {
             Ed25519,
             MultiEd25519,
             MultiAgent,
+            WebAuthn,
+            Keyless,
+            MultiKey,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4677,6 +5061,9 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" => Ok(GeneratedField::MultiEd25519),
                             "multiAgent" => Ok(GeneratedField::MultiAgent),
+                            "webAuthn" => Ok(GeneratedField::WebAuthn),
+                            "keyless" => Ok(GeneratedField::Keyless),
+                            "multiKey" => Ok(GeneratedField::MultiKey),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4725,6 +5112,24 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             }
                             signature__ = Some(signature::Signature::MultiAgent(map.next_value()?));
                         }
+                        GeneratedField::WebAuthn => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("webAuthn"));
+                            }
+                            signature__ = Some(signature::Signature::WebAuthn(map.next_value()?));
+                        }
+                        GeneratedField::Keyless => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("keyless"));
+                            }
+                            signature__ = Some(signature::Signature::Keyless(map.next_value()?));
+                        }
+                        GeneratedField::MultiKey => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("multiKey"));
+                            }
+                            signature__ = Some(signature::Signature::MultiKey(map.next_value()?));
+                        }
                     }
                 }
                 Ok(Signature {
@@ -4746,6 +5151,9 @@ impl serde::Serialize for signature::Type {
             Self::Ed25519 => "ED25519",
             Self::MultiEd25519 => "MULTI_ED25519",
             Self::MultiAgent => "MULTI_AGENT",
+            Self::WebAuthn => "WEB_AUTHN",
+            Self::Keyless => "KEYLESS",
+            Self::MultiKey => "MULTI_KEY",
         };
         serializer.serialize_str(variant)
     }
@@ -4756,7 +5164,14 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["ED25519", "MULTI_ED25519", "MULTI_AGENT"];
+        const FIELDS: &[&str] = &[
+            "ED25519",
+            "MULTI_ED25519",
+            "MULTI_AGENT",
+            "WEB_AUTHN",
+            "KEYLESS",
+            "MULTI_KEY",
+        ];
 
         struct GeneratedVisitor;
 
@@ -4801,6 +5216,9 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
                     "ED25519" => Ok(signature::Type::Ed25519),
                     "MULTI_ED25519" => Ok(signature::Type::MultiEd25519),
                     "MULTI_AGENT" => Ok(signature::Type::MultiAgent),
+                    "WEB_AUTHN" => Ok(signature::Type::WebAuthn),
+                    "KEYLESS" => Ok(signature::Type::Keyless),
+                    "MULTI_KEY" => Ok(signature::Type::MultiKey),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -4955,6 +5373,9 @@ impl serde::Serialize for Transaction {
                 transaction::TxnData::User(v) => {
                     struct_ser.serialize_field("user", v)?;
                 }
+                transaction::TxnData::Validator(v) => {
+                    struct_ser.serialize_field("validator", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -4977,6 +5398,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
             "genesis",
             "stateCheckpoint",
             "user",
+            "validator",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -4991,6 +5413,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
             Genesis,
             StateCheckpoint,
             User,
+            Validator,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -5025,6 +5448,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
                             "genesis" => Ok(GeneratedField::Genesis),
                             "stateCheckpoint" => Ok(GeneratedField::StateCheckpoint),
                             "user" => Ok(GeneratedField::User),
+                            "validator" => Ok(GeneratedField::Validator),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -5125,6 +5549,12 @@ impl<'de> serde::Deserialize<'de> for Transaction {
                             }
                             txn_data__ = Some(transaction::TxnData::User(map.next_value()?));
                         }
+                        GeneratedField::Validator => {
+                            if txn_data__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("validator"));
+                            }
+                            txn_data__ = Some(transaction::TxnData::Validator(map.next_value()?));
+                        }
                     }
                 }
                 Ok(Transaction {
@@ -5152,6 +5582,7 @@ impl serde::Serialize for transaction::TransactionType {
             Self::BlockMetadata => "BLOCK_METADATA",
             Self::StateCheckpoint => "STATE_CHECKPOINT",
             Self::User => "USER",
+            Self::Validator => "VALIDATOR",
         };
         serializer.serialize_str(variant)
     }
@@ -5162,7 +5593,13 @@ impl<'de> serde::Deserialize<'de> for transaction::TransactionType {
     where
         D: serde::Deserializer<'de>,
     {
-        const FIELDS: &[&str] = &["GENESIS", "BLOCK_METADATA", "STATE_CHECKPOINT", "USER"];
+        const FIELDS: &[&str] = &[
+            "GENESIS",
+            "BLOCK_METADATA",
+            "STATE_CHECKPOINT",
+            "USER",
+            "VALIDATOR",
+        ];
 
         struct GeneratedVisitor;
 
@@ -5208,6 +5645,7 @@ impl<'de> serde::Deserialize<'de> for transaction::TransactionType {
                     "BLOCK_METADATA" => Ok(transaction::TransactionType::BlockMetadata),
                     "STATE_CHECKPOINT" => Ok(transaction::TransactionType::StateCheckpoint),
                     "USER" => Ok(transaction::TransactionType::User),
+                    "VALIDATOR" => Ok(transaction::TransactionType::Validator),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -6162,6 +6600,315 @@ impl<'de> serde::Deserialize<'de> for UserTransactionRequest {
         )
     }
 }
+impl serde::Serialize for ValidatorTransaction {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.kind.is_empty() {
+            len += 1;
+        }
+        if !self.payload.is_empty() {
+            len += 1;
+        }
+        if !self.events.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.ValidatorTransaction", len)?;
+        if !self.kind.is_empty() {
+            struct_ser.serialize_field("kind", &self.kind)?;
+        }
+        if !self.payload.is_empty() {
+            struct_ser.serialize_field(
+                "payload",
+                pbjson::private::base64::encode(&self.payload).as_str(),
+            )?;
+        }
+        if !self.events.is_empty() {
+            struct_ser.serialize_field("events", &self.events)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ValidatorTransaction {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["kind", "payload", "events"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Kind,
+            Payload,
+            Events,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "kind" => Ok(GeneratedField::Kind),
+                            "payload" => Ok(GeneratedField::Payload),
+                            "events" => Ok(GeneratedField::Events),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ValidatorTransaction;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.ValidatorTransaction")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ValidatorTransaction, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut kind__ = None;
+                let mut payload__ = None;
+                let mut events__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Kind => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Payload => {
+                            if payload__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("payload"));
+                            }
+                            payload__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Events => {
+                            if events__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("events"));
+                            }
+                            events__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ValidatorTransaction {
+                    kind: kind__.unwrap_or_default(),
+                    payload: payload__.unwrap_or_default(),
+                    events: events__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.ValidatorTransaction",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for WebAuthnSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        if !self.authenticator_data.is_empty() {
+            len += 1;
+        }
+        if !self.client_data_json.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.extractor.v1.WebAuthnSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field(
+                "publicKey",
+                pbjson::private::base64::encode(&self.public_key).as_str(),
+            )?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field(
+                "signature",
+                pbjson::private::base64::encode(&self.signature).as_str(),
+            )?;
+        }
+        if !self.authenticator_data.is_empty() {
+            struct_ser.serialize_field(
+                "authenticatorData",
+                pbjson::private::base64::encode(&self.authenticator_data).as_str(),
+            )?;
+        }
+        if !self.client_data_json.is_empty() {
+            struct_ser.serialize_field(
+                "clientDataJson",
+                pbjson::private::base64::encode(&self.client_data_json).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for WebAuthnSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "publicKey",
+            "signature",
+            "authenticatorData",
+            "clientDataJson",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+            AuthenticatorData,
+            ClientDataJson,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            "authenticatorData" => Ok(GeneratedField::AuthenticatorData),
+                            "clientDataJson" => Ok(GeneratedField::ClientDataJson),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = WebAuthnSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.extractor.v1.WebAuthnSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<WebAuthnSignature, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                let mut authenticator_data__ = None;
+                let mut client_data_json__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::AuthenticatorData => {
+                            if authenticator_data__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("authenticatorData"));
+                            }
+                            authenticator_data__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::ClientDataJson => {
+                            if client_data_json__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientDataJson"));
+                            }
+                            client_data_json__ = Some(
+                                map.next_value::<::pbjson::private::BytesDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(WebAuthnSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                    authenticator_data: authenticator_data__.unwrap_or_default(),
+                    client_data_json: client_data_json__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.extractor.v1.WebAuthnSignature",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
 impl serde::Serialize for WriteModule {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
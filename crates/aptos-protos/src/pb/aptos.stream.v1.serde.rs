@@ -0,0 +1,434 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// @generated
+impl serde::Serialize for StreamTransactionsRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.starting_version != 0 {
+            len += 1;
+        }
+        if self.filter.is_some() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.stream.v1.StreamTransactionsRequest", len)?;
+        if self.starting_version != 0 {
+            struct_ser.serialize_field(
+                "startingVersion",
+                ToString::to_string(&self.starting_version).as_str(),
+            )?;
+        }
+        if let Some(v) = self.filter.as_ref() {
+            struct_ser.serialize_field("filter", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for StreamTransactionsRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["startingVersion", "filter"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            StartingVersion,
+            Filter,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "startingVersion" => Ok(GeneratedField::StartingVersion),
+                            "filter" => Ok(GeneratedField::Filter),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = StreamTransactionsRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.stream.v1.StreamTransactionsRequest")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<StreamTransactionsRequest, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut starting_version__ = None;
+                let mut filter__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::StartingVersion => {
+                            if starting_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("startingVersion"));
+                            }
+                            starting_version__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Filter => {
+                            if filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("filter"));
+                            }
+                            filter__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(StreamTransactionsRequest {
+                    starting_version: starting_version__.unwrap_or_default(),
+                    filter: filter__,
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.stream.v1.StreamTransactionsRequest",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for StreamTransactionsResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.chain_id != 0 {
+            len += 1;
+        }
+        if !self.transactions.is_empty() {
+            len += 1;
+        }
+        if self.next_version != 0 {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.stream.v1.StreamTransactionsResponse", len)?;
+        if self.chain_id != 0 {
+            struct_ser.serialize_field("chainId", &self.chain_id)?;
+        }
+        if !self.transactions.is_empty() {
+            struct_ser.serialize_field("transactions", &self.transactions)?;
+        }
+        if self.next_version != 0 {
+            struct_ser.serialize_field(
+                "nextVersion",
+                ToString::to_string(&self.next_version).as_str(),
+            )?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for StreamTransactionsResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["chainId", "transactions", "nextVersion"];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ChainId,
+            Transactions,
+            NextVersion,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "chainId" => Ok(GeneratedField::ChainId),
+                            "transactions" => Ok(GeneratedField::Transactions),
+                            "nextVersion" => Ok(GeneratedField::NextVersion),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = StreamTransactionsResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.stream.v1.StreamTransactionsResponse")
+            }
+
+            fn visit_map<V>(
+                self,
+                mut map: V,
+            ) -> std::result::Result<StreamTransactionsResponse, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut chain_id__ = None;
+                let mut transactions__ = None;
+                let mut next_version__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::ChainId => {
+                            if chain_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("chainId"));
+                            }
+                            chain_id__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                        GeneratedField::Transactions => {
+                            if transactions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactions"));
+                            }
+                            transactions__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::NextVersion => {
+                            if next_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nextVersion"));
+                            }
+                            next_version__ = Some(
+                                map.next_value::<::pbjson::private::NumberDeserialize<_>>()?
+                                    .0,
+                            );
+                        }
+                    }
+                }
+                Ok(StreamTransactionsResponse {
+                    chain_id: chain_id__.unwrap_or_default(),
+                    transactions: transactions__.unwrap_or_default(),
+                    next_version: next_version__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.stream.v1.StreamTransactionsResponse",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
+impl serde::Serialize for TransactionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.transaction_types.is_empty() {
+            len += 1;
+        }
+        if !self.sender_addresses.is_empty() {
+            len += 1;
+        }
+        if !self.entry_functions.is_empty() {
+            len += 1;
+        }
+        if !self.event_types.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser =
+            serializer.serialize_struct("aptos.stream.v1.TransactionFilter", len)?;
+        if !self.transaction_types.is_empty() {
+            let v = self
+                .transaction_types
+                .iter()
+                .cloned()
+                .map(|v| {
+                    super::super::extractor::v1::transaction::TransactionType::from_i32(v)
+                        .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", v)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            struct_ser.serialize_field("transactionTypes", &v)?;
+        }
+        if !self.sender_addresses.is_empty() {
+            struct_ser.serialize_field("senderAddresses", &self.sender_addresses)?;
+        }
+        if !self.entry_functions.is_empty() {
+            struct_ser.serialize_field("entryFunctions", &self.entry_functions)?;
+        }
+        if !self.event_types.is_empty() {
+            struct_ser.serialize_field("eventTypes", &self.event_types)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TransactionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "transactionTypes",
+            "senderAddresses",
+            "entryFunctions",
+            "eventTypes",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            TransactionTypes,
+            SenderAddresses,
+            EntryFunctions,
+            EventTypes,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "transactionTypes" => Ok(GeneratedField::TransactionTypes),
+                            "senderAddresses" => Ok(GeneratedField::SenderAddresses),
+                            "entryFunctions" => Ok(GeneratedField::EntryFunctions),
+                            "eventTypes" => Ok(GeneratedField::EventTypes),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TransactionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.stream.v1.TransactionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<TransactionFilter, V::Error>
+            where
+                V: serde::de::MapAccess<'de>,
+            {
+                let mut transaction_types__ = None;
+                let mut sender_addresses__ = None;
+                let mut entry_functions__ = None;
+                let mut event_types__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::TransactionTypes => {
+                            if transaction_types__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionTypes"));
+                            }
+                            transaction_types__ =
+                                Some(
+                                    map.next_value::<Vec<
+                                        super::super::extractor::v1::transaction::TransactionType,
+                                    >>()?
+                                    .into_iter()
+                                    .map(|x| x as i32)
+                                    .collect(),
+                                );
+                        }
+                        GeneratedField::SenderAddresses => {
+                            if sender_addresses__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("senderAddresses"));
+                            }
+                            sender_addresses__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::EntryFunctions => {
+                            if entry_functions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("entryFunctions"));
+                            }
+                            entry_functions__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::EventTypes => {
+                            if event_types__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("eventTypes"));
+                            }
+                            event_types__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(TransactionFilter {
+                    transaction_types: transaction_types__.unwrap_or_default(),
+                    sender_addresses: sender_addresses__.unwrap_or_default(),
+                    entry_functions: entry_functions__.unwrap_or_default(),
+                    event_types: event_types__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct(
+            "aptos.stream.v1.TransactionFilter",
+            FIELDS,
+            GeneratedVisitor,
+        )
+    }
+}
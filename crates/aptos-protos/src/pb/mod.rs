@@ -3,6 +3,14 @@
 
 // @generated
 pub mod aptos {
+    pub mod admin {
+        // @@protoc_insertion_point(attribute:aptos.admin.v1)
+        pub mod v1 {
+            include!("aptos.admin.v1.rs");
+            include!("aptos.admin.v1.tonic.rs");
+            // @@protoc_insertion_point(aptos.admin.v1)
+        }
+    }
     pub mod block_output {
         // @@protoc_insertion_point(attribute:aptos.block_output.v1)
         pub mod v1 {
@@ -17,6 +25,22 @@ pub mod aptos {
             // @@protoc_insertion_point(aptos.extractor.v1)
         }
     }
+    pub mod indexer {
+        // @@protoc_insertion_point(attribute:aptos.indexer.v1)
+        pub mod v1 {
+            include!("aptos.indexer.v1.rs");
+            include!("aptos.indexer.v1.tonic.rs");
+            // @@protoc_insertion_point(aptos.indexer.v1)
+        }
+    }
+    pub mod stream {
+        // @@protoc_insertion_point(attribute:aptos.stream.v1)
+        pub mod v1 {
+            include!("aptos.stream.v1.rs");
+            include!("aptos.stream.v1.tonic.rs");
+            // @@protoc_insertion_point(aptos.stream.v1)
+        }
+    }
     pub mod util {
         // @@protoc_insertion_point(attribute:aptos.util.timestamp)
         pub mod timestamp {
@@ -10,25 +10,31 @@ pub mod response;
 pub use response::Response;
 pub mod state;
 pub mod types;
+pub mod view;
 
 pub use aptos_api_types::{
     self, IndexResponse, MoveModuleBytecode, PendingTransaction, Transaction,
 };
 pub use state::State;
 pub use types::{Account, Resource};
+pub use view::ViewArguments;
 
 use crate::aptos::{AptosVersion, Balance};
 use anyhow::{anyhow, Result};
 use aptos_api_types::{
     mime_types::BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, AptosError, Block, HexEncodedBytes,
-    VersionedEvent,
+    MoveModuleAbi, MoveType, VersionedEvent,
 };
 use aptos_crypto::HashValue;
 use aptos_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
     account_config::{NewBlockEvent, CORE_CODE_ADDRESS},
+    resource_group::{decode_resource_group, ResourceGroup},
+    state_store::state_key::StateKey,
     transaction::SignedTransaction,
 };
+use move_deps::move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
 use poem_openapi::types::ParseFromJSON;
 use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -387,6 +393,18 @@ impl Client {
         self.json(response).await
     }
 
+    /// Gets the ABI of a module, e.g. to generate bindings for it with `aptos_sdk::codegen`
+    pub async fn get_account_module_abi(
+        &self,
+        address: AccountAddress,
+        module_name: &str,
+    ) -> Result<Response<MoveModuleAbi>> {
+        let url = self.build_path(&format!("accounts/{}/module/{}/abi", address, module_name))?;
+
+        let response = self.inner.get(url).send().await?;
+        self.json(response).await
+    }
+
     pub async fn get_account_events(
         &self,
         address: AccountAddress,
@@ -507,6 +525,93 @@ impl Client {
         self.json(response).await
     }
 
+    /// Calls the view function `function` of `module`, and decodes its return values into `Ret`.
+    /// Several return values are decoded as a tuple, as the BCS encoding of a tuple is the
+    /// encodings of its elements one after the other.
+    pub async fn view<Ret: DeserializeOwned>(
+        &self,
+        module: &ModuleId,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: impl ViewArguments,
+    ) -> Result<Response<Ret>> {
+        let url = self.build_path("view")?;
+        let data = json!({
+            "function": format!("{}::{}::{}", module.address().to_hex_literal(), module.name(), function),
+            "type_arguments": type_args.into_iter().map(MoveType::from).collect::<Vec<_>>(),
+            "arguments": args
+                .encode()?
+                .into_iter()
+                .map(HexEncodedBytes::from)
+                .collect::<Vec<_>>(),
+        });
+
+        let response = self.inner.post(url).json(&data).send().await?;
+        let return_values: Response<Vec<HexEncodedBytes>> = self.json(response).await?;
+        return_values.and_then(|return_values| {
+            let bytes: Vec<u8> = return_values
+                .into_iter()
+                .flat_map(|value| value.0)
+                .collect();
+            Ok(bcs::from_bytes(&bytes)?)
+        })
+    }
+
+    /// Gets the raw bytes stored under `state_key` at `version`, or `None` if nothing is stored
+    /// there. These are the bytes the VM reads, so they can be used to replay transactions.
+    pub async fn get_raw_state_value(
+        &self,
+        state_key: &StateKey,
+        version: u64,
+    ) -> Result<Option<Vec<u8>>> {
+        let url = self.build_path(&format!(
+            "experimental/state_values/raw?ledger_version={}",
+            version
+        ))?;
+        let data = json!({
+            "key": HexEncodedBytes::from(bcs::to_bytes(state_key)?),
+        });
+
+        let response = self.inner.post(url).json(&data).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let bytes: Response<HexEncodedBytes> = self.json(response).await?;
+        Ok(Some(bytes.into_inner().into()))
+    }
+
+    /// Gets the members of the resource group `group_tag` of `address` at `version`, by tag, or
+    /// `None` if the account has no such group.
+    pub async fn get_raw_resource_group(
+        &self,
+        address: AccountAddress,
+        group_tag: &StructTag,
+        version: u64,
+    ) -> Result<Option<ResourceGroup>> {
+        let state_key = StateKey::AccessPath(AccessPath::resource_group_access_path(
+            address,
+            group_tag.clone(),
+        ));
+        self.get_raw_state_value(&state_key, version)
+            .await?
+            .map(|bytes| decode_resource_group(group_tag, &bytes))
+            .transpose()
+    }
+
+    /// Gets the transaction committed at `version`, as executed by the VM.
+    pub async fn get_raw_transaction_by_version(
+        &self,
+        version: u64,
+    ) -> Result<Response<aptos_types::transaction::Transaction>> {
+        let url = self.build_path(&format!(
+            "experimental/transactions/by_version/{}/raw",
+            version
+        ))?;
+        let response = self.inner.get(url).send().await?;
+        let bytes: Response<HexEncodedBytes> = self.json(response).await?;
+        bytes.and_then(|bytes| Ok(bcs::from_bytes(bytes.inner())?))
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> Result<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address))?;
         let response = self.inner.get(url).send().await?;
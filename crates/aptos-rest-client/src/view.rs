@@ -0,0 +1,45 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// The arguments of a view function call, each BCS encoded.  Implemented for tuples of
+/// serializable values, one per argument, e.g. `(address, 10u64)`, or `(address,)` for a single
+/// argument, and for already encoded arguments.
+pub trait ViewArguments {
+    fn encode(self) -> Result<Vec<Vec<u8>>>;
+}
+
+impl ViewArguments for Vec<Vec<u8>> {
+    fn encode(self) -> Result<Vec<Vec<u8>>> {
+        Ok(self)
+    }
+}
+
+impl ViewArguments for () {
+    fn encode(self) -> Result<Vec<Vec<u8>>> {
+        Ok(vec![])
+    }
+}
+
+macro_rules! impl_view_arguments {
+    ($($name:ident),*) => {
+        impl<$($name: Serialize),*> ViewArguments for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn encode(self) -> Result<Vec<Vec<u8>>> {
+                let ($($name,)*) = self;
+                Ok(vec![$(bcs::to_bytes(&$name)?),*])
+            }
+        }
+    };
+}
+
+impl_view_arguments!(A);
+impl_view_arguments!(A, B);
+impl_view_arguments!(A, B, C);
+impl_view_arguments!(A, B, C, D);
+impl_view_arguments!(A, B, C, D, E);
+impl_view_arguments!(A, B, C, D, E, F);
+impl_view_arguments!(A, B, C, D, E, F, G);
+impl_view_arguments!(A, B, C, D, E, F, G, H);
@@ -17,8 +17,9 @@ use crate::{
     },
     error::{ApiError, ApiResult},
     types::{
-        coin_store_resource_identifier, AccountBalanceRequest, AccountBalanceResponse, Amount,
-        Currency, CurrencyMetadata,
+        coin_store_resource_identifier, deposit_events_field_identifier,
+        withdraw_events_field_identifier, AccountBalanceRequest, AccountBalanceResponse, Amount,
+        CoinEvent, Currency, CurrencyMetadata,
     },
     RosettaContext,
 };
@@ -34,10 +35,17 @@ use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, RwLock},
+    convert::TryFrom,
+    sync::RwLock,
 };
 use warp::Filter;
 
+/// Characters to percent encode in resource types used in URL paths
+const ENCODE_CHARS: &AsciiSet = &CONTROLS.add(b'<').add(b'>');
+
+/// Number of events or transactions to read at a time when rebuilding historical balances
+const HISTORY_PAGE_SIZE: u16 = 100;
+
 /// Account routes e.g. balance
 pub fn routes(
     server_context: RosettaContext,
@@ -88,14 +96,7 @@ async fn account_balance(
     )
     .await?;
 
-    let amounts = convert_balances_to_amounts(
-        &rest_client,
-        server_context.coin_cache.clone(),
-        request.currencies,
-        balances,
-        balance_version,
-    )
-    .await?;
+    let amounts = convert_balances_to_amounts(&server_context, request.currencies, balances)?;
 
     Ok(AccountBalanceResponse {
         block_identifier: block_info.block_id,
@@ -104,22 +105,17 @@ async fn account_balance(
     })
 }
 
-/// Lookup currencies and convert them to Rosetta types
-async fn convert_balances_to_amounts(
-    rest_client: &aptos_rest_client::Client,
-    coin_cache: Arc<CoinCache>,
+/// Lookup currencies and convert them to Rosetta types, skipping coins that aren't supported
+fn convert_balances_to_amounts(
+    server_context: &RosettaContext,
     maybe_filter_currencies: Option<Vec<Currency>>,
     balances: HashMap<TypeTag, Balance>,
-    balance_version: u64,
 ) -> ApiResult<Vec<Amount>> {
     let mut amounts = Vec::new();
 
     // Lookup coins, and fill in currency codes
     for (coin, balance) in balances {
-        if let Some(currency) = coin_cache
-            .get_currency(rest_client, coin, Some(balance_version))
-            .await?
-        {
+        if let Some(currency) = server_context.currency(&coin) {
             amounts.push(Amount {
                 value: balance.coin.value.0.to_string(),
                 currency,
@@ -129,6 +125,9 @@ async fn convert_balances_to_amounts(
 
     // Filter based on requested currencies
     if let Some(currencies) = maybe_filter_currencies {
+        for currency in currencies.iter() {
+            server_context.coin_type(currency)?;
+        }
         let mut currencies: HashSet<Currency> = currencies.into_iter().collect();
         // Remove extra currencies not requested
         amounts = amounts
@@ -212,6 +211,39 @@ async fn get_balances(
         // Retrieve balances
         Ok((sequence_number, balances))
     } else {
+        // The state at this version may have been pruned, in which case the balance can still be
+        // rebuilt from the events and transactions, which are kept for longer
+        get_native_balance_from_history(rest_client, address, version).await
+    }
+}
+
+/// Rebuilds the native coin balance and sequence number of an account at `version`, by taking
+/// the latest balance and undoing everything that happened to the account after `version`:
+///
+/// * Deposits and withdrawals, from the events on the account's `CoinStore`
+/// * Gas fees, from the transactions sent by the account
+///
+/// This only uses the event and transaction indexes, so it works for versions whose state has
+/// been pruned.  Other coins aren't rebuilt, as they can't be listed without the state.
+pub async fn get_native_balance_from_history(
+    rest_client: &aptos_rest_client::Client,
+    address: AccountAddress,
+    version: u64,
+) -> ApiResult<(u64, HashMap<TypeTag, Balance>)> {
+    /// Type for deserializing the event counters of a coin store
+    #[derive(Debug, Deserialize)]
+    struct CoinStoreEvents {
+        coin: AptosCoin,
+        deposit_events: EventHandle,
+        withdraw_events: EventHandle,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct EventHandle {
+        counter: U64,
+    }
+
+    let zero_balance = |sequence_number| {
         let mut currency_map = HashMap::new();
         currency_map.insert(
             native_coin_tag(),
@@ -219,12 +251,165 @@ async fn get_balances(
                 coin: AptosCoin { value: U64(0) },
             },
         );
-        Ok((0, currency_map))
+        (sequence_number, currency_map)
+    };
+
+    // Accounts can't be deleted, so if it doesn't exist now, it didn't exist then
+    let response = match rest_client.get_account_resources(address).await {
+        Ok(response) => response.into_inner(),
+        Err(_) => return Ok(zero_balance(0)),
+    };
+
+    let sequence_number = response
+        .iter()
+        .find(|resource| {
+            resource.resource_type.address == AccountAddress::ONE
+                && resource.resource_type.module == account_module_identifier()
+                && resource.resource_type.name == account_resource_identifier()
+        })
+        .and_then(|resource| serde_json::from_value::<AccountData>(resource.data.clone()).ok())
+        .map(|resource| resource.sequence_number.0)
+        .ok_or_else(|| {
+            ApiError::AptosError(Some(
+                "Failed to retrieve account sequence number".to_string(),
+            ))
+        })?;
+
+    // Undo every transaction sent after the version, along with its gas fee
+    let (sent_after, gas_fees_after) =
+        get_sent_transactions_after(rest_client, address, sequence_number, version).await?;
+    let sequence_number = sequence_number - sent_after;
+
+    let coin_store = response.iter().find(|resource| {
+        resource.resource_type.address == AccountAddress::ONE
+            && resource.resource_type.module == coin_module_identifier()
+            && resource.resource_type.name == coin_store_resource_identifier()
+            && resource.resource_type.type_params.first() == Some(&native_coin_tag())
+    });
+    let coin_store = if let Some(coin_store) = coin_store {
+        serde_json::from_value::<CoinStoreEvents>(coin_store.data.clone()).map_err(|_| {
+            ApiError::DeserializationFailed(Some("CoinStore failed to deserialize".to_string()))
+        })?
+    } else {
+        return Ok(zero_balance(sequence_number));
+    };
+
+    let deposited_after = sum_coin_events_after(
+        rest_client,
+        address,
+        deposit_events_field_identifier().as_str(),
+        coin_store.deposit_events.counter.0,
+        version,
+    )
+    .await?;
+    let withdrawn_after = sum_coin_events_after(
+        rest_client,
+        address,
+        withdraw_events_field_identifier().as_str(),
+        coin_store.withdraw_events.counter.0,
+        version,
+    )
+    .await?;
+
+    let balance = (coin_store.coin.value.0 as u128 + withdrawn_after + gas_fees_after)
+        .checked_sub(deposited_after)
+        .and_then(|balance| u64::try_from(balance).ok())
+        .ok_or_else(|| {
+            ApiError::AptosError(Some(format!(
+                "Historical balance of {} at version {} doesn't reconcile",
+                address, version
+            )))
+        })?;
+
+    let mut currency_map = HashMap::new();
+    currency_map.insert(
+        native_coin_tag(),
+        Balance {
+            coin: AptosCoin {
+                value: U64(balance),
+            },
+        },
+    );
+    Ok((sequence_number, currency_map))
+}
+
+/// Sums the amounts of the native coin events on the `field` event handle of the account's
+/// `CoinStore` emitted after `version`, walking back from the newest event
+async fn sum_coin_events_after(
+    rest_client: &aptos_rest_client::Client,
+    address: AccountAddress,
+    field: &str,
+    counter: u64,
+    version: u64,
+) -> ApiResult<u128> {
+    let coin_store_tag = format!("0x1::coin::CoinStore<{}>", native_coin_tag());
+    let encoded_coin_store_tag = utf8_percent_encode(&coin_store_tag, ENCODE_CHARS).to_string();
+
+    let mut total: u128 = 0;
+    let mut end = counter;
+    while end > 0 {
+        let start = end.saturating_sub(HISTORY_PAGE_SIZE as u64);
+        let events = rest_client
+            .get_account_events(
+                address,
+                &encoded_coin_store_tag,
+                field,
+                Some(start),
+                Some((end - start) as u16),
+            )
+            .await?
+            .into_inner();
+
+        for event in events.iter().rev() {
+            if event.version.0 <= version {
+                return Ok(total);
+            }
+            let event = serde_json::from_value::<CoinEvent>(event.data.clone()).map_err(|_| {
+                ApiError::DeserializationFailed(Some("CoinEvent failed to deserialize".to_string()))
+            })?;
+            total += event.amount.0 as u128;
+        }
+        end = start;
     }
+
+    Ok(total)
+}
+
+/// Counts the transactions sent by the account after `version`, and sums their gas fees, walking
+/// back from the latest sequence number.  Failed transactions are charged gas too.
+async fn get_sent_transactions_after(
+    rest_client: &aptos_rest_client::Client,
+    address: AccountAddress,
+    sequence_number: u64,
+    version: u64,
+) -> ApiResult<(u64, u128)> {
+    let mut count = 0;
+    let mut gas_fees: u128 = 0;
+    let mut end = sequence_number;
+    while end > 0 {
+        let start = end.saturating_sub(HISTORY_PAGE_SIZE as u64);
+        let transactions = rest_client
+            .get_account_transactions(address, Some(start), Some(end - start))
+            .await?
+            .into_inner();
+
+        for transaction in transactions.iter().rev() {
+            if let aptos_rest_client::Transaction::UserTransaction(txn) = transaction {
+                if txn.info.version.0 <= version {
+                    return Ok((count, gas_fees));
+                }
+                count += 1;
+                gas_fees += txn.info.gas_used.0 as u128 * txn.request.gas_unit_price.0 as u128;
+            }
+        }
+        end = start;
+    }
+
+    Ok((count, gas_fees))
 }
 
 /// A cache for currencies, so we don't have to keep looking up the status of it
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CoinCache {
     currencies: RwLock<HashMap<TypeTag, Option<Currency>>>,
 }
@@ -292,7 +477,6 @@ impl CoinCache {
         }
 
         // Retrieve the coin type
-        const ENCODE_CHARS: &AsciiSet = &CONTROLS.add(b'<').add(b'>');
         let address = struct_tag.address;
         let resource_tag = format!("0x1::coin::CoinInfo<{}>", struct_tag);
         let encoded_resource_tag = utf8_percent_encode(&resource_tag, ENCODE_CHARS).to_string();
@@ -12,7 +12,10 @@ use crate::{
 use aptos_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use aptos_logger::debug;
 use aptos_rest_client::{Account, Response};
-use aptos_sdk::move_types::language_storage::{StructTag, TypeTag};
+use aptos_sdk::move_types::{
+    language_storage::{StructTag, TypeTag},
+    parser::parse_type_tag,
+};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use futures::future::BoxFuture;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -158,6 +161,15 @@ pub fn native_coin_tag() -> TypeTag {
     })
 }
 
+/// The coin type of `currency`, from its metadata
+pub fn currency_coin_type(currency: &Currency) -> ApiResult<TypeTag> {
+    currency
+        .metadata
+        .as_ref()
+        .and_then(|metadata| parse_type_tag(&metadata.move_type).ok())
+        .ok_or_else(|| ApiError::UnsupportedCurrency(Some(currency.symbol.clone())))
+}
+
 pub fn is_native_coin(currency: &Currency) -> ApiResult<()> {
     if currency == &native_coin() {
         Ok(())
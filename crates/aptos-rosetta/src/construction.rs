@@ -27,7 +27,7 @@
 use crate::{
     common::{
         check_network, decode_bcs, decode_key, encode_bcs, get_account, handle_request,
-        is_native_coin, native_coin, to_hex_lower, with_context,
+        is_native_coin, to_hex_lower, with_context,
     },
     error::{ApiError, ApiResult},
     types::{InternalOperation, *},
@@ -40,10 +40,7 @@ use aptos_crypto::{
 };
 use aptos_logger::debug;
 use aptos_sdk::{
-    move_types::{
-        identifier::Identifier,
-        language_storage::{StructTag, TypeTag},
-    },
+    move_types::{identifier::Identifier, language_storage::TypeTag},
     transaction_builder::TransactionFactory,
 };
 use aptos_transaction_builder::aptos_stdlib;
@@ -316,7 +313,7 @@ async fn construction_parse(
                 && coin_module_identifier() == module_name
                 && transfer_function_identifier() == function_name
             {
-                parse_transfer_operation(sender, &type_args, &args)?
+                parse_transfer_operation(&server_context, sender, &type_args, &args)?
             } else if AccountAddress::ONE == *module.address()
                 && account_module_identifier() == module_name
                 && create_account_function_identifier() == function_name
@@ -327,6 +324,26 @@ async fn construction_parse(
                 && set_operator_function_identifier() == function_name
             {
                 parse_set_operator_operation(sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && set_delegated_voter_function_identifier() == function_name
+            {
+                parse_set_voter_operation(sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && add_stake_function_identifier() == function_name
+            {
+                parse_stake_operation(OperationType::AddStake, sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && unlock_function_identifier() == function_name
+            {
+                parse_stake_operation(OperationType::UnlockStake, sender, &type_args, &args)?
+            } else if AccountAddress::ONE == *module.address()
+                && stake_module_identifier() == module_name
+                && withdraw_function_identifier() == function_name
+            {
+                parse_stake_operation(OperationType::WithdrawStake, sender, &type_args, &args)?
             } else {
                 return Err(ApiError::TransactionParseError(Some(
                     "Unsupported operation type",
@@ -374,30 +391,20 @@ fn parse_create_account_operation(
 }
 
 fn parse_transfer_operation(
+    server_context: &RosettaContext,
     sender: AccountAddress,
     type_args: &[TypeTag],
     args: &[Vec<u8>],
 ) -> ApiResult<Vec<Operation>> {
     let mut operations = Vec::new();
 
-    // Check coin is the native coin
-    if let Some(TypeTag::Struct(StructTag {
-        address,
-        module,
-        name,
-        type_params,
-    })) = type_args.first()
-    {
-        // Currency must be the native coin for now
-        if *address != AccountAddress::ONE
-            || *module != aptos_coin_module_identifier()
-            || *name != aptos_coin_resource_identifier()
-            || !type_params.is_empty()
-        {
-            return Err(ApiError::TransactionParseError(Some(
+    // Check coin is one of the supported currencies
+    let currency = if let Some(coin_type) = type_args.first() {
+        server_context
+            .currency(coin_type)
+            .ok_or(ApiError::TransactionParseError(Some(
                 "Invalid coin for transfer",
-            )));
-        }
+            )))?
     } else {
         return Err(ApiError::TransactionParseError(Some(
             "No coin type in transfer",
@@ -421,8 +428,14 @@ fn parse_transfer_operation(
         )));
     };
 
-    operations.push(Operation::withdraw(0, None, sender, native_coin(), amount));
-    operations.push(Operation::deposit(1, None, receiver, native_coin(), amount));
+    operations.push(Operation::withdraw(
+        0,
+        None,
+        sender,
+        currency.clone(),
+        amount,
+    ));
+    operations.push(Operation::deposit(1, None, receiver, currency, amount));
     Ok(operations)
 }
 
@@ -448,6 +461,55 @@ fn parse_set_operator_operation(
     }
 }
 
+fn parse_set_voter_operation(
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(
+            "Set voter should not have type arguments",
+        )));
+    }
+
+    // Set delegated voter
+    if let Some(encoded_voter) = args.first() {
+        let voter: AccountAddress = bcs::from_bytes(encoded_voter)?;
+
+        Ok(vec![Operation::set_voter(0, None, sender, voter)])
+    } else {
+        Err(ApiError::InvalidOperations)
+    }
+}
+
+/// Parses add stake, unlock stake, and withdraw stake, which all take only an amount
+fn parse_stake_operation(
+    operation_type: OperationType,
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(
+            "Staking operations should not have type arguments",
+        )));
+    }
+
+    if let Some(encoded_amount) = args.first() {
+        let amount: u64 = bcs::from_bytes(encoded_amount)?;
+
+        Ok(vec![Operation::stake(
+            operation_type,
+            0,
+            None,
+            sender,
+            amount,
+        )])
+    } else {
+        Err(ApiError::InvalidOperations)
+    }
+}
+
 /// Construction payloads command (OFFLINE)
 ///
 /// Constructs payloads for given known operations
@@ -474,17 +536,31 @@ async fn construction_payloads(
             aptos_stdlib::account_create_account(create_account.new_account),
             create_account.sender,
         ),
-        InternalOperation::Transfer(transfer) => {
-            is_native_coin(&transfer.currency)?;
-            (
-                aptos_stdlib::aptos_coin_transfer(transfer.receiver, transfer.amount),
-                transfer.sender,
-            )
-        }
+        InternalOperation::Transfer(transfer) => (
+            aptos_stdlib::coin_transfer(
+                server_context.coin_type(&transfer.currency)?,
+                transfer.receiver,
+                transfer.amount,
+            ),
+            transfer.sender,
+        ),
         InternalOperation::SetOperator(set_operator) => (
             aptos_stdlib::stake_set_operator(set_operator.operator),
             set_operator.owner,
         ),
+        InternalOperation::SetVoter(set_voter) => (
+            aptos_stdlib::stake_set_delegated_voter(set_voter.voter),
+            set_voter.owner,
+        ),
+        InternalOperation::AddStake(stake) => {
+            (aptos_stdlib::stake_add_stake(stake.amount), stake.owner)
+        }
+        InternalOperation::UnlockStake(stake) => {
+            (aptos_stdlib::stake_unlock(stake.amount), stake.owner)
+        }
+        InternalOperation::WithdrawStake(stake) => {
+            (aptos_stdlib::stake_withdraw(stake.amount), stake.owner)
+        }
     };
 
     // Build the transaction and make it ready for signing
@@ -556,6 +632,9 @@ async fn construction_preprocess(
     };
 
     let internal_operation = InternalOperation::extract(&request.operations)?;
+    if let InternalOperation::Transfer(ref transfer) = internal_operation {
+        server_context.coin_type(&transfer.currency)?;
+    }
     let required_public_keys = vec![internal_operation.sender().into()];
 
     Ok(ConstructionPreprocessResponse {
@@ -8,16 +8,18 @@
 use crate::{
     account::CoinCache,
     block::BlockCache,
-    common::{handle_request, with_context},
+    common::{currency_coin_type, handle_request, native_coin, with_context},
     error::{ApiError, ApiResult},
+    types::Currency,
 };
 use aptos_config::config::ApiConfig;
-use aptos_logger::debug;
+use aptos_logger::{debug, warn};
+use aptos_sdk::move_types::language_storage::TypeTag;
 use aptos_types::account_address::AccountAddress;
 use aptos_types::chain_id::ChainId;
 use aptos_warp_webserver::WebServer;
 use aptos_warp_webserver::{logger, Error};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::{convert::Infallible, sync::Arc};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
@@ -27,11 +29,11 @@ use warp::{
     reply, Filter, Rejection, Reply,
 };
 
-mod account;
 mod block;
 mod construction;
 mod network;
 
+pub mod account;
 pub mod client;
 pub mod common;
 pub mod error;
@@ -49,8 +51,8 @@ pub struct RosettaContext {
     rest_client: Option<Arc<aptos_rest_client::Client>>,
     /// ChainId of the chain to connect to
     pub chain_id: ChainId,
-    /// Coin cache for looking up Currency details
-    pub coin_cache: Arc<CoinCache>,
+    /// Currencies supported for balances and transfers, always including the native coin
+    pub currencies: Arc<HashSet<Currency>>,
     /// Block index cache
     pub block_cache: Option<Arc<BlockCache>>,
     pub accounts: Arc<Mutex<BTreeMap<AccountAddress, SequenceNumber>>>,
@@ -72,13 +74,32 @@ impl RosettaContext {
             Err(ApiError::NodeIsOffline)
         }
     }
+
+    /// The coin type of `currency`, if it's supported
+    fn coin_type(&self, currency: &Currency) -> ApiResult<TypeTag> {
+        if self.currencies.contains(currency) {
+            currency_coin_type(currency)
+        } else {
+            Err(ApiError::UnsupportedCurrency(Some(currency.symbol.clone())))
+        }
+    }
+
+    /// The supported currency of `coin_type`, if any
+    fn currency(&self, coin_type: &TypeTag) -> Option<Currency> {
+        self.currencies
+            .iter()
+            .find(|currency| currency_coin_type(currency).ok().as_ref() == Some(coin_type))
+            .cloned()
+    }
 }
 
-/// Creates HTTP server (warp-based) for Rosetta
+/// Creates HTTP server (warp-based) for Rosetta, supporting the native coin and
+/// `supported_currencies`
 pub fn bootstrap(
     chain_id: ChainId,
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
+    supported_currencies: HashSet<Currency>,
 ) -> anyhow::Result<tokio::runtime::Runtime> {
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .thread_name("rosetta")
@@ -88,7 +109,12 @@ pub fn bootstrap(
 
     debug!("Starting up Rosetta server with {:?}", api_config);
 
-    runtime.spawn(bootstrap_async(chain_id, api_config, rest_client));
+    runtime.spawn(bootstrap_async(
+        chain_id,
+        api_config,
+        rest_client,
+        supported_currencies,
+    ));
     Ok(runtime)
 }
 
@@ -97,6 +123,7 @@ pub async fn bootstrap_async(
     chain_id: ChainId,
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
+    mut supported_currencies: HashSet<Currency>,
 ) -> anyhow::Result<JoinHandle<()>> {
     debug!("Starting up Rosetta server with {:?}", api_config);
     let api = WebServer::from(api_config);
@@ -107,10 +134,15 @@ pub async fn bootstrap_async(
             .as_ref()
             .map(|rest_client| Arc::new(BlockCache::new(rest_client.clone())));
 
+        supported_currencies.insert(native_coin());
+        if let Some(ref rest_client) = rest_client {
+            supported_currencies = discover_decimals(rest_client, supported_currencies).await;
+        }
+
         let context = RosettaContext {
             rest_client: rest_client.clone(),
             chain_id,
-            coin_cache: Arc::new(CoinCache::new()),
+            currencies: Arc::new(supported_currencies),
             block_cache,
             accounts: Arc::new(Mutex::new(BTreeMap::new())),
         };
@@ -119,6 +151,45 @@ pub async fn bootstrap_async(
     Ok(handle)
 }
 
+/// Replaces the decimals of the currencies with those of their on-chain `CoinInfo`, so that
+/// amounts are in the right units even if the configured decimals are wrong.  Currencies which
+/// can't be found on-chain are kept as configured.
+async fn discover_decimals(
+    rest_client: &aptos_rest_client::Client,
+    currencies: HashSet<Currency>,
+) -> HashSet<Currency> {
+    let coin_cache = CoinCache::new();
+    let mut discovered = HashSet::new();
+    for currency in currencies {
+        let on_chain_currency = match currency_coin_type(&currency) {
+            Ok(coin_type) => coin_cache.get_currency(rest_client, coin_type, None).await,
+            Err(error) => Err(error),
+        };
+        match on_chain_currency {
+            Ok(Some(on_chain_currency)) => {
+                if on_chain_currency.decimals != currency.decimals {
+                    warn!(
+                        "Currency {} is configured with {} decimals, but has {} on-chain",
+                        currency.symbol, currency.decimals, on_chain_currency.decimals
+                    );
+                }
+                discovered.insert(Currency {
+                    decimals: on_chain_currency.decimals,
+                    ..currency
+                });
+            }
+            _ => {
+                warn!(
+                    "Failed to discover the decimals of currency {} on-chain",
+                    currency.symbol
+                );
+                discovered.insert(currency);
+            }
+        }
+    }
+    discovered
+}
+
 /// Collection of all routes for the server
 pub fn routes(
     context: RosettaContext,
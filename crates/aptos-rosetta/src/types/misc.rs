@@ -85,6 +85,10 @@ pub enum OperationType {
     Withdraw,
     Fee,
     SetOperator,
+    SetVoter,
+    AddStake,
+    UnlockStake,
+    WithdrawStake,
 }
 
 impl OperationType {
@@ -93,6 +97,10 @@ impl OperationType {
     const WITHDRAW: &'static str = "withdraw";
     const FEE: &'static str = "fee";
     const SET_OPERATOR: &'static str = "set_operator";
+    const SET_VOTER: &'static str = "set_voter";
+    const ADD_STAKE: &'static str = "add_stake";
+    const UNLOCK_STAKE: &'static str = "unlock_stake";
+    const WITHDRAW_STAKE: &'static str = "withdraw_stake";
 
     pub fn all() -> Vec<OperationType> {
         vec![
@@ -101,6 +109,10 @@ impl OperationType {
             OperationType::Withdraw,
             OperationType::Fee,
             OperationType::SetOperator,
+            OperationType::SetVoter,
+            OperationType::AddStake,
+            OperationType::UnlockStake,
+            OperationType::WithdrawStake,
         ]
     }
 }
@@ -115,6 +127,10 @@ impl FromStr for OperationType {
             Self::WITHDRAW => Ok(OperationType::Withdraw),
             Self::FEE => Ok(OperationType::Fee),
             Self::SET_OPERATOR => Ok(OperationType::SetOperator),
+            Self::SET_VOTER => Ok(OperationType::SetVoter),
+            Self::ADD_STAKE => Ok(OperationType::AddStake),
+            Self::UNLOCK_STAKE => Ok(OperationType::UnlockStake),
+            Self::WITHDRAW_STAKE => Ok(OperationType::WithdrawStake),
             _ => Err(ApiError::DeserializationFailed(Some(format!(
                 "Invalid OperationType: {}",
                 s
@@ -130,6 +146,10 @@ impl Display for OperationType {
             OperationType::Deposit => Self::DEPOSIT,
             OperationType::Withdraw => Self::WITHDRAW,
             OperationType::SetOperator => Self::SET_OPERATOR,
+            OperationType::SetVoter => Self::SET_VOTER,
+            OperationType::AddStake => Self::ADD_STAKE,
+            OperationType::UnlockStake => Self::UNLOCK_STAKE,
+            OperationType::WithdrawStake => Self::WITHDRAW_STAKE,
             OperationType::Fee => Self::FEE,
         })
     }
@@ -7,13 +7,16 @@
 
 use crate::common::native_coin_tag;
 use crate::types::{
-    account_module_identifier, aptos_coin_module_identifier, aptos_coin_resource_identifier,
-    coin_module_identifier, create_account_function_identifier,
+    account_module_identifier, add_stake_events_field_identifier, add_stake_function_identifier,
+    aptos_coin_module_identifier, aptos_coin_resource_identifier, coin_module_identifier,
+    create_account_function_identifier, set_delegated_voter_function_identifier,
     set_operator_events_field_identifier, set_operator_function_identifier,
     stake_module_identifier, stake_pool_resource_identifier, transfer_function_identifier,
+    unlock_function_identifier, unlock_stake_events_field_identifier, withdraw_function_identifier,
+    withdraw_stake_events_field_identifier,
 };
 use crate::{
-    common::{is_native_coin, native_coin},
+    common::native_coin,
     error::ApiResult,
     types::{
         account_resource_identifier, coin_store_resource_identifier,
@@ -374,7 +377,7 @@ impl Operation {
         operator: AccountAddress,
     ) -> Operation {
         Operation::new(
-            OperationType::Withdraw,
+            OperationType::SetOperator,
             operation_index,
             status,
             address,
@@ -382,12 +385,50 @@ impl Operation {
             Some(OperationSpecificMetadata::set_operator(operator)),
         )
     }
+
+    pub fn set_voter(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        voter: AccountAddress,
+    ) -> Operation {
+        Operation::new(
+            OperationType::SetVoter,
+            operation_index,
+            status,
+            address,
+            None,
+            Some(OperationSpecificMetadata::set_voter(voter)),
+        )
+    }
+
+    /// Staking operations move coins within the owner's stake pool, so they don't have an
+    /// [`Amount`] of their own.  Coins moving between the owner's account and the stake pool show
+    /// up as separate withdraw (add stake) and deposit (withdraw stake) operations.
+    pub fn stake(
+        operation_type: OperationType,
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        address: AccountAddress,
+        amount: u64,
+    ) -> Operation {
+        Operation::new(
+            operation_type,
+            operation_index,
+            status,
+            address,
+            None,
+            Some(OperationSpecificMetadata::stake(amount)),
+        )
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum OperationSpecificMetadata {
     CreateAccount(CreateAccountArguments),
     SetOperator(SetOperatorArguments),
+    SetVoter(SetVoterArguments),
+    Stake(StakeArguments),
 }
 
 impl OperationSpecificMetadata {
@@ -402,6 +443,18 @@ impl OperationSpecificMetadata {
             operator: operator.into(),
         })
     }
+
+    pub fn set_voter(voter: AccountAddress) -> OperationSpecificMetadata {
+        OperationSpecificMetadata::SetVoter(SetVoterArguments {
+            voter: voter.into(),
+        })
+    }
+
+    pub fn stake(amount: u64) -> OperationSpecificMetadata {
+        OperationSpecificMetadata::Stake(StakeArguments {
+            amount: amount.into(),
+        })
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -415,6 +468,17 @@ pub struct SetOperatorArguments {
     operator: AccountIdentifier,
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SetVoterArguments {
+    voter: AccountIdentifier,
+}
+
+/// Amount of the native coin to add to, unlock from, or withdraw from the stake pool
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StakeArguments {
+    amount: U64,
+}
+
 /// Used for query operations to apply conditions.  Defaults to [`Operator::And`] if no value is
 /// present
 ///
@@ -560,6 +624,7 @@ pub enum TransactionType {
     Genesis,
     BlockMetadata,
     StateCheckpoint,
+    Validator,
 }
 
 impl Display for TransactionType {
@@ -570,6 +635,7 @@ impl Display for TransactionType {
             Genesis => "Genesis",
             BlockMetadata => "BlockResource",
             StateCheckpoint => "StateCheckpoint",
+            Validator => "Validator",
         })
     }
 }
@@ -593,6 +659,7 @@ impl Transaction {
             StateCheckpointTransaction(txn) => {
                 (TransactionType::StateCheckpoint, None, txn.info, vec![])
             }
+            ValidatorTransaction(txn) => (TransactionType::Validator, None, txn.info, txn.events),
         };
 
         // Operations must be sequential and operation index must always be in the same order
@@ -611,6 +678,19 @@ impl Transaction {
                 operation_index += ops.len() as u64;
                 operations.append(&mut ops);
             }
+
+            // Setting the voter doesn't emit an event, so it can only be found from the payload
+            if let Some(ref request) = maybe_user_transaction_request {
+                if let Some(operation) = parse_set_voter_from_txn_payload(
+                    operation_index,
+                    OperationStatusType::Success,
+                    *request.sender.inner(),
+                    &request.payload,
+                ) {
+                    operation_index += 1;
+                    operations.push(operation);
+                }
+            }
         } else {
             // Parse all failed operations from the payload
             if let Some(ref request) = maybe_user_transaction_request {
@@ -718,14 +798,70 @@ fn parse_operations_from_txn_payload(
             operations.push(Operation::set_operator(
                 operation_index,
                 Some(OperationStatusType::Failure),
-                operator.into(),
                 sender,
+                operator.into(),
             ));
+        } else if AccountAddress::ONE == *inner.function.module.address.inner()
+            && stake_module_identifier() == inner.function.module.name.0
+        {
+            let operation_type = if add_stake_function_identifier() == inner.function.name.0 {
+                Some(OperationType::AddStake)
+            } else if unlock_function_identifier() == inner.function.name.0 {
+                Some(OperationType::UnlockStake)
+            } else if withdraw_function_identifier() == inner.function.name.0 {
+                Some(OperationType::WithdrawStake)
+            } else {
+                None
+            };
+            if let Some(operation_type) = operation_type {
+                let amount =
+                    serde_json::from_value::<U64>(inner.arguments.get(0).cloned().unwrap())
+                        .unwrap()
+                        .0;
+                operations.push(Operation::stake(
+                    operation_type,
+                    operation_index,
+                    Some(OperationStatusType::Failure),
+                    sender,
+                    amount,
+                ));
+            } else if let Some(operation) = parse_set_voter_from_txn_payload(
+                operation_index,
+                OperationStatusType::Failure,
+                sender,
+                payload,
+            ) {
+                operations.push(operation);
+            }
         }
     }
     operations
 }
 
+/// Parses a set voter operation from the transaction payload
+fn parse_set_voter_from_txn_payload(
+    operation_index: u64,
+    status: OperationStatusType,
+    sender: AccountAddress,
+    payload: &TransactionPayload,
+) -> Option<Operation> {
+    if let TransactionPayload::EntryFunctionPayload(inner) = payload {
+        if AccountAddress::ONE == *inner.function.module.address.inner()
+            && stake_module_identifier() == inner.function.module.name.0
+            && set_delegated_voter_function_identifier() == inner.function.name.0
+        {
+            let voter = serde_json::from_value::<Address>(inner.arguments.get(0).cloned()?).ok()?;
+            return Some(Operation::set_voter(
+                operation_index,
+                Some(status),
+                sender,
+                voter.into(),
+            ));
+        }
+    }
+    None
+}
+
 /// Parses operations from the write set
 ///
 /// This can only be done during a successful transaction because there are actual state changes.
@@ -780,26 +916,49 @@ fn parse_operations_from_write_set(
                 }
             }
         } else if data.typ == stake_pool_tag {
-            // Account sequence number increase (possibly creation)
-            // Find out if it's the 0th sequence number (creation)
+            // Stake pool changes, each one of which emits an event on its own event handle
             for (id, value) in data.data.0.iter() {
-                if id.0 == set_operator_events_field_identifier() {
-                    serde_json::from_value::<EventId>(value.clone()).unwrap();
-                    if let Ok(event) = serde_json::from_value::<EventId>(value.clone()) {
-                        let set_operator_event =
-                            EventKey::new(event.guid.id.creation_num.0, event.guid.id.addr);
-                        if let Some(operator) =
-                            get_set_operator_from_event(events, set_operator_event)
-                        {
-                            operations.push(Operation::set_operator(
-                                operation_index,
-                                Some(OperationStatusType::Success),
-                                address,
-                                operator,
-                            ));
-                            operation_index += 1;
-                        }
-                    }
+                let operation_type = if id.0 == set_operator_events_field_identifier() {
+                    OperationType::SetOperator
+                } else if id.0 == add_stake_events_field_identifier() {
+                    OperationType::AddStake
+                } else if id.0 == unlock_stake_events_field_identifier() {
+                    OperationType::UnlockStake
+                } else if id.0 == withdraw_stake_events_field_identifier() {
+                    OperationType::WithdrawStake
+                } else {
+                    continue;
+                };
+                let event_key = if let Ok(event) = serde_json::from_value::<EventId>(value.clone())
+                {
+                    EventKey::new(event.guid.id.creation_num.0, event.guid.id.addr)
+                } else {
+                    continue;
+                };
+
+                let operation = if operation_type == OperationType::SetOperator {
+                    get_set_operator_from_event(events, event_key).map(|operator| {
+                        Operation::set_operator(
+                            operation_index,
+                            Some(OperationStatusType::Success),
+                            address,
+                            operator,
+                        )
+                    })
+                } else {
+                    get_stake_amount_from_event(events, event_key).map(|amount| {
+                        Operation::stake(
+                            operation_type,
+                            operation_index,
+                            Some(OperationStatusType::Success),
+                            address,
+                            amount,
+                        )
+                    })
+                };
+                if let Some(operation) = operation {
+                    operations.push(operation);
+                    operation_index += 1;
                 }
             }
         } else if data.typ == coin_store_tag {
@@ -859,6 +1018,21 @@ fn get_amount_from_event(events: &[Event], event_key: EventKey) -> Option<u64> {
     None
 }
 
+/// Pulls the amount from an add stake, unlock stake or withdraw stake event
+fn get_stake_amount_from_event(events: &[Event], event_key: EventKey) -> Option<u64> {
+    if let Some(event) = events
+        .iter()
+        .find(|event| EventKey::from(event.key) == event_key)
+    {
+        if let Ok(StakeEvent { amount }) = serde_json::from_value::<StakeEvent>(event.data.clone())
+        {
+            return Some(amount.0);
+        }
+    }
+
+    None
+}
+
 fn get_set_operator_from_event(events: &[Event], event_key: EventKey) -> Option<AccountAddress> {
     if let Some(event) = events
         .iter()
@@ -891,6 +1065,10 @@ pub enum InternalOperation {
     CreateAccount(CreateAccount),
     Transfer(Transfer),
     SetOperator(SetOperator),
+    SetVoter(SetVoter),
+    AddStake(Stake),
+    UnlockStake(Stake),
+    WithdrawStake(Stake),
 }
 
 impl InternalOperation {
@@ -928,6 +1106,41 @@ impl InternalOperation {
                                 }));
                             }
                         }
+                        Ok(OperationType::SetVoter) => {
+                            if let (
+                                Some(OperationSpecificMetadata::SetVoter(SetVoterArguments {
+                                    voter,
+                                })),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                return Ok(Self::SetVoter(SetVoter {
+                                    owner: account.account_address()?,
+                                    voter: voter.account_address()?,
+                                }));
+                            }
+                        }
+                        Ok(
+                            operation_type @ (OperationType::AddStake
+                            | OperationType::UnlockStake
+                            | OperationType::WithdrawStake),
+                        ) => {
+                            if let (
+                                Some(OperationSpecificMetadata::Stake(StakeArguments { amount })),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                let stake = Stake {
+                                    owner: account.account_address()?,
+                                    amount: amount.0,
+                                };
+                                return Ok(match operation_type {
+                                    OperationType::AddStake => Self::AddStake(stake),
+                                    OperationType::UnlockStake => Self::UnlockStake(stake),
+                                    _ => Self::WithdrawStake(stake),
+                                });
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -946,6 +1159,10 @@ impl InternalOperation {
             Self::CreateAccount(inner) => inner.sender,
             Self::Transfer(inner) => inner.sender,
             Self::SetOperator(inner) => inner.owner,
+            Self::SetVoter(inner) => inner.owner,
+            Self::AddStake(inner) | Self::UnlockStake(inner) | Self::WithdrawStake(inner) => {
+                inner.owner
+            }
         }
     }
 }
@@ -1023,9 +1240,8 @@ impl Transfer {
                     )));
                 }
 
-                // Check that the currency is supported
-                // TODO: in future use currency, since there's more than just 1
-                is_native_coin(&withdraw_amount.currency)?;
+                // Whether the currency is supported is checked by the construction APIs, as
+                // it depends on the configuration of the server
 
                 let withdraw_value = i64::from_str(&withdraw_amount.value).map_err(|_| {
                     ApiError::InvalidTransferOperations(Some("Withdraw amount is invalid"))
@@ -1064,18 +1280,41 @@ pub struct SetOperator {
     pub operator: AccountAddress,
 }
 
+/// Set delegated voter
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SetVoter {
+    pub owner: AccountAddress,
+    pub voter: AccountAddress,
+}
+
+/// Add, unlock or withdraw stake from the owner's stake pool
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Stake {
+    pub owner: AccountAddress,
+    pub amount: u64,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct CoinEvent {
-    amount: U64,
+    pub amount: U64,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct SetOperatorEvent {
-    _pool_address: Address,
-    _old_operator: Address,
     new_operator: Address,
 }
 
+/// An `AddStakeEvent`, `UnlockStakeEvent` or `WithdrawStakeEvent`
+#[derive(Clone, Debug, Deserialize)]
+pub struct StakeEvent {
+    #[serde(
+        alias = "amount_added",
+        alias = "amount_unlocked",
+        alias = "amount_withdrawn"
+    )]
+    amount: U64,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct EventId {
     guid: Id,
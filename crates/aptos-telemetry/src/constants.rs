@@ -5,6 +5,7 @@
 
 // Environment variables
 pub(crate) const ENV_APTOS_DISABLE_TELEMETRY: &str = "APTOS_DISABLE_TELEMETRY";
+pub(crate) const ENV_APTOS_ENABLE_CRASH_REPORTS: &str = "APTOS_ENABLE_CRASH_REPORTS";
 pub(crate) const ENV_GA_MEASUREMENT_ID: &str = "GA_MEASUREMENT_ID";
 pub(crate) const ENV_GA_API_SECRET: &str = "GA_API_SECRET";
 
@@ -24,3 +25,7 @@ pub(crate) const TELEMETRY_SERVICE_URL: &str = "https://telemetry.aptoslabs.com"
 pub(crate) const NODE_CORE_METRICS_FREQ_SECS: u64 = 30; // 30 seconds
 pub(crate) const NODE_NETWORK_METRICS_FREQ_SECS: u64 = 60; // 1 minute
 pub(crate) const NODE_SYS_INFO_FREQ_SECS: u64 = 5 * 60; // 5 minutes
+
+// Limits for crash reports
+pub(crate) const MAX_PENDING_CRASH_REPORTS: usize = 10; // Reports waiting to be uploaded
+pub(crate) const RECENT_LOGS_CAPACITY: usize = 100; // Log lines kept for a report
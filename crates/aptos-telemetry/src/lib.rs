@@ -7,6 +7,7 @@ pub mod build_information;
 pub mod cli_metrics;
 mod constants;
 mod core_metrics;
+pub mod crash_report;
 mod metrics;
 mod network_metrics;
 mod sender;
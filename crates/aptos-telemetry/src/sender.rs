@@ -58,7 +58,11 @@ impl TelemetrySender {
         }
     }
 
-    pub async fn send_metrics(&self, event_name: String, telemetry_dump: TelemetryDump) {
+    pub async fn send_metrics(
+        &self,
+        event_name: String,
+        telemetry_dump: TelemetryDump,
+    ) -> Result<(), anyhow::Error> {
         let retry_strategy = ExponentialBackoff::from_millis(10)
             .map(jitter) // add jitter to delays
             .take(4); // limit to 4 retries
@@ -75,10 +79,12 @@ impl TelemetrySender {
                     &event_name, &telemetry_dump
                 );
                 metrics::increment_telemetry_service_successes(&event_name);
+                Ok(())
             }
             Err(error) => {
                 error!("Failed to send telemetry event: Error: {}", error);
                 metrics::increment_telemetry_service_failures(&event_name);
+                Err(error)
             }
         }
     }
@@ -149,6 +155,11 @@ impl TelemetrySender {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_token(&self, token: String) {
+        *self.auth_context.token.write() = Some(token);
+    }
+
     fn reset_token(&self) {
         *self.auth_context.token.write() = None;
         *self.auth_context.server_public_key.lock() = None;
@@ -344,7 +355,9 @@ mod tests {
             *client.auth_context.token.write() = Some("SECRET_JWT_TOKEN".into());
         }
 
-        client.send_metrics(event_name.into(), telemetry_dump).await;
+        let result = client.send_metrics(event_name.into(), telemetry_dump).await;
+
+        assert!(result.is_err());
 
         mock.assert_hits(5);
         assert_eq!(
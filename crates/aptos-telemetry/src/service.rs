@@ -12,6 +12,9 @@ use crate::{
         TELEMETRY_SERVICE_URL,
     },
     core_metrics::create_core_metric_telemetry_event,
+    crash_report::{
+        crash_report_dir, crash_reports_enabled, setup_crash_reporter, upload_crash_reports,
+    },
     metrics,
     network_metrics::create_network_metric_telemetry_event,
     sender::TelemetrySender,
@@ -51,7 +54,7 @@ static TELEMETRY_TOKEN: Lazy<String> = Lazy::new(|| {
 });
 
 /// Returns true iff telemetry is disabled
-fn telemetry_is_disabled() -> bool {
+pub(crate) fn telemetry_is_disabled() -> bool {
     env::var(ENV_APTOS_DISABLE_TELEMETRY).is_ok()
 }
 
@@ -64,6 +67,11 @@ pub fn start_telemetry_service(node_config: NodeConfig, chain_id: ChainId) -> Op
         return None;
     }
 
+    // Capture crashes if the operator opted in to crash reports
+    if crash_reports_enabled() {
+        setup_crash_reporter(&node_config, chain_id);
+    }
+
     // Create the telemetry runtime
     let telemetry_runtime = Builder::new_multi_thread()
         .thread_name("aptos-telemetry")
@@ -101,6 +109,16 @@ async fn spawn_telemetry_service(peer_id: String, chain_id: ChainId, node_config
     )
     .await;
 
+    // Upload the crash reports left by previous runs (only on startup)
+    if crash_reports_enabled() {
+        upload_crash_reports(
+            &crash_report_dir(&node_config),
+            peer_id.clone(),
+            &telemetry_sender,
+        )
+        .await;
+    }
+
     // Periodically send node core metrics
     let mut core_metrics_interval = IntervalStream::new(tokio::time::interval(
         std::time::Duration::from_secs(NODE_CORE_METRICS_FREQ_SECS),
@@ -243,7 +261,8 @@ fn spawn_telemetry_service_event_sender(
         if telemetry_sender.is_none() {
             return;
         }
-        telemetry_sender
+        // Failures are logged and counted by the sender
+        let _ = telemetry_sender
             .unwrap()
             .send_metrics(event_name, telemetry_dump)
             .await;
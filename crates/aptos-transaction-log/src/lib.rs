@@ -0,0 +1,258 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Follows the transactions committed to storage and writes a JSON summary of each of them to a
+//! sink (a file, UDP or Kafka), separately from the node's own logs, so that analytics pipelines
+//! don't need to scrape the REST API.
+//!
+//! The Kafka sink builds and links librdkafka through `rdkafka`, so it's only compiled with the
+//! `kafka` feature, which the node forwards as its own `kafka` feature.
+
+mod sink;
+
+use anyhow::{anyhow, ensure};
+use aptos_config::config::NodeConfig;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ExecutionStatus, Transaction, TransactionListWithProof, Version},
+};
+use serde::Serialize;
+use sink::{create_sink, Sink};
+use std::{convert::TryInto, sync::Arc, time::Duration};
+use storage_interface::DbReader;
+use tokio::runtime::{Builder, Runtime};
+
+/// The most transactions read from storage at once
+const BATCH_SIZE: u64 = 100;
+/// How long to wait for new transactions once all committed ones are logged
+const POLL_INTERVAL_MS: u64 = 500;
+/// Failures are logged at most this often, as a sink which is down fails every batch
+const ERROR_LOG_INTERVAL_SECS: u64 = 10;
+
+/// The summary of a committed transaction, which is written as a JSON object
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TransactionSummary {
+    pub version: Version,
+    pub hash: HashValue,
+    #[serde(rename = "type")]
+    pub transaction_type: &'static str,
+    /// Only set for user transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<AccountAddress>,
+    /// Only set for user transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    pub status: ExecutionStatus,
+    pub gas_used: u64,
+    pub events_count: usize,
+}
+
+/// Summarizes the transactions of `txn_list`, which must have been fetched with their events
+fn summarize(txn_list: TransactionListWithProof) -> anyhow::Result<Vec<TransactionSummary>> {
+    let first_version = match txn_list.first_transaction_version {
+        Some(first_version) => first_version,
+        None => return Ok(vec![]),
+    };
+    let events = txn_list
+        .events
+        .ok_or_else(|| anyhow!("The transactions were fetched without their events"))?;
+    ensure!(
+        txn_list.transactions.len() == txn_list.proof.transaction_infos.len()
+            && txn_list.transactions.len() == events.len(),
+        "Got {} transactions, with {} infos and {} event lists",
+        txn_list.transactions.len(),
+        txn_list.proof.transaction_infos.len(),
+        events.len()
+    );
+
+    Ok(txn_list
+        .transactions
+        .iter()
+        .zip(txn_list.proof.transaction_infos.iter())
+        .zip(events.iter())
+        .enumerate()
+        .map(|(index, ((txn, txn_info), events))| {
+            let (transaction_type, sender, sequence_number) = match txn {
+                Transaction::UserTransaction(txn) => {
+                    ("user", Some(txn.sender()), Some(txn.sequence_number()))
+                }
+                Transaction::GenesisTransaction(_) => ("genesis", None, None),
+                Transaction::BlockMetadata(_) => ("block_metadata", None, None),
+                Transaction::StateCheckpoint(_) => ("state_checkpoint", None, None),
+                Transaction::ValidatorTransaction(_) => ("validator", None, None),
+            };
+            TransactionSummary {
+                version: first_version + index as u64,
+                hash: txn_info.transaction_hash(),
+                transaction_type,
+                sender,
+                sequence_number,
+                status: txn_info.status().clone(),
+                gas_used: txn_info.gas_used(),
+                events_count: events.len(),
+            }
+        })
+        .collect())
+}
+
+fn is_sampled(txn_hash: &HashValue, sample_threshold: u64) -> bool {
+    let prefix: [u8; 8] = txn_hash.as_ref()[..8]
+        .try_into()
+        .expect("Hash values are 32 bytes");
+    u64::from_be_bytes(prefix) <= sample_threshold
+}
+
+struct TransactionLogger {
+    db: Arc<dyn DbReader>,
+    sink: Box<dyn Sink>,
+    next_version: Version,
+    /// Transactions whose hash, as a number, is below this are logged
+    sample_threshold: u64,
+}
+
+impl TransactionLogger {
+    /// Logs the next batch of committed transactions, and returns how many were read
+    fn log_next_batch(&mut self) -> anyhow::Result<usize> {
+        let latest_version = match self.db.get_latest_version_option()? {
+            Some(latest_version) if latest_version >= self.next_version => latest_version,
+            _ => return Ok(0),
+        };
+        let txn_list =
+            self.db
+                .get_transactions(self.next_version, BATCH_SIZE, latest_version, true)?;
+        let summaries = summarize(txn_list)?;
+        // Even if the sink fails, the batch isn't retried, so that a sink which is down doesn't
+        // hold back the log
+        self.next_version += summaries.len() as u64;
+        self.write(&summaries)?;
+        Ok(summaries.len())
+    }
+
+    fn write(&mut self, summaries: &[TransactionSummary]) -> anyhow::Result<()> {
+        for summary in summaries {
+            if is_sampled(&summary.hash, self.sample_threshold) {
+                self.sink
+                    .write(&summary.hash, &serde_json::to_vec(summary)?)?;
+            }
+        }
+        self.sink.flush()
+    }
+
+    async fn run(mut self) {
+        loop {
+            let logged = self.log_next_batch().unwrap_or_else(|error| {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(ERROR_LOG_INTERVAL_SECS)),
+                    error!(
+                        next_version = self.next_version,
+                        "[transaction-log] failed to log transactions: {}", error
+                    )
+                );
+                0
+            });
+            if logged == 0 {
+                tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+            }
+        }
+    }
+}
+
+/// Starts logging committed transactions, if enabled in `config`
+pub fn bootstrap(config: &NodeConfig, db: Arc<dyn DbReader>) -> anyhow::Result<Option<Runtime>> {
+    let log_config = &config.transaction_log;
+    if !log_config.enabled {
+        return Ok(None);
+    }
+    ensure!(
+        (0.0..=1.0).contains(&log_config.sample_rate),
+        "The transaction log sample rate must be between 0 and 1, not {}",
+        log_config.sample_rate
+    );
+
+    let next_version = match log_config.starting_version {
+        Some(starting_version) => starting_version,
+        None => db
+            .get_latest_version_option()?
+            .map_or(0, |latest_version| latest_version + 1),
+    };
+    let logger = TransactionLogger {
+        db,
+        sink: create_sink(&log_config.sink, &config.base.data_dir)?,
+        next_version,
+        sample_threshold: (log_config.sample_rate * u64::MAX as f64) as u64,
+    };
+    let runtime = Builder::new_multi_thread()
+        .thread_name("txn-log")
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to create transaction log runtime {}", err))?;
+    info!(
+        next_version = next_version,
+        "[transaction-log] logging committed transactions"
+    );
+    runtime.spawn(logger.run());
+    Ok(Some(runtime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        contract_event::ContractEvent,
+        proof::{AccumulatorRangeProof, TransactionInfoListWithProof},
+        transaction::TransactionInfo,
+    };
+
+    #[test]
+    fn test_summarize() {
+        let txn_hash = HashValue::random();
+        let txn_list = TransactionListWithProof::new(
+            vec![Transaction::StateCheckpoint(HashValue::random())],
+            Some(vec![Vec::<ContractEvent>::new()]),
+            Some(42),
+            TransactionInfoListWithProof::new(
+                AccumulatorRangeProof::new_empty(),
+                vec![TransactionInfo::new(
+                    txn_hash,
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    None,
+                    7,
+                    ExecutionStatus::OutOfGas,
+                )],
+            ),
+        );
+
+        let summaries = summarize(txn_list).unwrap();
+        assert_eq!(
+            summaries,
+            vec![TransactionSummary {
+                version: 42,
+                hash: txn_hash,
+                transaction_type: "state_checkpoint",
+                sender: None,
+                sequence_number: None,
+                status: ExecutionStatus::OutOfGas,
+                gas_used: 7,
+                events_count: 0,
+            }]
+        );
+        let json = serde_json::to_value(&summaries[0]).unwrap();
+        assert_eq!(json["type"], "state_checkpoint");
+        assert_eq!(json["hash"], txn_hash.to_hex());
+        assert!(json.get("sender").is_none());
+    }
+
+    #[test]
+    fn test_sampling() {
+        let high = HashValue::new([0xff; HashValue::LENGTH]);
+        assert!(is_sampled(&HashValue::zero(), 0));
+        assert!(!is_sampled(&high, 0));
+        assert!(is_sampled(&high, u64::MAX));
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context};
+use aptos_config::config::TransactionLogSink;
+use aptos_crypto::HashValue;
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    net::{ToSocketAddrs, UdpSocket},
+    path::Path,
+};
+
+/// A destination of transaction summaries
+pub(crate) trait Sink: Send {
+    /// Writes the summary of the transaction with hash `txn_hash`, which is a JSON object
+    fn write(&mut self, txn_hash: &HashValue, summary: &[u8]) -> anyhow::Result<()>;
+
+    /// Called after each batch of transactions
+    fn flush(&mut self) -> anyhow::Result<()>;
+}
+
+pub(crate) fn create_sink(
+    config: &TransactionLogSink,
+    data_dir: &Path,
+) -> anyhow::Result<Box<dyn Sink>> {
+    Ok(match config {
+        TransactionLogSink::File { path } => {
+            let path = data_dir.join(path);
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("Failed to open the transaction log {:?}", path))?;
+            Box::new(FileSink(BufWriter::new(file)))
+        }
+        TransactionLogSink::Udp { address } => Box::new(UdpSink::new(address)?),
+        TransactionLogSink::Kafka { brokers, topic } => create_kafka_sink(brokers, topic)?,
+    })
+}
+
+/// Writes one summary per line
+struct FileSink(BufWriter<File>);
+
+impl Sink for FileSink {
+    fn write(&mut self, _txn_hash: &HashValue, summary: &[u8]) -> anyhow::Result<()> {
+        self.0.write_all(summary)?;
+        self.0.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        Ok(self.0.flush()?)
+    }
+}
+
+/// Sends one summary per datagram
+struct UdpSink(UdpSocket);
+
+impl UdpSink {
+    fn new(address: &str) -> anyhow::Result<Self> {
+        let address = address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("{} doesn't resolve to any address", address))?;
+        let local_address = if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(local_address)?;
+        socket.connect(address)?;
+        Ok(Self(socket))
+    }
+}
+
+impl Sink for UdpSink {
+    fn write(&mut self, _txn_hash: &HashValue, summary: &[u8]) -> anyhow::Result<()> {
+        self.0.send(summary)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "kafka")]
+fn create_kafka_sink(brokers: &str, topic: &str) -> anyhow::Result<Box<dyn Sink>> {
+    Ok(Box::new(kafka::KafkaSink::new(brokers, topic)?))
+}
+
+#[cfg(not(feature = "kafka"))]
+fn create_kafka_sink(_brokers: &str, _topic: &str) -> anyhow::Result<Box<dyn Sink>> {
+    Err(anyhow!(
+        "The Kafka transaction log sink requires building the node with the `kafka` feature"
+    ))
+}
+
+#[cfg(feature = "kafka")]
+mod kafka {
+    use super::Sink;
+    use anyhow::anyhow;
+    use aptos_crypto::HashValue;
+    use rdkafka::{
+        producer::{BaseProducer, BaseRecord, Producer},
+        ClientConfig,
+    };
+    use std::time::Duration;
+
+    /// How long to wait for the produced summaries to be delivered, after each batch
+    const FLUSH_TIMEOUT_SECS: u64 = 10;
+
+    /// Produces one message per summary, keyed by transaction hash
+    pub(super) struct KafkaSink {
+        producer: BaseProducer,
+        topic: String,
+    }
+
+    impl KafkaSink {
+        pub(super) fn new(brokers: &str, topic: &str) -> anyhow::Result<Self> {
+            let producer = ClientConfig::new()
+                .set("bootstrap.servers", brokers)
+                .create()?;
+            Ok(Self {
+                producer,
+                topic: topic.to_string(),
+            })
+        }
+    }
+
+    impl Sink for KafkaSink {
+        fn write(&mut self, txn_hash: &HashValue, summary: &[u8]) -> anyhow::Result<()> {
+            let key = txn_hash.to_hex();
+            self.producer
+                .send(BaseRecord::to(&self.topic).key(&key).payload(summary))
+                .map_err(|(error, _)| anyhow!("Failed to produce to Kafka: {}", error))?;
+            self.producer.poll(Duration::from_secs(0));
+            Ok(())
+        }
+
+        fn flush(&mut self) -> anyhow::Result<()> {
+            self.producer.flush(Duration::from_secs(FLUSH_TIMEOUT_SECS));
+            Ok(())
+        }
+    }
+}
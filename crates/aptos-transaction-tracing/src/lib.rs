@@ -0,0 +1,232 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Traces where a transaction spends its time on a node, from API submission to commit, as
+//! OpenTelemetry spans exported over OTLP. Each stage is recorded as a span once it's over, so
+//! components only need the transaction hash and when the stage started.
+//!
+//! The trace id of a transaction is the first 16 bytes of its hash, so the spans of every stage,
+//! on every node exporting to the same collector, end up in a single trace.
+//!
+//! The OTLP exporter is only built with the `otlp` feature, which the node forwards as its own
+//! `otlp` feature. Without it, enabling tracing in the config fails at startup.
+
+use anyhow::{anyhow, ensure};
+use aptos_config::config::TransactionTracingConfig;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use once_cell::sync::OnceCell;
+use std::{convert::TryInto, time::SystemTime};
+use tokio::runtime::Runtime;
+
+/// The stages of a transaction's lifecycle, which are the names of its spans
+pub struct TxnStage;
+
+impl TxnStage {
+    /// From the API receiving the transaction to mempool accepting or rejecting it
+    pub const API_SUBMIT: &'static str = "api_submit";
+    /// From mempool accepting the transaction to consensus pulling it into a proposal
+    pub const MEMPOOL: &'static str = "mempool";
+    /// From the block holding the transaction being proposed to it being executed
+    pub const CONSENSUS: &'static str = "consensus";
+    /// The execution of the block holding the transaction
+    pub const EXECUTION: &'static str = "execution";
+    /// The commit of the block holding the transaction to storage
+    pub const COMMIT: &'static str = "commit";
+}
+
+struct TransactionTracer {
+    tracer: otlp::OtlpTracer,
+    /// Transactions whose hash, as a number, is below this are traced
+    sample_threshold: u64,
+}
+
+static TRACER: OnceCell<TransactionTracer> = OnceCell::new();
+
+/// Starts exporting transaction spans, if enabled in `config`. The exporter runs on the returned
+/// runtime, which must be kept alive.
+pub fn init(config: &TransactionTracingConfig) -> anyhow::Result<Option<Runtime>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    ensure!(
+        (0.0..=1.0).contains(&config.sample_rate),
+        "The transaction tracing sample rate must be between 0 and 1, not {}",
+        config.sample_rate
+    );
+
+    let (runtime, tracer) = otlp::OtlpTracer::new(config)?;
+    TRACER
+        .set(TransactionTracer {
+            tracer,
+            sample_threshold: (config.sample_rate * u64::MAX as f64) as u64,
+        })
+        .map_err(|_| anyhow!("Transaction tracing is already initialized"))?;
+    info!(
+        otlp_endpoint = config.otlp_endpoint,
+        sample_rate = config.sample_rate,
+        "Exporting transaction traces"
+    );
+    Ok(Some(runtime))
+}
+
+/// Whether spans are exported at all, so callers can skip hashing transactions otherwise
+pub fn is_enabled() -> bool {
+    TRACER.get().is_some()
+}
+
+fn is_sampled(txn_hash: &HashValue, sample_threshold: u64) -> bool {
+    let prefix: [u8; 8] = txn_hash.as_ref()[..8]
+        .try_into()
+        .expect("Hash values are 32 bytes");
+    u64::from_be_bytes(prefix) <= sample_threshold
+}
+
+/// Records the `stage` of the transaction with hash `txn_hash`, which ran from `start` until now,
+/// if tracing is enabled and the transaction is sampled.
+pub fn record(txn_hash: HashValue, stage: &'static str, start: SystemTime) {
+    record_with_attributes(txn_hash, stage, start, vec![]);
+}
+
+/// Like `record`, with additional attributes on the span, e.g. the id of the block
+pub fn record_with_attributes(
+    txn_hash: HashValue,
+    stage: &'static str,
+    start: SystemTime,
+    attributes: Vec<(&'static str, String)>,
+) {
+    let transaction_tracer = match TRACER.get() {
+        Some(transaction_tracer) => transaction_tracer,
+        None => return,
+    };
+    if !is_sampled(&txn_hash, transaction_tracer.sample_threshold) {
+        return;
+    }
+
+    transaction_tracer
+        .tracer
+        .export(txn_hash, stage, start, attributes);
+}
+
+#[cfg(feature = "otlp")]
+mod otlp {
+    use anyhow::anyhow;
+    use aptos_config::config::TransactionTracingConfig;
+    use aptos_crypto::HashValue;
+    use opentelemetry::{
+        sdk::{trace as sdktrace, Resource},
+        trace::{Span, SpanBuilder, TraceId, Tracer},
+        KeyValue,
+    };
+    use opentelemetry_otlp::WithExportConfig;
+    use std::{convert::TryInto, time::SystemTime};
+    use tokio::runtime::{Builder, Runtime};
+
+    pub(super) struct OtlpTracer(sdktrace::Tracer);
+
+    impl OtlpTracer {
+        /// Creates a tracer exporting batches of spans over OTLP, from the returned runtime
+        pub(super) fn new(config: &TransactionTracingConfig) -> anyhow::Result<(Runtime, Self)> {
+            let runtime = Builder::new_multi_thread()
+                .thread_name("txn-tracing")
+                .worker_threads(1)
+                .enable_all()
+                .build()
+                .map_err(|err| anyhow!("Failed to create transaction tracing runtime {}", err))?;
+            // The batch exporter is spawned on the current runtime
+            let enter = runtime.enter();
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(&config.otlp_endpoint),
+                )
+                .with_trace_config(sdktrace::config().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", config.service_name.clone()),
+                ])))
+                .install_batch(opentelemetry::runtime::Tokio)
+                .map_err(|err| anyhow!("Failed to create the OTLP exporter {}", err))?;
+            drop(enter);
+            Ok((runtime, Self(tracer)))
+        }
+
+        pub(super) fn export(
+            &self,
+            txn_hash: HashValue,
+            stage: &'static str,
+            start: SystemTime,
+            attributes: Vec<(&'static str, String)>,
+        ) {
+            let trace_id: [u8; 16] = txn_hash.as_ref()[..16]
+                .try_into()
+                .expect("Hash values are 32 bytes");
+            let mut span_attributes = vec![KeyValue::new("txn_hash", txn_hash.to_hex_literal())];
+            span_attributes.extend(
+                attributes
+                    .into_iter()
+                    .map(|(key, value)| KeyValue::new(key, value)),
+            );
+            let mut span = self.0.build(
+                SpanBuilder::from_name(stage)
+                    .with_trace_id(TraceId::from_bytes(trace_id))
+                    .with_start_time(start)
+                    .with_attributes(span_attributes),
+            );
+            span.end();
+        }
+    }
+}
+
+#[cfg(not(feature = "otlp"))]
+mod otlp {
+    use anyhow::anyhow;
+    use aptos_config::config::TransactionTracingConfig;
+    use aptos_crypto::HashValue;
+    use std::time::SystemTime;
+    use tokio::runtime::Runtime;
+
+    /// Tracing can't be enabled without the `otlp` feature, so there's never a tracer
+    pub(super) enum OtlpTracer {}
+
+    impl OtlpTracer {
+        pub(super) fn new(_config: &TransactionTracingConfig) -> anyhow::Result<(Runtime, Self)> {
+            Err(anyhow!(
+                "Transaction tracing requires building the node with the `otlp` feature"
+            ))
+        }
+
+        pub(super) fn export(
+            &self,
+            _txn_hash: HashValue,
+            _stage: &'static str,
+            _start: SystemTime,
+            _attributes: Vec<(&'static str, String)>,
+        ) {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling() {
+        let low = HashValue::new([0; HashValue::LENGTH]);
+        let high = HashValue::new([0xff; HashValue::LENGTH]);
+        assert!(is_sampled(&low, 0));
+        assert!(!is_sampled(&high, 0));
+        assert!(is_sampled(&high, u64::MAX));
+
+        let half = u64::MAX / 2;
+        let mut bytes = [0; HashValue::LENGTH];
+        bytes[0] = 0x7f;
+        assert!(is_sampled(&HashValue::new(bytes), half));
+        bytes[0] = 0x80;
+        assert!(!is_sampled(&HashValue::new(bytes), half));
+    }
+}
@@ -9,6 +9,7 @@ pub mod create_resource_account;
 pub mod fund;
 pub mod list;
 pub mod transfer;
+pub mod watch;
 
 /// Tool for interacting with accounts
 ///
@@ -21,6 +22,7 @@ pub enum AccountTool {
     FundWithFaucet(fund::FundWithFaucet),
     List(list::ListAccount),
     Transfer(transfer::TransferCoins),
+    Watch(watch::WatchResource),
 }
 
 impl AccountTool {
@@ -31,6 +33,7 @@ impl AccountTool {
             AccountTool::FundWithFaucet(tool) => tool.execute_serialized().await,
             AccountTool::List(tool) => tool.execute_serialized().await,
             AccountTool::Transfer(tool) => tool.execute_serialized().await,
+            AccountTool::Watch(tool) => tool.execute_serialized().await,
         }
     }
 }
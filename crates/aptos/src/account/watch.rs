@@ -0,0 +1,237 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{
+    CliCommand, CliConfig, CliError, CliTypedResult, ConfigSearchMode, ProfileOptions, RestOptions,
+};
+use aptos_rest_client::Client;
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use move_deps::move_core_types::{language_storage::StructTag, parser::parse_struct_tag};
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::BTreeSet, time::Duration};
+
+/// Command to watch a resource of an account for changes
+///
+/// The resource is polled until interrupted, or until `--count` changes have been seen.  Each
+/// change is printed as a line of JSON, with the ledger version it was seen at and the fields
+/// that changed, by their path in the resource, e.g. `coin.value`.
+#[derive(Debug, Parser)]
+pub struct WatchResource {
+    /// Address of the account holding the resource
+    #[clap(long, alias = "address", parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// Type of the resource to watch, e.g. `0x1::stake::StakePool`
+    #[clap(long, parse(try_from_str = parse_struct_tag))]
+    pub(crate) resource: StructTag,
+
+    /// Seconds to wait between polls
+    #[clap(long, default_value_t = 1)]
+    pub(crate) interval_secs: u64,
+
+    /// Number of changes to wait for before exiting
+    #[clap(long)]
+    pub(crate) count: Option<u64>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// A change to a single field.  A missing `old` or `new` value means the field, or with an
+/// empty path the whole resource, was added or removed.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResourceChange {
+    pub version: u64,
+    pub changes: Vec<FieldChange>,
+}
+
+#[async_trait]
+impl CliCommand<u64> for WatchResource {
+    fn command_name(&self) -> &'static str {
+        "WatchResource"
+    }
+
+    async fn execute(self) -> CliTypedResult<u64> {
+        let account = if let Some(account) = self.account {
+            account
+        } else if let Some(Some(account)) = CliConfig::load_profile(
+            &self.profile_options.profile,
+            ConfigSearchMode::CurrentDirAndParents,
+        )?
+        .map(|p| p.account)
+        {
+            account
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "Please provide an account using --account or run aptos init".to_string(),
+            ));
+        };
+
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let interval = Duration::from_secs(self.interval_secs);
+        let (mut current, version) = get_resource(&client, account, &self.resource).await?;
+        eprintln!(
+            "Watching {} of {} from version {}",
+            self.resource, account, version
+        );
+        let mut seen = 0;
+        while self.count.map_or(true, |count| seen < count) {
+            tokio::time::sleep(interval).await;
+            let (resource, version) = get_resource(&client, account, &self.resource).await?;
+            let changes = diff_resource(current.as_ref(), resource.as_ref());
+            if !changes.is_empty() {
+                let change = ResourceChange { version, changes };
+                println!(
+                    "{}",
+                    serde_json::to_string(&change)
+                        .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                );
+                seen += 1;
+            }
+            current = resource;
+        }
+        Ok(seen)
+    }
+}
+
+/// The resource, if the account has it, and the ledger version it was read at.  Resources are
+/// listed rather than fetched one by one, to tell a missing resource apart from a failed request.
+async fn get_resource(
+    client: &Client,
+    account: AccountAddress,
+    resource_type: &StructTag,
+) -> CliTypedResult<(Option<Value>, u64)> {
+    let (resources, state) = client
+        .get_account_resources(account)
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?
+        .into_parts();
+    let resource = resources
+        .into_iter()
+        .find(|resource| &resource.resource_type == resource_type)
+        .map(|resource| resource.data);
+    Ok((resource, state.version))
+}
+
+/// The fields that differ between two states of a resource
+pub fn diff_resource(old: Option<&Value>, new: Option<&Value>) -> Vec<FieldChange> {
+    let mut changes = vec![];
+    match (old, new) {
+        (Some(old), Some(new)) => diff_values("", old, new, &mut changes),
+        (None, None) => {}
+        (old, new) => changes.push(FieldChange {
+            path: String::new(),
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+    }
+    changes
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changes: &mut Vec<FieldChange>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_values(&child(key), old, new, changes),
+                    (old, new) => changes.push(FieldChange {
+                        path: child(key),
+                        old: old.cloned(),
+                        new: new.cloned(),
+                    }),
+                }
+            }
+        }
+        // Vectors are compared element by element only while their length stays the same, as
+        // there's no telling which elements were inserted or removed otherwise
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                diff_values(&child(&index.to_string()), old, new, changes);
+            }
+        }
+        (old, new) => {
+            if old != new {
+                changes.push(FieldChange {
+                    path: path.to_string(),
+                    old: Some(old.clone()),
+                    new: Some(new.clone()),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_resource() {
+        let old = json!({
+            "active": {"value": "100"},
+            "pending_inactive": {"value": "0"},
+            "operator_address": "0x1",
+            "delegations": ["0xa", "0xb"],
+        });
+        let new = json!({
+            "active": {"value": "150"},
+            "pending_inactive": {"value": "0"},
+            "operator_address": "0x1",
+            "delegations": ["0xa", "0xc"],
+            "locked_until_secs": "10",
+        });
+        assert_eq!(
+            diff_resource(Some(&old), Some(&new)),
+            vec![
+                FieldChange {
+                    path: "active.value".to_string(),
+                    old: Some(json!("100")),
+                    new: Some(json!("150")),
+                },
+                FieldChange {
+                    path: "delegations.1".to_string(),
+                    old: Some(json!("0xb")),
+                    new: Some(json!("0xc")),
+                },
+                FieldChange {
+                    path: "locked_until_secs".to_string(),
+                    old: None,
+                    new: Some(json!("10")),
+                },
+            ]
+        );
+
+        assert!(diff_resource(Some(&old), Some(&old)).is_empty());
+        assert_eq!(
+            diff_resource(None, Some(&new)),
+            vec![FieldChange {
+                path: String::new(),
+                old: None,
+                new: Some(new),
+            }]
+        );
+    }
+}
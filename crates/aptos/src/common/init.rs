@@ -5,12 +5,14 @@ use crate::common::types::ConfigSearchMode;
 use crate::common::{
     types::{
         account_address_from_public_key, CliCommand, CliConfig, CliError, CliTypedResult,
-        EncodingOptions, PrivateKeyInputOptions, ProfileConfig, ProfileOptions, PromptOptions,
-        RngArgs,
+        EncodingOptions, LedgerOptions, PrivateKeyInputOptions, ProfileConfig, ProfileOptions,
+        PromptOptions, RngArgs,
     },
     utils::{fund_account, prompt_yes_with_override, read_line},
 };
 use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, ValidCryptoMaterialStringExt};
+use aptos_ledger::AptosLedger;
+use aptos_types::account_address::AccountAddress;
 use async_trait::async_trait;
 use clap::Parser;
 use reqwest::Url;
@@ -42,6 +44,8 @@ pub struct InitTool {
     #[clap(flatten)]
     pub(crate) private_key_options: PrivateKeyInputOptions,
     #[clap(flatten)]
+    pub(crate) ledger_options: LedgerOptions,
+    #[clap(flatten)]
     pub(crate) profile_options: ProfileOptions,
     #[clap(flatten)]
     pub(crate) prompt_options: PromptOptions,
@@ -135,7 +139,63 @@ impl CliCommand<()> for InitTool {
         };
         profile_config.faucet_url = faucet_url.clone().map(|inner| inner.to_string());
 
-        // Private key
+        // Private key, or the key on a Ledger device
+        let address = if self.ledger_options.ledger {
+            let path = self
+                .ledger_options
+                .derivation_path
+                .clone()
+                .unwrap_or_default();
+            let ledger = AptosLedger::connect()?;
+            let public_key = ledger.public_key(&path, false)?;
+            let address = account_address_from_public_key(&public_key);
+            eprintln!(
+                "Confirm that the Ledger device shows the address {} for {}",
+                address, path
+            );
+            ledger.public_key(&path, true)?;
+            profile_config.private_key = None;
+            profile_config.public_key = Some(public_key);
+            profile_config.account = Some(address);
+            profile_config.derivation_path = Some(path.to_string());
+            address
+        } else {
+            self.private_key_config(&mut profile_config)?
+        };
+
+        // Create account if it doesn't exist (and there's a faucet)
+        let client = aptos_rest_client::Client::new(rest_url);
+        if let Some(faucet_url) = faucet_url {
+            if client.get_account(address).await.is_err() {
+                eprintln!(
+                    "Account {} doesn't exist, creating it and funding it with {} coins",
+                    address, NUM_DEFAULT_COINS
+                );
+                fund_account(faucet_url, NUM_DEFAULT_COINS, address).await?;
+            }
+        }
+
+        // Ensure the loaded config has profiles setup for a possible empty file
+        if config.profiles.is_none() {
+            config.profiles = Some(BTreeMap::new());
+        }
+        config
+            .profiles
+            .as_mut()
+            .unwrap()
+            .insert(self.profile_options.profile, profile_config);
+        config.save()?;
+        eprintln!("Aptos is now set up for account {}!  Run `aptos help` for more information about commands", address);
+        Ok(())
+    }
+}
+
+impl InitTool {
+    /// Sets up the profile with a private key given, entered or generated, returning the account
+    fn private_key_config(
+        &self,
+        profile_config: &mut ProfileConfig,
+    ) -> CliTypedResult<AccountAddress> {
         let private_key = if let Some(private_key) = self
             .private_key_options
             .extract_private_key_cli(self.encoding_options.encoding)?
@@ -147,7 +207,7 @@ impl CliCommand<()> for InitTool {
             let input = read_line("Private key")?;
             let input = input.trim();
             if input.is_empty() {
-                if let Some(private_key) = profile_config.private_key {
+                if let Some(private_key) = profile_config.private_key.take() {
                     eprintln!("No key given, keeping existing key...");
                     private_key
                 } else {
@@ -166,30 +226,7 @@ impl CliCommand<()> for InitTool {
         profile_config.private_key = Some(private_key);
         profile_config.public_key = Some(public_key);
         profile_config.account = Some(address);
-
-        // Create account if it doesn't exist (and there's a faucet)
-        let client = aptos_rest_client::Client::new(rest_url);
-        if let Some(faucet_url) = faucet_url {
-            if client.get_account(address).await.is_err() {
-                eprintln!(
-                    "Account {} doesn't exist, creating it and funding it with {} coins",
-                    address, NUM_DEFAULT_COINS
-                );
-                fund_account(faucet_url, NUM_DEFAULT_COINS, address).await?;
-            }
-        }
-
-        // Ensure the loaded config has profiles setup for a possible empty file
-        if config.profiles.is_none() {
-            config.profiles = Some(BTreeMap::new());
-        }
-        config
-            .profiles
-            .as_mut()
-            .unwrap()
-            .insert(self.profile_options.profile, profile_config);
-        config.save()?;
-        eprintln!("Aptos is now set up for account {}!  Run `aptos help` for more information about commands", address);
-        Ok(())
+        profile_config.derivation_path = None;
+        Ok(address)
     }
 }
@@ -16,9 +16,10 @@ use crate::{
 };
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-    x25519, PrivateKey, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
+    x25519, PrivateKey, Signature, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
 use aptos_keygen::KeyGen;
+use aptos_ledger::{AptosLedger, DerivationPath};
 use aptos_rest_client::{
     aptos_api_types::{
         DeleteModule, DeleteResource, DeleteTableItem, WriteModule, WriteResource, WriteSetChange,
@@ -31,11 +32,11 @@ use aptos_sdk::{
         ident_str,
         language_storage::{ModuleId, TypeTag},
     },
-    transaction_builder::TransactionFactory,
+    transaction_builder::{TransactionBuilder, TransactionFactory},
     types::LocalAccount,
 };
 use aptos_types::transaction::{
-    authenticator::AuthenticationKey, EntryFunction, TransactionPayload,
+    authenticator::AuthenticationKey, EntryFunction, SignedTransaction, TransactionPayload,
 };
 use async_trait::async_trait;
 use clap::{ArgEnum, Parser};
@@ -79,6 +80,8 @@ pub enum CliError {
     IO(String, #[source] std::io::Error),
     #[error("Move compilation failed: {0}")]
     MoveCompilationError(String),
+    #[error("Move formatting check failed: {0}")]
+    MoveFormatError(String),
     #[error("Move unit tests failed")]
     MoveTestError,
     #[error("Move Prover failed: {0}")]
@@ -102,6 +105,7 @@ impl CliError {
             CliError::ConfigNotFoundError(_) => "ConfigNotFoundError",
             CliError::IO(_, _) => "IO",
             CliError::MoveCompilationError(_) => "MoveCompilationError",
+            CliError::MoveFormatError(_) => "MoveFormatError",
             CliError::MoveTestError => "MoveTestError",
             CliError::MoveProverError(_) => "MoveProverError",
             CliError::UnableToParse(_, _) => "UnableToParse",
@@ -117,6 +121,12 @@ impl From<aptos_config::config::Error> for CliError {
     }
 }
 
+impl From<aptos_ledger::AptosLedgerError> for CliError {
+    fn from(e: aptos_ledger::AptosLedgerError) -> Self {
+        CliError::UnexpectedError(e.to_string())
+    }
+}
+
 impl From<aptos_github_client::Error> for CliError {
     fn from(e: aptos_github_client::Error) -> Self {
         CliError::UnexpectedError(e.to_string())
@@ -195,6 +205,72 @@ pub struct ProfileConfig {
     /// URL for the Faucet endpoint (if applicable)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub faucet_url: Option<String>,
+    /// Derivation path of the key on a Ledger device, for profiles signing with a Ledger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    /// Named networks, each with its own endpoints and gas settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub networks: Option<BTreeMap<String, NetworkConfig>>,
+    /// The network in use, whose settings replace `rest_url` and `faucet_url`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+}
+
+/// A network of a profile, set up with `aptos config add-network`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// URL for the Aptos rest endpoint
+    pub rest_url: String,
+    /// URL for the Faucet endpoint, if the network has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub faucet_url: Option<String>,
+    /// Gas unit price for transactions, if not the default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_unit_price: Option<u64>,
+    /// Maximum gas for transactions, if not the default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_gas: Option<u64>,
+}
+
+impl ProfileConfig {
+    /// The network in use, if one was selected with `aptos config use-network`
+    pub fn active_network(&self) -> CliTypedResult<Option<&NetworkConfig>> {
+        let name = match self.network {
+            Some(ref name) => name,
+            None => return Ok(None),
+        };
+        self.networks
+            .as_ref()
+            .and_then(|networks| networks.get(name))
+            .map(Some)
+            .ok_or_else(|| {
+                CliError::ConfigLoadError(
+                    name.clone(),
+                    "is selected, but not one of the profile's networks".to_string(),
+                )
+            })
+    }
+
+    /// The rest URL of the network in use, or of the profile
+    pub fn rest_url(&self) -> CliTypedResult<Option<String>> {
+        Ok(match self.active_network()? {
+            Some(network) => Some(network.rest_url.clone()),
+            None => self.rest_url.clone(),
+        })
+    }
+
+    /// The faucet URL of the network in use, or of the profile
+    pub fn faucet_url(&self) -> CliTypedResult<Option<String>> {
+        match self.active_network()? {
+            Some(network) => network.faucet_url.clone().map(Some).ok_or_else(|| {
+                CliError::CommandArgumentError(format!(
+                    "Network {} doesn't have a faucet",
+                    self.network.as_deref().unwrap_or_default()
+                ))
+            }),
+            None => Ok(self.faucet_url.clone()),
+        }
+    }
 }
 
 /// ProfileConfig but without the private parts
@@ -209,6 +285,12 @@ pub struct ProfileSummary {
     pub rest_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub faucet_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub networks: Option<BTreeMap<String, NetworkConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
 }
 
 impl From<&ProfileConfig> for ProfileSummary {
@@ -219,6 +301,9 @@ impl From<&ProfileConfig> for ProfileSummary {
             account: config.account,
             rest_url: config.rest_url.clone(),
             faucet_url: config.faucet_url.clone(),
+            derivation_path: config.derivation_path.clone(),
+            networks: config.networks.clone(),
+            network: config.network.clone(),
         }
     }
 }
@@ -281,6 +366,20 @@ impl CliConfig {
         Ok(config.remove_profile(profile))
     }
 
+    /// Loads a setting of a profile, if the config and the profile exist
+    pub fn load_profile_setting<T>(
+        profile: &str,
+        setting: impl FnOnce(&ProfileConfig) -> CliTypedResult<Option<T>>,
+    ) -> CliTypedResult<Option<T>> {
+        if !Self::config_exists(ConfigSearchMode::CurrentDirAndParents) {
+            return Ok(None);
+        }
+        match Self::load_profile(profile, ConfigSearchMode::CurrentDirAndParents)? {
+            Some(profile) => setting(&profile),
+            None => Ok(None),
+        }
+    }
+
     pub fn remove_profile(&mut self, profile: &str) -> Option<ProfileConfig> {
         if let Some(ref mut profiles) = self.profiles {
             profiles.remove(&profile.to_string())
@@ -621,6 +720,11 @@ impl PrivateKeyInputOptions {
         }
     }
 
+    /// Whether a private key was given on the command line
+    pub fn is_set(&self) -> bool {
+        self.private_key.is_some() || self.private_key_file.is_some()
+    }
+
     /// Extract private key from CLI args
     pub fn extract_private_key_cli(
         &self,
@@ -639,6 +743,50 @@ impl PrivateKeyInputOptions {
     }
 }
 
+/// Options for signing with the Aptos app on a Ledger hardware wallet
+#[derive(Debug, Default, Parser)]
+pub struct LedgerOptions {
+    /// Sign with a connected Ledger device, instead of a private key
+    ///
+    /// Profiles set up with `aptos init --ledger` sign with the Ledger without this flag
+    #[clap(long, group = "private_key_input")]
+    pub(crate) ledger: bool,
+    /// BIP32 derivation path of the key on the Ledger device
+    ///
+    /// Defaults to the profile's derivation path, or m/44'/637'/0'/0'/0'
+    #[clap(long)]
+    pub(crate) derivation_path: Option<DerivationPath>,
+}
+
+impl LedgerOptions {
+    /// The derivation path to sign with, if signing with a Ledger.  Private keys given on the
+    /// command line take precedence over a Ledger set up in the profile.
+    pub fn derivation_path(
+        &self,
+        private_key_options: &PrivateKeyInputOptions,
+        profile: &str,
+    ) -> CliTypedResult<Option<DerivationPath>> {
+        if !self.ledger && private_key_options.is_set() {
+            return Ok(None);
+        }
+        let profile_path = CliConfig::load_profile_setting(profile, |profile| {
+            Ok(profile.derivation_path.clone())
+        })?;
+        if !self.ledger && profile_path.is_none() {
+            return Ok(None);
+        }
+        if let Some(path) = &self.derivation_path {
+            return Ok(Some(path.clone()));
+        }
+        match profile_path {
+            Some(path) => Ok(Some(DerivationPath::from_str(&path).map_err(|err| {
+                CliError::UnableToParse("derivation_path", err.to_string())
+            })?)),
+            None => Ok(Some(DerivationPath::default())),
+        }
+    }
+}
+
 impl ExtractPublicKey for PrivateKeyInputOptions {
     fn extract_public_key(
         &self,
@@ -712,7 +860,7 @@ impl SaveFile {
 pub struct RestOptions {
     /// URL to a fullnode on the network
     ///
-    /// Defaults to <https://fullnode.devnet.aptoslabs.com/v1>
+    /// Defaults to the URL of the profile's network, or <https://fullnode.devnet.aptoslabs.com/v1>
     #[clap(long)]
     url: Option<reqwest::Url>,
 }
@@ -726,9 +874,7 @@ impl RestOptions {
     pub fn url(&self, profile: &str) -> CliTypedResult<reqwest::Url> {
         if let Some(ref url) = self.url {
             Ok(url.clone())
-        } else if let Some(Some(url)) =
-            CliConfig::load_profile(profile, ConfigSearchMode::CurrentDirAndParents)?
-                .map(|p| p.rest_url)
+        } else if let Some(url) = CliConfig::load_profile_setting(profile, ProfileConfig::rest_url)?
         {
             reqwest::Url::parse(&url)
                 .map_err(|err| CliError::UnableToParse("Rest URL", err.to_string()))
@@ -996,6 +1142,8 @@ pub struct ChangeSummary {
 #[derive(Debug, Default, Parser)]
 pub struct FaucetOptions {
     /// URL for the faucet endpoint e.g. https://faucet.devnet.aptoslabs.com
+    ///
+    /// Defaults to the faucet of the profile's network
     #[clap(long)]
     faucet_url: Option<reqwest::Url>,
 }
@@ -1008,9 +1156,8 @@ impl FaucetOptions {
     pub fn faucet_url(&self, profile: &str) -> CliTypedResult<reqwest::Url> {
         if let Some(ref faucet_url) = self.faucet_url {
             Ok(faucet_url.clone())
-        } else if let Some(Some(url)) =
-            CliConfig::load_profile(profile, ConfigSearchMode::CurrentDirAndParents)?
-                .map(|profile| profile.faucet_url)
+        } else if let Some(url) =
+            CliConfig::load_profile_setting(profile, ProfileConfig::faucet_url)?
         {
             reqwest::Url::parse(&url)
                 .map_err(|err| CliError::UnableToParse("config faucet_url", err.to_string()))
@@ -1027,7 +1174,7 @@ pub const DEFAULT_MAX_GAS: u64 = 1_000;
 pub const DEFAULT_GAS_UNIT_PRICE: u64 = 1;
 
 /// Gas price options for manipulating how to prioritize transactions
-#[derive(Debug, Eq, Parser, PartialEq)]
+#[derive(Debug, Default, Eq, Parser, PartialEq)]
 pub struct GasOptions {
     /// Gas multiplier per unit of gas
     ///
@@ -1036,8 +1183,10 @@ pub struct GasOptions {
     /// be used as a multiplier for the amount of coins willing
     /// to be paid for a transaction.  This will prioritize the
     /// transaction with a higher gas unit price.
-    #[clap(long, default_value_t = DEFAULT_GAS_UNIT_PRICE)]
-    pub gas_unit_price: u64,
+    ///
+    /// Defaults to the gas unit price of the profile's network, or 1
+    #[clap(long)]
+    pub gas_unit_price: Option<u64>,
     /// Maximum amount of gas units to be used to send this transaction
     ///
     /// The maximum amount of gas units willing to pay for the transaction.
@@ -1047,16 +1196,37 @@ pub struct GasOptions {
     /// max gas set to 100 if the gas unit price is 1.  If I want it to have a
     /// gas unit price of 2, the max gas would need to be 50 to still only have
     /// a maximum price of 100 coins.
-    #[clap(long, default_value_t = DEFAULT_MAX_GAS)]
-    pub max_gas: u64,
+    ///
+    /// Defaults to the max gas of the profile's network, or 1000
+    #[clap(long)]
+    pub max_gas: Option<u64>,
 }
 
-impl Default for GasOptions {
-    fn default() -> Self {
-        GasOptions {
-            gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
-            max_gas: DEFAULT_MAX_GAS,
+impl GasOptions {
+    /// The gas unit price from the command line, or the profile's network
+    pub fn gas_unit_price(&self, profile: &str) -> CliTypedResult<u64> {
+        if let Some(gas_unit_price) = self.gas_unit_price {
+            return Ok(gas_unit_price);
         }
+        Ok(CliConfig::load_profile_setting(profile, |profile| {
+            Ok(profile
+                .active_network()?
+                .and_then(|network| network.gas_unit_price))
+        })?
+        .unwrap_or(DEFAULT_GAS_UNIT_PRICE))
+    }
+
+    /// The max gas from the command line, or the profile's network
+    pub fn max_gas(&self, profile: &str) -> CliTypedResult<u64> {
+        if let Some(max_gas) = self.max_gas {
+            return Ok(max_gas);
+        }
+        Ok(CliConfig::load_profile_setting(profile, |profile| {
+            Ok(profile
+                .active_network()?
+                .and_then(|network| network.max_gas))
+        })?
+        .unwrap_or(DEFAULT_MAX_GAS))
     }
 }
 
@@ -1066,6 +1236,8 @@ pub struct TransactionOptions {
     #[clap(flatten)]
     pub(crate) private_key_options: PrivateKeyInputOptions,
     #[clap(flatten)]
+    pub(crate) ledger_options: LedgerOptions,
+    #[clap(flatten)]
     pub(crate) encoding_options: EncodingOptions,
     #[clap(flatten)]
     pub(crate) profile_options: ProfileOptions,
@@ -1075,23 +1247,40 @@ pub struct TransactionOptions {
     pub(crate) gas_options: GasOptions,
 }
 
+/// What signs the transactions of a command
+enum TransactionSigner {
+    PrivateKey(Ed25519PrivateKey),
+    Ledger(DerivationPath),
+}
+
 impl TransactionOptions {
-    /// Retrieves the private key
-    fn private_key(&self) -> CliTypedResult<Ed25519PrivateKey> {
-        self.private_key_options.extract_private_key(
-            self.encoding_options.encoding,
-            &self.profile_options.profile,
-        )
+    /// Retrieves the private key, or the Ledger key to sign with
+    fn signer(&self) -> CliTypedResult<TransactionSigner> {
+        if let Some(path) = self
+            .ledger_options
+            .derivation_path(&self.private_key_options, &self.profile_options.profile)?
+        {
+            return Ok(TransactionSigner::Ledger(path));
+        }
+        self.private_key_options
+            .extract_private_key(
+                self.encoding_options.encoding,
+                &self.profile_options.profile,
+            )
+            .map(TransactionSigner::PrivateKey)
     }
 
     /// Builds a rest client
-    fn rest_client(&self) -> CliTypedResult<Client> {
+    pub(crate) fn rest_client(&self) -> CliTypedResult<Client> {
         self.rest_options.client(&self.profile_options.profile)
     }
 
     pub fn sender_address(&self) -> CliTypedResult<AccountAddress> {
-        let sender_key = self.private_key()?;
-        Ok(account_address_from_public_key(&sender_key.public_key()))
+        let sender_key = match self.signer()? {
+            TransactionSigner::PrivateKey(private_key) => private_key.public_key(),
+            TransactionSigner::Ledger(path) => AptosLedger::connect()?.public_key(&path, false)?,
+        };
+        Ok(account_address_from_public_key(&sender_key))
     }
 
     /// Submits an entry function based on module name and function inputs
@@ -1117,22 +1306,28 @@ impl TransactionOptions {
         &self,
         payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
-        let sender_key = self.private_key()?;
+        let signer = self.signer()?;
         let client = self.rest_client()?;
 
-        // Get sender address
-        let sender_address = self.sender_address()?;
-
-        // Get sequence number for account
-        let sequence_number = get_sequence_number(&client, sender_address).await?;
-
         // Sign and submit transaction
         let transaction_factory = TransactionFactory::new(chain_id(&client).await?)
-            .with_gas_unit_price(self.gas_options.gas_unit_price)
-            .with_max_gas_amount(self.gas_options.max_gas);
-        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-        let transaction =
-            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+            .with_gas_unit_price(
+                self.gas_options
+                    .gas_unit_price(&self.profile_options.profile)?,
+            )
+            .with_max_gas_amount(self.gas_options.max_gas(&self.profile_options.profile)?);
+        let transaction = match signer {
+            TransactionSigner::PrivateKey(sender_key) => {
+                let sender_address = account_address_from_public_key(&sender_key.public_key());
+                let sequence_number = get_sequence_number(&client, sender_address).await?;
+                let sender_account =
+                    &mut LocalAccount::new(sender_address, sender_key, sequence_number);
+                sender_account.sign_with_transaction_builder(transaction_factory.payload(payload))
+            }
+            TransactionSigner::Ledger(path) => {
+                sign_with_ledger(&client, &path, transaction_factory.payload(payload)).await?
+            }
+        };
         let response = client
             .submit_and_wait(&transaction)
             .await
@@ -1142,6 +1337,39 @@ impl TransactionOptions {
     }
 }
 
+/// Signs a transaction on a Ledger device, after the user checks the sender's address on the
+/// device's screen
+async fn sign_with_ledger(
+    client: &Client,
+    path: &DerivationPath,
+    builder: TransactionBuilder,
+) -> CliTypedResult<SignedTransaction> {
+    let ledger = AptosLedger::connect()?;
+    eprintln!(
+        "Using the Ledger device, Aptos app {}",
+        ledger.app_version()?
+    );
+    let public_key = ledger.public_key(path, false)?;
+    let sender_address = account_address_from_public_key(&public_key);
+    eprintln!(
+        "Confirm that the Ledger device shows the address {}",
+        sender_address
+    );
+    ledger.public_key(path, true)?;
+
+    let sequence_number = get_sequence_number(client, sender_address).await?;
+    let raw_txn = builder
+        .sender(sender_address)
+        .sequence_number(sequence_number)
+        .build();
+    eprintln!("Review and approve the transaction on the Ledger device");
+    let signature = ledger.sign(path, &raw_txn.signing_message())?;
+    signature.verify(&raw_txn, &public_key).map_err(|err| {
+        CliError::UnexpectedError(format!("Ledger returned an invalid signature: {}", err))
+    })?;
+    Ok(SignedTransaction::new(raw_txn, public_key, signature))
+}
+
 #[derive(Parser)]
 pub struct OptionalPoolAddressArgs {
     /// Address of the Staking pool
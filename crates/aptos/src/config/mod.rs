@@ -1,9 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::common::init::{DEFAULT_FAUCET_URL, DEFAULT_REST_URL};
 use crate::common::types::{
-    CliCommand, CliConfig, CliError, CliResult, CliTypedResult, ConfigSearchMode, ProfileSummary,
-    CONFIG_FOLDER,
+    CliCommand, CliConfig, CliError, CliResult, CliTypedResult, ConfigSearchMode, NetworkConfig,
+    ProfileConfig, ProfileOptions, ProfileSummary, CONFIG_FOLDER,
 };
 use crate::common::utils::{
     create_dir_if_not_exist, current_dir, read_from_file, write_to_user_only_file,
@@ -29,20 +30,26 @@ use std::str::FromStr;
 #[derive(Parser)]
 pub enum ConfigTool {
     Init(crate::common::init::InitTool),
+    AddNetwork(AddNetwork),
     GenerateShellCompletions(GenerateShellCompletions),
+    RemoveNetwork(RemoveNetwork),
     SetGlobalConfig(SetGlobalConfig),
     ShowGlobalConfig(ShowGlobalConfig),
     ShowProfiles(ShowProfiles),
+    UseNetwork(UseNetwork),
 }
 
 impl ConfigTool {
     pub async fn execute(self) -> CliResult {
         match self {
             ConfigTool::Init(tool) => tool.execute_serialized_success().await,
+            ConfigTool::AddNetwork(tool) => tool.execute_serialized().await,
             ConfigTool::GenerateShellCompletions(tool) => tool.execute_serialized_success().await,
+            ConfigTool::RemoveNetwork(tool) => tool.execute_serialized_success().await,
             ConfigTool::SetGlobalConfig(tool) => tool.execute_serialized().await,
             ConfigTool::ShowGlobalConfig(tool) => tool.execute_serialized().await,
             ConfigTool::ShowProfiles(tool) => tool.execute_serialized().await,
+            ConfigTool::UseNetwork(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -146,6 +153,182 @@ impl CliCommand<BTreeMap<String, ProfileSummary>> for ShowProfiles {
     }
 }
 
+const DEVNET: &str = "devnet";
+const LOCAL: &str = "local";
+const LOCAL_REST_URL: &str = "http://localhost:8080/v1";
+const LOCAL_FAUCET_URL: &str = "http://localhost:8081";
+
+/// Adds a named network to a profile, or replaces it
+///
+/// The endpoints of the `devnet` and `local` networks default to the Aptos devnet, and to
+/// a testnet run with `aptos node run-local-testnet --with-faucet`.  Select the network with
+/// `aptos config use-network` for commands to use its settings.
+#[derive(Parser, Debug)]
+pub struct AddNetwork {
+    /// Name of the network, e.g. devnet
+    #[clap(long)]
+    name: String,
+    /// URL to a fullnode on the network
+    #[clap(long)]
+    rest_url: Option<reqwest::Url>,
+    /// URL for the Faucet endpoint, if the network has one
+    #[clap(long)]
+    faucet_url: Option<reqwest::Url>,
+    /// Gas unit price for transactions on the network
+    #[clap(long)]
+    gas_unit_price: Option<u64>,
+    /// Maximum gas for transactions on the network
+    #[clap(long)]
+    max_gas: Option<u64>,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<NetworkConfig> for AddNetwork {
+    fn command_name(&self) -> &'static str {
+        "AddNetwork"
+    }
+
+    async fn execute(self) -> CliTypedResult<NetworkConfig> {
+        let (default_rest_url, default_faucet_url) = match self.name.as_str() {
+            DEVNET => (Some(DEFAULT_REST_URL), Some(DEFAULT_FAUCET_URL)),
+            LOCAL => (Some(LOCAL_REST_URL), Some(LOCAL_FAUCET_URL)),
+            _ => (None, None),
+        };
+        let rest_url = self
+            .rest_url
+            .map(|url| url.to_string())
+            .or_else(|| default_rest_url.map(str::to_string))
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(format!(
+                    "--rest-url is required for network {}",
+                    self.name
+                ))
+            })?;
+        let network = NetworkConfig {
+            rest_url,
+            faucet_url: self
+                .faucet_url
+                .map(|url| url.to_string())
+                .or_else(|| default_faucet_url.map(str::to_string)),
+            gas_unit_price: self.gas_unit_price,
+            max_gas: self.max_gas,
+        };
+
+        let name = self.name;
+        let result = network.clone();
+        update_profile(&self.profile_options.profile, |profile| {
+            profile
+                .networks
+                .get_or_insert_with(BTreeMap::new)
+                .insert(name, network);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+}
+
+/// Selects the network a profile's commands use
+///
+/// The network's endpoints and gas settings replace the profile's own `rest_url` and
+/// `faucet_url`, unless they are given on the command line.  Without `--name`, commands go
+/// back to using the profile's own endpoints.
+#[derive(Parser, Debug)]
+pub struct UseNetwork {
+    /// Name of a network added with `aptos config add-network`
+    #[clap(long)]
+    name: Option<String>,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<ProfileSummary> for UseNetwork {
+    fn command_name(&self) -> &'static str {
+        "UseNetwork"
+    }
+
+    async fn execute(self) -> CliTypedResult<ProfileSummary> {
+        let name = self.name;
+        update_profile(&self.profile_options.profile, |profile| {
+            if let Some(ref name) = name {
+                if !profile
+                    .networks
+                    .as_ref()
+                    .map_or(false, |networks| networks.contains_key(name))
+                {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "Network {} doesn't exist, add it with `aptos config add-network`",
+                        name
+                    )));
+                }
+            }
+            profile.network = name;
+            Ok(ProfileSummary::from(&*profile))
+        })
+    }
+}
+
+/// Removes a named network from a profile
+#[derive(Parser, Debug)]
+pub struct RemoveNetwork {
+    /// Name of the network
+    #[clap(long)]
+    name: String,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<()> for RemoveNetwork {
+    fn command_name(&self) -> &'static str {
+        "RemoveNetwork"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let name = self.name;
+        update_profile(&self.profile_options.profile, |profile| {
+            if profile
+                .networks
+                .as_mut()
+                .and_then(|networks| networks.remove(&name))
+                .is_none()
+            {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Network {} doesn't exist",
+                    name
+                )));
+            }
+            if profile.network.as_ref() == Some(&name) {
+                profile.network = None;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Applies `update` to a profile of the config in the current directory, and saves it
+fn update_profile<T>(
+    profile: &str,
+    update: impl FnOnce(&mut ProfileConfig) -> CliTypedResult<T>,
+) -> CliTypedResult<T> {
+    let mut config = CliConfig::load(ConfigSearchMode::CurrentDir)?;
+    let profile_config = config
+        .profiles
+        .as_mut()
+        .and_then(|profiles| profiles.get_mut(profile))
+        .ok_or_else(|| {
+            CliError::CommandArgumentError(format!(
+                "Profile {} doesn't exist, create it with `aptos init --profile {}`",
+                profile, profile
+            ))
+        })?;
+    let result = update(profile_config)?;
+    config.save()?;
+    Ok(result)
+}
+
 /// Shows the properties in the global config
 #[derive(Parser, Debug)]
 pub struct ShowGlobalConfig {}
@@ -0,0 +1,297 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::utils::{current_dir, read_from_file, write_to_file};
+use crate::genesis::git::{
+    from_yaml, to_yaml, Client, GitOptions, LAYOUT_FILE, OPERATOR_FILE, OPERATOR_SIGNATURE_FILE,
+    OWNER_FILE, OWNER_SIGNATURE_FILE,
+};
+use crate::{
+    common::types::{CliError, CliTypedResult},
+    CliCommand,
+};
+use aptos_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    hash::CryptoHash,
+    HashValue, PrivateKey, Signature, SigningKey, ValidCryptoMaterialStringExt,
+};
+use aptos_genesis::config::{
+    Layout, OperatorContribution, OwnerContribution, StringOperatorConfiguration,
+    StringOwnerConfiguration,
+};
+use aptos_genesis::keys::PrivateIdentity;
+use aptos_types::{chain_id::ChainId, waypoint::Waypoint};
+use async_trait::async_trait;
+use clap::{ArgEnum, Parser};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const PRIVATE_KEYS_FILE: &str = "private-keys.yaml";
+
+/// Which part of a validator's configuration a contribution covers
+#[derive(Debug, Copy, Clone, PartialEq, ArgEnum)]
+pub enum ContributionRole {
+    /// The owner configuration, signed with the owner account key
+    Owner,
+    /// The operator configuration, signed with the operator account key
+    Operator,
+}
+
+/// Sign a validator's owner or operator configuration in the git repository
+///
+/// The signature is stored next to the configuration, so the ceremony coordinator can verify
+/// that each configuration was set by the holder of the corresponding account key.
+#[derive(Parser)]
+pub struct SignContribution {
+    /// Name of validator
+    #[clap(long)]
+    pub(crate) username: String,
+
+    /// Which configuration to sign: [owner, operator]
+    #[clap(arg_enum, long)]
+    pub(crate) role: ContributionRole,
+
+    /// Path to private keys generated from GenerateKeys, defaults to `private-keys.yaml` in
+    /// the current directory
+    #[clap(long, parse(from_os_str))]
+    pub(crate) private_keys_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) git_options: GitOptions,
+}
+
+#[async_trait]
+impl CliCommand<()> for SignContribution {
+    fn command_name(&self) -> &'static str {
+        "SignContribution"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let private_keys_file = if let Some(private_keys_file) = self.private_keys_file {
+            private_keys_file
+        } else {
+            current_dir()?.join(PRIVATE_KEYS_FILE)
+        };
+        let bytes = read_from_file(private_keys_file.as_path())?;
+        let private_identity: PrivateIdentity =
+            from_yaml(&String::from_utf8(bytes).map_err(CliError::from)?)?;
+        let private_key = private_identity.account_private_key;
+
+        let client = self.git_options.get_client()?;
+        let layout: Layout = client.get(Path::new(LAYOUT_FILE))?;
+        let dir = PathBuf::from(&self.username);
+
+        // Refuse to sign with a key that the configuration doesn't expect, as the signature
+        // would never verify
+        let (expected_key, signature, signature_file) = match self.role {
+            ContributionRole::Owner => {
+                let contribution = OwnerContribution {
+                    chain_id: layout.chain_id,
+                    username: self.username.clone(),
+                    config: client.get(dir.join(OWNER_FILE).as_path())?,
+                };
+                (
+                    contribution.config.owner_account_public_key.clone(),
+                    private_key.sign(&contribution),
+                    dir.join(OWNER_SIGNATURE_FILE),
+                )
+            }
+            ContributionRole::Operator => {
+                let contribution = OperatorContribution {
+                    chain_id: layout.chain_id,
+                    username: self.username.clone(),
+                    config: client.get(dir.join(OPERATOR_FILE).as_path())?,
+                };
+                (
+                    contribution.config.operator_account_public_key.clone(),
+                    private_key.sign(&contribution),
+                    dir.join(OPERATOR_SIGNATURE_FILE),
+                )
+            }
+        };
+        let expected_key = expected_key
+            .map(|key| Ed25519PublicKey::from_encoded_string(&key))
+            .transpose()?;
+        if expected_key != Some(private_key.public_key()) {
+            return Err(CliError::CommandArgumentError(format!(
+                "Account key in {} does not match the {:?} account public key of {}",
+                private_keys_file.display(),
+                self.role,
+                self.username
+            )));
+        }
+
+        client.put(signature_file.as_path(), &signature)
+    }
+}
+
+/// Verify the signatures on all validator contributions in the git repository
+///
+/// This will output a transcript of the ceremony inputs, which can be compared against the
+/// transcript written by GenerateGenesis to check that the genesis blob was built from them.
+#[derive(Parser)]
+pub struct VerifyContributions {
+    /// Output file for the transcript
+    #[clap(long, parse(from_os_str))]
+    pub(crate) output_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) git_options: GitOptions,
+}
+
+#[async_trait]
+impl CliCommand<Transcript> for VerifyContributions {
+    fn command_name(&self) -> &'static str {
+        "VerifyContributions"
+    }
+
+    async fn execute(self) -> CliTypedResult<Transcript> {
+        let client = self.git_options.get_client()?;
+        let transcript = build_transcript(&client)?;
+
+        if let Some(output_file) = self.output_file {
+            write_to_file(
+                output_file.as_path(),
+                &output_file.display().to_string(),
+                to_yaml(&transcript)?.as_bytes(),
+            )?;
+        }
+
+        transcript.check_all_verified()?;
+        Ok(transcript)
+    }
+}
+
+/// An auditable record of the inputs to a genesis ceremony
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Transcript {
+    pub chain_id: ChainId,
+    /// Hash of the BCS encoded layout
+    pub layout_hash: HashValue,
+    /// Hash of the BCS encoded framework release bundle
+    pub framework_hash: HashValue,
+    pub contributions: Vec<ContributionRecord>,
+    /// Hash of the BCS encoded genesis transaction, when genesis was generated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis_hash: Option<HashValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waypoint: Option<Waypoint>,
+}
+
+impl Transcript {
+    /// Fails with every unverified contribution listed if any signature is missing or invalid
+    pub fn check_all_verified(&self) -> CliTypedResult<()> {
+        let mut errors = Vec::new();
+        for record in &self.contributions {
+            let checks = [("owner", &record.owner), ("operator", &record.operator)];
+            for (role, check) in checks.iter() {
+                if check.status != SignatureStatus::Verified {
+                    errors.push(format!("{} {}: {:?}", record.username, role, check.status));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CliError::UnexpectedError(format!(
+                "Failed to verify genesis contributions:\n{}",
+                errors.join("\n")
+            )))
+        }
+    }
+}
+
+/// The owner and operator contributions of a single validator
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContributionRecord {
+    pub username: String,
+    pub owner: ContributionCheck,
+    pub operator: ContributionCheck,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContributionCheck {
+    /// Hash of the signed contribution
+    pub hash: HashValue,
+    pub status: SignatureStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureStatus {
+    Verified,
+    Missing,
+    Invalid(String),
+}
+
+/// Checks the signatures of every user in the layout, and hashes the rest of the inputs
+pub fn build_transcript(client: &Client) -> CliTypedResult<Transcript> {
+    let layout: Layout = client.get(Path::new(LAYOUT_FILE))?;
+    let framework = client.get_framework()?;
+
+    let mut contributions = Vec::new();
+    for user in &layout.users {
+        let dir = PathBuf::from(user);
+        let owner = OwnerContribution {
+            chain_id: layout.chain_id,
+            username: user.clone(),
+            config: client.get::<StringOwnerConfiguration>(dir.join(OWNER_FILE).as_path())?,
+        };
+        let operator = OperatorContribution {
+            chain_id: layout.chain_id,
+            username: user.clone(),
+            config: client.get::<StringOperatorConfiguration>(dir.join(OPERATOR_FILE).as_path())?,
+        };
+
+        let owner_signature = client.get_optional(dir.join(OWNER_SIGNATURE_FILE).as_path())?;
+        let operator_signature =
+            client.get_optional(dir.join(OPERATOR_SIGNATURE_FILE).as_path())?;
+        contributions.push(ContributionRecord {
+            username: user.clone(),
+            owner: check_contribution(
+                &owner,
+                &owner.config.owner_account_public_key,
+                owner_signature,
+            ),
+            operator: check_contribution(
+                &operator,
+                &operator.config.operator_account_public_key,
+                operator_signature,
+            ),
+        });
+    }
+
+    Ok(Transcript {
+        chain_id: layout.chain_id,
+        layout_hash: HashValue::sha3_256_of(&bcs::to_bytes(&layout)?),
+        framework_hash: HashValue::sha3_256_of(&bcs::to_bytes(&framework)?),
+        contributions,
+        genesis_hash: None,
+        waypoint: None,
+    })
+}
+
+fn check_contribution<T: CryptoHash + Serialize>(
+    contribution: &T,
+    public_key: &Option<String>,
+    signature: Option<Ed25519Signature>,
+) -> ContributionCheck {
+    let status = match (public_key, signature) {
+        (_, None) => SignatureStatus::Missing,
+        (None, Some(_)) => SignatureStatus::Invalid("Missing account public key".to_string()),
+        (Some(public_key), Some(signature)) => {
+            match Ed25519PublicKey::from_encoded_string(public_key) {
+                Ok(public_key) => match signature.verify(contribution, &public_key) {
+                    Ok(()) => SignatureStatus::Verified,
+                    Err(err) => SignatureStatus::Invalid(err.to_string()),
+                },
+                Err(err) => SignatureStatus::Invalid(err.to_string()),
+            }
+        }
+    };
+
+    ContributionCheck {
+        hash: contribution.hash(),
+        status,
+    }
+}
@@ -22,6 +22,8 @@ use std::{fmt::Debug, io::Read, path::PathBuf, str::FromStr};
 pub const LAYOUT_FILE: &str = "layout.yaml";
 pub const OPERATOR_FILE: &str = "operator.yaml";
 pub const OWNER_FILE: &str = "owner.yaml";
+pub const OPERATOR_SIGNATURE_FILE: &str = "operator-signature.yaml";
+pub const OWNER_SIGNATURE_FILE: &str = "owner-signature.yaml";
 pub const FRAMEWORK_NAME: &str = "framework.mrb";
 
 /// Setup a shared Git repository for Genesis
@@ -165,6 +167,28 @@ impl Client {
         }
     }
 
+    /// Retrieves an object as a YAML encoded file from the appropriate storage, None if the
+    /// file doesn't exist
+    pub fn get_optional<T: DeserializeOwned + Debug>(
+        &self,
+        path: &Path,
+    ) -> CliTypedResult<Option<T>> {
+        match self {
+            Client::Local(local_repository_path) => {
+                if local_repository_path.join(path).exists() {
+                    self.get(path).map(Some)
+                } else {
+                    Ok(None)
+                }
+            }
+            Client::Github(client) => match client.get_file(&path.display().to_string()) {
+                Ok(contents) => from_base64_encoded_yaml(&contents).map(Some),
+                Err(aptos_github_client::Error::NotFound(_)) => Ok(None),
+                Err(err) => Err(err.into()),
+            },
+        }
+    }
+
     /// Puts an object as a YAML encoded file to the appropriate storage
     pub fn put<T: Serialize + ?Sized>(&self, name: &Path, input: &T) -> CliTypedResult<()> {
         match self {
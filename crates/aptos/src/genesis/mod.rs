@@ -1,12 +1,15 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod contributions;
 pub mod git;
 pub mod keys;
 #[cfg(test)]
 mod tests;
 
 use crate::common::utils::dir_default_to_current;
+use crate::genesis::contributions::build_transcript;
+use crate::genesis::git::to_yaml;
 use crate::genesis::git::{OPERATOR_FILE, OWNER_FILE};
 use crate::{
     common::{
@@ -16,7 +19,9 @@ use crate::{
     genesis::git::{Client, GitOptions, LAYOUT_FILE},
     CliCommand, CliResult,
 };
-use aptos_crypto::{bls12381, ed25519::Ed25519PublicKey, x25519, ValidCryptoMaterialStringExt};
+use aptos_crypto::{
+    bls12381, ed25519::Ed25519PublicKey, x25519, HashValue, ValidCryptoMaterialStringExt,
+};
 use aptos_genesis::builder::GenesisConfiguration;
 use aptos_genesis::config::{StringOperatorConfiguration, StringOwnerConfiguration};
 use aptos_genesis::{
@@ -31,6 +36,7 @@ use std::{path::PathBuf, str::FromStr};
 
 const WAYPOINT_FILE: &str = "waypoint.txt";
 const GENESIS_FILE: &str = "genesis.blob";
+const TRANSCRIPT_FILE: &str = "transcript.yaml";
 
 /// Tool for setting up an Aptos chain Genesis transaction
 ///
@@ -43,6 +49,8 @@ pub enum GenesisTool {
     GenerateLayoutTemplate(keys::GenerateLayoutTemplate),
     SetupGit(git::SetupGit),
     SetValidatorConfiguration(keys::SetValidatorConfiguration),
+    SignContribution(contributions::SignContribution),
+    VerifyContributions(contributions::VerifyContributions),
 }
 
 impl GenesisTool {
@@ -53,17 +61,26 @@ impl GenesisTool {
             GenesisTool::GenerateLayoutTemplate(tool) => tool.execute_serialized_success().await,
             GenesisTool::SetupGit(tool) => tool.execute_serialized_success().await,
             GenesisTool::SetValidatorConfiguration(tool) => tool.execute_serialized_success().await,
+            GenesisTool::SignContribution(tool) => tool.execute_serialized_success().await,
+            GenesisTool::VerifyContributions(tool) => tool.execute_serialized().await,
         }
     }
 }
 
 /// Generate genesis from a git repository
+///
+/// Alongside the genesis file and waypoint, this writes a transcript of the inputs, which lets
+/// anyone regenerate the genesis from the same repository and compare the results.
 #[derive(Parser)]
 pub struct GenerateGenesis {
-    /// Output directory for Genesis file and waypoint
+    /// Output directory for Genesis file, waypoint, and transcript
     #[clap(long, parse(from_os_str))]
     output_dir: Option<PathBuf>,
 
+    /// Fail if any owner or operator configuration isn't signed by its account key
+    #[clap(long)]
+    require_signatures: bool,
+
     #[clap(flatten)]
     prompt_options: PromptOptions,
     #[clap(flatten)]
@@ -82,15 +99,20 @@ impl CliCommand<Vec<PathBuf>> for GenerateGenesis {
         let waypoint_file = output_dir.join(WAYPOINT_FILE);
         check_if_file_exists(genesis_file.as_path(), self.prompt_options)?;
         check_if_file_exists(waypoint_file.as_path(), self.prompt_options)?;
+        let transcript_file = output_dir.join(TRANSCRIPT_FILE);
+        check_if_file_exists(transcript_file.as_path(), self.prompt_options)?;
+
+        // Verify contributions before doing anything expensive
+        let mut transcript = build_transcript(&self.git_options.clone().get_client()?)?;
+        if self.require_signatures {
+            transcript.check_all_verified()?;
+        }
 
         // Generate genesis file
         let mut genesis_info = fetch_genesis_info(self.git_options)?;
         let genesis = genesis_info.get_genesis();
-        write_to_file(
-            genesis_file.as_path(),
-            GENESIS_FILE,
-            &bcs::to_bytes(genesis).map_err(|e| CliError::BCS(GENESIS_FILE, e))?,
-        )?;
+        let genesis_bytes = bcs::to_bytes(genesis).map_err(|e| CliError::BCS(GENESIS_FILE, e))?;
+        write_to_file(genesis_file.as_path(), GENESIS_FILE, &genesis_bytes)?;
 
         // Generate waypoint file
         let waypoint = genesis_info.generate_waypoint()?;
@@ -99,7 +121,16 @@ impl CliCommand<Vec<PathBuf>> for GenerateGenesis {
             WAYPOINT_FILE,
             waypoint.to_string().as_bytes(),
         )?;
-        Ok(vec![genesis_file, waypoint_file])
+
+        // Generate transcript file
+        transcript.genesis_hash = Some(HashValue::sha3_256_of(&genesis_bytes));
+        transcript.waypoint = Some(waypoint);
+        write_to_file(
+            transcript_file.as_path(),
+            TRANSCRIPT_FILE,
+            to_yaml(&transcript)?.as_bytes(),
+        )?;
+        Ok(vec![genesis_file, waypoint_file, transcript_file])
     }
 }
 
@@ -152,6 +183,7 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
         validators,
         framework,
         &GenesisConfiguration {
+            accounts: vec![],
             allow_new_validators: layout.allow_new_validators,
             epoch_duration_secs: layout.epoch_duration_secs,
             is_test: layout.is_test,
@@ -161,6 +193,8 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
             recurring_lockup_duration_secs: layout.recurring_lockup_duration_secs,
             required_proposer_stake: layout.required_proposer_stake,
             rewards_apy_percentage: layout.rewards_apy_percentage,
+            total_supply: None,
+            vesting_accounts: vec![],
             voting_duration_secs: layout.voting_duration_secs,
             voting_power_increase_limit: layout.voting_power_increase_limit,
         },
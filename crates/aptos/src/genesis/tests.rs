@@ -3,8 +3,11 @@
 
 use crate::common::types::OptionalPoolAddressArgs;
 use crate::common::utils::read_from_file;
-use crate::genesis::git::from_yaml;
+use crate::genesis::contributions::{
+    ContributionRole, SignContribution, SignatureStatus, VerifyContributions,
+};
 use crate::genesis::git::FRAMEWORK_NAME;
+use crate::genesis::git::{from_yaml, to_yaml, OWNER_FILE};
 use crate::genesis::keys::{GenerateLayoutTemplate, PUBLIC_KEYS_FILE};
 use crate::{
     common::{
@@ -22,7 +25,7 @@ use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     PrivateKey,
 };
-use aptos_genesis::config::{HostAndPort, Layout};
+use aptos_genesis::config::{HostAndPort, Layout, StringOwnerConfiguration};
 use aptos_keygen::KeyGen;
 use aptos_temppath::TempPath;
 use aptos_types::chain_id::ChainId;
@@ -59,19 +62,97 @@ async fn test_genesis_e2e_flow() {
     // Add keys
     for (name, user_dir) in users.iter() {
         add_public_keys(name.to_string(), git_options.clone(), user_dir.as_path()).await;
+        sign_contributions(name.to_string(), git_options.clone(), user_dir.as_path()).await;
     }
 
     // Now generate genesis
     let output_dir = TempPath::new();
     output_dir.create_as_dir().unwrap();
     let output_dir = PathBuf::from(output_dir.path());
-    generate_genesis(git_options, output_dir.clone()).await;
+    generate_genesis(git_options.clone(), output_dir.clone()).await;
 
     // TODO: Verify that these are good
     let waypoint_file = output_dir.join("waypoint.txt");
     assert!(waypoint_file.exists());
     let genesis_file = output_dir.join("genesis.blob");
     assert!(genesis_file.exists());
+    let transcript_file = output_dir.join("transcript.yaml");
+    assert!(transcript_file.exists());
+
+    // Regenerating genesis from the same inputs must give the same transcript
+    let regenerated_dir = TempPath::new();
+    regenerated_dir.create_as_dir().unwrap();
+    let regenerated_dir = PathBuf::from(regenerated_dir.path());
+    generate_genesis(git_options, regenerated_dir.clone()).await;
+    assert_eq!(
+        read_from_file(transcript_file.as_path()).unwrap(),
+        read_from_file(regenerated_dir.join("transcript.yaml").as_path()).unwrap()
+    );
+}
+
+/// Changing a configuration after it was signed must fail verification
+#[tokio::test]
+async fn test_genesis_tampered_contribution() {
+    let dir = TempPath::new();
+    dir.create_as_dir().unwrap();
+    let name = "user-0".to_string();
+    let user_dir = generate_keys(dir.path(), 0).await;
+
+    let mut keygen = KeyGen::from_seed([1; 32]);
+    let root_private_key = keygen.generate_ed25519_private_key();
+    let git_options = setup_git_dir(&root_private_key, vec![name.clone()], ChainId::test()).await;
+    add_public_keys(name.clone(), git_options.clone(), user_dir.as_path()).await;
+    sign_contributions(name.clone(), git_options.clone(), user_dir.as_path()).await;
+
+    let transcript = VerifyContributions {
+        output_file: None,
+        git_options: git_options.clone(),
+    }
+    .execute()
+    .await
+    .unwrap();
+    assert_eq!(
+        SignatureStatus::Verified,
+        transcript.contributions[0].owner.status
+    );
+
+    // Bump the stake after signing
+    let owner_file = git_options
+        .local_repository_dir
+        .as_ref()
+        .unwrap()
+        .join(&name)
+        .join(OWNER_FILE);
+    let mut owner_config: StringOwnerConfiguration =
+        from_yaml(&String::from_utf8(read_from_file(owner_file.as_path()).unwrap()).unwrap())
+            .unwrap();
+    owner_config.stake_amount = Some("200000000000000".to_string());
+    write_to_file(
+        owner_file.as_path(),
+        "Owner file",
+        to_yaml(&owner_config).unwrap().as_bytes(),
+    )
+    .unwrap();
+
+    VerifyContributions {
+        output_file: None,
+        git_options: git_options.clone(),
+    }
+    .execute()
+    .await
+    .unwrap_err();
+
+    let output_dir = TempPath::new();
+    output_dir.create_as_dir().unwrap();
+    GenerateGenesis {
+        prompt_options: PromptOptions::yes(),
+        git_options,
+        output_dir: Some(PathBuf::from(output_dir.path())),
+        require_signatures: true,
+    }
+    .execute()
+    .await
+    .unwrap_err();
 }
 
 /// Generate genesis and waypoint
@@ -80,6 +161,7 @@ async fn generate_genesis(git_options: GitOptions, output_dir: PathBuf) {
         prompt_options: PromptOptions::yes(),
         git_options,
         output_dir: Some(output_dir),
+        require_signatures: true,
     };
     let _ = command.execute().await.unwrap();
 }
@@ -188,3 +270,18 @@ async fn add_public_keys(username: String, git_options: GitOptions, keys_dir: &P
 
     command.execute().await.unwrap()
 }
+
+/// Sign the owner and operator configurations for a user, who is both owner and operator
+async fn sign_contributions(username: String, git_options: GitOptions, keys_dir: &Path) {
+    for role in [ContributionRole::Owner, ContributionRole::Operator].iter() {
+        SignContribution {
+            username: username.clone(),
+            role: *role,
+            private_keys_file: Some(PathBuf::from(keys_dir).join("private-keys.yaml")),
+            git_options: git_options.clone(),
+        }
+        .execute()
+        .await
+        .unwrap()
+    }
+}
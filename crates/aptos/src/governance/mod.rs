@@ -2,28 +2,40 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::common::types::{
-    CliError, CliTypedResult, PoolAddressArgs, PromptOptions, TransactionOptions,
-    TransactionSummary,
+    CliError, CliTypedResult, PoolAddressArgs, ProfileOptions, PromptOptions, RestOptions,
+    TransactionOptions, TransactionSummary,
+};
+use crate::common::utils::{
+    check_if_file_exists, create_dir_if_not_exist, prompt_yes_with_override, write_to_file,
 };
-use crate::common::utils::prompt_yes_with_override;
 use crate::move_tool::{compile_move, init_move_dir, ArgWithType, FunctionArgType};
 use crate::{CliCommand, CliResult};
 use aptos_crypto::HashValue;
 use aptos_logger::warn;
-use aptos_rest_client::aptos_api_types::U64;
-use aptos_rest_client::{aptos_api_types::MoveType, Transaction};
+use aptos_rest_client::aptos_api_types::{HexEncodedBytes, U64};
+use aptos_rest_client::{aptos_api_types::MoveType, Client, Transaction};
 use aptos_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
+    governance::{GovernanceVotingForumResource, GovernanceVotingProposal},
+    state_store::state_key::StateKey,
     transaction::{Script, TransactionPayload},
 };
 use async_trait::async_trait;
 use clap::Parser;
+use framework::{ReleaseBundle, ReleasePackage};
 use move_deps::{
+    move_binary_format::access::ModuleAccess,
     move_compiler::compiled_unit::CompiledUnitEnum,
-    move_core_types::{language_storage::TypeTag, transaction_argument::TransactionArgument},
+    move_core_types::{
+        language_storage::{ResourceKey, TypeTag},
+        move_resource::MoveStructType,
+        transaction_argument::TransactionArgument,
+    },
     move_package::BuildConfig,
 };
 use reqwest::Url;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde::Serialize;
 use std::path::Path;
@@ -46,6 +58,8 @@ pub enum GovernanceTool {
     Propose(SubmitProposal),
     Vote(SubmitVote),
     ExecuteProposal(ExecuteProposal),
+    GenerateUpgradeProposal(GenerateUpgradeProposal),
+    VerifyProposal(VerifyProposal),
 }
 
 impl GovernanceTool {
@@ -55,6 +69,8 @@ impl GovernanceTool {
             Propose(tool) => tool.execute_serialized().await,
             Vote(tool) => tool.execute_serialized().await,
             ExecuteProposal(tool) => tool.execute_serialized().await,
+            GenerateUpgradeProposal(tool) => tool.execute_serialized().await,
+            VerifyProposal(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -206,6 +222,9 @@ async fn get_metadata(metadata_url: Url) -> CliTypedResult<(ProposalMetadata, Ha
 }
 
 /// Submit a vote on a current proposal
+///
+/// One vote is submitted for each of the given stake pools, which must all have the sender as
+/// their delegated voter.
 #[derive(Parser)]
 pub struct SubmitVote {
     /// Id of proposal to vote on
@@ -220,21 +239,29 @@ pub struct SubmitVote {
     #[clap(long, group = "vote")]
     pub(crate) no: bool,
 
+    /// Addresses of the stake pools to vote with, separated by spaces
+    #[clap(
+        long,
+        alias = "pool-address",
+        required = true,
+        multiple_values = true,
+        parse(try_from_str=crate::common::types::load_account_arg)
+    )]
+    pub(crate) pool_addresses: Vec<AccountAddress>,
+
     #[clap(flatten)]
     pub(crate) prompt_options: PromptOptions,
     #[clap(flatten)]
     pub(crate) txn_options: TransactionOptions,
-    #[clap(flatten)]
-    pub(crate) pool_address_args: PoolAddressArgs,
 }
 
 #[async_trait]
-impl CliCommand<Transaction> for SubmitVote {
+impl CliCommand<Vec<TransactionSummary>> for SubmitVote {
     fn command_name(&self) -> &'static str {
         "SubmitVote"
     }
 
-    async fn execute(mut self) -> CliTypedResult<Transaction> {
+    async fn execute(mut self) -> CliTypedResult<Vec<TransactionSummary>> {
         let (vote_str, vote) = match (self.yes, self.no) {
             (true, false) => ("Yes", true),
             (false, true) => ("No", false),
@@ -245,29 +272,104 @@ impl CliCommand<Transaction> for SubmitVote {
             }
         };
 
-        // TODO: Display details of proposal
+        let client = self.txn_options.rest_client()?;
+        let proposal = get_proposal(&client, self.proposal_id).await?;
+        if proposal.is_resolved {
+            return Err(CliError::CommandArgumentError(format!(
+                "Proposal {} has already been resolved",
+                self.proposal_id
+            )));
+        }
+        println!("{}", format_proposal(&proposal));
 
         prompt_yes_with_override(
-            &format!("Are you sure you want to vote {}", vote_str),
+            &format!(
+                "Are you sure you want to vote {} with {} stake pool(s)",
+                vote_str,
+                self.pool_addresses.len()
+            ),
             self.prompt_options,
         )?;
 
-        self.txn_options
-            .submit_entry_function(
-                AccountAddress::ONE,
-                "aptos_governance",
-                "vote",
-                vec![],
-                vec![
-                    bcs::to_bytes(&self.pool_address_args.pool_address)?,
-                    bcs::to_bytes(&self.proposal_id)?,
-                    bcs::to_bytes(&vote)?,
-                ],
-            )
-            .await
+        let mut summaries = vec![];
+        for pool_address in &self.pool_addresses {
+            let txn = self
+                .txn_options
+                .submit_entry_function(
+                    AccountAddress::ONE,
+                    "aptos_governance",
+                    "vote",
+                    vec![],
+                    vec![
+                        bcs::to_bytes(pool_address)?,
+                        bcs::to_bytes(&self.proposal_id)?,
+                        bcs::to_bytes(&vote)?,
+                    ],
+                )
+                .await?;
+            summaries.push(TransactionSummary::from(txn));
+        }
+        Ok(summaries)
     }
 }
 
+fn format_proposal(proposal: &GovernanceVotingProposal) -> String {
+    format!(
+        "Proposal:\n\tExecution hash:{}\n\tYes votes:{}\n\tNo votes:{}\n\tMinimum votes:{}\n\tExpiration:{}",
+        HexEncodedBytes::from(proposal.execution_hash.clone()),
+        proposal.yes_votes,
+        proposal.no_votes,
+        proposal.min_vote_threshold,
+        proposal.expiration_secs
+    )
+}
+
+/// Looks a proposal up in the governance voting forum. The forum and the proposal are read as
+/// stored, and decoded with their Rust bindings.
+async fn get_proposal(
+    client: &Client,
+    proposal_id: u64,
+) -> CliTypedResult<GovernanceVotingProposal> {
+    let version = client
+        .get_ledger_information()
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?
+        .into_inner()
+        .version;
+
+    let forum_key = StateKey::AccessPath(AccessPath::resource_access_path(ResourceKey::new(
+        AccountAddress::ONE,
+        GovernanceVotingForumResource::struct_tag(),
+    )));
+    let forum: GovernanceVotingForumResource = get_state_value(client, &forum_key, version)
+        .await?
+        .ok_or_else(|| CliError::ApiError("Governance voting forum not found".to_string()))?;
+
+    let proposal_key = StateKey::table_item(forum.proposals, bcs::to_bytes(&proposal_id)?);
+    get_state_value(client, &proposal_key, version)
+        .await?
+        .ok_or_else(|| {
+            CliError::CommandArgumentError(format!("Failed to find proposal {}", proposal_id))
+        })
+}
+
+async fn get_state_value<T: DeserializeOwned>(
+    client: &Client,
+    state_key: &StateKey,
+    version: u64,
+) -> CliTypedResult<Option<T>> {
+    let bytes = client
+        .get_raw_state_value(state_key, version)
+        .await
+        .map_err(|err| CliError::ApiError(err.to_string()))?;
+    bytes
+        .map(|bytes| bcs::from_bytes(&bytes))
+        .transpose()
+        .map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to decode {:?}: {}", state_key, err))
+        })
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProposalMetadata {
     title: String,
@@ -371,8 +473,15 @@ fn compile_script(package_dir: &Path) -> CliTypedResult<(Vec<u8>, HashValue)> {
 }
 
 /// Execute a proposal that has passed voting requirements
+///
+/// With `--proposal-id`, the compiled script is checked against the proposal's execution hash
+/// before anything is submitted, and the id is passed as the script's first argument.
 #[derive(Parser)]
 pub struct ExecuteProposal {
+    /// Id of the proposal to execute
+    #[clap(long)]
+    pub(crate) proposal_id: Option<u64>,
+
     /// Arguments combined with their type separated by spaces.
     ///
     /// Supported types [u8, u64, u128, bool, hex, string, address]
@@ -439,16 +548,35 @@ impl CliCommand<TransactionSummary> for ExecuteProposal {
     }
 
     async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
-        let (bytecode, _script_hash) = self.compile_proposal_args.compile()?;
-        // TODO: Check hash so we don't do a failed roundtrip?
+        let (bytecode, script_hash) = self.compile_proposal_args.compile()?;
 
         // TODO: Clean these up to be common with the run function in move
-        let args = self
+        let mut args = self
             .args
             .iter()
             .map(|arg_with_type| arg_with_type.try_into())
             .collect::<Result<Vec<TransactionArgument>, CliError>>()?;
 
+        if let Some(proposal_id) = self.proposal_id {
+            let client = self.txn_options.rest_client()?;
+            let proposal = get_proposal(&client, proposal_id).await?;
+            if proposal.is_resolved {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Proposal {} has already been resolved",
+                    proposal_id
+                )));
+            }
+            if proposal.execution_hash != script_hash.as_ref() {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Script hash {} doesn't match the execution hash {} of proposal {}",
+                    script_hash,
+                    HexEncodedBytes::from(proposal.execution_hash),
+                    proposal_id
+                )));
+            }
+            args.insert(0, TransactionArgument::U64(proposal_id));
+        }
+
         let mut type_args: Vec<TypeTag> = Vec::new();
 
         // These TypeArgs are used for generics
@@ -502,3 +630,144 @@ impl CompileProposalArgs {
         compile_in_temp_dir(script_path, &self.framework_git_rev, self.prompt_options)
     }
 }
+
+/// Generate the scripts of a proposal to upgrade the framework
+///
+/// One script is written for each package of the release, in the order they must be published,
+/// so that each can be submitted as its own proposal.  Each script takes the id of its proposal
+/// as its only argument.
+#[derive(Parser)]
+pub struct GenerateUpgradeProposal {
+    /// Path to the framework release bundle (.mrb) to upgrade to
+    ///
+    /// Defaults to the framework this CLI was built with
+    #[clap(long, parse(from_os_str))]
+    pub(crate) release_bundle: Option<PathBuf>,
+
+    /// Directory to write the proposal scripts to
+    #[clap(long, parse(from_os_str))]
+    pub(crate) output_dir: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for GenerateUpgradeProposal {
+    fn command_name(&self) -> &'static str {
+        "GenerateUpgradeProposal"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let bundle = if let Some(path) = &self.release_bundle {
+            ReleaseBundle::read(path.clone()).map_err(|err| {
+                CliError::UnableToReadFile(path.display().to_string(), err.to_string())
+            })?
+        } else {
+            cached_packages::head_release_bundle().clone()
+        };
+
+        create_dir_if_not_exist(self.output_dir.as_path())?;
+        let mut written = vec![];
+        for (index, package) in bundle.packages.iter().enumerate() {
+            let script = generate_upgrade_script(package)?;
+            let path = self
+                .output_dir
+                .join(format!("{}-{}.move", index, package.name()));
+            check_if_file_exists(path.as_path(), self.prompt_options)?;
+            write_to_file(path.as_path(), package.name(), script.as_bytes())?;
+            written.push(path.display().to_string());
+        }
+        Ok(written)
+    }
+}
+
+/// A script publishing `package` with the signer of the governance proposal it resolves
+fn generate_upgrade_script(package: &ReleasePackage) -> CliTypedResult<String> {
+    let modules = package.sorted_code_and_modules();
+    let address = match modules.first() {
+        Some((_, module)) => *module.self_id().address(),
+        None => {
+            return Err(CliError::UnexpectedError(format!(
+                "Package {} has no modules",
+                package.name()
+            )))
+        }
+    };
+    let metadata = bcs::to_bytes(package.package_metadata())?;
+
+    let mut script = String::new();
+    let mut line = |text: String| {
+        script.push_str(&text);
+        script.push('\n');
+    };
+    line(format!("// Upgrade of package {}", package.name()));
+    line("script {".to_string());
+    line("    use aptos_framework::aptos_governance;".to_string());
+    line("    use aptos_framework::code;".to_string());
+    line(String::new());
+    line("    fun main(proposal_id: u64) {".to_string());
+    line(format!(
+        "        let framework_signer = aptos_governance::resolve(proposal_id, @{});",
+        address.to_hex_literal()
+    ));
+    line("        let code = vector[".to_string());
+    let code = modules
+        .iter()
+        .map(|(code, _)| format!("            x\"{}\"", hex::encode(code)))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    line(code);
+    line("        ];".to_string());
+    line(format!(
+        "        code::publish_package_txn(&framework_signer, x\"{}\", code);",
+        hex::encode(&metadata)
+    ));
+    line("    }".to_string());
+    line("}".to_string());
+    Ok(script)
+}
+
+/// Verify that a proposal's execution hash matches a script
+///
+/// The script is compiled against the given framework revision, and its hash compared with the
+/// one the proposal was created with, which is the only script able to execute it.
+#[derive(Parser)]
+pub struct VerifyProposal {
+    /// Id of the proposal to verify
+    #[clap(long)]
+    pub(crate) proposal_id: u64,
+
+    #[clap(flatten)]
+    pub(crate) compile_proposal_args: CompileProposalArgs,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyProposalResponse {
+    pub verified: bool,
+    pub computed_hash: String,
+    pub onchain_hash: String,
+}
+
+#[async_trait]
+impl CliCommand<VerifyProposalResponse> for VerifyProposal {
+    fn command_name(&self) -> &'static str {
+        "VerifyProposal"
+    }
+
+    async fn execute(self) -> CliTypedResult<VerifyProposalResponse> {
+        let (_bytecode, script_hash) = self.compile_proposal_args.compile()?;
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let proposal = get_proposal(&client, self.proposal_id).await?;
+
+        Ok(VerifyProposalResponse {
+            verified: proposal.execution_hash == script_hash.as_ref(),
+            computed_hash: script_hash.to_hex_literal(),
+            onchain_hash: HexEncodedBytes::from(proposal.execution_hash).to_string(),
+        })
+    }
+}
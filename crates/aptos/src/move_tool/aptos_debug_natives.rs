@@ -10,6 +10,6 @@ pub fn aptos_debug_natives(gas_parameters: NativeGasParameters) -> NativeFunctio
     // As a side effect, also configure for unit testing
     natives::configure_for_unit_test();
     // Return all natives -- build with the 'testing' feature, therefore containing
-    // debug related functions.
-    natives::aptos_natives(gas_parameters)
+    // debug related functions -- and the natives of the framework's test-only modules.
+    natives::aptos_natives_for_unit_test(gas_parameters)
 }
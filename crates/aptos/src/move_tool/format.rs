@@ -0,0 +1,522 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A formatter for Move sources, for `aptos move fmt`
+//!
+//! The formatter works on tokens rather than on the syntax tree, so that it never loses
+//! comments and accepts any source the lexer does.  Line breaks are kept as written, while
+//! indentation, the spacing between tokens and blank lines are normalized:
+//! - Lines are indented by 4 spaces per open bracket, and by one more level when they continue
+//!   a statement from the line before.
+//! - Spaces around tokens follow fixed rules, e.g. `f(a, b)`, `x: u64` and `a == b`.  Tokens
+//!   whose spacing depends on how they are used, like `<` in `vector<u8>` and `a < b`, or `*` in
+//!   `*r` and `a * b`, keep whether they had a space before them.
+//! - Trailing whitespace is removed, runs of blank lines are collapsed into one, and blank
+//!   lines just inside brackets are removed.
+
+use crate::common::{
+    types::{CliCommand, CliError, CliTypedResult},
+    utils::{dir_default_to_current, read_from_file, write_to_file},
+};
+use async_trait::async_trait;
+use clap::Parser;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const INDENT: &str = "    ";
+
+/// Punctuation made of several characters, longest first
+const MULTI_CHAR_PUNCTUATION: &[&str] = &[
+    "<==>", "==>", "::", "==", "!=", "<=", ">=", "&&", "||", "..", "<<", ">>",
+];
+
+/// Tokens whose spacing depends on whether they are unary or binary, or on whether they delimit
+/// type arguments, so the space before them, and after them, is kept as written
+const CONTEXTUAL_PUNCTUATION: &[&str] =
+    &["<", ">", "<<", ">>", "&", "*", "-", "|", "||", "..", "$"];
+
+/// Keywords followed by a space before a parenthesis, unlike function names
+const KEYWORDS_BEFORE_PARENTHESIS: &[&str] = &[
+    "if",
+    "else",
+    "while",
+    "let",
+    "return",
+    "abort",
+    "as",
+    "in",
+    "requires",
+    "ensures",
+    "aborts_if",
+    "invariant",
+    "assume",
+    "assert",
+    "with",
+];
+
+/// Formats the Move source files of a package
+///
+/// Every `.move` file in the package is formatted in place, except for the build output.  With
+/// `--check`, the files are left as they are, and the command fails if any of them isn't
+/// formatted.
+#[derive(Parser)]
+pub struct FormatPackage {
+    /// Path to a move package (the folder with a Move.toml file)
+    #[clap(long, parse(from_os_str))]
+    pub(crate) package_dir: Option<PathBuf>,
+
+    /// Only check that the files are formatted, and fail if they aren't
+    #[clap(long)]
+    pub(crate) check: bool,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for FormatPackage {
+    fn command_name(&self) -> &'static str {
+        "FormatPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let package_dir = dir_default_to_current(self.package_dir)?;
+        let mut changed = vec![];
+        for path in move_files(&package_dir)? {
+            let name = path
+                .strip_prefix(&package_dir)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            let source = String::from_utf8(read_from_file(&path)?)
+                .map_err(|err| CliError::UnableToReadFile(name.clone(), err.to_string()))?;
+            let formatted = format_move_source(&source).map_err(|err| {
+                CliError::UnableToParse("Move source", format!("{}: {}", name, err))
+            })?;
+            if formatted == source {
+                continue;
+            }
+            if self.check {
+                let line = first_difference(&source, &formatted);
+                eprintln!(
+                    "{}:{} is not formatted, expected:\n{}",
+                    name,
+                    line + 1,
+                    formatted.lines().nth(line).unwrap_or_default()
+                );
+            } else {
+                write_to_file(&path, &name, formatted.as_bytes())?;
+            }
+            changed.push(name);
+        }
+
+        if self.check && !changed.is_empty() {
+            return Err(CliError::MoveFormatError(format!(
+                "{} files aren't formatted, run `aptos move fmt` to format them: {}",
+                changed.len(),
+                changed.join(", ")
+            )));
+        }
+        Ok(changed)
+    }
+}
+
+/// The `.move` files of a package, leaving out the build output and hidden directories
+fn move_files(package_dir: &Path) -> CliTypedResult<Vec<PathBuf>> {
+    let mut files = vec![];
+    let entries = WalkDir::new(package_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0
+                || !(name.starts_with('.') || (entry.depth() == 1 && name == "build"))
+        });
+    for entry in entries {
+        let entry = entry.map_err(|err| {
+            CliError::UnexpectedError(format!(
+                "Failed to list the files of {}: {}",
+                package_dir.display(),
+                err
+            ))
+        })?;
+        if entry.file_type().is_file()
+            && entry.path().extension().map_or(false, |ext| ext == "move")
+        {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+/// The index of the first line which differs between `a` and `b`
+fn first_difference(a: &str, b: &str) -> usize {
+    a.lines()
+        .zip(b.lines())
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| a.lines().count().min(b.lines().count()))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TokenKind {
+    Word,
+    Literal,
+    Punctuation,
+    Comment,
+    Newline,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    /// Whether the token was written with whitespace before it on the same line
+    space_before: bool,
+}
+
+impl<'a> Token<'a> {
+    fn is(&self, text: &str) -> bool {
+        self.kind == TokenKind::Punctuation && self.text == text
+    }
+
+    fn is_any(&self, texts: &[&str]) -> bool {
+        self.kind == TokenKind::Punctuation && texts.contains(&self.text)
+    }
+
+    fn opens(&self) -> bool {
+        self.is_any(&["{", "(", "["])
+    }
+
+    fn closes(&self) -> bool {
+        self.is_any(&["}", ")", "]"])
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let bytes = source.as_bytes();
+    let mut tokens = vec![];
+    let mut space_before = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &source[i..];
+        let c = bytes[i];
+        let (kind, len) = if c == b'\n' {
+            (TokenKind::Newline, 1)
+        } else if c.is_ascii_whitespace() {
+            space_before = true;
+            i += 1;
+            continue;
+        } else if rest.starts_with("//") {
+            (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if rest.starts_with("/*") {
+            let end = rest[2..]
+                .find("*/")
+                .ok_or_else(|| format!("unterminated block comment at byte {}", i))?;
+            (TokenKind::Comment, end + 4)
+        } else if c == b'"' || ((c == b'b' || c == b'x') && rest[1..].starts_with('"')) {
+            let start = if c == b'"' { 1 } else { 2 };
+            let mut end = None;
+            let mut escaped = false;
+            for (j, b) in rest.bytes().enumerate().skip(start) {
+                match b {
+                    b'\\' if !escaped => escaped = true,
+                    b'"' if !escaped => {
+                        end = Some(j + 1);
+                        break;
+                    }
+                    b'\n' => break,
+                    _ => escaped = false,
+                }
+            }
+            let end = end.ok_or_else(|| format!("unterminated string at byte {}", i))?;
+            (TokenKind::Literal, end)
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            (TokenKind::Word, word_length(rest))
+        } else if c.is_ascii_digit() {
+            (TokenKind::Literal, word_length(rest))
+        } else if let Some(punctuation) = MULTI_CHAR_PUNCTUATION
+            .iter()
+            .find(|punctuation| rest.starts_with(*punctuation))
+        {
+            (TokenKind::Punctuation, punctuation.len())
+        } else if c.is_ascii() {
+            (TokenKind::Punctuation, 1)
+        } else {
+            return Err(format!(
+                "unexpected character '{}' at byte {}",
+                rest.chars().next().unwrap_or_default(),
+                i
+            ));
+        };
+        tokens.push(Token {
+            kind,
+            text: &rest[..len],
+            space_before,
+        });
+        space_before = false;
+        i += len;
+    }
+    Ok(tokens)
+}
+
+fn word_length(text: &str) -> usize {
+    text.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+/// Whether to put a space between two tokens on the same line
+fn space_between(prev: &Token, next: &Token) -> bool {
+    // Comments inside brackets, like `f(/*limit=*/1000)`, are kept as written
+    if next.kind == TokenKind::Comment {
+        return !prev.opens() || next.space_before;
+    }
+    if prev.kind == TokenKind::Comment {
+        return next.space_before;
+    }
+    if next.is_any(&[",", ";", ")", "]", ".", "::", ":"]) {
+        return false;
+    }
+    if prev.is_any(&["(", "[", ".", "::", "@", "#", "!"]) {
+        return false;
+    }
+    if prev.is_any(CONTEXTUAL_PUNCTUATION) || next.is_any(CONTEXTUAL_PUNCTUATION) {
+        return next.space_before;
+    }
+    // `!` after a word is either a macro call like `assert!`, or a negation like `aborts_if !x`
+    if next.is("!") && prev.kind == TokenKind::Word {
+        return next.space_before;
+    }
+    // Spaces inside braces are kept as written, for both `S { f }` and `use 0x1::m::{Self, T}`
+    if prev.is("{") || next.is("}") {
+        return next.space_before;
+    }
+    if next.is_any(&["(", "["]) {
+        return match prev.kind {
+            TokenKind::Word => KEYWORDS_BEFORE_PARENTHESIS.contains(&prev.text),
+            TokenKind::Literal => false,
+            _ => !prev.is_any(&[")", "]"]),
+        };
+    }
+    true
+}
+
+/// Formats a Move source file, returning an error if it can't be tokenized, or if its brackets
+/// don't match
+pub fn format_move_source(source: &str) -> Result<String, String> {
+    let tokens = tokenize(source)?;
+    let lines: Vec<&[Token]> = tokens
+        .split(|token| token.kind == TokenKind::Newline)
+        .collect();
+
+    let mut output = String::new();
+    // The open brackets, with the indentation their content is relative to, and the indentation
+    // of the statement they were opened in
+    let mut open: Vec<(&str, usize, usize)> = vec![];
+    let mut blank_line = false;
+    // The last token of the line before, and the last code of the lines before
+    let mut last_token: Option<Token> = None;
+    let mut last_code: Option<Token> = None;
+    let mut last_code_is_attribute = false;
+
+    for (line_number, line) in lines.into_iter().enumerate() {
+        let first = match line.first() {
+            Some(first) => first,
+            None => {
+                blank_line = !output.is_empty();
+                continue;
+            }
+        };
+
+        let in_block = open.last().map_or(true, |(bracket, _, _)| *bracket == "{");
+        let continues = in_block
+            && !first.closes()
+            && !first.is("{")
+            && !last_code_is_attribute
+            && last_code.map_or(false, |token| {
+                !(token.is_any(&[";", ",", "{", "}"]) || token.opens())
+            });
+        let indent = match open.last() {
+            Some((_, indent, _)) if first.closes() => *indent,
+            Some((_, indent, _)) => indent + 1,
+            None => 0,
+        } + continues as usize;
+        // Blocks are indented relative to their statement, even when opened on a continuation
+        // line, or after a condition spanning several lines
+        let statement_indent = match open.last() {
+            Some((bracket, _, statement_indent)) if *bracket != "{" => *statement_indent,
+            _ => indent - continues as usize,
+        };
+
+        if blank_line && !first.closes() && !last_token.map_or(false, |token| token.opens()) {
+            output.push('\n');
+        }
+        blank_line = false;
+
+        output.push_str(&INDENT.repeat(indent));
+        let mut closes_attribute = false;
+        let mut prev: Option<&Token> = None;
+        for token in line {
+            if prev.map_or(false, |prev| space_between(prev, token)) {
+                output.push(' ');
+            }
+            if token.kind == TokenKind::Comment {
+                // Only the first line of a block comment is reindented
+                output.push_str(
+                    &token
+                        .text
+                        .lines()
+                        .map(str::trim_end)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+            } else {
+                output.push_str(token.text);
+            }
+
+            if token.kind != TokenKind::Comment {
+                closes_attribute = false;
+            }
+            if token.is("{") && open.last().map_or(true, |(bracket, _, _)| *bracket == "{") {
+                open.push((token.text, statement_indent, statement_indent));
+            } else if token.is("[") && prev.map_or(false, |prev| prev.is("#")) {
+                open.push(("#[", indent, statement_indent));
+            } else if token.opens() {
+                open.push((token.text, indent, statement_indent));
+            } else if token.closes() {
+                let expected: &[&str] = match token.text {
+                    "}" => &["{"],
+                    ")" => &["("],
+                    _ => &["[", "#["],
+                };
+                match open.pop() {
+                    Some((bracket, _, _)) if expected.contains(&bracket) => {
+                        closes_attribute = bracket == "#[";
+                    }
+                    _ => {
+                        return Err(format!(
+                            "unmatched '{}' on line {}",
+                            token.text,
+                            line_number + 1
+                        ))
+                    }
+                }
+            }
+            prev = Some(token);
+        }
+        output.push('\n');
+
+        last_token = line.last().copied();
+        if let Some(code) = line
+            .iter()
+            .rev()
+            .find(|token| token.kind != TokenKind::Comment)
+        {
+            last_code = Some(*code);
+            last_code_is_attribute = closes_attribute;
+        }
+    }
+
+    if let Some((bracket, _, _)) = open.last() {
+        return Err(format!("unclosed '{}'", bracket));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATTED: &str = "\
+/// A counter
+module 0x42::counter {
+    use std::signer;
+    use aptos_std::table::{Self, Table};
+
+    struct Counter has key {
+        value: u64,
+        history: vector<vector<u8>>,
+    }
+
+    #[test_only]
+    const E_TOO_BIG: u64 = 1;
+
+    /* Increments the counter,
+       by one */
+    public entry fun increment(account: &signer) acquires Counter {
+        let counter = borrow_global_mut<Counter>(signer::address_of(account));
+        if (counter.value < 10 && !is_max(counter)) {
+            counter.value = counter.value + 1; // Bump
+        } else {
+            abort E_TOO_BIG
+        };
+        let total = *&counter.value * 2
+            - 1;
+        assert!(total > 0, 0);
+        let s = Counter { value: 0, history: vector[b\"a\", x\"0F\"] };
+        let Counter { value: _, history: _ } = s;
+    }
+
+    spec increment {
+        aborts_if !exists<Counter>(signer::address_of(account));
+        ensures forall i in 0..10: i < 10 ==> true;
+    }
+}
+";
+
+    #[test]
+    fn test_format_move_source() {
+        let unformatted = "\
+/// A counter
+module 0x42::counter {
+  use std::signer ;
+  use aptos_std::table::{Self,Table};
+
+
+    struct Counter has key {
+
+      value : u64,
+      history: vector<vector<u8>>,
+    }
+
+    #[ test_only ]
+    const E_TOO_BIG: u64 = 1;
+
+/* Increments the counter,
+       by one */
+    public entry fun increment(account: &signer) acquires Counter
+    {
+        let counter=borrow_global_mut<Counter>( signer::address_of(account) );
+        if(counter.value < 10&&!is_max(counter)) {
+            counter.value = counter.value+1;// Bump
+        } else {
+            abort E_TOO_BIG
+        };
+        let total = *&counter.value * 2
+        - 1;
+        assert!(total > 0,0);
+        let s = Counter { value: 0, history: vector [b\"a\", x\"0F\"] };
+        let Counter { value: _, history: _ } = s;
+    }
+
+    spec increment {
+            aborts_if !exists<Counter>(signer::address_of(account));
+        ensures forall i in 0..10: i < 10 ==> true;
+    }
+}
+
+";
+        let formatted = format_move_source(unformatted).unwrap();
+        assert_eq!(
+            formatted,
+            FORMATTED.replace("acquires Counter {\n", "acquires Counter\n    {\n")
+        );
+        // Formatting is idempotent
+        assert_eq!(format_move_source(&formatted).unwrap(), formatted);
+        assert_eq!(format_move_source(FORMATTED).unwrap(), FORMATTED);
+    }
+
+    #[test]
+    fn test_format_move_source_errors() {
+        assert!(format_move_source("module 0x1::m {").is_err());
+        assert!(format_move_source("module 0x1::m { fun f() { } ) }").is_err());
+        assert!(format_move_source("/* unterminated").is_err());
+        assert!(format_move_source("const S: vector<u8> = b\"unterminated;").is_err());
+    }
+}
@@ -2,13 +2,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aptos_debug_natives;
+mod format;
 mod manifest;
 pub mod package_hooks;
 pub use package_hooks::*;
+mod prove;
+mod replay;
+mod replay_diff;
 pub mod stored_package;
+mod test_report;
 mod transactional_tests_runner;
+mod verify;
 
+pub use format::FormatPackage;
+pub use replay::ReplayTransaction;
+pub use replay_diff::ReplayDiff;
 pub use stored_package::*;
+pub use test_report::ReportFormat;
+pub use verify::VerifyPackage;
 
 use crate::common::types::MoveManifestAccountWrapper;
 use crate::common::types::{ProfileOptions, RestOptions};
@@ -37,11 +48,12 @@ use aptos_types::transaction::{EntryFunction, ModuleBundle, TransactionPayload};
 use async_trait::async_trait;
 use clap::{ArgEnum, Parser, Subcommand};
 use framework::natives::code::UpgradePolicy;
-use framework::{BuildOptions, BuiltPackage};
+use framework::{check_upgrade_compatibility, BuildOptions, BuiltPackage};
 use itertools::Itertools;
 use move_deps::move_cli::base::test::UnitTestResult;
 use move_deps::move_command_line_common::env::MOVE_HOME;
 use move_deps::{
+    move_binary_format::CompiledModule,
     move_cli,
     move_core_types::{
         identifier::Identifier,
@@ -72,6 +84,7 @@ use transactional_tests_runner::TransactionalTestOpts;
 #[derive(Subcommand)]
 pub enum MoveTool {
     Compile(CompilePackage),
+    Fmt(FormatPackage),
     Init(InitPackage),
     Publish(PublishPackage),
     Download(DownloadPackage),
@@ -80,13 +93,17 @@ pub enum MoveTool {
     Run(RunFunction),
     Test(TestPackage),
     Prove(ProvePackage),
+    Replay(ReplayTransaction),
+    ReplayDiff(ReplayDiff),
     TransactionalTest(TransactionalTestOpts),
+    VerifyPackage(VerifyPackage),
 }
 
 impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
             MoveTool::Compile(tool) => tool.execute_serialized().await,
+            MoveTool::Fmt(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
@@ -95,7 +112,10 @@ impl MoveTool {
             MoveTool::Run(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
+            MoveTool::Replay(tool) => tool.execute_serialized().await,
+            MoveTool::ReplayDiff(tool) => tool.execute_serialized().await,
             MoveTool::TransactionalTest(tool) => tool.execute_serialized_success().await,
+            MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -235,6 +255,20 @@ pub struct TestPackage {
     /// A filter string to determine which unit tests to run
     #[clap(long)]
     pub filter: Option<String>,
+
+    /// Report the bytecode instructions covered by the tests in each function of the package
+    #[clap(long)]
+    pub coverage: bool,
+
+    /// Report the gas used by each test
+    #[clap(long)]
+    pub gas_report: bool,
+
+    /// Format of the coverage and gas reports, either `table` or `json`
+    ///
+    /// JSON reports are written to `coverage.json` and `gas_report.json` in the output directory
+    #[clap(long, default_value_t = ReportFormat::Table)]
+    pub report_format: ReportFormat,
 }
 
 #[async_trait]
@@ -244,25 +278,74 @@ impl CliCommand<&'static str> for TestPackage {
     }
 
     async fn execute(self) -> CliTypedResult<&'static str> {
+        let package_path = self.move_options.get_package_path()?;
         let config = BuildConfig {
             additional_named_addresses: self.move_options.named_addresses(),
             test_mode: true,
             install_dir: self.move_options.output_dir.clone(),
             ..Default::default()
         };
-        let result = move_cli::base::test::run_move_unit_tests(
-            self.move_options.get_package_path()?.as_path(),
-            config,
-            UnitTestingConfig {
-                filter: self.filter,
-                ..UnitTestingConfig::default_with_bound(Some(100_000))
-            },
-            // TODO(Gas): we may want to switch to non-zero costs in the future
-            aptos_debug_natives::aptos_debug_natives(NativeGasParameters::zeros()),
-            false,
-            &mut std::io::stdout(),
-        )
-        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let unit_test_config = UnitTestingConfig {
+            filter: self.filter,
+            report_statistics: self.gas_report,
+            ..UnitTestingConfig::default_with_bound(Some(100_000))
+        };
+        // TODO(Gas): we may want to switch to non-zero costs in the future
+        let natives = aptos_debug_natives::aptos_debug_natives(NativeGasParameters::zeros());
+
+        // The gas used per test is only available from the test statistics in the output
+        let (result, gas_report) = if self.gas_report {
+            let mut output = Vec::new();
+            let result = move_cli::base::test::run_move_unit_tests(
+                package_path.as_path(),
+                config.clone(),
+                unit_test_config,
+                natives,
+                self.coverage,
+                &mut output,
+            );
+            let output = String::from_utf8_lossy(&output);
+            print!("{}", output);
+            (result, Some(test_report::parse_gas_report(&output)))
+        } else {
+            let result = move_cli::base::test::run_move_unit_tests(
+                package_path.as_path(),
+                config.clone(),
+                unit_test_config,
+                natives,
+                self.coverage,
+                &mut std::io::stdout(),
+            );
+            (result, None)
+        };
+        let result = result.map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+
+        let report_dir = self
+            .move_options
+            .output_dir
+            .clone()
+            .unwrap_or_else(|| package_path.join("build"));
+        if self.coverage {
+            let report = test_report::coverage_report(
+                package_path.as_path(),
+                BuildConfig {
+                    test_mode: false,
+                    ..config
+                },
+            )?;
+            match self.report_format {
+                ReportFormat::Table => test_report::print_coverage_table(&report),
+                ReportFormat::Json => test_report::write_json_report(
+                    &report_dir,
+                    test_report::COVERAGE_REPORT_FILE,
+                    &report,
+                )?,
+            }
+        }
+        // The statistics table printed by the tests already is the gas report table
+        if let (Some(gas_report), ReportFormat::Json) = (gas_report, self.report_format) {
+            test_report::write_json_report(&report_dir, test_report::GAS_REPORT_FILE, &gas_report)?;
+        }
 
         // TODO: commit back up to the move repo
         match result {
@@ -301,6 +384,13 @@ pub struct ProvePackage {
     /// A filter string to determine which files to verify
     #[clap(long)]
     pub filter: Option<String>,
+
+    /// Verify each function separately, skipping the functions whose code and specs didn't
+    /// change since they were last verified
+    ///
+    /// The results are cached in `<output_dir>/prover-cache.json`.
+    #[clap(long)]
+    pub incremental: bool,
 }
 
 #[async_trait]
@@ -316,6 +406,22 @@ impl CliCommand<&'static str> for ProvePackage {
             install_dir: self.move_options.output_dir.clone(),
             ..Default::default()
         };
+        if self.incremental {
+            let package_path = self.move_options.get_package_path()?;
+            let cache_path = self
+                .move_options
+                .output_dir
+                .clone()
+                .unwrap_or_else(|| package_path.join("build"))
+                .join(prove::CACHE_FILE);
+            return task::spawn_blocking(move || {
+                prove::prove_incrementally(config, &package_path, &self.filter, &cache_path)
+            })
+            .await
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            .map(|_| "Success");
+        }
+
         let result = task::spawn_blocking(move || {
             move_cli::base::prove::run_move_prover(
                 config,
@@ -361,11 +467,11 @@ pub struct PublishPackage {
     pub(crate) override_size_check: bool,
     /// What artifacts to include in the package. This can be one of `none`, `sparse`, and
     /// `all`. `none` is the most compact form and does not allow to reconstruct a source
-    /// package from chain; `sparse` is the minimal set of artifacts needed to reconstruct
-    /// a source package; `all` includes all available artifacts. The choice of included
-    /// artifacts heavily influences the size and therefore gas cost of publishing: `none`
-    /// is the size of bytecode alone; `sparse` is roughly 2 times as much; and `all` 3-4
-    /// as much.
+    /// package from chain, though it can still be depended on through interface stubs;
+    /// `sparse` is the minimal set of artifacts needed to reconstruct a source package;
+    /// `all` includes all available artifacts. The choice of included artifacts heavily
+    /// influences the size and therefore gas cost of publishing: `none` is the size of
+    /// bytecode alone; `sparse` is roughly 2 times as much; and `all` 3-4 as much.
     #[clap(long, default_value_t = IncludedArtifacts::Sparse)]
     pub(crate) included_artifacts: IncludedArtifacts,
 }
@@ -462,6 +568,9 @@ impl CliCommand<TransactionSummary> for PublishPackage {
         } else {
             // Send the compiled module and metadata using the code::publish_package_txn.
             let metadata = package.extract_metadata()?;
+            if metadata.upgrade_policy == UpgradePolicy::compat() {
+                check_onchain_compatibility(&txn_options, &compiled_units).await?;
+            }
             let payload = aptos_transaction_builder::aptos_stdlib::code_publish_package_txn(
                 bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
                 compiled_units,
@@ -484,6 +593,50 @@ impl CliCommand<TransactionSummary> for PublishPackage {
     }
 }
 
+/// Checks that the modules are compatible with the ones already published by the sender, as
+/// the upgrade would otherwise be rejected, with only a status code to tell why. Any error
+/// fetching the published modules is left for the transaction to report.
+async fn check_onchain_compatibility(
+    txn_options: &TransactionOptions,
+    compiled_units: &[Vec<u8>],
+) -> CliTypedResult<()> {
+    let onchain_modules = match txn_options
+        .rest_client()?
+        .get_account_modules(txn_options.sender_address()?)
+        .await
+    {
+        Ok(response) => response.into_inner(),
+        Err(_) => return Ok(()),
+    };
+    let mut onchain = BTreeMap::new();
+    for module in onchain_modules {
+        if let Ok(module) = CompiledModule::deserialize(module.bytecode.inner()) {
+            onchain.insert(module.self_id(), module);
+        }
+    }
+
+    let mut changes = vec![];
+    for code in compiled_units {
+        let module = CompiledModule::deserialize(code).map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to deserialize module: {}", err))
+        })?;
+        if let Some(old_module) = onchain.get(&module.self_id()) {
+            changes.extend(check_upgrade_compatibility(old_module, &module));
+        }
+    }
+    if changes.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::CommandArgumentError(format!(
+            "The package is not a compatible upgrade of the one on chain:\n{}",
+            changes
+                .iter()
+                .map(|change| format!("  {}", change))
+                .join("\n")
+        )))
+    }
+}
+
 /// Downloads a package and stores it in a directory named after the package.
 #[derive(Parser)]
 pub struct DownloadPackage {
@@ -531,6 +684,10 @@ impl CliCommand<&'static str> for DownloadPackage {
         package
             .save_package_to_disk(package_path.clone(), true)
             .map_err(|e| CliError::UnexpectedError(format!("cannot save package: {}", e)))?;
+        registry
+            .save_interface_stubs(&package, &package_path)
+            .await
+            .map_err(|e| CliError::UnexpectedError(format!("cannot save package: {}", e)))?;
         println!(
             "saved package with {} module(s) to `{}`",
             package.module_names().len(),
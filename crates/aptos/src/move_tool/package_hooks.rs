@@ -3,6 +3,8 @@
 
 use crate::common::types::load_account_arg;
 use crate::move_tool::CachedPackageRegistry;
+use anyhow::bail;
+use framework::natives::code::UpgradePolicy;
 use framework::UPGRADE_POLICY_CUSTOM_FIELD;
 use futures::executor::block_on;
 use move_deps::move_package::compilation::package_layout::CompiledPackageLayout;
@@ -35,6 +37,8 @@ impl PackageHooks for AptosPackageHooks {
     }
 }
 
+/// Downloads a package from the registry at the dependency's address, unless it was already
+/// downloaded to the local cache by an earlier build.
 async fn maybe_download_package(info: &CustomDepInfo) -> anyhow::Result<()> {
     if !info
         .download_to
@@ -47,6 +51,16 @@ async fn maybe_download_package(info: &CustomDepInfo) -> anyhow::Result<()> {
         )
         .await?;
         let package = registry.get_package(info.package_name).await?;
+        if package.upgrade_policy() == UpgradePolicy::arbitrary() {
+            bail!(
+                "package `{}` has upgrade policy `arbitrary`, and cannot be depended on",
+                info.package_name
+            )
+        }
+        // The build info marks the package as cached, so the stubs are written first
+        registry
+            .save_interface_stubs(&package, &info.download_to)
+            .await?;
         package.save_package_to_disk(info.download_to.clone(), true)
     } else {
         Ok(())
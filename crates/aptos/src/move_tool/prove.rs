@@ -0,0 +1,252 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental verification with the Move prover: each function is verified on its own, and
+//! skipped if neither its code nor the specs it's verified against changed since it was last
+//! verified successfully.
+
+use crate::common::{
+    types::{CliError, CliTypedResult},
+    utils::write_to_file,
+};
+use aptos_crypto::HashValue;
+use move_deps::{
+    move_model::model::{FunId, FunctionEnv, GlobalEnv, QualifiedId},
+    move_package::{BuildConfig, ModelConfig},
+    move_prover,
+    move_stackless_bytecode::options::VerificationScope,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+use termcolor::{ColorChoice, StandardStream};
+
+/// The name of the cache file, in the build directory of the package
+pub(crate) const CACHE_FILE: &str = "prover-cache.json";
+
+/// The hashes a verification result depends on
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct FunctionHashes {
+    /// The hash of the source of the function, and of the functions it calls
+    function_hash: HashValue,
+    /// The hash of the specs of the modules of the function, and of the functions it calls
+    spec_hash: HashValue,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct VerificationCache {
+    /// The version of the CLI which verified the functions, as other versions may bundle a
+    /// different prover
+    cli_version: String,
+    /// The hashes of the successfully verified functions, by name
+    functions: BTreeMap<String, FunctionHashes>,
+}
+
+impl VerificationCache {
+    fn new() -> Self {
+        Self {
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            functions: BTreeMap::new(),
+        }
+    }
+
+    /// Loads the cache, or returns an empty one if it's missing, unreadable or stale. Losing
+    /// the cache only means verifying every function again.
+    fn load(path: &Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+            .filter(|cache| cache.cli_version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_else(Self::new)
+    }
+
+    fn save(&self, path: &Path) -> CliTypedResult<()> {
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        write_to_file(path, CACHE_FILE, &bytes)
+    }
+
+    fn is_verified(&self, function: &str, hashes: &FunctionHashes) -> bool {
+        self.functions.get(function) == Some(hashes)
+    }
+}
+
+/// Verifies the functions of the package at `package_path` one by one, skipping the ones whose
+/// successful verification is recorded in the cache at `cache_path`, and prints the result of
+/// each function.
+pub(crate) fn prove_incrementally(
+    config: BuildConfig,
+    package_path: &Path,
+    filter: &Option<String>,
+    cache_path: &Path,
+) -> CliTypedResult<()> {
+    let env = build_model(&config, package_path, filter)?;
+    let mut functions = BTreeMap::new();
+    for module in env.get_modules().filter(|module| module.is_target()) {
+        for function in module.get_functions() {
+            // As with the default verification scope, only the entry points of the package are
+            // verified, along with the functions they call
+            if function.is_native() || !function.is_exposed() {
+                continue;
+            }
+            functions.insert(
+                function.get_full_name_str(),
+                function_hashes(&env, &function)?,
+            );
+        }
+    }
+    drop(env);
+
+    if let Some(parent) = cache_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| CliError::IO(parent.display().to_string(), err))?;
+    }
+    let mut cache = VerificationCache::load(cache_path);
+    let mut failed = vec![];
+    for (function, hashes) in functions {
+        if cache.is_verified(&function, &hashes) {
+            eprintln!("CACHED {}", function);
+            continue;
+        }
+        match verify_function(&config, package_path, filter, &function) {
+            Ok(()) => {
+                eprintln!("VERIFIED {}", function);
+                cache.functions.insert(function, hashes);
+            }
+            Err(err) => {
+                eprintln!("FAILED {}: {:#}", function, err);
+                cache.functions.remove(&function);
+                failed.push(function);
+            }
+        }
+        // Saved after each function, so that an interrupted run keeps its progress
+        cache.save(cache_path)?;
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::MoveProverError(format!(
+            "Failed to verify {}",
+            failed.join(", ")
+        )))
+    }
+}
+
+fn build_model(
+    config: &BuildConfig,
+    package_path: &Path,
+    filter: &Option<String>,
+) -> CliTypedResult<GlobalEnv> {
+    let env = config
+        .clone()
+        .move_model_for_package(
+            package_path,
+            ModelConfig {
+                all_files_as_targets: false,
+                target_filter: filter.clone(),
+            },
+        )
+        .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+    if env.has_errors() {
+        return Err(CliError::MoveCompilationError(
+            "The package has errors, run `aptos move prove` without --incremental to see them"
+                .to_string(),
+        ));
+    }
+    Ok(env)
+}
+
+/// Verifies a single function, in a model of its own, as the prover records its errors in the
+/// model
+fn verify_function(
+    config: &BuildConfig,
+    package_path: &Path,
+    filter: &Option<String>,
+    function: &str,
+) -> anyhow::Result<()> {
+    let mut env = build_model(config, package_path, filter)?;
+    let mut options = move_prover::cli::Options::default();
+    options.prover.verify_scope = VerificationScope::Only(function.to_string());
+    let mut error_writer = StandardStream::stderr(ColorChoice::Auto);
+    move_prover::run_move_prover_with_model(&mut env, &mut error_writer, options, None)
+}
+
+fn function_hashes(env: &GlobalEnv, function: &FunctionEnv) -> CliTypedResult<FunctionHashes> {
+    let mut functions = BTreeSet::new();
+    collect_called_functions(env, function.get_qualified_id(), &mut functions);
+
+    let mut code = vec![];
+    let mut modules = BTreeSet::new();
+    for id in &functions {
+        code.extend_from_slice(source(env, &env.get_function(*id).get_loc())?.as_bytes());
+        modules.insert(id.module_id);
+    }
+    let mut specs = vec![];
+    for module_id in modules {
+        for spec_block in env.get_module(module_id).get_spec_block_infos() {
+            specs.extend_from_slice(source(env, &spec_block.loc)?.as_bytes());
+        }
+    }
+    Ok(FunctionHashes {
+        function_hash: HashValue::sha3_256_of(&code),
+        spec_hash: HashValue::sha3_256_of(&specs),
+    })
+}
+
+fn collect_called_functions(
+    env: &GlobalEnv,
+    id: QualifiedId<FunId>,
+    functions: &mut BTreeSet<QualifiedId<FunId>>,
+) {
+    if functions.insert(id) {
+        for called in env.get_function(id).get_called_functions() {
+            collect_called_functions(env, called, functions);
+        }
+    }
+}
+
+fn source<'env>(
+    env: &'env GlobalEnv,
+    loc: &move_deps::move_model::model::Loc,
+) -> CliTypedResult<&'env str> {
+    env.get_source(loc)
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to read source: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILE);
+        let hashes = FunctionHashes {
+            function_hash: HashValue::random(),
+            spec_hash: HashValue::random(),
+        };
+
+        let mut cache = VerificationCache::load(&path);
+        assert!(!cache.is_verified("coin::transfer", &hashes));
+        cache
+            .functions
+            .insert("coin::transfer".to_string(), hashes.clone());
+        cache.save(&path).unwrap();
+
+        let mut cache = VerificationCache::load(&path);
+        assert!(cache.is_verified("coin::transfer", &hashes));
+        let changed_spec = FunctionHashes {
+            spec_hash: HashValue::random(),
+            ..hashes.clone()
+        };
+        assert!(!cache.is_verified("coin::transfer", &changed_spec));
+
+        // A cache written by another version of the CLI is ignored
+        cache.cli_version = "0.0.0".to_string();
+        cache.save(&path).unwrap();
+        assert!(VerificationCache::load(&path).functions.is_empty());
+    }
+}
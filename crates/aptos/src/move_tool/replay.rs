@@ -0,0 +1,270 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays committed transactions in the local VM, for post-mortem debugging
+
+use crate::common::types::{CliCommand, CliError, CliTypedResult, ProfileOptions, RestOptions};
+use anyhow::{anyhow, bail};
+use aptos_crypto::HashValue;
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_state_view_layers::CachingStateView;
+use aptos_types::{
+    state_store::state_key::StateKey,
+    transaction::{ExecutionStatus, TransactionStatus},
+    write_set::WriteOp,
+};
+use aptos_validator_interface::{
+    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+};
+use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::MoveResolverExt, AptosVM};
+use async_trait::async_trait;
+use clap::Parser;
+use serde::Serialize;
+use std::path::PathBuf;
+use tokio::{runtime::Handle, task};
+
+/// Read by the Move VM: the file to write the execution trace to
+const MOVE_VM_TRACE_ENV: &str = "MOVE_VM_TRACE";
+/// Read by the Move VM: whether to stop at each instruction for the interactive debugger
+const MOVE_VM_STEP_ENV: &str = "MOVE_VM_STEP";
+
+/// Replay a committed transaction locally
+///
+/// Fetches the transaction and the state it read from a node, or a local database, and
+/// executes it again in the local VM.  The events emitted and the state written are printed,
+/// with resources decoded into their Move structs.
+///
+/// With `--trace`, every executed bytecode instruction is written to a file.  With `--step`,
+/// execution stops before each instruction for the Move debugger, which reads commands from
+/// stdin: `step`, `continue`, `stack` to inspect the locals of the current frames,
+/// `breakpoint <function>`, `delete <function>` and `breakpoints`.
+#[derive(Parser)]
+pub struct ReplayTransaction {
+    /// Hash of the transaction to replay
+    #[clap(long)]
+    pub(crate) txn_hash: Option<String>,
+
+    /// Version of the transaction to replay
+    #[clap(long)]
+    pub(crate) txn_version: Option<u64>,
+
+    /// Path to a local AptosDB to replay from, instead of a node's REST API
+    ///
+    /// The transaction must be given by `--txn-version`
+    #[clap(long, parse(from_os_str))]
+    pub(crate) db_path: Option<PathBuf>,
+
+    /// File to write a trace of the executed instructions to
+    #[clap(long, parse(from_os_str))]
+    pub(crate) trace: Option<PathBuf>,
+
+    /// Step through the execution in the Move debugger
+    #[clap(long)]
+    pub(crate) step: bool,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The outcome of the transaction onchain, to compare the replay against
+#[derive(Debug, Serialize)]
+pub struct OnchainOutcome {
+    pub success: bool,
+    pub gas_used: u64,
+    pub vm_status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayedEvent {
+    pub key: String,
+    pub sequence_number: u64,
+    pub type_tag: String,
+    pub data: String,
+}
+
+/// A write to the state, with a value of `None` for deletions
+#[derive(Debug, Serialize)]
+pub struct ReplayedWrite {
+    pub state_key: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplaySummary {
+    pub version: u64,
+    pub success: bool,
+    pub gas_used: u64,
+    pub vm_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onchain: Option<OnchainOutcome>,
+    pub events: Vec<ReplayedEvent>,
+    pub writes: Vec<ReplayedWrite>,
+}
+
+#[async_trait]
+impl CliCommand<ReplaySummary> for ReplayTransaction {
+    fn command_name(&self) -> &'static str {
+        "ReplayTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<ReplaySummary> {
+        // The VM reads these once, on its first execution
+        if let Some(trace) = &self.trace {
+            std::env::set_var(MOVE_VM_TRACE_ENV, trace);
+        }
+        if self.step {
+            std::env::set_var(MOVE_VM_STEP_ENV, "1");
+        }
+
+        let (version, onchain, db): (_, _, Box<dyn AptosValidatorInterface + Send>) =
+            if let Some(db_path) = self.db_path {
+                let version = match (self.txn_hash, self.txn_version) {
+                    (None, Some(version)) => version,
+                    _ => {
+                        return Err(CliError::CommandArgumentError(
+                            "Replaying from a local database requires --txn-version".to_string(),
+                        ))
+                    }
+                };
+                let db = DBDebuggerInterface::open(&db_path).map_err(|err| {
+                    CliError::UnexpectedError(format!(
+                        "Failed to open database {}: {}",
+                        db_path.display(),
+                        err
+                    ))
+                })?;
+                (version, None, Box::new(db))
+            } else {
+                let client = self.rest_options.client(&self.profile_options.profile)?;
+                let transaction = match (self.txn_hash, self.txn_version) {
+                    (Some(hash), None) => {
+                        let hash =
+                            HashValue::from_hex(hash.trim_start_matches("0x")).map_err(|err| {
+                                CliError::UnableToParse("--txn-hash", err.to_string())
+                            })?;
+                        client.get_transaction_by_hash(hash).await
+                    }
+                    (None, Some(version)) => client.get_transaction_by_version(version).await,
+                    _ => {
+                        return Err(CliError::CommandArgumentError(
+                            "Exactly one of --txn-hash and --txn-version must be given".to_string(),
+                        ))
+                    }
+                }
+                .map_err(|err| CliError::ApiError(err.to_string()))?
+                .into_inner();
+                let info = transaction.transaction_info().map_err(|_| {
+                    CliError::CommandArgumentError(
+                        "The transaction hasn't been committed yet".to_string(),
+                    )
+                })?;
+                let onchain = OnchainOutcome {
+                    success: info.success,
+                    gas_used: info.gas_used.0,
+                    vm_status: info.vm_status.clone(),
+                };
+                let db = RestDebuggerInterface::new(client, Handle::current());
+                (info.version.0, Some(onchain), Box::new(db))
+            };
+
+        let mut summary = task::spawn_blocking(move || replay(&*db, version))
+            .await
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            .map_err(|err| CliError::UnexpectedError(format!("{:#}", err)))?;
+        summary.onchain = onchain;
+        if let Some(trace) = &self.trace {
+            eprintln!("Wrote execution trace to {}", trace.display());
+        }
+        Ok(summary)
+    }
+}
+
+/// Executes the transaction at `version` on top of the state left by the transaction before it
+fn replay(db: &dyn AptosValidatorInterface, version: u64) -> anyhow::Result<ReplaySummary> {
+    if version == 0 {
+        bail!("The genesis transaction can't be replayed");
+    }
+    let transaction = db
+        .get_committed_transactions(version, 1)?
+        .pop()
+        .ok_or_else(|| anyhow!("Transaction {} not found", version))?;
+
+    // The state is read again to decode the output, which shouldn't refetch it
+    let state_view = CachingStateView::new(DebuggerStateView::new(db, Some(version - 1)));
+    let (vm_status, output) =
+        AptosVM::execute_block_and_keep_vm_status(vec![transaction], &state_view)
+            .map_err(|err| anyhow!("Failed to execute transaction: {:?}", err))?
+            .pop()
+            .ok_or_else(|| anyhow!("No output for transaction {}", version))?;
+
+    // Resources are decoded with the types as they were before the transaction
+    let resolver = state_view.as_move_resolver();
+    let annotator = AptosValueAnnotator::new(&resolver);
+    let events = output
+        .events()
+        .iter()
+        .map(|event| ReplayedEvent {
+            key: event.key().to_string(),
+            sequence_number: event.sequence_number(),
+            type_tag: event.type_tag().to_string(),
+            data: annotator
+                .view_contract_event(event)
+                .map(|value| value.to_string())
+                .unwrap_or_else(|_| hex::encode(event.event_data())),
+        })
+        .collect();
+    let writes = output
+        .write_set()
+        .iter()
+        .map(|(state_key, op)| ReplayedWrite {
+            state_key: display_state_key(state_key),
+            value: display_write_op(&annotator, state_key, op),
+        })
+        .collect();
+
+    Ok(ReplaySummary {
+        version,
+        success: matches!(
+            output.status(),
+            TransactionStatus::Keep(ExecutionStatus::Success)
+        ),
+        gas_used: output.gas_used(),
+        vm_status: format!("{:?}", vm_status),
+        onchain: None,
+        events,
+        writes,
+    })
+}
+
+/// The value written by `op`, decoded if it's a resource, or `None` for a deletion
+pub(crate) fn display_write_op<V: MoveResolverExt>(
+    annotator: &AptosValueAnnotator<V>,
+    state_key: &StateKey,
+    op: &WriteOp,
+) -> Option<String> {
+    match op {
+        WriteOp::Creation(bytes) | WriteOp::Modification(bytes) => Some(match state_key {
+            StateKey::AccessPath(access_path) if access_path.is_code() => {
+                format!("<{} bytes of bytecode>", bytes.len())
+            }
+            StateKey::AccessPath(access_path) => annotator
+                .view_access_path(access_path.clone(), bytes)
+                .map(|value| value.to_string())
+                .unwrap_or_else(|_| hex::encode(bytes)),
+            _ => hex::encode(bytes),
+        }),
+        WriteOp::Deletion => None,
+    }
+}
+
+pub(crate) fn display_state_key(state_key: &StateKey) -> String {
+    match state_key {
+        StateKey::AccessPath(access_path) => access_path.to_string(),
+        StateKey::TableItem { handle, key } => {
+            format!("table {} item 0x{}", handle.0, hex::encode(key))
+        }
+        StateKey::Raw(bytes) => format!("raw 0x{}", hex::encode(bytes)),
+    }
+}
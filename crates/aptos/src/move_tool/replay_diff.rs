@@ -0,0 +1,350 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays a range of committed transactions under two configurations of the VM, and reports
+//! the first transaction whose output differs, e.g. to qualify a release or a gas schedule
+//! change against the history of a network.
+
+use crate::{
+    common::types::{CliCommand, CliError, CliTypedResult, ProfileOptions, RestOptions},
+    move_tool::replay::{display_state_key, display_write_op},
+};
+use anyhow::anyhow;
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_state_view::StateView;
+use aptos_state_view_layers::{CachingStateView, OverlayStateView};
+use aptos_types::{
+    contract_event::ContractEvent,
+    on_chain_config::{access_path_for_config, GasSchedule, OnChainConfig},
+    state_store::state_key::StateKey,
+    transaction::{Transaction, TransactionOutput, Version},
+    write_set::{WriteOp, WriteSet},
+};
+use aptos_validator_interface::{
+    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+};
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use async_trait::async_trait;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, path::PathBuf};
+use tokio::{runtime::Handle, task};
+
+/// Replay a range of committed transactions under two VMs, and report the first difference
+///
+/// The transactions from `--start-version` to `--end-version` are executed one by one, each on
+/// top of the state committed before it, by the local VM (the candidate) and by a baseline:
+///
+/// - With `--gas-schedule`, the candidate runs with the given gas schedule instead of the one
+///   on chain, and the baseline is the local VM with the gas schedule on chain.
+/// - With `--baseline`, the baseline is the outputs recorded with `--record` by another build
+///   of the CLI, e.g. the one of the release running on the network.
+///
+/// The first transaction whose status, gas, events or writes differ is reported, with the
+/// writes which differ decoded.
+#[derive(Parser)]
+pub struct ReplayDiff {
+    /// Version of the first transaction to replay
+    #[clap(long)]
+    pub(crate) start_version: Version,
+
+    /// Version of the last transaction to replay
+    #[clap(long)]
+    pub(crate) end_version: Version,
+
+    /// Path to a local AptosDB to replay from, instead of a node's REST API
+    #[clap(long, parse(from_os_str))]
+    pub(crate) db_path: Option<PathBuf>,
+
+    /// JSON file of a gas schedule for the candidate, in the format of the on chain config
+    #[clap(long, parse(from_os_str))]
+    pub(crate) gas_schedule: Option<PathBuf>,
+
+    /// File of outputs recorded with `--record`, to use as the baseline
+    #[clap(long, parse(from_os_str))]
+    pub(crate) baseline: Option<PathBuf>,
+
+    /// Only record the outputs of the candidate to this file, to be used as a baseline later
+    #[clap(long, parse(from_os_str))]
+    pub(crate) record: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The output of a transaction, as compared between the baseline and the candidate
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct RecordedOutput {
+    version: Version,
+    status: String,
+    gas_used: u64,
+    events: Vec<ContractEvent>,
+    write_set: WriteSet,
+}
+
+impl RecordedOutput {
+    fn new(version: Version, output: &TransactionOutput) -> Self {
+        Self {
+            version,
+            status: format!("{:?}", output.status()),
+            gas_used: output.gas_used(),
+            events: output.events().to_vec(),
+            write_set: output.write_set().clone(),
+        }
+    }
+}
+
+/// A state value written differently by the baseline and the candidate. A value of `None`
+/// means the value isn't written.
+#[derive(Debug, Serialize)]
+pub struct WriteDiff {
+    pub state_key: String,
+    pub baseline: Option<String>,
+    pub candidate: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Divergence {
+    pub version: Version,
+    pub baseline_status: String,
+    pub candidate_status: String,
+    pub baseline_gas_used: u64,
+    pub candidate_gas_used: u64,
+    pub events_differ: bool,
+    pub writes: Vec<WriteDiff>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayDiffSummary {
+    /// The number of transactions replayed
+    pub replayed: u64,
+    /// The first transaction whose output differs, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_divergence: Option<Divergence>,
+}
+
+/// Where the baseline outputs come from
+enum Baseline {
+    /// The local VM, with the gas schedule on chain
+    OnchainGasSchedule,
+    Recorded(Vec<RecordedOutput>),
+}
+
+#[async_trait]
+impl CliCommand<ReplayDiffSummary> for ReplayDiff {
+    fn command_name(&self) -> &'static str {
+        "ReplayDiff"
+    }
+
+    async fn execute(self) -> CliTypedResult<ReplayDiffSummary> {
+        if self.end_version < self.start_version || self.start_version == 0 {
+            return Err(CliError::CommandArgumentError(
+                "The versions must be a non-empty range, after genesis".to_string(),
+            ));
+        }
+        let baseline = match (&self.baseline, &self.record) {
+            (Some(_), Some(_)) => {
+                return Err(CliError::CommandArgumentError(
+                    "--record can't be used with --baseline".to_string(),
+                ))
+            }
+            (Some(path), None) => {
+                let bytes = std::fs::read(path)
+                    .map_err(|err| CliError::IO(path.display().to_string(), err))?;
+                Baseline::Recorded(
+                    bcs::from_bytes(&bytes).map_err(|err| CliError::BCS("baseline", err))?,
+                )
+            }
+            (None, None) if self.gas_schedule.is_none() => {
+                return Err(CliError::CommandArgumentError(
+                    "Nothing to compare: either --baseline or --gas-schedule must be given"
+                        .to_string(),
+                ))
+            }
+            (None, _) => Baseline::OnchainGasSchedule,
+        };
+        let gas_schedule = match &self.gas_schedule {
+            Some(path) => {
+                let bytes = std::fs::read(path)
+                    .map_err(|err| CliError::IO(path.display().to_string(), err))?;
+                let gas_schedule: GasSchedule = serde_json::from_slice(&bytes)
+                    .map_err(|err| CliError::UnableToParse("--gas-schedule", err.to_string()))?;
+                Some(gas_schedule)
+            }
+            None => None,
+        };
+
+        let db: Box<dyn AptosValidatorInterface + Send> = match &self.db_path {
+            Some(db_path) => Box::new(DBDebuggerInterface::open(db_path).map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Failed to open database {}: {}",
+                    db_path.display(),
+                    err
+                ))
+            })?),
+            None => Box::new(RestDebuggerInterface::new(
+                self.rest_options.client(&self.profile_options.profile)?,
+                Handle::current(),
+            )),
+        };
+
+        let (start_version, end_version, record) =
+            (self.start_version, self.end_version, self.record);
+        task::spawn_blocking(move || {
+            replay_diff(
+                &*db,
+                start_version..=end_version,
+                gas_schedule,
+                baseline,
+                record,
+            )
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+    }
+}
+
+fn replay_diff(
+    db: &dyn AptosValidatorInterface,
+    versions: std::ops::RangeInclusive<Version>,
+    gas_schedule: Option<GasSchedule>,
+    mut baseline: Baseline,
+    record: Option<PathBuf>,
+) -> CliTypedResult<ReplayDiffSummary> {
+    let gas_schedule = gas_schedule
+        .map(|gas_schedule| bcs::to_bytes(&gas_schedule))
+        .transpose()
+        .map_err(|err| CliError::BCS("gas schedule", err))?;
+    if let Baseline::Recorded(outputs) = &mut baseline {
+        outputs.retain(|output| versions.contains(&output.version));
+        outputs.reverse();
+    }
+
+    let mut recorded = vec![];
+    let mut replayed = 0;
+    for version in versions {
+        let transaction = db
+            .get_committed_transactions(version, 1)
+            .map_err(|err| CliError::UnexpectedError(format!("{:#}", err)))?
+            .pop()
+            .ok_or_else(|| {
+                CliError::UnexpectedError(format!("Transaction {} not found", version))
+            })?;
+        let state_view = CachingStateView::new(DebuggerStateView::new(db, Some(version - 1)));
+
+        let candidate_view = OverlayStateView::new(&state_view);
+        if let Some(gas_schedule) = &gas_schedule {
+            candidate_view.set_state_value(
+                StateKey::AccessPath(access_path_for_config(GasSchedule::CONFIG_ID)),
+                Some(gas_schedule.clone()),
+            );
+        }
+        let candidate = RecordedOutput::new(version, &execute(&candidate_view, &transaction)?);
+        replayed += 1;
+        if record.is_some() {
+            recorded.push(candidate);
+            continue;
+        }
+
+        let baseline = match &mut baseline {
+            Baseline::OnchainGasSchedule => {
+                RecordedOutput::new(version, &execute(&state_view, &transaction)?)
+            }
+            Baseline::Recorded(outputs) => match outputs.pop() {
+                Some(output) if output.version == version => output,
+                _ => {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "The baseline doesn't have the output of transaction {}",
+                        version
+                    )))
+                }
+            },
+        };
+        if baseline != candidate {
+            return Ok(ReplayDiffSummary {
+                replayed,
+                first_divergence: Some(diff(&state_view, &baseline, &candidate)),
+            });
+        }
+    }
+
+    if let Some(path) = record {
+        let bytes = bcs::to_bytes(&recorded).map_err(|err| CliError::BCS("outputs", err))?;
+        std::fs::write(&path, bytes)
+            .map_err(|err| CliError::IO(path.display().to_string(), err))?;
+    }
+    Ok(ReplayDiffSummary {
+        replayed,
+        first_divergence: None,
+    })
+}
+
+fn execute(
+    state_view: &impl StateView,
+    transaction: &Transaction,
+) -> CliTypedResult<TransactionOutput> {
+    AptosVM::execute_block(vec![transaction.clone()], state_view)
+        .map_err(|err| anyhow!("Failed to execute transaction: {:?}", err))
+        .and_then(|mut outputs| {
+            outputs
+                .pop()
+                .ok_or_else(|| anyhow!("No output for transaction"))
+        })
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))
+}
+
+/// Describes how the outputs differ, with the values written decoded with the types as they
+/// were before the transaction
+fn diff<'a>(
+    state_view: &impl StateView,
+    baseline: &'a RecordedOutput,
+    candidate: &'a RecordedOutput,
+) -> Divergence {
+    let resolver = state_view.as_move_resolver();
+    let annotator = AptosValueAnnotator::new(&resolver);
+    let write_op = |output: &'a RecordedOutput, state_key: &StateKey| {
+        output
+            .write_set
+            .iter()
+            .find(|(key, _)| *key == state_key)
+            .map(|(_, op)| op)
+    };
+    let display = |state_key: &StateKey, op: Option<&WriteOp>| {
+        op.map(|op| {
+            display_write_op(&annotator, state_key, op).unwrap_or_else(|| "<deleted>".to_string())
+        })
+    };
+
+    let state_keys: BTreeSet<_> = baseline
+        .write_set
+        .iter()
+        .chain(candidate.write_set.iter())
+        .map(|(state_key, _)| state_key)
+        .collect();
+    let writes = state_keys
+        .into_iter()
+        .filter_map(|state_key| {
+            let (baseline_op, candidate_op) = (
+                write_op(baseline, state_key),
+                write_op(candidate, state_key),
+            );
+            (baseline_op != candidate_op).then(|| WriteDiff {
+                state_key: display_state_key(state_key),
+                baseline: display(state_key, baseline_op),
+                candidate: display(state_key, candidate_op),
+            })
+        })
+        .collect();
+
+    Divergence {
+        version: candidate.version,
+        baseline_status: baseline.status.clone(),
+        candidate_status: candidate.status.clone(),
+        baseline_gas_used: baseline.gas_used,
+        candidate_gas_used: candidate.gas_used,
+        events_differ: baseline.events != candidate.events,
+        writes,
+    }
+}
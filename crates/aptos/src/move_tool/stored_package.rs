@@ -9,16 +9,21 @@ use framework::natives::code::{
     ModuleMetadata, PackageMetadata, PackageRegistry, PackageRegistryJson, UpgradePolicy,
 };
 use framework::unzip_metadata;
+use move_deps::move_binary_format::CompiledModule;
+use move_deps::move_compiler::interface_generator::write_module_to_string;
 use move_deps::move_package::compilation::package_layout::CompiledPackageLayout;
 use reqwest::Url;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // TODO: this is a first naive implementation of the package registry. Before mainnet
 // we need to use tables for the package registry.
 
 /// Represents the package registry at a given account.
 pub struct CachedPackageRegistry {
+    client: Client,
+    address: AccountAddress,
     inner: PackageRegistry,
 }
 
@@ -42,7 +47,11 @@ impl CachedPackageRegistry {
             .await?
             .into_inner();
         let inner = bcs::from_bytes::<PackageRegistry>(&bcs::to_bytes(&from_json)?)?;
-        Ok(Self { inner })
+        Ok(Self {
+            client,
+            address: addr,
+            inner,
+        })
     }
 
     /// Returns the list of packages in this registry by name.
@@ -79,6 +88,60 @@ impl CachedPackageRegistry {
         }
         bail!("package `{}` not found", name)
     }
+
+    /// Fetches the bytecode of the package's modules, by module name.
+    pub async fn get_bytecode(
+        &self,
+        package: &CachedPackageMetadata<'_>,
+    ) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+        let names = package.module_names();
+        let modules = self
+            .client
+            .get_account_modules(self.address)
+            .await?
+            .into_inner();
+        let mut bytecode = BTreeMap::new();
+        for module in modules {
+            let name = CompiledModule::deserialize(module.bytecode.inner())?
+                .self_id()
+                .name()
+                .to_string();
+            if names.contains(&name) {
+                bytecode.insert(name, module.bytecode.0);
+            }
+        }
+        Ok(bytecode)
+    }
+
+    /// Writes interface stubs for the modules of the package which were published without their
+    /// source, generated from their bytecode onchain. The stubs declare the module's public
+    /// types and functions, which is enough to compile against the package.
+    pub async fn save_interface_stubs(
+        &self,
+        package: &CachedPackageMetadata<'_>,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let missing = package.modules_without_source();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let sources_dir = path.join(CompiledPackageLayout::Sources.path());
+        fs::create_dir_all(&sources_dir)?;
+        let modules = self
+            .client
+            .get_account_modules(self.address)
+            .await?
+            .into_inner();
+        for module in modules {
+            let module = CompiledModule::deserialize(module.bytecode.inner())?;
+            let name = module.self_id().name().to_string();
+            if missing.contains(&name) {
+                let (_, stub) = write_module_to_string(&BTreeMap::new(), &module)?;
+                fs::write(sources_dir.join(format!("{}.move", name)), stub)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<'a> CachedPackageMetadata<'a> {
@@ -118,6 +181,16 @@ impl<'a> CachedPackageMetadata<'a> {
             .collect()
     }
 
+    /// Returns the modules of this package which were published without their source.
+    pub fn modules_without_source(&self) -> Vec<String> {
+        self.metadata
+            .modules
+            .iter()
+            .filter(|m| m.source.is_empty())
+            .map(|m| m.name.clone())
+            .collect()
+    }
+
     pub fn module(&self, name: impl AsRef<str>) -> anyhow::Result<CachedModuleMetadata<'_>> {
         let name = name.as_ref();
         for module in &self.metadata.modules {
@@ -142,7 +215,12 @@ impl<'a> CachedPackageMetadata<'a> {
         fs::write(path.join("BuildInfo.yaml"), &self.metadata.build_info)?;
         let sources_dir = path.join(CompiledPackageLayout::Sources.path());
         fs::create_dir_all(&sources_dir)?;
-        for module in &self.metadata.modules {
+        for module in self
+            .metadata
+            .modules
+            .iter()
+            .filter(|m| !m.source.is_empty())
+        {
             let source = std::str::from_utf8(&unzip_metadata(&module.source)?)?.to_string();
             fs::write(sources_dir.join(format!("{}.move", module.name)), source)?;
         }
@@ -165,7 +243,12 @@ impl<'a> CachedPackageMetadata<'a> {
             }
             let source_map_dir = path.join(CompiledPackageLayout::SourceMaps.path());
             fs::create_dir_all(&source_map_dir)?;
-            for module in &self.metadata.modules {
+            for module in self
+                .metadata
+                .modules
+                .iter()
+                .filter(|m| !m.source_map.is_empty())
+            {
                 fs::write(
                     source_map_dir.join(format!("{}.mvsm", module.name)),
                     &unzip_metadata(&module.source_map)?,
@@ -0,0 +1,260 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coverage and gas reports for `aptos move test`
+
+use crate::common::{
+    types::{CliError, CliTypedResult},
+    utils::write_to_file,
+};
+use clap::ArgEnum;
+use move_deps::{
+    move_command_line_common::files::MOVE_COVERAGE_MAP_EXTENSION,
+    move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule},
+    move_coverage::{coverage_map::CoverageMap, summary::summarize_inst_cov},
+    move_package::BuildConfig,
+};
+use serde::Serialize;
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+pub const COVERAGE_REPORT_FILE: &str = "coverage.json";
+pub const GAS_REPORT_FILE: &str = "gas_report.json";
+
+/// The header of the test statistics table printed by the Move unit test runner
+const TEST_STATISTICS_HEADER: &str = "Test Statistics";
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum ReportFormat {
+    Table,
+    Json,
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        use ReportFormat::*;
+        match self {
+            Table => f.write_str("table"),
+            Json => f.write_str("json"),
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ReportFormat::*;
+        match s {
+            "table" => Ok(Table),
+            "json" => Ok(Json),
+            _ => Err("unknown variant"),
+        }
+    }
+}
+
+/// Bytecode instructions executed by the unit tests in a single function
+#[derive(Debug, Serialize)]
+pub struct FunctionCoverage {
+    pub module: String,
+    pub function: String,
+    pub covered: u64,
+    pub total: u64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverageReport {
+    pub functions: Vec<FunctionCoverage>,
+    pub covered: u64,
+    pub total: u64,
+    pub percentage: f64,
+}
+
+/// Gas used by a single unit test
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct TestGasUsage {
+    pub test: String,
+    pub gas_used: u64,
+}
+
+fn percentage(covered: u64, total: u64) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        covered as f64 * 100.0 / total as f64
+    }
+}
+
+/// The coverage map written by the unit tests when run with coverage
+pub fn coverage_map_path(package_path: &Path) -> PathBuf {
+    package_path
+        .join(".coverage_map")
+        .with_extension(MOVE_COVERAGE_MAP_EXTENSION)
+}
+
+/// Summarizes the coverage map left by the unit tests per function of the package's own modules.
+/// Native functions are left out, as they have no bytecode to cover.
+pub fn coverage_report(
+    package_path: &Path,
+    build_config: BuildConfig,
+) -> CliTypedResult<CoverageReport> {
+    let coverage_map = CoverageMap::from_binary_file(coverage_map_path(package_path))
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to read coverage map {}", err)))?
+        .to_unified_exec_map();
+    let package = build_config
+        .compile_package(package_path, &mut Vec::new())
+        .map_err(|err| CliError::MoveCompilationError(err.to_string()))?;
+
+    let mut functions = vec![];
+    for unit in package.root_modules() {
+        let module = match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule { module, .. }) => module,
+            _ => continue,
+        };
+        let summary = summarize_inst_cov(module, &coverage_map);
+        let module_name = format!(
+            "{}::{}",
+            summary.module_name.address().short_str_lossless(),
+            summary.module_name.name()
+        );
+        for (function, function_summary) in summary.function_summaries {
+            if function_summary.fn_is_native {
+                continue;
+            }
+            functions.push(FunctionCoverage {
+                module: module_name.clone(),
+                function: function.to_string(),
+                covered: function_summary.covered,
+                total: function_summary.total,
+                percentage: percentage(function_summary.covered, function_summary.total),
+            });
+        }
+    }
+
+    let covered = functions.iter().map(|function| function.covered).sum();
+    let total = functions.iter().map(|function| function.total).sum();
+    Ok(CoverageReport {
+        functions,
+        covered,
+        total,
+        percentage: percentage(covered, total),
+    })
+}
+
+/// Pulls the gas used by each test out of the test statistics table in the unit test output
+pub fn parse_gas_report(output: &str) -> Vec<TestGasUsage> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with(TEST_STATISTICS_HEADER))
+        .filter_map(|line| {
+            let cells: Vec<_> = line
+                .trim()
+                .trim_matches('│')
+                .split('│')
+                .map(str::trim)
+                .collect();
+            // The header row and the borders don't have a number in the last column
+            match cells.as_slice() {
+                [test, _time, gas_used] => gas_used.parse().ok().map(|gas_used| TestGasUsage {
+                    test: test.to_string(),
+                    gas_used,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn print_coverage_table(report: &CoverageReport) {
+    let width = report
+        .functions
+        .iter()
+        .map(|function| function.module.len() + function.function.len() + 2)
+        .max()
+        .unwrap_or(0)
+        .max("Function".len());
+    println!("\nCoverage Report:\n");
+    println!(
+        "{:<width$} {:>8} {:>8} {:>8}",
+        "Function",
+        "Covered",
+        "Total",
+        "Percent",
+        width = width
+    );
+    for function in &report.functions {
+        println!(
+            "{:<width$} {:>8} {:>8} {:>7.2}%",
+            format!("{}::{}", function.module, function.function),
+            function.covered,
+            function.total,
+            function.percentage,
+            width = width
+        );
+    }
+    println!(
+        "{:<width$} {:>8} {:>8} {:>7.2}%",
+        "Total",
+        report.covered,
+        report.total,
+        report.percentage,
+        width = width
+    );
+}
+
+/// Writes a report as JSON into `dir`
+pub fn write_json_report<T: Serialize>(dir: &Path, name: &str, report: &T) -> CliTypedResult<()> {
+    let bytes = serde_json::to_vec_pretty(report).map_err(|err| {
+        CliError::UnexpectedError(format!("Failed to serialize {} {}", name, err))
+    })?;
+    std::fs::create_dir_all(dir)
+        .map_err(|err| CliError::IO(format!("Failed to create {}", dir.display()), err))?;
+    let path = dir.join(name);
+    write_to_file(&path, name, &bytes)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gas_report() {
+        let output = "\
+Running Move unit tests
+[ PASS    ] 0x42::message::test_a
+[ PASS    ] 0x42::message::test_b
+
+Test Statistics:
+
+┌────────────────────────┬────────────┬───────────────────────────┐
+│       Test Name        │    Time    │         Gas Used          │
+├────────────────────────┼────────────┼───────────────────────────┤
+│ 0x42::message::test_a  │ 0.004      │ 21                        │
+├────────────────────────┼────────────┼───────────────────────────┤
+│ 0x42::message::test_b  │ 0.001      │ 5                         │
+└────────────────────────┴────────────┴───────────────────────────┘
+
+Test result: OK. Total tests: 2; passed: 2; failed: 0
+";
+        assert_eq!(
+            parse_gas_report(output),
+            vec![
+                TestGasUsage {
+                    test: "0x42::message::test_a".to_string(),
+                    gas_used: 21,
+                },
+                TestGasUsage {
+                    test: "0x42::message::test_b".to_string(),
+                    gas_used: 5,
+                },
+            ]
+        );
+        assert!(parse_gas_report("Test result: OK. Total tests: 0").is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifies that the bytecode published onchain was compiled from a package's source
+
+use crate::common::types::{
+    CliCommand, CliError, CliTypedResult, MovePackageDir, ProfileOptions, RestOptions,
+};
+use crate::move_tool::CachedPackageRegistry;
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use framework::{BuildOptions, BuiltPackage};
+use move_deps::move_binary_format::CompiledModule;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Verifies a package published onchain against its source
+///
+/// The package is rebuilt from source with the same compiler settings as `aptos move publish`,
+/// and the bytecode of each of its modules compared with the bytecode published at `--account`.
+/// The named addresses of the package must resolve as they did when it was published.
+#[derive(Parser)]
+pub struct VerifyPackage {
+    /// Address of the account the package was published to
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) account: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub enum ModuleVerification {
+    /// The rebuilt bytecode is identical to the bytecode onchain
+    Match,
+    /// The rebuilt bytecode differs from the bytecode onchain
+    Mismatch,
+    /// The module is in the source, but wasn't published with the package
+    NotOnchain,
+    /// The module was published with the package, but isn't in the source
+    NotInSource,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifyPackageResponse {
+    pub package: String,
+    pub verified: bool,
+    pub modules: BTreeMap<String, ModuleVerification>,
+}
+
+#[async_trait]
+impl CliCommand<VerifyPackageResponse> for VerifyPackage {
+    fn command_name(&self) -> &'static str {
+        "VerifyPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<VerifyPackageResponse> {
+        // Artifacts other than the bytecode aren't compared, so there's no need to build them
+        let options = BuildOptions {
+            with_srcs: false,
+            with_abis: false,
+            with_source_maps: false,
+            with_error_map: false,
+            named_addresses: self.move_options.named_addresses(),
+        };
+        let built = BuiltPackage::build(self.move_options.get_package_path()?, options)
+            .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+        let mut local = BTreeMap::new();
+        for code in built.extract_code() {
+            let module = CompiledModule::deserialize(&code).map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to deserialize module: {}", err))
+            })?;
+            local.insert(module.self_id().name().to_string(), code);
+        }
+
+        let url = self.rest_options.url(&self.profile_options.profile)?;
+        let registry = CachedPackageRegistry::create(url, self.account)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        let package = registry
+            .get_package(built.name())
+            .await
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+        let mut onchain = registry
+            .get_bytecode(&package)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+
+        let mut modules = BTreeMap::new();
+        for (name, code) in local {
+            let verification = match onchain.remove(&name) {
+                Some(onchain_code) if onchain_code == code => ModuleVerification::Match,
+                Some(_) => ModuleVerification::Mismatch,
+                None => ModuleVerification::NotOnchain,
+            };
+            modules.insert(name, verification);
+        }
+        for name in onchain.into_keys() {
+            modules.insert(name, ModuleVerification::NotInSource);
+        }
+
+        Ok(VerifyPackageResponse {
+            package: built.name().to_string(),
+            verified: modules
+                .values()
+                .all(|verification| matches!(verification, ModuleVerification::Match)),
+            modules,
+        })
+    }
+}
@@ -0,0 +1,649 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tool for authoring transactions from multisig accounts
+//!
+//! A multisig account is an account whose authentication key is derived from a
+//! [`MultiEd25519PublicKey`], a set of public keys and the number of them that must sign.  With
+//! `--multi-key`, it is derived from a [`MultiKey`] instead, whose public keys may mix Ed25519,
+//! secp256k1 ECDSA and secp256r1 ECDSA keys.  The workflow is split so that every signer can work
+//! offline, passing BCS files around:
+//!
+//! 1. `create` derives the multisig account address, and saves the multisig public key
+//! 2. `propose` builds the unsigned transaction
+//! 3. `sign` signs the unsigned transaction with one signer's key
+//! 4. `combine` combines enough signatures into a signed transaction
+//! 5. `submit` submits the signed transaction
+
+use crate::common::{
+    types::{
+        CliCommand, CliError, CliResult, CliTypedResult, EncodingOptions, GasOptions,
+        PrivateKeyInputOptions, ProfileOptions, RestOptions, SaveFile, TransactionSummary,
+    },
+    utils::{chain_id, get_sequence_number, read_from_file},
+};
+use crate::move_tool::{ArgWithType, MemberId};
+use aptos_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256k1_ecdsa, secp256r1_ecdsa, PrivateKey, Signature, SigningKey,
+    ValidCryptoMaterialStringExt,
+};
+use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_sdk::transaction_builder::TransactionFactory;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{
+        authenticator::{AuthenticationKey, TransactionAuthenticator},
+        multi_key::{AnyPublicKey, AnySignature, MultiKey, MultiKeySignature},
+        EntryFunction, RawTransaction, SignedTransaction, TransactionPayload,
+    },
+};
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use move_deps::move_core_types::language_storage::TypeTag;
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
+
+/// Tool for authoring transactions from multisig accounts
+///
+/// Signatures are collected offline as BCS files, and combined into a
+/// transaction once enough signers have signed.
+#[derive(Subcommand)]
+pub enum MultisigTool {
+    Create(CreateMultisig),
+    Propose(ProposeTransaction),
+    Sign(SignTransaction),
+    Combine(CombineSignatures),
+    Submit(SubmitTransaction),
+}
+
+impl MultisigTool {
+    pub async fn execute(self) -> CliResult {
+        use MultisigTool::*;
+        match self {
+            Create(tool) => tool.execute_serialized().await,
+            Propose(tool) => tool.execute_serialized().await,
+            Sign(tool) => tool.execute_serialized().await,
+            Combine(tool) => tool.execute_serialized().await,
+            Submit(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// A single signer's signature of a multisig transaction
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PartialSignature {
+    pub public_key: Ed25519PublicKey,
+    pub signature: Ed25519Signature,
+}
+
+/// A single signer's signature of a multi-key transaction
+///
+/// Signers whose keys aren't Ed25519, e.g. because they are held in an HSM, sign the BCS signing
+/// message of the transaction with their own tools, and save their signature in this form.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MultiKeyPartialSignature {
+    pub public_key: AnyPublicKey,
+    pub signature: AnySignature,
+}
+
+/// The address of a multisig account
+pub fn multisig_account_address(public_key: &MultiEd25519PublicKey) -> AccountAddress {
+    AccountAddress::new(*AuthenticationKey::multi_ed25519(public_key).derived_address())
+}
+
+/// The address of a multi-key account
+pub fn multi_key_account_address(public_key: &MultiKey) -> AccountAddress {
+    AccountAddress::new(*AuthenticationKey::multi_key(public_key).derived_address())
+}
+
+/// Parses a public key of a multi-key account, as `<SCHEME>:<HEX>`, where the scheme is one of
+/// `ed25519`, `secp256k1` or `secp256r1`
+fn parse_any_public_key(str: &str) -> CliTypedResult<AnyPublicKey> {
+    let parse_error =
+        |err: String| CliError::UnableToParse("--public-keys", format!("{}: {}", str, err));
+    let (scheme, key) = str.split_once(':').ok_or_else(|| {
+        parse_error("Expected <SCHEME>:<HEX>, e.g. secp256k1:0x04...".to_string())
+    })?;
+    Ok(match scheme {
+        "ed25519" => AnyPublicKey::ed25519(
+            Ed25519PublicKey::from_encoded_string(key)
+                .map_err(|err| parse_error(err.to_string()))?,
+        ),
+        "secp256k1" => AnyPublicKey::secp256k1_ecdsa(
+            secp256k1_ecdsa::PublicKey::from_encoded_string(key)
+                .map_err(|err| parse_error(err.to_string()))?,
+        ),
+        "secp256r1" => AnyPublicKey::secp256r1_ecdsa(
+            secp256r1_ecdsa::PublicKey::from_encoded_string(key)
+                .map_err(|err| parse_error(err.to_string()))?,
+        ),
+        _ => {
+            return Err(parse_error(
+                "Scheme must be one of ed25519, secp256k1 or secp256r1".to_string(),
+            ))
+        }
+    })
+}
+
+fn read_bcs_file<T: for<'a> Deserialize<'a>>(path: &Path, name: &'static str) -> CliTypedResult<T> {
+    bcs::from_bytes(&read_from_file(path)?).map_err(|err| CliError::BCS(name, err))
+}
+
+/// The public key of a multisig account
+enum MultisigPublicKey {
+    MultiEd25519(MultiEd25519PublicKey),
+    MultiKey(MultiKey),
+}
+
+impl MultisigPublicKey {
+    fn address(&self) -> AccountAddress {
+        match self {
+            MultisigPublicKey::MultiEd25519(public_key) => multisig_account_address(public_key),
+            MultisigPublicKey::MultiKey(public_key) => multi_key_account_address(public_key),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct MultisigPublicKeyFile {
+    /// Multisig public key file, created from `aptos multisig create`
+    #[clap(long, parse(from_os_str))]
+    pub(crate) multisig_public_key_file: PathBuf,
+
+    /// The multisig account is a multi-key account, created with `--multi-key`
+    #[clap(long)]
+    pub(crate) multi_key: bool,
+}
+
+impl MultisigPublicKeyFile {
+    fn load(&self) -> CliTypedResult<MultisigPublicKey> {
+        if self.multi_key {
+            read_bcs_file(&self.multisig_public_key_file, "MultiKey")
+                .map(MultisigPublicKey::MultiKey)
+        } else {
+            read_bcs_file(&self.multisig_public_key_file, "MultiEd25519PublicKey")
+                .map(MultisigPublicKey::MultiEd25519)
+        }
+    }
+}
+
+/// Create a multisig account
+///
+/// Derives the multisig account's address from the signers' public keys and the
+/// threshold, and saves the multisig public key for the other commands.  The
+/// account still needs to be created onchain, e.g. with `aptos account create`.
+#[derive(Debug, Parser)]
+pub struct CreateMultisig {
+    /// Hex encoded Ed25519 public keys of the signers, separated by commas
+    ///
+    /// With `--multi-key`, each key is prefixed by its scheme, one of `ed25519`, `secp256k1` or
+    /// `secp256r1`, e.g. `secp256k1:0x04...`.  The order matters, as it determines the account
+    /// address
+    #[clap(
+        long,
+        multiple_values = true,
+        use_value_delimiter = true,
+        required = true
+    )]
+    pub(crate) public_keys: Vec<String>,
+
+    /// Number of signatures required to sign a transaction
+    #[clap(long)]
+    pub(crate) threshold: u8,
+
+    /// Create a multi-key account, whose signers may use different schemes
+    #[clap(long)]
+    pub(crate) multi_key: bool,
+
+    #[clap(flatten)]
+    pub(crate) save_file: SaveFile,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MultisigAccountSummary {
+    pub multisig_address: AccountAddress,
+    pub authentication_key: String,
+    pub threshold: u8,
+    pub num_public_keys: usize,
+}
+
+#[async_trait]
+impl CliCommand<MultisigAccountSummary> for CreateMultisig {
+    fn command_name(&self) -> &'static str {
+        "CreateMultisig"
+    }
+
+    async fn execute(self) -> CliTypedResult<MultisigAccountSummary> {
+        self.save_file.check_file()?;
+        if self.multi_key {
+            return self.create_multi_key();
+        }
+        let public_keys = self
+            .public_keys
+            .iter()
+            .map(|key| {
+                Ed25519PublicKey::from_encoded_string(key)
+                    .map_err(|err| CliError::UnableToParse("--public-keys", err.to_string()))
+            })
+            .collect::<CliTypedResult<Vec<_>>>()?;
+        let num_public_keys = public_keys.len();
+        let public_key =
+            MultiEd25519PublicKey::new(public_keys, self.threshold).map_err(|err| {
+                CliError::CommandArgumentError(format!(
+                    "Threshold must be between 1 and the number of public keys: {}",
+                    err
+                ))
+            })?;
+
+        let bytes = bcs::to_bytes(&public_key)
+            .map_err(|err| CliError::BCS("MultiEd25519PublicKey", err))?;
+        self.save_file.save_to_file("Multisig public key", &bytes)?;
+
+        Ok(MultisigAccountSummary {
+            multisig_address: multisig_account_address(&public_key),
+            authentication_key: AuthenticationKey::multi_ed25519(&public_key).to_string(),
+            threshold: self.threshold,
+            num_public_keys,
+        })
+    }
+}
+
+impl CreateMultisig {
+    fn create_multi_key(self) -> CliTypedResult<MultisigAccountSummary> {
+        let public_keys = self
+            .public_keys
+            .iter()
+            .map(|key| parse_any_public_key(key))
+            .collect::<CliTypedResult<Vec<_>>>()?;
+        let num_public_keys = public_keys.len();
+        let public_key = MultiKey::new(public_keys, self.threshold).map_err(|err| {
+            CliError::CommandArgumentError(format!(
+                "Threshold must be between 1 and the number of public keys: {}",
+                err
+            ))
+        })?;
+
+        let bytes = bcs::to_bytes(&public_key).map_err(|err| CliError::BCS("MultiKey", err))?;
+        self.save_file
+            .save_to_file("Multi-key public key", &bytes)?;
+
+        Ok(MultisigAccountSummary {
+            multisig_address: multi_key_account_address(&public_key),
+            authentication_key: AuthenticationKey::multi_key(&public_key).to_string(),
+            threshold: self.threshold,
+            num_public_keys,
+        })
+    }
+}
+
+/// Propose a transaction from a multisig account
+///
+/// Builds the unsigned transaction calling an entry function from the multisig
+/// account, for the signers to sign.  The sequence number is looked up onchain,
+/// unless it's given.
+#[derive(Parser)]
+pub struct ProposeTransaction {
+    #[clap(flatten)]
+    pub(crate) multisig_public_key: MultisigPublicKeyFile,
+
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    ///
+    /// Example: `0x1::coin::transfer`
+    #[clap(long)]
+    pub(crate) function_id: MemberId,
+
+    /// Arguments combined with their type separated by spaces.
+    ///
+    /// Supported types [u8, u64, u128, bool, hex, string, address]
+    ///
+    /// Example: `address:0x1 bool:true u8:0`
+    #[clap(long, multiple_values = true)]
+    pub(crate) args: Vec<ArgWithType>,
+
+    /// TypeTag arguments separated by spaces.
+    ///
+    /// Example: `u8 u64 u128 bool address vector signer`
+    #[clap(long, multiple_values = true)]
+    pub(crate) type_args: Vec<MoveType>,
+
+    /// Sequence number of the multisig account, looked up onchain if not given
+    #[clap(long)]
+    pub(crate) sequence_number: Option<u64>,
+
+    /// Seconds from now until the transaction expires, leaving time to collect the signatures
+    #[clap(long, default_value_t = 3600)]
+    pub(crate) expiration_secs: u64,
+
+    #[clap(flatten)]
+    pub(crate) gas_options: GasOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) save_file: SaveFile,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProposalSummary {
+    pub multisig_address: AccountAddress,
+    pub sequence_number: u64,
+    pub expiration_timestamp_secs: u64,
+}
+
+#[async_trait]
+impl CliCommand<ProposalSummary> for ProposeTransaction {
+    fn command_name(&self) -> &'static str {
+        "ProposeMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<ProposalSummary> {
+        self.save_file.check_file()?;
+        let multisig_address = self.multisig_public_key.load()?.address();
+
+        let args: Vec<Vec<u8>> = self
+            .args
+            .iter()
+            .map(|arg_with_type| arg_with_type.arg.clone())
+            .collect();
+        let mut type_args: Vec<TypeTag> = Vec::new();
+        for type_arg in self.type_args.iter().cloned() {
+            let type_tag = TypeTag::try_from(type_arg)
+                .map_err(|err| CliError::UnableToParse("--type-args", err.to_string()))?;
+            type_args.push(type_tag)
+        }
+        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
+            self.function_id.module_id.clone(),
+            self.function_id.member_id.clone(),
+            type_args,
+            args,
+        ));
+
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let sequence_number = if let Some(sequence_number) = self.sequence_number {
+            sequence_number
+        } else {
+            get_sequence_number(&client, multisig_address).await?
+        };
+        let raw_txn = TransactionFactory::new(chain_id(&client).await?)
+            .with_gas_unit_price(
+                self.gas_options
+                    .gas_unit_price(&self.profile_options.profile)?,
+            )
+            .with_max_gas_amount(self.gas_options.max_gas(&self.profile_options.profile)?)
+            .with_transaction_expiration_time(self.expiration_secs)
+            .payload(payload)
+            .sender(multisig_address)
+            .sequence_number(sequence_number)
+            .build();
+
+        let bytes = bcs::to_bytes(&raw_txn).map_err(|err| CliError::BCS("RawTransaction", err))?;
+        self.save_file
+            .save_to_file("Unsigned transaction", &bytes)?;
+
+        Ok(ProposalSummary {
+            multisig_address,
+            sequence_number,
+            expiration_timestamp_secs: raw_txn.expiration_timestamp_secs(),
+        })
+    }
+}
+
+/// Sign a proposed multisig transaction
+///
+/// Signs the unsigned transaction with a single signer's private key, and saves
+/// the signature to be combined with the others.  This doesn't need a connection
+/// to a node, so it can be run on an offline machine.
+#[derive(Debug, Parser)]
+pub struct SignTransaction {
+    /// Unsigned transaction file, created from `aptos multisig propose`
+    #[clap(long, parse(from_os_str))]
+    pub(crate) transaction_file: PathBuf,
+
+    /// Save the signature for a multi-key account, created with `--multi-key`
+    #[clap(long)]
+    pub(crate) multi_key: bool,
+
+    #[clap(flatten)]
+    pub(crate) private_key_options: PrivateKeyInputOptions,
+    #[clap(flatten)]
+    pub(crate) encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) save_file: SaveFile,
+}
+
+#[async_trait]
+impl CliCommand<Ed25519PublicKey> for SignTransaction {
+    fn command_name(&self) -> &'static str {
+        "SignMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<Ed25519PublicKey> {
+        self.save_file.check_file()?;
+        let raw_txn: RawTransaction = read_bcs_file(&self.transaction_file, "RawTransaction")?;
+        let private_key = self.private_key_options.extract_private_key(
+            self.encoding_options.encoding,
+            &self.profile_options.profile,
+        )?;
+
+        let public_key = private_key.public_key();
+        let signature = private_key.sign(&raw_txn);
+        let bytes = if self.multi_key {
+            bcs::to_bytes(&MultiKeyPartialSignature {
+                public_key: AnyPublicKey::ed25519(public_key.clone()),
+                signature: AnySignature::ed25519(signature),
+            })
+            .map_err(|err| CliError::BCS("MultiKeyPartialSignature", err))?
+        } else {
+            bcs::to_bytes(&PartialSignature {
+                public_key: public_key.clone(),
+                signature,
+            })
+            .map_err(|err| CliError::BCS("PartialSignature", err))?
+        };
+        self.save_file.save_to_file("Signature", &bytes)?;
+        Ok(public_key)
+    }
+}
+
+/// Combine signatures into a signed multisig transaction
+///
+/// Checks each signature against the transaction and the multisig public key,
+/// and combines them into a signed transaction once there are enough of them.
+#[derive(Debug, Parser)]
+pub struct CombineSignatures {
+    #[clap(flatten)]
+    pub(crate) multisig_public_key: MultisigPublicKeyFile,
+
+    /// Unsigned transaction file, created from `aptos multisig propose`
+    #[clap(long, parse(from_os_str))]
+    pub(crate) transaction_file: PathBuf,
+
+    /// Signature files, created from `aptos multisig sign`, separated by commas
+    #[clap(
+        long,
+        multiple_values = true,
+        use_value_delimiter = true,
+        required = true,
+        parse(from_os_str)
+    )]
+    pub(crate) signature_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) save_file: SaveFile,
+}
+
+#[async_trait]
+impl CliCommand<usize> for CombineSignatures {
+    fn command_name(&self) -> &'static str {
+        "CombineMultisigSignatures"
+    }
+
+    async fn execute(self) -> CliTypedResult<usize> {
+        self.save_file.check_file()?;
+        let public_key = self.multisig_public_key.load()?;
+        let raw_txn: RawTransaction = read_bcs_file(&self.transaction_file, "RawTransaction")?;
+        if raw_txn.sender() != public_key.address() {
+            return Err(CliError::CommandArgumentError(
+                "Transaction is not from the multisig account".to_string(),
+            ));
+        }
+        let (num_signatures, signed_txn) = match public_key {
+            MultisigPublicKey::MultiEd25519(public_key) => {
+                self.combine_multi_ed25519(raw_txn, public_key)?
+            }
+            MultisigPublicKey::MultiKey(public_key) => {
+                self.combine_multi_key(raw_txn, public_key)?
+            }
+        };
+
+        let bytes =
+            bcs::to_bytes(&signed_txn).map_err(|err| CliError::BCS("SignedTransaction", err))?;
+        self.save_file.save_to_file("Signed transaction", &bytes)?;
+        Ok(num_signatures)
+    }
+}
+
+impl CombineSignatures {
+    fn combine_multi_ed25519(
+        &self,
+        raw_txn: RawTransaction,
+        public_key: MultiEd25519PublicKey,
+    ) -> CliTypedResult<(usize, SignedTransaction)> {
+        let mut signatures = Vec::new();
+        for file in &self.signature_files {
+            let partial_signature: PartialSignature = read_bcs_file(file, "PartialSignature")?;
+            let index = public_key
+                .public_keys()
+                .iter()
+                .position(|key| *key == partial_signature.public_key)
+                .ok_or_else(|| {
+                    CliError::CommandArgumentError(format!(
+                        "Signature in {} is not from a signer of the multisig account",
+                        file.display()
+                    ))
+                })?;
+            partial_signature
+                .signature
+                .verify(&raw_txn, &partial_signature.public_key)
+                .map_err(|err| {
+                    CliError::CommandArgumentError(format!(
+                        "Signature in {} is not valid for the transaction: {}",
+                        file.display(),
+                        err
+                    ))
+                })?;
+            if !signatures.iter().any(|(_, i)| *i == index as u8) {
+                signatures.push((partial_signature.signature, index as u8));
+            }
+        }
+
+        let num_signatures = signatures.len();
+        if num_signatures < *public_key.threshold() as usize {
+            return Err(CliError::CommandArgumentError(format!(
+                "Only {} of the {} required signatures were provided",
+                num_signatures,
+                public_key.threshold()
+            )));
+        }
+        let signature = MultiEd25519Signature::new(signatures)
+            .map_err(|err| CliError::UnexpectedError(format!("Invalid signatures {}", err)))?;
+
+        Ok((
+            num_signatures,
+            SignedTransaction::new_multisig(raw_txn, public_key, signature),
+        ))
+    }
+
+    fn combine_multi_key(
+        &self,
+        raw_txn: RawTransaction,
+        public_key: MultiKey,
+    ) -> CliTypedResult<(usize, SignedTransaction)> {
+        let mut signatures = Vec::new();
+        for file in &self.signature_files {
+            let partial_signature: MultiKeyPartialSignature =
+                read_bcs_file(file, "MultiKeyPartialSignature")?;
+            let index = public_key
+                .public_keys()
+                .iter()
+                .position(|key| *key == partial_signature.public_key)
+                .ok_or_else(|| {
+                    CliError::CommandArgumentError(format!(
+                        "Signature in {} is not from a signer of the multi-key account",
+                        file.display()
+                    ))
+                })?;
+            partial_signature
+                .signature
+                .verify(&raw_txn, &partial_signature.public_key)
+                .map_err(|err| {
+                    CliError::CommandArgumentError(format!(
+                        "Signature in {} is not valid for the transaction: {}",
+                        file.display(),
+                        err
+                    ))
+                })?;
+            if !signatures.iter().any(|(i, _)| *i == index as u8) {
+                signatures.push((index as u8, partial_signature.signature));
+            }
+        }
+
+        let num_signatures = signatures.len();
+        if num_signatures < public_key.signatures_required() as usize {
+            return Err(CliError::CommandArgumentError(format!(
+                "Only {} of the {} required signatures were provided",
+                num_signatures,
+                public_key.signatures_required()
+            )));
+        }
+        let signature = MultiKeySignature::new(signatures)
+            .map_err(|err| CliError::UnexpectedError(format!("Invalid signatures {}", err)))?;
+
+        Ok((
+            num_signatures,
+            SignedTransaction::new_with_authenticator(
+                raw_txn,
+                TransactionAuthenticator::multi_key(public_key, signature),
+            ),
+        ))
+    }
+}
+
+/// Submit a signed multisig transaction
+#[derive(Debug, Parser)]
+pub struct SubmitTransaction {
+    /// Signed transaction file, created from `aptos multisig combine`
+    #[clap(long, parse(from_os_str))]
+    pub(crate) signed_transaction_file: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SubmitTransaction {
+    fn command_name(&self) -> &'static str {
+        "SubmitMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let signed_txn: SignedTransaction =
+            read_bcs_file(&self.signed_transaction_file, "SignedTransaction")?;
+        let client = self.rest_options.client(&self.profile_options.profile)?;
+        let response = client
+            .submit_and_wait(&signed_txn)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(TransactionSummary::from(response.into_inner()))
+    }
+}
@@ -7,12 +7,14 @@ use crate::{
             CliError, CliTypedResult, EncodingOptions, EncodingType, ExtractPublicKey, KeyType,
             PrivateKeyInputOptions, ProfileOptions, RngArgs, SaveFile,
         },
-        utils::{append_file_extension, check_if_file_exists, write_to_file},
+        utils::{append_file_extension, check_if_file_exists, read_from_file, write_to_file},
     },
     CliCommand, CliResult,
 };
 use aptos_config::config::{Peer, PeerRole};
-use aptos_crypto::{ed25519, x25519, PrivateKey, ValidCryptoMaterial};
+use aptos_crypto::{
+    ed25519, mnemonic::Mnemonic, slip10::DerivationPath, x25519, PrivateKey, ValidCryptoMaterial,
+};
 use aptos_types::account_address::{from_identity_public_key, AccountAddress};
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
@@ -30,6 +32,8 @@ pub const PUBLIC_KEY_EXTENSION: &str = "pub";
 #[derive(Debug, Subcommand)]
 pub enum KeyTool {
     Generate(GenerateKey),
+    GenerateMnemonic(GenerateMnemonic),
+    Derive(DeriveKey),
     ExtractPeer(ExtractPeer),
 }
 
@@ -37,6 +41,8 @@ impl KeyTool {
     pub async fn execute(self) -> CliResult {
         match self {
             KeyTool::Generate(tool) => tool.execute_serialized().await,
+            KeyTool::GenerateMnemonic(tool) => tool.execute_serialized().await,
+            KeyTool::Derive(tool) => tool.execute_serialized().await,
             KeyTool::ExtractPeer(tool) => tool.execute_serialized().await,
         }
     }
@@ -186,6 +192,94 @@ impl GenerateKey {
     }
 }
 
+/// Generates a BIP-39 mnemonic phrase
+///
+/// The phrase is saved to `output_file`, from which `ed25519` keys can then be derived with
+/// `aptos key derive`.  Keep it safe: anyone with the phrase can derive the keys.
+#[derive(Debug, Parser)]
+pub struct GenerateMnemonic {
+    /// Number of words of the phrase. Must be one of [12, 15, 18, 21, 24]
+    #[clap(long, default_value_t = 24)]
+    pub(crate) word_count: usize,
+
+    #[clap(flatten)]
+    pub rng_args: RngArgs,
+    #[clap(flatten)]
+    pub(crate) file_options: SaveFile,
+}
+
+#[async_trait]
+impl CliCommand<HashMap<&'static str, PathBuf>> for GenerateMnemonic {
+    fn command_name(&self) -> &'static str {
+        "GenerateMnemonic"
+    }
+
+    async fn execute(self) -> CliTypedResult<HashMap<&'static str, PathBuf>> {
+        self.file_options.check_file()?;
+        let mnemonic = self
+            .rng_args
+            .key_generator()?
+            .generate_mnemonic(self.word_count)
+            .map_err(|err| CliError::CommandArgumentError(err.to_string()))?;
+        self.file_options
+            .save_to_file_confidential("Mnemonic", mnemonic.phrase().as_bytes())?;
+
+        let mut map = HashMap::new();
+        map.insert("Mnemonic Path", self.file_options.output_file);
+        Ok(map)
+    }
+}
+
+/// Derives an `ed25519` key from a BIP-39 mnemonic phrase
+///
+/// Keys are derived with SLIP-0010, along the same paths as wallets, so that the accounts of a
+/// wallet can be used from the CLI and vice versa.  Two files will be created `output_file` and
+/// `output_file.pub`, as with `aptos key generate`.
+#[derive(Debug, Parser)]
+pub struct DeriveKey {
+    /// File containing the mnemonic phrase
+    #[clap(long, parse(from_os_str))]
+    pub(crate) mnemonic_file: PathBuf,
+    /// File containing the BIP-39 passphrase, if the mnemonic is protected by one
+    #[clap(long, parse(from_os_str))]
+    pub(crate) passphrase_file: Option<PathBuf>,
+    /// Derivation path of the key, with hardened indices only
+    ///
+    /// Defaults to the first account of wallets. Later accounts are at `m/44'/637'/{n}'/0'/0'`
+    #[clap(long, default_value_t = DerivationPath::default())]
+    pub(crate) derivation_path: DerivationPath,
+
+    #[clap(flatten)]
+    pub(crate) save_params: SaveKey,
+}
+
+#[async_trait]
+impl CliCommand<HashMap<&'static str, PathBuf>> for DeriveKey {
+    fn command_name(&self) -> &'static str {
+        "DeriveKey"
+    }
+
+    async fn execute(self) -> CliTypedResult<HashMap<&'static str, PathBuf>> {
+        self.save_params.check_key_file()?;
+        let phrase = String::from_utf8(read_from_file(&self.mnemonic_file)?)
+            .map_err(|err| CliError::UnableToParse("mnemonic", err.to_string()))?;
+        let mnemonic = Mnemonic::from_phrase(&phrase)
+            .map_err(|err| CliError::UnableToParse("mnemonic", err.to_string()))?;
+        let passphrase = match &self.passphrase_file {
+            Some(file) => String::from_utf8(read_from_file(file)?)
+                .map_err(|err| CliError::UnableToParse("passphrase", err.to_string()))?
+                .trim_end_matches(&['\r', '\n'][..])
+                .to_string(),
+            None => String::new(),
+        };
+
+        let private_key = mnemonic
+            .derive_ed25519_private_key(&passphrase, &self.derivation_path)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        self.save_params.save_key(&private_key, "ed25519")
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct SaveKey {
     #[clap(flatten)]
@@ -17,7 +17,7 @@ use crate::common::types::{
 use crate::common::utils::write_to_file;
 use crate::move_tool::{
     ArgWithType, CompilePackage, DownloadPackage, IncludedArtifacts, InitPackage, MemberId,
-    PublishPackage, RunFunction, TestPackage,
+    PublishPackage, ReportFormat, RunFunction, TestPackage,
 };
 use crate::node::{
     AnalyzeMode, AnalyzeValidatorPerformance, InitializeValidator, JoinValidatorSet,
@@ -409,6 +409,7 @@ impl CliTestFramework {
             faucet_url: Some(self.faucet_endpoint.clone()),
             rng_args: RngArgs::from_seed([0; 32]),
             private_key_options: PrivateKeyInputOptions::from_private_key(private_key)?,
+            ledger_options: Default::default(),
             profile_options: Default::default(),
             prompt_options: PromptOptions::yes(),
             encoding_options: EncodingOptions::default(),
@@ -651,6 +652,9 @@ impl CliTestFramework {
         TestPackage {
             move_options: self.move_options(account_strs),
             filter: filter.map(|str| str.to_string()),
+            coverage: false,
+            gas_report: false,
+            report_format: ReportFormat::Table,
         }
         .execute()
         .await
@@ -0,0 +1,92 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! On-demand diagnostics of a running node, to debug stalls without attaching a debugger.
+//!
+//! Tokio (as of 1.19) can't dump the state of its tasks, but each runtime's worker threads are
+//! named after it, so the thread backtraces show what every runtime is busy with. Capturing them
+//! relies on `rstack-self`, which is Linux only and links libdw, so thread dumps are only built
+//! with the `thread-dump` feature, which the node forwards as its own `thread-dump` feature.
+
+use anyhow::anyhow;
+use serde_json::json;
+#[cfg(feature = "thread-dump")]
+use std::{fmt::Write, process::Command};
+
+/// The argument the node is re-executed with to trace its own threads. A process can't
+/// ptrace itself, so the backtraces are captured by a child process.
+pub const THREAD_DUMP_CHILD_ARG: &str = "--inspection-thread-dump-child";
+
+/// Whether this process was started to trace the threads of its parent
+pub fn is_thread_dump_child() -> bool {
+    std::env::args_os()
+        .nth(1)
+        .map_or(false, |arg| arg == THREAD_DUMP_CHILD_ARG)
+}
+
+/// Traces the threads of the parent process. Must be called by the node binary before anything
+/// else, if `is_thread_dump_child` is true.
+#[cfg(feature = "thread-dump")]
+pub fn run_thread_dump_child() {
+    if let Err(error) = rstack_self::child() {
+        eprintln!("Failed to trace the node's threads: {}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "thread-dump"))]
+pub fn run_thread_dump_child() {
+    eprintln!("Thread dumps require building the node with the `thread-dump` feature");
+    std::process::exit(1);
+}
+
+/// The backtraces of every thread of the node, as text
+#[cfg(feature = "thread-dump")]
+pub(crate) fn thread_dump() -> anyhow::Result<String> {
+    let mut child = Command::new(std::env::current_exe()?);
+    child.arg(THREAD_DUMP_CHILD_ARG);
+    let trace = rstack_self::trace(&mut child)
+        .map_err(|error| anyhow!("Failed to trace the node's threads: {}", error))?;
+
+    let mut dump = String::new();
+    for thread in trace.threads() {
+        writeln!(dump, "Thread {} ({}):", thread.id(), thread.name())?;
+        for frame in thread.frames() {
+            match frame.symbols().first() {
+                Some(symbol) => {
+                    write!(dump, "    {}", symbol.name().unwrap_or("<unknown>"))?;
+                    if let (Some(file), Some(line)) = (symbol.file(), symbol.line()) {
+                        write!(dump, " at {}:{}", file.display(), line)?;
+                    }
+                    writeln!(dump)?;
+                }
+                None => writeln!(dump, "    {:#x}", frame.ip())?,
+            }
+        }
+        writeln!(dump)?;
+    }
+    Ok(dump)
+}
+
+#[cfg(not(feature = "thread-dump"))]
+pub(crate) fn thread_dump() -> anyhow::Result<String> {
+    Err(anyhow!(
+        "Thread dumps require building the node with the `thread-dump` feature"
+    ))
+}
+
+/// The contention of the node's locks, as JSON, by decreasing total wait
+pub(crate) fn lock_contention() -> String {
+    let contention: Vec<_> = aptos_infallible::lock_contention()
+        .into_iter()
+        .map(|contention| {
+            json!({
+                "location": contention.location.to_string(),
+                "contended": contention.contended,
+                "total_wait_ms": contention.total_wait.as_secs_f64() * 1000.0,
+                "max_wait_ms": contention.max_wait.as_secs_f64() * 1000.0,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&contention).unwrap()
+}
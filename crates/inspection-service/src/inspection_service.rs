@@ -1,9 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{gather_metrics, json_encoder::JsonEncoder, NUM_METRICS};
+use crate::{diagnostics, gather_metrics, json_encoder::JsonEncoder, NUM_METRICS};
 use aptos_config::config::NodeConfig;
 use hyper::{
+    header::AUTHORIZATION,
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
@@ -23,6 +24,10 @@ use tokio::runtime;
 const DISABLED_ENDPOINT_MESSAGE: &str =
     "This endpoint is disabled! Enable it in the InspectionServiceConfig.";
 
+// The message displayed when a debug endpoint is called without the admin auth token.
+const UNAUTHORIZED_MESSAGE: &str =
+    "Debug endpoints require the admin service auth token, as a bearer token.";
+
 fn encode_metrics(encoder: impl Encoder) -> Vec<u8> {
     let metric_families = gather_metrics();
     let mut buffer = vec![];
@@ -76,6 +81,16 @@ fn get_metrics(fams: Vec<MetricFamily>) -> HashMap<String, String> {
     all_metrics
 }
 
+/// Whether `req` carries the admin service auth token. If there is no token, no request does.
+pub(crate) fn is_admin_request(req: &Request<Body>, node_config: &NodeConfig) -> bool {
+    let auth_token = &node_config.admin_service.auth_token;
+    !auth_token.is_empty()
+        && req
+            .headers()
+            .get(AUTHORIZATION)
+            .map_or(false, |value| *value == format!("Bearer {}", auth_token))
+}
+
 pub fn get_all_metrics() -> HashMap<String, String> {
     let all_metric_families = gather_metrics();
     get_metrics(all_metric_families)
@@ -128,6 +143,39 @@ async fn serve_requests(
                 *resp.body_mut() = Body::from(DISABLED_ENDPOINT_MESSAGE);
             }
         }
+        // Expose the debug endpoints, which require the admin auth token
+        (&Method::GET, path) if path.starts_with("/debug/") => {
+            if !node_config.inspection_service.expose_debug_endpoints {
+                *resp.body_mut() = Body::from(DISABLED_ENDPOINT_MESSAGE);
+            } else if !is_admin_request(&req, &node_config) {
+                *resp.status_mut() = StatusCode::UNAUTHORIZED;
+                *resp.body_mut() = Body::from(UNAUTHORIZED_MESSAGE);
+            } else {
+                match path {
+                    // Backtraces of every thread, including the runtime worker threads
+                    "/debug/threads" => {
+                        let dump = tokio::task::spawn_blocking(diagnostics::thread_dump)
+                            .await
+                            .map_err(anyhow::Error::from)
+                            .and_then(|dump| dump);
+                        match dump {
+                            Ok(dump) => *resp.body_mut() = Body::from(dump),
+                            Err(error) => {
+                                *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                                *resp.body_mut() = Body::from(error.to_string());
+                            }
+                        }
+                    }
+                    // How long threads waited for each lock
+                    "/debug/lock_contention" => {
+                        *resp.body_mut() = Body::from(diagnostics::lock_contention());
+                    }
+                    _ => {
+                        *resp.status_mut() = StatusCode::NOT_FOUND;
+                    }
+                }
+            }
+        }
         _ => {
             *resp.status_mut() = StatusCode::NOT_FOUND;
         }
@@ -1,8 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::inspection_service::get_all_metrics;
+use crate::inspection_service::{get_all_metrics, is_admin_request};
+use aptos_config::config::NodeConfig;
 use assert_approx_eq::assert_approx_eq;
+use hyper::{Body, Request};
 use once_cell::sync::Lazy;
 use prometheus::{proto::MetricFamily, register_int_counter, Counter, IntCounter, Opts, Registry};
 use rusty_fork::rusty_fork_test;
@@ -70,3 +72,29 @@ fn get_all_metrics_test() {
     }
 }
 }
+
+#[test]
+fn admin_request_test() {
+    let request = |authorization: Option<&str>| {
+        let mut request = Request::builder().uri("/debug/lock_contention");
+        if let Some(authorization) = authorization {
+            request = request.header("authorization", authorization);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    // Without an admin auth token, no request is authorized
+    let mut node_config = NodeConfig::default();
+    assert!(!is_admin_request(&request(Some("Bearer ")), &node_config));
+
+    node_config.admin_service.auth_token = "token".to_string();
+    assert!(is_admin_request(
+        &request(Some("Bearer token")),
+        &node_config
+    ));
+    assert!(!is_admin_request(
+        &request(Some("Bearer other")),
+        &node_config
+    ));
+    assert!(!is_admin_request(&request(None), &node_config));
+}
@@ -1,7 +1,10 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{convert::TryFrom, path::Path};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{bail, format_err, Result};
 use aptos::common::types::EncodingType;
@@ -99,6 +102,12 @@ pub struct EmitArgs {
 
     #[clap(long, arg_enum, default_value = "p2p", ignore_case = true)]
     pub transaction_type: TransactionType,
+
+    /// Path to a YAML workload spec, describing a mix of transactions to emit instead of
+    /// --transaction-type. If the workload has phases, they override --duration and
+    /// --mempool-backlog.
+    #[clap(long)]
+    pub workload: Option<PathBuf>,
 }
 
 fn parse_target(target: &str) -> Result<Url> {
@@ -24,7 +24,7 @@ use std::{
     collections::HashSet,
     num::NonZeroU64,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
     time::{Duration, Instant},
@@ -36,8 +36,10 @@ use crate::{
     emitter::{account_minter::AccountMinter, submission_worker::SubmissionWorker},
     transaction_generator::{
         account_generator::AccountGeneratorCreator, nft_mint::NFTMintGeneratorCreator,
-        p2p_transaction_generator::P2PTransactionGeneratorCreator, TransactionGeneratorCreator,
+        p2p_transaction_generator::P2PTransactionGeneratorCreator,
+        workload_generator::WorkloadGeneratorCreator, TransactionGeneratorCreator,
     },
+    workload::WorkloadSpec,
 };
 use aptos_sdk::transaction_builder::aptos_stdlib;
 use rand::rngs::StdRng;
@@ -50,6 +52,8 @@ const MAX_TXNS: u64 = 1_000_000;
 const SEND_AMOUNT: u64 = 1;
 const TXN_EXPIRATION_SECONDS: u64 = 180;
 const TXN_MAX_WAIT: Duration = Duration::from_secs(TXN_EXPIRATION_SECONDS as u64 + 30);
+/// How often the number of active workers follows the phases of a workload
+const PHASE_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
 
 // This retry policy is used for important client calls necessary for setting
 // up the test (e.g. account creation) and collecting its results (e.g. checking
@@ -91,6 +95,7 @@ pub struct EmitJobRequest {
     pub duration: Duration,
     reuse_accounts: bool,
     transaction_type: TransactionType,
+    workload: Option<Arc<WorkloadSpec>>,
 }
 
 impl Default for EmitJobRequest {
@@ -104,6 +109,7 @@ impl Default for EmitJobRequest {
             duration: Duration::from_secs(300),
             reuse_accounts: false,
             transaction_type: TransactionType::P2P,
+            workload: None,
         }
     }
 }
@@ -138,6 +144,19 @@ impl EmitJobRequest {
         self
     }
 
+    /// Emits the transactions of `workload` instead of the transaction type. If the workload has
+    /// phases, they also set the duration of the job and its highest mempool backlog.
+    pub fn workload(mut self, workload: WorkloadSpec) -> Self {
+        if !workload.phases.is_empty() {
+            self.duration = workload.duration();
+        }
+        if let Some(max_mempool_backlog) = workload.max_mempool_backlog() {
+            self.mempool_backlog = max(max_mempool_backlog as usize, 1);
+        }
+        self.workload = Some(Arc::new(workload));
+        self
+    }
+
     pub fn calculate_workers_per_endpoint(&self) -> usize {
         // The target mempool backlog is set to be 3x of the target TPS because of the on an average,
         // we can ~3 blocks in consensus queue. As long as we have 3x the target TPS as backlog,
@@ -269,28 +288,38 @@ impl<'t> TxnEmitter<'t> {
         let stop = Arc::new(AtomicBool::new(false));
         let stats = Arc::new(StatsAccumulator::default());
         let tokio_handle = Handle::current();
-        let txn_generator_creator: Box<dyn TransactionGeneratorCreator> = match req.transaction_type
-        {
-            TransactionType::P2P => Box::new(P2PTransactionGeneratorCreator::new(
-                self.from_rng(),
-                self.txn_factory.clone(),
-                SEND_AMOUNT,
-            )),
-            TransactionType::AccountGeneration => {
-                Box::new(AccountGeneratorCreator::new(self.txn_factory.clone()))
-            }
-            TransactionType::NftMint => Box::new(
-                NFTMintGeneratorCreator::new(
+        let txn_generator_creator: Box<dyn TransactionGeneratorCreator> =
+            match (&req.workload, req.transaction_type) {
+                (Some(workload), _) => Box::new(WorkloadGeneratorCreator::new(
+                    self.txn_factory.clone(),
+                    workload,
+                )?),
+                (None, TransactionType::P2P) => Box::new(P2PTransactionGeneratorCreator::new(
                     self.from_rng(),
                     self.txn_factory.clone(),
-                    self.root_account,
-                    req.rest_clients[0].clone(),
-                )
-                .await,
-            ),
-        };
-        for client in req.rest_clients {
-            for _ in 0..workers_per_endpoint {
+                    SEND_AMOUNT,
+                )),
+                (None, TransactionType::AccountGeneration) => {
+                    Box::new(AccountGeneratorCreator::new(self.txn_factory.clone()))
+                }
+                (None, TransactionType::NftMint) => Box::new(
+                    NFTMintGeneratorCreator::new(
+                        self.from_rng(),
+                        self.txn_factory.clone(),
+                        self.root_account,
+                        req.rest_clients[0].clone(),
+                    )
+                    .await,
+                ),
+            };
+        // All workers are active, unless the workload has phases
+        let active_workers = Arc::new(AtomicUsize::new(usize::MAX));
+        if let Some(workload) = req.workload.clone().filter(|w| !w.phases.is_empty()) {
+            tokio_handle.spawn(run_phases(workload, active_workers.clone(), stop.clone()));
+        }
+        let clients_count = req.rest_clients.len();
+        for (client_index, client) in req.rest_clients.into_iter().enumerate() {
+            for worker_index in 0..workers_per_endpoint {
                 let accounts = (&mut all_accounts).take(1).collect();
                 let all_addresses = all_addresses.clone();
                 let stop = stop.clone();
@@ -307,6 +336,9 @@ impl<'t> TxnEmitter<'t> {
                     txn_generator_creator.create_transaction_generator(),
                     req.invalid_transaction_ratio,
                     self.from_rng(),
+                    // Interleaved, so that the active workers are spread over the endpoints
+                    worker_index * clients_count + client_index,
+                    active_workers.clone(),
                 );
                 let join_handle = tokio_handle.spawn(worker.run(req.gas_price).boxed());
                 workers.push(Worker { join_handle });
@@ -396,6 +428,26 @@ impl<'t> TxnEmitter<'t> {
     }
 }
 
+/// Activates as many workers as the backlog of the current phase of `workload` requires, until
+/// the job stops
+async fn run_phases(
+    workload: Arc<WorkloadSpec>,
+    active_workers: Arc<AtomicUsize>,
+    stop: Arc<AtomicBool>,
+) {
+    let start_time = Instant::now();
+    while !stop.load(Ordering::Relaxed) {
+        let mempool_backlog = workload
+            .mempool_backlog_at(start_time.elapsed())
+            .unwrap_or_default() as usize;
+        active_workers.store(
+            max(mempool_backlog / TRANSACTIONS_PER_ACCOUNT, 1),
+            Ordering::Relaxed,
+        );
+        time::sleep(PHASE_UPDATE_INTERVAL).await;
+    }
+}
+
 /// Waits for a single account to catch up to the expected sequence number
 async fn wait_for_single_account_sequence(
     client: &RestClient,
@@ -26,10 +26,13 @@ use core::{
 use futures::future::try_join_all;
 use rand::seq::IteratorRandom;
 use rand::Rng;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, AtomicUsize};
 use std::{sync::Arc, time::Instant};
 use tokio::time::sleep;
 
+/// How long an inactive worker waits before checking whether it was activated
+const INACTIVE_WORKER_WAIT: Duration = Duration::from_millis(100);
+
 #[derive(Debug)]
 pub struct SubmissionWorker {
     pub(crate) accounts: Vec<LocalAccount>,
@@ -41,6 +44,9 @@ pub struct SubmissionWorker {
     txn_generator: Box<dyn TransactionGenerator>,
     invalid_transaction_ratio: usize,
     rng: ::rand::rngs::StdRng,
+    index: usize,
+    /// Only the workers whose index is below this submit transactions
+    active_workers: Arc<AtomicUsize>,
 }
 
 // Note, there is an edge case that can occur if the transaction emitter
@@ -59,6 +65,8 @@ impl SubmissionWorker {
         txn_generator: Box<dyn TransactionGenerator>,
         invalid_transaction_ratio: usize,
         rng: ::rand::rngs::StdRng,
+        index: usize,
+        active_workers: Arc<AtomicUsize>,
     ) -> Self {
         Self {
             accounts,
@@ -70,6 +78,8 @@ impl SubmissionWorker {
             txn_generator,
             invalid_transaction_ratio,
             rng,
+            index,
+            active_workers,
         }
     }
 
@@ -92,6 +102,10 @@ impl SubmissionWorker {
         let mut total_num_requests = 0;
 
         while !self.stop.load(Ordering::Relaxed) {
+            if self.index >= self.active_workers.load(Ordering::Relaxed) {
+                sleep(INACTIVE_WORKER_WAIT).await;
+                continue;
+            }
             let requests = self.gen_requests(gas_price);
             let num_requests = requests.len();
             total_num_requests += num_requests;
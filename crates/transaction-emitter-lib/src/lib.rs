@@ -9,6 +9,7 @@ mod cluster;
 pub mod emitter;
 mod instance;
 mod transaction_generator;
+pub mod workload;
 mod wrappers;
 
 // These are the top level things you should need to run the emitter.
@@ -22,4 +23,5 @@ pub use emitter::{
     stats::{TxnStats, TxnStatsRate},
     EmitJob, EmitJobRequest, EmitThreadParams, TxnEmitter,
 };
+pub use workload::WorkloadSpec;
 pub use wrappers::emit_transactions_with_cluster;
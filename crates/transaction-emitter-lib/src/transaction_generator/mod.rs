@@ -10,6 +10,7 @@ use std::{fmt::Debug, sync::Arc};
 pub mod account_generator;
 pub mod nft_mint;
 pub mod p2p_transaction_generator;
+pub mod workload_generator;
 
 pub trait TransactionGenerator: Debug + Sync + Send {
     fn generate_transactions(
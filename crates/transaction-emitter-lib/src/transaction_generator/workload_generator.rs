@@ -0,0 +1,272 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    transaction_generator::{TransactionGenerator, TransactionGeneratorCreator},
+    workload::{ArgGenerator, HotAccounts, TransactionShape, WorkloadSpec},
+};
+use anyhow::{format_err, Result};
+use aptos::move_tool::MemberId;
+use aptos_sdk::{
+    bcs,
+    move_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+        language_storage::TypeTag, parser::parse_type_tag,
+    },
+    transaction_builder::{aptos_stdlib, TransactionFactory},
+    types::{
+        transaction::{EntryFunction, SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use framework::{BuildOptions, BuiltPackage};
+use move_deps::move_binary_format::CompiledModule;
+use rand::{
+    distributions::{Alphanumeric, WeightedIndex},
+    prelude::{Distribution, SliceRandom, StdRng},
+    Rng,
+};
+use rand_core::{OsRng, SeedableRng};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+/// The address named addresses of published packages are bound to at compile time, and which is
+/// replaced by the sender's address in each transaction
+const PACKAGE_PLACEHOLDER_ADDRESS: &str = "0xc0ffee";
+
+/// A transaction shape, prepared to be generated quickly
+#[derive(Debug)]
+enum PreparedShape {
+    P2p {
+        amount: u64,
+    },
+    PublishPackage {
+        metadata: Vec<u8>,
+        modules: Vec<CompiledModule>,
+    },
+    EntryFunction {
+        module: ModuleId,
+        function: Identifier,
+        type_args: Vec<TypeTag>,
+        args: Vec<ArgGenerator>,
+    },
+}
+
+impl PreparedShape {
+    fn prepare(shape: &TransactionShape) -> Result<Self> {
+        Ok(match shape {
+            TransactionShape::P2p { amount } => PreparedShape::P2p { amount: *amount },
+            TransactionShape::PublishPackage {
+                package_dir,
+                named_address,
+            } => {
+                let mut named_addresses = BTreeMap::new();
+                named_addresses.insert(named_address.clone(), placeholder_address());
+                let package = BuiltPackage::build(
+                    package_dir.clone(),
+                    BuildOptions {
+                        with_srcs: false,
+                        with_abis: false,
+                        with_source_maps: false,
+                        with_error_map: false,
+                        named_addresses,
+                    },
+                )?;
+                let modules = package
+                    .extract_code()
+                    .iter()
+                    .map(|code| {
+                        CompiledModule::deserialize(code)
+                            .map_err(|e| format_err!("Failed to deserialize module: {:?}", e))
+                    })
+                    .collect::<Result<_>>()?;
+                PreparedShape::PublishPackage {
+                    metadata: bcs::to_bytes(&package.extract_metadata()?)?,
+                    modules,
+                }
+            }
+            TransactionShape::EntryFunction {
+                function,
+                type_args,
+                args,
+            } => {
+                let function = MemberId::from_str(function)
+                    .map_err(|e| format_err!("Invalid function {}: {}", function, e))?;
+                let type_args = type_args
+                    .iter()
+                    .map(|type_arg| parse_type_tag(type_arg))
+                    .collect::<Result<_>>()?;
+                PreparedShape::EntryFunction {
+                    module: function.module_id,
+                    function: function.member_id,
+                    type_args,
+                    args: args.clone(),
+                }
+            }
+        })
+    }
+}
+
+fn placeholder_address() -> AccountAddress {
+    AccountAddress::from_hex_literal(PACKAGE_PLACEHOLDER_ADDRESS).unwrap()
+}
+
+/// Generates a mix of transactions, as described by a `WorkloadSpec`
+#[derive(Debug)]
+pub struct WorkloadGenerator {
+    rng: StdRng,
+    txn_factory: TransactionFactory,
+    shapes: Arc<Vec<PreparedShape>>,
+    weights: WeightedIndex<u32>,
+    hot_accounts: Option<HotAccounts>,
+}
+
+impl WorkloadGenerator {
+    fn choose_receiver(&mut self, all_addresses: &[AccountAddress]) -> AccountAddress {
+        let addresses = match &self.hot_accounts {
+            Some(hot_accounts) if self.rng.gen_bool(hot_accounts.probability) => {
+                &all_addresses[..hot_accounts.count.min(all_addresses.len())]
+            }
+            _ => all_addresses,
+        };
+        *addresses
+            .choose(&mut self.rng)
+            .expect("all_addresses can't be empty")
+    }
+
+    fn gen_arg(
+        &mut self,
+        arg: &ArgGenerator,
+        sender: AccountAddress,
+        all_addresses: &[AccountAddress],
+    ) -> Vec<u8> {
+        let arg = match arg {
+            ArgGenerator::Bool { value } => bcs::to_bytes(value),
+            ArgGenerator::U64 { value } => bcs::to_bytes(value),
+            ArgGenerator::U64Range { min, max } if min == max => bcs::to_bytes(min),
+            ArgGenerator::U64Range { min, max } => {
+                bcs::to_bytes(&self.rng.gen_range(*min, max.saturating_add(1)))
+            }
+            ArgGenerator::Sender => bcs::to_bytes(&sender),
+            ArgGenerator::Receiver => bcs::to_bytes(&self.choose_receiver(all_addresses)),
+            ArgGenerator::Bytes { length } => {
+                let bytes: Vec<u8> = (0..*length).map(|_| self.rng.gen()).collect();
+                bcs::to_bytes(&bytes)
+            }
+            ArgGenerator::String { length } => {
+                let string: String = (0..*length)
+                    .map(|_| self.rng.sample(Alphanumeric))
+                    .collect();
+                bcs::to_bytes(&string)
+            }
+        };
+        arg.expect("Arguments can always be serialized")
+    }
+
+    fn gen_payload(
+        &mut self,
+        sender: AccountAddress,
+        all_addresses: &[AccountAddress],
+    ) -> TransactionPayload {
+        let shapes = self.shapes.clone();
+        match &shapes[self.weights.sample(&mut self.rng)] {
+            PreparedShape::P2p { amount } => {
+                aptos_stdlib::aptos_coin_transfer(self.choose_receiver(all_addresses), *amount)
+            }
+            PreparedShape::PublishPackage { metadata, modules } => {
+                let code = modules
+                    .iter()
+                    .map(|module| {
+                        let mut module = module.clone();
+                        for address in module.address_identifiers.iter_mut() {
+                            if *address == placeholder_address() {
+                                *address = sender;
+                            }
+                        }
+                        let mut code = vec![];
+                        module
+                            .serialize(&mut code)
+                            .expect("Modules can always be serialized");
+                        code
+                    })
+                    .collect();
+                aptos_stdlib::code_publish_package_txn(metadata.clone(), code)
+            }
+            PreparedShape::EntryFunction {
+                module,
+                function,
+                type_args,
+                args,
+            } => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.gen_arg(arg, sender, all_addresses))
+                    .collect();
+                TransactionPayload::EntryFunction(EntryFunction::new(
+                    module.clone(),
+                    function.clone(),
+                    type_args.clone(),
+                    args,
+                ))
+            }
+        }
+    }
+}
+
+impl TransactionGenerator for WorkloadGenerator {
+    fn generate_transactions(
+        &mut self,
+        accounts: Vec<&mut LocalAccount>,
+        transactions_per_account: usize,
+        all_addresses: Arc<Vec<AccountAddress>>,
+        _invalid_transaction_ratio: usize,
+        gas_price: u64,
+    ) -> Vec<SignedTransaction> {
+        let mut requests = Vec::with_capacity(accounts.len() * transactions_per_account);
+        for account in accounts {
+            for _ in 0..transactions_per_account {
+                let payload = self.gen_payload(account.address(), &all_addresses);
+                requests.push(account.sign_with_transaction_builder(
+                    self.txn_factory.payload(payload).gas_unit_price(gas_price),
+                ));
+            }
+        }
+        requests
+    }
+}
+
+#[derive(Debug)]
+pub struct WorkloadGeneratorCreator {
+    txn_factory: TransactionFactory,
+    shapes: Arc<Vec<PreparedShape>>,
+    weights: WeightedIndex<u32>,
+    hot_accounts: Option<HotAccounts>,
+}
+
+impl WorkloadGeneratorCreator {
+    /// Prepares the transactions of `workload`, which compiles the packages it publishes
+    pub fn new(txn_factory: TransactionFactory, workload: &WorkloadSpec) -> Result<Self> {
+        let shapes = workload
+            .transactions
+            .iter()
+            .map(|txn| PreparedShape::prepare(&txn.shape))
+            .collect::<Result<Vec<_>>>()?;
+        let weights = WeightedIndex::new(workload.transactions.iter().map(|txn| txn.weight))?;
+        Ok(Self {
+            txn_factory,
+            shapes: Arc::new(shapes),
+            weights,
+            hot_accounts: workload.hot_accounts.clone(),
+        })
+    }
+}
+
+impl TransactionGeneratorCreator for WorkloadGeneratorCreator {
+    fn create_transaction_generator(&self) -> Box<dyn TransactionGenerator> {
+        Box::new(WorkloadGenerator {
+            rng: StdRng::from_seed(OsRng.gen()),
+            txn_factory: self.txn_factory.clone(),
+            shapes: self.shapes.clone(),
+            weights: self.weights.clone(),
+            hot_accounts: self.hot_accounts.clone(),
+        })
+    }
+}
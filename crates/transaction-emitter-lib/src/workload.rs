@@ -0,0 +1,281 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A declarative description of the traffic to emit, so that performance runs can model a mix
+//! of transactions instead of uniform transfers. A workload is usually loaded from YAML:
+//!
+//! ```yaml
+//! transactions:
+//!   - weight: 80
+//!     type: p2p
+//!     amount: 10
+//!   - weight: 15
+//!     type: entry_function
+//!     function: "0x1::coin::transfer"
+//!     type_args: ["0x1::aptos_coin::AptosCoin"]
+//!     args:
+//!       - type: receiver
+//!       - type: u64_range
+//!         min: 1
+//!         max: 100
+//!   - weight: 5
+//!     type: publish_package
+//!     package_dir: "aptos-move/move-examples/hello_blockchain"
+//!     named_address: "hello_blockchain"
+//! hot_accounts:
+//!   count: 10
+//!   probability: 0.5
+//! phases:
+//!   - duration_secs: 60
+//!     mempool_backlog: 5000
+//!     ramp: true
+//!   - duration_secs: 300
+//!     mempool_backlog: 5000
+//! ```
+
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, path::PathBuf, time::Duration};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadSpec {
+    /// The shapes of the emitted transactions, each picked in proportion to its weight
+    pub transactions: Vec<WeightedTransaction>,
+    /// If set, some receivers are picked from a small set of accounts, so that transactions
+    /// conflict with each other
+    #[serde(default)]
+    pub hot_accounts: Option<HotAccounts>,
+    /// If set, the load follows these phases, in order, instead of a constant backlog
+    #[serde(default)]
+    pub phases: Vec<Phase>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WeightedTransaction {
+    pub weight: u32,
+    #[serde(flatten)]
+    pub shape: TransactionShape,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransactionShape {
+    /// A transfer of `amount` coins to another account
+    P2p {
+        #[serde(default = "default_amount")]
+        amount: u64,
+    },
+    /// Publishes the Move package in `package_dir` under the sender's account. The package is
+    /// compiled once, and `named_address` is bound to the sender of each transaction.
+    PublishPackage {
+        package_dir: PathBuf,
+        named_address: String,
+    },
+    /// A call to `function`, e.g. `0x1::coin::transfer`, with generated arguments
+    EntryFunction {
+        function: String,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        args: Vec<ArgGenerator>,
+    },
+}
+
+fn default_amount() -> u64 {
+    1
+}
+
+/// Generates an argument of an entry function call
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArgGenerator {
+    Bool {
+        value: bool,
+    },
+    U64 {
+        value: u64,
+    },
+    /// A uniformly random `u64`, between `min` and `max` inclusive
+    U64Range {
+        min: u64,
+        max: u64,
+    },
+    /// The address of the sender
+    Sender,
+    /// The address of another account of the emitter, which may be a hot account
+    Receiver,
+    /// A random `vector<u8>`
+    Bytes {
+        length: usize,
+    },
+    /// A random alphanumeric `String`
+    String {
+        length: usize,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct HotAccounts {
+    /// How many accounts are hot
+    pub count: usize,
+    /// The probability that a receiver is a hot account
+    pub probability: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    pub duration_secs: u64,
+    /// The number of transactions kept in flight, see `EmitArgs::mempool_backlog`
+    pub mempool_backlog: u64,
+    /// If set, the backlog grows (or shrinks) linearly from the one of the previous phase (or 0)
+    /// over the phase, instead of changing at once
+    #[serde(default)]
+    pub ramp: bool,
+}
+
+impl WorkloadSpec {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format_err!("Failed to read workload {:?}: {}", path, e))?;
+        let spec: Self = serde_yaml::from_str(&contents)
+            .map_err(|e| format_err!("Failed to parse workload {:?}: {}", path, e))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.transactions.iter().any(|txn| txn.weight > 0),
+            "A workload needs at least one transaction with a positive weight"
+        );
+        if let Some(hot_accounts) = &self.hot_accounts {
+            ensure!(
+                hot_accounts.count > 0,
+                "There must be at least one hot account"
+            );
+            ensure!(
+                (0.0..=1.0).contains(&hot_accounts.probability),
+                "The hot account probability must be between 0 and 1"
+            );
+        }
+        for txn in &self.transactions {
+            if let TransactionShape::EntryFunction { args, .. } = &txn.shape {
+                for arg in args {
+                    if let ArgGenerator::U64Range { min, max } = arg {
+                        ensure!(min <= max, "Empty u64 range [{}, {}]", min, max);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The total duration of the phases
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.phases.iter().map(|phase| phase.duration_secs).sum())
+    }
+
+    /// The highest backlog of the phases
+    pub fn max_mempool_backlog(&self) -> Option<u64> {
+        self.phases.iter().map(|phase| phase.mempool_backlog).max()
+    }
+
+    /// The backlog to keep after running for `elapsed`. After the last phase, the backlog of the
+    /// last phase is kept.
+    pub fn mempool_backlog_at(&self, elapsed: Duration) -> Option<u64> {
+        let mut previous_backlog = 0;
+        let mut phase_start = Duration::ZERO;
+        for phase in &self.phases {
+            let phase_duration = Duration::from_secs(phase.duration_secs);
+            if elapsed < phase_start + phase_duration {
+                if !phase.ramp {
+                    return Some(phase.mempool_backlog);
+                }
+                let progress = (elapsed - phase_start).as_secs_f64() / phase_duration.as_secs_f64();
+                let delta = phase.mempool_backlog as f64 - previous_backlog as f64;
+                return Some((previous_backlog as f64 + delta * progress) as u64);
+            }
+            previous_backlog = phase.mempool_backlog;
+            phase_start += phase_duration;
+        }
+        self.phases.last().map(|phase| phase.mempool_backlog)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn phase(duration_secs: u64, mempool_backlog: u64, ramp: bool) -> Phase {
+        Phase {
+            duration_secs,
+            mempool_backlog,
+            ramp,
+        }
+    }
+
+    #[test]
+    pub fn test_parse_workload() {
+        let spec: WorkloadSpec = serde_yaml::from_str(
+            r#"
+transactions:
+  - weight: 3
+    type: p2p
+  - weight: 1
+    type: entry_function
+    function: "0x1::coin::transfer"
+    args:
+      - type: receiver
+      - type: u64_range
+        min: 1
+        max: 10
+hot_accounts:
+  count: 2
+  probability: 0.5
+"#,
+        )
+        .unwrap();
+        spec.validate().unwrap();
+        assert_eq!(
+            spec.transactions[0].shape,
+            TransactionShape::P2p { amount: 1 }
+        );
+        assert_eq!(
+            spec.transactions[1].shape,
+            TransactionShape::EntryFunction {
+                function: "0x1::coin::transfer".to_string(),
+                type_args: vec![],
+                args: vec![
+                    ArgGenerator::Receiver,
+                    ArgGenerator::U64Range { min: 1, max: 10 }
+                ],
+            }
+        );
+        assert!(spec.phases.is_empty());
+        assert_eq!(spec.mempool_backlog_at(Duration::ZERO), None);
+    }
+
+    #[test]
+    pub fn test_mempool_backlog_at() {
+        let spec = WorkloadSpec {
+            transactions: vec![],
+            hot_accounts: None,
+            phases: vec![
+                phase(10, 1000, true),
+                phase(10, 1000, false),
+                phase(10, 3000, true),
+            ],
+        };
+        let backlog_at = |secs| spec.mempool_backlog_at(Duration::from_secs(secs)).unwrap();
+        assert_eq!(backlog_at(0), 0);
+        assert_eq!(backlog_at(5), 500);
+        assert_eq!(backlog_at(15), 1000);
+        assert_eq!(backlog_at(25), 2000);
+        assert_eq!(backlog_at(100), 3000);
+        assert_eq!(spec.duration(), Duration::from_secs(30));
+        assert_eq!(spec.max_mempool_backlog(), Some(3000));
+    }
+}
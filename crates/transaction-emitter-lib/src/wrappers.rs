@@ -6,6 +6,7 @@ use crate::{
     cluster::Cluster,
     emitter::{stats::TxnStats, EmitJobRequest, EmitThreadParams, TxnEmitter},
     instance::Instance,
+    workload::WorkloadSpec,
 };
 use anyhow::{Context, Result};
 use aptos_sdk::transaction_builder::TransactionFactory;
@@ -60,8 +61,12 @@ pub async fn emit_transactions_with_cluster(
     if reuse_accounts {
         emit_job_request = emit_job_request.reuse_accounts();
     }
+    if let Some(workload) = &args.workload {
+        emit_job_request = emit_job_request.workload(WorkloadSpec::load(workload)?);
+    }
+    let stats_interval_secs = min(10, max(emit_job_request.duration.as_secs() / 5, 1));
     let stats = emitter
-        .emit_txn_for_with_stats(emit_job_request, min(10, max(args.duration / 5, 1)))
+        .emit_txn_for_with_stats(emit_job_request, stats_interval_secs)
         .await?;
     Ok(stats)
 }
@@ -16,6 +16,7 @@ RUSTFLAGS="--cfg tokio_unstable" cargo build --release \
         -p db-bootstrapper \
         -p forge-cli \
         -p transaction-emitter \
+        --features aptos-faucet/redis-store,aptos-indexer/graphql,aptos-node/otlp \
         "$@"
 
 # After building, copy the binaries we need to `dist` since the `target` directory is used as docker cache mount and only available during the RUN step
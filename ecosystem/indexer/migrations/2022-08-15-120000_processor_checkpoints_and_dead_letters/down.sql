@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS dead_letter_transactions;
+DROP TABLE IF EXISTS processor_checkpoints;
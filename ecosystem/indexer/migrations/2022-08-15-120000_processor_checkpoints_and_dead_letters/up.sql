@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE processor_checkpoints
+(
+    name         VARCHAR(50) NOT NULL,
+    -- Every version below this one has been processed, or dead lettered
+    next_version uint_64     NOT NULL,
+    last_updated TIMESTAMP   NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (name)
+);
+
+CREATE TABLE dead_letter_transactions
+(
+    name         VARCHAR(50) NOT NULL,
+    version      uint_64     NOT NULL,
+    attempts     INT         NOT NULL,
+    error        TEXT        NOT NULL,
+    inserted_at  TIMESTAMP   NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (name, version)
+);
@@ -0,0 +1,15 @@
+-- This file should undo anything in `up.sql`
+DROP MATERIALIZED VIEW IF EXISTS current_collection_ownerships;
+DROP MATERIALIZED VIEW IF EXISTS current_token_ownerships;
+
+DROP INDEX IF EXISTS token_activities_token_id_index;
+DROP INDEX IF EXISTS ownerships_token_data_id_index;
+
+ALTER TABLE ownerships
+    DROP COLUMN IF EXISTS token_data_id,
+    DROP COLUMN IF EXISTS property_version;
+
+ALTER TABLE token_activities
+    ADD CONSTRAINT fk_transactions
+        FOREIGN KEY (transaction_hash)
+            REFERENCES transactions (hash);
@@ -0,0 +1,42 @@
+-- Your SQL goes here
+-- Token activities are written by the token processor, which may run ahead of the default processor
+ALTER TABLE token_activities
+    DROP CONSTRAINT fk_transactions;
+
+ALTER TABLE ownerships
+    ADD COLUMN token_data_id VARCHAR,
+    ADD COLUMN property_version uint_64;
+
+CREATE INDEX ownerships_token_data_id_index ON ownerships (token_data_id);
+CREATE INDEX token_activities_token_id_index ON token_activities (token_id);
+
+-- Tokens currently held, along with their token data
+CREATE MATERIALIZED VIEW current_token_ownerships AS
+SELECT o.token_id,
+       o.owner,
+       o.amount,
+       o.token_data_id,
+       o.property_version,
+       td.creator,
+       td.collection,
+       td.name,
+       td.uri,
+       o.updated_at
+FROM ownerships o
+         INNER JOIN token_datas td ON td.token_data_id = o.token_data_id
+WHERE o.amount > 0;
+
+CREATE UNIQUE INDEX current_token_ownerships_token_id_owner_index ON current_token_ownerships (token_id, owner);
+CREATE INDEX current_token_ownerships_owner_index ON current_token_ownerships (owner);
+
+-- How many of each collection's tokens every account currently holds
+CREATE MATERIALIZED VIEW current_collection_ownerships AS
+SELECT owner,
+       creator,
+       collection,
+       COUNT(DISTINCT token_data_id) AS distinct_tokens,
+       SUM(amount)                   AS amount
+FROM current_token_ownerships
+GROUP BY owner, creator, collection;
+
+CREATE UNIQUE INDEX current_collection_ownerships_owner_collection_index ON current_collection_ownerships (owner, creator, collection);
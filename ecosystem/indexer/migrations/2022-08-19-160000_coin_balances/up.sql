@@ -0,0 +1,51 @@
+-- Your SQL goes here
+-- Every coin deposit and withdrawal, plus the gas fee paid by each user transaction
+CREATE TABLE coin_activities
+(
+    transaction_version    uint_64       NOT NULL,
+    -- Index of the event within the transaction, or the number of events for the gas fee
+    event_index            BIGINT        NOT NULL,
+    owner_address          VARCHAR(66)   NOT NULL,
+    coin_type              VARCHAR(5000) NOT NULL,
+    amount                 uint_64       NOT NULL,
+    activity_type          VARCHAR(5000) NOT NULL,
+    is_gas_fee             BOOLEAN       NOT NULL,
+    is_transaction_success BOOLEAN       NOT NULL,
+    transaction_timestamp  TIMESTAMP     NOT NULL,
+    inserted_at            TIMESTAMP     NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (transaction_version, event_index)
+);
+
+CREATE INDEX coin_activities_owner_address_index ON coin_activities (owner_address, coin_type, transaction_version);
+
+-- The balance of every coin store after every transaction which changed it
+CREATE TABLE coin_balances
+(
+    transaction_version   uint_64       NOT NULL,
+    owner_address         VARCHAR(66)   NOT NULL,
+    coin_type             VARCHAR(5000) NOT NULL,
+    amount                uint_64       NOT NULL,
+    transaction_timestamp TIMESTAMP     NOT NULL,
+    inserted_at           TIMESTAMP     NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (transaction_version, owner_address, coin_type)
+);
+
+CREATE INDEX coin_balances_owner_address_index ON coin_balances (owner_address, coin_type, transaction_version);
+
+-- The latest balance of every coin store, only ever overwritten by a balance at a later version
+CREATE TABLE current_coin_balances
+(
+    owner_address              VARCHAR(66)   NOT NULL,
+    coin_type                  VARCHAR(5000) NOT NULL,
+    amount                     uint_64       NOT NULL,
+    last_transaction_version   uint_64       NOT NULL,
+    last_transaction_timestamp TIMESTAMP     NOT NULL,
+    inserted_at                TIMESTAMP     NOT NULL DEFAULT NOW(),
+
+    -- Constraints
+    PRIMARY KEY (owner_address, coin_type)
+);
@@ -0,0 +1,200 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{execute_with_better_error, PgDbPool, PgPoolConnection},
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        transaction_processor::TransactionProcessor,
+    },
+    models::{
+        coin::{CoinActivity, CoinBalance, CoinStore},
+        events::EventModel,
+        transactions::{TransactionModel, UserTransaction},
+        write_set_changes::WriteSetChangeModel,
+    },
+    schema,
+    util::u64_to_bigdecimal,
+};
+use aptos_rest_client::Transaction;
+use async_trait::async_trait;
+use diesel::{
+    sql_types::{Numeric, Text, Timestamp},
+    Connection, RunQueryDsl,
+};
+use futures::future::Either;
+use std::{fmt::Debug, sync::Arc};
+
+/// Only overwrites a current balance with one from the same or a later version, so that
+/// reprocessing older versions (e.g. during a backfill, or after a restart) can't roll it back
+const UPSERT_CURRENT_COIN_BALANCE: &str = "
+INSERT INTO current_coin_balances
+    (owner_address, coin_type, amount, last_transaction_version, last_transaction_timestamp)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (owner_address, coin_type) DO UPDATE SET
+    amount = EXCLUDED.amount,
+    last_transaction_version = EXCLUDED.last_transaction_version,
+    last_transaction_timestamp = EXCLUDED.last_transaction_timestamp,
+    inserted_at = NOW()
+WHERE current_coin_balances.last_transaction_version <= EXCLUDED.last_transaction_version";
+
+pub struct CoinTransactionProcessor {
+    connection_pool: PgDbPool,
+}
+
+impl CoinTransactionProcessor {
+    pub fn new(connection_pool: PgDbPool) -> Self {
+        Self { connection_pool }
+    }
+}
+
+impl Debug for CoinTransactionProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "CoinTransactionProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+fn insert_coin_activities(conn: &PgPoolConnection, coin_activities: &[CoinActivity]) {
+    execute_with_better_error(
+        conn,
+        diesel::insert_into(schema::coin_activities::table)
+            .values(coin_activities)
+            .on_conflict_do_nothing(),
+    )
+    .expect("Error inserting row into coin_activities");
+}
+
+fn insert_coin_balances(conn: &PgPoolConnection, coin_balances: &[CoinBalance]) {
+    execute_with_better_error(
+        conn,
+        diesel::insert_into(schema::coin_balances::table)
+            .values(coin_balances)
+            .on_conflict_do_nothing(),
+    )
+    .expect("Error inserting row into coin_balances");
+}
+
+fn upsert_current_coin_balance(
+    conn: &PgPoolConnection,
+    coin_balance: &CoinBalance,
+) -> diesel::QueryResult<usize> {
+    diesel::sql_query(UPSERT_CURRENT_COIN_BALANCE)
+        .bind::<Text, _>(&coin_balance.owner_address)
+        .bind::<Text, _>(&coin_balance.coin_type)
+        .bind::<Numeric, _>(&coin_balance.amount)
+        .bind::<Numeric, _>(&coin_balance.transaction_version)
+        .bind::<Timestamp, _>(&coin_balance.transaction_timestamp)
+        .execute(conn)
+}
+
+/// Extracts the coin activities and coin store balances of a transaction. Deposit and withdraw
+/// events only carry an amount, so their owner and coin type come from the coin store whose event
+/// handle emitted them, which is always written by the same transaction.
+pub fn coin_activities_and_balances(
+    transaction: &Transaction,
+    user_transaction: Option<&UserTransaction>,
+    events: &[EventModel],
+    write_set_changes: &[WriteSetChangeModel],
+) -> (Vec<CoinActivity>, Vec<CoinBalance>) {
+    let version = transaction.version().unwrap_or(0);
+    let success = transaction.success();
+    let timestamp = match user_transaction {
+        Some(user_transaction) => user_transaction.timestamp,
+        None => {
+            chrono::NaiveDateTime::from_timestamp((transaction.timestamp() / 1_000_000) as i64, 0)
+        }
+    };
+
+    let coin_stores: Vec<CoinStore> = write_set_changes
+        .iter()
+        .filter_map(CoinStore::from_write_set_change)
+        .collect();
+    let mut coin_activities: Vec<CoinActivity> = events
+        .iter()
+        .enumerate()
+        .filter_map(|(index, event)| {
+            CoinActivity::from_event(
+                event,
+                index as i64,
+                &coin_stores,
+                version,
+                success,
+                timestamp,
+            )
+        })
+        .collect();
+    if let Some(user_transaction) = user_transaction {
+        let gas_used = transaction
+            .transaction_info()
+            .map(|info| u64_to_bigdecimal(info.gas_used.0))
+            .unwrap_or_else(|_| u64_to_bigdecimal(0));
+        coin_activities.push(CoinActivity::gas_fee(
+            &user_transaction.sender,
+            gas_used * user_transaction.gas_unit_price.clone(),
+            events.len(),
+            version,
+            success,
+            timestamp,
+        ));
+    }
+    let coin_balances = coin_stores
+        .iter()
+        .map(|coin_store| CoinBalance::new(coin_store, version, timestamp))
+        .collect();
+    (coin_activities, coin_balances)
+}
+
+#[async_trait]
+impl TransactionProcessor for CoinTransactionProcessor {
+    fn name(&self) -> &'static str {
+        "coin_processor"
+    }
+
+    async fn process_transaction(
+        &self,
+        transaction: Arc<Transaction>,
+    ) -> Result<ProcessingResult, TransactionProcessingError> {
+        let version = transaction.version().unwrap_or(0);
+
+        let (_, maybe_details_model, maybe_events, maybe_write_set_changes) =
+            TransactionModel::from_transaction(&transaction);
+        let user_transaction = match &maybe_details_model {
+            Some(Either::Left(user_transaction)) => Some(user_transaction),
+            _ => None,
+        };
+        let (coin_activities, coin_balances) = coin_activities_and_balances(
+            &transaction,
+            user_transaction,
+            &maybe_events.unwrap_or_default(),
+            &maybe_write_set_changes.unwrap_or_default(),
+        );
+
+        let conn = self.get_conn();
+        let tx_result = conn.transaction::<(), diesel::result::Error, _>(|| {
+            insert_coin_activities(&conn, &coin_activities);
+            insert_coin_balances(&conn, &coin_balances);
+            for coin_balance in &coin_balances {
+                upsert_current_coin_balance(&conn, coin_balance)?;
+            }
+            Ok(())
+        });
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::new(self.name(), version)),
+            Err(err) => Err(TransactionProcessingError::TransactionCommitError((
+                anyhow::Error::from(err),
+                version,
+                self.name(),
+            ))),
+        }
+    }
+
+    fn connection_pool(&self) -> &PgDbPool {
+        &self.connection_pool
+    }
+}
@@ -71,3 +71,23 @@ pub static FETCHED_TRANSACTION: Lazy<IntCounter> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Number of times any given processor has retried a transaction after an error
+pub static PROCESSOR_RETRIES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_processor_retry_count",
+        "Number of times any given processor has retried a transaction after an error",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Number of transactions any given processor has given up on and dead lettered
+pub static PROCESSOR_DEAD_LETTERS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_processor_dead_letter_count",
+        "Number of transactions any given processor has given up on and dead lettered",
+        &["processor_name"]
+    )
+    .unwrap()
+});
@@ -0,0 +1,51 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A read-only GraphQL API over the tables written by the processors, so that clients can fetch
+//! an account's coins, tokens and transactions in a single round trip.
+
+pub mod query;
+pub mod types;
+
+use crate::database::PgDbPool;
+use async_graphql::{
+    http::{playground_source, GraphQLPlaygroundConfig},
+    EmptyMutation, EmptySubscription, Schema,
+};
+use std::{convert::Infallible, net::SocketAddr};
+use warp::{http::Response as HttpResponse, Filter};
+
+pub type IndexerSchema = Schema<query::QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Nested list fields multiply the number of queries run, so keep queries shallow
+const MAX_QUERY_DEPTH: usize = 6;
+
+pub fn build_schema(connection_pool: PgDbPool) -> IndexerSchema {
+    Schema::build(query::QueryRoot, EmptyMutation, EmptySubscription)
+        .data(connection_pool)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+/// Serves the GraphQL API on `/graphql`, along with a playground on `/graphql/playground`
+pub async fn serve(connection_pool: PgDbPool, address: SocketAddr) {
+    let schema = build_schema(connection_pool);
+    let graphql = warp::path!("graphql")
+        .and(async_graphql_warp::graphql(schema))
+        .and_then(
+            |(schema, request): (IndexerSchema, async_graphql::Request)| async move {
+                Ok::<_, Infallible>(async_graphql_warp::GraphQLResponse::from(
+                    schema.execute(request).await,
+                ))
+            },
+        );
+    let playground = warp::path!("graphql" / "playground")
+        .and(warp::get())
+        .map(|| {
+            HttpResponse::builder()
+                .header("content-type", "text/html")
+                .body(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+        });
+    aptos_logger::info!("Serving GraphQL on {}", address);
+    warp::serve(playground.or(graphql)).run(address).await;
+}
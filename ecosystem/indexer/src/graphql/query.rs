@@ -0,0 +1,379 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    database::{PgDbPool, PgPoolConnection},
+    graphql::types::{
+        Account, CoinActivity, CoinBalance, Event, OwnershipRow, TokenOwnership, Transaction,
+        UserTransaction,
+    },
+    models::{
+        coin::{CoinActivity as CoinActivityModel, CurrentCoinBalance},
+        events::EventModel,
+        transactions::{TransactionModel, UserTransaction as UserTransactionModel},
+    },
+    schema::{
+        coin_activities, current_coin_balances, events, ownerships, transactions, user_transactions,
+    },
+    util::u64_to_bigdecimal,
+};
+use aptos_rest_client::aptos_api_types::Address;
+use async_graphql::{Context, Object, Result};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use std::str::FromStr;
+
+/// The most items a single list field returns
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// An `offset` and `limit` pair, validated and capped at `MAX_PAGE_SIZE`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Page {
+    pub offset: i64,
+    pub limit: i64,
+}
+
+impl Page {
+    pub fn new(offset: Option<i64>, limit: Option<i64>) -> Result<Self> {
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE);
+        if offset < 0 || limit < 0 {
+            return Err("offset and limit must not be negative".into());
+        }
+        Ok(Self {
+            offset,
+            limit: limit.min(MAX_PAGE_SIZE),
+        })
+    }
+
+    /// Every item, for lists which are bounded anyway, like the events of a transaction
+    pub fn all() -> Self {
+        Self {
+            offset: 0,
+            limit: i64::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TransactionFilter {
+    pub sender: Option<String>,
+    pub type_: Option<String>,
+    pub success: Option<bool>,
+    pub start_version: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct EventFilter {
+    pub transaction_hash: Option<String>,
+    pub key: Option<String>,
+    pub type_: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct TokenOwnershipFilter {
+    pub owner: Option<String>,
+    pub token_data_id: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct CoinActivityFilter {
+    pub owner_address: Option<String>,
+    pub coin_type: Option<String>,
+    pub activity_type: Option<String>,
+}
+
+fn get_conn(ctx: &Context<'_>) -> Result<PgPoolConnection> {
+    Ok(ctx.data::<PgDbPool>()?.get()?)
+}
+
+/// Addresses are stored in their shortest form, e.g. `0x1`
+fn normalize_address(address: &str) -> Result<String> {
+    Ok(Address::from_str(address)?.to_string())
+}
+
+pub fn transaction(
+    ctx: &Context<'_>,
+    version: Option<u64>,
+    hash: Option<String>,
+) -> Result<Option<Transaction>> {
+    let conn = get_conn(ctx)?;
+    let mut query = transactions::table.into_boxed();
+    match (version, hash) {
+        (Some(version), None) => {
+            query = query.filter(transactions::version.eq(u64_to_bigdecimal(version)))
+        }
+        (None, Some(hash)) => query = query.filter(transactions::hash.eq(hash)),
+        _ => return Err("exactly one of version and hash must be set".into()),
+    }
+    Ok(query
+        .first::<TransactionModel>(&conn)
+        .optional()?
+        .map(Transaction::from))
+}
+
+pub fn transactions(
+    ctx: &Context<'_>,
+    filter: TransactionFilter,
+    page: Page,
+) -> Result<Vec<Transaction>> {
+    let conn = get_conn(ctx)?;
+    let mut query = transactions::table.into_boxed();
+    if let Some(sender) = filter.sender {
+        query = query.filter(
+            transactions::hash.eq_any(
+                user_transactions::table
+                    .select(user_transactions::hash)
+                    .filter(user_transactions::sender.eq(normalize_address(&sender)?)),
+            ),
+        );
+    }
+    if let Some(type_) = filter.type_ {
+        query = query.filter(transactions::type_.eq(type_));
+    }
+    if let Some(success) = filter.success {
+        query = query.filter(transactions::success.eq(success));
+    }
+    if let Some(start_version) = filter.start_version {
+        query = query.filter(transactions::version.ge(u64_to_bigdecimal(start_version)));
+    }
+    Ok(query
+        .order(transactions::version.asc())
+        .offset(page.offset)
+        .limit(page.limit)
+        .load::<TransactionModel>(&conn)?
+        .into_iter()
+        .map(Transaction::from)
+        .collect())
+}
+
+pub fn user_transaction(ctx: &Context<'_>, hash: &str) -> Result<Option<UserTransaction>> {
+    let conn = get_conn(ctx)?;
+    Ok(user_transactions::table
+        .find(hash)
+        .first::<UserTransactionModel>(&conn)
+        .optional()?
+        .map(UserTransaction::from))
+}
+
+pub fn events(ctx: &Context<'_>, filter: EventFilter, page: Page) -> Result<Vec<Event>> {
+    let conn = get_conn(ctx)?;
+    let mut query = events::table.into_boxed();
+    if let Some(transaction_hash) = filter.transaction_hash {
+        query = query.filter(events::transaction_hash.eq(transaction_hash));
+    }
+    if let Some(key) = filter.key {
+        query = query.filter(events::key.eq(key));
+    }
+    if let Some(type_) = filter.type_ {
+        query = query.filter(events::type_.eq(type_));
+    }
+    Ok(query
+        .order((events::key.asc(), events::sequence_number.asc()))
+        .offset(page.offset)
+        .limit(page.limit)
+        .load::<EventModel>(&conn)?
+        .into_iter()
+        .map(Event::from)
+        .collect())
+}
+
+pub fn token_ownerships(
+    ctx: &Context<'_>,
+    filter: TokenOwnershipFilter,
+    page: Page,
+) -> Result<Vec<TokenOwnership>> {
+    let conn = get_conn(ctx)?;
+    // Ownerships are kept after the tokens are transferred away, with an amount of 0
+    let mut query = ownerships::table
+        .filter(ownerships::amount.gt(u64_to_bigdecimal(0)))
+        .into_boxed();
+    if let Some(owner) = filter.owner {
+        query = query.filter(ownerships::owner.eq(normalize_address(&owner)?));
+    }
+    if let Some(token_data_id) = filter.token_data_id {
+        query = query.filter(ownerships::token_data_id.eq(token_data_id));
+    }
+    Ok(query
+        .select((
+            ownerships::token_id,
+            ownerships::token_data_id,
+            ownerships::property_version,
+            ownerships::owner,
+            ownerships::amount,
+            ownerships::updated_at,
+        ))
+        .order(ownerships::ownership_id.asc())
+        .offset(page.offset)
+        .limit(page.limit)
+        .load::<OwnershipRow>(&conn)?
+        .into_iter()
+        .map(TokenOwnership::from)
+        .collect())
+}
+
+pub fn coin_activities(
+    ctx: &Context<'_>,
+    filter: CoinActivityFilter,
+    page: Page,
+) -> Result<Vec<CoinActivity>> {
+    let conn = get_conn(ctx)?;
+    let mut query = coin_activities::table.into_boxed();
+    if let Some(owner_address) = filter.owner_address {
+        query = query.filter(coin_activities::owner_address.eq(normalize_address(&owner_address)?));
+    }
+    if let Some(coin_type) = filter.coin_type {
+        query = query.filter(coin_activities::coin_type.eq(coin_type));
+    }
+    if let Some(activity_type) = filter.activity_type {
+        query = query.filter(coin_activities::activity_type.eq(activity_type));
+    }
+    Ok(query
+        .order((
+            coin_activities::transaction_version.asc(),
+            coin_activities::event_index.asc(),
+        ))
+        .offset(page.offset)
+        .limit(page.limit)
+        .load::<CoinActivityModel>(&conn)?
+        .into_iter()
+        .map(CoinActivity::from)
+        .collect())
+}
+
+pub fn coin_balances(ctx: &Context<'_>, owner_address: &str) -> Result<Vec<CoinBalance>> {
+    let conn = get_conn(ctx)?;
+    Ok(current_coin_balances::table
+        .filter(current_coin_balances::owner_address.eq(normalize_address(owner_address)?))
+        .order(current_coin_balances::coin_type.asc())
+        .load::<CurrentCoinBalance>(&conn)?
+        .into_iter()
+        .map(CoinBalance::from)
+        .collect())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// An account, whose coins, tokens and transactions can be queried
+    async fn account(&self, address: String) -> Result<Account> {
+        Ok(Account {
+            address: normalize_address(&address)?,
+        })
+    }
+
+    /// A transaction, by either its version or its hash
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        version: Option<String>,
+        hash: Option<String>,
+    ) -> Result<Option<Transaction>> {
+        let version = version.map(|version| version.parse::<u64>()).transpose()?;
+        transaction(ctx, version, hash)
+    }
+
+    /// Transactions, oldest first
+    #[allow(clippy::too_many_arguments)]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        sender: Option<String>,
+        #[graphql(name = "type")] type_: Option<String>,
+        success: Option<bool>,
+        start_version: Option<String>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Transaction>> {
+        let filter = TransactionFilter {
+            sender,
+            type_,
+            success,
+            start_version: start_version
+                .map(|version| version.parse::<u64>())
+                .transpose()?,
+        };
+        transactions(ctx, filter, Page::new(offset, limit)?)
+    }
+
+    /// Events, ordered by event key and sequence number
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        transaction_hash: Option<String>,
+        key: Option<String>,
+        #[graphql(name = "type")] type_: Option<String>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Event>> {
+        let filter = EventFilter {
+            transaction_hash,
+            key,
+            type_,
+        };
+        events(ctx, filter, Page::new(offset, limit)?)
+    }
+
+    /// Tokens currently held, ordered by token and owner
+    async fn token_ownerships(
+        &self,
+        ctx: &Context<'_>,
+        owner: Option<String>,
+        token_data_id: Option<String>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TokenOwnership>> {
+        let filter = TokenOwnershipFilter {
+            owner,
+            token_data_id,
+        };
+        token_ownerships(ctx, filter, Page::new(offset, limit)?)
+    }
+
+    /// Coin deposits, withdrawals and gas fees, oldest first
+    async fn coin_activities(
+        &self,
+        ctx: &Context<'_>,
+        owner_address: Option<String>,
+        coin_type: Option<String>,
+        activity_type: Option<String>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<CoinActivity>> {
+        let filter = CoinActivityFilter {
+            owner_address,
+            coin_type,
+            activity_type,
+        };
+        coin_activities(ctx, filter, Page::new(offset, limit)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page() {
+        assert_eq!(
+            Page::new(None, None).unwrap(),
+            Page {
+                offset: 0,
+                limit: MAX_PAGE_SIZE
+            }
+        );
+        assert_eq!(
+            Page::new(Some(20), Some(10)).unwrap(),
+            Page {
+                offset: 20,
+                limit: 10
+            }
+        );
+        assert_eq!(
+            Page::new(None, Some(MAX_PAGE_SIZE + 1)).unwrap().limit,
+            MAX_PAGE_SIZE
+        );
+        assert!(Page::new(Some(-1), None).is_err());
+        assert!(Page::new(None, Some(-1)).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! The GraphQL objects, built from the Postgres models. Unsigned 64 bit integers are returned as
+//! strings, as they are by the node API, since GraphQL integers are only 32 bits.
+
+use crate::{
+    graphql::query::{self, Page},
+    models::{
+        coin::{CoinActivity as CoinActivityModel, CurrentCoinBalance},
+        events::EventModel,
+        transactions::{TransactionModel, UserTransaction as UserTransactionModel},
+    },
+};
+use async_graphql::{ComplexObject, Context, Json, Result, SimpleObject};
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Account {
+    pub address: String,
+}
+
+#[ComplexObject]
+impl Account {
+    /// The account's current balance of every coin it holds
+    async fn coin_balances(&self, ctx: &Context<'_>) -> Result<Vec<CoinBalance>> {
+        query::coin_balances(ctx, &self.address)
+    }
+
+    /// The account's coin deposits, withdrawals and gas fees, oldest first
+    async fn coin_activities(
+        &self,
+        ctx: &Context<'_>,
+        coin_type: Option<String>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<CoinActivity>> {
+        let filter = query::CoinActivityFilter {
+            owner_address: Some(self.address.clone()),
+            coin_type,
+            activity_type: None,
+        };
+        query::coin_activities(ctx, filter, Page::new(offset, limit)?)
+    }
+
+    /// The tokens the account currently holds
+    async fn token_ownerships(
+        &self,
+        ctx: &Context<'_>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TokenOwnership>> {
+        let filter = query::TokenOwnershipFilter {
+            owner: Some(self.address.clone()),
+            token_data_id: None,
+        };
+        query::token_ownerships(ctx, filter, Page::new(offset, limit)?)
+    }
+
+    /// The transactions sent by the account, oldest first
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        offset: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Transaction>> {
+        let filter = query::TransactionFilter {
+            sender: Some(self.address.clone()),
+            ..Default::default()
+        };
+        query::transactions(ctx, filter, Page::new(offset, limit)?)
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Transaction {
+    pub version: String,
+    pub hash: String,
+    #[graphql(name = "type")]
+    pub type_: String,
+    pub payload: Json<serde_json::Value>,
+    pub state_root_hash: String,
+    pub event_root_hash: String,
+    pub accumulator_root_hash: String,
+    pub gas_used: String,
+    pub success: bool,
+    pub vm_status: String,
+}
+
+impl From<TransactionModel> for Transaction {
+    fn from(transaction: TransactionModel) -> Self {
+        Self {
+            version: transaction.version.to_string(),
+            hash: transaction.hash,
+            type_: transaction.type_,
+            payload: Json(transaction.payload),
+            state_root_hash: transaction.state_root_hash,
+            event_root_hash: transaction.event_root_hash,
+            accumulator_root_hash: transaction.accumulator_root_hash,
+            gas_used: transaction.gas_used.to_string(),
+            success: transaction.success,
+            vm_status: transaction.vm_status,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Transaction {
+    /// The sender, gas price and signature of user transactions
+    async fn user_transaction(&self, ctx: &Context<'_>) -> Result<Option<UserTransaction>> {
+        query::user_transaction(ctx, &self.hash)
+    }
+
+    /// The events emitted by the transaction
+    async fn events(&self, ctx: &Context<'_>) -> Result<Vec<Event>> {
+        let filter = query::EventFilter {
+            transaction_hash: Some(self.hash.clone()),
+            ..Default::default()
+        };
+        query::events(ctx, filter, Page::all())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct UserTransaction {
+    pub sender: String,
+    pub sequence_number: String,
+    pub max_gas_amount: String,
+    pub gas_unit_price: String,
+    pub expiration_timestamp_secs: chrono::NaiveDateTime,
+    pub timestamp: chrono::NaiveDateTime,
+    pub signature: Json<serde_json::Value>,
+}
+
+impl From<UserTransactionModel> for UserTransaction {
+    fn from(transaction: UserTransactionModel) -> Self {
+        Self {
+            sender: transaction.sender,
+            sequence_number: transaction.sequence_number.to_string(),
+            max_gas_amount: transaction.max_gas_amount.to_string(),
+            gas_unit_price: transaction.gas_unit_price.to_string(),
+            expiration_timestamp_secs: transaction.expiration_timestamp_secs,
+            timestamp: transaction.timestamp,
+            signature: Json(transaction.signature),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Event {
+    pub transaction_hash: String,
+    pub key: String,
+    pub sequence_number: String,
+    #[graphql(name = "type")]
+    pub type_: String,
+    pub data: Json<serde_json::Value>,
+}
+
+impl From<EventModel> for Event {
+    fn from(event: EventModel) -> Self {
+        Self {
+            transaction_hash: event.transaction_hash,
+            key: event.key,
+            sequence_number: event.sequence_number.to_string(),
+            type_: event.type_,
+            data: Json(event.data),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TokenOwnership {
+    pub token_id: String,
+    pub token_data_id: Option<String>,
+    pub property_version: Option<String>,
+    pub owner: String,
+    pub amount: String,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+/// The `ownerships` columns a `TokenOwnership` is built from
+pub type OwnershipRow = (
+    Option<String>,
+    Option<String>,
+    Option<bigdecimal::BigDecimal>,
+    Option<String>,
+    bigdecimal::BigDecimal,
+    chrono::NaiveDateTime,
+);
+
+impl From<OwnershipRow> for TokenOwnership {
+    fn from(
+        (token_id, token_data_id, property_version, owner, amount, updated_at): OwnershipRow,
+    ) -> Self {
+        Self {
+            token_id: token_id.unwrap_or_default(),
+            token_data_id,
+            property_version: property_version.map(|property_version| property_version.to_string()),
+            owner: owner.unwrap_or_default(),
+            amount: amount.to_string(),
+            updated_at,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct CoinActivity {
+    pub transaction_version: String,
+    pub event_index: i64,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: String,
+    pub activity_type: String,
+    pub is_gas_fee: bool,
+    pub is_transaction_success: bool,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl From<CoinActivityModel> for CoinActivity {
+    fn from(activity: CoinActivityModel) -> Self {
+        Self {
+            transaction_version: activity.transaction_version.to_string(),
+            event_index: activity.event_index,
+            owner_address: activity.owner_address,
+            coin_type: activity.coin_type,
+            amount: activity.amount.to_string(),
+            activity_type: activity.activity_type,
+            is_gas_fee: activity.is_gas_fee,
+            is_transaction_success: activity.is_transaction_success,
+            transaction_timestamp: activity.transaction_timestamp,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct CoinBalance {
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: String,
+    pub last_transaction_version: String,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl From<CurrentCoinBalance> for CoinBalance {
+    fn from(balance: CurrentCoinBalance) -> Self {
+        Self {
+            owner_address: balance.owner_address,
+            coin_type: balance.coin_type,
+            amount: balance.amount.to_string(),
+            last_transaction_version: balance.last_transaction_version.to_string(),
+            last_transaction_timestamp: balance.last_transaction_timestamp,
+        }
+    }
+}
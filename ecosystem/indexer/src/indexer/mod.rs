@@ -5,5 +5,6 @@ pub mod errors;
 pub mod fetcher;
 pub mod metadata_fetcher;
 pub mod processing_result;
+pub mod retry_policy;
 pub mod tailer;
 pub mod transaction_processor;
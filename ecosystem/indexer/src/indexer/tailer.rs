@@ -60,16 +60,18 @@ pub struct Tailer {
     transaction_fetcher: Arc<Mutex<dyn TransactionFetcherTrait>>,
     processors: Vec<Arc<dyn TransactionProcessor>>,
     connection_pool: PgDbPool,
+    node_url: Url,
 }
 
 impl Tailer {
     pub fn new(node_url: &str, connection_pool: PgDbPool) -> Result<Tailer, ParseError> {
         let url = Url::parse(node_url)?;
-        let transaction_fetcher = TransactionFetcher::new(url, None);
+        let transaction_fetcher = TransactionFetcher::new(url.clone(), None);
         Ok(Self {
             transaction_fetcher: Arc::new(Mutex::new(transaction_fetcher)),
             processors: vec![],
             connection_pool,
+            node_url: url,
         })
     }
 
@@ -133,6 +135,7 @@ impl Tailer {
     }
 
     /// For all versions which have an `success=false` in the `processor_status` table, re-run them
+    /// Versions which are fixed are also removed from the processor's dead letters
     /// TODO: also handle gaps in sequence numbers (pg query for this is super easy)
     pub async fn handle_previous_errors(&self) {
         info!("Checking for previously errored versions...");
@@ -159,6 +162,7 @@ impl Tailer {
                         .await
                         .is_ok()
                     {
+                        processor2.remove_dead_letter(version);
                         fixed += 1;
                     };
                 }
@@ -176,17 +180,21 @@ impl Tailer {
     }
 
     /// Sets the version of the fetcher to the lowest version among all processors
+    /// Processors resume from their checkpoint, falling back to their max version if they have none
     pub async fn set_fetcher_to_lowest_processor_version(&self) -> u64 {
         let mut lowest = u64::MAX;
         for processor in &self.processors {
-            let max_version = processor.get_max_version().unwrap_or_default();
+            let resume_version = processor
+                .get_checkpoint()
+                .or_else(|| processor.get_max_version())
+                .unwrap_or_default();
             aptos_logger::debug!(
-                "Processor {} max version is {}",
+                "Processor {} resume version is {}",
                 processor.name(),
-                max_version
+                resume_version
             );
-            if max_version < lowest {
-                lowest = max_version;
+            if resume_version < lowest {
+                lowest = resume_version;
             }
         }
         aptos_logger::info!("Lowest version amongst all processors is {}", lowest);
@@ -226,9 +234,84 @@ impl Tailer {
             tasks.push(task);
         }
         let results = await_tasks(tasks).await;
+        self.checkpoint_processors(&results);
         results
     }
 
+    /// Every version in a batch has either been processed or dead lettered once the batch
+    /// completes, so each processor can safely resume after the highest version it saw
+    fn checkpoint_processors(
+        &self,
+        results: &[anyhow::Result<Vec<Result<ProcessingResult, TransactionProcessingError>>>],
+    ) {
+        for processor in &self.processors {
+            let max_version = results
+                .iter()
+                .filter_map(|res| res.as_ref().ok())
+                .flatten()
+                .filter_map(|res| match res {
+                    Ok(processing_result) if processing_result.name == processor.name() => {
+                        Some(processing_result.version)
+                    }
+                    Err(tpe) if tpe.inner().2 == processor.name() => Some(tpe.inner().1),
+                    _ => None,
+                })
+                .max();
+            if let Some(max_version) = max_version {
+                processor.update_checkpoint(max_version + 1);
+            }
+        }
+    }
+
+    /// Processes the versions in `[start_version, end_version)` by splitting the range into
+    /// `num_workers` chunks, each fetched and processed concurrently by its own fetcher.
+    /// Checkpoints aren't updated, as the range is processed out of order: this is meant for
+    /// backfilling, and tailing afterwards will resume from each processor's own checkpoint.
+    /// Returns the number of versions processed.
+    pub async fn process_version_range(
+        &self,
+        start_version: u64,
+        end_version: u64,
+        num_workers: u64,
+    ) -> u64 {
+        if end_version <= start_version {
+            return 0;
+        }
+        let num_workers = num_workers.max(1);
+        let chunk_size = ((end_version - start_version) + num_workers - 1) / num_workers;
+        let mut tasks = vec![];
+        let mut chunk_start = start_version;
+        while chunk_start < end_version {
+            let chunk_end = (chunk_start + chunk_size).min(end_version);
+            let mut worker = self.clone();
+            worker.transaction_fetcher = Arc::new(Mutex::new(TransactionFetcher::new(
+                self.node_url.clone(),
+                Some(chunk_start),
+            )));
+            let task = tokio::task::spawn(async move {
+                info!(
+                    "Range worker processing versions [{}, {})",
+                    chunk_start, chunk_end
+                );
+                for _ in chunk_start..chunk_end {
+                    let txn = worker.get_next_txn().await;
+                    worker
+                        .process_transaction(txn)
+                        .await
+                        .expect("Processing a transaction never fails");
+                }
+                info!(
+                    "Range worker finished versions [{}, {})",
+                    chunk_start, chunk_end
+                );
+                chunk_end - chunk_start
+            });
+            tasks.push(task);
+            chunk_start = chunk_end;
+        }
+        await_tasks(tasks).await.into_iter().sum()
+    }
+
     pub async fn process_transaction(
         &self,
         txn: Arc<Transaction>,
@@ -280,10 +363,15 @@ pub async fn await_tasks<T: Debug>(tasks: Vec<JoinHandle<T>>) -> Vec<T> {
 mod test {
     use super::*;
     use crate::{
+        coin_processor::CoinTransactionProcessor,
         database::{new_db_pool, PgPoolConnection},
         default_processor::DefaultTransactionProcessor,
         models::transactions::TransactionModel,
-        token_processor::TokenTransactionProcessor,
+        schema::{
+            coin_activities, coin_balances, current_coin_balances, ownerships, token_activities,
+            token_datas,
+        },
+        token_processor::{refresh_current_ownership_views, TokenTransactionProcessor},
     };
     use aptos_rest_client::State;
     use diesel::Connection;
@@ -333,7 +421,14 @@ mod test {
     }
 
     pub fn wipe_database(conn: &PgPoolConnection) {
+        for view in ["current_collection_ownerships", "current_token_ownerships"] {
+            conn.execute(&format!("DROP MATERIALIZED VIEW IF EXISTS {}", view))
+                .unwrap();
+        }
         for table in [
+            "coin_activities",
+            "coin_balances",
+            "current_coin_balances",
             "metadatas",
             "token_activities",
             "token_datas",
@@ -346,6 +441,8 @@ mod test {
             "block_metadata_transactions",
             "transactions",
             "processor_statuses",
+            "processor_checkpoints",
+            "dead_letter_transactions",
             "ledger_infos",
             "__diesel_schema_migrations",
         ] {
@@ -868,4 +965,465 @@ mod test {
         tailer.set_fetcher_version(4).await;
         assert!(tailer.check_or_update_chain_id().await.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_checkpoints_and_dead_letters() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let (_conn_pool, tailer) = setup_indexer().unwrap();
+        let processor = tailer.processors[0].clone();
+        assert_eq!(processor.get_checkpoint(), None);
+
+        processor.update_checkpoint(5);
+        processor.update_checkpoint(10);
+        assert_eq!(processor.get_checkpoint(), Some(10));
+        // The other processor has no checkpoint or status, so it starts from scratch
+        assert_eq!(tailer.set_fetcher_to_lowest_processor_version().await, 0);
+        tailer.processors[1].update_checkpoint(20);
+        assert_eq!(tailer.set_fetcher_to_lowest_processor_version().await, 10);
+
+        let tpe = TransactionProcessingError::TransactionCommitError((
+            anyhow::anyhow!("boom"),
+            7,
+            processor.name(),
+        ));
+        processor.dead_letter(&tpe, 3);
+        processor.dead_letter(&tpe, 4);
+        assert_eq!(processor.get_dead_letter_versions(), vec![7]);
+        assert!(tailer.processors[1].get_dead_letter_versions().is_empty());
+
+        processor.remove_dead_letter(7);
+        assert!(processor.get_dead_letter_versions().is_empty());
+    }
+
+    fn token_event(
+        creation_number: &str,
+        address: &str,
+        sequence_number: u64,
+        event_type: &str,
+        data: Value,
+    ) -> Value {
+        json!({
+            "key": format!("0x{}{:0>64}", creation_number, address),
+            "sequence_number": sequence_number.to_string(),
+            "type": format!("0x3::token::{}", event_type),
+            "data": data,
+        })
+    }
+
+    fn token_user_txn(version: u64, hash: &str, sender: &str, events: Vec<Value>) -> Transaction {
+        serde_json::from_value(json!(
+            {
+              "type": "user_transaction",
+              "version": version.to_string(),
+              "hash": hash,
+              "state_root_hash": "0xebfe1eb7aa5321e7a7d741d927487163c34c821eaab60646ae0efd02b286c97c",
+              "event_root_hash": "0x414343554d554c41544f525f504c414345484f4c4445525f4841534800000000",
+              "gas_used": "43",
+              "success": true,
+              "vm_status": "Executed successfully",
+              "accumulator_root_hash": "0x97bfd5949d32f6c9a9efad93411924bfda658a8829de384d531ee73c2f740971",
+              "sender": sender,
+              "sequence_number": "0",
+              "max_gas_amount": "1000",
+              "gas_unit_price": "1",
+              "expiration_timestamp_secs": "1649713172",
+              "payload": {
+                "type": "entry_function_payload",
+                "function": "0x3::token::direct_transfer_script",
+                "type_arguments": [],
+                "arguments": []
+              },
+              "signature": {
+                "type": "ed25519_signature",
+                "public_key": "0x14ff6646855dad4a2dab30db773cdd4b22d6f9e6813f3e50142adf4f3efcf9f8",
+                "signature": "0x70781112e78cc8b54b86805c016cef2478bccdef21b721542af0323276ab906c989172adffed5bf2f475f2ec3a5b284a0ac46a6aef0d79f0dbb6b85bfca0080a"
+              },
+              "events": events,
+              "timestamp": "1649713141723410",
+              "changes": []
+            }
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_token_ownerships() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let (conn_pool, tailer) = setup_indexer().unwrap();
+        let conn = conn_pool.get().unwrap();
+        let token_data_id = json!({"creator": "0xcafe", "collection": "Cats", "name": "Tabby"});
+        let token_id = json!({"token_data_id": token_data_id, "property_version": "0"});
+
+        let mint_txn = token_user_txn(
+            100,
+            "0x0000000000000000000000000000000000000000000000000000000000000100",
+            "0xcafe",
+            vec![
+                token_event(
+                    "0000000000000000",
+                    "cafe",
+                    0,
+                    "CreateCollectionEvent",
+                    json!({
+                        "creator": "0xcafe",
+                        "collection_name": "Cats",
+                        "uri": "https://aptos.dev/cats",
+                        "description": "Some cats",
+                        "maximum": "0",
+                    }),
+                ),
+                token_event(
+                    "0100000000000000",
+                    "cafe",
+                    0,
+                    "CreateTokenDataEvent",
+                    json!({
+                        "id": token_data_id,
+                        "description": "A tabby cat",
+                        "maximum": "10",
+                        "uri": "https://aptos.dev/cats/tabby",
+                        "royalty_payee_address": "0xcafe",
+                        "royalty_points_denominator": "100",
+                        "royalty_points_numerator": "1",
+                        "name": "Tabby",
+                        "mutability_config": {},
+                        "property_keys": [],
+                        "property_values": [],
+                        "property_types": [],
+                    }),
+                ),
+                token_event(
+                    "0200000000000000",
+                    "cafe",
+                    0,
+                    "MintTokenEvent",
+                    json!({
+                        "id": token_data_id,
+                        "amount": "2",
+                    }),
+                ),
+                token_event(
+                    "0300000000000000",
+                    "cafe",
+                    0,
+                    "DepositEvent",
+                    json!({
+                        "id": token_id,
+                        "amount": "2",
+                    }),
+                ),
+            ],
+        );
+        let transfer_txn = token_user_txn(
+            101,
+            "0x0000000000000000000000000000000000000000000000000000000000000101",
+            "0xcafe",
+            vec![
+                token_event(
+                    "0400000000000000",
+                    "cafe",
+                    0,
+                    "WithdrawEvent",
+                    json!({
+                        "id": token_id,
+                        "amount": "1",
+                    }),
+                ),
+                // Deposits are attributed to the receiver, not the sender
+                token_event(
+                    "0300000000000000",
+                    "beef",
+                    0,
+                    "DepositEvent",
+                    json!({
+                        "id": token_id,
+                        "amount": "1",
+                    }),
+                ),
+            ],
+        );
+        let burn_txn = token_user_txn(
+            102,
+            "0x0000000000000000000000000000000000000000000000000000000000000102",
+            "0xcafe",
+            vec![
+                token_event(
+                    "0400000000000000",
+                    "cafe",
+                    1,
+                    "WithdrawEvent",
+                    json!({
+                        "id": token_id,
+                        "amount": "1",
+                    }),
+                ),
+                token_event(
+                    "0500000000000000",
+                    "cafe",
+                    0,
+                    "BurnTokenEvent",
+                    json!({
+                        "id": token_id,
+                        "amount": "1",
+                    }),
+                ),
+            ],
+        );
+        for txn in [mint_txn, transfer_txn.clone(), burn_txn, transfer_txn] {
+            // Reprocessing a transaction must not apply its activities twice
+            tailer.process_transaction(Arc::new(txn)).await.unwrap();
+        }
+
+        let token_id = "0xcafe::Cats::Tabby::0";
+        let amount_owned_by = |owner: &str| {
+            ownerships::table
+                .select(ownerships::amount)
+                .filter(ownerships::ownership_id.eq(format!("{}::{}", token_id, owner)))
+                .first::<bigdecimal::BigDecimal>(&conn)
+                .unwrap()
+        };
+        assert_eq!(amount_owned_by("0xcafe"), bigdecimal::BigDecimal::from(0));
+        assert_eq!(amount_owned_by("0xbeef"), bigdecimal::BigDecimal::from(1));
+
+        let supply = token_datas::table
+            .find("0xcafe::Cats::Tabby")
+            .select(token_datas::supply)
+            .first::<bigdecimal::BigDecimal>(&conn)
+            .unwrap();
+        assert_eq!(supply, bigdecimal::BigDecimal::from(1));
+
+        let activities = token_activities::table
+            .select(token_activities::account)
+            .filter(token_activities::token_id.eq(token_id))
+            .load::<String>(&conn)
+            .unwrap();
+        assert_eq!(activities.len(), 5);
+        assert_eq!(activities.iter().filter(|a| *a == "0xbeef").count(), 1);
+
+        refresh_current_ownership_views(&conn).unwrap();
+        let owners = diesel::dsl::sql::<diesel::sql_types::Text>(
+            "SELECT owner FROM current_token_ownerships WHERE collection = 'Cats'",
+        )
+        .load::<String>(&conn)
+        .unwrap();
+        assert_eq!(owners, vec!["0xbeef".to_string()]);
+    }
+
+    fn coin_event(creation_number: &str, address: &str, event_type: &str, amount: u64) -> Value {
+        json!({
+            "key": format!("0x{}{:0>64}", creation_number, address),
+            "sequence_number": "0",
+            "type": format!("0x1::coin::{}", event_type),
+            "data": {"amount": amount.to_string()},
+        })
+    }
+
+    fn coin_store_change(address: &str, value: u64) -> Value {
+        let address = format!("0x{:0>64}", address);
+        json!({
+            "type": "write_resource",
+            "address": address,
+            "state_key_hash": "0x5d4d5e8b8a9bdc2e1c0ea4c3cb1e3d5b1e41f3d9fdbc8cd6f7c6a1a2b3c4d5e6",
+            "data": {
+                "type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                "data": {
+                    "coin": {"value": value.to_string()},
+                    "frozen": false,
+                    "deposit_events": {
+                        "counter": "1",
+                        "guid": {"id": {"addr": address, "creation_num": "2"}}
+                    },
+                    "withdraw_events": {
+                        "counter": "1",
+                        "guid": {"id": {"addr": address, "creation_num": "3"}}
+                    }
+                }
+            }
+        })
+    }
+
+    fn coin_user_txn(version: u64, events: Vec<Value>, changes: Vec<Value>) -> Transaction {
+        let hash = format!("0x{:0>64x}", version);
+        let mut txn =
+            serde_json::to_value(token_user_txn(version, &hash, "0xcafe", events)).unwrap();
+        txn["changes"] = json!(changes);
+        serde_json::from_value(txn).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_coin_balances() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let (conn_pool, mut tailer) = setup_indexer().unwrap();
+        tailer.add_processor(Arc::new(CoinTransactionProcessor::new(conn_pool.clone())));
+        let conn = conn_pool.get().unwrap();
+
+        let earlier_txn = coin_user_txn(
+            99,
+            vec![coin_event("0200000000000000", "beef", "DepositEvent", 5)],
+            vec![coin_store_change("beef", 5)],
+        );
+        let transfer_txn = coin_user_txn(
+            100,
+            vec![
+                coin_event("0300000000000000", "cafe", "WithdrawEvent", 10),
+                coin_event("0200000000000000", "beef", "DepositEvent", 10),
+            ],
+            vec![
+                coin_store_change("cafe", 947),
+                coin_store_change("beef", 15),
+            ],
+        );
+        // Reprocessing a transaction, or processing an earlier one afterwards as during a
+        // backfill, must not roll back the current balances
+        for txn in [transfer_txn.clone(), earlier_txn, transfer_txn] {
+            tailer.process_transaction(Arc::new(txn)).await.unwrap();
+        }
+
+        let current_balance = |owner: &str| {
+            current_coin_balances::table
+                .find((owner, "0x1::aptos_coin::AptosCoin"))
+                .select((
+                    current_coin_balances::amount,
+                    current_coin_balances::last_transaction_version,
+                ))
+                .first::<(bigdecimal::BigDecimal, bigdecimal::BigDecimal)>(&conn)
+                .unwrap()
+        };
+        assert_eq!(
+            current_balance("0xcafe"),
+            (
+                bigdecimal::BigDecimal::from(947),
+                bigdecimal::BigDecimal::from(100)
+            )
+        );
+        assert_eq!(
+            current_balance("0xbeef"),
+            (
+                bigdecimal::BigDecimal::from(15),
+                bigdecimal::BigDecimal::from(100)
+            )
+        );
+
+        let history = coin_balances::table
+            .select(coin_balances::amount)
+            .filter(coin_balances::owner_address.eq("0xbeef"))
+            .order(coin_balances::transaction_version)
+            .load::<bigdecimal::BigDecimal>(&conn)
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                bigdecimal::BigDecimal::from(5),
+                bigdecimal::BigDecimal::from(15)
+            ]
+        );
+
+        let activities = coin_activities::table
+            .select((
+                coin_activities::owner_address,
+                coin_activities::activity_type,
+                coin_activities::amount,
+            ))
+            .filter(coin_activities::transaction_version.eq(bigdecimal::BigDecimal::from(100)))
+            .order(coin_activities::event_index)
+            .load::<(String, String, bigdecimal::BigDecimal)>(&conn)
+            .unwrap();
+        assert_eq!(
+            activities,
+            vec![
+                (
+                    "0xcafe".to_string(),
+                    "0x1::coin::WithdrawEvent".to_string(),
+                    bigdecimal::BigDecimal::from(10)
+                ),
+                (
+                    "0xbeef".to_string(),
+                    "0x1::coin::DepositEvent".to_string(),
+                    bigdecimal::BigDecimal::from(10)
+                ),
+                // 43 gas used at a gas unit price of 1
+                (
+                    "0xcafe".to_string(),
+                    "0x1::aptos_coin::GasFeeEvent".to_string(),
+                    bigdecimal::BigDecimal::from(43)
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "graphql")]
+    #[tokio::test]
+    async fn test_graphql_queries() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let (conn_pool, mut tailer) = setup_indexer().unwrap();
+        tailer.add_processor(Arc::new(CoinTransactionProcessor::new(conn_pool.clone())));
+        let transfer_txn = coin_user_txn(
+            100,
+            vec![
+                coin_event("0300000000000000", "cafe", "WithdrawEvent", 10),
+                coin_event("0200000000000000", "beef", "DepositEvent", 10),
+            ],
+            vec![
+                coin_store_change("cafe", 947),
+                coin_store_change("beef", 10),
+            ],
+        );
+        tailer
+            .process_transaction(Arc::new(transfer_txn))
+            .await
+            .unwrap();
+
+        let schema = crate::graphql::build_schema(conn_pool);
+        let response = schema
+            .execute(
+                r#"{
+                    account(address: "0x000000000000000000000000000000000000000000000000000000000000cafe") {
+                        address
+                        coinBalances { coinType amount lastTransactionVersion }
+                        coinActivities(limit: 1) { activityType amount }
+                        transactions { version events { type } userTransaction { sender } }
+                    }
+                }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({
+                "account": {
+                    "address": "0xcafe",
+                    "coinBalances": [{
+                        "coinType": "0x1::aptos_coin::AptosCoin",
+                        "amount": "947",
+                        "lastTransactionVersion": "100",
+                    }],
+                    "coinActivities": [{
+                        "activityType": "0x1::coin::WithdrawEvent",
+                        "amount": "10",
+                    }],
+                    "transactions": [{
+                        "version": "100",
+                        "events": [
+                            {"type": "0x1::coin::DepositEvent"},
+                            {"type": "0x1::coin::WithdrawEvent"},
+                        ],
+                        "userTransaction": {"sender": "0xcafe"},
+                    }],
+                }
+            })
+        );
+
+        let response = schema
+            .execute(r#"{ transactions(limit: -1) { version } }"#)
+            .await;
+        assert_eq!(response.errors.len(), 1);
+    }
 }
@@ -1,24 +1,39 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::util::bigdecimal_to_u64;
+use crate::util::{bigdecimal_to_u64, u64_to_bigdecimal};
 use crate::{
     counters::{
-        GOT_CONNECTION, PROCESSOR_ERRORS, PROCESSOR_INVOCATIONS, PROCESSOR_SUCCESSES,
-        UNABLE_TO_GET_CONNECTION,
+        GOT_CONNECTION, PROCESSOR_DEAD_LETTERS, PROCESSOR_ERRORS, PROCESSOR_INVOCATIONS,
+        PROCESSOR_RETRIES, PROCESSOR_SUCCESSES, UNABLE_TO_GET_CONNECTION,
     },
     database::{execute_with_better_error, PgDbPool, PgPoolConnection},
-    indexer::{errors::TransactionProcessingError, processing_result::ProcessingResult},
-    models::processor_statuses::ProcessorStatusModel,
+    indexer::{
+        errors::TransactionProcessingError, processing_result::ProcessingResult,
+        retry_policy::RetryPolicy,
+    },
+    models::{
+        dead_letter_transactions::DeadLetterTransaction,
+        processor_checkpoints::ProcessorCheckpoint, processor_statuses::ProcessorStatusModel,
+    },
     schema,
 };
 use aptos_rest_client::Transaction;
 use async_trait::async_trait;
 use diesel::{prelude::*, RunQueryDsl};
-use schema::processor_statuses::{self, dsl};
+use schema::{
+    dead_letter_transactions, processor_checkpoints,
+    processor_statuses::{self, dsl},
+};
 use std::{fmt::Debug, sync::Arc};
 
-/// The `TransactionProcessor` is used by an instance of a `Tailer` to process transactions
+/// The `TransactionProcessor` is used by an instance of a `Tailer` to process transactions.
+///
+/// This is the extension point for custom processors: implement `name`, `process_transaction`
+/// and `connection_pool`, and register the processor with `Tailer::add_processor`. The `Tailer`
+/// takes care of fetching transactions, retrying failures according to `retry_policy`, dead
+/// lettering transactions that keep failing, and checkpointing progress so the processor
+/// resumes where it left off.
 #[async_trait]
 pub trait TransactionProcessor: Send + Sync + Debug {
     /// name of the processor, for status logging
@@ -39,6 +54,11 @@ pub trait TransactionProcessor: Send + Sync + Debug {
 
     //* Below are helper methods that don't need to be implemented *//
 
+    /// How often to retry a transaction which failed to process before dead lettering it
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::default()
+    }
+
     /// Gets the connection.
     /// If it was unable to do so (default timeout: 30s), it will keep retrying until it can.
     fn get_conn(&self) -> PgPoolConnection {
@@ -62,6 +82,7 @@ pub trait TransactionProcessor: Send + Sync + Debug {
     }
 
     /// This is a helper method, tying together the other helper methods to allow tracking status in the DB
+    /// Failures are retried according to the `retry_policy`, and dead lettered once it is exhausted
     async fn process_transaction_with_status(
         &self,
         transaction: Arc<Transaction>,
@@ -71,13 +92,33 @@ pub trait TransactionProcessor: Send + Sync + Debug {
             .inc();
 
         self.mark_version_started(transaction.version().unwrap());
-        let res = self.process_transaction(transaction).await;
-        // Handle version success/failure
-        match res.as_ref() {
-            Ok(processing_result) => self.update_status_success(processing_result),
-            Err(tpe) => self.update_status_err(tpe),
-        };
-        res
+        let retry_policy = self.retry_policy();
+        let mut attempt = 1;
+        loop {
+            let res = self.process_transaction(transaction.clone()).await;
+            // Handle version success/failure
+            match res.as_ref() {
+                Ok(processing_result) => self.update_status_success(processing_result),
+                Err(tpe) if attempt < retry_policy.max_attempts => {
+                    aptos_logger::warn!(
+                        "[{}] Attempt {} of {} failed, retrying: {:?}",
+                        self.name(),
+                        attempt,
+                        retry_policy.max_attempts,
+                        tpe
+                    );
+                    PROCESSOR_RETRIES.with_label_values(&[self.name()]).inc();
+                    tokio::time::sleep(retry_policy.backoff(attempt)).await;
+                    attempt += 1;
+                    continue;
+                }
+                Err(tpe) => {
+                    self.update_status_err(tpe);
+                    self.dead_letter(tpe, attempt);
+                }
+            };
+            return res;
+        }
     }
 
     /// Writes that a version has been started for this `TransactionProcessor` to the DB
@@ -115,6 +156,94 @@ pub trait TransactionProcessor: Send + Sync + Debug {
         self.apply_processor_status(&psm);
     }
 
+    /// Records that this `TransactionProcessor` gave up on a version after `attempts` attempts
+    fn dead_letter(&self, tpe: &TransactionProcessingError, attempts: u32) {
+        let (_, version, _) = tpe.inner();
+        aptos_logger::error!(
+            "[{}] Dead lettering version {} after {} attempts: {:?}",
+            self.name(),
+            version,
+            attempts,
+            tpe
+        );
+        PROCESSOR_DEAD_LETTERS
+            .with_label_values(&[self.name()])
+            .inc();
+        let dead_letter = DeadLetterTransaction::from_transaction_processing_err(tpe, attempts);
+        let conn = self.get_conn();
+        execute_with_better_error(
+            &conn,
+            diesel::insert_into(dead_letter_transactions::table)
+                .values(&dead_letter)
+                .on_conflict((
+                    dead_letter_transactions::name,
+                    dead_letter_transactions::version,
+                ))
+                .do_update()
+                .set(&dead_letter),
+        )
+        .expect("Error dead lettering version!");
+    }
+
+    /// Removes a dead lettered version for this `TransactionProcessor`, once it has been processed
+    fn remove_dead_letter(&self, version: u64) {
+        let conn = self.get_conn();
+        diesel::delete(
+            dead_letter_transactions::table
+                .filter(dead_letter_transactions::name.eq(self.name()))
+                .filter(dead_letter_transactions::version.eq(u64_to_bigdecimal(version))),
+        )
+        .execute(&conn)
+        .expect("Error removing dead lettered version!");
+    }
+
+    /// Gets all dead lettered versions for this `TransactionProcessor` from the DB
+    fn get_dead_letter_versions(&self) -> Vec<u64> {
+        let conn = self.get_conn();
+
+        dead_letter_transactions::table
+            .select(dead_letter_transactions::version)
+            .filter(dead_letter_transactions::name.eq(self.name()))
+            .load::<bigdecimal::BigDecimal>(&conn)
+            .expect("Error loading the dead lettered versions query")
+            .iter()
+            .map(bigdecimal_to_u64)
+            .collect()
+    }
+
+    /// Records that every version below `next_version` has been handled by this `TransactionProcessor`
+    fn update_checkpoint(&self, next_version: u64) {
+        aptos_logger::debug!(
+            "[{}] Checkpointing at version: {}",
+            self.name(),
+            next_version
+        );
+        let checkpoint = ProcessorCheckpoint::new(self.name(), next_version);
+        let conn = self.get_conn();
+        execute_with_better_error(
+            &conn,
+            diesel::insert_into(processor_checkpoints::table)
+                .values(&checkpoint)
+                .on_conflict(processor_checkpoints::name)
+                .do_update()
+                .set(&checkpoint),
+        )
+        .expect("Error updating Processor Checkpoint!");
+    }
+
+    /// Gets the version this `TransactionProcessor` should resume from, if it has checkpointed before
+    fn get_checkpoint(&self) -> Option<u64> {
+        let conn = self.get_conn();
+
+        processor_checkpoints::table
+            .select(processor_checkpoints::next_version)
+            .filter(processor_checkpoints::name.eq(self.name()))
+            .first::<bigdecimal::BigDecimal>(&conn)
+            .optional()
+            .expect("Error loading the checkpoint query")
+            .map(|v| bigdecimal_to_u64(&v))
+    }
+
     /// Actually performs the write for a `ProcessorStatusModel` changeset
     fn apply_processor_status(&self, psm: &ProcessorStatusModel) {
         let conn = self.get_conn();
@@ -9,10 +9,11 @@
 
 use aptos_logger::info;
 use clap::Parser;
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 use aptos_indexer::{
-    database::new_db_pool, default_processor::DefaultTransactionProcessor, indexer::tailer::Tailer,
+    coin_processor::CoinTransactionProcessor, database::new_db_pool,
+    default_processor::DefaultTransactionProcessor, indexer::tailer::Tailer,
     token_processor::TokenTransactionProcessor,
 };
 
@@ -44,6 +45,16 @@ struct IndexerArgs {
     #[clap(long)]
     start_from_version: Option<u64>,
 
+    /// If set, will process the versions from the starting version up to (but excluding) this
+    /// version, split across `--range-workers` workers, and then exit instead of tailing.
+    /// Useful for backfilling a range of versions after adding a new processor.
+    #[clap(long)]
+    end_version: Option<u64>,
+
+    /// How many workers to split the range into when `--end-version` is set
+    #[clap(long, default_value_t = 4)]
+    range_workers: u64,
+
     /// How many versions to fetch and process from a node in parallel
     #[clap(long, default_value_t = 10)]
     batch_size: u8,
@@ -62,12 +73,26 @@ struct IndexerArgs {
     /// turn on the token URI fetcher
     #[clap(long)]
     index_token_uri_data: bool,
+
+    /// Turn on the indexer to collect coin activities and balance history, and store them in the
+    /// postgres DB tables.
+    #[clap(long)]
+    index_coin_data: bool,
+
+    /// If set, serves a GraphQL API over the indexed tables on this address, ex: "0.0.0.0:8090"
+    #[clap(long)]
+    graphql_address: Option<SocketAddr>,
 }
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     aptos_logger::Logger::new().init();
     let args: IndexerArgs = IndexerArgs::parse();
+    #[cfg(not(feature = "graphql"))]
+    assert!(
+        args.graphql_address.is_none(),
+        "--graphql-address requires the indexer to be built with the `graphql` feature"
+    );
 
     info!("Starting indexer...");
 
@@ -88,6 +113,10 @@ async fn main() -> std::io::Result<()> {
             TokenTransactionProcessor::new(conn_pool.clone(), args.index_token_uri_data);
         tailer.add_processor(Arc::new(token_transaction_processor));
     }
+    if args.index_coin_data {
+        let coin_transaction_processor = CoinTransactionProcessor::new(conn_pool.clone());
+        tailer.add_processor(Arc::new(coin_transaction_processor));
+    }
 
     let starting_version = match args.start_from_version {
         None => tailer.set_fetcher_to_lowest_processor_version().await,
@@ -103,6 +132,26 @@ async fn main() -> std::io::Result<()> {
         return Ok(());
     }
 
+    if let Some(end_version) = args.end_version {
+        info!(
+            "Processing versions [{}, {}) with {} workers",
+            starting_version, end_version, args.range_workers
+        );
+        let processed = tailer
+            .process_version_range(starting_version, end_version, args.range_workers)
+            .await;
+        info!("Processed {} versions, exiting!", processed);
+        return Ok(());
+    }
+
+    #[cfg(feature = "graphql")]
+    if let Some(graphql_address) = args.graphql_address {
+        tokio::spawn(aptos_indexer::graphql::serve(
+            conn_pool.clone(),
+            graphql_address,
+        ));
+    }
+
     info!("Indexing loop started!");
     let mut processed: usize = starting_version as usize;
     let mut base: usize = 0;
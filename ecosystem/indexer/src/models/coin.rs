@@ -0,0 +1,275 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::extra_unused_lifetimes)]
+use crate::{
+    models::{events::EventModel, write_set_changes::WriteSetChangeModel},
+    schema::{coin_activities, coin_balances},
+    util::u64_to_bigdecimal,
+};
+use aptos_rest_client::aptos_api_types::Address;
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+
+const COIN_STORE_TYPE_PREFIX: &str = "0x1::coin::CoinStore<";
+pub const DEPOSIT_EVENT_TYPE: &str = "0x1::coin::DepositEvent";
+pub const WITHDRAW_EVENT_TYPE: &str = "0x1::coin::WithdrawEvent";
+/// Gas isn't withdrawn through an event, so gas fees get an activity type of their own
+pub const GAS_FEE_EVENT_TYPE: &str = "0x1::aptos_coin::GasFeeEvent";
+pub const APTOS_COIN_TYPE: &str = "0x1::aptos_coin::AptosCoin";
+
+/// A `0x1::coin::CoinStore<T>` resource written by a transaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoinStore {
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: u64,
+    /// The (account address, creation number) of the store's deposit event handle
+    pub deposit_events: (String, u64),
+    /// The (account address, creation number) of the store's withdraw event handle
+    pub withdraw_events: (String, u64),
+}
+
+impl CoinStore {
+    pub fn from_write_set_change(write_set_change: &WriteSetChangeModel) -> Option<Self> {
+        if write_set_change.type_ != "write_resource" {
+            return None;
+        }
+        let coin_type = write_set_change
+            .data
+            .get("type")?
+            .as_str()?
+            .strip_prefix(COIN_STORE_TYPE_PREFIX)?
+            .strip_suffix('>')?;
+        let data = write_set_change.data.get("data")?;
+        Some(Self {
+            owner_address: normalize_address(&write_set_change.address)?,
+            coin_type: coin_type.to_string(),
+            amount: parse_u64(data.get("coin")?.get("value")?)?,
+            deposit_events: parse_event_handle(data.get("deposit_events")?)?,
+            withdraw_events: parse_event_handle(data.get("withdraw_events")?)?,
+        })
+    }
+
+    /// Returns the activity type of `event` if it was emitted by this store
+    pub fn activity_type_of(&self, event: &EventModel) -> Option<&'static str> {
+        let handle = (event.account_address()?, event.creation_number()?);
+        if event.type_ == DEPOSIT_EVENT_TYPE && handle == self.deposit_events {
+            Some(DEPOSIT_EVENT_TYPE)
+        } else if event.type_ == WITHDRAW_EVENT_TYPE && handle == self.withdraw_events {
+            Some(WITHDRAW_EVENT_TYPE)
+        } else {
+            None
+        }
+    }
+}
+
+fn normalize_address(address: &str) -> Option<String> {
+    Address::from_str(address)
+        .ok()
+        .map(|address| address.to_string())
+}
+
+fn parse_u64(value: &Value) -> Option<u64> {
+    value.as_str()?.parse().ok()
+}
+
+fn parse_event_handle(handle: &Value) -> Option<(String, u64)> {
+    let id = handle.get("guid")?.get("id")?;
+    Some((
+        normalize_address(id.get("addr")?.as_str()?)?,
+        parse_u64(id.get("creation_num")?)?,
+    ))
+}
+
+/// A deposit into or withdrawal from a coin store, or the gas fee paid by a user transaction
+#[derive(Debug, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "coin_activities")]
+pub struct CoinActivity {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub event_index: i64,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub activity_type: String,
+    pub is_gas_fee: bool,
+    pub is_transaction_success: bool,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+impl CoinActivity {
+    /// Returns the coin activity for `event`, if it was emitted by one of `coin_stores`
+    pub fn from_event(
+        event: &EventModel,
+        event_index: i64,
+        coin_stores: &[CoinStore],
+        transaction_version: u64,
+        is_transaction_success: bool,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Option<Self> {
+        let (coin_store, activity_type) = coin_stores.iter().find_map(|coin_store| {
+            coin_store
+                .activity_type_of(event)
+                .map(|activity_type| (coin_store, activity_type))
+        })?;
+        Some(Self {
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            event_index,
+            owner_address: coin_store.owner_address.clone(),
+            coin_type: coin_store.coin_type.clone(),
+            amount: u64_to_bigdecimal(parse_u64(event.data.get("amount")?)?),
+            activity_type: activity_type.to_string(),
+            is_gas_fee: false,
+            is_transaction_success,
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        })
+    }
+
+    /// The gas fee paid by the sender of a user transaction. It comes after all of the
+    /// transaction's events, so its `event_index` is the number of events.
+    pub fn gas_fee(
+        sender: &str,
+        amount: bigdecimal::BigDecimal,
+        num_events: usize,
+        transaction_version: u64,
+        is_transaction_success: bool,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        Self {
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            event_index: num_events as i64,
+            owner_address: normalize_address(sender).unwrap_or_else(|| sender.to_string()),
+            coin_type: APTOS_COIN_TYPE.to_string(),
+            amount,
+            activity_type: GAS_FEE_EVENT_TYPE.to_string(),
+            is_gas_fee: true,
+            is_transaction_success,
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// The balance of a coin store after a transaction changed it
+#[derive(Debug, Insertable, Queryable, Serialize, Clone)]
+#[diesel(table_name = "coin_balances")]
+pub struct CoinBalance {
+    pub transaction_version: bigdecimal::BigDecimal,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+impl CoinBalance {
+    pub fn new(
+        coin_store: &CoinStore,
+        transaction_version: u64,
+        transaction_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        Self {
+            transaction_version: u64_to_bigdecimal(transaction_version),
+            owner_address: coin_store.owner_address.clone(),
+            coin_type: coin_store.coin_type.clone(),
+            amount: u64_to_bigdecimal(coin_store.amount),
+            transaction_timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
+
+/// The latest balance of a coin store
+#[derive(Debug, Queryable, Serialize, Clone)]
+pub struct CurrentCoinBalance {
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: bigdecimal::BigDecimal,
+    pub last_transaction_version: bigdecimal::BigDecimal,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn coin_store_change(address: &str, coin_type: &str, value: &str) -> WriteSetChangeModel {
+        WriteSetChangeModel {
+            transaction_hash: "0x1".to_string(),
+            hash: "0x2".to_string(),
+            type_: "write_resource".to_string(),
+            address: address.to_string(),
+            module: Default::default(),
+            resource: Default::default(),
+            data: json!({
+                "type": format!("0x1::coin::CoinStore<{}>", coin_type),
+                "data": {
+                    "coin": {"value": value},
+                    "frozen": false,
+                    "deposit_events": {
+                        "counter": "1",
+                        "guid": {"id": {"addr": address, "creation_num": "2"}}
+                    },
+                    "withdraw_events": {
+                        "counter": "0",
+                        "guid": {"id": {"addr": address, "creation_num": "3"}}
+                    }
+                }
+            }),
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn test_coin_store_from_write_set_change() {
+        let coin_store =
+            CoinStore::from_write_set_change(&coin_store_change("0xcafe", APTOS_COIN_TYPE, "100"))
+                .unwrap();
+        assert_eq!(
+            coin_store,
+            CoinStore {
+                owner_address: "0xcafe".to_string(),
+                coin_type: APTOS_COIN_TYPE.to_string(),
+                amount: 100,
+                deposit_events: ("0xcafe".to_string(), 2),
+                withdraw_events: ("0xcafe".to_string(), 3),
+            }
+        );
+
+        let mut other_resource = coin_store_change("0xcafe", APTOS_COIN_TYPE, "100");
+        other_resource.data["type"] = json!("0x1::account::Account");
+        assert_eq!(CoinStore::from_write_set_change(&other_resource), None);
+    }
+
+    #[test]
+    fn test_activity_type_of() {
+        let coin_store =
+            CoinStore::from_write_set_change(&coin_store_change("0xcafe", APTOS_COIN_TYPE, "100"))
+                .unwrap();
+        let event = |creation_number: &str, type_: &str| EventModel {
+            transaction_hash: "0x1".to_string(),
+            key: format!("0x{}{:0>64}", creation_number, "cafe"),
+            sequence_number: u64_to_bigdecimal(0),
+            type_: type_.to_string(),
+            data: json!({"amount": "10"}),
+            inserted_at: chrono::Utc::now().naive_utc(),
+        };
+        assert_eq!(
+            coin_store.activity_type_of(&event("0200000000000000", DEPOSIT_EVENT_TYPE)),
+            Some(DEPOSIT_EVENT_TYPE)
+        );
+        assert_eq!(
+            coin_store.activity_type_of(&event("0300000000000000", WITHDRAW_EVENT_TYPE)),
+            Some(WITHDRAW_EVENT_TYPE)
+        );
+        // Emitted by another event handle of the same account
+        assert_eq!(
+            coin_store.activity_type_of(&event("0400000000000000", DEPOSIT_EVENT_TYPE)),
+            None
+        );
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::extra_unused_lifetimes)]
+use crate::{indexer::errors::TransactionProcessingError, schema::dead_letter_transactions};
+use bigdecimal::FromPrimitive;
+
+/// A version a processor gave up on after exhausting its retries. Dead lettered
+/// versions don't hold back the processor's checkpoint, and are retried on the
+/// next startup together with the other errored versions.
+#[derive(AsChangeset, Debug, Insertable, Queryable)]
+#[diesel(table_name = "dead_letter_transactions")]
+pub struct DeadLetterTransaction {
+    pub name: &'static str,
+    pub version: bigdecimal::BigDecimal,
+    pub attempts: i32,
+    pub error: String,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
+impl DeadLetterTransaction {
+    pub fn from_transaction_processing_err(
+        tpe: &TransactionProcessingError,
+        attempts: u32,
+    ) -> Self {
+        let (error, version, name) = tpe.inner();
+        Self {
+            name,
+            version: bigdecimal::BigDecimal::from_u64(*version).unwrap(),
+            attempts: attempts as i32,
+            error: format!("{:?}", error),
+            inserted_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
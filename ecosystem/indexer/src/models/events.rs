@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(clippy::extra_unused_lifetimes)]
 use crate::{models::transactions::Transaction, schema::events};
-use aptos_rest_client::aptos_api_types::Event as APIEvent;
+use aptos_rest_client::aptos_api_types::{Event as APIEvent, EventKey};
 use bigdecimal::{BigDecimal, FromPrimitive};
 use serde::Serialize;
+use std::str::FromStr;
 
 #[derive(Associations, Debug, Identifiable, Insertable, Queryable, Serialize)]
 #[diesel(table_name = "events")]
@@ -34,6 +35,20 @@ impl Event {
         }
     }
 
+    /// The address of the account which emitted the event, from its event key
+    pub fn account_address(&self) -> Option<String> {
+        EventKey::from_str(&self.key)
+            .ok()
+            .map(|key| key.0.get_creator_address().to_hex_literal())
+    }
+
+    /// The creation number of the event handle which emitted the event, from its event key
+    pub fn creation_number(&self) -> Option<u64> {
+        EventKey::from_str(&self.key)
+            .ok()
+            .map(|key| key.0.get_creation_number())
+    }
+
     pub fn from_events(transaction_hash: String, events: &[APIEvent]) -> Option<Vec<Self>> {
         if events.is_empty() {
             return None;
@@ -1,13 +1,17 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod coin;
 pub mod collection;
+pub mod dead_letter_transactions;
 pub mod events;
 pub mod ledger_info;
 pub mod metadata;
 pub mod ownership;
+pub mod processor_checkpoints;
 pub mod processor_statuses;
 pub mod token;
+pub mod token_activity;
 pub mod token_property;
 pub mod transactions;
 pub mod write_set_changes;
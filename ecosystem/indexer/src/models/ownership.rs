@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 #![allow(clippy::extra_unused_lifetimes)]
-use crate::schema::ownerships;
+use crate::{models::token::TokenId, schema::ownerships};
 use serde::Serialize;
 
 #[derive(Associations, Debug, Identifiable, Insertable, Queryable, Serialize, Clone)]
@@ -14,11 +14,13 @@ pub struct Ownership {
     pub amount: bigdecimal::BigDecimal,
     pub updated_at: chrono::NaiveDateTime,
     pub inserted_at: chrono::NaiveDateTime,
+    pub token_data_id: Option<String>,
+    pub property_version: Option<bigdecimal::BigDecimal>,
 }
 
 impl Ownership {
     pub fn new(
-        token_id: String,
+        token_id: &TokenId,
         owner: String,
         amount: bigdecimal::BigDecimal,
         updated_at: chrono::NaiveDateTime,
@@ -27,11 +29,13 @@ impl Ownership {
         let ownership_id = format!("{}::{}", token_id, owner);
         Ownership {
             ownership_id,
-            token_id,
+            token_id: token_id.to_string(),
             owner,
             amount,
             updated_at,
             inserted_at,
+            token_data_id: Some(token_id.token_data_id.to_string()),
+            property_version: Some(token_id.property_version.clone()),
         }
     }
 }
@@ -0,0 +1,25 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::extra_unused_lifetimes)]
+use crate::schema::processor_checkpoints;
+use bigdecimal::FromPrimitive;
+
+/// The version a processor should resume from: every version below
+/// `next_version` has either been processed or dead lettered.
+#[derive(AsChangeset, Debug, Insertable, Queryable)]
+#[diesel(table_name = "processor_checkpoints")]
+pub struct ProcessorCheckpoint {
+    pub name: &'static str,
+    pub next_version: bigdecimal::BigDecimal,
+    pub last_updated: chrono::NaiveDateTime,
+}
+
+impl ProcessorCheckpoint {
+    pub fn new(name: &'static str, next_version: u64) -> Self {
+        Self {
+            name,
+            next_version: bigdecimal::BigDecimal::from_u64(next_version).unwrap(),
+            last_updated: chrono::Utc::now().naive_utc(),
+        }
+    }
+}
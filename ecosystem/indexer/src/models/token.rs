@@ -135,6 +135,30 @@ pub enum TokenEvent {
 }
 
 impl TokenEvent {
+    /// The token the event is about (or its token data, for events which aren't about a specific
+    /// property version), and the amount of it which was minted, burned or moved
+    pub fn token_id_and_amount(&self) -> (Option<String>, Option<bigdecimal::BigDecimal>) {
+        match self {
+            TokenEvent::WithdrawEvent(event) => {
+                (Some(event.id.to_string()), Some(event.amount.clone()))
+            }
+            TokenEvent::DepositEvent(event) => {
+                (Some(event.id.to_string()), Some(event.amount.clone()))
+            }
+            TokenEvent::BurnTokenEvent(event) => {
+                (Some(event.id.to_string()), Some(event.amount.clone()))
+            }
+            TokenEvent::MintTokenEvent(event) => {
+                (Some(event.id.to_string()), Some(event.amount.clone()))
+            }
+            TokenEvent::CreateTokenDataEvent(event) => (Some(event.id.to_string()), None),
+            TokenEvent::MutateTokenPropertyMapEvent(event) => {
+                (Some(event.new_id.to_string()), None)
+            }
+            TokenEvent::CollectionCreationEvent(_) => (None, None),
+        }
+    }
+
     pub fn from_event(event: &Event) -> Option<TokenEvent> {
         let data = event.data.clone();
         match event.type_.as_str() {
@@ -0,0 +1,41 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+#![allow(clippy::extra_unused_lifetimes)]
+use crate::{
+    models::{events::EventModel, token::TokenEvent, transactions::UserTransaction},
+    schema::token_activities,
+};
+use serde::Serialize;
+
+/// A token event, along with the account which emitted it: the owner for deposits, withdrawals
+/// and burns, and the creator for everything else
+#[derive(Debug, Insertable, Serialize, Clone)]
+#[diesel(table_name = "token_activities")]
+pub struct TokenActivity {
+    pub event_key: String,
+    pub sequence_number: bigdecimal::BigDecimal,
+    pub account: String,
+    pub token_id: Option<String>,
+    pub event_type: Option<String>,
+    pub amount: Option<bigdecimal::BigDecimal>,
+    pub created_at: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+    pub transaction_hash: String,
+}
+
+impl TokenActivity {
+    pub fn from_event(event: &EventModel, token_event: &TokenEvent, txn: &UserTransaction) -> Self {
+        let (token_id, amount) = token_event.token_id_and_amount();
+        Self {
+            event_key: event.key.clone(),
+            sequence_number: event.sequence_number.clone(),
+            account: event.account_address().unwrap_or_default(),
+            token_id,
+            event_type: Some(event.type_.clone()),
+            amount,
+            created_at: txn.timestamp,
+            inserted_at: chrono::Utc::now().naive_utc(),
+            transaction_hash: txn.hash.clone(),
+        }
+    }
+}
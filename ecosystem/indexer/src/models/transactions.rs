@@ -252,6 +252,19 @@ impl Transaction {
                 None,
                 None,
             ),
+            APITransaction::ValidatorTransaction(tx) => (
+                Self::from_transaction_info(
+                    &tx.info,
+                    serde_json::to_value(&tx.payload).unwrap(),
+                    transaction.type_str().to_string(),
+                ),
+                None,
+                EventModel::from_events(tx.info.hash.to_string(), &tx.events),
+                WriteSetChangeModel::from_write_set_changes(
+                    tx.info.hash.to_string(),
+                    &tx.info.changes,
+                ),
+            ),
             APITransaction::PendingTransaction(..) => {
                 unreachable!()
             }
@@ -16,6 +16,32 @@ table! {
     }
 }
 
+table! {
+    coin_activities (transaction_version, event_index) {
+        transaction_version -> Numeric,
+        event_index -> Int8,
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        activity_type -> Varchar,
+        is_gas_fee -> Bool,
+        is_transaction_success -> Bool,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    coin_balances (transaction_version, owner_address, coin_type) {
+        transaction_version -> Numeric,
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     collections (collection_id) {
         collection_id -> Varchar,
@@ -29,6 +55,27 @@ table! {
     }
 }
 
+table! {
+    current_coin_balances (owner_address, coin_type) {
+        owner_address -> Varchar,
+        coin_type -> Varchar,
+        amount -> Numeric,
+        last_transaction_version -> Numeric,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+table! {
+    dead_letter_transactions (name, version) {
+        name -> Varchar,
+        version -> Numeric,
+        attempts -> Int4,
+        error -> Text,
+        inserted_at -> Timestamp,
+    }
+}
+
 table! {
     events (key, sequence_number) {
         transaction_hash -> Varchar,
@@ -72,6 +119,16 @@ table! {
         amount -> Numeric,
         updated_at -> Timestamp,
         inserted_at -> Timestamp,
+        token_data_id -> Nullable<Varchar>,
+        property_version -> Nullable<Numeric>,
+    }
+}
+
+table! {
+    processor_checkpoints (name) {
+        name -> Varchar,
+        next_version -> Numeric,
+        last_updated -> Timestamp,
     }
 }
 
@@ -181,11 +238,16 @@ table! {
 
 allow_tables_to_appear_in_same_query!(
     block_metadata_transactions,
+    coin_activities,
+    coin_balances,
     collections,
+    current_coin_balances,
+    dead_letter_transactions,
     events,
     ledger_infos,
     metadatas,
     ownerships,
+    processor_checkpoints,
     processor_statuses,
     token_activities,
     token_datas,
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::models::token::{
-    CreateCollectionEventType, CreateTokenDataEventType, MintTokenEventType,
-    MutateTokenPropertyMapEventType, TokenData, TokenEvent,
+    BurnTokenEventType, CreateCollectionEventType, CreateTokenDataEventType, MintTokenEventType,
+    MutateTokenPropertyMapEventType, TokenData, TokenEvent, TokenId,
 };
 use crate::schema::token_datas::dsl::token_datas;
 use crate::schema::token_datas::{last_minted_at, supply};
@@ -19,6 +19,7 @@ use crate::{
         events::EventModel,
         metadata::Metadata,
         ownership::Ownership,
+        token_activity::TokenActivity,
         token_property::TokenProperty,
         transactions::{TransactionModel, UserTransaction},
     },
@@ -29,11 +30,23 @@ use aptos_rest_client::Transaction;
 use async_trait::async_trait;
 use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
 use futures::future::Either;
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Refreshing the current ownership views recomputes them in full, so they're refreshed at most this often
+const VIEW_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Materialized views derived from `ownerships`, in the order they have to be refreshed in
+const CURRENT_OWNERSHIP_VIEWS: [&str; 2] =
+    ["current_token_ownerships", "current_collection_ownerships"];
 
 pub struct TokenTransactionProcessor {
     connection_pool: PgDbPool,
     index_token_uri: bool,
+    last_view_refresh: Mutex<Instant>,
 }
 
 impl TokenTransactionProcessor {
@@ -41,10 +54,35 @@ impl TokenTransactionProcessor {
         Self {
             connection_pool,
             index_token_uri,
+            last_view_refresh: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Refreshes the current ownership views, unless they've been refreshed recently or another
+    /// task is already refreshing them
+    fn maybe_refresh_current_ownership_views(&self, conn: &PgPoolConnection) {
+        let mut last_view_refresh = match self.last_view_refresh.try_lock() {
+            Ok(last_view_refresh) => last_view_refresh,
+            Err(_) => return,
+        };
+        if last_view_refresh.elapsed() < VIEW_REFRESH_INTERVAL {
+            return;
+        }
+        if let Err(err) = refresh_current_ownership_views(conn) {
+            aptos_logger::warn!("Error refreshing current ownership views: {:?}", err);
         }
+        *last_view_refresh = Instant::now();
     }
 }
 
+/// Recomputes the `current_token_ownerships` and `current_collection_ownerships` views
+pub fn refresh_current_ownership_views(conn: &PgPoolConnection) -> diesel::QueryResult<()> {
+    for view in CURRENT_OWNERSHIP_VIEWS {
+        conn.execute(&format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {}", view))?;
+    }
+    Ok(())
+}
+
 impl Debug for TokenTransactionProcessor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let state = &self.connection_pool.state();
@@ -75,6 +113,34 @@ fn update_mint_token(
     }
 }
 
+fn update_burn_token(conn: &PgPoolConnection, event_data: BurnTokenEventType) {
+    let result = diesel::update(token_datas.find(event_data.id.token_data_id.to_string()))
+        .set(supply.eq(supply - event_data.amount))
+        .get_result::<TokenData>(conn);
+    if result.is_err() {
+        aptos_logger::warn!("Error running query: {:?}", result.as_ref().err().unwrap());
+    }
+}
+
+/// Records the token activity, returning whether it's new: if the transaction has been processed
+/// before, its activities have already been applied, and must not be applied again
+fn insert_token_activity(
+    conn: &PgPoolConnection,
+    event: &EventModel,
+    token_event: &TokenEvent,
+    txn: &UserTransaction,
+) -> bool {
+    let token_activity = TokenActivity::from_event(event, token_event, txn);
+    let inserted = execute_with_better_error(
+        conn,
+        diesel::insert_into(schema::token_activities::table)
+            .values(&token_activity)
+            .on_conflict_do_nothing(),
+    )
+    .expect("Error inserting row into token_activities");
+    inserted > 0
+}
+
 async fn get_all_metadata(uris: &Vec<(String, String)>, res: &mut Vec<Metadata>) {
     let fetcher = MetaDataFetcher::new();
     for (tid, uri) in uris {
@@ -147,13 +213,14 @@ fn insert_token_data(
 
 fn update_token_ownership(
     conn: &PgPoolConnection,
-    token_id: String,
+    token_id: &TokenId,
+    owner: String,
     txn: &UserTransaction,
     amount_update: bigdecimal::BigDecimal,
 ) {
     let ownership = Ownership::new(
         token_id,
-        txn.sender.clone(),
+        owner,
         ensure_not_negative(amount_update.clone()),
         txn.timestamp,
         chrono::Utc::now().naive_utc(),
@@ -198,17 +265,22 @@ fn process_token_on_chain_data(
     events: &[EventModel],
     txn: &UserTransaction,
     uris: &mut Vec<(String, String)>,
-) {
-    // filter events to only keep token events
-    let token_events = events
-        .iter()
-        .map(TokenEvent::from_event)
-        .filter(|e| e.is_some())
-        .collect::<Vec<Option<TokenEvent>>>();
+) -> bool {
+    let mut processed_token_events = false;
     // for create token event, insert a new token to token table,
     // if token exists, increase the supply
-    for event in token_events {
-        match event.unwrap() {
+    for event in events {
+        let token_event = match TokenEvent::from_event(event) {
+            Some(token_event) => token_event,
+            None => continue,
+        };
+        if !insert_token_activity(conn, event, &token_event, txn) {
+            continue;
+        }
+        processed_token_events = true;
+        // deposits and withdrawals are emitted by the owner's token store
+        let account = event.account_address().unwrap_or_default();
+        match token_event {
             TokenEvent::CreateTokenDataEvent(event_data) => {
                 let uri = event_data.uri.clone();
                 let t_data_id = event_data.id.to_string();
@@ -218,21 +290,24 @@ fn process_token_on_chain_data(
             TokenEvent::MintTokenEvent(event_data) => {
                 update_mint_token(conn, event_data, txn);
             }
+            TokenEvent::BurnTokenEvent(event_data) => {
+                update_burn_token(conn, event_data);
+            }
             TokenEvent::CollectionCreationEvent(event_data) => {
                 insert_collection(conn, event_data, txn);
             }
             TokenEvent::DepositEvent(event_data) => {
-                update_token_ownership(conn, event_data.id.to_string(), txn, event_data.amount);
+                update_token_ownership(conn, &event_data.id, account, txn, event_data.amount);
             }
             TokenEvent::WithdrawEvent(event_data) => {
-                update_token_ownership(conn, event_data.id.to_string(), txn, -event_data.amount);
+                update_token_ownership(conn, &event_data.id, account, txn, -event_data.amount);
             }
             TokenEvent::MutateTokenPropertyMapEvent(event_data) => {
                 insert_token_properties(conn, event_data, txn);
             }
-            _ => (),
         }
     }
+    processed_token_events
 }
 
 #[async_trait]
@@ -252,11 +327,13 @@ impl TransactionProcessor for TokenTransactionProcessor {
 
         let conn = self.get_conn();
         let mut token_uris: Vec<(String, String)> = vec![];
+        let mut processed_token_events = false;
 
         let mut tx_result = conn.transaction::<(), diesel::result::Error, _>(|| {
             if let Some(Either::Left(user_txn)) = maybe_details_model {
                 if let Some(events) = maybe_events {
-                    process_token_on_chain_data(&conn, &events, &user_txn, &mut token_uris);
+                    processed_token_events =
+                        process_token_on_chain_data(&conn, &events, &user_txn, &mut token_uris);
                 }
             }
             Ok(())
@@ -269,6 +346,9 @@ impl TransactionProcessor for TokenTransactionProcessor {
                 self.name(),
             )));
         };
+        if processed_token_events {
+            self.maybe_refresh_current_ownership_views(&conn);
+        }
         if self.index_token_uri {
             let mut res: Vec<Metadata> = vec![];
             get_all_metadata(&token_uris, &mut res).await;
@@ -65,6 +65,9 @@ fn block_to_block_output(input_block: Block) -> Result<BlockOutput, Error> {
                 events_input = Some(&genesis_txn.events);
             }
             Some(TxnDataInput::StateCheckpoint(_)) => {}
+            Some(TxnDataInput::Validator(validator_txn)) => {
+                events_input = Some(&validator_txn.events);
+            }
         };
         let events = match events_input {
             None => vec![],
@@ -108,6 +111,7 @@ fn generate_trx_type_key(trx_type: TransactionType) -> String {
         TransactionType::BlockMetadata => "block_metadata",
         TransactionType::StateCheckpoint => "state_checkpoint",
         TransactionType::User => "user",
+        TransactionType::Validator => "validator",
     }
     .to_string()
 }
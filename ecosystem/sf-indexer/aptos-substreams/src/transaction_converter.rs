@@ -13,9 +13,9 @@ use aptos_protos::{
         signature::{Signature, Type as SignatureType},
         transaction::TransactionType,
         write_set_change::{Change as ChangeInput, Type as WriteSetChangeType},
-        BlockMetadataTransaction, Ed25519Signature, Event, GenesisTransaction, MoveResource,
-        MoveStructTag, MultiAgentSignature, MultiEd25519Signature, Transaction, TransactionInfo,
-        UserTransaction, UserTransactionRequest,
+        BlockMetadataTransaction, Ed25519Signature, Event, GenesisTransaction, KeylessSignature,
+        MoveResource, MoveStructTag, MultiAgentSignature, MultiEd25519Signature, MultiKeySignature,
+        Transaction, TransactionInfo, UserTransaction, UserTransactionRequest, WebAuthnSignature,
     },
 };
 
@@ -142,6 +142,7 @@ pub fn get_transaction_type(t: TransactionType) -> String {
         TransactionType::BlockMetadata => String::from("block_metadata_transaction"),
         TransactionType::User => String::from("user_transaction"),
         TransactionType::StateCheckpoint => String::from("state_checkpoint_transaction"),
+        TransactionType::Validator => String::from("validator_transaction"),
     }
 }
 
@@ -150,6 +151,9 @@ pub fn get_signature_type(t: SignatureType) -> String {
         SignatureType::Ed25519 => String::from("ed25519_signature"),
         SignatureType::MultiEd25519 => String::from("multi_ed25519_signature"),
         SignatureType::MultiAgent => String::from("multi_agent_signature"),
+        SignatureType::WebAuthn => String::from("web_authn_signature"),
+        SignatureType::Keyless => String::from("keyless_signature"),
+        SignatureType::MultiKey => String::from("multi_key_signature"),
     }
 }
 
@@ -177,6 +181,15 @@ pub fn get_signature_outputs(
             Ok(parse_multi_signature(sig, request, info, true, 0, None))
         }
         Signature::MultiAgent(sig) => parse_multi_agent_signature(sig, request, info),
+        Signature::WebAuthn(sig) => Ok(vec![parse_web_authn_signature(
+            sig, request, info, true, 0, None,
+        )]),
+        Signature::Keyless(sig) => Ok(vec![parse_keyless_signature(
+            sig, request, info, true, 0, None,
+        )]),
+        Signature::MultiKey(sig) => {
+            Ok(parse_multi_key_signature(sig, request, info, true, 0, None))
+        }
     }
 }
 
@@ -203,6 +216,52 @@ fn parse_single_signature(
     }
 }
 
+fn parse_web_authn_signature(
+    s: &WebAuthnSignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+    is_sender_primary: bool,
+    multi_agent_index: u32,
+    override_address: Option<&String>,
+) -> SignatureOutput {
+    let signer = override_address.unwrap_or(&request.sender);
+    SignatureOutput {
+        version: info.version,
+        signer: signer.clone(),
+        is_sender_primary,
+        signature_type: get_signature_type(SignatureType::WebAuthn),
+        public_key: s.public_key.clone(),
+        signature: s.signature.clone(),
+        threshold: 1,
+        bitmap: Vec::default(),
+        multi_agent_index,
+        multi_sig_index: 0,
+    }
+}
+
+fn parse_keyless_signature(
+    s: &KeylessSignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+    is_sender_primary: bool,
+    multi_agent_index: u32,
+    override_address: Option<&String>,
+) -> SignatureOutput {
+    let signer = override_address.unwrap_or(&request.sender);
+    SignatureOutput {
+        version: info.version,
+        signer: signer.clone(),
+        is_sender_primary,
+        signature_type: get_signature_type(SignatureType::Keyless),
+        public_key: s.public_key.clone(),
+        signature: s.signature.clone(),
+        threshold: 1,
+        bitmap: Vec::default(),
+        multi_agent_index,
+        multi_sig_index: 0,
+    }
+}
+
 fn parse_multi_signature(
     s: &MultiEd25519Signature,
     request: &UserTransactionRequest,
@@ -234,6 +293,37 @@ fn parse_multi_signature(
     signatures
 }
 
+fn parse_multi_key_signature(
+    s: &MultiKeySignature,
+    request: &UserTransactionRequest,
+    info: &TransactionInfoOutput,
+    is_sender_primary: bool,
+    multi_agent_index: u32,
+    override_address: Option<&String>,
+) -> Vec<SignatureOutput> {
+    let signer = override_address.unwrap_or(&request.sender);
+    s.signatures
+        .iter()
+        .zip(s.public_key_indices.iter())
+        .map(|(signature, index)| SignatureOutput {
+            version: info.version,
+            signer: signer.clone(),
+            is_sender_primary,
+            signature_type: get_signature_type(SignatureType::MultiKey),
+            public_key: s
+                .public_keys
+                .get(*index as usize)
+                .cloned()
+                .unwrap_or_default(),
+            signature: signature.clone(),
+            threshold: s.signatures_required,
+            bitmap: Vec::default(),
+            multi_agent_index,
+            multi_sig_index: *index,
+        })
+        .collect()
+}
+
 fn parse_multi_agent_signature(
     s: &MultiAgentSignature,
     request: &UserTransactionRequest,
@@ -296,6 +386,30 @@ fn parse_multi_agent_signature_helper(
             multi_agent_index,
             override_address,
         ),
+        AccountSignature::WebAuthn(sig) => vec![parse_web_authn_signature(
+            sig,
+            request,
+            info,
+            is_sender_primary,
+            multi_agent_index,
+            override_address,
+        )],
+        AccountSignature::Keyless(sig) => vec![parse_keyless_signature(
+            sig,
+            request,
+            info,
+            is_sender_primary,
+            multi_agent_index,
+            override_address,
+        )],
+        AccountSignature::MultiKey(sig) => parse_multi_key_signature(
+            sig,
+            request,
+            info,
+            is_sender_primary,
+            multi_agent_index,
+            override_address,
+        ),
     }
 }
 
@@ -0,0 +1,97 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{AccountResource, CoinStore},
+    state_store::state_key::StateKey,
+    transaction::{SignedTransaction, TransactionPayload},
+};
+use move_deps::move_core_types::move_resource::MoveStructType;
+use std::collections::BTreeSet;
+
+/// The storage locations a transaction reads and writes, from which the partitioner finds the
+/// conflicts between transactions. A location both read and written is only in `writes`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReadWriteSummary {
+    reads: BTreeSet<StateKey>,
+    writes: BTreeSet<StateKey>,
+}
+
+impl ReadWriteSummary {
+    pub fn new(reads: BTreeSet<StateKey>, writes: BTreeSet<StateKey>) -> Self {
+        let reads = reads.difference(&writes).cloned().collect();
+        Self { reads, writes }
+    }
+
+    pub fn add_read(&mut self, key: StateKey) {
+        if !self.writes.contains(&key) {
+            self.reads.insert(key);
+        }
+    }
+
+    pub fn add_write(&mut self, key: StateKey) {
+        self.reads.remove(&key);
+        self.writes.insert(key);
+    }
+
+    pub fn reads(&self) -> &BTreeSet<StateKey> {
+        &self.reads
+    }
+
+    pub fn writes(&self) -> &BTreeSet<StateKey> {
+        &self.writes
+    }
+}
+
+/// Estimates the read/write set of a transaction before executing it.
+///
+/// The estimate doesn't need to be exact: a missed conflict only costs a re-execution once the
+/// shards are executed, but the more accurate it is, the fewer cross-shard dependencies.
+pub trait ReadWriteSetAnalyzer {
+    fn analyze(&self, txn: &SignedTransaction) -> ReadWriteSummary;
+}
+
+/// Estimates the read/write set from what a transaction declares: the sender's account and coin
+/// store, written by the prologue and epilogue, the signers' accounts, the module of the entry
+/// function, and the coin stores of the addresses passed as arguments, e.g. the recipient of a
+/// transfer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeclaredReadWriteSetAnalyzer;
+
+fn resource_key<T: MoveStructType>(address: AccountAddress) -> StateKey {
+    StateKey::AccessPath(AccessPath::new(
+        address,
+        AccessPath::resource_access_vec(T::struct_tag()),
+    ))
+}
+
+impl ReadWriteSetAnalyzer for DeclaredReadWriteSetAnalyzer {
+    fn analyze(&self, txn: &SignedTransaction) -> ReadWriteSummary {
+        let mut summary = ReadWriteSummary::default();
+        summary.add_write(resource_key::<AccountResource>(txn.sender()));
+        summary.add_write(resource_key::<CoinStore>(txn.sender()));
+        for signer in txn.authenticator().secondary_signer_addreses() {
+            summary.add_read(resource_key::<AccountResource>(signer));
+        }
+
+        let entry_function = match txn.payload() {
+            TransactionPayload::EntryFunction(entry_function) => entry_function,
+            TransactionPayload::OrderlessEntryFunction(orderless) => orderless.entry_function(),
+            TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => return summary,
+        };
+        summary.add_read(StateKey::AccessPath(AccessPath::code_access_path(
+            entry_function.module().clone(),
+        )));
+        for arg in entry_function.args() {
+            if arg.len() != AccountAddress::LENGTH {
+                continue;
+            }
+            if let Ok(address) = bcs::from_bytes::<AccountAddress>(arg) {
+                summary.add_write(resource_key::<CoinStore>(address));
+            }
+        }
+        summary
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    exponential_buckets, register_histogram, register_int_counter, Histogram, IntCounter,
+};
+use once_cell::sync::Lazy;
+
+pub static APTOS_BLOCK_PARTITIONER_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_block_partitioner_seconds",
+        // metric description
+        "The time spent in seconds of partitioning a block into shards",
+        exponential_buckets(/*start=*/ 1e-6, /*factor=*/ 2.0, /*count=*/ 22).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static APTOS_BLOCK_PARTITIONER_CROSS_SHARD_EDGES: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_block_partitioner_cross_shard_edges",
+        // metric description
+        "The number of dependencies between transactions in different shards, per block",
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static APTOS_BLOCK_PARTITIONER_CROSS_SHARD_TXN_RATIO: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_block_partitioner_cross_shard_txn_ratio",
+        // metric description
+        "The fraction of the transactions of a block depending on a transaction in another shard",
+        vec![0.0, 0.01, 0.02, 0.05, 0.1, 0.2, 0.3, 0.5, 0.75, 1.0],
+    )
+    .unwrap()
+});
+
+pub static APTOS_BLOCK_PARTITIONER_IMBALANCE: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_block_partitioner_imbalance",
+        // metric description
+        "The size of the largest shard of a block relative to an even split",
+        vec![1.0, 1.05, 1.1, 1.2, 1.5, 2.0, 3.0, 5.0],
+    )
+    .unwrap()
+});
+
+pub static APTOS_BLOCK_PARTITIONER_HOT_KEYS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_block_partitioner_hot_keys",
+        "Cumulative number of keys too widely accessed to keep their accessors in one shard"
+    )
+    .unwrap()
+});
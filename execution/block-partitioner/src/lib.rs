@@ -0,0 +1,324 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Splits the transactions of a block into shards which can be executed in parallel, with as few
+//! conflicts between the shards as possible.
+//!
+//! The read/write set of each transaction is estimated ahead of execution by a
+//! [`ReadWriteSetAnalyzer`]. Transactions touching a common key that one of them writes are kept
+//! in the same shard, unless the key is hot, i.e. accessed by so many transactions that keeping
+//! them together would serialize the block. The resulting groups are then packed into shards of
+//! bounded size, and the conflicts left between shards are reported as cross-shard dependencies,
+//! which the executor of a shard waits on.
+//!
+//! Partitioning is deterministic: the same block is always split the same way, so that every
+//! validator executes the same shards.
+
+mod analyzer;
+mod counters;
+#[cfg(test)]
+mod tests;
+
+pub use analyzer::{DeclaredReadWriteSetAnalyzer, ReadWriteSetAnalyzer, ReadWriteSummary};
+
+use aptos_types::{state_store::state_key::StateKey, transaction::SignedTransaction};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The position of a transaction in the block.
+pub type TxnIndex = usize;
+pub type ShardId = usize;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartitionerConfig {
+    pub num_shards: usize,
+    /// A key accessed by more than this fraction of the block is hot: its accessors may be
+    /// spread across shards.
+    pub hot_key_fraction: f64,
+    /// How much larger than an even split a shard may grow to keep conflicting transactions
+    /// together.
+    pub max_imbalance: f64,
+}
+
+impl Default for PartitionerConfig {
+    fn default() -> Self {
+        Self {
+            num_shards: 4,
+            hot_key_fraction: 0.1,
+            max_imbalance: 0.2,
+        }
+    }
+}
+
+/// The transactions of a shard, with their index in the block, in block order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubBlock {
+    shard_id: ShardId,
+    transactions: Vec<(TxnIndex, SignedTransaction)>,
+}
+
+impl SubBlock {
+    pub fn shard_id(&self) -> ShardId {
+        self.shard_id
+    }
+
+    pub fn transactions(&self) -> &[(TxnIndex, SignedTransaction)] {
+        &self.transactions
+    }
+
+    pub fn into_transactions(self) -> Vec<(TxnIndex, SignedTransaction)> {
+        self.transactions
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+}
+
+/// How well a block was partitioned: the fewer cross-shard dependencies and the more even the
+/// shards, the more of the block is executed in parallel.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CutQuality {
+    pub cross_shard_edges: usize,
+    /// The number of transactions depending on a transaction in another shard
+    pub cross_shard_txns: usize,
+    pub largest_shard: usize,
+    pub hot_keys: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartitionedBlock {
+    sub_blocks: Vec<SubBlock>,
+    cross_shard_dependencies: BTreeSet<(TxnIndex, TxnIndex)>,
+    cut_quality: CutQuality,
+}
+
+impl PartitionedBlock {
+    /// One sub-block per shard, ordered by shard id. A shard may be empty.
+    pub fn sub_blocks(&self) -> &[SubBlock] {
+        &self.sub_blocks
+    }
+
+    /// The `(source, target)` pairs of transactions in different shards, where `target` must be
+    /// executed after `source` as it reads or writes a key `source` writes, or writes a key
+    /// `source` reads.
+    pub fn cross_shard_dependencies(&self) -> &BTreeSet<(TxnIndex, TxnIndex)> {
+        &self.cross_shard_dependencies
+    }
+
+    pub fn cut_quality(&self) -> &CutQuality {
+        &self.cut_quality
+    }
+
+    pub fn into_sub_blocks(self) -> Vec<SubBlock> {
+        self.sub_blocks
+    }
+}
+
+pub struct BlockPartitioner<A> {
+    config: PartitionerConfig,
+    analyzer: A,
+}
+
+impl<A: ReadWriteSetAnalyzer> BlockPartitioner<A> {
+    pub fn new(config: PartitionerConfig, analyzer: A) -> Self {
+        assert!(config.num_shards > 0, "At least one shard is required");
+        Self { config, analyzer }
+    }
+
+    pub fn partition(&self, transactions: Vec<SignedTransaction>) -> PartitionedBlock {
+        let _timer = counters::APTOS_BLOCK_PARTITIONER_SECONDS.start_timer();
+        let summaries: Vec<_> = transactions
+            .iter()
+            .map(|txn| self.analyzer.analyze(txn))
+            .collect();
+
+        let hot_keys = self.hot_keys(&summaries);
+        let shard_of = self.assign_shards(conflict_groups(&summaries, &hot_keys));
+        let cross_shard_dependencies = cross_shard_dependencies(&summaries, &shard_of);
+
+        let mut sub_blocks: Vec<_> = (0..self.config.num_shards)
+            .map(|shard_id| SubBlock {
+                shard_id,
+                transactions: vec![],
+            })
+            .collect();
+        for (index, txn) in transactions.into_iter().enumerate() {
+            sub_blocks[shard_of[index]].transactions.push((index, txn));
+        }
+
+        let cut_quality = CutQuality {
+            cross_shard_edges: cross_shard_dependencies.len(),
+            cross_shard_txns: cross_shard_dependencies
+                .iter()
+                .map(|(_, target)| *target)
+                .collect::<BTreeSet<_>>()
+                .len(),
+            largest_shard: sub_blocks.iter().map(SubBlock::len).max().unwrap_or(0),
+            hot_keys: hot_keys.len(),
+        };
+        self.observe(&cut_quality, summaries.len());
+
+        PartitionedBlock {
+            sub_blocks,
+            cross_shard_dependencies,
+            cut_quality,
+        }
+    }
+
+    /// The written keys accessed by too many transactions to keep them in one shard.
+    fn hot_keys(&self, summaries: &[ReadWriteSummary]) -> BTreeSet<StateKey> {
+        let threshold =
+            ((self.config.hot_key_fraction * summaries.len() as f64).ceil() as usize).max(2);
+        accessors(summaries)
+            .into_iter()
+            .filter(|(_, (accessors, written))| *written && accessors.len() > threshold)
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Packs the groups into the shards, largest first, each into the least loaded shard. A
+    /// group which doesn't fit in a shard is split, in block order.
+    fn assign_shards(&self, mut groups: Vec<Vec<TxnIndex>>) -> Vec<ShardId> {
+        let num_txns: usize = groups.iter().map(Vec::len).sum();
+        let num_shards = self.config.num_shards;
+        // Since the capacity of all shards is at least the size of the block, the least loaded
+        // shard always has room left.
+        let capacity = ((num_txns as f64 / num_shards as f64)
+            * (1.0 + self.config.max_imbalance.max(0.0)))
+        .ceil()
+        .max(1.0) as usize;
+
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+        let mut loads = vec![0; num_shards];
+        let mut shard_of = vec![0; num_txns];
+        for group in groups {
+            let mut remaining = &group[..];
+            while !remaining.is_empty() {
+                let (shard_id, load) = loads
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(shard_id, load)| (**load, *shard_id))
+                    .map(|(shard_id, load)| (shard_id, *load))
+                    .expect("At least one shard");
+                let (chunk, rest) = remaining.split_at((capacity - load).min(remaining.len()));
+                for index in chunk {
+                    shard_of[*index] = shard_id;
+                }
+                loads[shard_id] += chunk.len();
+                remaining = rest;
+            }
+        }
+        shard_of
+    }
+
+    fn observe(&self, cut_quality: &CutQuality, num_txns: usize) {
+        counters::APTOS_BLOCK_PARTITIONER_CROSS_SHARD_EDGES
+            .observe(cut_quality.cross_shard_edges as f64);
+        counters::APTOS_BLOCK_PARTITIONER_HOT_KEYS.inc_by(cut_quality.hot_keys as u64);
+        if num_txns > 0 {
+            counters::APTOS_BLOCK_PARTITIONER_CROSS_SHARD_TXN_RATIO
+                .observe(cut_quality.cross_shard_txns as f64 / num_txns as f64);
+            counters::APTOS_BLOCK_PARTITIONER_IMBALANCE.observe(
+                cut_quality.largest_shard as f64 * self.config.num_shards as f64 / num_txns as f64,
+            );
+        }
+    }
+}
+
+/// The transactions accessing each key, and whether any of them writes it.
+fn accessors(summaries: &[ReadWriteSummary]) -> BTreeMap<StateKey, (Vec<TxnIndex>, bool)> {
+    let mut accessors: BTreeMap<StateKey, (Vec<TxnIndex>, bool)> = BTreeMap::new();
+    for (index, summary) in summaries.iter().enumerate() {
+        for key in summary.reads() {
+            accessors.entry(key.clone()).or_default().0.push(index);
+        }
+        for key in summary.writes() {
+            let entry = accessors.entry(key.clone()).or_default();
+            entry.0.push(index);
+            entry.1 = true;
+        }
+    }
+    accessors
+}
+
+/// Groups the transactions conflicting on a key which isn't hot, each group in block order.
+fn conflict_groups(
+    summaries: &[ReadWriteSummary],
+    hot_keys: &BTreeSet<StateKey>,
+) -> Vec<Vec<TxnIndex>> {
+    let mut parents: Vec<TxnIndex> = (0..summaries.len()).collect();
+    fn find(parents: &mut [TxnIndex], mut index: TxnIndex) -> TxnIndex {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+
+    for (key, (accessors, written)) in accessors(summaries) {
+        if !written || hot_keys.contains(&key) {
+            continue;
+        }
+        let mut root = find(&mut parents, accessors[0]);
+        for index in &accessors[1..] {
+            let other = find(&mut parents, *index);
+            // The smallest index is the root, so that the groups don't depend on the order of
+            // the unions.
+            if other != root {
+                let (low, high) = (root.min(other), root.max(other));
+                parents[high] = low;
+                root = low;
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<TxnIndex, Vec<TxnIndex>> = BTreeMap::new();
+    for index in 0..summaries.len() {
+        let root = find(&mut parents, index);
+        groups.entry(root).or_default().push(index);
+    }
+    groups.into_values().collect()
+}
+
+/// The read-after-write, write-after-write and write-after-read dependencies between
+/// transactions of different shards.
+fn cross_shard_dependencies(
+    summaries: &[ReadWriteSummary],
+    shard_of: &[ShardId],
+) -> BTreeSet<(TxnIndex, TxnIndex)> {
+    let mut last_writer: BTreeMap<&StateKey, TxnIndex> = BTreeMap::new();
+    let mut readers_since_write: BTreeMap<&StateKey, Vec<TxnIndex>> = BTreeMap::new();
+    let mut dependencies = BTreeSet::new();
+    for (index, summary) in summaries.iter().enumerate() {
+        for key in summary.reads() {
+            if let Some(writer) = last_writer.get(key) {
+                dependencies.insert((*writer, index));
+            }
+        }
+        for key in summary.writes() {
+            if let Some(writer) = last_writer.get(key) {
+                dependencies.insert((*writer, index));
+            }
+            for reader in readers_since_write.remove(key).unwrap_or_default() {
+                dependencies.insert((reader, index));
+            }
+        }
+
+        for key in summary.reads() {
+            readers_since_write.entry(key).or_default().push(index);
+        }
+        for key in summary.writes() {
+            last_writer.insert(key, index);
+        }
+    }
+    dependencies
+        .into_iter()
+        .filter(|(source, target)| shard_of[*source] != shard_of[*target])
+        .collect()
+}
@@ -0,0 +1,148 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BlockPartitioner, DeclaredReadWriteSetAnalyzer, PartitionedBlock, PartitionerConfig,
+    ReadWriteSetAnalyzer, TxnIndex,
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::CORE_CODE_ADDRESS,
+    test_helpers::transaction_test_helpers::get_test_signed_txn,
+    transaction::{EntryFunction, SignedTransaction, TransactionPayload},
+};
+use move_deps::move_core_types::{ident_str, language_storage::ModuleId};
+
+fn transfer(sender: AccountAddress, seq_num: u64, receiver: AccountAddress) -> SignedTransaction {
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let payload = TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(CORE_CODE_ADDRESS, ident_str!("coin").to_owned()),
+        ident_str!("transfer").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&receiver).unwrap(),
+            bcs::to_bytes(&100u64).unwrap(),
+        ],
+    ));
+    get_test_signed_txn(
+        sender,
+        seq_num,
+        &private_key,
+        private_key.public_key(),
+        Some(payload),
+    )
+}
+
+fn partition(config: PartitionerConfig, txns: Vec<SignedTransaction>) -> PartitionedBlock {
+    BlockPartitioner::new(config, DeclaredReadWriteSetAnalyzer).partition(txns)
+}
+
+fn shard_of(block: &PartitionedBlock, index: TxnIndex) -> usize {
+    block
+        .sub_blocks()
+        .iter()
+        .find(|sub_block| sub_block.transactions().iter().any(|(i, _)| *i == index))
+        .unwrap()
+        .shard_id()
+}
+
+#[test]
+fn test_declared_read_write_set() {
+    let sender = AccountAddress::random();
+    let receiver = AccountAddress::random();
+    let summary = DeclaredReadWriteSetAnalyzer.analyze(&transfer(sender, 0, receiver));
+    // The account and coin store of the sender, and the coin store of the receiver
+    assert_eq!(summary.writes().len(), 3);
+    // The coin module
+    assert_eq!(summary.reads().len(), 1);
+    assert!(summary.reads().is_disjoint(summary.writes()));
+
+    let summary = DeclaredReadWriteSetAnalyzer.analyze(&transfer(sender, 0, sender));
+    assert_eq!(summary.writes().len(), 2);
+}
+
+#[test]
+fn test_independent_transactions_are_balanced() {
+    let txns: Vec<_> = (0..16)
+        .map(|_| transfer(AccountAddress::random(), 0, AccountAddress::random()))
+        .collect();
+    let block = partition(PartitionerConfig::default(), txns);
+
+    assert_eq!(block.sub_blocks().len(), 4);
+    for (shard_id, sub_block) in block.sub_blocks().iter().enumerate() {
+        assert_eq!(sub_block.shard_id(), shard_id);
+        assert_eq!(sub_block.len(), 4);
+        let indices: Vec<_> = sub_block.transactions().iter().map(|(i, _)| *i).collect();
+        let mut sorted = indices.clone();
+        sorted.sort_unstable();
+        assert_eq!(indices, sorted);
+    }
+    assert!(block.cross_shard_dependencies().is_empty());
+    assert_eq!(block.cut_quality().largest_shard, 4);
+}
+
+#[test]
+fn test_conflicting_transactions_share_a_shard() {
+    let senders: Vec<_> = (0..4).map(|_| AccountAddress::random()).collect();
+    // Each sender sends two transactions, and the receivers are unique.
+    let txns: Vec<_> = (0..8)
+        .map(|i| transfer(senders[i % 4], (i / 4) as u64, AccountAddress::random()))
+        .collect();
+    let block = partition(PartitionerConfig::default(), txns);
+
+    for i in 0..4 {
+        assert_eq!(shard_of(&block, i), shard_of(&block, i + 4));
+    }
+    assert!(block.cross_shard_dependencies().is_empty());
+    assert_eq!(block.cut_quality().cross_shard_txns, 0);
+}
+
+#[test]
+fn test_hot_key_is_cut() {
+    let receiver = AccountAddress::random();
+    let txns: Vec<_> = (0..20)
+        .map(|_| transfer(AccountAddress::random(), 0, receiver))
+        .collect();
+    let block = partition(PartitionerConfig::default(), txns.clone());
+
+    // All transactions write the coin store of the receiver, so they are spread across shards,
+    // each depending on the previous one when in different shards.
+    assert_eq!(block.cut_quality().hot_keys, 1);
+    assert_eq!(block.cut_quality().largest_shard, 5);
+    for (source, target) in block.cross_shard_dependencies() {
+        assert!(source < target);
+        assert_ne!(shard_of(&block, *source), shard_of(&block, *target));
+    }
+    for i in 1..20 {
+        let cut = shard_of(&block, i - 1) != shard_of(&block, i);
+        assert_eq!(cut, block.cross_shard_dependencies().contains(&(i - 1, i)));
+    }
+    assert!(block.cut_quality().cross_shard_edges > 0);
+
+    // Partitioning is deterministic.
+    assert_eq!(block, partition(PartitionerConfig::default(), txns));
+}
+
+#[test]
+fn test_large_group_is_split() {
+    let sender = AccountAddress::random();
+    let txns: Vec<_> = (0..10)
+        .map(|i| transfer(sender, i, AccountAddress::random()))
+        .collect();
+    let config = PartitionerConfig {
+        num_shards: 2,
+        hot_key_fraction: 1.0,
+        max_imbalance: 0.0,
+    };
+    let block = partition(config, txns);
+
+    assert_eq!(block.cut_quality().hot_keys, 0);
+    assert_eq!(block.cut_quality().largest_shard, 5);
+    // The group is split in block order, so only the first transaction of the second shard
+    // depends on the first shard.
+    assert_eq!(
+        block.cross_shard_dependencies().iter().collect::<Vec<_>>(),
+        vec![&(4, 5)]
+    );
+}
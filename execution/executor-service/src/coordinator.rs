@@ -0,0 +1,713 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    messages::{
+        open, seal, CoordinatorMessage, ExecuteSubBlock, ExecutorServiceMessage,
+        SubBlockExecutionResult,
+    },
+    Error,
+};
+use aptos_config::config::RemoteExecutorConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::warn;
+use aptos_secure_net::NetworkClient;
+use aptos_state_view::StateView;
+use aptos_state_view_layers::OverlayStateView;
+use aptos_types::{
+    on_chain_config::new_epoch_event_key,
+    state_store::state_key::StateKey,
+    transaction::{SignedTransaction, Transaction, TransactionOutput, TransactionStatus},
+    vm_status::VMStatus,
+};
+use aptos_vm::{AptosVM, VMExecutor};
+use block_partitioner::{
+    BlockPartitioner, DeclaredReadWriteSetAnalyzer, PartitionedBlock, PartitionerConfig,
+    ReadWriteSetAnalyzer, TxnIndex,
+};
+use executor_types::TransactionBlockExecutor;
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use storage_interface::cached_state_view::CachedStateView;
+
+/// Shards the user transactions of blocks across executor services.
+///
+/// The transactions are executed in two rounds. In the first one, each shard executes its
+/// transactions which don't depend on another shard, even through an earlier transaction of the
+/// shard, all shards in parallel. In the second round, the remaining transactions are executed by
+/// a single executor service, on top of the writes of the first round. As the partitioner only
+/// estimates the read/write sets, the keys written and the keys served to each executor service,
+/// which it may have read, are checked for conflicts within the first round and between the two
+/// rounds. If there are any, or if an executor service wrote a key it wasn't served, the sharded
+/// outputs are dropped and the block is executed locally and sequentially. Blocks which can't be
+/// sharded, e.g. ending an epoch, are executed locally.
+pub struct RemoteExecutorClient {
+    shards: Vec<Mutex<NetworkClient>>,
+    partitioner: BlockPartitioner<DeclaredReadWriteSetAnalyzer>,
+}
+
+impl TransactionBlockExecutor for RemoteExecutorClient {
+    fn execute_transaction_block(
+        &self,
+        transactions: Vec<Transaction>,
+        state_view: &CachedStateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        self.execute_block(transactions, state_view)
+    }
+}
+
+impl RemoteExecutorClient {
+    pub fn new(config: &RemoteExecutorConfig, network_timeout_ms: u64) -> Self {
+        assert!(
+            !config.shards.is_empty(),
+            "At least one executor service is required"
+        );
+        let shards: Vec<_> = config
+            .shards
+            .iter()
+            .map(|shard| {
+                Mutex::new(NetworkClient::new_authenticated(
+                    "executor-service",
+                    shard.address,
+                    network_timeout_ms,
+                    config.identity_key.private_key(),
+                    shard.server_key,
+                ))
+            })
+            .collect();
+        let partitioner = BlockPartitioner::new(
+            PartitionerConfig {
+                num_shards: shards.len(),
+                ..PartitionerConfig::default()
+            },
+            DeclaredReadWriteSetAnalyzer,
+        );
+        Self {
+            shards,
+            partitioner,
+        }
+    }
+
+    pub fn execute_block(
+        &self,
+        mut transactions: Vec<Transaction>,
+        state_view: &impl StateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let (start, end) = match user_transactions_range(&transactions) {
+            Some(range) if !state_view.is_genesis() => range,
+            _ => {
+                counters::APTOS_REMOTE_EXECUTOR_BLOCKS
+                    .with_label_values(&["local"])
+                    .inc();
+                return AptosVM::execute_block(transactions, state_view);
+            }
+        };
+        let _timer = counters::APTOS_REMOTE_EXECUTOR_EXECUTE_BLOCK_SECONDS.start_timer();
+
+        // The block metadata and validator transactions are executed locally.
+        let trailing_txns = transactions.split_off(end);
+        let user_txns: Vec<_> = transactions
+            .split_off(start)
+            .into_iter()
+            .map(|txn| match txn {
+                Transaction::UserTransaction(txn) => txn,
+                _ => unreachable!("Only user transactions are in range"),
+            })
+            .collect();
+        let leading_txns = transactions;
+
+        let state_view = OverlayStateView::new(state_view);
+        let mut outputs = AptosVM::execute_block(leading_txns.clone(), &state_view)?;
+        if outputs.iter().any(ends_epoch) {
+            counters::APTOS_REMOTE_EXECUTOR_BLOCKS
+                .with_label_values(&["local"])
+                .inc();
+            let mut transactions = leading_txns;
+            transactions.extend(user_txns.into_iter().map(Transaction::UserTransaction));
+            transactions.extend(trailing_txns);
+            return AptosVM::execute_block(transactions, state_view.base());
+        }
+        for output in &outputs {
+            state_view.apply_write_set(output.write_set());
+        }
+
+        let user_outputs = match self.execute_user_transactions(user_txns.clone(), &state_view) {
+            Ok(user_outputs) => {
+                counters::APTOS_REMOTE_EXECUTOR_BLOCKS
+                    .with_label_values(&["remote"])
+                    .inc();
+                user_outputs
+            }
+            Err(e) => {
+                warn!(
+                    "Remote execution failed, executing the block locally: {}",
+                    e
+                );
+                counters::APTOS_REMOTE_EXECUTOR_BLOCKS
+                    .with_label_values(&["fallback"])
+                    .inc();
+                // Drops all the outputs, and executes the whole block from the base state.
+                let mut transactions = leading_txns;
+                transactions.extend(user_txns.into_iter().map(Transaction::UserTransaction));
+                transactions.extend(trailing_txns);
+                return execute_block_sequentially(transactions, state_view.base());
+            }
+        };
+        for output in &user_outputs {
+            state_view.apply_write_set(output.write_set());
+        }
+        outputs.extend(user_outputs);
+        outputs.extend(AptosVM::execute_block(trailing_txns, &state_view)?);
+        Ok(outputs)
+    }
+
+    fn execute_user_transactions<S: StateView>(
+        &self,
+        transactions: Vec<SignedTransaction>,
+        state_view: &OverlayStateView<S>,
+    ) -> Result<Vec<TransactionOutput>, Error> {
+        let num_txns = transactions.len();
+        let (first_round, second_round) = split_rounds(self.partitioner.partition(transactions));
+
+        let results = first_round
+            .par_iter()
+            .enumerate()
+            .map(|(shard_id, sub_block)| self.execute_sub_block(shard_id, sub_block, state_view))
+            .collect::<Result<Vec<_>, _>>()?;
+        check_no_conflicts(&results)?;
+        let first_round_results: Vec<_> = first_round
+            .iter()
+            .zip(results)
+            .map(|(sub_block, (outputs, reads))| (with_indices(sub_block, outputs), reads))
+            .collect();
+
+        for (sub_block_outputs, _) in &first_round_results {
+            for (_, output) in sub_block_outputs {
+                state_view.apply_write_set(output.write_set());
+            }
+        }
+        let (second_round_outputs, second_round_reads) =
+            self.execute_sub_block(0, &second_round, state_view)?;
+        let second_round_result = (
+            with_indices(&second_round, second_round_outputs),
+            second_round_reads,
+        );
+        check_rounds_in_order(&first_round_results, &second_round_result)?;
+
+        let mut outputs: Vec<Option<TransactionOutput>> = vec![None; num_txns];
+        for (index, output) in first_round_results
+            .into_iter()
+            .flat_map(|(sub_block_outputs, _)| sub_block_outputs)
+            .chain(second_round_result.0)
+        {
+            outputs[index] = Some(output);
+        }
+
+        Ok(outputs
+            .into_iter()
+            .map(|output| output.expect("Every transaction is executed in a round"))
+            .collect())
+    }
+
+    /// Executes the transactions on the executor service of the shard, serving the state values
+    /// it reads, and returns their outputs with the keys served, which the transactions may have
+    /// read.
+    fn execute_sub_block(
+        &self,
+        shard_id: usize,
+        transactions: &[(TxnIndex, SignedTransaction)],
+        state_view: &impl StateView,
+    ) -> Result<(Vec<TransactionOutput>, Vec<StateKey>), Error> {
+        if transactions.is_empty() {
+            return Ok((vec![], vec![]));
+        }
+        let _timer = counters::APTOS_REMOTE_EXECUTOR_SUB_BLOCK_SECONDS.start_timer();
+
+        // Sends the values the transactions are expected to access along with them, to save
+        // round trips.
+        let keys: BTreeSet<_> = transactions
+            .iter()
+            .flat_map(|(_, txn)| {
+                let summary = DeclaredReadWriteSetAnalyzer.analyze(txn);
+                summary
+                    .reads()
+                    .iter()
+                    .chain(summary.writes())
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let mut served = keys.clone();
+        let usage = state_view
+            .get_usage()
+            .map_err(|e| Error::StateViewError(e.to_string()))?;
+        let sub_block = ExecuteSubBlock {
+            transactions: transactions
+                .iter()
+                .map(|(_, txn)| Transaction::UserTransaction(txn.clone()))
+                .collect(),
+            state_values: read_state_values(state_view, keys)?,
+            usage: (!usage.is_untracked()).then(|| (usage.items(), usage.bytes())),
+        };
+        let sub_block_hash = sub_block.hash();
+
+        let mut network_client = self.shards[shard_id].lock();
+        network_client.write(&seal(&CoordinatorMessage::ExecuteSubBlock(sub_block))?)?;
+        loop {
+            match open(&network_client.read()?)? {
+                ExecutorServiceMessage::ReadState(keys) => {
+                    counters::APTOS_REMOTE_EXECUTOR_STATE_READS.inc_by(keys.len() as u64);
+                    served.extend(keys.iter().cloned());
+                    let values = read_state_values(state_view, keys)?;
+                    network_client.write(&seal(&CoordinatorMessage::StateValues(values))?)?;
+                }
+                ExecutorServiceMessage::SubBlockExecuted(result) => {
+                    let outputs = check_result(result, sub_block_hash, transactions.len())?;
+                    check_writes_served(&outputs, &served)?;
+                    return Ok((outputs, served.into_iter().collect()));
+                }
+            }
+        }
+    }
+}
+
+/// The range of the user transactions of a block, which follow its block metadata and validator
+/// transactions and may be followed by a state checkpoint. None if the block has no user
+/// transactions or another shape.
+fn user_transactions_range(transactions: &[Transaction]) -> Option<(usize, usize)> {
+    let is_user_txn = |txn: &Transaction| matches!(txn, Transaction::UserTransaction(_));
+    let start = transactions
+        .iter()
+        .take_while(|txn| {
+            matches!(
+                txn,
+                Transaction::BlockMetadata(_) | Transaction::ValidatorTransaction(_)
+            )
+        })
+        .count();
+    let end = start
+        + transactions[start..]
+            .iter()
+            .take_while(|txn| is_user_txn(txn))
+            .count();
+    if start == end {
+        return None;
+    }
+    match &transactions[end..] {
+        [] | [Transaction::StateCheckpoint(_)] => Some((start, end)),
+        _ => None,
+    }
+}
+
+/// Splits the sub-blocks into the transactions of each shard executed in the first round, which
+/// depend on no transaction of another shard, even through an earlier transaction of their shard,
+/// and the others, executed in block order in the second round.
+#[allow(clippy::type_complexity)]
+fn split_rounds(
+    block: PartitionedBlock,
+) -> (
+    Vec<Vec<(TxnIndex, SignedTransaction)>>,
+    Vec<(TxnIndex, SignedTransaction)>,
+) {
+    let dependents: BTreeSet<_> = block
+        .cross_shard_dependencies()
+        .iter()
+        .map(|(_, target)| *target)
+        .collect();
+    let mut second_round = vec![];
+    let first_round = block
+        .into_sub_blocks()
+        .into_iter()
+        .map(|sub_block| {
+            let mut transactions = sub_block.into_transactions();
+            let split = transactions
+                .iter()
+                .position(|(index, _)| dependents.contains(index))
+                .unwrap_or(transactions.len());
+            second_round.extend(transactions.split_off(split));
+            transactions
+        })
+        .collect();
+    second_round.sort_by_key(|(index, _)| *index);
+    (first_round, second_round)
+}
+
+/// The outputs of a sub-block, along with the indices of their transactions in the block
+fn with_indices(
+    sub_block: &[(TxnIndex, SignedTransaction)],
+    outputs: Vec<TransactionOutput>,
+) -> Vec<(TxnIndex, TransactionOutput)> {
+    sub_block
+        .iter()
+        .map(|(index, _)| *index)
+        .zip(outputs)
+        .collect()
+}
+
+/// Executes the block locally, without the parallel executor, used once sharded execution failed.
+fn execute_block_sequentially(
+    transactions: Vec<Transaction>,
+    state_view: &impl StateView,
+) -> Result<Vec<TransactionOutput>, VMStatus> {
+    Ok(
+        AptosVM::execute_block_and_keep_vm_status(transactions, state_view)?
+            .into_iter()
+            .map(|(_vm_status, output)| output)
+            .collect(),
+    )
+}
+
+fn read_state_values(
+    state_view: &impl StateView,
+    keys: impl IntoIterator<Item = StateKey>,
+) -> Result<Vec<(StateKey, Option<Vec<u8>>)>, Error> {
+    keys.into_iter()
+        .map(|key| {
+            let value = state_view
+                .get_state_value(&key)
+                .map_err(|e| Error::StateViewError(e.to_string()))?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+/// Checks the result is for the sub-block sent, with an output for each transaction, and that no
+/// transaction ended the epoch, which requires the transactions after it to be retried.
+fn check_result(
+    result: SubBlockExecutionResult,
+    sub_block_hash: HashValue,
+    num_txns: usize,
+) -> Result<Vec<TransactionOutput>, Error> {
+    if result.sub_block_hash != sub_block_hash {
+        return Err(Error::InvalidResult(format!(
+            "result for sub-block {}, rather than {}",
+            result.sub_block_hash, sub_block_hash
+        )));
+    }
+    let outputs = result.outputs.map_err(Error::InvalidResult)?;
+    if outputs.len() != num_txns {
+        return Err(Error::InvalidResult(format!(
+            "{} outputs for {} transactions",
+            outputs.len(),
+            num_txns
+        )));
+    }
+    if outputs
+        .iter()
+        .any(|output| ends_epoch(output) || output.status() == &TransactionStatus::Retry)
+    {
+        return Err(Error::InvalidResult(
+            "the epoch ended in the sub-block".into(),
+        ));
+    }
+    Ok(outputs)
+}
+
+/// Checks the outputs of a sub-block only write keys served to its executor service. The VM
+/// reads a key before writing it, so a write outside of them can't come from executing the
+/// sub-block, and would escape the conflict checks, which take the keys served as the keys read.
+fn check_writes_served(
+    outputs: &[TransactionOutput],
+    served: &BTreeSet<StateKey>,
+) -> Result<(), Error> {
+    match outputs
+        .iter()
+        .flat_map(|output| output.write_set())
+        .find(|(key, _)| !served.contains(key))
+    {
+        Some((key, _)) => Err(Error::InvalidResult(format!(
+            "{:?} was written but never read",
+            key
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Checks no shard wrote a key another shard read or wrote, in which case the shards depended on
+/// each other.
+fn check_no_conflicts(results: &[(Vec<TransactionOutput>, Vec<StateKey>)]) -> Result<(), Error> {
+    let mut writers = HashMap::new();
+    for (shard_id, (outputs, _)) in results.iter().enumerate() {
+        for (key, _) in outputs.iter().flat_map(|output| output.write_set()) {
+            if let Some(writer) = writers.insert(key, shard_id) {
+                if writer != shard_id {
+                    return Err(Error::InvalidResult(format!(
+                        "shards {} and {} both wrote {:?}",
+                        writer, shard_id, key
+                    )));
+                }
+            }
+        }
+    }
+    for (shard_id, (_, reads)) in results.iter().enumerate() {
+        for key in reads {
+            match writers.get(key) {
+                Some(writer) if *writer != shard_id => {
+                    return Err(Error::InvalidResult(format!(
+                        "shard {} read {:?}, written by shard {}",
+                        shard_id, key, writer
+                    )))
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the rounds executed as if the transactions were executed in block order. Both rounds
+/// executed on top of the writes of the whole first round, so when a transaction of one round
+/// precedes a transaction of the other, the earlier one must not have read what the later one
+/// wrote, and the later one must not have read what the earlier one wrote if it didn't execute on
+/// top of it. The keys read are only known per sub-block, so any transaction of a sub-block is
+/// taken to have read them.
+#[allow(clippy::type_complexity)]
+fn check_rounds_in_order(
+    first_round: &[(Vec<(TxnIndex, TransactionOutput)>, Vec<StateKey>)],
+    second_round: &(Vec<(TxnIndex, TransactionOutput)>, Vec<StateKey>),
+) -> Result<(), Error> {
+    let (second_round_outputs, second_round_reads) = second_round;
+    let first_in_second_round = match second_round_outputs.iter().map(|(index, _)| *index).min() {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+    let second_round_reads: HashSet<_> = second_round_reads.iter().collect();
+
+    for (shard_id, (outputs, reads)) in first_round.iter().enumerate() {
+        // The second round read the writes of the later transactions of the first round.
+        for (index, output) in outputs {
+            if *index < first_in_second_round {
+                continue;
+            }
+            for (key, _) in output.write_set() {
+                if second_round_reads.contains(key) {
+                    return Err(Error::InvalidResult(format!(
+                        "transaction {} of shard {} wrote {:?}, read by an earlier transaction \
+                        of the second round",
+                        index, shard_id, key
+                    )));
+                }
+            }
+        }
+
+        // The first round didn't read the writes of the earlier transactions of the second round.
+        let last_in_shard = match outputs.iter().map(|(index, _)| *index).max() {
+            Some(index) => index,
+            None => continue,
+        };
+        let reads: HashSet<_> = reads.iter().collect();
+        for (index, output) in second_round_outputs {
+            if *index > last_in_shard {
+                continue;
+            }
+            for (key, _) in output.write_set() {
+                if reads.contains(key) {
+                    return Err(Error::InvalidResult(format!(
+                        "transaction {} of the second round wrote {:?}, read by a later \
+                        transaction of shard {}",
+                        index, key, shard_id
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn ends_epoch(output: &TransactionOutput) -> bool {
+    let new_epoch_event_key = new_epoch_event_key();
+    output
+        .events()
+        .iter()
+        .any(|event| *event.key() == new_epoch_event_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::{
+        account_address::AccountAddress,
+        block_metadata::BlockMetadata,
+        test_helpers::transaction_test_helpers::get_test_signed_txn,
+        write_set::{WriteOp, WriteSetMut},
+    };
+
+    fn user_txn(sender: AccountAddress, seq_num: u64) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        get_test_signed_txn(
+            sender,
+            seq_num,
+            &private_key,
+            private_key.public_key(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_user_transactions_range() {
+        let metadata = Transaction::BlockMetadata(BlockMetadata::new(
+            HashValue::zero(),
+            1,
+            1,
+            AccountAddress::random(),
+            vec![],
+            vec![],
+            1,
+        ));
+        let user = Transaction::UserTransaction(user_txn(AccountAddress::random(), 0));
+        let checkpoint = Transaction::StateCheckpoint(HashValue::zero());
+
+        assert_eq!(
+            user_transactions_range(&[
+                metadata.clone(),
+                user.clone(),
+                user.clone(),
+                checkpoint.clone()
+            ]),
+            Some((1, 3))
+        );
+        assert_eq!(
+            user_transactions_range(&[user.clone(), user.clone()]),
+            Some((0, 2))
+        );
+        assert_eq!(
+            user_transactions_range(&[metadata.clone(), checkpoint.clone()]),
+            None
+        );
+        assert_eq!(
+            user_transactions_range(&[metadata.clone(), user.clone(), metadata, user]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_split_rounds() {
+        let sender = AccountAddress::random();
+        // Every transaction of the sender depends on the previous one, and the group is split
+        // across the two shards as none may hold more than half of the block.
+        let mut txns: Vec<_> = (0..6).map(|i| user_txn(sender, i)).collect();
+        txns.extend((0..2).map(|_| user_txn(AccountAddress::random(), 0)));
+        let partitioner = BlockPartitioner::new(
+            PartitionerConfig {
+                num_shards: 2,
+                hot_key_fraction: 1.0,
+                max_imbalance: 0.0,
+            },
+            DeclaredReadWriteSetAnalyzer,
+        );
+        let block = partitioner.partition(txns);
+        let dependents: Vec<_> = block
+            .cross_shard_dependencies()
+            .iter()
+            .map(|(_, target)| *target)
+            .collect();
+        assert!(!dependents.is_empty());
+
+        let (first_round, second_round) = split_rounds(block);
+        let second_round: Vec<_> = second_round.iter().map(|(index, _)| *index).collect();
+        for dependent in dependents {
+            assert!(second_round.contains(&dependent));
+        }
+        let mut sorted = second_round.clone();
+        sorted.sort_unstable();
+        assert_eq!(second_round, sorted);
+        assert_eq!(
+            first_round.iter().map(Vec::len).sum::<usize>() + second_round.len(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_check_no_conflicts() {
+        let output = |key: &[u8]| {
+            let write_set = WriteSetMut::new(vec![(
+                StateKey::Raw(key.to_vec()),
+                WriteOp::Modification(vec![]),
+            )])
+            .freeze()
+            .unwrap();
+            TransactionOutput::new(write_set, vec![], 0, TransactionStatus::Retry)
+        };
+        let key = |key: &[u8]| StateKey::Raw(key.to_vec());
+
+        assert!(check_no_conflicts(&[
+            (vec![output(b"a"), output(b"a")], vec![key(b"a"), key(b"c")]),
+            (vec![output(b"b")], vec![key(b"c")]),
+        ])
+        .is_ok());
+        // Both shards write a
+        assert!(
+            check_no_conflicts(&[(vec![output(b"a")], vec![]), (vec![output(b"a")], vec![]),])
+                .is_err()
+        );
+        // The second shard reads what the first one writes
+        assert!(check_no_conflicts(&[
+            (vec![output(b"a")], vec![]),
+            (vec![output(b"b")], vec![key(b"a")]),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_writes_served() {
+        let output = |key: &[u8]| {
+            let write_set = WriteSetMut::new(vec![(
+                StateKey::Raw(key.to_vec()),
+                WriteOp::Modification(vec![]),
+            )])
+            .freeze()
+            .unwrap();
+            TransactionOutput::new(write_set, vec![], 0, TransactionStatus::Retry)
+        };
+        let served: BTreeSet<_> = vec![StateKey::Raw(b"a".to_vec()), StateKey::Raw(b"b".to_vec())]
+            .into_iter()
+            .collect();
+
+        assert!(check_writes_served(&[output(b"a"), output(b"b")], &served).is_ok());
+        assert!(check_writes_served(&[output(b"a"), output(b"c")], &served).is_err());
+    }
+
+    #[test]
+    fn test_check_rounds_in_order() {
+        let output = |key: &[u8]| {
+            let write_set = WriteSetMut::new(vec![(
+                StateKey::Raw(key.to_vec()),
+                WriteOp::Modification(vec![]),
+            )])
+            .freeze()
+            .unwrap();
+            TransactionOutput::new(write_set, vec![], 0, TransactionStatus::Retry)
+        };
+        let key = |key: &[u8]| StateKey::Raw(key.to_vec());
+
+        // The first round wrote a before the second round read it, and the second round wrote b
+        // after the first round read it.
+        assert!(check_rounds_in_order(
+            &[(vec![(0, output(b"a"))], vec![key(b"b")])],
+            &(vec![(1, output(b"b"))], vec![key(b"a")]),
+        )
+        .is_ok());
+        // The second round read a, written by a later transaction of the first round
+        assert!(check_rounds_in_order(
+            &[(vec![(1, output(b"a"))], vec![])],
+            &(vec![(0, output(b"b"))], vec![key(b"a")]),
+        )
+        .is_err());
+        // The first round read b, written by an earlier transaction of the second round
+        assert!(check_rounds_in_order(
+            &[
+                (vec![(0, output(b"a"))], vec![]),
+                (vec![(2, output(b"c"))], vec![key(b"b")])
+            ],
+            &(vec![(1, output(b"b"))], vec![]),
+        )
+        .is_err());
+        // An empty second round can't conflict
+        assert!(
+            check_rounds_in_order(&[(vec![(1, output(b"a"))], vec![])], &(vec![], vec![])).is_ok()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    exponential_buckets, register_histogram, register_int_counter, register_int_counter_vec,
+    Histogram, IntCounter, IntCounterVec,
+};
+use once_cell::sync::Lazy;
+
+/// Count of the blocks executed by the coordinator, by how: "remote" when sharded across the
+/// executor services, "local" when they can't be sharded, and "fallback" when remote execution
+/// failed
+pub static APTOS_REMOTE_EXECUTOR_BLOCKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_remote_executor_blocks",
+        "Count of the blocks executed by the coordinator, by how",
+        &["mode"]
+    )
+    .unwrap()
+});
+
+pub static APTOS_REMOTE_EXECUTOR_EXECUTE_BLOCK_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_remote_executor_execute_block_seconds",
+        // metric description
+        "The time spent in seconds of executing a block across the executor services",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static APTOS_REMOTE_EXECUTOR_SUB_BLOCK_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_remote_executor_sub_block_seconds",
+        // metric description
+        "The time spent in seconds of executing a sub-block on an executor service, round trips included",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static APTOS_REMOTE_EXECUTOR_STATE_READS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_remote_executor_state_reads",
+        "Cumulative number of state values requested by the executor services"
+    )
+    .unwrap()
+});
+
+pub static APTOS_EXECUTOR_SERVICE_EXECUTE_SUB_BLOCK_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_executor_service_execute_sub_block_seconds",
+        // metric description
+        "The time spent in seconds of executing a sub-block in the executor service",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static APTOS_EXECUTOR_SERVICE_REMOTE_READS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_executor_service_remote_reads",
+        "Cumulative number of state values the executor service requested from the coordinator"
+    )
+    .unwrap()
+});
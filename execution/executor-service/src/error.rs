@@ -0,0 +1,30 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Checksum mismatch, the message hashes to {actual} rather than {expected}")]
+    ChecksumMismatch {
+        expected: HashValue,
+        actual: HashValue,
+    },
+    #[error("Invalid execution result: {0}")]
+    InvalidResult(String),
+    #[error("Network error: {0}")]
+    NetworkError(#[from] aptos_secure_net::Error),
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+    #[error("State view error: {0}")]
+    StateViewError(String),
+    #[error("Unexpected message: {0}")]
+    UnexpectedMessage(String),
+}
+
+impl From<bcs::Error> for Error {
+    fn from(error: bcs::Error) -> Self {
+        Self::SerializationError(error.to_string())
+    }
+}
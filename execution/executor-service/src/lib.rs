@@ -0,0 +1,24 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Executes blocks on executor services running on other machines than consensus, so that the
+//! throughput of a validator isn't capped by the execution capacity of a single machine.
+//!
+//! The coordinator, running along consensus, partitions the user transactions of a block into
+//! sub-blocks with the block partitioner, and sends each sub-block to an executor service along
+//! with the state values it is expected to read. The executor service requests the other values
+//! it reads from the coordinator, and returns the output of each transaction. Every message is
+//! checksummed, each result is checked to be for the sub-block sent and to only write keys served
+//! to it, and the shards are checked not to conflict on the keys served to them. The connections
+//! are authenticated and encrypted.
+
+mod coordinator;
+mod counters;
+mod error;
+pub mod messages;
+mod remote_state_view;
+mod service;
+
+pub use crate::{coordinator::RemoteExecutorClient, error::Error, service::ExecutorService};
@@ -0,0 +1,105 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! The messages exchanged between a coordinator and an executor service. To execute a sub-block,
+//! the coordinator sends an [`ExecuteSubBlock`], then answers the [`ReadState`] requests of the
+//! executor service until it gets the [`SubBlockExecutionResult`].
+//!
+//! [`ReadState`]: ExecutorServiceMessage::ReadState
+
+use crate::Error;
+use aptos_crypto::HashValue;
+use aptos_types::{
+    state_store::state_key::StateKey,
+    transaction::{Transaction, TransactionOutput},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The transactions of a sub-block, with the state values the coordinator expects them to read.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExecuteSubBlock {
+    pub transactions: Vec<Transaction>,
+    pub state_values: Vec<(StateKey, Option<Vec<u8>>)>,
+    /// The number of items and bytes in the state storage, None if not tracked
+    pub usage: Option<(usize, usize)>,
+}
+
+impl ExecuteSubBlock {
+    pub fn hash(&self) -> HashValue {
+        HashValue::sha3_256_of(&bcs::to_bytes(self).expect("Unable to serialize sub-block"))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum CoordinatorMessage {
+    ExecuteSubBlock(ExecuteSubBlock),
+    /// The values of the keys requested by the executor service, `None` if nothing is stored
+    StateValues(Vec<(StateKey, Option<Vec<u8>>)>),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ExecutorServiceMessage {
+    /// Requests the values of keys read by the sub-block which were not sent with it
+    ReadState(Vec<StateKey>),
+    SubBlockExecuted(SubBlockExecutionResult),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SubBlockExecutionResult {
+    /// The hash of the sub-block executed
+    pub sub_block_hash: HashValue,
+    /// The output of each transaction, or why the sub-block failed to execute
+    pub outputs: Result<Vec<TransactionOutput>, String>,
+}
+
+/// A message along with the hash of its serialization, checked on receipt.
+#[derive(Deserialize, Serialize)]
+struct Envelope {
+    payload: Vec<u8>,
+    checksum: HashValue,
+}
+
+/// Serializes a message to be sent
+pub fn seal<T: Serialize>(message: &T) -> Result<Vec<u8>, Error> {
+    let payload = bcs::to_bytes(message)?;
+    let checksum = HashValue::sha3_256_of(&payload);
+    Ok(bcs::to_bytes(&Envelope { payload, checksum })?)
+}
+
+/// Deserializes a message received, after checking it wasn't corrupted
+pub fn open<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let envelope: Envelope = bcs::from_bytes(bytes)?;
+    let actual = HashValue::sha3_256_of(&envelope.payload);
+    if actual != envelope.checksum {
+        return Err(Error::ChecksumMismatch {
+            expected: envelope.checksum,
+            actual,
+        });
+    }
+    Ok(bcs::from_bytes(&envelope.payload)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let message = CoordinatorMessage::StateValues(vec![
+            (StateKey::Raw(b"a".to_vec()), Some(b"value".to_vec())),
+            (StateKey::Raw(b"b".to_vec()), None),
+        ]);
+        let sealed = seal(&message).unwrap();
+        assert_eq!(open::<CoordinatorMessage>(&sealed).unwrap(), message);
+
+        // A corrupted payload doesn't match the checksum.
+        let mut envelope: Envelope = bcs::from_bytes(&sealed).unwrap();
+        let last = envelope.payload.len() - 1;
+        envelope.payload[last] ^= 1;
+        let corrupted = bcs::to_bytes(&envelope).unwrap();
+        assert!(matches!(
+            open::<CoordinatorMessage>(&corrupted),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    messages::{open, seal, CoordinatorMessage, ExecutorServiceMessage},
+    Error,
+};
+use anyhow::Result;
+use aptos_infallible::{Mutex, RwLock};
+use aptos_secure_net::NetworkServer;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView};
+use aptos_types::state_store::state_key::StateKey;
+use std::collections::HashMap;
+
+/// The state a sub-block is executed against by an executor service: the values sent along with
+/// the sub-block, and the values of the other keys it reads, requested from the coordinator.
+pub(crate) struct RemoteStateView<'a> {
+    values: RwLock<HashMap<StateKey, Option<Vec<u8>>>>,
+    usage: StateStorageUsage,
+    network_server: Mutex<&'a mut NetworkServer>,
+}
+
+impl<'a> RemoteStateView<'a> {
+    pub fn new(
+        network_server: &'a mut NetworkServer,
+        values: Vec<(StateKey, Option<Vec<u8>>)>,
+        usage: Option<(usize, usize)>,
+    ) -> Self {
+        let usage = match usage {
+            Some((items, bytes)) => StateStorageUsage::new(items, bytes),
+            None => StateStorageUsage::new_untracked(),
+        };
+        Self {
+            values: RwLock::new(values.into_iter().collect()),
+            usage,
+            network_server: Mutex::new(network_server),
+        }
+    }
+
+    fn read_from_coordinator(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>, Error> {
+        let mut network_server = self.network_server.lock();
+        // Another thread may have read it while this one waited for the lock.
+        if let Some(value) = self.values.read().get(state_key) {
+            return Ok(value.clone());
+        }
+
+        counters::APTOS_EXECUTOR_SERVICE_REMOTE_READS.inc();
+        let request = ExecutorServiceMessage::ReadState(vec![state_key.clone()]);
+        network_server.write(&seal(&request)?)?;
+        let values = match open(&network_server.read()?)? {
+            CoordinatorMessage::StateValues(values) => values,
+            CoordinatorMessage::ExecuteSubBlock(_) => {
+                return Err(Error::UnexpectedMessage(
+                    "sub-block received while reading state".into(),
+                ))
+            }
+        };
+
+        let mut cached = self.values.write();
+        cached.extend(values);
+        cached.get(state_key).cloned().ok_or_else(|| {
+            Error::StateViewError(format!("The coordinator didn't send {:?}", state_key))
+        })
+    }
+}
+
+impl<'a> StateView for RemoteStateView<'a> {
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        if let Some(value) = self.values.read().get(state_key) {
+            return Ok(value.clone());
+        }
+        Ok(self.read_from_coordinator(state_key)?)
+    }
+
+    fn is_genesis(&self) -> bool {
+        // Genesis is never sharded.
+        false
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        Ok(self.usage)
+    }
+}
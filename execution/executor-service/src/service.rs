@@ -0,0 +1,74 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    messages::{
+        open, seal, CoordinatorMessage, ExecuteSubBlock, ExecutorServiceMessage,
+        SubBlockExecutionResult,
+    },
+    remote_state_view::RemoteStateView,
+    Error,
+};
+use aptos_config::config::ExecutorServiceConfig;
+use aptos_logger::{info, warn};
+use aptos_secure_net::NetworkServer;
+use aptos_vm::{AptosVM, VMExecutor};
+
+/// Executes the sub-blocks sent by a coordinator, one at a time.
+pub struct ExecutorService {
+    network_server: NetworkServer,
+}
+
+impl ExecutorService {
+    pub fn new(config: &ExecutorServiceConfig) -> Self {
+        AptosVM::set_concurrency_level_once(config.concurrency_level as usize);
+        let network_server = NetworkServer::new_authenticated(
+            "executor-service",
+            config.listen_address,
+            config.network_timeout_ms,
+            config.authentication.identity_key.private_key(),
+            config.authentication.trusted_clients.clone(),
+        );
+        Self { network_server }
+    }
+
+    pub fn start(&mut self) {
+        info!("Executor service started.");
+        loop {
+            if let Err(e) = self.process_one_message() {
+                warn!("Failed to process message: {}", e);
+            }
+        }
+    }
+
+    fn process_one_message(&mut self) -> Result<(), Error> {
+        let sub_block = match open(&self.network_server.read()?)? {
+            CoordinatorMessage::ExecuteSubBlock(sub_block) => sub_block,
+            CoordinatorMessage::StateValues(_) => {
+                return Err(Error::UnexpectedMessage(
+                    "state values received while no sub-block is executed".into(),
+                ))
+            }
+        };
+        let result = execute_sub_block(&mut self.network_server, sub_block);
+        let response = ExecutorServiceMessage::SubBlockExecuted(result);
+        self.network_server.write(&seal(&response)?)?;
+        Ok(())
+    }
+}
+
+fn execute_sub_block(
+    network_server: &mut NetworkServer,
+    sub_block: ExecuteSubBlock,
+) -> SubBlockExecutionResult {
+    let _timer = counters::APTOS_EXECUTOR_SERVICE_EXECUTE_SUB_BLOCK_SECONDS.start_timer();
+    let sub_block_hash = sub_block.hash();
+    let state_view = RemoteStateView::new(network_server, sub_block.state_values, sub_block.usage);
+    let outputs =
+        AptosVM::execute_block(sub_block.transactions, &state_view).map_err(|e| format!("{:?}", e));
+    SubBlockExecutionResult {
+        sub_block_hash,
+        outputs,
+    }
+}
@@ -19,15 +19,17 @@ use aptos_types::{
     proof::{accumulator::InMemoryAccumulator, AccumulatorExtensionProof, SparseMerkleProofExt},
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
-        Transaction, TransactionInfo, TransactionListWithProof, TransactionOutputListWithProof,
-        TransactionStatus, Version,
+        Transaction, TransactionInfo, TransactionListWithProof, TransactionOutput,
+        TransactionOutputListWithProof, TransactionStatus, Version,
     },
+    vm_status::VMStatus,
     write_set::WriteSet,
 };
 pub use error::Error;
 pub use executed_chunk::ExecutedChunk;
 pub use parsed_transaction_output::ParsedTransactionOutput;
 use scratchpad::{ProofRead, SparseMerkleTree};
+use storage_interface::cached_state_view::CachedStateView;
 
 mod error;
 mod executed_chunk;
@@ -131,6 +133,17 @@ pub trait BlockExecutorTrait: Send + Sync {
     fn finish(&self);
 }
 
+/// Executes the transactions of the blocks of a block executor in place of its VM, e.g. across
+/// executor services running on other machines.
+pub trait TransactionBlockExecutor: Send + Sync {
+    /// Executes a block of transactions and returns output for each one of them.
+    fn execute_transaction_block(
+        &self,
+        transactions: Vec<Transaction>,
+        state_view: &CachedStateView,
+    ) -> Result<Vec<TransactionOutput>, VMStatus>;
+}
+
 pub trait TransactionReplayer: Send {
     fn replay(
         &self,
@@ -10,11 +10,13 @@ use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
 use aptos_state_view::StateViewId;
 use aptos_types::{
-    ledger_info::LedgerInfoWithSignatures, state_store::state_value::StateValue,
+    ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::{OnChainConfig, OnChainConsensusConfig},
+    state_store::state_value::StateValue,
     transaction::Transaction,
 };
-use aptos_vm::VMExecutor;
-use executor_types::{BlockExecutorTrait, Error, StateComputeResult};
+use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::WarmVmCache, VMExecutor};
+use executor_types::{BlockExecutorTrait, Error, StateComputeResult, TransactionBlockExecutor};
 use fail::fail_point;
 use scratchpad::SparseMerkleTree;
 use std::{marker::PhantomData, sync::Arc};
@@ -23,6 +25,7 @@ use storage_interface::async_proof_fetcher::AsyncProofFetcher;
 use crate::{
     components::{block_tree::BlockTree, chunk_output::ChunkOutput},
     metrics::{
+        APTOS_EXECUTOR_BLOCK_OUTPUT_LIMIT_RETRIED_TRANSACTIONS,
         APTOS_EXECUTOR_COMMIT_BLOCKS_SECONDS, APTOS_EXECUTOR_EXECUTE_BLOCK_SECONDS,
         APTOS_EXECUTOR_SAVE_TRANSACTIONS_SECONDS, APTOS_EXECUTOR_TRANSACTIONS_SAVED,
         APTOS_EXECUTOR_VM_EXECUTE_BLOCK_SECONDS,
@@ -32,6 +35,8 @@ use storage_interface::DbReaderWriter;
 
 pub struct BlockExecutor<V> {
     pub db: DbReaderWriter,
+    /// Executes the transactions of the blocks in place of `V`, if set.
+    transaction_block_executor: Option<Arc<dyn TransactionBlockExecutor>>,
     inner: RwLock<Option<BlockExecutorInner<V>>>,
 }
 
@@ -42,6 +47,20 @@ where
     pub fn new(db: DbReaderWriter) -> Self {
         Self {
             db,
+            transaction_block_executor: None,
+            inner: RwLock::new(None),
+        }
+    }
+
+    /// Creates a block executor executing the transactions of the blocks with
+    /// `transaction_block_executor` rather than with `V`.
+    pub fn new_with_transaction_block_executor(
+        db: DbReaderWriter,
+        transaction_block_executor: Arc<dyn TransactionBlockExecutor>,
+    ) -> Self {
+        Self {
+            db,
+            transaction_block_executor: Some(transaction_block_executor),
             inner: RwLock::new(None),
         }
     }
@@ -76,7 +95,10 @@ where
     }
 
     fn reset(&self) -> Result<()> {
-        *self.inner.write() = Some(BlockExecutorInner::new(self.db.clone())?);
+        *self.inner.write() = Some(BlockExecutorInner::new(
+            self.db.clone(),
+            self.transaction_block_executor.clone(),
+        )?);
         Ok(())
     }
 
@@ -113,7 +135,10 @@ where
 
 struct BlockExecutorInner<V> {
     db: DbReaderWriter,
+    transaction_block_executor: Option<Arc<dyn TransactionBlockExecutor>>,
     block_tree: BlockTree,
+    /// The Move VM shared by the blocks, valid for the states identified by block ids.
+    warm_vm_cache: WarmVmCache,
     phantom: PhantomData<V>,
 }
 
@@ -121,11 +146,16 @@ impl<V> BlockExecutorInner<V>
 where
     V: VMExecutor,
 {
-    pub fn new(db: DbReaderWriter) -> Result<Self> {
+    pub fn new(
+        db: DbReaderWriter,
+        transaction_block_executor: Option<Arc<dyn TransactionBlockExecutor>>,
+    ) -> Result<Self> {
         let block_tree = BlockTree::new(&db.reader)?;
         Ok(Self {
             db,
+            transaction_block_executor,
             block_tree,
+            warm_vm_cache: WarmVmCache::new(),
             phantom: PhantomData,
         })
     }
@@ -190,6 +220,10 @@ where
                 Arc::clone(&self.db.reader),
                 Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
             )?;
+            // Read from the parent state, so that every validator cuts the block at the same place.
+            let block_output_limit =
+                OnChainConsensusConfig::fetch_config(&state_view.as_move_resolver())
+                    .and_then(|config| config.block_output_limit());
 
             let chunk_output = {
                 let _timer = APTOS_EXECUTOR_VM_EXECUTE_BLOCK_SECONDS.start_timer();
@@ -198,8 +232,50 @@ where
                         "Injected error in vm_execute_block"
                     )))
                 });
-                ChunkOutput::by_transaction_execution::<V>(transactions, state_view)?
+                if let Some(transaction_block_executor) = &self.transaction_block_executor {
+                    ChunkOutput::by_transaction_block_executor(
+                        transaction_block_executor.as_ref(),
+                        transactions,
+                        state_view,
+                        block_output_limit.as_ref(),
+                    )?
+                } else {
+                    self.warm_vm_cache.start_execution(parent_block_id);
+                    let chunk_output = match &block_output_limit {
+                        Some(limit) => {
+                            ChunkOutput::by_transaction_execution_with_output_limit::<V>(
+                                transactions,
+                                state_view,
+                                parent_view.num_transactions(),
+                                &self.warm_vm_cache,
+                                limit,
+                            )?
+                        }
+                        None => ChunkOutput::by_transaction_execution_with_warm_vm::<V>(
+                            transactions,
+                            state_view,
+                            parent_view.num_transactions(),
+                            &self.warm_vm_cache,
+                        )?,
+                    };
+                    self.warm_vm_cache
+                        .finish_execution(parent_block_id, block_id);
+                    chunk_output
+                }
             };
+            if block_output_limit.is_some() {
+                let num_retried = chunk_output.num_cut_by_output_limit();
+                if num_retried > 0 {
+                    info!(
+                        LogSchema::new(LogEntry::BlockExecutor)
+                            .block_id(block_id)
+                            .num(num_retried as u64),
+                        "block_output_limit_reached"
+                    );
+                    APTOS_EXECUTOR_BLOCK_OUTPUT_LIMIT_RETRIED_TRANSACTIONS
+                        .inc_by(num_retried as u64);
+                }
+            }
             chunk_output.trace_log_transaction_status();
 
             let (output, _, _) = chunk_output.apply_to_ledger(parent_view)?;
@@ -25,17 +25,27 @@ use aptos_types::{
         Transaction, TransactionInfo, TransactionListWithProof, TransactionOutputListWithProof,
     },
 };
-use aptos_vm::VMExecutor;
+use aptos_vm::{move_vm_ext::WarmVmCache, VMExecutor};
 use executor_types::{
     ChunkCommitNotification, ChunkExecutorTrait, ExecutedChunk, TransactionReplayer,
 };
 use fail::fail_point;
+use once_cell::sync::Lazy;
 use std::{marker::PhantomData, sync::Arc};
 use storage_interface::{
     cached_state_view::CachedStateView, sync_proof_fetcher::SyncProofFetcher, DbReaderWriter,
     ExecutedTrees,
 };
 
+/// The pool verifying and hashing the transaction outputs of the chunks applied by state sync, so
+/// that applying outputs isn't bound by a single core.
+static VERIFICATION_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
+    rayon::ThreadPoolBuilder::new()
+        .thread_name(|index| format!("chunk_verify_{}", index))
+        .build()
+        .unwrap()
+});
+
 pub struct ChunkExecutor<V> {
     db: DbReaderWriter,
     inner: RwLock<Option<ChunkExecutorInner<V>>>,
@@ -140,6 +150,8 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
 struct ChunkExecutorInner<V> {
     db: DbReaderWriter,
     commit_queue: Mutex<ChunkCommitQueue>,
+    /// The Move VM shared by the chunks, which are all executed against the latest state.
+    warm_vm_cache: WarmVmCache,
     _phantom: PhantomData<V>,
 }
 
@@ -149,6 +161,7 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         Ok(Self {
             db,
             commit_queue,
+            warm_vm_cache: WarmVmCache::new(),
             _phantom: PhantomData,
         })
     }
@@ -238,7 +251,12 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         let state_view = self.state_view(&latest_view)?;
         let chunk_output = {
             let _timer = APTOS_EXECUTOR_VM_EXECUTE_CHUNK_SECONDS.start_timer();
-            ChunkOutput::by_transaction_execution::<V>(transactions, state_view)?
+            ChunkOutput::by_transaction_execution_with_warm_vm::<V>(
+                transactions,
+                state_view,
+                latest_view.num_transactions(),
+                &self.warm_vm_cache,
+            )?
         };
         let executed_chunk = Self::apply_chunk_output_for_state_sync(
             verified_target_li,
@@ -275,8 +293,10 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         let (_persisted_view, latest_view) = self.commit_queue.lock().persisted_and_latest_view();
 
         // Verify input transaction list.
-        txn_output_list_with_proof
-            .verify(verified_target_li.ledger_info(), first_version_in_request)?;
+        VERIFICATION_POOL.install(|| {
+            txn_output_list_with_proof
+                .verify(verified_target_li.ledger_info(), first_version_in_request)
+        })?;
 
         // Skip transactions already in ledger.
         let txns_to_skip = txn_output_list_with_proof.proof.verify_extends_ledger(
@@ -290,13 +310,19 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         // Apply transaction outputs.
         let state_view = self.state_view(&latest_view)?;
         let chunk_output = ChunkOutput::by_transaction_output(txns_and_outputs, state_view)?;
-        let executed_chunk = Self::apply_chunk_output_for_state_sync(
-            verified_target_li,
-            epoch_change_li,
-            &latest_view,
-            chunk_output,
-            &txn_output_list_with_proof.proof.transaction_infos[txns_to_skip..],
-        )?;
+        self.warm_vm_cache.record_outputs(
+            latest_view.num_transactions(),
+            &chunk_output.transaction_outputs,
+        );
+        let executed_chunk = VERIFICATION_POOL.install(|| {
+            Self::apply_chunk_output_for_state_sync(
+                verified_target_li,
+                epoch_change_li,
+                &latest_view,
+                chunk_output,
+                &txn_output_list_with_proof.proof.transaction_infos[txns_to_skip..],
+            )
+        })?;
 
         // Add result to commit queue.
         self.commit_queue.lock().enqueue(executed_chunk);
@@ -382,8 +408,13 @@ impl<V: VMExecutor> TransactionReplayer for ChunkExecutorInner<V> {
             let state_view = self.state_view(&latest_view)?;
             let txns = to_run.take().unwrap();
             let (executed, to_discard, to_retry) =
-                ChunkOutput::by_transaction_execution::<V>(txns, state_view)?
-                    .apply_to_ledger(&latest_view)?;
+                ChunkOutput::by_transaction_execution_with_warm_vm::<V>(
+                    txns,
+                    state_view,
+                    latest_view.num_transactions(),
+                    &self.warm_vm_cache,
+                )?
+                .apply_to_ledger(&latest_view)?;
 
             // Accumulate result and deal with retry
             ensure_no_discard(to_discard)?;
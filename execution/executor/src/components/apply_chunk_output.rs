@@ -19,6 +19,7 @@ use executor_types::{
     in_memory_state_calculator::InMemoryStateCalculator, ExecutedChunk, ParsedTransactionOutput,
     TransactionData,
 };
+use rayon::prelude::*;
 use std::{collections::HashMap, iter::repeat, sync::Arc};
 use storage_interface::ExecutedTrees;
 
@@ -107,6 +108,17 @@ impl ApplyChunkOutput {
                     matches!(o.status(), TransactionStatus::Keep(_))
                 });
 
+        // Transactions before the epoch ending can be retried too, when the output of the block
+        // crossed its limit.
+        let (to_retry_before_epoch_ending, to_discard): (Vec<_>, Vec<_>) = to_discard
+            .into_iter()
+            .partition(|(_, o)| matches!(o.status(), TransactionStatus::Retry));
+        let to_retry = to_retry_before_epoch_ending
+            .into_iter()
+            .map(|(t, _)| t)
+            .chain(to_retry)
+            .collect();
+
         // Sanity check transactions with the Discard status:
         let to_discard = to_discard
             .into_iter()
@@ -143,47 +155,57 @@ impl ApplyChunkOutput {
         state_updates_vec: Vec<HashMap<StateKey, Option<StateValue>>>,
         state_checkpoint_hashes: Vec<Option<HashValue>>,
     ) -> (Vec<(Transaction, TransactionData)>, Vec<HashValue>) {
-        let mut to_commit = vec![];
-        let mut txn_info_hashes = vec![];
-        for (((txn, txn_output), state_checkpoint_hash), state_updates) in itertools::zip_eq(
-            itertools::zip_eq(to_keep, state_checkpoint_hashes),
-            state_updates_vec,
-        ) {
-            let (write_set, events, reconfig_events, gas_used, status) = txn_output.unpack();
-            let event_tree = {
-                let event_hashes: Vec<_> = events.iter().map(CryptoHash::hash).collect();
-                InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes)
-            };
-
-            let state_change_hash = CryptoHash::hash(&write_set);
-            let txn_info = match &status {
-                TransactionStatus::Keep(status) => TransactionInfo::new(
-                    txn.hash(),
-                    state_change_hash,
-                    event_tree.root_hash(),
-                    state_checkpoint_hash,
-                    gas_used,
-                    status.clone(),
-                ),
-                _ => unreachable!("Transaction sorted by status already."),
-            };
-            let txn_info_hash = txn_info.hash();
-            txn_info_hashes.push(txn_info_hash);
-            to_commit.push((
-                txn,
-                TransactionData::new(
-                    state_updates,
-                    write_set,
-                    events,
-                    reconfig_events,
-                    status,
-                    Arc::new(event_tree),
-                    gas_used,
-                    txn_info,
-                    txn_info_hash,
-                ),
-            ))
-        }
+        assert_eq!(to_keep.len(), state_checkpoint_hashes.len());
+        assert_eq!(to_keep.len(), state_updates_vec.len());
+        // The hashes of the transactions are independent, only appending them to the accumulator
+        // is sequential.
+        let to_commit: Vec<_> = to_keep
+            .into_par_iter()
+            .zip(state_checkpoint_hashes)
+            .zip(state_updates_vec)
+            .map(
+                |(((txn, txn_output), state_checkpoint_hash), state_updates)| {
+                    let (write_set, events, reconfig_events, gas_used, status) =
+                        txn_output.unpack();
+                    let event_tree = {
+                        let event_hashes: Vec<_> = events.iter().map(CryptoHash::hash).collect();
+                        InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes)
+                    };
+
+                    let state_change_hash = CryptoHash::hash(&write_set);
+                    let txn_info = match &status {
+                        TransactionStatus::Keep(status) => TransactionInfo::new(
+                            txn.hash(),
+                            state_change_hash,
+                            event_tree.root_hash(),
+                            state_checkpoint_hash,
+                            gas_used,
+                            status.clone(),
+                        ),
+                        _ => unreachable!("Transaction sorted by status already."),
+                    };
+                    let txn_info_hash = txn_info.hash();
+                    (
+                        txn,
+                        TransactionData::new(
+                            state_updates,
+                            write_set,
+                            events,
+                            reconfig_events,
+                            status,
+                            Arc::new(event_tree),
+                            gas_used,
+                            txn_info,
+                            txn_info_hash,
+                        ),
+                    )
+                },
+            )
+            .collect();
+        let txn_info_hashes = to_commit
+            .iter()
+            .map(|(_, txn_data)| txn_data.txn_info_hash())
+            .collect();
         (to_commit, txn_info_hashes)
     }
 }
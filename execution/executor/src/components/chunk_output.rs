@@ -7,9 +7,14 @@ use crate::components::apply_chunk_output::ApplyChunkOutput;
 use anyhow::Result;
 use aptos_logger::trace;
 use aptos_state_view::StateView;
-use aptos_types::transaction::{Transaction, TransactionOutput};
-use aptos_vm::VMExecutor;
-use executor_types::ExecutedChunk;
+use aptos_types::{
+    on_chain_config::BlockOutputLimit,
+    transaction::{Transaction, TransactionOutput, TransactionStatus, Version},
+};
+use aptos_vm::{block_output_limit, move_vm_ext::WarmVmCache, VMExecutor};
+use executor_types::{
+    in_memory_state_calculator::NEW_EPOCH_EVENT_KEY, ExecutedChunk, TransactionBlockExecutor,
+};
 use fail::fail_point;
 use std::collections::HashSet;
 use storage_interface::{
@@ -42,6 +47,84 @@ impl ChunkOutput {
         })
     }
 
+    /// Executes `transactions` like `by_transaction_execution`, sharing the Move VM kept in
+    /// `warm_vm_cache`, which is dropped if the execution fails or republishes modules it loaded.
+    /// The transactions are committed from `first_version` on.
+    pub fn by_transaction_execution_with_warm_vm<V: VMExecutor>(
+        transactions: Vec<Transaction>,
+        state_view: CachedStateView,
+        first_version: Version,
+        warm_vm_cache: &WarmVmCache,
+    ) -> Result<Self> {
+        let transaction_outputs =
+            V::execute_block_with_warm_vm(transactions.clone(), &state_view, warm_vm_cache)
+                .map_err(|err| {
+                    warm_vm_cache.invalidate();
+                    err
+                })?;
+        warm_vm_cache.record_outputs(first_version, &transaction_outputs);
+
+        Ok(Self {
+            transactions,
+            transaction_outputs,
+            state_cache: state_view.into_state_cache(),
+        })
+    }
+
+    /// Executes `transactions` like `by_transaction_execution_with_warm_vm`, stopping once the
+    /// cumulative output of the transactions crosses `output_limit`, see
+    /// `VMExecutor::execute_block_with_output_limit`.
+    pub fn by_transaction_execution_with_output_limit<V: VMExecutor>(
+        transactions: Vec<Transaction>,
+        state_view: CachedStateView,
+        first_version: Version,
+        warm_vm_cache: &WarmVmCache,
+        output_limit: &BlockOutputLimit,
+    ) -> Result<Self> {
+        let transaction_outputs = V::execute_block_with_output_limit(
+            transactions.clone(),
+            &state_view,
+            warm_vm_cache,
+            output_limit,
+        )
+        .map_err(|err| {
+            warm_vm_cache.invalidate();
+            err
+        })?;
+        warm_vm_cache.record_outputs(first_version, &transaction_outputs);
+
+        Ok(Self {
+            transactions,
+            transaction_outputs,
+            state_cache: state_view.into_state_cache(),
+        })
+    }
+
+    /// Executes `transactions` with `executor` in place of the VM, cutting the block like
+    /// `VMExecutor::execute_block_with_output_limit` when an `output_limit` is given.
+    pub fn by_transaction_block_executor(
+        executor: &dyn TransactionBlockExecutor,
+        transactions: Vec<Transaction>,
+        state_view: CachedStateView,
+        output_limit: Option<&BlockOutputLimit>,
+    ) -> Result<Self> {
+        let mut transaction_outputs =
+            executor.execute_transaction_block(transactions.clone(), &state_view)?;
+        if let Some(output_limit) = output_limit {
+            block_output_limit::limit_outputs(
+                &transactions,
+                &mut transaction_outputs,
+                output_limit,
+            );
+        }
+
+        Ok(Self {
+            transactions,
+            transaction_outputs,
+            state_cache: state_view.into_state_cache(),
+        })
+    }
+
     pub fn by_transaction_output(
         transactions_and_outputs: Vec<(Transaction, TransactionOutput)>,
         state_view: CachedStateView,
@@ -69,6 +152,23 @@ impl ChunkOutput {
         })
     }
 
+    /// The number of transactions the block output limit cut, i.e. retried without the block
+    /// reconfiguring.
+    pub fn num_cut_by_output_limit(&self) -> usize {
+        if self.transaction_outputs.iter().any(|output| {
+            output
+                .events()
+                .iter()
+                .any(|event| *event.key() == *NEW_EPOCH_EVENT_KEY)
+        }) {
+            return 0;
+        }
+        self.transaction_outputs
+            .iter()
+            .filter(|output| matches!(output.status(), TransactionStatus::Retry))
+            .count()
+    }
+
     pub fn apply_to_ledger(
         self,
         base_view: &ExecutedTrees,
@@ -107,3 +107,14 @@ pub static APTOS_EXECUTOR_TRANSACTIONS_SAVED: Lazy<Histogram> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static APTOS_EXECUTOR_BLOCK_OUTPUT_LIMIT_RETRIED_TRANSACTIONS: Lazy<IntCounter> =
+    Lazy::new(|| {
+        register_int_counter!(
+            // metric name
+            "aptos_executor_block_output_limit_retried_transactions",
+            // metric description
+            "The number of transactions retried because the output of their block crossed the limit"
+        )
+        .unwrap()
+    });
@@ -385,7 +385,7 @@ fn decode_transaction(txn: &SignedTransaction) -> MockVMTransaction {
                 _ => unimplemented!("Transaction must have one or two arguments."),
             }
         }
-        TransactionPayload::EntryFunction(_) => {
+        TransactionPayload::EntryFunction(_) | TransactionPayload::OrderlessEntryFunction(_) => {
             // TODO: we need to migrate Script to EntryFunction later
             unimplemented!("MockVM does not support entry function transaction payload.")
         }
@@ -11,8 +11,10 @@ use aptos_types::aggregate_signature::AggregateSignature;
 use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
+    block_metadata::BlockMetadata,
     chain_id::ChainId,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::BlockOutputLimit,
     proof::definition::LeafCount,
     state_store::{state_key::StateKey, state_value::StateValue},
     test_helpers::transaction_test_helpers::block,
@@ -21,8 +23,10 @@ use aptos_types::{
         TransactionListWithProof, TransactionOutput, TransactionPayload, TransactionStatus,
         Version,
     },
+    validator_txn::ValidatorTransaction,
     write_set::{WriteOp, WriteSet, WriteSetMut},
 };
+use aptos_vm::block_output_limit::limit_outputs;
 use aptosdb::AptosDB;
 use executor_types::{BlockExecutorTrait, ChunkExecutorTrait, TransactionReplayer};
 use storage_interface::{sync_proof_fetcher::SyncProofFetcher, DbReaderWriter, ExecutedTrees};
@@ -334,6 +338,120 @@ fn test_noop_block_after_reconfiguration() {
     assert_eq!(output1.root_hash(), output2.root_hash());
 }
 
+#[test]
+fn test_block_output_limit() {
+    let txns = block(
+        (0..4)
+            .map(|i| encode_mint_transaction(gen_address(i), 100))
+            .collect(),
+    );
+    let outputs: Vec<_> = (0..4u8)
+        .map(|i| {
+            let write_set = WriteSetMut::new(vec![(
+                StateKey::Raw(vec![i]),
+                WriteOp::Modification(vec![0; 99]),
+            )])
+            .freeze()
+            .unwrap();
+            TransactionOutput::new(write_set, vec![], 0, KEEP_STATUS.clone())
+        })
+        .chain(once(TransactionOutput::new(
+            WriteSet::default(),
+            vec![],
+            0,
+            KEEP_STATUS.clone(),
+        )))
+        .collect();
+
+    // Each write is 100 bytes, the second transaction crosses the limit.
+    let limit = BlockOutputLimit {
+        max_write_set_bytes: 150,
+        max_events: 100,
+    };
+    let mut limited = outputs.clone();
+    assert_eq!(limit_outputs(&txns, &mut limited, &limit), 2);
+    let status: Vec<_> = limited.iter().map(|o| o.status().clone()).collect();
+    assert_eq!(
+        status,
+        vec![
+            KEEP_STATUS.clone(),
+            KEEP_STATUS.clone(),
+            TransactionStatus::Retry,
+            TransactionStatus::Retry,
+            KEEP_STATUS.clone(),
+        ]
+    );
+
+    // Crossing the limit with the last transaction before the checkpoint cuts nothing.
+    let limit = BlockOutputLimit {
+        max_write_set_bytes: 350,
+        max_events: 100,
+    };
+    let mut limited = outputs.clone();
+    assert_eq!(limit_outputs(&txns, &mut limited, &limit), 0);
+    assert_eq!(limited, outputs);
+}
+
+#[test]
+fn test_block_output_limit_keeps_block_prologue() {
+    let txns: Vec<_> = once(Transaction::BlockMetadata(BlockMetadata::new(
+        HashValue::zero(),
+        0,
+        1,
+        AccountAddress::ZERO,
+        vec![],
+        vec![],
+        1,
+    )))
+    .chain(once(Transaction::ValidatorTransaction(
+        ValidatorTransaction::DKGTranscript(vec![0; 10]),
+    )))
+    .chain(block(
+        (0..2)
+            .map(|i| encode_mint_transaction(gen_address(i), 100))
+            .collect(),
+    ))
+    .collect();
+    let outputs: Vec<_> = (0..4u8)
+        .map(|i| {
+            let write_set = WriteSetMut::new(vec![(
+                StateKey::Raw(vec![i]),
+                WriteOp::Modification(vec![0; 99]),
+            )])
+            .freeze()
+            .unwrap();
+            TransactionOutput::new(write_set, vec![], 0, KEEP_STATUS.clone())
+        })
+        .chain(once(TransactionOutput::new(
+            WriteSet::default(),
+            vec![],
+            0,
+            KEEP_STATUS.clone(),
+        )))
+        .collect();
+
+    // The block metadata crosses the limit, but the validator transaction after it is kept, the
+    // block is cut from its first user transaction.
+    let limit = BlockOutputLimit {
+        max_write_set_bytes: 50,
+        max_events: 100,
+    };
+    let mut limited = outputs.clone();
+    assert_eq!(limit_outputs(&txns, &mut limited, &limit), 2);
+    assert_eq!(limited[..2], outputs[..2]);
+    let status: Vec<_> = limited.iter().map(|o| o.status().clone()).collect();
+    assert_eq!(
+        status,
+        vec![
+            KEEP_STATUS.clone(),
+            KEEP_STATUS.clone(),
+            TransactionStatus::Retry,
+            TransactionStatus::Retry,
+            KEEP_STATUS.clone(),
+        ]
+    );
+}
+
 fn create_test_transaction(sequence_number: u64) -> Transaction {
     let private_key = Ed25519PrivateKey::generate_for_testing();
     let public_key = private_key.public_key();
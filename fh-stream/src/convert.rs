@@ -3,11 +3,11 @@
 
 use aptos_api_types::{
     AccountSignature, DeleteModule, DeleteResource, Ed25519Signature, EntryFunctionId, Event,
-    GenesisPayload, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
+    GenesisPayload, KeylessSignature, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
     MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveResource,
     MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
-    MultiEd25519Signature, ScriptPayload, Transaction, TransactionInfo, TransactionPayload,
-    TransactionSignature, WriteSet, WriteSetChange,
+    MultiEd25519Signature, MultiKeySignature, ScriptPayload, Transaction, TransactionInfo,
+    TransactionPayload, TransactionSignature, WebAuthnSignature, WriteSet, WriteSetChange,
 };
 use aptos_bitvec::BitVec;
 use aptos_logger::warn;
@@ -502,6 +502,35 @@ pub fn convert_multi_ed25519_signature(
     }
 }
 
+pub fn convert_web_authn_signature(sig: &WebAuthnSignature) -> extractor::WebAuthnSignature {
+    extractor::WebAuthnSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+        authenticator_data: sig.authenticator_data.0.clone(),
+        client_data_json: sig.client_data_json.0.clone(),
+    }
+}
+
+pub fn convert_keyless_signature(sig: &KeylessSignature) -> extractor::KeylessSignature {
+    extractor::KeylessSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+    }
+}
+
+pub fn convert_multi_key_signature(sig: &MultiKeySignature) -> extractor::MultiKeySignature {
+    extractor::MultiKeySignature {
+        public_keys: sig.public_keys.iter().map(|pk| pk.0.clone()).collect(),
+        signatures: sig.signatures.iter().map(|sig| sig.0.clone()).collect(),
+        signatures_required: sig.signatures_required as u32,
+        public_key_indices: sig
+            .signature_indices
+            .iter()
+            .map(|index| *index as u32)
+            .collect(),
+    }
+}
+
 pub fn convert_account_signature(
     account_signature: &AccountSignature,
 ) -> extractor::AccountSignature {
@@ -510,6 +539,9 @@ pub fn convert_account_signature(
         AccountSignature::MultiEd25519Signature(_) => {
             extractor::account_signature::Type::MultiEd25519
         }
+        AccountSignature::WebAuthnSignature(_) => extractor::account_signature::Type::WebAuthn,
+        AccountSignature::KeylessSignature(_) => extractor::account_signature::Type::Keyless,
+        AccountSignature::MultiKeySignature(_) => extractor::account_signature::Type::MultiKey,
     };
     let signature = match account_signature {
         AccountSignature::Ed25519Signature(s) => {
@@ -520,6 +552,15 @@ pub fn convert_account_signature(
                 s,
             ))
         }
+        AccountSignature::WebAuthnSignature(s) => {
+            extractor::account_signature::Signature::WebAuthn(convert_web_authn_signature(s))
+        }
+        AccountSignature::KeylessSignature(s) => {
+            extractor::account_signature::Signature::Keyless(convert_keyless_signature(s))
+        }
+        AccountSignature::MultiKeySignature(s) => {
+            extractor::account_signature::Signature::MultiKey(convert_multi_key_signature(s))
+        }
     };
     extractor::AccountSignature {
         r#type: r#type as i32,
@@ -538,6 +579,9 @@ pub fn convert_transaction_signature(
         TransactionSignature::Ed25519Signature(_) => extractor::signature::Type::Ed25519,
         TransactionSignature::MultiEd25519Signature(_) => extractor::signature::Type::MultiEd25519,
         TransactionSignature::MultiAgentSignature(_) => extractor::signature::Type::MultiAgent,
+        TransactionSignature::WebAuthnSignature(_) => extractor::signature::Type::WebAuthn,
+        TransactionSignature::KeylessSignature(_) => extractor::signature::Type::Keyless,
+        TransactionSignature::MultiKeySignature(_) => extractor::signature::Type::MultiKey,
     };
 
     let signature = match signature {
@@ -562,6 +606,15 @@ pub fn convert_transaction_signature(
                     .collect(),
             })
         }
+        TransactionSignature::WebAuthnSignature(s) => {
+            extractor::signature::Signature::WebAuthn(convert_web_authn_signature(s))
+        }
+        TransactionSignature::KeylessSignature(s) => {
+            extractor::signature::Signature::Keyless(convert_keyless_signature(s))
+        }
+        TransactionSignature::MultiKeySignature(s) => {
+            extractor::signature::Signature::MultiKey(convert_multi_key_signature(s))
+        }
     };
 
     Some(extractor::Signature {
@@ -586,6 +639,7 @@ pub fn convert_transaction(
         Transaction::StateCheckpointTransaction(_) => {
             extractor::transaction::TransactionType::StateCheckpoint
         }
+        Transaction::ValidatorTransaction(_) => extractor::transaction::TransactionType::Validator,
         Transaction::PendingTransaction(_) => panic!("PendingTransaction is not supported"),
     };
 
@@ -632,6 +686,13 @@ pub fn convert_transaction(
                 extractor::StateCheckpointTransaction {},
             )
         }
+        Transaction::ValidatorTransaction(vt) => {
+            extractor::transaction::TxnData::Validator(extractor::ValidatorTransaction {
+                kind: vt.kind.clone(),
+                payload: vt.payload.0.clone(),
+                events: convert_events(&vt.events),
+            })
+        }
         Transaction::PendingTransaction(_) => panic!("PendingTransaction not supported"),
     };
 
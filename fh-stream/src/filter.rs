@@ -0,0 +1,134 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use aptos_protos::{
+    extractor::v1::{
+        move_type::Content, transaction::TxnData, transaction_payload::Payload, EntryFunctionId,
+        Event, Transaction as TransactionPB, UserTransactionRequest,
+    },
+    stream::v1::TransactionFilter as TransactionFilterPB,
+};
+use move_deps::move_core_types::account_address::AccountAddress;
+use std::{collections::HashSet, convert::TryFrom};
+
+/// A fully qualified Move name, e.g. the `0x1::coin::transfer` entry function or the
+/// `0x1::coin::DepositEvent` struct
+type QualifiedName = (AccountAddress, String, String);
+
+/// A `TransactionFilter` with its addresses and names parsed up front, so they don't have to be
+/// parsed again for every transaction matched against it
+#[derive(Debug, Default)]
+pub struct TransactionFilter {
+    transaction_types: HashSet<i32>,
+    sender_addresses: HashSet<AccountAddress>,
+    entry_functions: HashSet<QualifiedName>,
+    event_types: HashSet<QualifiedName>,
+}
+
+impl TryFrom<TransactionFilterPB> for TransactionFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: TransactionFilterPB) -> Result<Self> {
+        Ok(Self {
+            transaction_types: filter.transaction_types.into_iter().collect(),
+            sender_addresses: filter
+                .sender_addresses
+                .iter()
+                .map(|address| parse_address(address))
+                .collect::<Result<_>>()?,
+            entry_functions: filter
+                .entry_functions
+                .iter()
+                .map(|name| parse_qualified_name(name))
+                .collect::<Result<_>>()?,
+            event_types: filter
+                .event_types
+                .iter()
+                .map(|name| parse_qualified_name(name))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl TransactionFilter {
+    /// A transaction matches if it matches every non-empty criteria, and it matches a criteria if
+    /// it matches any of its values
+    pub fn matches(&self, txn: &TransactionPB) -> bool {
+        (self.transaction_types.is_empty() || self.transaction_types.contains(&txn.r#type))
+            && (self.sender_addresses.is_empty()
+                || user_request(txn)
+                    .and_then(|request| parse_address(&request.sender).ok())
+                    .map_or(false, |sender| self.sender_addresses.contains(&sender)))
+            && (self.entry_functions.is_empty()
+                || entry_function(txn)
+                    .and_then(|function| {
+                        let module = function.module.as_ref()?;
+                        Some((
+                            parse_address(&module.address).ok()?,
+                            module.name.clone(),
+                            function.name.clone(),
+                        ))
+                    })
+                    .map_or(false, |function| self.entry_functions.contains(&function)))
+            && (self.event_types.is_empty()
+                || events(txn).iter().any(|event| {
+                    event_type(event)
+                        .map_or(false, |event_type| self.event_types.contains(&event_type))
+                }))
+    }
+}
+
+fn parse_address(address: &str) -> Result<AccountAddress> {
+    AccountAddress::from_hex_literal(address)
+        .map_err(|_| format_err!("Invalid address: {}", address))
+}
+
+/// Parses `address::module::name`, ignoring any generic type parameters
+fn parse_qualified_name(name: &str) -> Result<QualifiedName> {
+    let without_generics = name.split('<').next().unwrap_or_default();
+    let parts: Vec<_> = without_generics.trim().split("::").collect();
+    if parts.len() != 3 || parts[1].is_empty() || parts[2].is_empty() {
+        bail!("Invalid name, expected `address::module::name`: {}", name);
+    }
+    Ok((
+        parse_address(parts[0])?,
+        parts[1].to_string(),
+        parts[2].to_string(),
+    ))
+}
+
+fn user_request(txn: &TransactionPB) -> Option<&UserTransactionRequest> {
+    match txn.txn_data.as_ref()? {
+        TxnData::User(user) => user.request.as_ref(),
+        _ => None,
+    }
+}
+
+fn entry_function(txn: &TransactionPB) -> Option<&EntryFunctionId> {
+    match user_request(txn)?.payload.as_ref()?.payload.as_ref()? {
+        Payload::EntryFunctionPayload(payload) => payload.function.as_ref(),
+        _ => None,
+    }
+}
+
+fn events(txn: &TransactionPB) -> &[Event] {
+    match txn.txn_data.as_ref() {
+        Some(TxnData::BlockMetadata(txn)) => &txn.events,
+        Some(TxnData::Genesis(txn)) => &txn.events,
+        Some(TxnData::User(txn)) => &txn.events,
+        Some(TxnData::Validator(txn)) => &txn.events,
+        Some(TxnData::StateCheckpoint(_)) | None => &[],
+    }
+}
+
+fn event_type(event: &Event) -> Option<QualifiedName> {
+    match event.r#type.as_ref()?.content.as_ref()? {
+        Content::Struct(tag) => Some((
+            parse_address(&tag.address).ok()?,
+            tag.module.clone(),
+            tag.name.clone(),
+        )),
+        _ => None,
+    }
+}
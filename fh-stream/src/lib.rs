@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod convert;
+pub mod filter;
 pub mod metrics;
 pub mod runtime;
+pub mod stream_service;
 
 #[cfg(test)]
 pub(crate) mod tests;
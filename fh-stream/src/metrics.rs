@@ -19,3 +19,11 @@ pub static BLOCKS_SENT: Lazy<IntCounter> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static GRPC_TRANSACTIONS_SENT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_fh_stream_grpc_transactions_sent_count",
+        "Transactions converted and sent to clients of the gRPC transaction stream",
+    )
+    .unwrap()
+});
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics;
+use crate::{metrics, stream_service};
 use aptos_protos::extractor::v1 as extractor;
 
 use crate::convert::convert_transaction;
@@ -9,7 +9,7 @@ use aptos_api::context::Context;
 use aptos_api_types::{AsConverter, Transaction};
 use aptos_config::config::NodeConfig;
 use aptos_logger::{debug, error, warn};
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{MempoolClientSender, TransactionFilter};
 use aptos_types::chain_id::ChainId;
 use aptos_vm::data_cache::RemoteStorageOwned;
 use extractor::Transaction as TransactionPB;
@@ -21,7 +21,8 @@ use storage_interface::DbReader;
 use tokio::runtime::{Builder, Runtime};
 use tokio::time::sleep;
 
-/// Creates a runtime which creates a thread pool which pushes firehose of block protobuf to SF endpoint
+/// Creates a runtime which creates a thread pool which pushes firehose of block protobuf to SF endpoint,
+/// and/or serves the gRPC transaction stream
 /// Returns corresponding Tokio runtime
 pub fn bootstrap(
     config: &NodeConfig,
@@ -29,7 +30,7 @@ pub fn bootstrap(
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
 ) -> Option<anyhow::Result<Runtime>> {
-    if !config.firehose_stream.enabled {
+    if !config.firehose_stream.enabled && config.firehose_stream.grpc_address.is_none() {
         return None;
     }
 
@@ -42,8 +43,21 @@ pub fn bootstrap(
     let node_config = config.clone();
 
     runtime.spawn(async move {
-        let context = Context::new(chain_id, db, mp_sender.clone(), node_config.clone());
+        // The stream doesn't submit transactions, so it has no use for the transaction filter
+        let context = Context::new(
+            chain_id,
+            db,
+            mp_sender.clone(),
+            node_config.clone(),
+            TransactionFilter::default(),
+        );
         let context_arc = Arc::new(context);
+        if let Some(address) = node_config.firehose_stream.grpc_address {
+            tokio::spawn(stream_service::serve(context_arc.clone(), address));
+        }
+        if !node_config.firehose_stream.enabled {
+            return;
+        }
         // Let the env variable take precedence over the config file
         let config_starting_version = node_config.firehose_stream.starting_version.unwrap_or(0);
         let starting_version = std::env::var("STARTING_VERSION")
@@ -109,7 +123,26 @@ impl FirehoseStreamer {
         }
     }
 
+    /// Converts the next block and prints it out to stdout for the firehose
     pub async fn convert_next_block(&mut self) -> Vec<TransactionPB> {
+        let result = self.fetch_next_block().await;
+        if result.is_empty() {
+            return result;
+        }
+
+        let block_height = result[0].block_height;
+        println!("\nFIRE BLOCK_START {}", block_height);
+        for txn_proto in &result {
+            self.print_transaction(txn_proto);
+        }
+        println!("\nFIRE BLOCK_END {}", block_height);
+        metrics::BLOCKS_SENT.inc();
+        result
+    }
+
+    /// Converts the next block, moving on to the block after it once the block is valid
+    /// Returns no transactions if the block isn't available yet, or failed validation
+    pub async fn fetch_next_block(&mut self) -> Vec<TransactionPB> {
         let mut result: Vec<TransactionPB> = vec![];
 
         let (block_start_version, block_last_version, _) = match self
@@ -139,10 +172,9 @@ impl FirehoseStreamer {
                     block_start_version
                 )
             });
-        // We are validating the block as we convert and print each transactions. The rules are as follows:
+        // We are validating the block as we convert each transactions. The rules are as follows:
         // 1. first (and only first) transaction is a block metadata or genesis 2. versions are monotonically increasing 3. start and end versions match block boundaries
         // Retry if the block is not valid. Panic if there's anything wrong with encoding a transaction.
-        println!("\nFIRE BLOCK_START {}", self.current_block_height);
 
         let transactions = match self.context.get_transactions(
             block_start_version,
@@ -203,7 +235,6 @@ impl FirehoseStreamer {
             }
             let txn_proto =
                 convert_transaction(&txn, self.current_block_height, self.current_epoch);
-            self.print_transaction(&txn_proto);
             result.push(txn_proto);
             curr_version += 1;
         }
@@ -219,8 +250,6 @@ impl FirehoseStreamer {
             return vec![];
         }
 
-        println!("\nFIRE BLOCK_END {}", self.current_block_height);
-        metrics::BLOCKS_SENT.inc();
         self.current_block_height += 1;
         result
     }
@@ -0,0 +1,105 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{filter::TransactionFilter, metrics, runtime::FirehoseStreamer};
+use aptos_api::context::Context;
+use aptos_logger::{debug, error, info};
+use aptos_protos::stream::v1::{
+    transaction_stream_server::{TransactionStream, TransactionStreamServer},
+    StreamTransactionsRequest, StreamTransactionsResponse,
+};
+use std::{convert::TryFrom, net::SocketAddr, sync::Arc};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{transport::Server, Request, Response, Status};
+
+/// How many responses to buffer per stream before waiting on the client
+const STREAM_CHANNEL_SIZE: usize = 16;
+
+/// Serves the `TransactionStream` gRPC service on `address`, until the runtime is shut down
+pub async fn serve(context: Arc<Context>, address: SocketAddr) {
+    info!("[fh-stream] serving transaction stream on {}", address);
+    if let Err(err) = Server::builder()
+        .add_service(TransactionStreamServer::new(TransactionStreamService::new(
+            context,
+        )))
+        .serve(address)
+        .await
+    {
+        error!("[fh-stream] transaction stream server failed: {}", err);
+    }
+}
+
+/// Streams the same transactions as the firehose, to any number of clients, each starting from
+/// their own version and with their own filter
+pub struct TransactionStreamService {
+    context: Arc<Context>,
+}
+
+impl TransactionStreamService {
+    pub fn new(context: Arc<Context>) -> Self {
+        Self { context }
+    }
+}
+
+#[tonic::async_trait]
+impl TransactionStream for TransactionStreamService {
+    type StreamTransactionsStream = ReceiverStream<Result<StreamTransactionsResponse, Status>>;
+
+    async fn stream_transactions(
+        &self,
+        request: Request<StreamTransactionsRequest>,
+    ) -> Result<Response<Self::StreamTransactionsStream>, Status> {
+        let request = request.into_inner();
+        let starting_version = request.starting_version;
+        let filter = TransactionFilter::try_from(request.filter.unwrap_or_default())
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        self.context
+            .db
+            .get_block_info_by_version(starting_version)
+            .map_err(|err| {
+                Status::out_of_range(format!(
+                    "Version {} is not available: {}",
+                    starting_version, err
+                ))
+            })?;
+
+        let context = self.context.clone();
+        let chain_id = context.chain_id().id() as u32;
+        let (sender, receiver) = mpsc::channel(STREAM_CHANNEL_SIZE);
+        tokio::spawn(async move {
+            let mut streamer = FirehoseStreamer::new(context, starting_version, None);
+            // Stop once the client goes away, even if nothing has matched the filter since
+            while !sender.is_closed() {
+                let block = streamer.fetch_next_block().await;
+                let next_version = match block.last() {
+                    Some(txn) => txn.version + 1,
+                    None => continue,
+                };
+                // The first block may start before the requested version
+                let transactions: Vec<_> = block
+                    .into_iter()
+                    .filter(|txn| txn.version >= starting_version && filter.matches(txn))
+                    .collect();
+                if transactions.is_empty() {
+                    continue;
+                }
+                let num_transactions = transactions.len() as u64;
+                let response = StreamTransactionsResponse {
+                    chain_id,
+                    transactions,
+                    next_version,
+                };
+                if sender.send(Ok(response)).await.is_err() {
+                    break;
+                }
+                metrics::GRPC_TRANSACTIONS_SENT.inc_by(num_transactions);
+            }
+            debug!(
+                "[fh-stream] transaction stream from version {} closed",
+                starting_version
+            );
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
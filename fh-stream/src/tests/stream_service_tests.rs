@@ -0,0 +1,103 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{stream_service::TransactionStreamService, tests::new_test_context};
+
+use aptos_api_test_context::current_function_name;
+use aptos_protos::{
+    extractor::v1::transaction::TransactionType,
+    stream::v1::{
+        transaction_stream_server::TransactionStream, StreamTransactionsRequest, TransactionFilter,
+    },
+};
+use futures::StreamExt;
+use std::sync::Arc;
+use tonic::{Code, Request};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_with_filter() {
+    let mut test_context = new_test_context(current_function_name!());
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account);
+    test_context.commit_block(&vec![txn.clone()]).await;
+
+    let chain_id = test_context.context.chain_id().id() as u32;
+    let sender = test_context.root_account().address().to_hex_literal();
+    let service = TransactionStreamService::new(Arc::new(test_context.context));
+    let request = StreamTransactionsRequest {
+        starting_version: 0,
+        filter: Some(TransactionFilter {
+            transaction_types: vec![TransactionType::User as i32],
+            sender_addresses: vec![sender],
+            entry_functions: vec!["0x1::account::create_account".to_string()],
+            ..TransactionFilter::default()
+        }),
+    };
+    let mut stream = service
+        .stream_transactions(Request::new(request))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Genesis doesn't match, and the second block only matches its user transaction
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.chain_id, chain_id);
+    assert_eq!(response.transactions.len(), 1);
+    assert_eq!(response.transactions[0].version, 2);
+    assert_eq!(response.transactions[0].r#type(), TransactionType::User);
+    assert_eq!(response.next_version, 4);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_resumes_from_version() {
+    let mut test_context = new_test_context(current_function_name!());
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account);
+    test_context.commit_block(&vec![txn.clone()]).await;
+
+    let service = TransactionStreamService::new(Arc::new(test_context.context));
+    let request = StreamTransactionsRequest {
+        starting_version: 2,
+        filter: None,
+    };
+    let mut stream = service
+        .stream_transactions(Request::new(request))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // The block starts at version 1, but only the requested versions are streamed
+    let response = stream.next().await.unwrap().unwrap();
+    let versions: Vec<_> = response.transactions.iter().map(|t| t.version).collect();
+    assert_eq!(versions, vec![2, 3]);
+    assert_eq!(response.next_version, 4);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_invalid_request() {
+    let test_context = new_test_context(current_function_name!());
+    let service = TransactionStreamService::new(Arc::new(test_context.context));
+
+    let request = StreamTransactionsRequest {
+        starting_version: 0,
+        filter: Some(TransactionFilter {
+            entry_functions: vec!["0x1::coin".to_string()],
+            ..TransactionFilter::default()
+        }),
+    };
+    let status = service
+        .stream_transactions(Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let request = StreamTransactionsRequest {
+        starting_version: 1000,
+        filter: None,
+    };
+    let status = service
+        .stream_transactions(Request::new(request))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::OutOfRange);
+}
@@ -3,7 +3,7 @@
 
 use aptos_config::config::NodeConfig;
 use aptos_crypto::{hash::HashValue, SigningKey};
-use aptos_mempool::mocks::MockSharedMempool;
+use aptos_mempool::{mocks::MockSharedMempool, TransactionFilter};
 use aptos_protos::extractor::v1::Transaction as TransactionPB;
 use aptos_sdk::{
     transaction_builder::TransactionFactory,
@@ -72,6 +72,7 @@ pub fn new_test_context(test_name: &str, fake_start_time_usecs: u64) -> TestCont
             db.clone(),
             mempool.ac_client.clone(),
             NodeConfig::default(),
+            TransactionFilter::default(),
         ),
         rng,
         root_key,
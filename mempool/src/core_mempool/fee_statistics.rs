@@ -0,0 +1,135 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Statistics of the gas prices in mempool, for clients to pick a gas price. They are updated as
+//! transactions enter and leave mempool, so that reading them doesn't scan mempool.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+/// The lowest gas unit price of each band, a band ends where the next one starts.
+pub const GAS_PRICE_BAND_LOWER_BOUNDS: &[u64] = &[0, 100, 150, 200, 300, 500, 1_000, 10_000];
+
+/// How long evictions and rejections of a full mempool count as recent.
+pub const EVICTION_WINDOW: Duration = Duration::from_secs(600);
+
+/// The weight of the latest delay in the moving average of the inclusion delay of a band.
+const INCLUSION_DELAY_WEIGHT: f64 = 0.1;
+
+/// Summary of mempool by gas price, as served to clients.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MempoolFeeStatistics {
+    pub bands: Vec<GasPriceBandStatistics>,
+    /// Transactions evicted from the parking lot to make room for others during the window.
+    pub num_evicted_recently: u64,
+    /// Transactions rejected because mempool was full during the window.
+    pub num_rejected_mempool_full_recently: u64,
+    pub eviction_window_secs: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GasPriceBandStatistics {
+    pub min_gas_unit_price: u64,
+    /// Exclusive, `None` for the last band.
+    pub max_gas_unit_price: Option<u64>,
+    /// Transactions of the band currently in mempool.
+    pub num_txns: u64,
+    /// Moving average of the time the committed transactions of the band spent in mempool,
+    /// `None` until one of them commits.
+    pub estimated_inclusion_delay_ms: Option<u64>,
+}
+
+#[derive(Default)]
+struct BandState {
+    num_txns: u64,
+    inclusion_delay_ms: Option<f64>,
+}
+
+pub(crate) struct FeeStatistics {
+    bands: Vec<BandState>,
+    evictions: VecDeque<SystemTime>,
+    mempool_full_rejections: VecDeque<SystemTime>,
+}
+
+impl FeeStatistics {
+    pub(crate) fn new() -> Self {
+        Self {
+            bands: GAS_PRICE_BAND_LOWER_BOUNDS
+                .iter()
+                .map(|_| BandState::default())
+                .collect(),
+            evictions: VecDeque::new(),
+            mempool_full_rejections: VecDeque::new(),
+        }
+    }
+
+    fn band_mut(&mut self, gas_unit_price: u64) -> &mut BandState {
+        // The first lower bound is 0, so there is always a band.
+        let idx = GAS_PRICE_BAND_LOWER_BOUNDS.partition_point(|bound| *bound <= gas_unit_price);
+        &mut self.bands[idx - 1]
+    }
+
+    pub(crate) fn add_txn(&mut self, gas_unit_price: u64) {
+        self.band_mut(gas_unit_price).num_txns += 1;
+    }
+
+    pub(crate) fn remove_txn(&mut self, gas_unit_price: u64) {
+        let band = self.band_mut(gas_unit_price);
+        band.num_txns = band.num_txns.saturating_sub(1);
+    }
+
+    pub(crate) fn record_inclusion(&mut self, gas_unit_price: u64, delay: Duration) {
+        let delay_ms = delay.as_millis() as f64;
+        let band = self.band_mut(gas_unit_price);
+        band.inclusion_delay_ms = Some(match band.inclusion_delay_ms {
+            Some(average) => average + INCLUSION_DELAY_WEIGHT * (delay_ms - average),
+            None => delay_ms,
+        });
+    }
+
+    pub(crate) fn record_eviction(&mut self, now: SystemTime) {
+        self.evictions.push_back(now);
+        Self::prune(&mut self.evictions, now);
+    }
+
+    pub(crate) fn record_mempool_full_rejection(&mut self, now: SystemTime) {
+        self.mempool_full_rejections.push_back(now);
+        Self::prune(&mut self.mempool_full_rejections, now);
+    }
+
+    fn prune(times: &mut VecDeque<SystemTime>, now: SystemTime) {
+        while let Some(time) = times.front() {
+            match now.duration_since(*time) {
+                Ok(elapsed) if elapsed > EVICTION_WINDOW => {
+                    times.pop_front();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    pub(crate) fn summary(&mut self, now: SystemTime) -> MempoolFeeStatistics {
+        Self::prune(&mut self.evictions, now);
+        Self::prune(&mut self.mempool_full_rejections, now);
+        let bands = self
+            .bands
+            .iter()
+            .enumerate()
+            .map(|(idx, band)| GasPriceBandStatistics {
+                min_gas_unit_price: GAS_PRICE_BAND_LOWER_BOUNDS[idx],
+                max_gas_unit_price: GAS_PRICE_BAND_LOWER_BOUNDS.get(idx + 1).copied(),
+                num_txns: band.num_txns,
+                estimated_inclusion_delay_ms: band.inclusion_delay_ms.map(|ms| ms.round() as u64),
+            })
+            .collect();
+        MempoolFeeStatistics {
+            bands,
+            num_evicted_recently: self.evictions.len() as u64,
+            num_rejected_mempool_full_recently: self.mempool_full_rejections.len() as u64,
+            eviction_window_secs: EVICTION_WINDOW.as_secs(),
+        }
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     logging::{LogEntry, LogSchema},
 };
 use aptos_logger::prelude::*;
-use aptos_types::account_address::AccountAddress;
+use aptos_types::{account_address::AccountAddress, transaction::ReplayProtector};
 use rand::seq::SliceRandom;
 use std::{
     cmp::Ordering,
@@ -18,7 +18,8 @@ use std::{
     time::Duration,
 };
 
-pub type AccountTransactions = BTreeMap<u64, MempoolTransaction>;
+/// The transactions of an account, the sequence numbered ones first, then the orderless ones.
+pub type AccountTransactions = BTreeMap<ReplayProtector, MempoolTransaction>;
 
 /// PriorityIndex represents the main Priority Queue in Mempool.
 /// It's used to form the transaction block for Consensus.
@@ -57,6 +58,7 @@ impl PriorityIndex {
             expiration_time: txn.expiration_time,
             address: txn.get_sender(),
             sequence_number: txn.sequence_info,
+            replay_protector: txn.get_replay_protector(),
         }
     }
 
@@ -75,6 +77,7 @@ pub struct OrderedQueueKey {
     pub expiration_time: Duration,
     pub address: AccountAddress,
     pub sequence_number: SequenceInfo,
+    pub replay_protector: ReplayProtector,
 }
 
 impl PartialOrd for OrderedQueueKey {
@@ -97,10 +100,7 @@ impl Ord for OrderedQueueKey {
             Ordering::Equal => {}
             ordering => return ordering,
         }
-        self.sequence_number
-            .transaction_sequence_number
-            .cmp(&other.sequence_number.transaction_sequence_number)
-            .reverse()
+        self.replay_protector.cmp(&other.replay_protector).reverse()
     }
 }
 
@@ -138,7 +138,7 @@ impl TTLIndex {
         let ttl_key = TTLOrderingKey {
             expiration_time: now,
             address: AccountAddress::ZERO,
-            sequence_number: 0,
+            replay_protector: ReplayProtector::SequenceNumber(0),
         };
 
         let mut active = self.data.split_off(&ttl_key);
@@ -152,7 +152,7 @@ impl TTLIndex {
         TTLOrderingKey {
             expiration_time: (self.get_expiration_time)(txn),
             address: txn.get_sender(),
-            sequence_number: txn.sequence_info.transaction_sequence_number,
+            replay_protector: txn.get_replay_protector(),
         }
     }
 
@@ -166,7 +166,7 @@ impl TTLIndex {
 pub struct TTLOrderingKey {
     pub expiration_time: Duration,
     pub address: AccountAddress,
-    pub sequence_number: u64,
+    pub replay_protector: ReplayProtector,
 }
 
 /// Be very careful with this, to not break the partial ordering.
@@ -175,9 +175,8 @@ pub struct TTLOrderingKey {
 impl Ord for TTLOrderingKey {
     fn cmp(&self, other: &TTLOrderingKey) -> Ordering {
         match self.expiration_time.cmp(&other.expiration_time) {
-            Ordering::Equal => {
-                (&self.address, self.sequence_number).cmp(&(&other.address, other.sequence_number))
-            }
+            Ordering::Equal => (&self.address, self.replay_protector)
+                .cmp(&(&other.address, other.replay_protector)),
             ordering => ordering,
         }
     }
@@ -187,12 +186,12 @@ impl Ord for TTLOrderingKey {
 /// We only add a transaction to the index if it has a chance to be included in the next consensus
 /// block (which means its status is != NotReady or its sequential to another "ready" transaction).
 ///
-/// It's represented as Map <timeline_id, (Address, sequence_number)>, where timeline_id is auto
-/// increment unique id of "ready" transaction in local Mempool. (Address, sequence_number) is a
-/// logical reference to transaction content in main storage.
+/// It's represented as Map <timeline_id, TxnPointer>, where timeline_id is auto increment unique
+/// id of "ready" transaction in local Mempool. The `TxnPointer` is a logical reference to
+/// transaction content in main storage.
 pub struct TimelineIndex {
     timeline_id: u64,
-    timeline: BTreeMap<u64, (AccountAddress, u64)>,
+    timeline: BTreeMap<u64, TxnPointer>,
 }
 
 impl TimelineIndex {
@@ -204,17 +203,13 @@ impl TimelineIndex {
     }
 
     /// Read all transactions from the timeline since <timeline_id>.
-    pub(crate) fn read_timeline(
-        &self,
-        timeline_id: u64,
-        count: usize,
-    ) -> Vec<(AccountAddress, u64)> {
+    pub(crate) fn read_timeline(&self, timeline_id: u64, count: usize) -> Vec<TxnPointer> {
         let mut batch = vec![];
-        for (_id, &(address, sequence_number)) in self
+        for (_id, &pointer) in self
             .timeline
             .range((Bound::Excluded(timeline_id), Bound::Unbounded))
         {
-            batch.push((address, sequence_number));
+            batch.push(pointer);
             if batch.len() == count {
                 break;
             }
@@ -223,7 +218,7 @@ impl TimelineIndex {
     }
 
     /// Read transactions from the timeline from `start_id` (exclusive) to `end_id` (inclusive).
-    pub(crate) fn timeline_range(&self, start_id: u64, end_id: u64) -> Vec<(AccountAddress, u64)> {
+    pub(crate) fn timeline_range(&self, start_id: u64, end_id: u64) -> Vec<TxnPointer> {
         self.timeline
             .range((Bound::Excluded(start_id), Bound::Included(end_id)))
             .map(|(_idx, txn)| txn)
@@ -232,13 +227,8 @@ impl TimelineIndex {
     }
 
     pub(crate) fn insert(&mut self, txn: &mut MempoolTransaction) {
-        self.timeline.insert(
-            self.timeline_id,
-            (
-                txn.get_sender(),
-                txn.sequence_info.transaction_sequence_number,
-            ),
-        );
+        self.timeline
+            .insert(self.timeline_id, TxnPointer::from(&*txn));
         txn.timeline_state = TimelineState::Ready(self.timeline_id);
         self.timeline_id += 1;
     }
@@ -255,7 +245,8 @@ impl TimelineIndex {
 }
 
 /// ParkingLotIndex keeps track of "not_ready" transactions, e.g., transactions that
-/// can't be included in the next block because their sequence number is too high.
+/// can't be included in the next block because their sequence number is too high. Orderless
+/// transactions are always ready, so they are never parked.
 /// We keep a separate index to be able to efficiently evict them when Mempool is full.
 pub struct ParkingLotIndex {
     // DS invariants:
@@ -337,9 +328,12 @@ impl ParkingLotIndex {
     /// Returns a random "non-ready" transaction (with highest sequence number for that account).
     pub(crate) fn get_poppable(&self) -> Option<TxnPointer> {
         let mut rng = rand::thread_rng();
-        self.data
-            .choose(&mut rng)
-            .and_then(|(sender, txns)| txns.iter().rev().next().map(|seq_num| (*sender, *seq_num)))
+        self.data.choose(&mut rng).and_then(|(sender, txns)| {
+            txns.iter()
+                .rev()
+                .next()
+                .map(|seq_num| (*sender, ReplayProtector::SequenceNumber(*seq_num)))
+        })
     }
 
     pub(crate) fn size(&self) -> usize {
@@ -348,20 +342,17 @@ impl ParkingLotIndex {
 }
 
 /// Logical pointer to `MempoolTransaction`.
-/// Includes Account's address and transaction sequence number.
-pub type TxnPointer = (AccountAddress, u64);
+/// Includes Account's address and transaction sequence number, or nonce if orderless.
+pub type TxnPointer = (AccountAddress, ReplayProtector);
 
 impl From<&MempoolTransaction> for TxnPointer {
     fn from(transaction: &MempoolTransaction) -> Self {
-        (
-            transaction.get_sender(),
-            transaction.sequence_info.transaction_sequence_number,
-        )
+        (transaction.get_sender(), transaction.get_replay_protector())
     }
 }
 
 impl From<&OrderedQueueKey> for TxnPointer {
     fn from(key: &OrderedQueueKey) -> Self {
-        (key.address, key.sequence_number.transaction_sequence_number)
+        (key.address, key.replay_protector)
     }
 }
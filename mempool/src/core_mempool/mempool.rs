@@ -5,6 +5,7 @@
 //! agreed upon.
 use crate::{
     core_mempool::{
+        fee_statistics::MempoolFeeStatistics,
         index::TxnPointer,
         transaction::{MempoolTransaction, TimelineState},
         transaction_store::TransactionStore,
@@ -16,11 +17,12 @@ use crate::{
 use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
+use aptos_transaction_tracing::TxnStage;
 use aptos_types::{
     account_address::AccountAddress,
     account_config::AccountSequenceInfo,
     mempool_status::{MempoolStatus, MempoolStatusCode},
-    transaction::SignedTransaction,
+    transaction::{ReplayProtector, SignedTransaction},
 };
 use std::{
     cmp::max,
@@ -36,7 +38,7 @@ pub struct Mempool {
     // For each transaction, an entry with a timestamp is added when the transaction enters mempool.
     // This is used to measure e2e latency of transactions in the system, as well as the time it
     // takes to pick it up by consensus.
-    pub(crate) metrics_cache: TtlCache<(AccountAddress, u64), SystemTime>,
+    pub(crate) metrics_cache: TtlCache<TxnPointer, SystemTime>,
     pub system_transaction_timeout: Duration,
 }
 
@@ -56,11 +58,11 @@ impl Mempool {
     pub(crate) fn remove_transaction(
         &mut self,
         sender: &AccountAddress,
-        sequence_number: u64,
+        replay_protector: ReplayProtector,
         is_rejected: bool,
     ) {
         trace!(
-            LogSchema::new(LogEntry::RemoveTxn).txns(TxnsLog::new_txn(*sender, sequence_number)),
+            LogSchema::new(LogEntry::RemoveTxn).txns(TxnsLog::new_txn(*sender, replay_protector)),
             is_rejected = is_rejected
         );
         let metric_label = if is_rejected {
@@ -68,8 +70,34 @@ impl Mempool {
         } else {
             counters::COMMIT_ACCEPTED_LABEL
         };
-        self.log_latency(*sender, sequence_number, metric_label);
-        self.metrics_cache.remove(&(*sender, sequence_number));
+        self.log_latency(*sender, replay_protector, metric_label);
+        if !is_rejected {
+            if let Some(time_delta) = self
+                .metrics_cache
+                .get(&(*sender, replay_protector))
+                .and_then(|creation_time| SystemTime::now().duration_since(*creation_time).ok())
+            {
+                self.transactions
+                    .record_inclusion_delay(sender, replay_protector, time_delta);
+            }
+        }
+        self.metrics_cache.remove(&(*sender, replay_protector));
+
+        let sequence_number = match replay_protector {
+            ReplayProtector::SequenceNumber(sequence_number) => sequence_number,
+            // An orderless transaction doesn't change the sequence number of its sender, nor
+            // affect the other transactions of the sender
+            ReplayProtector::Nonce(nonce) => {
+                if is_rejected {
+                    self.transactions
+                        .reject_transaction(sender, replay_protector);
+                } else {
+                    self.transactions
+                        .commit_orderless_transaction(sender, nonce);
+                }
+                return;
+            }
+        };
 
         let current_seq_number = self
             .sequence_number_cache
@@ -79,14 +107,14 @@ impl Mempool {
         if is_rejected {
             if sequence_number >= current_seq_number {
                 self.transactions
-                    .reject_transaction(sender, sequence_number);
+                    .reject_transaction(sender, replay_protector);
             }
         } else {
             let new_seq_number = max(current_seq_number, sequence_number + 1);
             self.sequence_number_cache.insert(*sender, new_seq_number);
 
             let new_seq_number = if let Some(mempool_transaction) =
-                self.transactions.get_mempool_txn(sender, sequence_number)
+                self.transactions.get_mempool_txn(sender, replay_protector)
             {
                 match mempool_transaction
                     .sequence_info
@@ -109,8 +137,13 @@ impl Mempool {
         }
     }
 
-    fn log_latency(&self, account: AccountAddress, sequence_number: u64, metric: &str) {
-        if let Some(&creation_time) = self.metrics_cache.get(&(account, sequence_number)) {
+    fn log_latency(
+        &self,
+        account: AccountAddress,
+        replay_protector: ReplayProtector,
+        metric: &str,
+    ) {
+        if let Some(&creation_time) = self.metrics_cache.get(&(account, replay_protector)) {
             if let Ok(time_delta) = SystemTime::now().duration_since(creation_time) {
                 counters::CORE_MEMPOOL_TXN_COMMIT_LATENCY
                     .with_label_values(&[metric])
@@ -124,7 +157,9 @@ impl Mempool {
     }
 
     /// Used to add a transaction to the Mempool.
-    /// Performs basic validation: checks account's sequence number.
+    /// Performs basic validation: checks account's sequence number, unless the transaction is
+    /// orderless. Whether the nonce of an orderless transaction was already used is left to the
+    /// VM validation.
     pub(crate) fn add_txn(
         &mut self,
         txn: SignedTransaction,
@@ -133,9 +168,9 @@ impl Mempool {
         timeline_state: TimelineState,
     ) -> MempoolStatus {
         let db_sequence_number = crsn_or_seqno.min_seq();
+        let replay_protector = txn.replay_protector();
         trace!(
-            LogSchema::new(LogEntry::AddTxn)
-                .txns(TxnsLog::new_txn(txn.sender(), txn.sequence_number())),
+            LogSchema::new(LogEntry::AddTxn).txns(TxnsLog::new_txn(txn.sender(), replay_protector)),
             committed_seq_number = db_sequence_number
         );
         let cached_value = self.sequence_number_cache.get(&txn.sender());
@@ -148,7 +183,8 @@ impl Mempool {
         self.sequence_number_cache
             .insert(txn.sender(), sequence_number.min_seq());
 
-        // don't accept old transactions (e.g. seq is less than account's current seq_number)
+        // don't accept old transactions (e.g. seq is less than account's current seq_number).
+        // Orderless transactions have the highest sequence number, so they always pass.
         if txn.sequence_number() < sequence_number.min_seq() {
             return MempoolStatus::new(MempoolStatusCode::InvalidSeqNumber).with_message(format!(
                 "transaction sequence number is {}, current sequence number is  {}",
@@ -161,7 +197,7 @@ impl Mempool {
             aptos_infallible::duration_since_epoch() + self.system_transaction_timeout;
         if timeline_state != TimelineState::NonQualified {
             self.metrics_cache
-                .insert((txn.sender(), txn.sequence_number()), SystemTime::now());
+                .insert((txn.sender(), replay_protector), SystemTime::now());
         }
 
         let txn_info = MempoolTransaction::new(
@@ -202,14 +238,22 @@ impl Mempool {
                 continue;
             }
             let account_seqtype = txn.sequence_number.account_sequence_number_type;
-            let tx_seq = txn.sequence_number.transaction_sequence_number;
+            // orderless transactions don't depend on other transactions
+            let tx_seq = match txn.replay_protector {
+                ReplayProtector::SequenceNumber(tx_seq) => Some(tx_seq),
+                ReplayProtector::Nonce(_) => None,
+            };
             let account_sequence_number = self.sequence_number_cache.get(&txn.address);
-            let seen_previous = tx_seq > 0 && seen.contains(&(txn.address, tx_seq - 1));
-            // include transaction if it's "next" for given account or
+            let seen_previous = tx_seq.map_or(false, |tx_seq| {
+                tx_seq > 0
+                    && seen.contains(&(txn.address, ReplayProtector::SequenceNumber(tx_seq - 1)))
+            });
+            // include transaction if it's orderless, "next" for given account or
             // we've already sent its ancestor to Consensus. In the case of CRSNs, we can safely
             // assume that it can be included.
-            if seen_previous
-                || account_sequence_number == Some(&tx_seq)
+            if tx_seq.is_none()
+                || seen_previous
+                || account_sequence_number == tx_seq.as_ref()
                 || matches!(account_seqtype, AccountSequenceInfo::CRSN { .. })
             {
                 let ptr = TxnPointer::from(txn);
@@ -221,14 +265,17 @@ impl Mempool {
 
                 // check if we can now include some transactions
                 // that were skipped before for given account
-                let mut skipped_txn = (txn.address, tx_seq + 1);
-                while skipped.contains(&skipped_txn) {
-                    seen.insert(skipped_txn);
-                    result.push(skipped_txn);
-                    if (result.len() as u64) == batch_size {
-                        break 'main;
+                if let Some(mut next_seq) = tx_seq.map(|tx_seq| tx_seq + 1) {
+                    let mut skipped_txn = (txn.address, ReplayProtector::SequenceNumber(next_seq));
+                    while skipped.contains(&skipped_txn) {
+                        seen.insert(skipped_txn);
+                        result.push(skipped_txn);
+                        if (result.len() as u64) == batch_size {
+                            break 'main;
+                        }
+                        next_seq += 1;
+                        skipped_txn = (txn.address, ReplayProtector::SequenceNumber(next_seq));
                     }
-                    skipped_txn = (txn.address, skipped_txn.1 + 1);
                 }
             } else {
                 skipped.insert(TxnPointer::from(txn));
@@ -238,7 +285,9 @@ impl Mempool {
         // convert transaction pointers to real values
         let block: Vec<_> = result
             .into_iter()
-            .filter_map(|(address, tx_seq)| self.transactions.get(&address, tx_seq))
+            .filter_map(|(address, replay_protector)| {
+                self.transactions.get(&address, replay_protector)
+            })
             .collect();
 
         debug!(
@@ -252,10 +301,24 @@ impl Mempool {
         for transaction in &block {
             self.log_latency(
                 transaction.sender(),
-                transaction.sequence_number(),
+                transaction.replay_protector(),
                 counters::GET_BLOCK_STAGE_LABEL,
             );
         }
+        if aptos_transaction_tracing::is_enabled() {
+            for transaction in &block {
+                if let Some(&creation_time) = self
+                    .metrics_cache
+                    .get(&(transaction.sender(), transaction.replay_protector()))
+                {
+                    aptos_transaction_tracing::record(
+                        transaction.clone().committed_hash(),
+                        TxnStage::MEMPOOL,
+                        creation_time,
+                    );
+                }
+            }
+        }
         block
     }
 
@@ -275,6 +338,13 @@ impl Mempool {
             .gc_by_expiration_time(block_time, &self.metrics_cache);
     }
 
+    /// Changes the capacity limits. Transactions already in mempool are kept, even if there are
+    /// now more than the limits allow.
+    pub(crate) fn update_capacity(&mut self, capacity: usize, capacity_per_user: usize) {
+        self.transactions
+            .update_capacity(capacity, capacity_per_user);
+    }
+
     /// Read `count` transactions from timeline since `timeline_id`.
     /// Returns block of transactions and new last_timeline_id.
     pub(crate) fn read_timeline(
@@ -294,6 +364,11 @@ impl Mempool {
         self.transactions.gen_snapshot(&self.metrics_cache)
     }
 
+    /// Summary of the transactions in mempool by gas price.
+    pub(crate) fn fee_statistics(&mut self) -> MempoolFeeStatistics {
+        self.transactions.fee_statistics()
+    }
+
     #[cfg(test)]
     pub fn get_parking_lot_size(&self) -> usize {
         self.transactions.get_parking_lot_size()
@@ -3,8 +3,9 @@
 
 use aptos_crypto::HashValue;
 use aptos_types::{
-    account_address::AccountAddress, account_config::AccountSequenceInfo,
-    transaction::SignedTransaction,
+    account_address::AccountAddress,
+    account_config::AccountSequenceInfo,
+    transaction::{ReplayProtector, SignedTransaction},
 };
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -41,6 +42,9 @@ impl MempoolTransaction {
     pub(crate) fn get_sender(&self) -> AccountAddress {
         self.txn.sender()
     }
+    pub(crate) fn get_replay_protector(&self) -> ReplayProtector {
+        self.txn.replay_protector()
+    }
     pub(crate) fn get_gas_price(&self) -> u64 {
         self.txn.gas_unit_price()
     }
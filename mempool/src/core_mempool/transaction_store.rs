@@ -3,9 +3,10 @@
 
 use crate::{
     core_mempool::{
+        fee_statistics::{FeeStatistics, MempoolFeeStatistics},
         index::{
             AccountTransactions, ParkingLotIndex, PriorityIndex, PriorityQueueIter, TTLIndex,
-            TimelineIndex,
+            TimelineIndex, TxnPointer,
         },
         transaction::{MempoolTransaction, TimelineState},
         ttl_cache::TtlCache,
@@ -20,7 +21,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::AccountSequenceInfo,
     mempool_status::{MempoolStatus, MempoolStatusCode},
-    transaction::SignedTransaction,
+    transaction::{ReplayProtector, SignedTransaction},
 };
 use std::{
     collections::HashMap,
@@ -46,11 +47,14 @@ pub struct TransactionStore {
     parking_lot_index: ParkingLotIndex,
 
     // Index for looking up transaction by hash.
-    // Transactions are stored by AccountAddress + sequence number.
-    // This index stores map of transaction committed hash to (AccountAddress, sequence number) pair.
+    // Transactions are stored by AccountAddress + sequence number, or nonce if orderless.
+    // This index stores map of transaction committed hash to its TxnPointer.
     // Using transaction commited hash because from end user's point view, a transaction should only have
     // one valid hash.
-    hash_index: HashMap<HashValue, (AccountAddress, u64)>,
+    hash_index: HashMap<HashValue, TxnPointer>,
+
+    // gas prices of the transactions, for clients to pick theirs
+    fee_statistics: FeeStatistics,
 
     // configuration
     capacity: usize,
@@ -72,6 +76,7 @@ impl TransactionStore {
             timeline_index: TimelineIndex::new(),
             parking_lot_index: ParkingLotIndex::new(),
             hash_index: HashMap::new(),
+            fee_statistics: FeeStatistics::new(),
 
             // configuration
             capacity: config.capacity,
@@ -79,16 +84,16 @@ impl TransactionStore {
         }
     }
 
-    /// Fetch transaction by account address + sequence_number.
+    /// Fetch transaction by account address + sequence_number, or nonce if orderless.
     pub(crate) fn get(
         &self,
         address: &AccountAddress,
-        sequence_number: u64,
+        replay_protector: ReplayProtector,
     ) -> Option<SignedTransaction> {
         if let Some(txn) = self
             .transactions
             .get(address)
-            .and_then(|txns| txns.get(&sequence_number))
+            .and_then(|txns| txns.get(&replay_protector))
         {
             return Some(txn.txn.clone());
         }
@@ -97,20 +102,20 @@ impl TransactionStore {
 
     pub(crate) fn get_by_hash(&self, hash: HashValue) -> Option<SignedTransaction> {
         match self.hash_index.get(&hash) {
-            Some((address, seq)) => self.get(address, *seq),
+            Some((address, replay_protector)) => self.get(address, *replay_protector),
             None => None,
         }
     }
 
-    /// Fetch mempool transaction by account address + sequence_number.
+    /// Fetch mempool transaction by account address + sequence_number, or nonce if orderless.
     pub(crate) fn get_mempool_txn(
         &self,
         address: &AccountAddress,
-        sequence_number: u64,
+        replay_protector: ReplayProtector,
     ) -> Option<MempoolTransaction> {
         self.transactions
             .get(address)
-            .and_then(|txns| txns.get(&sequence_number))
+            .and_then(|txns| txns.get(&replay_protector))
             .cloned()
     }
 
@@ -118,6 +123,7 @@ impl TransactionStore {
     pub(crate) fn insert(&mut self, txn: MempoolTransaction) -> MempoolStatus {
         let address = txn.get_sender();
         let sequence_number = txn.sequence_info;
+        let replay_protector = txn.get_replay_protector();
 
         // If the transaction is already in Mempool, we only allow the user to
         // increase the gas unit price to speed up a transaction, but not the max gas.
@@ -125,9 +131,7 @@ impl TransactionStore {
         // Transactions with all the same inputs (but possibly signed differently) are idempotent
         // since the raw transaction is the same
         if let Some(txns) = self.transactions.get_mut(&address) {
-            if let Some(current_version) =
-                txns.get_mut(&sequence_number.transaction_sequence_number)
-            {
+            if let Some(current_version) = txns.get_mut(&replay_protector) {
                 if current_version.txn.payload() != txn.txn.payload() {
                     return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
                         "Transaction already in mempool with different payload".to_string(),
@@ -145,7 +149,7 @@ impl TransactionStore {
                     );
                 } else if current_version.txn.gas_unit_price() < txn.get_gas_price() {
                     // Update txn if gas unit price is a larger value than before
-                    if let Some(txn) = txns.remove(&sequence_number.transaction_sequence_number) {
+                    if let Some(txn) = txns.remove(&replay_protector) {
                         self.index_remove(&txn);
                     };
                 } else if current_version.get_gas_price() > txn.get_gas_price() {
@@ -165,6 +169,8 @@ impl TransactionStore {
             &txn,
             sequence_number.account_sequence_number_type.min_seq(),
         ) {
+            self.fee_statistics
+                .record_mempool_full_rejection(SystemTime::now());
             return MempoolStatus::new(MempoolStatusCode::MempoolIsFull).with_message(format!(
                 "mempool size: {}, capacity: {}",
                 self.system_ttl_index.size(),
@@ -196,14 +202,10 @@ impl TransactionStore {
             // insert into storage and other indexes
             self.system_ttl_index.insert(&txn);
             self.expiration_time_index.insert(&txn);
-            self.hash_index.insert(
-                txn.get_committed_hash(),
-                (
-                    txn.get_sender(),
-                    sequence_number.transaction_sequence_number,
-                ),
-            );
-            txns.insert(sequence_number.transaction_sequence_number, txn);
+            self.hash_index
+                .insert(txn.get_committed_hash(), TxnPointer::from(&txn));
+            self.fee_statistics.add_txn(txn.get_gas_price());
+            txns.insert(replay_protector, txn);
             self.track_indices();
         }
         self.process_ready_transactions(&address, sequence_number.account_sequence_number_type);
@@ -249,19 +251,20 @@ impl TransactionStore {
             && self.check_txn_ready(txn, curr_sequence_number)
         {
             // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
-            if let Some((address, sequence_number)) = self.parking_lot_index.get_poppable() {
+            if let Some((address, replay_protector)) = self.parking_lot_index.get_poppable() {
                 if let Some(txn) = self
                     .transactions
                     .get_mut(&address)
-                    .and_then(|txns| txns.remove(&sequence_number))
+                    .and_then(|txns| txns.remove(&replay_protector))
                 {
                     debug!(
                         LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
                             txn.get_sender(),
-                            txn.sequence_info.transaction_sequence_number
+                            txn.get_replay_protector()
                         ))
                     );
                     self.index_remove(&txn);
+                    self.fee_statistics.record_eviction(SystemTime::now());
                 }
             }
         }
@@ -274,8 +277,12 @@ impl TransactionStore {
     /// (this handles both cases where, (1) txn is first possible txn for an account and (2) the
     /// previous txn is committed).
     /// 2. The txn before this is ready for broadcast but not yet committed.
+    /// Orderless transactions are always ready.
     fn check_txn_ready(&self, txn: &MempoolTransaction, curr_sequence_number: u64) -> bool {
-        let tx_sequence_number = txn.sequence_info.transaction_sequence_number;
+        let tx_sequence_number = match txn.get_replay_protector() {
+            ReplayProtector::SequenceNumber(sequence_number) => sequence_number,
+            ReplayProtector::Nonce(_) => return true,
+        };
         if tx_sequence_number == curr_sequence_number {
             return true;
         } else if tx_sequence_number == 0 {
@@ -285,7 +292,9 @@ impl TransactionStore {
 
         // check previous txn in sequence is ready
         if let Some(account_txns) = self.transactions.get(&txn.get_sender()) {
-            if let Some(prev_txn) = account_txns.get(&(tx_sequence_number - 1)) {
+            if let Some(prev_txn) =
+                account_txns.get(&ReplayProtector::SequenceNumber(tx_sequence_number - 1))
+            {
                 if let TimelineState::Ready(_) = prev_txn.timeline_state {
                     return true;
                 }
@@ -300,6 +309,8 @@ impl TransactionStore {
     ///   TimelineIndex (txns for SharedMempool).
     /// - All transactions of a given CRSN account that are greater than the account's min_nonce
     ///   should be included in both the PriorityIndex and TimelineIndex.
+    /// - All orderless transactions should be included in both the PriorityIndex and
+    ///   TimelineIndex.
     /// - Other txns are considered to be "non-ready" and should be added to ParkingLotIndex.
     fn process_ready_transactions(
         &mut self,
@@ -312,7 +323,7 @@ impl TransactionStore {
             match crsn_or_seqno {
                 AccountSequenceInfo::CRSN { min_nonce, size } => {
                     for i in min_nonce..size {
-                        if let Some(txn) = txns.get_mut(&i) {
+                        if let Some(txn) = txns.get_mut(&ReplayProtector::SequenceNumber(i)) {
                             self.priority_index.insert(txn);
 
                             if txn.timeline_state == TimelineState::NotReady {
@@ -327,7 +338,7 @@ impl TransactionStore {
                     }
                 }
                 AccountSequenceInfo::Sequential(_) => {
-                    while let Some(txn) = txns.get_mut(&min_seq) {
+                    while let Some(txn) = txns.get_mut(&ReplayProtector::SequenceNumber(min_seq)) {
                         self.priority_index.insert(txn);
 
                         if txn.timeline_state == TimelineState::NotReady {
@@ -342,8 +353,18 @@ impl TransactionStore {
                 }
             }
 
+            for (_, txn) in txns.range_mut(ReplayProtector::Nonce(0)..) {
+                self.priority_index.insert(txn);
+                if txn.timeline_state == TimelineState::NotReady {
+                    self.timeline_index.insert(txn);
+                }
+            }
+
             let mut parking_lot_txns = 0;
-            for (_, txn) in txns.range_mut((Bound::Excluded(min_seq), Bound::Unbounded)) {
+            for (_, txn) in txns.range_mut((
+                Bound::Excluded(ReplayProtector::SequenceNumber(min_seq)),
+                Bound::Excluded(ReplayProtector::Nonce(0)),
+            )) {
                 match txn.timeline_state {
                     TimelineState::Ready(_) => {}
                     _ => {
@@ -367,18 +388,16 @@ impl TransactionStore {
         // This can happen if transactions are sent to multiple nodes and one of the
         // nodes has sent the transaction to consensus but this node still has the
         // transaction sitting in mempool.
+        // Orderless transactions are ordered after all sequence numbers, so they are kept.
         if let Some(txns) = self.transactions.get_mut(address) {
-            let mut active = txns.split_off(&sequence_number);
+            let mut active = txns.split_off(&ReplayProtector::SequenceNumber(sequence_number));
             let txns_for_removal = txns.clone();
             txns.clear();
             txns.append(&mut active);
 
             let mut rm_txns = TxnsLog::new();
             for transaction in txns_for_removal.values() {
-                rm_txns.add(
-                    transaction.get_sender(),
-                    transaction.sequence_info.transaction_sequence_number,
-                );
+                rm_txns.add(transaction.get_sender(), transaction.get_replay_protector());
                 self.index_remove(transaction);
             }
             trace!(
@@ -402,20 +421,51 @@ impl TransactionStore {
         self.process_ready_transactions(account, account_sequence_number);
     }
 
-    pub(crate) fn reject_transaction(&mut self, account: &AccountAddress, _sequence_number: u64) {
-        if let Some(txns) = self.transactions.remove(account) {
-            let mut txns_log = TxnsLog::new();
-            for transaction in txns.values() {
-                txns_log.add(
-                    transaction.get_sender(),
-                    transaction.sequence_info.transaction_sequence_number,
-                );
-                self.index_remove(transaction);
-            }
-            debug!(LogSchema::new(LogEntry::CleanRejectedTxn).txns(txns_log));
+    /// Handles the commit of an orderless transaction, which only removes that transaction.
+    pub(crate) fn commit_orderless_transaction(&mut self, account: &AccountAddress, nonce: u64) {
+        if let Some(txn) = self
+            .transactions
+            .get_mut(account)
+            .and_then(|txns| txns.remove(&ReplayProtector::Nonce(nonce)))
+        {
+            self.index_remove(&txn);
         }
     }
 
+    /// Handles transaction rejection. A rejected sequence numbered transaction removes all the
+    /// sequence numbered transactions of the account, as the following ones can't be executed
+    /// anymore, while a rejected orderless transaction only removes itself.
+    pub(crate) fn reject_transaction(
+        &mut self,
+        account: &AccountAddress,
+        replay_protector: ReplayProtector,
+    ) {
+        let txns: Vec<MempoolTransaction> = match replay_protector {
+            ReplayProtector::Nonce(_) => self
+                .transactions
+                .get_mut(account)
+                .and_then(|txns| txns.remove(&replay_protector))
+                .into_iter()
+                .collect(),
+            ReplayProtector::SequenceNumber(_) => match self.transactions.remove(account) {
+                Some(mut txns) => {
+                    let orderless_txns = txns.split_off(&ReplayProtector::Nonce(0));
+                    if !orderless_txns.is_empty() {
+                        self.transactions.insert(*account, orderless_txns);
+                    }
+                    txns.into_values().collect()
+                }
+                None => vec![],
+            },
+        };
+        let mut txns_log = TxnsLog::new();
+        for transaction in txns.iter() {
+            txns_log.add(transaction.get_sender(), transaction.get_replay_protector());
+            self.index_remove(transaction);
+        }
+        debug!(LogSchema::new(LogEntry::CleanRejectedTxn).txns(txns_log));
+    }
+
     /// Removes transaction from all indexes.
     fn index_remove(&mut self, txn: &MempoolTransaction) {
         counters::CORE_MEMPOOL_REMOVED_TXNS.inc();
@@ -425,9 +475,32 @@ impl TransactionStore {
         self.timeline_index.remove(txn);
         self.parking_lot_index.remove(txn);
         self.hash_index.remove(&txn.get_committed_hash());
+        self.fee_statistics.remove_txn(txn.get_gas_price());
         self.track_indices();
     }
 
+    /// Records how long a transaction spent in mempool before it got committed, to be called
+    /// before the commit removes it.
+    pub(crate) fn record_inclusion_delay(
+        &mut self,
+        address: &AccountAddress,
+        replay_protector: ReplayProtector,
+        delay: Duration,
+    ) {
+        if let Some(txn) = self
+            .transactions
+            .get(address)
+            .and_then(|txns| txns.get(&replay_protector))
+        {
+            self.fee_statistics
+                .record_inclusion(txn.get_gas_price(), delay);
+        }
+    }
+
+    pub(crate) fn fee_statistics(&mut self) -> MempoolFeeStatistics {
+        self.fee_statistics.summary(SystemTime::now())
+    }
+
     /// Read `count` transactions from timeline since `timeline_id`.
     /// Returns block of transactions and new last_timeline_id.
     pub(crate) fn read_timeline(
@@ -437,11 +510,11 @@ impl TransactionStore {
     ) -> (Vec<SignedTransaction>, u64) {
         let mut batch = vec![];
         let mut last_timeline_id = timeline_id;
-        for (address, sequence_number) in self.timeline_index.read_timeline(timeline_id, count) {
+        for (address, replay_protector) in self.timeline_index.read_timeline(timeline_id, count) {
             if let Some(txn) = self
                 .transactions
                 .get(&address)
-                .and_then(|txns| txns.get(&sequence_number))
+                .and_then(|txns| txns.get(&replay_protector))
             {
                 batch.push(txn.txn.clone());
                 if let TimelineState::Ready(timeline_id) = txn.timeline_state {
@@ -456,20 +529,17 @@ impl TransactionStore {
         self.timeline_index
             .timeline_range(start_id, end_id)
             .iter()
-            .filter_map(|(account, sequence_number)| {
+            .filter_map(|(account, replay_protector)| {
                 self.transactions
                     .get(account)
-                    .and_then(|txns| txns.get(sequence_number))
+                    .and_then(|txns| txns.get(replay_protector))
                     .map(|txn| txn.txn.clone())
             })
             .collect()
     }
 
     /// Garbage collect old transactions.
-    pub(crate) fn gc_by_system_ttl(
-        &mut self,
-        metrics_cache: &TtlCache<(AccountAddress, u64), SystemTime>,
-    ) {
+    pub(crate) fn gc_by_system_ttl(&mut self, metrics_cache: &TtlCache<TxnPointer, SystemTime>) {
         let now = aptos_infallible::duration_since_epoch();
 
         self.gc(now, true, metrics_cache);
@@ -479,7 +549,7 @@ impl TransactionStore {
     pub(crate) fn gc_by_expiration_time(
         &mut self,
         block_time: Duration,
-        metrics_cache: &TtlCache<(AccountAddress, u64), SystemTime>,
+        metrics_cache: &TtlCache<TxnPointer, SystemTime>,
     ) {
         self.gc(block_time, false, metrics_cache);
     }
@@ -488,7 +558,7 @@ impl TransactionStore {
         &mut self,
         now: Duration,
         by_system_ttl: bool,
-        metrics_cache: &TtlCache<(AccountAddress, u64), SystemTime>,
+        metrics_cache: &TtlCache<TxnPointer, SystemTime>,
     ) {
         let (metric_label, index, log_event) = if by_system_ttl {
             (
@@ -508,27 +578,31 @@ impl TransactionStore {
             .inc();
 
         let mut gc_txns = index.gc(now);
-        // sort the expired txns by order of sequence number per account
-        gc_txns.sort_by_key(|key| (key.address, key.sequence_number));
+        // sort the expired txns by order of sequence number per account, orderless ones last
+        gc_txns.sort_by_key(|key| (key.address, key.replay_protector));
         let mut gc_iter = gc_txns.iter().peekable();
 
         let mut gc_txns_log = TxnsLog::new();
         while let Some(key) = gc_iter.next() {
             if let Some(txns) = self.transactions.get_mut(&key.address) {
-                let park_range_start = Bound::Excluded(key.sequence_number);
-                let park_range_end = gc_iter
-                    .peek()
-                    .filter(|next_key| key.address == next_key.address)
-                    .map_or(Bound::Unbounded, |next_key| {
-                        Bound::Excluded(next_key.sequence_number)
-                    });
-                // mark all following txns as non-ready, i.e. park them
-                for (_, t) in txns.range((park_range_start, park_range_end)) {
-                    self.parking_lot_index.insert(t);
-                    self.priority_index.remove(t);
-                    self.timeline_index.remove(t);
+                // mark all following sequence numbered txns as non-ready, i.e. park them
+                if let ReplayProtector::SequenceNumber(_) = key.replay_protector {
+                    let park_range_start = Bound::Excluded(key.replay_protector);
+                    let park_range_end = gc_iter
+                        .peek()
+                        .filter(|next_key| key.address == next_key.address)
+                        .map_or(Bound::Excluded(ReplayProtector::Nonce(0)), |next_key| {
+                            Bound::Excluded(
+                                next_key.replay_protector.min(ReplayProtector::Nonce(0)),
+                            )
+                        });
+                    for (_, t) in txns.range((park_range_start, park_range_end)) {
+                        self.parking_lot_index.insert(t);
+                        self.priority_index.remove(t);
+                        self.timeline_index.remove(t);
+                    }
                 }
-                if let Some(txn) = txns.remove(&key.sequence_number) {
+                if let Some(txn) = txns.remove(&key.replay_protector) {
                     let is_active = self.priority_index.contains(&txn);
                     let status = if is_active {
                         counters::GC_ACTIVE_TXN_LABEL
@@ -536,10 +610,9 @@ impl TransactionStore {
                         counters::GC_PARKED_TXN_LABEL
                     };
                     let account = txn.get_sender();
-                    let txn_sequence_number = txn.sequence_info.transaction_sequence_number;
-                    gc_txns_log.add_with_status(account, txn_sequence_number, status);
-                    if let Some(&creation_time) = metrics_cache.get(&(account, txn_sequence_number))
-                    {
+                    let replay_protector = txn.get_replay_protector();
+                    gc_txns_log.add_with_status(account, replay_protector, status);
+                    if let Some(&creation_time) = metrics_cache.get(&(account, replay_protector)) {
                         if let Ok(time_delta) = SystemTime::now().duration_since(creation_time) {
                             counters::CORE_MEMPOOL_GC_LATENCY
                                 .with_label_values(&[metric_label, status])
@@ -561,25 +634,26 @@ impl TransactionStore {
         self.priority_index.iter()
     }
 
-    pub(crate) fn gen_snapshot(
-        &self,
-        metrics_cache: &TtlCache<(AccountAddress, u64), SystemTime>,
-    ) -> TxnsLog {
+    pub(crate) fn gen_snapshot(&self, metrics_cache: &TtlCache<TxnPointer, SystemTime>) -> TxnsLog {
         let mut txns_log = TxnsLog::new();
         for (account, txns) in self.transactions.iter() {
-            for (seq_num, _txn) in txns.iter() {
-                let status = if self.parking_lot_index.contains(account, seq_num) {
-                    "parked"
-                } else {
-                    "ready"
-                };
-                let timestamp = metrics_cache.get(&(*account, *seq_num)).cloned();
-                txns_log.add_full_metadata(*account, *seq_num, status, timestamp);
+            for (replay_protector, _txn) in txns.iter() {
+                let is_parked = replay_protector.sequence_number().map_or(false, |seq_num| {
+                    self.parking_lot_index.contains(account, &seq_num)
+                });
+                let status = if is_parked { "parked" } else { "ready" };
+                let timestamp = metrics_cache.get(&(*account, *replay_protector)).cloned();
+                txns_log.add_full_metadata(*account, *replay_protector, status, timestamp);
             }
         }
         txns_log
     }
 
+    pub(crate) fn update_capacity(&mut self, capacity: usize, capacity_per_user: usize) {
+        self.capacity = capacity;
+        self.capacity_per_user = capacity_per_user;
+    }
+
     #[cfg(test)]
     pub(crate) fn get_parking_lot_size(&self) -> usize {
         self.parking_lot_index.size()
@@ -57,18 +57,22 @@
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use core_mempool::{GasPriceBandStatistics, MempoolFeeStatistics};
 pub use shared_mempool::{
     bootstrap, network,
     types::{
         MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
         QuorumStoreResponse, SubmissionStatus,
     },
+    MempoolCapacityHandle,
 };
 #[cfg(any(test, feature = "fuzzing"))]
 pub use tests::{fuzzing, mocks};
+pub use transaction_filter::{TransactionFilter, API_SOURCE, MEMPOOL_SOURCE};
 
 mod core_mempool;
 pub mod counters;
 mod logging;
 mod shared_mempool;
 pub(crate) mod thread_pool;
+mod transaction_filter;
@@ -5,13 +5,21 @@ use crate::shared_mempool::types::{BatchId, QuorumStoreRequest};
 use anyhow::Error;
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_logger::Schema;
-use aptos_types::{account_address::AccountAddress, on_chain_config::OnChainConfigPayload};
+use aptos_types::{
+    account_address::AccountAddress, on_chain_config::OnChainConfigPayload,
+    transaction::ReplayProtector,
+};
 use mempool_notifications::MempoolCommitNotification;
 use serde::Serialize;
 use std::{fmt, fmt::Write, time::SystemTime};
 
 pub struct TxnsLog {
-    txns: Vec<(AccountAddress, u64, Option<String>, Option<SystemTime>)>,
+    txns: Vec<(
+        AccountAddress,
+        ReplayProtector,
+        Option<String>,
+        Option<SystemTime>,
+    )>,
 }
 
 impl TxnsLog {
@@ -19,30 +27,39 @@ impl TxnsLog {
         Self { txns: vec![] }
     }
 
-    pub fn new_txn(account: AccountAddress, seq_num: u64) -> Self {
+    pub fn new_txn(account: AccountAddress, replay_protector: ReplayProtector) -> Self {
         Self {
-            txns: vec![(account, seq_num, None, None)],
+            txns: vec![(account, replay_protector, None, None)],
         }
     }
 
-    pub fn add(&mut self, account: AccountAddress, seq_num: u64) {
-        self.txns.push((account, seq_num, None, None));
+    pub fn add(&mut self, account: AccountAddress, replay_protector: ReplayProtector) {
+        self.txns.push((account, replay_protector, None, None));
     }
 
-    pub fn add_with_status(&mut self, account: AccountAddress, seq_num: u64, status: &str) {
+    pub fn add_with_status(
+        &mut self,
+        account: AccountAddress,
+        replay_protector: ReplayProtector,
+        status: &str,
+    ) {
         self.txns
-            .push((account, seq_num, Some(status.to_string()), None));
+            .push((account, replay_protector, Some(status.to_string()), None));
     }
 
     pub fn add_full_metadata(
         &mut self,
         account: AccountAddress,
-        seq_num: u64,
+        replay_protector: ReplayProtector,
         status: &str,
         timestamp: Option<SystemTime>,
     ) {
-        self.txns
-            .push((account, seq_num, Some(status.to_string()), timestamp));
+        self.txns.push((
+            account,
+            replay_protector,
+            Some(status.to_string()),
+            timestamp,
+        ));
     }
 }
 
@@ -50,8 +67,8 @@ impl fmt::Display for TxnsLog {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut txns = "".to_string();
 
-        for (account, seq_num, status, timestamp) in self.txns.iter() {
-            let mut txn = format!("{}:{}", account, seq_num);
+        for (account, replay_protector, status, timestamp) in self.txns.iter() {
+            let mut txn = format!("{}:{}", account, replay_protector);
             if let Some(status) = status {
                 write!(txn, ":{}", status)?;
             }
@@ -130,6 +147,7 @@ pub enum LogEntry {
     ReconfigUpdate,
     JsonRpc,
     GetTransaction,
+    GetFeeStatistics,
     GetBlock,
     QuorumStore,
     StateSyncCommit,
@@ -148,6 +148,26 @@ async fn handle_client_request<V>(
                 ))
                 .await;
         }
+        MempoolClientRequest::GetFeeStatistics(callback) => {
+            // This timer measures how long it took for the bounded executor to *schedule* the
+            // task.
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_GET_FEE_STATISTICS_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            // This timer measures how long it took for the task to go from scheduled to started.
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_GET_FEE_STATISTICS_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_get_fee_statistics(
+                    smp.clone(),
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        }
     }
 }
 
@@ -178,7 +198,7 @@ fn handle_commit_notification<V>(
             .iter()
             .map(|txn| TransactionSummary {
                 sender: txn.sender,
-                sequence_number: txn.sequence_number,
+                replay_protector: txn.replay_protector,
             })
             .collect(),
         msg.block_timestamp_usecs,
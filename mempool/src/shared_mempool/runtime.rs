@@ -8,6 +8,7 @@ use crate::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
+    transaction_filter::TransactionFilter,
     QuorumStoreRequest,
 };
 use aptos_config::{config::NodeConfig, network_id::NetworkId};
@@ -43,6 +44,7 @@ pub(crate) fn start_shared_mempool<V>(
     validator: Arc<RwLock<V>>,
     subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
+    transaction_filter: TransactionFilter,
 ) where
     V: TransactionValidation + 'static,
 {
@@ -62,6 +64,7 @@ pub(crate) fn start_shared_mempool<V>(
         subscribers,
         config.base.role,
         peer_metadata_storage,
+        transaction_filter,
     );
 
     executor.spawn(coordinator(
@@ -87,6 +90,20 @@ pub(crate) fn start_shared_mempool<V>(
     }
 }
 
+/// A handle for changing the capacity of mempool while it runs
+#[derive(Clone)]
+pub struct MempoolCapacityHandle {
+    mempool: Arc<Mutex<CoreMempool>>,
+}
+
+impl MempoolCapacityHandle {
+    pub fn update_capacity(&self, capacity: usize, capacity_per_user: usize) {
+        self.mempool
+            .lock()
+            .update_capacity(capacity, capacity_per_user);
+    }
+}
+
 pub fn bootstrap(
     config: &NodeConfig,
     db: Arc<dyn DbReader>,
@@ -98,7 +115,8 @@ pub fn bootstrap(
     mempool_listener: MempoolNotificationListener,
     mempool_reconfig_events: ReconfigNotificationListener,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
-) -> Runtime {
+    transaction_filter: TransactionFilter,
+) -> (Runtime, MempoolCapacityHandle) {
     let runtime = Builder::new_multi_thread()
         .thread_name("shared-mem")
         .enable_all()
@@ -106,6 +124,9 @@ pub fn bootstrap(
         .expect("[shared mempool] failed to create runtime");
     let mempool = Arc::new(Mutex::new(CoreMempool::new(config)));
     let vm_validator = Arc::new(RwLock::new(VMValidator::new(Arc::clone(&db))));
+    let capacity_handle = MempoolCapacityHandle {
+        mempool: mempool.clone(),
+    };
     start_shared_mempool(
         runtime.handle(),
         config,
@@ -119,6 +140,7 @@ pub fn bootstrap(
         vm_validator,
         vec![],
         peer_metadata_storage,
+        transaction_filter,
     );
-    runtime
+    (runtime, capacity_handle)
 }
@@ -4,7 +4,7 @@
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::thread_pool::IO_POOL;
 use crate::{
-    core_mempool::{CoreMempool, TimelineState, TxnPointer},
+    core_mempool::{CoreMempool, MempoolFeeStatistics, TimelineState, TxnPointer},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
@@ -12,6 +12,7 @@ use crate::{
         notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification,
         SubmissionStatusBundle,
     },
+    transaction_filter::MEMPOOL_SOURCE,
     QuorumStoreRequest, QuorumStoreResponse, SubmissionStatus,
 };
 use anyhow::Result;
@@ -151,6 +152,26 @@ pub(crate) async fn process_client_get_transaction<V>(
     }
 }
 
+/// Processes get fee statistics request by client.
+pub(crate) async fn process_client_get_fee_statistics<V>(
+    smp: SharedMempool<V>,
+    callback: oneshot::Sender<MempoolFeeStatistics>,
+    timer: HistogramTimer,
+) where
+    V: TransactionValidation,
+{
+    timer.stop_and_record();
+    let fee_statistics = smp.mempool.lock().fee_statistics();
+
+    if callback.send(fee_statistics).is_err() {
+        error!(LogSchema::event_log(
+            LogEntry::GetFeeStatistics,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<V>(
     smp: SharedMempool<V>,
@@ -242,6 +263,21 @@ where
 {
     let mut statuses = vec![];
 
+    // Transactions matching the transaction filter are rejected before anything is read for them
+    let transactions: Vec<_> = transactions
+        .into_iter()
+        .filter_map(|t| match smp.transaction_filter.check(&t, MEMPOOL_SOURCE) {
+            Ok(()) => Some(t),
+            Err(mempool_status) => {
+                statuses.push((t, (mempool_status, None)));
+                None
+            }
+        })
+        .collect();
+    if transactions.is_empty() {
+        return statuses;
+    }
+
     let start_storage_read = Instant::now();
     let state_view = smp
         .db
@@ -301,10 +337,10 @@ where
     let vm_validation_timer = counters::PROCESS_TXN_BREAKDOWN_LATENCY
         .with_label_values(&[counters::VM_VALIDATION_LABEL])
         .start_timer();
-    let validation_results = transactions
-        .iter()
-        .map(|t| smp.validator.read().validate_transaction(t.0.clone()))
-        .collect::<Vec<_>>();
+    let validation_results = smp
+        .validator
+        .read()
+        .validate_transactions(transactions.iter().map(|t| t.0.clone()).collect());
     vm_validation_timer.stop_and_record();
     {
         let mut mempool = smp.mempool.lock();
@@ -388,7 +424,7 @@ pub(crate) fn process_quorum_store_request<V: TransactionValidation>(
         QuorumStoreRequest::GetBatchRequest(max_batch_size, transactions, callback) => {
             let exclude_transactions: HashSet<TxnPointer> = transactions
                 .iter()
-                .map(|txn| (txn.sender, txn.sequence_number))
+                .map(|txn| (txn.sender, txn.replay_protector))
                 .collect();
             let txns;
             {
@@ -447,7 +483,7 @@ pub(crate) fn process_committed_transactions(
     for transaction in transactions {
         pool.remove_transaction(
             &transaction.sender,
-            transaction.sequence_number,
+            transaction.replay_protector,
             is_rejected,
         );
     }
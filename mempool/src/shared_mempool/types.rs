@@ -3,8 +3,10 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool, network::MempoolNetworkInterface,
+    core_mempool::{CoreMempool, MempoolFeeStatistics},
+    network::MempoolNetworkInterface,
     shared_mempool::network::MempoolNetworkSender,
+    transaction_filter::TransactionFilter,
 };
 use anyhow::Result;
 use aptos_config::{
@@ -48,6 +50,7 @@ where
     pub db: Arc<dyn DbReader>,
     pub validator: Arc<RwLock<V>>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
+    pub transaction_filter: TransactionFilter,
 }
 
 impl<V: TransactionValidation + 'static> SharedMempool<V> {
@@ -60,6 +63,7 @@ impl<V: TransactionValidation + 'static> SharedMempool<V> {
         subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
         role: RoleType,
         peer_metadata_storage: Arc<PeerMetadataStorage>,
+        transaction_filter: TransactionFilter,
     ) -> Self {
         let network_interface = MempoolNetworkInterface::new(
             peer_metadata_storage,
@@ -74,6 +78,7 @@ impl<V: TransactionValidation + 'static> SharedMempool<V> {
             db,
             validator,
             subscribers,
+            transaction_filter,
         }
     }
 
@@ -204,6 +209,7 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetFeeStatistics(oneshot::Sender<MempoolFeeStatistics>),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
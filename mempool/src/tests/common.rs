@@ -10,8 +10,9 @@ use aptos_types::{
     account_config::AccountSequenceInfo,
     chain_id::ChainId,
     mempool_status::MempoolStatusCode,
-    transaction::{RawTransaction, Script, SignedTransaction},
+    transaction::{EntryFunction, RawTransaction, Script, SignedTransaction},
 };
+use move_deps::move_core_types::{ident_str, language_storage::ModuleId};
 use once_cell::sync::Lazy;
 use rand::{rngs::StdRng, SeedableRng};
 use std::collections::HashSet;
@@ -38,6 +39,7 @@ pub struct TestTransaction {
     pub(crate) sequence_number: u64,
     pub(crate) gas_price: u64,
     pub(crate) account_seqno_type: AccountSequenceInfo,
+    pub(crate) nonce: Option<u64>,
 }
 
 impl TestTransaction {
@@ -47,6 +49,18 @@ impl TestTransaction {
             sequence_number,
             gas_price,
             account_seqno_type: AccountSequenceInfo::Sequential(0),
+            nonce: None,
+        }
+    }
+
+    /// An orderless transaction protected from replays by `nonce`
+    pub(crate) const fn orderless(address: usize, nonce: u64, gas_price: u64) -> Self {
+        Self {
+            address,
+            sequence_number: 0,
+            gas_price,
+            account_seqno_type: AccountSequenceInfo::Sequential(0),
+            nonce: Some(nonce),
         }
     }
 
@@ -82,15 +96,31 @@ impl TestTransaction {
         max_gas_amount: u64,
         exp_timestamp_secs: u64,
     ) -> SignedTransaction {
-        let raw_txn = RawTransaction::new_script(
-            TestTransaction::get_address(self.address),
-            self.sequence_number,
-            Script::new(vec![], vec![], vec![]),
-            max_gas_amount,
-            self.gas_price,
-            exp_timestamp_secs,
-            ChainId::test(),
-        );
+        let raw_txn = match self.nonce {
+            Some(nonce) => RawTransaction::new_orderless_entry_function(
+                TestTransaction::get_address(self.address),
+                nonce,
+                EntryFunction::new(
+                    ModuleId::new(AccountAddress::ONE, ident_str!("coin").to_owned()),
+                    ident_str!("transfer").to_owned(),
+                    vec![],
+                    vec![],
+                ),
+                max_gas_amount,
+                self.gas_price,
+                exp_timestamp_secs,
+                ChainId::test(),
+            ),
+            None => RawTransaction::new_script(
+                TestTransaction::get_address(self.address),
+                self.sequence_number,
+                Script::new(vec![], vec![], vec![]),
+                max_gas_amount,
+                self.gas_price,
+                exp_timestamp_secs,
+                ChainId::test(),
+            ),
+        };
         let mut seed: [u8; 32] = [0u8; 32];
         seed[..4].copy_from_slice(&[1, 2, 3, 4]);
         let mut rng: StdRng = StdRng::from_seed(seed);
@@ -174,7 +204,7 @@ impl ConsensusMock {
             .union(
                 &block
                     .iter()
-                    .map(|t| (t.sender(), t.sequence_number()))
+                    .map(|t| (t.sender(), t.replay_protector()))
                     .collect(),
             )
             .cloned()
@@ -186,6 +216,6 @@ impl ConsensusMock {
 pub(crate) fn exist_in_metrics_cache(mempool: &CoreMempool, txn: &SignedTransaction) -> bool {
     mempool
         .metrics_cache
-        .get(&(txn.sender(), txn.sequence_number()))
+        .get(&(txn.sender(), txn.replay_protector()))
         .is_some()
 }
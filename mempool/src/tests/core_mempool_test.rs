@@ -10,7 +10,10 @@ use crate::{
 };
 use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
-use aptos_types::{account_config::AccountSequenceInfo, transaction::SignedTransaction};
+use aptos_types::{
+    account_config::AccountSequenceInfo,
+    transaction::{ReplayProtector, SignedTransaction},
+};
 use std::{
     collections::HashSet,
     time::{Duration, SystemTime},
@@ -190,6 +193,93 @@ fn test_metric_cache_add_local_txns() {
     assert_eq!(exist_in_metrics_cache(&mempool, &txns[1]), true);
 }
 
+#[test]
+fn test_orderless_transactions() {
+    let (mut pool, mut consensus) = setup_mempool();
+    let txns = add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::orderless(0, 7, 1),
+            TestTransaction::orderless(0, 3, 2),
+            TestTransaction::new(0, 0, 1),
+            TestTransaction::new(0, 2, 5),
+        ],
+    );
+    // Orderless transactions are indexed by nonce, don't wait for each other or for the
+    // sequence numbers, and don't make the sequence number 2 ready
+    let block = consensus.get_block(&mut pool, 10);
+    assert_eq!(block.len(), 3);
+    assert_eq!(block[0], txns[1]);
+    assert!(block.contains(&txns[0]) && block.contains(&txns[2]));
+    assert_eq!(pool.get_parking_lot_size(), 1);
+    // Already sent to consensus
+    assert!(consensus.get_block(&mut pool, 10).is_empty());
+
+    // Committing the sequence number 0 keeps the orderless transactions
+    pool.remove_transaction(
+        &TestTransaction::get_address(0),
+        ReplayProtector::SequenceNumber(0),
+        false,
+    );
+    let (timeline, _) = pool.read_timeline(0, 10);
+    assert_eq!(timeline.len(), 2);
+
+    // Committing an orderless transaction only removes it
+    pool.remove_transaction(
+        &TestTransaction::get_address(0),
+        ReplayProtector::Nonce(3),
+        false,
+    );
+    let (timeline, _) = pool.read_timeline(0, 10);
+    assert_eq!(timeline, vec![txns[0].clone()]);
+    assert_eq!(pool.get_parking_lot_size(), 1);
+}
+
+#[test]
+fn test_orderless_transaction_update() {
+    let (mut pool, _) = setup_mempool();
+    let txn = TestTransaction::orderless(0, 7, 1);
+    add_txn(&mut pool, txn.clone()).unwrap();
+    // The same nonce with a higher gas price replaces the transaction
+    let mut better_txn = txn;
+    better_txn.gas_price = 2;
+    add_txn(&mut pool, better_txn.clone()).unwrap();
+    let block = pool.get_batch(10, HashSet::new());
+    assert_eq!(block, vec![better_txn.make_signed_transaction()]);
+}
+
+#[test]
+fn test_reject_orderless_transactions() {
+    let (mut pool, _) = setup_mempool();
+    add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::orderless(1, 1, 1),
+            TestTransaction::orderless(1, 2, 1),
+            TestTransaction::new(1, 0, 1),
+            TestTransaction::new(1, 1, 1),
+        ],
+    );
+
+    // A rejected orderless transaction only removes itself
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::Nonce(1),
+        true,
+    );
+    assert_eq!(pool.get_batch(10, HashSet::new()).len(), 3);
+
+    // A rejected sequence number removes the sequence numbers, but not the orderless ones
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(0),
+        true,
+    );
+    let block = pool.get_batch(10, HashSet::new());
+    assert_eq!(block.len(), 1);
+    assert_eq!(block[0].replay_protector(), ReplayProtector::Nonce(2));
+}
+
 #[test]
 fn test_update_transaction_in_mempool() {
     let (mut mempool, mut consensus) = setup_mempool();
@@ -319,7 +409,7 @@ fn test_remove_transaction() {
         vec![TestTransaction::new(0, 0, 1), TestTransaction::new(0, 1, 2)],
     );
     for txn in txns {
-        pool.remove_transaction(&txn.sender(), txn.sequence_number(), false);
+        pool.remove_transaction(&txn.sender(), txn.replay_protector(), false);
     }
     let new_txns = add_txns_to_mempool(
         &mut pool,
@@ -343,7 +433,7 @@ fn test_remove_transaction_crsn() {
         ],
     );
     for txn in txns {
-        pool.remove_transaction(&txn.sender(), txn.sequence_number(), false);
+        pool.remove_transaction(&txn.sender(), txn.replay_protector(), false);
     }
     let new_txns = add_txns_to_mempool(
         &mut pool,
@@ -389,7 +479,11 @@ fn test_commit_callback() {
     // Check that pool is empty.
     assert!(pool.get_batch(1, HashSet::new()).is_empty());
     // Transaction 5 got back from consensus.
-    pool.remove_transaction(&TestTransaction::get_address(1), 5, false);
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(5),
+        false,
+    );
     // Verify that we can execute transaction 6.
     assert_eq!(pool.get_batch(1, HashSet::new())[0], txns[0]);
 }
@@ -399,7 +493,11 @@ fn test_sequence_number_cache() {
     // Checks potential race where StateDB is lagging.
     let mut pool = setup_mempool().0;
     // Callback from consensus should set current sequence number for account.
-    pool.remove_transaction(&TestTransaction::get_address(1), 5, false);
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(5),
+        false,
+    );
 
     // Try to add transaction with sequence number 6 to pool (while last known executed transaction
     // for AC is 0).
@@ -418,8 +516,16 @@ fn test_reset_sequence_number_on_failure() {
     );
 
     // Notify mempool about failure in arbitrary order
-    pool.remove_transaction(&TestTransaction::get_address(1), 0, true);
-    pool.remove_transaction(&TestTransaction::get_address(1), 1, true);
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(0),
+        true,
+    );
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(1),
+        true,
+    );
 
     // Verify that new transaction for this account can be added.
     assert!(add_txn(&mut pool, TestTransaction::new(1, 0, 1)).is_ok());
@@ -459,7 +565,11 @@ fn test_timeline() {
     assert_eq!(view(timeline), vec![2, 3]);
 
     // Simulate callback from consensus to unblock txn 5.
-    pool.remove_transaction(&TestTransaction::get_address(1), 4, false);
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(4),
+        false,
+    );
     let (timeline, _) = pool.read_timeline(0, 10);
     assert_eq!(view(timeline), vec![5]);
     // check parking lot is empty
@@ -478,7 +588,11 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_err());
 
     // Commit transaction and free space.
-    pool.remove_transaction(&TestTransaction::get_address(1), 0, false);
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(0),
+        false,
+    );
     assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_ok());
 
     // Fill it up and check that GC routine will clear space.
@@ -487,6 +601,23 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_ok());
 }
 
+#[test]
+fn test_update_capacity() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 1;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_err());
+
+    pool.update_capacity(2, config.mempool.capacity_per_user);
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_ok());
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_err());
+
+    // Transactions over a lowered capacity are kept, but no more are accepted
+    pool.update_capacity(1, config.mempool.capacity_per_user);
+    assert!(add_txn(&mut pool, TestTransaction::new(2, 0, 1)).is_err());
+}
+
 #[test]
 fn test_parking_lot_eviction() {
     let mut config = NodeConfig::random();
@@ -672,3 +803,58 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
     let txn_by_new_hash = pool.get_by_hash(new_txn_hash);
     assert_eq!(txn_by_new_hash, Some(new_txn));
 }
+
+#[test]
+fn test_fee_statistics() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 4;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    add_txn(&mut pool, TestTransaction::new(1, 0, 150)).unwrap();
+    add_txn(&mut pool, TestTransaction::new(1, 1, 199)).unwrap();
+    // Not ready, gets evicted when mempool is full.
+    add_txn(&mut pool, TestTransaction::new(2, 5, 1_000)).unwrap();
+
+    let num_txns_by_band = |pool: &mut CoreMempool| -> Vec<(u64, u64)> {
+        pool.fee_statistics()
+            .bands
+            .iter()
+            .filter(|band| band.num_txns > 0)
+            .map(|band| (band.min_gas_unit_price, band.num_txns))
+            .collect()
+    };
+    assert_eq!(
+        num_txns_by_band(&mut pool),
+        vec![(0, 1), (150, 2), (1_000, 1)]
+    );
+
+    // Evicts the parked transaction, then mempool is full.
+    add_txn(&mut pool, TestTransaction::new(3, 0, 100)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(4, 0, 100)).is_err());
+    assert_eq!(
+        num_txns_by_band(&mut pool),
+        vec![(0, 1), (100, 1), (150, 2)]
+    );
+
+    pool.remove_transaction(
+        &TestTransaction::get_address(1),
+        ReplayProtector::SequenceNumber(0),
+        false,
+    );
+    let statistics = pool.fee_statistics();
+    assert_eq!(statistics.num_evicted_recently, 1);
+    assert_eq!(statistics.num_rejected_mempool_full_recently, 1);
+    let band = statistics
+        .bands
+        .iter()
+        .find(|band| band.min_gas_unit_price == 150)
+        .unwrap();
+    assert_eq!(band.max_gas_unit_price, Some(200));
+    assert_eq!(band.num_txns, 1);
+    assert!(band.estimated_inclusion_delay_ms.is_some());
+    assert!(statistics
+        .bands
+        .iter()
+        .filter(|band| band.min_gas_unit_price != 150)
+        .all(|band| band.estimated_inclusion_delay_ms.is_none()));
+}
@@ -4,6 +4,7 @@
 use crate::{
     core_mempool::{CoreMempool, TimelineState},
     shared_mempool::{tasks, types::SharedMempool},
+    transaction_filter::TransactionFilter,
 };
 use aptos_config::{config::NodeConfig, network_id::NetworkId};
 use aptos_infallible::{Mutex, RwLock};
@@ -45,6 +46,7 @@ pub fn test_mempool_process_incoming_transactions_impl(
         vec![],
         config.base.role,
         PeerMetadataStorage::new(&[NetworkId::Validator]),
+        TransactionFilter::default(),
     );
 
     let _ = tasks::process_incoming_transactions(&smp, txns, timeline_state);
@@ -5,6 +5,7 @@ use crate::{
     core_mempool::{CoreMempool, TimelineState},
     network::{MempoolNetworkEvents, MempoolNetworkSender},
     shared_mempool::start_shared_mempool,
+    transaction_filter::TransactionFilter,
     MempoolClientSender, QuorumStoreRequest,
 };
 use anyhow::{format_err, Result};
@@ -135,6 +136,7 @@ impl MockSharedMempool {
             Arc::new(RwLock::new(validator)),
             vec![],
             peer_metadata_storage,
+            TransactionFilter::default(),
         );
 
         (ac_client, mempool, quorum_store_sender, mempool_notifier)
@@ -168,7 +170,7 @@ impl MockSharedMempool {
 
     pub fn remove_txn(&self, txn: &SignedTransaction) {
         let mut pool = self.mempool.lock();
-        pool.remove_transaction(&txn.sender(), txn.sequence_number(), false)
+        pool.remove_transaction(&txn.sender(), txn.replay_protector(), false)
     }
 
     /// True if all the given txns are in mempool, else false.
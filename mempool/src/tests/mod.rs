@@ -13,6 +13,8 @@ mod multi_node_test;
 mod node;
 #[cfg(test)]
 mod shared_mempool_test;
+#[cfg(test)]
+mod transaction_filter_test;
 
 pub mod fuzzing;
 #[cfg(any(feature = "fuzzing", test))]
@@ -16,7 +16,10 @@ use aptos_config::{
     config::{NodeConfig, PeerRole},
     network_id::{NetworkId, PeerNetworkId},
 };
-use aptos_types::{transaction::SignedTransaction, PeerId};
+use aptos_types::{
+    transaction::{ReplayProtector, SignedTransaction},
+    PeerId,
+};
 use netcore::transport::ConnectionOrigin;
 use network::{
     peer_manager::{PeerManagerNotification, PeerManagerRequest},
@@ -434,7 +437,7 @@ impl TestHarness {
             .metrics_cache
             .get(&(
                 TestTransaction::get_address(txn.address),
-                txn.sequence_number,
+                ReplayProtector::SequenceNumber(txn.sequence_number),
             ))
             .is_some()
     }
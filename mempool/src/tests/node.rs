@@ -8,6 +8,7 @@ use crate::{
         network::MempoolNetworkSender, start_shared_mempool, types::SharedMempoolNotification,
     },
     tests::common::TestTransaction,
+    transaction_filter::TransactionFilter,
 };
 use aptos_config::{
     config::{Identity, NodeConfig, PeerRole, RoleType},
@@ -596,6 +597,7 @@ fn start_node_mempool(
         Arc::new(RwLock::new(MockVMValidator)),
         vec![sender],
         peer_metadata_storage,
+        TransactionFilter::default(),
     );
 
     (mempool, runtime, subscriber)
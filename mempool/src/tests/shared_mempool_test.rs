@@ -36,7 +36,7 @@ fn test_consensus_events_rejected_txns() {
 
     let transactions = vec![TransactionSummary {
         sender: committed_txn.sender(),
-        sequence_number: committed_txn.sequence_number(),
+        replay_protector: committed_txn.replay_protector(),
     }];
     let (callback, callback_rcv) = oneshot::channel();
     let req = QuorumStoreRequest::RejectNotification(transactions, callback);
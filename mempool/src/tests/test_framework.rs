@@ -6,6 +6,7 @@ use crate::{
     network::{MempoolNetworkEvents, MempoolNetworkSender, MempoolSyncMsg},
     shared_mempool::start_shared_mempool,
     tests::common::TestTransaction,
+    transaction_filter::TransactionFilter,
     MempoolClientRequest, MempoolClientSender, QuorumStoreRequest,
 };
 use aptos_config::{
@@ -498,6 +499,7 @@ fn setup_mempool(
         vm_validator,
         vec![sender],
         peer_metadata_storage,
+        TransactionFilter::default(),
     );
 
     (
@@ -0,0 +1,177 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    core_mempool::{CoreMempool, TimelineState},
+    shared_mempool::{tasks, types::SharedMempool},
+    tests::common::TestTransaction,
+    transaction_filter::{TransactionFilter, API_SOURCE},
+};
+use aptos_config::{
+    config::{NodeConfig, TransactionFilterConfig, TransactionFilterRule, TransactionPayloadType},
+    network_id::NetworkId,
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use aptos_infallible::{Mutex, RwLock};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    mempool_status::MempoolStatusCode,
+    transaction::{EntryFunction, RawTransaction, SignedTransaction},
+};
+use move_deps::move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use network::application::storage::PeerMetadataStorage;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{collections::HashMap, sync::Arc};
+use storage_interface::mock::MockDbReaderWriter;
+use vm_validator::mocks::mock_vm_validator::MockVMValidator;
+
+fn entry_function_transaction(
+    sender: AccountAddress,
+    module_address: AccountAddress,
+    module: &str,
+    function: &str,
+) -> SignedTransaction {
+    let entry_function = EntryFunction::new(
+        ModuleId::new(module_address, Identifier::new(module).unwrap()),
+        Identifier::new(function).unwrap(),
+        vec![],
+        vec![],
+    );
+    let private_key = Ed25519PrivateKey::generate(&mut StdRng::from_seed([0; 32]));
+    RawTransaction::new_entry_function(sender, 0, entry_function, 100, 1, u64::MAX, ChainId::test())
+        .sign(&private_key, private_key.public_key())
+        .unwrap()
+        .into_inner()
+}
+
+fn filter(rules: Vec<TransactionFilterRule>) -> TransactionFilter {
+    TransactionFilter::new(&TransactionFilterConfig { rules }).unwrap()
+}
+
+fn is_rejected(filter: &TransactionFilter, txn: &SignedTransaction) -> bool {
+    match filter.check(txn, API_SOURCE) {
+        Ok(()) => false,
+        Err(mempool_status) => {
+            assert_eq!(mempool_status.code, MempoolStatusCode::RejectedByFilter);
+            true
+        }
+    }
+}
+
+#[test]
+fn test_transaction_filter_rules() {
+    let sender = TestTransaction::get_address(0);
+    let framework = AccountAddress::from_hex_literal("0x2").unwrap();
+    let script = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let transfer = entry_function_transaction(sender, AccountAddress::ONE, "coin", "transfer");
+    let other_module = entry_function_transaction(
+        TestTransaction::get_address(1),
+        framework,
+        "coin",
+        "transfer",
+    );
+
+    // Without rules, everything goes through
+    let default_filter = TransactionFilter::default();
+    assert!(!is_rejected(&default_filter, &script));
+    assert!(!is_rejected(&default_filter, &transfer));
+
+    let sender_filter = filter(vec![TransactionFilterRule::Sender(sender)]);
+    assert!(is_rejected(&sender_filter, &script));
+    assert!(is_rejected(&sender_filter, &transfer));
+    assert!(!is_rejected(&sender_filter, &other_module));
+
+    let entry_function_filter = filter(vec![TransactionFilterRule::EntryFunction(
+        "0x1::coin::transfer".to_string(),
+    )]);
+    assert!(!is_rejected(&entry_function_filter, &script));
+    assert!(is_rejected(&entry_function_filter, &transfer));
+    assert!(!is_rejected(&entry_function_filter, &other_module));
+
+    let module_address_filter = filter(vec![TransactionFilterRule::ModuleAddress(framework)]);
+    assert!(!is_rejected(&module_address_filter, &script));
+    assert!(!is_rejected(&module_address_filter, &transfer));
+    assert!(is_rejected(&module_address_filter, &other_module));
+
+    let payload_type_filter = filter(vec![TransactionFilterRule::PayloadType(
+        TransactionPayloadType::Script,
+    )]);
+    assert!(is_rejected(&payload_type_filter, &script));
+    assert!(!is_rejected(&payload_type_filter, &transfer));
+}
+
+#[test]
+fn test_transaction_filter_update() {
+    let script = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let transaction_filter = TransactionFilter::default();
+    let handle = transaction_filter.clone();
+
+    handle
+        .update(&TransactionFilterConfig {
+            rules: vec![TransactionFilterRule::PayloadType(
+                TransactionPayloadType::Script,
+            )],
+        })
+        .unwrap();
+    assert!(is_rejected(&transaction_filter, &script));
+
+    // Invalid rules are rejected as a whole, and the current rules are kept
+    for entry_function in [
+        "0x1::coin",
+        "0x1::coin::transfer::extra",
+        "one::coin::transfer",
+    ] {
+        let config = TransactionFilterConfig {
+            rules: vec![TransactionFilterRule::EntryFunction(
+                entry_function.to_string(),
+            )],
+        };
+        assert!(TransactionFilter::new(&config).is_err());
+        assert!(handle.update(&config).is_err());
+    }
+    assert!(is_rejected(&transaction_filter, &script));
+
+    handle.update(&TransactionFilterConfig::default()).unwrap();
+    assert!(!is_rejected(&transaction_filter, &script));
+}
+
+#[test]
+fn test_filtered_transactions_not_ingested() {
+    let config = NodeConfig::default();
+    let smp = SharedMempool::new(
+        Arc::new(Mutex::new(CoreMempool::new(&config))),
+        config.mempool.clone(),
+        HashMap::new(),
+        Arc::new(MockDbReaderWriter),
+        Arc::new(RwLock::new(MockVMValidator)),
+        vec![],
+        config.base.role,
+        PeerMetadataStorage::new(&[NetworkId::Validator]),
+        filter(vec![TransactionFilterRule::Sender(
+            TestTransaction::get_address(0),
+        )]),
+    );
+
+    let filtered = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let allowed = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    let statuses = tasks::process_incoming_transactions(
+        &smp,
+        vec![filtered.clone(), allowed.clone()],
+        TimelineState::NotReady,
+    );
+    assert_eq!(statuses.len(), 2);
+    for (txn, (mempool_status, _)) in statuses {
+        if txn == filtered {
+            assert_eq!(mempool_status.code, MempoolStatusCode::RejectedByFilter);
+        } else {
+            assert_eq!(txn, allowed);
+            assert_ne!(mempool_status.code, MempoolStatusCode::RejectedByFilter);
+        }
+    }
+    assert!(smp
+        .mempool
+        .lock()
+        .get_by_hash(filtered.committed_hash())
+        .is_none());
+}
@@ -0,0 +1,173 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use anyhow::{bail, format_err};
+use aptos_config::config::{
+    TransactionFilterConfig, TransactionFilterRule, TransactionPayloadType,
+};
+use aptos_infallible::RwLock;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_address::AccountAddress,
+    mempool_status::{MempoolStatus, MempoolStatusCode},
+    transaction::{EntryFunction, SignedTransaction, TransactionPayload},
+};
+use move_deps::move_core_types::identifier::Identifier;
+use std::sync::Arc;
+
+/// Where a transaction was received from, for the audit log and metrics of rejections
+pub const API_SOURCE: &str = "api";
+pub const MEMPOOL_SOURCE: &str = "mempool";
+
+/// Rejects the transactions matching any of the rules of the `TransactionFilterConfig`. Every
+/// rejection is logged, with the rule it matched and where the transaction came from.
+///
+/// The rules can be replaced while the node runs with `update`, which is shared by all clones of
+/// the filter. The default filter has no rules, and lets every transaction through.
+#[derive(Clone, Default)]
+pub struct TransactionFilter {
+    rules: Arc<RwLock<Arc<Vec<CompiledRule>>>>,
+}
+
+impl TransactionFilter {
+    pub fn new(config: &TransactionFilterConfig) -> anyhow::Result<Self> {
+        let filter = Self::default();
+        filter.update(config)?;
+        Ok(filter)
+    }
+
+    /// Replaces the rules with those of `config`. If any of them is invalid, the current rules
+    /// are kept.
+    pub fn update(&self, config: &TransactionFilterConfig) -> anyhow::Result<()> {
+        let rules = config
+            .rules
+            .iter()
+            .map(CompiledRule::new)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        *self.rules.write() = Arc::new(rules);
+        Ok(())
+    }
+
+    /// Checks `txn` against the rules, returning the status to reject it with if it matches one.
+    /// `source` is where the transaction was received, e.g. `API_SOURCE`.
+    pub fn check(
+        &self,
+        txn: &SignedTransaction,
+        source: &'static str,
+    ) -> Result<(), MempoolStatus> {
+        let rules = self.rules.read().clone();
+        let rule = match rules.iter().find(|rule| rule.matches(txn)) {
+            Some(rule) => rule,
+            None => return Ok(()),
+        };
+
+        counters::TRANSACTION_FILTER_REJECTIONS
+            .with_label_values(&[source, rule.label()])
+            .inc();
+        info!(
+            source = source,
+            sender = %txn.sender(),
+            sequence_number = txn.sequence_number(),
+            rule = ?rule.rule,
+            "Rejected a transaction matching a transaction filter rule"
+        );
+        Err(
+            MempoolStatus::new(MempoolStatusCode::RejectedByFilter).with_message(format!(
+                "Transaction matches the filter rule {:?}",
+                rule.rule
+            )),
+        )
+    }
+}
+
+/// A rule of the config, with the entry function it names parsed
+struct CompiledRule {
+    rule: TransactionFilterRule,
+    entry_function: Option<(AccountAddress, Identifier, Identifier)>,
+}
+
+impl CompiledRule {
+    fn new(rule: &TransactionFilterRule) -> anyhow::Result<Self> {
+        let entry_function = match rule {
+            TransactionFilterRule::EntryFunction(entry_function) => {
+                Some(parse_entry_function(entry_function)?)
+            }
+            _ => None,
+        };
+        Ok(Self {
+            rule: rule.clone(),
+            entry_function,
+        })
+    }
+
+    fn matches(&self, txn: &SignedTransaction) -> bool {
+        let payload = txn.payload();
+        match &self.rule {
+            TransactionFilterRule::Sender(sender) => txn.sender() == *sender,
+            TransactionFilterRule::EntryFunction(_) => {
+                match (&self.entry_function, entry_function(payload)) {
+                    (Some((address, module, function)), Some(entry_function)) => {
+                        entry_function.module().address() == address
+                            && entry_function.module().name() == module.as_ident_str()
+                            && entry_function.function() == function.as_ident_str()
+                    }
+                    _ => false,
+                }
+            }
+            TransactionFilterRule::ModuleAddress(address) => entry_function(payload)
+                .map_or(false, |entry_function| {
+                    entry_function.module().address() == address
+                }),
+            TransactionFilterRule::PayloadType(payload_type) => {
+                self::payload_type(payload) == *payload_type
+            }
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self.rule {
+            TransactionFilterRule::Sender(_) => "sender",
+            TransactionFilterRule::EntryFunction(_) => "entry_function",
+            TransactionFilterRule::ModuleAddress(_) => "module_address",
+            TransactionFilterRule::PayloadType(_) => "payload_type",
+        }
+    }
+}
+
+/// Parses an entry function given as `address::module::function`
+fn parse_entry_function(
+    entry_function: &str,
+) -> anyhow::Result<(AccountAddress, Identifier, Identifier)> {
+    let parts: Vec<_> = entry_function.split("::").collect();
+    if parts.len() != 3 {
+        bail!(
+            "Invalid entry function {}, it must be given as address::module::function",
+            entry_function
+        );
+    }
+    let invalid = |part: &str| format_err!("Invalid {} in entry function {}", part, entry_function);
+    let address = AccountAddress::from_hex_literal(parts[0]).map_err(|_| invalid("address"))?;
+    let module = Identifier::new(parts[1]).map_err(|_| invalid("module name"))?;
+    let function = Identifier::new(parts[2]).map_err(|_| invalid("function name"))?;
+    Ok((address, module, function))
+}
+
+fn entry_function(payload: &TransactionPayload) -> Option<&EntryFunction> {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+        TransactionPayload::OrderlessEntryFunction(orderless) => Some(orderless.entry_function()),
+        TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+    }
+}
+
+fn payload_type(payload: &TransactionPayload) -> TransactionPayloadType {
+    match payload {
+        TransactionPayload::Script(_) => TransactionPayloadType::Script,
+        TransactionPayload::ModuleBundle(_) => TransactionPayloadType::ModuleBundle,
+        TransactionPayload::EntryFunction(_) => TransactionPayloadType::EntryFunction,
+        TransactionPayload::OrderlessEntryFunction(_) => {
+            TransactionPayloadType::OrderlessEntryFunction
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    metrics, stats::LatencyRecorder, BenchmarkResult, Error, NetworkBenchmarkMessage,
+    NetworkBenchmarkRequest, NetworkBenchmarkSender,
+};
+use aptos_config::config::NetworkBenchmarkConfig;
+use aptos_logger::prelude::*;
+use aptos_types::PeerId;
+use futures::stream::{FuturesUnordered, StreamExt};
+use network::ProtocolId;
+use std::time::{Duration, Instant};
+
+/// The duration, rate and message size of a benchmark, within the configured limits
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BenchmarkParameters {
+    pub duration: Duration,
+    pub bytes_per_sec: u64,
+    pub message_size: u64,
+}
+
+impl BenchmarkParameters {
+    /// Zero values fall back to the configured maximum duration and rate, and to the default
+    /// message size. Values over the configured limits are rejected, rather than silently capped.
+    pub fn new(
+        config: &NetworkBenchmarkConfig,
+        duration_ms: u64,
+        bytes_per_sec: u64,
+        message_size: u64,
+    ) -> Result<Self, Error> {
+        let duration_ms = if duration_ms == 0 {
+            config.max_duration_ms
+        } else {
+            duration_ms
+        };
+        let bytes_per_sec = if bytes_per_sec == 0 {
+            config.max_bytes_per_sec
+        } else {
+            bytes_per_sec
+        };
+        let message_size = if message_size == 0 {
+            config.default_message_size
+        } else {
+            message_size
+        };
+
+        if duration_ms > config.max_duration_ms {
+            return Err(Error::InvalidParameters(format!(
+                "Duration of {} ms is longer than the maximum of {} ms",
+                duration_ms, config.max_duration_ms
+            )));
+        }
+        if bytes_per_sec > config.max_bytes_per_sec {
+            return Err(Error::InvalidParameters(format!(
+                "Rate of {} bytes/s is higher than the maximum of {} bytes/s",
+                bytes_per_sec, config.max_bytes_per_sec
+            )));
+        }
+        if message_size > config.max_message_size {
+            return Err(Error::InvalidParameters(format!(
+                "Message size of {} bytes is larger than the maximum of {} bytes",
+                message_size, config.max_message_size
+            )));
+        }
+        if bytes_per_sec == 0 || message_size == 0 {
+            return Err(Error::InvalidParameters(
+                "The rate and message size must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            duration: Duration::from_millis(duration_ms),
+            bytes_per_sec,
+            message_size,
+        })
+    }
+
+    /// How long after the start of the benchmark the message with the given sequence number may
+    /// be sent, so the rate is never exceeded
+    pub fn send_time(&self, sequence: u64) -> Duration {
+        Duration::from_secs_f64(
+            (sequence as f64 * self.message_size as f64) / self.bytes_per_sec as f64,
+        )
+    }
+}
+
+/// Sends messages to the peer until the duration is up, paced to the rate and with at most
+/// `max_in_flight` messages awaiting an acknowledgement. Messages still in flight at the end are
+/// waited for, which is bounded by `request_timeout`.
+pub(crate) async fn run_benchmark(
+    network_sender: &NetworkBenchmarkSender,
+    peer_id: PeerId,
+    parameters: &BenchmarkParameters,
+    max_in_flight: usize,
+    request_timeout: Duration,
+) -> BenchmarkResult {
+    info!(
+        "Starting a network benchmark against {} for {:?} at {} bytes/s",
+        peer_id, parameters.duration, parameters.bytes_per_sec
+    );
+    let payload = vec![0; parameters.message_size as usize];
+    let send_message = |sequence: u64| {
+        let request = NetworkBenchmarkMessage::Request(NetworkBenchmarkRequest {
+            sequence,
+            payload: payload.clone(),
+        });
+        async move {
+            let sent_at = Instant::now();
+            let response = network_sender
+                .send_rpc(
+                    peer_id,
+                    ProtocolId::NetworkBenchmarkRpc,
+                    request,
+                    request_timeout,
+                )
+                .await;
+            let acknowledged = matches!(
+                response,
+                Ok(NetworkBenchmarkMessage::Response(Ok(ref response)))
+                    if response.sequence == sequence
+            );
+            if let Err(error) = response {
+                debug!("Network benchmark message to {} failed: {}", peer_id, error);
+            }
+            (acknowledged, sent_at.elapsed())
+        }
+    };
+
+    let start = Instant::now();
+    let mut recorder = LatencyRecorder::default();
+    let mut in_flight = FuturesUnordered::new();
+    let mut sequence = 0;
+    loop {
+        let elapsed = start.elapsed();
+        if elapsed >= parameters.duration {
+            break;
+        }
+
+        let send_time = parameters.send_time(sequence);
+        if in_flight.len() < max_in_flight && elapsed >= send_time {
+            in_flight.push(send_message(sequence));
+            sequence += 1;
+            continue;
+        }
+
+        // Wait for an acknowledgement, or until the next message may be sent
+        let wait = if in_flight.len() < max_in_flight {
+            send_time - elapsed
+        } else {
+            parameters.duration - elapsed
+        };
+        tokio::select! {
+            Some((acknowledged, latency)) = in_flight.next(), if !in_flight.is_empty() => {
+                record(&mut recorder, acknowledged, latency, parameters.message_size);
+            }
+            _ = tokio::time::sleep(wait.min(parameters.duration - elapsed)) => {}
+        }
+    }
+    while let Some((acknowledged, latency)) = in_flight.next().await {
+        record(
+            &mut recorder,
+            acknowledged,
+            latency,
+            parameters.message_size,
+        );
+    }
+
+    let result = recorder.finish(start.elapsed());
+    info!(
+        "Finished the network benchmark against {}: {:?}",
+        peer_id, result
+    );
+    result
+}
+
+fn record(recorder: &mut LatencyRecorder, acknowledged: bool, latency: Duration, size: u64) {
+    if acknowledged {
+        metrics::BENCHMARK_MESSAGES_SENT
+            .with_label_values(&["acknowledged"])
+            .inc();
+        recorder.record_success(latency, size);
+    } else {
+        metrics::BENCHMARK_MESSAGES_SENT
+            .with_label_values(&["failed"])
+            .inc();
+        recorder.record_failure();
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! An opt-in benchmark of the link to a connected peer. The node running the benchmark sends
+//! messages of a fixed size to the peer over the authenticated network transport, at a bounded
+//! rate for a bounded duration, and the peer acknowledges each of them. Only nodes that enable
+//! the benchmark in their config answer, or run, benchmarks.
+
+use aptos_config::{config::NetworkBenchmarkConfig, network_id::NetworkId};
+use aptos_types::PeerId;
+use channel::{aptos_channel, message_queues::QueueStyle};
+use network::{
+    protocols::network::{AppConfig, NetworkSender},
+    ProtocolId,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+mod client;
+mod metrics;
+mod server;
+mod stats;
+
+#[cfg(test)]
+mod tests;
+
+pub use client::BenchmarkParameters;
+pub use server::NetworkBenchmarkServer;
+pub use stats::BenchmarkResult;
+
+pub type NetworkBenchmarkSender = NetworkSender<NetworkBenchmarkMessage>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("A network benchmark is already running")]
+    AlreadyRunning,
+
+    #[error("Invalid benchmark parameters: {0}")]
+    InvalidParameters(String),
+
+    #[error("The network benchmark isn't enabled on the {0} network")]
+    NetworkNotEnabled(NetworkId),
+}
+
+/// An error returned by the peer when it can't acknowledge a message
+#[derive(Clone, Debug, Deserialize, Eq, Error, PartialEq, Serialize)]
+pub enum NetworkBenchmarkError {
+    #[error("Message of {0} bytes is larger than the maximum of {1} bytes")]
+    MessageTooLarge(u64, u64),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum NetworkBenchmarkMessage {
+    Request(NetworkBenchmarkRequest),
+    Response(Result<NetworkBenchmarkResponse, NetworkBenchmarkError>),
+}
+
+/// A message of benchmark traffic, which the peer acknowledges
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NetworkBenchmarkRequest {
+    pub sequence: u64,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NetworkBenchmarkResponse {
+    /// The sequence number of the acknowledged request
+    pub sequence: u64,
+}
+
+/// The network endpoint of the benchmark, which both sends and answers benchmark messages
+pub fn network_endpoint_config(config: &NetworkBenchmarkConfig) -> AppConfig {
+    AppConfig::p2p(
+        [ProtocolId::NetworkBenchmarkRpc],
+        aptos_channel::Config::new(config.max_network_channel_size as usize)
+            .queue_style(QueueStyle::FIFO)
+            .counters(&metrics::PENDING_NETWORK_BENCHMARK_NETWORK_EVENTS),
+    )
+}
+
+/// Runs benchmarks against peers on any of the networks the benchmark is enabled on. Only one
+/// benchmark runs at a time, so concurrent requests can't multiply the configured rate.
+#[derive(Clone)]
+pub struct NetworkBenchmarkClient {
+    config: NetworkBenchmarkConfig,
+    network_senders: HashMap<NetworkId, NetworkBenchmarkSender>,
+    running: Arc<Mutex<()>>,
+}
+
+impl NetworkBenchmarkClient {
+    pub fn new(
+        config: NetworkBenchmarkConfig,
+        network_senders: HashMap<NetworkId, NetworkBenchmarkSender>,
+    ) -> Self {
+        Self {
+            config,
+            network_senders,
+            running: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Benchmarks the link to `peer_id`. Zero values in the request fall back to the configured
+    /// limits and defaults.
+    pub async fn run(
+        &self,
+        network_id: NetworkId,
+        peer_id: PeerId,
+        duration_ms: u64,
+        bytes_per_sec: u64,
+        message_size: u64,
+    ) -> Result<BenchmarkResult, Error> {
+        let network_sender = self
+            .network_senders
+            .get(&network_id)
+            .ok_or(Error::NetworkNotEnabled(network_id))?;
+        let parameters =
+            BenchmarkParameters::new(&self.config, duration_ms, bytes_per_sec, message_size)?;
+        let _running = self.running.try_lock().map_err(|_| Error::AlreadyRunning)?;
+
+        Ok(client::run_benchmark(
+            network_sender,
+            peer_id,
+            &parameters,
+            self.config.max_in_flight_messages as usize,
+            Duration::from_millis(self.config.request_timeout_ms),
+        )
+        .await)
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec,
+};
+use once_cell::sync::Lazy;
+
+/// Counter for pending network events to the network benchmark
+pub static PENDING_NETWORK_BENCHMARK_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_benchmark_pending_network_events",
+        "Counters for pending network events for the network benchmark",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Counter for the benchmark messages sent by this node, by outcome
+pub static BENCHMARK_MESSAGES_SENT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_benchmark_messages_sent",
+        "Counters for the network benchmark messages sent, by outcome",
+        &["outcome"]
+    )
+    .unwrap()
+});
+
+/// Counter for the benchmark payload bytes received from peers
+pub static BENCHMARK_BYTES_RECEIVED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_network_benchmark_bytes_received",
+        "Payload bytes of the network benchmark messages received from peers"
+    )
+    .unwrap()
+});
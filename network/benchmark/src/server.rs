@@ -0,0 +1,72 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    metrics, NetworkBenchmarkError, NetworkBenchmarkMessage, NetworkBenchmarkRequest,
+    NetworkBenchmarkResponse,
+};
+use aptos_config::config::NetworkBenchmarkConfig;
+use aptos_logger::prelude::*;
+use bytes::Bytes;
+use futures::stream::StreamExt;
+use network::protocols::network::{Event, NetworkEvents, RpcError};
+
+pub type NetworkBenchmarkEvents = NetworkEvents<NetworkBenchmarkMessage>;
+
+/// Acknowledges the benchmark messages sent by peers
+pub struct NetworkBenchmarkServer {
+    config: NetworkBenchmarkConfig,
+    network_events: NetworkBenchmarkEvents,
+}
+
+impl NetworkBenchmarkServer {
+    pub fn new(config: NetworkBenchmarkConfig, network_events: NetworkBenchmarkEvents) -> Self {
+        Self {
+            config,
+            network_events,
+        }
+    }
+
+    pub async fn start(mut self) {
+        while let Some(event) = self.network_events.next().await {
+            if let Event::RpcRequest(
+                peer_id,
+                NetworkBenchmarkMessage::Request(request),
+                protocol_id,
+                response_sender,
+            ) = event
+            {
+                let response = handle_request(&self.config, &request);
+                if let Err(error) = &response {
+                    debug!(
+                        "Rejected network benchmark message from {}: {}",
+                        peer_id, error
+                    );
+                }
+                let response = protocol_id
+                    .to_bytes(&NetworkBenchmarkMessage::Response(response))
+                    .map(Bytes::from)
+                    .map_err(RpcError::Error);
+                let _ = response_sender.send(response);
+            }
+        }
+    }
+}
+
+/// Acknowledges a benchmark message, unless it's larger than the configured maximum
+pub(crate) fn handle_request(
+    config: &NetworkBenchmarkConfig,
+    request: &NetworkBenchmarkRequest,
+) -> Result<NetworkBenchmarkResponse, NetworkBenchmarkError> {
+    let size = request.payload.len() as u64;
+    metrics::BENCHMARK_BYTES_RECEIVED.inc_by(size);
+    if size > config.max_message_size {
+        return Err(NetworkBenchmarkError::MessageTooLarge(
+            size,
+            config.max_message_size,
+        ));
+    }
+    Ok(NetworkBenchmarkResponse {
+        sequence: request.sequence,
+    })
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use std::time::Duration;
+
+/// The outcome of a benchmark against a peer
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct BenchmarkResult {
+    /// Messages the peer acknowledged
+    pub messages_acknowledged: u64,
+    /// Messages that failed or timed out
+    pub messages_failed: u64,
+    /// Bytes of payload the peer acknowledged
+    pub bytes_acknowledged: u64,
+    /// From the first message sent to the last one answered
+    pub duration: Duration,
+    pub throughput_bytes_per_sec: u64,
+    pub min_latency: Duration,
+    pub avg_latency: Duration,
+    pub p50_latency: Duration,
+    pub p99_latency: Duration,
+    pub max_latency: Duration,
+    /// The mean difference between the latencies of consecutively answered messages
+    pub jitter: Duration,
+}
+
+/// Collects the round trip latencies of the messages of a benchmark
+#[derive(Debug, Default)]
+pub struct LatencyRecorder {
+    latencies: Vec<Duration>,
+    total_jitter: Duration,
+    messages_failed: u64,
+    bytes_acknowledged: u64,
+}
+
+impl LatencyRecorder {
+    pub fn record_success(&mut self, latency: Duration, payload_size: u64) {
+        if let Some(previous) = self.latencies.last() {
+            self.total_jitter += if latency > *previous {
+                latency - *previous
+            } else {
+                *previous - latency
+            };
+        }
+        self.latencies.push(latency);
+        self.bytes_acknowledged += payload_size;
+    }
+
+    pub fn record_failure(&mut self) {
+        self.messages_failed += 1;
+    }
+
+    pub fn finish(mut self, duration: Duration) -> BenchmarkResult {
+        let messages_acknowledged = self.latencies.len() as u64;
+        let throughput_bytes_per_sec = if duration.is_zero() {
+            0
+        } else {
+            (self.bytes_acknowledged as f64 / duration.as_secs_f64()) as u64
+        };
+        let jitter = if self.latencies.len() > 1 {
+            self.total_jitter / (self.latencies.len() as u32 - 1)
+        } else {
+            Duration::ZERO
+        };
+
+        self.latencies.sort();
+        let percentile = |percent: usize| -> Duration {
+            if self.latencies.is_empty() {
+                Duration::ZERO
+            } else {
+                self.latencies[(self.latencies.len() - 1) * percent / 100]
+            }
+        };
+        let avg_latency = if self.latencies.is_empty() {
+            Duration::ZERO
+        } else {
+            self.latencies.iter().sum::<Duration>() / self.latencies.len() as u32
+        };
+
+        BenchmarkResult {
+            messages_acknowledged,
+            messages_failed: self.messages_failed,
+            bytes_acknowledged: self.bytes_acknowledged,
+            duration,
+            throughput_bytes_per_sec,
+            min_latency: percentile(0),
+            avg_latency,
+            p50_latency: percentile(50),
+            p99_latency: percentile(99),
+            max_latency: percentile(100),
+            jitter,
+        }
+    }
+}
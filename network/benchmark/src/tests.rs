@@ -0,0 +1,116 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    server::handle_request, stats::LatencyRecorder, BenchmarkParameters, Error,
+    NetworkBenchmarkError, NetworkBenchmarkRequest, NetworkBenchmarkResponse,
+};
+use aptos_config::config::NetworkBenchmarkConfig;
+use std::time::Duration;
+
+#[test]
+fn test_parameters_default_to_config() {
+    let config = NetworkBenchmarkConfig::default();
+    let parameters = BenchmarkParameters::new(&config, 0, 0, 0).unwrap();
+    assert_eq!(
+        parameters,
+        BenchmarkParameters {
+            duration: Duration::from_millis(config.max_duration_ms),
+            bytes_per_sec: config.max_bytes_per_sec,
+            message_size: config.default_message_size,
+        }
+    );
+
+    let parameters = BenchmarkParameters::new(&config, 1_000, 1_024, 256).unwrap();
+    assert_eq!(
+        parameters,
+        BenchmarkParameters {
+            duration: Duration::from_secs(1),
+            bytes_per_sec: 1_024,
+            message_size: 256,
+        }
+    );
+}
+
+#[test]
+fn test_parameters_over_limits_are_rejected() {
+    let config = NetworkBenchmarkConfig::default();
+    for (duration_ms, bytes_per_sec, message_size) in [
+        (config.max_duration_ms + 1, 0, 0),
+        (0, config.max_bytes_per_sec + 1, 0),
+        (0, 0, config.max_message_size + 1),
+    ] {
+        assert!(matches!(
+            BenchmarkParameters::new(&config, duration_ms, bytes_per_sec, message_size),
+            Err(Error::InvalidParameters(_))
+        ));
+    }
+}
+
+#[test]
+fn test_send_times_respect_rate() {
+    let config = NetworkBenchmarkConfig::default();
+    let parameters = BenchmarkParameters::new(&config, 0, 1_000, 100).unwrap();
+    assert_eq!(parameters.send_time(0), Duration::ZERO);
+    assert_eq!(parameters.send_time(1), Duration::from_millis(100));
+    assert_eq!(parameters.send_time(10), Duration::from_secs(1));
+}
+
+#[test]
+fn test_latency_recorder() {
+    let mut recorder = LatencyRecorder::default();
+    for latency_ms in [10, 30, 20, 40] {
+        recorder.record_success(Duration::from_millis(latency_ms), 500);
+    }
+    recorder.record_failure();
+
+    let result = recorder.finish(Duration::from_secs(2));
+    assert_eq!(result.messages_acknowledged, 4);
+    assert_eq!(result.messages_failed, 1);
+    assert_eq!(result.bytes_acknowledged, 2_000);
+    assert_eq!(result.throughput_bytes_per_sec, 1_000);
+    assert_eq!(result.min_latency, Duration::from_millis(10));
+    assert_eq!(result.avg_latency, Duration::from_millis(25));
+    assert_eq!(result.p50_latency, Duration::from_millis(20));
+    assert_eq!(result.max_latency, Duration::from_millis(40));
+    // The differences between consecutive latencies are 20, 10 and 20 ms
+    assert_eq!(result.jitter, Duration::from_millis(50) / 3);
+}
+
+#[test]
+fn test_latency_recorder_without_acknowledgements() {
+    let mut recorder = LatencyRecorder::default();
+    recorder.record_failure();
+
+    let result = recorder.finish(Duration::ZERO);
+    assert_eq!(result.messages_acknowledged, 0);
+    assert_eq!(result.messages_failed, 1);
+    assert_eq!(result.throughput_bytes_per_sec, 0);
+    assert_eq!(result.max_latency, Duration::ZERO);
+    assert_eq!(result.jitter, Duration::ZERO);
+}
+
+#[test]
+fn test_server_acknowledges_requests() {
+    let config = NetworkBenchmarkConfig::default();
+    let request = NetworkBenchmarkRequest {
+        sequence: 7,
+        payload: vec![0; config.max_message_size as usize],
+    };
+    assert_eq!(
+        handle_request(&config, &request),
+        Ok(NetworkBenchmarkResponse { sequence: 7 })
+    );
+
+    let request = NetworkBenchmarkRequest {
+        sequence: 8,
+        payload: vec![0; config.max_message_size as usize + 1],
+    };
+    assert_eq!(
+        handle_request(&config, &request),
+        Err(NetworkBenchmarkError::MessageTooLarge(
+            config.max_message_size + 1,
+            config.max_message_size
+        ))
+    );
+}
@@ -10,6 +10,7 @@ use network::{
     application::{
         interface::{MultiNetworkSender, NetworkInterface},
         storage::{LockingHashMap, PeerMetadataStorage},
+        types::PeerInfo,
     },
     error::NetworkError,
     peer_manager::{ConnectionRequestSender, PeerManagerRequestSender},
@@ -22,9 +23,18 @@ use peer_monitoring_service_types::{
     PeerMonitoringServiceError, PeerMonitoringServiceMessage, PeerMonitoringServiceRequest,
     PeerMonitoringServiceResponse,
 };
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use thiserror::Error;
 
+mod peer_monitor;
+mod topology;
+
+pub use peer_monitor::start_peer_monitor;
+pub use topology::{
+    build_network_topology_snapshot, LatencyEdge, NetworkTopologySnapshot, TopologyNode,
+    UnreachablePeer,
+};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Aptos network rpc error: {0}")]
@@ -77,6 +87,14 @@ impl PeerMonitoringServiceClient {
             }
         }
     }
+
+    /// Returns all connected peers, across networks
+    pub fn get_connected_peers(&self) -> HashMap<PeerNetworkId, PeerInfo> {
+        self.peer_metadata
+            .networks()
+            .flat_map(|network_id| self.connected_peers(network_id))
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,125 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::PeerMonitoringServiceClient;
+use aptos_config::{config::PeerMonitoringServiceConfig, network_id::PeerNetworkId};
+use aptos_logger::prelude::*;
+use futures::future::join_all;
+use peer_monitoring_service_types::{
+    DepthFromValidatorsResponse, PeerMonitoringMetadata, PeerMonitoringMetadataMap,
+    PeerMonitoringServiceRequest,
+};
+use std::{
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+
+/// The weight of a new latency sample in the average ping latency of a peer
+const LATENCY_SAMPLE_WEIGHT_PERCENT: u64 = 10;
+
+/// Periodically pings every connected peer and asks it for its depth from the
+/// validators. The results are written to `peer_monitoring_metadata`, where the
+/// server reads them to answer depth and network summary requests.
+pub async fn start_peer_monitor(
+    config: PeerMonitoringServiceConfig,
+    client: PeerMonitoringServiceClient,
+    peer_monitoring_metadata: PeerMonitoringMetadataMap,
+) {
+    let request_timeout = Duration::from_millis(config.peer_monitor_request_timeout_ms);
+    let mut interval =
+        tokio::time::interval(Duration::from_millis(config.peer_monitor_interval_ms));
+    loop {
+        interval.tick().await;
+
+        // Forget the peers that are no longer connected
+        let connected_peers = client.get_connected_peers();
+        peer_monitoring_metadata
+            .write()
+            .retain(|peer_network_id, _| connected_peers.contains_key(peer_network_id));
+
+        // Monitor all connected peers concurrently
+        let monitor_peers = connected_peers.into_keys().map(|peer_network_id| {
+            monitor_peer(
+                &client,
+                &peer_monitoring_metadata,
+                peer_network_id,
+                request_timeout,
+            )
+        });
+        join_all(monitor_peers).await;
+    }
+}
+
+/// Measures the ping latency to the given peer and fetches its depth from the
+/// validators. A failed ping leaves the average latency unchanged, while a
+/// failed depth request makes the depth of the peer unknown.
+async fn monitor_peer(
+    client: &PeerMonitoringServiceClient,
+    peer_monitoring_metadata: &PeerMonitoringMetadataMap,
+    peer_network_id: PeerNetworkId,
+    request_timeout: Duration,
+) {
+    // Ping the peer
+    let ping_start = Instant::now();
+    let ping_latency_usecs = match client
+        .send_request(
+            peer_network_id,
+            PeerMonitoringServiceRequest::Ping,
+            request_timeout,
+        )
+        .await
+    {
+        Ok(_) => Some(ping_start.elapsed().as_micros() as u64),
+        Err(error) => {
+            debug!("Failed to ping peer {}: {}", peer_network_id, error);
+            None
+        }
+    };
+
+    // Fetch the depth of the peer
+    let depth_from_validators = match client
+        .send_request(
+            peer_network_id,
+            PeerMonitoringServiceRequest::GetDepthFromValidators,
+            request_timeout,
+        )
+        .await
+        .map_err(|error| error.to_string())
+        .and_then(|response| {
+            DepthFromValidatorsResponse::try_from(response).map_err(|error| error.to_string())
+        }) {
+        Ok(response) => response.depth_from_validators,
+        Err(error) => {
+            debug!(
+                "Failed to fetch the depth from validators of peer {}: {}",
+                peer_network_id, error
+            );
+            None
+        }
+    };
+
+    // Update the metadata of the peer
+    let mut peer_monitoring_metadata = peer_monitoring_metadata.write();
+    let metadata = peer_monitoring_metadata
+        .entry(peer_network_id)
+        .or_insert_with(PeerMonitoringMetadata::default);
+    if let Some(ping_latency_usecs) = ping_latency_usecs {
+        metadata.average_ping_latency_usecs = Some(update_average_latency(
+            metadata.average_ping_latency_usecs,
+            ping_latency_usecs,
+        ));
+    }
+    metadata.depth_from_validators = depth_from_validators;
+}
+
+/// Returns the exponential moving average of the latency, including the new sample
+fn update_average_latency(average_latency_usecs: Option<u64>, sample_usecs: u64) -> u64 {
+    match average_latency_usecs {
+        Some(average_latency_usecs) => {
+            (average_latency_usecs * (100 - LATENCY_SAMPLE_WEIGHT_PERCENT)
+                + sample_usecs * LATENCY_SAMPLE_WEIGHT_PERCENT)
+                / 100
+        }
+        None => sample_usecs,
+    }
+}
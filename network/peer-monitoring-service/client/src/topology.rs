@@ -0,0 +1,132 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::PeerMonitoringServiceClient;
+use aptos_config::network_id::PeerNetworkId;
+use aptos_infallible::duration_since_epoch;
+use futures::future::join_all;
+use peer_monitoring_service_types::{PeerMonitoringServiceRequest, SignedNetworkSummary};
+use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, time::Duration};
+
+/// A snapshot of the network topology around a node, built from the signed
+/// network summaries of its connected peers. It is meant to be exported (e.g.,
+/// as JSON) for operators investigating propagation issues.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NetworkTopologySnapshot {
+    pub timestamp_usecs: u64,
+    pub nodes: Vec<TopologyNode>, // The peers that returned a valid summary
+    pub unreachable_peers: Vec<UnreachablePeer>, // The peers that didn't
+}
+
+/// A peer and the network summary it signed
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TopologyNode {
+    pub peer_network_id: PeerNetworkId,
+    pub signed_summary: SignedNetworkSummary,
+}
+
+/// A peer that failed to return a valid network summary, and why
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct UnreachablePeer {
+    pub peer_network_id: PeerNetworkId,
+    pub error: String,
+}
+
+/// The latency from a node to one of its peers, as measured by the node
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LatencyEdge {
+    pub from: PeerNetworkId,
+    pub to: PeerNetworkId,
+    pub average_ping_latency_usecs: u64,
+}
+
+impl NetworkTopologySnapshot {
+    /// Returns the latency edges reported by all nodes of the snapshot. Note
+    /// that a node is identified by the peer network id it was reached with,
+    /// which may differ from the one its own peers know it by.
+    pub fn latency_edges(&self) -> Vec<LatencyEdge> {
+        self.nodes
+            .iter()
+            .flat_map(|node| {
+                node.signed_summary
+                    .summary()
+                    .connected_peers
+                    .iter()
+                    .filter_map(move |peer| {
+                        peer.average_ping_latency_usecs
+                            .map(|average_ping_latency_usecs| LatencyEdge {
+                                from: node.peer_network_id,
+                                to: peer.peer_network_id,
+                                average_ping_latency_usecs,
+                            })
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Fetches the network summary of every connected peer and aggregates the
+/// summaries with valid signatures into a topology snapshot.
+///
+/// The requests go over authenticated connections, so a summary is known to
+/// come from the peer it was requested from. The signature lets the summary
+/// be verified again once exported, against the public key it carries.
+pub async fn build_network_topology_snapshot(
+    client: &PeerMonitoringServiceClient,
+    request_timeout: Duration,
+) -> NetworkTopologySnapshot {
+    let mut peer_network_ids: Vec<_> = client.get_connected_peers().into_keys().collect();
+    peer_network_ids.sort();
+
+    // Fetch the summaries concurrently
+    let summaries =
+        join_all(peer_network_ids.iter().map(|peer_network_id| {
+            fetch_network_summary(client, *peer_network_id, request_timeout)
+        }))
+        .await;
+
+    // Split the peers by whether they returned a valid summary
+    let mut nodes = vec![];
+    let mut unreachable_peers = vec![];
+    for (peer_network_id, summary) in peer_network_ids.into_iter().zip(summaries) {
+        match summary {
+            Ok(signed_summary) => nodes.push(TopologyNode {
+                peer_network_id,
+                signed_summary,
+            }),
+            Err(error) => unreachable_peers.push(UnreachablePeer {
+                peer_network_id,
+                error,
+            }),
+        }
+    }
+
+    NetworkTopologySnapshot {
+        timestamp_usecs: duration_since_epoch().as_micros() as u64,
+        nodes,
+        unreachable_peers,
+    }
+}
+
+/// Fetches the network summary of the given peer and verifies its signature
+async fn fetch_network_summary(
+    client: &PeerMonitoringServiceClient,
+    peer_network_id: PeerNetworkId,
+    request_timeout: Duration,
+) -> Result<SignedNetworkSummary, String> {
+    let response = client
+        .send_request(
+            peer_network_id,
+            PeerMonitoringServiceRequest::GetNetworkSummary,
+            request_timeout,
+        )
+        .await
+        .map_err(|error| error.to_string())?;
+    let signed_summary =
+        SignedNetworkSummary::try_from(response).map_err(|error| error.to_string())?;
+    signed_summary
+        .verify()
+        .map_err(|error| format!("Invalid network summary signature: {}", error))?;
+    Ok(signed_summary)
+}
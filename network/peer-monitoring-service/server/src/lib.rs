@@ -8,17 +8,27 @@ use crate::{
     metrics::{increment_counter, start_timer},
     network::PeerMonitoringServiceNetworkEvents,
 };
-use ::network::{application::storage::PeerMetadataStorage, ProtocolId};
-use aptos_config::config::PeerMonitoringServiceConfig;
+use ::network::{
+    application::{storage::PeerMetadataStorage, types::PeerInfo},
+    ProtocolId,
+};
+use aptos_config::{
+    config::{PeerMonitoringServiceConfig, PeerRole, RoleType},
+    network_id::PeerNetworkId,
+};
+use aptos_crypto::ed25519::Ed25519PrivateKey;
+use aptos_infallible::duration_since_epoch;
 use aptos_logger::prelude::*;
 use bounded_executor::BoundedExecutor;
 use futures::stream::StreamExt;
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse, Result, ServerProtocolVersionResponse,
+    ConnectedPeerSummary, ConnectedPeersResponse, DepthFromValidatorsResponse, NetworkSummary,
+    NodeMetadata, PeerMonitoringMetadataMap, PeerMonitoringServiceError,
+    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, PingResponse, Result,
+    ServerProtocolVersionResponse, SignedNetworkSummary,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Instant};
 use thiserror::Error;
 use tokio::runtime::Handle;
 
@@ -32,6 +42,11 @@ mod tests;
 /// Peer monitoring server constants
 pub const PEER_MONITORING_SERVER_VERSION: u64 = 1;
 
+/// Depths beyond this are reported as unknown. This stops nodes that derive
+/// their depth from each other from counting up forever once they lose their
+/// path to the validators.
+pub const MAX_DEPTH_FROM_VALIDATORS: u64 = 16;
+
 #[derive(Clone, Debug, Deserialize, Error, PartialEq, Serialize)]
 pub enum Error {
     #[error("Invalid request received: {0}")]
@@ -54,7 +69,7 @@ impl Error {
 pub struct PeerMonitoringServiceServer {
     bounded_executor: BoundedExecutor,
     network_requests: PeerMonitoringServiceNetworkEvents,
-    peer_metadata: Arc<PeerMetadataStorage>,
+    handler: Handler,
 }
 
 impl PeerMonitoringServiceServer {
@@ -63,14 +78,24 @@ impl PeerMonitoringServiceServer {
         executor: Handle,
         network_requests: PeerMonitoringServiceNetworkEvents,
         peer_metadata: Arc<PeerMetadataStorage>,
+        peer_monitoring_metadata: PeerMonitoringMetadataMap,
+        role: RoleType,
+        signing_key: Arc<Ed25519PrivateKey>,
     ) -> Self {
         let bounded_executor =
             BoundedExecutor::new(config.max_concurrent_requests as usize, executor);
+        let handler = Handler::new(
+            peer_metadata,
+            peer_monitoring_metadata,
+            role,
+            signing_key,
+            Instant::now(),
+        );
 
         Self {
             bounded_executor,
             network_requests,
-            peer_metadata,
+            handler,
         }
     }
 
@@ -89,10 +114,10 @@ impl PeerMonitoringServiceServer {
 
             // All handler methods are currently CPU-bound so we want
             // to spawn on the blocking thread pool.
-            let peer_metadata = self.peer_metadata.clone();
+            let handler = self.handler.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
-                    let response = Handler::new(peer_metadata).call(protocol, request);
+                    let response = handler.call(protocol, request);
                     log_monitoring_service_response(&response);
                     response_sender.send(response);
                 })
@@ -107,11 +132,27 @@ impl PeerMonitoringServiceServer {
 #[derive(Clone)]
 pub struct Handler {
     peer_metadata: Arc<PeerMetadataStorage>,
+    peer_monitoring_metadata: PeerMonitoringMetadataMap,
+    role: RoleType,
+    signing_key: Arc<Ed25519PrivateKey>,
+    start_time: Instant,
 }
 
 impl Handler {
-    pub fn new(peer_metadata: Arc<PeerMetadataStorage>) -> Self {
-        Self { peer_metadata }
+    pub fn new(
+        peer_metadata: Arc<PeerMetadataStorage>,
+        peer_monitoring_metadata: PeerMonitoringMetadataMap,
+        role: RoleType,
+        signing_key: Arc<Ed25519PrivateKey>,
+        start_time: Instant,
+    ) -> Self {
+        Self {
+            peer_metadata,
+            peer_monitoring_metadata,
+            role,
+            signing_key,
+            start_time,
+        }
     }
 
     pub fn call(
@@ -140,6 +181,7 @@ impl Handler {
                 self.get_depth_from_validators()
             }
             PeerMonitoringServiceRequest::GetKnownPeers => self.get_known_peers(),
+            PeerMonitoringServiceRequest::GetNetworkSummary => self.get_network_summary(),
             PeerMonitoringServiceRequest::GetServerProtocolVersion => {
                 self.get_server_protocol_version()
             }
@@ -181,30 +223,65 @@ impl Handler {
     }
 
     fn get_connected_peers(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        // Fetch all connected peers
-        let mut connected_peers = HashMap::new();
-        for network in self.peer_metadata.networks() {
-            for (peer_network_id, peer_info) in self.peer_metadata.read_all(network) {
-                if peer_info.is_connected() {
-                    connected_peers.insert(peer_network_id, peer_info);
-                }
-            }
-        }
-
-        // Return the connected peers
+        let connected_peers = self.connected_peers();
         Ok(PeerMonitoringServiceResponse::ConnectedPeers(
             ConnectedPeersResponse { connected_peers },
         ))
     }
 
     fn get_depth_from_validators(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        let depth_from_validators = self.depth_from_validators(&self.connected_peers());
+        Ok(PeerMonitoringServiceResponse::DepthFromValidators(
+            DepthFromValidatorsResponse {
+                depth_from_validators,
+            },
+        ))
     }
 
     fn get_known_peers(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         unimplemented!();
     }
 
+    fn get_network_summary(&self) -> Result<PeerMonitoringServiceResponse, Error> {
+        let connected_peers = self.connected_peers();
+        let depth_from_validators = self.depth_from_validators(&connected_peers);
+
+        // Summarize each connected peer with what the peer monitor measured
+        let peer_monitoring_metadata = self.peer_monitoring_metadata.read().clone();
+        let mut connected_peers: Vec<_> = connected_peers
+            .into_iter()
+            .map(|(peer_network_id, peer_info)| {
+                let metadata = peer_monitoring_metadata
+                    .get(&peer_network_id)
+                    .cloned()
+                    .unwrap_or_default();
+                ConnectedPeerSummary {
+                    peer_network_id,
+                    role: peer_info.active_connection.role,
+                    origin: peer_info.active_connection.origin,
+                    average_ping_latency_usecs: metadata.average_ping_latency_usecs,
+                    depth_from_validators: metadata.depth_from_validators,
+                }
+            })
+            .collect();
+        connected_peers.sort_by_key(|peer| peer.peer_network_id);
+
+        // Sign the summary
+        let summary = NetworkSummary {
+            timestamp_usecs: duration_since_epoch().as_micros() as u64,
+            depth_from_validators,
+            node_metadata: NodeMetadata {
+                role: self.role,
+                server_protocol_version: PEER_MONITORING_SERVER_VERSION,
+                uptime_secs: self.start_time.elapsed().as_secs(),
+            },
+            connected_peers,
+        };
+        Ok(PeerMonitoringServiceResponse::NetworkSummary(
+            SignedNetworkSummary::new(summary, &self.signing_key),
+        ))
+    }
+
     fn get_server_protocol_version(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         Ok(PeerMonitoringServiceResponse::ServerProtocolVersion(
             ServerProtocolVersionResponse {
@@ -218,7 +295,48 @@ impl Handler {
     }
 
     fn handle_ping(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Ok(PeerMonitoringServiceResponse::Ping(PingResponse))
+    }
+
+    /// Returns all connected peers, across networks
+    fn connected_peers(&self) -> HashMap<PeerNetworkId, PeerInfo> {
+        let mut connected_peers = HashMap::new();
+        for network in self.peer_metadata.networks() {
+            for (peer_network_id, peer_info) in self.peer_metadata.read_all(network) {
+                if peer_info.is_connected() {
+                    connected_peers.insert(peer_network_id, peer_info);
+                }
+            }
+        }
+        connected_peers
+    }
+
+    /// Returns the depth of the node from the validators: 0 for a validator,
+    /// 1 for a node connected to a validator, and otherwise one more than the
+    /// smallest depth reported by a connected peer.
+    fn depth_from_validators(
+        &self,
+        connected_peers: &HashMap<PeerNetworkId, PeerInfo>,
+    ) -> Option<u64> {
+        if self.role.is_validator() {
+            return Some(0);
+        }
+
+        let peer_monitoring_metadata = self.peer_monitoring_metadata.read();
+        connected_peers
+            .iter()
+            .filter_map(|(peer_network_id, peer_info)| {
+                if peer_info.active_connection.role == PeerRole::Validator {
+                    Some(1)
+                } else {
+                    peer_monitoring_metadata
+                        .get(peer_network_id)
+                        .and_then(|metadata| metadata.depth_from_validators)
+                        .map(|depth| depth + 1)
+                }
+            })
+            .min()
+            .filter(|depth| *depth <= MAX_DEPTH_FROM_VALIDATORS)
     }
 }
 
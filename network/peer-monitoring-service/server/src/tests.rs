@@ -4,12 +4,15 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer, PEER_MONITORING_SERVER_VERSION,
+    PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer, MAX_DEPTH_FROM_VALIDATORS,
+    PEER_MONITORING_SERVER_VERSION,
 };
 use aptos_config::{
-    config::{PeerMonitoringServiceConfig, PeerRole},
+    config::{PeerMonitoringServiceConfig, PeerRole, RoleType},
     network_id::{NetworkId, PeerNetworkId},
 };
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use aptos_infallible::RwLock;
 use aptos_logger::Level;
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use channel::aptos_channel;
@@ -29,8 +32,10 @@ use network::{
     transport::{ConnectionId, ConnectionMetadata},
 };
 use peer_monitoring_service_types::{
-    ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
-    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+    ConnectedPeerSummary, ConnectedPeersResponse, DepthFromValidatorsResponse,
+    PeerMonitoringMetadata, PeerMonitoringMetadataMap, PeerMonitoringServiceError,
+    PeerMonitoringServiceMessage, PeerMonitoringServiceRequest, PeerMonitoringServiceResponse,
+    PingResponse, ServerProtocolVersionResponse, SignedNetworkSummary,
 };
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -41,7 +46,7 @@ use std::{
 #[tokio::test]
 async fn test_get_server_protocol_version() {
     // Create the peer monitoring client and server
-    let (mut mock_client, service, _) = MockClient::new();
+    let (mut mock_client, service, _, _) = MockClient::new(RoleType::FullNode);
     tokio::spawn(service.start());
 
     // Process a request to fetch the protocol version
@@ -59,7 +64,7 @@ async fn test_get_server_protocol_version() {
 #[tokio::test]
 async fn test_get_connected_peers() {
     // Create the peer monitoring client and server
-    let (mut mock_client, service, peer_metadata_storage) = MockClient::new();
+    let (mut mock_client, service, peer_metadata_storage, _) = MockClient::new(RoleType::FullNode);
     tokio::spawn(service.start());
 
     // Process a request to fetch the connected peers
@@ -73,19 +78,7 @@ async fn test_get_connected_peers() {
     assert_eq!(response, expected_response);
 
     // Update the connected peers with a new peer
-    let peer_id = PeerId::random();
-    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
-    let connection_metadata = ConnectionMetadata::new(
-        peer_id,
-        ConnectionId::default(),
-        NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
-        ConnectionOrigin::Inbound,
-        MessagingProtocolVersion::V1,
-        ProtocolIdSet::empty(),
-        PeerRole::Unknown,
-    );
-    let peer_info = PeerInfo::new(connection_metadata);
-    peer_metadata_storage.insert(peer_network_id, peer_info);
+    let peer_network_id = connect_peer(&peer_metadata_storage, PeerRole::Unknown);
 
     // Process a request to fetch the connected peers
     let request = PeerMonitoringServiceRequest::GetConnectedPeers;
@@ -102,15 +95,7 @@ async fn test_get_connected_peers() {
     assert_eq!(response, expected_response);
 
     // Disconnect the peer
-    peer_metadata_storage
-        .write(peer_network_id, |entry| match entry {
-            Entry::Vacant(..) => Err(PeerError::NotFound),
-            Entry::Occupied(inner) => {
-                inner.get_mut().status = PeerState::Disconnected;
-                Ok(())
-            }
-        })
-        .unwrap();
+    disconnect_peer(&peer_metadata_storage, peer_network_id);
 
     // Process a request to fetch the connected peers
     let request = PeerMonitoringServiceRequest::GetConnectedPeers;
@@ -123,6 +108,183 @@ async fn test_get_connected_peers() {
     assert_eq!(response, expected_response);
 }
 
+#[tokio::test]
+async fn test_ping() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _, _) = MockClient::new(RoleType::FullNode);
+    tokio::spawn(service.start());
+
+    // Process a ping request and verify the response
+    let request = PeerMonitoringServiceRequest::Ping;
+    let response = mock_client.send_request(request).await.unwrap();
+    assert_eq!(response, PeerMonitoringServiceResponse::Ping(PingResponse));
+}
+
+#[tokio::test]
+async fn test_get_depth_from_validators() {
+    // A validator is always at depth 0
+    let (mut mock_client, service, _, _) = MockClient::new(RoleType::Validator);
+    tokio::spawn(service.start());
+    verify_depth_from_validators(&mut mock_client, Some(0)).await;
+
+    // A fullnode without peers has no known depth
+    let (mut mock_client, service, peer_metadata_storage, peer_monitoring_metadata) =
+        MockClient::new(RoleType::FullNode);
+    tokio::spawn(service.start());
+    verify_depth_from_validators(&mut mock_client, None).await;
+
+    // Connect a fullnode peer at depth 2 and verify the depth
+    let fullnode_peer = connect_peer(&peer_metadata_storage, PeerRole::Upstream);
+    verify_depth_from_validators(&mut mock_client, None).await;
+    update_peer_monitoring_metadata(&peer_monitoring_metadata, fullnode_peer, None, Some(2));
+    verify_depth_from_validators(&mut mock_client, Some(3)).await;
+
+    // Connect a validator and verify the depth
+    let validator_peer = connect_peer(&peer_metadata_storage, PeerRole::Validator);
+    verify_depth_from_validators(&mut mock_client, Some(1)).await;
+
+    // Disconnect the validator and verify the depth
+    disconnect_peer(&peer_metadata_storage, validator_peer);
+    verify_depth_from_validators(&mut mock_client, Some(3)).await;
+
+    // Depths that are too large are unknown
+    update_peer_monitoring_metadata(
+        &peer_monitoring_metadata,
+        fullnode_peer,
+        None,
+        Some(MAX_DEPTH_FROM_VALIDATORS),
+    );
+    verify_depth_from_validators(&mut mock_client, None).await;
+}
+
+#[tokio::test]
+async fn test_get_network_summary() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, peer_metadata_storage, peer_monitoring_metadata) =
+        MockClient::new(RoleType::FullNode);
+    tokio::spawn(service.start());
+
+    // Connect a validator and a fullnode, and record the latency to the validator
+    let validator_peer = connect_peer(&peer_metadata_storage, PeerRole::Validator);
+    let fullnode_peer = connect_peer(&peer_metadata_storage, PeerRole::Downstream);
+    update_peer_monitoring_metadata(
+        &peer_monitoring_metadata,
+        validator_peer,
+        Some(1_500),
+        Some(0),
+    );
+
+    // Process a request to fetch the network summary
+    let request = PeerMonitoringServiceRequest::GetNetworkSummary;
+    let response = mock_client.send_request(request).await.unwrap();
+    let signed_summary = match response {
+        PeerMonitoringServiceResponse::NetworkSummary(signed_summary) => signed_summary,
+        response => panic!("Unexpected response: {:?}", response),
+    };
+
+    // Verify the signature and the summary
+    signed_summary.verify().unwrap();
+    assert_eq!(
+        signed_summary.public_key(),
+        &Ed25519PrivateKey::generate_for_testing().public_key()
+    );
+    let summary = signed_summary.summary();
+    assert_eq!(summary.depth_from_validators, Some(1));
+    assert_eq!(summary.node_metadata.role, RoleType::FullNode);
+    assert_eq!(
+        summary.node_metadata.server_protocol_version,
+        PEER_MONITORING_SERVER_VERSION
+    );
+    let mut expected_peers = vec![
+        ConnectedPeerSummary {
+            peer_network_id: validator_peer,
+            role: PeerRole::Validator,
+            origin: ConnectionOrigin::Inbound,
+            average_ping_latency_usecs: Some(1_500),
+            depth_from_validators: Some(0),
+        },
+        ConnectedPeerSummary {
+            peer_network_id: fullnode_peer,
+            role: PeerRole::Downstream,
+            origin: ConnectionOrigin::Inbound,
+            average_ping_latency_usecs: None,
+            depth_from_validators: None,
+        },
+    ];
+    expected_peers.sort_by_key(|peer| peer.peer_network_id);
+    assert_eq!(summary.connected_peers, expected_peers);
+
+    // Verify a tampered summary is rejected
+    let mut tampered_summary = summary.clone();
+    tampered_summary.depth_from_validators = Some(0);
+    let tampered_bytes = bcs::to_bytes(&(
+        tampered_summary,
+        signed_summary.public_key(),
+        signed_summary.signature(),
+    ))
+    .unwrap();
+    let tampered_signed_summary: SignedNetworkSummary = bcs::from_bytes(&tampered_bytes).unwrap();
+    assert!(tampered_signed_summary.verify().is_err());
+}
+
+/// Sends a depth from validators request and verifies the response
+async fn verify_depth_from_validators(mock_client: &mut MockClient, expected_depth: Option<u64>) {
+    let request = PeerMonitoringServiceRequest::GetDepthFromValidators;
+    let response = mock_client.send_request(request).await.unwrap();
+    let expected_response =
+        PeerMonitoringServiceResponse::DepthFromValidators(DepthFromValidatorsResponse {
+            depth_from_validators: expected_depth,
+        });
+    assert_eq!(response, expected_response);
+}
+
+/// Connects a new peer with the given role and returns its peer network id
+fn connect_peer(peer_metadata_storage: &PeerMetadataStorage, role: PeerRole) -> PeerNetworkId {
+    let peer_id = PeerId::random();
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
+    let connection_metadata = ConnectionMetadata::new(
+        peer_id,
+        ConnectionId::default(),
+        NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
+        ConnectionOrigin::Inbound,
+        MessagingProtocolVersion::V1,
+        ProtocolIdSet::empty(),
+        role,
+    );
+    let peer_info = PeerInfo::new(connection_metadata);
+    peer_metadata_storage.insert(peer_network_id, peer_info);
+    peer_network_id
+}
+
+/// Marks the given peer as disconnected
+fn disconnect_peer(peer_metadata_storage: &PeerMetadataStorage, peer_network_id: PeerNetworkId) {
+    peer_metadata_storage
+        .write(peer_network_id, |entry| match entry {
+            Entry::Vacant(..) => Err(PeerError::NotFound),
+            Entry::Occupied(inner) => {
+                inner.get_mut().status = PeerState::Disconnected;
+                Ok(())
+            }
+        })
+        .unwrap();
+}
+
+/// Updates what the peer monitor measured about the given peer
+fn update_peer_monitoring_metadata(
+    peer_monitoring_metadata: &PeerMonitoringMetadataMap,
+    peer_network_id: PeerNetworkId,
+    average_ping_latency_usecs: Option<u64>,
+    depth_from_validators: Option<u64>,
+) {
+    peer_monitoring_metadata.write().insert(
+        peer_network_id,
+        PeerMonitoringMetadata {
+            average_ping_latency_usecs,
+            depth_from_validators,
+        },
+    );
+}
+
 /// A wrapper around the inbound network interface/channel for easily sending
 /// mock client requests to a [`PeerMonitoringServiceServer`].
 struct MockClient {
@@ -130,7 +292,14 @@ struct MockClient {
 }
 
 impl MockClient {
-    fn new() -> (Self, PeerMonitoringServiceServer, Arc<PeerMetadataStorage>) {
+    fn new(
+        role: RoleType,
+    ) -> (
+        Self,
+        PeerMonitoringServiceServer,
+        Arc<PeerMetadataStorage>,
+        PeerMonitoringMetadataMap,
+    ) {
         initialize_logger();
 
         // Create the peer monitoring service event stream
@@ -150,12 +319,16 @@ impl MockClient {
 
         // Create the peer monitoring server
         let peer_metadata_storage = PeerMetadataStorage::new(&[NetworkId::Validator]);
+        let peer_monitoring_metadata = Arc::new(RwLock::new(HashMap::new()));
         let executor = tokio::runtime::Handle::current();
         let peer_monitoring_server = PeerMonitoringServiceServer::new(
             peer_monitoring_service_config,
             executor,
             network_request_stream,
             peer_metadata_storage.clone(),
+            peer_monitoring_metadata.clone(),
+            role,
+            Arc::new(Ed25519PrivateKey::generate_for_testing()),
         );
 
         // Create the mock client
@@ -164,7 +337,12 @@ impl MockClient {
         };
 
         // Return the client and server
-        (mock_client, peer_monitoring_server, peer_metadata_storage)
+        (
+            mock_client,
+            peer_monitoring_server,
+            peer_metadata_storage,
+            peer_monitoring_metadata,
+        )
     }
 
     async fn send_request(
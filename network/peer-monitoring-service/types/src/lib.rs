@@ -3,10 +3,20 @@
 
 #![forbid(unsafe_code)]
 
-use aptos_config::network_id::PeerNetworkId;
+use aptos_config::{
+    config::{PeerRole, RoleType},
+    network_id::PeerNetworkId,
+};
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    PrivateKey, Signature, SigningKey,
+};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_infallible::RwLock;
+use netcore::transport::ConnectionOrigin;
 use network::application::types::PeerInfo;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom};
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
 use thiserror::Error;
 
 pub type Result<T, E = PeerMonitoringServiceError> = ::std::result::Result<T, E>;
@@ -36,6 +46,7 @@ pub enum PeerMonitoringServiceRequest {
     GetConnectedPeers,        // Returns all connected peers
     GetDepthFromValidators,   // Returns the depth of the node from the validators
     GetKnownPeers,            // Returns all of the known peers in the network
+    GetNetworkSummary,        // Returns a signed summary of the network as seen by the node
     GetServerProtocolVersion, // Fetches the protocol version run by the server
     GetValidatorsAndVFNs,     // Returns the current validators and VFNs
     Ping, // A simple message used by the client to ensure liveness and measure latency
@@ -48,6 +59,7 @@ impl PeerMonitoringServiceRequest {
             Self::GetConnectedPeers => "get_connected_peers",
             Self::GetDepthFromValidators => "get_depth_from_validators",
             Self::GetKnownPeers => "get_known_peers",
+            Self::GetNetworkSummary => "get_network_summary",
             Self::GetServerProtocolVersion => "get_server_protocol_version",
             Self::GetValidatorsAndVFNs => "get_validators_and_vfns",
             Self::Ping => "ping",
@@ -62,6 +74,7 @@ pub enum PeerMonitoringServiceResponse {
     ConnectedPeers(ConnectedPeersResponse), // Holds all currently connected peers
     DepthFromValidators(DepthFromValidatorsResponse), // Holds the min depth from the validators
     KnownPeers(KnownPeersResponse),         // Holds all currently known peers
+    NetworkSummary(SignedNetworkSummary),   // Holds the network summary signed by the node
     Ping(PingResponse), // A simple message to respond to liveness checks (i.e., pings)
     ServerProtocolVersion(ServerProtocolVersionResponse), // Returns the current server protocol version
     ValidatorsAndVFNs(ValidatorsAndVFNsResponse), // Holds the current validator set and VFNs
@@ -74,6 +87,7 @@ impl PeerMonitoringServiceResponse {
             Self::ConnectedPeers(_) => "connected_peers",
             Self::DepthFromValidators(_) => "depth_from_validators",
             Self::KnownPeers(_) => "known_peers",
+            Self::NetworkSummary(_) => "network_summary",
             Self::Ping(_) => "ping",
             Self::ServerProtocolVersion(_) => "server_protocol_version",
             Self::ValidatorsAndVFNs(_) => "validators_and_vfns",
@@ -90,7 +104,9 @@ pub struct ConnectedPeersResponse {
 /// A response for the depth from validators request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct DepthFromValidatorsResponse {
-    pub todo: bool,
+    /// The number of hops between the node and the closest validator
+    /// (None if the node has no known path to a validator).
+    pub depth_from_validators: Option<u64>,
 }
 
 /// A response for the known peers request
@@ -99,11 +115,9 @@ pub struct KnownPeersResponse {
     pub todo: bool,
 }
 
-/// A response for the ping request
+/// A response for the ping request. The latency is measured by the client.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-pub struct PingResponse {
-    pub todo: bool,
-}
+pub struct PingResponse;
 
 /// A response for the server protocol version request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -117,6 +131,87 @@ pub struct ValidatorsAndVFNsResponse {
     pub todo: bool,
 }
 
+/// A summary of the network as seen by a node: its own metadata, how far it is
+/// from the validators, and the latency to each of its connected peers.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, CryptoHasher, BCSCryptoHash)]
+pub struct NetworkSummary {
+    pub timestamp_usecs: u64, // The time at which the node built the summary
+    pub depth_from_validators: Option<u64>,
+    pub node_metadata: NodeMetadata,
+    pub connected_peers: Vec<ConnectedPeerSummary>, // Sorted by peer network id
+}
+
+/// Metadata about the node that built a network summary
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NodeMetadata {
+    pub role: RoleType,
+    pub server_protocol_version: u64,
+    pub uptime_secs: u64,
+}
+
+/// What a node knows about one of its connected peers
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ConnectedPeerSummary {
+    pub peer_network_id: PeerNetworkId,
+    pub role: PeerRole,
+    pub origin: ConnectionOrigin,
+    pub average_ping_latency_usecs: Option<u64>,
+    pub depth_from_validators: Option<u64>,
+}
+
+/// A network summary signed by the node that built it. The signature allows
+/// summaries to be relayed and aggregated without trusting the relayer, as
+/// long as the public key of the node is known.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SignedNetworkSummary {
+    summary: NetworkSummary,
+    public_key: Ed25519PublicKey,
+    signature: Ed25519Signature,
+}
+
+impl SignedNetworkSummary {
+    pub fn new(summary: NetworkSummary, signing_key: &Ed25519PrivateKey) -> Self {
+        let signature = signing_key.sign(&summary);
+        Self {
+            summary,
+            public_key: signing_key.public_key(),
+            signature,
+        }
+    }
+
+    pub fn summary(&self) -> &NetworkSummary {
+        &self.summary
+    }
+
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        &self.public_key
+    }
+
+    pub fn signature(&self) -> &Ed25519Signature {
+        &self.signature
+    }
+
+    /// Verifies that the summary was signed by the holder of the public key
+    pub fn verify(&self) -> anyhow::Result<()> {
+        self.signature.verify(&self.summary, &self.public_key)
+    }
+
+    pub fn into_summary(self) -> NetworkSummary {
+        self.summary
+    }
+}
+
+/// What the peer monitor of a node has measured about a connected peer
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerMonitoringMetadata {
+    pub average_ping_latency_usecs: Option<u64>,
+    pub depth_from_validators: Option<u64>,
+}
+
+/// The peer monitoring metadata of each peer, written by the peer monitor
+/// (client-side) and read by the server to build network summaries.
+pub type PeerMonitoringMetadataMap = Arc<RwLock<HashMap<PeerNetworkId, PeerMonitoringMetadata>>>;
+
 #[derive(Clone, Debug, Error)]
 #[error("Unexpected response variant: {0}")]
 pub struct UnexpectedResponseError(pub String);
@@ -160,6 +255,19 @@ impl TryFrom<PeerMonitoringServiceResponse> for KnownPeersResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for SignedNetworkSummary {
+    type Error = UnexpectedResponseError;
+    fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::NetworkSummary(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected network_summary_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for PingResponse {
     type Error = UnexpectedResponseError;
     fn try_from(response: PeerMonitoringServiceResponse) -> Result<Self, Self::Error> {
@@ -0,0 +1,97 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Faults injected into the messages exchanged with peers, to test how the node behaves on a
+//! degraded network. They are configured through failpoints, so they are only available when
+//! the node is built with the `failpoints` feature, and can be changed at runtime (e.g. through
+//! the admin service):
+//!
+//! * `network::partition`: `return(<peer>,<peer>,...)` drops every message sent to, or received
+//!   from, the given peers. Peers are given in hex, and may be shortened to a prefix.
+//! * `network::send::<protocol>` and `network::receive::<protocol>`, e.g.
+//!   `network::send::ConsensusRpcBcs`: `return` drops the messages of the protocol, and
+//!   `return(<ms>)` delays them. Any failpoint action can be used, e.g. `10%return(200)`.
+
+use crate::ProtocolId;
+use aptos_types::PeerId;
+use std::time::Duration;
+
+/// The failpoint partitioning the node from some peers
+pub const PARTITION_FAILPOINT: &str = "network::partition";
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Direction {
+    Send,
+    Receive,
+}
+
+impl Direction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Send => "send",
+            Direction::Receive => "receive",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum MessageFault {
+    Drop,
+    Delay(Duration),
+}
+
+/// The name of the failpoint injecting faults into the messages of `protocol_id`
+pub fn message_failpoint(direction: &str, protocol_id: ProtocolId) -> String {
+    format!("network::{}::{}", direction, protocol_id.as_str())
+}
+
+/// The fault to inject into a message of `protocol_id` exchanged with `peer_id`, if any
+pub(crate) fn message_fault(
+    direction: Direction,
+    peer_id: PeerId,
+    protocol_id: ProtocolId,
+) -> Option<MessageFault> {
+    // Failpoint names are only built when failpoints are compiled in, to keep the message path
+    // free of allocations otherwise
+    if !fail::has_failpoints() {
+        return None;
+    }
+    let partitioned = fail::eval(PARTITION_FAILPOINT, |peers| {
+        peers.map_or(false, |peers| is_partitioned(&peers, peer_id))
+    });
+    if partitioned == Some(true) {
+        return Some(MessageFault::Drop);
+    }
+    fail::eval(
+        &message_failpoint(direction.as_str(), protocol_id),
+        |delay_ms| match delay_ms.and_then(|delay_ms| delay_ms.trim().parse().ok()) {
+            Some(delay_ms) => MessageFault::Delay(Duration::from_millis(delay_ms)),
+            None => MessageFault::Drop,
+        },
+    )
+}
+
+/// Whether `peer_id` is one of the comma separated `peers`
+fn is_partitioned(peers: &str, peer_id: PeerId) -> bool {
+    let peer_id = peer_id.to_hex();
+    peers
+        .split(',')
+        .map(|peer| peer.trim().trim_start_matches("0x"))
+        .any(|peer| !peer.is_empty() && peer_id.starts_with(&peer.to_lowercase()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_partitioned() {
+        let peer_id = PeerId::from_hex_literal("0xabcd").unwrap();
+        let peer_hex = peer_id.to_hex();
+        assert!(is_partitioned(&peer_hex, peer_id));
+        assert!(is_partitioned(&format!("0x1, 0x{}", peer_hex), peer_id));
+        assert!(is_partitioned(&peer_hex[..8].to_uppercase(), peer_id));
+        assert!(!is_partitioned("0x1", peer_id));
+        assert!(!is_partitioned("", peer_id));
+    }
+}
@@ -15,6 +15,7 @@ pub mod connectivity_manager;
 pub mod constants;
 pub mod counters;
 pub mod error;
+pub mod fault_injection;
 pub mod logging;
 pub mod noise;
 pub mod peer;
@@ -19,6 +19,7 @@ use crate::{
         self, network_application_inbound_traffic, network_application_outbound_traffic,
         RECEIVED_LABEL, SENT_LABEL,
     },
+    fault_injection::{message_fault, Direction, MessageFault},
     logging::NetworkSchema,
     peer_manager::{PeerManagerError, TransportNotification},
     protocols::{
@@ -426,6 +427,17 @@ where
         &mut self,
         message: NetworkMessage,
     ) -> Result<(), PeerManagerError> {
+        let protocol_id = match &message {
+            NetworkMessage::DirectSendMsg(message) => Some(message.protocol_id),
+            NetworkMessage::RpcRequest(request) => Some(request.protocol_id),
+            _ => None,
+        };
+        if let Some(protocol_id) = protocol_id {
+            if !self.inject_fault(Direction::Receive, protocol_id).await {
+                return Ok(());
+            }
+        }
+
         match message {
             NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message),
             NetworkMessage::Error(error_msg) => {
@@ -569,6 +581,14 @@ where
             self.remote_peer_id().short_str(),
             request
         );
+        let protocol_id = match &request {
+            PeerRequest::SendDirectSend(message) => message.protocol_id,
+            PeerRequest::SendRpc(request) => request.protocol_id,
+        };
+        if !self.inject_fault(Direction::Send, protocol_id).await {
+            return;
+        }
+
         match request {
             // To send an outbound DirectSendMsg, we just bump some counters and
             // push it onto our outbound writer queue.
@@ -631,6 +651,29 @@ where
         }
     }
 
+    /// Applies the faults injected into the messages of `protocol_id` exchanged with the remote
+    /// peer, and returns whether the message should still be processed.
+    async fn inject_fault(&self, direction: Direction, protocol_id: ProtocolId) -> bool {
+        match message_fault(direction, self.remote_peer_id(), protocol_id) {
+            None => true,
+            Some(MessageFault::Drop) => {
+                trace!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&self.remote_peer_id()),
+                    protocol_id = protocol_id,
+                    "{} Dropping {:?} message for protocol {:?} (injected fault)",
+                    self.network_context,
+                    direction,
+                    protocol_id
+                );
+                false
+            }
+            Some(MessageFault::Delay(delay)) => {
+                self.time_service.sleep(delay).await;
+                true
+            }
+        }
+    }
+
     fn shutdown(&mut self, reason: DisconnectReason) {
         // Set the state of the actor to `State::ShuttingDown` to true ensures that the peer actor
         // will terminate and close the connection.
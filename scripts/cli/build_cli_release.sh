@@ -29,7 +29,7 @@ elif [ "$OS" == "Linux" ]; then
 fi
 
 echo "Building release $VERSION of $NAME for $OS-$ARCH"
-cargo build -p $CRATE_NAME --profile cli
+cargo build -p $CRATE_NAME --profile cli --features ledger
 
 cd target/cli/
 
@@ -0,0 +1,385 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation of typed Rust bindings for Move modules.
+//!
+//! Bindings are generated from module ABIs, as served by the
+//! `/accounts/:address/module/:module_name/abi` endpoint of a node, or read from a framework
+//! release bundle.  Each module gets a Rust module with a function building the
+//! `TransactionPayload` of each of its entry functions, and a struct for each of its Move structs
+//! that can be emitted as events, which the data of these events can be deserialized into with BCS.
+//!
+//! The bindings only depend on `aptos-sdk`.  They are best generated from a build script:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     let modules = aptos_sdk::codegen::release_bundle_modules("framework.mrb".as_ref()).unwrap();
+//!     aptos_sdk::codegen::generate_in_out_dir("framework.rs", &modules).unwrap();
+//!     println!("cargo:rerun-if-changed=framework.mrb");
+//! }
+//!
+//! // lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/framework.rs"));
+//! ```
+
+use anyhow::{bail, Context};
+use aptos_api_types::{MoveAbility, MoveModuleAbi, MoveStruct, MoveType};
+use framework::ReleaseBundle;
+use move_deps::{
+    move_binary_format::file_format::Ability,
+    move_core_types::{account_address::AccountAddress, language_storage::CORE_CODE_ADDRESS},
+};
+use std::{collections::BTreeSet, fmt::Write as _, io::Write, path::Path};
+
+/// Keywords which can only be used as identifiers in their raw form, e.g. `r#type`
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "try", "type", "unsafe", "use", "where",
+    "while", "yield",
+];
+
+/// The ABIs of the modules of every package in a release bundle
+pub fn release_bundle_modules(path: &Path) -> anyhow::Result<Vec<MoveModuleAbi>> {
+    let bundle = ReleaseBundle::read(path.to_path_buf())
+        .with_context(|| format!("Failed to read release bundle {}", path.display()))?;
+    Ok(bundle
+        .compiled_modules()
+        .into_iter()
+        .map(MoveModuleAbi::from)
+        .collect())
+}
+
+/// Writes the bindings of `modules` to `$OUT_DIR/<file_name>`, from a build script
+pub fn generate_in_out_dir(file_name: &str, modules: &[MoveModuleAbi]) -> anyhow::Result<()> {
+    let out_dir = std::env::var("OUT_DIR").context("OUT_DIR is only set for build scripts")?;
+    let mut file = std::fs::File::create(Path::new(&out_dir).join(file_name))?;
+    generate(&mut file, modules)
+}
+
+/// Writes the bindings of `modules`, one Rust module per Move module
+pub fn generate(out: &mut dyn Write, modules: &[MoveModuleAbi]) -> anyhow::Result<()> {
+    let mut names = BTreeSet::new();
+    for module in modules {
+        if !names.insert(module.name.as_str()) {
+            bail!(
+                "Module name {} is used by more than one address, generate them separately",
+                module.name
+            );
+        }
+    }
+
+    writeln!(
+        out,
+        "// This file was generated by `aptos_sdk::codegen`. Do not modify!\n"
+    )?;
+    for module in modules {
+        out.write_all(generate_module(module).as_bytes())?;
+    }
+    Ok(())
+}
+
+fn generate_module(module: &MoveModuleAbi) -> String {
+    let mut code = String::new();
+    let events = event_structs(module);
+
+    writeln!(
+        code,
+        "/// Bindings for `{}::{}`",
+        module.address, module.name
+    )
+    .unwrap();
+    writeln!(code, "#[allow(dead_code, unused_imports, clippy::all)]").unwrap();
+    writeln!(code, "pub mod {} {{", rust_ident(module.name.as_str())).unwrap();
+    writeln!(
+        code,
+        "    use aptos_sdk::{{
+        bcs,
+        move_types::{{
+            account_address::AccountAddress,
+            identifier::Identifier,
+            language_storage::{{ModuleId, TypeTag}},
+        }},
+        serde::{{Deserialize, Serialize}},
+        types::transaction::{{EntryFunction, TransactionPayload}},
+    }};
+
+    /// The id of the module
+    pub fn module_id() -> ModuleId {{
+        ModuleId::new(
+            AccountAddress::from_hex_literal(\"{}\").unwrap(),
+            Identifier::new(\"{}\").unwrap(),
+        )
+    }}",
+        module.address, module.name
+    )
+    .unwrap();
+
+    for function in &module.entry_functions {
+        // Signers are provided by the transaction, not as arguments
+        let params: Vec<_> = function
+            .params
+            .iter()
+            .skip_while(|param| is_signer(param))
+            .collect();
+        let types: Option<Vec<_>> = params
+            .iter()
+            .map(|param| rust_type(module, param, &BTreeSet::new()))
+            .collect();
+        let types = match types {
+            Some(types) => types,
+            None => {
+                writeln!(
+                    code,
+                    "\n    // `{}` is skipped, as some of its arguments have no Rust type",
+                    function.name
+                )
+                .unwrap();
+                continue;
+            }
+        };
+
+        let type_args: Vec<_> = (0..function.generic_type_params.len())
+            .map(|index| format!("type_arg_{}", index))
+            .collect();
+        let args: Vec<_> = (0..types.len())
+            .map(|index| format!("arg_{}", index))
+            .collect();
+        let signature = type_args
+            .iter()
+            .map(|type_arg| format!("{}: TypeTag", type_arg))
+            .chain(
+                args.iter()
+                    .zip(&types)
+                    .map(|(arg, ty)| format!("{}: {}", arg, ty)),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        let encoded_args = args
+            .iter()
+            .map(|arg| format!("bcs::to_bytes(&{}).unwrap()", arg))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(
+            code,
+            "
+    /// Calls the entry function `{address}::{module}::{function}`
+    pub fn {name}({signature}) -> TransactionPayload {{
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            module_id(),
+            Identifier::new(\"{function}\").unwrap(),
+            vec![{type_args}],
+            vec![{encoded_args}],
+        ))
+    }}",
+            address = module.address,
+            module = module.name,
+            function = function.name,
+            name = rust_ident(function.name.as_str()),
+            signature = signature,
+            type_args = type_args.join(", "),
+            encoded_args = encoded_args,
+        )
+        .unwrap();
+    }
+
+    let event_names: BTreeSet<_> = events.iter().map(|event| event.name.as_str()).collect();
+    for event in &events {
+        writeln!(
+            code,
+            "
+    /// The event `{}::{}::{}`
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(crate = \"aptos_sdk::serde\")]
+    pub struct {} {{",
+            module.address, module.name, event.name, event.name
+        )
+        .unwrap();
+        for field in &event.fields {
+            writeln!(
+                code,
+                "        pub {}: {},",
+                rust_ident(field.name.as_str()),
+                rust_type(module, &field.typ, &event_names).unwrap()
+            )
+            .unwrap();
+        }
+        writeln!(code, "    }}").unwrap();
+    }
+
+    writeln!(code, "}}\n").unwrap();
+    code
+}
+
+/// The structs of `module` which can be emitted as events: those with `drop` and `store`, but not
+/// `key`, and whose fields all have a Rust type.  Generic structs are left out, as their fields
+/// can't be typed without knowing the type arguments.
+fn event_structs(module: &MoveModuleAbi) -> Vec<&MoveStruct> {
+    let mut events: Vec<_> = module
+        .structs
+        .iter()
+        .filter(|s| {
+            let has = |ability| s.abilities.contains(&MoveAbility(ability));
+            !s.is_native
+                && s.generic_type_params.is_empty()
+                && has(Ability::Drop)
+                && has(Ability::Store)
+                && !has(Ability::Key)
+        })
+        .collect();
+    // Events can contain other structs of the module, which must have bindings themselves
+    loop {
+        let names: BTreeSet<_> = events.iter().map(|s| s.name.as_str()).collect();
+        let typed: Vec<_> = events
+            .iter()
+            .copied()
+            .filter(|s| {
+                s.fields
+                    .iter()
+                    .all(|field| rust_type(module, &field.typ, &names).is_some())
+            })
+            .collect();
+        if typed.len() == events.len() {
+            return events;
+        }
+        events = typed;
+    }
+}
+
+fn is_signer(ty: &MoveType) -> bool {
+    match ty {
+        MoveType::Signer => true,
+        MoveType::Reference { to, .. } => is_signer(to),
+        _ => false,
+    }
+}
+
+/// The Rust type with the same BCS encoding as a Move type, if there is one.  `structs` are the
+/// structs of `module` which have bindings.
+fn rust_type(module: &MoveModuleAbi, ty: &MoveType, structs: &BTreeSet<&str>) -> Option<String> {
+    Some(match ty {
+        MoveType::Bool => "bool".to_string(),
+        MoveType::U8 => "u8".to_string(),
+        MoveType::U64 => "u64".to_string(),
+        MoveType::U128 => "u128".to_string(),
+        MoveType::Address => "AccountAddress".to_string(),
+        MoveType::Vector { items } => format!("Vec<{}>", rust_type(module, items, structs)?),
+        MoveType::Struct(tag) => {
+            let address = AccountAddress::from(&tag.address);
+            match (
+                tag.module.as_str(),
+                tag.name.as_str(),
+                tag.generic_type_params.as_slice(),
+            ) {
+                ("string", "String", []) if address == CORE_CODE_ADDRESS => "String".to_string(),
+                // An option is encoded as a vector of at most one element, which for BCS is the
+                // same as a Rust option
+                ("option", "Option", [item]) if address == CORE_CODE_ADDRESS => {
+                    format!("Option<{}>", rust_type(module, item, structs)?)
+                }
+                (module_name, name, [])
+                    if address == AccountAddress::from(&module.address)
+                        && module_name == module.name.as_str()
+                        && structs.contains(name) =>
+                {
+                    name.to_string()
+                }
+                _ => return None,
+            }
+        }
+        MoveType::Signer
+        | MoveType::GenericTypeParam { .. }
+        | MoveType::Reference { .. }
+        | MoveType::Unparsable(_) => return None,
+    })
+}
+
+fn rust_ident(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_api_types::{
+        MoveFunction, MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveStructField,
+        MoveStructTag,
+    };
+    use move_deps::move_core_types::identifier::Identifier;
+
+    fn ident(name: &str) -> aptos_api_types::IdentifierWrapper {
+        Identifier::new(name).unwrap().into()
+    }
+
+    #[test]
+    fn test_generate() {
+        let module = MoveModuleAbi {
+            address: AccountAddress::from_hex_literal("0xcafe").unwrap().into(),
+            name: ident("message"),
+            entry_functions: vec![MoveFunction {
+                name: ident("set_message"),
+                visibility: MoveFunctionVisibility::Public,
+                is_entry: true,
+                generic_type_params: vec![MoveFunctionGenericTypeParam {
+                    constraints: vec![],
+                }],
+                params: vec![
+                    MoveType::Reference {
+                        mutable: false,
+                        to: Box::new(MoveType::Signer),
+                    },
+                    MoveType::Struct(MoveStructTag::new(
+                        CORE_CODE_ADDRESS.into(),
+                        ident("string"),
+                        ident("String"),
+                        vec![],
+                    )),
+                    MoveType::Vector {
+                        items: Box::new(MoveType::U64),
+                    },
+                ],
+                return_: vec![],
+            }],
+            view_functions: vec![],
+            structs: vec![
+                MoveStruct {
+                    name: ident("MessageChangeEvent"),
+                    is_native: false,
+                    abilities: vec![MoveAbility(Ability::Drop), MoveAbility(Ability::Store)],
+                    generic_type_params: vec![],
+                    fields: vec![MoveStructField {
+                        name: ident("type"),
+                        typ: MoveType::U8,
+                    }],
+                },
+                MoveStruct {
+                    name: ident("MessageHolder"),
+                    is_native: false,
+                    abilities: vec![MoveAbility(Ability::Key)],
+                    generic_type_params: vec![],
+                    fields: vec![],
+                },
+            ],
+        };
+
+        let mut out = vec![];
+        generate(&mut out, &[module]).unwrap();
+        let code = String::from_utf8(out).unwrap();
+        assert!(code.contains("pub mod message {"));
+        assert!(code.contains(
+            "pub fn set_message(type_arg_0: TypeTag, arg_0: String, arg_1: Vec<u64>) -> TransactionPayload {"
+        ));
+        assert!(
+            code.contains("vec![bcs::to_bytes(&arg_0).unwrap(), bcs::to_bytes(&arg_1).unwrap()],")
+        );
+        assert!(code.contains("pub struct MessageChangeEvent {"));
+        assert!(code.contains("pub r#type: u8,"));
+        assert!(!code.contains("MessageHolder"));
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reliable delivery of the events of an event handle, e.g. to detect deposits.
+//!
+//! An [`EventStream`] delivers each event of a handle, decoded, to a handler in order of sequence
+//! number.  The sequence number of the next event to deliver is kept in a [`CursorStore`], and
+//! only advanced once the handler succeeds, so every event is delivered at least once, even if
+//! the process stops in between: the events after the stored cursor are delivered again.
+//!
+//! Events are read from any of several nodes, failing over to the next when one can't be
+//! reached, or is missing events, e.g. because it pruned them.
+
+use crate::{
+    rest_client::{aptos_api_types::VersionedEvent, Client},
+    types::account_address::AccountAddress,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use std::{
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+/// Where an [`EventStream`] keeps the sequence number of the next event to deliver
+pub trait CursorStore: Send + Sync {
+    /// The stored cursor, or `None` if none was stored yet
+    fn load(&self) -> Result<Option<u64>>;
+
+    fn store(&self, cursor: u64) -> Result<()>;
+}
+
+/// Keeps the cursor in memory, so it's lost when the process stops
+#[derive(Debug, Default)]
+pub struct MemoryCursorStore(Mutex<Option<u64>>);
+
+impl CursorStore for MemoryCursorStore {
+    fn load(&self) -> Result<Option<u64>> {
+        Ok(*self.0.lock().unwrap())
+    }
+
+    fn store(&self, cursor: u64) -> Result<()> {
+        *self.0.lock().unwrap() = Some(cursor);
+        Ok(())
+    }
+}
+
+/// Keeps the cursor in a file
+#[derive(Debug)]
+pub struct FileCursorStore {
+    path: PathBuf,
+}
+
+impl FileCursorStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl CursorStore for FileCursorStore {
+    fn load(&self) -> Result<Option<u64>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read cursor from {}", self.path.display()))?;
+        Ok(Some(contents.trim().parse().with_context(|| {
+            format!("Invalid cursor in {}", self.path.display())
+        })?))
+    }
+
+    fn store(&self, cursor: u64) -> Result<()> {
+        // Written to the side, then renamed over the cursor, so it's never left half written
+        let temp_path = self.path.with_extension("tmp");
+        fs::write(&temp_path, cursor.to_string())
+            .with_context(|| format!("Failed to write cursor to {}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to write cursor to {}", self.path.display()))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EventStreamConfig {
+    /// Number of events requested at once
+    pub batch_size: u16,
+    /// How long to wait for new events, once all of the events so far were delivered
+    pub poll_interval: Duration,
+    /// Number of times in a row every node may fail before giving up
+    pub max_failed_rounds: usize,
+}
+
+impl Default for EventStreamConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            poll_interval: Duration::from_secs(1),
+            max_failed_rounds: 10,
+        }
+    }
+}
+
+/// An event, with its data decoded
+#[derive(Clone, Debug)]
+pub struct StreamEvent<T> {
+    pub version: u64,
+    pub sequence_number: u64,
+    pub data: T,
+}
+
+/// The events of the event handle `field_name` of the resource `struct_tag` of `address`
+pub struct EventStream<S> {
+    clients: Vec<Client>,
+    /// The client events are read from, until it fails
+    current: usize,
+    address: AccountAddress,
+    struct_tag: String,
+    field_name: String,
+    store: S,
+    config: EventStreamConfig,
+}
+
+impl<S: CursorStore> EventStream<S> {
+    pub fn new(
+        clients: Vec<Client>,
+        address: AccountAddress,
+        struct_tag: impl Into<String>,
+        field_name: impl Into<String>,
+        store: S,
+        config: EventStreamConfig,
+    ) -> Self {
+        assert!(!clients.is_empty(), "At least one client is required");
+        Self {
+            clients,
+            current: 0,
+            address,
+            struct_tag: struct_tag.into(),
+            field_name: field_name.into(),
+            store,
+            config,
+        }
+    }
+
+    /// The sequence number of the next event to deliver
+    pub fn cursor(&self) -> Result<u64> {
+        Ok(self.store.load()?.unwrap_or(0))
+    }
+
+    /// Delivers events to `handler` as they're emitted, until it fails, or no node can be read
+    /// from.  A failed event is delivered again by the next call.
+    pub async fn run<T, F, Fut>(&mut self, mut handler: F) -> Result<()>
+    where
+        T: DeserializeOwned,
+        F: FnMut(StreamEvent<T>) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        loop {
+            let events = self.next_batch::<T>().await?;
+            if events.is_empty() {
+                tokio::time::sleep(self.config.poll_interval).await;
+                continue;
+            }
+            for event in events {
+                let sequence_number = event.sequence_number;
+                handler(event).await?;
+                self.store.store(sequence_number + 1)?;
+            }
+        }
+    }
+
+    /// The events from the cursor on, up to the batch size, without advancing the cursor
+    pub async fn next_batch<T: DeserializeOwned>(&mut self) -> Result<Vec<StreamEvent<T>>> {
+        let start = self.cursor()?;
+        self.fetch(start)
+            .await?
+            .into_iter()
+            .map(|event| {
+                let sequence_number = event.sequence_number.0;
+                Ok(StreamEvent {
+                    version: event.version.0,
+                    sequence_number,
+                    data: serde_json::from_value(event.data)
+                        .with_context(|| format!("Failed to decode event {}", sequence_number))?,
+                })
+            })
+            .collect()
+    }
+
+    /// Reads the events from `start` on, from the current client, or the next which succeeds
+    async fn fetch(&mut self, start: u64) -> Result<Vec<VersionedEvent>> {
+        let mut failed_rounds = 0;
+        loop {
+            let mut last_error = None;
+            for _ in 0..self.clients.len() {
+                let result = self.clients[self.current]
+                    .get_account_events(
+                        self.address,
+                        &self.struct_tag,
+                        &self.field_name,
+                        Some(start),
+                        Some(self.config.batch_size),
+                    )
+                    .await;
+                match result {
+                    Ok(response) => {
+                        let events = response.into_inner();
+                        match find_gap(start, &events) {
+                            None => return Ok(events),
+                            Some((from, to)) => {
+                                last_error = Some(anyhow!(
+                                    "Events {} to {} are missing from node {}",
+                                    from,
+                                    to,
+                                    self.current
+                                ))
+                            }
+                        }
+                    }
+                    Err(err) => last_error = Some(err.into()),
+                }
+                self.current = (self.current + 1) % self.clients.len();
+            }
+
+            failed_rounds += 1;
+            if failed_rounds >= self.config.max_failed_rounds {
+                bail!(
+                    "Failed to read events from every node: {:#}",
+                    last_error.expect("At least one client is required")
+                );
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+}
+
+/// The first and last missing sequence numbers, if `events` aren't consecutive from `start`
+fn find_gap(start: u64, events: &[VersionedEvent]) -> Option<(u64, u64)> {
+    let mut expected = start;
+    for event in events {
+        let sequence_number = event.sequence_number.0;
+        if sequence_number != expected {
+            return Some((expected, sequence_number.saturating_sub(1)));
+        }
+        expected += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::event::EventKey;
+
+    fn event(sequence_number: u64) -> VersionedEvent {
+        VersionedEvent {
+            version: (sequence_number * 10).into(),
+            key: EventKey::new(0, AccountAddress::ONE).into(),
+            sequence_number: sequence_number.into(),
+            typ: "u64".parse().unwrap(),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_find_gap() {
+        assert_eq!(find_gap(5, &[]), None);
+        assert_eq!(find_gap(5, &[event(5), event(6), event(7)]), None);
+        assert_eq!(find_gap(5, &[event(8), event(9)]), Some((5, 7)));
+        assert_eq!(find_gap(5, &[event(5), event(7)]), Some((6, 6)));
+    }
+
+    #[test]
+    fn test_file_cursor_store() {
+        let path = std::env::temp_dir().join(format!("event-cursor-{}", std::process::id()));
+        let store = FileCursorStore::new(&path);
+        assert_eq!(store.load().unwrap(), None);
+        store.store(42).unwrap();
+        assert_eq!(store.load().unwrap(), Some(42));
+        store.store(43).unwrap();
+        assert_eq!(FileCursorStore::new(&path).load().unwrap(), Some(43));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -7,8 +7,13 @@
 //!
 //! This SDK provides all the necessary components for building on top of the Aptos Blockchain. Some of the important modules are:
 //!
+//! * `codegen` - Generates typed Rust bindings for Move modules, with the `codegen` feature
 //! * `crypto` - Types used for signing and verifying
+//! * `event_stream` - Delivers the events of an event handle at least once, across restarts and node failures
+//! * `rest_client` - A client for the REST API of Aptos nodes
+//! * `simulation` - Runs transactions locally against the state of a network, with the `simulation` feature
 //! * `transaction_builder` - Includes helpers for constructing transactions
+//! * `transaction_manager` - Submits transactions from an account concurrently, and resubmits them when they expire
 //! * `types` - Includes types for Aptos on-chain data structures
 //!
 //! ## Example
@@ -17,14 +22,30 @@
 //! todo(davidiw) bring back example using rest
 //!
 
+#[cfg(feature = "codegen")]
+pub mod codegen;
+
 pub mod crypto {
     pub use aptos_crypto::*;
 }
 
+pub mod event_stream;
+
+pub use aptos_rest_client as rest_client;
+
+#[cfg(feature = "simulation")]
+pub mod simulation;
+
 pub mod transaction_builder;
 
+pub mod transaction_manager;
+
 pub mod types;
 
 pub mod move_types {
     pub use move_deps::move_core_types::*;
 }
+
+// Re-exported for the bindings generated by `codegen`, so that they only depend on this crate
+pub use bcs;
+pub use serde;
@@ -0,0 +1,174 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local simulation of transactions against the state of a network, without a node.
+//!
+//! A [`ForkedState`] is a view of the state at a fixed version, which fetches each state value
+//! from a node's REST API the first time the VM reads it, and keeps it in memory from then on.
+//! Transactions run on it with the same VM as on the node, and the writes of those executed with
+//! [`ForkedState::execute`] are kept, so later transactions see them, e.g. to evaluate what
+//! happens after a sequence of transactions.
+
+use crate::{
+    crypto::ed25519::{Ed25519PublicKey, Ed25519Signature, ED25519_SIGNATURE_LENGTH},
+    rest_client::Client,
+    types::transaction::{RawTransaction, SignedTransaction, TransactionOutput},
+};
+use anyhow::{anyhow, Result};
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView, StateViewId};
+use aptos_state_view_layers::{
+    CachingStateView, OverlayStateView, RecordingStateView, RestStateView,
+};
+use aptos_types::{
+    state_store::state_key::StateKey,
+    transaction::{TransactionStatus, Version},
+    vm_status::VMStatus,
+    write_set::{WriteOp, WriteSet},
+};
+use aptos_vm::AptosVM;
+use serde::Serialize;
+use std::convert::TryFrom;
+use tokio::runtime::Handle;
+
+/// The state at `version`, fetched from a node as it's read, with local changes on top.
+///
+/// The REST client is async, so requests are run on the given runtime. Transactions must be run
+/// from outside of the runtime's worker threads, e.g. from `tokio::task::spawn_blocking`.
+pub struct ForkedState {
+    state: OverlayStateView<CachingStateView<RestStateView>>,
+}
+
+/// Where the gas of a simulated transaction went
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GasProfile {
+    pub gas_used: u64,
+    pub gas_unit_price: u64,
+    /// The fee paid, in Octas
+    pub fee: u64,
+    pub state_reads: usize,
+    pub bytes_read: usize,
+    pub state_writes: usize,
+    pub bytes_written: usize,
+    pub state_deletions: usize,
+    pub events: usize,
+}
+
+#[derive(Debug)]
+pub struct Simulation {
+    pub status: VMStatus,
+    pub output: TransactionOutput,
+    pub gas_profile: GasProfile,
+}
+
+impl Simulation {
+    /// Whether the transaction would be committed, and its execution succeed
+    pub fn success(&self) -> bool {
+        matches!(self.output.status(), TransactionStatus::Keep(status) if status.is_success())
+    }
+}
+
+impl ForkedState {
+    pub fn new(client: Client, runtime: Handle, version: Version) -> Self {
+        Self::from_view(RestStateView::new(client, runtime, version))
+    }
+
+    /// Forks the state at the latest version of the node
+    pub async fn latest(client: Client) -> Result<Self> {
+        Ok(Self::from_view(RestStateView::latest(client).await?))
+    }
+
+    fn from_view(view: RestStateView) -> Self {
+        Self {
+            state: OverlayStateView::new(CachingStateView::new(view)),
+        }
+    }
+
+    pub fn version(&self) -> Version {
+        self.state.base().base().version()
+    }
+
+    /// Overrides the value stored under `state_key`, or removes it with `None`
+    pub fn set_state_value(&self, state_key: StateKey, value: Option<Vec<u8>>) {
+        self.state.set_state_value(state_key, value);
+    }
+
+    pub fn apply_write_set(&self, write_set: &WriteSet) {
+        self.state.apply_write_set(write_set);
+    }
+
+    /// Runs `txn` without keeping its writes.  As on a node, `txn` mustn't have a valid signature,
+    /// see [`simulation_transaction`].
+    pub fn simulate(&self, txn: &SignedTransaction) -> Result<Simulation> {
+        let view = RecordingStateView::new(&self.state);
+        let (status, output) = AptosVM::simulate_signed_transaction(txn, &view);
+        let output = output
+            .into_transaction_output(&view)
+            .map_err(|status| anyhow!("Failed to apply deltas: {:?}", status))?;
+
+        let reads = view.into_reads();
+        let mut gas_profile = GasProfile {
+            gas_used: output.gas_used(),
+            gas_unit_price: txn.gas_unit_price(),
+            fee: output.gas_used() * txn.gas_unit_price(),
+            state_reads: reads.len(),
+            bytes_read: reads
+                .values()
+                .map(|value| value.as_ref().map_or(0, |bytes| bytes.len()))
+                .sum(),
+            events: output.events().len(),
+            ..Default::default()
+        };
+        for (_, write_op) in output.write_set() {
+            match write_op {
+                WriteOp::Creation(bytes) | WriteOp::Modification(bytes) => {
+                    gas_profile.state_writes += 1;
+                    gas_profile.bytes_written += bytes.len();
+                }
+                WriteOp::Deletion => gas_profile.state_deletions += 1,
+            }
+        }
+
+        Ok(Simulation {
+            status,
+            output,
+            gas_profile,
+        })
+    }
+
+    /// Runs `txn`, and keeps its writes if it would be committed
+    pub fn execute(&self, txn: &SignedTransaction) -> Result<Simulation> {
+        let simulation = self.simulate(txn)?;
+        if let TransactionStatus::Keep(_) = simulation.output.status() {
+            self.apply_write_set(simulation.output.write_set());
+        }
+        Ok(simulation)
+    }
+}
+
+impl StateView for ForkedState {
+    fn id(&self) -> StateViewId {
+        self.state.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        self.state.get_state_value(state_key)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.state.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.state.get_usage()
+    }
+}
+
+/// Signs `raw_txn` with an all-zero signature, as simulated transactions mustn't have a valid one
+pub fn simulation_transaction(
+    raw_txn: RawTransaction,
+    public_key: Ed25519PublicKey,
+) -> SignedTransaction {
+    let signature = Ed25519Signature::try_from(&[0u8; ED25519_SIGNATURE_LENGTH][..])
+        .expect("All-zero signature must be well formed");
+    SignedTransaction::new(raw_txn, public_key, signature)
+}
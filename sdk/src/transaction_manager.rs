@@ -0,0 +1,222 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Submission of transactions from a single account by many concurrent tasks.
+//!
+//! The [`TransactionManager`] hands out sequence numbers to the transactions submitted through it,
+//! waits for each to be committed, and resubmits those which expire, with the same sequence
+//! number and a higher gas unit price, until they're committed or it runs out of attempts.
+//!
+//! A transaction which is never committed leaves a gap in the account's sequence numbers, which
+//! holds back every transaction after it.  Its sequence number is handed out again, to the next
+//! transaction submitted, to fill the gap.
+
+use crate::{
+    rest_client::{Client, Transaction},
+    transaction_builder::TransactionFactory,
+    types::{
+        transaction::{SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use anyhow::Result;
+use std::{collections::BTreeSet, sync::Mutex, time::Duration};
+
+#[derive(Clone, Debug)]
+pub struct TransactionManagerConfig {
+    /// Number of times a transaction is submitted before giving up on it
+    pub max_attempts: usize,
+    /// How much the gas unit price is raised by on each resubmission, in percent
+    pub gas_unit_price_bump_percent: u64,
+    /// The highest gas unit price a transaction is resubmitted with
+    pub max_gas_unit_price: u64,
+    /// How often to check whether a submitted transaction was committed
+    pub poll_interval: Duration,
+}
+
+impl Default for TransactionManagerConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            gas_unit_price_bump_percent: 20,
+            max_gas_unit_price: 10_000,
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+}
+
+/// What became of a transaction submitted through a [`TransactionManager`]
+#[derive(Debug)]
+pub enum TransactionOutcome {
+    /// The transaction was committed, whether its execution succeeded or not
+    Committed(Transaction),
+    /// Every submission of the transaction expired before being committed
+    Expired { attempts: usize },
+    /// The node refused the transaction, e.g. because it failed validation
+    Rejected { reason: String },
+}
+
+impl TransactionOutcome {
+    /// Whether the transaction was committed, and its execution succeeded
+    pub fn success(&self) -> bool {
+        matches!(self, TransactionOutcome::Committed(txn) if txn.success())
+    }
+}
+
+struct SequenceNumbers {
+    next: u64,
+    /// Sequence numbers handed out to transactions which were never committed, to reuse first
+    released: BTreeSet<u64>,
+}
+
+pub struct TransactionManager {
+    client: Client,
+    factory: TransactionFactory,
+    account: LocalAccount,
+    sequence_numbers: Mutex<SequenceNumbers>,
+    config: TransactionManagerConfig,
+}
+
+impl TransactionManager {
+    /// Creates a manager for `account`, starting from its sequence number onchain
+    pub async fn new(
+        client: Client,
+        factory: TransactionFactory,
+        mut account: LocalAccount,
+        config: TransactionManagerConfig,
+    ) -> Result<Self> {
+        let sequence_number = client
+            .get_account(account.address())
+            .await?
+            .into_inner()
+            .sequence_number;
+        *account.sequence_number_mut() = sequence_number;
+        Ok(Self {
+            client,
+            factory,
+            account,
+            sequence_numbers: Mutex::new(SequenceNumbers {
+                next: sequence_number,
+                released: BTreeSet::new(),
+            }),
+            config,
+        })
+    }
+
+    pub fn account(&self) -> &LocalAccount {
+        &self.account
+    }
+
+    /// Submits a transaction with `payload`, and waits until it's committed, or given up on.
+    /// Errors are only returned if the node couldn't be reached.
+    pub async fn submit(&self, payload: TransactionPayload) -> Result<TransactionOutcome> {
+        let sequence_number = self.allocate_sequence_number();
+        let outcome = self.submit_with_retries(payload, sequence_number).await;
+        if !matches!(outcome, Ok(TransactionOutcome::Committed(_))) {
+            self.release_sequence_number(sequence_number);
+        }
+        outcome
+    }
+
+    async fn submit_with_retries(
+        &self,
+        payload: TransactionPayload,
+        sequence_number: u64,
+    ) -> Result<TransactionOutcome> {
+        let mut gas_unit_price = self.factory.get_gas_unit_price();
+        for _ in 0..self.config.max_attempts {
+            // The expiration is set anew on each attempt
+            let raw_txn = self
+                .factory
+                .payload(payload.clone())
+                .sender(self.account.address())
+                .sequence_number(sequence_number)
+                .gas_unit_price(gas_unit_price)
+                .build();
+            let txn = self.account.sign_transaction(raw_txn);
+            if let Err(err) = self.client.submit(&txn).await {
+                return Ok(TransactionOutcome::Rejected {
+                    reason: err.to_string(),
+                });
+            }
+            if let Some(committed) = self.wait_for_commit(&txn).await? {
+                return Ok(TransactionOutcome::Committed(committed));
+            }
+
+            let bump = gas_unit_price * self.config.gas_unit_price_bump_percent / 100;
+            gas_unit_price = (gas_unit_price + bump.max(1)).min(self.config.max_gas_unit_price);
+        }
+        Ok(TransactionOutcome::Expired {
+            attempts: self.config.max_attempts,
+        })
+    }
+
+    /// The committed transaction, or `None` once the ledger is past its expiration
+    async fn wait_for_commit(&self, txn: &SignedTransaction) -> Result<Option<Transaction>> {
+        let hash = txn.clone().committed_hash();
+        loop {
+            // Transactions which aren't in mempool nor committed aren't found
+            if let Ok(response) = self.client.get_transaction_by_hash(hash).await {
+                let committed = response.into_inner();
+                if !committed.is_pending() {
+                    return Ok(Some(committed));
+                }
+            }
+            let ledger = self.client.get_ledger_information().await?.into_inner();
+            if ledger.timestamp_usecs / 1_000_000 > txn.expiration_timestamp_secs() {
+                return Ok(None);
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    fn allocate_sequence_number(&self) -> u64 {
+        let mut sequence_numbers = self.sequence_numbers.lock().unwrap();
+        if let Some(released) = sequence_numbers.released.iter().next().copied() {
+            sequence_numbers.released.remove(&released);
+            return released;
+        }
+        let sequence_number = sequence_numbers.next;
+        sequence_numbers.next += 1;
+        sequence_number
+    }
+
+    fn release_sequence_number(&self, sequence_number: u64) {
+        self.sequence_numbers
+            .lock()
+            .unwrap()
+            .released
+            .insert(sequence_number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{chain_id::ChainId, AccountKey};
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_sequence_number_allocation() {
+        let manager = TransactionManager {
+            client: Client::new("http://localhost:8080".parse().unwrap()),
+            factory: TransactionFactory::new(ChainId::test()),
+            account: LocalAccount::new(Default::default(), AccountKey::generate(&mut OsRng), 5),
+            sequence_numbers: Mutex::new(SequenceNumbers {
+                next: 5,
+                released: BTreeSet::new(),
+            }),
+            config: Default::default(),
+        };
+
+        assert_eq!(manager.allocate_sequence_number(), 5);
+        assert_eq!(manager.allocate_sequence_number(), 6);
+        assert_eq!(manager.allocate_sequence_number(), 7);
+        // Gaps are filled first, lowest first
+        manager.release_sequence_number(6);
+        manager.release_sequence_number(5);
+        assert_eq!(manager.allocate_sequence_number(), 5);
+        assert_eq!(manager.allocate_sequence_number(), 6);
+        assert_eq!(manager.allocate_sequence_number(), 8);
+    }
+}
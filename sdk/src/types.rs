@@ -4,17 +4,25 @@
 use crate::{
     crypto::{
         ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+        mnemonic::Mnemonic,
+        slip10::DerivationPath,
         traits::Uniform,
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AuthenticationKey, TransactionAuthenticator},
+            multi_key::{AnySignature, MultiKey, MultiKeySignature},
+            RawTransaction, SignedTransaction,
+        },
     },
 };
 
+use anyhow::Result;
 use aptos_types::event::EventKey;
 pub use aptos_types::*;
+use std::str::FromStr;
 
 #[derive(Debug)]
 pub struct LocalAccount {
@@ -45,6 +53,25 @@ impl LocalAccount {
         Self::new(address, key, 0)
     }
 
+    /// Recovers an account of a wallet, from the wallet's BIP-39 mnemonic phrase and the
+    /// derivation path of the account, e.g. `m/44'/637'/0'/0'/0'` for its first account.
+    ///
+    /// The address is derived from the key, so this only recovers accounts whose key hasn't
+    /// been rotated.
+    pub fn from_derive_path(
+        derive_path: &str,
+        mnemonic_phrase: &str,
+        sequence_number: u64,
+    ) -> Result<Self> {
+        let derive_path = DerivationPath::from_str(derive_path)?;
+        let mnemonic = Mnemonic::from_phrase(mnemonic_phrase)?;
+        let key =
+            AccountKey::from_private_key(mnemonic.derive_ed25519_private_key("", &derive_path)?);
+        let address = key.authentication_key().derived_address();
+
+        Ok(Self::new(address, key, sequence_number))
+    }
+
     pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
         txn.sign(self.private_key(), self.public_key().clone())
             .expect("Signing a txn can't fail")
@@ -173,3 +200,81 @@ impl From<Ed25519PrivateKey> for AccountKey {
         Self::from_private_key(private_key)
     }
 }
+
+/// An account controlled by K of N public keys, which may each use a different scheme. The
+/// private keys are not held locally, e.g. as they are in HSMs, so the signers sign the
+/// transactions built here, and their signatures are combined with `sign_transaction`.
+#[derive(Debug)]
+pub struct MultiKeyAccount {
+    /// Address of the account.
+    address: AccountAddress,
+    /// Public keys of the signers, and the number of them that must sign.
+    public_key: MultiKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl MultiKeyAccount {
+    pub fn new(address: AccountAddress, public_key: MultiKey, sequence_number: u64) -> Self {
+        Self {
+            address,
+            public_key,
+            sequence_number,
+        }
+    }
+
+    /// An account whose address is derived from `public_key`, which is only the case if its key
+    /// hasn't been rotated.
+    pub fn from_public_key(public_key: MultiKey, sequence_number: u64) -> Self {
+        let address = AuthenticationKey::multi_key(&public_key).derived_address();
+        Self::new(address, public_key, sequence_number)
+    }
+
+    /// Builds the transaction for the signers to sign, with the next sequence number.
+    pub fn build_transaction(&mut self, builder: TransactionBuilder) -> RawTransaction {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        raw_txn
+    }
+
+    /// Combines the signatures of `txn`, each with the index of its signer's public key.
+    /// Fails if there are not enough valid signatures.
+    pub fn sign_transaction(
+        &self,
+        txn: RawTransaction,
+        signatures: Vec<(u8, AnySignature)>,
+    ) -> Result<SignedTransaction> {
+        let authenticator = TransactionAuthenticator::multi_key(
+            self.public_key.clone(),
+            MultiKeySignature::new(signatures)?,
+        );
+        authenticator.verify(&txn)?;
+        Ok(SignedTransaction::new_with_authenticator(
+            txn,
+            authenticator,
+        ))
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn public_key(&self) -> &MultiKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::multi_key(&self.public_key)
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
@@ -14,7 +14,14 @@
 //!
 //! Internally both the client and server leverage a NetworkStream that communications in blocks
 //! where a block is a length prefixed array of bytes.
+//!
+//! Optionally, the client and server can mutually authenticate each other through a Noise IK
+//! handshake when a stream is established, after which all blocks are encrypted.
 
+use aptos_crypto::{
+    noise::{self, NoiseConfig, NoiseError, NoiseSession},
+    x25519,
+};
 use aptos_logger::{info, trace, warn, Schema};
 use aptos_secure_push_metrics::{register_int_counter_vec, IntCounterVec};
 use once_cell::sync::Lazy;
@@ -55,6 +62,7 @@ enum LogEvent {
     ConnectionAttempt,
     ConnectionSuccessful,
     ConnectionFailed,
+    AuthenticationFailed,
     DisconnectedPeerOnRead,
     DisconnectedPeerOnWrite,
     Shutdown,
@@ -142,10 +150,26 @@ pub enum Error {
     NetworkError(#[from] std::io::Error),
     #[error("No active stream")]
     NoActiveStream,
+    #[error("Noise error: {0}")]
+    NoiseError(#[from] NoiseError),
     #[error("Overflow error: {0}")]
     OverflowError(String),
     #[error("Remote stream cleanly closed")]
     RemoteStreamClosed,
+    #[error("Untrusted peer key: {0}")]
+    UntrustedPeer(x25519::PublicKey),
+}
+
+/// The keys a client authenticates with and expects from the server.
+struct ClientAuthentication {
+    noise: NoiseConfig,
+    server_key: x25519::PublicKey,
+}
+
+/// The keys a server authenticates with and accepts from clients.
+struct ServerAuthentication {
+    noise: NoiseConfig,
+    trusted_clients: Vec<x25519::PublicKey>,
 }
 
 pub struct NetworkClient {
@@ -154,6 +178,7 @@ pub struct NetworkClient {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    authentication: Option<ClientAuthentication>,
 }
 
 impl NetworkClient {
@@ -163,6 +188,25 @@ impl NetworkClient {
             server,
             stream: None,
             timeout_ms,
+            authentication: None,
+        }
+    }
+
+    /// Creates a client that only talks to a server holding the private key of `server_key`, and
+    /// that authenticates itself with `identity_key`.
+    pub fn new_authenticated(
+        service: &'static str,
+        server: SocketAddr,
+        timeout_ms: u64,
+        identity_key: x25519::PrivateKey,
+        server_key: x25519::PublicKey,
+    ) -> Self {
+        Self {
+            authentication: Some(ClientAuthentication {
+                noise: NoiseConfig::new(identity_key),
+                server_key,
+            }),
+            ..Self::new(service, server, timeout_ms)
         }
     }
 
@@ -257,7 +301,28 @@ impl NetworkClient {
 
             let stream = stream?;
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, self.server, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, self.server, self.timeout_ms);
+            if let Some(authentication) = &self.authentication {
+                if let Err(err) = stream.initiate_handshake(
+                    self.service.as_bytes(),
+                    &authentication.noise,
+                    authentication.server_key,
+                ) {
+                    self.increment_counter(Method::Connect, MethodResult::Failure);
+                    warn!(SecureNetLogSchema::new(
+                        self.service,
+                        NetworkMode::Client,
+                        LogEvent::AuthenticationFailed,
+                    )
+                    .error(&err)
+                    .remote_peer(&self.server));
+
+                    // Avoid hammering the server if the caller retries right away
+                    thread::sleep(sleeptime);
+                    return Err(err);
+                }
+            }
+            self.stream = Some(stream);
             self.increment_counter(Method::Connect, MethodResult::Success);
             info!(SecureNetLogSchema::new(
                 self.service,
@@ -277,6 +342,7 @@ pub struct NetworkServer {
     stream: Option<NetworkStream>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    authentication: Option<ServerAuthentication>,
 }
 
 impl NetworkServer {
@@ -287,6 +353,25 @@ impl NetworkServer {
             listener: Some(listener.unwrap()),
             stream: None,
             timeout_ms,
+            authentication: None,
+        }
+    }
+
+    /// Creates a server that authenticates itself with `identity_key` and only accepts clients
+    /// holding the private key of one of `trusted_clients`.
+    pub fn new_authenticated(
+        service: &'static str,
+        listen: SocketAddr,
+        timeout_ms: u64,
+        identity_key: x25519::PrivateKey,
+        trusted_clients: Vec<x25519::PublicKey>,
+    ) -> Self {
+        Self {
+            authentication: Some(ServerAuthentication {
+                noise: NoiseConfig::new(identity_key),
+                trusted_clients,
+            }),
+            ..Self::new(service, listen, timeout_ms)
         }
     }
 
@@ -399,18 +484,40 @@ impl NetworkServer {
             .remote_peer(&stream_addr));
 
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, stream_addr, self.timeout_ms));
+            let mut stream = NetworkStream::new(stream, stream_addr, self.timeout_ms);
+            if let Some(authentication) = &self.authentication {
+                if let Err(err) = stream.accept_handshake(
+                    self.service.as_bytes(),
+                    &authentication.noise,
+                    &authentication.trusted_clients,
+                ) {
+                    warn!(SecureNetLogSchema::new(
+                        self.service,
+                        NetworkMode::Server,
+                        LogEvent::AuthenticationFailed,
+                    )
+                    .error(&err)
+                    .remote_peer(&stream_addr));
+                    return Err(err);
+                }
+            }
+            self.stream = Some(stream);
         }
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
     }
 }
 
+/// Noise messages are bounded in size, so larger blocks are encrypted as a sequence of chunks,
+/// each followed by its authentication tag.
+const MAX_NOISE_CHUNK_SIZE: usize = noise::MAX_SIZE_NOISE_MSG - noise::AES_GCM_TAGLEN;
+
 struct NetworkStream {
     stream: TcpStream,
     remote: SocketAddr,
     buffer: Vec<u8>,
     temp_buffer: [u8; 1024],
+    session: Option<NoiseSession>,
 }
 
 impl NetworkStream {
@@ -425,11 +532,87 @@ impl NetworkStream {
             remote,
             buffer: Vec::new(),
             temp_buffer: [0; 1024],
+            session: None,
         }
     }
 
-    /// Blocking read until able to successfully read an entire message
+    /// Performs the initiator side of the Noise IK handshake, expecting the responder to hold
+    /// the private key of `remote_key`.
+    pub fn initiate_handshake(
+        &mut self,
+        prologue: &[u8],
+        noise: &NoiseConfig,
+        remote_key: x25519::PublicKey,
+    ) -> Result<(), Error> {
+        let mut rng = rand::rngs::OsRng;
+        let mut init_msg = vec![0; noise::handshake_init_msg_len(0)];
+        let handshake_state =
+            noise.initiate_connection(&mut rng, prologue, remote_key, None, &mut init_msg)?;
+        self.write_block(&init_msg)?;
+        let resp_msg = self.read_block()?;
+        let (_, session) = noise.finalize_connection(handshake_state, &resp_msg)?;
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Performs the responder side of the Noise IK handshake, rejecting initiators whose key is
+    /// not in `trusted_keys`.
+    pub fn accept_handshake(
+        &mut self,
+        prologue: &[u8],
+        noise: &NoiseConfig,
+        trusted_keys: &[x25519::PublicKey],
+    ) -> Result<(), Error> {
+        let init_msg = self.read_block()?;
+        let (remote_key, handshake_state, _) =
+            noise.parse_client_init_message(prologue, &init_msg)?;
+        if !trusted_keys.contains(&remote_key) {
+            return Err(Error::UntrustedPeer(remote_key));
+        }
+        let mut rng = rand::rngs::OsRng;
+        let mut resp_msg = vec![0; noise::handshake_resp_msg_len(0)];
+        let session = noise.respond_to_client(&mut rng, handshake_state, None, &mut resp_msg)?;
+        self.write_block(&resp_msg)?;
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Blocking read until able to successfully read an entire message, decrypting it if the
+    /// stream is authenticated
     pub fn read(&mut self) -> Result<Vec<u8>, Error> {
+        let mut data = self.read_block()?;
+        let session = match &mut self.session {
+            Some(session) => session,
+            None => return Ok(data),
+        };
+        let mut plaintext = Vec::with_capacity(data.len());
+        for chunk in data.chunks_mut(noise::MAX_SIZE_NOISE_MSG) {
+            plaintext.extend_from_slice(session.read_message_in_place(chunk)?);
+        }
+        Ok(plaintext)
+    }
+
+    /// Blocking write until able to successfully send an entire message, encrypting it if the
+    /// stream is authenticated
+    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        let session = match &mut self.session {
+            Some(session) => session,
+            None => return self.write_block(data),
+        };
+        let mut ciphertext = Vec::with_capacity(
+            data.len() + (data.len() / MAX_NOISE_CHUNK_SIZE + 1) * noise::AES_GCM_TAGLEN,
+        );
+        for chunk in data.chunks(MAX_NOISE_CHUNK_SIZE) {
+            let mut chunk = chunk.to_vec();
+            let tag = session.write_message_in_place(&mut chunk)?;
+            ciphertext.extend(chunk);
+            ciphertext.extend(tag);
+        }
+        self.write_block(&ciphertext)
+    }
+
+    /// Blocking read until able to successfully read an entire block
+    fn read_block(&mut self) -> Result<Vec<u8>, Error> {
         let result = self.read_buffer();
         if !result.is_empty() {
             return Ok(result);
@@ -457,8 +640,8 @@ impl NetworkStream {
         Ok(self.stream.shutdown(Shutdown::Both)?)
     }
 
-    /// Blocking write until able to successfully send an entire message
-    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+    /// Blocking write until able to successfully send an entire block
+    fn write_block(&mut self, data: &[u8]) -> Result<(), Error> {
         let u32_max = u32::max_value() as usize;
         if u32_max <= data.len() {
             return Err(Error::DataTooLarge(data.len()));
@@ -518,6 +701,8 @@ impl NetworkStream {
 mod test {
     use super::*;
     use aptos_config::utils;
+    use aptos_crypto::{PrivateKey, Uniform};
+    use rand::{rngs::StdRng, SeedableRng};
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
     /// Read, Write, Connect timeout in milliseconds.
@@ -656,4 +841,78 @@ mod test {
         let result2 = server2.read().unwrap();
         assert_eq!(data2, result2);
     }
+
+    #[test]
+    fn test_authenticated_ping() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let server_key = x25519::PrivateKey::generate(&mut rng);
+        let server_public_key = server_key.public_key();
+        let client_key = x25519::PrivateKey::generate(&mut rng);
+        let client_public_key = client_key.public_key();
+
+        let mut server = NetworkServer::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_key,
+            vec![client_public_key],
+        );
+        // The handshake requires both ends to make progress, so the server runs in its own thread
+        let server_thread = thread::spawn(move || {
+            let data = server.read().unwrap();
+            server.write(&data).unwrap();
+        });
+        let mut client = NetworkClient::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            client_key,
+            server_public_key,
+        );
+
+        // Larger than a single noise message
+        let data: Vec<u8> = (0..3 * noise::MAX_SIZE_NOISE_MSG)
+            .map(|i| i as u8)
+            .collect();
+        client.write(&data).unwrap();
+        let result = client.read().unwrap();
+        assert_eq!(data, result);
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_untrusted_client() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let server_key = x25519::PrivateKey::generate(&mut rng);
+        let server_public_key = server_key.public_key();
+        let trusted_key = x25519::PrivateKey::generate(&mut rng);
+        let untrusted_key = x25519::PrivateKey::generate(&mut rng);
+        let untrusted_public_key = untrusted_key.public_key();
+
+        let mut server = NetworkServer::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            server_key,
+            vec![trusted_key.public_key()],
+        );
+        let server_thread = thread::spawn(move || match server.read() {
+            Err(Error::UntrustedPeer(key)) => assert_eq!(key, untrusted_public_key),
+            result => panic!("Unexpected result: {:?}", result),
+        });
+        let mut client = NetworkClient::new_authenticated(
+            "test",
+            server_addr,
+            TIMEOUT,
+            untrusted_key,
+            server_public_key,
+        );
+
+        client.write(&[0, 1, 2, 3]).unwrap_err();
+        server_thread.join().unwrap();
+    }
 }
@@ -0,0 +1,190 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with Ed25519 keys held by AWS KMS, through its JSON API: <https://docs.aws.amazon.com/kms/latest/APIReference/>
+//!
+//! Credentials are read from the standard environment variables, `AWS_ACCESS_KEY_ID`,
+//! `AWS_SECRET_ACCESS_KEY` and, for temporary credentials, `AWS_SESSION_TOKEN`.
+
+use crate::{ed25519_from_spki, Error, KeyScheme, SigningBackend, REQUEST_TIMEOUT};
+use aptos_config::config::AwsKmsConfig;
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::env;
+
+pub struct AwsKmsBackend {
+    client: reqwest::Client,
+    endpoint: String,
+    host: String,
+    region: String,
+    key_id: String,
+    credentials: Credentials,
+}
+
+struct Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl Credentials {
+    fn from_env() -> Result<Self, Error> {
+        let var = |name: &str| {
+            env::var(name).map_err(|_| Error::ConfigError(format!("{} is not set", name)))
+        };
+        Ok(Self {
+            access_key_id: var("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: var("AWS_SECRET_ACCESS_KEY")?,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+impl AwsKmsBackend {
+    pub fn new(config: &AwsKmsConfig) -> Result<Self, Error> {
+        let endpoint = config
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://kms.{}.amazonaws.com", config.region));
+        let host = reqwest::Url::parse(&endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .ok_or_else(|| Error::ConfigError(format!("Invalid KMS endpoint: {}", endpoint)))?;
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            endpoint,
+            host,
+            region: config.region.clone(),
+            key_id: config.key_id.clone(),
+            credentials: Credentials::from_env()?,
+        })
+    }
+
+    /// Calls the action `target` of the KMS API, signing the request with Signature Version 4:
+    /// <https://docs.aws.amazon.com/general/latest/gr/sigv4_signing.html>
+    async fn call(&self, target: &str, body: Value) -> Result<Value, Error> {
+        let body = body.to_string();
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let target = format!("TrentService.{}", target);
+
+        // Headers must be sorted by name
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.1".to_string()),
+            ("host", self.host.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(session_token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", session_token.clone()));
+        }
+        headers.push(("x-amz-target", target));
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_headers,
+            hex::encode(Sha256::digest(body.as_bytes()))
+        );
+        let scope = format!("{}/{}/kms/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = format!("AWS4{}", self.credentials.secret_access_key);
+        let key = hmac_sha256(key.as_bytes(), date.as_bytes());
+        let key = hmac_sha256(&key, self.region.as_bytes());
+        let key = hmac_sha256(&key, b"kms");
+        let key = hmac_sha256(&key, b"aws4_request");
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.credentials.access_key_id, scope, signed_headers, signature
+        );
+
+        let mut request = self
+            .client
+            .post(&self.endpoint)
+            .header("authorization", authorization)
+            .body(body);
+        for (name, value) in headers {
+            // The host header is set by the client
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(Error::BackendError(format!(
+                "AWS KMS returned {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            )));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn base64_field(response: &Value, field: &str) -> Result<Vec<u8>, Error> {
+    let value = response[field]
+        .as_str()
+        .ok_or_else(|| Error::BackendError(format!("AWS KMS response is missing {}", field)))?;
+    Ok(base64::decode(value)?)
+}
+
+#[async_trait]
+impl SigningBackend for AwsKmsBackend {
+    fn name(&self) -> &'static str {
+        "aws_kms"
+    }
+
+    fn scheme(&self) -> KeyScheme {
+        KeyScheme::Ed25519
+    }
+
+    async fn public_key(&self) -> Result<Vec<u8>, Error> {
+        let response = self
+            .call("GetPublicKey", json!({ "KeyId": self.key_id }))
+            .await?;
+        ed25519_from_spki(&base64_field(&response, "PublicKey")?)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let response = self
+            .call(
+                "Sign",
+                json!({
+                    "KeyId": self.key_id,
+                    "Message": base64::encode(message),
+                    "MessageType": "RAW",
+                    "SigningAlgorithm": "ED25519_SHA_512",
+                }),
+            )
+            .await?;
+        base64_field(&response, "Signature")
+    }
+}
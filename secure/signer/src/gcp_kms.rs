@@ -0,0 +1,148 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with Ed25519 keys held by Google Cloud KMS, through its REST API:
+//! <https://cloud.google.com/kms/docs/reference/rest>
+
+use crate::{ed25519_from_spki, Error, KeyScheme, SigningBackend, REQUEST_TIMEOUT};
+use aptos_config::config::GcpKmsConfig;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const KMS_URL: &str = "https://cloudkms.googleapis.com/v1";
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Tokens from the metadata server are renewed this long before they expire.
+const TOKEN_RENEWAL_MARGIN: Duration = Duration::from_secs(60);
+
+pub struct GcpKmsBackend {
+    client: reqwest::Client,
+    key_version: String,
+    /// The token from the config, if any
+    token: Option<String>,
+    /// The last token from the metadata server, and when to renew it
+    metadata_token: Mutex<Option<(String, Instant)>>,
+}
+
+#[derive(Deserialize)]
+struct MetadataToken {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+    algorithm: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+impl GcpKmsBackend {
+    pub fn new(config: &GcpKmsConfig) -> Result<Self, Error> {
+        let token = match &config.token {
+            Some(token) => Some(token.read_token()?),
+            None => None,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            key_version: config.key_version.clone(),
+            token,
+            metadata_token: Mutex::new(None),
+        })
+    }
+
+    async fn access_token(&self) -> Result<String, Error> {
+        if let Some(token) = &self.token {
+            return Ok(token.clone());
+        }
+
+        let mut metadata_token = self.metadata_token.lock().await;
+        if let Some((token, renew_at)) = metadata_token.as_ref() {
+            if Instant::now() < *renew_at {
+                return Ok(token.clone());
+            }
+        }
+        let token: MetadataToken = self
+            .client
+            .get(METADATA_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let lifetime = Duration::from_secs(token.expires_in).saturating_sub(TOKEN_RENEWAL_MARGIN);
+        *metadata_token = Some((token.access_token.clone(), Instant::now() + lifetime));
+        Ok(token.access_token)
+    }
+
+    async fn check(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Error::BackendError(format!(
+                "GCP KMS returned {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            )))
+        }
+    }
+}
+
+#[async_trait]
+impl SigningBackend for GcpKmsBackend {
+    fn name(&self) -> &'static str {
+        "gcp_kms"
+    }
+
+    fn scheme(&self) -> KeyScheme {
+        KeyScheme::Ed25519
+    }
+
+    async fn public_key(&self) -> Result<Vec<u8>, Error> {
+        let response = self
+            .client
+            .get(format!("{}/{}/publicKey", KMS_URL, self.key_version))
+            .bearer_auth(self.access_token().await?)
+            .send()
+            .await?;
+        let response: PublicKeyResponse = Self::check(response).await?.json().await?;
+        if response.algorithm != "EC_SIGN_ED25519" {
+            return Err(Error::UnsupportedScheme(
+                KeyScheme::Ed25519,
+                format!("GCP KMS key with algorithm {}", response.algorithm),
+            ));
+        }
+
+        let der: String = response
+            .pem
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        ed25519_from_spki(&base64::decode(der)?)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        // Ed25519 keys sign the message itself, rather than a digest of it
+        let response = self
+            .client
+            .post(format!("{}/{}:asymmetricSign", KMS_URL, self.key_version))
+            .bearer_auth(self.access_token().await?)
+            .json(&json!({ "data": base64::encode(message) }))
+            .send()
+            .await?;
+        let response: SignResponse = Self::check(response).await?.json().await?;
+        Ok(base64::decode(response.signature)?)
+    }
+}
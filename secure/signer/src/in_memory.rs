@@ -0,0 +1,44 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, KeyScheme, SigningBackend};
+use aptos_crypto::{bls12381, ed25519::Ed25519PrivateKey, PrivateKey, SigningKey};
+use async_trait::async_trait;
+
+/// Signs with a key held in memory, to stand in for an HSM or a KMS in tests.
+pub enum InMemoryBackend {
+    Ed25519(Ed25519PrivateKey),
+    Bls12381(bls12381::PrivateKey),
+}
+
+#[async_trait]
+impl SigningBackend for InMemoryBackend {
+    fn name(&self) -> &'static str {
+        "in_memory"
+    }
+
+    fn scheme(&self) -> KeyScheme {
+        match self {
+            InMemoryBackend::Ed25519(_) => KeyScheme::Ed25519,
+            InMemoryBackend::Bls12381(_) => KeyScheme::Bls12381,
+        }
+    }
+
+    async fn public_key(&self) -> Result<Vec<u8>, Error> {
+        Ok(match self {
+            InMemoryBackend::Ed25519(key) => key.public_key().to_bytes().to_vec(),
+            InMemoryBackend::Bls12381(key) => key.public_key().to_bytes().to_vec(),
+        })
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(match self {
+            InMemoryBackend::Ed25519(key) => {
+                key.sign_arbitrary_message(message).to_bytes().to_vec()
+            }
+            InMemoryBackend::Bls12381(key) => {
+                key.sign_arbitrary_message(message).to_bytes().to_vec()
+            }
+        })
+    }
+}
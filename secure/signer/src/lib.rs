@@ -0,0 +1,366 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with keys held outside of the node, in a hardware security module (HSM) or a key
+//! management service (KMS), so that they never touch the node's disk.
+//!
+//! A [`SigningBackend`] signs raw messages with a single key, asynchronously. A [`RemoteSigner`]
+//! wraps a backend for a signature scheme: it signs the same hashable types as local keys, checks
+//! that the backend is healthy and holds the expected kind of key, and also serves synchronous
+//! callers, e.g. safety rules, by running the requests on a runtime of its own.
+//!
+//! Among Aptos' signature schemes, PKCS#11 devices and the AWS and GCP KMSs only support Ed25519.
+//! BLS12-381 keys, e.g. consensus keys, are held by a signing service instead, which typically
+//! fronts an HSM with vendor support for BLS12-381, see [`signing_service`].
+//!
+//! The PKCS#11 backend links the vendor's PKCS#11 library through `cryptoki`, so it's only built
+//! with the `pkcs11` feature, which the node forwards as its own `pkcs11` feature. The KMS and
+//! signing service backends only need an HTTP client.
+
+pub mod aws_kms;
+pub mod gcp_kms;
+#[cfg(any(test, feature = "testing"))]
+pub mod in_memory;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
+pub mod signing_service;
+
+use aptos_config::config::SignerConfig;
+use aptos_crypto::{
+    bls12381,
+    ed25519::{Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH},
+    hash::CryptoHash,
+    signing_message, traits, CryptoMaterialError,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, fmt, future::Future, sync::Arc, time::Duration};
+use thiserror::Error;
+use tokio::runtime::Runtime;
+
+/// Timeout for requests to signing backends.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The message signed by health checks, which no other message can be mistaken for as it's not
+/// prefixed by the hash of a type.
+const HEALTH_CHECK_MESSAGE: &[u8] = b"APTOS::RemoteSigner::HealthCheck";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("Signing backend error: {0}")]
+    BackendError(String),
+    #[error("Invalid signing backend configuration: {0}")]
+    ConfigError(String),
+    #[error("Signing backend failed its health check: {0}")]
+    HealthCheckFailed(String),
+    #[error("Invalid key material returned by the signing backend: {0}")]
+    InvalidKeyMaterial(String),
+    #[error("{1} does not support {0:?} keys")]
+    UnsupportedScheme(KeyScheme, String),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Self::BackendError(error.to_string())
+    }
+}
+
+impl From<base64::DecodeError> for Error {
+    fn from(error: base64::DecodeError) -> Self {
+        Self::InvalidKeyMaterial(error.to_string())
+    }
+}
+
+impl From<hex::FromHexError> for Error {
+    fn from(error: hex::FromHexError) -> Self {
+        Self::InvalidKeyMaterial(error.to_string())
+    }
+}
+
+impl From<CryptoMaterialError> for Error {
+    fn from(error: CryptoMaterialError) -> Self {
+        Self::InvalidKeyMaterial(error.to_string())
+    }
+}
+
+impl From<aptos_config::config::Error> for Error {
+    fn from(error: aptos_config::config::Error) -> Self {
+        Self::ConfigError(error.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyScheme {
+    Ed25519,
+    Bls12381,
+}
+
+/// Signs with a single key held by an HSM or a KMS.
+#[async_trait]
+pub trait SigningBackend: Send + Sync {
+    /// The name of the backend, for errors and logs.
+    fn name(&self) -> &'static str;
+
+    fn scheme(&self) -> KeyScheme;
+
+    /// The serialized public key.
+    async fn public_key(&self) -> Result<Vec<u8>, Error>;
+
+    /// Signs `message` as is, returning the serialized signature.
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// A signature scheme whose keys can be held by a [`SigningBackend`].
+pub trait RemoteScheme: Sized + Send + 'static {
+    const SCHEME: KeyScheme;
+
+    type PublicKey: Clone + fmt::Debug + PartialEq + Send + Sync;
+
+    /// Deserializes a public key returned by a backend, which isn't trusted to return a valid one.
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error>;
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self, Error>;
+
+    fn verify(&self, message: &[u8], public_key: &Self::PublicKey) -> Result<(), Error>;
+}
+
+impl RemoteScheme for Ed25519Signature {
+    const SCHEME: KeyScheme = KeyScheme::Ed25519;
+
+    type PublicKey = Ed25519PublicKey;
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Ed25519PublicKey, Error> {
+        Ok(Ed25519PublicKey::try_from(bytes)?)
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Ed25519Signature::try_from(bytes)?)
+    }
+
+    fn verify(&self, message: &[u8], public_key: &Ed25519PublicKey) -> Result<(), Error> {
+        traits::Signature::verify_arbitrary_msg(self, message, public_key)
+            .map_err(|error| Error::HealthCheckFailed(error.to_string()))
+    }
+}
+
+impl RemoteScheme for bls12381::Signature {
+    const SCHEME: KeyScheme = KeyScheme::Bls12381;
+
+    type PublicKey = bls12381::PublicKey;
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<bls12381::PublicKey, Error> {
+        let public_key = bls12381::PublicKey::try_from(bytes)?;
+        public_key
+            .subgroup_check()
+            .map_err(|error| Error::InvalidKeyMaterial(error.to_string()))?;
+        Ok(public_key)
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(bls12381::Signature::try_from(bytes)?)
+    }
+
+    fn verify(&self, message: &[u8], public_key: &bls12381::PublicKey) -> Result<(), Error> {
+        traits::Signature::verify_arbitrary_msg(self, message, public_key)
+            .map_err(|error| Error::HealthCheckFailed(error.to_string()))
+    }
+}
+
+/// Signs with the key of `S` held by a [`SigningBackend`].
+pub struct RemoteSigner<S: RemoteScheme> {
+    backend: Arc<dyn SigningBackend>,
+    public_key: S::PublicKey,
+    /// Runs the requests of synchronous callers, which may themselves run on another runtime.
+    runtime: Runtime,
+}
+
+impl<S: RemoteScheme> RemoteSigner<S> {
+    /// Fetches the public key from `backend` and checks that it can sign with it.
+    pub fn new(backend: Arc<dyn SigningBackend>) -> Result<Self, Error> {
+        if backend.scheme() != S::SCHEME {
+            return Err(Error::UnsupportedScheme(S::SCHEME, backend.name().into()));
+        }
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("remote-signer")
+            .enable_all()
+            .build()
+            .map_err(|error| Error::BackendError(error.to_string()))?;
+
+        let public_key_backend = backend.clone();
+        let public_key = run(
+            &runtime,
+            async move { public_key_backend.public_key().await },
+        )?;
+        let signer = Self {
+            backend,
+            public_key: S::public_key_from_bytes(&public_key)?,
+            runtime,
+        };
+        signer.health_check()?;
+        Ok(signer)
+    }
+
+    pub fn from_config(config: &SignerConfig) -> Result<Self, Error> {
+        Self::new(backend(config, S::SCHEME)?)
+    }
+
+    pub fn public_key(&self) -> &S::PublicKey {
+        &self.public_key
+    }
+
+    /// Signs `message` on the caller's runtime.
+    pub async fn sign_async<T: CryptoHash + Serialize>(&self, message: &T) -> Result<S, Error> {
+        let signature = self.backend.sign(&signing_message(message)).await?;
+        S::signature_from_bytes(&signature)
+    }
+
+    /// Signs `message`, blocking until the backend responds.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Result<S, Error> {
+        let backend = self.backend.clone();
+        let message = signing_message(message);
+        let signature = run(&self.runtime, async move { backend.sign(&message).await })?;
+        S::signature_from_bytes(&signature)
+    }
+
+    /// Signs a probe message and verifies the signature, to detect a backend which can't be
+    /// reached, or which no longer holds the key.
+    pub async fn health_check_async(&self) -> Result<(), Error> {
+        let signature = self.backend.sign(HEALTH_CHECK_MESSAGE).await?;
+        S::signature_from_bytes(&signature)?.verify(HEALTH_CHECK_MESSAGE, &self.public_key)
+    }
+
+    /// Like [`RemoteSigner::health_check_async`], blocking until the backend responds.
+    pub fn health_check(&self) -> Result<(), Error> {
+        let backend = self.backend.clone();
+        let signature = run(&self.runtime, async move {
+            backend.sign(HEALTH_CHECK_MESSAGE).await
+        })?;
+        S::signature_from_bytes(&signature)?.verify(HEALTH_CHECK_MESSAGE, &self.public_key)
+    }
+}
+
+impl<S: RemoteScheme> fmt::Debug for RemoteSigner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RemoteSigner({}, {:?})",
+            self.backend.name(),
+            self.public_key
+        )
+    }
+}
+
+/// Runs `future` on `runtime`, and blocks until it completes. Unlike `Runtime::block_on`, this may
+/// be called from within another runtime.
+fn run<F, T>(runtime: &Runtime, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>> + Send + 'static,
+    T: Send + 'static,
+{
+    futures::executor::block_on(runtime.spawn(future))
+        .map_err(|error| Error::BackendError(error.to_string()))?
+}
+
+/// Creates the backend for a key of `scheme` described by `config`.
+pub fn backend(config: &SignerConfig, scheme: KeyScheme) -> Result<Arc<dyn SigningBackend>, Error> {
+    let backend: Arc<dyn SigningBackend> = match config {
+        SignerConfig::AwsKms(config) => Arc::new(aws_kms::AwsKmsBackend::new(config)?),
+        SignerConfig::GcpKms(config) => Arc::new(gcp_kms::GcpKmsBackend::new(config)?),
+        #[cfg(feature = "pkcs11")]
+        SignerConfig::Pkcs11(config) => Arc::new(pkcs11::Pkcs11Backend::new(config)?),
+        #[cfg(not(feature = "pkcs11"))]
+        SignerConfig::Pkcs11(_) => {
+            return Err(Error::ConfigError(
+                "PKCS#11 support requires building the node with the `pkcs11` feature".into(),
+            ))
+        }
+        SignerConfig::SigningService(config) => {
+            Arc::new(signing_service::SigningServiceBackend::new(config, scheme)?)
+        }
+    };
+    if backend.scheme() != scheme {
+        return Err(Error::UnsupportedScheme(scheme, backend.name().into()));
+    }
+    Ok(backend)
+}
+
+/// The DER encoding of an Ed25519 SubjectPublicKeyInfo, up to the key itself.
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// Extracts the Ed25519 public key from its DER encoded SubjectPublicKeyInfo, as returned by KMSs.
+pub(crate) fn ed25519_from_spki(der: &[u8]) -> Result<Vec<u8>, Error> {
+    if der.len() != ED25519_SPKI_PREFIX.len() + ED25519_PUBLIC_KEY_LENGTH
+        || der[..ED25519_SPKI_PREFIX.len()] != ED25519_SPKI_PREFIX
+    {
+        return Err(Error::InvalidKeyMaterial(
+            "Not an Ed25519 SubjectPublicKeyInfo".into(),
+        ));
+    }
+    Ok(der[ED25519_SPKI_PREFIX.len()..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::in_memory::InMemoryBackend;
+    use aptos_crypto::{
+        ed25519::Ed25519PrivateKey, test_utils::TestAptosCrypto, PrivateKey, Signature, Uniform,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_remote_signer() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let message = TestAptosCrypto("test".to_string());
+
+        let private_key = Ed25519PrivateKey::generate(&mut rng);
+        let public_key = private_key.public_key();
+        let signer: RemoteSigner<Ed25519Signature> =
+            RemoteSigner::new(Arc::new(InMemoryBackend::Ed25519(private_key))).unwrap();
+        assert_eq!(signer.public_key(), &public_key);
+        signer
+            .sign(&message)
+            .unwrap()
+            .verify(&message, &public_key)
+            .unwrap();
+
+        let private_key = bls12381::PrivateKey::generate(&mut rng);
+        let public_key = private_key.public_key();
+        let signer: RemoteSigner<bls12381::Signature> =
+            RemoteSigner::new(Arc::new(InMemoryBackend::Bls12381(private_key))).unwrap();
+        signer
+            .sign(&message)
+            .unwrap()
+            .verify(&message, &public_key)
+            .unwrap();
+        // Synchronous signing works from within another runtime
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(async { signer.sign(&message) })
+            .unwrap();
+
+        // The key must be of the signer's scheme
+        let backend = Arc::new(InMemoryBackend::Ed25519(Ed25519PrivateKey::generate(
+            &mut rng,
+        )));
+        assert_eq!(
+            RemoteSigner::<bls12381::Signature>::new(backend).unwrap_err(),
+            Error::UnsupportedScheme(KeyScheme::Bls12381, "in_memory".into())
+        );
+    }
+
+    #[test]
+    fn test_ed25519_from_spki() {
+        let key = [7u8; ED25519_PUBLIC_KEY_LENGTH];
+        let der = [&ED25519_SPKI_PREFIX[..], &key[..]].concat();
+        assert_eq!(ed25519_from_spki(&der).unwrap(), key.to_vec());
+        assert!(ed25519_from_spki(&der[1..]).is_err());
+        let mut der = der;
+        der[8] = 0x71;
+        assert!(ed25519_from_spki(&der).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing with Ed25519 keys held by a PKCS#11 device, e.g. an HSM or SoftHSM for testing.
+//!
+//! The private key and its public key are looked up by label, and signed with the EdDSA mechanism
+//! of PKCS#11 3.0. PKCS#11 calls block, so they're run on tokio's blocking threads.
+
+use crate::{Error, KeyScheme, SigningBackend};
+use aptos_config::config::Pkcs11Config;
+use aptos_crypto::ed25519::ED25519_PUBLIC_KEY_LENGTH;
+use async_trait::async_trait;
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    slot::Slot,
+};
+use std::{
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+};
+
+pub struct Pkcs11Backend {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    session: Mutex<Session>,
+    private_key: ObjectHandle,
+    public_key: ObjectHandle,
+}
+
+impl From<cryptoki::error::Error> for Error {
+    fn from(error: cryptoki::error::Error) -> Self {
+        Self::BackendError(error.to_string())
+    }
+}
+
+impl Pkcs11Backend {
+    pub fn new(config: &Pkcs11Config) -> Result<Self, Error> {
+        let pkcs11 = Pkcs11::new(&config.library)?;
+        pkcs11.initialize(CInitializeArgs::OsThreads)?;
+
+        let slot = match config.slot {
+            Some(slot) => Slot::try_from(slot)?,
+            None => *pkcs11
+                .get_slots_with_token()?
+                .first()
+                .ok_or_else(|| Error::ConfigError("No PKCS#11 slot with a token".into()))?,
+        };
+        let session = pkcs11.open_ro_session(slot)?;
+        session.login(UserType::User, Some(&config.pin.read_token()?))?;
+
+        let private_key = find_key(&session, ObjectClass::PRIVATE_KEY, &config.key_label)?;
+        let public_key = find_key(&session, ObjectClass::PUBLIC_KEY, &config.key_label)?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                session: Mutex::new(session),
+                private_key,
+                public_key,
+            }),
+        })
+    }
+
+    /// Runs `f` with the session on a blocking thread.
+    async fn with_session<F, T>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Inner, &Session) -> Result<T, Error> + Send + 'static,
+        T: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || {
+            let session = inner
+                .session
+                .lock()
+                .map_err(|_| Error::BackendError("PKCS#11 session lock poisoned".into()))?;
+            f(&inner, &session)
+        })
+        .await
+        .map_err(|error| Error::BackendError(error.to_string()))?
+    }
+}
+
+fn find_key(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle, Error> {
+    let template = [
+        Attribute::Class(class),
+        Attribute::Label(label.as_bytes().to_vec()),
+    ];
+    match session.find_objects(&template)?.as_slice() {
+        [key] => Ok(*key),
+        [] => Err(Error::ConfigError(format!(
+            "No PKCS#11 {} labelled {}",
+            class, label
+        ))),
+        _ => Err(Error::ConfigError(format!(
+            "Several PKCS#11 {}s labelled {}",
+            class, label
+        ))),
+    }
+}
+
+/// Extracts the public key from an EC point, which devices return either DER encoded as an
+/// OCTET STRING, or raw.
+fn ed25519_from_ec_point(point: &[u8]) -> Result<Vec<u8>, Error> {
+    match point {
+        [0x04, 0x20, key @ ..] if key.len() == ED25519_PUBLIC_KEY_LENGTH => Ok(key.to_vec()),
+        key if key.len() == ED25519_PUBLIC_KEY_LENGTH => Ok(key.to_vec()),
+        _ => Err(Error::InvalidKeyMaterial("Not an Ed25519 EC point".into())),
+    }
+}
+
+#[async_trait]
+impl SigningBackend for Pkcs11Backend {
+    fn name(&self) -> &'static str {
+        "pkcs11"
+    }
+
+    fn scheme(&self) -> KeyScheme {
+        KeyScheme::Ed25519
+    }
+
+    async fn public_key(&self) -> Result<Vec<u8>, Error> {
+        self.with_session(|inner, session| {
+            let attributes = session.get_attributes(inner.public_key, &[AttributeType::EcPoint])?;
+            match attributes.as_slice() {
+                [Attribute::EcPoint(point)] => ed25519_from_ec_point(point),
+                _ => Err(Error::InvalidKeyMaterial(
+                    "PKCS#11 public key has no EC point".into(),
+                )),
+            }
+        })
+        .await
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let message = message.to_vec();
+        self.with_session(move |inner, session| {
+            Ok(session.sign(&Mechanism::Eddsa, inner.private_key, &message)?)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ed25519_from_ec_point() {
+        let key = [7u8; ED25519_PUBLIC_KEY_LENGTH];
+        let der = [&[0x04, 0x20][..], &key[..]].concat();
+        assert_eq!(ed25519_from_ec_point(&der).unwrap(), key.to_vec());
+        assert_eq!(ed25519_from_ec_point(&key).unwrap(), key.to_vec());
+        assert!(ed25519_from_ec_point(&der[1..]).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing through a signing service, typically fronting an HSM, over a small HTTP JSON protocol:
+//!
+//! * `GET {server}/v1/keys/{key_id}` returns the key, as `{"scheme": "bls12381", "public_key": hex}`
+//! * `POST {server}/v1/keys/{key_id}/sign` with `{"message": hex}` signs the message as is, and
+//!   returns `{"signature": hex}`
+//!
+//! Schemes are named as in [`KeyScheme`], and keys and signatures are serialized as by
+//! `aptos_crypto`. If configured, requests carry the token as a bearer token.
+
+use crate::{Error, KeyScheme, SigningBackend, REQUEST_TIMEOUT};
+use aptos_config::config::SigningServiceConfig;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub struct SigningServiceBackend {
+    client: reqwest::Client,
+    key_url: String,
+    scheme: KeyScheme,
+    token: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct KeyResponse {
+    scheme: KeyScheme,
+    public_key: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignRequest {
+    message: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SignResponse {
+    signature: String,
+}
+
+impl SigningServiceBackend {
+    /// Creates a backend for the key of `scheme` described by `config`. The key's scheme is checked
+    /// against the service when fetching the public key.
+    pub fn new(config: &SigningServiceConfig, scheme: KeyScheme) -> Result<Self, Error> {
+        let token = match &config.token {
+            Some(token) => Some(token.read_token()?),
+            None => None,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            key_url: format!(
+                "{}/v1/keys/{}",
+                config.server.trim_end_matches('/'),
+                config.key_id
+            ),
+            scheme,
+            token,
+        })
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn check(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Error::BackendError(format!(
+                "Signing service returned {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            )))
+        }
+    }
+}
+
+#[async_trait]
+impl SigningBackend for SigningServiceBackend {
+    fn name(&self) -> &'static str {
+        "signing_service"
+    }
+
+    fn scheme(&self) -> KeyScheme {
+        self.scheme
+    }
+
+    async fn public_key(&self) -> Result<Vec<u8>, Error> {
+        let response = self
+            .authorize(self.client.get(&self.key_url))
+            .send()
+            .await?;
+        let response: KeyResponse = Self::check(response).await?.json().await?;
+        if response.scheme != self.scheme {
+            return Err(Error::UnsupportedScheme(
+                self.scheme,
+                format!("Signing service key of scheme {:?}", response.scheme),
+            ));
+        }
+        Ok(hex::decode(response.public_key)?)
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let request = SignRequest {
+            message: hex::encode(message),
+        };
+        let response = self
+            .authorize(self.client.post(format!("{}/sign", self.key_url)))
+            .json(&request)
+            .send()
+            .await?;
+        let response: SignResponse = Self::check(response).await?.json().await?;
+        Ok(hex::decode(response.signature)?)
+    }
+}
@@ -0,0 +1,373 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac, NewMac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use thiserror::Error;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+const SERVICE: &str = "secretsmanager";
+
+/// Default request timeouts for Secrets Manager operations
+const DEFAULT_CONNECTION_TIMEOUT_MS: u64 = 1_000;
+const DEFAULT_RESPONSE_TIMEOUT_MS: u64 = 5_000;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("Http error, status code: {0}, status text: {1}, body: {2}")]
+    HttpError(u16, String, String),
+    #[error("Internal error: {0}")]
+    InternalError(String),
+    #[error("404: Not Found: {0}")]
+    NotFound(String),
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<ureq::Response> for Error {
+    fn from(resp: ureq::Response) -> Self {
+        if let Some(e) = resp.synthetic_error() {
+            // Local error
+            Error::InternalError(e.to_string())
+        } else {
+            let status = resp.status();
+            let status_text = resp.status_text().to_string();
+            match resp.into_string() {
+                Ok(body) => Error::HttpError(status, status_text, body),
+                Err(e) => Error::InternalError(e.to_string()),
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+/// The AWS credentials requests are signed with, either those of an IAM user or temporary ones,
+/// which come with a session token
+#[derive(Clone)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+/// Client provides a client around the JSON interface to AWS Secrets Manager. Learn more
+/// here: <https://docs.aws.amazon.com/secretsmanager/latest/apireference/>
+///
+/// Each secret holds a single string value, and every update creates a new version of the
+/// secret, of which Secrets Manager returns the latest. Requests are authenticated with
+/// Signature Version 4.
+pub struct Client {
+    agent: ureq::Agent,
+    credentials: Credentials,
+    endpoint: String,
+    host: String,
+    region: String,
+
+    /// Timeout for new socket connections to Secrets Manager.
+    connection_timeout_ms: u64,
+    /// Timeout for Secrets Manager responses.
+    response_timeout_ms: u64,
+}
+
+impl Client {
+    /// Creates a client for Secrets Manager in `region`, at its public endpoint unless
+    /// `endpoint` is given, e.g. for a VPC endpoint
+    pub fn new(
+        region: String,
+        endpoint: Option<String>,
+        credentials: Credentials,
+        connection_timeout_ms: Option<u64>,
+        response_timeout_ms: Option<u64>,
+    ) -> Self {
+        let endpoint = endpoint
+            .unwrap_or_else(|| format!("https://{}.{}.amazonaws.com", SERVICE, region))
+            .trim_end_matches('/')
+            .to_string();
+        let host = endpoint
+            .split("://")
+            .last()
+            .unwrap_or(&endpoint)
+            .to_string();
+        Self {
+            agent: ureq::Agent::new().set("connection", "keep-alive").build(),
+            credentials,
+            endpoint,
+            host,
+            region,
+            connection_timeout_ms: connection_timeout_ms.unwrap_or(DEFAULT_CONNECTION_TIMEOUT_MS),
+            response_timeout_ms: response_timeout_ms.unwrap_or(DEFAULT_RESPONSE_TIMEOUT_MS),
+        }
+    }
+
+    /// Create a new secret with an initial value
+    pub fn create_secret(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.request(
+            "CreateSecret",
+            name,
+            json!({ "Name": name, "SecretString": value }),
+        )?;
+        Ok(())
+    }
+
+    /// Delete a secret and all of its versions, immediately rather than after a recovery window
+    pub fn delete_secret(&self, name: &str) -> Result<(), Error> {
+        self.request(
+            "DeleteSecret",
+            name,
+            json!({ "SecretId": name, "ForceDeleteWithoutRecovery": true }),
+        )?;
+        Ok(())
+    }
+
+    /// Retrieve the value of the latest version of a secret
+    pub fn get_secret_value(&self, name: &str) -> Result<String, Error> {
+        let resp = self.request("GetSecretValue", name, json!({ "SecretId": name }))?;
+        let resp: GetSecretValueResponse = serde_json::from_value(resp)?;
+        Ok(resp.secret_string)
+    }
+
+    /// List the names of all secrets
+    pub fn list_secrets(&self) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        let mut next_token = None;
+        loop {
+            let body = match &next_token {
+                Some(token) => json!({ "NextToken": token }),
+                None => json!({}),
+            };
+            let resp = self.request("ListSecrets", "", body)?;
+            let resp: ListSecretsResponse = serde_json::from_value(resp)?;
+            names.extend(resp.secret_list.into_iter().map(|secret| secret.name));
+            next_token = resp.next_token;
+            if next_token.is_none() {
+                return Ok(names);
+            }
+        }
+    }
+
+    /// Store a new value as the latest version of an existing secret
+    pub fn put_secret_value(&self, name: &str, value: &str) -> Result<(), Error> {
+        self.request(
+            "PutSecretValue",
+            name,
+            json!({ "SecretId": name, "SecretString": value }),
+        )?;
+        Ok(())
+    }
+
+    /// Send a signed request for `action` on the secret `name`
+    fn request(&self, action: &str, name: &str, body: Value) -> Result<Value, Error> {
+        let body = body.to_string();
+        let target = format!("{}.{}", SERVICE, action);
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+
+        let mut request = self.agent.post(&self.endpoint);
+        request
+            .timeout_connect(self.connection_timeout_ms)
+            .timeout(Duration::from_millis(self.response_timeout_ms))
+            .set("Content-Type", CONTENT_TYPE)
+            .set("X-Amz-Date", &amz_date)
+            .set("X-Amz-Target", &target)
+            .set(
+                "Authorization",
+                &authorization(
+                    &self.credentials,
+                    &self.region,
+                    &self.host,
+                    &target,
+                    &body,
+                    now,
+                ),
+            );
+        if let Some(session_token) = &self.credentials.session_token {
+            request.set("X-Amz-Security-Token", session_token);
+        }
+        let resp = request.send_string(&body);
+
+        match resp.status() {
+            200 => Ok(serde_json::from_str(&resp.into_string()?)?),
+            400 => {
+                let status_text = resp.status_text().to_string();
+                let body = resp.into_string()?;
+                let error: Result<ErrorResponse, _> = serde_json::from_str(&body);
+                match error {
+                    Ok(error) if error.error_type.ends_with("ResourceNotFoundException") => {
+                        Err(Error::NotFound(name.into()))
+                    }
+                    _ => Err(Error::HttpError(400, status_text, body)),
+                }
+            }
+            _ => Err(resp.into()),
+        }
+    }
+}
+
+/// The Signature Version 4 authorization header of a Secrets Manager request, made at `now`
+pub fn authorization(
+    credentials: &Credentials,
+    region: &str,
+    host: &str,
+    target: &str,
+    body: &str,
+    now: DateTime<Utc>,
+) -> String {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    // Headers must be in lexicographic order of their lowercase names
+    let mut headers = vec![
+        ("content-type", CONTENT_TYPE),
+        ("host", host),
+        ("x-amz-date", amz_date.as_str()),
+    ];
+    if let Some(session_token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", session_token.as_str()));
+    }
+    headers.push(("x-amz-target", target));
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "POST\n/\n\n{}\n{}\n{}",
+        canonical_headers,
+        signed_headers,
+        hex::encode(Sha256::digest(body.as_bytes()))
+    );
+    let scope = format!("{}/{}/{}/aws4_request", date, region, SERVICE);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        ALGORITHM,
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let key = signing_key(&credentials.secret_access_key, &date, region, SERVICE);
+    let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+        ALGORITHM, credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+/// Derive the key requests to `service` in `region` are signed with on `date`
+pub fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let key = hmac_sha256(
+        format!("AWS4{}", secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct ErrorResponse {
+    #[serde(rename = "__type")]
+    error_type: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetSecretValueResponse {
+    secret_string: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListSecretsResponse {
+    next_token: Option<String>,
+    secret_list: Vec<SecretListEntry>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct SecretListEntry {
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    // The example from the AWS documentation on deriving a signing key
+    #[test]
+    fn test_signing_key() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_authorization() {
+        let mut credentials = Credentials {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".into(),
+            session_token: None,
+        };
+        let now = Utc.ymd(2022, 8, 1).and_hms(12, 0, 0);
+        let host = "secretsmanager.us-east-1.amazonaws.com";
+        let target = "secretsmanager.GetSecretValue";
+        let sign = |credentials: &Credentials| {
+            authorization(credentials, "us-east-1", host, target, "{}", now)
+        };
+
+        let unsigned = sign(&credentials);
+        assert!(unsigned.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20220801/us-east-1/secretsmanager/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date;x-amz-target, Signature="
+        ));
+        assert_eq!(unsigned, sign(&credentials));
+
+        // The session token is signed as well
+        credentials.session_token = Some("token".into());
+        let signed = sign(&credentials);
+        assert!(signed.contains(
+            "SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target"
+        ));
+        assert_ne!(
+            unsigned.rsplit_once('=').unwrap().1,
+            signed.rsplit_once('=').unwrap().1
+        );
+    }
+}
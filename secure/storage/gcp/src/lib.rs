@@ -0,0 +1,298 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+const URL: &str = "https://secretmanager.googleapis.com/v1";
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Default request timeouts for Secret Manager operations
+const DEFAULT_CONNECTION_TIMEOUT_MS: u64 = 1_000;
+const DEFAULT_RESPONSE_TIMEOUT_MS: u64 = 5_000;
+
+/// Access tokens from the metadata server are refreshed this long before they expire
+const TOKEN_REFRESH_MARGIN_SECS: u64 = 60;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("Http error, status code: {0}, status text: {1}, body: {2}")]
+    HttpError(u16, String, String),
+    #[error("Internal error: {0}")]
+    InternalError(String),
+    #[error("404: Not Found: {0}")]
+    NotFound(String),
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+}
+
+impl From<base64::DecodeError> for Error {
+    fn from(error: base64::DecodeError) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(error: std::string::FromUtf8Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<ureq::Response> for Error {
+    fn from(resp: ureq::Response) -> Self {
+        if let Some(e) = resp.synthetic_error() {
+            // Local error
+            Error::InternalError(e.to_string())
+        } else {
+            let status = resp.status();
+            let status_text = resp.status_text().to_string();
+            match resp.into_string() {
+                Ok(body) => Error::HttpError(status, status_text, body),
+                Err(e) => Error::InternalError(e.to_string()),
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+/// Where the OAuth 2.0 access tokens requests are authorized with come from
+pub enum TokenSource {
+    /// A token obtained out of band, e.g. with `gcloud auth print-access-token`
+    Static(String),
+    /// The tokens of the service account attached to the instance or, with workload identity,
+    /// to the Kubernetes service account, which are refreshed before they expire
+    MetadataServer,
+}
+
+/// Client provides a client around the REST interface to GCP Secret Manager. Learn more
+/// here: <https://cloud.google.com/secret-manager/docs/reference/rest>
+///
+/// A secret is a container of versions, each holding a value. Secrets are created empty, values
+/// are written by adding versions, and reads return the latest version.
+pub struct Client {
+    agent: ureq::Agent,
+    project: String,
+    token_source: TokenSource,
+    /// The current access token from the metadata server, and when it should be refreshed
+    cached_token: Mutex<Option<(String, Instant)>>,
+
+    /// Timeout for new socket connections to Secret Manager.
+    connection_timeout_ms: u64,
+    /// Timeout for Secret Manager responses.
+    response_timeout_ms: u64,
+}
+
+impl Client {
+    pub fn new(
+        project: String,
+        token_source: TokenSource,
+        connection_timeout_ms: Option<u64>,
+        response_timeout_ms: Option<u64>,
+    ) -> Self {
+        Self {
+            agent: ureq::Agent::new().set("connection", "keep-alive").build(),
+            project,
+            token_source,
+            cached_token: Mutex::new(None),
+            connection_timeout_ms: connection_timeout_ms.unwrap_or(DEFAULT_CONNECTION_TIMEOUT_MS),
+            response_timeout_ms: response_timeout_ms.unwrap_or(DEFAULT_RESPONSE_TIMEOUT_MS),
+        }
+    }
+
+    /// Retrieve the value of the latest version of a secret
+    pub fn access_secret(&self, secret: &str) -> Result<String, Error> {
+        let url = format!("{}/versions/latest:access", self.secret_url(secret));
+        let resp = self.upgrade_request(self.agent.get(&url))?.call();
+
+        match resp.status() {
+            200 => {
+                let resp: AccessSecretVersionResponse = serde_json::from_str(&resp.into_string()?)?;
+                Ok(String::from_utf8(base64::decode(&resp.payload.data)?)?)
+            }
+            404 => {
+                // Explicitly clear buffer so the stream can be re-used.
+                resp.into_string()?;
+                Err(Error::NotFound(secret.into()))
+            }
+            _ => Err(resp.into()),
+        }
+    }
+
+    /// Store a value as the new latest version of an existing secret
+    pub fn add_secret_version(&self, secret: &str, value: &str) -> Result<(), Error> {
+        let url = format!("{}:addVersion", self.secret_url(secret));
+        let resp = self
+            .upgrade_request(self.agent.post(&url))?
+            .send_json(json!({ "payload": { "data": base64::encode(value) } }));
+
+        match resp.status() {
+            200 => {
+                resp.into_string()?;
+                Ok(())
+            }
+            404 => {
+                resp.into_string()?;
+                Err(Error::NotFound(secret.into()))
+            }
+            _ => Err(resp.into()),
+        }
+    }
+
+    /// Create a secret without any version, replicated in locations chosen by Google
+    pub fn create_secret(&self, secret: &str) -> Result<(), Error> {
+        let url = format!(
+            "{}/projects/{}/secrets?secretId={}",
+            URL, self.project, secret
+        );
+        let resp = self
+            .upgrade_request(self.agent.post(&url))?
+            .send_json(json!({ "replication": { "automatic": {} } }));
+
+        process_generic_response(resp)
+    }
+
+    /// Delete a secret and all of its versions
+    pub fn delete_secret(&self, secret: &str) -> Result<(), Error> {
+        let resp = self
+            .upgrade_request(self.agent.delete(&self.secret_url(secret)))?
+            .call();
+
+        process_generic_response(resp)
+    }
+
+    /// List the ids of all secrets in the project
+    pub fn list_secrets(&self) -> Result<Vec<String>, Error> {
+        let mut secrets = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut url = format!("{}/projects/{}/secrets", URL, self.project);
+            if let Some(page_token) = &page_token {
+                url = format!("{}?pageToken={}", url, page_token);
+            }
+            let resp = self.upgrade_request(self.agent.get(&url))?.call();
+            if !resp.ok() {
+                return Err(resp.into());
+            }
+            let resp: ListSecretsResponse = serde_json::from_str(&resp.into_string()?)?;
+            // Secret names are of the form projects/<project>/secrets/<secret>
+            secrets.extend(resp.secrets.into_iter().filter_map(|secret| {
+                secret
+                    .name
+                    .rsplit_once('/')
+                    .map(|(_, secret)| secret.to_string())
+            }));
+            page_token = resp.next_page_token;
+            if page_token.is_none() {
+                return Ok(secrets);
+            }
+        }
+    }
+
+    fn secret_url(&self, secret: &str) -> String {
+        format!("{}/projects/{}/secrets/{}", URL, self.project, secret)
+    }
+
+    /// Returns the current access token, refreshing it from the metadata server if it is about
+    /// to expire
+    fn token(&self) -> Result<String, Error> {
+        match &self.token_source {
+            TokenSource::Static(token) => Ok(token.clone()),
+            TokenSource::MetadataServer => {
+                let mut cached_token = self
+                    .cached_token
+                    .lock()
+                    .map_err(|e| Error::InternalError(e.to_string()))?;
+                if let Some((token, refresh_at)) = cached_token.as_ref() {
+                    if Instant::now() < *refresh_at {
+                        return Ok(token.clone());
+                    }
+                }
+
+                let mut request = self.agent.get(METADATA_TOKEN_URL);
+                request
+                    .set("Metadata-Flavor", "Google")
+                    .timeout_connect(self.connection_timeout_ms)
+                    .timeout(Duration::from_millis(self.response_timeout_ms));
+                let resp = request.call();
+                if !resp.ok() {
+                    return Err(resp.into());
+                }
+                let resp: MetadataTokenResponse = serde_json::from_str(&resp.into_string()?)?;
+                let refresh_at = Instant::now()
+                    + Duration::from_secs(
+                        resp.expires_in.saturating_sub(TOKEN_REFRESH_MARGIN_SECS),
+                    );
+                *cached_token = Some((resp.access_token.clone(), refresh_at));
+                Ok(resp.access_token)
+            }
+        }
+    }
+
+    /// Simple wrapper around requests to add default parameters to the request
+    fn upgrade_request(&self, mut request: ureq::Request) -> Result<ureq::Request, Error> {
+        request
+            .set("Authorization", &format!("Bearer {}", self.token()?))
+            .timeout_connect(self.connection_timeout_ms)
+            .timeout(Duration::from_millis(self.response_timeout_ms));
+        Ok(request)
+    }
+}
+
+/// Checks that the response was not an error and clears the ureq stream
+fn process_generic_response(resp: ureq::Response) -> Result<(), Error> {
+    if resp.ok() {
+        // Explicitly clear buffer so the stream can be re-used.
+        resp.into_string()?;
+        Ok(())
+    } else {
+        Err(resp.into())
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct AccessSecretVersionResponse {
+    payload: SecretPayload,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ListSecretsResponse {
+    #[serde(default)]
+    secrets: Vec<Secret>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct MetadataTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Secret {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct SecretPayload {
+    data: String,
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    key_policy::{self, KeyPolicy, SecretCache},
+    CryptoKVStorage, Error, GetResponse, KVStorage,
+};
+use aptos_aws_secrets_client::{Client, Credentials};
+use aptos_time_service::TimeService;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+
+/// A secret which is never written, read to check that Secrets Manager is reachable
+const AVAILABILITY_PROBE: &str = "aptos-secure-storage-probe";
+
+/// AwsSecretsManagerStorage stores each key as a secret in AWS Secrets Manager, whose latest
+/// version holds its value. Cryptographic keys are stored as private keys, as by CryptoKVStorage,
+/// so this is intended for a few long lived keys rather than for frequently updated data, each
+/// update being a new version of the secret.
+pub struct AwsSecretsManagerStorage {
+    client: Client,
+    cache: SecretCache,
+}
+
+impl AwsSecretsManagerStorage {
+    pub fn new(
+        region: String,
+        endpoint: Option<String>,
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: Option<String>,
+        key_policies: BTreeMap<String, KeyPolicy>,
+        connection_timeout_ms: Option<u64>,
+        response_timeout_ms: Option<u64>,
+    ) -> Self {
+        let credentials = Credentials {
+            access_key_id,
+            secret_access_key,
+            session_token,
+        };
+        Self {
+            client: Client::new(
+                region,
+                endpoint,
+                credentials,
+                connection_timeout_ms,
+                response_timeout_ms,
+            ),
+            cache: SecretCache::new(key_policies, TimeService::real()),
+        }
+    }
+
+    /// Rotates the keys which are due for rotation according to their policy, and returns their
+    /// names
+    pub fn rotate_expired_keys(&mut self) -> Result<Vec<String>, Error> {
+        let rotation_intervals = self.cache.rotation_intervals();
+        let now = self.cache.now_secs();
+        key_policy::rotate_expired_keys(self, rotation_intervals, now)
+    }
+}
+
+impl KVStorage for AwsSecretsManagerStorage {
+    fn available(&self) -> Result<(), Error> {
+        match self.client.get_secret_value(AVAILABILITY_PROBE) {
+            Ok(_) | Err(aptos_aws_secrets_client::Error::NotFound(_)) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn get<T: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<T>, Error> {
+        let data = match self.cache.get(key) {
+            Some(data) => data,
+            None => {
+                let data = self.client.get_secret_value(key)?;
+                self.cache.insert(key, data.clone());
+                data
+            }
+        };
+        serde_json::from_str(&data).map_err(|e| e.into())
+    }
+
+    fn set<T: Serialize>(&mut self, key: &str, value: T) -> Result<(), Error> {
+        let now = self.cache.now_secs();
+        let data = serde_json::to_string(&GetResponse::new(value, now))?;
+        self.cache.invalidate(key);
+        match self.client.put_secret_value(key, &data) {
+            Ok(()) => (),
+            Err(aptos_aws_secrets_client::Error::NotFound(_)) => {
+                self.client.create_secret(key, &data)?
+            }
+            Err(e) => return Err(e.into()),
+        }
+        self.cache.insert(key, data);
+        Ok(())
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.cache.clear();
+        for secret in self.client.list_secrets()? {
+            self.client.delete_secret(&secret)?;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoKVStorage for AwsSecretsManagerStorage {}
@@ -63,6 +63,26 @@ impl From<aptos_vault_client::Error> for Error {
     }
 }
 
+impl From<aptos_aws_secrets_client::Error> for Error {
+    fn from(error: aptos_aws_secrets_client::Error) -> Self {
+        match error {
+            aptos_aws_secrets_client::Error::NotFound(key) => Self::KeyNotSet(key),
+            aptos_aws_secrets_client::Error::HttpError(403, _, _) => Self::PermissionDenied,
+            _ => Self::InternalError(format!("{}", error)),
+        }
+    }
+}
+
+impl From<aptos_gcp_secrets_client::Error> for Error {
+    fn from(error: aptos_gcp_secrets_client::Error) -> Self {
+        match error {
+            aptos_gcp_secrets_client::Error::NotFound(key) => Self::KeyNotSet(key),
+            aptos_gcp_secrets_client::Error::HttpError(403, _, _) => Self::PermissionDenied,
+            _ => Self::InternalError(format!("{}", error)),
+        }
+    }
+}
+
 impl From<aptos_github_client::Error> for Error {
     fn from(error: aptos_github_client::Error) -> Self {
         match error {
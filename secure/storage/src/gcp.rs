@@ -0,0 +1,109 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    key_policy::{self, KeyPolicy, SecretCache},
+    namespaced::NAMESPACE_SEPARATOR,
+    CryptoKVStorage, Error, GetResponse, KVStorage,
+};
+use aptos_gcp_secrets_client::{Client, TokenSource};
+use aptos_time_service::TimeService;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+
+/// Secret ids may only contain letters, digits, dashes and underscores
+const SECRET_NAMESPACE_SEPARATOR: &str = "__";
+
+/// GcpSecretManagerStorage stores each key as a secret in GCP Secret Manager, whose latest
+/// version holds its value. Cryptographic keys are stored as private keys, as by CryptoKVStorage,
+/// so this is intended for a few long lived keys rather than for frequently updated data, each
+/// update being a new version of the secret.
+pub struct GcpSecretManagerStorage {
+    client: Client,
+    cache: SecretCache,
+}
+
+impl GcpSecretManagerStorage {
+    /// Creates a storage for the secrets of `project`, authorized with `token`, or with the
+    /// tokens of the instance's service account if no token is given
+    pub fn new(
+        project: String,
+        token: Option<String>,
+        key_policies: BTreeMap<String, KeyPolicy>,
+        connection_timeout_ms: Option<u64>,
+        response_timeout_ms: Option<u64>,
+    ) -> Self {
+        let token_source = match token {
+            Some(token) => TokenSource::Static(token),
+            None => TokenSource::MetadataServer,
+        };
+        Self {
+            client: Client::new(
+                project,
+                token_source,
+                connection_timeout_ms,
+                response_timeout_ms,
+            ),
+            cache: SecretCache::new(key_policies, TimeService::real()),
+        }
+    }
+
+    /// Rotates the keys which are due for rotation according to their policy, and returns their
+    /// names
+    pub fn rotate_expired_keys(&mut self) -> Result<Vec<String>, Error> {
+        let rotation_intervals = self.cache.rotation_intervals();
+        let now = self.cache.now_secs();
+        key_policy::rotate_expired_keys(self, rotation_intervals, now)
+    }
+
+    fn secret_name(&self, key: &str) -> String {
+        key.replace(NAMESPACE_SEPARATOR, SECRET_NAMESPACE_SEPARATOR)
+    }
+}
+
+impl KVStorage for GcpSecretManagerStorage {
+    fn available(&self) -> Result<(), Error> {
+        self.client.list_secrets()?;
+        Ok(())
+    }
+
+    fn get<T: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<T>, Error> {
+        let data = match self.cache.get(key) {
+            Some(data) => data,
+            None => {
+                let data = self.client.access_secret(&self.secret_name(key))?;
+                self.cache.insert(key, data.clone());
+                data
+            }
+        };
+        serde_json::from_str(&data).map_err(|e| e.into())
+    }
+
+    fn set<T: Serialize>(&mut self, key: &str, value: T) -> Result<(), Error> {
+        let secret = self.secret_name(key);
+        let now = self.cache.now_secs();
+        let data = serde_json::to_string(&GetResponse::new(value, now))?;
+        self.cache.invalidate(key);
+        match self.client.add_secret_version(&secret, &data) {
+            Ok(()) => (),
+            Err(aptos_gcp_secrets_client::Error::NotFound(_)) => {
+                self.client.create_secret(&secret)?;
+                self.client.add_secret_version(&secret, &data)?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        self.cache.insert(key, data);
+        Ok(())
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.cache.clear();
+        for secret in self.client.list_secrets()? {
+            self.client.delete_secret(&secret)?;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoKVStorage for GcpSecretManagerStorage {}
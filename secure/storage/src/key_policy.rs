@@ -0,0 +1,107 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoStorage, Error};
+use aptos_infallible::RwLock;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// How a key held in a remote secrets manager is cached and rotated. Policies are configured per
+/// key, by its full name in the storage, including any namespace.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyPolicy {
+    /// How long a value read from the secrets manager is served from memory before being read
+    /// again. Values are not cached unless this is specified.
+    pub cache_ttl_secs: Option<u64>,
+    /// How long after its last update a key is rotated by `rotate_expired_keys`. Keys are only
+    /// rotated on demand unless this is specified.
+    pub rotation_interval_secs: Option<u64>,
+}
+
+/// A cache of the serialized values of keys, for the duration set by their policy
+pub(crate) struct SecretCache {
+    policies: BTreeMap<String, KeyPolicy>,
+    entries: RwLock<HashMap<String, (u64, String)>>,
+    time_service: TimeService,
+}
+
+impl SecretCache {
+    pub fn new(policies: BTreeMap<String, KeyPolicy>, time_service: TimeService) -> Self {
+        Self {
+            policies,
+            entries: RwLock::new(HashMap::new()),
+            time_service,
+        }
+    }
+
+    pub fn now_secs(&self) -> u64 {
+        self.time_service.now_secs()
+    }
+
+    /// Returns the cached value of `key`, if it has not expired
+    pub fn get(&self, key: &str) -> Option<String> {
+        let now = self.now_secs();
+        match self.entries.read().get(key) {
+            Some((expiration, value)) if now < *expiration => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Caches the value of `key`, if its policy allows it
+    pub fn insert(&self, key: &str, value: String) {
+        if let Some(ttl) = self
+            .policies
+            .get(key)
+            .and_then(|policy| policy.cache_ttl_secs)
+        {
+            let expiration = self.now_secs().saturating_add(ttl);
+            self.entries
+                .write()
+                .insert(key.to_string(), (expiration, value));
+        }
+    }
+
+    pub fn invalidate(&self, key: &str) {
+        self.entries.write().remove(key);
+    }
+
+    pub fn clear(&self) {
+        self.entries.write().clear();
+    }
+
+    /// The keys which have a rotation interval, with their interval
+    pub fn rotation_intervals(&self) -> Vec<(String, u64)> {
+        self.policies
+            .iter()
+            .filter_map(|(key, policy)| {
+                policy
+                    .rotation_interval_secs
+                    .map(|interval| (key.clone(), interval))
+            })
+            .collect()
+    }
+}
+
+/// Rotates each key whose last update is at least its rotation interval before `now_secs`, and
+/// returns their names. Keys which are not set are skipped.
+pub(crate) fn rotate_expired_keys<S: CryptoStorage>(
+    storage: &mut S,
+    rotation_intervals: Vec<(String, u64)>,
+    now_secs: u64,
+) -> Result<Vec<String>, Error> {
+    let mut rotated = Vec::new();
+    for (name, interval) in rotation_intervals {
+        let last_update = match storage.get_public_key(&name) {
+            Ok(response) => response.last_update,
+            Err(Error::KeyNotSet(_)) => continue,
+            Err(e) => return Err(e),
+        };
+        if now_secs.saturating_sub(last_update) >= interval {
+            storage.rotate_key(&name)?;
+            rotated.push(name);
+        }
+    }
+    Ok(rotated)
+}
@@ -3,11 +3,14 @@
 
 #![forbid(unsafe_code)]
 
+mod aws;
 mod crypto_kv_storage;
 mod crypto_storage;
 mod error;
+mod gcp;
 mod github;
 mod in_memory;
+mod key_policy;
 mod kv_storage;
 mod namespaced;
 mod on_disk;
@@ -17,11 +20,14 @@ mod storage;
 mod vault;
 
 pub use crate::{
+    aws::AwsSecretsManagerStorage,
     crypto_kv_storage::CryptoKVStorage,
     crypto_storage::{CryptoStorage, PublicKeyResponse},
     error::Error,
+    gcp::GcpSecretManagerStorage,
     github::GitHubStorage,
     in_memory::InMemoryStorage,
+    key_policy::KeyPolicy,
     kv_storage::{GetResponse, KVStorage},
     namespaced::Namespaced,
     on_disk::OnDiskStorage,
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::rocks_db::RocksDbStorage;
 use crate::{
-    CryptoStorage, Error, GetResponse, GitHubStorage, InMemoryStorage, KVStorage, Namespaced,
-    OnDiskStorage, PublicKeyResponse, VaultStorage,
+    AwsSecretsManagerStorage, CryptoStorage, Error, GcpSecretManagerStorage, GetResponse,
+    GitHubStorage, InMemoryStorage, KVStorage, Namespaced, OnDiskStorage, PublicKeyResponse,
+    VaultStorage,
 };
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
 use enum_dispatch::enum_dispatch;
@@ -17,6 +18,8 @@ use serde::{de::DeserializeOwned, Serialize};
 /// T: Storage. This boilerplate can be 100% generated by a proc macro.
 #[enum_dispatch(KVStorage, CryptoStorage)]
 pub enum Storage {
+    AwsSecretsManagerStorage(AwsSecretsManagerStorage),
+    GcpSecretManagerStorage(GcpSecretManagerStorage),
     GitHubStorage(GitHubStorage),
     VaultStorage(VaultStorage),
     InMemoryStorage(InMemoryStorage),
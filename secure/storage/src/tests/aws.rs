@@ -0,0 +1,27 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, AwsSecretsManagerStorage, Storage};
+
+const REGION: &str = "REGION";
+const ACCESS_KEY_ID: &str = "ACCESS_KEY_ID";
+const SECRET_ACCESS_KEY: &str = "SECRET_ACCESS_KEY";
+
+// These tests must be run in series via: `cargo xtest -- --ignored --test-threads=1`
+// Also the constants above must be defined with proper values -- never commit these values to the
+// repository. The tests delete every secret the credentials can list, so use a dedicated account.
+#[ignore]
+#[test]
+fn aws_secrets_manager_storage() {
+    let mut storage = Storage::from(AwsSecretsManagerStorage::new(
+        REGION.into(),
+        None,
+        ACCESS_KEY_ID.into(),
+        SECRET_ACCESS_KEY.into(),
+        None,
+        Default::default(),
+        None,
+        None,
+    ));
+    suite::execute_all_storage_tests(&mut storage);
+}
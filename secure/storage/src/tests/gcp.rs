@@ -0,0 +1,23 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, GcpSecretManagerStorage, Storage};
+
+const PROJECT: &str = "PROJECT";
+const TOKEN: &str = "TOKEN";
+
+// These tests must be run in series via: `cargo xtest -- --ignored --test-threads=1`
+// Also the constants above must be defined with proper values -- never commit these values to the
+// repository. The tests delete every secret of the project, so use a dedicated project.
+#[ignore]
+#[test]
+fn gcp_secret_manager_storage() {
+    let mut storage = Storage::from(GcpSecretManagerStorage::new(
+        PROJECT.into(),
+        Some(TOKEN.into()),
+        Default::default(),
+        None,
+        None,
+    ));
+    suite::execute_all_storage_tests(&mut storage);
+}
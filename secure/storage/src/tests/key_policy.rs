@@ -0,0 +1,80 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    key_policy::{rotate_expired_keys, SecretCache},
+    CryptoStorage, InMemoryStorage, KeyPolicy,
+};
+use aptos_time_service::TimeService;
+use std::collections::BTreeMap;
+
+const CACHED: &str = "cached";
+const UNCACHED: &str = "uncached";
+
+fn policies() -> BTreeMap<String, KeyPolicy> {
+    let mut policies = BTreeMap::new();
+    policies.insert(
+        CACHED.to_string(),
+        KeyPolicy {
+            cache_ttl_secs: Some(10),
+            rotation_interval_secs: Some(100),
+        },
+    );
+    policies
+}
+
+#[test]
+fn test_secret_cache() {
+    let time_service = TimeService::mock();
+    let cache = SecretCache::new(policies(), time_service.clone());
+
+    // Only keys with a cache policy are cached
+    cache.insert(CACHED, "value".into());
+    cache.insert(UNCACHED, "value".into());
+    assert_eq!(cache.get(CACHED), Some("value".into()));
+    assert_eq!(cache.get(UNCACHED), None);
+
+    // Values expire after their TTL
+    time_service.clone().into_mock().advance_secs(9);
+    assert_eq!(cache.get(CACHED), Some("value".into()));
+    time_service.clone().into_mock().advance_secs(1);
+    assert_eq!(cache.get(CACHED), None);
+
+    // Values can be invalidated before they expire
+    cache.insert(CACHED, "value".into());
+    cache.invalidate(CACHED);
+    assert_eq!(cache.get(CACHED), None);
+}
+
+#[test]
+fn test_rotate_expired_keys() {
+    let time_service = TimeService::mock();
+    let cache = SecretCache::new(policies(), time_service.clone());
+    let mut storage = InMemoryStorage::new_with_time_service(time_service.clone());
+    assert_eq!(cache.rotation_intervals(), vec![(CACHED.to_string(), 100)]);
+
+    // Keys which are not set are skipped
+    let rotated =
+        rotate_expired_keys(&mut storage, cache.rotation_intervals(), cache.now_secs()).unwrap();
+    assert!(rotated.is_empty());
+
+    let public_key = storage.create_key(CACHED).unwrap();
+    storage.create_key(UNCACHED).unwrap();
+    time_service.clone().into_mock().advance_secs(99);
+    let rotated =
+        rotate_expired_keys(&mut storage, cache.rotation_intervals(), cache.now_secs()).unwrap();
+    assert!(rotated.is_empty());
+
+    time_service.into_mock().advance_secs(1);
+    let rotated =
+        rotate_expired_keys(&mut storage, cache.rotation_intervals(), cache.now_secs()).unwrap();
+    assert_eq!(rotated, vec![CACHED.to_string()]);
+    assert_ne!(
+        storage.get_public_key(CACHED).unwrap().public_key,
+        public_key
+    );
+    assert_eq!(
+        storage.get_public_key_previous_version(CACHED).unwrap(),
+        public_key
+    );
+}
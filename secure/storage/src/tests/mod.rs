@@ -1,8 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod aws;
+mod gcp;
 mod github;
 mod in_memory;
+mod key_policy;
 mod on_disk;
 mod rocks_db;
 mod suite;
@@ -98,6 +98,7 @@ fn create_vault_storage(token: String, renew_ttl_secs: Option<u32>, use_cas: boo
         dev::test_host(),
         token,
         None,
+        None,
         renew_ttl_secs,
         use_cas,
         None,
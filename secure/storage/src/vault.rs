@@ -24,6 +24,9 @@ use aptos_vault_client::ReadResponse;
 
 const TRANSIT_NAMESPACE_SEPARATOR: &str = "__";
 
+/// How long to wait before retrying a failed token renewal
+const RENEWAL_RETRY_SECS: u64 = 5;
+
 /// VaultStorage utilizes Vault for maintaining encrypted, authenticated data. This
 /// version currently matches the behavior of OnDiskStorage and InMemoryStorage. In the future,
 /// Vault will be able to create keys, sign messages, and handle permissions across different
@@ -44,6 +47,7 @@ impl VaultStorage {
     pub fn new(
         host: String,
         token: String,
+        vault_namespace: Option<String>,
         certificate: Option<String>,
         renew_ttl_secs: Option<u32>,
         use_cas: bool,
@@ -54,6 +58,7 @@ impl VaultStorage {
             client: Client::new(
                 host,
                 token,
+                vault_namespace,
                 certificate,
                 connection_timeout_ms,
                 response_timeout_ms,
@@ -71,13 +76,30 @@ impl VaultStorage {
         if self.renew_ttl_secs.is_some() {
             let now = self.time_service.now_secs();
             let next_renewal = self.next_renewal.load(Ordering::Relaxed);
-            if now >= next_renewal {
-                let result = self.client.renew_token_self(self.renew_ttl_secs);
-                if let Ok(ttl) = result {
-                    let next_renewal = now + (ttl as u64) / 2;
-                    self.next_renewal.store(next_renewal, Ordering::Relaxed);
-                } else if let Err(e) = result {
-                    aptos_logger::error!("Unable to renew lease: {}", e.to_string());
+            // Only the caller which pushes back the next renewal renews the token, the others
+            // keep using it in the meantime. A failed renewal is retried after a short delay.
+            if now >= next_renewal
+                && self
+                    .next_renewal
+                    .compare_exchange(
+                        next_renewal,
+                        now + RENEWAL_RETRY_SECS,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                match self.client.renew_token_self(self.renew_ttl_secs) {
+                    // Tokens without a TTL, such as root tokens, never expire
+                    Ok(0) => self.next_renewal.store(u64::MAX, Ordering::Relaxed),
+                    Ok(ttl) => self
+                        .next_renewal
+                        .store(now + (ttl as u64) / 2, Ordering::Relaxed),
+                    Err(e) => aptos_logger::error!(
+                        "Unable to renew lease, retrying in {}s: {}",
+                        RENEWAL_RETRY_SECS,
+                        e.to_string()
+                    ),
                 }
             }
         }
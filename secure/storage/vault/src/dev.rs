@@ -125,6 +125,7 @@ impl VaultRunner {
             None,
             None,
             None,
+            None,
         )
     }
 }
@@ -147,7 +148,7 @@ fn run_vault() {
 #[test]
 fn run_test_vault() {
     if let Some(host) = test_host_safe() {
-        Client::new(host, ROOT_TOKEN.to_string(), None, None, None)
+        Client::new(host, ROOT_TOKEN.to_string(), None, None, None, None)
             .unsealed()
             .unwrap();
     }
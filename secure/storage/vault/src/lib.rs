@@ -114,6 +114,8 @@ pub struct Client {
     agent: ureq::Agent,
     host: String,
     token: String,
+    /// The Vault Enterprise namespace all requests are made in, if any
+    namespace: Option<String>,
     tls_connector: Arc<native_tls::TlsConnector>,
 
     /// Timeout for new socket connections to vault.
@@ -126,6 +128,7 @@ impl Client {
     pub fn new(
         host: String,
         token: String,
+        namespace: Option<String>,
         ca_certificate: Option<String>,
         connection_timeout_ms: Option<u64>,
         response_timeout_ms: Option<u64>,
@@ -149,6 +152,7 @@ impl Client {
             agent: ureq::Agent::new().set("connection", "keep-alive").build(),
             host,
             token,
+            namespace,
             tls_connector,
             connection_timeout_ms,
             response_timeout_ms,
@@ -481,6 +485,11 @@ impl Client {
     fn upgrade_request(&self, request: ureq::Request) -> ureq::Request {
         let mut request = self.upgrade_request_without_token(request);
         request.set("X-Vault-Token", &self.token);
+        // The seal status is only served from the root namespace, so unauthenticated requests
+        // are not namespaced
+        if let Some(namespace) = &self.namespace {
+            request.set("X-Vault-Namespace", namespace);
+        }
         request
     }
 
@@ -3,7 +3,10 @@
 
 #![forbid(unsafe_code)]
 
-use aptos_types::{account_address::AccountAddress, transaction::Transaction};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ReplayProtector, Transaction},
+};
 use async_trait::async_trait;
 use futures::{
     channel::{mpsc, oneshot},
@@ -87,7 +90,7 @@ impl MempoolNotificationSender for MempoolNotifier {
             .filter_map(|transaction| match transaction {
                 Transaction::UserTransaction(signed_txn) => Some(CommittedTransaction {
                     sender: signed_txn.sender(),
-                    sequence_number: signed_txn.sequence_number(),
+                    replay_protector: signed_txn.replay_protector(),
                 }),
                 _ => None,
             })
@@ -193,12 +196,12 @@ impl fmt::Display for MempoolCommitNotification {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CommittedTransaction {
     pub sender: AccountAddress,
-    pub sequence_number: u64,
+    pub replay_protector: ReplayProtector,
 }
 
 impl fmt::Display for CommittedTransaction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.sender, self.sequence_number,)
+        write!(f, "{}:{}", self.sender, self.replay_protector,)
     }
 }
 
@@ -307,7 +310,7 @@ mod tests {
                         mempool_commit_notification.transactions,
                         vec![CommittedTransaction {
                             sender: signed_transaction.sender(),
-                            sequence_number: signed_transaction.sequence_number(),
+                            replay_protector: signed_transaction.replay_protector(),
                         }]
                     );
                     assert_eq!(
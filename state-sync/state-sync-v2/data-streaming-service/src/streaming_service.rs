@@ -13,6 +13,7 @@ use crate::{
 use aptos_config::config::DataStreamingServiceConfig;
 use aptos_data_client::{AptosDataClient, GlobalDataSummary, OptimalChunkSizes};
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
+use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
 use futures::StreamExt;
 use std::{collections::HashMap, sync::Arc, time::Duration};
@@ -24,11 +25,32 @@ const GLOBAL_DATA_REFRESH_LOG_FREQ_SECS: u64 = 3;
 const NO_DATA_TO_FETCH_LOG_FREQ_SECS: u64 = 3;
 const STREAM_REQUEST_ERROR_LOG_FREQ_SECS: u64 = 3;
 
+/// A handle for changing how many requests the data streams send concurrently, while the
+/// streaming service runs. Changes apply to the streams created afterwards.
+#[derive(Clone)]
+pub struct StreamConcurrencyHandle {
+    // The max concurrent requests and max concurrent state requests
+    limits: Arc<RwLock<(u64, u64)>>,
+}
+
+impl StreamConcurrencyHandle {
+    pub fn update_concurrency(
+        &self,
+        max_concurrent_requests: u64,
+        max_concurrent_state_requests: u64,
+    ) {
+        *self.limits.write() = (max_concurrent_requests, max_concurrent_state_requests);
+    }
+}
+
 /// The data streaming service that responds to data stream requests.
 pub struct DataStreamingService<T> {
     // The configuration for this streaming service.
     config: DataStreamingServiceConfig,
 
+    // The request concurrency of new streams, which may change while the service runs
+    concurrency_handle: StreamConcurrencyHandle,
+
     // The data client through which to fetch data from the Aptos network
     aptos_data_client: T,
 
@@ -52,8 +74,15 @@ impl<T: AptosDataClient + Send + Clone + 'static> DataStreamingService<T> {
         aptos_data_client: T,
         stream_requests: StreamingServiceListener,
     ) -> Self {
+        let concurrency_handle = StreamConcurrencyHandle {
+            limits: Arc::new(RwLock::new((
+                config.max_concurrent_requests,
+                config.max_concurrent_state_requests,
+            ))),
+        };
         Self {
             config,
+            concurrency_handle,
             aptos_data_client,
             global_data_summary: GlobalDataSummary::empty(),
             data_streams: HashMap::new(),
@@ -63,6 +92,11 @@ impl<T: AptosDataClient + Send + Clone + 'static> DataStreamingService<T> {
         }
     }
 
+    /// Returns a handle for changing the request concurrency of new streams
+    pub fn concurrency_handle(&self) -> StreamConcurrencyHandle {
+        self.concurrency_handle.clone()
+    }
+
     /// Starts the dedicated streaming service
     pub async fn start_service(mut self) {
         let mut data_refresh_interval = IntervalStream::new(interval(Duration::from_millis(
@@ -177,10 +211,15 @@ impl<T: AptosDataClient + Send + Clone + 'static> DataStreamingService<T> {
         // Refresh the cached global data summary
         self.refresh_global_data_summary();
 
-        // Create a new data stream
+        // Create a new data stream, with the current request concurrency
+        let mut config = self.config;
+        let (max_concurrent_requests, max_concurrent_state_requests) =
+            *self.concurrency_handle.limits.read();
+        config.max_concurrent_requests = max_concurrent_requests;
+        config.max_concurrent_state_requests = max_concurrent_state_requests;
         let stream_id = self.stream_id_generator.next();
         let (data_stream, stream_listener) = DataStream::new(
-            self.config,
+            config,
             stream_id,
             &request_message.stream_request,
             self.aptos_data_client.clone(),
@@ -23,9 +23,9 @@ use aptos_types::{
     },
     state_store::state_value::StateValueChunkWithProof,
     transaction::{
-        ExecutionStatus, RawTransaction, Script, SignedTransaction, Transaction, TransactionInfo,
-        TransactionListWithProof, TransactionOutput, TransactionOutputListWithProof,
-        TransactionPayload, TransactionStatus, Version,
+        ExecutionStatus, RawTransaction, ReplayProtector, Script, SignedTransaction, Transaction,
+        TransactionInfo, TransactionListWithProof, TransactionOutput,
+        TransactionOutputListWithProof, TransactionPayload, TransactionStatus, Version,
     },
     waypoint::Waypoint,
     write_set::WriteSet,
@@ -229,7 +229,7 @@ pub async fn verify_mempool_and_event_notification(
         .into_iter()
         .map(|txn| CommittedTransaction {
             sender: txn.as_signed_user_txn().unwrap().sender(),
-            sequence_number: 0,
+            replay_protector: ReplayProtector::SequenceNumber(0),
         })
         .collect();
     assert_eq!(mempool_notification.transactions, committed_transactions);
@@ -54,7 +54,8 @@ impl RestoreHandler {
         version: Version,
         expected_root_hash: HashValue,
     ) -> Result<StateSnapshotRestore<StateKey, StateValue>> {
-        StateSnapshotRestore::new_overwrite(
+        // Resumes the restore if a previous attempt at the same version was interrupted.
+        StateSnapshotRestore::new(
             &self.state_store.state_merkle_db,
             &self.state_store,
             version,
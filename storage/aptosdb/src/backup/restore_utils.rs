@@ -191,6 +191,11 @@ pub fn save_transactions_impl(
     for (idx, txn) in txns.iter().enumerate() {
         transaction_store.put_transaction(first_version + idx as Version, txn, change_set)?;
     }
+    transaction_store.put_transaction_auxiliary_data(
+        first_version,
+        txns.iter().zip(events.iter().map(Vec::as_slice)),
+        change_set,
+    )?;
     ledger_store.put_transaction_infos(first_version, txn_infos, change_set)?;
     event_store.put_events_multiple_versions(first_version, events, change_set)?;
 
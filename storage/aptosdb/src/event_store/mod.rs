@@ -12,7 +12,8 @@ use crate::{
     ledger_counters::{LedgerCounter, LedgerCounterBumps},
     schema::{
         event::EventSchema, event_accumulator::EventAccumulatorSchema,
-        event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
+        event_by_key::EventByKeySchema, event_by_type::EventByTypeSchema,
+        event_by_version::EventByVersionSchema,
     },
 };
 use accumulator::{HashReader, MerkleAccumulator};
@@ -29,6 +30,7 @@ use aptos_types::{
     proof::position::Position,
     transaction::Version,
 };
+use move_deps::move_core_types::language_storage::TypeTag;
 use schemadb::{schema::ValueCodec, ReadOptions, SchemaBatch, SchemaIterator, DB};
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
@@ -207,6 +209,37 @@ impl EventStore {
         Ok((version, index))
     }
 
+    /// Given `type_tag`, returns the events of that type emitted by transactions with a version in
+    /// [`start_version`, `end_version`), identified by transaction version and index among all
+    /// events emitted by the same transaction. Result is in ascending order.
+    pub fn lookup_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        end_version: Version,
+        limit: u64,
+    ) -> Result<
+        Vec<(
+            Version, // transaction version it belongs to
+            u64,     // index among events for the same transaction
+        )>,
+    > {
+        let type_tag_hash = event_type_tag_hash(type_tag)?;
+        let mut iter = self.db.iter::<EventByTypeSchema>(ReadOptions::default())?;
+        iter.seek(&(type_tag_hash, start_version, 0))?;
+
+        let mut result = Vec::new();
+        for res in iter.take(limit as usize) {
+            let ((hash, ver, idx), ()) = res?;
+            if hash != type_tag_hash || ver >= end_version {
+                break;
+            }
+            result.push((ver, idx));
+        }
+
+        Ok(result)
+    }
+
     pub fn lookup_event_before_or_at_version(
         &self,
         event_key: &EventKey,
@@ -326,6 +359,10 @@ impl EventStore {
                     &(*event.key(), version, event.sequence_number()),
                     &(idx as u64),
                 )?;
+                cs.batch.put::<EventByTypeSchema>(
+                    &(event_type_tag_hash(event.type_tag())?, version, idx as u64),
+                    &(),
+                )?;
                 Ok(())
             })?;
 
@@ -436,6 +473,31 @@ impl EventStore {
             .ok_or_else(|| format_err!("A block with non-zero seq num started at version 0."))
     }
 
+    /// Gets the version of the first transaction of the first block committed at or after
+    /// timestamp, if such a block exists.
+    pub(crate) fn get_first_version_at_or_after_timestamp(
+        &self,
+        timestamp: u64,
+        ledger_version: Version,
+    ) -> Result<Option<Version>> {
+        let event_key = new_block_event_key();
+        let seq_at_or_after_ts = self.search_for_event_lower_bound(
+            &event_key,
+            |event| {
+                let new_block_event: NewBlockEvent = event.try_into()?;
+                Ok(new_block_event.proposed_time() < timestamp)
+            },
+            ledger_version,
+        )?;
+
+        seq_at_or_after_ts
+            .map(|seq| {
+                let (version, _idx) = self.lookup_event_by_key(&event_key, seq, ledger_version)?;
+                Ok(version)
+            })
+            .transpose()
+    }
+
     /// Prunes the events by key store for a set of events
     pub fn prune_events_by_key(
         &self,
@@ -499,6 +561,11 @@ impl EventStore {
                     event.sequence_number(),
                 ))?;
                 db_batch.delete::<EventByKeySchema>(&(*event.key(), event.sequence_number()))?;
+                db_batch.delete::<EventByTypeSchema>(&(
+                    event_type_tag_hash(event.type_tag())?,
+                    current_version as u64,
+                    current_index as u64,
+                ))?;
                 db_batch.delete::<EventSchema>(&(current_version as u64, current_index as u64))?;
             }
             current_version += 1;
@@ -532,6 +599,11 @@ impl EventStore {
     }
 }
 
+/// The hash of an event type under which `EventByTypeSchema` indexes the events of that type.
+fn event_type_tag_hash(type_tag: &TypeTag) -> Result<HashValue> {
+    Ok(HashValue::sha3_256_of(&bcs::to_bytes(type_tag)?))
+}
+
 type Accumulator<'a> = MerkleAccumulator<EventHashReader<'a>, EventAccumulatorHasher>;
 
 struct EventHashReader<'a> {
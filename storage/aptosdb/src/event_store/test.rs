@@ -112,6 +112,53 @@ proptest! {
         let events_102 = store.get_events_by_version(102 /*version*/).unwrap();
         prop_assert_eq!(events_102.len(), 0);
     }
+
+    #[test]
+    fn test_lookup_events_by_type(
+        events1 in vec(any::<ContractEvent>().no_shrink(), 1..100),
+        events2 in vec(any::<ContractEvent>().no_shrink(), 1..100),
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let store = &db.event_store;
+        save(store, 99 /*version*/, &events1);
+        save(store, 100 /*version*/, &events2);
+
+        for event in events1.iter().chain(events2.iter()) {
+            let type_tag = event.type_tag();
+            let expected_99: Vec<_> = events1
+                .iter()
+                .enumerate()
+                .filter(|(_idx, e)| e.type_tag() == type_tag)
+                .map(|(idx, _e)| (99, idx as u64))
+                .collect();
+            let expected_100: Vec<_> = events2
+                .iter()
+                .enumerate()
+                .filter(|(_idx, e)| e.type_tag() == type_tag)
+                .map(|(idx, _e)| (100, idx as u64))
+                .collect();
+
+            let expected: Vec<_> = expected_99.iter().chain(expected_100.iter()).cloned().collect();
+
+            prop_assert_eq!(
+                store.lookup_events_by_type(type_tag, 0, Version::MAX, 1000).unwrap(),
+                expected.clone()
+            );
+            prop_assert_eq!(
+                store.lookup_events_by_type(type_tag, 0, 100, 1000).unwrap(),
+                expected_99
+            );
+            prop_assert_eq!(
+                store.lookup_events_by_type(type_tag, 100, 101, 1000).unwrap(),
+                expected_100
+            );
+            prop_assert_eq!(
+                store.lookup_events_by_type(type_tag, 0, Version::MAX, 1).unwrap(),
+                expected[..1].to_vec()
+            );
+        }
+    }
 }
 
 fn traverse_events_by_key(
@@ -346,6 +393,15 @@ fn test_get_last_version_before_timestamp_impl(new_block_events: Vec<(Version, C
                 .unwrap(),
             version - 1,
         );
+        assert_eq!(
+            store
+                .get_first_version_at_or_after_timestamp(
+                    (last_block_ts + ts + 1) / 2,
+                    ledger_version
+                )
+                .unwrap(),
+            Some(*version),
+        );
 
         last_block_version = *version;
         last_block_ts = ts;
@@ -355,6 +411,12 @@ fn test_get_last_version_before_timestamp_impl(new_block_events: Vec<(Version, C
     assert!(store
         .get_last_version_before_timestamp(last_block_ts + 1, ledger_version)
         .is_err());
+    assert_eq!(
+        store
+            .get_first_version_at_or_after_timestamp(last_block_ts + 1, ledger_version)
+            .unwrap(),
+        None,
+    );
 }
 
 proptest! {
@@ -53,7 +53,7 @@ use crate::{
     },
     pruner::{pruner_manager::PrunerManager, utils},
     schema::*,
-    state_store::StateStore,
+    state_store::{hot_state_cache::HotStateCache, StateStore},
     system_store::SystemStore,
     transaction_store::TransactionStore,
 };
@@ -61,7 +61,8 @@ use anyhow::{bail, ensure, Result};
 #[cfg(any(test, feature = "fuzzing"))]
 use aptos_config::config::DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD;
 use aptos_config::config::{
-    PrunerConfig, RocksdbConfig, RocksdbConfigs, NO_OP_STORAGE_PRUNER_CONFIG, TARGET_SNAPSHOT_SIZE,
+    HotStateCacheConfig, PrunerConfig, RocksdbConfig, RocksdbConfigs, NO_OP_STORAGE_PRUNER_CONFIG,
+    TARGET_SNAPSHOT_SIZE,
 };
 
 use aptos_crypto::hash::HashValue;
@@ -71,7 +72,7 @@ use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_state_view::state_storage_usage::StateStorageUsage;
 use aptos_types::{
     account_address::AccountAddress,
-    account_config::{new_block_event_key, NewBlockEvent},
+    account_config::{new_block_event_key, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
@@ -89,17 +90,20 @@ use aptos_types::{
         table::{TableHandle, TableInfo},
     },
     transaction::{
-        AccountTransactionsWithProof, Transaction, TransactionInfo, TransactionListWithProof,
-        TransactionOutput, TransactionOutputListWithProof, TransactionToCommit,
-        TransactionWithProof, Version,
+        AccountActivity, AccountTransactionsWithProof, Transaction, TransactionAuxiliaryData,
+        TransactionInfo, TransactionListWithProof, TransactionOutput,
+        TransactionOutputListWithProof, TransactionToCommit, TransactionWithProof, Version,
     },
-    write_set::WriteSet,
+    write_set::{WriteOp, WriteSet},
 };
 use aptos_vm::data_cache::AsMoveResolver;
-use aptosdb_indexer::Indexer;
+use aptosdb_indexer::{table_info::TableInfoDb, Indexer, TransactionToIndex};
+use fail::fail_point;
 use itertools::zip_eq;
-use move_deps::move_resource_viewer::MoveValueAnnotator;
-use once_cell::sync::Lazy;
+use move_deps::{
+    move_core_types::language_storage::TypeTag, move_resource_viewer::MoveValueAnnotator,
+};
+use once_cell::sync::{Lazy, OnceCell};
 use schemadb::DB;
 use std::{
     collections::HashMap,
@@ -128,6 +132,9 @@ const MAX_LIMIT: u64 = 5000;
 // TODO: Either implement an iteration API to allow a very old client to loop through a long history
 // or guarantee that there is always a recent enough waypoint and client knows to boot from there.
 const MAX_NUM_EPOCH_ENDING_LEDGER_INFO: usize = 100;
+// A persisted hot state cache further behind is dropped rather than caught up with, which would
+// take longer than warming it up again.
+const MAX_HOT_STATE_VERSIONS_TO_CATCH_UP: u64 = 100_000;
 static ROCKSDB_PROPERTY_MAP: Lazy<HashMap<&str, String>> = Lazy::new(|| {
     [
         "rocksdb.num-immutable-mem-table",
@@ -273,6 +280,7 @@ pub struct AptosDB {
     _rocksdb_property_reporter: RocksdbPropertyReporter,
     ledger_commit_lock: std::sync::Mutex<()>,
     indexer: Option<Indexer>,
+    table_info_db: OnceCell<Arc<TableInfoDb>>,
 }
 
 impl AptosDB {
@@ -319,6 +327,7 @@ impl AptosDB {
             ),
             ledger_commit_lock: std::sync::Mutex::new(()),
             indexer: None,
+            table_info_db: OnceCell::new(),
         }
     }
 
@@ -420,11 +429,34 @@ impl AptosDB {
             while next_version < ledger_next_version {
                 info!(next_version = next_version, "AptosDB Indexer catching up. ",);
                 let end_version = std::cmp::min(ledger_next_version, next_version + BATCH_SIZE);
+                let num_txns = (end_version - next_version) as usize;
+                let txns = self
+                    .transaction_store
+                    .get_transaction_iter(next_version, num_txns)?
+                    .collect::<Result<Vec<_>>>()?;
+                let txn_infos = self
+                    .ledger_store
+                    .get_transaction_info_iter(next_version, num_txns)?
+                    .collect::<Result<Vec<_>>>()?;
                 let write_sets = self
                     .transaction_store
                     .get_write_sets(next_version, end_version)?;
-                let write_sets_ref: Vec<_> = write_sets.iter().collect();
-                indexer.index_with_annotator(&annotator, next_version, &write_sets_ref)?;
+                let events = self
+                    .event_store
+                    .get_events_by_version_iter(next_version, num_txns)?
+                    .collect::<Result<Vec<_>>>()?;
+                let txns_to_index: Vec<_> =
+                    itertools::izip!(&txns, &txn_infos, &write_sets, &events)
+                        .map(
+                            |(transaction, txn_info, write_set, events)| TransactionToIndex {
+                                transaction,
+                                status: txn_info.status(),
+                                write_set,
+                                events,
+                            },
+                        )
+                        .collect();
+                indexer.index_with_annotator(&annotator, next_version, &txns_to_index)?;
 
                 next_version = end_version;
             }
@@ -555,11 +587,142 @@ impl AptosDB {
         self.state_store.buffered_state()
     }
 
+    /// Serves the table infos from the DB of the table info service, if the internal indexer is
+    /// not enabled. Returns false if already set.
+    pub fn set_table_info_db(&self, table_info_db: Arc<TableInfoDb>) -> bool {
+        self.table_info_db.set(table_info_db).is_ok()
+    }
+
     /// This force the db to update rocksdb properties immediately.
     pub fn update_rocksdb_properties(&self) -> Result<()> {
         update_rocksdb_properties(&self.ledger_db, &self.state_merkle_db)
     }
 
+    /// Puts a cache of the latest state values in front of the state value reads, warmed up from
+    /// the snapshot persisted by a previous run and the framework account, as configured.
+    pub fn enable_hot_state_cache(&self, config: &HotStateCacheConfig) -> Result<()> {
+        if !config.enabled {
+            return Ok(());
+        }
+        // No transactions are committed until the cache is set, so that it misses none.
+        let _lock = self.ledger_commit_lock.lock();
+        let next_version = self
+            .ledger_store
+            .get_latest_transaction_info_option()?
+            .map_or(0, |(version, _)| version + 1);
+        let cache = Arc::new(HotStateCache::new(
+            config.max_bytes,
+            config.eviction_policy,
+            next_version,
+        ));
+        if let Some(dir) = &config.persist_dir {
+            if let Err(e) = self.restore_hot_state_cache(&cache, dir, next_version) {
+                warn!(error = ?e, "Failed to restore the hot state cache from {:?}.", dir);
+                cache.clear();
+            }
+        }
+        ensure!(
+            self.state_store.set_hot_state_cache(Arc::clone(&cache)),
+            "Hot state cache already enabled."
+        );
+        info!(
+            next_version = next_version,
+            "Hot state cache enabled, with {:?} eviction.", config.eviction_policy
+        );
+
+        if config.prefetch_framework && next_version > 0 {
+            let state_store = Arc::clone(&self.state_store);
+            thread::Builder::new()
+                .name("hot_state_prefetch".into())
+                .spawn(move || {
+                    let version = next_version - 1;
+                    let result = state_store
+                        .get_values_by_key_prefix(&StateKeyPrefix::from(CORE_CODE_ADDRESS), version)
+                        .and_then(|values| {
+                            // Reads through the cache, caching the values.
+                            for key in values.keys() {
+                                state_store.get_state_value_by_version(key, version)?;
+                            }
+                            Ok(values.len())
+                        });
+                    match result {
+                        Ok(num_values) => {
+                            info!("Prefetched {} framework state values.", num_values)
+                        }
+                        Err(e) => {
+                            warn!(error = ?e, "Failed to prefetch the framework state values.")
+                        }
+                    }
+                })?;
+        }
+
+        if let Some(dir) = config.persist_dir.clone() {
+            let interval = Duration::from_secs(config.persist_interval_secs);
+            thread::Builder::new()
+                .name("hot_state_persist".into())
+                .spawn(move || loop {
+                    thread::sleep(interval);
+                    if let Err(e) = cache.persist(&dir) {
+                        warn!(error = ?e, "Failed to persist the hot state cache to {:?}.", dir);
+                    }
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Loads the persisted hot state cache, catching up with the transactions committed since.
+    fn restore_hot_state_cache(
+        &self,
+        cache: &HotStateCache,
+        dir: &Path,
+        next_version: Version,
+    ) -> Result<()> {
+        let snapshot = match HotStateCache::read_snapshot(dir)? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+        ensure!(
+            snapshot.next_version <= next_version
+                && next_version - snapshot.next_version <= MAX_HOT_STATE_VERSIONS_TO_CATCH_UP,
+            "Snapshot at version {} is too far from the latest version {}.",
+            snapshot.next_version,
+            next_version,
+        );
+        let first_version = snapshot.next_version;
+        let write_sets = self
+            .transaction_store
+            .get_write_sets(first_version, next_version)?;
+        let writes: Vec<_> = write_sets
+            .into_iter()
+            .zip(first_version..)
+            .flat_map(|(write_set, version)| {
+                write_set.into_iter().map(move |(key, op)| {
+                    let value = match op {
+                        WriteOp::Creation(bytes) | WriteOp::Modification(bytes) => {
+                            Some(StateValue::from(bytes))
+                        }
+                        WriteOp::Deletion => None,
+                    };
+                    (version, key, value)
+                })
+            })
+            .collect();
+        let num_entries = snapshot.entries.len();
+        cache.restore(snapshot);
+        cache.update(
+            writes
+                .iter()
+                .map(|(version, key, value)| (*version, key, value)),
+            next_version,
+        );
+        info!(
+            "Restored {} hot state cache entries, replaying {} versions.",
+            num_entries,
+            next_version - first_version
+        );
+        Ok(())
+    }
+
     /// Returns ledger infos reflecting epoch bumps starting with the given epoch. If there are no
     /// more than `MAX_NUM_EPOCH_ENDING_LEDGER_INFO` results, this function returns all of them,
     /// otherwise the first `MAX_NUM_EPOCH_ENDING_LEDGER_INFO` results are returned and a flag
@@ -680,6 +843,33 @@ impl AptosDB {
         Ok(())
     }
 
+    // ================================== Admin APIs ====================================
+
+    /// Sets the target of the enabled pruners to the latest version now, instead of waiting for a
+    /// full batch of versions to be committed. The pruners catch up in the background, so this
+    /// returns the min readable (state, ledger) versions from before they do.
+    pub fn trigger_pruning(&self) -> Result<(Version, Version)> {
+        ensure!(
+            self.state_pruner.is_pruner_enabled() || self.ledger_pruner.is_pruner_enabled(),
+            "Both the state and the ledger pruners are disabled."
+        );
+        let latest_version = self.get_latest_version()?;
+        if self.state_pruner.is_pruner_enabled() {
+            self.state_pruner
+                .set_pruner_target_db_version(latest_version);
+        }
+        if self.ledger_pruner.is_pruner_enabled() {
+            self.ledger_pruner
+                .set_pruner_target_db_version(latest_version);
+            *self.ledger_pruner.last_version_sent_to_pruner.lock() = latest_version;
+        }
+        info!(latest_version = latest_version, "Triggered pruning.");
+        Ok((
+            self.state_pruner.get_min_readable_version(),
+            self.ledger_pruner.get_min_readable_version(),
+        ))
+    }
+
     // ================================== Private APIs ==================================
     fn get_events_by_event_key(
         &self,
@@ -823,6 +1013,13 @@ impl AptosDB {
                         .put_write_set(ver, txn_to_commit.write_set(), cs)
                 },
             )?;
+            self.transaction_store.put_transaction_auxiliary_data(
+                first_version,
+                txns_to_commit
+                    .iter()
+                    .map(|txn_to_commit| (txn_to_commit.transaction(), txn_to_commit.events())),
+                cs,
+            )?;
             // Transaction accumulator updates. Get result root hash.
             let txn_infos: Vec<_> = txns_to_commit
                 .iter()
@@ -851,9 +1048,10 @@ impl AptosDB {
     }
 
     fn get_table_info_option(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
-        match &self.indexer {
-            Some(indexer) => indexer.get_table_info(handle),
-            None => {
+        match (&self.indexer, self.table_info_db.get()) {
+            (Some(indexer), _) => indexer.get_table_info(handle),
+            (None, Some(table_info_db)) => table_info_db.get_table_info(handle),
+            (None, None) => {
                 bail!("Indexer not enabled.");
             }
         }
@@ -1127,6 +1325,20 @@ impl DbReader for AptosDB {
         })
     }
 
+    /// Gets the auxiliary data of the transaction at `version`: the block it was executed in and
+    /// the gas unit price it paid. `None` for transactions saved before it was recorded.
+    fn get_transaction_auxiliary_data_by_version(
+        &self,
+        version: Version,
+    ) -> Result<Option<TransactionAuxiliaryData>> {
+        gauged_api("get_transaction_auxiliary_data_by_version", || {
+            error_if_version_is_pruned(&self.ledger_pruner, "Transaction", version)?;
+
+            self.transaction_store
+                .get_transaction_auxiliary_data(version)
+        })
+    }
+
     fn get_events(
         &self,
         event_key: &EventKey,
@@ -1140,6 +1352,36 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        end_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        gauged_api("get_events_by_type", || {
+            error_if_too_many_requested(limit, MAX_LIMIT)?;
+            error_if_version_is_pruned(&self.ledger_pruner, "Event", start_version)?;
+            let end_version = std::cmp::min(end_version, ledger_version.saturating_add(1));
+
+            self.event_store
+                .lookup_events_by_type(type_tag, start_version, end_version, limit)?
+                .into_iter()
+                .map(|(ver, idx)| {
+                    let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+                    ensure!(
+                        event.type_tag() == type_tag,
+                        "Index broken, expected type: {}, actual: {}",
+                        type_tag,
+                        event.type_tag()
+                    );
+                    Ok(EventWithVersion::new(ver, event))
+                })
+                .collect()
+        })
+    }
+
     /// Gets ledger info at specified version and ensures it's an epoch ending.
     fn get_epoch_ending_ledger_info(&self, version: u64) -> Result<LedgerInfoWithSignatures> {
         gauged_api("get_epoch_ending_ledger_info", || {
@@ -1336,6 +1578,17 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_first_version_at_or_after_timestamp(
+        &self,
+        timestamp: u64,
+        ledger_version: Version,
+    ) -> Result<Option<Version>> {
+        gauged_api("get_first_version_at_or_after_timestamp", || {
+            self.event_store
+                .get_first_version_at_or_after_timestamp(timestamp, ledger_version)
+        })
+    }
+
     fn get_latest_transaction_info_option(&self) -> Result<Option<(Version, TransactionInfo)>> {
         gauged_api("get_latest_transaction_info_option", || {
             self.ledger_store.get_latest_transaction_info_option()
@@ -1432,6 +1685,58 @@ impl DbReader for AptosDB {
         self.indexer.is_some()
     }
 
+    fn table_info_enabled(&self) -> bool {
+        self.indexer.is_some() || self.table_info_db.get().is_some()
+    }
+
+    fn get_account_related_versions(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        gauged_api("get_account_related_versions", || {
+            error_if_too_many_requested(limit, MAX_LIMIT)?;
+            let indexer = match &self.indexer {
+                Some(indexer) => indexer,
+                None => bail!("Indexer not enabled."),
+            };
+            // The index isn't pruned, skip the versions whose transactions are.
+            let start_version =
+                std::cmp::max(start_version, self.ledger_pruner.get_min_readable_version());
+            // Versions beyond the ledger version may already be indexed while being committed.
+            Ok(indexer
+                .get_account_related_versions(address, start_version, limit)?
+                .into_iter()
+                .take_while(|version| *version <= ledger_version)
+                .collect())
+        })
+    }
+
+    fn get_account_activities(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<AccountActivity>> {
+        gauged_api("get_account_activities", || {
+            error_if_too_many_requested(limit, MAX_LIMIT)?;
+            let indexer = match &self.indexer {
+                Some(indexer) => indexer,
+                None => bail!("Indexer not enabled."),
+            };
+            let start_version =
+                std::cmp::max(start_version, self.ledger_pruner.get_min_readable_version());
+            Ok(indexer
+                .get_account_activities(address, start_version, limit)?
+                .into_iter()
+                .take_while(|activity| activity.version <= ledger_version)
+                .collect())
+        })
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         gauged_api("get_state_storage_usage", || {
             if let Some(v) = version {
@@ -1513,6 +1818,11 @@ impl DbWriter for AptosDB {
                latest_in_memory_state.current_version.expect("Must exist")
             );
 
+            // Injects disk write faults, e.g. `sleep(500)` to stall the write, or `return` to fail it.
+            fail_point!("aptosdb::save_transactions::write", |_| {
+                bail!("Injected error in save_transactions")
+            });
+
             // Persist.
             let (sealed_cs, counters) = self.seal_change_set(first_version, num_txns, cs)?;
             {
@@ -1522,6 +1832,20 @@ impl DbWriter for AptosDB {
                 self.commit(sealed_cs)?;
             }
 
+            if let Some(cache) = self.state_store.hot_state_cache() {
+                cache.update(
+                    txns_to_commit.iter().zip(first_version..).flat_map(
+                        |(txn_to_commit, version)| {
+                            txn_to_commit
+                                .state_updates()
+                                .iter()
+                                .map(move |(key, value)| (version, key, value))
+                        },
+                    ),
+                    last_version + 1,
+                );
+            }
+
             {
                 let mut buffered_state = self.state_store.buffered_state().lock();
                 ensure!(
@@ -1589,8 +1913,16 @@ impl DbWriter for AptosDB {
             // Note: this must happen after txns have been saved to db because types can be newly
             // created in this same chunk of transactions.
             if let Some(indexer) = &self.indexer {
-                let write_sets: Vec<_> = txns_to_commit.iter().map(|txn| txn.write_set()).collect();
-                indexer.index(self.state_store.clone(), first_version, &write_sets)?;
+                let txns_to_index: Vec<_> = txns_to_commit
+                    .iter()
+                    .map(|txn| TransactionToIndex {
+                        transaction: txn.transaction(),
+                        status: txn.transaction_info().status(),
+                        write_set: txn.write_set(),
+                        events: txn.events(),
+                    })
+                    .collect();
+                indexer.index(self.state_store.clone(), first_version, &txns_to_index)?;
             }
 
             Ok(())
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    exponential_buckets, register_histogram_vec, register_int_counter, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounter, IntGauge, IntGaugeVec,
+    exponential_buckets, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -141,6 +142,39 @@ pub static NODE_CACHE_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+// Hot state cache metrics:
+pub(crate) static HOT_STATE_CACHE_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "aptos_storage_hot_state_cache_requests",
+        // metric description
+        "State value reads served by the hot state cache (hit) or the DB (miss), from which the hit rate is derived.",
+        // metric labels (dimensions)
+        &["result"]
+    )
+    .unwrap()
+});
+
+pub(crate) static HOT_STATE_CACHE_EVICTIONS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_storage_hot_state_cache_evictions",
+        "State values evicted from the hot state cache."
+    )
+    .unwrap()
+});
+
+pub(crate) static HOT_STATE_CACHE_USAGE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        // metric name
+        "aptos_storage_hot_state_cache_usage",
+        // metric description
+        "Number of items and bytes in the hot state cache.",
+        // metric labels (dimensions)
+        &["type"]
+    )
+    .unwrap()
+});
+
 /// Rocksdb metrics
 pub static ROCKSDB_PROPERTIES: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
@@ -31,6 +31,11 @@ impl DBSubPruner for TransactionStorePruner {
             target_version,
             db_batch,
         )?;
+        self.transaction_store.prune_transaction_auxiliary_data(
+            min_readable_version,
+            target_version,
+            db_batch,
+        )?;
         self.transaction_store.prune_transaction_info_schema(
             min_readable_version,
             target_version,
@@ -0,0 +1,56 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the progress of the restore of the state
+//! snapshot at a version, written with each chunk of state values so that an interrupted restore
+//! resumes after the last chunk written.
+//!
+//! ```text
+//! |<--key-->|<--value->|
+//! | version | progress |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, STATE_SNAPSHOT_RESTORE_PROGRESS_CF_NAME};
+use anyhow::Result;
+use aptos_jellyfish_merkle::StateSnapshotProgress;
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(
+    StateSnapshotRestoreProgressSchema,
+    Version,
+    StateSnapshotProgress,
+    STATE_SNAPSHOT_RESTORE_PROGRESS_CF_NAME
+);
+
+impl KeyCodec<StateSnapshotRestoreProgressSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Version>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<StateSnapshotRestoreProgressSchema> for StateSnapshotProgress {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,29 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_crypto::HashValue;
+use aptos_types::transaction::Version;
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        key_hash in any::<HashValue>(),
+        num_items in any::<usize>(),
+        total_bytes in any::<usize>(),
+    ) {
+        assert_encode_decode::<StateSnapshotRestoreProgressSchema>(
+            &version,
+            &StateSnapshotProgress {
+                key_hash,
+                num_items,
+                total_bytes,
+            },
+        );
+    }
+}
+
+test_no_panic_decoding!(StateSnapshotRestoreProgressSchema);
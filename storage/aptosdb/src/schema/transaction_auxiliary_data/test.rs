@@ -0,0 +1,18 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        auxiliary_data in any::<TransactionAuxiliaryData>(),
+    ) {
+        assert_encode_decode::<TransactionAuxiliaryDataSchema>(&version, &auxiliary_data);
+    }
+}
+
+test_no_panic_decoding!(TransactionAuxiliaryDataSchema);
@@ -0,0 +1,308 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-memory cache of the latest writes of the most read state keys, in front of the state
+//! KV reads, optionally persisted to disk so that it's warm again after a restart.
+//!
+//! An entry is the latest write of its key as of the latest commit, which serves a read at any
+//! version since that write. Committed writes update the cached entries, so the cache never
+//! serves a value overwritten since.
+
+use crate::metrics::{HOT_STATE_CACHE_EVICTIONS, HOT_STATE_CACHE_REQUESTS, HOT_STATE_CACHE_USAGE};
+use anyhow::Result;
+use aptos_config::config::HotStateEvictionPolicy;
+use aptos_infallible::Mutex;
+use aptos_types::{
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(test)]
+mod test;
+
+const NUM_SHARDS: usize = 16;
+const SNAPSHOT_FILE_NAME: &str = "hot_state_cache.bcs";
+
+/// The latest write of a key: its version and the value written, None for a deletion. None if
+/// the key was never written.
+pub(crate) type LatestWrite = Option<(Version, Option<StateValue>)>;
+
+/// The cached entries persisted to disk, with the version they are the latest writes before.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct HotStateSnapshot {
+    pub next_version: Version,
+    pub entries: Vec<(StateKey, LatestWrite)>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    write: LatestWrite,
+    // The position of the entry in the eviction order.
+    rank: (u64, u64),
+    bytes: usize,
+}
+
+#[derive(Debug, Default)]
+struct Shard {
+    entries: HashMap<StateKey, Entry>,
+    // The keys in eviction order, the first evicted first.
+    order: BTreeMap<(u64, u64), StateKey>,
+    clock: u64,
+    bytes: usize,
+}
+
+fn entry_size(key: &StateKey, write: &LatestWrite) -> usize {
+    key.size()
+        + match write {
+            Some((_, Some(value))) => value.size(),
+            _ => 0,
+        }
+}
+
+fn record_usage(items: i64, bytes: i64) {
+    HOT_STATE_CACHE_USAGE
+        .with_label_values(&["items"])
+        .add(items);
+    HOT_STATE_CACHE_USAGE
+        .with_label_values(&["bytes"])
+        .add(bytes);
+}
+
+impl Shard {
+    /// The rank of an entry used once more, given its previous rank if cached already.
+    fn next_rank(
+        &mut self,
+        policy: HotStateEvictionPolicy,
+        rank: Option<(u64, u64)>,
+    ) -> (u64, u64) {
+        self.clock += 1;
+        match policy {
+            HotStateEvictionPolicy::Lru => (0, self.clock),
+            HotStateEvictionPolicy::Lfu => (rank.map_or(0, |(uses, _)| uses) + 1, self.clock),
+        }
+    }
+
+    /// Returns the latest write of the key if cached and it serves a read at `version`.
+    fn get(
+        &mut self,
+        key: &StateKey,
+        version: Version,
+        policy: HotStateEvictionPolicy,
+    ) -> Option<LatestWrite> {
+        let rank = match self.entries.get(key) {
+            Some(Entry {
+                write: Some((write_version, _)),
+                ..
+            }) if *write_version > version => return None,
+            Some(entry) => entry.rank,
+            None => return None,
+        };
+        let new_rank = self.next_rank(policy, Some(rank));
+        self.order.remove(&rank);
+        self.order.insert(new_rank, key.clone());
+        let entry = self.entries.get_mut(key).expect("Entry must exist.");
+        entry.rank = new_rank;
+        Some(entry.write.clone())
+    }
+
+    fn insert(
+        &mut self,
+        key: StateKey,
+        write: LatestWrite,
+        policy: HotStateEvictionPolicy,
+        max_bytes: usize,
+    ) {
+        // Keeps the entry a concurrent read inserted, which may have been updated since.
+        if self.entries.contains_key(&key) {
+            return;
+        }
+        let bytes = entry_size(&key, &write);
+        if bytes > max_bytes {
+            return;
+        }
+        // Evicts first, as the new entry may be the least frequently used.
+        self.evict(max_bytes - bytes);
+        let rank = self.next_rank(policy, None);
+        self.order.insert(rank, key.clone());
+        self.entries.insert(key, Entry { write, rank, bytes });
+        self.bytes += bytes;
+        record_usage(1, bytes as i64);
+    }
+
+    /// Updates the entry of the key, if cached.
+    fn update(&mut self, key: &StateKey, write: (Version, &Option<StateValue>), max_bytes: usize) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            let write = Some((write.0, write.1.clone()));
+            let bytes = entry_size(key, &write);
+            record_usage(0, bytes as i64 - entry.bytes as i64);
+            self.bytes = self.bytes + bytes - entry.bytes;
+            *entry = Entry {
+                write,
+                rank: entry.rank,
+                bytes,
+            };
+            self.evict(max_bytes);
+        }
+    }
+
+    fn evict(&mut self, max_bytes: usize) {
+        while self.bytes > max_bytes {
+            let rank = *self.order.keys().next().expect("Entries must exist.");
+            let key = self.order.remove(&rank).expect("Key must exist.");
+            let entry = self.entries.remove(&key).expect("Entry must exist.");
+            self.bytes -= entry.bytes;
+            record_usage(-1, -(entry.bytes as i64));
+            HOT_STATE_CACHE_EVICTIONS.inc();
+        }
+    }
+
+    fn clear(&mut self) {
+        record_usage(-(self.entries.len() as i64), -(self.bytes as i64));
+        self.entries.clear();
+        self.order.clear();
+        self.bytes = 0;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct HotStateCache {
+    shards: Vec<Mutex<Shard>>,
+    policy: HotStateEvictionPolicy,
+    max_bytes_per_shard: usize,
+    // Bumped before the cached entries are updated or cleared, so that a read racing with a
+    // commit doesn't cache the value it read before the commit.
+    generation: AtomicU64,
+    // The version the cached entries are the latest writes before.
+    next_version: AtomicU64,
+}
+
+impl HotStateCache {
+    pub fn new(max_bytes: usize, policy: HotStateEvictionPolicy, next_version: Version) -> Self {
+        Self {
+            shards: (0..NUM_SHARDS)
+                .map(|_| Mutex::new(Shard::default()))
+                .collect(),
+            policy,
+            max_bytes_per_shard: max_bytes / NUM_SHARDS,
+            generation: AtomicU64::new(0),
+            next_version: AtomicU64::new(next_version),
+        }
+    }
+
+    fn shard(&self, key: &StateKey) -> &Mutex<Shard> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % NUM_SHARDS]
+    }
+
+    /// Returns the latest write of the key as of `version`, from the cache if possible.
+    /// Otherwise `read` is used to read the latest write as of a version from the DB, and the
+    /// latest write overall is cached.
+    pub fn get_or_read(
+        &self,
+        key: &StateKey,
+        version: Version,
+        read: impl Fn(Version) -> Result<LatestWrite>,
+    ) -> Result<LatestWrite> {
+        if let Some(write) = self.shard(key).lock().get(key, version, self.policy) {
+            HOT_STATE_CACHE_REQUESTS.with_label_values(&["hit"]).inc();
+            return Ok(write);
+        }
+        HOT_STATE_CACHE_REQUESTS.with_label_values(&["miss"]).inc();
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let latest = read(Version::MAX)?;
+        {
+            let mut shard = self.shard(key).lock();
+            if self.generation.load(Ordering::SeqCst) == generation {
+                shard.insert(
+                    key.clone(),
+                    latest.clone(),
+                    self.policy,
+                    self.max_bytes_per_shard,
+                );
+            }
+        }
+        match latest {
+            Some((write_version, _)) if write_version > version => read(version),
+            latest => Ok(latest),
+        }
+    }
+
+    /// Applies the committed writes, in version order, to the cached entries. `next_version` is
+    /// the version after the last commit.
+    pub fn update<'a>(
+        &self,
+        writes: impl IntoIterator<Item = (Version, &'a StateKey, &'a Option<StateValue>)>,
+        next_version: Version,
+    ) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        for (version, key, value) in writes {
+            self.shard(key)
+                .lock()
+                .update(key, (version, value), self.max_bytes_per_shard);
+        }
+        self.next_version.store(next_version, Ordering::SeqCst);
+    }
+
+    /// Drops all entries, e.g. when state values are written other than by a commit.
+    pub fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        for shard in &self.shards {
+            shard.lock().clear();
+        }
+    }
+
+    /// Writes the cached entries to `dir`, replacing the previous snapshot.
+    pub fn persist(&self, dir: &Path) -> Result<()> {
+        // Loaded first, as entries copied later reflect at least the commits before.
+        let next_version = self.next_version.load(Ordering::SeqCst);
+        let mut entries = vec![];
+        for shard in &self.shards {
+            let shard = shard.lock();
+            entries.extend(
+                shard
+                    .order
+                    .values()
+                    .map(|key| (key.clone(), shard.entries[key].write.clone())),
+            );
+        }
+        let snapshot = HotStateSnapshot {
+            next_version,
+            entries,
+        };
+
+        fs::create_dir_all(dir)?;
+        let tmp_path = dir.join(format!("{}.tmp", SNAPSHOT_FILE_NAME));
+        fs::write(&tmp_path, bcs::to_bytes(&snapshot)?)?;
+        fs::rename(&tmp_path, dir.join(SNAPSHOT_FILE_NAME))?;
+        Ok(())
+    }
+
+    /// Reads the snapshot persisted to `dir`, if any.
+    pub fn read_snapshot(dir: &Path) -> Result<Option<HotStateSnapshot>> {
+        let path = dir.join(SNAPSHOT_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(bcs::from_bytes(&fs::read(path)?)?))
+    }
+
+    /// Caches the entries of a snapshot, in the order they were persisted. The writes committed
+    /// since the snapshot must be applied with `update` before the cache is read.
+    pub fn restore(&self, snapshot: HotStateSnapshot) {
+        for (key, write) in snapshot.entries {
+            self.shard(&key)
+                .lock()
+                .insert(key, write, self.policy, self.max_bytes_per_shard);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_temppath::TempPath;
+use std::cell::Cell;
+
+// The writes of each key in version order, standing in for the DB.
+type Writes = HashMap<StateKey, Vec<(Version, Option<StateValue>)>>;
+
+fn key(name: &str) -> StateKey {
+    StateKey::Raw(name.as_bytes().to_vec())
+}
+
+fn value(bytes: &[u8]) -> Option<StateValue> {
+    Some(StateValue::from(bytes.to_vec()))
+}
+
+fn read(writes: &Writes, key: &StateKey, version: Version) -> LatestWrite {
+    writes
+        .get(key)?
+        .iter()
+        .rev()
+        .find(|(write_version, _)| *write_version <= version)
+        .cloned()
+}
+
+fn commit(
+    cache: &HotStateCache,
+    writes: &mut Writes,
+    version: Version,
+    key: &StateKey,
+    v: Option<StateValue>,
+) {
+    writes
+        .entry(key.clone())
+        .or_default()
+        .push((version, v.clone()));
+    cache.update(vec![(version, key, &v)], version + 1);
+}
+
+#[test]
+fn test_read_through() {
+    let cache = HotStateCache::new(1 << 20, HotStateEvictionPolicy::Lru, 0);
+    let mut writes = Writes::new();
+    let (k1, k2) = (key("k1"), key("k2"));
+    commit(&cache, &mut writes, 0, &k1, value(b"v0"));
+
+    let reads = Cell::new(0);
+    let get = |k: &StateKey, version| {
+        cache
+            .get_or_read(k, version, |version| {
+                reads.set(reads.get() + 1);
+                Ok(read(&writes, k, version))
+            })
+            .unwrap()
+    };
+    assert_eq!(get(&k1, 0), Some((0, value(b"v0"))));
+    assert_eq!(reads.get(), 1);
+    assert_eq!(get(&k1, 5), Some((0, value(b"v0"))));
+    // Keys never written are cached too.
+    assert_eq!(get(&k2, 5), None);
+    assert_eq!(get(&k2, 5), None);
+    assert_eq!(reads.get(), 2);
+}
+
+#[test]
+fn test_commit_updates_cached_entries() {
+    let cache = HotStateCache::new(1 << 20, HotStateEvictionPolicy::Lru, 0);
+    let mut writes = Writes::new();
+    let k = key("k");
+    commit(&cache, &mut writes, 0, &k, value(b"v0"));
+
+    let reads = Cell::new(0);
+    let get = |writes: &Writes, version| {
+        cache
+            .get_or_read(&k, version, |version| {
+                reads.set(reads.get() + 1);
+                Ok(read(writes, &k, version))
+            })
+            .unwrap()
+    };
+    assert_eq!(get(&writes, 0), Some((0, value(b"v0"))));
+    commit(&cache, &mut writes, 1, &k, value(b"v1"));
+    commit(&cache, &mut writes, 2, &k, None);
+    assert_eq!(get(&writes, 2), Some((2, None)));
+    assert_eq!(reads.get(), 1);
+
+    // Reads before the latest write go to the DB.
+    assert_eq!(get(&writes, 1), Some((1, value(b"v1"))));
+    assert_eq!(reads.get(), 3);
+    assert_eq!(cache.next_version.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_clear() {
+    let cache = HotStateCache::new(1 << 20, HotStateEvictionPolicy::Lru, 0);
+    let mut writes = Writes::new();
+    let k = key("k");
+    cache.get_or_read(&k, 0, |_| Ok(None)).unwrap();
+
+    // A value written other than by a commit, e.g. by a state snapshot restore.
+    writes.entry(k.clone()).or_default().push((0, value(b"v0")));
+    cache.clear();
+    assert_eq!(
+        cache
+            .get_or_read(&k, 0, |version| Ok(read(&writes, &k, version)))
+            .unwrap(),
+        Some((0, value(b"v0")))
+    );
+}
+
+#[test]
+fn test_lru_eviction() {
+    let policy = HotStateEvictionPolicy::Lru;
+    let mut shard = Shard::default();
+    let max_bytes = 3 * entry_size(&key("k1"), &Some((0, value(b"v")))); // Three entries
+    for name in ["k1", "k2", "k3"].iter() {
+        shard.insert(key(name), Some((0, value(b"v"))), policy, max_bytes);
+    }
+    assert!(shard.get(&key("k1"), 0, policy).is_some());
+    shard.insert(key("k4"), Some((0, value(b"v"))), policy, max_bytes);
+
+    assert!(shard.entries.contains_key(&key("k1")));
+    assert!(!shard.entries.contains_key(&key("k2")));
+    assert_eq!(shard.entries.len(), 3);
+    assert_eq!(shard.bytes, max_bytes);
+
+    // Growing an entry evicts the least recently used others.
+    shard.update(&key("k4"), (1, &value(b"vvvv")), max_bytes);
+    assert!(!shard.entries.contains_key(&key("k3")));
+    assert!(shard.entries.contains_key(&key("k1")));
+}
+
+#[test]
+fn test_lfu_eviction() {
+    let policy = HotStateEvictionPolicy::Lfu;
+    let mut shard = Shard::default();
+    let max_bytes = 3 * entry_size(&key("k1"), &Some((0, value(b"v"))));
+    for name in ["k1", "k2", "k3"].iter() {
+        shard.insert(key(name), Some((0, value(b"v"))), policy, max_bytes);
+    }
+    for _ in 0..2 {
+        shard.get(&key("k2"), 0, policy);
+        shard.get(&key("k1"), 0, policy);
+    }
+    shard.get(&key("k3"), 0, policy);
+    shard.insert(key("k4"), Some((0, value(b"v"))), policy, max_bytes);
+
+    // The least frequently used entry is evicted, even though used last.
+    assert!(!shard.entries.contains_key(&key("k3")));
+    assert!(shard.entries.contains_key(&key("k4")));
+
+    // Among entries used as often, the least recently used one is evicted.
+    shard.get(&key("k4"), 0, policy);
+    shard.get(&key("k4"), 0, policy);
+    shard.insert(key("k5"), Some((0, value(b"v"))), policy, max_bytes);
+    assert!(!shard.entries.contains_key(&key("k2")));
+    assert!(shard.entries.contains_key(&key("k1")));
+    assert!(shard.entries.contains_key(&key("k5")));
+}
+
+#[test]
+fn test_persist_and_restore() {
+    let dir = TempPath::new();
+    dir.create_as_dir().unwrap();
+    let mut writes = Writes::new();
+    let (k1, k2) = (key("k1"), key("k2"));
+
+    let cache = HotStateCache::new(1 << 20, HotStateEvictionPolicy::Lru, 0);
+    commit(&cache, &mut writes, 0, &k1, value(b"v0"));
+    for k in [&k1, &k2].iter() {
+        cache
+            .get_or_read(k, 0, |version| Ok(read(&writes, k, version)))
+            .unwrap();
+    }
+    cache.persist(dir.path()).unwrap();
+
+    let snapshot = HotStateCache::read_snapshot(dir.path()).unwrap().unwrap();
+    assert_eq!(snapshot.next_version, 1);
+    assert_eq!(snapshot.entries.len(), 2);
+
+    // Committed after the snapshot, replayed when restoring it.
+    let restored = HotStateCache::new(1 << 20, HotStateEvictionPolicy::Lru, 2);
+    restored.restore(snapshot);
+    restored.update(vec![(1, &k2, &value(b"v1"))], 2);
+
+    let get = |k: &StateKey| {
+        restored
+            .get_or_read(k, 1, |_| panic!("Must be cached."))
+            .unwrap()
+    };
+    assert_eq!(get(&k1), Some((0, value(b"v0"))));
+    assert_eq!(get(&k2), Some((1, value(b"v1"))));
+}
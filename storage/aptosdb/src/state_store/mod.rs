@@ -10,7 +10,8 @@ use aptos_crypto::{
 };
 use aptos_infallible::Mutex;
 use aptos_jellyfish_merkle::{
-    iterator::JellyfishMerkleIterator, restore::StateSnapshotRestore, StateValueWriter,
+    iterator::JellyfishMerkleIterator, restore::StateSnapshotRestore, StateSnapshotProgress,
+    StateValueWriter,
 };
 use aptos_logger::info;
 use aptos_state_view::{state_storage_usage::StateStorageUsage, StateViewId};
@@ -24,6 +25,7 @@ use aptos_types::{
     transaction::Version,
 };
 use executor_types::in_memory_state_calculator::InMemoryStateCalculator;
+use once_cell::sync::OnceCell;
 use schemadb::{ReadOptions, SchemaBatch, DB};
 use std::{collections::HashMap, ops::Deref, sync::Arc};
 use storage_interface::{
@@ -34,10 +36,16 @@ use storage_interface::{
 use crate::{
     change_set::ChangeSet,
     metrics::{STATE_ITEMS, TOTAL_STATE_BYTES},
-    schema::state_value::StateValueSchema,
+    schema::{
+        state_snapshot_restore_progress::StateSnapshotRestoreProgressSchema,
+        state_value::StateValueSchema,
+    },
     stale_state_value_index::StaleStateValueIndexSchema,
     state_merkle_db::StateMerkleDb,
-    state_store::buffered_state::BufferedState,
+    state_store::{
+        buffered_state::BufferedState,
+        hot_state_cache::{HotStateCache, LatestWrite},
+    },
     version_data::{VersionData, VersionDataSchema},
     AptosDbError, LedgerStore, TransactionStore, OTHER_TIMERS_SECONDS,
 };
@@ -46,6 +54,7 @@ use crate::{
 use aptos_types::nibble::nibble_path::NibblePath;
 
 pub(crate) mod buffered_state;
+pub(crate) mod hot_state_cache;
 mod state_merkle_batch_committer;
 mod state_snapshot_committer;
 #[cfg(test)]
@@ -63,6 +72,7 @@ const MAX_WRITE_SETS_AFTER_SNAPSHOT: LeafCount = buffered_state::TARGET_SNAPSHOT
 pub struct StateDb {
     pub ledger_db: Arc<DB>,
     pub state_merkle_db: Arc<StateMerkleDb>,
+    hot_state_cache: OnceCell<Arc<HotStateCache>>,
 }
 
 #[derive(Debug)]
@@ -161,6 +171,17 @@ impl StateDb {
         state_key: &StateKey,
         version: Version,
     ) -> Result<Option<(Version, StateValue)>> {
+        let write = match self.hot_state_cache.get() {
+            Some(cache) => cache.get_or_read(state_key, version, |version| {
+                self.get_latest_write(state_key, version)
+            })?,
+            None => self.get_latest_write(state_key, version)?,
+        };
+        Ok(write.and_then(|(version, value_opt)| value_opt.map(|value| (version, value))))
+    }
+
+    /// Get the latest write of the given key up to the given version, including deletions.
+    fn get_latest_write(&self, state_key: &StateKey, version: Version) -> Result<LatestWrite> {
         let mut read_opts = ReadOptions::default();
         // We want `None` if the state_key changes in iteration.
         read_opts.set_prefix_same_as_start(true);
@@ -169,7 +190,11 @@ impl StateDb {
         Ok(iter
             .next()
             .transpose()?
-            .and_then(|((_, version), value_opt)| value_opt.map(|value| (version, value))))
+            .map(|((_, version), value_opt)| (version, value_opt)))
+    }
+
+    pub(crate) fn hot_state_cache(&self) -> Option<&Arc<HotStateCache>> {
+        self.hot_state_cache.get()
     }
 }
 
@@ -248,6 +273,7 @@ impl StateStore {
         let state_db = Arc::new(StateDb {
             ledger_db,
             state_merkle_db,
+            hot_state_cache: OnceCell::new(),
         });
         let buffered_state = Mutex::new(
             Self::create_buffered_state_from_latest_snapshot(
@@ -369,6 +395,9 @@ impl StateStore {
     }
 
     pub fn reset(&self) {
+        if let Some(cache) = self.hot_state_cache() {
+            cache.clear();
+        }
         *self.buffered_state.lock() = Self::create_buffered_state_from_latest_snapshot(
             &self.state_db,
             self.target_snapshot_size,
@@ -381,6 +410,11 @@ impl StateStore {
         &self.buffered_state
     }
 
+    /// Puts the cache in front of the state value reads. Returns false if already set.
+    pub(crate) fn set_hot_state_cache(&self, cache: Arc<HotStateCache>) -> bool {
+        self.state_db.hot_state_cache.set(cache).is_ok()
+    }
+
     /// Returns the key, value pairs for a particular state key prefix at at desired version. This
     /// API can be used to get all resources of an account by passing the account address as the
     /// key prefix.
@@ -683,10 +717,21 @@ impl StateStore {
 }
 
 impl StateValueWriter<StateKey, StateValue> for StateStore {
-    fn write_kv_batch(&self, node_batch: &StateValueBatch) -> Result<()> {
+    fn write_kv_batch(
+        &self,
+        version: Version,
+        node_batch: &StateValueBatch,
+        progress: StateSnapshotProgress,
+    ) -> Result<()> {
         let mut batch = SchemaBatch::new();
         add_kv_batch(&mut batch, node_batch)?;
-        self.ledger_db.write_schemas(batch)
+        batch.put::<StateSnapshotRestoreProgressSchema>(&version, &progress)?;
+        self.ledger_db.write_schemas(batch)?;
+        // The restored values may shadow the cached latest writes.
+        if let Some(cache) = self.hot_state_cache() {
+            cache.clear();
+        }
+        Ok(())
     }
 
     fn write_usage(&self, version: Version, items: usize, total_bytes: usize) -> Result<()> {
@@ -698,6 +743,11 @@ impl StateValueWriter<StateKey, StateValue> for StateStore {
             },
         )
     }
+
+    fn get_progress(&self, version: Version) -> Result<Option<StateSnapshotProgress>> {
+        self.ledger_db
+            .get::<StateSnapshotRestoreProgressSchema>(&version)
+    }
 }
 
 fn add_kv_batch(batch: &mut SchemaBatch, kv_batch: &StateValueBatch) -> Result<()> {
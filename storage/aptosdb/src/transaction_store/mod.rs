@@ -8,7 +8,8 @@ use crate::{
     change_set::ChangeSet,
     errors::AptosDbError,
     schema::{
-        transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
+        transaction::TransactionSchema, transaction_auxiliary_data::TransactionAuxiliaryDataSchema,
+        transaction_by_account::TransactionByAccountSchema,
         transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
     },
     transaction_info::TransactionInfoSchema,
@@ -17,8 +18,10 @@ use anyhow::{ensure, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_types::{
     account_address::AccountAddress,
+    account_config::{new_block_event_key, NewBlockEvent},
+    contract_event::ContractEvent,
     proof::position::Position,
-    transaction::{Transaction, Version},
+    transaction::{Transaction, TransactionAuxiliaryData, Version},
     write_set::WriteSet,
 };
 use schemadb::{ReadOptions, SchemaBatch, SchemaIterator, DB};
@@ -264,6 +267,57 @@ impl TransactionStore {
         cs.batch.put::<WriteSetSchema>(&version, write_set)
     }
 
+    /// Get the auxiliary data of the transaction at `version`, if it was saved with it.
+    pub fn get_transaction_auxiliary_data(
+        &self,
+        version: Version,
+    ) -> Result<Option<TransactionAuxiliaryData>> {
+        self.db.get::<TransactionAuxiliaryDataSchema>(&version)
+    }
+
+    /// Save the auxiliary data of the transactions starting at `first_version`, given with the
+    /// events they emitted. A transaction emitting a `NewBlockEvent` starts a new block, the others
+    /// belong to the block of the previous version.
+    pub fn put_transaction_auxiliary_data<'a>(
+        &self,
+        first_version: Version,
+        txns_and_events: impl IntoIterator<Item = (&'a Transaction, &'a [ContractEvent])>,
+        cs: &mut ChangeSet,
+    ) -> Result<()> {
+        // Before the first block, e.g. for genesis, the block is all zeros.
+        let mut block = match first_version.checked_sub(1) {
+            Some(previous_version) => self
+                .get_transaction_auxiliary_data(previous_version)?
+                .unwrap_or_default(),
+            None => TransactionAuxiliaryData::default(),
+        };
+        let block_event_key = new_block_event_key();
+        for (version, (txn, events)) in (first_version..).zip(txns_and_events) {
+            if let Some(event) = events.iter().find(|event| *event.key() == block_event_key) {
+                let new_block = NewBlockEvent::try_from_bytes(event.event_data())?;
+                block = TransactionAuxiliaryData::new(
+                    new_block.hash(),
+                    new_block.height(),
+                    new_block.proposer(),
+                    0,
+                );
+            }
+            let gas_unit_price = match txn {
+                Transaction::UserTransaction(user_txn) => user_txn.gas_unit_price(),
+                _ => 0,
+            };
+            let auxiliary_data = TransactionAuxiliaryData::new(
+                block.block_hash(),
+                block.block_height(),
+                block.proposer(),
+                gas_unit_price,
+            );
+            cs.batch
+                .put::<TransactionAuxiliaryDataSchema>(&version, &auxiliary_data)?;
+        }
+        Ok(())
+    }
+
     /// Prune the transaction by hash store given a list of transaction
     pub fn prune_transaction_by_hash(
         &self,
@@ -304,6 +358,19 @@ impl TransactionStore {
         Ok(())
     }
 
+    /// Prune the transaction auxiliary data between a range of version in [begin, end)
+    pub fn prune_transaction_auxiliary_data(
+        &self,
+        begin: Version,
+        end: Version,
+        db_batch: &mut SchemaBatch,
+    ) -> anyhow::Result<()> {
+        for version in begin..end {
+            db_batch.delete::<TransactionAuxiliaryDataSchema>(&version)?;
+        }
+        Ok(())
+    }
+
     /// Prune the transaction schema store between a range of version in [begin, end)
     pub fn prune_transaction_info_schema(
         &self,
@@ -9,6 +9,7 @@ use aptos_types::{
     proptest_types::{AccountInfoUniverse, SignatureCheckedTransactionGen},
     transaction::Transaction,
 };
+use move_deps::move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
 use proptest::{collection::vec, prelude::*};
 use std::collections::BTreeMap;
 
@@ -198,6 +199,74 @@ proptest! {
     }
 }
 
+fn new_block_event(id: HashValue, height: u64, proposer: AccountAddress) -> ContractEvent {
+    let new_block_event = NewBlockEvent::new(
+        id,
+        0,          // epoch
+        height,     // round
+        height,     // height
+        vec![],     // prev block voters
+        proposer,   // proposer
+        Vec::new(), // failed_proposers
+        0,          // timestamp
+    );
+    ContractEvent::new(
+        new_block_event_key(),
+        height,
+        TypeTag::Struct(NewBlockEvent::struct_tag()),
+        bcs::to_bytes(&new_block_event).unwrap(),
+    )
+}
+
+#[test]
+fn test_transaction_auxiliary_data() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.transaction_store;
+
+    let txn = Transaction::StateCheckpoint(HashValue::zero());
+    let proposer = AccountAddress::random();
+    let (block_1, block_2) = (HashValue::random(), HashValue::random());
+    let block_1_events = vec![new_block_event(block_1, 1, proposer)];
+    let block_2_events = vec![new_block_event(block_2, 2, proposer)];
+
+    // Genesis is before any block, and the block started in the first chunk continues in the
+    // second one.
+    let mut cs = ChangeSet::new();
+    store
+        .put_transaction_auxiliary_data(
+            0,
+            vec![(&txn, &[][..]), (&txn, block_1_events.as_slice())],
+            &mut cs,
+        )
+        .unwrap();
+    store.db.write_schemas(cs.batch).unwrap();
+    let mut cs = ChangeSet::new();
+    store
+        .put_transaction_auxiliary_data(
+            2,
+            vec![(&txn, &[][..]), (&txn, block_2_events.as_slice())],
+            &mut cs,
+        )
+        .unwrap();
+    store.db.write_schemas(cs.batch).unwrap();
+
+    let auxiliary_data = |version| {
+        store
+            .get_transaction_auxiliary_data(version)
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(auxiliary_data(0), TransactionAuxiliaryData::default());
+    for (version, block, height) in [(1, block_1, 1), (2, block_1, 1), (3, block_2, 2)] {
+        assert_eq!(
+            auxiliary_data(version),
+            TransactionAuxiliaryData::new(block, height, proposer, 0)
+        );
+    }
+    assert_eq!(store.get_transaction_auxiliary_data(4).unwrap(), None);
+}
+
 fn init_store(
     mut universe: AccountInfoUniverse,
     gens: Vec<(Index, SignatureCheckedTransactionGen)>,
@@ -16,18 +16,19 @@ use crate::{
     },
     storage::{BackupStorage, FileHandle},
     utils::{
-        read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt, GlobalRestoreOptions,
-        RestoreRunMode,
+        read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt, stream::StreamX,
+        GlobalRestoreOptions, RestoreRunMode,
     },
 };
 use anyhow::{anyhow, ensure, Result};
 use aptos_logger::prelude::*;
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    proof::TransactionInfoWithProof,
+    proof::{SparseMerkleRangeProof, TransactionInfoWithProof},
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::Version,
 };
+use futures::{stream, TryStreamExt};
 use std::sync::Arc;
 use storage_interface::StateSnapshotReceiver;
 use structopt::StructOpt;
@@ -50,6 +51,7 @@ pub struct StateSnapshotRestoreController {
     /// nothing will be done, otherwise, this has no effect.
     target_version: Version,
     epoch_history: Option<Arc<EpochHistory>>,
+    concurrent_downloads: usize,
 }
 
 impl StateSnapshotRestoreController {
@@ -66,6 +68,7 @@ impl StateSnapshotRestoreController {
             manifest_handle: opt.manifest_handle,
             target_version: global_opt.target_version,
             epoch_history,
+            concurrent_downloads: global_opt.concurrent_downloads,
         }
     }
 
@@ -134,12 +137,46 @@ impl StateSnapshotRestoreController {
         // FIXME update counters
         ver_gauge.set(self.version as i64);
         tgt_leaf_idx.set(manifest.chunks.last().map_or(0, |c| c.last_idx as i64));
-        for chunk in manifest.chunks {
-            let blobs = self.read_state_value(chunk.blobs).await?;
-            let proof = self.storage.load_bcs_file(&chunk.proof).await?;
+
+        // Chunks restored by an interrupted previous attempt are skipped.
+        let chunks = match receiver.previous_key_hash() {
+            Some(resume_point) => {
+                let chunks: Vec<_> = manifest
+                    .chunks
+                    .into_iter()
+                    .skip_while(|chunk| chunk.last_key <= resume_point)
+                    .collect();
+                info!(
+                    resume_point = resume_point,
+                    remaining_chunks = chunks.len(),
+                    "Resuming state snapshot restore."
+                );
+                chunks
+            }
+            None => manifest.chunks,
+        };
+
+        // Chunks are downloaded and deserialized concurrently, but added in order.
+        let storage = self.storage.clone();
+        let futs_iter = chunks.into_iter().map(|chunk| {
+            let storage = storage.clone();
+            async move {
+                tokio::spawn(async move {
+                    let blobs = Self::read_state_value(&storage, chunk.blobs.clone()).await?;
+                    let proof: SparseMerkleRangeProof = storage.load_bcs_file(&chunk.proof).await?;
+                    Result::<_>::Ok((chunk.last_idx, blobs, proof))
+                })
+                .await?
+            }
+        });
+        let mut futs_stream = stream::iter(futs_iter).buffered_x(
+            self.concurrent_downloads * 2, /* buffer size */
+            self.concurrent_downloads,     /* concurrency */
+        );
+        while let Some((last_idx, blobs, proof)) = futs_stream.try_next().await? {
             receiver.add_chunk(blobs, proof)?;
 
-            leaf_idx.set(chunk.last_idx as i64);
+            leaf_idx.set(last_idx as i64);
         }
 
         receiver.finish()?;
@@ -148,10 +185,10 @@ impl StateSnapshotRestoreController {
     }
 
     async fn read_state_value(
-        &self,
+        storage: &Arc<dyn BackupStorage>,
         file_handle: FileHandle,
     ) -> Result<Vec<(StateKey, StateValue)>> {
-        let mut file = self.storage.open_for_read(&file_handle).await?;
+        let mut file = storage.open_for_read(&file_handle).await?;
 
         let mut chunk = vec![];
 
@@ -4,12 +4,14 @@
 use anyhow::Result;
 use aptos_logger::{prelude::*, Level, Logger};
 use aptos_secure_push_metrics::MetricsPusher;
+use aptos_types::transaction::Version;
 use backup_cli::{
     coordinators::verify::VerifyCoordinator,
     metadata::cache::MetadataCacheOpt,
     storage::StorageOpt,
     utils::{ConcurrentDownloadsOpt, TrustedWaypointOpt},
 };
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -22,6 +24,23 @@ struct Opt {
     storage: StorageOpt,
     #[structopt(flatten)]
     concurrent_downloads: ConcurrentDownloadsOpt,
+    #[structopt(
+        long,
+        help = "[Defaults to 0] The first transaction version required to be verified."
+    )]
+    start_version: Option<Version>,
+    #[structopt(
+        long,
+        help = "[Defaults to the latest version available] The last transaction version required \
+                to be verified (if present in the backup)."
+    )]
+    end_version: Option<Version>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Write the outcome of the verification of each backup to this file, as JSON."
+    )]
+    output_report: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -42,6 +61,9 @@ async fn main_impl() -> Result<()> {
         opt.metadata_cache_opt,
         opt.trusted_waypoints_opt,
         opt.concurrent_downloads.get(),
+        opt.start_version.unwrap_or(0),
+        opt.end_version.unwrap_or(Version::MAX),
+        opt.output_report,
     )?
     .run()
     .await
@@ -12,19 +12,70 @@ use crate::{
     metrics::verify::{
         VERIFY_COORDINATOR_FAIL_TS, VERIFY_COORDINATOR_START_TS, VERIFY_COORDINATOR_SUCC_TS,
     },
-    storage::BackupStorage,
+    storage::{BackupStorage, FileHandle},
     utils::{unix_timestamp_sec, GlobalRestoreOptions, RestoreRunMode, TrustedWaypointOpt},
 };
-use anyhow::Result;
+use anyhow::{bail, Result};
 use aptos_logger::prelude::*;
 use aptos_types::transaction::Version;
-use std::sync::Arc;
+use futures::{stream, StreamExt};
+use serde::Serialize;
+use std::{path::PathBuf, sync::Arc};
 
 pub struct VerifyCoordinator {
     storage: Arc<dyn BackupStorage>,
     metadata_cache_opt: MetadataCacheOpt,
     trusted_waypoints_opt: TrustedWaypointOpt,
     concurrent_downloads: usize,
+    start_version: Version,
+    end_version: Version,
+    report_path: Option<PathBuf>,
+}
+
+/// The outcome of verifying the backups covering a range of versions, written as JSON if asked
+/// for.
+#[derive(Debug, Default, Serialize)]
+pub struct VerifyReport {
+    pub start_version: Version,
+    pub end_version: Version,
+    /// Errors which aren't about a single backup, e.g. gaps between backups
+    pub errors: Vec<String>,
+    pub epoch_ending_backups: Vec<BackupVerifyResult>,
+    pub state_snapshot_backup: Option<BackupVerifyResult>,
+    pub transaction_backups: Vec<BackupVerifyResult>,
+}
+
+/// The outcome of verifying one backup
+#[derive(Debug, Serialize)]
+pub struct BackupVerifyResult {
+    pub manifest: FileHandle,
+    /// The versions covered by the backup, both ends included
+    pub first_version: Version,
+    pub last_version: Version,
+    /// Why the backup failed verification, `None` if it passed
+    pub error: Option<String>,
+}
+
+impl VerifyReport {
+    pub fn is_success(&self) -> bool {
+        self.failures().is_empty()
+    }
+
+    /// The errors, and the backups which failed verification with their errors
+    pub fn failures(&self) -> Vec<String> {
+        let backup_failures = self
+            .epoch_ending_backups
+            .iter()
+            .chain(self.state_snapshot_backup.iter())
+            .chain(self.transaction_backups.iter())
+            .filter_map(|result| {
+                result
+                    .error
+                    .as_ref()
+                    .map(|error| format!("{}: {}", result.manifest, error))
+            });
+        self.errors.iter().cloned().chain(backup_failures).collect()
+    }
 }
 
 impl VerifyCoordinator {
@@ -33,12 +84,18 @@ impl VerifyCoordinator {
         metadata_cache_opt: MetadataCacheOpt,
         trusted_waypoints_opt: TrustedWaypointOpt,
         concurrent_downloads: usize,
+        start_version: Version,
+        end_version: Version,
+        report_path: Option<PathBuf>,
     ) -> Result<Self> {
         Ok(Self {
             storage,
             metadata_cache_opt,
             trusted_waypoints_opt,
             concurrent_downloads,
+            start_version,
+            end_version,
+            report_path,
         })
     }
 
@@ -46,7 +103,17 @@ impl VerifyCoordinator {
         info!("Verify coordinator started.");
         VERIFY_COORDINATOR_START_TS.set(unix_timestamp_sec());
 
-        let ret = self.run_impl().await;
+        let report_path = self.report_path.clone();
+        let ret = self.run_impl().await.and_then(|report| {
+            if let Some(path) = report_path {
+                std::fs::write(&path, serde_json::to_vec_pretty(&report)?)?;
+                info!("Verify report written to {}.", path.display());
+            }
+            if !report.is_success() {
+                bail!("Backup verification failed: {:?}", report.failures());
+            }
+            Ok(())
+        });
 
         if let Err(e) = &ret {
             error!(
@@ -62,42 +129,78 @@ impl VerifyCoordinator {
         ret
     }
 
-    async fn run_impl(self) -> Result<()> {
+    /// Verifies the backups covering [`start_version`, `end_version`]. The epoch ending backups
+    /// are always verified from genesis, since the ledger infos of the other backups are verified
+    /// against the epoch history. The transaction backups are verified concurrently, each one on
+    /// its own, so that a corrupt one doesn't hide the others.
+    async fn run_impl(self) -> Result<VerifyReport> {
+        let mut report = VerifyReport {
+            start_version: self.start_version,
+            end_version: self.end_version,
+            ..Default::default()
+        };
         let metadata_view = metadata::cache::sync_and_load(
             &self.metadata_cache_opt,
             Arc::clone(&self.storage),
             self.concurrent_downloads,
         )
         .await?;
-        let ver_max = Version::max_value();
-        let state_snapshot = metadata_view.select_state_snapshot(ver_max)?;
-        let transactions = metadata_view.select_transaction_backups(0, ver_max)?;
-        let epoch_endings = metadata_view.select_epoch_ending_backups(ver_max)?;
+        let state_snapshot = metadata_view
+            .select_state_snapshot(self.end_version)?
+            .filter(|backup| backup.version >= self.start_version);
+        let epoch_endings = metadata_view.select_epoch_ending_backups(self.end_version)?;
+        let transactions =
+            match metadata_view.select_transaction_backups(self.start_version, self.end_version) {
+                Ok(transactions) => transactions,
+                Err(e) => {
+                    report.errors.push(e.to_string());
+                    vec![]
+                }
+            };
 
         let global_opt = GlobalRestoreOptions {
-            target_version: ver_max,
+            target_version: self.end_version,
             trusted_waypoints: Arc::new(self.trusted_waypoints_opt.verify()?),
             run_mode: Arc::new(RestoreRunMode::Verify),
             concurrent_downloads: self.concurrent_downloads,
         };
 
-        let epoch_history = Arc::new(
-            EpochHistoryRestoreController::new(
-                epoch_endings
-                    .into_iter()
-                    .map(|backup| backup.manifest)
-                    .collect(),
-                global_opt.clone(),
-                self.storage.clone(),
-            )
-            .run()
-            .await?,
-        );
+        let epoch_history_res = EpochHistoryRestoreController::new(
+            epoch_endings
+                .iter()
+                .map(|backup| backup.manifest.clone())
+                .collect(),
+            global_opt.clone(),
+            self.storage.clone(),
+        )
+        .run()
+        .await;
+        // The epoch history is verified as a whole, so its error is reported for every backup.
+        let epoch_history_error = epoch_history_res.as_ref().err().map(ToString::to_string);
+        report.epoch_ending_backups = epoch_endings
+            .into_iter()
+            .map(|backup| BackupVerifyResult {
+                manifest: backup.manifest,
+                first_version: backup.first_version,
+                last_version: backup.last_version,
+                error: epoch_history_error.clone(),
+            })
+            .collect();
+        let epoch_history = match epoch_history_res {
+            Ok(epoch_history) => Arc::new(epoch_history),
+            Err(e) => {
+                report.errors.push(format!(
+                    "Other backups not verified, the epoch history is needed: {}",
+                    e
+                ));
+                return Ok(report);
+            }
+        };
 
         if let Some(backup) = state_snapshot {
-            StateSnapshotRestoreController::new(
+            let res = StateSnapshotRestoreController::new(
                 StateSnapshotRestoreOpt {
-                    manifest_handle: backup.manifest,
+                    manifest_handle: backup.manifest.clone(),
                     version: backup.version,
                 },
                 global_opt.clone(),
@@ -105,20 +208,43 @@ impl VerifyCoordinator {
                 Some(Arc::clone(&epoch_history)),
             )
             .run()
-            .await?;
+            .await;
+            report.state_snapshot_backup = Some(BackupVerifyResult {
+                manifest: backup.manifest,
+                first_version: backup.version,
+                last_version: backup.version,
+                error: res.err().map(|e| e.to_string()),
+            });
         }
 
-        let txn_manifests = transactions.into_iter().map(|b| b.manifest).collect();
-        TransactionRestoreBatchController::new(
-            global_opt,
-            self.storage,
-            txn_manifests,
-            None, /* replay_from_version */
-            Some(epoch_history),
-        )
-        .run()
-        .await?;
+        // Each backup is verified by one task, so the chunks of a backup are loaded one by one.
+        let txn_global_opt = GlobalRestoreOptions {
+            concurrent_downloads: 1,
+            ..global_opt
+        };
+        report.transaction_backups = stream::iter(transactions)
+            .map(|backup| {
+                let controller = TransactionRestoreBatchController::new(
+                    txn_global_opt.clone(),
+                    Arc::clone(&self.storage),
+                    vec![backup.manifest.clone()],
+                    None, /* replay_from_version */
+                    Some(Arc::clone(&epoch_history)),
+                );
+                async move {
+                    let res = controller.run().await;
+                    BackupVerifyResult {
+                        manifest: backup.manifest,
+                        first_version: backup.first_version,
+                        last_version: backup.last_version,
+                        error: res.err().map(|e| e.to_string()),
+                    }
+                }
+            })
+            .buffered(self.concurrent_downloads)
+            .collect()
+            .await;
 
-        Ok(())
+        Ok(report)
     }
 }
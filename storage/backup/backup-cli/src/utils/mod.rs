@@ -18,7 +18,8 @@ use aptos_config::config::{
 use aptos_crypto::HashValue;
 use aptos_infallible::duration_since_epoch;
 use aptos_jellyfish_merkle::{
-    restore::StateSnapshotRestore, NodeBatch, StateValueBatch, StateValueWriter, TreeWriter,
+    restore::StateSnapshotRestore, NodeBatch, StateSnapshotProgress, StateValueBatch,
+    StateValueWriter, TreeWriter,
 };
 use aptos_types::{
     state_store::{state_key::StateKey, state_value::StateValue},
@@ -142,7 +143,9 @@ impl TreeWriter<StateKey> for MockStore {
 impl StateValueWriter<StateKey, StateValue> for MockStore {
     fn write_kv_batch(
         &self,
+        _version: Version,
         _kv_batch: &StateValueBatch<StateKey, Option<StateValue>>,
+        _progress: StateSnapshotProgress,
     ) -> Result<()> {
         Ok(())
     }
@@ -150,6 +153,10 @@ impl StateValueWriter<StateKey, StateValue> for MockStore {
     fn write_usage(&self, _version: Version, _items: usize, _total_bytes: usize) -> Result<()> {
         Ok(())
     }
+
+    fn get_progress(&self, _version: Version) -> Result<Option<StateSnapshotProgress>> {
+        Ok(None)
+    }
 }
 
 impl RestoreRunMode {
@@ -2,3 +2,4 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub const INDEX_DB_NAME: &str = "index_indexer_db";
+pub const TABLE_INFO_DB_NAME: &str = "table_info_db";
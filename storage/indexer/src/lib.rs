@@ -3,10 +3,16 @@
 
 mod db;
 mod metadata;
+mod metrics;
 mod schema;
+pub mod table_info;
+#[cfg(test)]
+mod table_info_test;
 
 use crate::db::INDEX_DB_NAME;
 use crate::metadata::{Metadata, MetadataTag};
+use crate::schema::account_activity::AccountActivitySchema;
+use crate::schema::account_transaction_version::AccountTransactionVersionSchema;
 use crate::schema::column_families;
 use crate::schema::indexer_metadata::IndexerMetadataSchema;
 use crate::schema::table_info::TableInfoSchema;
@@ -16,23 +22,35 @@ use aptos_logger::warn;
 use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_types::access_path::Path;
 use aptos_types::account_address::AccountAddress;
+use aptos_types::contract_event::ContractEvent;
+use aptos_types::resource_group::decode_resource_group;
 use aptos_types::state_store::state_key::StateKey;
 use aptos_types::state_store::table::TableHandle;
 use aptos_types::state_store::table::TableInfo;
-use aptos_types::transaction::{AtomicVersion, Version};
+use aptos_types::transaction::{
+    AccountActivity, AtomicVersion, ExecutionStatus, Transaction, Version,
+};
 use aptos_types::write_set::{WriteOp, WriteSet};
 use aptos_vm::data_cache::{AsMoveResolver, RemoteStorage};
 use move_deps::move_core_types::identifier::IdentStr;
 use move_deps::move_core_types::language_storage::{StructTag, TypeTag};
 use move_deps::move_resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator};
-use schemadb::{SchemaBatch, DB};
-use std::collections::HashMap;
+use schemadb::{ReadOptions, SchemaBatch, DB};
+use std::collections::{BTreeSet, HashMap};
 use std::convert::TryInto;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use storage_interface::state_view::DbStateView;
 use storage_interface::DbReader;
 
+/// A committed transaction and its output, as indexed
+pub struct TransactionToIndex<'a> {
+    pub transaction: &'a Transaction,
+    pub status: &'a ExecutionStatus,
+    pub write_set: &'a WriteSet,
+    pub events: &'a [ContractEvent],
+}
+
 #[derive(Debug)]
 pub struct Indexer {
     db: DB,
@@ -69,23 +87,23 @@ impl Indexer {
         &self,
         db_reader: Arc<dyn DbReader>,
         first_version: Version,
-        write_sets: &[&WriteSet],
+        transactions: &[TransactionToIndex],
     ) -> Result<()> {
-        let last_version = first_version + write_sets.len() as Version;
+        let last_version = first_version + transactions.len() as Version;
         let state_view = DbStateView {
             db: db_reader,
             version: Some(last_version),
         };
         let resolver = state_view.as_move_resolver();
         let annotator = MoveValueAnnotator::new(&resolver);
-        self.index_with_annotator(&annotator, first_version, write_sets)
+        self.index_with_annotator(&annotator, first_version, transactions)
     }
 
     pub fn index_with_annotator(
         &self,
         annotator: &MoveValueAnnotator<RemoteStorage<DbStateView>>,
         first_version: Version,
-        write_sets: &[&WriteSet],
+        transactions: &[TransactionToIndex],
     ) -> Result<()> {
         let next_version = self.next_version();
         ensure!(
@@ -94,26 +112,36 @@ impl Indexer {
             next_version,
             first_version,
         );
-        let end_version = first_version + write_sets.len() as Version;
+        let end_version = first_version + transactions.len() as Version;
         if end_version <= next_version {
             warn!(
                 "Seeing old transactions. Expecting version: {}, got {} transactions starting from version {}.",
                 next_version,
-                write_sets.len(),
+                transactions.len(),
                 first_version,
             );
             return Ok(());
         }
 
         let mut table_info_parser = TableInfoParser::new(self, annotator);
-        for write_set in write_sets {
-            for (state_key, write_op) in write_set.iter() {
+        for txn in transactions {
+            for (state_key, write_op) in txn.write_set.iter() {
                 table_info_parser.parse_write_op(state_key, write_op)?;
             }
         }
 
         let mut batch = SchemaBatch::new();
         table_info_parser.finish(&mut batch)?;
+        for (version, txn) in (first_version..).zip(transactions.iter()) {
+            for address in Self::related_accounts(txn.write_set, txn.events) {
+                batch.put::<AccountTransactionVersionSchema>(&(address, version), &())?;
+            }
+            if let Some((sender, activity)) =
+                AccountActivity::from_transaction(version, txn.transaction, txn.status)
+            {
+                batch.put::<AccountActivitySchema>(&(sender, version), &activity)?;
+            }
+        }
         batch.put::<IndexerMetadataSchema>(
             &MetadataTag::LatestVersion,
             &Metadata::LatestVersion(end_version - 1),
@@ -131,10 +159,94 @@ impl Indexer {
     pub fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         self.db.get::<TableInfoSchema>(&handle)
     }
+
+    /// Returns the versions of up to `limit` transactions related to `address`, starting at
+    /// `start_version`, in ascending order. Transactions committed before the indexer started
+    /// indexing accounts are not included.
+    pub fn get_account_related_versions(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Vec<Version>> {
+        let mut iter = self
+            .db
+            .iter::<AccountTransactionVersionSchema>(ReadOptions::default())?;
+        iter.seek(&(address, start_version))?;
+
+        let mut versions = Vec::new();
+        for res in iter {
+            if versions.len() as u64 >= limit {
+                break;
+            }
+            let ((key_address, version), ()) = res?;
+            if key_address != address {
+                break;
+            }
+            versions.push(version);
+        }
+        Ok(versions)
+    }
+
+    /// Returns the activities of up to `limit` transactions sent by `address`, starting at
+    /// `start_version`, in ascending order of version. Transactions committed before the indexer
+    /// started indexing account activities are not included.
+    pub fn get_account_activities(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        limit: u64,
+    ) -> Result<Vec<AccountActivity>> {
+        let mut iter = self
+            .db
+            .iter::<AccountActivitySchema>(ReadOptions::default())?;
+        iter.seek(&(address, start_version))?;
+
+        let mut activities = Vec::new();
+        for res in iter {
+            if activities.len() as u64 >= limit {
+                break;
+            }
+            let ((key_address, _version), activity) = res?;
+            if key_address != address {
+                break;
+            }
+            activities.push(activity);
+        }
+        Ok(activities)
+    }
+
+    /// The accounts whose resources or modules are written by a transaction, and the creators of
+    /// the event handles it emits to. Table items can't be attributed to an account.
+    fn related_accounts(
+        write_set: &WriteSet,
+        events: &[ContractEvent],
+    ) -> BTreeSet<AccountAddress> {
+        let mut accounts: BTreeSet<_> = write_set
+            .iter()
+            .filter_map(|(state_key, _write_op)| match state_key {
+                StateKey::AccessPath(access_path) => Some(access_path.address),
+                StateKey::TableItem { .. } | StateKey::Raw(_) => None,
+            })
+            .collect();
+        accounts.extend(events.iter().map(|event| event.key().get_creator_address()));
+        accounts
+    }
+}
+
+/// Where the parser looks up the table infos parsed from earlier transactions
+trait TableInfoReader {
+    fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>>;
+}
+
+impl TableInfoReader for Indexer {
+    fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
+        Indexer::get_table_info(self, handle)
+    }
 }
 
 struct TableInfoParser<'a> {
-    indexer: &'a Indexer,
+    reader: &'a dyn TableInfoReader,
     annotator: &'a MoveValueAnnotator<'a, RemoteStorage<'a, DbStateView>>,
     result: HashMap<TableHandle, TableInfo>,
     pending_on: HashMap<TableHandle, Vec<&'a [u8]>>,
@@ -142,11 +254,11 @@ struct TableInfoParser<'a> {
 
 impl<'a> TableInfoParser<'a> {
     pub fn new(
-        indexer: &'a Indexer,
+        reader: &'a dyn TableInfoReader,
         annotator: &'a MoveValueAnnotator<RemoteStorage<DbStateView>>,
     ) -> Self {
         Self {
-            indexer,
+            reader,
             annotator,
             result: HashMap::new(),
             pending_on: HashMap::new(),
@@ -161,6 +273,11 @@ impl<'a> TableInfoParser<'a> {
                     match path {
                         Path::Code(_) => (),
                         Path::Resource(struct_tag) => self.parse_struct(struct_tag, bytes)?,
+                        Path::ResourceGroup(group_tag) => {
+                            for (struct_tag, bytes) in decode_resource_group(&group_tag, bytes)? {
+                                self.parse_struct(struct_tag, &bytes)?
+                            }
+                        }
                     }
                 }
                 StateKey::TableItem { handle, .. } => self.parse_table_item(*handle, bytes)?,
@@ -256,7 +373,7 @@ impl<'a> TableInfoParser<'a> {
     fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         match self.result.get(&handle) {
             Some(table_info) => Ok(Some(table_info.clone())),
-            None => self.indexer.get_table_info(handle),
+            None => self.reader.get_table_info(handle),
         }
     }
 
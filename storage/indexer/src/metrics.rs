@@ -0,0 +1,26 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    exponential_buckets, register_histogram, register_int_gauge, Histogram, IntGauge,
+};
+use once_cell::sync::Lazy;
+
+pub static TABLE_INFO_PARSER_NEXT_VERSION: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_table_info_parser_next_version",
+        "The version of the first write set the table info parser hasn't parsed yet."
+    )
+    .unwrap()
+});
+
+pub static TABLE_INFO_PARSER_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_table_info_parser_seconds",
+        // metric description
+        "The time spent in seconds parsing a batch of write sets for table infos",
+        exponential_buckets(/*start=*/ 1e-6, /*factor=*/ 2.0, /*count=*/ 22).unwrap(),
+    )
+    .unwrap()
+});
@@ -0,0 +1,75 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an index of the transactions sent by each
+//! account, holding a summary of each transaction, e.g. whether it succeeded and the entry
+//! function it called, so that an account's transactions can be filtered and counted without
+//! reading them.
+//!
+//! ```text
+//! |<------key------>|<--value-->|
+//! | address | txn_ver | activity  |
+//! ```
+//!
+//! `txn_ver` is serialized in big endian so that the transactions of an account are iterated in
+//! order.
+
+use crate::schema::ACCOUNT_ACTIVITY_CF_NAME;
+use anyhow::{ensure, Result};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{AccountActivity, Version},
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::{convert::TryFrom, mem::size_of};
+
+define_schema!(
+    AccountActivitySchema,
+    Key,
+    AccountActivity,
+    ACCOUNT_ACTIVITY_CF_NAME
+);
+
+type Key = (AccountAddress, Version);
+
+impl KeyCodec<AccountActivitySchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref address, version) = *self;
+
+        let mut encoded = address.to_vec();
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == size_of::<Self>(),
+            "Unexpected data len {}, expected {}.",
+            data.len(),
+            size_of::<Self>(),
+        );
+
+        let address = AccountAddress::try_from(&data[..AccountAddress::LENGTH])?;
+        let version = (&data[AccountAddress::LENGTH..]).read_u64::<BigEndian>()?;
+
+        Ok((address, version))
+    }
+}
+
+impl ValueCodec<AccountActivitySchema> for AccountActivity {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
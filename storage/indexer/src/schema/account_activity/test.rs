@@ -0,0 +1,36 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use move_deps::move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        address in any::<AccountAddress>(),
+        version in any::<Version>(),
+        sequence_number in any::<u64>(),
+        success in any::<bool>(),
+        is_entry_function in any::<bool>(),
+    ) {
+        let entry_function = if is_entry_function {
+            Some((
+                ModuleId::new(address, Identifier::new("coin").unwrap()),
+                Identifier::new("transfer").unwrap(),
+            ))
+        } else {
+            None
+        };
+        let activity = AccountActivity {
+            version,
+            sequence_number,
+            success,
+            entry_function,
+        };
+        assert_encode_decode::<AccountActivitySchema>(&(address, version), &activity);
+    }
+}
+
+test_no_panic_decoding!(AccountActivitySchema);
@@ -0,0 +1,76 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an index via which the versions of the
+//! transactions related to an account can be found. A transaction is related to an account if it
+//! wrote to a resource or module of the account, or emitted an event to one of its event handles.
+//!
+//! ```text
+//! |<------key------>|
+//! | address | txn_ver |
+//! ```
+//!
+//! `txn_ver` is serialized in big endian so that the versions related to an account are iterated
+//! in order.
+
+use crate::schema::ACCOUNT_TRANSACTION_VERSION_CF_NAME;
+use anyhow::{ensure, Result};
+use aptos_types::{account_address::AccountAddress, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::{convert::TryFrom, mem::size_of};
+
+define_schema!(
+    AccountTransactionVersionSchema,
+    Key,
+    (),
+    ACCOUNT_TRANSACTION_VERSION_CF_NAME
+);
+
+type Key = (AccountAddress, Version);
+
+impl KeyCodec<AccountTransactionVersionSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref address, version) = *self;
+
+        let mut encoded = address.to_vec();
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == size_of::<Self>(),
+            "Unexpected data len {}, expected {}.",
+            data.len(),
+            size_of::<Self>(),
+        );
+
+        let address = AccountAddress::try_from(&data[..AccountAddress::LENGTH])?;
+        let version = (&data[AccountAddress::LENGTH..]).read_u64::<BigEndian>()?;
+
+        Ok((address, version))
+    }
+}
+
+impl ValueCodec<AccountTransactionVersionSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.is_empty(),
+            "Unexpected data len {}, expected 0.",
+            data.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
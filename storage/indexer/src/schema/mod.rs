@@ -6,16 +6,31 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod account_activity;
+pub(crate) mod account_transaction_version;
 pub(crate) mod indexer_metadata;
 pub(crate) mod table_info;
 
 use schemadb::ColumnFamilyName;
 
+pub const ACCOUNT_ACTIVITY_CF_NAME: ColumnFamilyName = "account_activity";
+pub const ACCOUNT_TRANSACTION_VERSION_CF_NAME: ColumnFamilyName = "account_transaction_version";
 pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
 pub const INDEXER_METADATA_CF_NAME: ColumnFamilyName = "indexer_metadata";
 pub const TABLE_INFO_CF_NAME: ColumnFamilyName = "table_info";
 
 pub fn column_families() -> Vec<ColumnFamilyName> {
+    vec![
+        /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        ACCOUNT_ACTIVITY_CF_NAME,
+        ACCOUNT_TRANSACTION_VERSION_CF_NAME,
+        INDEXER_METADATA_CF_NAME,
+        TABLE_INFO_CF_NAME,
+    ]
+}
+
+/// The column families of the table info DB, a subset of the indexer DB's
+pub fn table_info_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         INDEXER_METADATA_CF_NAME,
@@ -0,0 +1,154 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! The table info DB maps table handles to the types of their keys and values, so that table items
+//! can be decoded. Unlike the internal indexer, which parses each chunk of transactions as it's
+//! committed, it's filled in asynchronously by parsing the committed write sets, so a slow parse
+//! never holds up the commit.
+
+use crate::{
+    db::TABLE_INFO_DB_NAME,
+    metadata::{Metadata, MetadataTag},
+    metrics::{TABLE_INFO_PARSER_NEXT_VERSION, TABLE_INFO_PARSER_SECONDS},
+    schema::{
+        indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        table_info_column_families,
+    },
+    TableInfoParser, TableInfoReader,
+};
+use anyhow::{ensure, Result};
+use aptos_config::config::RocksdbConfig;
+use aptos_logger::warn;
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_types::{
+    state_store::table::{TableHandle, TableInfo},
+    transaction::{AtomicVersion, Version},
+    write_set::WriteSet,
+};
+use aptos_vm::data_cache::AsMoveResolver;
+use move_deps::move_resource_viewer::MoveValueAnnotator;
+use schemadb::{SchemaBatch, DB};
+use std::sync::{atomic::Ordering, Arc};
+use storage_interface::{state_view::DbStateView, DbReader};
+
+#[derive(Debug)]
+pub struct TableInfoDb {
+    db: DB,
+    next_version: AtomicVersion,
+}
+
+impl TableInfoDb {
+    pub fn open(
+        db_root_path: impl AsRef<std::path::Path>,
+        rocksdb_config: RocksdbConfig,
+    ) -> Result<Self> {
+        let db_path = db_root_path.as_ref().join(TABLE_INFO_DB_NAME);
+
+        let db = DB::open(
+            db_path,
+            "table_info_db",
+            table_info_column_families(),
+            &gen_rocksdb_options(&rocksdb_config, false),
+        )?;
+
+        let next_version = db
+            .get::<IndexerMetadataSchema>(&MetadataTag::LatestVersion)?
+            .map_or(0, |meta| match meta {
+                Metadata::LatestVersion(version) => version + 1,
+            });
+        TABLE_INFO_PARSER_NEXT_VERSION.set(next_version as i64);
+
+        Ok(Self {
+            db,
+            next_version: AtomicVersion::new(next_version),
+        })
+    }
+
+    /// The version of the first write set not parsed yet.
+    pub fn next_version(&self) -> Version {
+        self.next_version.load(Ordering::Relaxed)
+    }
+
+    /// Returns the info of the table, None if unknown, including if the write set creating the
+    /// table hasn't been parsed yet.
+    pub fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
+        self.db.get::<TableInfoSchema>(&handle)
+    }
+
+    /// Parses the committed write sets following the ones parsed already, up to `batch_size` of
+    /// them. Returns the number of write sets parsed, 0 if caught up with the DB.
+    pub fn parse_next_batch(&self, db_reader: Arc<dyn DbReader>, batch_size: u64) -> Result<u64> {
+        let next_version = self.next_version();
+        let ledger_next_version = db_reader.get_latest_version_option()?.map_or(0, |v| v + 1);
+        if next_version >= ledger_next_version {
+            return Ok(0);
+        }
+        let end_version = std::cmp::min(ledger_next_version, next_version + batch_size);
+        let write_sets = db_reader.get_write_sets(next_version, end_version)?;
+        let write_sets_ref: Vec<_> = write_sets.iter().collect();
+        self.index(db_reader, next_version, &write_sets_ref)?;
+        Ok(end_version - next_version)
+    }
+
+    /// Parses the table infos out of the write sets of the transactions starting at
+    /// `first_version`, which must follow the ones parsed already.
+    pub fn index(
+        &self,
+        db_reader: Arc<dyn DbReader>,
+        first_version: Version,
+        write_sets: &[&WriteSet],
+    ) -> Result<()> {
+        let _timer = TABLE_INFO_PARSER_SECONDS.start_timer();
+        let next_version = self.next_version();
+        ensure!(
+            first_version <= next_version,
+            "Table info parser expects to see continuous transaction versions. Expecting: {}, got: {}",
+            next_version,
+            first_version,
+        );
+        let end_version = first_version + write_sets.len() as Version;
+        if end_version <= next_version {
+            warn!(
+                "Seeing old transactions. Expecting version: {}, got {} transactions starting from version {}.",
+                next_version,
+                write_sets.len(),
+                first_version,
+            );
+            return Ok(());
+        }
+
+        // The modules are read at the latest version, as the state at older ones may be pruned
+        // already. Since module upgrades keep the struct layouts, that's as good.
+        let latest_version = db_reader.get_latest_version()?;
+        let state_view = DbStateView {
+            db: db_reader,
+            version: Some(latest_version),
+        };
+        let resolver = state_view.as_move_resolver();
+        let annotator = MoveValueAnnotator::new(&resolver);
+        let mut table_info_parser = TableInfoParser::new(self, &annotator);
+        for write_set in write_sets {
+            for (state_key, write_op) in write_set.iter() {
+                table_info_parser.parse_write_op(state_key, write_op)?;
+            }
+        }
+
+        let mut batch = SchemaBatch::new();
+        table_info_parser.finish(&mut batch)?;
+        batch.put::<IndexerMetadataSchema>(
+            &MetadataTag::LatestVersion,
+            &Metadata::LatestVersion(end_version - 1),
+        )?;
+        self.db.write_schemas(batch)?;
+        self.next_version.store(end_version, Ordering::Relaxed);
+        TABLE_INFO_PARSER_NEXT_VERSION.set(end_version as i64);
+
+        Ok(())
+    }
+}
+
+impl TableInfoReader for TableInfoDb {
+    fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
+        TableInfoDb::get_table_info(self, handle)
+    }
+}
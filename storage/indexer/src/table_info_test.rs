@@ -0,0 +1,63 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::table_info::TableInfoDb;
+use anyhow::Result;
+use aptos_config::config::RocksdbConfig;
+use aptos_temppath::TempPath;
+use aptos_types::{transaction::Version, write_set::WriteSet};
+use std::sync::Arc;
+use storage_interface::DbReader;
+
+/// A ledger of transactions writing nothing
+struct MockDbReader {
+    num_transactions: Version,
+}
+
+impl DbReader for MockDbReader {
+    fn get_latest_version_option(&self) -> Result<Option<Version>> {
+        Ok(self.num_transactions.checked_sub(1))
+    }
+
+    fn get_latest_version(&self) -> Result<Version> {
+        Ok(self.num_transactions - 1)
+    }
+
+    fn get_write_sets(
+        &self,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<Vec<WriteSet>> {
+        assert!(end_version <= self.num_transactions);
+        Ok(vec![
+            WriteSet::default();
+            (end_version - start_version) as usize
+        ])
+    }
+}
+
+#[test]
+fn test_parse_in_batches() {
+    let tmp_dir = TempPath::new();
+    let db = TableInfoDb::open(&tmp_dir, RocksdbConfig::default()).unwrap();
+    let reader: Arc<dyn DbReader> = Arc::new(MockDbReader {
+        num_transactions: 25,
+    });
+
+    assert_eq!(db.parse_next_batch(reader.clone(), 10).unwrap(), 10);
+    assert_eq!(db.parse_next_batch(reader.clone(), 10).unwrap(), 10);
+    assert_eq!(db.parse_next_batch(reader.clone(), 10).unwrap(), 5);
+    assert_eq!(db.parse_next_batch(reader.clone(), 10).unwrap(), 0);
+    assert_eq!(db.next_version(), 25);
+
+    // The progress is persisted.
+    drop(db);
+    let db = TableInfoDb::open(&tmp_dir, RocksdbConfig::default()).unwrap();
+    assert_eq!(db.next_version(), 25);
+
+    // Transactions must be parsed in order.
+    let write_set = WriteSet::default();
+    db.index(reader.clone(), 10, &[&write_set]).unwrap();
+    assert_eq!(db.next_version(), 25);
+    assert!(db.index(reader, 30, &[&write_set]).is_err());
+}
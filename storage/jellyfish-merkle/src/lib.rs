@@ -97,7 +97,7 @@ use proptest::arbitrary::Arbitrary;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
     hash::Hash,
@@ -145,10 +145,32 @@ pub trait TreeWriter<K>: Send + Sync {
 }
 
 pub trait StateValueWriter<K, V>: Send + Sync {
-    /// Writes a kv batch into storage.
-    fn write_kv_batch(&self, kv_batch: &StateValueBatch<K, Option<V>>) -> Result<()>;
+    /// Writes a kv batch of the state snapshot restored at `version` into storage, atomically with
+    /// the progress of the restore once the batch is written.
+    fn write_kv_batch(
+        &self,
+        version: Version,
+        kv_batch: &StateValueBatch<K, Option<V>>,
+        progress: StateSnapshotProgress,
+    ) -> Result<()>;
 
     fn write_usage(&self, version: Version, items: usize, total_bytes: usize) -> Result<()>;
+
+    /// Gets the progress of the restore of the state snapshot at `version`, or `None` if no kv
+    /// batch of it was written.
+    fn get_progress(&self, version: Version) -> Result<Option<StateSnapshotProgress>>;
+}
+
+/// How far the state values of a state snapshot have been restored, so that an interrupted restore
+/// resumes after the last key written.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateSnapshotProgress {
+    /// The hash of the last key written. Keys are restored in the order of their hashes.
+    pub key_hash: HashValue,
+    /// The number of state items written so far
+    pub num_items: usize,
+    /// The total size of the keys and values written so far
+    pub total_bytes: usize,
 }
 
 pub trait Key: Clone + Serialize + DeserializeOwned + Send + Sync {
@@ -12,7 +12,7 @@ use crate::{
         get_child_and_sibling_half_start, Child, Children, InternalNode, LeafNode, Node, NodeKey,
         NodeType,
     },
-    NibbleExt, StateValueWriter, TreeReader, TreeWriter, ROOT_NIBBLE_HEIGHT,
+    NibbleExt, StateSnapshotProgress, StateValueWriter, TreeReader, TreeWriter, ROOT_NIBBLE_HEIGHT,
 };
 use anyhow::{ensure, Result};
 use aptos_crypto::{
@@ -30,6 +30,7 @@ use aptos_types::{
 };
 use itertools::Itertools;
 use mirai_annotations::*;
+use rayon::prelude::*;
 use std::{cmp::Eq, collections::HashMap, hash::Hash, sync::Arc};
 use storage_interface::StateSnapshotReceiver;
 
@@ -173,21 +174,19 @@ where
         expected_root_hash: HashValue,
     ) -> Result<Self> {
         let tree_reader = Arc::clone(&store);
-        let (partial_nodes, previous_leaf) =
-            if let Some((node_key, leaf_node)) = tree_reader.get_rightmost_leaf()? {
-                // TODO: confirm rightmost leaf is at the desired version
-                // If the system crashed in the middle of the previous restoration attempt, we need
-                // to recover the partial nodes to the state right before the crash.
-                (
-                    Self::recover_partial_nodes(tree_reader.as_ref(), version, node_key)?,
-                    Some(leaf_node),
-                )
-            } else {
-                (
-                    vec![InternalInfo::new_empty(NodeKey::new_empty_path(version))],
-                    None,
-                )
-            };
+        let (partial_nodes, previous_leaf) = match tree_reader.get_rightmost_leaf()? {
+            // If the system crashed in the middle of the previous restoration attempt, we need
+            // to recover the partial nodes to the state right before the crash. A leaf at another
+            // version belongs to another tree, which is not resumed.
+            Some((node_key, leaf_node)) if node_key.version() == version => (
+                Self::recover_partial_nodes(tree_reader.as_ref(), version, node_key)?,
+                Some(leaf_node),
+            ),
+            _ => (
+                vec![InternalInfo::new_empty(NodeKey::new_empty_path(version))],
+                None,
+            ),
+        };
 
         Ok(Self {
             store,
@@ -216,6 +215,12 @@ where
         })
     }
 
+    /// Returns the hash of the last key added to the tree, including by a previous restoration
+    /// attempt this one resumes. Keys up to it are skipped if they are added again.
+    pub fn previous_key_hash(&self) -> Option<HashValue> {
+        self.previous_leaf.as_ref().map(|leaf| leaf.account_key())
+    }
+
     /// Recovers partial nodes from storage. We do this by looking at all the ancestors of the
     /// rightmost leaf. The ones do not exist in storage are the partial nodes.
     fn recover_partial_nodes(
@@ -721,30 +726,68 @@ where
 struct StateValueRestore<K, V> {
     version: Version,
     db: Arc<dyn StateValueWriter<K, V>>,
+    /// The hash of the last key written, including by a previous restoration attempt.
+    previous_key_hash: Option<HashValue>,
     num_items: usize,
     total_bytes: usize,
 }
 
 impl<K: crate::Key + Hash + Eq, V: crate::Value> StateValueRestore<K, V> {
-    pub fn new<D: 'static + StateValueWriter<K, V>>(db: Arc<D>, version: Version) -> Self {
+    /// Resumes the restore of the state values at `version` from its persisted progress, if any.
+    pub fn new<D: 'static + StateValueWriter<K, V>>(db: Arc<D>, version: Version) -> Result<Self> {
+        let progress = db.get_progress(version)?;
+        Ok(Self {
+            version,
+            db,
+            previous_key_hash: progress.map(|p| p.key_hash),
+            num_items: progress.map_or(0, |p| p.num_items),
+            total_bytes: progress.map_or(0, |p| p.total_bytes),
+        })
+    }
+
+    pub fn new_overwrite<D: 'static + StateValueWriter<K, V>>(
+        db: Arc<D>,
+        version: Version,
+    ) -> Self {
         Self {
             version,
             db,
+            previous_key_hash: None,
             num_items: 0,
             total_bytes: 0,
         }
     }
 
-    pub fn add_chunk(&mut self, chunk: Vec<(K, V)>) -> Result<()> {
-        let kv_batch = chunk
-            .into_iter()
-            .map(|(k, v)| {
-                self.num_items += 1;
-                self.total_bytes += k.key_size() + v.value_size();
-                ((k, self.version), Some(v))
-            })
-            .collect();
-        self.db.write_kv_batch(&kv_batch)
+    /// Writes the values of a chunk, given with the hashes of their keys. Values already written
+    /// by a previous restoration attempt are skipped.
+    pub fn add_chunk(&mut self, chunk: Vec<(K, V)>, key_hashes: &[HashValue]) -> Result<()> {
+        let mut kv_batch = HashMap::new();
+        let mut last_key_hash = None;
+        for ((k, v), key_hash) in chunk.into_iter().zip(key_hashes) {
+            if let Some(previous_key_hash) = self.previous_key_hash {
+                if *key_hash <= previous_key_hash {
+                    continue;
+                }
+            }
+            self.num_items += 1;
+            self.total_bytes += k.key_size() + v.value_size();
+            kv_batch.insert((k, self.version), Some(v));
+            last_key_hash = Some(*key_hash);
+        }
+
+        if let Some(key_hash) = last_key_hash {
+            self.db.write_kv_batch(
+                self.version,
+                &kv_batch,
+                StateSnapshotProgress {
+                    key_hash,
+                    num_items: self.num_items,
+                    total_bytes: self.total_bytes,
+                },
+            )?;
+            self.previous_key_hash = Some(key_hash);
+        }
+        Ok(())
     }
 
     pub fn finish(self) -> Result<()> {
@@ -771,7 +814,7 @@ impl<K: crate::Key + CryptoHash + Hash + Eq, V: crate::Value> StateSnapshotResto
                 version,
                 expected_root_hash,
             )?,
-            kv_restore: StateValueRestore::new(Arc::clone(value_store), version),
+            kv_restore: StateValueRestore::new(Arc::clone(value_store), version)?,
         })
     }
 
@@ -787,20 +830,35 @@ impl<K: crate::Key + CryptoHash + Hash + Eq, V: crate::Value> StateSnapshotResto
                 version,
                 expected_root_hash,
             )?,
-            kv_restore: StateValueRestore::new(Arc::clone(value_store), version),
+            kv_restore: StateValueRestore::new_overwrite(Arc::clone(value_store), version),
         })
     }
+
+    /// Returns the hash of the last key restored, by this or a previous restoration attempt.
+    /// Chunks ending at or before it don't need to be added again.
+    pub fn previous_key_hash(&self) -> Option<HashValue> {
+        // The values of a chunk are written before its leaves, so the tree is never ahead.
+        self.tree_restore.previous_key_hash()
+    }
 }
 
 impl<K: crate::Key + CryptoHash + Hash + Eq, V: crate::Value> StateSnapshotReceiver<K, V>
     for StateSnapshotRestore<K, V>
 {
     fn add_chunk(&mut self, chunk: Vec<(K, V)>, proof: SparseMerkleRangeProof) -> Result<()> {
+        let key_hashes: Vec<HashValue> = chunk.par_iter().map(|(k, _v)| k.hash()).collect();
         // Write KV out first because we are likely to resume according to the rightmost key in the
-        // tree after crashing.
-        self.kv_restore.add_chunk(chunk.clone())?;
-        self.tree_restore
-            .add_chunk_impl(chunk.iter().map(|(k, v)| (k, v.hash())).collect(), proof)?;
+        // tree after crashing. The values are hashed for the tree meanwhile.
+        let kv_restore = &mut self.kv_restore;
+        let (kv_res, value_hashes) = rayon::join(
+            || kv_restore.add_chunk(chunk.clone(), &key_hashes),
+            || chunk.par_iter().map(|(_k, v)| v.hash()).collect::<Vec<_>>(),
+        );
+        kv_res?;
+        self.tree_restore.add_chunk_impl(
+            chunk.iter().map(|(k, _v)| k).zip(value_hashes).collect(),
+            proof,
+        )?;
         Ok(())
     }
 
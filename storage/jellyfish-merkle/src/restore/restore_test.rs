@@ -6,21 +6,26 @@ use crate::{
     node_type::{LeafNode, Node, NodeKey},
     restore::StateSnapshotRestore,
     test_helper::{init_mock_db, ValueBlob},
-    JellyfishMerkleTree, NodeBatch, StateValueBatch, StateValueWriter, TestKey, TestValue,
-    TreeReader, TreeWriter,
+    JellyfishMerkleTree, Key, NodeBatch, StateSnapshotProgress, StateValueBatch, StateValueWriter,
+    TestKey, TestValue, TreeReader, TreeWriter, Value,
 };
 use anyhow::Result;
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_infallible::RwLock;
 use aptos_types::transaction::Version;
 use proptest::{collection::btree_map, prelude::*};
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 use storage_interface::StateSnapshotReceiver;
 
 #[derive(Default)]
 struct MockSnapshotStore<K: TestKey, V: TestValue> {
     tree_store: MockTreeStore<K>,
     kv_store: RwLock<BTreeMap<(K, Version), V>>,
+    progress: RwLock<HashMap<Version, StateSnapshotProgress>>,
+    usage: RwLock<HashMap<Version, (usize, usize)>>,
 }
 
 impl<K, V> MockSnapshotStore<K, V>
@@ -32,6 +37,8 @@ where
         Self {
             tree_store: MockTreeStore::new(overwrite),
             kv_store: RwLock::new(BTreeMap::default()),
+            progress: RwLock::new(HashMap::default()),
+            usage: RwLock::new(HashMap::default()),
         }
     }
 
@@ -45,7 +52,12 @@ where
     K: TestKey,
     V: TestValue,
 {
-    fn write_kv_batch(&self, kv_batch: &StateValueBatch<K, Option<V>>) -> Result<()> {
+    fn write_kv_batch(
+        &self,
+        version: Version,
+        kv_batch: &StateValueBatch<K, Option<V>>,
+        progress: StateSnapshotProgress,
+    ) -> Result<()> {
         for (k, v) in kv_batch {
             if let Some(v) = v {
                 self.kv_store.write().insert(k.clone(), v.clone());
@@ -53,12 +65,18 @@ where
                 self.kv_store.write().remove(k);
             }
         }
+        self.progress.write().insert(version, progress);
         Ok(())
     }
 
-    fn write_usage(&self, _version: Version, _items: usize, _total_bytes: usize) -> Result<()> {
+    fn write_usage(&self, version: Version, items: usize, total_bytes: usize) -> Result<()> {
+        self.usage.write().insert(version, (items, total_bytes));
         Ok(())
     }
+
+    fn get_progress(&self, version: Version) -> Result<Option<StateSnapshotProgress>> {
+        Ok(self.progress.read().get(&version).copied())
+    }
 }
 
 impl<K, V> TreeReader<K> for MockSnapshotStore<K, V>
@@ -104,6 +122,8 @@ where
         MockSnapshotStore {
             tree_store,
             kv_store: RwLock::new(kv_store),
+            progress: RwLock::new(HashMap::default()),
+            usage: RwLock::new(HashMap::default()),
         },
         version,
     )
@@ -144,6 +164,7 @@ proptest! {
         let tree = JellyfishMerkleTree::new(&db);
         let expected_root_hash = tree.get_root_hash(version).unwrap();
         let batch1: Vec<_> = all.clone().into_iter().take(batch1_size).collect();
+        let batch1_last_key = batch1.last().map(|(key, _value)| *key);
 
         let restore_db = Arc::new(MockSnapshotStore::default());
         {
@@ -161,6 +182,7 @@ proptest! {
 
             let mut restore =
                 StateSnapshotRestore::new(&restore_db, &restore_db,  version, expected_root_hash ).unwrap();
+            prop_assert_eq!(restore.previous_key_hash(), batch1_last_key);
             let proof = tree
                 .get_range_proof(
                     remaining_accounts.last().map(|(h, _)| *h).unwrap(),
@@ -175,6 +197,11 @@ proptest! {
         }
 
         assert_success(&restore_db, expected_root_hash, &all, version);
+        // The values written before the interruption are counted once.
+        let expected_usage = all.values().fold((0, 0), |(items, bytes), (k, v)| {
+            (items + 1, bytes + k.key_size() + v.value_size())
+        });
+        prop_assert_eq!(restore_db.usage.read().get(&version).copied(), Some(expected_usage));
     }
 
     #[test]
@@ -0,0 +1,65 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView, StateViewId};
+use aptos_types::state_store::state_key::StateKey;
+use std::{collections::HashMap, sync::RwLock};
+
+/// Keeps the values read from `base` in memory, so that each key is only read from it once.
+/// Meant for slow views of a fixed state, e.g. a `RestStateView`.
+pub struct CachingStateView<S> {
+    base: S,
+    /// The values read from `base`, by key. `None` if nothing is stored.
+    cache: RwLock<HashMap<StateKey, Option<Vec<u8>>>>,
+}
+
+impl<S: StateView> CachingStateView<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn base(&self) -> &S {
+        &self.base
+    }
+
+    /// The number of keys read so far
+    pub fn len(&self) -> usize {
+        self.cache.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S: StateView> StateView for CachingStateView<S> {
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        if let Some(value) = self.cache.read().unwrap().get(state_key) {
+            return Ok(value.clone());
+        }
+        // Concurrent misses on the same key may read it twice, which is harmless as the state
+        // doesn't change
+        let value = self.base.get_state_value(state_key)?;
+        self.cache
+            .write()
+            .unwrap()
+            .insert(state_key.clone(), value.clone());
+        Ok(value)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Layers which wrap a [`StateView`](aptos_state_view::StateView) to change how it's read, and
+//! compose with each other, e.g. to run transactions against the state of a network with local
+//! changes on top:
+//!
+//! ```ignore
+//! let state = OverlayStateView::new(CachingStateView::new(RestStateView::new(
+//!     client, runtime, version,
+//! )));
+//! state.set_state_value(state_key, Some(value));
+//! let view = RecordingStateView::new(&state);
+//! AptosVM::simulate_signed_transaction(&txn, &view);
+//! let reads = view.into_reads();
+//! ```
+
+mod caching;
+mod overlay;
+mod recording;
+mod rest;
+
+pub use crate::{
+    caching::CachingStateView, overlay::OverlayStateView, recording::RecordingStateView,
+    rest::RestStateView,
+};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView, StateViewId};
+use aptos_types::{
+    state_store::state_key::StateKey,
+    write_set::{WriteOp, WriteSet},
+};
+use std::{collections::HashMap, sync::RwLock};
+
+/// A view of `base` with local changes on top, which are never written to `base`
+pub struct OverlayStateView<S> {
+    base: S,
+    /// The values changed locally, by key. `None` if the value was removed.
+    changes: RwLock<HashMap<StateKey, Option<Vec<u8>>>>,
+}
+
+impl<S: StateView> OverlayStateView<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            changes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn base(&self) -> &S {
+        &self.base
+    }
+
+    /// Overrides the value stored under `state_key`, or removes it with `None`
+    pub fn set_state_value(&self, state_key: StateKey, value: Option<Vec<u8>>) {
+        self.changes.write().unwrap().insert(state_key, value);
+    }
+
+    /// Applies the writes of a transaction on top of the view
+    pub fn apply_write_set(&self, write_set: &WriteSet) {
+        let mut changes = self.changes.write().unwrap();
+        for (state_key, write_op) in write_set {
+            let value = match write_op {
+                WriteOp::Creation(bytes) | WriteOp::Modification(bytes) => Some(bytes.clone()),
+                WriteOp::Deletion => None,
+            };
+            changes.insert(state_key.clone(), value);
+        }
+    }
+
+    /// Drops the local changes
+    pub fn reset(&self) {
+        self.changes.write().unwrap().clear();
+    }
+}
+
+impl<S: StateView> StateView for OverlayStateView<S> {
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        if let Some(value) = self.changes.read().unwrap().get(state_key) {
+            return Ok(value.clone());
+        }
+        self.base.get_state_value(state_key)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        // The local changes aren't accounted for
+        self.base.get_usage()
+    }
+}
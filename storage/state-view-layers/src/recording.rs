@@ -0,0 +1,56 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView, StateViewId};
+use aptos_types::state_store::state_key::StateKey;
+use std::{collections::HashMap, sync::Mutex};
+
+/// Records the values read from `base`, e.g. to tell what a transaction depends on, or to
+/// replay it later from the recorded values alone
+pub struct RecordingStateView<S> {
+    base: S,
+    /// The values read, by key. `None` if nothing is stored.
+    reads: Mutex<HashMap<StateKey, Option<Vec<u8>>>>,
+}
+
+impl<S: StateView> RecordingStateView<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            reads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The values read so far, by key
+    pub fn reads(&self) -> HashMap<StateKey, Option<Vec<u8>>> {
+        self.reads.lock().unwrap().clone()
+    }
+
+    pub fn into_reads(self) -> HashMap<StateKey, Option<Vec<u8>>> {
+        self.reads.into_inner().unwrap()
+    }
+}
+
+impl<S: StateView> StateView for RecordingStateView<S> {
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        let value = self.base.get_state_value(state_key)?;
+        self.reads
+            .lock()
+            .unwrap()
+            .insert(state_key.clone(), value.clone());
+        Ok(value)
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.base.is_genesis()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_rest_client::Client;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView};
+use aptos_types::{state_store::state_key::StateKey, transaction::Version};
+use tokio::runtime::Handle;
+
+/// The state at `version`, read from a node's REST API. Every read is a request, so this is
+/// usually wrapped in a `CachingStateView`.
+///
+/// The REST client is async, so requests are run on the given runtime. The view must be read
+/// from outside of the runtime's worker threads, e.g. from `tokio::task::spawn_blocking`.
+pub struct RestStateView {
+    client: Client,
+    runtime: Handle,
+    version: Version,
+}
+
+impl RestStateView {
+    pub fn new(client: Client, runtime: Handle, version: Version) -> Self {
+        Self {
+            client,
+            runtime,
+            version,
+        }
+    }
+
+    /// The state at the latest version of the node
+    pub async fn latest(client: Client) -> Result<Self> {
+        let version = client.get_ledger_information().await?.into_inner().version;
+        Ok(Self::new(client, Handle::current(), version))
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl StateView for RestStateView {
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        self.runtime
+            .block_on(self.client.get_raw_state_value(state_key, self.version))
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
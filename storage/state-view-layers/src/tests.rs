@@ -0,0 +1,71 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CachingStateView, OverlayStateView, RecordingStateView};
+use anyhow::Result;
+use aptos_state_view::{state_storage_usage::StateStorageUsage, StateView};
+use aptos_types::{
+    state_store::state_key::StateKey,
+    write_set::{WriteOp, WriteSetMut},
+};
+use std::{collections::HashMap, sync::Mutex};
+
+/// A fixed state, which counts how many times it's read
+#[derive(Default)]
+struct MockStateView {
+    state: HashMap<StateKey, Vec<u8>>,
+    reads: Mutex<usize>,
+}
+
+impl StateView for MockStateView {
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        *self.reads.lock().unwrap() += 1;
+        Ok(self.state.get(state_key).cloned())
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
+
+fn key(name: &str) -> StateKey {
+    StateKey::Raw(name.as_bytes().to_vec())
+}
+
+#[test]
+fn test_layers() {
+    let mut base = MockStateView::default();
+    base.state.insert(key("a"), vec![1]);
+    base.state.insert(key("b"), vec![2]);
+    let state = OverlayStateView::new(CachingStateView::new(base));
+
+    state.set_state_value(key("c"), Some(vec![3]));
+    state.apply_write_set(
+        &WriteSetMut::new(vec![(key("b"), WriteOp::Deletion)])
+            .freeze()
+            .unwrap(),
+    );
+
+    let view = RecordingStateView::new(&state);
+    for _ in 0..2 {
+        assert_eq!(view.get_state_value(&key("a")).unwrap(), Some(vec![1]));
+        assert_eq!(view.get_state_value(&key("b")).unwrap(), None);
+        assert_eq!(view.get_state_value(&key("c")).unwrap(), Some(vec![3]));
+        assert_eq!(view.get_state_value(&key("d")).unwrap(), None);
+    }
+    let reads = view.into_reads();
+    assert_eq!(reads.len(), 4);
+    assert_eq!(reads[&key("c")], Some(vec![3]));
+
+    // Only the keys which aren't overridden are read from the base, once
+    assert_eq!(state.base().len(), 2);
+    assert_eq!(*state.base().base().reads.lock().unwrap(), 2);
+
+    state.reset();
+    assert_eq!(state.get_state_value(&key("b")).unwrap(), Some(vec![2]));
+    assert_eq!(state.get_state_value(&key("c")).unwrap(), None);
+}
@@ -0,0 +1,118 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Async access to a `DbReader` for callers running on an async runtime, like the API. Reads run on
+//! a bounded pool of blocking threads so that slow disk reads don't block the runtime's workers,
+//! and fail once their deadline passes.
+
+use crate::{metrics::TIMER, DbReader};
+use anyhow::{format_err, Result};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    sync::{oneshot, Semaphore},
+    time::{timeout_at, Instant},
+};
+
+pub struct AsyncDbReader {
+    reader: Arc<dyn DbReader>,
+    pool: rayon::ThreadPool,
+    pending_reads: Arc<Semaphore>,
+    read_timeout: Duration,
+}
+
+impl AsyncDbReader {
+    /// `max_pending_reads` bounds the reads queued or running on the `num_threads` threads of the
+    /// pool, further reads wait for one of them to complete.
+    pub fn new(
+        reader: Arc<dyn DbReader>,
+        num_threads: usize,
+        max_pending_reads: usize,
+        read_timeout: Duration,
+    ) -> Self {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("async_db_reader_{}", index))
+            .build()
+            .expect("Failed to create the storage read pool.");
+
+        Self {
+            reader,
+            pool,
+            pending_reads: Arc::new(Semaphore::new(max_pending_reads)),
+            read_timeout,
+        }
+    }
+
+    pub fn reader(&self) -> &Arc<dyn DbReader> {
+        &self.reader
+    }
+
+    /// The number of reads which can be queued before further reads have to wait for a slot.
+    pub fn available_read_slots(&self) -> usize {
+        self.pending_reads.available_permits()
+    }
+
+    /// Runs `read` on the pool. Fails if the read doesn't complete within the read timeout,
+    /// including the time spent waiting for a slot in the pool.
+    ///
+    /// Dropping the returned future, or it timing out, cancels the read if it hasn't started yet.
+    /// A started read can't be interrupted, it runs to completion and its result is discarded.
+    pub async fn read<T, F>(&self, name: &'static str, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn DbReader) -> Result<T> + Send + 'static,
+    {
+        let _timer = TIMER.with_label_values(&[name]).start_timer();
+        let deadline = Instant::now() + self.read_timeout;
+
+        let permit = timeout_at(deadline, self.pending_reads.clone().acquire_owned())
+            .await
+            .map_err(|_| {
+                format_err!(
+                    "Storage read {} timed out after {:?} waiting for a slot.",
+                    name,
+                    self.read_timeout,
+                )
+            })?
+            .expect("The semaphore is never closed.");
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let _cancel_on_drop = CancelOnDrop(cancelled.clone());
+        let (sender, receiver) = oneshot::channel();
+        let reader = self.reader.clone();
+        self.pool.spawn(move || {
+            let _permit = permit;
+            if !cancelled.load(Ordering::Acquire) {
+                // The receiver is gone if the read timed out meanwhile.
+                let _ = sender.send(read(reader.as_ref()));
+            }
+        });
+
+        timeout_at(deadline, receiver)
+            .await
+            .map_err(|_| {
+                format_err!(
+                    "Storage read {} timed out after {:?}.",
+                    name,
+                    self.read_timeout,
+                )
+            })?
+            .map_err(|_| format_err!("Storage read {} was dropped.", name))?
+    }
+}
+
+/// Flags a read as cancelled when the future waiting for it is done, so that it is skipped if it
+/// hasn't started yet.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
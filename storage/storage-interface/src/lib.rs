@@ -28,15 +28,18 @@ use aptos_types::{
         state_value::{StateValue, StateValueChunkWithProof},
     },
     transaction::{
-        AccountTransactionsWithProof, TransactionInfo, TransactionListWithProof,
-        TransactionOutputListWithProof, TransactionToCommit, TransactionWithProof, Version,
+        AccountActivity, AccountTransactionsWithProof, TransactionAuxiliaryData, TransactionInfo,
+        TransactionListWithProof, TransactionOutputListWithProof, TransactionToCommit,
+        TransactionWithProof, Version,
     },
     write_set::WriteSet,
 };
+use move_deps::move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
 
+pub mod async_db_reader;
 pub mod async_proof_fetcher;
 pub mod cached_state_view;
 mod executed_trees;
@@ -193,6 +196,17 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// See [`AptosDB::get_transaction_auxiliary_data_by_version`].
+    ///
+    /// [`AptosDB::get_transaction_auxiliary_data_by_version`]:
+    /// ../aptosdb/struct.AptosDB.html#method.get_transaction_auxiliary_data_by_version
+    fn get_transaction_auxiliary_data_by_version(
+        &self,
+        version: Version,
+    ) -> Result<Option<TransactionAuxiliaryData>> {
+        unimplemented!()
+    }
+
     /// Returns events by given event key
     fn get_events(
         &self,
@@ -205,6 +219,20 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns up to `limit` events of type `type_tag` emitted by transactions with a version in
+    /// [`start_version`, `end_version`), in ascending order. Will ignore events with a version
+    /// greater than `ledger_version`.
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        end_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        unimplemented!()
+    }
+
     /// See [AptosDB::get_block_timestamp].
     ///
     /// [AptosDB::get_block_timestamp]:
@@ -243,6 +271,16 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Gets the version of the first transaction of the first block committed at or after
+    /// timestamp, or `None` if no such block has been committed yet.
+    fn get_first_version_at_or_after_timestamp(
+        &self,
+        timestamp: u64,
+        ledger_version: Version,
+    ) -> Result<Option<Version>> {
+        unimplemented!()
+    }
+
     /// See [AptosDB::get_latest_account_state].
     ///
     /// [AptosDB::get_latest_account_state]:
@@ -496,7 +534,7 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
-    /// Get table info from the internal indexer.
+    /// Get table info from the internal indexer, or the table info service.
     fn get_table_info(&self, handle: TableHandle) -> Result<TableInfo> {
         unimplemented!()
     }
@@ -506,6 +544,40 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns whether table infos are available, from the internal indexer or the table info
+    /// service. The latter parses them asynchronously, so the info of a recent table may be
+    /// missing.
+    fn table_info_enabled(&self) -> bool {
+        self.indexer_enabled()
+    }
+
+    /// Returns the versions of up to `limit` transactions related to the account with `address`,
+    /// starting at `start_version`, from the internal indexer. A transaction is related to an
+    /// account if it writes to a resource or module of the account or emits an event to one of its
+    /// event handles. Will ignore versions greater than `ledger_version`.
+    fn get_account_related_versions(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        unimplemented!()
+    }
+
+    /// Returns the activities of up to `limit` transactions sent by the account with `address`,
+    /// starting at `start_version`, from the internal indexer. Will ignore versions greater than
+    /// `ledger_version`.
+    fn get_account_activities(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<AccountActivity>> {
+        unimplemented!()
+    }
+
     /// Returns state storage usage at the end of an epoch.
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         unimplemented!()
@@ -15,8 +15,10 @@ use std::{
 };
 
 mod cargo;
+mod network_builder;
 mod node;
 mod swarm;
+pub use network_builder::LocalNetworkBuilder;
 pub use node::LocalNode;
 pub use swarm::{LocalSwarm, SwarmDirectory};
 
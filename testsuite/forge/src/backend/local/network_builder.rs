@@ -0,0 +1,199 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{LocalFactory, LocalSwarm, LocalVersion, NodeExt, Result, Swarm, Version};
+use anyhow::{anyhow, Context};
+use aptos_config::config::NodeConfig;
+use aptos_genesis::builder::{InitConfigFn, InitGenesisConfigFn};
+use framework::ReleaseBundle;
+use once_cell::sync::OnceCell;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How long validator fullnodes have to catch up with their validator after being started
+const FULLNODE_STARTUP_TIMEOUT_SECS: u64 = 60;
+
+/// Builds and launches a network of local `aptos-node` processes, e.g. to run the integration
+/// tests of a protocol built on Aptos against real validators.
+///
+/// By default the `aptos-node` binary is built from the current workspace, with the
+/// `failpoints` feature, and each node runs in a temporary directory which is removed when the
+/// swarm is dropped.
+///
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// use forge::{LocalNetworkBuilder, Node};
+///
+/// let swarm = LocalNetworkBuilder::new(4)
+///     .with_validator_fullnodes()
+///     .with_init_genesis_config(std::sync::Arc::new(|genesis_config| {
+///         genesis_config.epoch_duration_secs = 60;
+///     }))
+///     .with_failpoint("consensus::send_proposal", "5%return")
+///     .build()
+///     .await?;
+/// let client = swarm.validators().next().unwrap().rest_client();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Faults can also be injected while the network runs: stop and restart nodes with
+/// `LocalNode::stop` and `LocalNode::start`, or set failpoints through the REST API of a node
+/// with `Client::set_failpoint`, which the builder always enables. Besides the failpoints of
+/// each component, network faults can be injected with `network::partition` and
+/// `network::send::<protocol>` (see `network::fault_injection`), and disk write stalls with
+/// `aptosdb::save_transactions::write`.
+pub struct LocalNetworkBuilder {
+    num_validators: usize,
+    validator_fullnodes: bool,
+    genesis_framework: Option<ReleaseBundle>,
+    init_config: Option<InitConfigFn>,
+    init_genesis_config: Option<InitGenesisConfigFn>,
+    failpoints: HashMap<String, String>,
+    node_binary: Option<PathBuf>,
+    dir: Option<PathBuf>,
+    seed: Option<[u8; 32]>,
+}
+
+impl LocalNetworkBuilder {
+    pub fn new(num_validators: usize) -> Self {
+        Self {
+            num_validators,
+            validator_fullnodes: false,
+            genesis_framework: None,
+            init_config: None,
+            init_genesis_config: None,
+            failpoints: HashMap::new(),
+            node_binary: None,
+            dir: None,
+            seed: None,
+        }
+    }
+
+    /// Starts a fullnode next to each validator
+    pub fn with_validator_fullnodes(mut self) -> Self {
+        self.validator_fullnodes = true;
+        self
+    }
+
+    /// Overrides the framework published at genesis, e.g. with a locally modified one
+    pub fn with_framework(mut self, genesis_framework: ReleaseBundle) -> Self {
+        self.genesis_framework = Some(genesis_framework);
+        self
+    }
+
+    /// Customizes the config of each validator, which is called with its index
+    pub fn with_init_config(mut self, init_config: InitConfigFn) -> Self {
+        self.init_config = Some(init_config);
+        self
+    }
+
+    /// Customizes genesis, e.g. the epoch duration or the validator set limits
+    pub fn with_init_genesis_config(mut self, init_genesis_config: InitGenesisConfigFn) -> Self {
+        self.init_genesis_config = Some(init_genesis_config);
+        self
+    }
+
+    /// Configures the failpoint `name` with `actions` (e.g. `10%return` or `sleep(100)`) on
+    /// every node, from the start. The node binary must be built with the `failpoints` feature.
+    pub fn with_failpoint(mut self, name: &str, actions: &str) -> Self {
+        self.failpoints
+            .insert(name.to_string(), actions.to_string());
+        self
+    }
+
+    /// Runs the given `aptos-node` binary instead of building one from the workspace
+    pub fn with_node_binary(mut self, node_binary: PathBuf) -> Self {
+        self.node_binary = Some(node_binary);
+        self
+    }
+
+    /// Keeps the nodes' data, configs and logs in `dir`, which is emptied first
+    pub fn with_dir(mut self, dir: PathBuf) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Derives the nodes' keys from `seed`, so that the network is the same on every run
+    pub fn with_seed(mut self, seed: [u8; 32]) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Launches the network, and returns once every node is healthy
+    pub async fn build(self) -> Result<LocalSwarm> {
+        let num_validators = NonZeroUsize::new(self.num_validators)
+            .ok_or_else(|| anyhow!("A network needs at least one validator"))?;
+        let versions = match self.node_binary {
+            Some(node_binary) => {
+                let version = Version::new(0, "custom".to_string());
+                let mut versions = HashMap::new();
+                versions.insert(version.clone(), LocalVersion::new(node_binary, version));
+                Arc::new(versions)
+            }
+            None => workspace_versions()?,
+        };
+        let version = versions
+            .keys()
+            .max()
+            .expect("There's always a version")
+            .clone();
+
+        let failpoints = (!self.failpoints.is_empty()).then(|| self.failpoints);
+        let mut fullnode_config = NodeConfig::default_for_validator_full_node();
+        fullnode_config.api.failpoints_enabled = true;
+        fullnode_config.failpoints = failpoints.clone();
+        let init_config = self.init_config;
+        let init_config: InitConfigFn = Arc::new(move |index, config, genesis_stake_amount| {
+            config.api.failpoints_enabled = true;
+            config.failpoints = failpoints.clone();
+            if let Some(init_config) = &init_config {
+                (init_config)(index, config, genesis_stake_amount);
+            }
+        });
+        let rng = match self.seed {
+            Some(seed) => StdRng::from_seed(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let mut swarm = LocalSwarm::build(
+            rng,
+            num_validators,
+            versions,
+            Some(version.clone()),
+            Some(init_config),
+            self.init_genesis_config,
+            self.dir,
+            self.genesis_framework,
+        )?;
+        swarm
+            .launch()
+            .await
+            .with_context(|| format!("Swarm logs can be found here: {}", swarm.logs_location()))?;
+
+        if self.validator_fullnodes {
+            let validators: Vec<_> = swarm.validators().map(|v| v.peer_id()).collect();
+            for validator in validators {
+                swarm.add_validator_fullnode(&version, fullnode_config.clone(), validator)?;
+            }
+            let deadline = Instant::now() + Duration::from_secs(FULLNODE_STARTUP_TIMEOUT_SECS);
+            for fullnode in swarm.full_nodes_mut() {
+                fullnode.wait_until_healthy(deadline).await?;
+            }
+        }
+        Ok(swarm)
+    }
+}
+
+/// The `aptos-node` built from the workspace, which is only built once per process
+fn workspace_versions() -> Result<Arc<HashMap<Version, LocalVersion>>> {
+    static WORKSPACE_FACTORY: OnceCell<LocalFactory> = OnceCell::new();
+    let factory = WORKSPACE_FACTORY.get_or_try_init(LocalFactory::from_workspace)?;
+    Ok(factory.versions.clone())
+}
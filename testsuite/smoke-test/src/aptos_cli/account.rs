@@ -25,8 +25,8 @@ async fn test_account_flow() {
             1,
             transfer_amount,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
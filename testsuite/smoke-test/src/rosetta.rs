@@ -26,7 +26,7 @@ use aptos_rosetta::{
 };
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use forge::{LocalSwarm, Node, NodeExt};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 use std::{future::Future, time::Duration};
 use tokio::{task::JoinHandle, time::Instant};
@@ -60,6 +60,7 @@ pub async fn setup_test(
         tls_key_path: None,
         content_length_limit: None,
         failpoints_enabled: false,
+        ..ApiConfig::default()
     };
 
     // Start the server
@@ -69,6 +70,7 @@ pub async fn setup_test(
         Some(aptos_rest_client::Client::new(
             validator.rest_api_endpoint(),
         )),
+        HashSet::new(),
     )
     .await
     .unwrap();
@@ -170,8 +172,8 @@ async fn test_account_balance() {
             1,
             TRANSFER_AMOUNT,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -191,8 +193,8 @@ async fn test_account_balance() {
             0,
             TRANSFER_AMOUNT,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -226,6 +228,88 @@ async fn test_account_balance() {
     .unwrap();
 }
 
+/// Reconciles the balances rebuilt from events and transactions, which are used once the state
+/// has been pruned, against the balances read from the state
+#[tokio::test]
+async fn test_historical_balance_reconciliation() {
+    let (swarm, cli, _faucet, _rosetta_client) = setup_test(1, 2).await;
+    let account_1 = cli.account_id(0);
+    let account_2 = cli.account_id(1);
+    let validator = swarm.validators().next().unwrap();
+    let rest_client = validator.rest_client();
+
+    // Deposits, withdrawals, and gas for both successful and failed transactions
+    const TRANSFER_AMOUNT: u64 = 5000;
+    cli.fund_account(0, Some(DEFAULT_FUNDED_COINS))
+        .await
+        .unwrap();
+    cli.transfer_coins(0, 1, TRANSFER_AMOUNT, None)
+        .await
+        .unwrap();
+    cli.transfer_coins(1, 0, TRANSFER_AMOUNT / 2, None)
+        .await
+        .unwrap();
+    let _ = cli
+        .transfer_invalid_addr(0, TRANSFER_AMOUNT, None)
+        .await
+        .unwrap_err();
+
+    let latest_version = rest_client
+        .get_ledger_information()
+        .await
+        .unwrap()
+        .into_inner()
+        .version;
+    for account in [account_1, account_2] {
+        // Check every version the account was touched, and the one before it
+        let txns = rest_client
+            .get_account_transactions(account, None, None)
+            .await
+            .unwrap()
+            .into_inner();
+        let mut versions: Vec<u64> = txns
+            .iter()
+            .flat_map(|txn| {
+                let version = txn.version().unwrap();
+                [version - 1, version]
+            })
+            .collect();
+        versions.push(latest_version);
+
+        for version in versions {
+            let expected_balance = rest_client
+                .get_account_balance_at_version(account, version)
+                .await
+                .map(|balance| balance.into_inner().get())
+                .unwrap_or(0);
+            let (sequence_number, balances) =
+                aptos_rosetta::account::get_native_balance_from_history(
+                    &rest_client,
+                    account,
+                    version,
+                )
+                .await
+                .unwrap();
+            let balance = balances.values().next().unwrap().get();
+            assert_eq!(
+                expected_balance, balance,
+                "Balance of {} at version {} should reconcile",
+                account, version
+            );
+
+            let expected_sequence_number = txns
+                .iter()
+                .filter(|txn| txn.version().unwrap() <= version)
+                .count() as u64;
+            assert_eq!(
+                expected_sequence_number, sequence_number,
+                "Sequence number of {} at version {} should reconcile",
+                account, version
+            );
+        }
+    }
+}
+
 async fn account_has_balance(
     rosetta_client: &RosettaClient,
     chain_id: ChainId,
@@ -429,7 +513,9 @@ async fn parse_block_transactions(
                 assert_eq!(0, *current_version);
             }
             TransactionType::User => {}
-            TransactionType::BlockMetadata | TransactionType::StateCheckpoint => {
+            TransactionType::BlockMetadata
+            | TransactionType::StateCheckpoint
+            | TransactionType::Validator => {
                 assert!(transaction.operations.is_empty());
             }
         }
@@ -597,6 +683,24 @@ async fn parse_operations(
                     );
                 }
             }
+            OperationType::SetVoter
+            | OperationType::AddStake
+            | OperationType::UnlockStake
+            | OperationType::WithdrawStake => {
+                if actual_txn.success() {
+                    assert_eq!(
+                        OperationStatusType::Success,
+                        status,
+                        "Successful transaction should have successful staking operation"
+                    );
+                } else {
+                    assert_eq!(
+                        OperationStatusType::Failure,
+                        status,
+                        "Failed transaction should have failed staking operation"
+                    );
+                }
+            }
             OperationType::Fee => {
                 has_gas_op = true;
                 assert_eq!(OperationStatusType::Success, status);
@@ -718,8 +822,8 @@ async fn test_invalid_transaction_gas_charged() {
             0,
             TRANSFER_AMOUNT,
             Some(GasOptions {
-                gas_unit_price: DEFAULT_GAS_UNIT_PRICE * 2,
-                max_gas: DEFAULT_MAX_GAS,
+                gas_unit_price: Some(DEFAULT_GAS_UNIT_PRICE * 2),
+                max_gas: Some(DEFAULT_MAX_GAS),
             }),
         )
         .await
@@ -6,14 +6,11 @@ use aptos_config::{keys::ConfigKey, utils::get_available_port};
 use aptos_crypto::ed25519::Ed25519PrivateKey;
 use aptos_faucet::FaucetArgs;
 use aptos_genesis::builder::{InitConfigFn, InitGenesisConfigFn};
-use aptos_logger::info;
 use aptos_types::{account_config::aptos_test_root_address, chain_id::ChainId};
 use forge::Node;
-use forge::{Factory, LocalFactory, LocalSwarm};
+use forge::{LocalNetworkBuilder, LocalSwarm};
 use framework::ReleaseBundle;
-use once_cell::sync::Lazy;
-use rand::rngs::OsRng;
-use std::{num::NonZeroUsize, path::PathBuf, sync::Arc};
+use std::{num::NonZeroUsize, path::PathBuf};
 use tokio::task::JoinHandle;
 
 pub struct SwarmBuilder {
@@ -57,33 +54,21 @@ impl SwarmBuilder {
     // Gas is not enabled with this setup, it's enabled via forge instance.
     pub async fn build(self) -> LocalSwarm {
         ::aptos_logger::Logger::new().init();
-        info!("Preparing to finish compiling");
         // TODO change to return Swarm trait
         // Add support for forge
         assert!(self.local);
-        static FACTORY: Lazy<LocalFactory> = Lazy::new(|| LocalFactory::from_workspace().unwrap());
 
-        let version = FACTORY.versions().max().unwrap();
-
-        info!("Node finished compiling");
-
-        let init_genesis_config = self.init_genesis_config;
-
-        FACTORY
-            .new_swarm_with_version(
-                OsRng,
-                self.num_validators,
-                &version,
-                self.genesis_framework,
-                self.init_config,
-                Some(Arc::new(move |genesis_config| {
-                    if let Some(init_genesis_config) = &init_genesis_config {
-                        (init_genesis_config)(genesis_config);
-                    }
-                })),
-            )
-            .await
-            .unwrap()
+        let mut builder = LocalNetworkBuilder::new(self.num_validators.get());
+        if let Some(genesis_framework) = self.genesis_framework {
+            builder = builder.with_framework(genesis_framework);
+        }
+        if let Some(init_config) = self.init_config {
+            builder = builder.with_init_config(init_config);
+        }
+        if let Some(init_genesis_config) = self.init_genesis_config {
+            builder = builder.with_init_genesis_config(init_genesis_config);
+        }
+        builder.build().await.unwrap()
     }
 
     pub async fn build_with_cli(
@@ -98,6 +98,7 @@ async fn test_external_transaction_signer() {
                 function: _,
                 type_arguments: _,
                 arguments,
+                ..
             }) = user_txn.request.payload
             {
                 assert_eq!(
@@ -54,10 +54,17 @@ pub struct AccessPath {
     pub path: Vec<u8>,
 }
 
+/// The first byte of the path of a resource group, the BCS variant index of
+/// [`Path::ResourceGroup`], following `CODE_TAG` and `RESOURCE_TAG`.
+pub const RESOURCE_GROUP_TAG: u8 = 2;
+
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Ord, PartialOrd)]
 pub enum Path {
     Code(ModuleId),
     Resource(StructTag),
+    /// A resource group, whose members are stored together under the group's tag, see
+    /// [`crate::resource_group`]
+    ResourceGroup(StructTag),
 }
 
 impl AccessPath {
@@ -79,6 +86,17 @@ impl AccessPath {
         }
     }
 
+    pub fn resource_group_access_vec(group_tag: StructTag) -> Vec<u8> {
+        bcs::to_bytes(&Path::ResourceGroup(group_tag)).expect("Unexpected serialization error")
+    }
+
+    pub fn resource_group_access_path(address: AccountAddress, group_tag: StructTag) -> AccessPath {
+        AccessPath {
+            address,
+            path: AccessPath::resource_group_access_vec(group_tag),
+        }
+    }
+
     fn code_access_path_vec(key: ModuleId) -> Vec<u8> {
         bcs::to_bytes(&Path::Code(key)).expect("Unexpected serialization error")
     }
@@ -99,7 +117,7 @@ impl AccessPath {
     pub fn get_struct_tag(&self) -> Option<StructTag> {
         match self.get_path() {
             Path::Resource(s) => Some(s),
-            Path::Code(_) => None,
+            Path::Code(_) | Path::ResourceGroup(_) => None,
         }
     }
 
@@ -132,6 +150,7 @@ impl fmt::Display for AccessPath {
             match self.path[0] {
                 RESOURCE_TAG => write!(f, "type: Resource, ")?,
                 CODE_TAG => write!(f, "type: Module, ")?,
+                RESOURCE_GROUP_TAG => write!(f, "type: ResourceGroup, ")?,
                 tag => write!(f, "type: {:?}, ", tag)?,
             };
             write!(
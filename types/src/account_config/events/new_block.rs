@@ -31,6 +31,11 @@ pub struct NewBlockEvent {
 }
 
 impl NewBlockEvent {
+    /// The id of the block
+    pub fn hash(&self) -> HashValue {
+        self.id
+    }
+
     pub fn epoch(&self) -> u64 {
         self.epoch
     }
@@ -1,28 +1,22 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{event::EventHandle, utility_coin::APTOS_COIN_TYPE};
+use crate::{
+    event::EventHandle,
+    framework_bindings::coin::{Coin, CoinStore},
+    utility_coin::APTOS_COIN_TYPE,
+};
 use move_deps::move_core_types::{
     ident_str,
     identifier::IdentStr,
     language_storage::TypeTag,
     move_resource::{MoveResource, MoveStructType},
 };
-#[cfg(any(test, feature = "fuzzing"))]
-use proptest_derive::Arbitrary;
-use serde::{Deserialize, Serialize};
 
 /// The balance resource held under an account.
-#[derive(Debug, Serialize, Deserialize)]
-#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
-pub struct CoinStoreResource {
-    coin: u64,
-    frozen: bool,
-    deposit_events: EventHandle,
-    withdraw_events: EventHandle,
-}
+pub type CoinStoreResource = CoinStore;
 
-impl CoinStoreResource {
+impl CoinStore {
     pub fn new(
         coin: u64,
         frozen: bool,
@@ -30,7 +24,7 @@ impl CoinStoreResource {
         withdraw_events: EventHandle,
     ) -> Self {
         Self {
-            coin,
+            coin: Coin { value: coin },
             frozen,
             deposit_events,
             withdraw_events,
@@ -38,7 +32,7 @@ impl CoinStoreResource {
     }
 
     pub fn coin(&self) -> u64 {
-        self.coin
+        self.coin.value
     }
 
     pub fn frozen(&self) -> bool {
@@ -54,7 +48,8 @@ impl CoinStoreResource {
     }
 }
 
-impl MoveStructType for CoinStoreResource {
+/// Rust code only reads the coin store of the APT coin.
+impl MoveStructType for CoinStore {
     const MODULE_NAME: &'static IdentStr = ident_str!("coin");
     const STRUCT_NAME: &'static IdentStr = ident_str!("CoinStore");
 
@@ -63,4 +58,4 @@ impl MoveStructType for CoinStoreResource {
     }
 }
 
-impl MoveResource for CoinStoreResource {}
+impl MoveResource for CoinStore {}
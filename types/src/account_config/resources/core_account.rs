@@ -1,41 +1,28 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, event::EventHandle};
-use move_deps::move_core_types::{
-    ident_str,
-    identifier::IdentStr,
-    move_resource::{MoveResource, MoveStructType},
+use crate::{
+    account_address::AccountAddress,
+    event::EventHandle,
+    framework_bindings::account::{Account, CapabilityOffer},
 };
-#[cfg(any(test, feature = "fuzzing"))]
-use proptest_derive::Arbitrary;
-use serde::{Deserialize, Serialize};
 
 /// A Rust representation of an Account resource.
-/// This is not how the Account is represented in the VM but it's a convenient representation.
-#[derive(Debug, Serialize, Deserialize)]
-#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
-pub struct AccountResource {
-    authentication_key: Vec<u8>,
-    sequence_number: u64,
-    coin_register_events: EventHandle,
-    rotation_capability_offer: Option<AccountAddress>,
-    signer_capability_offer: Option<AccountAddress>,
-}
+pub type AccountResource = Account;
 
-impl AccountResource {
+impl Account {
     /// Constructs an Account resource.
     pub fn new(
         sequence_number: u64,
         authentication_key: Vec<u8>,
         coin_register_events: EventHandle,
     ) -> Self {
-        AccountResource {
+        Account {
             authentication_key,
             sequence_number,
             coin_register_events,
-            rotation_capability_offer: None,
-            signer_capability_offer: None,
+            rotation_capability_offer: CapabilityOffer { r#for: None },
+            signer_capability_offer: CapabilityOffer { r#for: None },
         }
     }
 
@@ -54,17 +41,10 @@ impl AccountResource {
     }
 
     pub fn rotation_capability_offer(&self) -> Option<AccountAddress> {
-        self.rotation_capability_offer
+        self.rotation_capability_offer.r#for
     }
 
     pub fn signer_capability_offer(&self) -> Option<AccountAddress> {
-        self.signer_capability_offer
+        self.signer_capability_offer.r#for
     }
 }
-
-impl MoveStructType for AccountResource {
-    const MODULE_NAME: &'static IdentStr = ident_str!("account");
-    const STRUCT_NAME: &'static IdentStr = ident_str!("Account");
-}
-
-impl MoveResource for AccountResource {}
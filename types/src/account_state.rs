@@ -5,6 +5,7 @@ use crate::{
     access_path::Path,
     account_config::{AccountResource, CoinStoreResource},
     account_view::AccountView,
+    resource_group::decode_resource_group,
     state_store::{state_key::StateKey, state_value::StateValue},
 };
 use anyhow::{anyhow, Error, Result};
@@ -54,7 +55,7 @@ impl AccountState {
         self.data.iter().filter_map(|(k, v)| {
             match Path::try_from(k).expect("Invalid access path") {
                 Path::Code(_) => Some(v),
-                Path::Resource(_) => None,
+                Path::Resource(_) | Path::ResourceGroup(_) => None,
             }
         })
     }
@@ -64,7 +65,7 @@ impl AccountState {
         self.data.into_iter().filter_map(|(k, v)| {
             match Path::try_from(&k).expect("Invalid access path") {
                 Path::Code(_) => Some(v),
-                Path::Resource(_) => None,
+                Path::Resource(_) | Path::ResourceGroup(_) => None,
             }
         })
     }
@@ -78,10 +79,31 @@ impl AccountState {
             .iter()
             .filter_map(|(k, v)| match Path::try_from(k) {
                 Ok(Path::Resource(struct_tag)) => Some((struct_tag, v.as_ref())),
-                Ok(Path::Code(_)) | Err(_) => None,
+                Ok(Path::Code(_)) | Ok(Path::ResourceGroup(_)) | Err(_) => None,
             })
     }
 
+    /// Return an iterator over all resource groups stored under this account, with their
+    /// undecoded values.
+    pub fn get_resource_groups(&self) -> impl Iterator<Item = (StructTag, &[u8])> {
+        self.data
+            .iter()
+            .filter_map(|(k, v)| match Path::try_from(k) {
+                Ok(Path::ResourceGroup(group_tag)) => Some((group_tag, v.as_ref())),
+                Ok(Path::Code(_)) | Ok(Path::Resource(_)) | Err(_) => None,
+            })
+    }
+
+    /// Return the members of all resource groups stored under this account, as if they were
+    /// stored as individual resources.
+    pub fn get_resource_group_members(&self) -> Result<Vec<(StructTag, Vec<u8>)>> {
+        let mut members = vec![];
+        for (group_tag, bytes) in self.get_resource_groups() {
+            members.extend(decode_resource_group(&group_tag, bytes)?);
+        }
+        Ok(members)
+    }
+
     pub fn from_access_paths_and_values(
         account_address: AccountAddress,
         key_value_map: &HashMap<StateKey, StateValue>,
@@ -0,0 +1,298 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+// This file was generated. Do not modify!
+//
+// To update this code, run: `cargo run --release -p framework`.
+
+//! Rust structs with the layout of framework structs, for decoding the resources read from
+//! storage. They are generated from the compiled framework, one module per Move module.
+//!
+//! Type parameters which are phantom, or only used as phantom type arguments, are left out.
+//! Generic structs therefore don't implement `MoveStructType`: their type arguments are
+//! chosen where they are used.
+
+#![allow(unused_imports)]
+
+pub mod account {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::account::Account`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct Account {
+        pub authentication_key: Vec<u8>,
+        pub sequence_number: u64,
+        pub coin_register_events: EventHandle,
+        pub rotation_capability_offer: CapabilityOffer,
+        pub signer_capability_offer: CapabilityOffer,
+    }
+
+    impl MoveStructType for Account {
+        const MODULE_NAME: &'static IdentStr = ident_str!("account");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("Account");
+    }
+
+    impl MoveResource for Account {}
+
+    /// `0x1::account::CapabilityOffer`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct CapabilityOffer {
+        pub r#for: Option<AccountAddress>,
+    }
+}
+
+pub mod aptos_governance {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::aptos_governance::GovernanceConfig`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct GovernanceConfig {
+        pub min_voting_threshold: u128,
+        pub required_proposer_stake: u64,
+        pub voting_duration_secs: u64,
+    }
+
+    impl MoveStructType for GovernanceConfig {
+        const MODULE_NAME: &'static IdentStr = ident_str!("aptos_governance");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("GovernanceConfig");
+    }
+
+    impl MoveResource for GovernanceConfig {}
+
+    /// `0x1::aptos_governance::RecordKey`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct RecordKey {
+        pub stake_pool: AccountAddress,
+        pub proposal_id: u64,
+    }
+
+    impl MoveStructType for RecordKey {
+        const MODULE_NAME: &'static IdentStr = ident_str!("aptos_governance");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("RecordKey");
+    }
+
+    /// `0x1::aptos_governance::VotingRecords`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct VotingRecords {
+        pub votes: TableHandle,
+    }
+
+    impl MoveStructType for VotingRecords {
+        const MODULE_NAME: &'static IdentStr = ident_str!("aptos_governance");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("VotingRecords");
+    }
+
+    impl MoveResource for VotingRecords {}
+}
+
+pub mod coin {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::coin::Coin`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct Coin {
+        pub value: u64,
+    }
+
+    /// `0x1::coin::CoinStore`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct CoinStore {
+        pub coin: Coin,
+        pub frozen: bool,
+        pub deposit_events: EventHandle,
+        pub withdraw_events: EventHandle,
+    }
+}
+
+pub mod governance_proposal {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::governance_proposal::GovernanceProposal`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct GovernanceProposal {
+        pub dummy_field: bool,
+    }
+
+    impl MoveStructType for GovernanceProposal {
+        const MODULE_NAME: &'static IdentStr = ident_str!("governance_proposal");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("GovernanceProposal");
+    }
+}
+
+pub mod simple_map {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::simple_map::Element`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct Element<T0, T1> {
+        pub key: T0,
+        pub value: T1,
+    }
+
+    /// `0x1::simple_map::SimpleMap`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct SimpleMap<T0, T1> {
+        pub data: Vec<Element<T0, T1>>,
+    }
+}
+
+pub mod stake {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::stake::StakePool`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct StakePool {
+        pub active: super::coin::Coin,
+        pub inactive: super::coin::Coin,
+        pub pending_active: super::coin::Coin,
+        pub pending_inactive: super::coin::Coin,
+        pub locked_until_secs: u64,
+        pub operator_address: AccountAddress,
+        pub delegated_voter: AccountAddress,
+        pub initialize_validator_events: EventHandle,
+        pub set_operator_events: EventHandle,
+        pub add_stake_events: EventHandle,
+        pub reactivate_stake_events: EventHandle,
+        pub rotate_consensus_key_events: EventHandle,
+        pub update_network_and_fullnode_addresses_events: EventHandle,
+        pub increase_lockup_events: EventHandle,
+        pub join_validator_set_events: EventHandle,
+        pub distribute_rewards_events: EventHandle,
+        pub unlock_stake_events: EventHandle,
+        pub withdraw_stake_events: EventHandle,
+        pub leave_validator_set_events: EventHandle,
+    }
+
+    impl MoveStructType for StakePool {
+        const MODULE_NAME: &'static IdentStr = ident_str!("stake");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("StakePool");
+    }
+
+    impl MoveResource for StakePool {}
+}
+
+pub mod voting {
+    use crate::{
+        account_address::AccountAddress, event::EventHandle, state_store::table::TableHandle,
+    };
+    use move_deps::move_core_types::{
+        ident_str,
+        identifier::IdentStr,
+        move_resource::{MoveResource, MoveStructType},
+    };
+    #[cfg(any(test, feature = "fuzzing"))]
+    use proptest_derive::Arbitrary;
+    use serde::{Deserialize, Serialize};
+
+    /// `0x1::voting::Proposal`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct Proposal<T0> {
+        pub proposer: AccountAddress,
+        pub execution_content: Option<T0>,
+        pub metadata: super::simple_map::SimpleMap<String, Vec<u8>>,
+        pub creation_time_secs: u64,
+        pub execution_hash: Vec<u8>,
+        pub min_vote_threshold: u128,
+        pub expiration_secs: u64,
+        pub early_resolution_vote_threshold: Option<u128>,
+        pub yes_votes: u128,
+        pub no_votes: u128,
+        pub is_resolved: bool,
+    }
+
+    /// `0x1::voting::VotingEvents`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct VotingEvents {
+        pub create_proposal_events: EventHandle,
+        pub register_forum_events: EventHandle,
+        pub resolve_proposal_events: EventHandle,
+        pub vote_events: EventHandle,
+    }
+
+    impl MoveStructType for VotingEvents {
+        const MODULE_NAME: &'static IdentStr = ident_str!("voting");
+        const STRUCT_NAME: &'static IdentStr = ident_str!("VotingEvents");
+    }
+
+    /// `0x1::voting::VotingForum`
+    #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+    pub struct VotingForum {
+        pub proposals: TableHandle,
+        pub events: VotingEvents,
+        pub next_proposal_id: u64,
+    }
+}
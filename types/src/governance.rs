@@ -0,0 +1,66 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! The on-chain governance resources of the framework, for the services which read them from
+//! storage.
+
+use crate::framework_bindings::{aptos_governance, governance_proposal, voting};
+use move_deps::move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::TypeTag,
+    move_resource::{MoveResource, MoveStructType},
+};
+
+/// `0x1::aptos_governance::GovernanceConfig`
+pub type GovernanceConfigResource = aptos_governance::GovernanceConfig;
+
+/// `0x1::aptos_governance::VotingRecords`, which records whether a stake pool voted on a
+/// proposal, under a `VotingRecordKey`.
+pub type VotingRecordsResource = aptos_governance::VotingRecords;
+
+/// `0x1::aptos_governance::RecordKey`
+pub type VotingRecordKey = aptos_governance::RecordKey;
+
+/// `0x1::governance_proposal::GovernanceProposal`. The Move struct has no fields, the compiler
+/// adds a dummy one.
+pub type GovernanceProposal = governance_proposal::GovernanceProposal;
+
+/// `0x1::voting::VotingForum<0x1::governance_proposal::GovernanceProposal>`, the forum holding
+/// the governance proposals, in a table keyed by proposal id.
+pub type GovernanceVotingForumResource = voting::VotingForum;
+
+impl MoveStructType for voting::VotingForum {
+    const MODULE_NAME: &'static IdentStr = ident_str!("voting");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("VotingForum");
+
+    fn type_params() -> Vec<TypeTag> {
+        vec![TypeTag::Struct(GovernanceProposal::struct_tag())]
+    }
+}
+
+impl MoveResource for voting::VotingForum {}
+
+/// `0x1::voting::Proposal<0x1::governance_proposal::GovernanceProposal>`, an item of the
+/// proposals table of the governance voting forum.
+pub type GovernanceVotingProposal = voting::Proposal<GovernanceProposal>;
+
+impl GovernanceVotingProposal {
+    /// Returns the value of a metadata entry, e.g. `metadata_location`
+    pub fn metadata(&self, key: &str) -> Option<&[u8]> {
+        self.metadata
+            .data
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.as_slice())
+    }
+}
+
+impl MoveStructType for GovernanceVotingProposal {
+    const MODULE_NAME: &'static IdentStr = ident_str!("voting");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Proposal");
+
+    fn type_params() -> Vec<TypeTag> {
+        vec![TypeTag::Struct(GovernanceProposal::struct_tag())]
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::keyless::KEYLESS_ACCOUNT_MODULE_NAME;
+use move_deps::move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+};
+use serde::{Deserialize, Serialize};
+
+/// An RSA JSON Web Key, with which an issuer signs JWTs. `e` and `n` are base64url-encoded.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct RsaJwk {
+    pub kid: String,
+    pub alg: String,
+    pub e: String,
+    pub n: String,
+}
+
+/// An OpenID Connect provider, and the keys it currently signs JWTs with
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Issuer {
+    pub iss: String,
+    pub jwks: Vec<RsaJwk>,
+}
+
+/// The Rust representation of `0x1::keyless_account::Configuration`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Configuration {
+    pub issuers: Vec<Issuer>,
+    /// How long after a JWT is issued the ephemeral key it commits to may expire
+    pub max_exp_horizon_secs: u64,
+    pub max_jwt_header_bytes: u64,
+}
+
+impl Configuration {
+    /// The key `kid` of the issuer `iss`, if the issuer is accepted and currently has this key
+    pub fn find_jwk(&self, iss: &str, kid: &str) -> Option<&RsaJwk> {
+        self.issuers
+            .iter()
+            .find(|issuer| issuer.iss == iss)?
+            .jwks
+            .iter()
+            .find(|jwk| jwk.kid == kid)
+    }
+}
+
+impl MoveStructType for Configuration {
+    const MODULE_NAME: &'static IdentStr = ident_str!(KEYLESS_ACCOUNT_MODULE_NAME);
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Configuration");
+}
+
+impl MoveResource for Configuration {}
@@ -0,0 +1,138 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::keyless::KEYLESS_ACCOUNT_MODULE_NAME;
+use anyhow::{anyhow, ensure, Result};
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_groth16::{Groth16, PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use move_deps::move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// The length of a compressed point of G1
+pub const G1_COMPRESSED_LENGTH: usize = 32;
+
+/// The length of a compressed point of G2
+pub const G2_COMPRESSED_LENGTH: usize = 64;
+
+fn serialize_point<P: CanonicalSerialize>(point: &P) -> Vec<u8> {
+    let mut bytes = vec![];
+    point
+        .serialize_compressed(&mut bytes)
+        .expect("Serializing a point cannot fail");
+    bytes
+}
+
+fn deserialize_point<P: CanonicalDeserialize>(bytes: &[u8], length: usize) -> Result<P> {
+    ensure!(
+        bytes.len() == length,
+        "Compressed point has length {}, expected {}",
+        bytes.len(),
+        length
+    );
+    P::deserialize_compressed(bytes).map_err(|e| anyhow!("Invalid point: {}", e))
+}
+
+/// The verification key of the keyless circuit, prepared for verifying proofs, so that users of
+/// `aptos-types` don't need to depend on arkworks themselves
+pub type PreparedGroth16VerificationKey = PreparedVerifyingKey<Bn254>;
+
+/// A Groth16 proof over BN254, with compressed points
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Groth16Proof {
+    #[serde(with = "serde_bytes")]
+    a: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    b: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    c: Vec<u8>,
+}
+
+impl Groth16Proof {
+    pub fn new(a: G1Affine, b: G2Affine, c: G1Affine) -> Self {
+        Self {
+            a: serialize_point(&a),
+            b: serialize_point(&b),
+            c: serialize_point(&c),
+        }
+    }
+
+    /// Return Ok if this is a proof for `public_input` under `pvk`, Err otherwise
+    pub fn verify(&self, pvk: &PreparedGroth16VerificationKey, public_input: &Fr) -> Result<()> {
+        let proof = Proof {
+            a: deserialize_point(&self.a, G1_COMPRESSED_LENGTH)?,
+            b: deserialize_point(&self.b, G2_COMPRESSED_LENGTH)?,
+            c: deserialize_point(&self.c, G1_COMPRESSED_LENGTH)?,
+        };
+        let verified = Groth16::<Bn254>::verify_proof(pvk, &proof, &[*public_input])
+            .map_err(|e| anyhow!("Groth16 proof verification failed: {}", e))?;
+        ensure!(verified, "Groth16 proof verification failed");
+        Ok(())
+    }
+}
+
+impl From<Proof<Bn254>> for Groth16Proof {
+    fn from(proof: Proof<Bn254>) -> Self {
+        Self::new(proof.a, proof.b, proof.c)
+    }
+}
+
+/// The Rust representation of `0x1::keyless_account::Groth16VerificationKey`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Groth16VerificationKey {
+    pub alpha_g1: Vec<u8>,
+    pub beta_g2: Vec<u8>,
+    pub gamma_g2: Vec<u8>,
+    pub delta_g2: Vec<u8>,
+    pub gamma_abc_g1: Vec<Vec<u8>>,
+}
+
+impl From<&VerifyingKey<Bn254>> for Groth16VerificationKey {
+    fn from(vk: &VerifyingKey<Bn254>) -> Self {
+        Self {
+            alpha_g1: serialize_point(&vk.alpha_g1),
+            beta_g2: serialize_point(&vk.beta_g2),
+            gamma_g2: serialize_point(&vk.gamma_g2),
+            delta_g2: serialize_point(&vk.delta_g2),
+            gamma_abc_g1: vk.gamma_abc_g1.iter().map(serialize_point).collect(),
+        }
+    }
+}
+
+impl TryFrom<&Groth16VerificationKey> for PreparedGroth16VerificationKey {
+    type Error = anyhow::Error;
+
+    fn try_from(vk: &Groth16VerificationKey) -> Result<Self> {
+        // The keyless circuit has a single public input, so the key has one point per input
+        // and one for the constant term
+        ensure!(
+            vk.gamma_abc_g1.len() == 2,
+            "Verification key has {} points in gamma_abc_g1, expected 2",
+            vk.gamma_abc_g1.len()
+        );
+        let vk = VerifyingKey {
+            alpha_g1: deserialize_point(&vk.alpha_g1, G1_COMPRESSED_LENGTH)?,
+            beta_g2: deserialize_point(&vk.beta_g2, G2_COMPRESSED_LENGTH)?,
+            gamma_g2: deserialize_point(&vk.gamma_g2, G2_COMPRESSED_LENGTH)?,
+            delta_g2: deserialize_point(&vk.delta_g2, G2_COMPRESSED_LENGTH)?,
+            gamma_abc_g1: vk
+                .gamma_abc_g1
+                .iter()
+                .map(|point| deserialize_point(point, G1_COMPRESSED_LENGTH))
+                .collect::<Result<_>>()?,
+        };
+        Ok(ark_groth16::prepare_verifying_key(&vk))
+    }
+}
+
+impl MoveStructType for Groth16VerificationKey {
+    const MODULE_NAME: &'static IdentStr = ident_str!(KEYLESS_ACCOUNT_MODULE_NAME);
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Groth16VerificationKey");
+}
+
+impl MoveResource for Groth16VerificationKey {}
@@ -0,0 +1,128 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! JWK consensus: validators fetch the JWKs of the issuers configured in `0x1::keyless_account`,
+//! agree on what they observed, and publish updates through validator transactions, so that the
+//! keys on chain follow the providers' key rotations without a governance proposal each time.
+
+use crate::{
+    aggregate_signature::AggregateSignature,
+    keyless::{RsaJwk, KEYLESS_ACCOUNT_MODULE_NAME},
+    validator_txn::ValidatorTransaction,
+    validator_verifier::ValidatorVerifier,
+};
+use anyhow::{Context, Result};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use move_deps::move_core_types::{
+    account_address::AccountAddress,
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+    value::MoveValue,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The JWKs of an issuer, as observed by a validator. `version` is the on-chain version of the
+/// issuer's JWKs once this update is applied, so an update only applies once.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
+)]
+pub struct ProviderJWKs {
+    pub issuer: String,
+    pub version: u64,
+    /// The RS256 keys of the issuer, sorted by `kid`
+    pub jwks: Vec<RsaJwk>,
+}
+
+impl ProviderJWKs {
+    pub fn new(issuer: String, version: u64, mut jwks: Vec<RsaJwk>) -> Self {
+        jwks.sort_by(|a, b| a.kid.cmp(&b.kid));
+        Self {
+            issuer,
+            version,
+            jwks,
+        }
+    }
+
+    /// The arguments of `keyless_account::upsert_observed_jwks`
+    pub fn get_move_args(&self, signer: AccountAddress) -> Vec<MoveValue> {
+        let bytes = |s: &str| MoveValue::Vector(s.bytes().map(MoveValue::U8).collect());
+        let field = |f: fn(&RsaJwk) -> &str| {
+            MoveValue::Vector(self.jwks.iter().map(|jwk| bytes(f(jwk))).collect())
+        };
+        vec![
+            MoveValue::Signer(signer),
+            bytes(&self.issuer),
+            MoveValue::U64(self.version),
+            field(|jwk| jwk.kid.as_str()),
+            field(|jwk| jwk.alg.as_str()),
+            field(|jwk| jwk.e.as_str()),
+            field(|jwk| jwk.n.as_str()),
+        ]
+    }
+}
+
+impl fmt::Display for ProviderJWKs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[ProviderJWKs of {} at version {}: {} keys]",
+            self.issuer,
+            self.version,
+            self.jwks.len()
+        )
+    }
+}
+
+/// An update of an issuer's JWKs signed by a quorum of validators, which is the payload of a
+/// `ValidatorTransaction::ObservedJWKUpdate`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuorumCertifiedUpdate {
+    pub update: ProviderJWKs,
+    pub multi_sig: AggregateSignature,
+}
+
+impl QuorumCertifiedUpdate {
+    /// Decodes the payload of a `ValidatorTransaction::ObservedJWKUpdate`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes).context("Malformed quorum certified JWK update")
+    }
+
+    /// Returns Ok if the update is signed by a quorum of `verifier`, Err otherwise
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> Result<()> {
+        verifier
+            .verify_multi_signatures(&self.update, &self.multi_sig)
+            .context("Failed to verify the quorum signature of the JWK update")
+    }
+
+    pub fn to_validator_txn(&self) -> ValidatorTransaction {
+        ValidatorTransaction::ObservedJWKUpdate(
+            bcs::to_bytes(self).expect("JWK update serialization cannot fail"),
+        )
+    }
+}
+
+/// The Rust representation of `0x1::keyless_account::ObservedJWKVersions`: the version of the
+/// JWKs of each issuer updated through JWK consensus
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ObservedJWKVersions {
+    versions: Vec<(String, u64)>,
+}
+
+impl ObservedJWKVersions {
+    /// The version of the JWKs of `issuer`, 0 if they were never updated by JWK consensus
+    pub fn version(&self, issuer: &str) -> u64 {
+        self.versions
+            .iter()
+            .find(|(iss, _)| iss == issuer)
+            .map_or(0, |(_, version)| *version)
+    }
+}
+
+impl MoveStructType for ObservedJWKVersions {
+    const MODULE_NAME: &'static IdentStr = ident_str!(KEYLESS_ACCOUNT_MODULE_NAME);
+    const STRUCT_NAME: &'static IdentStr = ident_str!("ObservedJWKVersions");
+}
+
+impl MoveResource for ObservedJWKVersions {}
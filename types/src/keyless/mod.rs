@@ -0,0 +1,190 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keyless accounts, whose transactions are authorized by signing in with an OpenID Connect
+//! (OIDC) provider, such as Google or Apple, instead of with a long-lived private key.
+//!
+//! To sign transactions, a user generates an ephemeral key pair and signs in with the provider,
+//! which issues a JWT whose `nonce` commits to the ephemeral public key and its expiry date. The
+//! transactions are signed with the ephemeral private key, along with a Groth16 proof that the
+//! user holds a JWT, signed by the provider, which:
+//!  * was issued by `iss`, for the application `aud`, to the user identified by a uid claim,
+//!    e.g. `sub`,
+//!  * has a nonce committing to the ephemeral public key and expiry date.
+//!
+//! The account is identified by `iss` and an `IdCommitment`, a hash of the application, the uid
+//! and a secret pepper, so that neither the application nor the user is revealed on chain.
+//!
+//! The statement proven is hashed into the single public input of the circuit: see
+//! `KeylessSignature::public_input`. The ephemeral signature can be checked on its own, but the
+//! proof depends on the issuers, JWKs and verification key published in
+//! `0x1::keyless_account`, so it is verified by the VM.
+
+use anyhow::{anyhow, ensure, Result};
+use aptos_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    hash::CryptoHash,
+    traits::Signature,
+    HashValue,
+};
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use serde::{Deserialize, Serialize};
+
+mod configuration;
+mod groth16;
+mod jwks;
+
+pub use self::{
+    configuration::{Configuration, Issuer, RsaJwk},
+    groth16::{
+        Groth16Proof, Groth16VerificationKey, PreparedGroth16VerificationKey, G1_COMPRESSED_LENGTH,
+        G2_COMPRESSED_LENGTH,
+    },
+    jwks::{ObservedJWKVersions, ProviderJWKs, QuorumCertifiedUpdate},
+};
+
+pub const KEYLESS_ACCOUNT_MODULE_NAME: &str = "keyless_account";
+
+/// The only JWT signature algorithm the circuit supports
+pub const JWT_ALGORITHM_RS256: &str = "RS256";
+
+/// A commitment to the application, the uid claim and its value, and a pepper
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct IdCommitment(#[serde(with = "serde_bytes")] Vec<u8>);
+
+impl IdCommitment {
+    pub const LENGTH: usize = HashValue::LENGTH;
+
+    pub fn new(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(bytes.to_vec())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The public key of a keyless account
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct KeylessPublicKey {
+    /// The `iss` claim of the JWTs, e.g. `https://accounts.google.com`
+    pub iss_val: String,
+    pub idc: IdCommitment,
+}
+
+impl KeylessPublicKey {
+    /// Serialize the public key, as BCS
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Keyless public key serialization cannot fail")
+    }
+}
+
+/// The fields of a JWT header we check
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+    kid: String,
+}
+
+/// The statement proven by a keyless signature
+#[derive(Serialize)]
+struct PublicInputs<'a> {
+    ephemeral_pubkey: &'a Ed25519PublicKey,
+    exp_date_secs: u64,
+    exp_horizon_secs: u64,
+    iss_val: &'a str,
+    idc: &'a IdCommitment,
+    jwt_header_json: &'a str,
+    jwk: &'a RsaJwk,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct KeylessSignature {
+    pub proof: Groth16Proof,
+    /// The header of the JWT, which names the key it was signed with
+    pub jwt_header_json: String,
+    /// The UNIX time in seconds at which the ephemeral key expires
+    pub exp_date_secs: u64,
+    /// How long after the JWT was issued the ephemeral key may expire
+    pub exp_horizon_secs: u64,
+    pub ephemeral_pubkey: Ed25519PublicKey,
+    pub ephemeral_signature: Ed25519Signature,
+}
+
+impl KeylessSignature {
+    /// Return Ok if the ephemeral signature on `message` matches the ephemeral public key, Err
+    /// otherwise. This does not verify the proof, which needs the on-chain configuration.
+    pub fn verify_ephemeral_signature<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
+        self.ephemeral_signature
+            .verify(message, &self.ephemeral_pubkey)
+    }
+
+    /// Return Ok if the ephemeral key has not expired at the UNIX time `now_secs`, Err otherwise
+    pub fn verify_expiry(&self, now_secs: u64) -> Result<()> {
+        ensure!(
+            now_secs < self.exp_date_secs,
+            "Keyless signature expired at {}",
+            self.exp_date_secs
+        );
+        Ok(())
+    }
+
+    /// Return Ok if the proof shows that `public_key` was issued a JWT binding the ephemeral
+    /// public key, by an issuer of `config` and with one of its JWKs, Err otherwise
+    pub fn verify_proof(
+        &self,
+        public_key: &KeylessPublicKey,
+        config: &Configuration,
+        pvk: &PreparedGroth16VerificationKey,
+    ) -> Result<()> {
+        ensure!(
+            self.exp_horizon_secs <= config.max_exp_horizon_secs,
+            "Keyless expiry horizon {}s exceeds the maximum of {}s",
+            self.exp_horizon_secs,
+            config.max_exp_horizon_secs
+        );
+        ensure!(
+            self.jwt_header_json.len() as u64 <= config.max_jwt_header_bytes,
+            "JWT header exceeds the maximum of {} bytes",
+            config.max_jwt_header_bytes
+        );
+        let header: JwtHeader = serde_json::from_str(&self.jwt_header_json)?;
+        ensure!(
+            header.alg == JWT_ALGORITHM_RS256,
+            "Unsupported JWT algorithm {}",
+            header.alg
+        );
+        let jwk = config
+            .find_jwk(&public_key.iss_val, &header.kid)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No JWK {} for the issuer {}",
+                    header.kid,
+                    public_key.iss_val
+                )
+            })?;
+        self.proof.verify(pvk, &self.public_input(public_key, jwk))
+    }
+
+    /// The public input of the circuit: the SHA3-256 hash of the BCS-serialized statement,
+    /// little-endian, reduced into the scalar field
+    pub fn public_input(&self, public_key: &KeylessPublicKey, jwk: &RsaJwk) -> Fr {
+        let inputs = PublicInputs {
+            ephemeral_pubkey: &self.ephemeral_pubkey,
+            exp_date_secs: self.exp_date_secs,
+            exp_horizon_secs: self.exp_horizon_secs,
+            iss_val: &public_key.iss_val,
+            idc: &public_key.idc,
+            jwt_header_json: &self.jwt_header_json,
+            jwk,
+        };
+        let bytes = bcs::to_bytes(&inputs).expect("Public inputs serialization cannot fail");
+        Fr::from_le_bytes_mod_order(HashValue::sha3_256_of(&bytes).as_ref())
+    }
+
+    /// Serialize the signature, as BCS
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Keyless signature serialization cannot fail")
+    }
+}
@@ -14,6 +14,9 @@ pub mod contract_event;
 pub mod epoch_change;
 pub mod epoch_state;
 pub mod event;
+pub mod framework_bindings;
+pub mod governance;
+pub mod keyless;
 pub mod ledger_info;
 pub mod mempool_status;
 pub mod move_resource;
@@ -23,6 +26,7 @@ pub mod on_chain_config;
 pub mod proof;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
+pub mod resource_group;
 pub mod serde_helper;
 pub mod stake_pool;
 pub mod state_proof;
@@ -35,6 +39,7 @@ pub mod utility_coin;
 pub mod validator_config;
 pub mod validator_info;
 pub mod validator_signer;
+pub mod validator_txn;
 pub mod validator_verifier;
 pub mod vm_status;
 pub mod waypoint;
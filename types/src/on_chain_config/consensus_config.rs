@@ -12,6 +12,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum OnChainConsensusConfig {
     V1(ConsensusConfigV1),
+    V2(ConsensusConfigV2),
+    V3(ConsensusConfigV3),
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -20,6 +22,8 @@ impl OnChainConsensusConfig {
     pub fn leader_reputation_exclude_round(&self) -> u64 {
         match &self {
             OnChainConsensusConfig::V1(config) => config.exclude_round,
+            OnChainConsensusConfig::V2(config) => config.exclude_round,
+            OnChainConsensusConfig::V3(config) => config.exclude_round,
         }
     }
 
@@ -27,6 +31,8 @@ impl OnChainConsensusConfig {
     pub fn decoupled_execution(&self) -> bool {
         match &self {
             OnChainConsensusConfig::V1(config) => config.decoupled_execution,
+            OnChainConsensusConfig::V2(config) => config.decoupled_execution,
+            OnChainConsensusConfig::V3(config) => config.decoupled_execution,
         }
     }
 
@@ -39,6 +45,8 @@ impl OnChainConsensusConfig {
         }
         match &self {
             OnChainConsensusConfig::V1(config) => config.back_pressure_limit,
+            OnChainConsensusConfig::V2(config) => config.back_pressure_limit,
+            OnChainConsensusConfig::V3(config) => config.back_pressure_limit,
         }
     }
 
@@ -47,6 +55,8 @@ impl OnChainConsensusConfig {
     pub fn max_failed_authors_to_store(&self) -> usize {
         match &self {
             OnChainConsensusConfig::V1(config) => config.max_failed_authors_to_store,
+            OnChainConsensusConfig::V2(config) => config.max_failed_authors_to_store,
+            OnChainConsensusConfig::V3(config) => config.max_failed_authors_to_store,
         }
     }
 
@@ -54,6 +64,27 @@ impl OnChainConsensusConfig {
     pub fn proposer_election_type(&self) -> &ProposerElectionType {
         match &self {
             OnChainConsensusConfig::V1(config) => &config.proposer_election_type,
+            OnChainConsensusConfig::V2(config) => &config.proposer_election_type,
+            OnChainConsensusConfig::V3(config) => &config.proposer_election_type,
+        }
+    }
+
+    /// The cap on the cumulative output of the transactions of a block, `None` if there is none.
+    pub fn block_output_limit(&self) -> Option<BlockOutputLimit> {
+        match &self {
+            OnChainConsensusConfig::V1(_) => None,
+            OnChainConsensusConfig::V2(config) => config.block_output_limit,
+            OnChainConsensusConfig::V3(config) => config.block_output_limit,
+        }
+    }
+
+    /// Whether validator transactions can be proposed in blocks, and how many.
+    pub fn validator_txn_config(&self) -> ValidatorTxnConfig {
+        match &self {
+            OnChainConsensusConfig::V1(_) | OnChainConsensusConfig::V2(_) => {
+                ValidatorTxnConfig::Disabled
+            }
+            OnChainConsensusConfig::V3(config) => config.validator_txn_config,
         }
     }
 }
@@ -119,6 +150,123 @@ impl Default for ConsensusConfigV1 {
     }
 }
 
+/// V1 with a cap on the output of blocks.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ConsensusConfigV2 {
+    pub decoupled_execution: bool,
+    pub back_pressure_limit: u64,
+    pub exclude_round: u64,
+    pub proposer_election_type: ProposerElectionType,
+    pub max_failed_authors_to_store: usize,
+    pub block_output_limit: Option<BlockOutputLimit>,
+}
+
+impl Default for ConsensusConfigV2 {
+    fn default() -> Self {
+        let v1 = ConsensusConfigV1::default();
+        Self {
+            decoupled_execution: v1.decoupled_execution,
+            back_pressure_limit: v1.back_pressure_limit,
+            exclude_round: v1.exclude_round,
+            proposer_election_type: v1.proposer_election_type,
+            max_failed_authors_to_store: v1.max_failed_authors_to_store,
+            block_output_limit: Some(BlockOutputLimit::default()),
+        }
+    }
+}
+
+/// V2 with validator transactions.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ConsensusConfigV3 {
+    pub decoupled_execution: bool,
+    pub back_pressure_limit: u64,
+    pub exclude_round: u64,
+    pub proposer_election_type: ProposerElectionType,
+    pub max_failed_authors_to_store: usize,
+    pub block_output_limit: Option<BlockOutputLimit>,
+    pub validator_txn_config: ValidatorTxnConfig,
+}
+
+impl Default for ConsensusConfigV3 {
+    fn default() -> Self {
+        let v2 = ConsensusConfigV2::default();
+        Self {
+            decoupled_execution: v2.decoupled_execution,
+            back_pressure_limit: v2.back_pressure_limit,
+            exclude_round: v2.exclude_round,
+            proposer_election_type: v2.proposer_election_type,
+            max_failed_authors_to_store: v2.max_failed_authors_to_store,
+            block_output_limit: v2.block_output_limit,
+            validator_txn_config: ValidatorTxnConfig::default_enabled(),
+        }
+    }
+}
+
+/// Validator transactions are only proposed, and only accepted in proposals, when enabled.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorTxnConfig {
+    Disabled,
+    Enabled {
+        per_block_limit_txn_count: u64,
+        per_block_limit_total_bytes: u64,
+    },
+}
+
+impl ValidatorTxnConfig {
+    pub fn default_enabled() -> Self {
+        ValidatorTxnConfig::Enabled {
+            per_block_limit_txn_count: 2,
+            // 2 MB
+            per_block_limit_total_bytes: 2 << 20,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        matches!(self, ValidatorTxnConfig::Enabled { .. })
+    }
+
+    pub fn per_block_limit_txn_count(&self) -> u64 {
+        match self {
+            ValidatorTxnConfig::Disabled => 0,
+            ValidatorTxnConfig::Enabled {
+                per_block_limit_txn_count,
+                ..
+            } => *per_block_limit_txn_count,
+        }
+    }
+
+    pub fn per_block_limit_total_bytes(&self) -> u64 {
+        match self {
+            ValidatorTxnConfig::Disabled => 0,
+            ValidatorTxnConfig::Enabled {
+                per_block_limit_total_bytes,
+                ..
+            } => *per_block_limit_total_bytes,
+        }
+    }
+}
+
+/// Once the cumulative output of the transactions of a block crosses either limit, the
+/// transactions after the one crossing it are not committed and get retried in a later block.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockOutputLimit {
+    // Total size of the write sets, counting the state keys as well as the values
+    pub max_write_set_bytes: u64,
+    // Total number of events
+    pub max_events: u64,
+}
+
+impl Default for BlockOutputLimit {
+    fn default() -> Self {
+        Self {
+            // 100 MB
+            max_write_set_bytes: 100 << 20,
+            max_events: 100_000,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum ProposerElectionType {
@@ -236,4 +384,47 @@ mod test {
             ProposerElectionType::RoundProposer(_value)
         ));
     }
+
+    #[test]
+    fn test_config_block_output_limit() {
+        assert_eq!(OnChainConsensusConfig::default().block_output_limit(), None);
+
+        let limit = BlockOutputLimit {
+            max_write_set_bytes: 1 << 20,
+            max_events: 100,
+        };
+        let config = OnChainConsensusConfig::V2(ConsensusConfigV2 {
+            block_output_limit: Some(limit),
+            ..ConsensusConfigV2::default()
+        });
+        let s = bcs::to_bytes(&config).unwrap();
+        let result = bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
+        assert_eq!(result, config);
+        assert_eq!(result.block_output_limit(), Some(limit));
+    }
+
+    #[test]
+    fn test_config_validator_txn_config() {
+        assert!(!OnChainConsensusConfig::default()
+            .validator_txn_config()
+            .enabled());
+
+        let validator_txn_config = ValidatorTxnConfig::Enabled {
+            per_block_limit_txn_count: 1,
+            per_block_limit_total_bytes: 1024,
+        };
+        let config = OnChainConsensusConfig::V3(ConsensusConfigV3 {
+            validator_txn_config,
+            ..ConsensusConfigV3::default()
+        });
+        let s = bcs::to_bytes(&config).unwrap();
+        let result = bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
+        assert_eq!(result, config);
+        assert_eq!(result.validator_txn_config(), validator_txn_config);
+        assert_eq!(result.validator_txn_config().per_block_limit_txn_count(), 1);
+        assert_eq!(
+            result.validator_txn_config().per_block_limit_total_bytes(),
+            1024
+        );
+    }
 }
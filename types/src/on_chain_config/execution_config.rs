@@ -0,0 +1,177 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use anyhow::{format_err, Result};
+use serde::{Deserialize, Serialize};
+
+/// The on-chain execution config, in order to be able to add fields, we use enum to wrap the actual struct.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum OnChainExecutionConfig {
+    V1(ExecutionConfigV1),
+    V2(ExecutionConfigV2),
+}
+
+/// The public interface that exposes all values with safe fallback.
+impl OnChainExecutionConfig {
+    /// How the transactions of a block payload are reordered before execution.
+    pub fn transaction_shuffler_type(&self) -> TransactionShufflerType {
+        match &self {
+            OnChainExecutionConfig::V1(config) => config.transaction_shuffler_type,
+            OnChainExecutionConfig::V2(config) => config.transaction_shuffler_type,
+        }
+    }
+
+    /// The limits on the execution of transactions enforced by the VM, on top of gas.
+    pub fn execution_limits(&self) -> ExecutionLimits {
+        match &self {
+            OnChainExecutionConfig::V1(_) => ExecutionLimits::default(),
+            OnChainExecutionConfig::V2(config) => config.execution_limits,
+        }
+    }
+}
+
+/// This is used when on-chain config is not initialized.
+impl Default for OnChainExecutionConfig {
+    fn default() -> Self {
+        OnChainExecutionConfig::V1(ExecutionConfigV1::default())
+    }
+}
+
+impl OnChainConfig for OnChainExecutionConfig {
+    const MODULE_IDENTIFIER: &'static str = "execution_config";
+    const TYPE_IDENTIFIER: &'static str = "ExecutionConfig";
+
+    /// The Move resource is
+    /// ```ignore
+    /// struct ExecutionConfig has key {
+    ///    config: vector<u8>,
+    /// }
+    /// ```
+    /// so we need two rounds of bcs deserilization to turn it back to OnChainExecutionConfig
+    fn deserialize_into_config(bytes: &[u8]) -> Result<Self> {
+        let raw_bytes: Vec<u8> = bcs::from_bytes(bytes)?;
+        bcs::from_bytes(&raw_bytes)
+            .map_err(|e| format_err!("[on-chain config] Failed to deserialize into config: {}", e))
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExecutionConfigV1 {
+    pub transaction_shuffler_type: TransactionShufflerType,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExecutionConfigV2 {
+    pub transaction_shuffler_type: TransactionShufflerType,
+    pub execution_limits: ExecutionLimits,
+}
+
+/// Limits on the execution of a transaction, enforced by the VM on top of gas. A transaction
+/// exceeding one aborts, in the module whose code exceeded it, with the abort code of the limit.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExecutionLimits {
+    /// The maximum depth of nested function calls, native functions excluded
+    pub max_call_depth: u64,
+    /// The maximum number of loop iterations in a single metering tick, i.e. the execution of a
+    /// function the VM calls directly along with all its callees: the script or entry function of
+    /// the transaction, the creation of the account of an implicit sender, or an `init_module`.
+    /// Iterations are counted as the branch instructions executed, each iteration executing at
+    /// least one.
+    pub max_loop_iterations_per_tick: u64,
+    /// Whether a function may call into a module which already has a function on the call stack,
+    /// below a function of another module
+    pub allow_cross_module_reentrancy: bool,
+}
+
+impl ExecutionLimits {
+    /// The abort code of a transaction exceeding `max_call_depth`. The abort codes of the limits
+    /// are above the range of the error codes of `std::error`, so they don't clash with the
+    /// errors of the framework.
+    pub const ECALL_DEPTH_EXCEEDED: u64 = 0xFFFF_FFFF_0000_0001;
+    /// The abort code of a transaction exceeding `max_loop_iterations_per_tick`
+    pub const ELOOP_ITERATIONS_EXCEEDED: u64 = 0xFFFF_FFFF_0000_0002;
+    /// The abort code of a transaction re-entering a module, if not allowed
+    pub const ECROSS_MODULE_REENTRANCY: u64 = 0xFFFF_FFFF_0000_0003;
+}
+
+/// The limits before they were configurable: the call stack limit of the Move VM, nothing else.
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            max_call_depth: 1024,
+            max_loop_iterations_per_tick: u64::MAX,
+            allow_cross_module_reentrancy: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionShufflerType {
+    // Execute the transactions in the order of the payload
+    NoShuffling,
+    // Interleave the transactions of different senders, keeping the order of the transactions
+    // of each sender, so that consecutive transactions rarely conflict on their sender's account
+    SenderAwareV1,
+}
+
+impl Default for TransactionShufflerType {
+    fn default() -> Self {
+        TransactionShufflerType::NoShuffling
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::on_chain_config::OnChainConfigPayload;
+    use std::{collections::HashMap, sync::Arc};
+
+    #[test]
+    fn test_config_onchain_payload() {
+        let execution_config = OnChainExecutionConfig::V1(ExecutionConfigV1 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV1,
+        });
+
+        let mut configs = HashMap::new();
+        configs.insert(
+            OnChainExecutionConfig::CONFIG_ID,
+            // Requires double serialization, check deserialize_into_config for more details
+            bcs::to_bytes(&bcs::to_bytes(&execution_config).unwrap()).unwrap(),
+        );
+
+        let payload = OnChainConfigPayload::new(1, Arc::new(configs));
+
+        let result: OnChainExecutionConfig = payload.get().unwrap();
+        assert_eq!(
+            result.transaction_shuffler_type(),
+            TransactionShufflerType::SenderAwareV1
+        );
+        assert_eq!(result.execution_limits(), ExecutionLimits::default());
+    }
+
+    #[test]
+    fn test_config_v2_onchain_payload() {
+        let execution_limits = ExecutionLimits {
+            max_call_depth: 64,
+            max_loop_iterations_per_tick: 10_000,
+            allow_cross_module_reentrancy: false,
+        };
+        let execution_config = OnChainExecutionConfig::V2(ExecutionConfigV2 {
+            transaction_shuffler_type: TransactionShufflerType::NoShuffling,
+            execution_limits,
+        });
+
+        let mut configs = HashMap::new();
+        configs.insert(
+            OnChainExecutionConfig::CONFIG_ID,
+            bcs::to_bytes(&bcs::to_bytes(&execution_config).unwrap()).unwrap(),
+        );
+
+        let payload = OnChainConfigPayload::new(1, Arc::new(configs));
+
+        let result: OnChainExecutionConfig = payload.get().unwrap();
+        assert_eq!(result.execution_limits(), execution_limits);
+    }
+}
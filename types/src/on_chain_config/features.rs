@@ -0,0 +1,159 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// The behaviors of the VM and natives which can be turned on and off by governance, without a
+/// release. The values are the bits of the feature in `Features`, and must match the constants of
+/// `0x1::features`.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FeatureFlag {
+    /// Accounts authenticated with WebAuthn assertions, e.g. passkeys
+    WebAuthnAuthenticator = 1,
+    /// Accounts authenticated with OpenID Connect sign-ins
+    KeylessAuthenticator = 2,
+    /// Accounts authenticated with K-of-N signatures of mixed schemes
+    MultiKeyAuthenticator = 3,
+    /// The natives of `0x1::secp256r1`
+    Secp256r1Natives = 4,
+    /// The natives of `0x1::ristretto255`
+    Ristretto255Natives = 5,
+    /// The natives of `0x1::streaming_hash`
+    StreamingHashNatives = 6,
+    /// The natives of `0x1::merkle`
+    MerkleProofNatives = 7,
+    /// The natives of `0x1::light_client`
+    LightClientNatives = 8,
+    /// The natives of `0x1::poseidon`
+    PoseidonNatives = 9,
+    /// Orderless transactions, protected from replays by nonces instead of sequence numbers
+    OrderlessTransactions = 10,
+    /// Transfers of AptosCoin to a new address only publish its coin store, its account is created
+    /// by its first transaction
+    ImplicitAccounts = 11,
+}
+
+impl FeatureFlag {
+    /// Every feature known to this release
+    pub const ALL: &'static [FeatureFlag] = &[
+        FeatureFlag::WebAuthnAuthenticator,
+        FeatureFlag::KeylessAuthenticator,
+        FeatureFlag::MultiKeyAuthenticator,
+        FeatureFlag::Secp256r1Natives,
+        FeatureFlag::Ristretto255Natives,
+        FeatureFlag::StreamingHashNatives,
+        FeatureFlag::MerkleProofNatives,
+        FeatureFlag::LightClientNatives,
+        FeatureFlag::PoseidonNatives,
+        FeatureFlag::OrderlessTransactions,
+        FeatureFlag::ImplicitAccounts,
+    ];
+}
+
+/// A change of a feature, which takes effect from an epoch on
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct FeatureActivation {
+    pub feature: u64,
+    pub epoch: u64,
+}
+
+/// The `0x1::features::Features` config: a bitset of the enabled features, where feature `i` is
+/// bit `i % 8` of byte `i / 8`, and the pending changes of features. The bit of a pending
+/// feature is already flipped, but the feature keeps its previous state until the epoch of its
+/// activation.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Features {
+    pub features: Vec<u8>,
+    pub activations: Vec<FeatureActivation>,
+}
+
+impl OnChainConfig for Features {
+    const MODULE_IDENTIFIER: &'static str = "features";
+    const TYPE_IDENTIFIER: &'static str = "Features";
+}
+
+impl Features {
+    /// Whether `flag` is enabled during `epoch`
+    pub fn is_enabled(&self, flag: FeatureFlag, epoch: u64) -> bool {
+        let feature = flag as u64;
+        let bit = self
+            .features
+            .get((feature / 8) as usize)
+            .map_or(false, |byte| byte & (1 << (feature % 8)) != 0);
+        let pending = self
+            .activations
+            .iter()
+            .any(|activation| activation.feature == feature && epoch < activation.epoch);
+        bit != pending
+    }
+
+    /// The features enabled during `epoch`
+    pub fn enabled_at(&self, epoch: u64) -> EnabledFeatures {
+        EnabledFeatures(
+            FeatureFlag::ALL
+                .iter()
+                .copied()
+                .filter(|flag| self.is_enabled(*flag, epoch))
+                .collect(),
+        )
+    }
+}
+
+/// The features enabled during an epoch, as seen by the VM
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EnabledFeatures(BTreeSet<FeatureFlag>);
+
+impl EnabledFeatures {
+    /// Every known feature, for genesis and tools which run without on-chain configs
+    pub fn all() -> Self {
+        Self(FeatureFlag::ALL.iter().copied().collect())
+    }
+
+    pub fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        self.0.contains(&flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_activation() {
+        let features = Features {
+            // Keyless and secp256r1 are set
+            features: vec![0b0001_0100],
+            activations: vec![
+                // Keyless is enabled from epoch 10 on
+                FeatureActivation {
+                    feature: FeatureFlag::KeylessAuthenticator as u64,
+                    epoch: 10,
+                },
+                // WebAuthn is disabled from epoch 5 on
+                FeatureActivation {
+                    feature: FeatureFlag::WebAuthnAuthenticator as u64,
+                    epoch: 5,
+                },
+            ],
+        };
+
+        let enabled = features.enabled_at(4);
+        assert!(enabled.is_enabled(FeatureFlag::WebAuthnAuthenticator));
+        assert!(!enabled.is_enabled(FeatureFlag::KeylessAuthenticator));
+        assert!(!enabled.is_enabled(FeatureFlag::MultiKeyAuthenticator));
+        assert!(enabled.is_enabled(FeatureFlag::Secp256r1Natives));
+
+        let enabled = features.enabled_at(10);
+        assert!(!enabled.is_enabled(FeatureFlag::WebAuthnAuthenticator));
+        assert!(enabled.is_enabled(FeatureFlag::KeylessAuthenticator));
+        assert!(enabled.is_enabled(FeatureFlag::Secp256r1Natives));
+
+        assert_eq!(
+            Features::default().enabled_at(0),
+            EnabledFeatures::default()
+        );
+    }
+}
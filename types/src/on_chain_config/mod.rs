@@ -18,6 +18,8 @@ use std::{collections::HashMap, fmt, sync::Arc};
 
 mod aptos_version;
 mod consensus_config;
+mod execution_config;
+mod features;
 mod gas_schedule;
 mod validator_set;
 
@@ -26,8 +28,14 @@ pub use self::{
         Version, APTOS_MAX_KNOWN_VERSION, APTOS_VERSION_2, APTOS_VERSION_3, APTOS_VERSION_4,
     },
     consensus_config::{
-        ConsensusConfigV1, LeaderReputationType, OnChainConsensusConfig, ProposerElectionType,
+        BlockOutputLimit, ConsensusConfigV1, ConsensusConfigV2, ConsensusConfigV3,
+        LeaderReputationType, OnChainConsensusConfig, ProposerElectionType, ValidatorTxnConfig,
     },
+    execution_config::{
+        ExecutionConfigV1, ExecutionConfigV2, ExecutionLimits, OnChainExecutionConfig,
+        TransactionShufflerType,
+    },
+    features::{EnabledFeatures, FeatureActivation, FeatureFlag, Features},
     gas_schedule::GasSchedule,
     validator_set::{ConsensusScheme, ValidatorSet},
 };
@@ -61,6 +69,7 @@ pub const ON_CHAIN_CONFIG_REGISTRY: &[ConfigID] = &[
     ValidatorSet::CONFIG_ID,
     Version::CONFIG_ID,
     OnChainConsensusConfig::CONFIG_ID,
+    OnChainExecutionConfig::CONFIG_ID,
 ];
 
 #[derive(Clone, Debug, PartialEq)]
@@ -23,6 +23,7 @@ use aptos_crypto::{
 };
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
@@ -818,7 +819,7 @@ impl TransactionInfoListWithProof {
     ) -> Result<()> {
         let txn_info_hashes: Vec<_> = self
             .transaction_infos
-            .iter()
+            .par_iter()
             .map(CryptoHash::hash)
             .collect();
         self.ledger_info_to_transaction_infos_proof.verify(
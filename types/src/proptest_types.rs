@@ -390,6 +390,18 @@ fn new_raw_transaction(
             expiration_time_secs,
             chain_id,
         ),
+        TransactionPayload::OrderlessEntryFunction(orderless) => {
+            let (nonce, script_fn) = orderless.into_inner();
+            RawTransaction::new_orderless_entry_function(
+                sender,
+                nonce,
+                script_fn,
+                max_gas_amount,
+                gas_unit_price,
+                expiration_time_secs,
+                chain_id,
+            )
+        }
     }
 }
 
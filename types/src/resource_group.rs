@@ -0,0 +1,34 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A resource group stores several resources of an account under a single state key, the
+//! [`Path::ResourceGroup`](crate::access_path::Path::ResourceGroup) of the group's tag. Its
+//! value is the BCS-encoded map from the tags of its members to their BCS-encoded values.
+
+use crate::account_address::AccountAddress;
+use anyhow::{Context, Result};
+use move_deps::move_core_types::{ident_str, language_storage::StructTag};
+use std::collections::BTreeMap;
+
+/// The members of a resource group, by tag.
+pub type ResourceGroup = BTreeMap<StructTag, Vec<u8>>;
+
+/// Decodes the value stored for the resource group `group_tag`.
+pub fn decode_resource_group(group_tag: &StructTag, bytes: &[u8]) -> Result<ResourceGroup> {
+    bcs::from_bytes(bytes).with_context(|| format!("Failed to decode resource group {}", group_tag))
+}
+
+/// Encodes the value stored for a resource group.
+pub fn encode_resource_group(group: &ResourceGroup) -> Result<Vec<u8>> {
+    bcs::to_bytes(group).map_err(Into::into)
+}
+
+/// The tag of the group holding the core resources of objects, `0x1::object::ObjectGroup`.
+pub fn object_group_tag() -> StructTag {
+    StructTag {
+        address: AccountAddress::ONE,
+        module: ident_str!("object").to_owned(),
+        name: ident_str!("ObjectGroup").to_owned(),
+        type_params: vec![],
+    }
+}
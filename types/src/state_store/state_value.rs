@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::transaction::Version;
-use crate::{proof::SparseMerkleRangeProof, state_store::state_key::StateKey};
+use crate::{
+    ledger_info::LedgerInfo,
+    proof::{SparseMerkleProof, SparseMerkleRangeProof, TransactionInfoWithProof},
+    state_store::state_key::StateKey,
+};
+use anyhow::{ensure, format_err};
 use aptos_crypto::{
     hash::{CryptoHash, CryptoHasher, SPARSE_MERKLE_PLACEHOLDER_HASH},
     HashValue,
@@ -101,6 +106,65 @@ impl StateValueChunkWithProof {
     }
 }
 
+/// A state value (or its absence) at a specific version, together with the proofs
+/// needed to authenticate it against a `LedgerInfo`: a sparse merkle proof against
+/// the state checkpoint root at `version`, and a proof that the transaction info
+/// carrying that root is part of the ledger.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueWithProof {
+    pub version: Version,
+    pub value: Option<StateValue>,
+    pub state_proof: SparseMerkleProof,
+    pub transaction_info_with_proof: TransactionInfoWithProof,
+}
+
+impl StateValueWithProof {
+    pub fn new(
+        version: Version,
+        value: Option<StateValue>,
+        state_proof: SparseMerkleProof,
+        transaction_info_with_proof: TransactionInfoWithProof,
+    ) -> Self {
+        Self {
+            version,
+            value,
+            state_proof,
+            transaction_info_with_proof,
+        }
+    }
+
+    /// Verifies that the state value of `state_key` at `version` is `self.value`,
+    /// given a trusted `ledger_info` at or after `version`.
+    pub fn verify(
+        &self,
+        ledger_info: &LedgerInfo,
+        version: Version,
+        state_key: &StateKey,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.version == version,
+            "State value version ({}) is not the expected version ({}).",
+            self.version,
+            version,
+        );
+
+        self.transaction_info_with_proof
+            .verify(ledger_info, version)?;
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .ok_or_else(|| {
+                format_err!(
+                    "Transaction at version {} is not a state checkpoint.",
+                    version
+                )
+            })?;
+        self.state_proof
+            .verify(state_root_hash, state_key.hash(), self.value.as_ref())
+    }
+}
+
 /// Indicates a state value becomes stale since `stale_since_version`.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
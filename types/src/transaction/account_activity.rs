@@ -0,0 +1,56 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress,
+    transaction::{ExecutionStatus, Transaction, TransactionPayload, Version},
+};
+use move_deps::move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use serde::{Deserialize, Serialize};
+
+/// A summary of a user transaction, recorded for its sender, so that the transactions of an
+/// account can be filtered and counted without reading them.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccountActivity {
+    pub version: Version,
+    pub sequence_number: u64,
+    pub success: bool,
+    /// The module and name of the entry function called, `None` for scripts and module bundles
+    pub entry_function: Option<(ModuleId, Identifier)>,
+}
+
+impl AccountActivity {
+    /// The sender of a transaction and its activity, `None` if it's not a user transaction.
+    pub fn from_transaction(
+        version: Version,
+        transaction: &Transaction,
+        status: &ExecutionStatus,
+    ) -> Option<(AccountAddress, Self)> {
+        let txn = match transaction {
+            Transaction::UserTransaction(txn) => txn,
+            _ => return None,
+        };
+        let entry_function = match txn.payload() {
+            TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+            TransactionPayload::OrderlessEntryFunction(orderless) => {
+                Some(orderless.entry_function())
+            }
+            TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+        }
+        .map(|entry_function| {
+            (
+                entry_function.module().clone(),
+                entry_function.function().to_owned(),
+            )
+        });
+        Some((
+            txn.sender(),
+            Self {
+                version,
+                sequence_number: txn.sequence_number(),
+                success: status.is_success(),
+                entry_function,
+            },
+        ))
+    }
+}
@@ -3,13 +3,19 @@
 
 use crate::{
     account_address::AccountAddress,
-    transaction::{RawTransaction, RawTransactionWithData},
+    keyless::{KeylessPublicKey, KeylessSignature},
+    transaction::{
+        multi_key::{MultiKey, MultiKeySignature},
+        webauthn::WebAuthnAssertion,
+        RawTransaction, RawTransactionWithData,
+    },
 };
 use anyhow::{ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256r1_ecdsa,
     traits::Signature,
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
@@ -57,6 +63,21 @@ pub enum TransactionAuthenticator {
         secondary_signer_addresses: Vec<AccountAddress>,
         secondary_signers: Vec<AccountAuthenticator>,
     },
+    /// WebAuthn assertion from a secp256r1 authenticator, e.g. a passkey
+    WebAuthn {
+        public_key: secp256r1_ecdsa::PublicKey,
+        signature: WebAuthnAssertion,
+    },
+    /// Ephemeral signature with a proof of an OpenID Connect sign-in
+    Keyless {
+        public_key: KeylessPublicKey,
+        signature: KeylessSignature,
+    },
+    /// K-of-N signature, with public keys of mixed schemes
+    MultiKey {
+        public_key: MultiKey,
+        signature: MultiKeySignature,
+    },
 }
 
 impl TransactionAuthenticator {
@@ -79,6 +100,30 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a WebAuthn authenticator
+    pub fn web_authn(public_key: secp256r1_ecdsa::PublicKey, signature: WebAuthnAssertion) -> Self {
+        Self::WebAuthn {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a keyless authenticator
+    pub fn keyless(public_key: KeylessPublicKey, signature: KeylessSignature) -> Self {
+        Self::Keyless {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a multi-key authenticator
+    pub fn multi_key(public_key: MultiKey, signature: MultiKeySignature) -> Self {
+        Self::MultiKey {
+            public_key,
+            signature,
+        }
+    }
+
     /// Create a multi-agent authenticator
     pub fn multi_agent(
         sender: AccountAuthenticator,
@@ -127,6 +172,15 @@ impl TransactionAuthenticator {
                 }
                 Ok(())
             }
+            Self::WebAuthn {
+                public_key,
+                signature,
+            } => signature.verify(raw_txn, public_key),
+            Self::Keyless { signature, .. } => signature.verify_ephemeral_signature(raw_txn),
+            Self::MultiKey {
+                public_key,
+                signature,
+            } => signature.verify(raw_txn, public_key),
         }
     }
 
@@ -144,6 +198,18 @@ impl TransactionAuthenticator {
                 signature,
             } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
             Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::WebAuthn {
+                public_key,
+                signature,
+            } => AccountAuthenticator::web_authn(public_key.clone(), signature.clone()),
+            Self::Keyless {
+                public_key,
+                signature,
+            } => AccountAuthenticator::keyless(public_key.clone(), signature.clone()),
+            Self::MultiKey {
+                public_key,
+                signature,
+            } => AccountAuthenticator::multi_key(public_key.clone(), signature.clone()),
         }
     }
 
@@ -153,7 +219,10 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::WebAuthn { .. }
+            | Self::Keyless { .. }
+            | Self::MultiKey { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses,
@@ -168,7 +237,10 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::WebAuthn { .. }
+            | Self::Keyless { .. }
+            | Self::MultiKey { .. } => vec![],
             Self::MultiAgent {
                 sender: _,
                 secondary_signer_addresses: _,
@@ -201,6 +273,27 @@ impl fmt::Display for TransactionAuthenticator {
                     self.sender()
                 )
             }
+            Self::WebAuthn { .. } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: WebAuthn, sender: {}]",
+                    self.sender()
+                )
+            }
+            Self::Keyless { .. } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: Keyless, sender: {}]",
+                    self.sender()
+                )
+            }
+            Self::MultiKey { .. } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: MultiKey, sender: {}]",
+                    self.sender()
+                )
+            }
             Self::MultiAgent {
                 sender,
                 secondary_signer_addresses,
@@ -241,6 +334,9 @@ impl fmt::Display for TransactionAuthenticator {
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    WebAuthn = 2,
+    Keyless = 3,
+    MultiKey = 4,
     // ... add more schemes here
 }
 
@@ -249,6 +345,9 @@ impl fmt::Display for Scheme {
         let display = match self {
             Scheme::Ed25519 => "Ed25519",
             Scheme::MultiEd25519 => "MultiEd25519",
+            Scheme::WebAuthn => "WebAuthn",
+            Scheme::Keyless => "Keyless",
+            Scheme::MultiKey => "MultiKey",
         };
         write!(f, "Scheme::{}", display)
     }
@@ -266,6 +365,21 @@ pub enum AccountAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// WebAuthn assertion from a secp256r1 authenticator, e.g. a passkey
+    WebAuthn {
+        public_key: secp256r1_ecdsa::PublicKey,
+        signature: WebAuthnAssertion,
+    },
+    /// Ephemeral signature with a proof of an OpenID Connect sign-in
+    Keyless {
+        public_key: KeylessPublicKey,
+        signature: KeylessSignature,
+    },
+    /// K-of-N signature, with public keys of mixed schemes
+    MultiKey {
+        public_key: MultiKey,
+        signature: MultiKeySignature,
+    },
     // ... add more schemes here
 }
 
@@ -275,6 +389,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::WebAuthn { .. } => Scheme::WebAuthn,
+            Self::Keyless { .. } => Scheme::Keyless,
+            Self::MultiKey { .. } => Scheme::MultiKey,
         }
     }
 
@@ -297,6 +414,30 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Create a WebAuthn authenticator
+    pub fn web_authn(public_key: secp256r1_ecdsa::PublicKey, signature: WebAuthnAssertion) -> Self {
+        Self::WebAuthn {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a keyless authenticator
+    pub fn keyless(public_key: KeylessPublicKey, signature: KeylessSignature) -> Self {
+        Self::Keyless {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a multi-key authenticator
+    pub fn multi_key(public_key: MultiKey, signature: MultiKeySignature) -> Self {
+        Self::MultiKey {
+            public_key,
+            signature,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -308,6 +449,15 @@ impl AccountAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::WebAuthn {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::Keyless { signature, .. } => signature.verify_ephemeral_signature(message),
+            Self::MultiKey {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
         }
     }
 
@@ -316,6 +466,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::WebAuthn { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Keyless { public_key, .. } => public_key.to_bytes(),
+            Self::MultiKey { public_key, .. } => public_key.to_bytes(),
         }
     }
 
@@ -324,6 +477,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::WebAuthn { signature, .. } => signature.to_bytes(),
+            Self::Keyless { signature, .. } => signature.to_bytes(),
+            Self::MultiKey { signature, .. } => signature.to_bytes(),
         }
     }
 
@@ -342,6 +498,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => 1,
             Self::MultiEd25519 { signature, .. } => signature.signatures().len(),
+            Self::WebAuthn { .. } => 1,
+            Self::Keyless { .. } => 1,
+            Self::MultiKey { signature, .. } => signature.signatures().len(),
         }
     }
 }
@@ -394,6 +553,21 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from the secp256r1 public key of a WebAuthn authenticator
+    pub fn web_authn(public_key: &secp256r1_ecdsa::PublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::web_authn(public_key))
+    }
+
+    /// Create an authentication key from a keyless public key
+    pub fn keyless(public_key: &KeylessPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::keyless(public_key))
+    }
+
+    /// Create an authentication key from a multi-key public key
+    pub fn multi_key(public_key: &MultiKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::multi_key(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -449,6 +623,21 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from the secp256r1 public key of a WebAuthn authenticator
+    pub fn web_authn(public_key: &secp256r1_ecdsa::PublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::WebAuthn)
+    }
+
+    /// Construct a preimage from a keyless public key
+    pub fn keyless(public_key: &KeylessPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes(), Scheme::Keyless)
+    }
+
+    /// Construct a preimage from a multi-key public key, as its BCS serialization
+    pub fn multi_key(public_key: &MultiKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes(), Scheme::MultiKey)
+    }
+
     /// Construct a vector from this authentication key
     pub fn into_vec(self) -> Vec<u8> {
         self.0
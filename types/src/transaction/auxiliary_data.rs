@@ -0,0 +1,54 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account_address::AccountAddress;
+use aptos_crypto::HashValue;
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+/// Data about the execution of a transaction which isn't part of its output: the block it was
+/// executed in, and the gas unit price it paid. It's persisted with the transaction, so that
+/// readers don't need to join it from the block metadata transactions.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct TransactionAuxiliaryData {
+    block_hash: HashValue,
+    block_height: u64,
+    proposer: AccountAddress,
+    gas_unit_price: u64,
+}
+
+impl TransactionAuxiliaryData {
+    pub fn new(
+        block_hash: HashValue,
+        block_height: u64,
+        proposer: AccountAddress,
+        gas_unit_price: u64,
+    ) -> Self {
+        Self {
+            block_hash,
+            block_height,
+            proposer,
+            gas_unit_price,
+        }
+    }
+
+    /// The id of the block of the transaction
+    pub fn block_hash(&self) -> HashValue {
+        self.block_hash
+    }
+
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    pub fn proposer(&self) -> AccountAddress {
+        self.proposer
+    }
+
+    /// The gas unit price paid by a user transaction, 0 for other transactions
+    pub fn gas_unit_price(&self) -> u64 {
+        self.gas_unit_price
+    }
+}
@@ -11,11 +11,13 @@ use crate::{
         accumulator::InMemoryAccumulator, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     transaction::authenticator::{AccountAuthenticator, TransactionAuthenticator},
+    validator_txn::ValidatorTransaction,
     vm_status::{DiscardedVMStatus, KeptVMStatus, StatusCode, StatusType, VMStatus},
     write_set::WriteSet,
 };
 use anyhow::{ensure, format_err, Error, Result};
 use aptos_crypto::{
+    batch_verifier::BatchVerifier,
     ed25519::*,
     hash::{CryptoHash, EventAccumulatorHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
@@ -26,6 +28,7 @@ use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use move_deps::move_core_types::transaction_argument::convert_txn_args;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
@@ -34,17 +37,23 @@ use std::{
     fmt::{Debug, Display, Formatter},
 };
 
+mod account_activity;
 pub mod authenticator;
+mod auxiliary_data;
 mod change_set;
 mod module;
+pub mod multi_key;
 mod script;
 mod transaction_argument;
+pub mod webauthn;
 
+pub use account_activity::AccountActivity;
+pub use auxiliary_data::TransactionAuxiliaryData;
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
 pub use script::{
-    ArgumentABI, EntryABI, EntryFunction, EntryFunctionABI, Script, TransactionScriptABI,
-    TypeArgumentABI,
+    ArgumentABI, EntryABI, EntryFunction, EntryFunctionABI, OrderlessEntryFunction, Script,
+    TransactionScriptABI, TypeArgumentABI,
 };
 
 use crate::state_store::{state_key::StateKey, state_value::StateValue};
@@ -54,8 +63,40 @@ use std::{collections::BTreeSet, hash::Hash, ops::Deref, sync::atomic::AtomicU64
 pub use transaction_argument::{parse_transaction_argument, TransactionArgument};
 
 pub type Version = u64; // Height - also used for MVCC in StateDB
+
+/// The sequence number of transactions which are protected from replays by a nonce instead.
+pub const ORDERLESS_SEQUENCE_NUMBER: u64 = u64::MAX;
 pub type AtomicVersion = AtomicU64;
 
+/// What protects a transaction from being replayed, which identifies it among the transactions
+/// of its sender: the sequence number of the sender, or the nonce of an orderless transaction.
+///
+/// Sequence numbers are ordered before nonces.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum ReplayProtector {
+    SequenceNumber(u64),
+    Nonce(u64),
+}
+
+impl ReplayProtector {
+    /// The sequence number, None for a nonce
+    pub fn sequence_number(&self) -> Option<u64> {
+        match self {
+            ReplayProtector::SequenceNumber(sequence_number) => Some(*sequence_number),
+            ReplayProtector::Nonce(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ReplayProtector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayProtector::SequenceNumber(sequence_number) => write!(f, "{}", sequence_number),
+            ReplayProtector::Nonce(nonce) => write!(f, "nonce {}", nonce),
+        }
+    }
+}
+
 /// RawTransaction is the portion of a transaction that a client signs.
 #[derive(
     Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
@@ -159,6 +200,33 @@ impl RawTransaction {
         }
     }
 
+    /// Create a new `RawTransaction` with an entry function protected from replays by `nonce`.
+    ///
+    /// The sequence number of the sender is neither checked nor incremented by such a
+    /// transaction, so it's set to `ORDERLESS_SEQUENCE_NUMBER`.
+    pub fn new_orderless_entry_function(
+        sender: AccountAddress,
+        nonce: u64,
+        entry_function: EntryFunction,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        expiration_timestamp_secs: u64,
+        chain_id: ChainId,
+    ) -> Self {
+        RawTransaction {
+            sender,
+            sequence_number: ORDERLESS_SEQUENCE_NUMBER,
+            payload: TransactionPayload::OrderlessEntryFunction(OrderlessEntryFunction::new(
+                nonce,
+                entry_function,
+            )),
+            max_gas_amount,
+            gas_unit_price,
+            expiration_timestamp_secs,
+            chain_id,
+        }
+    }
+
     /// Create a new `RawTransaction` with a module to publish.
     ///
     /// A module transaction is the only way to publish code. Only one module per transaction
@@ -292,6 +360,13 @@ impl RawTransaction {
                 format!("{}::{}", script_fn.module(), script_fn.function()),
                 script_fn.args().to_vec(),
             ),
+            TransactionPayload::OrderlessEntryFunction(orderless) => {
+                let script_fn = orderless.entry_function();
+                (
+                    format!("{}::{}", script_fn.module(), script_fn.function()),
+                    script_fn.args().to_vec(),
+                )
+            }
             TransactionPayload::ModuleBundle(_) => ("module publishing".to_string(), vec![]),
         };
         let mut f_args: String = "".to_string();
@@ -364,9 +439,21 @@ pub enum TransactionPayload {
     ModuleBundle(ModuleBundle),
     /// A transaction that executes an existing entry function published on-chain.
     EntryFunction(EntryFunction),
+    /// A transaction that executes an existing entry function published on-chain, and which is
+    /// protected from replays by a nonce instead of the sequence number of the sender.
+    OrderlessEntryFunction(OrderlessEntryFunction),
 }
 
 impl TransactionPayload {
+    /// The nonce protecting the transaction from replays, if it isn't protected by the sequence
+    /// number of the sender
+    pub fn replay_nonce(&self) -> Option<u64> {
+        match self {
+            Self::OrderlessEntryFunction(orderless) => Some(orderless.nonce()),
+            _ => None,
+        }
+    }
+
     pub fn into_entry_function(self) -> EntryFunction {
         match self {
             Self::EntryFunction(f) => f,
@@ -528,6 +615,13 @@ impl SignedTransaction {
         self.raw_txn.sequence_number
     }
 
+    pub fn replay_protector(&self) -> ReplayProtector {
+        match self.raw_txn.payload.replay_nonce() {
+            Some(nonce) => ReplayProtector::Nonce(nonce),
+            None => ReplayProtector::SequenceNumber(self.raw_txn.sequence_number),
+        }
+    }
+
     pub fn chain_id(&self) -> ChainId {
         self.raw_txn.chain_id
     }
@@ -563,6 +657,43 @@ impl SignedTransaction {
         Ok(SignatureCheckedTransaction(self))
     }
 
+    /// Checks the signatures of the given transactions, as `check_signature` does, but verifies
+    /// single Ed25519 signatures in batches. Each transaction gets the same result as from
+    /// `check_signature`.
+    pub fn check_signatures_batched(
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Result<SignatureCheckedTransaction>> {
+        let mut verifier = BatchVerifier::<Ed25519Signature>::new();
+        let batched: Vec<bool> = txns
+            .iter()
+            .map(|txn| match &txn.authenticator {
+                TransactionAuthenticator::Ed25519 {
+                    public_key,
+                    signature,
+                } => {
+                    verifier.add(&txn.raw_txn, public_key.clone(), signature.clone());
+                    true
+                }
+                _ => false,
+            })
+            .collect();
+
+        let mut batch_results = verifier.verify().into_iter();
+        txns.into_iter()
+            .zip(batched)
+            .map(|(txn, batched)| {
+                if batched {
+                    batch_results
+                        .next()
+                        .expect("Every batched signature has a result")?;
+                    Ok(SignatureCheckedTransaction(txn))
+                } else {
+                    txn.check_signature()
+                }
+            })
+            .collect()
+    }
+
     pub fn contains_duplicate_signers(&self) -> bool {
         let mut all_signer_addresses = self.authenticator.secondary_signer_addreses();
         all_signer_addresses.push(self.sender());
@@ -696,6 +827,38 @@ pub enum ExecutionStatus {
         code_offset: u16,
     },
     MiscellaneousError(Option<StatusCode>),
+    /// The output of the transaction exceeded `limit`. For a write op exceeding its own size limit,
+    /// `state_key` is the key it writes.
+    ChangeSetLimitExceeded {
+        limit: ChangeSetLimit,
+        state_key: Option<StateKey>,
+    },
+}
+
+/// A limit on the output of a single transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub enum ChangeSetLimit {
+    /// The size of a single write op, key included
+    WriteOpBytes,
+    /// The total size of the write ops
+    WriteSetBytes,
+    /// The number of write ops
+    WriteOps,
+    /// The number of events
+    Events,
+}
+
+impl fmt::Display for ChangeSetLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = match self {
+            ChangeSetLimit::WriteOpBytes => "write op size",
+            ChangeSetLimit::WriteSetBytes => "write set size",
+            ChangeSetLimit::WriteOps => "number of write ops",
+            ChangeSetLimit::Events => "number of events",
+        };
+        write!(f, "{}", limit)
+    }
 }
 
 impl From<KeptVMStatus> for ExecutionStatus {
@@ -1278,55 +1441,54 @@ impl TransactionOutputListWithProof {
             self.transactions_and_outputs.len(),
         );
 
-        // Verify the events, status, gas used and transaction hashes.
-        itertools::zip_eq(
-            &self.transactions_and_outputs,
-            &self.proof.transaction_infos,
-        )
-        .map(|((txn, txn_output), txn_info)| {
-            // Check the events against the expected events root hash
-            verify_events_against_root_hash(&txn_output.events, txn_info)?;
-
-            // Verify the write set matches for both the transaction info and output
-            let write_set_hash = CryptoHash::hash(&txn_output.write_set);
-            ensure!(
-                txn_info.state_change_hash == write_set_hash,
-                "The write set in transaction output does not match the transaction info \
-                     in proof. Hash of write set in transaction output: {}. Write set hash in txn_info: {}.",
-                write_set_hash,
-                txn_info.state_change_hash,
-            );
+        // Verify the events, status, gas used and transaction hashes, in parallel since hashing
+        // the outputs dominates the cost of the verification. The lengths were checked above.
+        self.transactions_and_outputs
+            .par_iter()
+            .zip(&self.proof.transaction_infos)
+            .try_for_each(|((txn, txn_output), txn_info)| {
+                // Check the events against the expected events root hash
+                verify_events_against_root_hash(&txn_output.events, txn_info)?;
+
+                // Verify the write set matches for both the transaction info and output
+                let write_set_hash = CryptoHash::hash(&txn_output.write_set);
+                ensure!(
+                    txn_info.state_change_hash == write_set_hash,
+                    "The write set in transaction output does not match the transaction info \
+                         in proof. Hash of write set in transaction output: {}. Write set hash in txn_info: {}.",
+                    write_set_hash,
+                    txn_info.state_change_hash,
+                );
 
-            // Verify the gas matches for both the transaction info and output
-            ensure!(
-                txn_output.gas_used() == txn_info.gas_used(),
-                "The gas used in transaction output does not match the transaction info \
-                     in proof. Gas used in transaction output: {}. Gas used in txn_info: {}.",
-                txn_output.gas_used(),
-                txn_info.gas_used(),
-            );
+                // Verify the gas matches for both the transaction info and output
+                ensure!(
+                    txn_output.gas_used() == txn_info.gas_used(),
+                    "The gas used in transaction output does not match the transaction info \
+                         in proof. Gas used in transaction output: {}. Gas used in txn_info: {}.",
+                    txn_output.gas_used(),
+                    txn_info.gas_used(),
+                );
 
-            // Verify the execution status matches for both the transaction info and output.
-            ensure!(
-                *txn_output.status() == TransactionStatus::Keep(txn_info.status().clone()),
-                "The execution status of transaction output does not match the transaction \
-                     info in proof. Status in transaction output: {:?}. Status in txn_info: {:?}.",
-                txn_output.status(),
-                txn_info.status(),
-            );
+                // Verify the execution status matches for both the transaction info and output.
+                ensure!(
+                    *txn_output.status() == TransactionStatus::Keep(txn_info.status().clone()),
+                    "The execution status of transaction output does not match the transaction \
+                         info in proof. Status in transaction output: {:?}. Status in txn_info: {:?}.",
+                    txn_output.status(),
+                    txn_info.status(),
+                );
 
-            // Verify the transaction hashes match those of the transaction infos
-            let txn_hash = txn.hash();
-            ensure!(
-                txn_hash == txn_info.transaction_hash(),
-                "The transaction hash does not match the hash in transaction info. \
-                     Transaction hash: {:x}. Transaction hash in txn_info: {:x}.",
-                txn_hash,
-                txn_info.transaction_hash(),
-            );
-            Ok(())
-        })
-        .collect::<Result<Vec<_>>>()?;
+                // Verify the transaction hashes match those of the transaction infos
+                let txn_hash = txn.hash();
+                ensure!(
+                    txn_hash == txn_info.transaction_hash(),
+                    "The transaction hash does not match the hash in transaction info. \
+                         Transaction hash: {:x}. Transaction hash in txn_info: {:x}.",
+                    txn_hash,
+                    txn_info.transaction_hash(),
+                );
+                Ok(())
+            })?;
 
         // Verify the transaction infos are proven by the ledger info.
         self.proof
@@ -1463,6 +1625,10 @@ pub enum Transaction {
     /// in the TransactionInfo
     /// The hash value inside is unique block id which can generate unique hash of state checkpoint transaction
     StateCheckpoint(HashValue),
+
+    /// Transaction through which validators write consensus-derived data on chain, executed
+    /// after the block metadata.
+    ValidatorTransaction(ValidatorTransaction),
 }
 
 impl Transaction {
@@ -1484,6 +1650,7 @@ impl Transaction {
             Transaction::BlockMetadata(_block_metadata) => String::from("block_metadata"),
             // TODO: display proper information for client
             Transaction::StateCheckpoint(_) => String::from("state_checkpoint"),
+            Transaction::ValidatorTransaction(txn) => txn.to_string(),
         }
     }
 }
@@ -0,0 +1,206 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Multi-key accounts, controlled by K of N public keys which may each use a different signature
+//! scheme, so that keys held in HSMs supporting different curves can sign for the same account.
+//!
+//! The authentication key of a multi-key account is derived from the BCS serialization of its
+//! `MultiKey`, so both the order of the public keys and the number of signatures required
+//! determine its address.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    hash::CryptoHash,
+    secp256k1_ecdsa, secp256r1_ecdsa,
+    traits::Signature,
+};
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of public keys in a `MultiKey`
+pub const MAX_NUM_OF_KEYS: usize = 32;
+
+/// A public key of any of the schemes which may be mixed in a `MultiKey`
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AnyPublicKey {
+    Ed25519 {
+        public_key: Ed25519PublicKey,
+    },
+    Secp256k1Ecdsa {
+        public_key: secp256k1_ecdsa::PublicKey,
+    },
+    Secp256r1Ecdsa {
+        public_key: secp256r1_ecdsa::PublicKey,
+    },
+}
+
+impl AnyPublicKey {
+    pub fn ed25519(public_key: Ed25519PublicKey) -> Self {
+        Self::Ed25519 { public_key }
+    }
+
+    pub fn secp256k1_ecdsa(public_key: secp256k1_ecdsa::PublicKey) -> Self {
+        Self::Secp256k1Ecdsa { public_key }
+    }
+
+    pub fn secp256r1_ecdsa(public_key: secp256r1_ecdsa::PublicKey) -> Self {
+        Self::Secp256r1Ecdsa { public_key }
+    }
+}
+
+/// A signature of any of the schemes which may be mixed in a `MultiKey`
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AnySignature {
+    Ed25519 {
+        signature: Ed25519Signature,
+    },
+    Secp256k1Ecdsa {
+        signature: secp256k1_ecdsa::Signature,
+    },
+    Secp256r1Ecdsa {
+        signature: secp256r1_ecdsa::Signature,
+    },
+}
+
+impl AnySignature {
+    pub fn ed25519(signature: Ed25519Signature) -> Self {
+        Self::Ed25519 { signature }
+    }
+
+    pub fn secp256k1_ecdsa(signature: secp256k1_ecdsa::Signature) -> Self {
+        Self::Secp256k1Ecdsa { signature }
+    }
+
+    pub fn secp256r1_ecdsa(signature: secp256r1_ecdsa::Signature) -> Self {
+        Self::Secp256r1Ecdsa { signature }
+    }
+
+    /// Return Ok if the signature of `message` matches `public_key`, which must be of the same
+    /// scheme, Err otherwise
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &AnyPublicKey,
+    ) -> Result<()> {
+        match (self, public_key) {
+            (Self::Ed25519 { signature }, AnyPublicKey::Ed25519 { public_key }) => {
+                signature.verify(message, public_key)
+            }
+            (Self::Secp256k1Ecdsa { signature }, AnyPublicKey::Secp256k1Ecdsa { public_key }) => {
+                signature.verify(message, public_key)
+            }
+            (Self::Secp256r1Ecdsa { signature }, AnyPublicKey::Secp256r1Ecdsa { public_key }) => {
+                signature.verify(message, public_key)
+            }
+            _ => bail!("Signature scheme does not match the public key"),
+        }
+    }
+}
+
+/// The public key of a multi-key account: N public keys, of which K must sign
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MultiKey {
+    public_keys: Vec<AnyPublicKey>,
+    signatures_required: u8,
+}
+
+impl MultiKey {
+    pub fn new(public_keys: Vec<AnyPublicKey>, signatures_required: u8) -> Result<Self> {
+        let multi_key = Self {
+            public_keys,
+            signatures_required,
+        };
+        multi_key.check_threshold()?;
+        Ok(multi_key)
+    }
+
+    /// Keys are deserialized without going through `new`, so this is checked again on
+    /// verification
+    fn check_threshold(&self) -> Result<()> {
+        ensure!(
+            self.public_keys.len() <= MAX_NUM_OF_KEYS,
+            "A multi-key has at most {} public keys, got {}",
+            MAX_NUM_OF_KEYS,
+            self.public_keys.len()
+        );
+        ensure!(
+            self.signatures_required > 0
+                && self.signatures_required as usize <= self.public_keys.len(),
+            "Signatures required must be between 1 and the {} public keys, got {}",
+            self.public_keys.len(),
+            self.signatures_required
+        );
+        Ok(())
+    }
+
+    pub fn public_keys(&self) -> &[AnyPublicKey] {
+        &self.public_keys
+    }
+
+    pub fn signatures_required(&self) -> u8 {
+        self.signatures_required
+    }
+
+    /// Serialize the multi-key, as BCS
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Multi-key serialization cannot fail")
+    }
+}
+
+/// The signatures of a multi-key account, each with the index of its public key in the
+/// `MultiKey`, in increasing order of index
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MultiKeySignature {
+    signatures: Vec<(u8, AnySignature)>,
+}
+
+impl MultiKeySignature {
+    /// Create a multi-key signature from signatures in any order, with the index of their public
+    /// key. Fails if two signatures are for the same public key.
+    pub fn new(mut signatures: Vec<(u8, AnySignature)>) -> Result<Self> {
+        signatures.sort_by_key(|(index, _)| *index);
+        ensure!(
+            signatures.windows(2).all(|pair| pair[0].0 != pair[1].0),
+            "Duplicate signatures for the same public key"
+        );
+        Ok(Self { signatures })
+    }
+
+    pub fn signatures(&self) -> &[(u8, AnySignature)] {
+        &self.signatures
+    }
+
+    /// Return Ok if there are enough signatures of `message` from distinct public keys of
+    /// `public_key`, and they all match their public key, Err otherwise
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &MultiKey,
+    ) -> Result<()> {
+        public_key.check_threshold()?;
+        ensure!(
+            self.signatures.len() >= public_key.signatures_required as usize,
+            "{} of the {} required signatures were provided",
+            self.signatures.len(),
+            public_key.signatures_required
+        );
+        // Strictly increasing indices, so that each key signs at most once
+        ensure!(
+            self.signatures.windows(2).all(|pair| pair[0].0 < pair[1].0),
+            "Signatures must be sorted by the index of their public key, without duplicates"
+        );
+        for (index, signature) in &self.signatures {
+            let key = public_key
+                .public_keys
+                .get(*index as usize)
+                .ok_or_else(|| anyhow!("No public key at index {}", index))?;
+            signature.verify(message, key)?;
+        }
+        Ok(())
+    }
+
+    /// Serialize the signatures, as BCS
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Multi-key signature serialization cannot fail")
+    }
+}
@@ -149,3 +149,32 @@ impl EntryFunction {
         (self.module, self.function, self.ty_args, self.args)
     }
 }
+
+/// Call a Move entry function, protected from replays by a nonce of the sender instead of the
+/// sequence number, so that transactions of a sender don't have to execute in order.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OrderlessEntryFunction {
+    nonce: u64,
+    entry_function: EntryFunction,
+}
+
+impl OrderlessEntryFunction {
+    pub fn new(nonce: u64, entry_function: EntryFunction) -> Self {
+        OrderlessEntryFunction {
+            nonce,
+            entry_function,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn entry_function(&self) -> &EntryFunction {
+        &self.entry_function
+    }
+
+    pub fn into_inner(self) -> (u64, EntryFunction) {
+        (self.nonce, self.entry_function)
+    }
+}
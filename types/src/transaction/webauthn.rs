@@ -0,0 +1,160 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebAuthn assertions, with which accounts controlled by a WebAuthn authenticator, such as a
+//! passkey, sign transactions.
+//!
+//! An authenticator does not sign the transaction itself, but its `authenticatorData` followed by
+//! the SHA2-256 digest of the `clientDataJSON` assembled by the client, which embeds a challenge.
+//! For a transaction, the challenge is the SHA3-256 digest of its signing message,
+//! base64url-encoded without padding. See
+//! <https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion> for the checks performed by a
+//! relying party, of which we only perform those that do not depend on the relying party: the
+//! origin and relying party ID are not checked, as the public key is already bound to them.
+
+use anyhow::{ensure, Result};
+use aptos_crypto::{hash::CryptoHash, secp256r1_ecdsa, traits::Signature, HashValue};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The `type` of the client data of an assertion, as opposed to a credential creation
+pub const CLIENT_DATA_TYPE_GET: &str = "webauthn.get";
+
+/// The length of the authenticator data, without extensions: the relying party ID hash, the
+/// flags and the signature counter
+pub const MIN_AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// The index of the flags in the authenticator data
+const FLAGS_INDEX: usize = 32;
+
+/// The flag set in the authenticator data when the user was present
+pub const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// The flag set in the authenticator data when the user was verified, e.g. by biometrics
+pub const FLAG_USER_VERIFIED: u8 = 0x04;
+
+/// The fields of a WebAuthn `AuthenticatorAssertionResponse` needed to verify its signature
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct WebAuthnAssertion {
+    signature: secp256r1_ecdsa::Signature,
+    #[serde(with = "serde_bytes")]
+    authenticator_data: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    client_data_json: Vec<u8>,
+}
+
+/// The fields of the `CollectedClientData` we check
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+}
+
+impl WebAuthnAssertion {
+    pub fn new(
+        signature: secp256r1_ecdsa::Signature,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            signature,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    pub fn signature(&self) -> &secp256r1_ecdsa::Signature {
+        &self.signature
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &[u8] {
+        &self.client_data_json
+    }
+
+    /// The challenge a client must request an assertion for, to sign `message`
+    pub fn challenge<T: Serialize + CryptoHash>(message: &T) -> String {
+        let signing_message = aptos_crypto::traits::signing_message(message);
+        base64::encode_config(
+            HashValue::sha3_256_of(&signing_message).to_vec(),
+            base64::URL_SAFE_NO_PAD,
+        )
+    }
+
+    /// The data signed by the authenticator: the authenticator data followed by the SHA2-256
+    /// digest of the client data
+    fn signed_data(&self) -> Vec<u8> {
+        let mut signed_data = self.authenticator_data.clone();
+        signed_data.extend_from_slice(&Sha256::digest(&self.client_data_json));
+        signed_data
+    }
+
+    /// Return Ok if this is an assertion of a present user on `message`, signed under
+    /// `public_key`, Err otherwise
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &secp256r1_ecdsa::PublicKey,
+    ) -> Result<()> {
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)?;
+        ensure!(
+            client_data.ty == CLIENT_DATA_TYPE_GET,
+            "WebAuthn client data has type {}, expected {}",
+            client_data.ty,
+            CLIENT_DATA_TYPE_GET
+        );
+        ensure!(
+            client_data.challenge == Self::challenge(message),
+            "WebAuthn challenge does not match the message"
+        );
+        ensure!(
+            self.authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LENGTH,
+            "WebAuthn authenticator data is too short"
+        );
+        ensure!(
+            self.authenticator_data[FLAGS_INDEX] & FLAG_USER_PRESENT != 0,
+            "WebAuthn user was not present"
+        );
+        self.signature
+            .verify_arbitrary_msg(&self.signed_data(), public_key)
+    }
+
+    /// Serialize the assertion, as BCS
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("WebAuthn assertion serialization cannot fail")
+    }
+
+    /// Signs `message` as an authenticator holding `private_key` would, with the `origin` and
+    /// relying party ID `localhost`
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn sign<T: Serialize + CryptoHash>(
+        private_key: &secp256r1_ecdsa::PrivateKey,
+        message: &T,
+    ) -> Self {
+        use aptos_crypto::SigningKey;
+
+        let mut authenticator_data = Sha256::digest(b"localhost").to_vec();
+        authenticator_data.push(FLAG_USER_PRESENT | FLAG_USER_VERIFIED);
+        authenticator_data.extend_from_slice(&0u32.to_be_bytes());
+        let client_data_json = serde_json::json!({
+            "type": CLIENT_DATA_TYPE_GET,
+            "challenge": Self::challenge(message),
+            "origin": "http://localhost",
+        })
+        .to_string()
+        .into_bytes();
+
+        let mut assertion = Self::new(
+            // Replaced below, once the signed data is known
+            private_key.sign_arbitrary_message(&[]),
+            authenticator_data,
+            client_data_json,
+        );
+        assertion.signature = private_key.sign_arbitrary_message(&assertion.signed_data());
+        assertion
+    }
+}
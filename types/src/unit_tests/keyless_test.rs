@@ -0,0 +1,242 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress,
+    aggregate_signature::PartialSignatures,
+    chain_id::ChainId,
+    keyless::{
+        Configuration, Groth16Proof, Groth16VerificationKey, IdCommitment, Issuer,
+        KeylessPublicKey, KeylessSignature, ProviderJWKs, QuorumCertifiedUpdate, RsaJwk,
+    },
+    transaction::{
+        authenticator::{AuthenticationKey, TransactionAuthenticator},
+        RawTransaction, Script, SignedTransaction,
+    },
+    validator_verifier::random_validator_verifier,
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, PreparedVerifyingKey, ProvingKey};
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError, Variable},
+};
+use ark_snark::SNARK;
+use std::convert::TryFrom;
+
+const ISSUER: &str = "https://accounts.google.com";
+const JWT_HEADER: &str = r#"{"alg":"RS256","kid":"1","typ":"JWT"}"#;
+
+/// A circuit which only exposes its input, so that a proof can be made for any statement
+struct PublicInputCircuit(Fr);
+
+impl ConstraintSynthesizer<Fr> for PublicInputCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let input = cs.new_input_variable(|| Ok(self.0))?;
+        let witness = cs.new_witness_variable(|| Ok(self.0))?;
+        cs.enforce_constraint(lc!() + input, lc!() + Variable::One, lc!() + witness)
+    }
+}
+
+struct TestContext {
+    config: Configuration,
+    pk: ProvingKey<Bn254>,
+    pvk: PreparedVerifyingKey<Bn254>,
+    ephemeral_private_key: Ed25519PrivateKey,
+    public_key: KeylessPublicKey,
+    raw_txn: RawTransaction,
+}
+
+impl TestContext {
+    fn new() -> Self {
+        let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(
+            PublicInputCircuit(Fr::from(0u64)),
+            &mut ark_std::test_rng(),
+        )
+        .unwrap();
+        // Round trip the key through its on-chain representation
+        let pvk = PreparedVerifyingKey::try_from(&Groth16VerificationKey::from(&vk)).unwrap();
+        let config = Configuration {
+            issuers: vec![Issuer {
+                iss: ISSUER.to_string(),
+                jwks: vec![RsaJwk {
+                    kid: "1".to_string(),
+                    alg: "RS256".to_string(),
+                    e: "AQAB".to_string(),
+                    n: "n".to_string(),
+                }],
+            }],
+            max_exp_horizon_secs: 10_000_000,
+            max_jwt_header_bytes: 1024,
+        };
+        let public_key = KeylessPublicKey {
+            iss_val: ISSUER.to_string(),
+            idc: IdCommitment::new([7; IdCommitment::LENGTH]),
+        };
+        let raw_txn = RawTransaction::new_script(
+            AccountAddress::random(),
+            0,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        );
+        Self {
+            config,
+            pk,
+            pvk,
+            ephemeral_private_key: Ed25519PrivateKey::generate_for_testing(),
+            public_key,
+            raw_txn,
+        }
+    }
+
+    /// A signature of the transaction, proven for `public_key` with the JWK of `jwt_header_json`
+    fn sign(&self, public_key: &KeylessPublicKey, jwt_header_json: &str) -> KeylessSignature {
+        let mut signature = KeylessSignature {
+            proof: Groth16Proof::new(Default::default(), Default::default(), Default::default()),
+            jwt_header_json: jwt_header_json.to_string(),
+            exp_date_secs: 1_000,
+            exp_horizon_secs: 1_000,
+            ephemeral_pubkey: self.ephemeral_private_key.public_key(),
+            ephemeral_signature: self.ephemeral_private_key.sign(&self.raw_txn),
+        };
+        let jwk = &self.config.issuers[0].jwks[0];
+        let public_input = signature.public_input(public_key, jwk);
+        let proof = Groth16::<Bn254>::prove(
+            &self.pk,
+            PublicInputCircuit(public_input),
+            &mut ark_std::test_rng(),
+        )
+        .unwrap();
+        signature.proof = proof.into();
+        signature
+    }
+}
+
+#[test]
+fn test_keyless_signature() {
+    let context = TestContext::new();
+    let signature = context.sign(&context.public_key, JWT_HEADER);
+    signature
+        .verify_proof(&context.public_key, &context.config, &context.pvk)
+        .unwrap();
+    signature.verify_expiry(999).unwrap();
+    assert!(signature.verify_expiry(1_000).is_err());
+
+    let txn = SignedTransaction::new_with_authenticator(
+        context.raw_txn.clone(),
+        TransactionAuthenticator::keyless(context.public_key.clone(), signature),
+    );
+    assert!(txn.check_signature().is_ok());
+    assert_eq!(
+        txn.authenticator().sender().authentication_key(),
+        AuthenticationKey::keyless(&context.public_key)
+    );
+}
+
+#[test]
+fn test_keyless_signature_for_another_account() {
+    let context = TestContext::new();
+    let other_public_key = KeylessPublicKey {
+        iss_val: ISSUER.to_string(),
+        idc: IdCommitment::new([8; IdCommitment::LENGTH]),
+    };
+    let signature = context.sign(&other_public_key, JWT_HEADER);
+    assert!(signature
+        .verify_proof(&context.public_key, &context.config, &context.pvk)
+        .is_err());
+}
+
+#[test]
+fn test_keyless_signature_with_tampered_ephemeral_key() {
+    let context = TestContext::new();
+    let mut signature = context.sign(&context.public_key, JWT_HEADER);
+    let other_private_key = Ed25519PrivateKey::generate_for_testing();
+    signature.ephemeral_pubkey = other_private_key.public_key();
+    signature.ephemeral_signature = other_private_key.sign(&context.raw_txn);
+    assert!(signature
+        .verify_ephemeral_signature(&context.raw_txn)
+        .is_ok());
+    assert!(signature
+        .verify_proof(&context.public_key, &context.config, &context.pvk)
+        .is_err());
+}
+
+#[test]
+fn test_keyless_signature_rejected_by_configuration() {
+    let context = TestContext::new();
+
+    // The key is not one of the issuer's
+    let signature = context.sign(
+        &context.public_key,
+        r#"{"alg":"RS256","kid":"2","typ":"JWT"}"#,
+    );
+    assert!(signature
+        .verify_proof(&context.public_key, &context.config, &context.pvk)
+        .is_err());
+
+    // The algorithm is not supported by the circuit
+    let signature = context.sign(
+        &context.public_key,
+        r#"{"alg":"ES256","kid":"1","typ":"JWT"}"#,
+    );
+    assert!(signature
+        .verify_proof(&context.public_key, &context.config, &context.pvk)
+        .is_err());
+
+    // The ephemeral key expires too long after the JWT was issued
+    let signature = context.sign(&context.public_key, JWT_HEADER);
+    let mut config = context.config.clone();
+    config.max_exp_horizon_secs = signature.exp_horizon_secs - 1;
+    assert!(signature
+        .verify_proof(&context.public_key, &config, &context.pvk)
+        .is_err());
+
+    // The issuer is no longer accepted
+    let mut config = context.config.clone();
+    config.issuers.clear();
+    assert!(signature
+        .verify_proof(&context.public_key, &config, &context.pvk)
+        .is_err());
+}
+
+#[test]
+fn test_quorum_certified_jwk_update() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+    let jwk = |kid: &str| RsaJwk {
+        kid: kid.to_owned(),
+        alg: "RS256".to_owned(),
+        e: "AQAB".to_owned(),
+        n: "n".to_owned(),
+    };
+    let update = ProviderJWKs::new(ISSUER.to_owned(), 1, vec![jwk("2"), jwk("1")]);
+    assert_eq!(update.jwks[0].kid, "1");
+
+    let certify = |num_signers: usize| {
+        let mut signatures = PartialSignatures::empty();
+        for signer in &signers[..num_signers] {
+            signatures.add_signature(signer.author(), signer.sign(&update));
+        }
+        QuorumCertifiedUpdate {
+            update: update.clone(),
+            multi_sig: verifier.aggregate_signatures(&signatures).unwrap(),
+        }
+    };
+
+    let certified = certify(3);
+    certified.verify(&verifier).unwrap();
+    let decoded =
+        QuorumCertifiedUpdate::from_bytes(certified.to_validator_txn().payload()).unwrap();
+    assert_eq!(decoded, certified);
+
+    // Not signed by a quorum
+    assert!(certify(2).verify(&verifier).is_err());
+
+    // Signed for another update
+    let mut tampered = certified;
+    tampered.update.version = 2;
+    assert!(tampered.verify(&verifier).is_err());
+}
@@ -5,7 +5,10 @@ mod access_path_test;
 mod block_metadata_test;
 mod code_debug_fmt_test;
 mod contract_event_test;
+mod keyless_test;
+mod resource_group_test;
 mod transaction_test;
 mod trusted_state_test;
+mod validator_txn_test;
 mod validator_set_test;
 mod write_set_test;
@@ -0,0 +1,74 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    access_path::{AccessPath, Path},
+    account_address::AccountAddress,
+    account_state::AccountState,
+    resource_group::{decode_resource_group, encode_resource_group, object_group_tag},
+};
+use move_deps::move_core_types::{
+    language_storage::{ResourceKey, StructTag},
+    parser::parse_struct_tag,
+};
+use std::collections::BTreeMap;
+
+fn struct_tag(module: &str, name: &str) -> StructTag {
+    parse_struct_tag(&format!("0x1::{}::{}", module, name)).unwrap()
+}
+
+#[test]
+fn resource_group_access_path() {
+    let address = AccountAddress::new([1u8; AccountAddress::LENGTH]);
+    let access_path = AccessPath::resource_group_access_path(address, object_group_tag());
+    assert!(access_path.get_path() == Path::ResourceGroup(object_group_tag()));
+    assert_eq!(access_path.get_struct_tag(), None);
+    assert_ne!(
+        access_path,
+        AccessPath::resource_access_path(ResourceKey::new(address, object_group_tag()))
+    );
+}
+
+#[test]
+fn account_state_resource_group_members() {
+    let address = AccountAddress::new([1u8; AccountAddress::LENGTH]);
+    let coin_store = struct_tag("coin", "CoinStore");
+    let object_core = struct_tag("object", "ObjectCore");
+    let token = struct_tag("token", "Token");
+
+    let mut group = BTreeMap::new();
+    group.insert(object_core.clone(), vec![1, 2, 3]);
+    group.insert(token.clone(), vec![4]);
+    let group_bytes = encode_resource_group(&group).unwrap();
+    assert_eq!(
+        decode_resource_group(&object_group_tag(), &group_bytes).unwrap(),
+        group
+    );
+
+    let mut data = BTreeMap::new();
+    data.insert(AccessPath::resource_access_vec(coin_store.clone()), vec![5]);
+    data.insert(
+        AccessPath::resource_group_access_vec(object_group_tag()),
+        group_bytes,
+    );
+    let account_state = AccountState::new(address, data);
+
+    let resources: Vec<_> = account_state.get_resources().collect();
+    assert_eq!(resources, vec![(coin_store, &[5u8][..])]);
+    assert_eq!(
+        account_state.get_resource_group_members().unwrap(),
+        vec![(object_core, vec![1, 2, 3]), (token, vec![4])]
+    );
+}
+
+#[test]
+fn account_state_invalid_resource_group() {
+    let address = AccountAddress::new([1u8; AccountAddress::LENGTH]);
+    let mut data = BTreeMap::new();
+    data.insert(
+        AccessPath::resource_group_access_vec(object_group_tag()),
+        vec![0xff],
+    );
+    let account_state = AccountState::new(address, data);
+    assert!(account_state.get_resource_group_members().is_err());
+}
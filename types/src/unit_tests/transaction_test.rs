@@ -5,16 +5,19 @@ use crate::{
     account_address::AccountAddress,
     chain_id::ChainId,
     transaction::{
+        authenticator::{AuthenticationKey, TransactionAuthenticator},
+        multi_key::{AnyPublicKey, AnySignature, MultiKey, MultiKeySignature},
+        webauthn::WebAuthnAssertion,
         AccountTransactionsWithProof, RawTransaction, Script, SignedTransaction, Transaction,
         TransactionInfo, TransactionListWithProof, TransactionPayload, TransactionWithProof,
     },
 };
 use aptos_crypto::{
     ed25519::{self, Ed25519PrivateKey, Ed25519Signature},
-    PrivateKey, Uniform,
+    secp256k1_ecdsa, secp256r1_ecdsa, PrivateKey, SigningKey, Uniform,
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
-use proptest::prelude::*;
+use proptest::{collection::vec, prelude::*};
 use std::convert::TryFrom;
 
 #[test]
@@ -36,7 +39,129 @@ fn test_invalid_signature() {
         .expect_err("signature checking should fail");
 }
 
+#[test]
+fn test_multi_key_threshold() {
+    let public_key = AnyPublicKey::ed25519(Ed25519PrivateKey::generate_for_testing().public_key());
+    assert!(MultiKey::new(vec![public_key.clone(); 2], 0).is_err());
+    assert!(MultiKey::new(vec![public_key.clone(); 2], 3).is_err());
+    assert!(MultiKey::new(vec![public_key.clone(); 33], 1).is_err());
+    assert!(MultiKey::new(vec![public_key; 2], 2).is_ok());
+}
+
+#[test]
+fn test_web_authn_authentication_key() {
+    let public_key = secp256r1_ecdsa::PublicKey::try_from(
+        &hex::decode("04d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97fa5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f48").unwrap()[..],
+    )
+    .unwrap();
+    assert_eq!(
+        AuthenticationKey::web_authn(&public_key).to_vec(),
+        hex::decode("9b7b35a2e64c1ec645871ba43283e7520358120027e322a574a4066174e38a8e").unwrap()
+    );
+}
+
 proptest! {
+    #[test]
+    fn test_web_authn_signature(
+        raw_txn in any::<RawTransaction>(),
+        other_raw_txn in any::<RawTransaction>(),
+        keypair in secp256r1_ecdsa::keypair_strategy(),
+    ) {
+        let assertion = WebAuthnAssertion::sign(&keypair.private_key, &raw_txn);
+        let txn = SignedTransaction::new_with_authenticator(
+            raw_txn.clone(),
+            TransactionAuthenticator::web_authn(keypair.public_key.clone(), assertion.clone()),
+        );
+        prop_assert!(txn.check_signature().is_ok());
+        prop_assert_eq!(
+            txn.authenticator().sender().authentication_key(),
+            AuthenticationKey::web_authn(&keypair.public_key)
+        );
+
+        // The assertion is for another transaction
+        prop_assume!(other_raw_txn != raw_txn);
+        let txn = SignedTransaction::new_with_authenticator(
+            other_raw_txn,
+            TransactionAuthenticator::web_authn(keypair.public_key.clone(), assertion.clone()),
+        );
+        prop_assert!(txn.check_signature().is_err());
+
+        // The user was not present
+        let mut authenticator_data = assertion.authenticator_data().to_vec();
+        authenticator_data[32] = 0;
+        let absent = WebAuthnAssertion::new(
+            assertion.signature().clone(),
+            authenticator_data,
+            assertion.client_data_json().to_vec(),
+        );
+        prop_assert!(absent.verify(&raw_txn, &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_multi_key_signature(
+        raw_txn in any::<RawTransaction>(),
+        ed25519_keypair in ed25519::keypair_strategy(),
+        secp256k1_keypair in secp256k1_ecdsa::keypair_strategy(),
+        secp256r1_keypair in secp256r1_ecdsa::keypair_strategy(),
+    ) {
+        let public_key = MultiKey::new(
+            vec![
+                AnyPublicKey::ed25519(ed25519_keypair.public_key.clone()),
+                AnyPublicKey::secp256k1_ecdsa(secp256k1_keypair.public_key.clone()),
+                AnyPublicKey::secp256r1_ecdsa(secp256r1_keypair.public_key.clone()),
+            ],
+            2,
+        )
+        .unwrap();
+        let ed25519_signature = AnySignature::ed25519(ed25519_keypair.private_key.sign(&raw_txn));
+        let secp256k1_signature =
+            AnySignature::secp256k1_ecdsa(secp256k1_keypair.private_key.sign(&raw_txn));
+        let secp256r1_signature =
+            AnySignature::secp256r1_ecdsa(secp256r1_keypair.private_key.sign(&raw_txn));
+
+        let signature = MultiKeySignature::new(vec![
+            (2, secp256r1_signature.clone()),
+            (1, secp256k1_signature.clone()),
+        ])
+        .unwrap();
+        let txn = SignedTransaction::new_with_authenticator(
+            raw_txn.clone(),
+            TransactionAuthenticator::multi_key(public_key.clone(), signature),
+        );
+        prop_assert!(txn.check_signature().is_ok());
+        prop_assert_eq!(
+            txn.authenticator().sender().authentication_key(),
+            AuthenticationKey::multi_key(&public_key)
+        );
+
+        // Not enough signatures
+        let signature = MultiKeySignature::new(vec![(0, ed25519_signature.clone())]).unwrap();
+        prop_assert!(signature.verify(&raw_txn, &public_key).is_err());
+
+        // The same key signs twice
+        prop_assert!(MultiKeySignature::new(vec![
+            (0, ed25519_signature.clone()),
+            (0, ed25519_signature.clone()),
+        ])
+        .is_err());
+
+        // A signature of another scheme than its public key
+        let signature = MultiKeySignature::new(vec![
+            (0, ed25519_signature),
+            (1, secp256r1_signature),
+        ])
+        .unwrap();
+        prop_assert!(signature.verify(&raw_txn, &public_key).is_err());
+
+        // No public key at the index
+        let signature = MultiKeySignature::new(vec![
+            (1, secp256k1_signature.clone()),
+            (3, secp256k1_signature),
+        ])
+        .unwrap();
+        prop_assert!(signature.verify(&raw_txn, &public_key).is_err());
+    }
+
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
         let txn = raw_txn.sign(&keypair.private_key, keypair.public_key).unwrap();
@@ -44,6 +169,37 @@ proptest! {
         assert!(signed_txn.check_signature().is_ok());
     }
 
+    #[test]
+    fn test_check_signatures_batched(
+        raw_txns in vec(any::<RawTransaction>(), 1..20),
+        keypair in ed25519::keypair_strategy(),
+        invalid in any::<prop::sample::Index>(),
+    ) {
+        let invalid = invalid.index(raw_txns.len());
+        let txns: Vec<_> = raw_txns
+            .into_iter()
+            .enumerate()
+            .map(|(i, raw_txn)| {
+                if i == invalid {
+                    SignedTransaction::new(
+                        raw_txn,
+                        keypair.public_key.clone(),
+                        Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(),
+                    )
+                } else {
+                    raw_txn
+                        .sign(&keypair.private_key, keypair.public_key.clone())
+                        .unwrap()
+                        .into_inner()
+                }
+            })
+            .collect();
+        let results = SignedTransaction::check_signatures_batched(txns);
+        for (i, result) in results.iter().enumerate() {
+            prop_assert_eq!(result.is_err(), i == invalid);
+        }
+    }
+
     #[test]
     fn transaction_payload_bcs_roundtrip(txn_payload in any::<TransactionPayload>()) {
         assert_canonical_encode_decode(txn_payload);
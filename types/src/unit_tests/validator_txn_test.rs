@@ -0,0 +1,87 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aggregate_signature::PartialSignatures,
+    validator_signer::ValidatorSigner,
+    validator_txn::{QuorumCertifiedData, ValidatorTransaction, ValidatorTxnData},
+    validator_verifier::{random_validator_verifier, ValidatorVerifier},
+};
+
+const EPOCH: u64 = 3;
+
+fn certify(
+    signers: &[ValidatorSigner],
+    verifier: &ValidatorVerifier,
+    data: ValidatorTxnData,
+) -> QuorumCertifiedData {
+    let mut signatures = PartialSignatures::empty();
+    for signer in signers {
+        signatures.add_signature(signer.author(), signer.sign(&data));
+    }
+    QuorumCertifiedData {
+        data,
+        multi_sig: verifier.aggregate_signatures(&signatures).unwrap(),
+    }
+}
+
+fn prices(epoch: u64) -> ValidatorTxnData {
+    ValidatorTxnData {
+        kind: ValidatorTransaction::OBSERVED_PRICES_KIND,
+        epoch,
+        data: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn test_quorum_certified_data() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+
+    let txn = certify(&signers[..3], &verifier, prices(EPOCH))
+        .to_validator_txn()
+        .unwrap();
+    assert!(matches!(txn, ValidatorTransaction::ObservedPrices(_)));
+    txn.verify(&verifier, EPOCH).unwrap();
+    // Replayed in a later epoch
+    assert!(txn.verify(&verifier, EPOCH + 1).is_err());
+
+    // Not signed by a quorum
+    let txn = certify(&signers[..2], &verifier, prices(EPOCH))
+        .to_validator_txn()
+        .unwrap();
+    assert!(txn.verify(&verifier, EPOCH).is_err());
+
+    // Tampered with after signing
+    let mut certified = certify(&signers, &verifier, prices(EPOCH));
+    certified.data.data.push(4);
+    let txn = certified.to_validator_txn().unwrap();
+    assert!(txn.verify(&verifier, EPOCH).is_err());
+}
+
+#[test]
+fn test_uncertified_payloads_rejected() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+
+    // Raw data, as proposed before payloads had to be certified
+    assert!(ValidatorTransaction::DKGTranscript(vec![0; 10])
+        .verify(&verifier, EPOCH)
+        .is_err());
+    assert!(ValidatorTransaction::ObservedJWKUpdate(vec![1; 10])
+        .verify(&verifier, EPOCH)
+        .is_err());
+
+    // Data certified for another kind
+    let payload = bcs::to_bytes(&certify(&signers, &verifier, prices(EPOCH))).unwrap();
+    assert!(ValidatorTransaction::DKGTranscript(payload)
+        .verify(&verifier, EPOCH)
+        .is_err());
+
+    // JWK updates are certified as a `QuorumCertifiedUpdate`
+    let jwk_data = ValidatorTxnData {
+        kind: ValidatorTransaction::OBSERVED_JWK_UPDATE_KIND,
+        ..prices(EPOCH)
+    };
+    assert!(certify(&signers, &verifier, jwk_data)
+        .to_validator_txn()
+        .is_err());
+}
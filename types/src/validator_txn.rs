@@ -0,0 +1,175 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aggregate_signature::AggregateSignature, keyless::QuorumCertifiedUpdate,
+    validator_verifier::ValidatorVerifier,
+};
+use anyhow::{bail, ensure, Context, Result};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use move_deps::move_core_types::{account_address::AccountAddress, value::MoveValue};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A transaction through which validators write consensus-derived data on chain, e.g. the
+/// transcript of a distributed key generation, or JWKs and prices observed off chain.
+///
+/// A validator transaction has no sender, signature or gas. The block proposer alone chooses
+/// which validator transactions go in a block, so certifying the block doesn't vouch for their
+/// content: every payload carries the signatures of a quorum of validators over its data, which
+/// is checked against the validator set before voting for the block and again by the VM before
+/// executing it. The VM then calls `validator_txn::process`, which records the latest payload of
+/// each kind.
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[derive(
+    Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
+)]
+pub enum ValidatorTransaction {
+    /// The transcript of a distributed key generation run by the validators: a BCS-serialized
+    /// `QuorumCertifiedData`.
+    DKGTranscript(#[serde(with = "serde_bytes")] Vec<u8>),
+    /// The JSON Web Keys of an OpenID Connect provider, as observed by the validators: a
+    /// BCS-serialized `keyless::QuorumCertifiedUpdate`.
+    ObservedJWKUpdate(#[serde(with = "serde_bytes")] Vec<u8>),
+    /// Prices observed off chain by the validators: a BCS-serialized `QuorumCertifiedData`.
+    ObservedPrices(#[serde(with = "serde_bytes")] Vec<u8>),
+}
+
+impl ValidatorTransaction {
+    /// Should be in-sync with the KIND constants of validator_txn.move
+    pub const DKG_TRANSCRIPT_KIND: u64 = 0;
+    pub const OBSERVED_JWK_UPDATE_KIND: u64 = 1;
+    pub const OBSERVED_PRICES_KIND: u64 = 2;
+
+    pub fn kind(&self) -> u64 {
+        match self {
+            ValidatorTransaction::DKGTranscript(_) => Self::DKG_TRANSCRIPT_KIND,
+            ValidatorTransaction::ObservedJWKUpdate(_) => Self::OBSERVED_JWK_UPDATE_KIND,
+            ValidatorTransaction::ObservedPrices(_) => Self::OBSERVED_PRICES_KIND,
+        }
+    }
+
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            ValidatorTransaction::DKGTranscript(_) => "dkg_transcript",
+            ValidatorTransaction::ObservedJWKUpdate(_) => "observed_jwk_update",
+            ValidatorTransaction::ObservedPrices(_) => "observed_prices",
+        }
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        match self {
+            ValidatorTransaction::DKGTranscript(payload)
+            | ValidatorTransaction::ObservedJWKUpdate(payload)
+            | ValidatorTransaction::ObservedPrices(payload) => payload,
+        }
+    }
+
+    pub fn size_in_bytes(&self) -> usize {
+        self.payload().len()
+    }
+
+    /// Returns Ok if the payload is signed by a quorum of `verifier`, the validator set of
+    /// `epoch`, Err otherwise. Data other than JWK updates must have been signed in `epoch`, so
+    /// that it can't be replayed once the validator set changes; JWK updates carry the version
+    /// they apply to instead.
+    pub fn verify(&self, verifier: &ValidatorVerifier, epoch: u64) -> Result<()> {
+        match self {
+            ValidatorTransaction::ObservedJWKUpdate(payload) => {
+                QuorumCertifiedUpdate::from_bytes(payload)?.verify(verifier)
+            }
+            ValidatorTransaction::DKGTranscript(payload)
+            | ValidatorTransaction::ObservedPrices(payload) => {
+                let certified = QuorumCertifiedData::from_bytes(payload)?;
+                ensure!(
+                    certified.data.kind == self.kind(),
+                    "{} carries data of kind {}",
+                    self,
+                    certified.data.kind
+                );
+                ensure!(
+                    certified.data.epoch == epoch,
+                    "{} was certified in epoch {}, current epoch {}",
+                    self,
+                    certified.data.epoch,
+                    epoch
+                );
+                certified.verify(verifier)
+            }
+        }
+    }
+
+    pub fn get_move_args(&self, signer: AccountAddress) -> Vec<MoveValue> {
+        vec![
+            MoveValue::Signer(signer),
+            MoveValue::U64(self.kind()),
+            MoveValue::Vector(self.payload().iter().copied().map(MoveValue::U8).collect()),
+        ]
+    }
+}
+
+/// The data of a validator transaction other than a JWK update, as signed by each validator.
+#[derive(
+    Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, CryptoHasher, BCSCryptoHash,
+)]
+pub struct ValidatorTxnData {
+    /// The `ValidatorTransaction::kind` the data is meant for
+    pub kind: u64,
+    /// The epoch in which the data was signed
+    pub epoch: u64,
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+/// Data signed by a quorum of validators, which is the payload of a
+/// `ValidatorTransaction::DKGTranscript` or a `ValidatorTransaction::ObservedPrices`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuorumCertifiedData {
+    pub data: ValidatorTxnData,
+    pub multi_sig: AggregateSignature,
+}
+
+impl QuorumCertifiedData {
+    /// Decodes the payload of a validator transaction
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes).context("Malformed quorum certified validator transaction data")
+    }
+
+    /// Returns Ok if the data is signed by a quorum of `verifier`, Err otherwise
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> Result<()> {
+        verifier
+            .verify_multi_signatures(&self.data, &self.multi_sig)
+            .context("Failed to verify the quorum signature of the validator transaction data")
+    }
+
+    /// The validator transaction of the data's kind, Err if JWK updates, which are certified
+    /// as a `QuorumCertifiedUpdate`, or unknown
+    pub fn to_validator_txn(&self) -> Result<ValidatorTransaction> {
+        let payload = bcs::to_bytes(self).expect("Validator txn data serialization cannot fail");
+        match self.data.kind {
+            ValidatorTransaction::DKG_TRANSCRIPT_KIND => {
+                Ok(ValidatorTransaction::DKGTranscript(payload))
+            }
+            ValidatorTransaction::OBSERVED_PRICES_KIND => {
+                Ok(ValidatorTransaction::ObservedPrices(payload))
+            }
+            kind => bail!(
+                "No validator transaction carries certified data of kind {}",
+                kind
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ValidatorTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ValidatorTransaction[{}, {} bytes]",
+            self.kind_name(),
+            self.size_in_bytes()
+        )
+    }
+}
@@ -10,7 +10,7 @@ use aptos_types::{
     on_chain_config::OnChainConfigPayload,
     transaction::{SignedTransaction, VMValidatorResult},
 };
-use aptos_vm::AptosVM;
+use aptos_vm::{AptosVM, VMValidator as _};
 use fail::fail_point;
 use std::sync::Arc;
 use storage_interface::state_view::DbStateView;
@@ -28,6 +28,16 @@ pub trait TransactionValidation: Send + Sync + Clone {
     /// Validate a txn from client
     fn validate_transaction(&self, _txn: SignedTransaction) -> Result<VMValidatorResult>;
 
+    /// Validate a batch of txns from clients, verifying their signatures together
+    fn validate_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Result<VMValidatorResult>> {
+        txns.into_iter()
+            .map(|txn| self.validate_transaction(txn))
+            .collect()
+    }
+
     /// Restart the transaction validation instance
     fn restart(&mut self, config: OnChainConfigPayload) -> Result<()>;
 
@@ -71,11 +81,21 @@ impl TransactionValidation for VMValidator {
                 "Injected error in vm_validator::validate_transaction"
             ))
         });
-        use aptos_vm::VMValidator;
 
         Ok(self.vm.validate_transaction(txn, &self.state_view))
     }
 
+    fn validate_transactions(
+        &self,
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<Result<VMValidatorResult>> {
+        self.vm
+            .validate_transactions(txns, &self.state_view)
+            .into_iter()
+            .map(Ok)
+            .collect()
+    }
+
     fn restart(&mut self, _config: OnChainConfigPayload) -> Result<()> {
         self.notify_commit();
 